@@ -1,13 +1,31 @@
 pub mod account_overlay;
+pub mod anonymize_export;
+pub mod backup;
 pub mod generate_prompt;
 pub mod import_django;
 pub mod initdb;
 pub mod migrate_and_serve;
+pub mod report;
+pub mod restore;
 pub mod serve;
+pub mod set_admin;
+pub mod set_password;
+pub mod tui;
+pub mod verify_audit_chain;
+pub mod verify_schema;
 
 pub use account_overlay::{apply_account_overlay, export_account_overlay};
+pub use anonymize_export::anonymize_export;
+pub use backup::backup;
 pub use generate_prompt::generate_prompt;
 pub use import_django::import_django;
 pub use initdb::init_database;
 pub use migrate_and_serve::migrate_and_serve;
+pub use report::report;
+pub use restore::restore;
 pub use serve::serve;
+pub use set_admin::set_admin;
+pub use set_password::set_password;
+pub use tui::tui;
+pub use verify_audit_chain::verify_audit_chain;
+pub use verify_schema::verify_schema;
@@ -0,0 +1,221 @@
+use anyhow::Result;
+use rust_decimal::Decimal;
+use sea_orm::Database;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::info;
+
+use crate::handlers::export::{build_archive, export_tables, ExportFormat, ExportTable};
+use crate::helpers::access::AccountAccess;
+
+/// Columns holding money; they are all scaled by the same factor.
+const AMOUNT_COLUMNS: &[&str] = &[
+    "amount",
+    "target_amount",
+    "recurring_approval_threshold",
+    "monthly_limit",
+    "expected_amount",
+    "paid_amount",
+    "original_amount",
+    "source_amount",
+    "statement_balance",
+    "opening_balance",
+    "fx_fee",
+    "purchase_price",
+    "salvage_value",
+    "sale_price",
+];
+
+/// Replaces original values with numbered pseudonyms, giving equal values the same one
+/// so that grouping by payee or name behaves as in the original data.
+#[derive(Default)]
+struct Pseudonyms {
+    assigned: HashMap<(&'static str, String), String>,
+    counters: HashMap<&'static str, usize>,
+}
+
+impl Pseudonyms {
+    fn get(&mut self, prefix: &'static str, original: &str) -> String {
+        let key = (prefix, original.to_string());
+        if let Some(pseudonym) = self.assigned.get(&key) {
+            return pseudonym.clone();
+        }
+        let counter = self.counters.entry(prefix).or_insert(0);
+        *counter += 1;
+        let pseudonym = format!("{} {}", prefix, counter);
+        self.assigned.insert(key, pseudonym.clone());
+        pseudonym
+    }
+}
+
+/// Secret transformation applied to the whole dataset.
+struct Anonymizer {
+    /// Every amount is multiplied by this, keeping sums, balances and the shape of the
+    /// amount distribution consistent while hiding the actual figures
+    amount_factor: Decimal,
+    /// Coordinates are moved by this offset, keeping distances between places
+    latitude_offset: f64,
+    longitude_offset: f64,
+    pseudonyms: Pseudonyms,
+}
+
+/// Eight pseudo-random bytes derived from the seed, as a number in `0.0..1.0`.
+fn seeded_unit(seed: u64, purpose: &str) -> f64 {
+    let digest = Sha256::new()
+        .chain_update(seed.to_le_bytes())
+        .chain_update(purpose.as_bytes())
+        .finalize();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    (u64::from_le_bytes(bytes) >> 11) as f64 / (1u64 << 53) as f64
+}
+
+impl Anonymizer {
+    fn new(seed: u64) -> Self {
+        // Log-uniform between 0.5 and 2, so amounts are as likely to shrink as to grow
+        let factor = 0.5 * 4f64.powf(seeded_unit(seed, "amount"));
+        Self {
+            amount_factor: Decimal::from_f64_retain(factor).unwrap_or(Decimal::ONE).round_dp(6),
+            latitude_offset: (seeded_unit(seed, "latitude") - 0.5) * 20.0,
+            longitude_offset: (seeded_unit(seed, "longitude") - 0.5) * 40.0,
+            pseudonyms: Pseudonyms::default(),
+        }
+    }
+
+    /// Pseudonym prefix for a text column, `None` for columns kept as they are.
+    ///
+    /// Transactions, recurring transactions and incomes share the payee prefix so the
+    /// same payee keeps matching across them.
+    fn text_prefix(table: &str, column: &str) -> Option<&'static str> {
+        match (table, column) {
+            ("accounts", "name") => Some("Account"),
+            ("categories", "name") => Some("Category"),
+            ("tags", "name") => Some("Tag"),
+            ("scenarios", "name") => Some("Scenario"),
+            ("account_statistics_exclusions", "reason") => Some("Reason"),
+            ("recurring_transaction_instances", "notes") => Some("Note"),
+            ("assets", "name") => Some("Asset"),
+            ("account_notes", "title") | ("account_notes", "body") | ("locked_periods", "note") => Some("Note"),
+            ("account_documents", "file_name") => Some("Document"),
+            ("merchants", "pattern") => Some("Pattern"),
+            // Public holidays are the same for everyone
+            ("holidays", "name") => None,
+            (_, "name") => Some("Payee"),
+            (_, "description") => Some("Description"),
+            (_, "source_name") => Some("Source"),
+            (_, "linked_import_id") => Some("Import"),
+            (_, "import_hash") => Some("Hash"),
+            _ => None,
+        }
+    }
+
+    fn scale_amount(&self, value: &Value) -> Value {
+        let parsed = match value {
+            Value::String(text) => Decimal::from_str(text).ok(),
+            Value::Number(number) => Decimal::from_str(&number.to_string()).ok(),
+            _ => None,
+        };
+        let Some(amount) = parsed else {
+            return value.clone();
+        };
+        let scaled = (amount * self.amount_factor).round_dp(2);
+        match value {
+            Value::String(_) => Value::String(scaled.to_string()),
+            _ => serde_json::from_str(&scaled.to_string()).unwrap_or(Value::Null),
+        }
+    }
+
+    fn shift_coordinate(value: &Value, offset: f64, limit: f64) -> Value {
+        match value.as_f64() {
+            Some(coordinate) => {
+                // Wrap around instead of clamping so nearby points don't collapse
+                let span = 2.0 * limit;
+                let shifted = (coordinate + offset + limit).rem_euclid(span) - limit;
+                serde_json::Number::from_f64(shifted).map(Value::Number).unwrap_or(Value::Null)
+            }
+            None => value.clone(),
+        }
+    }
+
+    fn anonymize_value(&mut self, table: &str, column: &str, value: &Value) -> Value {
+        if value.is_null() {
+            return Value::Null;
+        }
+        // Attached files are replaced by empty ones
+        if table == "account_documents" {
+            match column {
+                "content" => return Value::Array(Vec::new()),
+                "size_bytes" => return Value::from(0),
+                "sha256" => return Value::String(format!("{:x}", Sha256::digest(b""))),
+                _ => {}
+            }
+        }
+        if AMOUNT_COLUMNS.contains(&column) {
+            return self.scale_amount(value);
+        }
+        match column {
+            // Raw bank rows repeat every original field, there is nothing worth keeping
+            "raw_data" => return Value::Null,
+            "secret_ciphertext" | "website" | "logo_url" => return Value::Null,
+            "latitude" => return Self::shift_coordinate(value, self.latitude_offset, 90.0),
+            "longitude" => return Self::shift_coordinate(value, self.longitude_offset, 180.0),
+            // Ledger names are colon-separated paths, each level is replaced on its own
+            "ledger_name" => {
+                if let Some(text) = value.as_str() {
+                    let parts: Vec<String> = text.split(':').map(|part| self.pseudonyms.get("Ledger", part)).collect();
+                    return Value::String(parts.join(":"));
+                }
+            }
+            _ => {}
+        }
+        match (Self::text_prefix(table, column), value.as_str()) {
+            (Some(prefix), Some(text)) => Value::String(self.pseudonyms.get(prefix, text)),
+            _ => value.clone(),
+        }
+    }
+
+    fn anonymize(&mut self, table: &mut ExportTable) {
+        for row in table.rows.iter_mut() {
+            let Value::Object(fields) = row else {
+                continue;
+            };
+            for (column, value) in fields.iter_mut() {
+                *value = self.anonymize_value(&table.name, column, value);
+            }
+        }
+    }
+}
+
+/// Write an anonymized copy of all data as a full export archive
+///
+/// The archive has the layout of `GET /api/v1/export/full`. Ids, dates, currencies and
+/// relations are kept; names, descriptions and payees become numbered pseudonyms, all
+/// amounts are scaled by one secret factor and coordinates are moved by a secret offset.
+pub async fn anonymize_export(database_url: &str, output: &str, format: ExportFormat, seed: Option<u64>) -> Result<()> {
+    info!("Exporting anonymized data to {}", output);
+    let db = Database::connect(database_url).await?;
+    let mut tables = export_tables(&db, &AccountAccess::Unrestricted).await?;
+
+    let seed = seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos() as u64)
+            .unwrap_or_default()
+    });
+    let mut anonymizer = Anonymizer::new(seed);
+    for table in tables.iter_mut() {
+        anonymizer.anonymize(table);
+    }
+
+    let archive = build_archive(&tables, format)?;
+    std::fs::write(output, &archive)?;
+    println!(
+        "Wrote anonymized export of {} rows to {}",
+        tables.iter().map(|table| table.rows.len()).sum::<usize>(),
+        output
+    );
+    Ok(())
+}
@@ -0,0 +1,411 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{ConnectionTrait, Database, DatabaseConnection, DbBackend, Statement, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::Path;
+use tracing::{debug, info, warn};
+
+/// Describes the backup, stored next to the dump.
+const MANIFEST_FILE: &str = "backup.json";
+/// Copy of a SQLite database file.
+const SQLITE_FILE: &str = "finrust.db";
+/// Data of a PostgreSQL database as SQL statements.
+const POSTGRES_FILE: &str = "dump.sql";
+/// Recorded in the manifest instead of being dumped, the schema is recreated by the migrations.
+const MIGRATIONS_TABLE: &str = "seaql_migrations";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupManifest {
+    created_at: DateTime<Utc>,
+    /// `sqlite` or `postgres`
+    backend: String,
+    /// finrust version that wrote the backup
+    version: String,
+    /// Migrations applied to the database, in order
+    migrations: Vec<String>,
+}
+
+fn backend_name(backend: DbBackend) -> Result<&'static str> {
+    match backend {
+        DbBackend::Sqlite => Ok("sqlite"),
+        DbBackend::Postgres => Ok("postgres"),
+        other => bail!("Backups of {:?} databases are not supported", other),
+    }
+}
+
+/// Path of the database file of a `sqlite:` URL.
+fn sqlite_path(database_url: &str) -> Result<&str> {
+    let path = database_url
+        .strip_prefix("sqlite://")
+        .or_else(|| database_url.strip_prefix("sqlite:"))
+        .unwrap_or(database_url);
+    let path = path.split('?').next().unwrap_or_default();
+    if path.is_empty() || path == ":memory:" {
+        bail!("{} is not a SQLite database file", database_url);
+    }
+    Ok(path)
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn quote_literal(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+/// A JSON value as text that PostgreSQL casts to the column type.
+fn json_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Names of all tables, including the migrations table.
+async fn user_tables(db: &DatabaseConnection) -> Result<Vec<String>> {
+    let backend = db.get_database_backend();
+    let sql = match backend {
+        DbBackend::Sqlite => "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+        DbBackend::Postgres => {
+            "SELECT table_name AS name FROM information_schema.tables \
+             WHERE table_schema = current_schema() AND table_type = 'BASE TABLE' ORDER BY table_name"
+        }
+        other => bail!("Backups of {:?} databases are not supported", other),
+    };
+    let rows = db.query_all(Statement::from_string(backend, sql)).await?;
+    Ok(rows.iter().map(|row| row.try_get("", "name")).collect::<Result<_, _>>()?)
+}
+
+/// Migrations recorded as applied, in the order they were applied; empty for a database
+/// that was never migrated.
+async fn applied_migrations(db: &DatabaseConnection) -> Result<Vec<String>> {
+    if !user_tables(db).await?.iter().any(|table| table == MIGRATIONS_TABLE) {
+        return Ok(Vec::new());
+    }
+    let rows = db
+        .query_all(Statement::from_string(
+            db.get_database_backend(),
+            format!("SELECT version FROM {} ORDER BY version", MIGRATIONS_TABLE),
+        ))
+        .await?;
+    Ok(rows.iter().map(|row| row.try_get("", "version")).collect::<Result<_, _>>()?)
+}
+
+/// Checks that `applied` starts this build's list of migrations, so this build can
+/// recreate the schema they produced and upgrade it.
+fn check_schema_version(applied: &[String]) -> Result<()> {
+    let known: Vec<String> = Migrator::migrations().iter().map(|migration| migration.name().to_string()).collect();
+    for (index, name) in applied.iter().enumerate() {
+        if known.get(index) == Some(name) {
+            continue;
+        }
+        if known.contains(name) {
+            bail!("Migration {} was applied out of order, the schema can't be reproduced", name);
+        }
+        bail!("Migration {} is unknown to this build of finrust, upgrade finrust first", name);
+    }
+    Ok(())
+}
+
+/// Fails unless every table other than the migrations table is empty.
+async fn ensure_empty(db: &DatabaseConnection) -> Result<()> {
+    let backend = db.get_database_backend();
+    let mut filled = Vec::new();
+    for table in user_tables(db).await? {
+        if table == MIGRATIONS_TABLE {
+            continue;
+        }
+        let row = db
+            .query_one(Statement::from_string(
+                backend,
+                format!("SELECT COUNT(*) AS count FROM {}", quote_ident(&table)),
+            ))
+            .await?;
+        let count: i64 = match row {
+            Some(row) => row.try_get("", "count")?,
+            None => 0,
+        };
+        if count > 0 {
+            filled.push(table);
+        }
+    }
+    if !filled.is_empty() {
+        bail!("Restoring needs an empty database, these tables have rows: {}", filled.join(", "));
+    }
+    Ok(())
+}
+
+/// Consistent copy of a SQLite database.
+///
+/// `VACUUM INTO` writes a snapshot even while a running server writes to the database,
+/// where copying the file could catch it halfway through a transaction.
+async fn sqlite_snapshot(db: &DatabaseConnection) -> Result<Vec<u8>> {
+    let path = std::env::temp_dir().join(format!(
+        "finrust-backup-{}-{}.db",
+        std::process::id(),
+        Utc::now().timestamp_micros()
+    ));
+    db.execute_unprepared(&format!("VACUUM INTO {}", quote_literal(&path.to_string_lossy())))
+        .await?;
+    let snapshot = std::fs::read(&path);
+    if let Err(e) = std::fs::remove_file(&path) {
+        warn!("Failed to remove temporary snapshot {}: {}", path.display(), e);
+    }
+    Ok(snapshot?)
+}
+
+/// Tables ordered so that referenced tables come before the tables referring to them.
+///
+/// Tables in a reference cycle are taken in name order; the references that can't be
+/// satisfied that way are set by the dump's trailing updates.
+fn dependency_order(tables: &[String], foreign_keys: &[(String, String, String)]) -> Vec<String> {
+    let mut ordered: Vec<String> = Vec::with_capacity(tables.len());
+    let mut remaining: Vec<String> = tables.to_vec();
+    while !remaining.is_empty() {
+        let ready = remaining.iter().position(|table| {
+            foreign_keys
+                .iter()
+                .filter(|(from, _, to)| from == table && to != table)
+                .all(|(_, _, to)| ordered.contains(to) || !remaining.contains(to))
+        });
+        ordered.push(remaining.remove(ready.unwrap_or(0)));
+    }
+    ordered
+}
+
+/// Data of all tables as SQL, in the spirit of `pg_dump --data-only`.
+///
+/// Rows are dumped as JSON and inserted with `json_populate_record`, which converts every
+/// column type PostgreSQL can cast from JSON. References to rows of tables dumped later,
+/// including rows of the same table, are inserted as `NULL` and set by updates once all
+/// rows exist. Sequences are moved past the restored ids at the end.
+async fn postgres_dump(db: &DatabaseConnection) -> Result<String> {
+    let tables: Vec<String> = user_tables(db)
+        .await?
+        .into_iter()
+        .filter(|table| table != MIGRATIONS_TABLE)
+        .collect();
+    let foreign_keys: Vec<(String, String, String)> = db
+        .query_all(Statement::from_string(
+            DbBackend::Postgres,
+            "SELECT tc.table_name, kcu.column_name, ccu.table_name AS referenced_table \
+             FROM information_schema.table_constraints tc \
+             JOIN information_schema.key_column_usage kcu \
+               ON kcu.constraint_name = tc.constraint_name AND kcu.table_schema = tc.table_schema \
+             JOIN information_schema.constraint_column_usage ccu \
+               ON ccu.constraint_name = tc.constraint_name AND ccu.table_schema = tc.table_schema \
+             WHERE tc.constraint_type = 'FOREIGN KEY' AND tc.table_schema = current_schema()",
+        ))
+        .await?
+        .iter()
+        .map(|row| {
+            Ok((
+                row.try_get("", "table_name")?,
+                row.try_get("", "column_name")?,
+                row.try_get("", "referenced_table")?,
+            ))
+        })
+        .collect::<Result<_, sea_orm::DbErr>>()?;
+    let serial_columns: Vec<(String, String)> = db
+        .query_all(Statement::from_string(
+            DbBackend::Postgres,
+            "SELECT table_name, column_name FROM information_schema.columns \
+             WHERE table_schema = current_schema() AND (column_default LIKE 'nextval(%' OR is_identity = 'YES')",
+        ))
+        .await?
+        .iter()
+        .map(|row| Ok((row.try_get("", "table_name")?, row.try_get("", "column_name")?)))
+        .collect::<Result<_, sea_orm::DbErr>>()?;
+
+    let order = dependency_order(&tables, &foreign_keys);
+    let mut sql = String::from("-- finrust database dump\nSET standard_conforming_strings = on;\nSET client_encoding = 'UTF8';\n\n");
+    let mut updates = String::new();
+    for (position, table) in order.iter().enumerate() {
+        let later: Vec<&str> = foreign_keys
+            .iter()
+            .filter(|(from, _, to)| from == table && !order[..position].contains(to))
+            .map(|(_, column, _)| column.as_str())
+            .collect();
+        let rows = db
+            .query_all(Statement::from_string(
+                DbBackend::Postgres,
+                format!("SELECT row_to_json(t)::text AS row FROM {} t", quote_ident(table)),
+            ))
+            .await?;
+        debug!("Dumping {} rows of {}", rows.len(), table);
+
+        sql.push_str(&format!("-- {} ({} rows)\n", table, rows.len()));
+        for row in rows {
+            let text: String = row.try_get("", "row")?;
+            let mut record: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&text)?;
+            if let Some(id) = record.get("id").cloned() {
+                for column in &later {
+                    match record.insert(column.to_string(), serde_json::Value::Null) {
+                        None | Some(serde_json::Value::Null) => {}
+                        Some(value) => updates.push_str(&format!(
+                            "UPDATE {} SET {} = {} WHERE id = {};\n",
+                            quote_ident(table),
+                            quote_ident(column),
+                            quote_literal(&json_text(&value)),
+                            quote_literal(&json_text(&id))
+                        )),
+                    }
+                }
+            }
+            sql.push_str(&format!(
+                "INSERT INTO {0} SELECT * FROM json_populate_record(NULL::{0}, {1});\n",
+                quote_ident(table),
+                quote_literal(&serde_json::Value::Object(record).to_string())
+            ));
+        }
+        sql.push('\n');
+    }
+    if !updates.is_empty() {
+        sql.push_str("-- References to rows inserted later\n");
+        sql.push_str(&updates);
+        sql.push('\n');
+    }
+    for (table, column) in serial_columns.iter().filter(|(table, _)| order.contains(table)) {
+        sql.push_str(&format!(
+            "SELECT setval(pg_get_serial_sequence({0}, {1}), COALESCE((SELECT MAX({2}) FROM {3}), 0) + 1, false);\n",
+            quote_literal(&quote_ident(table)),
+            quote_literal(column),
+            quote_ident(column),
+            quote_ident(table)
+        ));
+    }
+    Ok(sql)
+}
+
+fn append_file<W: Write>(archive: &mut tar::Builder<W>, name: &str, content: &[u8]) -> std::io::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o600);
+    header.set_mtime(Utc::now().timestamp().max(0) as u64);
+    archive.append_data(&mut header, name, content)
+}
+
+/// Back up the whole database into a `.tar.gz` file
+///
+/// SQLite databases are copied as a file, PostgreSQL databases are dumped as SQL. The
+/// applied migrations are recorded, so `finrust restore` can check that it is able to
+/// recreate the schema.
+pub async fn backup(database_url: &str, output: &str) -> Result<()> {
+    info!("Backing up database to {}", output);
+    let db = Database::connect(database_url).await?;
+    let backend = db.get_database_backend();
+    let migrations = applied_migrations(&db).await?;
+    if migrations.is_empty() {
+        bail!("The database has no schema, run `finrust init-db` first");
+    }
+    check_schema_version(&migrations).context("The database can't be backed up by this build")?;
+
+    let (file, dump) = match backend {
+        DbBackend::Sqlite => (SQLITE_FILE, sqlite_snapshot(&db).await?),
+        DbBackend::Postgres => (POSTGRES_FILE, postgres_dump(&db).await?.into_bytes()),
+        other => bail!("Backups of {:?} databases are not supported", other),
+    };
+    let manifest = BackupManifest {
+        created_at: Utc::now(),
+        backend: backend_name(backend)?.to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        migrations,
+    };
+
+    let mut archive = tar::Builder::new(GzEncoder::new(std::fs::File::create(output)?, Compression::default()));
+    append_file(&mut archive, MANIFEST_FILE, &serde_json::to_vec_pretty(&manifest)?)?;
+    append_file(&mut archive, file, &dump)?;
+    archive.into_inner()?.finish()?;
+
+    println!(
+        "Backed up {} database at migration {} ({} bytes) to {}",
+        manifest.backend,
+        manifest.migrations.last().map(String::as_str).unwrap_or_default(),
+        dump.len(),
+        output
+    );
+    Ok(())
+}
+
+fn read_backup(backup: &[u8]) -> Result<(BackupManifest, Vec<u8>)> {
+    let mut archive = tar::Archive::new(GzDecoder::new(backup));
+    let mut manifest = None;
+    let mut dump = None;
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().into_owned();
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content)?;
+        match name.as_str() {
+            MANIFEST_FILE => manifest = Some(serde_json::from_slice::<BackupManifest>(&content)?),
+            SQLITE_FILE | POSTGRES_FILE => dump = Some(content),
+            _ => debug!("Skipping {} in backup", name),
+        }
+    }
+    match (manifest, dump) {
+        (Some(manifest), Some(dump)) => Ok((manifest, dump)),
+        _ => bail!("Not a finrust backup, {} or the database dump is missing", MANIFEST_FILE),
+    }
+}
+
+/// Restore a backup written by `finrust backup` into an empty database
+///
+/// The backup must come from the same kind of database and must not be newer than this
+/// build. The schema is brought to the backup's migration, the data loaded and then the
+/// remaining migrations applied.
+pub async fn restore_backup(database_url: &str, backup: &[u8]) -> Result<()> {
+    let (manifest, dump) = read_backup(backup)?;
+    info!(
+        "Restoring {} backup from {} written by finrust {}",
+        manifest.backend, manifest.created_at, manifest.version
+    );
+    check_schema_version(&manifest.migrations).context("The backup can't be restored by this build")?;
+
+    let target = if database_url.starts_with("sqlite:") { "sqlite" } else { "postgres" };
+    if target != manifest.backend {
+        bail!("The backup is of a {} database and can't be restored into {}", manifest.backend, target);
+    }
+
+    if manifest.backend == "sqlite" {
+        let path = sqlite_path(database_url)?;
+        if Path::new(path).exists() {
+            let db = Database::connect(database_url).await?;
+            ensure_empty(&db).await?;
+            db.close().await?;
+        }
+        std::fs::write(path, &dump)?;
+        // Journals of the replaced file would be replayed onto the restored one
+        for suffix in ["-wal", "-shm", "-journal"] {
+            let journal = format!("{}{}", path, suffix);
+            if Path::new(&journal).exists() {
+                std::fs::remove_file(&journal)?;
+            }
+        }
+    } else {
+        let db = Database::connect(database_url).await?;
+        ensure_empty(&db).await?;
+        let applied = applied_migrations(&db).await?;
+        if !manifest.migrations.starts_with(&applied) {
+            bail!("The target database has migrations the backup lacks, restore into a new database");
+        }
+        let steps = (manifest.migrations.len() - applied.len()) as u32;
+        if steps > 0 {
+            Migrator::up(&db, Some(steps)).await?;
+        }
+        let txn = db.begin().await?;
+        txn.execute_unprepared(std::str::from_utf8(&dump)?).await?;
+        txn.commit().await?;
+    }
+
+    let db = Database::connect(database_url).await?;
+    Migrator::up(&db, None).await?;
+    println!(
+        "Restored {} backup from {} and migrated it to the current schema",
+        manifest.backend, manifest.created_at
+    );
+    Ok(())
+}
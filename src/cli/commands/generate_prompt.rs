@@ -1,7 +1,7 @@
 use anyhow::Result;
 use chrono::{Datelike, Months, NaiveDate, Utc};
 use common::metrics::{AccountKindMetricsDto, AccountMetricsDto, DashboardMetricsDto};
-use compute::account::utils::generate_occurrences;
+use compute::account::utils::{generate_occurrences, load_holiday_calendar, recurring_transaction_occurrences};
 use compute::metrics::account_metrics::monthly_equivalent;
 use compute::metrics::account_role::derive_account_role as compute_account_role;
 use compute::metrics::cross_account_metrics;
@@ -9,7 +9,7 @@ use compute::metrics::filter_active_recurring;
 use compute::{account::AccountStateCalculator, account_stats, default_compute};
 use model::entities::{
     account::{self, AccountKind},
-    category, one_off_transaction, recurring_income, recurring_transaction,
+    account_statistics_exclusion, category, one_off_transaction, recurring_income, recurring_transaction,
     recurring_transaction_instance,
 };
 use polars::prelude::*;
@@ -577,7 +577,12 @@ async fn write_account_details(
 
     // Per-account year stats
     let year = today.year();
-    let year_stats = compute_year_stats(compute, db, accounts, year).await;
+    let exclusions = account_statistics_exclusion::Entity::find()
+        .all(db)
+        .await
+        .map(|rows| account_stats::StatisticsExclusions::new(&rows))
+        .unwrap_or_default();
+    let year_stats = compute_year_stats(compute, db, accounts, year, &exclusions).await;
 
     for a in accounts {
         let _ = writeln!(out, "### {}\n", a.name);
@@ -918,6 +923,14 @@ async fn write_income_summary(
     let account_map: HashMap<i32, &account::Model> =
         accounts.iter().map(|a| (a.id, a)).collect();
 
+    let holidays = match load_holiday_calendar(db).await {
+        Ok(h) => h,
+        Err(e) => {
+            let _ = writeln!(out, "Error loading holidays: {}\n", e);
+            return;
+        }
+    };
+
     let mut yearly_totals: BTreeMap<i32, Decimal> = BTreeMap::new();
     let mut per_income: Vec<(String, String, BTreeMap<i32, Decimal>)> = Vec::new();
 
@@ -926,6 +939,7 @@ async fn write_income_summary(
             income.start_date,
             income.end_date,
             &income.period,
+            &holidays,
             range_start,
             today,
         );
@@ -1051,6 +1065,14 @@ async fn write_category_breakdown(
     let category_map: HashMap<i32, &category::Model> =
         categories.iter().map(|c| (c.id, c)).collect();
 
+    let holidays = match load_holiday_calendar(db).await {
+        Ok(h) => h,
+        Err(e) => {
+            let _ = writeln!(out, "Error loading holidays: {}\n", e);
+            return;
+        }
+    };
+
     let mut stats_map: HashMap<i32, (BTreeMap<i32, Decimal>, i64)> = HashMap::new();
 
     for txn in &one_off_txns {
@@ -1065,13 +1087,7 @@ async fn write_category_breakdown(
     }
 
     for rtxn in &recurring_txns {
-        let occurrences = generate_occurrences(
-            rtxn.start_date,
-            rtxn.end_date,
-            &rtxn.period,
-            range_start,
-            today,
-        );
+        let occurrences = recurring_transaction_occurrences(rtxn, &holidays, range_start, today);
 
         for date in occurrences {
             let (amount, cat_id) = if let Some(instance) = instance_map.get(&(rtxn.id, date)) {
@@ -2009,6 +2025,7 @@ async fn compute_year_stats(
     db: &DatabaseConnection,
     accounts: &[account::Model],
     year: i32,
+    exclusions: &account_stats::StatisticsExclusions,
 ) -> HashMap<i32, account_stats::AccountStats> {
     let mut result: HashMap<i32, account_stats::AccountStats> = HashMap::new();
 
@@ -2028,21 +2045,21 @@ async fn compute_year_stats(
         );
     }
 
-    if let Ok(min) = account_stats::min_state_in_year(compute, db, accounts, year).await {
+    if let Ok(min) = account_stats::min_state_in_year(compute, db, accounts, year, exclusions).await {
         for s in min {
             if let Some(entry) = result.get_mut(&s.account_id) {
                 entry.min_state = s.min_state;
             }
         }
     }
-    if let Ok(max) = account_stats::max_state_in_year(compute, db, accounts, year).await {
+    if let Ok(max) = account_stats::max_state_in_year(compute, db, accounts, year, exclusions).await {
         for s in max {
             if let Some(entry) = result.get_mut(&s.account_id) {
                 entry.max_state = s.max_state;
             }
         }
     }
-    if let Ok(avg_exp) = account_stats::average_expense_in_year(compute, db, accounts, year).await
+    if let Ok(avg_exp) = account_stats::average_expense_in_year(compute, db, accounts, year, exclusions).await
     {
         for s in avg_exp {
             if let Some(entry) = result.get_mut(&s.account_id) {
@@ -2050,7 +2067,7 @@ async fn compute_year_stats(
             }
         }
     }
-    if let Ok(avg_inc) = account_stats::average_income_in_year(compute, db, accounts, year).await {
+    if let Ok(avg_inc) = account_stats::average_income_in_year(compute, db, accounts, year, exclusions).await {
         for s in avg_inc {
             if let Some(entry) = result.get_mut(&s.account_id) {
                 entry.average_income = s.average_income;
@@ -2059,7 +2076,7 @@ async fn compute_year_stats(
     }
     let today = Utc::now().date_naive();
     if let Ok(upcoming) =
-        account_stats::upcoming_expenses_until_year_end(compute, db, accounts, year, today).await
+        account_stats::upcoming_expenses_until_year_end(compute, db, accounts, year, today, exclusions).await
     {
         for s in upcoming {
             if let Some(entry) = result.get_mut(&s.account_id) {
@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use chrono::{Months, NaiveDate};
 use crate::helpers::colors;
+use common::{AmountLocale, parse_amount};
 use rust_decimal::Decimal;
 use sea_orm::{ActiveModelTrait, Database, DatabaseConnection, EntityTrait, Set};
 use serde::Deserialize;
@@ -16,6 +17,16 @@ use model::entities::{
     recurring_transaction, recurring_transaction_instance, recurring_transaction_tag, tag, user,
 };
 
+/// Django serializes decimal fields with a dot separator regardless of the
+/// locale the old application was running in.
+const DJANGO_AMOUNT_LOCALE: AmountLocale = AmountLocale::DotDecimal;
+
+/// Parses an amount field of a dump record, naming the record and field if it isn't a number.
+fn parse_record_amount(record: &DjangoRecord, field: &str, value: &str) -> Result<Decimal> {
+    parse_amount(value, DJANGO_AMOUNT_LOCALE)
+        .with_context(|| format!("Invalid {} '{}' in {} record {}", field, value, record.model, record.pk))
+}
+
 /// Main structure for Django dump
 #[derive(Debug, Deserialize)]
 struct DjangoRecord {
@@ -283,8 +294,7 @@ pub async fn import_django(json_path: &str, database_url: &str) -> Result<()> {
             let state: DjangoManualAccountState = serde_json::from_value(record.fields.clone())?;
 
             if let Some(&account_id) = account_map.get(&state.account) {
-                let amount = state.amount.parse::<Decimal>()
-                    .unwrap_or_else(|_| Decimal::ZERO);
+                let amount = parse_record_amount(record, "amount", &state.amount)?;
                 let date = NaiveDate::parse_from_str(&state.date, "%Y-%m-%d")
                     .unwrap_or_else(|_| chrono::Utc::now().naive_utc().date());
 
@@ -313,7 +323,7 @@ pub async fn import_django(json_path: &str, database_url: &str) -> Result<()> {
             let tx: DjangoRegularTransaction = serde_json::from_value(record.fields.clone())?;
 
             if let Some(&target_account_id) = account_map.get(&tx.target_account) {
-                let amount = tx.amount.parse::<Decimal>().unwrap_or_else(|_| Decimal::ZERO);
+                let amount = parse_record_amount(record, "amount", &tx.amount)?;
                 let start_date = NaiveDate::parse_from_str(&tx.billing_start, "%Y-%m-%d")
                     .unwrap_or_else(|_| chrono::Utc::now().naive_utc().date());
                 let end_date = tx.billing_end.as_ref()
@@ -423,7 +433,7 @@ pub async fn import_django(json_path: &str, database_url: &str) -> Result<()> {
             let tx: DjangoExtraTransaction = serde_json::from_value(record.fields.clone())?;
 
             if let Some(&target_account_id) = account_map.get(&tx.target_account) {
-                let amount = tx.amount.parse::<Decimal>().unwrap_or_else(|_| Decimal::ZERO);
+                let amount = parse_record_amount(record, "amount", &tx.amount)?;
                 let date = NaiveDate::parse_from_str(&tx.date, "%Y-%m-%d")
                     .unwrap_or_else(|_| chrono::Utc::now().naive_utc().date());
 
@@ -1,14 +1,34 @@
 use anyhow::Result;
+use chrono_tz::Tz;
 use migration::{Migrator, MigratorTrait};
 use sea_orm::Database;
+use std::time::Duration;
 use tokio::net::TcpListener;
 use tokio::signal;
 use tracing::{debug, error, info, trace};
 
 use crate::config::initialize_app_state_with_url;
 use crate::router::create_router;
+use crate::tasks::exchange_rates::FxProvider;
+use crate::tasks::spawn_background_tasks;
 
-pub async fn migrate_and_serve(database_url: &str, bind_address: &str) -> Result<()> {
+#[allow(clippy::too_many_arguments)]
+pub async fn migrate_and_serve(
+    database_url: &str,
+    bind_address: &str,
+    double_entry_strict: bool,
+    audit_chain: bool,
+    fx_provider: Option<FxProvider>,
+    fx_refresh_hours: u64,
+    instance_days_ahead: u32,
+    instance_generation_hours: u64,
+    field_encryption_key: Option<&str>,
+    auth_required: bool,
+    timezone: Tz,
+    smtp_url: Option<&str>,
+    smtp_from: &str,
+    payment_alert_hours: u64,
+) -> Result<()> {
     trace!("Entering migrate_and_serve function");
     info!("Applying database migrations and starting server");
     debug!("Database URL: {}", database_url);
@@ -43,7 +63,7 @@ pub async fn migrate_and_serve(database_url: &str, bind_address: &str) -> Result
 
     // Initialize application state
     trace!("Initializing application state");
-    let state = match initialize_app_state_with_url(database_url).await {
+    let state = match initialize_app_state_with_url(database_url, double_entry_strict, audit_chain, fx_provider, field_encryption_key, auth_required, timezone, smtp_url, smtp_from).await {
         Ok(state) => {
             debug!("Application state initialized successfully");
             state
@@ -54,6 +74,16 @@ pub async fn migrate_and_serve(database_url: &str, bind_address: &str) -> Result
         }
     };
 
+    // Start background tasks; they stop with the runtime on shutdown
+    trace!("Spawning background tasks");
+    spawn_background_tasks(
+        &state,
+        Duration::from_secs(fx_refresh_hours.max(1) * 3600),
+        instance_days_ahead,
+        Duration::from_secs(instance_generation_hours.max(1) * 3600),
+        Duration::from_secs(payment_alert_hours.max(1) * 3600),
+    );
+
     // Create router
     trace!("Creating application router");
     let app = create_router(state);
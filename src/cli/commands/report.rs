@@ -0,0 +1,208 @@
+use anyhow::{bail, Result};
+use chrono::{Datelike, Months, NaiveDate};
+use common::reports::{CashflowReportDto, FlowReportDto, ReportGranularity};
+use rust_decimal::Decimal;
+use sea_orm::Database;
+use std::collections::HashMap;
+use std::path::Path;
+use tracing::info;
+
+use crate::handlers::export::csv_field;
+use crate::handlers::reports::{build_cashflow_report, build_money_flow_report};
+use crate::helpers::access::AccountAccess;
+use crate::helpers::pdf::text_pdf;
+
+/// Report produced by `finrust report`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportType {
+    /// Income and expenses per week
+    Weekly,
+    /// Income and expenses per month
+    Monthly,
+    /// Income and expenses per quarter
+    Quarterly,
+    /// Money flow from income sources through accounts to spending categories
+    Flows,
+}
+
+/// File format, taken from the output file's extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Csv,
+    Json,
+    Pdf,
+}
+
+impl OutputFormat {
+    fn from_path(path: &str) -> Result<Self> {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase);
+        match extension.as_deref() {
+            Some("csv") => Ok(OutputFormat::Csv),
+            Some("json") => Ok(OutputFormat::Json),
+            Some("pdf") => Ok(OutputFormat::Pdf),
+            _ => bail!("Unsupported output file {}, expected a .csv, .json or .pdf file", path),
+        }
+    }
+}
+
+/// First and last day of a period given as `YYYY`, `YYYY-Qn` or `YYYY-MM`.
+pub fn parse_period(value: &str) -> Result<(NaiveDate, NaiveDate), String> {
+    let invalid = || format!("Invalid period {}, expected YYYY, YYYY-Qn or YYYY-MM", value);
+    let (year, rest) = match value.split_once('-') {
+        Some((year, rest)) => (year, Some(rest)),
+        None => (value, None),
+    };
+    let year: i32 = year.parse().map_err(|_| invalid())?;
+    let (first_month, months) = match rest {
+        None => (1, 12),
+        Some(quarter) if quarter.starts_with(['Q', 'q']) => match quarter[1..].parse::<u32>() {
+            Ok(quarter @ 1..=4) => ((quarter - 1) * 3 + 1, 3),
+            _ => return Err(invalid()),
+        },
+        Some(month) => match month.parse::<u32>() {
+            Ok(month @ 1..=12) => (month, 1),
+            _ => return Err(invalid()),
+        },
+    };
+    let start = NaiveDate::from_ymd_opt(year, first_month, 1).ok_or_else(invalid)?;
+    let end = start
+        .checked_add_months(Months::new(months))
+        .and_then(|next| next.pred_opt())
+        .ok_or_else(invalid)?;
+    Ok((start, end))
+}
+
+/// The calendar month before the one containing `today`.
+fn previous_month(today: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let this_month = today.with_day(1).unwrap_or(today);
+    let start = this_month.checked_sub_months(Months::new(1)).unwrap_or(this_month);
+    (start, this_month.pred_opt().unwrap_or(this_month))
+}
+
+fn amount(value: Decimal) -> String {
+    format!("{:.2}", value.round_dp(2))
+}
+
+fn cashflow_csv(report: &CashflowReportDto) -> String {
+    let mut csv = String::from("start_date,end_date,income,expense,net\n");
+    for period in &report.periods {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            period.start_date, period.end_date, period.income, period.expense, period.net
+        ));
+    }
+    csv
+}
+
+fn cashflow_lines(report: &CashflowReportDto, title: &str) -> Vec<String> {
+    let mut lines = vec![title.to_string(), String::new()];
+    lines.push(format!("{:<25}{:>15}{:>15}{:>15}", "Period", "Income", "Expense", "Net"));
+    for period in &report.periods {
+        lines.push(format!(
+            "{:<25}{:>15}{:>15}{:>15}",
+            format!("{} - {}", period.start_date, period.end_date),
+            amount(period.income),
+            amount(period.expense),
+            amount(period.net)
+        ));
+    }
+    lines.push(format!(
+        "{:<25}{:>15}{:>15}{:>15}",
+        "Total",
+        amount(report.total_income),
+        amount(report.total_expense),
+        amount(report.total_net)
+    ));
+    lines
+}
+
+fn flow_labels(report: &FlowReportDto) -> HashMap<&str, &str> {
+    report.nodes.iter().map(|node| (node.id.as_str(), node.label.as_str())).collect()
+}
+
+fn flow_csv(report: &FlowReportDto) -> String {
+    let labels = flow_labels(report);
+    let mut csv = String::from("source_id,source,target_id,target,amount\n");
+    for edge in &report.edges {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            csv_field(&edge.source),
+            csv_field(labels.get(edge.source.as_str()).copied().unwrap_or_default()),
+            csv_field(&edge.target),
+            csv_field(labels.get(edge.target.as_str()).copied().unwrap_or_default()),
+            edge.amount
+        ));
+    }
+    csv
+}
+
+fn flow_lines(report: &FlowReportDto, title: &str) -> Vec<String> {
+    let labels = flow_labels(report);
+    let mut lines = vec![title.to_string(), String::new()];
+    lines.push(format!("{:<30}{:<30}{:>15}", "From", "To", "Amount"));
+    for edge in &report.edges {
+        let label = |id: &str| {
+            let label = labels.get(id).map(|label| label.to_string()).unwrap_or_else(|| id.to_string());
+            label.chars().take(28).collect::<String>()
+        };
+        lines.push(format!(
+            "{:<30}{:<30}{:>15}",
+            label(&edge.source),
+            label(&edge.target),
+            amount(edge.amount)
+        ));
+    }
+    lines
+}
+
+/// Generate a report and write it to a file
+///
+/// Uses the same report builders as the `/api/v1/reports` endpoints. Without a period
+/// the previous calendar month is reported, so the command can run from cron on the
+/// first of every month.
+pub async fn report(
+    database_url: &str,
+    report_type: ReportType,
+    period: Option<(NaiveDate, NaiveDate)>,
+    output: &str,
+    timezone: chrono_tz::Tz,
+) -> Result<()> {
+    let format = OutputFormat::from_path(output)?;
+    let today = compute::today_in(&timezone);
+    let (start_date, end_date) = period.unwrap_or_else(|| previous_month(today));
+    info!("Generating {:?} report from {} to {}", report_type, start_date, end_date);
+
+    let db = Database::connect(database_url).await?;
+    let content = match report_type {
+        ReportType::Weekly | ReportType::Monthly | ReportType::Quarterly => {
+            let granularity = match report_type {
+                ReportType::Weekly => ReportGranularity::Week,
+                ReportType::Quarterly => ReportGranularity::Quarter,
+                _ => ReportGranularity::Month,
+            };
+            let report = build_cashflow_report(&db, &AccountAccess::Unrestricted, start_date, end_date, granularity).await?;
+            let title = format!("Cashflow report {} to {}", start_date, end_date);
+            match format {
+                OutputFormat::Csv => cashflow_csv(&report).into_bytes(),
+                OutputFormat::Json => serde_json::to_vec_pretty(&report)?,
+                OutputFormat::Pdf => text_pdf(&title, &cashflow_lines(&report, &title)),
+            }
+        }
+        ReportType::Flows => {
+            let report = build_money_flow_report(&db, &AccountAccess::Unrestricted, start_date, end_date).await?;
+            let title = format!("Money flow report {} to {}", start_date, end_date);
+            match format {
+                OutputFormat::Csv => flow_csv(&report).into_bytes(),
+                OutputFormat::Json => serde_json::to_vec_pretty(&report)?,
+                OutputFormat::Pdf => text_pdf(&title, &flow_lines(&report, &title)),
+            }
+        }
+    };
+
+    std::fs::write(output, &content)?;
+    println!("Wrote {:?} report for {} to {} to {}", report_type, start_date, end_date, output);
+    Ok(())
+}
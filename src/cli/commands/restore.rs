@@ -0,0 +1,47 @@
+use anyhow::{bail, Result};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::Database;
+use tracing::info;
+
+use super::backup::restore_backup;
+use crate::handlers::restore::{restore_archive, RestoreError};
+
+/// Magic bytes of a gzip file, i.e. a backup written by `finrust backup`.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Restore a backup or a full export archive into an empty database
+///
+/// Backups are handed to [`restore_backup`]. For export archives pending migrations are
+/// applied first, so the database may be a new file. Users are not part of an export
+/// archive; the owners of the restored accounts must exist.
+pub async fn restore(database_url: &str, input: &str) -> Result<()> {
+    info!("Restoring {} into database", input);
+    let archive = std::fs::read(input)?;
+    if archive.starts_with(&GZIP_MAGIC) {
+        return restore_backup(database_url, &archive).await;
+    }
+
+    let db = Database::connect(database_url).await?;
+    Migrator::up(&db, None).await?;
+
+    match restore_archive(&db, &archive).await {
+        Ok(tables) => {
+            for table in &tables {
+                println!("{:<35}{:>8} rows", table.table, table.rows);
+            }
+            println!(
+                "Restored {} rows from {}",
+                tables.iter().map(|table| table.rows).sum::<usize>(),
+                input
+            );
+            Ok(())
+        }
+        Err(RestoreError::Conflicts(conflicts)) => {
+            for conflict in &conflicts {
+                eprintln!("{}", conflict);
+            }
+            bail!("Nothing was restored, the archive has {} conflicts", conflicts.len());
+        }
+        Err(e) => Err(e.into()),
+    }
+}
@@ -1,20 +1,46 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
+use chrono_tz::Tz;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::Database;
+use std::time::Duration;
 use tokio::net::TcpListener;
 use tokio::signal;
-use tracing::{info, debug, trace, error};
+use tracing::{info, debug, trace, error, warn};
 
 use crate::config::initialize_app_state_with_url;
 use crate::router::create_router;
+use crate::tasks::exchange_rates::FxProvider;
+use crate::tasks::spawn_background_tasks;
 
-pub async fn serve(database_url: &str, bind_address: &str) -> Result<()> {
+#[allow(clippy::too_many_arguments)]
+pub async fn serve(
+    database_url: &str,
+    bind_address: &str,
+    double_entry_strict: bool,
+    audit_chain: bool,
+    fx_provider: Option<FxProvider>,
+    fx_refresh_hours: u64,
+    instance_days_ahead: u32,
+    instance_generation_hours: u64,
+    field_encryption_key: Option<&str>,
+    auth_required: bool,
+    timezone: Tz,
+    smtp_url: Option<&str>,
+    smtp_from: &str,
+    payment_alert_hours: u64,
+    auto_migrate: bool,
+) -> Result<()> {
     trace!("Entering serve function");
     info!("FinRust application starting up");
     debug!("Database URL: {}", database_url);
     debug!("Bind address: {}", bind_address);
 
+    trace!("Checking for pending migrations");
+    ensure_migrations_applied(database_url, auto_migrate).await?;
+
     // Initialize application state
     trace!("Initializing application state");
-    let state = match initialize_app_state_with_url(database_url).await {
+    let state = match initialize_app_state_with_url(database_url, double_entry_strict, audit_chain, fx_provider, field_encryption_key, auth_required, timezone, smtp_url, smtp_from).await {
         Ok(state) => {
             debug!("Application state initialized successfully");
             state
@@ -25,6 +51,16 @@ pub async fn serve(database_url: &str, bind_address: &str) -> Result<()> {
         }
     };
 
+    // Start background tasks; they stop with the runtime on shutdown
+    trace!("Spawning background tasks");
+    spawn_background_tasks(
+        &state,
+        Duration::from_secs(fx_refresh_hours.max(1) * 3600),
+        instance_days_ahead,
+        Duration::from_secs(instance_generation_hours.max(1) * 3600),
+        Duration::from_secs(payment_alert_hours.max(1) * 3600),
+    );
+
     // Create router
     trace!("Creating application router");
     let app = create_router(state);
@@ -61,6 +97,49 @@ pub async fn serve(database_url: &str, bind_address: &str) -> Result<()> {
     Ok(())
 }
 
+/// Applies pending migrations when `auto_migrate` is set, otherwise refuses to start
+/// while any are pending so an outdated schema doesn't surface later as SQL errors.
+async fn ensure_migrations_applied(database_url: &str, auto_migrate: bool) -> Result<()> {
+    let db = match Database::connect(database_url).await {
+        Ok(connection) => connection,
+        Err(e) => {
+            error!("Failed to connect to database '{}': {}", database_url, e);
+            return Err(e.into());
+        }
+    };
+
+    let pending: Vec<String> = Migrator::get_pending_migrations(&db)
+        .await?
+        .iter()
+        .map(|migration| migration.name().to_string())
+        .collect();
+    if pending.is_empty() {
+        debug!("Database schema is up to date");
+        return Ok(());
+    }
+
+    if !auto_migrate {
+        error!("{} database migration(s) pending, refusing to start", pending.len());
+        bail!(
+            "Database has {} pending migration(s):\n  {}\nRun `finrust init-db`, or start with --auto-migrate (AUTO_MIGRATE=true) to apply them on startup",
+            pending.len(),
+            pending.join("\n  ")
+        );
+    }
+
+    warn!("Applying {} pending database migration(s): {}", pending.len(), pending.join(", "));
+    match Migrator::up(&db, None).await {
+        Ok(_) => {
+            info!("Database migrations completed successfully");
+            Ok(())
+        }
+        Err(e) => {
+            error!("Failed to run database migrations: {}", e);
+            Err(e.into())
+        }
+    }
+}
+
 async fn shutdown_signal() {
     let ctrl_c = async {
         signal::ctrl_c()
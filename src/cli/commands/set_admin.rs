@@ -0,0 +1,27 @@
+use anyhow::{bail, Result};
+use model::entities::user;
+use sea_orm::{ActiveModelTrait, ColumnTrait, Database, EntityTrait, QueryFilter, Set};
+use tracing::info;
+
+pub async fn set_admin(database_url: &str, username: &str, revoke: bool) -> Result<()> {
+    info!("{} administrator rights of user '{}'", if revoke { "Revoking" } else { "Granting" }, username);
+    let db = Database::connect(database_url).await?;
+    let Some(existing) = user::Entity::find()
+        .filter(user::Column::Username.eq(username))
+        .one(&db)
+        .await?
+    else {
+        bail!("User '{}' does not exist", username);
+    };
+
+    let mut active: user::ActiveModel = existing.into();
+    active.is_admin = Set(!revoke);
+    active.update(&db).await?;
+
+    if revoke {
+        println!("User '{}' is no longer an administrator", username);
+    } else {
+        println!("User '{}' is now an administrator", username);
+    }
+    Ok(())
+}
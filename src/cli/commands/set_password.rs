@@ -0,0 +1,41 @@
+use anyhow::{anyhow, bail, Result};
+use model::entities::{user, user_session};
+use sea_orm::{ActiveModelTrait, ColumnTrait, Database, EntityTrait, QueryFilter, Set};
+use tracing::info;
+
+use crate::handlers::auth::MIN_PASSWORD_LENGTH;
+use crate::helpers::auth::hash_password;
+
+pub async fn set_password(database_url: &str, username: &str, password: &str) -> Result<()> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        bail!("Password must be at least {} characters long", MIN_PASSWORD_LENGTH);
+    }
+
+    info!("Setting password of user '{}'", username);
+    let db = Database::connect(database_url).await?;
+    let Some(existing) = user::Entity::find()
+        .filter(user::Column::Username.eq(username))
+        .one(&db)
+        .await?
+    else {
+        bail!("User '{}' does not exist", username);
+    };
+    let user_id = existing.id;
+
+    let password_hash = hash_password(password).map_err(|e| anyhow!("Failed to hash password: {}", e))?;
+    let mut active: user::ActiveModel = existing.into();
+    active.password_hash = Set(Some(password_hash));
+    active.update(&db).await?;
+
+    // Sessions opened with the old password stop working
+    let removed = user_session::Entity::delete_many()
+        .filter(user_session::Column::UserId.eq(user_id))
+        .exec(&db)
+        .await?;
+
+    println!(
+        "Password of user '{}' set, {} existing sessions logged out",
+        username, removed.rows_affected
+    );
+    Ok(())
+}
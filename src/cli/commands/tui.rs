@@ -0,0 +1,289 @@
+use anyhow::Result;
+use chrono::{Local, NaiveTime};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use rust_decimal::Decimal;
+use sea_orm::Database;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+mod client;
+mod data;
+
+use client::ApiClient;
+use data::{Dashboard, DashboardSource};
+
+/// Number of recent transactions listed.
+const RECENT_LIMIT: usize = 50;
+/// How long to wait for a key press before checking whether a refresh is due.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Panel {
+    Accounts,
+    Upcoming,
+    Recent,
+}
+
+impl Panel {
+    fn next(self) -> Self {
+        match self {
+            Panel::Accounts => Panel::Upcoming,
+            Panel::Upcoming => Panel::Recent,
+            Panel::Recent => Panel::Accounts,
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            Panel::Accounts => 0,
+            Panel::Upcoming => 1,
+            Panel::Recent => 2,
+        }
+    }
+}
+
+struct App {
+    source: DashboardSource,
+    timezone: chrono_tz::Tz,
+    horizon_days: i64,
+    dashboard: Option<Dashboard>,
+    /// The last refresh failed; the previous dashboard stays on screen
+    error: Option<String>,
+    loaded_at: Option<NaiveTime>,
+    focus: Panel,
+    tables: [TableState; 3],
+}
+
+impl App {
+    async fn refresh(&mut self) {
+        let today = compute::today_in(&self.timezone);
+        match self.source.load(today, self.horizon_days, RECENT_LIMIT).await {
+            Ok(dashboard) => {
+                self.dashboard = Some(dashboard);
+                self.error = None;
+                self.loaded_at = Some(Local::now().time());
+            }
+            Err(e) => self.error = Some(format!("{:#}", e)),
+        }
+    }
+
+    fn row_count(&self, panel: Panel) -> usize {
+        self.dashboard.as_ref().map_or(0, |dashboard| match panel {
+            Panel::Accounts => dashboard.accounts.len(),
+            Panel::Upcoming => dashboard.upcoming.len(),
+            Panel::Recent => dashboard.recent.len(),
+        })
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        let rows = self.row_count(self.focus);
+        if rows == 0 {
+            return;
+        }
+        let state = &mut self.tables[self.focus.index()];
+        let selected = match (state.selected(), down) {
+            (None, _) => 0,
+            (Some(current), true) => (current + 1).min(rows - 1),
+            (Some(current), false) => current.saturating_sub(1),
+        };
+        state.select(Some(selected));
+    }
+}
+
+/// Interactive terminal dashboard with account balances, upcoming bills and recent transactions
+///
+/// Reads the database directly, or a running server when `api_url` is given.
+pub async fn tui(
+    database_url: &str,
+    api_url: Option<&str>,
+    api_token: Option<String>,
+    timezone: chrono_tz::Tz,
+    horizon_days: i64,
+    refresh_secs: u64,
+) -> Result<()> {
+    let source = match api_url {
+        Some(url) => {
+            info!("Starting dashboard against API at {}", url);
+            DashboardSource::Api(ApiClient::new(url, api_token)?)
+        }
+        None => {
+            info!("Starting dashboard against database");
+            DashboardSource::Database(Database::connect(database_url).await?)
+        }
+    };
+    let mut app = App {
+        source,
+        timezone,
+        horizon_days,
+        dashboard: None,
+        error: None,
+        loaded_at: None,
+        focus: Panel::Accounts,
+        tables: Default::default(),
+    };
+    // Fail before taking over the terminal when the source is unreachable
+    app.refresh().await;
+    if let Some(error) = app.error.take() {
+        anyhow::bail!("Failed to load dashboard: {}", error);
+    }
+
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &mut app, Duration::from_secs(refresh_secs.max(1))).await;
+    ratatui::restore();
+    result
+}
+
+async fn run(terminal: &mut DefaultTerminal, app: &mut App, refresh_every: Duration) -> Result<()> {
+    let mut last_refresh = Instant::now();
+    loop {
+        terminal.draw(|frame| draw(frame, app))?;
+
+        if event::poll(POLL_INTERVAL)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char('r') => {
+                        app.refresh().await;
+                        last_refresh = Instant::now();
+                    }
+                    KeyCode::Tab => app.focus = app.focus.next(),
+                    KeyCode::Down | KeyCode::Char('j') => app.move_selection(true),
+                    KeyCode::Up | KeyCode::Char('k') => app.move_selection(false),
+                    _ => {}
+                }
+            }
+        }
+
+        if last_refresh.elapsed() >= refresh_every {
+            app.refresh().await;
+            last_refresh = Instant::now();
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, app: &mut App) {
+    let [header, body, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [left, right] = Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(body);
+    let [upcoming, recent] = Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(right);
+
+    let Some(dashboard) = app.dashboard.clone() else {
+        frame.render_widget(Paragraph::new("Loading..."), body);
+        return;
+    };
+
+    let updated = app
+        .loaded_at
+        .map(|time| format!("updated {}", time.format("%H:%M:%S")))
+        .unwrap_or_default();
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::from(" FinRust ").bold().reversed(),
+            Span::from(format!("  {}  {}", dashboard.today.format("%Y-%m-%d"), updated)),
+        ])),
+        header,
+    );
+
+    let account_rows = dashboard.accounts.iter().map(|account| {
+        Row::new(vec![
+            Line::from(account.name.clone()),
+            amount_line(account.balance, &account.currency_code),
+        ])
+    });
+    draw_table(
+        frame,
+        app,
+        Panel::Accounts,
+        left,
+        "Balances",
+        Row::new(vec!["Account", "Balance"]),
+        account_rows.collect(),
+        &[Constraint::Min(10), Constraint::Length(20)],
+    );
+
+    let upcoming_title = format!("Upcoming bills ({} days)", app.horizon_days);
+    let upcoming_rows = dashboard.upcoming.iter().map(|bill| {
+        Row::new(vec![
+            Line::from(bill.date.format("%d.%m.").to_string()),
+            Line::from(bill.name.clone()),
+            Line::from(bill.account_name.clone()),
+            amount_line(bill.amount, ""),
+        ])
+    });
+    draw_table(
+        frame,
+        app,
+        Panel::Upcoming,
+        upcoming,
+        &upcoming_title,
+        Row::new(vec!["Due", "Name", "Account", "Amount"]),
+        upcoming_rows.collect(),
+        &[Constraint::Length(7), Constraint::Min(10), Constraint::Length(16), Constraint::Length(14)],
+    );
+
+    let recent_rows = dashboard.recent.iter().map(|transaction| {
+        Row::new(vec![
+            Line::from(transaction.date.format("%d.%m.%Y").to_string()),
+            Line::from(transaction.name.clone()),
+            Line::from(transaction.account_name.clone()),
+            amount_line(transaction.amount, ""),
+        ])
+    });
+    draw_table(
+        frame,
+        app,
+        Panel::Recent,
+        recent,
+        "Recent transactions",
+        Row::new(vec!["Date", "Name", "Account", "Amount"]),
+        recent_rows.collect(),
+        &[Constraint::Length(10), Constraint::Min(10), Constraint::Length(16), Constraint::Length(14)],
+    );
+
+    let status = match &app.error {
+        Some(error) => Line::from(format!(" Refresh failed: {}", error)).fg(Color::Red),
+        None => Line::from(" q quit  r refresh  Tab switch panel  ↑/↓ scroll").dim(),
+    };
+    frame.render_widget(Paragraph::new(status), footer);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn draw_table(
+    frame: &mut Frame,
+    app: &mut App,
+    panel: Panel,
+    area: Rect,
+    title: &str,
+    header: Row,
+    rows: Vec<Row>,
+    widths: &[Constraint],
+) {
+    let border = if app.focus == panel {
+        Style::default().fg(Color::Cyan)
+    } else {
+        Style::default()
+    };
+    let table = Table::new(rows, widths.to_vec())
+        .header(header.style(Style::default().add_modifier(Modifier::BOLD)))
+        .block(Block::bordered().title(title.to_string()).border_style(border))
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(table, area, &mut app.tables[panel.index()]);
+}
+
+fn amount_line(amount: Decimal, currency: &str) -> Line<'static> {
+    let text = format!("{:.2} {}", amount.round_dp(2), currency);
+    let color = if amount < Decimal::ZERO { Color::Red } else { Color::Green };
+    Line::from(text.trim_end().to_string()).fg(color).right_aligned()
+}
@@ -0,0 +1,127 @@
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+use common::AccountStateTimeseries;
+use model::entities::recurring_transaction::RecurrencePeriod;
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+/// Typed client for the parts of the REST API the dashboard reads.
+pub struct ApiClient {
+    http: reqwest::Client,
+    base_url: String,
+    token: Option<String>,
+}
+
+/// Both plain and paginated API responses carry their payload in `data`.
+#[derive(Deserialize)]
+struct Envelope<T> {
+    data: T,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiAccount {
+    pub id: i32,
+    pub name: String,
+    pub currency_code: String,
+    pub include_in_statistics: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiRecurringTransaction {
+    pub name: String,
+    pub amount: Decimal,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub period: String,
+    /// Not sent by servers predating recurrence rules.
+    #[serde(default)]
+    pub rrule: Option<String>,
+    pub target_account_id: i32,
+    pub approval_status: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiHoliday {
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiTransaction {
+    pub name: String,
+    pub amount: Decimal,
+    pub date: NaiveDate,
+    pub target_account_id: i32,
+}
+
+impl ApiRecurringTransaction {
+    /// The period as sent by the API, which uses the variant names.
+    pub fn recurrence_period(&self) -> Option<RecurrencePeriod> {
+        match self.period.as_str() {
+            "Daily" => Some(RecurrencePeriod::Daily),
+            "Weekly" => Some(RecurrencePeriod::Weekly),
+            "WorkDay" => Some(RecurrencePeriod::WorkDay),
+            "Monthly" => Some(RecurrencePeriod::Monthly),
+            "Quarterly" => Some(RecurrencePeriod::Quarterly),
+            "HalfYearly" => Some(RecurrencePeriod::HalfYearly),
+            "Yearly" => Some(RecurrencePeriod::Yearly),
+            _ => None,
+        }
+    }
+}
+
+impl ApiClient {
+    /// `base_url` is the server root, e.g. `http://localhost:3000`.
+    pub fn new(base_url: &str, token: Option<String>) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()?;
+        Ok(Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token,
+        })
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let url = format!("{}/api/v1{}", self.base_url, path);
+        let mut request = self.http.get(&url);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await.with_context(|| format!("Failed to reach {}", url))?;
+        let status = response.status();
+        if !status.is_success() {
+            bail!("GET {} returned {}", url, status);
+        }
+        let envelope: Envelope<T> = response
+            .json()
+            .await
+            .with_context(|| format!("Unexpected response from {}", url))?;
+        Ok(envelope.data)
+    }
+
+    pub async fn accounts(&self) -> Result<Vec<ApiAccount>> {
+        self.get("/accounts").await
+    }
+
+    /// Balances of the accounts included in statistics on `date`.
+    pub async fn balances(&self, date: NaiveDate) -> Result<AccountStateTimeseries> {
+        self.get(&format!("/accounts/timeseries?start_date={}&end_date={}", date, date)).await
+    }
+
+    pub async fn recurring_transactions(&self) -> Result<Vec<ApiRecurringTransaction>> {
+        self.get("/recurring-transactions?is_simulated=false&limit=1000").await
+    }
+
+    /// Holidays of the enabled country calendars and custom holidays between the dates.
+    pub async fn holidays(&self, start_date: NaiveDate, end_date: NaiveDate) -> Result<Vec<ApiHoliday>> {
+        self.get(&format!("/holidays?start_date={}&end_date={}", start_date, end_date)).await
+    }
+
+    /// The most recent non-simulated one-off transactions, newest first.
+    pub async fn recent_transactions(&self, limit: usize) -> Result<Vec<ApiTransaction>> {
+        self.get(&format!("/transactions?page=1&limit={}&isSimulated=false", limit)).await
+    }
+}
@@ -0,0 +1,293 @@
+use anyhow::{anyhow, Result};
+use chrono::{Duration, NaiveDate};
+use common::AccountStatePoint;
+use compute::account::utils::{generate_occurrences, load_holiday_calendar};
+use compute::holidays::HolidayCalendar;
+use compute::recurrence_rule::RecurrenceRule;
+use compute::{account::AccountStateCalculator, default_compute};
+use model::entities::recurring_transaction::{self, ApprovalStatus, RecurrencePeriod};
+use model::entities::{account, one_off_transaction};
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use std::collections::HashMap;
+
+use super::client::ApiClient;
+use crate::helpers::converters::convert_dataframe_to_timeseries;
+
+/// Where the dashboard reads its data from.
+pub enum DashboardSource {
+    Database(DatabaseConnection),
+    Api(ApiClient),
+}
+
+#[derive(Debug, Clone)]
+pub struct AccountBalance {
+    pub name: String,
+    pub currency_code: String,
+    pub balance: Decimal,
+}
+
+#[derive(Debug, Clone)]
+pub struct UpcomingBill {
+    pub date: NaiveDate,
+    pub name: String,
+    pub account_name: String,
+    /// Outflow as a negative number
+    pub amount: Decimal,
+}
+
+#[derive(Debug, Clone)]
+pub struct RecentTransaction {
+    pub date: NaiveDate,
+    pub name: String,
+    pub account_name: String,
+    pub amount: Decimal,
+}
+
+/// Everything shown on one screen of the dashboard.
+#[derive(Debug, Clone)]
+pub struct Dashboard {
+    pub today: NaiveDate,
+    pub accounts: Vec<AccountBalance>,
+    pub upcoming: Vec<UpcomingBill>,
+    pub recent: Vec<RecentTransaction>,
+}
+
+struct AccountInfo {
+    id: i32,
+    name: String,
+    currency_code: String,
+    include_in_statistics: bool,
+}
+
+struct Schedule {
+    name: String,
+    amount: Decimal,
+    start_date: NaiveDate,
+    end_date: Option<NaiveDate>,
+    period: RecurrencePeriod,
+    /// Overrides `period` when set.
+    rrule: Option<RecurrenceRule>,
+    account_id: i32,
+}
+
+struct Booked {
+    name: String,
+    amount: Decimal,
+    date: NaiveDate,
+    account_id: i32,
+}
+
+/// Raw data as read from either source, before it is arranged for display.
+struct Snapshot {
+    accounts: Vec<AccountInfo>,
+    balances: Vec<AccountStatePoint>,
+    schedules: Vec<Schedule>,
+    holidays: HolidayCalendar,
+    recent: Vec<Booked>,
+}
+
+impl DashboardSource {
+    /// Loads the dashboard as of `today`, listing bills due within `horizon_days`
+    /// and the `recent_limit` latest transactions.
+    pub async fn load(&self, today: NaiveDate, horizon_days: i64, recent_limit: usize) -> Result<Dashboard> {
+        let snapshot = match self {
+            DashboardSource::Database(db) => load_from_database(db, today, recent_limit).await?,
+            DashboardSource::Api(client) => load_from_api(client, today, horizon_days, recent_limit).await?,
+        };
+        Ok(build_dashboard(snapshot, today, horizon_days))
+    }
+}
+
+async fn load_from_database(db: &DatabaseConnection, today: NaiveDate, recent_limit: usize) -> Result<Snapshot> {
+    let accounts = account::Entity::find().all(db).await?;
+    let included: Vec<account::Model> = accounts.iter().filter(|a| a.include_in_statistics).cloned().collect();
+    let balances = if included.is_empty() {
+        Vec::new()
+    } else {
+        let df = default_compute(Some(today))
+            .compute_account_state(db, &included, today, today)
+            .await?;
+        convert_dataframe_to_timeseries(df).map_err(|e| anyhow!(e))?.data_points
+    };
+
+    let schedules = recurring_transaction::Entity::find()
+        .filter(recurring_transaction::Column::IsSimulated.eq(false))
+        .filter(recurring_transaction::Column::ApprovalStatus.eq(ApprovalStatus::Approved))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|recurring| Schedule {
+            name: recurring.name,
+            amount: recurring.amount,
+            start_date: recurring.start_date,
+            end_date: recurring.end_date,
+            period: recurring.period,
+            rrule: recurring.rrule.as_deref().and_then(|rrule| rrule.parse().ok()),
+            account_id: recurring.target_account_id,
+        })
+        .collect();
+    let holidays = load_holiday_calendar(db).await?;
+
+    let recent = one_off_transaction::Entity::find()
+        .filter(one_off_transaction::Column::IsSimulated.eq(false))
+        .order_by_desc(one_off_transaction::Column::Date)
+        .order_by_desc(one_off_transaction::Column::Id)
+        .limit(recent_limit as u64)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|transaction| Booked {
+            name: transaction.name,
+            amount: transaction.amount,
+            date: transaction.date,
+            account_id: transaction.target_account_id,
+        })
+        .collect();
+
+    Ok(Snapshot {
+        accounts: accounts
+            .into_iter()
+            .map(|a| AccountInfo {
+                id: a.id,
+                name: a.name,
+                currency_code: a.currency_code,
+                include_in_statistics: a.include_in_statistics,
+            })
+            .collect(),
+        balances,
+        schedules,
+        holidays,
+        recent,
+    })
+}
+
+async fn load_from_api(
+    client: &ApiClient,
+    today: NaiveDate,
+    horizon_days: i64,
+    recent_limit: usize,
+) -> Result<Snapshot> {
+    let accounts = client.accounts().await?;
+    let balances = client.balances(today).await?.data_points;
+    let schedules = client
+        .recurring_transactions()
+        .await?
+        .into_iter()
+        .filter(|recurring| recurring.approval_status == "Approved")
+        .filter_map(|recurring| {
+            Some(Schedule {
+                period: recurring.recurrence_period()?,
+                rrule: recurring.rrule.as_deref().and_then(|rrule| rrule.parse().ok()),
+                name: recurring.name,
+                amount: recurring.amount,
+                start_date: recurring.start_date,
+                end_date: recurring.end_date,
+                account_id: recurring.target_account_id,
+            })
+        })
+        .collect();
+    // Servers predating holiday calendars have none, leaving weekends as the only days off
+    let holidays = client
+        .holidays(today, today + Duration::days(horizon_days))
+        .await
+        .map(|holidays| HolidayCalendar::new([], holidays.into_iter().map(|holiday| (holiday.date, holiday.name))))
+        .unwrap_or_default();
+    let recent = client
+        .recent_transactions(recent_limit)
+        .await?
+        .into_iter()
+        .map(|transaction| Booked {
+            name: transaction.name,
+            amount: transaction.amount,
+            date: transaction.date,
+            account_id: transaction.target_account_id,
+        })
+        .collect();
+
+    Ok(Snapshot {
+        accounts: accounts
+            .into_iter()
+            .map(|a| AccountInfo {
+                id: a.id,
+                name: a.name,
+                currency_code: a.currency_code,
+                include_in_statistics: a.include_in_statistics,
+            })
+            .collect(),
+        balances,
+        schedules,
+        holidays,
+        recent,
+    })
+}
+
+fn build_dashboard(snapshot: Snapshot, today: NaiveDate, horizon_days: i64) -> Dashboard {
+    let names: HashMap<i32, &str> = snapshot.accounts.iter().map(|a| (a.id, a.name.as_str())).collect();
+    let account_name = |id: i32| names.get(&id).map(|name| name.to_string()).unwrap_or_else(|| format!("#{}", id));
+
+    let balances: HashMap<i32, Decimal> = snapshot
+        .balances
+        .iter()
+        .filter(|point| point.date == today)
+        .map(|point| (point.account_id, point.balance))
+        .collect();
+    let mut accounts: Vec<AccountBalance> = snapshot
+        .accounts
+        .iter()
+        .filter(|a| a.include_in_statistics)
+        .map(|a| AccountBalance {
+            name: a.name.clone(),
+            currency_code: a.currency_code.clone(),
+            balance: balances.get(&a.id).copied().unwrap_or(Decimal::ZERO),
+        })
+        .collect();
+    accounts.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let horizon = today + Duration::days(horizon_days);
+    let mut upcoming: Vec<UpcomingBill> = snapshot
+        .schedules
+        .iter()
+        .filter(|schedule| schedule.amount < Decimal::ZERO)
+        .flat_map(|schedule| {
+            let dates = match &schedule.rrule {
+                Some(rule) => rule.occurrences(schedule.start_date, schedule.end_date, today, horizon),
+                None => generate_occurrences(
+                    schedule.start_date,
+                    schedule.end_date,
+                    &schedule.period,
+                    &snapshot.holidays,
+                    today,
+                    horizon,
+                ),
+            };
+            dates
+                .into_iter()
+                .map(|date| UpcomingBill {
+                    date,
+                    name: schedule.name.clone(),
+                    account_name: account_name(schedule.account_id),
+                    amount: schedule.amount,
+                })
+        })
+        .collect();
+    upcoming.sort_by(|a, b| a.date.cmp(&b.date).then(a.amount.cmp(&b.amount)));
+
+    let recent = snapshot
+        .recent
+        .into_iter()
+        .map(|booked| RecentTransaction {
+            date: booked.date,
+            account_name: account_name(booked.account_id),
+            name: booked.name,
+            amount: booked.amount,
+        })
+        .collect();
+
+    Dashboard {
+        today,
+        accounts,
+        upcoming,
+        recent,
+    }
+}
@@ -0,0 +1,26 @@
+use anyhow::{bail, Result};
+use sea_orm::Database;
+use tracing::info;
+
+use crate::helpers::audit_chain::verify_chain;
+
+pub async fn verify_audit_chain(database_url: &str) -> Result<()> {
+    info!("Verifying audit hash chain");
+    let db = Database::connect(database_url).await?;
+    let verification = verify_chain(&db).await?;
+
+    if !verification.valid {
+        bail!(
+            "Audit chain is broken at entry {}: {} ({} entries checked)",
+            verification.first_invalid_id.unwrap_or_default(),
+            verification.reason.unwrap_or_default(),
+            verification.entries_checked
+        );
+    }
+
+    println!("Audit chain is intact: {} entries verified", verification.entries_checked);
+    if let Some(head_hash) = verification.head_hash {
+        println!("Head hash: {}", head_hash);
+    }
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anyhow::{bail, Result};
+use sea_orm::Database;
+use tracing::info;
+
+use crate::helpers::schema_drift::detect_schema_drift;
+
+pub async fn verify_schema(database_url: &str) -> Result<()> {
+    info!("Verifying database schema");
+    let db = Database::connect(database_url).await?;
+    let report = detect_schema_drift(&db).await?;
+
+    if report.is_clean() {
+        println!("Database schema matches the expected schema");
+        return Ok(());
+    }
+
+    for migration in &report.pending_migrations {
+        println!("pending migration: {}", migration);
+    }
+    for migration in &report.unknown_migrations {
+        println!("unknown migration: {}", migration);
+    }
+    for drift in &report.drift {
+        println!("{}", drift);
+    }
+    bail!(
+        "Database schema drift detected: {} difference(s), {} pending and {} unknown migration(s)",
+        report.drift.len(),
+        report.pending_migrations.len(),
+        report.unknown_migrations.len()
+    );
+}
@@ -1,12 +1,27 @@
+use crate::helpers::field_encryption::{field_encryption_salt, FieldCipher};
+use crate::helpers::notifications::SmtpSettings;
+use crate::helpers::time_travel::restored_books_cache;
 use crate::schemas::AppState;
+use crate::tasks::exchange_rates::FxProvider;
 use anyhow::Result;
+use chrono_tz::Tz;
 use moka::future::Cache;
 use sea_orm::Database;
 use std::time::Duration;
 use tracing::{debug, error, info, trace};
 
 /// Initialize application configuration and state with provided database URL
-pub async fn initialize_app_state_with_url(database_url: &str) -> Result<AppState> {
+pub async fn initialize_app_state_with_url(
+    database_url: &str,
+    double_entry_strict: bool,
+    audit_chain: bool,
+    fx_provider: Option<FxProvider>,
+    field_encryption_key: Option<&str>,
+    auth_required: bool,
+    timezone: Tz,
+    smtp_url: Option<&str>,
+    smtp_from: &str,
+) -> Result<AppState> {
     trace!("Entering initialize_app_state_with_url function");
     info!("Initializing application state");
     debug!("Database URL: {}", database_url);
@@ -41,7 +56,37 @@ pub async fn initialize_app_state_with_url(database_url: &str) -> Result<AppStat
         .build();
     debug!("Cache initialized with max_capacity=1000, ttl=24h");
 
-    let app_state = AppState { db, cache };
+    if double_entry_strict {
+        info!("Strict double-entry mode enabled");
+    }
+    if audit_chain {
+        info!("Audit hash chain enabled");
+    }
+    if let Some(provider) = fx_provider {
+        info!("Exchange rate provider: {:?}", provider);
+    }
+
+    let field_cipher = match field_encryption_key {
+        Some(passphrase) => {
+            let salt = field_encryption_salt(&db).await?;
+            Some(FieldCipher::from_passphrase(passphrase, &salt)?)
+        }
+        None => None,
+    };
+    if field_cipher.is_some() {
+        info!("Field encryption enabled");
+    }
+    if auth_required {
+        info!("Authentication required for API requests");
+    }
+    info!("Default timezone: {}", timezone);
+    let smtp = smtp_url.map(|url| SmtpSettings::new(url, smtp_from)).transpose()?;
+    if let Some(smtp) = &smtp {
+        info!("E-mail notifications enabled: {:?}", smtp);
+    }
+
+    let restored_books = restored_books_cache();
+    let app_state = AppState { db, cache, double_entry_strict, audit_chain, fx_provider, field_cipher, auth_required, timezone, smtp, restored_books };
     info!("Application state initialized successfully");
     trace!("initialize_app_state_with_url function completed");
 
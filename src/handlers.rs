@@ -1,14 +1,42 @@
+pub mod account_comparison;
+pub mod account_freshness;
+pub mod account_notes;
+pub mod account_shares;
 pub mod accounts;
+pub mod assets;
+pub mod audit;
+pub mod auth;
+pub mod balance_preview;
+pub mod batch;
+pub mod budgets;
 pub mod cache;
 pub mod categories;
+pub mod diagnostics;
+pub mod double_entry;
+pub mod exchange_rates;
+pub mod export;
+pub mod forecast;
+pub mod goals;
 pub mod health;
+pub mod holidays;
+pub mod insights;
 pub mod manual_account_states;
+pub mod merchants;
 pub mod metrics;
+pub mod networth;
+pub mod notifications;
+pub mod openapi;
+pub mod periods;
 pub mod prompt;
+pub mod reconciliation;
 pub mod recurring_income;
+pub mod reports;
+pub mod restore;
+pub mod safe_to_spend;
 pub mod scenarios;
 pub mod statistics;
 pub mod tags;
 pub mod timeseries;
+pub mod tokens;
 pub mod transactions;
 pub mod users;
@@ -0,0 +1,299 @@
+use crate::handlers::reports::gather_flow_entries;
+use crate::helpers::access::AccountAccess;
+use crate::helpers::converters::convert_dataframe_to_timeseries;
+use crate::helpers::timezone::UserTimezone;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::NaiveDate;
+use compute::{
+    account::{utils::history_start, AccountStateCalculator},
+    comparison::{category_changes, effect_on_accounts, largest_changes},
+    default_compute_with_scenario,
+    flows::UNCATEGORIZED_LABEL,
+};
+use model::entities::{account, category, one_off_transaction};
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, Condition, DbErr, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tracing::{debug, error, info, instrument, trace, warn};
+use utoipa::{IntoParams, ToSchema};
+
+/// Most contributors returned per list
+const MAX_CONTRIBUTORS: usize = 50;
+
+/// Query parameters for comparing account balances between two dates
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct AccountComparisonQuery {
+    /// Earlier date (YYYY-MM-DD)
+    pub date_a: NaiveDate,
+    /// Later date (YYYY-MM-DD)
+    pub date_b: NaiveDate,
+    /// Include accounts excluded from statistics
+    #[serde(default)]
+    pub include_ignored: bool,
+    /// Number of top transactions and categories to return (default: 5, at most 50)
+    pub limit: Option<usize>,
+}
+
+/// Balance change of one account
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AccountBalanceChange {
+    pub account_id: i32,
+    pub name: String,
+    pub currency_code: String,
+    /// Balance at the end of `date_a`
+    pub balance_a: Decimal,
+    /// Balance at the end of `date_b`
+    pub balance_b: Decimal,
+    pub change: Decimal,
+}
+
+/// A transaction that moved the compared balances
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TransactionContribution {
+    pub transaction_id: i32,
+    pub name: String,
+    pub date: NaiveDate,
+    pub target_account_id: i32,
+    pub source_account_id: Option<i32>,
+    pub category_id: Option<i32>,
+    /// How much the transaction changed the combined balance of the compared accounts
+    pub amount: Decimal,
+}
+
+/// How much everything booked under one category moved the compared balances
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CategoryContribution {
+    /// Empty for amounts without a category
+    pub category_id: Option<i32>,
+    pub name: String,
+    pub amount: Decimal,
+}
+
+/// What changed between two dates
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AccountComparisonResponse {
+    pub date_a: NaiveDate,
+    pub date_b: NaiveDate,
+    pub accounts: Vec<AccountBalanceChange>,
+    /// Combined balance at `date_a` of the compared accounts counting towards net worth
+    pub total_a: Decimal,
+    /// Combined balance at `date_b` of the compared accounts counting towards net worth
+    pub total_b: Decimal,
+    pub total_change: Decimal,
+    /// One-off transactions after `date_a` up to `date_b` with the largest effect, largest first
+    pub top_transactions: Vec<TransactionContribution>,
+    /// Categories with the largest net effect after `date_a` up to `date_b`, largest first
+    pub top_categories: Vec<CategoryContribution>,
+}
+
+fn comparison_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// Compare account balances between two dates
+///
+/// Returns the balance of every accessible account at both dates, the combined change
+/// and the transactions and categories that contributed most to it, e.g. for a
+/// "what changed since last month" view. Virtual accounts (allowances) are listed but
+/// left out of the totals, as in the net worth.
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/compare",
+    params(AccountComparisonQuery),
+    responses(
+        (status = 200, description = "Balance changes between the two dates", body = ApiResponse<AccountComparisonResponse>),
+        (status = 400, description = "Invalid dates or limit", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "accounts"
+)]
+#[instrument(skip(state))]
+pub async fn compare_accounts(
+    State(state): State<AppState>,
+    Query(query): Query<AccountComparisonQuery>,
+    access: AccountAccess,
+    timezone: UserTimezone,
+) -> Result<Json<ApiResponse<AccountComparisonResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering compare_accounts function");
+
+    if query.date_a > query.date_b {
+        warn!("Rejected comparison of {} with {}", query.date_a, query.date_b);
+        return Err(comparison_error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_DATE_RANGE",
+            "date_a must be before or equal to date_b".to_string(),
+        ));
+    }
+    let limit = query.limit.unwrap_or(5);
+    if limit == 0 || limit > MAX_CONTRIBUTORS {
+        warn!("Rejected comparison limit {}", limit);
+        return Err(comparison_error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_LIMIT",
+            format!("limit must be between 1 and {}", MAX_CONTRIBUTORS),
+        ));
+    }
+
+    let database_error = |e: DbErr| {
+        error!("Database error while comparing accounts: {}", e);
+        comparison_error(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", e.to_string())
+    };
+    let compute_error = |e: String| {
+        error!("Failed to compute account balances for comparison: {}", e);
+        comparison_error(StatusCode::INTERNAL_SERVER_ERROR, "COMPUTE_ERROR", e)
+    };
+
+    let accounts: Vec<account::Model> = access
+        .filter(account::Entity::find(), [account::Column::Id])
+        .all(&state.db)
+        .await
+        .map_err(database_error)?
+        .into_iter()
+        .filter(|account| query.include_ignored || account.include_in_statistics)
+        .collect();
+    let account_ids: HashSet<i32> = accounts.iter().map(|account| account.id).collect();
+    debug!("Comparing {} accounts between {} and {}", accounts.len(), query.date_a, query.date_b);
+
+    let mut balances: HashMap<(i32, NaiveDate), Decimal> = HashMap::new();
+    if !accounts.is_empty() {
+        let from = history_start(&state.db, &accounts, query.date_a)
+            .await
+            .map_err(database_error)?;
+        let df = default_compute_with_scenario(Some(timezone.today()), None)
+            .compute_account_state(&state.db, &accounts, from, query.date_b)
+            .await
+            .map_err(|e| compute_error(e.to_string()))?;
+        balances.extend(
+            convert_dataframe_to_timeseries(df)
+                .map_err(compute_error)?
+                .data_points
+                .into_iter()
+                .filter(|point| point.date == query.date_a || point.date == query.date_b)
+                .map(|point| ((point.account_id, point.date), point.balance)),
+        );
+    }
+    let balance_on = |account_id: i32, date: NaiveDate| balances.get(&(account_id, date)).copied().unwrap_or_default();
+
+    let changes: Vec<AccountBalanceChange> = accounts
+        .iter()
+        .map(|account| {
+            let balance_a = balance_on(account.id, query.date_a);
+            let balance_b = balance_on(account.id, query.date_b);
+            AccountBalanceChange {
+                account_id: account.id,
+                name: account.name.clone(),
+                currency_code: account.currency_code.clone(),
+                balance_a,
+                balance_b,
+                change: balance_b - balance_a,
+            }
+        })
+        .collect();
+    let net_worth_ids: HashSet<i32> = accounts
+        .iter()
+        .filter(|account| account.account_kind.counts_towards_net_worth())
+        .map(|account| account.id)
+        .collect();
+    let (total_a, total_b) = changes
+        .iter()
+        .filter(|change| net_worth_ids.contains(&change.account_id))
+        .fold((Decimal::ZERO, Decimal::ZERO), |(a, b), change| (a + change.balance_a, b + change.balance_b));
+
+    // Contributions are booked after date_a and up to date_b
+    let mut top_transactions = Vec::new();
+    let mut top_categories = Vec::new();
+    if let Some(start) = query.date_a.succ_opt().filter(|start| *start <= query.date_b && !accounts.is_empty()) {
+        let ids: Vec<i32> = account_ids.iter().copied().collect();
+        let transactions = one_off_transaction::Entity::find()
+            .filter(one_off_transaction::Column::Date.between(start, query.date_b))
+            .filter(one_off_transaction::Column::IsSimulated.eq(false))
+            .filter(one_off_transaction::Column::ParentTransactionId.is_null())
+            .filter(
+                Condition::any()
+                    .add(one_off_transaction::Column::TargetAccountId.is_in(ids.clone()))
+                    .add(one_off_transaction::Column::SourceAccountId.is_in(ids.clone())),
+            )
+            .all(&state.db)
+            .await
+            .map_err(database_error)?;
+        top_transactions = largest_changes(
+            transactions.into_iter().map(|transaction| {
+                let amount = effect_on_accounts(
+                    transaction.target_account_id,
+                    transaction.source_account_id,
+                    transaction.amount,
+                    &account_ids,
+                );
+                (transaction, amount)
+            }),
+            limit,
+        )
+        .into_iter()
+        .map(|(transaction, amount)| TransactionContribution {
+            transaction_id: transaction.id,
+            name: transaction.name,
+            date: transaction.date,
+            target_account_id: transaction.target_account_id,
+            source_account_id: transaction.source_account_id,
+            category_id: transaction.category_id,
+            amount,
+        })
+        .collect();
+
+        let entries = gather_flow_entries(&state.db, &ids, start, query.date_b)
+            .await
+            .map_err(database_error)?;
+        let categories: HashMap<i32, String> = category::Entity::find()
+            .all(&state.db)
+            .await
+            .map_err(database_error)?
+            .into_iter()
+            .map(|category| (category.id, category.name))
+            .collect();
+        top_categories = category_changes(&entries, &account_ids, limit)
+            .into_iter()
+            .map(|(category_id, amount)| CategoryContribution {
+                category_id,
+                name: category_id
+                    .and_then(|id| categories.get(&id).cloned())
+                    .unwrap_or_else(|| UNCATEGORIZED_LABEL.to_string()),
+                amount,
+            })
+            .collect();
+    }
+
+    info!(
+        "Compared {} accounts between {} and {}: total change {}",
+        changes.len(),
+        query.date_a,
+        query.date_b,
+        total_b - total_a
+    );
+    Ok(Json(ApiResponse {
+        data: AccountComparisonResponse {
+            date_a: query.date_a,
+            date_b: query.date_b,
+            accounts: changes,
+            total_a,
+            total_b,
+            total_change: total_b - total_a,
+            top_transactions,
+            top_categories,
+        },
+        message: "Account comparison retrieved successfully".to_string(),
+        success: true,
+    }))
+}
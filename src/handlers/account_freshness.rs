@@ -0,0 +1,196 @@
+use crate::helpers::access::AccountAccess;
+use crate::helpers::timezone::UserTimezone;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use axum_valid::Valid;
+use chrono::NaiveDate;
+use model::entities::{account, imported_transaction, manual_account_state, one_off_transaction};
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QuerySelect};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{debug, error, info, instrument, trace};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Days without data after which an account is reported stale when not configured
+const DEFAULT_STALE_AFTER_DAYS: i64 = 30;
+
+/// Query parameters for account freshness
+#[derive(Debug, Deserialize, IntoParams, Validate)]
+pub struct AccountFreshnessQuery {
+    /// Flag accounts without any data for more than this many days (default: 30)
+    #[validate(range(min = 1, max = 3650))]
+    pub stale_after_days: Option<i64>,
+}
+
+/// When an account last received data
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AccountFreshness {
+    pub account_id: i32,
+    pub name: String,
+    /// Latest confirmed, non-simulated transaction into or out of the account
+    pub last_transaction_date: Option<NaiveDate>,
+    /// Latest imported bank transaction
+    pub last_import_date: Option<NaiveDate>,
+    /// Latest manually entered balance
+    pub last_manual_state_date: Option<NaiveDate>,
+    /// Latest of the three dates above
+    pub last_activity_date: Option<NaiveDate>,
+    /// Days since `last_activity_date`; empty for accounts without any data
+    pub days_since_last_activity: Option<i64>,
+    /// No data for more than `stale_after_days` days, or none at all
+    pub is_stale: bool,
+}
+
+/// Latest `date_column` value up to `today` per value of `account_column`.
+async fn latest_dates<E: EntityTrait>(
+    db: &DatabaseConnection,
+    select: sea_orm::Select<E>,
+    account_column: E::Column,
+    date_column: E::Column,
+    today: NaiveDate,
+) -> Result<HashMap<i32, NaiveDate>, DbErr> {
+    let rows: Vec<(Option<i32>, Option<NaiveDate>)> = select
+        .filter(date_column.lte(today))
+        .select_only()
+        .column(account_column)
+        .column_as(date_column.max(), "last_date")
+        .group_by(account_column)
+        .into_tuple()
+        .all(db)
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|(account_id, date)| Some((account_id?, date?)))
+        .collect())
+}
+
+/// Get account data freshness
+///
+/// Reports, for every accessible account, when it last got a transaction, an imported
+/// bank transaction or a manual balance, and flags accounts without data for more than
+/// `stale_after_days` days, e.g. because a bank sync silently stopped. Stalest accounts
+/// come first.
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/freshness",
+    params(AccountFreshnessQuery),
+    responses(
+        (status = 200, description = "Account freshness", body = ApiResponse<Vec<AccountFreshness>>),
+        (status = 400, description = "Invalid stale_after_days", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "accounts"
+)]
+#[instrument(skip(state))]
+pub async fn get_account_freshness(
+    State(state): State<AppState>,
+    Valid(Query(query)): Valid<Query<AccountFreshnessQuery>>,
+    access: AccountAccess,
+    timezone: UserTimezone,
+) -> Result<Json<ApiResponse<Vec<AccountFreshness>>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_account_freshness function");
+
+    let database_error = |e: DbErr| {
+        error!("Database error while checking account freshness: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to check account freshness".to_string(),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    };
+
+    let today = timezone.today();
+    let stale_after_days = query.stale_after_days.unwrap_or(DEFAULT_STALE_AFTER_DAYS);
+
+    let accounts = access
+        .filter(account::Entity::find(), [account::Column::Id])
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+
+    let transactions = || {
+        one_off_transaction::Entity::find()
+            .filter(one_off_transaction::Column::IsSimulated.eq(false))
+            .filter(one_off_transaction::Column::Status.eq(one_off_transaction::OneOffStatus::Confirmed))
+    };
+    let as_target = latest_dates(
+        &state.db,
+        transactions(),
+        one_off_transaction::Column::TargetAccountId,
+        one_off_transaction::Column::Date,
+        today,
+    )
+    .await
+    .map_err(database_error)?;
+    let as_source = latest_dates(
+        &state.db,
+        transactions(),
+        one_off_transaction::Column::SourceAccountId,
+        one_off_transaction::Column::Date,
+        today,
+    )
+    .await
+    .map_err(database_error)?;
+    let imports = latest_dates(
+        &state.db,
+        imported_transaction::Entity::find(),
+        imported_transaction::Column::AccountId,
+        imported_transaction::Column::Date,
+        today,
+    )
+    .await
+    .map_err(database_error)?;
+    let manual_states = latest_dates(
+        &state.db,
+        manual_account_state::Entity::find(),
+        manual_account_state::Column::AccountId,
+        manual_account_state::Column::Date,
+        today,
+    )
+    .await
+    .map_err(database_error)?;
+
+    let mut freshness: Vec<AccountFreshness> = accounts
+        .into_iter()
+        .map(|account| {
+            let last_transaction_date = as_target
+                .get(&account.id)
+                .copied()
+                .max(as_source.get(&account.id).copied());
+            let last_import_date = imports.get(&account.id).copied();
+            let last_manual_state_date = manual_states.get(&account.id).copied();
+            let last_activity_date = last_transaction_date.max(last_import_date).max(last_manual_state_date);
+            let days_since_last_activity = last_activity_date.map(|date| (today - date).num_days());
+            AccountFreshness {
+                account_id: account.id,
+                name: account.name,
+                last_transaction_date,
+                last_import_date,
+                last_manual_state_date,
+                last_activity_date,
+                days_since_last_activity,
+                is_stale: days_since_last_activity.is_none_or(|days| days > stale_after_days),
+            }
+        })
+        .collect();
+    // Accounts without any data first, then the longest silent
+    freshness.sort_by_key(|account| (account.last_activity_date, account.account_id));
+    debug!("Checked freshness of {} accounts", freshness.len());
+
+    let stale_count = freshness.iter().filter(|account| account.is_stale).count();
+    info!("{} of {} accounts have no data for over {} days", stale_count, freshness.len(), stale_after_days);
+
+    Ok(Json(ApiResponse {
+        data: freshness,
+        message: "Account freshness retrieved successfully".to_string(),
+        success: true,
+    }))
+}
@@ -0,0 +1,624 @@
+use crate::helpers::access::{account_read_only, AccountAccess};
+use crate::helpers::field_encryption::FieldCipher;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::NaiveDateTime;
+use model::entities::{account, account_document, account_note, one_off_transaction};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, FromQueryResult, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{debug, error, info, instrument, trace, warn};
+use utoipa::ToSchema;
+
+/// Content type documents are served with when the upload didn't name one.
+const DEFAULT_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// Request body for creating an account note
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateAccountNoteRequest {
+    pub title: String,
+    /// Free-form text, e.g. contract terms or contact details
+    #[serde(default)]
+    pub body: String,
+    /// Sensitive value such as an account number; stored encrypted
+    pub secret: Option<String>,
+}
+
+/// Request body for updating an account note
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UpdateAccountNoteRequest {
+    pub title: Option<String>,
+    pub body: Option<String>,
+    /// New secret; an empty string removes the stored one
+    pub secret: Option<String>,
+}
+
+/// Account note response model
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AccountNoteResponse {
+    pub id: i32,
+    pub account_id: i32,
+    pub title: String,
+    pub body: String,
+    /// Whether the note stores a secret
+    pub has_secret: bool,
+    /// The decrypted secret; null when the server has no encryption key configured
+    pub secret: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl AccountNoteResponse {
+    fn new(model: account_note::Model, cipher: Option<&FieldCipher>) -> Self {
+        let secret = match (&model.secret_ciphertext, cipher) {
+            (Some(ciphertext), Some(cipher)) => cipher
+                .decrypt(ciphertext)
+                .inspect_err(|e| warn!("Failed to decrypt secret of account note {}: {}", model.id, e))
+                .ok(),
+            _ => None,
+        };
+        Self {
+            id: model.id,
+            account_id: model.account_id,
+            title: model.title,
+            body: model.body,
+            has_secret: model.secret_ciphertext.is_some(),
+            secret,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+/// Request body for uploading a document
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UploadAccountDocumentRequest {
+    pub file_name: String,
+    /// MIME type, e.g. "application/pdf" (default: application/octet-stream)
+    pub content_type: Option<String>,
+    /// File content, base64 encoded
+    pub content_base64: String,
+}
+
+/// Document metadata; the content is fetched through the download endpoint
+#[derive(Debug, Serialize, Deserialize, ToSchema, FromQueryResult)]
+pub struct AccountDocumentResponse {
+    pub id: i32,
+    pub account_id: i32,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// Hex encoded SHA-256 of the content
+    pub sha256: String,
+    pub uploaded_at: NaiveDateTime,
+}
+
+fn note_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+fn database_error(e: DbErr) -> (StatusCode, Json<ErrorResponse>) {
+    error!("Database error in account notes: {}", e);
+    note_error(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", e.to_string())
+}
+
+fn account_not_found(account_id: i32) -> (StatusCode, Json<ErrorResponse>) {
+    warn!("Account {} not found", account_id);
+    note_error(
+        StatusCode::NOT_FOUND,
+        "ACCOUNT_NOT_FOUND",
+        format!("Account with id {} not found", account_id),
+    )
+}
+
+/// Rejects accounts the caller can't see as not found and, with `write`, accounts only
+/// shared read-only with them.
+fn ensure_account_access(
+    access: &AccountAccess,
+    account_id: i32,
+    write: bool,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if !access.can_access(account_id) {
+        return Err(account_not_found(account_id));
+    }
+    if write && !access.can_write(account_id) {
+        warn!("Account {} is read-only for user {:?}", account_id, access.user_id());
+        return Err(account_read_only(account_id));
+    }
+    Ok(())
+}
+
+async fn ensure_account_exists(
+    db: &DatabaseConnection,
+    access: &AccountAccess,
+    account_id: i32,
+    write: bool,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    ensure_account_access(access, account_id, write)?;
+    if account::Entity::find_by_id(account_id).one(db).await.map_err(database_error)?.is_none() {
+        return Err(account_not_found(account_id));
+    }
+    Ok(())
+}
+
+fn require_non_empty(field: &str, value: &str) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if value.trim().is_empty() {
+        warn!("Rejected empty {}", field);
+        return Err(note_error(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_ERROR",
+            format!("{} must not be empty", field),
+        ));
+    }
+    Ok(())
+}
+
+fn encrypt_secret(state: &AppState, secret: &str) -> Result<String, (StatusCode, Json<ErrorResponse>)> {
+    let Some(cipher) = &state.field_cipher else {
+        warn!("Rejected a secret without a configured field encryption key");
+        return Err(note_error(
+            StatusCode::BAD_REQUEST,
+            "ENCRYPTION_NOT_CONFIGURED",
+            "Secrets can only be stored when the server has FIELD_ENCRYPTION_KEY set".to_string(),
+        ));
+    };
+    cipher.encrypt(secret).map_err(|e| {
+        error!("Failed to encrypt account note secret: {}", e);
+        note_error(StatusCode::INTERNAL_SERVER_ERROR, "ENCRYPTION_ERROR", e.to_string())
+    })
+}
+
+async fn find_note(
+    db: &DatabaseConnection,
+    account_id: i32,
+    note_id: i32,
+) -> Result<account_note::Model, (StatusCode, Json<ErrorResponse>)> {
+    account_note::Entity::find_by_id(note_id)
+        .filter(account_note::Column::AccountId.eq(account_id))
+        .one(db)
+        .await
+        .map_err(database_error)?
+        .ok_or_else(|| {
+            warn!("Note {} of account {} not found", note_id, account_id);
+            note_error(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                format!("Note with ID {} not found for account {}", note_id, account_id),
+            )
+        })
+}
+
+fn document_not_found(account_id: i32, document_id: i32) -> (StatusCode, Json<ErrorResponse>) {
+    warn!("Document {} of account {} not found", document_id, account_id);
+    note_error(
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+        format!("Document with ID {} not found for account {}", document_id, account_id),
+    )
+}
+
+/// Add a note to an account
+#[utoipa::path(
+    post,
+    path = "/api/v1/accounts/{account_id}/notes",
+    params(("account_id" = i32, Path, description = "Account ID")),
+    request_body = CreateAccountNoteRequest,
+    responses(
+        (status = 201, description = "Note created", body = ApiResponse<AccountNoteResponse>),
+        (status = 400, description = "Empty title, or a secret without an encryption key", body = ErrorResponse),
+        (status = 403, description = "Account is shared read-only", body = ErrorResponse),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "account-notes"
+)]
+#[instrument(skip(state, request))]
+pub async fn create_account_note(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Path(account_id): Path<i32>,
+    Json(request): Json<CreateAccountNoteRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<AccountNoteResponse>>), (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering create_account_note function");
+    require_non_empty("title", &request.title)?;
+    ensure_account_exists(&state.db, &access, account_id, true).await?;
+    let secret_ciphertext = match request.secret.as_deref().filter(|secret| !secret.is_empty()) {
+        Some(secret) => Some(encrypt_secret(&state, secret)?),
+        None => None,
+    };
+
+    let now = chrono::Local::now().naive_local();
+    let created = account_note::ActiveModel {
+        account_id: Set(account_id),
+        title: Set(request.title),
+        body: Set(request.body),
+        secret_ciphertext: Set(secret_ciphertext),
+        created_at: Set(now),
+        updated_at: Set(now),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await
+    .map_err(database_error)?;
+
+    info!("Created note {} for account {}", created.id, account_id);
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse {
+            data: AccountNoteResponse::new(created, state.field_cipher.as_ref()),
+            message: "Account note created successfully".to_string(),
+            success: true,
+        }),
+    ))
+}
+
+/// List the notes of an account
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{account_id}/notes",
+    params(("account_id" = i32, Path, description = "Account ID")),
+    responses(
+        (status = 200, description = "Notes of the account, most recently updated first", body = ApiResponse<Vec<AccountNoteResponse>>),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "account-notes"
+)]
+#[instrument(skip(state))]
+pub async fn get_account_notes(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Path(account_id): Path<i32>,
+) -> Result<Json<ApiResponse<Vec<AccountNoteResponse>>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_account_notes function");
+    ensure_account_exists(&state.db, &access, account_id, false).await?;
+
+    let notes = account_note::Entity::find()
+        .filter(account_note::Column::AccountId.eq(account_id))
+        .order_by_desc(account_note::Column::UpdatedAt)
+        .order_by_desc(account_note::Column::Id)
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+
+    debug!("Found {} notes for account {}", notes.len(), account_id);
+    Ok(Json(ApiResponse {
+        data: notes
+            .into_iter()
+            .map(|note| AccountNoteResponse::new(note, state.field_cipher.as_ref()))
+            .collect(),
+        message: "Success".to_string(),
+        success: true,
+    }))
+}
+
+/// Update an account note
+#[utoipa::path(
+    put,
+    path = "/api/v1/accounts/{account_id}/notes/{note_id}",
+    params(
+        ("account_id" = i32, Path, description = "Account ID"),
+        ("note_id" = i32, Path, description = "Note ID")
+    ),
+    request_body = UpdateAccountNoteRequest,
+    responses(
+        (status = 200, description = "Note updated", body = ApiResponse<AccountNoteResponse>),
+        (status = 400, description = "Empty title, or a secret without an encryption key", body = ErrorResponse),
+        (status = 403, description = "Account is shared read-only", body = ErrorResponse),
+        (status = 404, description = "Note not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "account-notes"
+)]
+#[instrument(skip(state, request))]
+pub async fn update_account_note(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Path((account_id, note_id)): Path<(i32, i32)>,
+    Json(request): Json<UpdateAccountNoteRequest>,
+) -> Result<Json<ApiResponse<AccountNoteResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering update_account_note function");
+    ensure_account_access(&access, account_id, true)?;
+    let existing = find_note(&state.db, account_id, note_id).await?;
+
+    let mut note: account_note::ActiveModel = existing.into();
+    if let Some(title) = request.title {
+        require_non_empty("title", &title)?;
+        note.title = Set(title);
+    }
+    if let Some(body) = request.body {
+        note.body = Set(body);
+    }
+    match request.secret.as_deref() {
+        Some("") => note.secret_ciphertext = Set(None),
+        Some(secret) => note.secret_ciphertext = Set(Some(encrypt_secret(&state, secret)?)),
+        None => {}
+    }
+    note.updated_at = Set(chrono::Local::now().naive_local());
+    let updated = note.update(&state.db).await.map_err(database_error)?;
+
+    info!("Updated note {} of account {}", note_id, account_id);
+    Ok(Json(ApiResponse {
+        data: AccountNoteResponse::new(updated, state.field_cipher.as_ref()),
+        message: "Account note updated successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Delete an account note
+#[utoipa::path(
+    delete,
+    path = "/api/v1/accounts/{account_id}/notes/{note_id}",
+    params(
+        ("account_id" = i32, Path, description = "Account ID"),
+        ("note_id" = i32, Path, description = "Note ID")
+    ),
+    responses(
+        (status = 204, description = "Note deleted"),
+        (status = 403, description = "Account is shared read-only", body = ErrorResponse),
+        (status = 404, description = "Note not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "account-notes"
+)]
+#[instrument(skip(state))]
+pub async fn delete_account_note(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Path((account_id, note_id)): Path<(i32, i32)>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering delete_account_note function");
+    ensure_account_access(&access, account_id, true)?;
+    let note = find_note(&state.db, account_id, note_id).await?;
+    account_note::Entity::delete_by_id(note.id)
+        .exec(&state.db)
+        .await
+        .map_err(database_error)?;
+
+    info!("Deleted note {} of account {}", note_id, account_id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Attach a document to an account
+///
+/// The file is sent base64 encoded and is limited by the server's request size limit.
+#[utoipa::path(
+    post,
+    path = "/api/v1/accounts/{account_id}/documents",
+    params(("account_id" = i32, Path, description = "Account ID")),
+    request_body = UploadAccountDocumentRequest,
+    responses(
+        (status = 201, description = "Document stored", body = ApiResponse<AccountDocumentResponse>),
+        (status = 400, description = "Empty file name or content that is not valid base64", body = ErrorResponse),
+        (status = 403, description = "Account is shared read-only", body = ErrorResponse),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "account-notes"
+)]
+#[instrument(skip(state, request))]
+pub async fn upload_account_document(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Path(account_id): Path<i32>,
+    Json(request): Json<UploadAccountDocumentRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<AccountDocumentResponse>>), (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering upload_account_document function");
+    require_non_empty("file_name", &request.file_name)?;
+    let content = STANDARD.decode(request.content_base64.trim()).map_err(|e| {
+        warn!("Rejected document with invalid base64 content: {}", e);
+        note_error(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_ERROR",
+            format!("content_base64 is not valid base64: {}", e),
+        )
+    })?;
+    if content.is_empty() {
+        return Err(note_error(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_ERROR",
+            "Document must not be empty".to_string(),
+        ));
+    }
+    ensure_account_exists(&state.db, &access, account_id, true).await?;
+
+    let created = account_document::ActiveModel {
+        account_id: Set(account_id),
+        file_name: Set(request.file_name),
+        content_type: Set(request
+            .content_type
+            .filter(|content_type| !content_type.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_CONTENT_TYPE.to_string())),
+        size_bytes: Set(content.len() as i64),
+        sha256: Set(format!("{:x}", Sha256::digest(&content))),
+        content: Set(content),
+        uploaded_at: Set(chrono::Local::now().naive_local()),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await
+    .map_err(database_error)?;
+
+    info!(
+        "Stored document {} ({} bytes) for account {}",
+        created.id, created.size_bytes, account_id
+    );
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse {
+            data: AccountDocumentResponse {
+                id: created.id,
+                account_id: created.account_id,
+                file_name: created.file_name,
+                content_type: created.content_type,
+                size_bytes: created.size_bytes,
+                sha256: created.sha256,
+                uploaded_at: created.uploaded_at,
+            },
+            message: "Account document stored successfully".to_string(),
+            success: true,
+        }),
+    ))
+}
+
+/// List the documents of an account
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{account_id}/documents",
+    params(("account_id" = i32, Path, description = "Account ID")),
+    responses(
+        (status = 200, description = "Documents of the account, newest first", body = ApiResponse<Vec<AccountDocumentResponse>>),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "account-notes"
+)]
+#[instrument(skip(state))]
+pub async fn get_account_documents(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Path(account_id): Path<i32>,
+) -> Result<Json<ApiResponse<Vec<AccountDocumentResponse>>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_account_documents function");
+    ensure_account_exists(&state.db, &access, account_id, false).await?;
+
+    // Leave the file contents in the database, listing only needs the metadata
+    let documents = account_document::Entity::find()
+        .select_only()
+        .columns([
+            account_document::Column::Id,
+            account_document::Column::AccountId,
+            account_document::Column::FileName,
+            account_document::Column::ContentType,
+            account_document::Column::SizeBytes,
+            account_document::Column::Sha256,
+            account_document::Column::UploadedAt,
+        ])
+        .filter(account_document::Column::AccountId.eq(account_id))
+        .order_by_desc(account_document::Column::UploadedAt)
+        .order_by_desc(account_document::Column::Id)
+        .into_model::<AccountDocumentResponse>()
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+
+    debug!("Found {} documents for account {}", documents.len(), account_id);
+    Ok(Json(ApiResponse {
+        data: documents,
+        message: "Success".to_string(),
+        success: true,
+    }))
+}
+
+/// Download a document
+///
+/// Returns the raw file with its content type, as an attachment.
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{account_id}/documents/{document_id}/download",
+    params(
+        ("account_id" = i32, Path, description = "Account ID"),
+        ("document_id" = i32, Path, description = "Document ID")
+    ),
+    responses(
+        (status = 200, description = "The document's content"),
+        (status = 404, description = "Document not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "account-notes"
+)]
+#[instrument(skip(state))]
+pub async fn download_account_document(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Path((account_id, document_id)): Path<(i32, i32)>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering download_account_document function");
+    ensure_account_access(&access, account_id, false)?;
+    let document = account_document::Entity::find_by_id(document_id)
+        .filter(account_document::Column::AccountId.eq(account_id))
+        .one(&state.db)
+        .await
+        .map_err(database_error)?
+        .ok_or_else(|| document_not_found(account_id, document_id))?;
+
+    info!("Serving document {} of account {}", document_id, account_id);
+    Ok((
+        [
+            (header::CONTENT_TYPE, document.content_type),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"{}\"",
+                    document.file_name.replace(['"', '\\', '\r', '\n'], "_")
+                ),
+            ),
+        ],
+        document.content,
+    )
+        .into_response())
+}
+
+/// Delete a document
+#[utoipa::path(
+    delete,
+    path = "/api/v1/accounts/{account_id}/documents/{document_id}",
+    params(
+        ("account_id" = i32, Path, description = "Account ID"),
+        ("document_id" = i32, Path, description = "Document ID")
+    ),
+    responses(
+        (status = 204, description = "Document deleted"),
+        (status = 403, description = "Account is shared read-only", body = ErrorResponse),
+        (status = 404, description = "Document not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "account-notes"
+)]
+#[instrument(skip(state))]
+pub async fn delete_account_document(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Path((account_id, document_id)): Path<(i32, i32)>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering delete_account_document function");
+    ensure_account_access(&access, account_id, true)?;
+    let result = account_document::Entity::delete_many()
+        .filter(account_document::Column::Id.eq(document_id))
+        .filter(account_document::Column::AccountId.eq(account_id))
+        .exec(&state.db)
+        .await
+        .map_err(database_error)?;
+    if result.rows_affected == 0 {
+        return Err(document_not_found(account_id, document_id));
+    }
+
+    // Purchases keep their warranty dates but lose the receipt
+    one_off_transaction::Entity::update_many()
+        .col_expr(
+            one_off_transaction::Column::ReceiptDocumentId,
+            sea_orm::sea_query::Expr::value(Option::<i32>::None),
+        )
+        .filter(one_off_transaction::Column::ReceiptDocumentId.eq(document_id))
+        .exec(&state.db)
+        .await
+        .map_err(database_error)?;
+
+    info!("Deleted document {} of account {}", document_id, account_id);
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,263 @@
+use crate::helpers::access::AccountAccess;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::NaiveDateTime;
+use model::entities::{account, account_share, user};
+use sea_orm::{ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, Set};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, instrument, trace, warn};
+use utoipa::ToSchema;
+
+/// What a user an account is shared with may do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+pub enum SharePermission {
+    /// See the account, its balances and everything booked on it
+    Read,
+    /// Additionally book, change and delete transactions and balances on it
+    Write,
+}
+
+impl From<account_share::SharePermission> for SharePermission {
+    fn from(permission: account_share::SharePermission) -> Self {
+        match permission {
+            account_share::SharePermission::Read => SharePermission::Read,
+            account_share::SharePermission::Write => SharePermission::Write,
+        }
+    }
+}
+
+impl From<SharePermission> for account_share::SharePermission {
+    fn from(permission: SharePermission) -> Self {
+        match permission {
+            SharePermission::Read => account_share::SharePermission::Read,
+            SharePermission::Write => account_share::SharePermission::Write,
+        }
+    }
+}
+
+/// Request body for sharing an account with a user
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ShareAccountRequest {
+    pub permission: SharePermission,
+}
+
+/// A user an account is shared with
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AccountShareResponse {
+    pub account_id: i32,
+    pub user_id: i32,
+    pub username: String,
+    pub permission: SharePermission,
+    pub created_at: NaiveDateTime,
+}
+
+impl AccountShareResponse {
+    fn new(share: account_share::Model, username: String) -> Self {
+        Self {
+            account_id: share.account_id,
+            user_id: share.user_id,
+            username,
+            permission: share.permission.into(),
+            created_at: share.created_at,
+        }
+    }
+}
+
+fn share_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+fn database_error(e: DbErr) -> (StatusCode, Json<ErrorResponse>) {
+    error!("Database error in account shares: {}", e);
+    share_error(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", e.to_string())
+}
+
+/// List the users an account is shared with
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{account_id}/shares",
+    params(
+        ("account_id" = i32, Path, description = "Account ID"),
+    ),
+    responses(
+        (status = 200, description = "Shares of the account", body = ApiResponse<Vec<AccountShareResponse>>),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "accounts"
+)]
+#[instrument(skip(state))]
+pub async fn get_account_shares(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Path(account_id): Path<i32>,
+) -> Result<Json<ApiResponse<Vec<AccountShareResponse>>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_account_shares function for account_id: {}", account_id);
+
+    if !access.can_access(account_id) {
+        warn!("Account {} is not accessible to user {:?}", account_id, access.user_id());
+        return Err(share_error(
+            StatusCode::NOT_FOUND,
+            "ACCOUNT_NOT_FOUND",
+            format!("Account with id {} not found", account_id),
+        ));
+    }
+
+    let shares = account_share::Entity::find()
+        .filter(account_share::Column::AccountId.eq(account_id))
+        .order_by_asc(account_share::Column::UserId)
+        .find_also_related(user::Entity)
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+
+    debug!("Account {} is shared with {} users", account_id, shares.len());
+    Ok(Json(ApiResponse {
+        data: shares
+            .into_iter()
+            .map(|(share, user)| AccountShareResponse::new(share, user.map(|user| user.username).unwrap_or_default()))
+            .collect(),
+        message: "Account shares retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Share an account with a user
+///
+/// Grants the user read or write access, or changes the permission of an existing
+/// share. Only the owner of the account may share it.
+#[utoipa::path(
+    put,
+    path = "/api/v1/accounts/{account_id}/shares/{user_id}",
+    params(
+        ("account_id" = i32, Path, description = "Account ID"),
+        ("user_id" = i32, Path, description = "User the account is shared with"),
+    ),
+    request_body = ShareAccountRequest,
+    responses(
+        (status = 200, description = "Account shared", body = ApiResponse<AccountShareResponse>),
+        (status = 400, description = "User already owns the account", body = ErrorResponse),
+        (status = 403, description = "Account is only shared with the caller", body = ErrorResponse),
+        (status = 404, description = "Account or user not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "accounts"
+)]
+#[instrument(skip(state))]
+pub async fn share_account(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Path((account_id, user_id)): Path<(i32, i32)>,
+    Json(request): Json<ShareAccountRequest>,
+) -> Result<Json<ApiResponse<AccountShareResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering share_account function for account_id: {}, user_id: {}", account_id, user_id);
+
+    access.ensure_can_manage(account_id)?;
+    let account = account::Entity::find_by_id(account_id)
+        .one(&state.db)
+        .await
+        .map_err(database_error)?
+        .ok_or_else(|| {
+            share_error(
+                StatusCode::NOT_FOUND,
+                "ACCOUNT_NOT_FOUND",
+                format!("Account with id {} not found", account_id),
+            )
+        })?;
+    let shared_with = user::Entity::find_by_id(user_id)
+        .one(&state.db)
+        .await
+        .map_err(database_error)?
+        .ok_or_else(|| {
+            share_error(StatusCode::NOT_FOUND, "USER_NOT_FOUND", format!("User with id {} not found", user_id))
+        })?;
+    if account.owner_id == user_id {
+        warn!("Refusing to share account {} with its owner {}", account_id, user_id);
+        return Err(share_error(
+            StatusCode::BAD_REQUEST,
+            "SHARE_WITH_OWNER",
+            format!("User {} already owns account {}", user_id, account_id),
+        ));
+    }
+
+    let existing = account_share::Entity::find_by_id((account_id, user_id))
+        .one(&state.db)
+        .await
+        .map_err(database_error)?;
+    let share = match existing {
+        Some(existing) => {
+            let mut active: account_share::ActiveModel = existing.into();
+            active.permission = Set(request.permission.into());
+            active.update(&state.db).await.map_err(database_error)?
+        }
+        None => account_share::ActiveModel {
+            account_id: Set(account_id),
+            user_id: Set(user_id),
+            permission: Set(request.permission.into()),
+            created_at: Set(chrono::Utc::now().naive_utc()),
+        }
+        .insert(&state.db)
+        .await
+        .map_err(database_error)?,
+    };
+
+    info!("Shared account {} with user {} ({:?})", account_id, user_id, request.permission);
+    Ok(Json(ApiResponse {
+        data: AccountShareResponse::new(share, shared_with.username),
+        message: "Account shared successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Stop sharing an account with a user
+#[utoipa::path(
+    delete,
+    path = "/api/v1/accounts/{account_id}/shares/{user_id}",
+    params(
+        ("account_id" = i32, Path, description = "Account ID"),
+        ("user_id" = i32, Path, description = "User the account is shared with"),
+    ),
+    responses(
+        (status = 204, description = "Share removed"),
+        (status = 403, description = "Account is only shared with the caller", body = ErrorResponse),
+        (status = 404, description = "Account or share not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "accounts"
+)]
+#[instrument(skip(state))]
+pub async fn unshare_account(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Path((account_id, user_id)): Path<(i32, i32)>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering unshare_account function for account_id: {}, user_id: {}", account_id, user_id);
+
+    access.ensure_can_manage(account_id)?;
+    let removed = account_share::Entity::delete_by_id((account_id, user_id))
+        .exec(&state.db)
+        .await
+        .map_err(database_error)?;
+    if removed.rows_affected == 0 {
+        warn!("Account {} is not shared with user {}", account_id, user_id);
+        return Err(share_error(
+            StatusCode::NOT_FOUND,
+            "SHARE_NOT_FOUND",
+            format!("Account {} is not shared with user {}", account_id, user_id),
+        ));
+    }
+
+    info!("Stopped sharing account {} with user {}", account_id, user_id);
+    Ok(StatusCode::NO_CONTENT)
+}
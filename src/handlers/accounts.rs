@@ -1,12 +1,19 @@
+use crate::helpers::access::AccountAccess;
 use crate::helpers::colors;
+use crate::helpers::timezone::UserTimezone;
+use compute::{account::AccountStateCalculator, account_stats, default_compute};
 use crate::schemas::{ApiResponse, AppState, ErrorResponse};
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
     response::Json,
 };
-use model::entities::{account, user, tag, account_tag, account_allowed_user};
-use sea_orm::{ActiveModelTrait, EntityTrait, Set, DbErr, ColumnTrait, QueryFilter, PaginatorTrait};
+use chrono::NaiveDate;
+use model::entities::{account, account_statistics_exclusion, user, tag, account_tag, account_allowed_user};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Set,
+    TransactionTrait,
+};
 use serde::{Deserialize, Serialize};
 use tracing::{instrument, error, warn, info, debug, trace};
 use utoipa::ToSchema;
@@ -67,6 +74,33 @@ impl From<AccountKind> for account::AccountKind {
     }
 }
 
+/// How the balance between two manual account states is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+pub enum BalanceInterpolation {
+    /// Keep the last state until the next one
+    Step,
+    /// Move in a straight line between states with no transactions in between
+    Linear,
+}
+
+impl From<account::BalanceInterpolation> for BalanceInterpolation {
+    fn from(interpolation: account::BalanceInterpolation) -> Self {
+        match interpolation {
+            account::BalanceInterpolation::Step => BalanceInterpolation::Step,
+            account::BalanceInterpolation::Linear => BalanceInterpolation::Linear,
+        }
+    }
+}
+
+impl From<BalanceInterpolation> for account::BalanceInterpolation {
+    fn from(interpolation: BalanceInterpolation) -> Self {
+        match interpolation {
+            BalanceInterpolation::Step => account::BalanceInterpolation::Step,
+            BalanceInterpolation::Linear => account::BalanceInterpolation::Linear,
+        }
+    }
+}
+
 /// Request body for creating a new account
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct CreateAccountRequest {
@@ -90,6 +124,8 @@ pub struct CreateAccountRequest {
     pub color: Option<String>,
     /// Whether the account is liquid (default based on account kind)
     pub is_liquid: Option<bool>,
+    /// How balances between manual states are drawn (default: Step)
+    pub balance_interpolation: Option<BalanceInterpolation>,
 }
 
 /// Request body for updating an account
@@ -113,6 +149,32 @@ pub struct UpdateAccountRequest {
     pub color: Option<String>,
     /// Whether the account is liquid
     pub is_liquid: Option<bool>,
+    /// How balances between manual states are drawn
+    pub balance_interpolation: Option<BalanceInterpolation>,
+    /// Date ranges left out of statistics and budgets; replaces the current ones when given
+    pub statistics_exclusions: Option<Vec<AccountStatisticsExclusion>>,
+}
+
+/// Date range of an account that statistics and budgets leave out,
+/// e.g. the months an account paid for a one-off house purchase
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, ToSchema)]
+pub struct AccountStatisticsExclusion {
+    /// First excluded day (inclusive)
+    pub start_date: NaiveDate,
+    /// Last excluded day (inclusive)
+    pub end_date: NaiveDate,
+    /// Why the range is excluded
+    pub reason: Option<String>,
+}
+
+impl From<account_statistics_exclusion::Model> for AccountStatisticsExclusion {
+    fn from(model: account_statistics_exclusion::Model) -> Self {
+        Self {
+            start_date: model.start_date,
+            end_date: model.end_date,
+            reason: model.reason,
+        }
+    }
 }
 
 /// Account response model
@@ -129,6 +191,10 @@ pub struct AccountResponse {
     pub target_amount: Option<rust_decimal::Decimal>,
     pub color: Option<String>,
     pub is_liquid: bool,
+    pub balance_interpolation: BalanceInterpolation,
+    /// Date ranges left out of statistics and budgets, only returned for a single account
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statistics_exclusions: Option<Vec<AccountStatisticsExclusion>>,
 }
 
 impl From<account::Model> for AccountResponse {
@@ -145,8 +211,68 @@ impl From<account::Model> for AccountResponse {
             target_amount: model.target_amount,
             color: model.color,
             is_liquid: model.is_liquid,
+            balance_interpolation: model.balance_interpolation.into(),
+            statistics_exclusions: None,
+        }
+    }
+}
+
+/// Statistics exclusion windows of an account, oldest first
+async fn load_statistics_exclusions<C: ConnectionTrait>(
+    db: &C,
+    account_id: i32,
+) -> Result<Vec<AccountStatisticsExclusion>, DbErr> {
+    Ok(account_statistics_exclusion::Entity::find()
+        .filter(account_statistics_exclusion::Column::AccountId.eq(account_id))
+        .order_by_asc(account_statistics_exclusion::Column::StartDate)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(AccountStatisticsExclusion::from)
+        .collect())
+}
+
+/// Replaces the statistics exclusion windows of an account
+async fn replace_statistics_exclusions<C: ConnectionTrait>(
+    db: &C,
+    account_id: i32,
+    exclusions: &[AccountStatisticsExclusion],
+) -> Result<(), DbErr> {
+    account_statistics_exclusion::Entity::delete_many()
+        .filter(account_statistics_exclusion::Column::AccountId.eq(account_id))
+        .exec(db)
+        .await?;
+    for exclusion in exclusions {
+        account_statistics_exclusion::ActiveModel {
+            account_id: Set(account_id),
+            start_date: Set(exclusion.start_date),
+            end_date: Set(exclusion.end_date),
+            reason: Set(exclusion.reason.clone()),
+            ..Default::default()
         }
+        .insert(db)
+        .await?;
     }
+    Ok(())
+}
+
+/// Reject a savings-goal account that has no positive target amount.
+fn ensure_target_amount(
+    account_kind: &account::AccountKind,
+    target_amount: Option<rust_decimal::Decimal>,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if account_kind.requires_target_amount() && !target_amount.is_some_and(|target| target > rust_decimal::Decimal::ZERO) {
+        warn!("Rejected {:?} account without a positive target_amount", account_kind);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: format!("{:?} accounts require a positive target_amount", account_kind),
+                code: "TARGET_AMOUNT_REQUIRED".to_string(),
+                success: false,
+            }),
+        ));
+    }
+    Ok(())
 }
 
 /// Create a new account
@@ -157,19 +283,33 @@ impl From<account::Model> for AccountResponse {
     request_body = CreateAccountRequest,
     responses(
         (status = 201, description = "Account created successfully", body = ApiResponse<AccountResponse>),
-        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 400, description = "Invalid request, missing target amount for a goal account or invalid exclusion range", body = ErrorResponse),
+        (status = 403, description = "Accounts can only be created for the authenticated user", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
 #[instrument]
 pub async fn create_account(
     State(state): State<AppState>,
+    access: AccountAccess,
     Json(request): Json<CreateAccountRequest>,
 ) -> Result<(StatusCode, Json<ApiResponse<AccountResponse>>), (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering create_account function");
     debug!("Creating account with name: {}, currency: {}, owner_id: {}", 
            request.name, request.currency_code, request.owner_id);
 
+    if access.user_id().is_some_and(|user_id| user_id != request.owner_id) {
+        warn!("User {:?} attempted to create an account for owner {}", access.user_id(), request.owner_id);
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse {
+                error: "Accounts can only be created for the authenticated user".to_string(),
+                code: "OWNER_FORBIDDEN".to_string(),
+                success: false,
+            }),
+        ));
+    }
+
     // Validate that the owner exists
     trace!("Validating owner_id: {}", request.owner_id);
     match user::Entity::find_by_id(request.owner_id).one(&state.db).await {
@@ -208,6 +348,7 @@ pub async fn create_account(
     };
 
     let account_kind: account::AccountKind = request.account_kind.unwrap_or(AccountKind::RealAccount).into();
+    ensure_target_amount(&account_kind, request.target_amount)?;
     let is_liquid = request.is_liquid.unwrap_or_else(|| account_kind.default_is_liquid());
 
     let new_account = account::ActiveModel {
@@ -221,6 +362,7 @@ pub async fn create_account(
         target_amount: Set(request.target_amount),
         color: Set(assigned_color),
         is_liquid: Set(is_liquid),
+        balance_interpolation: Set(request.balance_interpolation.unwrap_or(BalanceInterpolation::Step).into()),
         ..Default::default()
     };
 
@@ -295,6 +437,7 @@ pub struct GetAccountsQuery {
 #[instrument]
 pub async fn get_accounts(
     State(state): State<AppState>,
+    access: AccountAccess,
     Query(query): Query<GetAccountsQuery>,
 ) -> Result<Json<ApiResponse<Vec<AccountResponse>>>, StatusCode> {
     trace!("Entering get_accounts function with include_ignored={}", query.include_ignored);
@@ -308,6 +451,7 @@ pub async fn get_accounts(
             let filtered_accounts: Vec<AccountResponse> = accounts
                 .into_iter()
                 .filter(|a| query.include_ignored || a.include_in_statistics)
+                .filter(|a| access.can_access(a.id))
                 .map(AccountResponse::from)
                 .collect();
 
@@ -354,29 +498,41 @@ pub struct GetAccountQuery {
 pub async fn get_account(
     Path(account_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
     Query(query): Query<GetAccountQuery>,
 ) -> Result<Json<ApiResponse<AccountResponse>>, StatusCode> {
     trace!("Entering get_account function for account_id: {}, include_ignored={}", account_id, query.include_ignored);
     debug!("Fetching account with ID: {}", account_id);
 
     match account::Entity::find_by_id(account_id).one(&state.db).await {
-        Ok(Some(account_model)) => {
+        Ok(Some(account_model)) if access.can_access(account_model.id) => {
             // Check if the account should be filtered
             if !query.include_ignored && !account_model.include_in_statistics {
                 warn!("Account with ID {} is excluded from statistics and include_ignored=false", account_id);
                 return Err(StatusCode::NOT_FOUND);
             }
 
+            let statistics_exclusions = match load_statistics_exclusions(&state.db, account_id).await {
+                Ok(exclusions) => exclusions,
+                Err(db_error) => {
+                    error!("Failed to load statistics exclusions of account {}: {}", account_id, db_error);
+                    return Err(StatusCode::INTERNAL_SERVER_ERROR);
+                }
+            };
+
             info!("Successfully retrieved account with ID: {}, name: {}",
                   account_model.id, account_model.name);
             let response = ApiResponse {
-                data: AccountResponse::from(account_model),
+                data: AccountResponse {
+                    statistics_exclusions: Some(statistics_exclusions),
+                    ..AccountResponse::from(account_model)
+                },
                 message: "Account retrieved successfully".to_string(),
                 success: true,
             };
             Ok(Json(response))
         }
-        Ok(None) => {
+        Ok(_) => {
             warn!("Account with ID {} not found", account_id);
             Err(StatusCode::NOT_FOUND)
         }
@@ -387,6 +543,134 @@ pub async fn get_account(
     }
 }
 
+/// A kind-specific rule an account currently violates
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountValidationIssue {
+    /// Machine readable rule code (e.g. "TARGET_AMOUNT_REQUIRED", "LIABILITY_POSITIVE_BALANCE")
+    pub code: String,
+    pub message: String,
+}
+
+/// Result of checking an account against the rules of its kind
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountValidationResponse {
+    pub account_id: i32,
+    pub account_kind: AccountKind,
+    /// Balance as of today, as computed by the forecasting engine
+    pub current_balance: Option<rust_decimal::Decimal>,
+    /// Whether imported (bank statement) transactions may target this account
+    pub accepts_imports: bool,
+    pub issues: Vec<AccountValidationIssue>,
+}
+
+/// Check an account against its kind-specific rules
+///
+/// Hard rules (a goal needs a target, envelopes can't receive imports) are enforced when
+/// writing; this reports what still needs attention, most notably liability accounts
+/// whose computed balance has turned positive.
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{account_id}/validation",
+    tag = "accounts",
+    params(
+        ("account_id" = i32, Path, description = "Account ID"),
+    ),
+    responses(
+        (status = 200, description = "Account validated", body = ApiResponse<AccountValidationResponse>),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument]
+pub async fn validate_account(
+    Path(account_id): Path<i32>,
+    State(state): State<AppState>,
+    access: AccountAccess,
+    timezone: UserTimezone,
+) -> Result<Json<ApiResponse<AccountValidationResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering validate_account function for account_id: {}", account_id);
+
+    let internal_error = |message: &str| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: message.to_string(),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    };
+
+    let account_model = match account::Entity::find_by_id(account_id).one(&state.db).await {
+        Ok(Some(account_model)) if access.can_access(account_model.id) => account_model,
+        Ok(_) => {
+            warn!("Account with ID {} not found for validation", account_id);
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: format!("Account with id {} not found", account_id),
+                    code: "ACCOUNT_NOT_FOUND".to_string(),
+                    success: false,
+                }),
+            ));
+        }
+        Err(db_error) => {
+            error!("Failed to load account {} for validation: {}", account_id, db_error);
+            return Err(internal_error("Failed to load account"));
+        }
+    };
+
+    let today = timezone.today();
+    let compute = default_compute(Some(today));
+    let current_balance = account_stats::state_at_date(
+        &compute as &dyn AccountStateCalculator,
+        &state.db,
+        std::slice::from_ref(&account_model),
+        today,
+    )
+    .await
+    .map_err(|e| {
+        error!("Failed to compute balance of account {}: {}", account_id, e);
+        internal_error("Failed to compute account balance")
+    })?
+    .into_iter()
+    .find(|stats| stats.account_id == account_id)
+    .and_then(|stats| stats.end_of_period_state);
+
+    let mut issues = Vec::new();
+    if let Err((_, Json(rule))) = ensure_target_amount(&account_model.account_kind, account_model.target_amount) {
+        issues.push(AccountValidationIssue {
+            code: rule.code,
+            message: rule.error,
+        });
+    }
+    if account_model.account_kind.is_liability() {
+        if let Some(balance) = current_balance.filter(|balance| *balance > rust_decimal::Decimal::ZERO) {
+            warn!("Liability account {} has a positive balance of {}", account_id, balance);
+            issues.push(AccountValidationIssue {
+                code: "LIABILITY_POSITIVE_BALANCE".to_string(),
+                message: format!(
+                    "{:?} accounts track money owed and should not have a positive balance (currently {})",
+                    account_model.account_kind, balance
+                ),
+            });
+        }
+    }
+
+    info!("Account {} validated with {} issue(s)", account_id, issues.len());
+    Ok(Json(ApiResponse {
+        data: AccountValidationResponse {
+            account_id,
+            accepts_imports: !account_model.account_kind.is_virtual(),
+            account_kind: account_model.account_kind.into(),
+            current_balance,
+            issues,
+        },
+        message: "Account validated successfully".to_string(),
+        success: true,
+    }))
+}
+
 /// Update an account
 #[utoipa::path(
     put,
@@ -398,8 +682,9 @@ pub async fn get_account(
     request_body = UpdateAccountRequest,
     responses(
         (status = 200, description = "Account updated successfully", body = ApiResponse<AccountResponse>),
+        (status = 403, description = "Account is only shared with the caller", body = ErrorResponse),
         (status = 404, description = "Account not found", body = ErrorResponse),
-        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 400, description = "Invalid request, missing target amount for a goal account or invalid exclusion range", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
@@ -407,10 +692,23 @@ pub async fn get_account(
 pub async fn update_account(
     Path(account_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
     Json(request): Json<UpdateAccountRequest>,
-) -> Result<Json<ApiResponse<AccountResponse>>, StatusCode> {
+) -> Result<Json<ApiResponse<AccountResponse>>, (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering update_account function for account_id: {}", account_id);
     debug!("Updating account with ID: {}", account_id);
+    access.ensure_can_manage(account_id)?;
+
+    let update_database_error = || {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Internal server error while updating account".to_string(),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    };
 
     // First, find the existing account
     trace!("Looking up existing account with ID: {}", account_id);
@@ -421,14 +719,47 @@ pub async fn update_account(
         }
         Ok(None) => {
             warn!("Account with ID {} not found for update", account_id);
-            return Err(StatusCode::NOT_FOUND);
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: format!("Account with id {} not found", account_id),
+                    code: "ACCOUNT_NOT_FOUND".to_string(),
+                    success: false,
+                }),
+            ));
         }
         Err(db_error) => {
             error!("Failed to lookup account with ID {} for update: {}", account_id, db_error);
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            return Err(update_database_error());
         }
     };
 
+    // Validate the kind-specific rules against the account as it will look after the update
+    let effective_kind: account::AccountKind = match request.account_kind {
+        Some(kind) => kind.into(),
+        None => existing_account.account_kind.clone(),
+    };
+    ensure_target_amount(&effective_kind, request.target_amount.or(existing_account.target_amount))?;
+    if let Some(exclusion) = request
+        .statistics_exclusions
+        .iter()
+        .flatten()
+        .find(|exclusion| exclusion.start_date > exclusion.end_date)
+    {
+        warn!("Rejected statistics exclusion from {} to {}", exclusion.start_date, exclusion.end_date);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: format!(
+                    "Statistics exclusion start_date {} is after its end_date {}",
+                    exclusion.start_date, exclusion.end_date
+                ),
+                code: "INVALID_DATE_RANGE".to_string(),
+                success: false,
+            }),
+        ));
+    }
+
     // Create active model for update
     let mut account_active: account::ActiveModel = existing_account.into();
     let mut updated_fields = Vec::new();
@@ -479,6 +810,14 @@ pub async fn update_account(
         account_active.is_liquid = Set(is_liquid);
         updated_fields.push(format!("is_liquid: {}", is_liquid));
     }
+    if let Some(balance_interpolation) = request.balance_interpolation {
+        debug!("Updating account balance_interpolation to: {:?}", balance_interpolation);
+        account_active.balance_interpolation = Set(balance_interpolation.into());
+        updated_fields.push(format!("balance_interpolation: {:?}", balance_interpolation));
+    }
+    if let Some(exclusions) = &request.statistics_exclusions {
+        updated_fields.push(format!("statistics_exclusions: {}", exclusions.len()));
+    }
 
     if updated_fields.is_empty() {
         debug!("No fields to update for account ID: {}", account_id);
@@ -487,12 +826,27 @@ pub async fn update_account(
     }
 
     trace!("Attempting to update account in database");
-    match account_active.update(&state.db).await {
-        Ok(updated_account) => {
+    let new_exclusions = request.statistics_exclusions.as_deref();
+    let result: Result<_, DbErr> = async {
+        let txn = state.db.begin().await?;
+        let updated_account = account_active.update(&txn).await?;
+        if let Some(exclusions) = new_exclusions {
+            replace_statistics_exclusions(&txn, account_id, exclusions).await?;
+        }
+        let statistics_exclusions = load_statistics_exclusions(&txn, account_id).await?;
+        txn.commit().await?;
+        Ok((updated_account, statistics_exclusions))
+    }
+    .await;
+    match result {
+        Ok((updated_account, statistics_exclusions)) => {
             info!("Account with ID {} updated successfully. Updated fields: {}", 
                   account_id, if updated_fields.is_empty() { "none".to_string() } else { updated_fields.join(", ") });
             let response = ApiResponse {
-                data: AccountResponse::from(updated_account),
+                data: AccountResponse {
+                    statistics_exclusions: Some(statistics_exclusions),
+                    ..AccountResponse::from(updated_account)
+                },
                 message: "Account updated successfully".to_string(),
                 success: true,
             };
@@ -500,7 +854,7 @@ pub async fn update_account(
         }
         Err(db_error) => {
             error!("Failed to update account with ID {}: {}", account_id, db_error);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(update_database_error())
         }
     }
 }
@@ -515,6 +869,7 @@ pub async fn update_account(
     ),
     responses(
         (status = 200, description = "Account deleted successfully", body = ApiResponse<String>),
+        (status = 403, description = "Account is only shared with the caller", body = ErrorResponse),
         (status = 404, description = "Account not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
@@ -523,9 +878,11 @@ pub async fn update_account(
 pub async fn delete_account(
     Path(account_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
 ) -> Result<Json<ApiResponse<String>>, StatusCode> {
     trace!("Entering delete_account function for account_id: {}", account_id);
     debug!("Attempting to delete account with ID: {}", account_id);
+    access.ensure_can_manage(account_id).map_err(|(status, _)| status)?;
 
     match account::Entity::delete_by_id(account_id).exec(&state.db).await {
         Ok(delete_result) => {
@@ -581,6 +938,7 @@ pub struct AllowedUserLinkResponse {
     responses(
         (status = 200, description = "Tag linked to account", body = ApiResponse<AccountTagLinkResponse>),
         (status = 404, description = "Account or Tag not found", body = ErrorResponse),
+        (status = 403, description = "Account is only shared with the caller", body = ErrorResponse),
         (status = 409, description = "Link already exists", body = ErrorResponse),
         (status = 500, description = "Database error", body = ErrorResponse),
     )
@@ -588,9 +946,11 @@ pub struct AllowedUserLinkResponse {
 #[instrument]
 pub async fn link_account_tag(
     State(state): State<AppState>,
+    access: AccountAccess,
     Path((account_id, tag_id)): Path<(i32, i32)>,
 ) -> Result<(StatusCode, Json<ApiResponse<AccountTagLinkResponse>>), (StatusCode, Json<ErrorResponse>)> {
     trace!(account_id, tag_id, "link_account_tag");
+    access.ensure_can_manage(account_id)?;
     // Validate existence
     if account::Entity::find_by_id(account_id).one(&state.db).await.map_err(|e| {
         error!(%e, "DB error while checking account");
@@ -642,6 +1002,7 @@ pub async fn link_account_tag(
     ),
     responses(
         (status = 200, description = "Tag unlinked from account", body = ApiResponse<AccountTagLinkResponse>),
+        (status = 403, description = "Account is only shared with the caller", body = ErrorResponse),
         (status = 404, description = "Account or Tag not found", body = ErrorResponse),
         (status = 500, description = "Database error", body = ErrorResponse),
     )
@@ -649,9 +1010,11 @@ pub async fn link_account_tag(
 #[instrument]
 pub async fn unlink_account_tag(
     State(state): State<AppState>,
+    access: AccountAccess,
     Path((account_id, tag_id)): Path<(i32, i32)>,
 ) -> Result<(StatusCode, Json<ApiResponse<AccountTagLinkResponse>>), (StatusCode, Json<ErrorResponse>)> {
     trace!(account_id, tag_id, "unlink_account_tag");
+    access.ensure_can_manage(account_id)?;
     // Validate account exists
     let account_exists = account::Entity::find_by_id(account_id)
         .one(&state.db)
@@ -700,8 +1063,12 @@ pub async fn unlink_account_tag(
 #[instrument]
 pub async fn get_account_tags(
     State(state): State<AppState>,
+    access: AccountAccess,
     Path(account_id): Path<i32>,
 ) -> Result<(StatusCode, Json<ApiResponse<Vec<TagDto>>>), (StatusCode, Json<ErrorResponse>)> {
+    if !access.can_access(account_id) {
+        return Err((StatusCode::NOT_FOUND, Json(ErrorResponse{ error: "Account not found".into(), code: "NOT_FOUND".into(), success: false })));
+    }
     // Ensure account exists
     if account::Entity::find_by_id(account_id).one(&state.db).await.map_err(|e| {
         error!(%e, "DB error while checking account");
@@ -751,6 +1118,7 @@ pub async fn get_account_tags(
     responses(
         (status = 200, description = "User granted access", body = ApiResponse<AllowedUserLinkResponse>),
         (status = 404, description = "Account or User not found", body = ErrorResponse),
+        (status = 403, description = "Account is only shared with the caller", body = ErrorResponse),
         (status = 409, description = "Access already granted", body = ErrorResponse),
         (status = 500, description = "Database error", body = ErrorResponse),
     )
@@ -758,8 +1126,10 @@ pub async fn get_account_tags(
 #[instrument]
 pub async fn link_account_allowed_user(
     State(state): State<AppState>,
+    access: AccountAccess,
     Path((account_id, user_id)): Path<(i32, i32)>,
 ) -> Result<(StatusCode, Json<ApiResponse<AllowedUserLinkResponse>>), (StatusCode, Json<ErrorResponse>)> {
+    access.ensure_can_manage(account_id)?;
     // Validate existence
     if account::Entity::find_by_id(account_id).one(&state.db).await.map_err(|e| {
         error!(%e, "DB error");
@@ -797,6 +1167,7 @@ pub async fn link_account_allowed_user(
     ),
     responses(
         (status = 200, description = "User access revoked", body = ApiResponse<AllowedUserLinkResponse>),
+        (status = 403, description = "Account is only shared with the caller", body = ErrorResponse),
         (status = 404, description = "Account or User not found", body = ErrorResponse),
         (status = 500, description = "Database error", body = ErrorResponse),
     )
@@ -804,8 +1175,10 @@ pub async fn link_account_allowed_user(
 #[instrument]
 pub async fn unlink_account_allowed_user(
     State(state): State<AppState>,
+    access: AccountAccess,
     Path((account_id, user_id)): Path<(i32, i32)>,
 ) -> Result<(StatusCode, Json<ApiResponse<AllowedUserLinkResponse>>), (StatusCode, Json<ErrorResponse>)> {
+    access.ensure_can_manage(account_id)?;
     // Validate account & user exist
     if account::Entity::find_by_id(account_id).one(&state.db).await.map_err(|e| {
         error!(%e, "DB error");
@@ -841,8 +1214,12 @@ pub async fn unlink_account_allowed_user(
 #[instrument]
 pub async fn get_account_allowed_users(
     State(state): State<AppState>,
+    access: AccountAccess,
     Path(account_id): Path<i32>,
 ) -> Result<(StatusCode, Json<ApiResponse<Vec<UserDto>>>), (StatusCode, Json<ErrorResponse>)> {
+    if !access.can_access(account_id) {
+        return Err((StatusCode::NOT_FOUND, Json(ErrorResponse{ error: "Account not found".into(), code: "NOT_FOUND".into(), success: false })));
+    }
     if account::Entity::find_by_id(account_id).one(&state.db).await.map_err(|e| {
         error!(%e, "DB error while checking account");
         (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse{ error: "Database error".into(), code: "DATABASE_ERROR".into(), success: false }))
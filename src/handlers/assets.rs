@@ -0,0 +1,638 @@
+use crate::handlers::double_entry::ensure_double_entry;
+use crate::handlers::periods::{ensure_dates_unlocked, PeriodLockOverride};
+use crate::helpers::access::{account_forbidden, account_read_only, AccountAccess};
+use crate::helpers::auth::AuthenticatedUser;
+use crate::helpers::timezone::UserTimezone;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::NaiveDate;
+use compute::assets::{book_value, gain_on_disposal, value_on};
+use model::entities::{asset, one_off_transaction};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, Set,
+    TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, instrument, trace, warn};
+use utoipa::ToSchema;
+
+/// Longest useful life in months, a century
+const MAX_USEFUL_LIFE_MONTHS: i32 = 1200;
+
+/// How an asset loses value over its useful life
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "PascalCase")]
+pub enum DepreciationMethod {
+    /// Keeps its purchase price
+    None,
+    /// Loses the same amount every month
+    StraightLine,
+    /// Loses twice the straight-line rate of its remaining value every month
+    DoubleDeclining,
+}
+
+impl From<asset::DepreciationMethod> for DepreciationMethod {
+    fn from(method: asset::DepreciationMethod) -> Self {
+        match method {
+            asset::DepreciationMethod::None => DepreciationMethod::None,
+            asset::DepreciationMethod::StraightLine => DepreciationMethod::StraightLine,
+            asset::DepreciationMethod::DoubleDeclining => DepreciationMethod::DoubleDeclining,
+        }
+    }
+}
+
+impl From<DepreciationMethod> for asset::DepreciationMethod {
+    fn from(method: DepreciationMethod) -> Self {
+        match method {
+            DepreciationMethod::None => asset::DepreciationMethod::None,
+            DepreciationMethod::StraightLine => asset::DepreciationMethod::StraightLine,
+            DepreciationMethod::DoubleDeclining => asset::DepreciationMethod::DoubleDeclining,
+        }
+    }
+}
+
+/// Request body for adding an asset
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateAssetRequest {
+    pub name: String,
+    pub description: Option<String>,
+    /// Account the asset was paid from
+    pub account_id: i32,
+    /// Transaction the asset was bought with; fills in the purchase date and price when they are left out
+    pub purchase_transaction_id: Option<i32>,
+    pub purchase_date: Option<NaiveDate>,
+    pub purchase_price: Option<Decimal>,
+    /// Value left at the end of the useful life (default: 0)
+    pub salvage_value: Option<Decimal>,
+    /// Default: StraightLine
+    pub depreciation_method: Option<DepreciationMethod>,
+    pub useful_life_months: i32,
+}
+
+/// Request body for updating an asset
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UpdateAssetRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub purchase_date: Option<NaiveDate>,
+    pub purchase_price: Option<Decimal>,
+    pub salvage_value: Option<Decimal>,
+    pub depreciation_method: Option<DepreciationMethod>,
+    pub useful_life_months: Option<i32>,
+}
+
+/// Request body for selling or throwing away an asset
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct DisposeAssetRequest {
+    pub date: NaiveDate,
+    /// Proceeds of the sale; zero or empty when the asset was thrown away
+    pub sale_price: Option<Decimal>,
+    /// Account receiving the proceeds (default: the account the asset was paid from)
+    pub account_id: Option<i32>,
+    /// Category of the booked proceeds
+    pub category_id: Option<i32>,
+}
+
+/// Asset response model
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AssetResponse {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub account_id: i32,
+    pub purchase_date: NaiveDate,
+    pub purchase_price: Decimal,
+    pub salvage_value: Decimal,
+    pub depreciation_method: DepreciationMethod,
+    pub useful_life_months: i32,
+    pub purchase_transaction_id: Option<i32>,
+    pub disposal_date: Option<NaiveDate>,
+    pub sale_price: Option<Decimal>,
+    pub disposal_transaction_id: Option<i32>,
+    /// Value the asset adds to the net worth today; zero once disposed of
+    pub current_value: Decimal,
+    /// Sale price minus the depreciated value on the disposal date; empty while held
+    pub gain_on_disposal: Option<Decimal>,
+}
+
+impl AssetResponse {
+    fn new(model: asset::Model, today: NaiveDate) -> Self {
+        let current_value = value_on(&model, today);
+        let gain = model
+            .disposal_date
+            .map(|date| gain_on_disposal(&model, date, model.sale_price.unwrap_or(Decimal::ZERO)));
+        Self {
+            id: model.id,
+            name: model.name,
+            description: model.description,
+            account_id: model.account_id,
+            purchase_date: model.purchase_date,
+            purchase_price: model.purchase_price,
+            salvage_value: model.salvage_value,
+            depreciation_method: model.depreciation_method.into(),
+            useful_life_months: model.useful_life_months,
+            purchase_transaction_id: model.purchase_transaction_id,
+            disposal_date: model.disposal_date,
+            sale_price: model.sale_price,
+            disposal_transaction_id: model.disposal_transaction_id,
+            current_value,
+            gain_on_disposal: gain,
+        }
+    }
+}
+
+fn asset_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+fn database_error(e: DbErr) -> (StatusCode, Json<ErrorResponse>) {
+    error!("Database error in assets: {}", e);
+    asset_error(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", e.to_string())
+}
+
+fn not_found(asset_id: i32) -> (StatusCode, Json<ErrorResponse>) {
+    warn!("Asset {} not found", asset_id);
+    asset_error(
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+        format!("Asset with ID {} not found", asset_id),
+    )
+}
+
+/// Rejects prices, lives and salvage values an asset can't have.
+fn validate_terms(asset: &asset::Model) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    let problem = if asset.name.trim().is_empty() {
+        Some("name must not be empty".to_string())
+    } else if asset.purchase_price < Decimal::ZERO {
+        Some("purchase_price must not be negative".to_string())
+    } else if asset.salvage_value < Decimal::ZERO || asset.salvage_value > asset.purchase_price {
+        Some("salvage_value must be between zero and the purchase price".to_string())
+    } else if !(1..=MAX_USEFUL_LIFE_MONTHS).contains(&asset.useful_life_months) {
+        Some(format!("useful_life_months must be 1-{}", MAX_USEFUL_LIFE_MONTHS))
+    } else if asset.disposal_date.is_some_and(|date| date < asset.purchase_date) {
+        Some("the asset can't be disposed of before it was bought".to_string())
+    } else {
+        None
+    };
+    match problem {
+        Some(problem) => {
+            warn!("Rejected asset terms: {}", problem);
+            Err(asset_error(StatusCode::BAD_REQUEST, "VALIDATION_ERROR", problem))
+        }
+        None => Ok(()),
+    }
+}
+
+/// The asset, if the caller may see the account it was paid from.
+async fn find_asset(
+    db: &DatabaseConnection,
+    access: &AccountAccess,
+    asset_id: i32,
+) -> Result<asset::Model, (StatusCode, Json<ErrorResponse>)> {
+    asset::Entity::find_by_id(asset_id)
+        .one(db)
+        .await
+        .map_err(database_error)?
+        .filter(|asset| access.can_access(asset.account_id))
+        .ok_or_else(|| not_found(asset_id))
+}
+
+fn ensure_writable(access: &AccountAccess, account_id: i32) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if access.can_write(account_id) {
+        return Ok(());
+    }
+    warn!("Account {} is not writable for user {:?}", account_id, access.user_id());
+    if access.can_access(account_id) {
+        Err(account_read_only(account_id))
+    } else {
+        Err(account_forbidden(account_id))
+    }
+}
+
+/// Add an asset
+///
+/// Records a household item of lasting value. When `purchase_transaction_id` is given,
+/// the purchase date and price default to that transaction's date and (negated) amount.
+#[utoipa::path(
+    post,
+    path = "/api/v1/assets",
+    request_body = CreateAssetRequest,
+    responses(
+        (status = 201, description = "Asset created", body = ApiResponse<AssetResponse>),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "No write access to the account", body = ErrorResponse),
+        (status = 404, description = "Purchase transaction not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "assets"
+)]
+#[instrument(skip(state))]
+pub async fn create_asset(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    timezone: UserTimezone,
+    Json(request): Json<CreateAssetRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<AssetResponse>>), (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering create_asset function");
+    debug!("Creating asset: {:?}", request);
+
+    ensure_writable(&access, request.account_id)?;
+
+    let purchase = match request.purchase_transaction_id {
+        Some(transaction_id) => Some(
+            one_off_transaction::Entity::find_by_id(transaction_id)
+                .one(&state.db)
+                .await
+                .map_err(database_error)?
+                .filter(|transaction| access.can_access_any(transaction.target_account_id, transaction.source_account_id))
+                .ok_or_else(|| {
+                    warn!("Purchase transaction {} not found", transaction_id);
+                    asset_error(
+                        StatusCode::NOT_FOUND,
+                        "NOT_FOUND",
+                        format!("Transaction with id {} not found", transaction_id),
+                    )
+                })?,
+        ),
+        None => None,
+    };
+
+    let (Some(purchase_date), Some(purchase_price)) = (
+        request.purchase_date.or(purchase.as_ref().map(|transaction| transaction.date)),
+        request
+            .purchase_price
+            .or(purchase.as_ref().map(|transaction| transaction.amount.abs())),
+    ) else {
+        warn!("Rejected asset without purchase date or price");
+        return Err(asset_error(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_ERROR",
+            "purchase_date and purchase_price are required without a purchase transaction".to_string(),
+        ));
+    };
+
+    let terms = asset::Model {
+        id: 0,
+        name: request.name.trim().to_string(),
+        description: request.description,
+        account_id: request.account_id,
+        purchase_date,
+        purchase_price,
+        salvage_value: request.salvage_value.unwrap_or(Decimal::ZERO),
+        depreciation_method: request
+            .depreciation_method
+            .unwrap_or(DepreciationMethod::StraightLine)
+            .into(),
+        useful_life_months: request.useful_life_months,
+        purchase_transaction_id: request.purchase_transaction_id,
+        disposal_date: None,
+        sale_price: None,
+        disposal_transaction_id: None,
+    };
+    validate_terms(&terms)?;
+
+    let created = asset::ActiveModel {
+        name: Set(terms.name),
+        description: Set(terms.description),
+        account_id: Set(terms.account_id),
+        purchase_date: Set(terms.purchase_date),
+        purchase_price: Set(terms.purchase_price),
+        salvage_value: Set(terms.salvage_value),
+        depreciation_method: Set(terms.depreciation_method),
+        useful_life_months: Set(terms.useful_life_months),
+        purchase_transaction_id: Set(terms.purchase_transaction_id),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await
+    .map_err(database_error)?;
+
+    info!("Asset created: id={}, name={}", created.id, created.name);
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse {
+            data: AssetResponse::new(created, timezone.today()),
+            message: "Asset created successfully".to_string(),
+            success: true,
+        }),
+    ))
+}
+
+/// List assets
+///
+/// Assets paid from accounts the caller can see, held ones first.
+#[utoipa::path(
+    get,
+    path = "/api/v1/assets",
+    responses(
+        (status = 200, description = "List of assets", body = ApiResponse<Vec<AssetResponse>>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "assets"
+)]
+#[instrument(skip(state))]
+pub async fn get_assets(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    timezone: UserTimezone,
+) -> Result<Json<ApiResponse<Vec<AssetResponse>>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_assets function");
+
+    let mut assets = access
+        .filter(asset::Entity::find(), [asset::Column::AccountId])
+        .order_by_asc(asset::Column::PurchaseDate)
+        .order_by_asc(asset::Column::Id)
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+    assets.sort_by_key(|asset| asset.disposal_date.is_some());
+
+    let today = timezone.today();
+    Ok(Json(ApiResponse {
+        data: assets.into_iter().map(|asset| AssetResponse::new(asset, today)).collect(),
+        message: "Assets retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Get an asset
+#[utoipa::path(
+    get,
+    path = "/api/v1/assets/{asset_id}",
+    params(("asset_id" = i32, Path, description = "Asset ID")),
+    responses(
+        (status = 200, description = "Asset", body = ApiResponse<AssetResponse>),
+        (status = 404, description = "Asset not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "assets"
+)]
+#[instrument(skip(state))]
+pub async fn get_asset(
+    State(state): State<AppState>,
+    Path(asset_id): Path<i32>,
+    access: AccountAccess,
+    timezone: UserTimezone,
+) -> Result<Json<ApiResponse<AssetResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_asset function");
+
+    let asset = find_asset(&state.db, &access, asset_id).await?;
+    Ok(Json(ApiResponse {
+        data: AssetResponse::new(asset, timezone.today()),
+        message: "Asset retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Update an asset
+#[utoipa::path(
+    put,
+    path = "/api/v1/assets/{asset_id}",
+    params(("asset_id" = i32, Path, description = "Asset ID")),
+    request_body = UpdateAssetRequest,
+    responses(
+        (status = 200, description = "Asset updated", body = ApiResponse<AssetResponse>),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "No write access to the account", body = ErrorResponse),
+        (status = 404, description = "Asset not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "assets"
+)]
+#[instrument(skip(state))]
+pub async fn update_asset(
+    State(state): State<AppState>,
+    Path(asset_id): Path<i32>,
+    access: AccountAccess,
+    timezone: UserTimezone,
+    Json(request): Json<UpdateAssetRequest>,
+) -> Result<Json<ApiResponse<AssetResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering update_asset function");
+    debug!("Updating asset {}: {:?}", asset_id, request);
+
+    let existing = find_asset(&state.db, &access, asset_id).await?;
+    ensure_writable(&access, existing.account_id)?;
+
+    let mut terms = existing.clone();
+    if let Some(name) = request.name {
+        terms.name = name.trim().to_string();
+    }
+    if let Some(description) = request.description {
+        terms.description = Some(description).filter(|description| !description.trim().is_empty());
+    }
+    if let Some(purchase_date) = request.purchase_date {
+        terms.purchase_date = purchase_date;
+    }
+    if let Some(purchase_price) = request.purchase_price {
+        terms.purchase_price = purchase_price;
+    }
+    if let Some(salvage_value) = request.salvage_value {
+        terms.salvage_value = salvage_value;
+    }
+    if let Some(method) = request.depreciation_method {
+        terms.depreciation_method = method.into();
+    }
+    if let Some(useful_life_months) = request.useful_life_months {
+        terms.useful_life_months = useful_life_months;
+    }
+    validate_terms(&terms)?;
+
+    let mut model = existing.into_active_model();
+    model.name = Set(terms.name);
+    model.description = Set(terms.description);
+    model.purchase_date = Set(terms.purchase_date);
+    model.purchase_price = Set(terms.purchase_price);
+    model.salvage_value = Set(terms.salvage_value);
+    model.depreciation_method = Set(terms.depreciation_method);
+    model.useful_life_months = Set(terms.useful_life_months);
+    let updated = model.update(&state.db).await.map_err(database_error)?;
+
+    info!("Asset updated: id={}", updated.id);
+    Ok(Json(ApiResponse {
+        data: AssetResponse::new(updated, timezone.today()),
+        message: "Asset updated successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Delete an asset
+///
+/// Removes the asset from the inventory; its purchase and sale transactions are kept.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/assets/{asset_id}",
+    params(("asset_id" = i32, Path, description = "Asset ID")),
+    responses(
+        (status = 204, description = "Asset deleted"),
+        (status = 403, description = "No write access to the account", body = ErrorResponse),
+        (status = 404, description = "Asset not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "assets"
+)]
+#[instrument(skip(state))]
+pub async fn delete_asset(
+    State(state): State<AppState>,
+    Path(asset_id): Path<i32>,
+    access: AccountAccess,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering delete_asset function");
+
+    let existing = find_asset(&state.db, &access, asset_id).await?;
+    ensure_writable(&access, existing.account_id)?;
+
+    asset::Entity::delete_by_id(asset_id)
+        .exec(&state.db)
+        .await
+        .map_err(database_error)?;
+
+    info!("Asset deleted: id={}", asset_id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Dispose of an asset
+///
+/// Marks the asset as sold or thrown away on `date`, from which day it no longer counts
+/// towards the net worth. A positive sale price is booked as a one-off income on the
+/// receiving account in the same step; the response shows the gain or loss against the
+/// depreciated value.
+#[utoipa::path(
+    post,
+    path = "/api/v1/assets/{asset_id}/dispose",
+    params(
+        ("asset_id" = i32, Path, description = "Asset ID"),
+        PeriodLockOverride,
+    ),
+    request_body = DisposeAssetRequest,
+    responses(
+        (status = 200, description = "Asset disposed of", body = ApiResponse<AssetResponse>),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "No write access to the account, or admin_override was passed by a non-administrator", body = ErrorResponse),
+        (status = 404, description = "Asset not found", body = ErrorResponse),
+        (status = 409, description = "Asset already disposed of, or date inside a locked period", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "assets"
+)]
+#[instrument(skip(state))]
+pub async fn dispose_asset(
+    State(state): State<AppState>,
+    Path(asset_id): Path<i32>,
+    access: AccountAccess,
+    timezone: UserTimezone,
+    auth: Option<AuthenticatedUser>,
+    Query(lock_override): Query<PeriodLockOverride>,
+    Json(request): Json<DisposeAssetRequest>,
+) -> Result<Json<ApiResponse<AssetResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering dispose_asset function");
+    debug!("Disposing of asset {}: {:?}", asset_id, request);
+
+    let existing = find_asset(&state.db, &access, asset_id).await?;
+    ensure_writable(&access, existing.account_id)?;
+
+    if existing.disposal_date.is_some() {
+        warn!("Asset {} was already disposed of", asset_id);
+        return Err(asset_error(
+            StatusCode::CONFLICT,
+            "ALREADY_DISPOSED",
+            format!("Asset {} was already disposed of", asset_id),
+        ));
+    }
+    let sale_price = request.sale_price.unwrap_or(Decimal::ZERO);
+    if sale_price < Decimal::ZERO || request.date < existing.purchase_date {
+        warn!("Rejected disposal of asset {} on {} for {}", asset_id, request.date, sale_price);
+        return Err(asset_error(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_ERROR",
+            "sale_price must not be negative and date must not be before the purchase date".to_string(),
+        ));
+    }
+
+    let receiving_account_id = request.account_id.unwrap_or(existing.account_id);
+    let books_proceeds = sale_price > Decimal::ZERO;
+    if books_proceeds {
+        ensure_writable(&access, receiving_account_id)?;
+        ensure_dates_unlocked(&state.db, auth.as_ref(), &[request.date], &lock_override).await?;
+        ensure_double_entry(&state, receiving_account_id, None, request.category_id, false).await?;
+    }
+
+    let value = book_value(&existing, request.date);
+    let name = existing.name.clone();
+    let result: Result<asset::Model, DbErr> = async {
+        let txn = state.db.begin().await?;
+
+        let disposal_transaction_id = if books_proceeds {
+            let proceeds = one_off_transaction::ActiveModel {
+                name: Set(format!("Sale of {}", name)),
+                description: Set(None),
+                amount: Set(sale_price),
+                date: Set(request.date),
+                include_in_statistics: Set(true),
+                target_account_id: Set(receiving_account_id),
+                source_account_id: Set(None),
+                category_id: Set(request.category_id),
+                ledger_name: Set(None),
+                linked_import_id: Set(None),
+                scenario_id: Set(None),
+                is_simulated: Set(false),
+                status: Set(one_off_transaction::OneOffStatus::Confirmed),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+            Some(proceeds.id)
+        } else {
+            None
+        };
+
+        let mut model = existing.into_active_model();
+        model.disposal_date = Set(Some(request.date));
+        model.sale_price = Set(Some(sale_price));
+        model.disposal_transaction_id = Set(disposal_transaction_id);
+        let disposed = model.update(&txn).await?;
+
+        txn.commit().await?;
+        Ok(disposed)
+    }
+    .await;
+    let disposed = result.map_err(database_error)?;
+
+    info!(
+        "Asset {} disposed of on {} for {} (book value {})",
+        asset_id, request.date, sale_price, value
+    );
+    Ok(Json(ApiResponse {
+        data: AssetResponse::new(disposed, timezone.today()),
+        message: "Asset disposed of successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Assets that may hold value between `start_date` and `end_date`.
+pub async fn assets_between(
+    db: &DatabaseConnection,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<Vec<asset::Model>, DbErr> {
+    asset::Entity::find()
+        .filter(asset::Column::PurchaseDate.lte(end_date))
+        .filter(
+            sea_orm::Condition::any()
+                .add(asset::Column::DisposalDate.is_null())
+                .add(asset::Column::DisposalDate.gt(start_date)),
+        )
+        .all(db)
+        .await
+}
@@ -0,0 +1,53 @@
+use crate::helpers::audit_chain::{verify_chain, AuditChainVerification};
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{extract::State, http::StatusCode, response::Json};
+use tracing::{error, info, instrument, warn};
+
+/// Verify the audit hash chain
+///
+/// Recomputes the hash of every audit entry and checks that each links to the one
+/// before it. A modified entry fails its own hash; a removed or reordered entry breaks
+/// the link of the entry after it. Entries are only recorded while the server runs
+/// with `--audit-chain`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/audit/verify",
+    tag = "audit",
+    responses(
+        (status = 200, description = "Verification result; check `valid`", body = ApiResponse<AuditChainVerification>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn verify_audit_chain(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<AuditChainVerification>>, (StatusCode, Json<ErrorResponse>)> {
+    let verification = verify_chain(&state.db).await.map_err(|e| {
+        error!("Database error while verifying audit chain: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to verify audit chain".to_string(),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    })?;
+
+    let message = if verification.valid {
+        info!("Audit chain verified: {} entries", verification.entries_checked);
+        "Audit chain is intact".to_string()
+    } else {
+        warn!(
+            "Audit chain broken at entry {:?}: {:?}",
+            verification.first_invalid_id, verification.reason
+        );
+        "Audit chain is broken".to_string()
+    };
+
+    Ok(Json(ApiResponse {
+        data: verification,
+        message,
+        success: true,
+    }))
+}
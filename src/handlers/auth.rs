@@ -0,0 +1,296 @@
+use crate::handlers::users::UserResponse;
+use crate::helpers::auth::{
+    generate_session_token, hash_bearer_token, hash_password, verify_password, AuthenticatedUser, Credential,
+    SESSION_TTL_DAYS,
+};
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{extract::State, http::StatusCode, response::Json};
+use chrono::{Duration, NaiveDateTime};
+use model::entities::{user, user_session};
+use sea_orm::{ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, Set};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use tracing::{debug, error, info, instrument, trace, warn};
+use utoipa::ToSchema;
+
+/// Shortest password accepted when setting one.
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// Request body for logging in
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct LoginRequest {
+    pub username: String,
+    pub password: String,
+}
+
+impl fmt::Debug for LoginRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LoginRequest")
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A new session
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LoginResponse {
+    /// Bearer token to send as `Authorization: Bearer <token>`
+    pub token: String,
+    /// When the token stops being accepted (UTC)
+    pub expires_at: NaiveDateTime,
+    pub user_id: i32,
+    pub username: String,
+}
+
+/// Request body for changing the caller's password
+#[derive(Deserialize, Serialize, ToSchema)]
+pub struct ChangePasswordRequest {
+    /// Required when the user already has a password
+    pub current_password: Option<String>,
+    /// At least 8 characters
+    pub new_password: String,
+}
+
+impl fmt::Debug for ChangePasswordRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChangePasswordRequest").finish_non_exhaustive()
+    }
+}
+
+fn auth_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+fn database_error(e: DbErr) -> (StatusCode, Json<ErrorResponse>) {
+    error!("Database error in authentication: {}", e);
+    auth_error(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", e.to_string())
+}
+
+fn invalid_credentials() -> (StatusCode, Json<ErrorResponse>) {
+    auth_error(
+        StatusCode::UNAUTHORIZED,
+        "INVALID_CREDENTIALS",
+        "Invalid username or password".to_string(),
+    )
+}
+
+/// Log in
+///
+/// Checks the password and opens a session. The returned token authenticates
+/// subsequent requests until it expires or the session is logged out.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/login",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Logged in", body = ApiResponse<LoginResponse>),
+        (status = 401, description = "Invalid username or password", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "auth"
+)]
+#[instrument(skip(state))]
+pub async fn login(
+    State(state): State<AppState>,
+    Json(request): Json<LoginRequest>,
+) -> Result<Json<ApiResponse<LoginResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering login function");
+
+    let user = user::Entity::find()
+        .filter(user::Column::Username.eq(&request.username))
+        .one(&state.db)
+        .await
+        .map_err(database_error)?;
+    let user = match user {
+        Some(user)
+            if user
+                .password_hash
+                .as_deref()
+                .is_some_and(|password_hash| verify_password(&request.password, password_hash)) =>
+        {
+            user
+        }
+        _ => {
+            warn!("Failed login attempt for username '{}'", request.username);
+            return Err(invalid_credentials());
+        }
+    };
+
+    let now = chrono::Utc::now().naive_utc();
+    let removed = user_session::Entity::delete_many()
+        .filter(user_session::Column::UserId.eq(user.id))
+        .filter(user_session::Column::ExpiresAt.lte(now))
+        .exec(&state.db)
+        .await
+        .map_err(database_error)?;
+    debug!("Removed {} expired sessions of user {}", removed.rows_affected, user.id);
+
+    let token = generate_session_token();
+    let session = user_session::ActiveModel {
+        user_id: Set(user.id),
+        token_hash: Set(hash_bearer_token(&token)),
+        created_at: Set(now),
+        expires_at: Set(now + Duration::days(SESSION_TTL_DAYS)),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await
+    .map_err(database_error)?;
+
+    info!("User {} logged in, session {}", user.id, session.id);
+    Ok(Json(ApiResponse {
+        data: LoginResponse {
+            token,
+            expires_at: session.expires_at,
+            user_id: user.id,
+            username: user.username,
+        },
+        message: "Logged in successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Log out
+///
+/// Ends the session the request was made with; its token is rejected afterwards.
+/// API tokens are revoked through `/api/v1/tokens` instead.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/logout",
+    responses(
+        (status = 204, description = "Logged out"),
+        (status = 400, description = "Request was authenticated with an API token", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "auth"
+)]
+#[instrument(skip(state))]
+pub async fn logout(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering logout function");
+
+    let Credential::Session(session_id) = auth.credential else {
+        warn!("User {} tried to log out with an API token", auth.user_id);
+        return Err(auth_error(
+            StatusCode::BAD_REQUEST,
+            "NOT_A_SESSION",
+            "API tokens are revoked through /api/v1/tokens".to_string(),
+        ));
+    };
+    user_session::Entity::delete_by_id(session_id)
+        .exec(&state.db)
+        .await
+        .map_err(database_error)?;
+
+    info!("User {} logged out, session {}", auth.user_id, session_id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get the current user
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/me",
+    responses(
+        (status = 200, description = "The authenticated user", body = ApiResponse<UserResponse>),
+        (status = 401, description = "Not authenticated", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "auth"
+)]
+#[instrument]
+pub async fn get_current_user(auth: AuthenticatedUser) -> Json<ApiResponse<UserResponse>> {
+    trace!("Entering get_current_user function");
+    Json(ApiResponse {
+        data: UserResponse {
+            id: auth.user_id,
+            username: auth.username,
+        },
+        message: "Current user retrieved successfully".to_string(),
+        success: true,
+    })
+}
+
+/// Change the current user's password
+///
+/// Every other session of the user is logged out.
+#[utoipa::path(
+    put,
+    path = "/api/v1/auth/password",
+    request_body = ChangePasswordRequest,
+    responses(
+        (status = 204, description = "Password changed"),
+        (status = 400, description = "New password too short", body = ErrorResponse),
+        (status = 401, description = "Not authenticated or wrong current password", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "auth"
+)]
+#[instrument(skip(state))]
+pub async fn change_password(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Json(request): Json<ChangePasswordRequest>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering change_password function");
+
+    if request.new_password.chars().count() < MIN_PASSWORD_LENGTH {
+        warn!("Rejected too short password for user {}", auth.user_id);
+        return Err(auth_error(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_ERROR",
+            format!("Password must be at least {} characters long", MIN_PASSWORD_LENGTH),
+        ));
+    }
+
+    let user = user::Entity::find_by_id(auth.user_id)
+        .one(&state.db)
+        .await
+        .map_err(database_error)?
+        .ok_or_else(|| auth_error(StatusCode::UNAUTHORIZED, "UNAUTHORIZED", "Authentication required".to_string()))?;
+    if let Some(password_hash) = &user.password_hash {
+        let current_matches = request
+            .current_password
+            .as_deref()
+            .is_some_and(|current_password| verify_password(current_password, password_hash));
+        if !current_matches {
+            warn!("Wrong current password for user {}", auth.user_id);
+            return Err(invalid_credentials());
+        }
+    }
+
+    let password_hash = hash_password(&request.new_password).map_err(|e| {
+        error!("Failed to hash password for user {}: {}", auth.user_id, e);
+        auth_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "PASSWORD_HASH_ERROR",
+            "Failed to hash password".to_string(),
+        )
+    })?;
+    let mut user: user::ActiveModel = user.into();
+    user.password_hash = Set(Some(password_hash));
+    user.update(&state.db).await.map_err(database_error)?;
+
+    let mut other_sessions = user_session::Entity::delete_many().filter(user_session::Column::UserId.eq(auth.user_id));
+    if let Credential::Session(session_id) = auth.credential {
+        other_sessions = other_sessions.filter(user_session::Column::Id.ne(session_id));
+    }
+    let removed = other_sessions.exec(&state.db).await.map_err(database_error)?;
+
+    info!(
+        "User {} changed their password, {} other sessions logged out",
+        auth.user_id, removed.rows_affected
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
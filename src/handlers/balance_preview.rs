@@ -0,0 +1,223 @@
+use crate::helpers::access::AccountAccess;
+use crate::helpers::converters::convert_dataframe_to_timeseries;
+use crate::helpers::scenarios::ScenarioPreferences;
+use crate::helpers::timezone::UserTimezone;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use common::{BalancePreviewPoint, BalancePreviewRequest, BalancePreviewResponse, PreviewTransaction};
+use compute::{
+    account::AccountStateCalculator,
+    default_compute_with_scenario,
+    preview::{preview_balance, PlannedTransaction},
+    recurrence::Recurrence,
+};
+use model::entities::{account, one_off_transaction, recurring_transaction};
+use rust_decimal::Decimal;
+use sea_orm::{DbErr, EntityTrait};
+use tracing::{debug, error, instrument, trace, warn};
+
+/// Longest preview in days, about five years
+const MAX_PREVIEW_DAYS: i64 = 1830;
+
+fn preview_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+fn not_found(what: &str, id: i32) -> (StatusCode, Json<ErrorResponse>) {
+    preview_error(StatusCode::NOT_FOUND, "NOT_FOUND", format!("{} {} not found", what, id))
+}
+
+/// Effect of a transaction on `account_id`: its amount on the target account, the
+/// negated amount on the source account, nothing on any other account.
+fn effect_on(account_id: i32, amount: Decimal, target_account_id: i32, source_account_id: Option<i32>) -> Option<Decimal> {
+    if target_account_id == account_id {
+        Some(amount)
+    } else if source_account_id == Some(account_id) {
+        Some(-amount)
+    } else {
+        None
+    }
+}
+
+fn planned_from_request(transaction: &PreviewTransaction) -> Result<PlannedTransaction, (StatusCode, Json<ErrorResponse>)> {
+    match transaction.period.as_deref().filter(|period| !period.is_empty()) {
+        None => Ok(PlannedTransaction::one_off(transaction.amount, transaction.date)),
+        Some(period) => {
+            let recurrence: Recurrence = period
+                .parse()
+                .map_err(|e: String| preview_error(StatusCode::BAD_REQUEST, "INVALID_PERIOD", e))?;
+            if transaction.end_date.is_some_and(|end| end < transaction.date) {
+                return Err(preview_error(
+                    StatusCode::BAD_REQUEST,
+                    "INVALID_DATE_RANGE",
+                    "end_date of the transaction must not be before its date".to_string(),
+                ));
+            }
+            Ok(PlannedTransaction::recurring(transaction.amount, transaction.date, recurrence, transaction.end_date))
+        }
+    }
+}
+
+/// Saved version of the transaction being replaced, as it affects `account_id`.
+async fn load_original(
+    state: &AppState,
+    access: &AccountAccess,
+    account_id: i32,
+    request: &BalancePreviewRequest,
+) -> Result<Option<PlannedTransaction>, (StatusCode, Json<ErrorResponse>)> {
+    let database_error = |e: DbErr| {
+        error!("Database error while loading the previewed transaction: {}", e);
+        preview_error(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", e.to_string())
+    };
+
+    if let Some(id) = request.transaction_id {
+        let transaction = one_off_transaction::Entity::find_by_id(id)
+            .one(&state.db)
+            .await
+            .map_err(database_error)?
+            .filter(|transaction| access.can_access_any(transaction.target_account_id, transaction.source_account_id))
+            .ok_or_else(|| not_found("Transaction", id))?;
+        return Ok(
+            effect_on(account_id, transaction.amount, transaction.target_account_id, transaction.source_account_id)
+                .map(|amount| PlannedTransaction::one_off(amount, transaction.date)),
+        );
+    }
+
+    if let Some(id) = request.recurring_transaction_id {
+        let transaction = recurring_transaction::Entity::find_by_id(id)
+            .one(&state.db)
+            .await
+            .map_err(database_error)?
+            .filter(|transaction| access.can_access_any(transaction.target_account_id, transaction.source_account_id))
+            .ok_or_else(|| not_found("Recurring transaction", id))?;
+        return Ok(
+            effect_on(account_id, transaction.amount, transaction.target_account_id, transaction.source_account_id).map(
+                |amount| {
+                    PlannedTransaction::recurring(
+                        amount,
+                        transaction.start_date,
+                        Recurrence::from(&transaction.period),
+                        transaction.end_date,
+                    )
+                },
+            ),
+        );
+    }
+
+    Ok(None)
+}
+
+/// Preview how a transaction would move an account's balance
+///
+/// Computes the account's balances for the requested days and applies the transaction
+/// from the form to them, taking out the saved version when `transaction_id` or
+/// `recurring_transaction_id` names the transaction being edited. Nothing is saved.
+/// The frontend computes the same preview locally while typing; this endpoint gives the
+/// authoritative numbers based on the current data.
+#[utoipa::path(
+    post,
+    path = "/api/v1/accounts/{account_id}/balance-preview",
+    tag = "timeseries",
+    params(
+        ("account_id" = i32, Path, description = "Account ID"),
+    ),
+    request_body = BalancePreviewRequest,
+    responses(
+        (status = 200, description = "Balances without and with the transaction", body = ApiResponse<BalancePreviewResponse>),
+        (status = 400, description = "Invalid dates or period", body = ErrorResponse),
+        (status = 404, description = "Account or transaction not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn preview_account_balance(
+    Path(account_id): Path<i32>,
+    State(state): State<AppState>,
+    access: AccountAccess,
+    timezone: UserTimezone,
+    Json(request): Json<BalancePreviewRequest>,
+) -> Result<Json<ApiResponse<BalancePreviewResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering preview_account_balance function for account_id: {}", account_id);
+
+    if request.start_date > request.end_date || (request.end_date - request.start_date).num_days() > MAX_PREVIEW_DAYS {
+        warn!("Rejected balance preview from {} to {}", request.start_date, request.end_date);
+        return Err(preview_error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_DATE_RANGE",
+            format!("start_date must not be after end_date and the range must not exceed {} days", MAX_PREVIEW_DAYS),
+        ));
+    }
+
+    let account_model = account::Entity::find_by_id(account_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| {
+            error!("Failed to retrieve account with ID {}: {}", account_id, e);
+            preview_error(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", e.to_string())
+        })?
+        .filter(|account| access.can_access(account.id))
+        .ok_or_else(|| not_found("Account", account_id))?;
+
+    let edited = request.transaction.as_ref().map(planned_from_request).transpose()?;
+    let original = load_original(&state, &access, account_id, &request).await?;
+
+    let scenario_id = ScenarioPreferences::load(&state.db)
+        .await
+        .map_err(|e| {
+            error!("Failed to load scenario preferences: {}", e);
+            preview_error(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", e.to_string())
+        })?
+        .resolve(None, std::slice::from_ref(&account_model));
+    let compute_error = |e: String| {
+        error!("Failed to compute balances for preview of account {}: {}", account_id, e);
+        preview_error(StatusCode::INTERNAL_SERVER_ERROR, "COMPUTE_ERROR", e)
+    };
+    let df = default_compute_with_scenario(Some(timezone.today()), scenario_id)
+        .compute_account_state(&state.db, &[account_model], request.start_date, request.end_date)
+        .await
+        .map_err(|e| compute_error(e.to_string()))?;
+    let mut balances: Vec<_> = convert_dataframe_to_timeseries(df)
+        .map_err(compute_error)?
+        .data_points
+        .into_iter()
+        .filter(|point| point.account_id == account_id)
+        .map(|point| (point.date, point.balance))
+        .collect();
+    balances.sort_by_key(|(date, _)| *date);
+    debug!("Previewing transaction against {} balances of account {}", balances.len(), account_id);
+
+    let preview = preview_balance(&balances, original.as_ref(), edited.as_ref());
+    let response = BalancePreviewResponse {
+        account_id,
+        points: preview
+            .points
+            .iter()
+            .map(|point| BalancePreviewPoint {
+                date: point.date,
+                before: point.before,
+                after: point.after,
+            })
+            .collect(),
+        end_before: preview.end_before,
+        end_after: preview.end_after,
+        lowest_balance: preview.lowest_after.map(|(_, balance)| balance),
+        lowest_date: preview.lowest_after.map(|(date, _)| date),
+    };
+
+    Ok(Json(ApiResponse {
+        data: response,
+        message: "Balance preview computed successfully".to_string(),
+        success: true,
+    }))
+}
@@ -0,0 +1,176 @@
+use crate::router::create_test_router;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    body::{to_bytes, Body},
+    extract::State,
+    http::{header, HeaderMap, Method, Request, StatusCode, Uri},
+    response::Json,
+};
+use axum_valid::Valid;
+use serde::{Deserialize, Serialize};
+use tower::ServiceExt;
+use tracing::{debug, error, info, instrument, trace, warn};
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// Path of the batch endpoint; batches can't contain themselves.
+pub const BATCH_PATH: &str = "/api/v1/batch";
+/// Largest sub-response body collected into the batch response.
+const MAX_SUB_RESPONSE_BYTES: usize = 10 * 1024 * 1024;
+/// Prefix every sub-request path must start with.
+const API_PATH_PREFIX: &str = "/api/v1/";
+
+/// One request of a batch
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct BatchRequestItem {
+    /// HTTP method, e.g. "GET"
+    pub method: String,
+    /// Absolute API path including the query string, e.g. "/api/v1/accounts?page=1"
+    pub path: String,
+    /// JSON request body
+    #[schema(value_type = Option<Object>)]
+    pub body: Option<serde_json::Value>,
+}
+
+/// Requests executed in order by the batch endpoint
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct BatchRequest {
+    #[validate(length(min = 1, max = 20))]
+    pub requests: Vec<BatchRequestItem>,
+}
+
+/// Outcome of one request of a batch
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BatchResponseItem {
+    /// HTTP status the request would have been answered with
+    pub status: u16,
+    /// The response body; a string when it isn't JSON and null when empty
+    #[schema(value_type = Object)]
+    pub body: serde_json::Value,
+}
+
+impl BatchResponseItem {
+    fn error(status: StatusCode, code: &str, error: String) -> Self {
+        let body = ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        };
+        Self {
+            status: status.as_u16(),
+            body: serde_json::to_value(body).unwrap_or_default(),
+        }
+    }
+}
+
+/// Builds the sub-request, or the error response it is answered with when it is invalid.
+fn sub_request(item: &BatchRequestItem, headers: &HeaderMap) -> Result<Request<Body>, BatchResponseItem> {
+    let method = Method::from_bytes(item.method.to_uppercase().as_bytes()).map_err(|_| {
+        BatchResponseItem::error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_METHOD",
+            format!("Invalid method '{}'", item.method),
+        )
+    })?;
+    let uri: Uri = item.path.parse().map_err(|_| {
+        BatchResponseItem::error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_PATH",
+            format!("Invalid path '{}'", item.path),
+        )
+    })?;
+    if !uri.path().starts_with(API_PATH_PREFIX) || uri.path() == BATCH_PATH {
+        return Err(BatchResponseItem::error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_PATH",
+            format!("Path '{}' is not an API endpoint that can be batched", item.path),
+        ));
+    }
+
+    let mut builder = Request::builder().method(method).uri(uri);
+    // Every sub-request runs as the caller of the batch
+    if let Some(authorization) = headers.get(header::AUTHORIZATION) {
+        builder = builder.header(header::AUTHORIZATION, authorization);
+    }
+    let body = match &item.body {
+        Some(body) => {
+            builder = builder.header(header::CONTENT_TYPE, "application/json");
+            Body::from(body.to_string())
+        }
+        None => Body::empty(),
+    };
+    builder.body(body).map_err(|e| {
+        BatchResponseItem::error(StatusCode::BAD_REQUEST, "INVALID_REQUEST", e.to_string())
+    })
+}
+
+/// Execute several API requests at once
+///
+/// Runs up to 20 requests in order, as if each was sent on its own with the
+/// `Authorization` header of the batch, and returns their statuses and bodies in the
+/// same order. A failing request doesn't stop the ones after it.
+#[utoipa::path(
+    post,
+    path = "/api/v1/batch",
+    tag = "batch",
+    request_body = BatchRequest,
+    responses(
+        (status = 200, description = "Responses of all requests, in order", body = ApiResponse<Vec<BatchResponseItem>>),
+        (status = 400, description = "Empty batch or more than 20 requests", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state, headers, request))]
+pub async fn execute_batch(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Valid(Json(request)): Valid<Json<BatchRequest>>,
+) -> Json<ApiResponse<Vec<BatchResponseItem>>> {
+    trace!("Entering execute_batch function");
+    debug!("Executing batch of {} requests", request.requests.len());
+
+    let router = create_test_router(state);
+    let mut responses = Vec::with_capacity(request.requests.len());
+    for item in &request.requests {
+        let sub_request = match sub_request(item, &headers) {
+            Ok(sub_request) => sub_request,
+            Err(response) => {
+                warn!("Rejected batched {} {}", item.method, item.path);
+                responses.push(response);
+                continue;
+            }
+        };
+
+        let response = router
+            .clone()
+            .oneshot(sub_request)
+            .await
+            .unwrap_or_else(|never| match never {});
+        let status = response.status();
+        let body = match to_bytes(response.into_body(), MAX_SUB_RESPONSE_BYTES).await {
+            Ok(bytes) if bytes.is_empty() => serde_json::Value::Null,
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .unwrap_or_else(|_| serde_json::Value::String(String::from_utf8_lossy(&bytes).into_owned())),
+            Err(e) => {
+                error!("Failed to read response of batched {} {}: {}", item.method, item.path, e);
+                responses.push(BatchResponseItem::error(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "RESPONSE_TOO_LARGE",
+                    "Failed to read response".to_string(),
+                ));
+                continue;
+            }
+        };
+        debug!("Batched {} {} answered with {}", item.method, item.path, status);
+        responses.push(BatchResponseItem {
+            status: status.as_u16(),
+            body,
+        });
+    }
+
+    info!("Executed batch of {} requests", responses.len());
+    Json(ApiResponse {
+        data: responses,
+        message: "Batch executed successfully".to_string(),
+        success: true,
+    })
+}
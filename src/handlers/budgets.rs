@@ -0,0 +1,882 @@
+use crate::helpers::access::AccountAccess;
+use crate::helpers::timezone::UserTimezone;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::NaiveDate;
+use compute::account::utils::{load_holiday_calendar, recurring_transaction_occurrences};
+use compute::account_stats::StatisticsExclusions;
+use compute::budgets::{
+    budget_progress, evaluate_budgets, suggest_monthly_budgets, BudgetProgressPoint, BudgetStatus, CategorizedExpense,
+};
+use model::entities::{
+    account_statistics_exclusion,
+    budget::{self, BudgetPeriod},
+    category, one_off_transaction, recurring_transaction, recurring_transaction_instance,
+};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{debug, error, info, instrument, trace, warn};
+use utoipa::{IntoParams, ToSchema};
+
+/// Request body for creating a budget
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateBudgetRequest {
+    /// Category whose expenses (including subcategories) count against the budget
+    pub category_id: i32,
+    /// "Monthly", "Quarterly" or "Yearly"
+    pub period: String,
+    /// Spending limit per period, must be positive
+    pub amount: Decimal,
+    /// Carry unspent or overspent amounts into the next period
+    #[serde(default)]
+    pub rollover: bool,
+    /// The budget applies from the period containing this date
+    pub start_date: NaiveDate,
+}
+
+/// Request body for updating a budget
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UpdateBudgetRequest {
+    pub category_id: Option<i32>,
+    /// "Monthly", "Quarterly" or "Yearly"
+    pub period: Option<String>,
+    pub amount: Option<Decimal>,
+    pub rollover: Option<bool>,
+    pub start_date: Option<NaiveDate>,
+}
+
+/// Budget response model
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BudgetResponse {
+    pub id: i32,
+    pub category_id: i32,
+    pub period: String,
+    pub amount: Decimal,
+    pub rollover: bool,
+    pub start_date: NaiveDate,
+}
+
+impl From<budget::Model> for BudgetResponse {
+    fn from(model: budget::Model) -> Self {
+        Self {
+            id: model.id,
+            category_id: model.category_id,
+            period: format!("{:?}", model.period),
+            amount: model.amount,
+            rollover: model.rollover,
+            start_date: model.start_date,
+        }
+    }
+}
+
+/// Query parameters for the budget status report
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct BudgetStatusQuery {
+    /// Day to evaluate; its period is reported (default: today)
+    pub date: Option<NaiveDate>,
+}
+
+/// Spending against a budget in one period
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BudgetStatusResponse {
+    pub budget_id: i32,
+    pub category_id: i32,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    /// Limit of a single period
+    pub limit: Decimal,
+    /// Left over from earlier periods; negative when they were overspent
+    pub carried_over: Decimal,
+    /// Limit plus the carried over amount
+    pub available: Decimal,
+    /// Expenses of the category and its subcategories up to the evaluated day
+    pub spent: Decimal,
+    /// Negative once the budget is exceeded
+    pub remaining: Decimal,
+}
+
+impl From<BudgetStatus> for BudgetStatusResponse {
+    fn from(status: BudgetStatus) -> Self {
+        Self {
+            available: status.available(),
+            remaining: status.remaining(),
+            budget_id: status.budget_id,
+            category_id: status.category_id,
+            period_start: status.period_start,
+            period_end: status.period_end,
+            limit: status.limit,
+            carried_over: status.carried_over,
+            spent: status.spent,
+        }
+    }
+}
+
+/// Query parameters for a budget's progress series
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct BudgetProgressQuery {
+    /// First day of the series (default: start of the current period)
+    pub start_date: Option<NaiveDate>,
+    /// Last day of the series (default: end of the current period)
+    pub end_date: Option<NaiveDate>,
+}
+
+/// Cumulative spending against a budget at the end of one day
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BudgetProgressPointResponse {
+    pub date: NaiveDate,
+    /// Start of the period the day belongs to; spending restarts from zero with each period
+    pub period_start: NaiveDate,
+    /// Limit plus the amount carried over into the period
+    pub available: Decimal,
+    /// Spent since the start of the period up to and including the day
+    pub spent: Decimal,
+    /// Negative once the budget is exceeded
+    pub remaining: Decimal,
+}
+
+impl From<BudgetProgressPoint> for BudgetProgressPointResponse {
+    fn from(point: BudgetProgressPoint) -> Self {
+        Self {
+            remaining: point.remaining(),
+            date: point.date,
+            period_start: point.period_start,
+            available: point.available,
+            spent: point.spent,
+        }
+    }
+}
+
+/// Day-by-day spending against a budget
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BudgetProgressResponse {
+    pub budget: BudgetResponse,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// One point per day in the range; days before the budget starts are left out
+    pub points: Vec<BudgetProgressPointResponse>,
+}
+
+/// Months of history budget suggestions are based on by default.
+const DEFAULT_SUGGESTION_MONTHS: u32 = 6;
+
+/// Request body for suggesting budgets from past spending
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct SuggestBudgetsRequest {
+    /// Number of full months before the current one to learn from (default 6, max 24)
+    pub months: Option<u32>,
+    /// Create the suggested budgets for categories that do not have one yet
+    #[serde(default)]
+    pub apply: bool,
+}
+
+/// Monthly budget proposed for a top-level category
+///
+/// Apart from `category_name`, `median` and `existing_budget_id` it can be sent to
+/// `POST /api/v1/budgets` as is, or after adjusting the amount.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BudgetSuggestionResponse {
+    pub category_id: i32,
+    pub category_name: String,
+    pub period: String,
+    /// Median monthly spending rounded to a multiple of 10
+    pub amount: Decimal,
+    /// Median monthly spending, months without spending counting as zero
+    pub median: Decimal,
+    pub rollover: bool,
+    /// First day of the current month
+    pub start_date: NaiveDate,
+    /// Budget the category already has; suggestions for it are never applied
+    pub existing_budget_id: Option<i32>,
+}
+
+/// Budgets proposed from past spending
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BudgetSuggestionsResponse {
+    /// First day of the months the suggestions are based on
+    pub history_start: NaiveDate,
+    /// Last day of the months the suggestions are based on
+    pub history_end: NaiveDate,
+    pub suggestions: Vec<BudgetSuggestionResponse>,
+    /// Budgets created because `apply` was set
+    pub created: Vec<BudgetResponse>,
+}
+
+fn budget_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+fn parse_budget_period(period: &str) -> Result<BudgetPeriod, (StatusCode, Json<ErrorResponse>)> {
+    match period {
+        "Monthly" => Ok(BudgetPeriod::Monthly),
+        "Quarterly" => Ok(BudgetPeriod::Quarterly),
+        "Yearly" => Ok(BudgetPeriod::Yearly),
+        _ => {
+            warn!("Invalid budget period: {}", period);
+            Err(budget_error(
+                StatusCode::BAD_REQUEST,
+                "VALIDATION_ERROR",
+                format!("Invalid budget period '{}'; expected Monthly, Quarterly or Yearly", period),
+            ))
+        }
+    }
+}
+
+fn validate_amount(amount: Decimal) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if amount <= Decimal::ZERO {
+        warn!("Rejected non-positive budget amount {}", amount);
+        return Err(budget_error(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_ERROR",
+            "Budget amount must be positive".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+async fn ensure_category_exists(
+    db: &DatabaseConnection,
+    category_id: i32,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    let found = category::Entity::find_by_id(category_id).one(db).await.map_err(|e| {
+        error!("Failed to fetch category {}: {}", category_id, e);
+        budget_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "DATABASE_ERROR",
+            format!("Failed to fetch category: {}", e),
+        )
+    })?;
+    if found.is_none() {
+        warn!("Category {} not found for budget", category_id);
+        return Err(budget_error(
+            StatusCode::BAD_REQUEST,
+            "CATEGORY_NOT_FOUND",
+            format!("Category with ID {} not found", category_id),
+        ));
+    }
+    Ok(())
+}
+
+fn budget_not_found(budget_id: i32) -> (StatusCode, Json<ErrorResponse>) {
+    warn!("Budget {} not found", budget_id);
+    budget_error(
+        StatusCode::NOT_FOUND,
+        "NOT_FOUND",
+        format!("Budget with ID {} not found", budget_id),
+    )
+}
+
+/// Collects categorized expenses dated between `start` and `end`.
+///
+/// Covers confirmed, non-simulated one-off expenses included in statistics and occurrences of
+/// approved recurring expenses, where a recorded instance overrides the amount and
+/// category and skipped instances are left out. Transfers between own accounts are
+/// not expenses, nor are expenses dated within a statistics exclusion window of their
+/// account. Only expenses booked on accounts in `access` count. Amounts are returned as
+/// positive numbers.
+pub(crate) async fn gather_category_expenses(
+    db: &DatabaseConnection,
+    access: &AccountAccess,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<Vec<CategorizedExpense>, DbErr> {
+    let exclusions = StatisticsExclusions::new(&account_statistics_exclusion::Entity::find().all(db).await?);
+    let mut expenses: Vec<CategorizedExpense> = access
+        .filter(one_off_transaction::Entity::find(), [one_off_transaction::Column::TargetAccountId])
+        .filter(one_off_transaction::Column::Date.between(start, end))
+        .filter(one_off_transaction::Column::Amount.lt(Decimal::ZERO))
+        .filter(one_off_transaction::Column::CategoryId.is_not_null())
+        .filter(one_off_transaction::Column::SourceAccountId.is_null())
+        .filter(one_off_transaction::Column::IsSimulated.eq(false))
+        .filter(one_off_transaction::Column::Status.eq(one_off_transaction::OneOffStatus::Confirmed))
+        .filter(one_off_transaction::Column::IncludeInStatistics.eq(true))
+        .all(db)
+        .await?
+        .into_iter()
+        .filter(|transaction| !exclusions.excludes(transaction.target_account_id, transaction.date))
+        .filter_map(|transaction| {
+            Some(CategorizedExpense {
+                category_id: transaction.category_id?,
+                date: transaction.date,
+                amount: transaction.amount.abs(),
+            })
+        })
+        .collect();
+
+    let recurring = access
+        .filter(recurring_transaction::Entity::find(), [recurring_transaction::Column::TargetAccountId])
+        .filter(recurring_transaction::Column::SourceAccountId.is_null())
+        .filter(recurring_transaction::Column::IsSimulated.eq(false))
+        .filter(recurring_transaction::Column::IncludeInStatistics.eq(true))
+        .filter(recurring_transaction::Column::ApprovalStatus.eq(recurring_transaction::ApprovalStatus::Approved))
+        .all(db)
+        .await?;
+    let instances = recurring_transaction_instance::Entity::find()
+        .filter(recurring_transaction_instance::Column::DueDate.between(start, end))
+        .all(db)
+        .await?;
+    let instance_map: HashMap<(i32, NaiveDate), &recurring_transaction_instance::Model> = instances
+        .iter()
+        .map(|instance| ((instance.recurring_transaction_id, instance.due_date), instance))
+        .collect();
+    let holidays = load_holiday_calendar(db).await?;
+
+    for rtxn in &recurring {
+        for date in recurring_transaction_occurrences(rtxn, &holidays, start, end) {
+            if exclusions.excludes(rtxn.target_account_id, date) {
+                continue;
+            }
+            let (amount, category_id) = match instance_map.get(&(rtxn.id, date)) {
+                Some(instance) if instance.status == recurring_transaction_instance::InstanceStatus::Skipped => {
+                    trace!("Skipping instance for recurring txn {} on {}", rtxn.id, date);
+                    continue;
+                }
+                Some(instance) => (
+                    instance.paid_amount.unwrap_or(instance.expected_amount),
+                    instance.category_id.or(rtxn.category_id),
+                ),
+                None => (rtxn.amount, rtxn.category_id),
+            };
+            if let Some(category_id) = category_id.filter(|_| amount < Decimal::ZERO) {
+                expenses.push(CategorizedExpense {
+                    category_id,
+                    date,
+                    amount: amount.abs(),
+                });
+            }
+        }
+    }
+
+    Ok(expenses)
+}
+
+/// Evaluates every budget for the period containing `date` against the spending on the
+/// accounts in `access`.
+///
+/// Also returns the categories, which callers need to resolve the budgets' subcategories.
+pub(crate) async fn budget_statuses(
+    db: &DatabaseConnection,
+    access: &AccountAccess,
+    date: NaiveDate,
+) -> Result<(Vec<BudgetStatus>, Vec<category::Model>), DbErr> {
+    let budgets = budget::Entity::find().all(db).await?;
+    // Rollover budgets need every period since they started, the others only the current one
+    let Some(earliest) = budgets
+        .iter()
+        .map(|budget| {
+            let from = if budget.rollover { budget.start_date } else { date };
+            budget.period.bounds(from).0
+        })
+        .min()
+    else {
+        return Ok((Vec::new(), Vec::new()));
+    };
+
+    let categories = category::Entity::find().all(db).await?;
+    let expenses = gather_category_expenses(db, access, earliest, date).await?;
+    Ok((evaluate_budgets(&budgets, &categories, &expenses, date), categories))
+}
+
+/// Create a budget
+#[utoipa::path(
+    post,
+    path = "/api/v1/budgets",
+    request_body = CreateBudgetRequest,
+    responses(
+        (status = 201, description = "Budget created", body = ApiResponse<BudgetResponse>),
+        (status = 400, description = "Invalid period, amount or category", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "budgets"
+)]
+#[instrument(skip(state))]
+pub async fn create_budget(
+    State(state): State<AppState>,
+    Json(request): Json<CreateBudgetRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<BudgetResponse>>), (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering create_budget function");
+    debug!("Creating budget: {:?}", request);
+
+    let period = parse_budget_period(&request.period)?;
+    validate_amount(request.amount)?;
+    ensure_category_exists(&state.db, request.category_id).await?;
+
+    let created = budget::ActiveModel {
+        category_id: Set(request.category_id),
+        period: Set(period),
+        amount: Set(request.amount),
+        rollover: Set(request.rollover),
+        start_date: Set(request.start_date),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await
+    .map_err(|e| {
+        error!("Failed to create budget: {}", e);
+        budget_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "DATABASE_ERROR",
+            format!("Failed to create budget: {}", e),
+        )
+    })?;
+
+    info!("Budget created: id={}, category_id={}", created.id, created.category_id);
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse {
+            data: BudgetResponse::from(created),
+            message: "Budget created successfully".to_string(),
+            success: true,
+        }),
+    ))
+}
+
+/// List budgets
+#[utoipa::path(
+    get,
+    path = "/api/v1/budgets",
+    responses(
+        (status = 200, description = "List of budgets", body = ApiResponse<Vec<BudgetResponse>>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "budgets"
+)]
+#[instrument(skip(state))]
+pub async fn get_budgets(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<BudgetResponse>>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_budgets function");
+
+    let budgets = budget::Entity::find()
+        .order_by_asc(budget::Column::Id)
+        .all(&state.db)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch budgets: {}", e);
+            budget_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DATABASE_ERROR",
+                format!("Failed to fetch budgets: {}", e),
+            )
+        })?;
+
+    Ok(Json(ApiResponse {
+        data: budgets.into_iter().map(BudgetResponse::from).collect(),
+        message: "Budgets retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Get a budget by ID
+#[utoipa::path(
+    get,
+    path = "/api/v1/budgets/{budget_id}",
+    params(
+        ("budget_id" = i32, Path, description = "Budget ID")
+    ),
+    responses(
+        (status = 200, description = "Budget found", body = ApiResponse<BudgetResponse>),
+        (status = 404, description = "Budget not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "budgets"
+)]
+#[instrument(skip(state))]
+pub async fn get_budget(
+    State(state): State<AppState>,
+    Path(budget_id): Path<i32>,
+) -> Result<Json<ApiResponse<BudgetResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_budget function");
+
+    let budget = budget::Entity::find_by_id(budget_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch budget {}: {}", budget_id, e);
+            budget_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DATABASE_ERROR",
+                format!("Failed to fetch budget: {}", e),
+            )
+        })?
+        .ok_or_else(|| budget_not_found(budget_id))?;
+
+    Ok(Json(ApiResponse {
+        data: BudgetResponse::from(budget),
+        message: "Budget retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Update a budget
+#[utoipa::path(
+    put,
+    path = "/api/v1/budgets/{budget_id}",
+    params(
+        ("budget_id" = i32, Path, description = "Budget ID")
+    ),
+    request_body = UpdateBudgetRequest,
+    responses(
+        (status = 200, description = "Budget updated", body = ApiResponse<BudgetResponse>),
+        (status = 400, description = "Invalid period, amount or category", body = ErrorResponse),
+        (status = 404, description = "Budget not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "budgets"
+)]
+#[instrument(skip(state))]
+pub async fn update_budget(
+    State(state): State<AppState>,
+    Path(budget_id): Path<i32>,
+    Json(request): Json<UpdateBudgetRequest>,
+) -> Result<Json<ApiResponse<BudgetResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering update_budget function");
+    debug!("Updating budget {}: {:?}", budget_id, request);
+
+    let database_error = |e: DbErr| {
+        error!("Failed to update budget {}: {}", budget_id, e);
+        budget_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "DATABASE_ERROR",
+            format!("Failed to update budget: {}", e),
+        )
+    };
+
+    let existing = budget::Entity::find_by_id(budget_id)
+        .one(&state.db)
+        .await
+        .map_err(database_error)?
+        .ok_or_else(|| budget_not_found(budget_id))?;
+
+    let mut update: budget::ActiveModel = existing.into();
+    if let Some(category_id) = request.category_id {
+        ensure_category_exists(&state.db, category_id).await?;
+        update.category_id = Set(category_id);
+    }
+    if let Some(period) = &request.period {
+        update.period = Set(parse_budget_period(period)?);
+    }
+    if let Some(amount) = request.amount {
+        validate_amount(amount)?;
+        update.amount = Set(amount);
+    }
+    if let Some(rollover) = request.rollover {
+        update.rollover = Set(rollover);
+    }
+    if let Some(start_date) = request.start_date {
+        update.start_date = Set(start_date);
+    }
+
+    let updated = update.update(&state.db).await.map_err(database_error)?;
+
+    info!("Budget updated: id={}", updated.id);
+    Ok(Json(ApiResponse {
+        data: BudgetResponse::from(updated),
+        message: "Budget updated successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Delete a budget
+#[utoipa::path(
+    delete,
+    path = "/api/v1/budgets/{budget_id}",
+    params(
+        ("budget_id" = i32, Path, description = "Budget ID")
+    ),
+    responses(
+        (status = 204, description = "Budget deleted"),
+        (status = 404, description = "Budget not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "budgets"
+)]
+#[instrument(skip(state))]
+pub async fn delete_budget(
+    State(state): State<AppState>,
+    Path(budget_id): Path<i32>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering delete_budget function");
+
+    let result = budget::Entity::delete_by_id(budget_id)
+        .exec(&state.db)
+        .await
+        .map_err(|e| {
+            error!("Failed to delete budget {}: {}", budget_id, e);
+            budget_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DATABASE_ERROR",
+                format!("Failed to delete budget: {}", e),
+            )
+        })?;
+
+    if result.rows_affected == 0 {
+        return Err(budget_not_found(budget_id));
+    }
+
+    info!("Budget deleted: id={}", budget_id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get spending against every budget
+///
+/// Reports each budget for its period containing `date`, counting expenses up to that
+/// day. Budgets starting in a later period are left out.
+#[utoipa::path(
+    get,
+    path = "/api/v1/budgets/status",
+    params(BudgetStatusQuery),
+    responses(
+        (status = 200, description = "Spending per budget", body = ApiResponse<Vec<BudgetStatusResponse>>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "budgets"
+)]
+#[instrument(skip(state))]
+pub async fn get_budget_status(
+    State(state): State<AppState>,
+    Query(query): Query<BudgetStatusQuery>,
+    timezone: UserTimezone,
+    access: AccountAccess,
+) -> Result<Json<ApiResponse<Vec<BudgetStatusResponse>>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_budget_status function");
+    let date = query.date.unwrap_or_else(|| timezone.today());
+    debug!("Evaluating budgets on {}", date);
+
+    let database_error = |e: DbErr| {
+        error!("Database error while evaluating budgets: {}", e);
+        budget_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "DATABASE_ERROR",
+            format!("Failed to evaluate budgets: {}", e),
+        )
+    };
+
+    let (statuses, _) = budget_statuses(&state.db, &access, date).await.map_err(database_error)?;
+    let statuses: Vec<BudgetStatusResponse> = statuses.into_iter().map(BudgetStatusResponse::from).collect();
+    info!("Evaluated {} budgets on {}", statuses.len(), date);
+
+    Ok(Json(ApiResponse {
+        data: statuses,
+        message: "Success".to_string(),
+        success: true,
+    }))
+}
+
+/// Get a budget's progress
+///
+/// Returns the cumulative spending for every day between `start_date` and `end_date`
+/// together with the amount available in that day's period, for burn-down charts.
+#[utoipa::path(
+    get,
+    path = "/api/v1/budgets/{budget_id}/progress",
+    params(
+        ("budget_id" = i32, Path, description = "Budget ID"),
+        BudgetProgressQuery
+    ),
+    responses(
+        (status = 200, description = "Daily spending against the budget", body = ApiResponse<BudgetProgressResponse>),
+        (status = 400, description = "Invalid date range", body = ErrorResponse),
+        (status = 404, description = "Budget not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "budgets"
+)]
+#[instrument(skip(state))]
+pub async fn get_budget_progress(
+    State(state): State<AppState>,
+    Path(budget_id): Path<i32>,
+    Query(query): Query<BudgetProgressQuery>,
+    timezone: UserTimezone,
+    access: AccountAccess,
+) -> Result<Json<ApiResponse<BudgetProgressResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_budget_progress function");
+
+    let database_error = |e: DbErr| {
+        error!("Database error while computing progress of budget {}: {}", budget_id, e);
+        budget_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "DATABASE_ERROR",
+            format!("Failed to compute budget progress: {}", e),
+        )
+    };
+
+    let budget = budget::Entity::find_by_id(budget_id)
+        .one(&state.db)
+        .await
+        .map_err(database_error)?
+        .ok_or_else(|| budget_not_found(budget_id))?;
+
+    let (period_start, period_end) = budget.period.bounds(timezone.today());
+    let start_date = query.start_date.unwrap_or(period_start);
+    let end_date = query.end_date.unwrap_or(period_end);
+    if start_date > end_date {
+        warn!("Invalid date range: start_date > end_date");
+        return Err(budget_error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_DATE_RANGE",
+            "start_date must be before or equal to end_date".to_string(),
+        ));
+    }
+    debug!("Computing progress of budget {} from {} to {}", budget_id, start_date, end_date);
+
+    // Rollover needs every period since the budget started, otherwise the first period in range suffices
+    let from = if budget.rollover { budget.start_date.min(start_date) } else { start_date };
+    let categories = category::Entity::find().all(&state.db).await.map_err(database_error)?;
+    let expenses = gather_category_expenses(&state.db, &access, budget.period.bounds(from).0, end_date)
+        .await
+        .map_err(database_error)?;
+
+    let points: Vec<BudgetProgressPointResponse> = budget_progress(&budget, &categories, &expenses, start_date, end_date)
+        .into_iter()
+        .map(BudgetProgressPointResponse::from)
+        .collect();
+    info!("Computed {} progress points for budget {}", points.len(), budget_id);
+
+    Ok(Json(ApiResponse {
+        data: BudgetProgressResponse {
+            budget: BudgetResponse::from(budget),
+            start_date,
+            end_date,
+            points,
+        },
+        message: "Budget progress retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Suggest budgets from past spending
+///
+/// Proposes a monthly budget for every top-level category, set to the median of its
+/// monthly spending (subcategories included) over the last full months, rounded to a
+/// multiple of 10. With `apply` the suggestions are created as budgets starting this
+/// month, except for categories that already have a budget.
+#[utoipa::path(
+    post,
+    path = "/api/v1/budgets/suggest",
+    request_body = SuggestBudgetsRequest,
+    responses(
+        (status = 200, description = "Suggested budgets", body = ApiResponse<BudgetSuggestionsResponse>),
+        (status = 400, description = "Invalid number of months", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "budgets"
+)]
+#[instrument(skip(state))]
+pub async fn suggest_budgets(
+    State(state): State<AppState>,
+    timezone: UserTimezone,
+    access: AccountAccess,
+    Json(request): Json<SuggestBudgetsRequest>,
+) -> Result<Json<ApiResponse<BudgetSuggestionsResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering suggest_budgets function");
+    let months = request.months.unwrap_or(DEFAULT_SUGGESTION_MONTHS);
+    if !(1..=24).contains(&months) {
+        warn!("Rejected budget suggestion over {} months", months);
+        return Err(budget_error(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_ERROR",
+            "months must be between 1 and 24".to_string(),
+        ));
+    }
+
+    let database_error = |e: DbErr| {
+        error!("Database error while suggesting budgets: {}", e);
+        budget_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "DATABASE_ERROR",
+            format!("Failed to suggest budgets: {}", e),
+        )
+    };
+
+    let today = timezone.today();
+    let (start_date, _) = BudgetPeriod::Monthly.bounds(today);
+    let history: Vec<(NaiveDate, NaiveDate)> = (1..=months)
+        .rev()
+        .map(|back| BudgetPeriod::Monthly.bounds(start_date - chrono::Months::new(back)))
+        .collect();
+    let (history_start, history_end) = (history[0].0, history[history.len() - 1].1);
+    debug!("Suggesting budgets from spending between {} and {}", history_start, history_end);
+
+    let categories = category::Entity::find().all(&state.db).await.map_err(database_error)?;
+    let expenses = gather_category_expenses(&state.db, &access, history_start, history_end)
+        .await
+        .map_err(database_error)?;
+    let existing: HashMap<i32, i32> = budget::Entity::find()
+        .order_by_desc(budget::Column::Id)
+        .all(&state.db)
+        .await
+        .map_err(database_error)?
+        .into_iter()
+        .map(|budget| (budget.category_id, budget.id))
+        .collect();
+    let names: HashMap<i32, &str> = categories
+        .iter()
+        .map(|category| (category.id, category.name.as_str()))
+        .collect();
+
+    let suggestions: Vec<BudgetSuggestionResponse> = suggest_monthly_budgets(&categories, &expenses, &history)
+        .into_iter()
+        .map(|suggestion| BudgetSuggestionResponse {
+            category_id: suggestion.category_id,
+            category_name: names.get(&suggestion.category_id).copied().unwrap_or_default().to_string(),
+            period: format!("{:?}", BudgetPeriod::Monthly),
+            amount: suggestion.amount,
+            median: suggestion.median,
+            rollover: false,
+            start_date,
+            existing_budget_id: existing.get(&suggestion.category_id).copied(),
+        })
+        .collect();
+
+    let mut created = Vec::new();
+    if request.apply {
+        let txn = state.db.begin().await.map_err(database_error)?;
+        for suggestion in suggestions.iter().filter(|suggestion| suggestion.existing_budget_id.is_none()) {
+            let budget = budget::ActiveModel {
+                category_id: Set(suggestion.category_id),
+                period: Set(BudgetPeriod::Monthly),
+                amount: Set(suggestion.amount),
+                rollover: Set(suggestion.rollover),
+                start_date: Set(suggestion.start_date),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await
+            .map_err(database_error)?;
+            created.push(BudgetResponse::from(budget));
+        }
+        txn.commit().await.map_err(database_error)?;
+    }
+
+    info!(
+        "Suggested {} budgets from {} expenses, created {}",
+        suggestions.len(),
+        expenses.len(),
+        created.len()
+    );
+    Ok(Json(ApiResponse {
+        data: BudgetSuggestionsResponse {
+            history_start,
+            history_end,
+            suggestions,
+            created,
+        },
+        message: "Budget suggestions computed successfully".to_string(),
+        success: true,
+    }))
+}
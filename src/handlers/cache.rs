@@ -3,7 +3,15 @@ use axum::{extract::State, http::StatusCode, response::Json};
 use tracing::{info, instrument};
 
 /// Flush all cached data, forcing fresh computation on next request
-#[instrument]
+#[utoipa::path(
+    post,
+    path = "/api/v1/cache/flush",
+    tag = "cache",
+    responses(
+        (status = 200, description = "Cache flushed", body = ApiResponse<String>)
+    )
+)]
+#[instrument(skip(state))]
 pub async fn flush_cache(
     State(state): State<AppState>,
 ) -> Result<Json<ApiResponse<String>>, StatusCode> {
@@ -1,16 +1,21 @@
+use crate::helpers::access::AccountAccess;
+use crate::helpers::category_taxonomy::{seed_starter_categories, SUPPORTED_LOCALES};
+use crate::helpers::timezone::UserTimezone;
 use crate::schemas::{ApiResponse, AppState, ErrorResponse};
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
     response::Json,
 };
-use chrono::{Datelike, NaiveDate};
+use chrono::NaiveDate;
 use model::entities::{
-    category, account, one_off_transaction, recurring_transaction,
+    category, account, imported_transaction, one_off_transaction, recurring_transaction,
     recurring_transaction_instance,
 };
-use compute::account::utils::generate_occurrences;
-use sea_orm::{ActiveModelTrait, EntityTrait, Set, ColumnTrait, QueryFilter};
+use compute::account::utils::{load_holiday_calendar, recurring_transaction_occurrences};
+use compute::category_forecast::{month_start, CategoryForecaster};
+use compute::category_stats::{compute_category_stats, CategoryAmount};
+use sea_orm::{ActiveModelTrait, EntityTrait, Set, ColumnTrait, QueryFilter, TransactionTrait};
 use rust_decimal::Decimal;
 use std::collections::{BTreeMap, HashMap};
 use serde::{Deserialize, Serialize};
@@ -59,6 +64,23 @@ impl From<category::Model> for CategoryResponse {
     }
 }
 
+/// Query parameters for seeding the starter categories
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct SeedCategoriesQuery {
+    /// Language of the category names ("en" or "cs", default: "en")
+    pub locale: Option<String>,
+}
+
+/// Result of seeding the starter categories
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SeedCategoriesResponse {
+    pub locale: String,
+    /// Number of starter categories that already existed and were kept as they are
+    pub existing_count: usize,
+    /// Categories added by this request
+    pub created: Vec<CategoryResponse>,
+}
+
 /// Query parameters for category statistics
 #[derive(Debug, Deserialize, IntoParams)]
 pub struct CategoryStatsQuery {
@@ -96,6 +118,37 @@ pub struct CategoryStatsResponse {
     pub transaction_count: i64,
 }
 
+/// Query parameters for a category spending forecast
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct CategoryForecastQuery {
+    /// Number of past months to average over (default 12)
+    pub lookback_months: Option<u32>,
+    /// Number of months to forecast, starting with the current month (default 3)
+    pub horizon: Option<u32>,
+}
+
+/// Expected spend in one forecasted month
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MonthlyForecastEntry {
+    /// First day of the month
+    pub month: NaiveDate,
+    /// Expected spend as a positive amount
+    pub amount: String,
+}
+
+/// Per-category spending forecast, including child categories
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CategoryForecastResponse {
+    pub category_id: i32,
+    pub category_name: String,
+    pub lookback_months: u32,
+    /// Average monthly spend over the lookback window
+    pub average_monthly: String,
+    /// Suggested budget for the current month (forecast rounded up to a whole amount)
+    pub suggested_budget: String,
+    pub forecast: Vec<MonthlyForecastEntry>,
+}
+
 /// Create a new category
 #[utoipa::path(
     post,
@@ -496,6 +549,80 @@ pub async fn delete_category(
     }
 }
 
+/// Seed the starter category taxonomy
+///
+/// Installs a curated hierarchy of common categories (housing, food, transport, ...)
+/// with names in the requested locale. Categories are matched by name, so calling
+/// this again only adds the ones that are missing.
+#[utoipa::path(
+    post,
+    path = "/api/v1/categories/seed",
+    params(SeedCategoriesQuery),
+    responses(
+        (status = 200, description = "Starter categories seeded successfully", body = ApiResponse<SeedCategoriesResponse>),
+        (status = 400, description = "Unsupported locale", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "categories"
+)]
+#[instrument(skip(state))]
+pub async fn seed_categories(
+    State(state): State<AppState>,
+    Query(query): Query<SeedCategoriesQuery>,
+) -> Result<Json<ApiResponse<SeedCategoriesResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    let locale = query.locale.unwrap_or_else(|| "en".to_string()).to_lowercase();
+    debug!("Seeding starter categories in locale '{}'", locale);
+
+    if !SUPPORTED_LOCALES.contains(&locale.as_str()) {
+        warn!("Unsupported category locale '{}'", locale);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: format!(
+                    "Unsupported locale '{}', expected one of: {}",
+                    locale,
+                    SUPPORTED_LOCALES.join(", ")
+                ),
+                code: "UNSUPPORTED_LOCALE".to_string(),
+                success: false,
+            }),
+        ));
+    }
+
+    let database_error = |e: sea_orm::DbErr| {
+        error!("Failed to seed starter categories: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to seed starter categories".to_string(),
+                code: "ERROR".to_string(),
+                success: false,
+            }),
+        )
+    };
+
+    let txn = state.db.begin().await.map_err(database_error)?;
+    let outcome = seed_starter_categories(&txn, &locale).await.map_err(database_error)?;
+    txn.commit().await.map_err(database_error)?;
+
+    info!(
+        "Seeded starter categories in '{}': {} created, {} already present",
+        locale,
+        outcome.created.len(),
+        outcome.existing
+    );
+
+    Ok(Json(ApiResponse {
+        data: SeedCategoriesResponse {
+            locale,
+            existing_count: outcome.existing,
+            created: outcome.created.into_iter().map(CategoryResponse::from).collect(),
+        },
+        message: "Success".to_string(),
+        success: true,
+    }))
+}
+
 /// Get children of a category
 #[utoipa::path(
     get,
@@ -569,6 +696,10 @@ pub async fn get_category_children(
 }
 
 /// Get category statistics
+///
+/// Sums confirmed one-off transactions, recurring occurrences (with their instance overrides) and
+/// unreconciled imported transactions per category over the date range. Totals of a
+/// category include all of its subcategories.
 #[utoipa::path(
     get,
     path = "/api/v1/categories/stats",
@@ -576,6 +707,7 @@ pub async fn get_category_children(
     responses(
         (status = 200, description = "Category statistics", body = ApiResponse<Vec<CategoryStatsResponse>>),
         (status = 400, description = "Invalid query parameters", body = ErrorResponse),
+        (status = 404, description = "Account not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "categories"
@@ -583,6 +715,7 @@ pub async fn get_category_children(
 #[instrument(skip(state))]
 pub async fn get_category_stats(
     State(state): State<AppState>,
+    access: AccountAccess,
     Query(query): Query<CategoryStatsQuery>,
 ) -> Result<Json<ApiResponse<Vec<CategoryStatsResponse>>>, (StatusCode, Json<ErrorResponse>)> {
     debug!(
@@ -606,11 +739,11 @@ pub async fn get_category_stats(
     // Get all accounts or filter by account_id
     let accounts = if let Some(account_id) = query.account_id {
         match account::Entity::find_by_id(account_id).one(&state.db).await {
-            Ok(Some(acc)) => vec![acc],
-            Ok(None) => {
+            Ok(Some(acc)) if access.can_access(acc.id) => vec![acc],
+            Ok(_) => {
                 warn!("Account {} not found", account_id);
                 return Err((
-                    StatusCode::BAD_REQUEST,
+                    StatusCode::NOT_FOUND,
                     Json(ErrorResponse {
                         error: format!("Account with ID {} not found", account_id),
                         code: "ACCOUNT_NOT_FOUND".to_string(),
@@ -631,7 +764,7 @@ pub async fn get_category_stats(
             }
         }
     } else {
-        match account::Entity::find().all(&state.db).await {
+        match access.filter(account::Entity::find(), [account::Column::Id]).all(&state.db).await {
             Ok(accounts) => accounts,
             Err(e) => {
                 error!("Failed to fetch accounts: {}", e);
@@ -654,6 +787,8 @@ pub async fn get_category_stats(
         .filter(one_off_transaction::Column::Date.between(query.start_date, query.end_date))
         .filter(one_off_transaction::Column::CategoryId.is_not_null())
         .filter(one_off_transaction::Column::TargetAccountId.is_in(account_ids.clone()))
+        .filter(one_off_transaction::Column::Status.eq(one_off_transaction::OneOffStatus::Confirmed))
+
         .all(&state.db)
         .await
     {
@@ -712,6 +847,30 @@ pub async fn get_category_stats(
         }
     };
 
+    // Unreconciled imported transactions; reconciled ones are already counted through
+    // the transaction they are linked to
+    let imported_txns = match imported_transaction::Entity::find()
+        .filter(imported_transaction::Column::Date.between(query.start_date, query.end_date))
+        .filter(imported_transaction::Column::CategoryId.is_not_null())
+        .filter(imported_transaction::Column::ReconciledTransactionId.is_null())
+        .filter(imported_transaction::Column::AccountId.is_in(account_ids.clone()))
+        .all(&state.db)
+        .await
+    {
+        Ok(txns) => txns,
+        Err(e) => {
+            error!("Failed to fetch imported transactions: {}", e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Failed to fetch imported transactions".to_string(),
+                    code: "ERROR".to_string(),
+                    success: false,
+                }),
+            ));
+        }
+    };
+
     // Build instance lookup: (recurring_transaction_id, due_date) -> instance
     let instance_map: HashMap<(i32, NaiveDate), &recurring_transaction_instance::Model> = instances
         .iter()
@@ -734,33 +893,42 @@ pub async fn get_category_stats(
         }
     };
 
-    let category_map: HashMap<i32, &category::Model> = categories
+    let mut amounts: Vec<CategoryAmount> = one_off_txns
         .iter()
-        .map(|cat| (cat.id, cat))
+        .filter_map(|txn| {
+            Some(CategoryAmount {
+                category_id: txn.category_id?,
+                date: txn.date,
+                amount: txn.amount,
+            })
+        })
+        .chain(imported_txns.iter().filter_map(|txn| {
+            Some(CategoryAmount {
+                category_id: txn.category_id?,
+                date: txn.date,
+                amount: txn.amount,
+            })
+        }))
         .collect();
 
-    // Aggregate: category_id -> (yearly amounts BTreeMap<year, Decimal>, transaction_count)
-    let mut stats_map: HashMap<i32, (BTreeMap<i32, Decimal>, i64)> = HashMap::new();
-
-    // Process one-off transactions
-    for txn in &one_off_txns {
-        if let Some(category_id) = txn.category_id {
-            let year = txn.date.year();
-            let entry = stats_map.entry(category_id).or_insert_with(|| (BTreeMap::new(), 0));
-            *entry.0.entry(year).or_insert(Decimal::ZERO) += txn.amount;
-            entry.1 += 1;
+    let holidays = match load_holiday_calendar(&state.db).await {
+        Ok(holidays) => holidays,
+        Err(e) => {
+            error!("Failed to load holidays: {}", e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Failed to load holidays".to_string(),
+                    code: "ERROR".to_string(),
+                    success: false,
+                }),
+            ));
         }
-    }
+    };
 
     // Process recurring transactions by generating occurrences
     for rtxn in &recurring_txns {
-        let occurrences = generate_occurrences(
-            rtxn.start_date,
-            rtxn.end_date,
-            &rtxn.period,
-            query.start_date,
-            query.end_date,
-        );
+        let occurrences = recurring_transaction_occurrences(rtxn, &holidays, query.start_date, query.end_date);
 
         for date in occurrences {
             // Check if there's an instance override for this occurrence
@@ -778,123 +946,42 @@ pub async fn get_category_stats(
             };
 
             if let Some(category_id) = cat_id {
-                let year = date.year();
-                let entry = stats_map.entry(category_id).or_insert_with(|| (BTreeMap::new(), 0));
-                *entry.0.entry(year).or_insert(Decimal::ZERO) += amount;
-                entry.1 += 1;
+                amounts.push(CategoryAmount { category_id, date, amount });
             }
         }
     }
 
-    // Build children map for tree propagation
-    let mut children_map: HashMap<i32, Vec<i32>> = HashMap::new();
-    for cat in &categories {
-        if let Some(parent_id) = cat.parent_id {
-            children_map.entry(parent_id).or_default().push(cat.id);
-        }
-    }
-
-    // Compute tree-aggregated yearly totals (own + all descendants)
-    // Using post-order traversal: collect all category IDs in topological order (leaves first)
-    let all_cat_ids: Vec<i32> = categories.iter().map(|c| c.id).collect();
-    let topo_order = topological_sort_leaves_first(&all_cat_ids, &children_map);
-
-    // tree_yearly: category_id -> BTreeMap<year, Decimal> (aggregated including children)
-    // tree_count: category_id -> i64 (aggregated transaction count)
-    let mut tree_yearly: HashMap<i32, BTreeMap<i32, Decimal>> = HashMap::new();
-    let mut tree_count: HashMap<i32, i64> = HashMap::new();
-
-    // Initialize with own stats
-    for cat in &categories {
-        if let Some((yearly, count)) = stats_map.get(&cat.id) {
-            tree_yearly.insert(cat.id, yearly.clone());
-            tree_count.insert(cat.id, *count);
-        } else {
-            tree_yearly.insert(cat.id, BTreeMap::new());
-            tree_count.insert(cat.id, 0);
-        }
-    }
-
-    // Propagate children up in leaves-first order
-    for &cat_id in &topo_order {
-        if let Some(parent_id) = category_map.get(&cat_id).and_then(|c| c.parent_id) {
-            let child_yearly = tree_yearly.get(&cat_id).cloned().unwrap_or_default();
-            let child_count = tree_count.get(&cat_id).copied().unwrap_or(0);
-
-            let parent_yearly = tree_yearly.entry(parent_id).or_default();
-            for (year, amount) in &child_yearly {
-                *parent_yearly.entry(*year).or_insert(Decimal::ZERO) += amount;
-            }
-            *tree_count.entry(parent_id).or_insert(0) += child_count;
-        }
-    }
-
-    // Calculate grand total (sum of absolute values of root categories only)
-    let grand_total: Decimal = categories
+    let category_map: HashMap<i32, &category::Model> = categories
         .iter()
-        .filter(|c| c.parent_id.is_none())
-        .map(|c| {
-            tree_yearly
-                .get(&c.id)
-                .map(|y| y.values().copied().sum::<Decimal>().abs())
-                .unwrap_or(Decimal::ZERO)
-        })
-        .sum();
-
-    // Determine how many distinct years appear in the date range
-    let num_years = (query.start_date.year()..=query.end_date.year()).count() as i64;
-    let num_years_dec = Decimal::from(num_years.max(1));
+        .map(|cat| (cat.id, cat))
+        .collect();
 
     // Build response
-    let stats: Vec<CategoryStatsResponse> = categories
-        .iter()
-        .filter_map(|cat| {
-            let own_stats = stats_map.get(&cat.id);
-            let agg_yearly = tree_yearly.get(&cat.id)?;
-            let agg_count = tree_count.get(&cat.id).copied().unwrap_or(0);
-
-            // Skip categories with no transactions at all (own or aggregated)
-            let agg_total: Decimal = agg_yearly.values().copied().sum();
-            let own_total: Decimal = own_stats
-                .map(|(y, _)| y.values().copied().sum())
-                .unwrap_or(Decimal::ZERO);
-
-            if agg_count == 0 && agg_total == Decimal::ZERO {
-                return None;
-            }
-
-            let average_per_year = agg_total / num_years_dec;
-
-            let percentage = if grand_total > Decimal::ZERO {
-                use rust_decimal::prelude::ToPrimitive;
-                (agg_total.abs() / grand_total * Decimal::from(100))
-                    .to_f64()
-                    .unwrap_or(0.0)
-            } else {
-                0.0
-            };
-
-            let yearly_totals: Vec<YearlyTotal> = agg_yearly
-                .iter()
-                .map(|(year, amount)| YearlyTotal {
-                    year: *year,
-                    amount: amount.round_dp(0).to_string(),
+    let stats: Vec<CategoryStatsResponse> =
+        compute_category_stats(&categories, &amounts, query.start_date, query.end_date)
+            .into_iter()
+            .filter_map(|stats| {
+                let cat = category_map.get(&stats.category_id)?;
+                Some(CategoryStatsResponse {
+                    category_id: cat.id,
+                    category_name: cat.name.clone(),
+                    parent_id: cat.parent_id,
+                    own_total: stats.own_total.round_dp(0).to_string(),
+                    total_amount: stats.total.round_dp(0).to_string(),
+                    yearly_totals: stats
+                        .yearly_totals
+                        .iter()
+                        .map(|(year, amount)| YearlyTotal {
+                            year: *year,
+                            amount: amount.round_dp(0).to_string(),
+                        })
+                        .collect(),
+                    average_per_year: stats.average_per_year.round_dp(0).to_string(),
+                    percentage: stats.percentage,
+                    transaction_count: stats.transaction_count,
                 })
-                .collect();
-
-            Some(CategoryStatsResponse {
-                category_id: cat.id,
-                category_name: cat.name.clone(),
-                parent_id: cat.parent_id,
-                own_total: own_total.round_dp(0).to_string(),
-                total_amount: agg_total.round_dp(0).to_string(),
-                yearly_totals,
-                average_per_year: average_per_year.round_dp(0).to_string(),
-                percentage,
-                transaction_count: agg_count,
             })
-        })
-        .collect();
+            .collect();
 
     info!("Computed statistics for {} categories", stats.len());
 
@@ -905,35 +992,162 @@ pub async fn get_category_stats(
     }))
 }
 
-/// Topological sort returning leaves first (post-order) for bottom-up tree propagation.
-fn topological_sort_leaves_first(
-    all_ids: &[i32],
-    children_map: &HashMap<i32, Vec<i32>>,
-) -> Vec<i32> {
-    let mut result = Vec::with_capacity(all_ids.len());
-    let mut visited = HashMap::new();
-
-    fn visit(
-        id: i32,
-        children_map: &HashMap<i32, Vec<i32>>,
-        visited: &mut HashMap<i32, bool>,
-        result: &mut Vec<i32>,
-    ) {
-        if visited.contains_key(&id) {
-            return;
-        }
-        visited.insert(id, true);
-        if let Some(children) = children_map.get(&id) {
+/// Forecast spending in a category
+///
+/// Uses a seasonal average of the category's (and its children's) past expenses.
+/// Simulated transactions and income are ignored.
+#[utoipa::path(
+    get,
+    path = "/api/v1/categories/{id}/forecast",
+    params(
+        ("id" = i32, Path, description = "Category ID"),
+        CategoryForecastQuery
+    ),
+    responses(
+        (status = 200, description = "Category spending forecast", body = ApiResponse<CategoryForecastResponse>),
+        (status = 400, description = "Invalid query parameters", body = ErrorResponse),
+        (status = 404, description = "Category not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "categories"
+)]
+#[instrument(skip(state))]
+pub async fn get_category_forecast(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Path(id): Path<i32>,
+    Query(query): Query<CategoryForecastQuery>,
+    timezone: UserTimezone,
+) -> Result<Json<ApiResponse<CategoryForecastResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    let lookback_months = query.lookback_months.unwrap_or(12);
+    let horizon = query.horizon.unwrap_or(3);
+    debug!(
+        "Forecasting category {} with lookback {} and horizon {}",
+        id, lookback_months, horizon
+    );
+
+    if lookback_months == 0 || lookback_months > 120 || horizon == 0 || horizon > 24 {
+        warn!("Invalid forecast parameters: lookback {}, horizon {}", lookback_months, horizon);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "lookback_months must be 1-120 and horizon 1-24".to_string(),
+                code: "INVALID_FORECAST_PARAMETERS".to_string(),
+                success: false,
+            }),
+        ));
+    }
+
+    let categories = match category::Entity::find().all(&state.db).await {
+        Ok(cats) => cats,
+        Err(e) => {
+            error!("Failed to fetch categories: {}", e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Failed to fetch categories".to_string(),
+                    code: "ERROR".to_string(),
+                    success: false,
+                }),
+            ));
+        }
+    };
+
+    let Some(target) = categories.iter().find(|cat| cat.id == id) else {
+        warn!("Category {} not found", id);
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("Category with ID {} not found", id),
+                code: "NOT_FOUND".to_string(),
+                success: false,
+            }),
+        ));
+    };
+
+    // Collect the category and all of its descendants
+    let mut children_map: HashMap<i32, Vec<i32>> = HashMap::new();
+    for cat in &categories {
+        if let Some(parent_id) = cat.parent_id {
+            children_map.entry(parent_id).or_default().push(cat.id);
+        }
+    }
+    let mut category_ids = vec![id];
+    let mut index = 0;
+    while index < category_ids.len() {
+        if let Some(children) = children_map.get(&category_ids[index]) {
             for &child in children {
-                visit(child, children_map, visited, result);
+                if !category_ids.contains(&child) {
+                    category_ids.push(child);
+                }
             }
         }
-        result.push(id);
+        index += 1;
     }
 
-    for &id in all_ids {
-        visit(id, children_map, &mut visited, &mut result);
+    let forecaster = CategoryForecaster::new(lookback_months);
+    let first_month = month_start(timezone.today());
+    let history_start = first_month
+        .checked_sub_months(chrono::Months::new(lookback_months))
+        .unwrap_or(first_month);
+
+    let transactions = match access
+        .filter(one_off_transaction::Entity::find(), [one_off_transaction::Column::TargetAccountId])
+        .filter(one_off_transaction::Column::CategoryId.is_in(category_ids))
+        .filter(one_off_transaction::Column::Date.gte(history_start))
+        .filter(one_off_transaction::Column::Date.lt(first_month))
+        .filter(one_off_transaction::Column::IsSimulated.eq(false))
+        .filter(one_off_transaction::Column::Status.eq(one_off_transaction::OneOffStatus::Confirmed))
+        .filter(one_off_transaction::Column::Amount.lt(Decimal::ZERO))
+        .all(&state.db)
+        .await
+    {
+        Ok(txns) => txns,
+        Err(e) => {
+            error!("Failed to fetch transactions for category {}: {}", id, e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Failed to fetch transactions".to_string(),
+                    code: "ERROR".to_string(),
+                    success: false,
+                }),
+            ));
+        }
+    };
+
+    let mut monthly_expenses: BTreeMap<NaiveDate, Decimal> = BTreeMap::new();
+    for txn in &transactions {
+        *monthly_expenses.entry(month_start(txn.date)).or_insert(Decimal::ZERO) -= txn.amount;
     }
 
-    result
+    let average = forecaster.average(&monthly_expenses, first_month);
+    let forecast = forecaster.forecast(&monthly_expenses, first_month, horizon);
+    let suggested_budget = forecast.first().map(|entry| entry.amount.ceil()).unwrap_or(Decimal::ZERO);
+
+    info!(
+        "Forecasted category {} from {} transactions: suggested budget {}",
+        id,
+        transactions.len(),
+        suggested_budget
+    );
+
+    Ok(Json(ApiResponse {
+        data: CategoryForecastResponse {
+            category_id: target.id,
+            category_name: target.name.clone(),
+            lookback_months,
+            average_monthly: average.round_dp(2).to_string(),
+            suggested_budget: suggested_budget.to_string(),
+            forecast: forecast
+                .into_iter()
+                .map(|entry| MonthlyForecastEntry {
+                    month: entry.month,
+                    amount: entry.amount.to_string(),
+                })
+                .collect(),
+        },
+        message: "Success".to_string(),
+        success: true,
+    }))
 }
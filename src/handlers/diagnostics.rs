@@ -0,0 +1,135 @@
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{extract::State, http::StatusCode, response::Json};
+use axum_valid::Valid;
+use chrono::NaiveDateTime;
+use model::entities::diagnostic_report;
+use sea_orm::{ActiveModelTrait, Set};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, instrument, trace, warn};
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// One API call from the client-side call log
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ApiCallRecord {
+    /// HTTP method, e.g. "GET"
+    pub method: String,
+    /// Endpoint relative to the API base, e.g. "/accounts/1"
+    pub endpoint: String,
+    /// Response status; `null` when the request never got a response
+    pub status: Option<u16>,
+    /// How long the call took in milliseconds
+    pub duration_ms: Option<f64>,
+    /// Error reported by the client for this call
+    pub error: Option<String>,
+}
+
+/// Diagnostic payload sent by the frontend error boundary
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct DiagnosticReportRequest {
+    /// Panic or render error message
+    #[validate(length(min = 1, max = 10000))]
+    pub message: String,
+    /// Frontend route the user was on
+    pub route: Option<String>,
+    /// Frontend build version
+    pub app_version: Option<String>,
+    pub user_agent: Option<String>,
+    /// Most recent API calls, oldest first
+    #[serde(default)]
+    #[validate(length(max = 200))]
+    pub api_calls: Vec<ApiCallRecord>,
+}
+
+/// Receipt for a stored diagnostic report
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DiagnosticReportResponse {
+    /// Reference the user can quote when reporting the problem
+    pub id: i32,
+    pub received_at: NaiveDateTime,
+}
+
+fn diagnostics_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// Submit a frontend diagnostic report
+///
+/// Stores the error message, route, version info and the client's recent API call
+/// log so crashes users run into can be investigated afterwards.
+#[utoipa::path(
+    post,
+    path = "/api/v1/diagnostics",
+    tag = "diagnostics",
+    request_body = DiagnosticReportRequest,
+    responses(
+        (status = 201, description = "Diagnostic report stored", body = ApiResponse<DiagnosticReportResponse>),
+        (status = 400, description = "Invalid report", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state, request))]
+pub async fn submit_diagnostic_report(
+    State(state): State<AppState>,
+    Valid(Json(request)): Valid<Json<DiagnosticReportRequest>>,
+) -> Result<(StatusCode, Json<ApiResponse<DiagnosticReportResponse>>), (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering submit_diagnostic_report function");
+    debug!(
+        "Received diagnostic report for route {:?} with {} API calls",
+        request.route,
+        request.api_calls.len()
+    );
+
+    let api_calls = serde_json::to_string(&request.api_calls).map_err(|e| {
+        error!("Failed to serialize API call log: {}", e);
+        diagnostics_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "SERIALIZATION_ERROR",
+            "Failed to store diagnostic report".to_string(),
+        )
+    })?;
+
+    let report = diagnostic_report::ActiveModel {
+        received_at: Set(chrono::Local::now().naive_local()),
+        message: Set(request.message),
+        route: Set(request.route),
+        app_version: Set(request.app_version),
+        user_agent: Set(request.user_agent),
+        api_calls: Set(api_calls),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await
+    .map_err(|e| {
+        error!("Failed to store diagnostic report: {}", e);
+        diagnostics_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "DATABASE_ERROR",
+            "Failed to store diagnostic report".to_string(),
+        )
+    })?;
+
+    warn!(
+        "Frontend diagnostic report {} on route {:?} (version {:?}): {}",
+        report.id, report.route, report.app_version, report.message
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse {
+            data: DiagnosticReportResponse {
+                id: report.id,
+                received_at: report.received_at,
+            },
+            message: "Diagnostic report stored successfully".to_string(),
+            success: true,
+        }),
+    ))
+}
@@ -0,0 +1,270 @@
+use crate::helpers::access::AccountAccess;
+use crate::helpers::double_entry::{check_double_entry, DoubleEntryViolation};
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::NaiveDate;
+use model::entities::{account, one_off_transaction, recurring_transaction};
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{debug, error, info, instrument, warn};
+use utoipa::{IntoParams, ToSchema};
+
+/// Query parameters for the double-entry validation report
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct DoubleEntryReportQuery {
+    /// Only check one-off transactions on or after this date
+    pub start_date: Option<NaiveDate>,
+    /// Only check one-off transactions on or before this date
+    pub end_date: Option<NaiveDate>,
+    /// Also check simulated transactions (default: false)
+    pub include_simulated: Option<bool>,
+}
+
+/// A transaction that violates double-entry rules
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DoubleEntryReportEntry {
+    /// "one_off" or "recurring"
+    pub transaction_kind: String,
+    pub transaction_id: i32,
+    pub name: String,
+    /// Transaction date, or the start date for recurring transactions
+    pub date: NaiveDate,
+    pub amount: Decimal,
+    pub target_account_id: i32,
+    pub source_account_id: Option<i32>,
+    pub category_id: Option<i32>,
+    pub violations: Vec<DoubleEntryViolation>,
+    /// Human readable explanation of each violation
+    pub messages: Vec<String>,
+}
+
+/// Double-entry validation report
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DoubleEntryReport {
+    /// Whether new transactions are rejected when they violate the rules
+    pub strict_mode: bool,
+    /// Number of transactions checked
+    pub checked: usize,
+    pub entries: Vec<DoubleEntryReportEntry>,
+}
+
+async fn account_currencies<C: ConnectionTrait>(
+    db: &C,
+    account_ids: Option<Vec<i32>>,
+) -> Result<HashMap<i32, String>, DbErr> {
+    let mut query = account::Entity::find();
+    if let Some(account_ids) = account_ids {
+        query = query.filter(account::Column::Id.is_in(account_ids));
+    }
+    Ok(query
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|account| (account.id, account.currency_code))
+        .collect())
+}
+
+/// Reject the request with `400 Bad Request` if strict double-entry mode is on and the legs don't balance.
+///
+/// `converts_currency` is set for transfers valuing their source leg separately, see
+/// [`check_double_entry`].
+pub async fn ensure_double_entry(
+    state: &AppState,
+    target_account_id: i32,
+    source_account_id: Option<i32>,
+    category_id: Option<i32>,
+    converts_currency: bool,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    ensure_double_entry_in(
+        state,
+        &state.db,
+        target_account_id,
+        source_account_id,
+        category_id,
+        converts_currency,
+    )
+    .await
+}
+
+/// Like [`ensure_double_entry`], reading the accounts through `db`, such as an open
+/// database transaction.
+pub async fn ensure_double_entry_in<C: ConnectionTrait>(
+    state: &AppState,
+    db: &C,
+    target_account_id: i32,
+    source_account_id: Option<i32>,
+    category_id: Option<i32>,
+    converts_currency: bool,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if !state.double_entry_strict {
+        return Ok(());
+    }
+
+    let account_ids = std::iter::once(target_account_id).chain(source_account_id).collect();
+    let currencies = account_currencies(db, Some(account_ids)).await.map_err(|e| {
+        error!("Database error while checking double-entry rules: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Internal server error while checking double-entry rules".to_string(),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    })?;
+
+    let violations =
+        check_double_entry(target_account_id, source_account_id, category_id, &currencies, converts_currency);
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    warn!("Rejected transaction violating double-entry rules: {:?}", violations);
+    let messages: Vec<&str> = violations.iter().map(DoubleEntryViolation::description).collect();
+    Err((
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse {
+            error: messages.join("; "),
+            code: "DOUBLE_ENTRY_VIOLATION".to_string(),
+            success: false,
+        }),
+    ))
+}
+
+/// Validate transactions against double-entry rules
+///
+/// Lists one-off and recurring transactions that don't balance: income or expenses
+/// without a category, transfers that also post to a category, self transfers and
+/// transfers between accounts in different currencies. Useful for cleaning up data
+/// before enabling strict mode or when migrating from GnuCash. Only transactions touching
+/// an account the caller can access are checked.
+#[utoipa::path(
+    get,
+    path = "/api/v1/double-entry/report",
+    tag = "double-entry",
+    params(DoubleEntryReportQuery),
+    responses(
+        (status = 200, description = "Double-entry validation report", body = ApiResponse<DoubleEntryReport>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_double_entry_report(
+    State(state): State<AppState>,
+    Query(query): Query<DoubleEntryReportQuery>,
+    access: AccountAccess,
+) -> Result<Json<ApiResponse<DoubleEntryReport>>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("Building double-entry report");
+    let database_error = |e: DbErr| {
+        error!("Database error while building double-entry report: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Internal server error while building double-entry report".to_string(),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    };
+
+    let currencies = account_currencies(&state.db, None).await.map_err(database_error)?;
+    let include_simulated = query.include_simulated.unwrap_or(false);
+
+    let mut one_off_query = access.filter(
+        one_off_transaction::Entity::find(),
+        [one_off_transaction::Column::TargetAccountId, one_off_transaction::Column::SourceAccountId],
+    );
+    if !include_simulated {
+        one_off_query = one_off_query.filter(one_off_transaction::Column::IsSimulated.eq(false));
+    }
+    if let Some(start_date) = query.start_date {
+        one_off_query = one_off_query.filter(one_off_transaction::Column::Date.gte(start_date));
+    }
+    if let Some(end_date) = query.end_date {
+        one_off_query = one_off_query.filter(one_off_transaction::Column::Date.lte(end_date));
+    }
+    let one_offs = one_off_query
+        .order_by_asc(one_off_transaction::Column::Date)
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+
+    let mut recurring_query = access.filter(
+        recurring_transaction::Entity::find(),
+        [recurring_transaction::Column::TargetAccountId, recurring_transaction::Column::SourceAccountId],
+    );
+    if !include_simulated {
+        recurring_query = recurring_query.filter(recurring_transaction::Column::IsSimulated.eq(false));
+    }
+    let recurring = recurring_query
+        .order_by_asc(recurring_transaction::Column::StartDate)
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+
+    let mut entries = Vec::new();
+    for transaction in &one_offs {
+        let violations = check_double_entry(
+            transaction.target_account_id,
+            transaction.source_account_id,
+            transaction.category_id,
+            &currencies,
+            false,
+        );
+        if !violations.is_empty() {
+            entries.push(DoubleEntryReportEntry {
+                transaction_kind: "one_off".to_string(),
+                transaction_id: transaction.id,
+                name: transaction.name.clone(),
+                date: transaction.date,
+                amount: transaction.amount,
+                target_account_id: transaction.target_account_id,
+                source_account_id: transaction.source_account_id,
+                category_id: transaction.category_id,
+                messages: violations.iter().map(|v| v.description().to_string()).collect(),
+                violations,
+            });
+        }
+    }
+    for transaction in &recurring {
+        let violations = check_double_entry(
+            transaction.target_account_id,
+            transaction.source_account_id,
+            transaction.category_id,
+            &currencies,
+            transaction.fx_mode.is_some(),
+        );
+        if !violations.is_empty() {
+            entries.push(DoubleEntryReportEntry {
+                transaction_kind: "recurring".to_string(),
+                transaction_id: transaction.id,
+                name: transaction.name.clone(),
+                date: transaction.start_date,
+                amount: transaction.amount,
+                target_account_id: transaction.target_account_id,
+                source_account_id: transaction.source_account_id,
+                category_id: transaction.category_id,
+                messages: violations.iter().map(|v| v.description().to_string()).collect(),
+                violations,
+            });
+        }
+    }
+
+    let checked = one_offs.len() + recurring.len();
+    info!("Double-entry report: {} of {} transactions violate the rules", entries.len(), checked);
+    Ok(Json(ApiResponse {
+        data: DoubleEntryReport {
+            strict_mode: state.double_entry_strict,
+            checked,
+            entries,
+        },
+        message: "Double-entry report generated successfully".to_string(),
+        success: true,
+    }))
+}
@@ -0,0 +1,260 @@
+use crate::helpers::fx::{normalize_currency, store_rate};
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use crate::tasks::exchange_rates::{self, ExchangeRateRefreshSummary, FxFetchError};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::NaiveDate;
+use model::entities::exchange_rate;
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, instrument, trace};
+use utoipa::{IntoParams, ToSchema};
+
+/// Request body for storing an exchange rate
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateExchangeRateRequest {
+    /// ISO 4217 code of the currency being priced, e.g. "EUR"
+    pub base_currency: String,
+    /// ISO 4217 code of the currency the price is expressed in, e.g. "CZK"
+    pub quote_currency: String,
+    /// Day the rate applies to
+    pub date: NaiveDate,
+    /// Units of the quote currency per unit of the base currency
+    pub rate: Decimal,
+}
+
+/// Exchange rate response model
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ExchangeRateResponse {
+    pub id: i32,
+    pub base_currency: String,
+    pub quote_currency: String,
+    pub date: NaiveDate,
+    pub rate: Decimal,
+}
+
+impl From<exchange_rate::Model> for ExchangeRateResponse {
+    fn from(model: exchange_rate::Model) -> Self {
+        Self {
+            id: model.id,
+            base_currency: model.base_currency,
+            quote_currency: model.quote_currency,
+            date: model.date,
+            rate: model.rate,
+        }
+    }
+}
+
+/// Query parameters for filtering exchange rates
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct ExchangeRateQuery {
+    /// Filter by base currency
+    pub base_currency: Option<String>,
+    /// Filter by quote currency
+    pub quote_currency: Option<String>,
+    /// Filter by date range start
+    pub date_from: Option<NaiveDate>,
+    /// Filter by date range end
+    pub date_to: Option<NaiveDate>,
+}
+
+fn exchange_rate_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// Store an exchange rate
+///
+/// Replaces the rate already stored for the same currency pair and day.
+#[utoipa::path(
+    post,
+    path = "/api/v1/exchange-rates",
+    request_body = CreateExchangeRateRequest,
+    responses(
+        (status = 201, description = "Exchange rate stored", body = ApiResponse<ExchangeRateResponse>),
+        (status = 200, description = "Existing exchange rate replaced", body = ApiResponse<ExchangeRateResponse>),
+        (status = 400, description = "Invalid currency code or rate", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "exchange-rates"
+)]
+#[instrument(skip(state))]
+pub async fn create_exchange_rate(
+    State(state): State<AppState>,
+    Json(request): Json<CreateExchangeRateRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<ExchangeRateResponse>>), (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering create_exchange_rate function");
+    debug!("Storing exchange rate: {:?}", request);
+
+    let (Some(base_currency), Some(quote_currency)) =
+        (normalize_currency(&request.base_currency), normalize_currency(&request.quote_currency))
+    else {
+        return Err(exchange_rate_error(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_ERROR",
+            "Currencies must be three-letter ISO 4217 codes".to_string(),
+        ));
+    };
+    if base_currency == quote_currency {
+        return Err(exchange_rate_error(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_ERROR",
+            "Base and quote currency must differ".to_string(),
+        ));
+    }
+    if request.rate <= Decimal::ZERO {
+        return Err(exchange_rate_error(
+            StatusCode::BAD_REQUEST,
+            "VALIDATION_ERROR",
+            "Rate must be positive".to_string(),
+        ));
+    }
+
+    let database_error = |e: DbErr| {
+        error!("Failed to store exchange rate: {}", e);
+        exchange_rate_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "DATABASE_ERROR",
+            format!("Failed to store exchange rate: {}", e),
+        )
+    };
+
+    let (stored, created) = store_rate(&state.db, &base_currency, &quote_currency, request.date, request.rate)
+        .await
+        .map_err(database_error)?;
+    let status = if created { StatusCode::CREATED } else { StatusCode::OK };
+
+    info!(
+        "Exchange rate stored: {}/{} on {} = {}",
+        stored.base_currency, stored.quote_currency, stored.date, stored.rate
+    );
+    Ok((
+        status,
+        Json(ApiResponse {
+            data: ExchangeRateResponse::from(stored),
+            message: "Exchange rate stored successfully".to_string(),
+            success: true,
+        }),
+    ))
+}
+
+/// List exchange rates
+///
+/// Newest rates first.
+#[utoipa::path(
+    get,
+    path = "/api/v1/exchange-rates",
+    params(ExchangeRateQuery),
+    responses(
+        (status = 200, description = "List of exchange rates", body = ApiResponse<Vec<ExchangeRateResponse>>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "exchange-rates"
+)]
+#[instrument(skip(state))]
+pub async fn get_exchange_rates(
+    State(state): State<AppState>,
+    Query(query): Query<ExchangeRateQuery>,
+) -> Result<Json<ApiResponse<Vec<ExchangeRateResponse>>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_exchange_rates function");
+    debug!("Fetching exchange rates: {:?}", query);
+
+    let mut select = exchange_rate::Entity::find();
+    if let Some(base_currency) = &query.base_currency {
+        select = select.filter(exchange_rate::Column::BaseCurrency.eq(base_currency.trim().to_uppercase()));
+    }
+    if let Some(quote_currency) = &query.quote_currency {
+        select = select.filter(exchange_rate::Column::QuoteCurrency.eq(quote_currency.trim().to_uppercase()));
+    }
+    if let Some(date_from) = query.date_from {
+        select = select.filter(exchange_rate::Column::Date.gte(date_from));
+    }
+    if let Some(date_to) = query.date_to {
+        select = select.filter(exchange_rate::Column::Date.lte(date_to));
+    }
+
+    let rates = select
+        .order_by_desc(exchange_rate::Column::Date)
+        .order_by_asc(exchange_rate::Column::BaseCurrency)
+        .order_by_asc(exchange_rate::Column::QuoteCurrency)
+        .all(&state.db)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch exchange rates: {}", e);
+            exchange_rate_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DATABASE_ERROR",
+                format!("Failed to fetch exchange rates: {}", e),
+            )
+        })?;
+
+    Ok(Json(ApiResponse {
+        data: rates.into_iter().map(ExchangeRateResponse::from).collect(),
+        message: "Exchange rates retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Refresh exchange rates from the configured provider now
+///
+/// Runs the same fetch as the background refresh started with `--fx-provider`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/exchange-rates/refresh",
+    responses(
+        (status = 200, description = "Exchange rates refreshed", body = ApiResponse<ExchangeRateRefreshSummary>),
+        (status = 409, description = "No exchange rate provider configured", body = ErrorResponse),
+        (status = 502, description = "Provider unreachable or returned an unexpected response", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "exchange-rates"
+)]
+#[instrument(skip(state))]
+pub async fn refresh_exchange_rates(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<ExchangeRateRefreshSummary>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering refresh_exchange_rates function");
+
+    let Some(provider) = state.fx_provider else {
+        return Err(exchange_rate_error(
+            StatusCode::CONFLICT,
+            "NOT_CONFIGURED",
+            "No exchange rate provider configured; start the server with --fx-provider".to_string(),
+        ));
+    };
+
+    let summary = exchange_rates::refresh_exchange_rates(&state.db, provider)
+        .await
+        .map_err(|e| {
+            error!("Failed to refresh exchange rates from {:?}: {}", provider, e);
+            match e {
+                FxFetchError::Database(_) => exchange_rate_error(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "DATABASE_ERROR",
+                    format!("Failed to store exchange rates: {}", e),
+                ),
+                _ => exchange_rate_error(
+                    StatusCode::BAD_GATEWAY,
+                    "PROVIDER_ERROR",
+                    format!("Failed to fetch exchange rates from {:?}: {}", provider, e),
+                ),
+            }
+        })?;
+
+    info!("Refreshed {} exchange rates from {:?}", summary.rates_stored, provider);
+    Ok(Json(ApiResponse {
+        data: summary,
+        message: "Exchange rates refreshed successfully".to_string(),
+        success: true,
+    }))
+}
@@ -0,0 +1,635 @@
+use crate::helpers::access::AccountAccess;
+use crate::helpers::timezone::UserTimezone;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use compute::{account::AccountStateCalculator, account_stats, default_compute};
+use model::entities::{
+    account, account_allowed_user, account_document, account_note, account_share, account_statistics_exclusion,
+    account_tag, asset, budget, category, exchange_rate, forecast_snapshot, holiday, holiday_calendar,
+    imported_transaction, locked_period, manual_account_state, merchant, one_off_transaction, one_off_transaction_tag,
+    reconciliation_session, recurring_income, recurring_income_tag, recurring_transaction,
+    recurring_transaction_instance, recurring_transaction_tag, scenario, tag,
+};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, IdenStatic, Iterable, PrimaryKeyToColumn, QueryFilter,
+    QueryOrder, QuerySelect, QueryTrait, Select,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use tracing::{debug, error, info, instrument, warn};
+use utoipa::{IntoParams, ToSchema};
+
+/// Output format of an export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+/// Query parameters for the chart of accounts export
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ChartOfAccountsQuery {
+    /// Balances are computed as of this date (default: today)
+    pub cutoff_date: Option<NaiveDate>,
+    /// Start of the income/expense period (default: January 1st of the cutoff year)
+    pub period_start: Option<NaiveDate>,
+    /// `json` (default) or `csv`
+    pub format: Option<ExportFormat>,
+}
+
+/// Accounting classification of a chart of accounts entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema)]
+pub enum AccountType {
+    Asset,
+    Liability,
+    Income,
+    Expense,
+}
+
+impl AccountType {
+    /// Root name used in hierarchical account names
+    fn root(&self) -> &'static str {
+        match self {
+            AccountType::Asset => "Assets",
+            AccountType::Liability => "Liabilities",
+            AccountType::Income => "Income",
+            AccountType::Expense => "Expenses",
+        }
+    }
+
+    /// First digit of account codes of this type
+    fn code_prefix(&self) -> u32 {
+        match self {
+            AccountType::Asset => 1,
+            AccountType::Liability => 2,
+            AccountType::Income => 4,
+            AccountType::Expense => 5,
+        }
+    }
+}
+
+/// One line of the chart of accounts
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ChartOfAccountsEntry {
+    /// Sequential account code, grouped by type (1xxx assets, 2xxx liabilities, 4xxx income, 5xxx expenses)
+    pub code: String,
+    /// Hierarchical name, e.g. `Expenses:Food:Groceries`
+    pub full_name: String,
+    pub account_type: AccountType,
+    pub currency: String,
+    /// Balance in the account's natural sign (assets, liabilities, income and expenses are all positive when "normal")
+    pub balance: Decimal,
+    /// `account` or `category`
+    pub source: String,
+    /// ID of the account or category (empty for uncategorized entries)
+    pub source_id: Option<i32>,
+}
+
+/// Chart of accounts with balances at a cutoff date
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ChartOfAccountsExport {
+    pub cutoff_date: NaiveDate,
+    /// Income and expense balances cover `period_start..=cutoff_date`
+    pub period_start: NaiveDate,
+    pub entries: Vec<ChartOfAccountsEntry>,
+}
+
+impl ChartOfAccountsExport {
+    fn to_csv(&self) -> String {
+        let mut csv = String::from("code,full_name,account_type,currency,balance,source,source_id\n");
+        for entry in &self.entries {
+            let row = [
+                csv_field(&entry.code),
+                csv_field(&entry.full_name),
+                csv_field(&format!("{:?}", entry.account_type)),
+                csv_field(&entry.currency),
+                entry.balance.to_string(),
+                csv_field(&entry.source),
+                entry.source_id.map(|id| id.to_string()).unwrap_or_default(),
+            ];
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+/// Quote a CSV field when it contains separators, quotes or line breaks.
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Full `Parent:Child` name of a category, guarding against cycles.
+fn category_path(category_id: i32, categories: &HashMap<i32, category::Model>) -> String {
+    let mut names = Vec::new();
+    let mut current = categories.get(&category_id);
+    while let Some(cat) = current {
+        if names.len() > categories.len() {
+            break;
+        }
+        names.push(cat.name.replace(':', " "));
+        current = cat.parent_id.and_then(|parent_id| categories.get(&parent_id));
+    }
+    names.reverse();
+    names.join(":")
+}
+
+fn export_error(message: &str) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            error: message.to_string(),
+            code: "EXPORT_ERROR".to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// Export the chart of accounts
+///
+/// Lists every account visible to the caller (as an asset or liability) with its balance
+/// at the cutoff date and every category (as an income or expense account) with its total
+/// on those accounts over the period, one row per currency. Categories are classified by the sign of their net total.
+/// Transfers between accounts and simulated transactions are not counted in categories.
+#[utoipa::path(
+    get,
+    path = "/api/v1/export/chart-of-accounts",
+    tag = "export",
+    params(ChartOfAccountsQuery),
+    responses(
+        (status = 200, description = "Chart of accounts (JSON, or CSV when format=csv)", body = ApiResponse<ChartOfAccountsExport>),
+        (status = 400, description = "Invalid date range", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn export_chart_of_accounts(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Query(query): Query<ChartOfAccountsQuery>,
+    timezone: UserTimezone,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let today = timezone.today();
+    let cutoff_date = query.cutoff_date.unwrap_or(today);
+    let period_start = query
+        .period_start
+        .unwrap_or_else(|| NaiveDate::from_ymd_opt(cutoff_date.year(), 1, 1).unwrap_or(cutoff_date));
+    debug!("Exporting chart of accounts at {} (period from {})", cutoff_date, period_start);
+
+    if period_start > cutoff_date {
+        warn!("Invalid export range: {} > {}", period_start, cutoff_date);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "period_start must be before or equal to cutoff_date".to_string(),
+                code: "INVALID_DATE_RANGE".to_string(),
+                success: false,
+            }),
+        ));
+    }
+
+    let database_error = |e: DbErr| {
+        error!("Database error while exporting chart of accounts: {}", e);
+        export_error("Failed to export chart of accounts")
+    };
+
+    let accounts = access
+        .filter(account::Entity::find(), [account::Column::Id])
+        .order_by_asc(account::Column::Id)
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+    let categories: HashMap<i32, category::Model> = category::Entity::find()
+        .all(&state.db)
+        .await
+        .map_err(database_error)?
+        .into_iter()
+        .map(|cat| (cat.id, cat))
+        .collect();
+    let currencies: HashMap<i32, String> = accounts
+        .iter()
+        .map(|acc| (acc.id, acc.currency_code.clone()))
+        .collect();
+
+    // Account balances at the cutoff
+    let compute = default_compute(Some(today));
+    let balances: HashMap<i32, Decimal> = if accounts.is_empty() {
+        HashMap::new()
+    } else {
+        account_stats::state_at_date(&compute as &dyn AccountStateCalculator, &state.db, &accounts, cutoff_date)
+            .await
+            .map_err(|e| {
+                error!("Failed to compute balances for export: {}", e);
+                export_error("Failed to compute account balances")
+            })?
+            .into_iter()
+            .filter_map(|stats| stats.end_of_period_state.map(|balance| (stats.account_id, balance)))
+            .collect()
+    };
+
+    // Category totals over the period: (category, currency) -> net amount
+    let mut category_totals: BTreeMap<(Option<i32>, String), Decimal> = BTreeMap::new();
+    let one_offs = access
+        .filter(one_off_transaction::Entity::find(), [one_off_transaction::Column::TargetAccountId])
+        .filter(one_off_transaction::Column::Date.between(period_start, cutoff_date))
+        .filter(one_off_transaction::Column::IsSimulated.eq(false))
+        .filter(one_off_transaction::Column::Status.eq(one_off_transaction::OneOffStatus::Confirmed))
+        .filter(one_off_transaction::Column::SourceAccountId.is_null())
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+    for txn in &one_offs {
+        if let Some(currency) = currencies.get(&txn.target_account_id) {
+            *category_totals
+                .entry((txn.category_id, currency.clone()))
+                .or_insert(Decimal::ZERO) += txn.amount;
+        }
+    }
+
+    let recurring: HashMap<i32, recurring_transaction::Model> = access
+        .filter(recurring_transaction::Entity::find(), [recurring_transaction::Column::TargetAccountId])
+        .filter(recurring_transaction::Column::IsSimulated.eq(false))
+        .filter(recurring_transaction::Column::SourceAccountId.is_null())
+        .all(&state.db)
+        .await
+        .map_err(database_error)?
+        .into_iter()
+        .map(|rtxn| (rtxn.id, rtxn))
+        .collect();
+    let paid_instances = recurring_transaction_instance::Entity::find()
+        .filter(recurring_transaction_instance::Column::Status.eq(recurring_transaction_instance::InstanceStatus::Paid))
+        .filter(recurring_transaction_instance::Column::PaidDate.between(period_start, cutoff_date))
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+    for instance in &paid_instances {
+        let Some(rtxn) = recurring.get(&instance.recurring_transaction_id) else {
+            continue;
+        };
+        if let Some(currency) = currencies.get(&rtxn.target_account_id) {
+            let category_id = instance.category_id.or(rtxn.category_id);
+            *category_totals
+                .entry((category_id, currency.clone()))
+                .or_insert(Decimal::ZERO) += instance.paid_amount.unwrap_or(instance.expected_amount);
+        }
+    }
+
+    // Build rows, then number them per type
+    let mut rows: Vec<(AccountType, String, String, Decimal, &'static str, Option<i32>)> = Vec::new();
+    for acc in &accounts {
+        let balance = balances.get(&acc.id).copied().unwrap_or(Decimal::ZERO);
+        let (account_type, balance) = match acc.account_kind {
+            account::AccountKind::Debt => (AccountType::Liability, -balance),
+            _ => (AccountType::Asset, balance),
+        };
+        let name = acc.ledger_name.clone().unwrap_or_else(|| acc.name.replace(':', " "));
+        let full_name = if name.contains(':') {
+            name
+        } else {
+            format!("{}:{}", account_type.root(), name)
+        };
+        rows.push((account_type, full_name, acc.currency_code.clone(), balance, "account", Some(acc.id)));
+    }
+    for ((category_id, currency), total) in category_totals {
+        let (account_type, balance) = if total < Decimal::ZERO {
+            (AccountType::Expense, -total)
+        } else {
+            (AccountType::Income, total)
+        };
+        let path = match category_id {
+            Some(id) => category_path(id, &categories),
+            None => "Uncategorized".to_string(),
+        };
+        let full_name = format!("{}:{}", account_type.root(), path);
+        rows.push((account_type, full_name, currency, balance, "category", category_id));
+    }
+    rows.sort_by(|a, b| (a.0, &a.1, &a.2).cmp(&(b.0, &b.1, &b.2)));
+
+    let mut counters: HashMap<AccountType, u32> = HashMap::new();
+    let entries: Vec<ChartOfAccountsEntry> = rows
+        .into_iter()
+        .map(|(account_type, full_name, currency, balance, source, source_id)| {
+            let counter = counters.entry(account_type).or_insert(0);
+            *counter += 1;
+            ChartOfAccountsEntry {
+                code: format!("{}{:03}", account_type.code_prefix(), *counter),
+                full_name,
+                account_type,
+                currency,
+                balance,
+                source: source.to_string(),
+                source_id,
+            }
+        })
+        .collect();
+
+    let export = ChartOfAccountsExport {
+        cutoff_date,
+        period_start,
+        entries,
+    };
+    info!("Exported chart of accounts with {} entries", export.entries.len());
+
+    match query.format.unwrap_or_default() {
+        ExportFormat::Csv => Ok((
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"chart-of-accounts-{}.csv\"", cutoff_date),
+                ),
+            ],
+            export.to_csv(),
+        )
+            .into_response()),
+        ExportFormat::Json => Ok(Json(ApiResponse {
+            data: export,
+            message: "Chart of accounts exported successfully".to_string(),
+            success: true,
+        })
+        .into_response()),
+    }
+}
+
+/// Query parameters for the full data export
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct FullExportQuery {
+    /// `json` (default, one array per table) or `csv` (one file per table)
+    pub format: Option<ExportFormat>,
+}
+
+/// Rows of one database table in a full export
+pub(crate) struct ExportTable {
+    pub(crate) name: String,
+    /// Column names in schema order
+    pub(crate) columns: Vec<String>,
+    pub(crate) rows: Vec<serde_json::Value>,
+}
+
+impl ExportTable {
+    fn to_csv(&self) -> String {
+        let mut csv = self.columns.join(",");
+        csv.push('\n');
+        for row in &self.rows {
+            let fields: Vec<String> = self
+                .columns
+                .iter()
+                .map(|column| match row.get(column) {
+                    None | Some(serde_json::Value::Null) => String::new(),
+                    Some(serde_json::Value::String(value)) => csv_field(value),
+                    Some(value) => csv_field(&value.to_string()),
+                })
+                .collect();
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+/// Table of contents stored as `manifest.json` in the archive
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ExportManifest {
+    pub(crate) exported_at: DateTime<Utc>,
+    pub(crate) format: ExportFormat,
+    pub(crate) tables: Vec<ExportManifestEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ExportManifestEntry {
+    pub(crate) table: String,
+    pub(crate) file: String,
+    pub(crate) rows: usize,
+}
+
+/// Rows of an entity's table picked by `select`, ordered by primary key.
+async fn export_rows<E: EntityTrait>(db: &DatabaseConnection, mut select: Select<E>) -> Result<ExportTable, DbErr> {
+    for key in E::PrimaryKey::iter() {
+        select = select.order_by_asc(key.into_column());
+    }
+    Ok(ExportTable {
+        name: E::default().table_name().to_string(),
+        columns: E::Column::iter().map(|column| column.as_str().to_string()).collect(),
+        rows: select.into_json().all(db).await?,
+    })
+}
+
+/// All rows of an entity's table, ordered by primary key.
+async fn export_table<E: EntityTrait>(db: &DatabaseConnection) -> Result<ExportTable, DbErr> {
+    export_rows(db, E::find()).await
+}
+
+/// Rows of an entity's table booked on an account in `access`.
+async fn export_account_rows<E: EntityTrait>(
+    db: &DatabaseConnection,
+    access: &AccountAccess,
+    columns: impl IntoIterator<Item = E::Column>,
+) -> Result<ExportTable, DbErr> {
+    export_rows(db, access.filter(E::find(), columns)).await
+}
+
+/// Every table holding the user's financial data, parents before children.
+///
+/// Rows booked on accounts are limited to the ones `access` can see; categories, tags,
+/// scenarios, budgets and the other settings not tied to an account are exported in full.
+/// Users, sessions, tokens, notification channels and the audit log are not exported.
+///
+/// Keep in sync with the tables restored by [`crate::handlers::restore`].
+pub(crate) async fn export_tables(db: &DatabaseConnection, access: &AccountAccess) -> Result<Vec<ExportTable>, DbErr> {
+    let one_off_ids = access
+        .filter(
+            one_off_transaction::Entity::find(),
+            [one_off_transaction::Column::TargetAccountId, one_off_transaction::Column::SourceAccountId],
+        )
+        .select_only()
+        .column(one_off_transaction::Column::Id)
+        .into_query();
+    let recurring_ids = || {
+        access
+            .filter(
+                recurring_transaction::Entity::find(),
+                [recurring_transaction::Column::TargetAccountId, recurring_transaction::Column::SourceAccountId],
+            )
+            .select_only()
+            .column(recurring_transaction::Column::Id)
+            .into_query()
+    };
+    let income_ids = access
+        .filter(recurring_income::Entity::find(), [recurring_income::Column::TargetAccountId])
+        .select_only()
+        .column(recurring_income::Column::Id)
+        .into_query();
+
+    Ok(vec![
+        export_account_rows::<account::Entity>(db, access, [account::Column::Id]).await?,
+        export_table::<category::Entity>(db).await?,
+        export_table::<tag::Entity>(db).await?,
+        export_account_rows::<account_tag::Entity>(db, access, [account_tag::Column::AccountId]).await?,
+        export_account_rows::<account_share::Entity>(db, access, [account_share::Column::AccountId]).await?,
+        export_account_rows::<account_allowed_user::Entity>(db, access, [account_allowed_user::Column::AccountId])
+            .await?,
+        export_account_rows::<account_note::Entity>(db, access, [account_note::Column::AccountId]).await?,
+        export_account_rows::<account_document::Entity>(db, access, [account_document::Column::AccountId]).await?,
+        export_table::<scenario::Entity>(db).await?,
+        export_table::<budget::Entity>(db).await?,
+        export_table::<exchange_rate::Entity>(db).await?,
+        export_table::<locked_period::Entity>(db).await?,
+        export_table::<holiday_calendar::Entity>(db).await?,
+        export_table::<holiday::Entity>(db).await?,
+        export_table::<merchant::Entity>(db).await?,
+        export_account_rows::<reconciliation_session::Entity>(db, access, [reconciliation_session::Column::AccountId])
+            .await?,
+        export_account_rows::<manual_account_state::Entity>(db, access, [manual_account_state::Column::AccountId])
+            .await?,
+        export_account_rows::<account_statistics_exclusion::Entity>(
+            db,
+            access,
+            [account_statistics_exclusion::Column::AccountId],
+        )
+        .await?,
+        export_account_rows::<forecast_snapshot::Entity>(db, access, [forecast_snapshot::Column::AccountId]).await?,
+        export_account_rows::<one_off_transaction::Entity>(
+            db,
+            access,
+            [one_off_transaction::Column::TargetAccountId, one_off_transaction::Column::SourceAccountId],
+        )
+        .await?,
+        export_rows(
+            db,
+            one_off_transaction_tag::Entity::find()
+                .filter(one_off_transaction_tag::Column::TransactionId.in_subquery(one_off_ids)),
+        )
+        .await?,
+        export_account_rows::<asset::Entity>(db, access, [asset::Column::AccountId]).await?,
+        export_account_rows::<recurring_transaction::Entity>(
+            db,
+            access,
+            [recurring_transaction::Column::TargetAccountId, recurring_transaction::Column::SourceAccountId],
+        )
+        .await?,
+        export_rows(
+            db,
+            recurring_transaction_tag::Entity::find()
+                .filter(recurring_transaction_tag::Column::TransactionId.in_subquery(recurring_ids())),
+        )
+        .await?,
+        export_account_rows::<imported_transaction::Entity>(db, access, [imported_transaction::Column::AccountId])
+            .await?,
+        export_rows(
+            db,
+            recurring_transaction_instance::Entity::find().filter(
+                recurring_transaction_instance::Column::RecurringTransactionId.in_subquery(recurring_ids()),
+            ),
+        )
+        .await?,
+        export_account_rows::<recurring_income::Entity>(db, access, [recurring_income::Column::TargetAccountId])
+            .await?,
+        export_rows(
+            db,
+            recurring_income_tag::Entity::find().filter(recurring_income_tag::Column::IncomeId.in_subquery(income_ids)),
+        )
+        .await?,
+    ])
+}
+
+pub(crate) fn build_archive(tables: &[ExportTable], format: ExportFormat) -> zip::result::ZipResult<Vec<u8>> {
+    let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    let mut archive = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let mut manifest = ExportManifest {
+        exported_at: Utc::now(),
+        format,
+        tables: Vec::new(),
+    };
+
+    for table in tables {
+        let (file, content) = match format {
+            ExportFormat::Json => (
+                format!("{}.json", table.name),
+                serde_json::to_vec_pretty(&table.rows).map_err(std::io::Error::from)?,
+            ),
+            ExportFormat::Csv => (format!("{}.csv", table.name), table.to_csv().into_bytes()),
+        };
+        archive.start_file(file.as_str(), options)?;
+        archive.write_all(&content)?;
+        manifest.tables.push(ExportManifestEntry {
+            table: table.name.clone(),
+            file,
+            rows: table.rows.len(),
+        });
+    }
+
+    archive.start_file("manifest.json", options)?;
+    archive.write_all(&serde_json::to_vec_pretty(&manifest).map_err(std::io::Error::from)?)?;
+    Ok(archive.finish()?.into_inner())
+}
+
+/// Export all data as a zip archive
+///
+/// Contains the caller's accounts with their shares, notes, documents, reconciliation
+/// sessions, manual states, statistics exclusions and forecast snapshots, the one-off,
+/// recurring and imported transactions, recurring instances and incomes booked on them,
+/// including the tag assignments, and the shared settings: categories, tags with their
+/// limits, scenarios, budgets, exchange rates, locked periods, holidays and merchants.
+/// Each table is one file named after it. Values are exported as stored, with ids kept, so
+/// the archive can be restored with `POST /api/v1/import/full`. A `manifest.json` lists
+/// the files and their row counts.
+#[utoipa::path(
+    get,
+    path = "/api/v1/export/full",
+    tag = "export",
+    params(FullExportQuery),
+    responses(
+        (status = 200, description = "Zip archive with one JSON or CSV file per table", content_type = "application/zip"),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn export_full(
+    State(state): State<AppState>,
+    Query(query): Query<FullExportQuery>,
+    timezone: UserTimezone,
+    access: AccountAccess,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let format = query.format.unwrap_or_default();
+    debug!("Exporting all data as {:?}", format);
+
+    let tables = export_tables(&state.db, &access).await.map_err(|e| {
+        error!("Database error while exporting all data: {}", e);
+        export_error("Failed to export data")
+    })?;
+    let archive = build_archive(&tables, format).map_err(|e| {
+        error!("Failed to build export archive: {}", e);
+        export_error("Failed to build export archive")
+    })?;
+
+    info!(
+        "Exported {} rows from {} tables ({} bytes)",
+        tables.iter().map(|table| table.rows.len()).sum::<usize>(),
+        tables.len(),
+        archive.len()
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"finrust-export-{}.zip\"", timezone.today()),
+            ),
+        ],
+        archive,
+    )
+        .into_response())
+}
@@ -0,0 +1,452 @@
+use crate::helpers::access::AccountAccess;
+use crate::helpers::forecast_snapshots::{balances_between, record_account_forecast_snapshots, ForecastSnapshotError};
+use crate::helpers::scenarios::ScenarioPreferences;
+use crate::helpers::stats::load_statistics_exclusions;
+use crate::helpers::timezone::UserTimezone;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::NaiveDate;
+use compute::balance_forecast::{BalanceForecastCalculator, DEFAULT_LOOKBACK_MONTHS};
+use compute::forecast_accuracy::{summarize_accuracy, ForecastOutcome, HorizonAccuracy};
+use model::entities::{account, category, forecast_snapshot};
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{debug, error, info, instrument, trace, warn};
+use utoipa::{IntoParams, ToSchema};
+
+/// Stored projection of an account balance
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ForecastSnapshotResponse {
+    pub id: i32,
+    pub account_id: i32,
+    pub made_on: NaiveDate,
+    pub horizon_days: i32,
+    pub target_date: NaiveDate,
+    pub projected_balance: Decimal,
+}
+
+impl From<forecast_snapshot::Model> for ForecastSnapshotResponse {
+    fn from(model: forecast_snapshot::Model) -> Self {
+        Self {
+            id: model.id,
+            account_id: model.account_id,
+            made_on: model.made_on,
+            horizon_days: model.horizon_days,
+            target_date: model.target_date,
+            projected_balance: model.projected_balance,
+        }
+    }
+}
+
+/// Query parameters for the forecast accuracy report
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct ForecastAccuracyQuery {
+    /// Only evaluate projections of this account
+    pub account_id: Option<i32>,
+}
+
+/// A past projection compared with the balance actually reached
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct EvaluatedForecastResponse {
+    pub account_id: i32,
+    pub made_on: NaiveDate,
+    pub horizon_days: i32,
+    pub target_date: NaiveDate,
+    pub projected_balance: Decimal,
+    pub actual_balance: Decimal,
+    /// Projected minus actual; positive when the projection was too optimistic
+    pub error: Decimal,
+}
+
+/// Accuracy of all evaluated projections of one horizon
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct HorizonAccuracyResponse {
+    pub horizon_days: i32,
+    pub samples: usize,
+    /// Average signed error; positive means projections tend to be too optimistic
+    pub mean_error: Decimal,
+    pub mean_absolute_error: Decimal,
+    /// Average absolute error relative to the actual balance, in percent
+    pub mean_absolute_percentage_error: Option<Decimal>,
+}
+
+impl From<HorizonAccuracy> for HorizonAccuracyResponse {
+    fn from(accuracy: HorizonAccuracy) -> Self {
+        Self {
+            horizon_days: accuracy.horizon_days,
+            samples: accuracy.samples,
+            mean_error: accuracy.mean_error,
+            mean_absolute_error: accuracy.mean_absolute_error,
+            mean_absolute_percentage_error: accuracy.mean_absolute_percentage_error,
+        }
+    }
+}
+
+/// How past projections compare with what happened
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ForecastAccuracyResponse {
+    /// Shortest horizon first
+    pub horizons: Vec<HorizonAccuracyResponse>,
+    /// Projections whose target date has passed, most recent target first
+    pub forecasts: Vec<EvaluatedForecastResponse>,
+}
+
+/// Longest forecast horizon in months
+const MAX_FORECAST_MONTHS: u32 = 60;
+/// Longest spending history in months
+const MAX_LOOKBACK_MONTHS: u32 = 120;
+
+/// Query parameters for an account balance forecast
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct AccountForecastQuery {
+    /// Months to project after today, 1 to 60 (default 12)
+    pub months: Option<u32>,
+    /// Past full months the spending averages look at, 1 to 120 (default 6)
+    pub lookback_months: Option<u32>,
+    /// Scenario to include; defaults to the account owner's active scenario
+    pub scenario_id: Option<i32>,
+}
+
+/// Projected balance on one day with its 80% confidence band
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AccountForecastPoint {
+    pub date: NaiveDate,
+    pub balance: Decimal,
+    pub lower: Decimal,
+    pub upper: Decimal,
+}
+
+/// Expected monthly one-off spending of one category
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CategorySpendingForecast {
+    /// Empty for uncategorized transactions
+    pub category_id: Option<i32>,
+    pub category_name: Option<String>,
+    /// Average monthly spend, as a positive amount
+    pub monthly_average: Decimal,
+}
+
+/// Projected balances of an account
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AccountForecastResponse {
+    pub account_id: i32,
+    pub months: u32,
+    pub lookback_months: u32,
+    /// Expected one-off spending per month over all categories
+    pub monthly_spending: Decimal,
+    /// How much the monthly spending varied within the lookback (standard deviation)
+    pub monthly_deviation: Decimal,
+    /// Largest spenders first
+    pub categories: Vec<CategorySpendingForecast>,
+    /// One point per day from today to the end of the horizon
+    pub points: Vec<AccountForecastPoint>,
+}
+
+fn forecast_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+fn snapshot_error(e: ForecastSnapshotError) -> (StatusCode, Json<ErrorResponse>) {
+    error!("Failed to evaluate forecast snapshots: {}", e);
+    let code = match e {
+        ForecastSnapshotError::Database(_) => "DATABASE_ERROR",
+        _ => "COMPUTE_ERROR",
+    };
+    forecast_error(StatusCode::INTERNAL_SERVER_ERROR, code, e.to_string())
+}
+
+/// Record today's forecast snapshots
+///
+/// Projects the balance of every account visible to the caller 30 and 90 days ahead and
+/// stores the projections, replacing today's earlier ones. The server also does this once
+/// a day for all accounts. Snapshots are shared by all users, so "today" is the date in
+/// the server's timezone.
+#[utoipa::path(
+    post,
+    path = "/api/v1/forecast/snapshots",
+    responses(
+        (status = 200, description = "Forecast snapshots recorded", body = ApiResponse<Vec<ForecastSnapshotResponse>>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "forecast"
+)]
+#[instrument(skip(state))]
+pub async fn create_forecast_snapshots(
+    State(state): State<AppState>,
+    access: AccountAccess,
+) -> Result<Json<ApiResponse<Vec<ForecastSnapshotResponse>>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering create_forecast_snapshots function");
+    let today = compute::today_in(&state.timezone);
+
+    let accounts = access
+        .filter(account::Entity::find(), [account::Column::Id])
+        .all(&state.db)
+        .await
+        .map_err(|e| snapshot_error(ForecastSnapshotError::Database(e)))?;
+    let snapshots = record_account_forecast_snapshots(&state.db, &accounts, today)
+        .await
+        .map_err(snapshot_error)?;
+
+    info!("Recorded {} forecast snapshots for {}", snapshots.len(), today);
+    Ok(Json(ApiResponse {
+        data: snapshots.into_iter().map(ForecastSnapshotResponse::from).collect(),
+        message: "Forecast snapshots recorded successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Get forecast accuracy
+///
+/// Compares stored projections whose target date has passed with the balance the
+/// account actually had on that day, per projection horizon.
+#[utoipa::path(
+    get,
+    path = "/api/v1/forecast/accuracy",
+    params(ForecastAccuracyQuery),
+    responses(
+        (status = 200, description = "Forecast accuracy", body = ApiResponse<ForecastAccuracyResponse>),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "forecast"
+)]
+#[instrument(skip(state))]
+pub async fn get_forecast_accuracy(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Query(query): Query<ForecastAccuracyQuery>,
+    timezone: UserTimezone,
+) -> Result<Json<ApiResponse<ForecastAccuracyResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_forecast_accuracy function");
+    let today = timezone.today();
+    debug!("Evaluating forecast accuracy on {}: {:?}", today, query);
+
+    let database_error = |e: DbErr| snapshot_error(ForecastSnapshotError::Database(e));
+
+    let mut select = access
+        .filter(forecast_snapshot::Entity::find(), [forecast_snapshot::Column::AccountId])
+        .filter(forecast_snapshot::Column::TargetDate.lte(today));
+    if let Some(account_id) = query.account_id {
+        if !access.can_access(account_id) {
+            warn!("Account {} not found for forecast accuracy", account_id);
+            return Err(forecast_error(
+                StatusCode::NOT_FOUND,
+                "ACCOUNT_NOT_FOUND",
+                format!("Account with id {} not found", account_id),
+            ));
+        }
+        select = select.filter(forecast_snapshot::Column::AccountId.eq(account_id));
+    }
+    let snapshots = select
+        .order_by_desc(forecast_snapshot::Column::TargetDate)
+        .order_by_asc(forecast_snapshot::Column::AccountId)
+        .order_by_asc(forecast_snapshot::Column::HorizonDays)
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+
+    let (Some(earliest), Some(latest)) = (
+        snapshots.iter().map(|snapshot| snapshot.target_date).min(),
+        snapshots.iter().map(|snapshot| snapshot.target_date).max(),
+    ) else {
+        return Ok(Json(ApiResponse {
+            data: ForecastAccuracyResponse {
+                horizons: Vec::new(),
+                forecasts: Vec::new(),
+            },
+            message: "No forecast has reached its target date yet".to_string(),
+            success: true,
+        }));
+    };
+
+    let account_ids: Vec<i32> = snapshots.iter().map(|snapshot| snapshot.account_id).collect();
+    let accounts = account::Entity::find()
+        .filter(account::Column::Id.is_in(account_ids))
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+    let actual = balances_between(&state.db, &accounts, today, earliest, latest)
+        .await
+        .map_err(snapshot_error)?;
+
+    let forecasts: Vec<EvaluatedForecastResponse> = snapshots
+        .into_iter()
+        .filter_map(|snapshot| {
+            let actual_balance = *actual.get(&(snapshot.account_id, snapshot.target_date))?;
+            Some(EvaluatedForecastResponse {
+                error: snapshot.projected_balance - actual_balance,
+                account_id: snapshot.account_id,
+                made_on: snapshot.made_on,
+                horizon_days: snapshot.horizon_days,
+                target_date: snapshot.target_date,
+                projected_balance: snapshot.projected_balance,
+                actual_balance,
+            })
+        })
+        .collect();
+    let outcomes: Vec<ForecastOutcome> = forecasts
+        .iter()
+        .map(|forecast| ForecastOutcome {
+            horizon_days: forecast.horizon_days,
+            projected: forecast.projected_balance,
+            actual: forecast.actual_balance,
+        })
+        .collect();
+
+    info!("Evaluated {} past forecasts", forecasts.len());
+    Ok(Json(ApiResponse {
+        data: ForecastAccuracyResponse {
+            horizons: summarize_accuracy(&outcomes)
+                .into_iter()
+                .map(HorizonAccuracyResponse::from)
+                .collect(),
+            forecasts,
+        },
+        message: "Forecast accuracy retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Forecast an account balance
+///
+/// Projects the balance from today for the requested number of months. Recurring
+/// transactions, recurring incomes and planned transactions are carried forward as in the
+/// timeseries; on top of that the average monthly one-off spending per category over the
+/// lookback is spent evenly, and the band around the projection grows with how much that
+/// spending varied.
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{account_id}/forecast",
+    params(
+        ("account_id" = i32, Path, description = "Account ID"),
+        AccountForecastQuery
+    ),
+    responses(
+        (status = 200, description = "Projected balances", body = ApiResponse<AccountForecastResponse>),
+        (status = 400, description = "Invalid forecast parameters", body = ErrorResponse),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "forecast"
+)]
+#[instrument(skip(state))]
+pub async fn get_account_forecast(
+    Path(account_id): Path<i32>,
+    State(state): State<AppState>,
+    Query(query): Query<AccountForecastQuery>,
+    access: AccountAccess,
+    timezone: UserTimezone,
+) -> Result<Json<ApiResponse<AccountForecastResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_account_forecast function for account_id: {}", account_id);
+
+    let months = query.months.unwrap_or(12);
+    let lookback_months = query.lookback_months.unwrap_or(DEFAULT_LOOKBACK_MONTHS);
+    if !(1..=MAX_FORECAST_MONTHS).contains(&months) || !(1..=MAX_LOOKBACK_MONTHS).contains(&lookback_months) {
+        warn!("Invalid forecast parameters: months {}, lookback {}", months, lookback_months);
+        return Err(forecast_error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_FORECAST_PARAMETERS",
+            format!(
+                "months must be 1-{} and lookback_months 1-{}",
+                MAX_FORECAST_MONTHS, MAX_LOOKBACK_MONTHS
+            ),
+        ));
+    }
+
+    let database_error = |e: DbErr| {
+        error!("Database error while forecasting account {}: {}", account_id, e);
+        forecast_error(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", e.to_string())
+    };
+
+    let account_model = account::Entity::find_by_id(account_id)
+        .one(&state.db)
+        .await
+        .map_err(database_error)?
+        .filter(|account| access.can_access(account.id))
+        .ok_or_else(|| {
+            forecast_error(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                format!("Account {} not found", account_id),
+            )
+        })?;
+
+    let scenario_id = ScenarioPreferences::load(&state.db)
+        .await
+        .map_err(database_error)?
+        .resolve(query.scenario_id, std::slice::from_ref(&account_model));
+    let exclusions = load_statistics_exclusions(&state.db, &[account_id])
+        .await
+        .map_err(database_error)?;
+
+    let forecast = BalanceForecastCalculator::new(timezone.today())
+        .with_lookback_months(lookback_months)
+        .with_scenario(scenario_id)
+        .with_exclusions(exclusions)
+        .forecast(&state.db, &account_model, months)
+        .await
+        .map_err(|e| {
+            error!("Failed to forecast account {}: {}", account_id, e);
+            forecast_error(StatusCode::INTERNAL_SERVER_ERROR, "COMPUTE_ERROR", e.to_string())
+        })?;
+
+    let category_ids: Vec<i32> = forecast.categories.iter().filter_map(|spending| spending.category_id).collect();
+    let category_names: HashMap<i32, String> = category::Entity::find()
+        .filter(category::Column::Id.is_in(category_ids))
+        .all(&state.db)
+        .await
+        .map_err(database_error)?
+        .into_iter()
+        .map(|category| (category.id, category.name))
+        .collect();
+
+    debug!(
+        "Forecast {} days of account {} with monthly spending {}",
+        forecast.points.len(),
+        account_id,
+        forecast.monthly_spending
+    );
+    Ok(Json(ApiResponse {
+        data: AccountForecastResponse {
+            account_id,
+            months,
+            lookback_months,
+            monthly_spending: forecast.monthly_spending,
+            monthly_deviation: forecast.monthly_deviation,
+            categories: forecast
+                .categories
+                .into_iter()
+                .map(|spending| CategorySpendingForecast {
+                    category_name: spending.category_id.and_then(|id| category_names.get(&id).cloned()),
+                    category_id: spending.category_id,
+                    monthly_average: spending.monthly_average,
+                })
+                .collect(),
+            points: forecast
+                .points
+                .into_iter()
+                .map(|point| AccountForecastPoint {
+                    date: point.date,
+                    balance: point.balance,
+                    lower: point.lower,
+                    upper: point.upper,
+                })
+                .collect(),
+        },
+        message: "Account forecast computed successfully".to_string(),
+        success: true,
+    }))
+}
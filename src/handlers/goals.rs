@@ -0,0 +1,160 @@
+use crate::helpers::access::AccountAccess;
+use crate::helpers::forecast_snapshots::balances_between;
+use crate::helpers::timezone::UserTimezone;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::{Months, NaiveDate};
+use compute::goals::{goal_progress, monthly_recurring_inflow};
+use model::entities::account;
+use rust_decimal::Decimal;
+use sea_orm::{DbErr, EntityTrait};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, instrument, trace, warn};
+use utoipa::{IntoParams, ToSchema};
+
+/// Months ahead the required contribution is spread over when no target date is given
+const DEFAULT_GOAL_MONTHS: u32 = 12;
+
+/// Query parameters for savings goal progress
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct GoalProgressQuery {
+    /// Day the target should be reached by, for the required monthly contribution
+    /// (default: a year from today)
+    pub target_date: Option<NaiveDate>,
+}
+
+/// Progress of an account towards its target amount
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct GoalProgressResponse {
+    pub account_id: i32,
+    pub target_amount: Decimal,
+    pub current_balance: Decimal,
+    /// What is still missing; zero once the target is reached
+    pub remaining: Decimal,
+    /// Share of the target saved, 0-100
+    pub percent_complete: Decimal,
+    /// Recurring transactions and incomes paid into the account, per month
+    pub monthly_inflow: Decimal,
+    pub target_date: NaiveDate,
+    /// Monthly saving needed to reach the target by `target_date`
+    pub required_monthly_contribution: Decimal,
+    /// When the recurring inflows reach the target; empty if they never do
+    pub projected_completion_date: Option<NaiveDate>,
+    /// Whether the recurring inflows reach the target by `target_date`
+    pub on_track: bool,
+}
+
+fn goal_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// Get savings goal progress
+///
+/// Compares the account balance today with its target amount and projects when the
+/// recurring inflows into the account reach it. One-off deposits are not projected.
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{account_id}/goal-progress",
+    params(
+        ("account_id" = i32, Path, description = "Account ID"),
+        GoalProgressQuery
+    ),
+    responses(
+        (status = 200, description = "Goal progress", body = ApiResponse<GoalProgressResponse>),
+        (status = 400, description = "Account has no target amount", body = ErrorResponse),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "accounts"
+)]
+#[instrument(skip(state))]
+pub async fn get_goal_progress(
+    Path(account_id): Path<i32>,
+    State(state): State<AppState>,
+    Query(query): Query<GoalProgressQuery>,
+    access: AccountAccess,
+    timezone: UserTimezone,
+) -> Result<Json<ApiResponse<GoalProgressResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_goal_progress function for account_id: {}", account_id);
+
+    let database_error = |e: DbErr| {
+        error!("Database error while computing goal progress of account {}: {}", account_id, e);
+        goal_error(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", e.to_string())
+    };
+    let compute_error = |e: String| {
+        error!("Failed to compute goal progress of account {}: {}", account_id, e);
+        goal_error(StatusCode::INTERNAL_SERVER_ERROR, "COMPUTE_ERROR", e)
+    };
+
+    let account_model = account::Entity::find_by_id(account_id)
+        .one(&state.db)
+        .await
+        .map_err(database_error)?
+        .filter(|account| access.can_access(account.id))
+        .ok_or_else(|| {
+            goal_error(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                format!("Account {} not found", account_id),
+            )
+        })?;
+
+    let Some(target_amount) = account_model.target_amount.filter(|target| *target > Decimal::ZERO) else {
+        warn!("Account {} has no target amount", account_id);
+        return Err(goal_error(
+            StatusCode::BAD_REQUEST,
+            "NO_TARGET_AMOUNT",
+            format!("Account {} has no positive target_amount", account_id),
+        ));
+    };
+
+    let today = timezone.today();
+    let target_date = query
+        .target_date
+        .or_else(|| today.checked_add_months(Months::new(DEFAULT_GOAL_MONTHS)))
+        .unwrap_or(today);
+
+    let current_balance = balances_between(&state.db, std::slice::from_ref(&account_model), today, today, today)
+        .await
+        .map_err(|e| compute_error(e.to_string()))?
+        .get(&(account_id, today))
+        .copied()
+        .unwrap_or(Decimal::ZERO);
+    let monthly_inflow = monthly_recurring_inflow(&state.db, account_id, today)
+        .await
+        .map_err(|e| compute_error(e.to_string()))?;
+
+    let progress = goal_progress(target_amount, current_balance, monthly_inflow, today, target_date);
+    debug!(
+        "Account {} is {}% towards its goal, projected completion {:?}",
+        account_id, progress.percent_complete, progress.projected_completion_date
+    );
+
+    Ok(Json(ApiResponse {
+        data: GoalProgressResponse {
+            account_id,
+            target_amount: progress.target_amount,
+            current_balance: progress.current_balance,
+            remaining: progress.remaining,
+            percent_complete: progress.percent_complete,
+            monthly_inflow: progress.monthly_inflow,
+            target_date,
+            required_monthly_contribution: progress.required_monthly_contribution,
+            on_track: progress.projected_completion_date.is_some_and(|date| date <= target_date),
+            projected_completion_date: progress.projected_completion_date,
+        },
+        message: "Goal progress retrieved successfully".to_string(),
+        success: true,
+    }))
+}
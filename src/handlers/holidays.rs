@@ -0,0 +1,384 @@
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::{Datelike, NaiveDate};
+use compute::holidays::HolidayCountry;
+use model::entities::{holiday, holiday_calendar};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, instrument, trace, warn};
+use utoipa::{IntoParams, ToSchema};
+
+/// Date range of the holidays to list
+#[derive(Debug, Deserialize, IntoParams, ToSchema)]
+pub struct HolidayQuery {
+    /// First day of the range (inclusive)
+    pub start_date: NaiveDate,
+    /// Last day of the range (inclusive)
+    pub end_date: NaiveDate,
+}
+
+/// Request body for adding a custom holiday
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateHolidayRequest {
+    pub date: NaiveDate,
+    /// e.g. "Regional holiday" or "Bank closed"
+    pub name: String,
+}
+
+/// A non-business day, either custom or from an enabled country calendar
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct HolidayResponse {
+    /// ID of a custom holiday, absent for country holidays
+    pub id: Option<i32>,
+    pub date: NaiveDate,
+    pub name: String,
+    /// Country of the calendar the holiday comes from, absent for custom holidays
+    pub country_code: Option<String>,
+}
+
+impl From<holiday::Model> for HolidayResponse {
+    fn from(model: holiday::Model) -> Self {
+        Self {
+            id: Some(model.id),
+            date: model.date,
+            name: model.name,
+            country_code: None,
+        }
+    }
+}
+
+/// A built-in country holiday calendar
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct HolidayCalendarInfo {
+    /// ISO 3166-1 alpha-2 code, e.g. "CZ"
+    pub code: String,
+    pub name: String,
+}
+
+impl From<HolidayCountry> for HolidayCalendarInfo {
+    fn from(country: HolidayCountry) -> Self {
+        Self {
+            code: country.code().to_string(),
+            name: country.name().to_string(),
+        }
+    }
+}
+
+/// Enabled and available country holiday calendars
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct HolidayCalendarsResponse {
+    pub enabled: Vec<HolidayCalendarInfo>,
+    pub available: Vec<HolidayCalendarInfo>,
+}
+
+/// Request body for choosing the enabled country calendars
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UpdateHolidayCalendarsRequest {
+    /// Replaces the enabled calendars; an empty list disables all of them
+    pub country_codes: Vec<String>,
+}
+
+fn database_error(e: DbErr) -> (StatusCode, Json<ErrorResponse>) {
+    error!("Database error while handling holidays: {}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            error: format!("Database error: {}", e),
+            code: "DATABASE_ERROR".to_string(),
+            success: false,
+        }),
+    )
+}
+
+async fn enabled_countries(db: &impl sea_orm::ConnectionTrait) -> Result<Vec<HolidayCountry>, DbErr> {
+    Ok(holiday_calendar::Entity::find()
+        .order_by_asc(holiday_calendar::Column::CountryCode)
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|calendar| calendar.country_code.parse().ok())
+        .collect())
+}
+
+/// List holidays
+///
+/// Custom holidays and the holidays of the enabled country calendars within the range,
+/// ordered by date. Work-day recurring transactions skip these days.
+#[utoipa::path(
+    get,
+    path = "/api/v1/holidays",
+    params(HolidayQuery),
+    responses(
+        (status = 200, description = "Holidays within the range", body = ApiResponse<Vec<HolidayResponse>>),
+        (status = 400, description = "Invalid date range", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "holidays"
+)]
+#[instrument(skip(state))]
+pub async fn get_holidays(
+    State(state): State<AppState>,
+    Query(query): Query<HolidayQuery>,
+) -> Result<Json<ApiResponse<Vec<HolidayResponse>>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_holidays function");
+
+    if query.start_date > query.end_date {
+        warn!("Rejected holiday range {} to {}", query.start_date, query.end_date);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "start_date must be before or equal to end_date".to_string(),
+                code: "INVALID_DATE_RANGE".to_string(),
+                success: false,
+            }),
+        ));
+    }
+
+    let mut holidays: Vec<HolidayResponse> = holiday::Entity::find()
+        .filter(holiday::Column::Date.between(query.start_date, query.end_date))
+        .all(&state.db)
+        .await
+        .map_err(database_error)?
+        .into_iter()
+        .map(HolidayResponse::from)
+        .collect();
+
+    for country in enabled_countries(&state.db).await.map_err(database_error)? {
+        for year in query.start_date.year()..=query.end_date.year() {
+            holidays.extend(
+                country
+                    .holidays(year)
+                    .into_iter()
+                    .filter(|(date, _)| (query.start_date..=query.end_date).contains(date))
+                    .map(|(date, name)| HolidayResponse {
+                        id: None,
+                        date,
+                        name: name.to_string(),
+                        country_code: Some(country.code().to_string()),
+                    }),
+            );
+        }
+    }
+    holidays.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.country_code.cmp(&b.country_code)));
+
+    info!("Fetched {} holidays between {} and {}", holidays.len(), query.start_date, query.end_date);
+    Ok(Json(ApiResponse {
+        data: holidays,
+        message: "Holidays retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Add a custom holiday
+#[utoipa::path(
+    post,
+    path = "/api/v1/holidays",
+    request_body = CreateHolidayRequest,
+    responses(
+        (status = 201, description = "Holiday added", body = ApiResponse<HolidayResponse>),
+        (status = 400, description = "Missing name", body = ErrorResponse),
+        (status = 409, description = "A custom holiday already exists on the date", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "holidays"
+)]
+#[instrument(skip(state))]
+pub async fn create_holiday(
+    State(state): State<AppState>,
+    Json(request): Json<CreateHolidayRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<HolidayResponse>>), (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering create_holiday function");
+    debug!("Adding holiday: {:?}", request);
+
+    let name = request.name.trim();
+    if name.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "name must not be empty".to_string(),
+                code: "INVALID_NAME".to_string(),
+                success: false,
+            }),
+        ));
+    }
+
+    let existing = holiday::Entity::find()
+        .filter(holiday::Column::Date.eq(request.date))
+        .one(&state.db)
+        .await
+        .map_err(database_error)?;
+    if let Some(existing) = existing {
+        warn!("Custom holiday {} already exists on {}", existing.id, request.date);
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ErrorResponse {
+                error: format!("A custom holiday already exists on {}: {}", request.date, existing.name),
+                code: "HOLIDAY_EXISTS".to_string(),
+                success: false,
+            }),
+        ));
+    }
+
+    let holiday = holiday::ActiveModel {
+        date: Set(request.date),
+        name: Set(name.to_string()),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await
+    .map_err(database_error)?;
+
+    info!("Added holiday {} on {}", holiday.id, holiday.date);
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse {
+            data: HolidayResponse::from(holiday),
+            message: "Holiday added successfully".to_string(),
+            success: true,
+        }),
+    ))
+}
+
+/// Remove a custom holiday
+#[utoipa::path(
+    delete,
+    path = "/api/v1/holidays/{holiday_id}",
+    params(
+        ("holiday_id" = i32, Path, description = "Custom holiday ID")
+    ),
+    responses(
+        (status = 204, description = "Holiday removed"),
+        (status = 404, description = "Holiday not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "holidays"
+)]
+#[instrument(skip(state))]
+pub async fn delete_holiday(
+    State(state): State<AppState>,
+    Path(holiday_id): Path<i32>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering delete_holiday function");
+
+    let result = holiday::Entity::delete_by_id(holiday_id)
+        .exec(&state.db)
+        .await
+        .map_err(database_error)?;
+
+    if result.rows_affected == 0 {
+        warn!("Holiday not found: id={}", holiday_id);
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("Holiday with id {} not found", holiday_id),
+                code: "NOT_FOUND".to_string(),
+                success: false,
+            }),
+        ));
+    }
+
+    info!("Removed holiday {}", holiday_id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List country holiday calendars
+#[utoipa::path(
+    get,
+    path = "/api/v1/holidays/calendars",
+    responses(
+        (status = 200, description = "Enabled and available calendars", body = ApiResponse<HolidayCalendarsResponse>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "holidays"
+)]
+#[instrument(skip(state))]
+pub async fn get_holiday_calendars(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<HolidayCalendarsResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_holiday_calendars function");
+
+    let enabled = enabled_countries(&state.db).await.map_err(database_error)?;
+    Ok(Json(ApiResponse {
+        data: HolidayCalendarsResponse {
+            enabled: enabled.into_iter().map(HolidayCalendarInfo::from).collect(),
+            available: HolidayCountry::ALL.into_iter().map(HolidayCalendarInfo::from).collect(),
+        },
+        message: "Holiday calendars retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Choose the enabled country holiday calendars
+///
+/// Public holidays of every enabled country are skipped by work-day recurring
+/// transactions and count as non-business days for date adjustments.
+#[utoipa::path(
+    put,
+    path = "/api/v1/holidays/calendars",
+    request_body = UpdateHolidayCalendarsRequest,
+    responses(
+        (status = 200, description = "Calendars updated", body = ApiResponse<HolidayCalendarsResponse>),
+        (status = 400, description = "Unknown country code", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "holidays"
+)]
+#[instrument(skip(state))]
+pub async fn update_holiday_calendars(
+    State(state): State<AppState>,
+    Json(request): Json<UpdateHolidayCalendarsRequest>,
+) -> Result<Json<ApiResponse<HolidayCalendarsResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering update_holiday_calendars function");
+    debug!("Updating holiday calendars: {:?}", request);
+
+    let mut countries = request
+        .country_codes
+        .iter()
+        .map(|code| code.parse::<HolidayCountry>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| {
+            warn!("Rejected holiday calendars {:?}: {}", request.country_codes, e);
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: e,
+                    code: "UNKNOWN_HOLIDAY_CALENDAR".to_string(),
+                    success: false,
+                }),
+            )
+        })?;
+    countries.sort();
+    countries.dedup();
+
+    let txn = state.db.begin().await.map_err(database_error)?;
+    holiday_calendar::Entity::delete_many()
+        .exec(&txn)
+        .await
+        .map_err(database_error)?;
+    for country in &countries {
+        holiday_calendar::ActiveModel {
+            country_code: Set(country.code().to_string()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(database_error)?;
+    }
+    txn.commit().await.map_err(database_error)?;
+
+    info!("Enabled holiday calendars: {:?}", countries);
+    Ok(Json(ApiResponse {
+        data: HolidayCalendarsResponse {
+            enabled: countries.into_iter().map(HolidayCalendarInfo::from).collect(),
+            available: HolidayCountry::ALL.into_iter().map(HolidayCalendarInfo::from).collect(),
+        },
+        message: "Holiday calendars updated successfully".to_string(),
+        success: true,
+    }))
+}
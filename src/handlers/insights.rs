@@ -0,0 +1,286 @@
+use crate::helpers::access::AccountAccess;
+use crate::helpers::converters::convert_dataframe_to_timeseries;
+use crate::helpers::timezone::UserTimezone;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use axum_valid::Valid;
+use chrono::{Datelike, Months, NaiveDate};
+use compute::account::utils::{load_holiday_calendar, recurring_transaction_occurrences};
+use compute::account::AccountStateCalculator;
+use compute::cashflow_smoothing::{ShiftCandidate, SmoothingAdvisor};
+use compute::default_compute;
+use compute::due_profile::{due_day_profile, DueOccurrence};
+use model::entities::{account, recurring_income, recurring_transaction};
+use model::entities::recurring_transaction::RecurrencePeriod;
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use tracing::{debug, error, info, instrument, warn};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Query parameters for the cashflow smoothing advisor
+#[derive(Debug, Deserialize, IntoParams, Validate)]
+pub struct CashflowSmoothingQuery {
+    /// Account whose cashflow should be smoothed
+    pub account_id: i32,
+    /// Minimum balance the account should never drop below (defaults to 0)
+    pub buffer: Option<Decimal>,
+    /// Number of months to forecast (defaults to 3)
+    #[validate(range(min = 1, max = 24))]
+    pub months: Option<u32>,
+}
+
+/// A recommendation to move the due day of a recurring payment
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PaymentShiftSuggestion {
+    pub recurring_transaction_id: i32,
+    pub name: String,
+    /// Current due day of month
+    pub from_day: u32,
+    /// Recommended due day of month
+    pub to_day: u32,
+    /// Outflow per occurrence (positive)
+    pub amount: Decimal,
+    /// Lowest forecasted balance before and after this change (including earlier suggestions)
+    pub min_balance_before: Decimal,
+    pub min_balance_after: Decimal,
+    /// Number of forecasted days below the buffer before and after this change
+    pub days_below_buffer_before: usize,
+    pub days_below_buffer_after: usize,
+}
+
+/// Cashflow smoothing advice for one account
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CashflowSmoothingResponse {
+    pub account_id: i32,
+    pub buffer: Decimal,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// Days of the month income arrives on, used as target due days
+    pub income_days: Vec<u32>,
+    /// Day of the month with the highest expected recurring outflow
+    pub peak_outflow_day: Option<u32>,
+    /// Lowest balance of the unchanged forecast
+    pub min_balance: Decimal,
+    /// Days of the unchanged forecast below the buffer
+    pub days_below_buffer: usize,
+    /// Lowest balance once all suggestions are applied
+    pub projected_min_balance: Decimal,
+    /// Days below the buffer once all suggestions are applied
+    pub projected_days_below_buffer: usize,
+    /// Suggested changes, in the order they should be applied
+    pub suggestions: Vec<PaymentShiftSuggestion>,
+}
+
+/// Whether the due day of a recurring rule is a fixed day of the month.
+fn has_monthly_due_day(period: &RecurrencePeriod) -> bool {
+    matches!(
+        period,
+        RecurrencePeriod::Monthly | RecurrencePeriod::Quarterly | RecurrencePeriod::HalfYearly | RecurrencePeriod::Yearly
+    )
+}
+
+fn internal_error(message: &str) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            error: message.to_string(),
+            code: "INTERNAL_ERROR".to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// Get cashflow smoothing advice
+///
+/// Forecasts the account's daily balance and recommends moving recurring payments
+/// to the days income arrives, so the balance stays above the buffer within each month.
+/// Every suggestion is validated against the forecast; only changes that reduce the
+/// number of days below the buffer (or raise the lowest balance) are returned.
+#[utoipa::path(
+    get,
+    path = "/api/v1/insights/cashflow-smoothing",
+    tag = "insights",
+    params(CashflowSmoothingQuery),
+    responses(
+        (status = 200, description = "Cashflow smoothing advice computed successfully", body = ApiResponse<CashflowSmoothingResponse>),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_cashflow_smoothing_advice(
+    State(state): State<AppState>,
+    Valid(Query(query)): Valid<Query<CashflowSmoothingQuery>>,
+    timezone: UserTimezone,
+    access: AccountAccess,
+) -> Result<Json<ApiResponse<CashflowSmoothingResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    let buffer = query.buffer.unwrap_or(Decimal::ZERO);
+    let months = query.months.unwrap_or(3);
+    let start_date = timezone.today();
+    let end_date = start_date
+        .checked_add_months(Months::new(months))
+        .unwrap_or(start_date);
+    debug!(
+        "Computing cashflow smoothing advice for account {} from {} to {}",
+        query.account_id, start_date, end_date
+    );
+
+    let database_error = |e: DbErr| {
+        error!("Database error while computing cashflow smoothing advice: {}", e);
+        internal_error("Failed to load recurring transactions")
+    };
+
+    let account = account::Entity::find_by_id(query.account_id)
+        .one(&state.db)
+        .await
+        .map_err(database_error)?
+        .filter(|account| access.can_access(account.id))
+        .ok_or_else(|| {
+            warn!("Account with ID {} not found", query.account_id);
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: format!("Account with id {} not found", query.account_id),
+                    code: "NOT_FOUND".to_string(),
+                    success: false,
+                }),
+            )
+        })?;
+
+    // Baseline daily balances from the forecast engine
+    let compute = default_compute(Some(start_date));
+    let dataframe = compute
+        .compute_account_state(&state.db, std::slice::from_ref(&account), start_date, end_date)
+        .await
+        .map_err(|e| {
+            error!("Failed to forecast account {}: {}", account.id, e);
+            internal_error("Failed to forecast account balance")
+        })?;
+    let baseline: BTreeMap<NaiveDate, Decimal> = convert_dataframe_to_timeseries(dataframe)
+        .map_err(|e| {
+            error!("Failed to convert forecast for account {}: {}", account.id, e);
+            internal_error("Failed to forecast account balance")
+        })?
+        .data_points
+        .into_iter()
+        .filter(|point| point.account_id == account.id && point.date >= start_date && point.date <= end_date)
+        .map(|point| (point.date, point.balance))
+        .collect();
+
+    // Recurring outflows of the account are the payments that can be moved
+    let recurring_rules = recurring_transaction::Entity::find()
+        .filter(recurring_transaction::Column::IsSimulated.eq(false))
+        .filter(
+            recurring_transaction::Column::TargetAccountId
+                .eq(account.id)
+                .or(recurring_transaction::Column::SourceAccountId.eq(account.id)),
+        )
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+    let holidays = load_holiday_calendar(&state.db).await.map_err(database_error)?;
+
+    let mut income_days = BTreeSet::new();
+    let mut candidates = Vec::new();
+    let mut due_occurrences = Vec::new();
+    for rule in &recurring_rules {
+        // Transfers leave the source account with the opposite sign
+        let outflow = if rule.target_account_id == account.id { -rule.amount } else { rule.amount };
+        if outflow <= Decimal::ZERO {
+            if has_monthly_due_day(&rule.period) {
+                income_days.insert(rule.start_date.day());
+            }
+            continue;
+        }
+        let occurrences = recurring_transaction_occurrences(rule, &holidays, start_date, end_date);
+        due_occurrences.extend(occurrences.iter().map(|date| DueOccurrence {
+            source_id: rule.id,
+            name: rule.name.clone(),
+            date: *date,
+            amount: outflow,
+        }));
+        if has_monthly_due_day(&rule.period) && !occurrences.is_empty() {
+            candidates.push(ShiftCandidate {
+                source_id: rule.id,
+                name: rule.name.clone(),
+                day: rule.start_date.day(),
+                amount: outflow,
+                occurrences,
+            });
+        }
+    }
+
+    let incomes = recurring_income::Entity::find()
+        .filter(recurring_income::Column::IsSimulated.eq(false))
+        .filter(recurring_income::Column::TargetAccountId.eq(account.id))
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+    income_days.extend(
+        incomes
+            .iter()
+            .filter(|income| has_monthly_due_day(&income.period))
+            .map(|income| income.start_date.day()),
+    );
+
+    let peak_outflow_day = due_day_profile(&due_occurrences, months)
+        .into_iter()
+        .filter(|day| day.monthly_average > Decimal::ZERO)
+        .max_by(|a, b| a.monthly_average.cmp(&b.monthly_average).then(b.day.cmp(&a.day)))
+        .map(|day| day.day);
+
+    let income_days: Vec<u32> = income_days.into_iter().collect();
+    let advisor = SmoothingAdvisor::new(buffer, income_days.clone());
+    let baseline_score = advisor.score(&baseline);
+    let suggestions = advisor.advise(&baseline, &candidates);
+    let (projected_min_balance, projected_days_below_buffer) = suggestions
+        .last()
+        .map(|s| (s.min_balance_after, s.days_below_buffer_after))
+        .unwrap_or((baseline_score.min_balance, baseline_score.days_below_buffer));
+
+    info!(
+        "Cashflow smoothing for account {}: {} suggestions, days below buffer {} -> {}",
+        account.id,
+        suggestions.len(),
+        baseline_score.days_below_buffer,
+        projected_days_below_buffer
+    );
+
+    Ok(Json(ApiResponse {
+        data: CashflowSmoothingResponse {
+            account_id: account.id,
+            buffer,
+            start_date,
+            end_date,
+            income_days,
+            peak_outflow_day,
+            min_balance: baseline_score.min_balance,
+            days_below_buffer: baseline_score.days_below_buffer,
+            projected_min_balance,
+            projected_days_below_buffer,
+            suggestions: suggestions
+                .into_iter()
+                .map(|s| PaymentShiftSuggestion {
+                    recurring_transaction_id: s.source_id,
+                    name: s.name,
+                    from_day: s.from_day,
+                    to_day: s.to_day,
+                    amount: s.amount,
+                    min_balance_before: s.min_balance_before,
+                    min_balance_after: s.min_balance_after,
+                    days_below_buffer_before: s.days_below_buffer_before,
+                    days_below_buffer_after: s.days_below_buffer_after,
+                })
+                .collect(),
+        },
+        message: "Cashflow smoothing advice computed successfully".to_string(),
+        success: true,
+    }))
+}
@@ -1,3 +1,4 @@
+use crate::helpers::access::{account_read_only, AccountAccess};
 use crate::schemas::{ApiResponse, AppState, ErrorResponse};
 use axum::{
     extract::{Path, State},
@@ -50,6 +51,17 @@ impl From<manual_account_state::Model> for ManualAccountStateResponse {
     }
 }
 
+fn account_not_found(account_id: i32) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse {
+            error: format!("Account with id {} does not exist", account_id),
+            code: "INVALID_ACCOUNT_ID".to_string(),
+            success: false,
+        }),
+    )
+}
+
 /// Create a new manual account state
 #[utoipa::path(
     post,
@@ -59,6 +71,7 @@ impl From<manual_account_state::Model> for ManualAccountStateResponse {
     responses(
         (status = 201, description = "Manual account state created successfully", body = ApiResponse<ManualAccountStateResponse>),
         (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "Account is shared read-only", body = ErrorResponse),
         (status = 404, description = "Account not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
@@ -67,12 +80,22 @@ impl From<manual_account_state::Model> for ManualAccountStateResponse {
 pub async fn create_manual_account_state(
     Path(account_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
     Json(request): Json<CreateManualAccountStateRequest>,
 ) -> Result<(StatusCode, Json<ApiResponse<ManualAccountStateResponse>>), (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering create_manual_account_state function");
     debug!("Creating manual account state for account_id: {}, date: {}, amount: {}", 
            account_id, request.date, request.amount);
 
+    if !access.can_access(account_id) {
+        warn!("Account {} is not accessible to user {:?}", account_id, access.user_id());
+        return Err(account_not_found(account_id));
+    }
+    if !access.can_write(account_id) {
+        warn!("Account {} is read-only for user {:?}", account_id, access.user_id());
+        return Err(account_read_only(account_id));
+    }
+
     // Validate that the account exists
     trace!("Validating account_id: {}", account_id);
     match account::Entity::find_by_id(account_id).one(&state.db).await {
@@ -130,7 +153,7 @@ pub async fn create_manual_account_state(
     }
 }
 
-/// Get all manual account states across all accounts
+/// Get all manual account states across all accounts the caller can access
 #[utoipa::path(
     get,
     path = "/api/v1/manual-account-states",
@@ -143,12 +166,14 @@ pub async fn create_manual_account_state(
 #[instrument]
 pub async fn get_all_manual_account_states(
     State(state): State<AppState>,
+    access: AccountAccess,
 ) -> Result<Json<ApiResponse<Vec<ManualAccountStateResponse>>>, (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering get_all_manual_account_states function");
     debug!("Retrieving all manual account states");
 
     trace!("Querying all manual account states from database");
-    match manual_account_state::Entity::find()
+    match access
+        .filter(manual_account_state::Entity::find(), [manual_account_state::Column::AccountId])
         .all(&state.db)
         .await
     {
@@ -193,10 +218,16 @@ pub async fn get_all_manual_account_states(
 pub async fn get_manual_account_states(
     Path(account_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
 ) -> Result<Json<ApiResponse<Vec<ManualAccountStateResponse>>>, (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering get_manual_account_states function");
     debug!("Retrieving manual account states for account_id: {}", account_id);
 
+    if !access.can_access(account_id) {
+        warn!("Account {} is not accessible to user {:?}", account_id, access.user_id());
+        return Err(account_not_found(account_id));
+    }
+
     // Validate that the account exists
     trace!("Validating account_id: {}", account_id);
     match account::Entity::find_by_id(account_id).one(&state.db).await {
@@ -270,10 +301,16 @@ pub async fn get_manual_account_states(
 pub async fn get_manual_account_state(
     Path((account_id, state_id)): Path<(i32, i32)>,
     State(state): State<AppState>,
+    access: AccountAccess,
 ) -> Result<Json<ApiResponse<ManualAccountStateResponse>>, (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering get_manual_account_state function");
     debug!("Retrieving manual account state with ID: {} for account_id: {}", state_id, account_id);
 
+    if !access.can_access(account_id) {
+        warn!("Account {} is not accessible to user {:?}", account_id, access.user_id());
+        return Err(account_not_found(account_id));
+    }
+
     trace!("Querying manual account state from database");
     match manual_account_state::Entity::find()
         .filter(manual_account_state::Column::Id.eq(state_id))
@@ -319,6 +356,7 @@ pub async fn get_manual_account_state(
     request_body = UpdateManualAccountStateRequest,
     responses(
         (status = 200, description = "Manual account state updated successfully", body = ApiResponse<ManualAccountStateResponse>),
+        (status = 403, description = "Account is shared read-only", body = ErrorResponse),
         (status = 404, description = "Manual account state not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
@@ -327,11 +365,21 @@ pub async fn get_manual_account_state(
 pub async fn update_manual_account_state(
     Path((account_id, state_id)): Path<(i32, i32)>,
     State(state): State<AppState>,
+    access: AccountAccess,
     Json(request): Json<UpdateManualAccountStateRequest>,
 ) -> Result<Json<ApiResponse<ManualAccountStateResponse>>, (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering update_manual_account_state function");
     debug!("Updating manual account state with ID: {} for account_id: {}", state_id, account_id);
 
+    if !access.can_access(account_id) {
+        warn!("Account {} is not accessible to user {:?}", account_id, access.user_id());
+        return Err(account_not_found(account_id));
+    }
+    if !access.can_write(account_id) {
+        warn!("Account {} is read-only for user {:?}", account_id, access.user_id());
+        return Err(account_read_only(account_id));
+    }
+
     // Find the existing manual account state
     trace!("Querying existing manual account state from database");
     let existing_state = match manual_account_state::Entity::find()
@@ -401,6 +449,7 @@ pub async fn update_manual_account_state(
     tag = "manual-account-states",
     responses(
         (status = 200, description = "Manual account state deleted successfully", body = ApiResponse<String>),
+        (status = 403, description = "Account is shared read-only", body = ErrorResponse),
         (status = 404, description = "Manual account state not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
@@ -409,10 +458,20 @@ pub async fn update_manual_account_state(
 pub async fn delete_manual_account_state(
     Path((account_id, state_id)): Path<(i32, i32)>,
     State(state): State<AppState>,
+    access: AccountAccess,
 ) -> Result<Json<ApiResponse<String>>, (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering delete_manual_account_state function");
     debug!("Deleting manual account state with ID: {} for account_id: {}", state_id, account_id);
 
+    if !access.can_access(account_id) {
+        warn!("Account {} is not accessible to user {:?}", account_id, access.user_id());
+        return Err(account_not_found(account_id));
+    }
+    if !access.can_write(account_id) {
+        warn!("Account {} is read-only for user {:?}", account_id, access.user_id());
+        return Err(account_read_only(account_id));
+    }
+
     // Find the existing manual account state
     trace!("Querying existing manual account state from database");
     let existing_state = match manual_account_state::Entity::find()
@@ -0,0 +1,235 @@
+use crate::helpers::auth::{ensure_admin, AuthenticatedUser};
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use model::entities::merchant;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, instrument, trace, warn};
+use utoipa::ToSchema;
+
+/// Request body for creating a merchant mapping
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateMerchantRequest {
+    /// Substring matched case-insensitively against raw descriptions
+    pub pattern: String,
+    /// Cleaned display name
+    pub name: String,
+    /// Optional logo URL
+    pub logo_url: Option<String>,
+    /// Optional merchant website
+    pub website: Option<String>,
+}
+
+/// Merchant mapping response model
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MerchantResponse {
+    pub id: i32,
+    pub pattern: String,
+    pub name: String,
+    pub logo_url: Option<String>,
+    pub website: Option<String>,
+}
+
+impl From<merchant::Model> for MerchantResponse {
+    fn from(model: merchant::Model) -> Self {
+        Self {
+            id: model.id,
+            pattern: model.pattern,
+            name: model.name,
+            logo_url: model.logo_url,
+            website: model.website,
+        }
+    }
+}
+
+/// Create a merchant mapping
+#[utoipa::path(
+    post,
+    path = "/api/v1/merchants",
+    request_body = CreateMerchantRequest,
+    responses(
+        (status = 201, description = "Merchant mapping created", body = ApiResponse<MerchantResponse>),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "The caller is not an administrator", body = ErrorResponse),
+        (status = 409, description = "Pattern already mapped", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "merchants"
+)]
+#[instrument(skip(state))]
+pub async fn create_merchant(
+    State(state): State<AppState>,
+    auth: Option<AuthenticatedUser>,
+    Json(request): Json<CreateMerchantRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<MerchantResponse>>), (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering create_merchant function");
+    debug!("Creating merchant mapping: {:?}", request);
+    ensure_admin(&state.db, auth.as_ref(), "manage merchant mappings").await?;
+
+    let pattern = request.pattern.trim().to_string();
+    if pattern.is_empty() || request.name.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "pattern and name must not be empty".to_string(),
+                code: "VALIDATION_ERROR".to_string(),
+                success: false,
+            }),
+        ));
+    }
+
+    let existing = merchant::Entity::find()
+        .filter(merchant::Column::Pattern.eq(pattern.clone()))
+        .one(&state.db)
+        .await
+        .map_err(|e| {
+            error!("Failed to check merchant pattern: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("Failed to check merchant pattern: {}", e),
+                    code: "DATABASE_ERROR".to_string(),
+                    success: false,
+                }),
+            )
+        })?;
+    if existing.is_some() {
+        warn!("Merchant pattern '{}' already mapped", pattern);
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ErrorResponse {
+                error: format!("Pattern '{}' is already mapped", pattern),
+                code: "CONFLICT".to_string(),
+                success: false,
+            }),
+        ));
+    }
+
+    let created = merchant::ActiveModel {
+        pattern: Set(pattern),
+        name: Set(request.name.trim().to_string()),
+        logo_url: Set(request.logo_url),
+        website: Set(request.website),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await
+    .map_err(|e| {
+        error!("Failed to create merchant mapping: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Failed to create merchant mapping: {}", e),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    })?;
+
+    info!("Merchant mapping created: id={}, pattern={}", created.id, created.pattern);
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse {
+            data: MerchantResponse::from(created),
+            message: "Merchant mapping created successfully".to_string(),
+            success: true,
+        }),
+    ))
+}
+
+/// List merchant mappings
+#[utoipa::path(
+    get,
+    path = "/api/v1/merchants",
+    responses(
+        (status = 200, description = "List of merchant mappings", body = ApiResponse<Vec<MerchantResponse>>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "merchants"
+)]
+#[instrument(skip(state))]
+pub async fn get_merchants(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<MerchantResponse>>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_merchants function");
+
+    let merchants = merchant::Entity::find()
+        .order_by_asc(merchant::Column::Name)
+        .all(&state.db)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch merchant mappings: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("Failed to fetch merchant mappings: {}", e),
+                    code: "DATABASE_ERROR".to_string(),
+                    success: false,
+                }),
+            )
+        })?;
+
+    Ok(Json(ApiResponse {
+        data: merchants.into_iter().map(MerchantResponse::from).collect(),
+        message: "Merchant mappings retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Delete a merchant mapping
+#[utoipa::path(
+    delete,
+    path = "/api/v1/merchants/{merchant_id}",
+    params(
+        ("merchant_id" = i32, Path, description = "Merchant mapping ID")
+    ),
+    responses(
+        (status = 204, description = "Merchant mapping deleted"),
+        (status = 403, description = "The caller is not an administrator", body = ErrorResponse),
+        (status = 404, description = "Merchant mapping not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "merchants"
+)]
+#[instrument(skip(state))]
+pub async fn delete_merchant(
+    State(state): State<AppState>,
+    auth: Option<AuthenticatedUser>,
+    Path(merchant_id): Path<i32>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering delete_merchant function");
+    ensure_admin(&state.db, auth.as_ref(), "manage merchant mappings").await?;
+
+    let result = merchant::Entity::delete_by_id(merchant_id)
+        .exec(&state.db)
+        .await
+        .map_err(|e| {
+            error!("Failed to delete merchant mapping {}: {}", merchant_id, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("Failed to delete merchant mapping: {}", e),
+                    code: "DATABASE_ERROR".to_string(),
+                    success: false,
+                }),
+            )
+        })?;
+
+    if result.rows_affected == 0 {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("Merchant mapping with id {} not found", merchant_id),
+                code: "NOT_FOUND".to_string(),
+                success: false,
+            }),
+        ));
+    }
+
+    info!("Merchant mapping deleted: id={}", merchant_id);
+    Ok(StatusCode::NO_CONTENT)
+}
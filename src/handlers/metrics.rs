@@ -1,3 +1,5 @@
+use crate::helpers::access::AccountAccess;
+use crate::helpers::timezone::UserTimezone;
 use crate::schemas::{ApiResponse, AppState, CachedData, ErrorResponse};
 use axum::{
     extract::{Path, State},
@@ -13,6 +15,8 @@ use sea_orm::EntityTrait;
 use tracing::{debug, error, info, instrument, trace, warn};
 
 /// Get the full financial dashboard with cross-account and per-account metrics
+///
+/// Covers the accounts visible to the caller.
 #[utoipa::path(
     get,
     path = "/api/v1/metrics/dashboard",
@@ -25,10 +29,16 @@ use tracing::{debug, error, info, instrument, trace, warn};
 #[instrument]
 pub async fn get_dashboard_metrics(
     State(state): State<AppState>,
+    access: AccountAccess,
+    timezone: UserTimezone,
 ) -> Result<Json<ApiResponse<DashboardMetricsDto>>, StatusCode> {
     trace!("Entering get_dashboard_metrics");
 
-    let cache_key = "dashboard_metrics".to_string();
+    let today = timezone.today();
+    let cache_key = match access.user_id() {
+        Some(user_id) => format!("dashboard_metrics_{}_user_{}", today, user_id),
+        None => format!("dashboard_metrics_{}", today),
+    };
     if let Some(CachedData::Dashboard(dashboard)) = state.cache.get(&cache_key).await {
         info!("Dashboard metrics retrieved from cache");
         return Ok(Json(ApiResponse {
@@ -38,13 +48,20 @@ pub async fn get_dashboard_metrics(
         }));
     }
 
-    let today = chrono::Utc::now().date_naive();
-    let compute = default_compute(None);
+    let accounts = match access.filter(account::Entity::find(), [account::Column::Id]).all(&state.db).await {
+        Ok(accounts) => accounts,
+        Err(e) => {
+            error!("Failed to fetch accounts for dashboard metrics: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    let compute = default_compute(Some(today));
 
     debug!("Computing dashboard metrics for date: {}", today);
-    match cross_account_metrics::compute_dashboard_metrics(
+    match cross_account_metrics::compute_dashboard_metrics_for_accounts(
         &compute as &dyn AccountStateCalculator,
         &state.db,
+        accounts,
         today,
     )
         .await
@@ -87,6 +104,8 @@ pub async fn get_dashboard_metrics(
 pub async fn get_account_metrics(
     Path(account_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
+    timezone: UserTimezone,
 ) -> Result<Json<ApiResponse<AccountMetricsDto>>, StatusCode> {
     trace!("Entering get_account_metrics for account_id: {}", account_id);
 
@@ -94,11 +113,11 @@ pub async fn get_account_metrics(
         .one(&state.db)
         .await
     {
-        Ok(Some(account)) => {
+        Ok(Some(account)) if access.can_access(account.id) => {
             debug!("Found account: {}", account.name);
             account
         }
-        Ok(None) => {
+        Ok(_) => {
             warn!("Account with ID {} not found", account_id);
             return Err(StatusCode::NOT_FOUND);
         }
@@ -111,8 +130,8 @@ pub async fn get_account_metrics(
         }
     };
 
-    let today = chrono::Utc::now().date_naive();
-    let compute = default_compute(None);
+    let today = timezone.today();
+    let compute = default_compute(Some(today));
 
     match account_metrics::compute_account_metrics(
         &compute as &dyn AccountStateCalculator,
@@ -0,0 +1,267 @@
+use crate::handlers::assets::assets_between;
+use crate::helpers::access::AccountAccess;
+use crate::helpers::converters::convert_dataframe_to_timeseries;
+use crate::helpers::scenarios::ScenarioPreferences;
+use crate::helpers::timezone::UserTimezone;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::NaiveDate;
+use compute::{
+    account::AccountStateCalculator,
+    assets::value_on,
+    default_compute_with_scenario,
+    networth::{sum_by_date, sum_by_group, NetWorthPoint},
+};
+use model::entities::account;
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{debug, error, info, instrument, trace, warn};
+use utoipa::{IntoParams, ToSchema};
+
+/// How the household net worth is broken down next to the total
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NetWorthGrouping {
+    /// One series per account owner, keyed by user id
+    Owner,
+    /// One series per account kind
+    AccountKind,
+}
+
+/// Query parameters for the net worth timeseries
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct NetWorthQuery {
+    /// First day of the series (YYYY-MM-DD)
+    pub start_date: NaiveDate,
+    /// Last day of the series (YYYY-MM-DD)
+    pub end_date: NaiveDate,
+    /// `owner` or `account_kind`; only the total is returned when omitted
+    pub group_by: Option<NetWorthGrouping>,
+    /// Scenario ID to include simulated transactions (default: the owners' active scenario)
+    pub scenario_id: Option<i32>,
+}
+
+/// Net worth on one day
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NetWorthPointResponse {
+    pub date: NaiveDate,
+    pub net_worth: Decimal,
+}
+
+impl From<NetWorthPoint> for NetWorthPointResponse {
+    fn from(point: NetWorthPoint) -> Self {
+        Self {
+            date: point.date,
+            net_worth: point.net_worth,
+        }
+    }
+}
+
+/// Net worth series of one owner or account kind
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NetWorthGroupResponse {
+    /// User id or account kind, depending on `group_by`
+    pub key: String,
+    pub points: Vec<NetWorthPointResponse>,
+}
+
+/// Household net worth over time
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NetWorthTimeseriesResponse {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub group_by: Option<NetWorthGrouping>,
+    /// Accounts that contributed to the series
+    pub account_ids: Vec<i32>,
+    /// Assets whose depreciated value contributed to the series
+    pub asset_ids: Vec<i32>,
+    pub points: Vec<NetWorthPointResponse>,
+    /// Depreciated value of the household assets, already included in `points`
+    pub assets: Vec<NetWorthPointResponse>,
+    /// Empty unless `group_by` is set
+    pub groups: Vec<NetWorthGroupResponse>,
+}
+
+fn networth_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+fn group_key(account: &account::Model, grouping: NetWorthGrouping) -> String {
+    match grouping {
+        NetWorthGrouping::Owner => account.owner_id.to_string(),
+        NetWorthGrouping::AccountKind => format!("{:?}", account.account_kind),
+    }
+}
+
+/// Key of the asset value line in the `account_kind` grouping
+const ASSET_GROUP_KEY: &str = "Asset";
+
+/// Get the household net worth timeseries
+///
+/// Merges the daily balances of all accessible accounts included in statistics into a single
+/// series. Virtual accounts (allowances) are skipped because their money is already
+/// counted in the account holding it; liabilities reduce the net worth. Assets paid
+/// from those accounts add their depreciated value until they are disposed of.
+#[utoipa::path(
+    get,
+    path = "/api/v1/networth/timeseries",
+    params(NetWorthQuery),
+    responses(
+        (status = 200, description = "Net worth timeseries", body = ApiResponse<NetWorthTimeseriesResponse>),
+        (status = 400, description = "Invalid date range", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "networth"
+)]
+#[instrument(skip(state))]
+pub async fn get_networth_timeseries(
+    State(state): State<AppState>,
+    Query(query): Query<NetWorthQuery>,
+    timezone: UserTimezone,
+    access: AccountAccess,
+) -> Result<Json<ApiResponse<NetWorthTimeseriesResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_networth_timeseries function");
+    debug!("Computing net worth timeseries: {:?}", query);
+
+    if query.start_date > query.end_date {
+        warn!("Rejected net worth range {} to {}", query.start_date, query.end_date);
+        return Err(networth_error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_DATE_RANGE",
+            "start_date must be before or equal to end_date".to_string(),
+        ));
+    }
+
+    let database_error = |e: DbErr| {
+        error!("Database error while computing net worth: {}", e);
+        networth_error(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", e.to_string())
+    };
+    let compute_error = |e: String| {
+        error!("Failed to compute net worth: {}", e);
+        networth_error(StatusCode::INTERNAL_SERVER_ERROR, "COMPUTE_ERROR", e)
+    };
+
+    let accounts: Vec<account::Model> = access
+        .filter(account::Entity::find(), [account::Column::Id])
+        .filter(account::Column::IncludeInStatistics.eq(true))
+        .all(&state.db)
+        .await
+        .map_err(database_error)?
+        .into_iter()
+        .filter(|account| account.account_kind.counts_towards_net_worth())
+        .collect();
+
+    let balances = if accounts.is_empty() {
+        Vec::new()
+    } else {
+        let scenario_id = ScenarioPreferences::load(&state.db)
+            .await
+            .map_err(database_error)?
+            .resolve(query.scenario_id, &accounts);
+        debug!(
+            "Merging balances of {} accounts from {} to {} (scenario_id={:?})",
+            accounts.len(),
+            query.start_date,
+            query.end_date,
+            scenario_id
+        );
+
+        let df = default_compute_with_scenario(Some(timezone.today()), scenario_id)
+            .compute_account_state(&state.db, &accounts, query.start_date, query.end_date)
+            .await
+            .map_err(|e| compute_error(e.to_string()))?;
+        convert_dataframe_to_timeseries(df).map_err(compute_error)?.data_points
+    };
+
+    let account_ids: Vec<i32> = accounts.iter().map(|account| account.id).collect();
+    let assets: Vec<_> = assets_between(&state.db, query.start_date, query.end_date)
+        .await
+        .map_err(database_error)?
+        .into_iter()
+        .filter(|asset| account_ids.contains(&asset.account_id))
+        .collect();
+    let asset_values: Vec<(i32, NaiveDate, Decimal)> = query
+        .start_date
+        .iter_days()
+        .take_while(|date| *date <= query.end_date)
+        .flat_map(|date| {
+            assets
+                .iter()
+                .map(move |asset| (asset.account_id, date, value_on(asset, date)))
+                .filter(|(_, _, value)| !value.is_zero())
+        })
+        .collect();
+    debug!("Valued {} assets on {} days", assets.len(), asset_values.len());
+
+    let groups = match query.group_by {
+        Some(grouping) => {
+            let keys: HashMap<i32, String> = accounts
+                .iter()
+                .map(|account| (account.id, group_key(account, grouping)))
+                .collect();
+            let asset_key = |account_id: i32| match grouping {
+                NetWorthGrouping::Owner => keys.get(&account_id).cloned(),
+                NetWorthGrouping::AccountKind => Some(ASSET_GROUP_KEY.to_string()),
+            };
+            sum_by_group(
+                balances
+                    .iter()
+                    .filter_map(|point| Some((keys.get(&point.account_id)?.clone(), point.date, point.balance)))
+                    .chain(
+                        asset_values
+                            .iter()
+                            .filter_map(|(account_id, date, value)| Some((asset_key(*account_id)?, *date, *value))),
+                    ),
+            )
+            .into_iter()
+            .map(|(key, points)| NetWorthGroupResponse {
+                key,
+                points: points.into_iter().map(NetWorthPointResponse::from).collect(),
+            })
+            .collect()
+        }
+        None => Vec::new(),
+    };
+    let points: Vec<NetWorthPointResponse> = sum_by_date(
+        balances
+            .iter()
+            .map(|point| (point.date, point.balance))
+            .chain(asset_values.iter().map(|(_, date, value)| (*date, *value))),
+    )
+    .into_iter()
+    .map(NetWorthPointResponse::from)
+    .collect();
+    let asset_points: Vec<NetWorthPointResponse> = sum_by_date(asset_values.iter().map(|(_, date, value)| (*date, *value)))
+        .into_iter()
+        .map(NetWorthPointResponse::from)
+        .collect();
+
+    info!("Computed {} net worth points over {} accounts", points.len(), accounts.len());
+    Ok(Json(ApiResponse {
+        data: NetWorthTimeseriesResponse {
+            start_date: query.start_date,
+            end_date: query.end_date,
+            group_by: query.group_by,
+            account_ids,
+            asset_ids: assets.iter().map(|asset| asset.id).collect(),
+            points,
+            assets: asset_points,
+            groups,
+        },
+        message: "Net worth timeseries retrieved successfully".to_string(),
+        success: true,
+    }))
+}
@@ -0,0 +1,658 @@
+use crate::helpers::access::AccountAccess;
+use crate::helpers::auth::AuthenticatedUser;
+use crate::helpers::notifications::{validate_target, Notification, NotificationError, Notifier};
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use crate::tasks::payment_alerts::{send_payment_alerts, PaymentAlertSummary};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::{NaiveDateTime, Utc};
+use model::entities::{notification_channel, recurring_alert, recurring_transaction};
+use sea_orm::{ActiveModelTrait, ColumnTrait, Condition, DbErr, EntityTrait, QueryFilter, QueryOrder, Set};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, instrument, trace, warn};
+use utoipa::ToSchema;
+
+/// How a notification channel delivers messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum NotificationChannelKind {
+    /// E-mail through the SMTP server configured with `--smtp-url`; the target is the
+    /// recipient address
+    Email,
+    /// ntfy push notification; the target is the topic URL, e.g. https://ntfy.sh/family-bills
+    Ntfy,
+    /// JSON `{title, message, data}` posted to the target URL
+    Webhook,
+}
+
+impl From<notification_channel::NotificationChannelKind> for NotificationChannelKind {
+    fn from(kind: notification_channel::NotificationChannelKind) -> Self {
+        match kind {
+            notification_channel::NotificationChannelKind::Email => NotificationChannelKind::Email,
+            notification_channel::NotificationChannelKind::Ntfy => NotificationChannelKind::Ntfy,
+            notification_channel::NotificationChannelKind::Webhook => NotificationChannelKind::Webhook,
+        }
+    }
+}
+
+impl From<NotificationChannelKind> for notification_channel::NotificationChannelKind {
+    fn from(kind: NotificationChannelKind) -> Self {
+        match kind {
+            NotificationChannelKind::Email => notification_channel::NotificationChannelKind::Email,
+            NotificationChannelKind::Ntfy => notification_channel::NotificationChannelKind::Ntfy,
+            NotificationChannelKind::Webhook => notification_channel::NotificationChannelKind::Webhook,
+        }
+    }
+}
+
+/// Request body for adding a notification channel
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateNotificationChannelRequest {
+    /// e.g. "Family phone"
+    pub name: String,
+    pub kind: NotificationChannelKind,
+    /// Recipient address for e-mail, topic URL for ntfy, endpoint URL for webhooks
+    pub target: String,
+    /// Defaults to true
+    pub enabled: Option<bool>,
+}
+
+/// Request body for changing a notification channel; absent fields are kept
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UpdateNotificationChannelRequest {
+    pub name: Option<String>,
+    pub target: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+/// Notification channel response model
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NotificationChannelResponse {
+    pub id: i32,
+    pub name: String,
+    pub kind: NotificationChannelKind,
+    pub target: String,
+    pub enabled: bool,
+    pub created_at: NaiveDateTime,
+    /// User the channel belongs to; absent for household channels
+    pub user_id: Option<i32>,
+}
+
+impl From<notification_channel::Model> for NotificationChannelResponse {
+    fn from(model: notification_channel::Model) -> Self {
+        Self {
+            id: model.id,
+            name: model.name,
+            kind: model.kind.into(),
+            target: model.target,
+            enabled: model.enabled,
+            created_at: model.created_at,
+            user_id: model.user_id,
+        }
+    }
+}
+
+/// When to alert about the instances of a recurring transaction
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RecurringAlertSettings {
+    pub recurring_transaction_id: i32,
+    /// Alert once a pending instance is due within this many days; absent for no
+    /// upcoming alerts
+    pub days_before: Option<i32>,
+    /// Alert once a pending instance is past its due date
+    pub notify_overdue: bool,
+    /// Channel to alert through; absent for every enabled channel
+    pub channel_id: Option<i32>,
+}
+
+impl From<recurring_alert::Model> for RecurringAlertSettings {
+    fn from(model: recurring_alert::Model) -> Self {
+        Self {
+            recurring_transaction_id: model.recurring_transaction_id,
+            days_before: model.days_before,
+            notify_overdue: model.notify_overdue,
+            channel_id: model.channel_id,
+        }
+    }
+}
+
+/// Request body for the alert settings of a recurring transaction
+///
+/// Without `days_before` and `notify_overdue` the recurring transaction is not alerted
+/// about.
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UpdateRecurringAlertsRequest {
+    /// 0 to 365; 0 alerts on the due date only
+    pub days_before: Option<i32>,
+    #[serde(default)]
+    pub notify_overdue: bool,
+    pub channel_id: Option<i32>,
+}
+
+fn database_error(e: DbErr) -> (StatusCode, Json<ErrorResponse>) {
+    error!("Database error while handling notifications: {}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            error: format!("Database error: {}", e),
+            code: "DATABASE_ERROR".to_string(),
+            success: false,
+        }),
+    )
+}
+
+fn bad_request(code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    warn!("Rejected notification request: {}", error);
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+fn channel_not_found(channel_id: i32) -> (StatusCode, Json<ErrorResponse>) {
+    warn!("Notification channel not found: id={}", channel_id);
+    (
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse {
+            error: format!("Notification channel with id {} not found", channel_id),
+            code: "NOT_FOUND".to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// The notification channel, if it is a household channel or belongs to the caller.
+async fn visible_channel(
+    state: &AppState,
+    auth: Option<&AuthenticatedUser>,
+    channel_id: i32,
+) -> Result<notification_channel::Model, (StatusCode, Json<ErrorResponse>)> {
+    notification_channel::Entity::find_by_id(channel_id)
+        .one(&state.db)
+        .await
+        .map_err(database_error)?
+        .filter(|channel| match (channel.user_id, auth) {
+            (Some(owner_id), Some(auth)) => owner_id == auth.user_id,
+            _ => true,
+        })
+        .ok_or_else(|| channel_not_found(channel_id))
+}
+
+fn notifier(state: &AppState) -> Result<Notifier, (StatusCode, Json<ErrorResponse>)> {
+    Notifier::new(state.smtp.clone()).map_err(|e| {
+        error!("Failed to set up notifications: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Failed to set up notifications: {}", e),
+                code: "NOTIFICATION_SETUP_FAILED".to_string(),
+                success: false,
+            }),
+        )
+    })
+}
+
+fn validated_target(
+    kind: notification_channel::NotificationChannelKind,
+    target: &str,
+) -> Result<String, (StatusCode, Json<ErrorResponse>)> {
+    let target = target.trim();
+    validate_target(kind, target).map_err(|e| bad_request("INVALID_TARGET", e.to_string()))?;
+    Ok(target.to_string())
+}
+
+/// The recurring transaction, if the caller may see it; `write` also requires that
+/// they may book on its accounts.
+async fn accessible_recurring_transaction(
+    state: &AppState,
+    access: &AccountAccess,
+    recurring_transaction_id: i32,
+    write: bool,
+) -> Result<recurring_transaction::Model, (StatusCode, Json<ErrorResponse>)> {
+    let transaction = recurring_transaction::Entity::find_by_id(recurring_transaction_id)
+        .one(&state.db)
+        .await
+        .map_err(database_error)?
+        .filter(|transaction| access.can_access_any(transaction.target_account_id, transaction.source_account_id));
+    let Some(transaction) = transaction else {
+        warn!("Recurring transaction with ID {} not found", recurring_transaction_id);
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("Recurring transaction with id {} does not exist", recurring_transaction_id),
+                code: "RECURRING_TRANSACTION_NOT_FOUND".to_string(),
+                success: false,
+            }),
+        ));
+    };
+    if write {
+        access.ensure_can_write_any(transaction.target_account_id, transaction.source_account_id)?;
+    }
+    Ok(transaction)
+}
+
+/// List notification channels
+///
+/// Lists household channels and the caller's own.
+#[utoipa::path(
+    get,
+    path = "/api/v1/notification-channels",
+    responses(
+        (status = 200, description = "Notification channels", body = ApiResponse<Vec<NotificationChannelResponse>>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "notifications"
+)]
+#[instrument(skip(state))]
+pub async fn get_notification_channels(
+    State(state): State<AppState>,
+    auth: Option<AuthenticatedUser>,
+) -> Result<Json<ApiResponse<Vec<NotificationChannelResponse>>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_notification_channels function");
+
+    let mut query = notification_channel::Entity::find();
+    if let Some(auth) = &auth {
+        query = query.filter(
+            Condition::any()
+                .add(notification_channel::Column::UserId.is_null())
+                .add(notification_channel::Column::UserId.eq(auth.user_id)),
+        );
+    }
+    let channels: Vec<NotificationChannelResponse> = query
+        .order_by_asc(notification_channel::Column::Id)
+        .all(&state.db)
+        .await
+        .map_err(database_error)?
+        .into_iter()
+        .map(NotificationChannelResponse::from)
+        .collect();
+
+    info!("Fetched {} notification channels", channels.len());
+    Ok(Json(ApiResponse {
+        data: channels,
+        message: "Notification channels retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Add a notification channel
+///
+/// The channel belongs to the caller and receives payment alerts about the accounts they
+/// own or that are shared with them; added without authentication it is a household
+/// channel receiving all of them. Payment alerts go to every enabled channel unless a
+/// recurring transaction picks one.
+#[utoipa::path(
+    post,
+    path = "/api/v1/notification-channels",
+    request_body = CreateNotificationChannelRequest,
+    responses(
+        (status = 201, description = "Notification channel added", body = ApiResponse<NotificationChannelResponse>),
+        (status = 400, description = "Missing name or invalid target", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "notifications"
+)]
+#[instrument(skip(state))]
+pub async fn create_notification_channel(
+    State(state): State<AppState>,
+    auth: Option<AuthenticatedUser>,
+    Json(request): Json<CreateNotificationChannelRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<NotificationChannelResponse>>), (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering create_notification_channel function");
+    debug!("Adding notification channel: {:?}", request);
+
+    let name = request.name.trim();
+    if name.is_empty() {
+        return Err(bad_request("INVALID_NAME", "name must not be empty".to_string()));
+    }
+    let kind = request.kind.into();
+    let target = validated_target(kind, &request.target)?;
+
+    let channel = notification_channel::ActiveModel {
+        name: Set(name.to_string()),
+        kind: Set(kind),
+        target: Set(target),
+        enabled: Set(request.enabled.unwrap_or(true)),
+        created_at: Set(Utc::now().naive_utc()),
+        user_id: Set(auth.map(|auth| auth.user_id)),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await
+    .map_err(database_error)?;
+
+    info!("Added {:?} notification channel {}", channel.kind, channel.id);
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse {
+            data: NotificationChannelResponse::from(channel),
+            message: "Notification channel added successfully".to_string(),
+            success: true,
+        }),
+    ))
+}
+
+/// Change a notification channel
+#[utoipa::path(
+    put,
+    path = "/api/v1/notification-channels/{channel_id}",
+    params(
+        ("channel_id" = i32, Path, description = "Notification channel ID")
+    ),
+    request_body = UpdateNotificationChannelRequest,
+    responses(
+        (status = 200, description = "Notification channel updated", body = ApiResponse<NotificationChannelResponse>),
+        (status = 400, description = "Empty name or invalid target", body = ErrorResponse),
+        (status = 404, description = "Notification channel not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "notifications"
+)]
+#[instrument(skip(state))]
+pub async fn update_notification_channel(
+    State(state): State<AppState>,
+    auth: Option<AuthenticatedUser>,
+    Path(channel_id): Path<i32>,
+    Json(request): Json<UpdateNotificationChannelRequest>,
+) -> Result<Json<ApiResponse<NotificationChannelResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering update_notification_channel function");
+
+    let channel = visible_channel(&state, auth.as_ref(), channel_id).await?;
+    let kind = channel.kind;
+    let mut active: notification_channel::ActiveModel = channel.into();
+
+    if let Some(name) = request.name {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(bad_request("INVALID_NAME", "name must not be empty".to_string()));
+        }
+        active.name = Set(name.to_string());
+    }
+    if let Some(target) = request.target {
+        active.target = Set(validated_target(kind, &target)?);
+    }
+    if let Some(enabled) = request.enabled {
+        active.enabled = Set(enabled);
+    }
+
+    let channel = active.update(&state.db).await.map_err(database_error)?;
+    info!("Updated notification channel {}", channel.id);
+    Ok(Json(ApiResponse {
+        data: NotificationChannelResponse::from(channel),
+        message: "Notification channel updated successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Remove a notification channel
+///
+/// Recurring transactions alerting through it fall back to every enabled channel.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/notification-channels/{channel_id}",
+    params(
+        ("channel_id" = i32, Path, description = "Notification channel ID")
+    ),
+    responses(
+        (status = 204, description = "Notification channel removed"),
+        (status = 404, description = "Notification channel not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "notifications"
+)]
+#[instrument(skip(state))]
+pub async fn delete_notification_channel(
+    State(state): State<AppState>,
+    auth: Option<AuthenticatedUser>,
+    Path(channel_id): Path<i32>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering delete_notification_channel function");
+
+    visible_channel(&state, auth.as_ref(), channel_id).await?;
+    let result = notification_channel::Entity::delete_by_id(channel_id)
+        .exec(&state.db)
+        .await
+        .map_err(database_error)?;
+    if result.rows_affected == 0 {
+        return Err(channel_not_found(channel_id));
+    }
+
+    info!("Removed notification channel {}", channel_id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Send a test notification
+///
+/// Delivers a test message through the channel right away, even when it is disabled.
+#[utoipa::path(
+    post,
+    path = "/api/v1/notification-channels/{channel_id}/test",
+    params(
+        ("channel_id" = i32, Path, description = "Notification channel ID")
+    ),
+    responses(
+        (status = 200, description = "Test notification delivered", body = ApiResponse<String>),
+        (status = 404, description = "Notification channel not found", body = ErrorResponse),
+        (status = 409, description = "E-mail channel without a configured SMTP server", body = ErrorResponse),
+        (status = 502, description = "The channel did not accept the notification", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "notifications"
+)]
+#[instrument(skip(state))]
+pub async fn test_notification_channel(
+    State(state): State<AppState>,
+    auth: Option<AuthenticatedUser>,
+    Path(channel_id): Path<i32>,
+) -> Result<Json<ApiResponse<String>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering test_notification_channel function");
+
+    let channel = visible_channel(&state, auth.as_ref(), channel_id).await?;
+    let notification = Notification {
+        title: "FinRust test notification".to_string(),
+        message: format!("Payment alerts will be delivered through \"{}\".", channel.name),
+        data: serde_json::json!({ "kind": "Test", "channel_id": channel.id }),
+    };
+
+    match notifier(&state)?.send(&channel, &notification).await {
+        Ok(()) => {
+            info!("Sent test notification through channel {}", channel.id);
+            Ok(Json(ApiResponse {
+                data: format!("Test notification sent through {}", channel.name),
+                message: "Test notification sent successfully".to_string(),
+                success: true,
+            }))
+        }
+        Err(NotificationError::SmtpNotConfigured) => Err((
+            StatusCode::CONFLICT,
+            Json(ErrorResponse {
+                error: NotificationError::SmtpNotConfigured.to_string(),
+                code: "SMTP_NOT_CONFIGURED".to_string(),
+                success: false,
+            }),
+        )),
+        Err(e) => {
+            warn!("Test notification through channel {} failed: {}", channel.id, e);
+            Err((
+                StatusCode::BAD_GATEWAY,
+                Json(ErrorResponse {
+                    error: format!("Failed to deliver the notification: {}", e),
+                    code: "NOTIFICATION_FAILED".to_string(),
+                    success: false,
+                }),
+            ))
+        }
+    }
+}
+
+/// Get the alert settings of a recurring transaction
+#[utoipa::path(
+    get,
+    path = "/api/v1/recurring-transactions/{recurring_transaction_id}/alerts",
+    params(
+        ("recurring_transaction_id" = i32, Path, description = "Recurring transaction ID")
+    ),
+    responses(
+        (status = 200, description = "Alert settings; both alerts off when never configured", body = ApiResponse<RecurringAlertSettings>),
+        (status = 404, description = "Recurring transaction not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "notifications"
+)]
+#[instrument(skip(state))]
+pub async fn get_recurring_alerts(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Path(recurring_transaction_id): Path<i32>,
+) -> Result<Json<ApiResponse<RecurringAlertSettings>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_recurring_alerts function");
+    accessible_recurring_transaction(&state, &access, recurring_transaction_id, false).await?;
+
+    let settings = recurring_alert::Entity::find()
+        .filter(recurring_alert::Column::RecurringTransactionId.eq(recurring_transaction_id))
+        .one(&state.db)
+        .await
+        .map_err(database_error)?
+        .map(RecurringAlertSettings::from)
+        .unwrap_or(RecurringAlertSettings {
+            recurring_transaction_id,
+            days_before: None,
+            notify_overdue: false,
+            channel_id: None,
+        });
+
+    Ok(Json(ApiResponse {
+        data: settings,
+        message: "Alert settings retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Configure the alerts of a recurring transaction
+///
+/// Replaces the settings. Alerts are only sent for pending instances, so instances need
+/// to be generated, which the server does in the background.
+#[utoipa::path(
+    put,
+    path = "/api/v1/recurring-transactions/{recurring_transaction_id}/alerts",
+    params(
+        ("recurring_transaction_id" = i32, Path, description = "Recurring transaction ID")
+    ),
+    request_body = UpdateRecurringAlertsRequest,
+    responses(
+        (status = 200, description = "Alert settings updated", body = ApiResponse<RecurringAlertSettings>),
+        (status = 400, description = "Invalid days_before or unknown channel", body = ErrorResponse),
+        (status = 403, description = "Recurring transaction is shared read-only", body = ErrorResponse),
+        (status = 404, description = "Recurring transaction not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "notifications"
+)]
+#[instrument(skip(state))]
+pub async fn update_recurring_alerts(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    auth: Option<AuthenticatedUser>,
+    Path(recurring_transaction_id): Path<i32>,
+    Json(request): Json<UpdateRecurringAlertsRequest>,
+) -> Result<Json<ApiResponse<RecurringAlertSettings>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering update_recurring_alerts function");
+    accessible_recurring_transaction(&state, &access, recurring_transaction_id, true).await?;
+
+    if let Some(days_before) = request.days_before.filter(|days| !(0..=365).contains(days)) {
+        return Err(bad_request(
+            "INVALID_DAYS_BEFORE",
+            format!("days_before must be between 0 and 365, got {}", days_before),
+        ));
+    }
+    if let Some(channel_id) = request.channel_id {
+        if let Err((status, error)) = visible_channel(&state, auth.as_ref(), channel_id).await {
+            if status != StatusCode::NOT_FOUND {
+                return Err((status, error));
+            }
+            return Err(bad_request(
+                "UNKNOWN_NOTIFICATION_CHANNEL",
+                format!("Notification channel with id {} does not exist", channel_id),
+            ));
+        }
+    }
+
+    let existing = recurring_alert::Entity::find()
+        .filter(recurring_alert::Column::RecurringTransactionId.eq(recurring_transaction_id))
+        .one(&state.db)
+        .await
+        .map_err(database_error)?;
+    let settings = match existing {
+        Some(existing) => {
+            let mut active: recurring_alert::ActiveModel = existing.into();
+            active.days_before = Set(request.days_before);
+            active.notify_overdue = Set(request.notify_overdue);
+            active.channel_id = Set(request.channel_id);
+            active.update(&state.db).await
+        }
+        None => {
+            recurring_alert::ActiveModel {
+                recurring_transaction_id: Set(recurring_transaction_id),
+                days_before: Set(request.days_before),
+                notify_overdue: Set(request.notify_overdue),
+                channel_id: Set(request.channel_id),
+                ..Default::default()
+            }
+            .insert(&state.db)
+            .await
+        }
+    }
+    .map_err(database_error)?;
+
+    info!(
+        "Alerts of recurring transaction {}: days_before={:?}, notify_overdue={}",
+        recurring_transaction_id, settings.days_before, settings.notify_overdue
+    );
+    Ok(Json(ApiResponse {
+        data: RecurringAlertSettings::from(settings),
+        message: "Alert settings updated successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Send due payment alerts
+///
+/// Alerts about pending recurring instances that are due soon or overdue according to
+/// the alert settings of their recurring transactions, like the server does in the
+/// background. Each instance is alerted about at most once per kind. "Today" is the
+/// date in the server's timezone.
+#[utoipa::path(
+    post,
+    path = "/api/v1/payment-alerts/send",
+    responses(
+        (status = 200, description = "Payment alerts sent", body = ApiResponse<PaymentAlertSummary>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "notifications"
+)]
+#[instrument(skip(state))]
+pub async fn send_due_payment_alerts(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<PaymentAlertSummary>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering send_due_payment_alerts function");
+    let today = compute::today_in(&state.timezone);
+
+    let summary = send_payment_alerts(&state.db, &notifier(&state)?, today)
+        .await
+        .map_err(database_error)?;
+    info!(
+        "Sent {} upcoming and {} overdue payment alerts, {} failed",
+        summary.upcoming_sent, summary.overdue_sent, summary.failed
+    );
+    Ok(Json(ApiResponse {
+        data: summary,
+        message: "Payment alerts sent successfully".to_string(),
+        success: true,
+    }))
+}
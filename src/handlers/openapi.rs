@@ -0,0 +1,129 @@
+use crate::router::{api_routes, ApiRoute};
+use crate::schemas::{ApiDoc, ApiResponse};
+use axum::response::Json;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use tracing::{debug, info, instrument, warn};
+use utoipa::openapi::{OpenApi as OpenApiSpec, PathItemType};
+use utoipa::{OpenApi, ToSchema};
+
+/// A method and path pair in OpenAPI path syntax
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+pub struct DocumentedRoute {
+    /// HTTP method, e.g. "GET"
+    pub method: String,
+    /// Path with `{param}` placeholders
+    pub path: String,
+}
+
+/// How completely the OpenAPI specification covers the router
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OpenApiCoverageResponse {
+    /// Method and path pairs served by the router
+    pub route_count: usize,
+    /// Routes with a matching operation in the specification
+    pub documented_count: usize,
+    /// Routes missing from the specification
+    pub undocumented: Vec<DocumentedRoute>,
+    /// Operations in the specification that no route serves
+    pub unrouted: Vec<DocumentedRoute>,
+    /// True when the router and the specification agree exactly
+    pub complete: bool,
+}
+
+/// Converts an axum `:param` path to the `{param}` syntax used by OpenAPI.
+fn openapi_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(name) => format!("{{{}}}", name),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn operation_method(item_type: &PathItemType) -> &'static str {
+    match item_type {
+        PathItemType::Get => "GET",
+        PathItemType::Post => "POST",
+        PathItemType::Put => "PUT",
+        PathItemType::Delete => "DELETE",
+        PathItemType::Options => "OPTIONS",
+        PathItemType::Head => "HEAD",
+        PathItemType::Patch => "PATCH",
+        PathItemType::Trace => "TRACE",
+        PathItemType::Connect => "CONNECT",
+    }
+}
+
+/// Compares the routes of the router with the operations of the specification.
+pub fn openapi_coverage(routes: &[ApiRoute], spec: &OpenApiSpec) -> OpenApiCoverageResponse {
+    let routed: BTreeSet<DocumentedRoute> = routes
+        .iter()
+        .map(|route| DocumentedRoute {
+            method: route.method.to_string(),
+            path: openapi_path(route.path),
+        })
+        .collect();
+    let documented: BTreeSet<DocumentedRoute> = spec
+        .paths
+        .paths
+        .iter()
+        .flat_map(|(path, item)| {
+            item.operations.keys().map(move |item_type| DocumentedRoute {
+                method: operation_method(item_type).to_string(),
+                path: path.clone(),
+            })
+        })
+        .collect();
+
+    let undocumented: Vec<DocumentedRoute> = routed.difference(&documented).cloned().collect();
+    let unrouted: Vec<DocumentedRoute> = documented.difference(&routed).cloned().collect();
+    OpenApiCoverageResponse {
+        route_count: routed.len(),
+        documented_count: routed.len() - undocumented.len(),
+        complete: undocumented.is_empty() && unrouted.is_empty(),
+        undocumented,
+        unrouted,
+    }
+}
+
+/// Check that every route of the router appears in the OpenAPI specification
+#[utoipa::path(
+    get,
+    path = "/api/v1/openapi/coverage",
+    tag = "openapi",
+    responses(
+        (status = 200, description = "Coverage of the router by the specification", body = ApiResponse<OpenApiCoverageResponse>)
+    )
+)]
+#[instrument]
+pub async fn get_openapi_coverage() -> Json<ApiResponse<OpenApiCoverageResponse>> {
+    let coverage = openapi_coverage(&api_routes(), &ApiDoc::openapi());
+    debug!(
+        "{} of {} routes documented in the OpenAPI specification",
+        coverage.documented_count, coverage.route_count
+    );
+
+    let message = if coverage.complete {
+        info!("OpenAPI specification covers all {} routes", coverage.route_count);
+        "OpenAPI specification matches the router".to_string()
+    } else {
+        warn!(
+            "OpenAPI specification is incomplete: {} undocumented routes, {} unrouted operations",
+            coverage.undocumented.len(),
+            coverage.unrouted.len()
+        );
+        format!(
+            "{} routes are undocumented and {} documented operations have no route",
+            coverage.undocumented.len(),
+            coverage.unrouted.len()
+        )
+    };
+
+    Json(ApiResponse {
+        data: coverage,
+        message,
+        success: true,
+    })
+}
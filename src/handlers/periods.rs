@@ -0,0 +1,296 @@
+use crate::helpers::auth::{ensure_admin, AuthenticatedUser};
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::{NaiveDate, NaiveDateTime};
+use model::entities::locked_period;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, Set,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, instrument, trace, warn};
+use utoipa::{IntoParams, ToSchema};
+
+/// Request body for locking an accounting period
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct LockPeriodRequest {
+    /// First day of the period (inclusive)
+    pub start_date: NaiveDate,
+    /// Last day of the period (inclusive)
+    pub end_date: NaiveDate,
+    /// Optional note, e.g. "Q1 reported to accountant"
+    pub note: Option<String>,
+}
+
+/// Locked period response model
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LockedPeriodResponse {
+    pub id: i32,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub note: Option<String>,
+    pub locked_at: NaiveDateTime,
+}
+
+impl From<locked_period::Model> for LockedPeriodResponse {
+    fn from(model: locked_period::Model) -> Self {
+        Self {
+            id: model.id,
+            start_date: model.start_date,
+            end_date: model.end_date,
+            note: model.note,
+            locked_at: model.locked_at,
+        }
+    }
+}
+
+/// Query parameter accepted by transaction mutations to bypass period locks
+#[derive(Debug, Default, Deserialize, IntoParams, ToSchema)]
+pub struct PeriodLockOverride {
+    /// Set to true to modify transactions dated inside a locked period (administrators only)
+    pub admin_override: Option<bool>,
+}
+
+impl PeriodLockOverride {
+    /// Returns true if the caller asked to bypass period locks.
+    pub fn is_set(&self) -> bool {
+        self.admin_override.unwrap_or(false)
+    }
+}
+
+/// Find the first locked period covering any of the given dates.
+///
+/// Always returns `None` when the admin override is set, which only administrators
+/// may do; anyone else is answered with `403 Forbidden`.
+pub async fn find_locked_period(
+    db: &DatabaseConnection,
+    auth: Option<&AuthenticatedUser>,
+    dates: &[NaiveDate],
+    lock_override: &PeriodLockOverride,
+) -> Result<Option<locked_period::Model>, (StatusCode, Json<ErrorResponse>)> {
+    if lock_override.is_set() {
+        ensure_admin(db, auth, "modify transactions in locked periods").await?;
+        return Ok(None);
+    }
+    if dates.is_empty() {
+        return Ok(None);
+    }
+
+    let mut condition = sea_orm::Condition::any();
+    for date in dates {
+        condition = condition.add(
+            sea_orm::Condition::all()
+                .add(locked_period::Column::StartDate.lte(*date))
+                .add(locked_period::Column::EndDate.gte(*date)),
+        );
+    }
+
+    locked_period::Entity::find().filter(condition).one(db).await.map_err(|e| {
+        error!("Database error while checking locked periods: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Internal server error while checking locked periods".to_string(),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    })
+}
+
+/// Reject the request with `409 Conflict` if any of the dates is inside a locked period.
+///
+/// A non-administrator passing the admin override gets `403 Forbidden`.
+pub async fn ensure_dates_unlocked(
+    db: &DatabaseConnection,
+    auth: Option<&AuthenticatedUser>,
+    dates: &[NaiveDate],
+    lock_override: &PeriodLockOverride,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    match find_locked_period(db, auth, dates, lock_override).await? {
+        None => Ok(()),
+        Some(period) => {
+            warn!("Rejected mutation touching locked period {}", period.id);
+            Err((
+                StatusCode::CONFLICT,
+                Json(ErrorResponse {
+                    error: format!(
+                        "Period {} to {} is locked; pass admin_override=true to modify it",
+                        period.start_date, period.end_date
+                    ),
+                    code: "PERIOD_LOCKED".to_string(),
+                    success: false,
+                }),
+            ))
+        }
+    }
+}
+
+/// Lock an accounting period
+///
+/// Transactions dated within the period can no longer be created, updated or
+/// deleted unless the request passes `admin_override=true`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/periods/lock",
+    request_body = LockPeriodRequest,
+    responses(
+        (status = 201, description = "Period locked", body = ApiResponse<LockedPeriodResponse>),
+        (status = 400, description = "Invalid date range", body = ErrorResponse),
+        (status = 403, description = "The caller is not an administrator", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "periods"
+)]
+#[instrument(skip(state))]
+pub async fn lock_period(
+    State(state): State<AppState>,
+    auth: Option<AuthenticatedUser>,
+    Json(request): Json<LockPeriodRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<LockedPeriodResponse>>), (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering lock_period function");
+    debug!("Locking period: {:?}", request);
+    ensure_admin(&state.db, auth.as_ref(), "lock periods").await?;
+
+    if request.start_date > request.end_date {
+        warn!("Rejected period lock with start {} after end {}", request.start_date, request.end_date);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "start_date must not be after end_date".to_string(),
+                code: "INVALID_PERIOD".to_string(),
+                success: false,
+            }),
+        ));
+    }
+
+    let period = locked_period::ActiveModel {
+        start_date: Set(request.start_date),
+        end_date: Set(request.end_date),
+        note: Set(request.note),
+        locked_at: Set(chrono::Local::now().naive_local()),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await
+    .map_err(|e| {
+        error!("Failed to lock period: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Failed to lock period: {}", e),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    })?;
+
+    info!("Locked period {}: {} to {}", period.id, period.start_date, period.end_date);
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse {
+            data: LockedPeriodResponse::from(period),
+            message: "Period locked successfully".to_string(),
+            success: true,
+        }),
+    ))
+}
+
+/// List locked periods
+#[utoipa::path(
+    get,
+    path = "/api/v1/periods",
+    responses(
+        (status = 200, description = "List of locked periods", body = ApiResponse<Vec<LockedPeriodResponse>>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "periods"
+)]
+#[instrument(skip(state))]
+pub async fn get_locked_periods(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<LockedPeriodResponse>>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_locked_periods function");
+
+    let periods = locked_period::Entity::find()
+        .order_by_asc(locked_period::Column::StartDate)
+        .all(&state.db)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch locked periods: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("Failed to fetch locked periods: {}", e),
+                    code: "DATABASE_ERROR".to_string(),
+                    success: false,
+                }),
+            )
+        })?;
+
+    info!("Fetched {} locked periods", periods.len());
+    Ok(Json(ApiResponse {
+        data: periods.into_iter().map(LockedPeriodResponse::from).collect(),
+        message: "Locked periods retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Unlock a previously locked period
+#[utoipa::path(
+    delete,
+    path = "/api/v1/periods/{period_id}",
+    params(
+        ("period_id" = i32, Path, description = "Locked period ID")
+    ),
+    responses(
+        (status = 204, description = "Period unlocked"),
+        (status = 403, description = "The caller is not an administrator", body = ErrorResponse),
+        (status = 404, description = "Locked period not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "periods"
+)]
+#[instrument(skip(state))]
+pub async fn unlock_period(
+    State(state): State<AppState>,
+    auth: Option<AuthenticatedUser>,
+    Path(period_id): Path<i32>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering unlock_period function");
+    ensure_admin(&state.db, auth.as_ref(), "unlock periods").await?;
+
+    let result = locked_period::Entity::delete_by_id(period_id)
+        .exec(&state.db)
+        .await
+        .map_err(|e| {
+            error!("Failed to unlock period {}: {}", period_id, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("Failed to unlock period: {}", e),
+                    code: "DATABASE_ERROR".to_string(),
+                    success: false,
+                }),
+            )
+        })?;
+
+    if result.rows_affected == 0 {
+        warn!("Locked period not found: id={}", period_id);
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("Locked period with id {} not found", period_id),
+                code: "NOT_FOUND".to_string(),
+                success: false,
+            }),
+        ));
+    }
+
+    info!("Unlocked period {}", period_id);
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,800 @@
+use crate::helpers::access::{account_forbidden, account_read_only, AccountAccess};
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::{NaiveDate, NaiveDateTime};
+use model::entities::{account, one_off_transaction, reconciliation_session};
+use model::entities::reconciliation_session::ReconciliationStatus;
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+    TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, instrument, trace, warn};
+use utoipa::{IntoParams, ToSchema};
+
+/// Request body for starting a reconciliation session
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateReconciliationSessionRequest {
+    /// Account being reconciled
+    pub account_id: i32,
+    /// Closing date printed on the bank statement
+    pub statement_date: NaiveDate,
+    /// Closing balance printed on the bank statement
+    pub statement_balance: Decimal,
+    /// Balance the statement starts from. Defaults to the closing balance of
+    /// the last completed session for the account, or zero.
+    pub opening_balance: Option<Decimal>,
+}
+
+/// Request body for updating an open reconciliation session
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UpdateReconciliationSessionRequest {
+    /// Closing date printed on the bank statement
+    pub statement_date: Option<NaiveDate>,
+    /// Closing balance printed on the bank statement
+    pub statement_balance: Option<Decimal>,
+    /// Balance the statement starts from
+    pub opening_balance: Option<Decimal>,
+}
+
+/// Query parameters for listing reconciliation sessions
+#[derive(Debug, Deserialize, IntoParams, ToSchema)]
+pub struct ReconciliationSessionQuery {
+    /// Filter by account ID
+    pub account_id: Option<i32>,
+}
+
+/// Reconciliation session response model
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReconciliationSessionResponse {
+    pub id: i32,
+    pub account_id: i32,
+    pub statement_date: NaiveDate,
+    pub statement_balance: Decimal,
+    pub opening_balance: Decimal,
+    /// Opening balance plus the effect of all ticked transactions on the account
+    pub cleared_balance: Decimal,
+    /// Statement balance minus cleared balance; the session can be completed once this is zero
+    pub difference: Decimal,
+    /// "Open" or "Completed"
+    pub status: String,
+    pub created_at: NaiveDateTime,
+    pub completed_at: Option<NaiveDateTime>,
+    /// IDs of the one-off transactions ticked off in this session
+    pub transaction_ids: Vec<i32>,
+}
+
+impl ReconciliationSessionResponse {
+    /// Build the response from a session and the transactions ticked off in it.
+    fn from_parts(
+        session: reconciliation_session::Model,
+        transactions: &[one_off_transaction::Model],
+    ) -> Self {
+        let cleared_balance = session.opening_balance
+            + transactions
+                .iter()
+                .map(|tx| signed_amount(tx, session.account_id))
+                .sum::<Decimal>();
+
+        Self {
+            id: session.id,
+            account_id: session.account_id,
+            statement_date: session.statement_date,
+            statement_balance: session.statement_balance,
+            opening_balance: session.opening_balance,
+            cleared_balance,
+            difference: session.statement_balance - cleared_balance,
+            status: match session.status {
+                ReconciliationStatus::Open => "Open".to_string(),
+                ReconciliationStatus::Completed => "Completed".to_string(),
+            },
+            created_at: session.created_at,
+            completed_at: session.completed_at,
+            transaction_ids: transactions.iter().map(|tx| tx.id).collect(),
+        }
+    }
+}
+
+/// Effect of a one-off transaction on the given account (transfers debit the source account).
+fn signed_amount(tx: &one_off_transaction::Model, account_id: i32) -> Decimal {
+    if tx.target_account_id == account_id {
+        tx.amount
+    } else if tx.source_account_id == Some(account_id) {
+        -tx.amount
+    } else {
+        Decimal::ZERO
+    }
+}
+
+fn reconciliation_error(status: StatusCode, message: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error: message,
+            code: "RECONCILIATION_ERROR".to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// Load a session of an account in `access` or fail with 404.
+///
+/// With `write`, sessions of accounts shared read-only are rejected with 403.
+async fn find_session(
+    db: &DatabaseConnection,
+    access: &AccountAccess,
+    session_id: i32,
+    write: bool,
+) -> Result<reconciliation_session::Model, (StatusCode, Json<ErrorResponse>)> {
+    let session = reconciliation_session::Entity::find_by_id(session_id)
+        .one(db)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch reconciliation session {}: {}", session_id, e);
+            reconciliation_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to fetch reconciliation session: {}", e),
+            )
+        })?
+        .ok_or_else(|| {
+            warn!("Reconciliation session not found: id={}", session_id);
+            reconciliation_error(
+                StatusCode::NOT_FOUND,
+                format!("Reconciliation session with id {} not found", session_id),
+            )
+        })?;
+
+    if !access.can_access(session.account_id) {
+        warn!("Account {} is not accessible to user {:?}", session.account_id, access.user_id());
+        return Err(reconciliation_error(
+            StatusCode::NOT_FOUND,
+            format!("Reconciliation session with id {} not found", session_id),
+        ));
+    }
+    if write && !access.can_write(session.account_id) {
+        return Err(account_read_only(session.account_id));
+    }
+    Ok(session)
+}
+
+/// Load a writable session and make sure it can still be modified.
+async fn find_open_session(
+    db: &DatabaseConnection,
+    access: &AccountAccess,
+    session_id: i32,
+) -> Result<reconciliation_session::Model, (StatusCode, Json<ErrorResponse>)> {
+    let session = find_session(db, access, session_id, true).await?;
+    if session.status != ReconciliationStatus::Open {
+        warn!("Reconciliation session {} is already completed", session_id);
+        return Err(reconciliation_error(
+            StatusCode::CONFLICT,
+            format!("Reconciliation session {} is already completed", session_id),
+        ));
+    }
+    Ok(session)
+}
+
+/// Load the transactions ticked off in a session.
+async fn session_transactions(
+    db: &DatabaseConnection,
+    session_id: i32,
+) -> Result<Vec<one_off_transaction::Model>, (StatusCode, Json<ErrorResponse>)> {
+    one_off_transaction::Entity::find()
+        .filter(one_off_transaction::Column::ReconciliationSessionId.eq(session_id))
+        .order_by_asc(one_off_transaction::Column::Date)
+        .all(db)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch transactions for reconciliation session {}: {}", session_id, e);
+            reconciliation_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to fetch session transactions: {}", e),
+            )
+        })
+}
+
+/// Build the full response for a session.
+async fn session_response(
+    db: &DatabaseConnection,
+    session: reconciliation_session::Model,
+) -> Result<ReconciliationSessionResponse, (StatusCode, Json<ErrorResponse>)> {
+    let transactions = session_transactions(db, session.id).await?;
+    Ok(ReconciliationSessionResponse::from_parts(session, &transactions))
+}
+
+/// Start a reconciliation session
+///
+/// Opens a statement-style reconciliation for an account. Only one session per
+/// account may be open at a time.
+#[utoipa::path(
+    post,
+    path = "/api/v1/reconciliation-sessions",
+    request_body = CreateReconciliationSessionRequest,
+    responses(
+        (status = 201, description = "Reconciliation session started", body = ApiResponse<ReconciliationSessionResponse>),
+        (status = 400, description = "Invalid account", body = ErrorResponse),
+        (status = 403, description = "Account is not accessible or shared read-only", body = ErrorResponse),
+        (status = 409, description = "Account already has an open session", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "reconciliation"
+)]
+#[instrument(skip(state))]
+pub async fn create_reconciliation_session(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Json(request): Json<CreateReconciliationSessionRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<ReconciliationSessionResponse>>), (StatusCode, Json<ErrorResponse>)>
+{
+    trace!("Entering create_reconciliation_session function");
+    debug!("Starting reconciliation session: {:?}", request);
+
+    let db = &state.db;
+
+    let account_exists = account::Entity::find_by_id(request.account_id)
+        .one(db)
+        .await
+        .map_err(|e| {
+            error!("Failed to validate account {}: {}", request.account_id, e);
+            reconciliation_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to validate account: {}", e),
+            )
+        })?
+        .is_some();
+    if !account_exists {
+        warn!("Account {} not found for reconciliation", request.account_id);
+        return Err(reconciliation_error(
+            StatusCode::BAD_REQUEST,
+            format!("Account with id {} does not exist", request.account_id),
+        ));
+    }
+    if !access.can_access(request.account_id) {
+        warn!("Account {} is not accessible to user {:?}", request.account_id, access.user_id());
+        return Err(account_forbidden(request.account_id));
+    }
+    if !access.can_write(request.account_id) {
+        return Err(account_read_only(request.account_id));
+    }
+
+    let sessions = reconciliation_session::Entity::find()
+        .filter(reconciliation_session::Column::AccountId.eq(request.account_id))
+        .order_by_desc(reconciliation_session::Column::StatementDate)
+        .all(db)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch reconciliation sessions: {}", e);
+            reconciliation_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to fetch reconciliation sessions: {}", e),
+            )
+        })?;
+
+    if let Some(open) = sessions.iter().find(|s| s.status == ReconciliationStatus::Open) {
+        warn!("Account {} already has open reconciliation session {}", request.account_id, open.id);
+        return Err(reconciliation_error(
+            StatusCode::CONFLICT,
+            format!(
+                "Account {} already has an open reconciliation session ({})",
+                request.account_id, open.id
+            ),
+        ));
+    }
+
+    let opening_balance = request.opening_balance.unwrap_or_else(|| {
+        sessions
+            .iter()
+            .find(|s| s.status == ReconciliationStatus::Completed)
+            .map(|s| s.statement_balance)
+            .unwrap_or(Decimal::ZERO)
+    });
+
+    let session = reconciliation_session::ActiveModel {
+        account_id: Set(request.account_id),
+        statement_date: Set(request.statement_date),
+        statement_balance: Set(request.statement_balance),
+        opening_balance: Set(opening_balance),
+        status: Set(ReconciliationStatus::Open),
+        created_at: Set(chrono::Local::now().naive_local()),
+        completed_at: Set(None),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|e| {
+        error!("Failed to create reconciliation session: {}", e);
+        reconciliation_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to create reconciliation session: {}", e),
+        )
+    })?;
+
+    info!("Reconciliation session created: id={}, account_id={}", session.id, session.account_id);
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse {
+            data: ReconciliationSessionResponse::from_parts(session, &[]),
+            message: "Reconciliation session started".to_string(),
+            success: true,
+        }),
+    ))
+}
+
+/// List reconciliation sessions
+#[utoipa::path(
+    get,
+    path = "/api/v1/reconciliation-sessions",
+    params(ReconciliationSessionQuery),
+    responses(
+        (status = 200, description = "List of reconciliation sessions", body = ApiResponse<Vec<ReconciliationSessionResponse>>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "reconciliation"
+)]
+#[instrument(skip(state))]
+pub async fn get_reconciliation_sessions(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Query(query): Query<ReconciliationSessionQuery>,
+) -> Result<Json<ApiResponse<Vec<ReconciliationSessionResponse>>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_reconciliation_sessions function");
+
+    let db = &state.db;
+    let mut finder = access.filter(reconciliation_session::Entity::find(), [reconciliation_session::Column::AccountId]);
+    if let Some(account_id) = query.account_id {
+        finder = finder.filter(reconciliation_session::Column::AccountId.eq(account_id));
+    }
+
+    let sessions = finder
+        .order_by_desc(reconciliation_session::Column::StatementDate)
+        .all(db)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch reconciliation sessions: {}", e);
+            reconciliation_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to fetch reconciliation sessions: {}", e),
+            )
+        })?;
+
+    let mut responses = Vec::with_capacity(sessions.len());
+    for session in sessions {
+        responses.push(session_response(db, session).await?);
+    }
+
+    info!("Fetched {} reconciliation sessions", responses.len());
+    Ok(Json(ApiResponse {
+        data: responses,
+        message: "Reconciliation sessions retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Get a reconciliation session with its running difference
+#[utoipa::path(
+    get,
+    path = "/api/v1/reconciliation-sessions/{session_id}",
+    params(
+        ("session_id" = i32, Path, description = "Reconciliation session ID")
+    ),
+    responses(
+        (status = 200, description = "Reconciliation session details", body = ApiResponse<ReconciliationSessionResponse>),
+        (status = 404, description = "Session not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "reconciliation"
+)]
+#[instrument(skip(state))]
+pub async fn get_reconciliation_session(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Path(session_id): Path<i32>,
+) -> Result<Json<ApiResponse<ReconciliationSessionResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_reconciliation_session function");
+
+    let db = &state.db;
+    let session = find_session(db, &access, session_id, false).await?;
+
+    Ok(Json(ApiResponse {
+        data: session_response(db, session).await?,
+        message: "Reconciliation session retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Update the statement figures of an open reconciliation session
+#[utoipa::path(
+    put,
+    path = "/api/v1/reconciliation-sessions/{session_id}",
+    params(
+        ("session_id" = i32, Path, description = "Reconciliation session ID")
+    ),
+    request_body = UpdateReconciliationSessionRequest,
+    responses(
+        (status = 200, description = "Reconciliation session updated", body = ApiResponse<ReconciliationSessionResponse>),
+        (status = 403, description = "Account is shared read-only", body = ErrorResponse),
+        (status = 404, description = "Session not found", body = ErrorResponse),
+        (status = 409, description = "Session already completed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "reconciliation"
+)]
+#[instrument(skip(state))]
+pub async fn update_reconciliation_session(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Path(session_id): Path<i32>,
+    Json(request): Json<UpdateReconciliationSessionRequest>,
+) -> Result<Json<ApiResponse<ReconciliationSessionResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering update_reconciliation_session function");
+    debug!("Updating reconciliation session {}: {:?}", session_id, request);
+
+    let db = &state.db;
+    let session = find_open_session(db, &access, session_id).await?;
+
+    let mut active: reconciliation_session::ActiveModel = session.into();
+    if let Some(statement_date) = request.statement_date {
+        active.statement_date = Set(statement_date);
+    }
+    if let Some(statement_balance) = request.statement_balance {
+        active.statement_balance = Set(statement_balance);
+    }
+    if let Some(opening_balance) = request.opening_balance {
+        active.opening_balance = Set(opening_balance);
+    }
+
+    let updated = active.update(db).await.map_err(|e| {
+        error!("Failed to update reconciliation session {}: {}", session_id, e);
+        reconciliation_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to update reconciliation session: {}", e),
+        )
+    })?;
+
+    info!("Reconciliation session updated: id={}", session_id);
+    Ok(Json(ApiResponse {
+        data: session_response(db, updated).await?,
+        message: "Reconciliation session updated successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Discard an open reconciliation session
+///
+/// Ticked transactions are released back to the unreconciled pool.
+/// Completed sessions cannot be deleted because their transactions are locked.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/reconciliation-sessions/{session_id}",
+    params(
+        ("session_id" = i32, Path, description = "Reconciliation session ID")
+    ),
+    responses(
+        (status = 204, description = "Reconciliation session discarded"),
+        (status = 403, description = "Account is shared read-only", body = ErrorResponse),
+        (status = 404, description = "Session not found", body = ErrorResponse),
+        (status = 409, description = "Session already completed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "reconciliation"
+)]
+#[instrument(skip(state))]
+pub async fn delete_reconciliation_session(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Path(session_id): Path<i32>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering delete_reconciliation_session function");
+
+    let db = &state.db;
+    let session = find_open_session(db, &access, session_id).await?;
+
+    let txn = db.begin().await.map_err(|e| {
+        error!("Failed to start database transaction: {}", e);
+        reconciliation_error(StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e))
+    })?;
+
+    one_off_transaction::Entity::update_many()
+        .col_expr(
+            one_off_transaction::Column::ReconciliationSessionId,
+            sea_orm::sea_query::Expr::value(Option::<i32>::None),
+        )
+        .filter(one_off_transaction::Column::ReconciliationSessionId.eq(session_id))
+        .exec(&txn)
+        .await
+        .map_err(|e| {
+            error!("Failed to release transactions of session {}: {}", session_id, e);
+            reconciliation_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to release session transactions: {}", e),
+            )
+        })?;
+
+    let active: reconciliation_session::ActiveModel = session.into();
+    active.delete(&txn).await.map_err(|e| {
+        error!("Failed to delete reconciliation session {}: {}", session_id, e);
+        reconciliation_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to delete reconciliation session: {}", e),
+        )
+    })?;
+
+    txn.commit().await.map_err(|e| {
+        error!("Failed to commit session deletion: {}", e);
+        reconciliation_error(StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e))
+    })?;
+
+    info!("Reconciliation session discarded: id={}", session_id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Tick off a transaction in a reconciliation session
+#[utoipa::path(
+    post,
+    path = "/api/v1/reconciliation-sessions/{session_id}/transactions/{transaction_id}",
+    params(
+        ("session_id" = i32, Path, description = "Reconciliation session ID"),
+        ("transaction_id" = i32, Path, description = "One-off transaction ID")
+    ),
+    responses(
+        (status = 200, description = "Transaction ticked off", body = ApiResponse<ReconciliationSessionResponse>),
+        (status = 400, description = "Transaction cannot be reconciled in this session", body = ErrorResponse),
+        (status = 403, description = "Account is shared read-only", body = ErrorResponse),
+        (status = 404, description = "Session or transaction not found", body = ErrorResponse),
+        (status = 409, description = "Session completed or transaction already reconciled", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "reconciliation"
+)]
+#[instrument(skip(state))]
+pub async fn add_reconciliation_transaction(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Path((session_id, transaction_id)): Path<(i32, i32)>,
+) -> Result<Json<ApiResponse<ReconciliationSessionResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering add_reconciliation_transaction function");
+
+    let db = &state.db;
+    let session = find_open_session(db, &access, session_id).await?;
+    let transaction = find_transaction(db, transaction_id).await?;
+
+    if transaction.target_account_id != session.account_id
+        && transaction.source_account_id != Some(session.account_id)
+    {
+        warn!("Transaction {} does not touch account {}", transaction_id, session.account_id);
+        return Err(reconciliation_error(
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Transaction {} does not belong to account {}",
+                transaction_id, session.account_id
+            ),
+        ));
+    }
+    if transaction.is_simulated {
+        return Err(reconciliation_error(
+            StatusCode::BAD_REQUEST,
+            "Simulated transactions cannot be reconciled".to_string(),
+        ));
+    }
+    if transaction.date > session.statement_date {
+        return Err(reconciliation_error(
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Transaction {} is dated after the statement date {}",
+                transaction_id, session.statement_date
+            ),
+        ));
+    }
+    if transaction.is_locked {
+        return Err(reconciliation_error(
+            StatusCode::CONFLICT,
+            format!("Transaction {} is already reconciled and locked", transaction_id),
+        ));
+    }
+    match transaction.reconciliation_session_id {
+        Some(other) if other != session_id => {
+            return Err(reconciliation_error(
+                StatusCode::CONFLICT,
+                format!("Transaction {} is already part of reconciliation session {}", transaction_id, other),
+            ));
+        }
+        _ => {}
+    }
+
+    let mut active: one_off_transaction::ActiveModel = transaction.into();
+    active.reconciliation_session_id = Set(Some(session_id));
+    active.update(db).await.map_err(|e| {
+        error!("Failed to tick transaction {}: {}", transaction_id, e);
+        reconciliation_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to update transaction: {}", e),
+        )
+    })?;
+
+    debug!("Ticked transaction {} in reconciliation session {}", transaction_id, session_id);
+    Ok(Json(ApiResponse {
+        data: session_response(db, session).await?,
+        message: "Transaction added to reconciliation session".to_string(),
+        success: true,
+    }))
+}
+
+/// Untick a transaction from an open reconciliation session
+#[utoipa::path(
+    delete,
+    path = "/api/v1/reconciliation-sessions/{session_id}/transactions/{transaction_id}",
+    params(
+        ("session_id" = i32, Path, description = "Reconciliation session ID"),
+        ("transaction_id" = i32, Path, description = "One-off transaction ID")
+    ),
+    responses(
+        (status = 200, description = "Transaction removed from session", body = ApiResponse<ReconciliationSessionResponse>),
+        (status = 400, description = "Transaction is not part of this session", body = ErrorResponse),
+        (status = 403, description = "Account is shared read-only", body = ErrorResponse),
+        (status = 404, description = "Session or transaction not found", body = ErrorResponse),
+        (status = 409, description = "Session already completed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "reconciliation"
+)]
+#[instrument(skip(state))]
+pub async fn remove_reconciliation_transaction(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Path((session_id, transaction_id)): Path<(i32, i32)>,
+) -> Result<Json<ApiResponse<ReconciliationSessionResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering remove_reconciliation_transaction function");
+
+    let db = &state.db;
+    let session = find_open_session(db, &access, session_id).await?;
+    let transaction = find_transaction(db, transaction_id).await?;
+
+    if transaction.reconciliation_session_id != Some(session_id) {
+        return Err(reconciliation_error(
+            StatusCode::BAD_REQUEST,
+            format!("Transaction {} is not part of reconciliation session {}", transaction_id, session_id),
+        ));
+    }
+
+    let mut active: one_off_transaction::ActiveModel = transaction.into();
+    active.reconciliation_session_id = Set(None);
+    active.update(db).await.map_err(|e| {
+        error!("Failed to untick transaction {}: {}", transaction_id, e);
+        reconciliation_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to update transaction: {}", e),
+        )
+    })?;
+
+    debug!("Unticked transaction {} from reconciliation session {}", transaction_id, session_id);
+    Ok(Json(ApiResponse {
+        data: session_response(db, session).await?,
+        message: "Transaction removed from reconciliation session".to_string(),
+        success: true,
+    }))
+}
+
+/// Complete a reconciliation session
+///
+/// Requires the difference between the statement balance and the cleared balance
+/// to be zero. All ticked transactions are locked against further edits.
+#[utoipa::path(
+    post,
+    path = "/api/v1/reconciliation-sessions/{session_id}/complete",
+    params(
+        ("session_id" = i32, Path, description = "Reconciliation session ID")
+    ),
+    responses(
+        (status = 200, description = "Reconciliation session completed", body = ApiResponse<ReconciliationSessionResponse>),
+        (status = 400, description = "Statement does not balance yet", body = ErrorResponse),
+        (status = 403, description = "Account is shared read-only", body = ErrorResponse),
+        (status = 404, description = "Session not found", body = ErrorResponse),
+        (status = 409, description = "Session already completed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "reconciliation"
+)]
+#[instrument(skip(state))]
+pub async fn complete_reconciliation_session(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Path(session_id): Path<i32>,
+) -> Result<Json<ApiResponse<ReconciliationSessionResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering complete_reconciliation_session function");
+
+    let db = &state.db;
+    let session = find_open_session(db, &access, session_id).await?;
+    let transactions = session_transactions(db, session_id).await?;
+
+    let summary = ReconciliationSessionResponse::from_parts(session.clone(), &transactions);
+    if !summary.difference.is_zero() {
+        warn!(
+            "Reconciliation session {} does not balance: difference {}",
+            session_id, summary.difference
+        );
+        return Err(reconciliation_error(
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Statement does not balance yet: difference of {} remains",
+                summary.difference
+            ),
+        ));
+    }
+
+    let txn = db.begin().await.map_err(|e| {
+        error!("Failed to start database transaction: {}", e);
+        reconciliation_error(StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e))
+    })?;
+
+    one_off_transaction::Entity::update_many()
+        .col_expr(
+            one_off_transaction::Column::IsLocked,
+            sea_orm::sea_query::Expr::value(true),
+        )
+        .filter(one_off_transaction::Column::ReconciliationSessionId.eq(session_id))
+        .exec(&txn)
+        .await
+        .map_err(|e| {
+            error!("Failed to lock transactions of session {}: {}", session_id, e);
+            reconciliation_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to lock session transactions: {}", e),
+            )
+        })?;
+
+    let mut active: reconciliation_session::ActiveModel = session.into();
+    active.status = Set(ReconciliationStatus::Completed);
+    active.completed_at = Set(Some(chrono::Local::now().naive_local()));
+    let completed = active.update(&txn).await.map_err(|e| {
+        error!("Failed to complete reconciliation session {}: {}", session_id, e);
+        reconciliation_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to complete reconciliation session: {}", e),
+        )
+    })?;
+
+    txn.commit().await.map_err(|e| {
+        error!("Failed to commit session completion: {}", e);
+        reconciliation_error(StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e))
+    })?;
+
+    info!(
+        "Reconciliation session {} completed, {} transaction(s) locked",
+        session_id,
+        transactions.len()
+    );
+    Ok(Json(ApiResponse {
+        data: ReconciliationSessionResponse::from_parts(completed, &transactions),
+        message: "Reconciliation session completed".to_string(),
+        success: true,
+    }))
+}
+
+/// Load a one-off transaction or fail with 404.
+async fn find_transaction(
+    db: &DatabaseConnection,
+    transaction_id: i32,
+) -> Result<one_off_transaction::Model, (StatusCode, Json<ErrorResponse>)> {
+    one_off_transaction::Entity::find_by_id(transaction_id)
+        .one(db)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch transaction {}: {}", transaction_id, e);
+            reconciliation_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to fetch transaction: {}", e),
+            )
+        })?
+        .ok_or_else(|| {
+            warn!("Transaction not found: id={}", transaction_id);
+            reconciliation_error(
+                StatusCode::NOT_FOUND,
+                format!("Transaction with id {} not found", transaction_id),
+            )
+        })
+}
@@ -1,3 +1,4 @@
+use crate::helpers::access::{account_forbidden, account_read_only, AccountAccess};
 use crate::schemas::{ApiResponse, AppState, ErrorResponse};
 use axum::{
     extract::{Path, Query, State},
@@ -6,9 +7,10 @@ use axum::{
 };
 use axum_valid::Valid;
 use chrono::NaiveDate;
+use common::{PaginatedResponse, Pagination};
 use model::entities::{recurring_income, recurring_transaction};
 use rust_decimal::Decimal;
-use sea_orm::{ActiveModelTrait, EntityTrait, Set, PaginatorTrait, QueryOrder, QueryFilter, ColumnTrait};
+use sea_orm::{ActiveModelTrait, DbErr, EntityTrait, Set, PaginatorTrait, QueryOrder, QueryFilter, ColumnTrait};
 use serde::{Deserialize, Serialize};
 use tracing::{instrument, error, warn, info, debug, trace};
 use utoipa::{ToSchema, IntoParams};
@@ -129,6 +131,30 @@ fn parse_recurrence_period(period_str: &str) -> Result<recurring_transaction::Re
     }
 }
 
+fn recurring_income_not_found(recurring_income_id: i32) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse {
+            error: format!("Recurring income with id {} does not exist", recurring_income_id),
+            code: "RECURRING_INCOME_NOT_FOUND".to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// Rejects booking a recurring income on an account the caller can't write to.
+fn ensure_target_writable(access: &AccountAccess, account_id: i32) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if !access.can_access(account_id) {
+        warn!("User {:?} has no access to account {}", access.user_id(), account_id);
+        return Err(account_forbidden(account_id));
+    }
+    if !access.can_write(account_id) {
+        warn!("Account {} is read-only for user {:?}", account_id, access.user_id());
+        return Err(account_read_only(account_id));
+    }
+    Ok(())
+}
+
 /// Create a new recurring income
 #[utoipa::path(
     post,
@@ -138,17 +164,21 @@ fn parse_recurrence_period(period_str: &str) -> Result<recurring_transaction::Re
     responses(
         (status = 201, description = "Recurring income created successfully", body = ApiResponse<RecurringIncomeResponse>),
         (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "No access to the target account, or it is shared read-only", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
 #[instrument]
 pub async fn create_recurring_income(
     State(state): State<AppState>,
+    access: AccountAccess,
     Json(request): Json<CreateRecurringIncomeRequest>,
 ) -> Result<(StatusCode, Json<ApiResponse<RecurringIncomeResponse>>), (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering create_recurring_income function");
     debug!("Creating recurring income: {}", request.name);
 
+    ensure_target_writable(&access, request.target_account_id)?;
+
     // Parse the recurrence period
     let period = match parse_recurrence_period(&request.period) {
         Ok(p) => p,
@@ -213,7 +243,7 @@ pub async fn create_recurring_income(
     tag = "recurring-incomes",
     params(RecurringIncomeQuery),
     responses(
-        (status = 200, description = "Recurring incomes retrieved successfully", body = ApiResponse<Vec<RecurringIncomeResponse>>),
+        (status = 200, description = "Recurring incomes retrieved successfully", body = PaginatedResponse<Vec<RecurringIncomeResponse>>),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
@@ -221,7 +251,8 @@ pub async fn create_recurring_income(
 pub async fn get_recurring_incomes(
     Valid(Query(query)): Valid<Query<RecurringIncomeQuery>>,
     State(state): State<AppState>,
-) -> Result<(StatusCode, Json<ApiResponse<Vec<RecurringIncomeResponse>>>), (StatusCode, Json<ErrorResponse>)> {
+    access: AccountAccess,
+) -> Result<(StatusCode, Json<PaginatedResponse<Vec<RecurringIncomeResponse>>>), (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering get_recurring_incomes function");
 
     let page = query.page.unwrap_or(1);
@@ -229,45 +260,39 @@ pub async fn get_recurring_incomes(
 
     debug!("Fetching recurring incomes - page: {}, limit: {}", page, limit);
 
-    let mut query_builder = recurring_income::Entity::find();
+    let mut query_builder = access.filter(recurring_income::Entity::find(), [recurring_income::Column::TargetAccountId]);
 
     // Apply filters
     if let Some(target_account_id) = query.target_account_id {
         query_builder = query_builder.filter(recurring_income::Column::TargetAccountId.eq(target_account_id));
     }
 
-    match query_builder
+    let database_error = |e: DbErr| {
+        error!("Failed to retrieve recurring incomes: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to retrieve recurring incomes".to_string(),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    };
+
+    let paginator = query_builder
         .order_by_asc(recurring_income::Column::Id)
-        .paginate(&state.db, limit)
-        .fetch_page(page - 1)
-        .await
-    {
-        Ok(incomes) => {
-            info!("Successfully retrieved {} recurring incomes", incomes.len());
-            let response_data: Vec<RecurringIncomeResponse> = incomes
-                .into_iter()
-                .map(RecurringIncomeResponse::from)
-                .collect();
+        .paginate(&state.db, limit);
+    let total_count = paginator.num_items().await.map_err(database_error)?;
+    let incomes = paginator.fetch_page(page - 1).await.map_err(database_error)?;
+    info!("Successfully retrieved {} of {} recurring incomes", incomes.len(), total_count);
 
-            let response = ApiResponse {
-                data: response_data,
-                message: "Recurring incomes retrieved successfully".to_string(),
-                success: true,
-            };
-            Ok((StatusCode::OK, Json(response)))
-        }
-        Err(e) => {
-            error!("Failed to retrieve recurring incomes: {}", e);
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "Failed to retrieve recurring incomes".to_string(),
-                    code: "DATABASE_ERROR".to_string(),
-                    success: false,
-                }),
-            ))
-        }
-    }
+    let response = PaginatedResponse {
+        data: incomes.into_iter().map(RecurringIncomeResponse::from).collect(),
+        pagination: Pagination::new(total_count, page, limit),
+        message: "Recurring incomes retrieved successfully".to_string(),
+        success: true,
+    };
+    Ok((StatusCode::OK, Json(response)))
 }
 
 /// Get a specific recurring income by ID
@@ -285,6 +310,7 @@ pub async fn get_recurring_incomes(
 pub async fn get_recurring_income(
     Path(recurring_income_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
 ) -> Result<(StatusCode, Json<ApiResponse<RecurringIncomeResponse>>), (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering get_recurring_income function");
     debug!("Fetching recurring income with ID: {}", recurring_income_id);
@@ -293,7 +319,7 @@ pub async fn get_recurring_income(
         .one(&state.db)
         .await
     {
-        Ok(Some(income)) => {
+        Ok(Some(income)) if access.can_access(income.target_account_id) => {
             info!("Successfully retrieved recurring income: {}", income.name);
             let response = ApiResponse {
                 data: RecurringIncomeResponse::from(income),
@@ -302,16 +328,9 @@ pub async fn get_recurring_income(
             };
             Ok((StatusCode::OK, Json(response)))
         }
-        Ok(None) => {
+        Ok(_) => {
             warn!("Recurring income with ID {} not found", recurring_income_id);
-            Err((
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse {
-                    error: format!("Recurring income with id {} does not exist", recurring_income_id),
-                    code: "RECURRING_INCOME_NOT_FOUND".to_string(),
-                    success: false,
-                }),
-            ))
+            Err(recurring_income_not_found(recurring_income_id))
         }
         Err(e) => {
             error!("Database error while fetching recurring income: {}", e);
@@ -336,6 +355,7 @@ pub async fn get_recurring_income(
     responses(
         (status = 200, description = "Recurring income updated successfully", body = ApiResponse<RecurringIncomeResponse>),
         (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "The account is shared read-only, or no access to the new target account", body = ErrorResponse),
         (status = 404, description = "Recurring income not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
@@ -344,6 +364,7 @@ pub async fn get_recurring_income(
 pub async fn update_recurring_income(
     Path(recurring_income_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
     Json(request): Json<UpdateRecurringIncomeRequest>,
 ) -> Result<(StatusCode, Json<ApiResponse<RecurringIncomeResponse>>), (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering update_recurring_income function");
@@ -354,17 +375,10 @@ pub async fn update_recurring_income(
         .one(&state.db)
         .await
     {
-        Ok(Some(income)) => income,
-        Ok(None) => {
+        Ok(Some(income)) if access.can_access(income.target_account_id) => income,
+        Ok(_) => {
             warn!("Recurring income with ID {} not found", recurring_income_id);
-            return Err((
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse {
-                    error: format!("Recurring income with id {} does not exist", recurring_income_id),
-                    code: "RECURRING_INCOME_NOT_FOUND".to_string(),
-                    success: false,
-                }),
-            ));
+            return Err(recurring_income_not_found(recurring_income_id));
         }
         Err(e) => {
             error!("Database error while fetching recurring income: {}", e);
@@ -379,6 +393,14 @@ pub async fn update_recurring_income(
         }
     };
 
+    if !access.can_write(existing_income.target_account_id) {
+        warn!("Recurring income {} is on a read-only account", recurring_income_id);
+        return Err(account_read_only(existing_income.target_account_id));
+    }
+    if let Some(target_account_id) = request.target_account_id {
+        ensure_target_writable(&access, target_account_id)?;
+    }
+
     // Parse period if provided
     let period = if let Some(period_str) = &request.period {
         match parse_recurrence_period(period_str) {
@@ -464,6 +486,7 @@ pub async fn update_recurring_income(
     tag = "recurring-incomes",
     responses(
         (status = 200, description = "Recurring income deleted successfully", body = ApiResponse<String>),
+        (status = 403, description = "The account is shared read-only", body = ErrorResponse),
         (status = 404, description = "Recurring income not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
@@ -472,6 +495,7 @@ pub async fn update_recurring_income(
 pub async fn delete_recurring_income(
     Path(recurring_income_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
 ) -> Result<(StatusCode, Json<ApiResponse<String>>), (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering delete_recurring_income function");
     debug!("Deleting recurring income with ID: {}", recurring_income_id);
@@ -481,7 +505,11 @@ pub async fn delete_recurring_income(
         .one(&state.db)
         .await
     {
-        Ok(Some(_)) => {
+        Ok(Some(income)) if access.can_access(income.target_account_id) => {
+            if !access.can_write(income.target_account_id) {
+                warn!("Recurring income {} is on a read-only account", recurring_income_id);
+                return Err(account_read_only(income.target_account_id));
+            }
             // Income exists, proceed with deletion
             match recurring_income::Entity::delete_by_id(recurring_income_id)
                 .exec(&state.db)
@@ -509,16 +537,9 @@ pub async fn delete_recurring_income(
                 }
             }
         }
-        Ok(None) => {
+        Ok(_) => {
             warn!("Recurring income with ID {} not found", recurring_income_id);
-            Err((
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse {
-                    error: format!("Recurring income with id {} does not exist", recurring_income_id),
-                    code: "RECURRING_INCOME_NOT_FOUND".to_string(),
-                    success: false,
-                }),
-            ))
+            Err(recurring_income_not_found(recurring_income_id))
         }
         Err(e) => {
             error!("Database error while checking recurring income existence: {}", e);
@@ -0,0 +1,337 @@
+use crate::helpers::access::AccountAccess;
+use crate::helpers::timezone::UserTimezone;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::{Months, NaiveDate};
+use common::reports::{CashflowReportDto, FlowReportDto, ReportGranularity};
+use compute::account::utils::{generate_occurrences, load_holiday_calendar, recurring_transaction_occurrences};
+use compute::cashflow_report::compute_cashflow_report;
+use compute::flows::{build_flow_report, FlowEntry};
+use model::entities::{
+    account, category, imported_transaction, one_off_transaction, recurring_income, recurring_transaction,
+    recurring_transaction_instance,
+};
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter};
+use serde::Deserialize;
+use std::collections::HashMap;
+use tracing::{debug, error, info, instrument, trace, warn};
+use utoipa::{IntoParams, ToSchema};
+
+/// Query parameters for the cashflow report
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct CashflowReportQuery {
+    /// `week`, `month` or `quarter` (default: month)
+    pub granularity: Option<ReportGranularity>,
+    /// First day of the report (default: one year before end_date)
+    pub start_date: Option<NaiveDate>,
+    /// Last day of the report (default: today)
+    pub end_date: Option<NaiveDate>,
+}
+
+fn report_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// Collects amounts booked to the given accounts between `start` and `end`.
+///
+/// Covers confirmed, non-simulated one-off transactions, occurrences of approved recurring
+/// transactions (recorded instances override the amount and category, skipped ones are
+/// left out), recurring incomes and imported transactions not yet reconciled to one of
+/// those. Transfers between own accounts are included and carry their source account.
+pub(crate) async fn gather_flow_entries(
+    db: &DatabaseConnection,
+    account_ids: &[i32],
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<Vec<FlowEntry>, DbErr> {
+    let mut entries: Vec<FlowEntry> = one_off_transaction::Entity::find()
+        .filter(one_off_transaction::Column::Date.between(start, end))
+        .filter(one_off_transaction::Column::TargetAccountId.is_in(account_ids.to_vec()))
+        .filter(one_off_transaction::Column::IsSimulated.eq(false))
+        .filter(one_off_transaction::Column::Status.eq(one_off_transaction::OneOffStatus::Confirmed))
+        .filter(one_off_transaction::Column::IncludeInStatistics.eq(true))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|transaction| FlowEntry {
+            date: transaction.date,
+            account_id: transaction.target_account_id,
+            source_account_id: transaction.source_account_id,
+            amount: transaction.amount,
+            category_id: transaction.category_id,
+            source_name: None,
+        })
+        .collect();
+
+    entries.extend(
+        imported_transaction::Entity::find()
+            .filter(imported_transaction::Column::Date.between(start, end))
+            .filter(imported_transaction::Column::AccountId.is_in(account_ids.to_vec()))
+            .filter(imported_transaction::Column::ReconciledTransactionId.is_null())
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|transaction| FlowEntry {
+                date: transaction.date,
+                account_id: transaction.account_id,
+                source_account_id: None,
+                amount: transaction.amount,
+                category_id: transaction.category_id,
+                source_name: None,
+            }),
+    );
+
+    let recurring = recurring_transaction::Entity::find()
+        .filter(recurring_transaction::Column::TargetAccountId.is_in(account_ids.to_vec()))
+        .filter(recurring_transaction::Column::IsSimulated.eq(false))
+        .filter(recurring_transaction::Column::IncludeInStatistics.eq(true))
+        .filter(recurring_transaction::Column::ApprovalStatus.eq(recurring_transaction::ApprovalStatus::Approved))
+        .all(db)
+        .await?;
+    let instances = recurring_transaction_instance::Entity::find()
+        .filter(recurring_transaction_instance::Column::DueDate.between(start, end))
+        .all(db)
+        .await?;
+    let instance_map: HashMap<(i32, NaiveDate), &recurring_transaction_instance::Model> = instances
+        .iter()
+        .map(|instance| ((instance.recurring_transaction_id, instance.due_date), instance))
+        .collect();
+    let holidays = load_holiday_calendar(db).await?;
+
+    for rtxn in &recurring {
+        for date in recurring_transaction_occurrences(rtxn, &holidays, start, end) {
+            let (amount, category_id) = match instance_map.get(&(rtxn.id, date)) {
+                Some(instance) if instance.status == recurring_transaction_instance::InstanceStatus::Skipped => {
+                    trace!("Skipping instance for recurring txn {} on {}", rtxn.id, date);
+                    continue;
+                }
+                Some(instance) => (
+                    instance.paid_amount.unwrap_or(instance.expected_amount),
+                    instance.category_id.or(rtxn.category_id),
+                ),
+                None => (rtxn.amount, rtxn.category_id),
+            };
+            entries.push(FlowEntry {
+                date,
+                account_id: rtxn.target_account_id,
+                source_account_id: rtxn.source_account_id,
+                amount,
+                category_id,
+                source_name: Some(rtxn.name.clone()),
+            });
+        }
+    }
+
+    let incomes = recurring_income::Entity::find()
+        .filter(recurring_income::Column::TargetAccountId.is_in(account_ids.to_vec()))
+        .filter(recurring_income::Column::IsSimulated.eq(false))
+        .filter(recurring_income::Column::IncludeInStatistics.eq(true))
+        .all(db)
+        .await?;
+    for income in &incomes {
+        for date in generate_occurrences(income.start_date, income.end_date, &income.period, &holidays, start, end) {
+            entries.push(FlowEntry {
+                date,
+                account_id: income.target_account_id,
+                source_account_id: None,
+                amount: income.amount,
+                category_id: None,
+                source_name: Some(income.source_name.clone().unwrap_or_else(|| income.name.clone())),
+            });
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Cashflow report across all accounts in `access` included in statistics.
+///
+/// Shared by the HTTP endpoint and `finrust report`.
+pub(crate) async fn build_cashflow_report(
+    db: &DatabaseConnection,
+    access: &AccountAccess,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    granularity: ReportGranularity,
+) -> Result<CashflowReportDto, DbErr> {
+    let accounts = access
+        .filter(account::Entity::find(), [account::Column::Id])
+        .filter(account::Column::IncludeInStatistics.eq(true))
+        .all(db)
+        .await?;
+    let account_ids: Vec<i32> = accounts.iter().map(|account| account.id).collect();
+    debug!(
+        "Building {:?} cashflow report from {} to {} over {} accounts",
+        granularity,
+        start_date,
+        end_date,
+        account_ids.len()
+    );
+
+    // Transfers between own accounts move money around without earning or spending it
+    let amounts = gather_flow_entries(db, &account_ids, start_date, end_date)
+        .await?
+        .into_iter()
+        .filter(|entry| entry.source_account_id.is_none())
+        .map(|entry| (entry.date, entry.amount));
+    Ok(compute_cashflow_report(amounts, start_date, end_date, granularity))
+}
+
+/// Money flow report across all accounts in `access` included in statistics.
+///
+/// Shared by the HTTP endpoint and `finrust report`.
+pub(crate) async fn build_money_flow_report(
+    db: &DatabaseConnection,
+    access: &AccountAccess,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<FlowReportDto, DbErr> {
+    let accounts = access
+        .filter(account::Entity::find(), [account::Column::Id])
+        .filter(account::Column::IncludeInStatistics.eq(true))
+        .all(db)
+        .await?;
+    let categories = category::Entity::find().all(db).await?;
+    let account_ids: Vec<i32> = accounts.iter().map(|account| account.id).collect();
+    debug!(
+        "Building flow report from {} to {} over {} accounts",
+        start_date,
+        end_date,
+        account_ids.len()
+    );
+
+    let entries = gather_flow_entries(db, &account_ids, start_date, end_date).await?;
+    Ok(build_flow_report(&entries, &accounts, &categories, start_date, end_date))
+}
+
+fn resolve_range(
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+    today: NaiveDate,
+) -> Result<(NaiveDate, NaiveDate), (StatusCode, Json<ErrorResponse>)> {
+    let end_date = end_date.unwrap_or(today);
+    let start_date = start_date.unwrap_or_else(|| {
+        end_date
+            .checked_sub_months(Months::new(12))
+            .and_then(|date| date.succ_opt())
+            .unwrap_or(end_date)
+    });
+    if start_date > end_date {
+        warn!("Rejected report range {} to {}", start_date, end_date);
+        return Err(report_error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_DATE_RANGE",
+            "start_date must be before or equal to end_date".to_string(),
+        ));
+    }
+    Ok((start_date, end_date))
+}
+
+fn database_error(e: DbErr) -> (StatusCode, Json<ErrorResponse>) {
+    error!("Database error while building report: {}", e);
+    report_error(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", e.to_string())
+}
+
+/// Get the cashflow report
+///
+/// Income, expenses and their difference per week, month or quarter across all accounts
+/// the caller can access that are included in statistics.
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/cashflow",
+    params(CashflowReportQuery),
+    responses(
+        (status = 200, description = "Cashflow per period", body = ApiResponse<CashflowReportDto>),
+        (status = 400, description = "Invalid date range", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "reports"
+)]
+#[instrument(skip(state))]
+pub async fn get_cashflow_report(
+    State(state): State<AppState>,
+    Query(query): Query<CashflowReportQuery>,
+    timezone: UserTimezone,
+    access: AccountAccess,
+) -> Result<Json<ApiResponse<CashflowReportDto>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_cashflow_report function");
+    let granularity = query.granularity.unwrap_or_default();
+    let (start_date, end_date) = resolve_range(query.start_date, query.end_date, timezone.today())?;
+
+    let report = build_cashflow_report(&state.db, &access, start_date, end_date, granularity)
+        .await
+        .map_err(database_error)?;
+
+    info!(
+        "Computed cashflow report with {} periods, net {}",
+        report.periods.len(),
+        report.total_net
+    );
+    Ok(Json(ApiResponse {
+        data: report,
+        message: "Cashflow report retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Query parameters for the flow report
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct FlowReportQuery {
+    /// First day of the report (default: one year before end_date)
+    pub start_date: Option<NaiveDate>,
+    /// Last day of the report (default: today)
+    pub end_date: Option<NaiveDate>,
+}
+
+/// Get the money flow report
+///
+/// Nodes and edges describing how money flowed from income sources into accounts, between
+/// accounts and out to top-level spending categories, for rendering a Sankey diagram.
+/// Covers all accounts the caller can access that are included in statistics.
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/flows",
+    params(FlowReportQuery),
+    responses(
+        (status = 200, description = "Money flow between income sources, accounts and categories", body = ApiResponse<FlowReportDto>),
+        (status = 400, description = "Invalid date range", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "reports"
+)]
+#[instrument(skip(state))]
+pub async fn get_flow_report(
+    State(state): State<AppState>,
+    Query(query): Query<FlowReportQuery>,
+    timezone: UserTimezone,
+    access: AccountAccess,
+) -> Result<Json<ApiResponse<FlowReportDto>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_flow_report function");
+    let (start_date, end_date) = resolve_range(query.start_date, query.end_date, timezone.today())?;
+
+    let report = build_money_flow_report(&state.db, &access, start_date, end_date)
+        .await
+        .map_err(database_error)?;
+
+    info!(
+        "Computed flow report with {} nodes and {} edges",
+        report.nodes.len(),
+        report.edges.len()
+    );
+    Ok(Json(ApiResponse {
+        data: report,
+        message: "Flow report retrieved successfully".to_string(),
+        success: true,
+    }))
+}
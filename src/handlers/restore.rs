@@ -0,0 +1,780 @@
+use crate::handlers::export::{ExportFormat, ExportManifest};
+use crate::helpers::auth::{ensure_admin, AuthenticatedUser};
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{body::Bytes, extract::State, http::StatusCode, response::Json};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use model::entities::{
+    account, account_allowed_user, account_document, account_note, account_share, account_statistics_exclusion,
+    account_tag, asset, budget, category, exchange_rate, forecast_snapshot, holiday, holiday_calendar,
+    imported_transaction, locked_period, manual_account_state, merchant, one_off_transaction, one_off_transaction_tag,
+    reconciliation_session, recurring_income, recurring_income_tag, recurring_transaction,
+    recurring_transaction_instance, recurring_transaction_tag, scenario, tag, user,
+};
+use rust_decimal::Decimal;
+use sea_orm::sea_query::{Alias, Query, SimpleExpr};
+use sea_orm::{
+    ColumnTrait, ColumnType, ConnectionTrait, DatabaseBackend, DatabaseConnection, DatabaseTransaction, DbErr,
+    EntityTrait, IdenStatic, Iterable, PrimaryKeyToColumn, QuerySelect, Statement, TransactionTrait, Value,
+};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::Read;
+use std::str::FromStr;
+use thiserror::Error;
+use tracing::{debug, error, info, instrument, warn};
+use utoipa::ToSchema;
+
+/// Largest archive accepted by `POST /api/v1/import/full`.
+pub const MAX_ARCHIVE_BYTES: usize = 256 * 1024 * 1024;
+
+/// Largest total size the files of an archive may unpack to.
+pub const MAX_RESTORE_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Rows inserted per statement, well below SQLite's limit on bound parameters.
+const INSERT_BATCH_ROWS: usize = 100;
+
+/// References checked before restoring: table, column and the table whose `id` it points to.
+///
+/// Users are not part of the archive, references to them are checked against the
+/// target database.
+const FOREIGN_KEYS: &[(&str, &str, &str)] = &[
+    ("accounts", "owner_id", "users"),
+    ("categories", "parent_id", "categories"),
+    ("tags", "parent_id", "tags"),
+    ("accounts_tags", "account_id", "accounts"),
+    ("accounts_tags", "tag_id", "tags"),
+    ("account_shares", "account_id", "accounts"),
+    ("account_shares", "user_id", "users"),
+    ("accounts_allowed_users", "account_id", "accounts"),
+    ("accounts_allowed_users", "user_id", "users"),
+    ("account_notes", "account_id", "accounts"),
+    ("account_documents", "account_id", "accounts"),
+    ("budgets", "category_id", "categories"),
+    ("reconciliation_sessions", "account_id", "accounts"),
+    ("manual_account_states", "account_id", "accounts"),
+    ("account_statistics_exclusions", "account_id", "accounts"),
+    ("forecast_snapshots", "account_id", "accounts"),
+    ("one_off_transactions", "target_account_id", "accounts"),
+    ("one_off_transactions", "source_account_id", "accounts"),
+    ("one_off_transactions", "category_id", "categories"),
+    ("one_off_transactions", "scenario_id", "scenarios"),
+    ("one_off_transactions", "reconciliation_session_id", "reconciliation_sessions"),
+    ("one_off_transactions", "parent_transaction_id", "one_off_transactions"),
+    ("one_off_transactions", "receipt_document_id", "account_documents"),
+    ("one_off_transactions_tags", "transaction_id", "one_off_transactions"),
+    ("one_off_transactions_tags", "tag_id", "tags"),
+    ("assets", "account_id", "accounts"),
+    ("assets", "purchase_transaction_id", "one_off_transactions"),
+    ("assets", "disposal_transaction_id", "one_off_transactions"),
+    ("recurring_transactions", "target_account_id", "accounts"),
+    ("recurring_transactions", "source_account_id", "accounts"),
+    ("recurring_transactions", "category_id", "categories"),
+    ("recurring_transactions", "scenario_id", "scenarios"),
+    ("recurring_transactions", "created_by_user_id", "users"),
+    ("recurring_transactions", "reviewed_by_user_id", "users"),
+    ("recurring_transactions_tags", "transaction_id", "recurring_transactions"),
+    ("recurring_transactions_tags", "tag_id", "tags"),
+    ("imported_transactions", "account_id", "accounts"),
+    ("imported_transactions", "category_id", "categories"),
+    ("recurring_transaction_instances", "recurring_transaction_id", "recurring_transactions"),
+    ("recurring_transaction_instances", "reconciled_imported_transaction_id", "imported_transactions"),
+    ("recurring_transaction_instances", "category_id", "categories"),
+    ("recurring_incomes", "target_account_id", "accounts"),
+    ("recurring_incomes", "scenario_id", "scenarios"),
+    ("recurring_incomes_tags", "income_id", "recurring_incomes"),
+    ("recurring_incomes_tags", "tag_id", "tags"),
+];
+
+/// Number of rows restored into one table
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RestoredTable {
+    pub table: String,
+    pub rows: usize,
+}
+
+/// A problem that prevents the archive from being restored
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RestoreConflict {
+    pub table: String,
+    /// Row in the table's file, counted from 1 (empty when the whole table is affected)
+    pub row: Option<usize>,
+    pub column: Option<String>,
+    pub message: String,
+}
+
+impl RestoreConflict {
+    fn table(table: &str, message: String) -> Self {
+        Self {
+            table: table.to_string(),
+            row: None,
+            column: None,
+            message,
+        }
+    }
+
+    fn cell(table: &str, row: usize, column: &str, message: String) -> Self {
+        Self {
+            table: table.to_string(),
+            row: Some(row),
+            column: Some(column.to_string()),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for RestoreConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.table)?;
+        if let Some(row) = self.row {
+            write!(f, " row {}", row)?;
+        }
+        if let Some(column) = &self.column {
+            write!(f, " column {}", column)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Outcome of a restore
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RestoreResponse {
+    /// Restored tables in insertion order (empty when there are conflicts)
+    pub tables: Vec<RestoredTable>,
+    /// Everything that prevented the restore; nothing is written unless this is empty
+    pub conflicts: Vec<RestoreConflict>,
+}
+
+#[derive(Debug, Error)]
+pub enum RestoreError {
+    #[error("Invalid archive: {0}")]
+    InvalidArchive(String),
+
+    #[error("The archive unpacks to more than {} bytes", MAX_RESTORE_BYTES)]
+    TooLarge,
+
+    #[error("The archive can't be restored, {} conflicts found", .0.len())]
+    Conflicts(Vec<RestoreConflict>),
+
+    #[error("Database error: {0}")]
+    Database(#[from] DbErr),
+}
+
+struct ColumnSpec {
+    name: String,
+    column_type: ColumnType,
+    nullable: bool,
+}
+
+/// Layout of a restorable table, taken from its entity.
+struct TableSpec {
+    name: String,
+    columns: Vec<ColumnSpec>,
+    /// Indexes of the primary key columns
+    primary_key: Vec<usize>,
+}
+
+impl TableSpec {
+    fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|column| column.name == name)
+    }
+}
+
+fn table_spec<E: EntityTrait>() -> TableSpec {
+    let columns: Vec<ColumnSpec> = E::Column::iter()
+        .map(|column| {
+            let def = column.def();
+            ColumnSpec {
+                name: column.as_str().to_string(),
+                column_type: def.get_column_type().clone(),
+                nullable: def.is_null(),
+            }
+        })
+        .collect();
+    let primary_key = E::PrimaryKey::iter()
+        .filter_map(|key| {
+            let column = key.into_column();
+            columns.iter().position(|spec| spec.name == column.as_str())
+        })
+        .collect();
+    TableSpec {
+        name: E::default().table_name().to_string(),
+        columns,
+        primary_key,
+    }
+}
+
+/// Tables written by [`crate::handlers::export::export_tables`], parents before children.
+fn restorable_tables() -> Vec<TableSpec> {
+    vec![
+        table_spec::<account::Entity>(),
+        table_spec::<category::Entity>(),
+        table_spec::<tag::Entity>(),
+        table_spec::<account_tag::Entity>(),
+        table_spec::<account_share::Entity>(),
+        table_spec::<account_allowed_user::Entity>(),
+        table_spec::<account_note::Entity>(),
+        table_spec::<account_document::Entity>(),
+        table_spec::<scenario::Entity>(),
+        table_spec::<budget::Entity>(),
+        table_spec::<exchange_rate::Entity>(),
+        table_spec::<locked_period::Entity>(),
+        table_spec::<holiday_calendar::Entity>(),
+        table_spec::<holiday::Entity>(),
+        table_spec::<merchant::Entity>(),
+        table_spec::<reconciliation_session::Entity>(),
+        table_spec::<manual_account_state::Entity>(),
+        table_spec::<account_statistics_exclusion::Entity>(),
+        table_spec::<forecast_snapshot::Entity>(),
+        table_spec::<one_off_transaction::Entity>(),
+        table_spec::<one_off_transaction_tag::Entity>(),
+        table_spec::<asset::Entity>(),
+        table_spec::<recurring_transaction::Entity>(),
+        table_spec::<recurring_transaction_tag::Entity>(),
+        table_spec::<imported_transaction::Entity>(),
+        table_spec::<recurring_transaction_instance::Entity>(),
+        table_spec::<recurring_income::Entity>(),
+        table_spec::<recurring_income_tag::Entity>(),
+    ]
+}
+
+/// Rows of one table read from the archive, with values converted to the column types.
+struct ArchivedTable {
+    spec: TableSpec,
+    /// Row number in the file and the values in column order
+    rows: Vec<(usize, Vec<Value>)>,
+}
+
+/// Reads the manifest and all other files of an export archive.
+///
+/// Unpacking stops with [`RestoreError::TooLarge`] once the files add up to more than
+/// [`MAX_RESTORE_BYTES`], whatever sizes the archive claims.
+fn read_archive(bytes: &[u8]) -> Result<(ExportManifest, HashMap<String, String>), RestoreError> {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes))
+        .map_err(|e| RestoreError::InvalidArchive(format!("not a zip archive: {}", e)))?;
+    let mut files = HashMap::new();
+    let mut remaining = MAX_RESTORE_BYTES;
+    for index in 0..archive.len() {
+        let file = archive
+            .by_index(index)
+            .map_err(|e| RestoreError::InvalidArchive(e.to_string()))?;
+        if file.is_dir() {
+            continue;
+        }
+        let name = file.name().to_string();
+        let mut content = String::new();
+        file.take(remaining + 1)
+            .read_to_string(&mut content)
+            .map_err(|e| RestoreError::InvalidArchive(format!("{} can't be read: {}", name, e)))?;
+        remaining = remaining
+            .checked_sub(content.len() as u64)
+            .ok_or(RestoreError::TooLarge)?;
+        files.insert(name, content);
+    }
+
+    let manifest = files
+        .remove("manifest.json")
+        .ok_or_else(|| RestoreError::InvalidArchive("manifest.json is missing".to_string()))?;
+    let manifest = serde_json::from_str(&manifest)
+        .map_err(|e| RestoreError::InvalidArchive(format!("manifest.json is invalid: {}", e)))?;
+    Ok((manifest, files))
+}
+
+/// Splits CSV text into records, honouring quoted fields with separators, quotes and
+/// line breaks as written by [`crate::handlers::export::csv_field`].
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => quoted = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => quoted = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+fn is_text(column_type: &ColumnType) -> bool {
+    matches!(column_type, ColumnType::String(_) | ColumnType::Text | ColumnType::Char(_))
+}
+
+fn null_value(column_type: &ColumnType) -> Value {
+    match column_type {
+        ColumnType::TinyInteger | ColumnType::SmallInteger | ColumnType::Integer => Value::Int(None),
+        ColumnType::BigInteger => Value::BigInt(None),
+        ColumnType::Float | ColumnType::Double => Value::Double(None),
+        ColumnType::Decimal(_) => Value::Decimal(None),
+        ColumnType::Boolean => Value::Bool(None),
+        ColumnType::Date => Value::ChronoDate(None),
+        ColumnType::DateTime | ColumnType::Timestamp => Value::ChronoDateTime(None),
+        ColumnType::TimestampWithTimeZone => Value::ChronoDateTimeUtc(None),
+        ColumnType::Json | ColumnType::JsonBinary => Value::Json(None),
+        ColumnType::Blob | ColumnType::Binary(_) | ColumnType::VarBinary(_) => Value::Bytes(None),
+        _ => Value::String(None),
+    }
+}
+
+/// Date and time as written by the export: ISO 8601 with `T` or a space, or RFC 3339.
+fn parse_datetime(text: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::from_str(text)
+        .or_else(|_| NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f"))
+        .ok()
+        .or_else(|| DateTime::parse_from_rfc3339(text).ok().map(|timestamp| timestamp.naive_utc()))
+}
+
+/// Converts an archived value to the type of `column`.
+///
+/// CSV files hold every value as text and write nulls as empty fields, so empty CSV
+/// fields are nulls unless the column is a non-nullable text column.
+fn column_value(column: &ColumnSpec, value: &serde_json::Value, from_csv: bool) -> Result<Value, String> {
+    let empty_field = from_csv && value.as_str() == Some("");
+    if value.is_null() || (empty_field && (column.nullable || !is_text(&column.column_type))) {
+        return if column.nullable {
+            Ok(null_value(&column.column_type))
+        } else {
+            Err("must not be empty".to_string())
+        };
+    }
+
+    let text = match value {
+        serde_json::Value::String(text) => text.clone(),
+        other => other.to_string(),
+    };
+    let invalid = |kind: &str| format!("{} is not a valid {}", text, kind);
+    match &column.column_type {
+        ColumnType::TinyInteger | ColumnType::SmallInteger | ColumnType::Integer => {
+            text.parse::<i32>().map(|number| Some(number).into()).map_err(|_| invalid("integer"))
+        }
+        ColumnType::BigInteger => text.parse::<i64>().map(|number| Some(number).into()).map_err(|_| invalid("integer")),
+        ColumnType::Float | ColumnType::Double => {
+            text.parse::<f64>().map(|number| Some(number).into()).map_err(|_| invalid("number"))
+        }
+        ColumnType::Decimal(_) => Decimal::from_str(&text)
+            .or_else(|_| Decimal::from_scientific(&text))
+            .map(|number| Some(number).into())
+            .map_err(|_| invalid("decimal")),
+        ColumnType::Boolean => match text.as_str() {
+            "true" | "1" => Ok(Some(true).into()),
+            "false" | "0" => Ok(Some(false).into()),
+            _ => Err(invalid("boolean")),
+        },
+        ColumnType::Date => NaiveDate::parse_from_str(&text, "%Y-%m-%d")
+            .map(|date| Some(date).into())
+            .map_err(|_| invalid("date")),
+        ColumnType::DateTime | ColumnType::Timestamp => parse_datetime(&text)
+            .map(|timestamp| Some(timestamp).into())
+            .ok_or_else(|| invalid("date and time")),
+        ColumnType::TimestampWithTimeZone => DateTime::parse_from_rfc3339(&text)
+            .map(|timestamp| Some(timestamp.with_timezone(&Utc)).into())
+            .map_err(|_| invalid("timestamp")),
+        ColumnType::Json | ColumnType::JsonBinary => {
+            let json = match value {
+                serde_json::Value::String(text) if from_csv => {
+                    serde_json::from_str(text).unwrap_or_else(|_| value.clone())
+                }
+                _ => value.clone(),
+            };
+            Ok(Some(json).into())
+        }
+        // Exported as an array of byte values
+        ColumnType::Blob | ColumnType::Binary(_) | ColumnType::VarBinary(_) => serde_json::from_str::<Vec<u8>>(&text)
+            .map(|bytes| Some(bytes).into())
+            .map_err(|_| invalid("byte array")),
+        _ => Ok(Some(text).into()),
+    }
+}
+
+/// Reads a table's file, reporting values that don't fit their column.
+fn parse_rows(
+    spec: &TableSpec,
+    content: &str,
+    format: ExportFormat,
+    conflicts: &mut Vec<RestoreConflict>,
+) -> Vec<(usize, Vec<Value>)> {
+    let records: Vec<serde_json::Map<String, serde_json::Value>> = match format {
+        ExportFormat::Json => match serde_json::from_str(content) {
+            Ok(records) => records,
+            Err(e) => {
+                conflicts.push(RestoreConflict::table(&spec.name, format!("file is not a JSON array of rows: {}", e)));
+                return Vec::new();
+            }
+        },
+        ExportFormat::Csv => {
+            let mut records = parse_csv(content).into_iter();
+            let header = records.next().unwrap_or_default();
+            records
+                .map(|record| {
+                    header
+                        .iter()
+                        .cloned()
+                        .zip(record.into_iter().map(serde_json::Value::String))
+                        .collect()
+                })
+                .collect()
+        }
+    };
+
+    let mut rows = Vec::with_capacity(records.len());
+    for (index, record) in records.iter().enumerate() {
+        let mut values = Vec::with_capacity(spec.columns.len());
+        for column in &spec.columns {
+            let value = record.get(&column.name).unwrap_or(&serde_json::Value::Null);
+            match column_value(column, value, format == ExportFormat::Csv) {
+                Ok(value) => values.push(value),
+                Err(message) => conflicts.push(RestoreConflict::cell(&spec.name, index + 1, &column.name, message)),
+            }
+        }
+        if values.len() == spec.columns.len() {
+            rows.push((index + 1, values));
+        }
+    }
+    rows
+}
+
+fn id_of(value: &Value) -> Option<i64> {
+    match value {
+        Value::Int(Some(id)) => Some(i64::from(*id)),
+        Value::BigInt(Some(id)) => Some(*id),
+        _ => None,
+    }
+}
+
+fn check_primary_keys(table: &ArchivedTable, conflicts: &mut Vec<RestoreConflict>) {
+    let mut seen = HashSet::new();
+    for (row, values) in &table.rows {
+        let key: Vec<Option<i64>> = table.spec.primary_key.iter().map(|&index| id_of(&values[index])).collect();
+        if !seen.insert(key.clone()) {
+            let key: Vec<String> = key.iter().map(|id| id.map(|id| id.to_string()).unwrap_or_default()).collect();
+            conflicts.push(RestoreConflict {
+                table: table.spec.name.clone(),
+                row: Some(*row),
+                column: None,
+                message: format!("duplicate primary key {}", key.join(", ")),
+            });
+        }
+    }
+}
+
+/// Reports references to rows that are neither in the archive nor, for users, in the database.
+fn check_references(tables: &[ArchivedTable], user_ids: &HashSet<i64>, conflicts: &mut Vec<RestoreConflict>) {
+    let ids: HashMap<&str, HashSet<i64>> = tables
+        .iter()
+        .filter_map(|table| {
+            let index = table.spec.column_index("id")?;
+            let ids = table.rows.iter().filter_map(|(_, values)| id_of(&values[index])).collect();
+            Some((table.spec.name.as_str(), ids))
+        })
+        .collect();
+
+    for &(table_name, column, referenced) in FOREIGN_KEYS {
+        let Some(table) = tables.iter().find(|table| table.spec.name == table_name) else {
+            continue;
+        };
+        let Some(index) = table.spec.column_index(column) else {
+            continue;
+        };
+        let known = match referenced {
+            "users" => user_ids,
+            _ => match ids.get(referenced) {
+                Some(ids) => ids,
+                None => continue,
+            },
+        };
+        for (row, values) in &table.rows {
+            if let Some(id) = id_of(&values[index]) {
+                if !known.contains(&id) {
+                    conflicts.push(RestoreConflict::cell(
+                        table_name,
+                        *row,
+                        column,
+                        format!("refers to {} {}, which doesn't exist", referenced, id),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Orders rows so that rows referring to another row of their table come after it,
+/// reporting rows whose references form a cycle.
+fn order_parents_first(table: &mut ArchivedTable, parent_column: &str, conflicts: &mut Vec<RestoreConflict>) {
+    let (Some(id_index), Some(parent_index)) = (table.spec.column_index("id"), table.spec.column_index(parent_column))
+    else {
+        return;
+    };
+    let all_ids: HashSet<i64> = table.rows.iter().filter_map(|(_, values)| id_of(&values[id_index])).collect();
+    let mut placed: HashSet<i64> = HashSet::new();
+    let mut pending = std::mem::take(&mut table.rows);
+    while !pending.is_empty() {
+        // Parents missing from the table are reported by the reference check
+        let (ready, waiting): (Vec<_>, Vec<_>) = pending.into_iter().partition(|(_, values)| {
+            id_of(&values[parent_index]).is_none_or(|parent| placed.contains(&parent) || !all_ids.contains(&parent))
+        });
+        if ready.is_empty() {
+            for (row, _) in &waiting {
+                conflicts.push(RestoreConflict::cell(
+                    &table.spec.name,
+                    *row,
+                    parent_column,
+                    "is part of a cycle of parent references".to_string(),
+                ));
+            }
+            table.rows.extend(waiting);
+            return;
+        }
+        placed.extend(ready.iter().filter_map(|(_, values)| id_of(&values[id_index])));
+        table.rows.extend(ready);
+        pending = waiting;
+    }
+}
+
+async fn row_count(db: &DatabaseConnection, table: &str) -> Result<i64, DbErr> {
+    let backend = db.get_database_backend();
+    let row = db
+        .query_one(Statement::from_string(backend, format!("SELECT COUNT(*) AS count FROM {}", table)))
+        .await?;
+    match row {
+        Some(row) => row.try_get("", "count"),
+        None => Ok(0),
+    }
+}
+
+async fn insert_rows(txn: &DatabaseTransaction, table: &ArchivedTable) -> Result<(), DbErr> {
+    let backend = txn.get_database_backend();
+    for batch in table.rows.chunks(INSERT_BATCH_ROWS) {
+        let mut insert = Query::insert();
+        insert
+            .into_table(Alias::new(&table.spec.name))
+            .columns(table.spec.columns.iter().map(|column| Alias::new(&column.name)));
+        for (_, values) in batch {
+            insert
+                .values(values.iter().cloned().map(SimpleExpr::Value))
+                .map_err(|e| DbErr::Custom(e.to_string()))?;
+        }
+        txn.execute(backend.build(&insert)).await?;
+    }
+
+    // Explicit ids don't advance PostgreSQL sequences, new rows would collide with restored ones
+    if backend == DatabaseBackend::Postgres && table.spec.column_index("id").is_some() {
+        txn.execute(Statement::from_string(
+            backend,
+            format!(
+                "SELECT setval(pg_get_serial_sequence('{0}', 'id'), COALESCE((SELECT MAX(id) FROM {0}), 0) + 1, false)",
+                table.spec.name
+            ),
+        ))
+        .await?;
+    }
+    Ok(())
+}
+
+/// Restores an archive written by `GET /api/v1/export/full` into an empty database.
+///
+/// Every file is read and checked before anything is written: the target tables must
+/// be empty, values must fit their columns, primary keys must be unique and every
+/// reference must point to a row in the archive (or an existing user). All rows are
+/// then inserted in one transaction, keeping their ids.
+pub(crate) async fn restore_archive(db: &DatabaseConnection, archive: &[u8]) -> Result<Vec<RestoredTable>, RestoreError> {
+    let (manifest, files) = read_archive(archive)?;
+    let specs = restorable_tables();
+    let mut conflicts = Vec::new();
+
+    for entry in &manifest.tables {
+        if !specs.iter().any(|spec| spec.name == entry.table) {
+            conflicts.push(RestoreConflict::table(&entry.table, "is not a table that can be restored".to_string()));
+        }
+    }
+
+    let mut tables = Vec::with_capacity(specs.len());
+    for spec in specs {
+        let existing = row_count(db, &spec.name).await?;
+        if existing > 0 {
+            conflicts.push(RestoreConflict::table(
+                &spec.name,
+                format!("already contains {} rows, restoring needs an empty database", existing),
+            ));
+        }
+        let rows = match manifest.tables.iter().find(|entry| entry.table == spec.name) {
+            // Archives from older versions lack the tables added since
+            None => Vec::new(),
+            Some(entry) => match files.get(&entry.file) {
+                Some(content) => parse_rows(&spec, content, manifest.format, &mut conflicts),
+                None => {
+                    conflicts.push(RestoreConflict::table(
+                        &spec.name,
+                        format!("{} is missing from the archive", entry.file),
+                    ));
+                    Vec::new()
+                }
+            },
+        };
+        debug!("Read {} rows of {} from the archive", rows.len(), spec.name);
+        tables.push(ArchivedTable { spec, rows });
+    }
+
+    let user_ids: HashSet<i64> = user::Entity::find()
+        .select_only()
+        .column(user::Column::Id)
+        .into_tuple::<i32>()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(i64::from)
+        .collect();
+    for table in &tables {
+        check_primary_keys(table, &mut conflicts);
+    }
+    check_references(&tables, &user_ids, &mut conflicts);
+    for table in tables.iter_mut() {
+        for &(table_name, column, referenced) in FOREIGN_KEYS {
+            if table_name == table.spec.name && referenced == table_name {
+                order_parents_first(table, column, &mut conflicts);
+            }
+        }
+    }
+    if !conflicts.is_empty() {
+        return Err(RestoreError::Conflicts(conflicts));
+    }
+
+    let txn = db.begin().await?;
+    for table in &tables {
+        insert_rows(&txn, table).await?;
+    }
+    txn.commit().await?;
+
+    Ok(tables
+        .iter()
+        .map(|table| RestoredTable {
+            table: table.spec.name.clone(),
+            rows: table.rows.len(),
+        })
+        .collect())
+}
+
+/// Restore all data from a full export archive
+///
+/// Takes the zip archive of `GET /api/v1/export/full`, in either format, as the request
+/// body and restores it into an empty database with all ids kept. Nothing is written
+/// unless the whole archive can be restored: when a target table already has rows, a
+/// value doesn't fit its column, or a row refers to a missing row, every such conflict
+/// is listed in the response instead. Users are not part of the archive, the owners of
+/// restored accounts must already exist.
+///
+/// Only administrators may restore. Requests without a user, which only get through when
+/// authentication isn't required, are allowed.
+#[utoipa::path(
+    post,
+    path = "/api/v1/import/full",
+    tag = "export",
+    request_body(content = Vec<u8>, description = "Zip archive from /api/v1/export/full", content_type = "application/zip"),
+    responses(
+        (status = 201, description = "Archive restored", body = ApiResponse<RestoreResponse>),
+        (status = 400, description = "Not a valid export archive", body = ErrorResponse),
+        (status = 403, description = "The caller is not an administrator", body = ErrorResponse),
+        (status = 409, description = "Conflicts with the database or within the archive, nothing was restored", body = ApiResponse<RestoreResponse>),
+        (status = 413, description = "The archive unpacks to more than MAX_RESTORE_BYTES", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state, body))]
+pub async fn import_full(
+    State(state): State<AppState>,
+    auth: Option<AuthenticatedUser>,
+    body: Bytes,
+) -> Result<(StatusCode, Json<ApiResponse<RestoreResponse>>), (StatusCode, Json<ErrorResponse>)> {
+    debug!("Restoring archive of {} bytes", body.len());
+
+    ensure_admin(&state.db, auth.as_ref(), "restore archives").await?;
+
+    match restore_archive(&state.db, &body).await {
+        Ok(tables) => {
+            info!(
+                "Restored {} rows into {} tables",
+                tables.iter().map(|table| table.rows).sum::<usize>(),
+                tables.len()
+            );
+            Ok((
+                StatusCode::CREATED,
+                Json(ApiResponse {
+                    data: RestoreResponse {
+                        tables,
+                        conflicts: Vec::new(),
+                    },
+                    message: "Archive restored successfully".to_string(),
+                    success: true,
+                }),
+            ))
+        }
+        Err(RestoreError::Conflicts(conflicts)) => {
+            warn!("Archive not restored, {} conflicts", conflicts.len());
+            Ok((
+                StatusCode::CONFLICT,
+                Json(ApiResponse {
+                    data: RestoreResponse {
+                        tables: Vec::new(),
+                        conflicts,
+                    },
+                    message: "Archive conflicts with the database, nothing was restored".to_string(),
+                    success: false,
+                }),
+            ))
+        }
+        Err(RestoreError::InvalidArchive(message)) => {
+            warn!("Rejected invalid archive: {}", message);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: message,
+                    code: "INVALID_ARCHIVE".to_string(),
+                    success: false,
+                }),
+            ))
+        }
+        Err(e @ RestoreError::TooLarge) => {
+            warn!("Rejected archive: {}", e);
+            Err((
+                StatusCode::PAYLOAD_TOO_LARGE,
+                Json(ErrorResponse {
+                    error: e.to_string(),
+                    code: "ARCHIVE_TOO_LARGE".to_string(),
+                    success: false,
+                }),
+            ))
+        }
+        Err(RestoreError::Database(e)) => {
+            error!("Database error while restoring archive: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Failed to restore archive".to_string(),
+                    code: "RESTORE_ERROR".to_string(),
+                    success: false,
+                }),
+            ))
+        }
+    }
+}
@@ -0,0 +1,383 @@
+use crate::handlers::budgets::budget_statuses;
+use crate::helpers::access::AccountAccess;
+use crate::helpers::converters::convert_dataframe_to_timeseries;
+use crate::helpers::timezone::UserTimezone;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use compute::{
+    account::{utils::{generate_occurrences, load_holiday_calendar, recurring_transaction_occurrences}, AccountStateCalculator},
+    comparison::effect_on_accounts,
+    default_compute,
+    recurring_amounts::estimated_amount,
+    safe_to_spend::{budget_reservations, next_income_date, safe_to_spend, Commitment, CommitmentKind},
+};
+use model::entities::{account, one_off_transaction, recurring_income, recurring_transaction, recurring_transaction_instance};
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, Condition, DbErr, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tracing::{debug, error, info, instrument, trace, warn};
+use utoipa::{IntoParams, ToSchema};
+
+/// How many days ahead the next income is looked for
+const INCOME_HORIZON_DAYS: i64 = 366;
+
+/// Query parameters for the safe-to-spend figure
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct SafeToSpendQuery {
+    /// Comma-separated account IDs (default: all liquid accounts counting towards net worth
+    /// and included in statistics)
+    pub account_ids: Option<String>,
+}
+
+/// What a committed outflow comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum CommitmentType {
+    /// An unpaid occurrence of a recurring expense
+    RecurringPayment,
+    /// A one-off transaction dated after today
+    PlannedTransaction,
+    /// What is left of a budget in its current period
+    BudgetRemainder,
+}
+
+impl From<CommitmentKind> for CommitmentType {
+    fn from(kind: CommitmentKind) -> Self {
+        match kind {
+            CommitmentKind::RecurringPayment => CommitmentType::RecurringPayment,
+            CommitmentKind::PlannedTransaction => CommitmentType::PlannedTransaction,
+            CommitmentKind::BudgetRemainder => CommitmentType::BudgetRemainder,
+        }
+    }
+}
+
+/// An outflow subtracted from the balance
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CommittedOutflow {
+    pub kind: CommitmentType,
+    /// Recurring transaction, one-off transaction or budget ID, depending on `kind`
+    pub source_id: i32,
+    pub name: String,
+    /// Due date; the end of the budget period for budget remainders
+    pub date: NaiveDate,
+    pub category_id: Option<i32>,
+    /// Positive amount subtracted from the balance
+    pub amount: Decimal,
+}
+
+impl From<Commitment> for CommittedOutflow {
+    fn from(commitment: Commitment) -> Self {
+        Self {
+            kind: commitment.kind.into(),
+            source_id: commitment.source_id,
+            name: commitment.name,
+            date: commitment.date,
+            category_id: commitment.category_id,
+            amount: commitment.amount,
+        }
+    }
+}
+
+/// How much can be spent today without touching money already committed
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SafeToSpendResponse {
+    /// Balance minus all committed outflows; negative when the commitments exceed the balance
+    pub safe_to_spend: Decimal,
+    /// Combined balance of the accounts at the end of today
+    pub balance: Decimal,
+    /// Sum of the committed outflows
+    pub committed: Decimal,
+    pub today: NaiveDate,
+    /// Next day recurring income arrives on the accounts, if any within a year
+    pub next_income_date: Option<NaiveDate>,
+    /// Last day whose outflows are counted: the day before the next income, or the end
+    /// of the month without one
+    pub until: NaiveDate,
+    pub account_ids: Vec<i32>,
+    /// Committed outflows ordered by date
+    pub commitments: Vec<CommittedOutflow>,
+}
+
+fn safe_to_spend_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// Parses a comma-separated list of IDs, ignoring empty entries.
+fn parse_account_ids(value: &str) -> Option<Vec<i32>> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse().ok())
+        .collect()
+}
+
+/// Get the amount that is safe to spend today
+///
+/// Takes today's balance of the selected accounts and subtracts what is already
+/// committed until the next recurring income arrives: unpaid recurring expenses,
+/// one-off transactions dated after today and the remainders of the current budget
+/// periods. Overdue payments are already part of today's balance. A single figure for
+/// the dashboard header, with the subtracted outflows for a drill-down.
+#[utoipa::path(
+    get,
+    path = "/api/v1/statistics/safe-to-spend",
+    params(SafeToSpendQuery),
+    responses(
+        (status = 200, description = "Safe-to-spend figure and the committed outflows", body = ApiResponse<SafeToSpendResponse>),
+        (status = 400, description = "Invalid account IDs", body = ErrorResponse),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "statistics"
+)]
+#[instrument(skip(state))]
+pub async fn get_safe_to_spend(
+    State(state): State<AppState>,
+    Query(query): Query<SafeToSpendQuery>,
+    access: AccountAccess,
+    timezone: UserTimezone,
+) -> Result<Json<ApiResponse<SafeToSpendResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_safe_to_spend function");
+
+    let requested = match query.account_ids.as_deref().map(parse_account_ids) {
+        Some(Some(ids)) => Some(ids),
+        Some(None) => {
+            warn!("Rejected safe-to-spend account IDs {:?}", query.account_ids);
+            return Err(safe_to_spend_error(
+                StatusCode::BAD_REQUEST,
+                "INVALID_ACCOUNT_IDS",
+                "account_ids must be a comma-separated list of account IDs".to_string(),
+            ));
+        }
+        None => None,
+    };
+
+    let database_error = |e: DbErr| {
+        error!("Database error while computing safe-to-spend: {}", e);
+        safe_to_spend_error(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", e.to_string())
+    };
+    let compute_error = |e: String| {
+        error!("Failed to compute balances for safe-to-spend: {}", e);
+        safe_to_spend_error(StatusCode::INTERNAL_SERVER_ERROR, "COMPUTE_ERROR", e)
+    };
+
+    let accessible = access
+        .filter(account::Entity::find(), [account::Column::Id])
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+    let accounts: Vec<account::Model> = match &requested {
+        Some(ids) => {
+            if let Some(missing) = ids.iter().find(|id| !accessible.iter().any(|account| account.id == **id)) {
+                warn!("Account {} not found for safe-to-spend", missing);
+                return Err(safe_to_spend_error(
+                    StatusCode::NOT_FOUND,
+                    "ACCOUNT_NOT_FOUND",
+                    format!("Account with ID {} not found", missing),
+                ));
+            }
+            accessible.into_iter().filter(|account| ids.contains(&account.id)).collect()
+        }
+        None => accessible
+            .into_iter()
+            .filter(|account| account.is_liquid && account.include_in_statistics)
+            .filter(|account| account.account_kind.counts_towards_net_worth())
+            .collect(),
+    };
+    let account_ids: HashSet<i32> = accounts.iter().map(|account| account.id).collect();
+    let ids: Vec<i32> = accounts.iter().map(|account| account.id).collect();
+
+    let today = timezone.today();
+    debug!("Computing safe-to-spend for accounts {:?} on {}", ids, today);
+
+    let mut balance = Decimal::ZERO;
+    if !accounts.is_empty() {
+        let df = default_compute(Some(today))
+            .compute_account_state(&state.db, &accounts, today, today)
+            .await
+            .map_err(|e| compute_error(e.to_string()))?;
+        balance = convert_dataframe_to_timeseries(df)
+            .map_err(compute_error)?
+            .data_points
+            .into_iter()
+            .filter(|point| point.date == today)
+            .map(|point| point.balance)
+            .sum();
+    }
+
+    let window_start = today.succ_opt().unwrap_or(today);
+    let horizon = today + Duration::days(INCOME_HORIZON_DAYS);
+
+    let incomes = recurring_income::Entity::find()
+        .filter(recurring_income::Column::TargetAccountId.is_in(ids.clone()))
+        .filter(recurring_income::Column::IsSimulated.eq(false))
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+    let recurring = recurring_transaction::Entity::find()
+        .filter(
+            Condition::any()
+                .add(recurring_transaction::Column::TargetAccountId.is_in(ids.clone()))
+                .add(recurring_transaction::Column::SourceAccountId.is_in(ids.clone())),
+        )
+        .filter(recurring_transaction::Column::IsSimulated.eq(false))
+        .filter(recurring_transaction::Column::ApprovalStatus.eq(recurring_transaction::ApprovalStatus::Approved))
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+    let paid = recurring_transaction_instance::Entity::find()
+        .filter(
+            recurring_transaction_instance::Column::RecurringTransactionId
+                .is_in(recurring.iter().map(|rtxn| rtxn.id).collect::<Vec<_>>()),
+        )
+        .filter(recurring_transaction_instance::Column::Status.eq(recurring_transaction_instance::InstanceStatus::Paid))
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+    // Amount upcoming occurrences without an instance are expected to have
+    let estimated: HashMap<i32, Decimal> = recurring
+        .iter()
+        .map(|rtxn| (rtxn.id, estimated_amount(rtxn, &paid)))
+        .collect();
+    let holidays = load_holiday_calendar(&state.db).await.map_err(database_error)?;
+    let effect = |rtxn: &recurring_transaction::Model, amount: Decimal| {
+        effect_on_accounts(rtxn.target_account_id, rtxn.source_account_id, amount, &account_ids)
+    };
+
+    let income_dates = incomes
+        .iter()
+        .flat_map(|income| {
+            generate_occurrences(income.start_date, income.end_date, &income.period, &holidays, window_start, horizon)
+        })
+        .chain(
+            recurring
+                .iter()
+                .filter(|rtxn| effect(rtxn, estimated[&rtxn.id]) > Decimal::ZERO)
+                .flat_map(|rtxn| recurring_transaction_occurrences(rtxn, &holidays, window_start, horizon)),
+        );
+    let next_income = next_income_date(income_dates, today);
+    let until = match next_income {
+        Some(date) => date.pred_opt().unwrap_or(today),
+        None => today
+            .with_day(1)
+            .and_then(|first| first.checked_add_months(Months::new(1)))
+            .and_then(|next_month| next_month.pred_opt())
+            .unwrap_or(today),
+    };
+    debug!("Next income on {:?}, counting outflows until {}", next_income, until);
+
+    let mut commitments = Vec::new();
+    if until > today {
+        let instances: HashMap<(i32, NaiveDate), recurring_transaction_instance::Model> =
+            recurring_transaction_instance::Entity::find()
+                .filter(
+                    recurring_transaction_instance::Column::RecurringTransactionId
+                        .is_in(recurring.iter().map(|rtxn| rtxn.id).collect::<Vec<_>>()),
+                )
+                .filter(recurring_transaction_instance::Column::DueDate.between(window_start, until))
+                .all(&state.db)
+                .await
+                .map_err(database_error)?
+                .into_iter()
+                .map(|instance| ((instance.recurring_transaction_id, instance.due_date), instance))
+                .collect();
+
+        for rtxn in &recurring {
+            for date in recurring_transaction_occurrences(rtxn, &holidays, window_start, until) {
+                let (amount, category_id) = match instances.get(&(rtxn.id, date)) {
+                    Some(instance) if instance.status != recurring_transaction_instance::InstanceStatus::Pending => {
+                        trace!("Occurrence of recurring transaction {} on {} is settled", rtxn.id, date);
+                        continue;
+                    }
+                    Some(instance) => (instance.expected_amount, instance.category_id.or(rtxn.category_id)),
+                    None => (estimated[&rtxn.id], rtxn.category_id),
+                };
+                let amount = effect(rtxn, amount);
+                if amount < Decimal::ZERO {
+                    commitments.push(Commitment {
+                        kind: CommitmentKind::RecurringPayment,
+                        source_id: rtxn.id,
+                        name: rtxn.name.clone(),
+                        date,
+                        category_id,
+                        amount: -amount,
+                    });
+                }
+            }
+        }
+
+        let planned = one_off_transaction::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(one_off_transaction::Column::TargetAccountId.is_in(ids.clone()))
+                    .add(one_off_transaction::Column::SourceAccountId.is_in(ids.clone())),
+            )
+            .filter(one_off_transaction::Column::Date.between(window_start, until))
+            .filter(one_off_transaction::Column::IsSimulated.eq(false))
+            .all(&state.db)
+            .await
+            .map_err(database_error)?;
+        commitments.extend(planned.into_iter().filter_map(|transaction| {
+            let amount = effect_on_accounts(
+                transaction.target_account_id,
+                transaction.source_account_id,
+                transaction.amount,
+                &account_ids,
+            );
+            (amount < Decimal::ZERO).then(|| Commitment {
+                kind: CommitmentKind::PlannedTransaction,
+                source_id: transaction.id,
+                name: transaction.name,
+                date: transaction.date,
+                category_id: transaction.category_id,
+                amount: -amount,
+            })
+        }));
+    }
+
+    if !accounts.is_empty() {
+        let (statuses, categories) = budget_statuses(&state.db, &access, today).await.map_err(database_error)?;
+        let reservations = budget_reservations(&statuses, &categories, &commitments, today);
+        commitments.extend(reservations);
+    }
+    commitments.sort_by_key(|commitment| commitment.date);
+
+    let committed: Decimal = commitments.iter().map(|commitment| commitment.amount).sum();
+    let safe_to_spend = safe_to_spend(balance, &commitments);
+    info!(
+        "Safe to spend on {}: {} (balance {}, {} commitments of {})",
+        today,
+        safe_to_spend,
+        balance,
+        commitments.len(),
+        committed
+    );
+
+    Ok(Json(ApiResponse {
+        data: SafeToSpendResponse {
+            safe_to_spend,
+            balance,
+            committed,
+            today,
+            next_income_date: next_income,
+            until,
+            account_ids: ids,
+            commitments: commitments.into_iter().map(CommittedOutflow::from).collect(),
+        },
+        message: "Safe-to-spend computed successfully".to_string(),
+        success: true,
+    }))
+}
@@ -1,14 +1,20 @@
+use crate::helpers::access::{account_read_only, AccountAccess};
 use crate::schemas::{ApiResponse, AppState, ErrorResponse};
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
     response::Json,
 };
-use chrono::NaiveDateTime;
-use model::entities::{one_off_transaction, recurring_income, recurring_transaction, scenario};
+use chrono::{NaiveDate, NaiveDateTime};
+use model::entities::{
+    one_off_transaction, one_off_transaction_tag, recurring_income, recurring_income_tag, recurring_transaction,
+    recurring_transaction_tag, scenario, user,
+};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Set,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbErr, EntityTrait,
+    IntoActiveModel, NotSet, PaginatorTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
 };
+use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, instrument, trace, warn};
 use utoipa::{IntoParams, ToSchema};
@@ -20,6 +26,8 @@ pub struct CreateScenarioRequest {
     pub name: String,
     /// Optional description
     pub description: Option<String>,
+    /// Date from which the scenario's simulated transactions apply (e.g., a new salary from March)
+    pub activation_date: Option<NaiveDate>,
 }
 
 /// Request body for updating a scenario
@@ -31,6 +39,10 @@ pub struct UpdateScenarioRequest {
     pub description: Option<String>,
     /// Whether this scenario is active
     pub is_active: Option<bool>,
+    /// Date from which the scenario's simulated transactions apply
+    pub activation_date: Option<NaiveDate>,
+    /// Remove the activation date so the scenario applies over the whole timeline
+    pub clear_activation_date: Option<bool>,
 }
 
 /// Scenario response model
@@ -41,6 +53,7 @@ pub struct ScenarioResponse {
     pub description: Option<String>,
     pub created_at: NaiveDateTime,
     pub is_active: bool,
+    pub activation_date: Option<NaiveDate>,
 }
 
 impl From<scenario::Model> for ScenarioResponse {
@@ -51,10 +64,18 @@ impl From<scenario::Model> for ScenarioResponse {
             description: model.description,
             created_at: model.created_at,
             is_active: model.is_active,
+            activation_date: model.activation_date,
         }
     }
 }
 
+/// Request body for cloning a scenario
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CloneScenarioRequest {
+    /// Name of the copy (default: the original name followed by "(copy)")
+    pub name: Option<String>,
+}
+
 /// Query parameters for listing scenarios
 #[derive(Debug, Deserialize, IntoParams, ToSchema)]
 pub struct ListScenariosQuery {
@@ -64,6 +85,74 @@ pub struct ListScenariosQuery {
     pub page_size: Option<u64>,
 }
 
+fn scenario_error(status: StatusCode, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: "SCENARIO_ERROR".to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// Accounts touched by the simulated transactions of a scenario.
+async fn scenario_account_ids<C: ConnectionTrait>(db: &C, scenario_id: i32) -> Result<Vec<i32>, DbErr> {
+    let mut account_ids = Vec::new();
+    for transaction in one_off_transaction::Entity::find()
+        .filter(one_off_transaction::Column::ScenarioId.eq(scenario_id))
+        .filter(one_off_transaction::Column::IsSimulated.eq(true))
+        .all(db)
+        .await?
+    {
+        account_ids.push(transaction.target_account_id);
+        account_ids.extend(transaction.source_account_id);
+    }
+    for transaction in recurring_transaction::Entity::find()
+        .filter(recurring_transaction::Column::ScenarioId.eq(scenario_id))
+        .filter(recurring_transaction::Column::IsSimulated.eq(true))
+        .all(db)
+        .await?
+    {
+        account_ids.push(transaction.target_account_id);
+        account_ids.extend(transaction.source_account_id);
+    }
+    for income in recurring_income::Entity::find()
+        .filter(recurring_income::Column::ScenarioId.eq(scenario_id))
+        .filter(recurring_income::Column::IsSimulated.eq(true))
+        .all(db)
+        .await?
+    {
+        account_ids.push(income.target_account_id);
+    }
+    account_ids.sort_unstable();
+    account_ids.dedup();
+    Ok(account_ids)
+}
+
+/// Rejects changing a scenario unless the caller can write to every account it touches.
+///
+/// A scenario with transactions on accounts the caller can't see is answered as not found.
+async fn ensure_scenario_writable(
+    db: &DatabaseConnection,
+    access: &AccountAccess,
+    scenario_id: i32,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    let account_ids = scenario_account_ids(db, scenario_id).await.map_err(|e| {
+        error!("Failed to fetch scenario accounts: {}", e);
+        scenario_error(StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to fetch scenario: {}", e))
+    })?;
+    if let Some(account_id) = account_ids.iter().find(|account_id| !access.can_access(**account_id)) {
+        warn!("Scenario {} touches account {} hidden from user {:?}", scenario_id, account_id, access.user_id());
+        return Err(scenario_error(StatusCode::NOT_FOUND, format!("Scenario with id {} not found", scenario_id)));
+    }
+    if let Some(account_id) = account_ids.iter().find(|account_id| !access.can_write(**account_id)) {
+        warn!("Scenario {} touches read-only account {}", scenario_id, account_id);
+        return Err(account_read_only(*account_id));
+    }
+    Ok(())
+}
+
 /// Create a new scenario
 ///
 /// Creates a new what-if scenario that can contain simulated transactions.
@@ -95,6 +184,7 @@ pub async fn create_scenario(
         description: Set(request.description),
         created_at: Set(chrono::Local::now().naive_local()),
         is_active: Set(false),
+        activation_date: Set(request.activation_date),
         ..Default::default()
     };
 
@@ -299,6 +389,11 @@ pub async fn update_scenario(
     if let Some(is_active) = request.is_active {
         active_model.is_active = Set(is_active);
     }
+    if let Some(activation_date) = request.activation_date {
+        active_model.activation_date = Set(Some(activation_date));
+    } else if request.clear_activation_date == Some(true) {
+        active_model.activation_date = Set(None);
+    }
 
     let updated = active_model.update(db).await.map_err(|e| {
         error!("Failed to update scenario: {}", e);
@@ -387,6 +482,27 @@ pub async fn delete_scenario(
         )
     })?;
 
+    // Users that had it as their active scenario fall back to the baseline
+    user::Entity::update_many()
+        .col_expr(
+            user::Column::ActiveScenarioId,
+            sea_orm::sea_query::Expr::value(Option::<i32>::None),
+        )
+        .filter(user::Column::ActiveScenarioId.eq(scenario_id))
+        .exec(db)
+        .await
+        .map_err(|e| {
+            error!("Failed to clear active scenario preferences: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("Failed to clear active scenario preferences: {}", e),
+                    code: "SCENARIO_ERROR".to_string(),
+                    success: false,
+                }),
+            )
+        })?;
+
     info!("Scenario deleted successfully: id={}", scenario_id);
     Ok(StatusCode::NO_CONTENT)
 }
@@ -394,7 +510,8 @@ pub async fn delete_scenario(
 /// Apply a scenario
 ///
 /// Applies a scenario by converting all its simulated transactions to real transactions.
-/// This sets `is_simulated = false` for all transactions associated with the scenario.
+/// This sets `is_simulated = false` for all transactions associated with the scenario,
+/// in a single database transaction.
 #[utoipa::path(
     post,
     path = "/api/v1/scenarios/{scenario_id}/apply",
@@ -403,7 +520,8 @@ pub async fn delete_scenario(
     ),
     responses(
         (status = 200, description = "Scenario applied successfully", body = String),
-        (status = 404, description = "Scenario not found", body = ErrorResponse),
+        (status = 403, description = "The scenario touches an account shared read-only", body = ErrorResponse),
+        (status = 404, description = "Scenario not found, or it touches accounts the caller can't see", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     tag = "scenarios"
@@ -411,6 +529,7 @@ pub async fn delete_scenario(
 #[instrument(skip(state))]
 pub async fn apply_scenario(
     State(state): State<AppState>,
+    access: AccountAccess,
     Path(scenario_id): Path<i32>,
 ) -> Result<Json<ApiResponse<String>>, (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering apply_scenario function");
@@ -444,14 +563,20 @@ pub async fn apply_scenario(
                 }),
             )
         })?;
+    ensure_scenario_writable(db, &access, scenario_id).await?;
 
+    // All or nothing, so a failure halfway does not leave the plan partly real
+    let txn = db.begin().await.map_err(|e| {
+        error!("Failed to start transaction: {}", e);
+        scenario_error(StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to apply scenario: {}", e))
+    })?;
     let mut total_applied = 0;
 
     // Apply one-off transactions
     let one_off_txs = one_off_transaction::Entity::find()
         .filter(one_off_transaction::Column::ScenarioId.eq(scenario_id))
         .filter(one_off_transaction::Column::IsSimulated.eq(true))
-        .all(db)
+        .all(&txn)
         .await
         .map_err(|e| {
             error!("Failed to fetch one-off transactions: {}", e);
@@ -468,7 +593,7 @@ pub async fn apply_scenario(
     for tx in one_off_txs {
         let mut active_tx: one_off_transaction::ActiveModel = tx.into();
         active_tx.is_simulated = Set(false);
-        active_tx.update(db).await.map_err(|e| {
+        active_tx.update(&txn).await.map_err(|e| {
             error!("Failed to update one-off transaction: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -486,7 +611,7 @@ pub async fn apply_scenario(
     let recurring_txs = recurring_transaction::Entity::find()
         .filter(recurring_transaction::Column::ScenarioId.eq(scenario_id))
         .filter(recurring_transaction::Column::IsSimulated.eq(true))
-        .all(db)
+        .all(&txn)
         .await
         .map_err(|e| {
             error!("Failed to fetch recurring transactions: {}", e);
@@ -503,7 +628,7 @@ pub async fn apply_scenario(
     for tx in recurring_txs {
         let mut active_tx: recurring_transaction::ActiveModel = tx.into();
         active_tx.is_simulated = Set(false);
-        active_tx.update(db).await.map_err(|e| {
+        active_tx.update(&txn).await.map_err(|e| {
             error!("Failed to update recurring transaction: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -521,7 +646,7 @@ pub async fn apply_scenario(
     let recurring_incomes = recurring_income::Entity::find()
         .filter(recurring_income::Column::ScenarioId.eq(scenario_id))
         .filter(recurring_income::Column::IsSimulated.eq(true))
-        .all(db)
+        .all(&txn)
         .await
         .map_err(|e| {
             error!("Failed to fetch recurring incomes: {}", e);
@@ -538,7 +663,7 @@ pub async fn apply_scenario(
     for income in recurring_incomes {
         let mut active_income: recurring_income::ActiveModel = income.into();
         active_income.is_simulated = Set(false);
-        active_income.update(db).await.map_err(|e| {
+        active_income.update(&txn).await.map_err(|e| {
             error!("Failed to update recurring income: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -552,6 +677,11 @@ pub async fn apply_scenario(
         total_applied += 1;
     }
 
+    txn.commit().await.map_err(|e| {
+        error!("Failed to commit applied scenario: {}", e);
+        scenario_error(StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to apply scenario: {}", e))
+    })?;
+
     info!(
         "Scenario applied successfully: id={}, transactions_applied={}",
         scenario_id, total_applied
@@ -565,3 +695,189 @@ pub async fn apply_scenario(
         success: true,
     }))
 }
+
+/// Copies the simulated one-off transactions, recurring transactions and recurring
+/// incomes of `source_id` into `target_id`, tags included, and returns how many were copied.
+///
+/// Copies are unlocked and not linked to imports or reconciliations. A split part whose
+/// parent is copied as well points at the parent's copy.
+async fn copy_scenario_transactions(
+    txn: &DatabaseTransaction,
+    source_id: i32,
+    target_id: i32,
+) -> Result<usize, DbErr> {
+    let one_offs = one_off_transaction::Entity::find()
+        .filter(one_off_transaction::Column::ScenarioId.eq(source_id))
+        .filter(one_off_transaction::Column::IsSimulated.eq(true))
+        .order_by_asc(one_off_transaction::Column::Id)
+        .all(txn)
+        .await?;
+    let mut one_off_copies: HashMap<i32, one_off_transaction::Model> = HashMap::new();
+    for transaction in &one_offs {
+        let mut copy = transaction.clone().into_active_model().reset_all();
+        copy.id = NotSet;
+        copy.scenario_id = Set(Some(target_id));
+        copy.linked_import_id = Set(None);
+        copy.reconciliation_session_id = Set(None);
+        copy.is_locked = Set(false);
+        one_off_copies.insert(transaction.id, copy.insert(txn).await?);
+    }
+    let one_off_ids: HashMap<i32, i32> = one_off_copies.iter().map(|(id, copy)| (*id, copy.id)).collect();
+    for copy in one_off_copies.into_values() {
+        if let Some(parent_copy_id) = copy.parent_transaction_id.and_then(|parent_id| one_off_ids.get(&parent_id)) {
+            let mut copy: one_off_transaction::ActiveModel = copy.into();
+            copy.parent_transaction_id = Set(Some(*parent_copy_id));
+            copy.update(txn).await?;
+        }
+    }
+    let one_off_tags: Vec<one_off_transaction_tag::ActiveModel> = one_off_transaction_tag::Entity::find()
+        .filter(one_off_transaction_tag::Column::TransactionId.is_in(one_off_ids.keys().copied()))
+        .all(txn)
+        .await?
+        .into_iter()
+        .map(|link| one_off_transaction_tag::ActiveModel {
+            transaction_id: Set(one_off_ids[&link.transaction_id]),
+            tag_id: Set(link.tag_id),
+        })
+        .collect();
+    if !one_off_tags.is_empty() {
+        one_off_transaction_tag::Entity::insert_many(one_off_tags).exec(txn).await?;
+    }
+
+    let recurring = recurring_transaction::Entity::find()
+        .filter(recurring_transaction::Column::ScenarioId.eq(source_id))
+        .filter(recurring_transaction::Column::IsSimulated.eq(true))
+        .all(txn)
+        .await?;
+    let mut recurring_ids: HashMap<i32, i32> = HashMap::new();
+    for transaction in &recurring {
+        let mut copy = transaction.clone().into_active_model().reset_all();
+        copy.id = NotSet;
+        copy.scenario_id = Set(Some(target_id));
+        let inserted = copy.insert(txn).await?;
+        recurring_ids.insert(transaction.id, inserted.id);
+    }
+    let recurring_tags: Vec<recurring_transaction_tag::ActiveModel> = recurring_transaction_tag::Entity::find()
+        .filter(recurring_transaction_tag::Column::TransactionId.is_in(recurring_ids.keys().copied()))
+        .all(txn)
+        .await?
+        .into_iter()
+        .map(|link| recurring_transaction_tag::ActiveModel {
+            transaction_id: Set(recurring_ids[&link.transaction_id]),
+            tag_id: Set(link.tag_id),
+        })
+        .collect();
+    if !recurring_tags.is_empty() {
+        recurring_transaction_tag::Entity::insert_many(recurring_tags).exec(txn).await?;
+    }
+
+    let incomes = recurring_income::Entity::find()
+        .filter(recurring_income::Column::ScenarioId.eq(source_id))
+        .filter(recurring_income::Column::IsSimulated.eq(true))
+        .all(txn)
+        .await?;
+    let mut income_ids: HashMap<i32, i32> = HashMap::new();
+    for income in &incomes {
+        let mut copy = income.clone().into_active_model().reset_all();
+        copy.id = NotSet;
+        copy.scenario_id = Set(Some(target_id));
+        let inserted = copy.insert(txn).await?;
+        income_ids.insert(income.id, inserted.id);
+    }
+    let income_tags: Vec<recurring_income_tag::ActiveModel> = recurring_income_tag::Entity::find()
+        .filter(recurring_income_tag::Column::IncomeId.is_in(income_ids.keys().copied()))
+        .all(txn)
+        .await?
+        .into_iter()
+        .map(|link| recurring_income_tag::ActiveModel {
+            income_id: Set(income_ids[&link.income_id]),
+            tag_id: Set(link.tag_id),
+        })
+        .collect();
+    if !income_tags.is_empty() {
+        recurring_income_tag::Entity::insert_many(income_tags).exec(txn).await?;
+    }
+
+    Ok(one_off_ids.len() + recurring_ids.len() + income_ids.len())
+}
+
+/// Clone a scenario
+///
+/// Creates a new inactive scenario with the same description and activation date and
+/// copies of all simulated transactions of the original, so a variant of the plan can be
+/// tweaked without touching the original.
+#[utoipa::path(
+    post,
+    path = "/api/v1/scenarios/{scenario_id}/clone",
+    params(
+        ("scenario_id" = i32, Path, description = "Scenario ID")
+    ),
+    request_body = CloneScenarioRequest,
+    responses(
+        (status = 201, description = "Scenario cloned successfully", body = ScenarioResponse),
+        (status = 403, description = "The scenario touches an account shared read-only", body = ErrorResponse),
+        (status = 404, description = "Scenario not found, or it touches accounts the caller can't see", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "scenarios"
+)]
+#[instrument(skip(state))]
+pub async fn clone_scenario(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Path(scenario_id): Path<i32>,
+    Json(request): Json<CloneScenarioRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<ScenarioResponse>>), (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering clone_scenario function");
+    debug!("Cloning scenario {}: {:?}", scenario_id, request);
+
+    let db = &state.db;
+
+    let original = scenario::Entity::find_by_id(scenario_id)
+        .one(db)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch scenario: {}", e);
+            scenario_error(StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to fetch scenario: {}", e))
+        })?
+        .ok_or_else(|| {
+            warn!("Scenario not found: id={}", scenario_id);
+            scenario_error(StatusCode::NOT_FOUND, format!("Scenario with id {} not found", scenario_id))
+        })?;
+    ensure_scenario_writable(db, &access, scenario_id).await?;
+
+    let result: Result<_, DbErr> = async {
+        let txn = db.begin().await?;
+        let copy = scenario::ActiveModel {
+            name: Set(request.name.unwrap_or_else(|| format!("{} (copy)", original.name))),
+            description: Set(original.description.clone()),
+            created_at: Set(chrono::Local::now().naive_local()),
+            is_active: Set(false),
+            activation_date: Set(original.activation_date),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+        let copied = copy_scenario_transactions(&txn, scenario_id, copy.id).await?;
+        txn.commit().await?;
+        Ok((copy, copied))
+    }
+    .await;
+    let (copy, copied) = result.map_err(|e| {
+        error!("Failed to clone scenario {}: {}", scenario_id, e);
+        scenario_error(StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to clone scenario: {}", e))
+    })?;
+
+    info!(
+        "Scenario cloned successfully: id={}, clone_id={}, transactions_copied={}",
+        scenario_id, copy.id, copied
+    );
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse {
+            data: copy.into(),
+            message: format!("Scenario cloned successfully. {} transaction(s) copied.", copied),
+            success: true,
+        }),
+    ))
+}
@@ -1,4 +1,7 @@
-use crate::helpers::stats::{compute_account_statistics, determine_time_period};
+use crate::helpers::access::AccountAccess;
+use crate::helpers::scenarios::ScenarioPreferences;
+use crate::helpers::stats::{compute_account_statistics, determine_time_period, load_statistics_exclusions};
+use crate::helpers::timezone::UserTimezone;
 use crate::schemas::{ApiResponse, AppState, CachedData, MonthlyMinBalanceQuery, StatisticsQuery, ErrorResponse};
 use axum::{
     extract::{Path, Query, State},
@@ -8,7 +11,7 @@ use axum::{
 use axum_valid::Valid;
 use chrono::{Datelike, Months};
 use common::{AccountStatisticsCollection, MonthlyMinBalance, MonthlyMinBalanceSeries};
-use compute::{account::AccountStateCalculator, account_stats, default_compute};
+use compute::{account::AccountStateCalculator, account_stats, default_compute_with_scenario};
 use model::entities::account;
 use sea_orm::EntityTrait;
 use tracing::{instrument, error, warn, info, debug, trace};
@@ -32,35 +35,20 @@ pub async fn get_account_statistics(
     Path(account_id): Path<i32>,
     Valid(Query(query)): Valid<Query<StatisticsQuery>>,
     State(state): State<AppState>,
+    access: AccountAccess,
+    timezone: UserTimezone,
 ) -> Result<Json<ApiResponse<AccountStatisticsCollection>>, StatusCode> {
     trace!("Entering get_account_statistics function for account_id: {}", account_id);
     debug!("Fetching statistics for account ID: {} with query: {:?}", account_id, query);
 
-    // Create cache key
-    let cache_key = format!("stats_{}_{:?}", account_id, query);
-    trace!("Generated cache key: {}", cache_key);
-
-    // Check cache first
-    debug!("Checking cache for statistics");
-    if let Some(CachedData::Statistics(stats)) = state.cache.get(&cache_key).await {
-        info!("Statistics for account ID {} retrieved from cache", account_id);
-        let response = ApiResponse {
-            data: stats,
-            message: "Account statistics retrieved from cache".to_string(),
-            success: true,
-        };
-        return Ok(Json(response));
-    }
-    debug!("Cache miss for account statistics, proceeding with database query");
-
     // Get the account from database
     trace!("Looking up account with ID: {}", account_id);
     let account_model = match account::Entity::find_by_id(account_id).one(&state.db).await {
-        Ok(Some(account)) => {
+        Ok(Some(account)) if access.can_access(account.id) => {
             debug!("Found account: {}", account.name);
             account
         }
-        Ok(None) => {
+        Ok(_) => {
             warn!("Account with ID {} not found", account_id);
             return Err(StatusCode::NOT_FOUND);
         }
@@ -76,12 +64,39 @@ pub async fn get_account_statistics(
         return Err(StatusCode::NOT_FOUND);
     }
 
-    let period = determine_time_period(&query);
+    let scenario_id = match ScenarioPreferences::load(&state.db).await {
+        Ok(preferences) => preferences.resolve(query.scenario_id, std::slice::from_ref(&account_model)),
+        Err(db_error) => {
+            error!("Failed to load scenario preferences: {}", db_error);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    // Create cache key; the resolved scenario and today's date are part of it so a changed
+    // preference or a new day isn't served stale data
+    let today = timezone.today();
+    let cache_key = format!("stats_{}_{:?}_scenario_{:?}_{}", account_id, query, scenario_id, today);
+    trace!("Generated cache key: {}", cache_key);
+
+    // Check cache first
+    debug!("Checking cache for statistics");
+    if let Some(CachedData::Statistics(stats)) = state.cache.get(&cache_key).await {
+        info!("Statistics for account ID {} retrieved from cache", account_id);
+        let response = ApiResponse {
+            data: stats,
+            message: "Account statistics retrieved from cache".to_string(),
+            success: true,
+        };
+        return Ok(Json(response));
+    }
+    debug!("Cache miss for account statistics, computing");
+
+    let period = determine_time_period(&query, today);
     debug!("Determined time period: {:?}", period);
 
     // Compute statistics using helper function
-    trace!("Computing statistics for account: {}", account_model.name);
-    let statistics = match compute_account_statistics(&state.db, &account_model, &period).await {
+    trace!("Computing statistics for account: {} (scenario_id={:?})", account_model.name, scenario_id);
+    let statistics = match compute_account_statistics(&state.db, &account_model, &period, scenario_id, today).await {
         Ok(stats) => {
             debug!("Successfully computed statistics for account ID: {}", account_id);
             vec![stats]
@@ -126,6 +141,8 @@ pub async fn get_account_statistics(
 pub async fn get_all_accounts_statistics(
     Valid(Query(query)): Valid<Query<StatisticsQuery>>,
     State(state): State<AppState>,
+    access: AccountAccess,
+    timezone: UserTimezone,
 ) -> Result<Json<ApiResponse<Vec<AccountStatisticsCollection>>>, StatusCode> {
     trace!("Entering get_all_accounts_statistics function");
     debug!("Fetching statistics for all accounts with query: {:?}", query);
@@ -137,6 +154,7 @@ pub async fn get_all_accounts_statistics(
             let all_count = accounts.len();
             let filtered_accounts: Vec<_> = accounts
                 .into_iter()
+                .filter(|a| access.can_access(a.id))
                 .filter(|a| query.include_ignored || a.include_in_statistics)
                 .collect();
             let filtered_count = filtered_accounts.len();
@@ -159,14 +177,25 @@ pub async fn get_all_accounts_statistics(
         return Ok(Json(response));
     }
 
-    let period = determine_time_period(&query);
+    let preferences = match ScenarioPreferences::load(&state.db).await {
+        Ok(preferences) => preferences,
+        Err(db_error) => {
+            error!("Failed to load scenario preferences: {}", db_error);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    let today = timezone.today();
+    let period = determine_time_period(&query, today);
     debug!("Determined time period: {:?}", period);
     let mut all_statistics = Vec::new();
 
     for account in accounts {
-        trace!("Computing statistics for account: {} (ID: {})", account.name, account.id);
+        // Each account follows its owner's active scenario unless the request names one
+        let scenario_id = preferences.resolve(query.scenario_id, std::slice::from_ref(&account));
+        trace!("Computing statistics for account: {} (ID: {}, scenario_id={:?})", account.name, account.id, scenario_id);
         // Compute statistics for this account using helper function
-        let statistics = match compute_account_statistics(&state.db, &account, &period).await {
+        let statistics = match compute_account_statistics(&state.db, &account, &period, scenario_id, today).await {
             Ok(stats) => {
                 debug!("Successfully computed statistics for account: {}", account.name);
                 vec![stats]
@@ -204,6 +233,7 @@ pub async fn get_all_accounts_statistics(
     params(
         ("account_id" = i32, Path, description = "Account ID"),
         ("months" = Option<u32>, Query, description = "Number of past months to include (default 12)"),
+        ("scenario_id" = Option<i32>, Query, description = "Scenario to include (default: the owner's active scenario)"),
     ),
     responses(
         (status = 200, description = "Monthly minimum balance series", body = ApiResponse<MonthlyMinBalanceSeries>),
@@ -216,6 +246,8 @@ pub async fn get_monthly_min_balance(
     Path(account_id): Path<i32>,
     Valid(Query(query)): Valid<Query<MonthlyMinBalanceQuery>>,
     State(state): State<AppState>,
+    access: AccountAccess,
+    timezone: UserTimezone,
 ) -> Result<Json<ApiResponse<MonthlyMinBalanceSeries>>, StatusCode> {
     trace!("Entering get_monthly_min_balance for account_id: {}", account_id);
 
@@ -223,11 +255,11 @@ pub async fn get_monthly_min_balance(
         .one(&state.db)
         .await
     {
-        Ok(Some(account)) => {
+        Ok(Some(account)) if access.can_access(account.id) => {
             debug!("Found account: {}", account.name);
             account
         }
-        Ok(None) => {
+        Ok(_) => {
             warn!("Account with ID {} not found", account_id);
             return Err(StatusCode::NOT_FOUND);
         }
@@ -241,15 +273,29 @@ pub async fn get_monthly_min_balance(
     };
 
     let months = query.months.unwrap_or(12);
-    let today = chrono::Utc::now().date_naive();
+    let today = timezone.today();
     let end_date = today;
     let start_date = today
         .checked_sub_months(Months::new(months))
         .unwrap_or_else(|| chrono::NaiveDate::from_ymd_opt(today.year() - 1, today.month(), 1).unwrap());
 
-    let compute = default_compute(None);
+    let scenario_id = match ScenarioPreferences::load(&state.db).await {
+        Ok(preferences) => preferences.resolve(query.scenario_id, std::slice::from_ref(&account_model)),
+        Err(db_error) => {
+            error!("Failed to load scenario preferences: {}", db_error);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    let exclusions = match load_statistics_exclusions(&state.db, &[account_id]).await {
+        Ok(exclusions) => exclusions,
+        Err(db_error) => {
+            error!("Failed to load statistics exclusions for account {}: {}", account_id, db_error);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    let compute = default_compute_with_scenario(Some(today), scenario_id);
 
-    debug!("Computing monthly min balance from {} to {} ({} months)", start_date, end_date, months);
+    debug!("Computing monthly min balance from {} to {} ({} months, scenario_id={:?})", start_date, end_date, months, scenario_id);
 
     match account_stats::min_balance_per_month(
         &compute as &dyn AccountStateCalculator,
@@ -257,6 +303,7 @@ pub async fn get_monthly_min_balance(
         &account_model,
         start_date,
         end_date,
+        &exclusions,
     )
     .await
     {
@@ -1,11 +1,18 @@
+use crate::helpers::access::AccountAccess;
+use crate::helpers::timezone::UserTimezone;
 use crate::schemas::{ApiResponse, AppState, ErrorResponse};
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
     response::Json,
 };
-use model::entities::tag;
-use sea_orm::{ActiveModelTrait, EntityTrait, Set, DbErr, ColumnTrait, QueryFilter, RelationTrait, JoinType, PaginatorTrait};
+use chrono::{Datelike, NaiveDate};
+use compute::account_stats::get_last_day_of_month;
+use compute::tags::{evaluate_tag_limits, TagLimitStatus, TaggedExpense};
+use model::entities::{one_off_transaction, one_off_transaction_tag, tag};
+use rust_decimal::Decimal;
+use sea_orm::{ActiveModelTrait, EntityTrait, Set, DbErr, ColumnTrait, Condition, QueryFilter, QuerySelect, RelationTrait, JoinType, PaginatorTrait, TransactionTrait};
+use std::collections::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 use tracing::{instrument, error, warn, info, debug, trace};
 use utoipa::ToSchema;
@@ -21,6 +28,8 @@ pub struct CreateTagRequest {
     pub parent_id: Option<i32>,
     /// Optional Ledger CLI export name template
     pub ledger_name: Option<String>,
+    /// Optional maximum spending per month, regardless of category
+    pub monthly_limit: Option<Decimal>,
 }
 
 /// Request structure for updating an existing tag
@@ -34,6 +43,8 @@ pub struct UpdateTagRequest {
     pub parent_id: Option<i32>,
     /// Optional Ledger CLI export name template
     pub ledger_name: Option<String>,
+    /// Maximum spending per month; a limit of zero removes it
+    pub monthly_limit: Option<Decimal>,
 }
 
 /// Response structure for tag operations
@@ -44,6 +55,7 @@ pub struct TagResponse {
     pub description: Option<String>,
     pub parent_id: Option<i32>,
     pub ledger_name: Option<String>,
+    pub monthly_limit: Option<Decimal>,
 }
 
 impl From<tag::Model> for TagResponse {
@@ -54,10 +66,145 @@ impl From<tag::Model> for TagResponse {
             description: model.description,
             parent_id: model.parent_id,
             ledger_name: model.ledger_name,
+            monthly_limit: model.monthly_limit,
         }
     }
 }
 
+/// Transaction filter used to select one-off transactions for bulk tagging and bulk changes.
+/// All provided criteria must match.
+#[derive(Debug, Default, Deserialize, Serialize, ToSchema)]
+pub struct TransactionTagFilter {
+    /// Transactions where this account is the target or the source
+    pub account_id: Option<i32>,
+    /// Transactions in this category
+    pub category_id: Option<i32>,
+    /// Transactions on or after this date
+    pub start_date: Option<NaiveDate>,
+    /// Transactions on or before this date
+    pub end_date: Option<NaiveDate>,
+    /// Case-sensitive substring of the transaction name
+    pub name_contains: Option<String>,
+    /// Minimum amount (inclusive)
+    pub min_amount: Option<Decimal>,
+    /// Maximum amount (inclusive)
+    pub max_amount: Option<Decimal>,
+}
+
+impl TransactionTagFilter {
+    /// Returns true if no criterion is set.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.account_id.is_none()
+            && self.category_id.is_none()
+            && self.start_date.is_none()
+            && self.end_date.is_none()
+            && self.name_contains.is_none()
+            && self.min_amount.is_none()
+            && self.max_amount.is_none()
+    }
+
+    pub(crate) fn condition(&self) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(account_id) = self.account_id {
+            condition = condition.add(
+                Condition::any()
+                    .add(one_off_transaction::Column::TargetAccountId.eq(account_id))
+                    .add(one_off_transaction::Column::SourceAccountId.eq(account_id)),
+            );
+        }
+        if let Some(category_id) = self.category_id {
+            condition = condition.add(one_off_transaction::Column::CategoryId.eq(category_id));
+        }
+        if let Some(start_date) = self.start_date {
+            condition = condition.add(one_off_transaction::Column::Date.gte(start_date));
+        }
+        if let Some(end_date) = self.end_date {
+            condition = condition.add(one_off_transaction::Column::Date.lte(end_date));
+        }
+        if let Some(name_contains) = &self.name_contains {
+            condition = condition.add(one_off_transaction::Column::Name.contains(name_contains));
+        }
+        if let Some(min_amount) = self.min_amount {
+            condition = condition.add(one_off_transaction::Column::Amount.gte(min_amount));
+        }
+        if let Some(max_amount) = self.max_amount {
+            condition = condition.add(one_off_transaction::Column::Amount.lte(max_amount));
+        }
+        condition
+    }
+}
+
+/// Request structure for assigning a tag to many transactions at once.
+/// Either `transaction_ids` or `filter` must be provided; when both are given
+/// only transactions matching both are tagged.
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct AssignTagRequest {
+    /// Explicit list of one-off transaction IDs
+    pub transaction_ids: Option<Vec<i32>>,
+    /// Filter selecting one-off transactions
+    pub filter: Option<TransactionTagFilter>,
+    /// When true, nothing is written and only the counts are returned
+    pub dry_run: Option<bool>,
+}
+
+/// Result of a bulk tag assignment
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AssignTagResponse {
+    pub tag_id: i32,
+    /// Number of transactions selected by the request
+    pub matched: usize,
+    /// Selected transactions that already carried the tag
+    pub already_tagged: usize,
+    /// Transactions newly tagged (or that would be, on a dry run)
+    pub affected: usize,
+    pub dry_run: bool,
+}
+
+/// Query parameters for the tag spending limits report
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TagLimitsQuery {
+    /// Year to evaluate (default: current year)
+    pub year: Option<i32>,
+    /// Month to evaluate, 1-12 (default: current month)
+    pub month: Option<u32>,
+}
+
+/// Spending of a tag against its monthly limit
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TagLimitResponse {
+    pub tag_id: i32,
+    pub tag_name: String,
+    pub limit: Decimal,
+    /// Expenses tagged with the tag or one of its child tags
+    pub spent: Decimal,
+    /// Negative once the limit is exceeded
+    pub remaining: Decimal,
+    /// "WithinLimit", "Warning" (80% or more spent) or "Exceeded"
+    pub level: String,
+}
+
+impl From<TagLimitStatus> for TagLimitResponse {
+    fn from(status: TagLimitStatus) -> Self {
+        Self {
+            tag_id: status.tag_id,
+            remaining: status.remaining(),
+            tag_name: status.tag_name,
+            limit: status.limit,
+            spent: status.spent,
+            level: format!("{:?}", status.level),
+        }
+    }
+}
+
+/// Tag spending limits for one month
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TagLimitsResponse {
+    pub year: i32,
+    pub month: u32,
+    /// Most used limits first
+    pub limits: Vec<TagLimitResponse>,
+}
+
 /// Query parameters for getting child tags
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct TagChildrenQuery {
@@ -65,6 +212,18 @@ pub struct TagChildrenQuery {
     pub recursive: Option<bool>,
 }
 
+fn negative_limit_error() -> (StatusCode, Json<ErrorResponse>) {
+    warn!("Rejected negative tag monthly limit");
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse {
+            error: "Monthly limit must not be negative".to_string(),
+            code: "INVALID_LIMIT".to_string(),
+            success: false,
+        }),
+    )
+}
+
 /// Create a new tag
 #[utoipa::path(
     post,
@@ -85,6 +244,10 @@ pub async fn create_tag(
 ) -> Result<(StatusCode, Json<ApiResponse<TagResponse>>), (StatusCode, Json<ErrorResponse>)> {
     debug!("Creating tag with name: {}", request.name);
 
+    if request.monthly_limit.is_some_and(|limit| limit.is_sign_negative()) {
+        return Err(negative_limit_error());
+    }
+
     // Validate parent_id exists if provided
     if let Some(parent_id) = request.parent_id {
         match tag::Entity::find_by_id(parent_id).one(&state.db).await {
@@ -121,6 +284,7 @@ pub async fn create_tag(
         description: Set(request.description),
         parent_id: Set(request.parent_id),
         ledger_name: Set(request.ledger_name),
+        monthly_limit: Set(request.monthly_limit),
         ..Default::default()
     };
 
@@ -260,6 +424,10 @@ pub async fn update_tag(
 ) -> Result<Json<ApiResponse<TagResponse>>, (StatusCode, Json<ErrorResponse>)> {
     debug!("Updating tag with ID: {}", tag_id);
 
+    if request.monthly_limit.is_some_and(|limit| limit.is_sign_negative()) {
+        return Err(negative_limit_error());
+    }
+
     // First, find the existing tag
     let existing_tag = match tag::Entity::find_by_id(tag_id).one(&state.db).await {
         Ok(Some(tag)) => tag,
@@ -345,6 +513,9 @@ pub async fn update_tag(
     if let Some(ledger_name) = request.ledger_name {
         active_tag.ledger_name = Set(Some(ledger_name));
     }
+    if let Some(monthly_limit) = request.monthly_limit {
+        active_tag.monthly_limit = Set(Some(monthly_limit).filter(|limit| !limit.is_zero()));
+    }
 
     match active_tag.update(&state.db).await {
         Ok(updated_tag) => {
@@ -575,6 +746,114 @@ pub async fn get_tag_children(
     }
 }
 
+/// Get tag spending limits
+///
+/// Evaluates every tag with a monthly limit against the month's expenses: confirmed,
+/// non-simulated one-off transactions included in statistics, excluding transfers, on
+/// the accounts visible to the caller. Expenses count towards their tag and all its
+/// parent tags.
+#[utoipa::path(
+    get,
+    path = "/api/v1/tags/limits",
+    params(
+        ("year" = Option<i32>, Query, description = "Year to evaluate (default: current year)"),
+        ("month" = Option<u32>, Query, description = "Month to evaluate, 1-12 (default: current month)")
+    ),
+    responses(
+        (status = 200, description = "Spending per limited tag", body = ApiResponse<TagLimitsResponse>),
+        (status = 400, description = "Invalid month", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "tags"
+)]
+#[instrument(skip(state))]
+pub async fn get_tag_limits(
+    Query(query): Query<TagLimitsQuery>,
+    State(state): State<AppState>,
+    access: AccountAccess,
+    timezone: UserTimezone,
+) -> Result<Json<ApiResponse<TagLimitsResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    let today = timezone.today();
+    let year = query.year.unwrap_or(today.year());
+    let month = query.month.unwrap_or(today.month());
+    debug!("Evaluating tag limits for {}-{:02}", year, month);
+
+    let Some(start_date) = NaiveDate::from_ymd_opt(year, month, 1) else {
+        warn!("Invalid month {}-{} for tag limits", year, month);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: format!("Invalid month {}-{}", year, month),
+                code: "INVALID_MONTH".to_string(),
+                success: false,
+            }),
+        ));
+    };
+    let end_date = get_last_day_of_month(year, month);
+
+    let database_error = |e: DbErr| {
+        error!("Database error while evaluating tag limits: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to evaluate tag limits".to_string(),
+                code: "ERROR".to_string(),
+                success: false,
+            }),
+        )
+    };
+
+    let tags = tag::Entity::find().all(&state.db).await.map_err(database_error)?;
+    if tags.iter().all(|tag| tag.monthly_limit.is_none()) {
+        return Ok(Json(ApiResponse {
+            data: TagLimitsResponse { year, month, limits: Vec::new() },
+            message: "Success".to_string(),
+            success: true,
+        }));
+    }
+
+    let expenses: HashMap<i32, Decimal> = access
+        .filter(one_off_transaction::Entity::find(), [one_off_transaction::Column::TargetAccountId])
+        .filter(one_off_transaction::Column::Date.between(start_date, end_date))
+        .filter(one_off_transaction::Column::Amount.lt(Decimal::ZERO))
+        .filter(one_off_transaction::Column::SourceAccountId.is_null())
+        .filter(one_off_transaction::Column::IsSimulated.eq(false))
+        .filter(one_off_transaction::Column::Status.eq(one_off_transaction::OneOffStatus::Confirmed))
+        .filter(one_off_transaction::Column::IncludeInStatistics.eq(true))
+        .all(&state.db)
+        .await
+        .map_err(database_error)?
+        .into_iter()
+        .map(|transaction| (transaction.id, transaction.amount.abs()))
+        .collect();
+
+    let links = one_off_transaction_tag::Entity::find()
+        .filter(one_off_transaction_tag::Column::TransactionId.is_in(expenses.keys().copied()))
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+    let tagged: Vec<TaggedExpense> = links
+        .into_iter()
+        .map(|link| TaggedExpense {
+            transaction_id: link.transaction_id,
+            tag_id: link.tag_id,
+            amount: expenses[&link.transaction_id],
+        })
+        .collect();
+
+    let limits: Vec<TagLimitResponse> = evaluate_tag_limits(&tags, &tagged)
+        .into_iter()
+        .map(TagLimitResponse::from)
+        .collect();
+    info!("Evaluated {} tag limits for {}-{:02}", limits.len(), year, month);
+
+    Ok(Json(ApiResponse {
+        data: TagLimitsResponse { year, month, limits },
+        message: "Success".to_string(),
+        success: true,
+    }))
+}
+
 /// Link a tag to a parent (move tag under another tag)
 #[utoipa::path(
     put,
@@ -789,6 +1068,173 @@ pub async fn unlink_tag_from_parent(
     }
 }
 
+/// Assign a tag to many one-off transactions at once
+///
+/// Only transactions on accounts the caller may change are matched.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tags/{tag_id}/assign",
+    params(
+        ("tag_id" = i32, Path, description = "Tag ID to assign")
+    ),
+    request_body = AssignTagRequest,
+    responses(
+        (status = 200, description = "Tag assigned (or counted on dry run)", body = ApiResponse<AssignTagResponse>),
+        (status = 400, description = "Neither transaction IDs nor a filter were provided", body = ErrorResponse),
+        (status = 404, description = "Tag not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "tags"
+)]
+#[instrument(skip(state))]
+pub async fn assign_tag_to_transactions(
+    Path(tag_id): Path<i32>,
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Json(request): Json<AssignTagRequest>,
+) -> Result<Json<ApiResponse<AssignTagResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("Bulk assigning tag {}: {:?}", tag_id, request);
+
+    let internal_error = |message: &str| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: message.to_string(),
+                code: "ERROR".to_string(),
+                success: false,
+            }),
+        )
+    };
+
+    let ids_given = request.transaction_ids.as_ref().is_some_and(|ids| !ids.is_empty());
+    let filter_given = request.filter.as_ref().is_some_and(|f| !f.is_empty());
+    if !ids_given && !filter_given {
+        warn!("Bulk tag assignment without transaction IDs or filter");
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Provide transaction_ids or a non-empty filter".to_string(),
+                code: "ERROR".to_string(),
+                success: false,
+            }),
+        ));
+    }
+
+    match tag::Entity::find_by_id(tag_id).one(&state.db).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            warn!("Tag with ID {} not found", tag_id);
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: "Tag not found".to_string(),
+                    code: "ERROR".to_string(),
+                    success: false,
+                }),
+            ));
+        }
+        Err(e) => {
+            error!("Failed to fetch tag: {}", e);
+            return Err(internal_error("Failed to fetch tag"));
+        }
+    }
+
+    let mut condition = Condition::all();
+    if let Some(ids) = request.transaction_ids.as_ref().filter(|ids| !ids.is_empty()) {
+        condition = condition.add(one_off_transaction::Column::Id.is_in(ids.clone()));
+    }
+    if let Some(filter) = &request.filter {
+        condition = condition.add(filter.condition());
+    }
+
+    let matched_ids: Vec<i32> = one_off_transaction::Entity::find()
+        .select_only()
+        .column(one_off_transaction::Column::Id)
+        .column(one_off_transaction::Column::TargetAccountId)
+        .column(one_off_transaction::Column::SourceAccountId)
+        .filter(condition)
+        .into_tuple::<(i32, i32, Option<i32>)>()
+        .all(&state.db)
+        .await
+        .map_err(|e| {
+            error!("Failed to select transactions for tagging: {}", e);
+            internal_error("Failed to select transactions")
+        })?
+        .into_iter()
+        .filter(|(_, target_account_id, source_account_id)| access.can_write_any(*target_account_id, *source_account_id))
+        .map(|(id, _, _)| id)
+        .collect();
+
+    let already_tagged: HashSet<i32> = if matched_ids.is_empty() {
+        HashSet::new()
+    } else {
+        one_off_transaction_tag::Entity::find()
+            .filter(one_off_transaction_tag::Column::TagId.eq(tag_id))
+            .filter(one_off_transaction_tag::Column::TransactionId.is_in(matched_ids.clone()))
+            .all(&state.db)
+            .await
+            .map_err(|e| {
+                error!("Failed to fetch existing tag links: {}", e);
+                internal_error("Failed to fetch existing tag links")
+            })?
+            .into_iter()
+            .map(|link| link.transaction_id)
+            .collect()
+    };
+
+    let to_tag: Vec<i32> = matched_ids
+        .iter()
+        .copied()
+        .filter(|id| !already_tagged.contains(id))
+        .collect();
+    let dry_run = request.dry_run.unwrap_or(false);
+
+    if !dry_run && !to_tag.is_empty() {
+        let txn = state.db.begin().await.map_err(|e| {
+            error!("Failed to start database transaction: {}", e);
+            internal_error("Failed to assign tag")
+        })?;
+
+        one_off_transaction_tag::Entity::insert_many(to_tag.iter().map(|transaction_id| {
+            one_off_transaction_tag::ActiveModel {
+                transaction_id: Set(*transaction_id),
+                tag_id: Set(tag_id),
+            }
+        }))
+        .exec(&txn)
+        .await
+        .map_err(|e| {
+            error!("Failed to insert tag links: {}", e);
+            internal_error("Failed to assign tag")
+        })?;
+
+        txn.commit().await.map_err(|e| {
+            error!("Failed to commit tag assignment: {}", e);
+            internal_error("Failed to assign tag")
+        })?;
+    }
+
+    info!(
+        "Tag {} bulk assignment: matched={}, already_tagged={}, affected={}, dry_run={}",
+        tag_id,
+        matched_ids.len(),
+        already_tagged.len(),
+        to_tag.len(),
+        dry_run
+    );
+    Ok(Json(ApiResponse {
+        data: AssignTagResponse {
+            tag_id,
+            matched: matched_ids.len(),
+            already_tagged: already_tagged.len(),
+            affected: to_tag.len(),
+            dry_run,
+        },
+        message: if dry_run { "Dry run".to_string() } else { "Success".to_string() },
+        success: true,
+    }))
+}
+
 // Helper functions
 
 /// Recursively get all nested children of a tag
@@ -1,4 +1,7 @@
+use crate::helpers::access::AccountAccess;
 use crate::helpers::converters::convert_dataframe_to_timeseries;
+use crate::helpers::scenarios::ScenarioPreferences;
+use crate::helpers::timezone::UserTimezone;
 use crate::schemas::{ApiResponse, AppState, CachedData, TimeseriesQuery, ErrorResponse};
 use axum::{
     extract::{Path, Query, State},
@@ -6,11 +9,16 @@ use axum::{
     response::Json,
 };
 use axum_valid::Valid;
-use common::AccountStateTimeseries;
-use compute::{account::AccountStateCalculator, default_compute, default_compute_with_scenario};
+use chrono::NaiveDate;
+use common::{AccountStatePoint, AccountStateTimeseries, DateRange};
+use compute::{account::AccountStateCalculator, default_compute_with_scenario};
 use model::entities::account;
+use rust_decimal::Decimal;
 use sea_orm::EntityTrait;
+use serde::{Deserialize, Serialize};
 use tracing::{instrument, error, warn, info, debug, trace};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
 
 /// Get timeseries data for a specific account
 #[utoipa::path(
@@ -31,35 +39,20 @@ pub async fn get_account_timeseries(
     Path(account_id): Path<i32>,
     Valid(Query(query)): Valid<Query<TimeseriesQuery>>,
     State(state): State<AppState>,
+    access: AccountAccess,
+    timezone: UserTimezone,
 ) -> Result<Json<ApiResponse<AccountStateTimeseries>>, StatusCode> {
     trace!("Entering get_account_timeseries function for account_id: {}", account_id);
     debug!("Fetching timeseries for account ID: {} with query: {:?}", account_id, query);
 
-    // Create cache key
-    let cache_key = format!("timeseries_{}_{:?}", account_id, query);
-    trace!("Generated cache key: {}", cache_key);
-
-    // Check cache first
-    debug!("Checking cache for timeseries");
-    if let Some(CachedData::Timeseries(timeseries)) = state.cache.get(&cache_key).await {
-        info!("Timeseries for account ID {} retrieved from cache", account_id);
-        let response = ApiResponse {
-            data: timeseries,
-            message: "Account timeseries retrieved from cache".to_string(),
-            success: true,
-        };
-        return Ok(Json(response));
-    }
-    debug!("Cache miss for account timeseries, proceeding with database query");
-
     // Get the account from database
     trace!("Looking up account with ID: {}", account_id);
     let account_model = match account::Entity::find_by_id(account_id).one(&state.db).await {
-        Ok(Some(account)) => {
+        Ok(Some(account)) if access.can_access(account.id) => {
             debug!("Found account: {}", account.name);
             account
         }
-        Ok(None) => {
+        Ok(_) => {
             warn!("Account with ID {} not found", account_id);
             return Err(StatusCode::NOT_FOUND);
         }
@@ -75,11 +68,38 @@ pub async fn get_account_timeseries(
         return Err(StatusCode::NOT_FOUND);
     }
 
+    let scenario_id = match ScenarioPreferences::load(&state.db).await {
+        Ok(preferences) => preferences.resolve(query.scenario_id, std::slice::from_ref(&account_model)),
+        Err(db_error) => {
+            error!("Failed to load scenario preferences: {}", db_error);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    // Create cache key; the resolved scenario and today's date are part of it so a changed
+    // preference or a new day isn't served stale data
+    let today = timezone.today();
+    let cache_key = format!("timeseries_{}_{:?}_scenario_{:?}_{}", account_id, query, scenario_id, today);
+    trace!("Generated cache key: {}", cache_key);
+
+    // Check cache first
+    debug!("Checking cache for timeseries");
+    if let Some(CachedData::Timeseries(timeseries)) = state.cache.get(&cache_key).await {
+        info!("Timeseries for account ID {} retrieved from cache", account_id);
+        let response = ApiResponse {
+            data: timeseries,
+            message: "Account timeseries retrieved from cache".to_string(),
+            success: true,
+        };
+        return Ok(Json(response));
+    }
+    debug!("Cache miss for account timeseries, computing");
+
     // Compute timeseries using the compute module
-    debug!("Computing timeseries for account: {} from {} to {}", 
-           account_model.name, query.start_date, query.end_date);
+    debug!("Computing timeseries for account: {} from {} to {} (scenario_id={:?})",
+           account_model.name, query.start_date, query.end_date, scenario_id);
     let accounts = vec![account_model];
-    let compute = default_compute(None);
+    let compute = default_compute_with_scenario(Some(today), scenario_id);
 
     trace!("Executing timeseries computation");
     let timeseries_result = compute
@@ -140,6 +160,8 @@ pub async fn get_account_timeseries(
 pub async fn get_all_accounts_timeseries(
     Valid(Query(query)): Valid<Query<TimeseriesQuery>>,
     State(state): State<AppState>,
+    access: AccountAccess,
+    timezone: UserTimezone,
 ) -> Result<Json<ApiResponse<AccountStateTimeseries>>, StatusCode> {
     trace!("Entering get_all_accounts_timeseries function");
     debug!("Fetching timeseries for all accounts with query: {:?}", query);
@@ -151,6 +173,7 @@ pub async fn get_all_accounts_timeseries(
             let all_count = accounts.len();
             let filtered_accounts: Vec<_> = accounts
                 .into_iter()
+                .filter(|a| access.can_access(a.id))
                 .filter(|a| query.include_ignored || a.include_in_statistics)
                 .collect();
             let filtered_count = filtered_accounts.len();
@@ -176,9 +199,17 @@ pub async fn get_all_accounts_timeseries(
     }
 
     // Compute timeseries for all accounts using the compute module
+    let scenario_id = match ScenarioPreferences::load(&state.db).await {
+        Ok(preferences) => preferences.resolve(query.scenario_id, &accounts),
+        Err(db_error) => {
+            error!("Failed to load scenario preferences: {}", db_error);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
     debug!("Computing timeseries for {} accounts from {} to {} (scenario_id={:?})",
-           accounts.len(), query.start_date, query.end_date, query.scenario_id);
-    let compute = default_compute_with_scenario(None, query.scenario_id);
+           accounts.len(), query.start_date, query.end_date, scenario_id);
+    let compute = default_compute_with_scenario(Some(timezone.today()), scenario_id);
 
     trace!("Executing timeseries computation for all accounts");
     let timeseries_result = compute
@@ -215,3 +246,164 @@ pub async fn get_all_accounts_timeseries(
 
     Ok(Json(response))
 }
+
+/// Query parameters for account sparklines
+#[derive(Debug, Deserialize, ToSchema, IntoParams, Validate)]
+pub struct SparklineQuery {
+    /// Number of days back from today to cover (default: 90)
+    #[validate(range(min = 7, max = 730))]
+    pub days: Option<i64>,
+}
+
+/// A downsampled balance history small enough to draw inline next to an account
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountSparkline {
+    pub account_id: i32,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// Evenly spaced balance points, always including the first and last day
+    pub points: Vec<AccountStatePoint>,
+    pub min_balance: Option<Decimal>,
+    pub max_balance: Option<Decimal>,
+    /// Balance change between the first and last point
+    pub change: Option<Decimal>,
+}
+
+/// Number of points a sparkline is reduced to.
+const SPARKLINE_POINTS: usize = 30;
+
+/// Keeps `target` evenly spaced points, always including the first and the last one.
+fn downsample(points: Vec<AccountStatePoint>, target: usize) -> Vec<AccountStatePoint> {
+    if points.len() <= target || target < 2 {
+        return points;
+    }
+    let last = points.len() - 1;
+    (0..target)
+        .map(|i| points[i * last / (target - 1)].clone())
+        .collect()
+}
+
+fn sparkline_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// Get a lightweight balance sparkline for an account
+///
+/// Computes the daily balance over the last `days` days (honoring the owner's active
+/// scenario) and downsamples it to about 30 points for inline charts.
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{account_id}/sparkline",
+    tag = "timeseries",
+    params(
+        ("account_id" = i32, Path, description = "Account ID"),
+        SparklineQuery,
+    ),
+    responses(
+        (status = 200, description = "Account sparkline retrieved successfully", body = ApiResponse<AccountSparkline>),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_account_sparkline(
+    Path(account_id): Path<i32>,
+    Valid(Query(query)): Valid<Query<SparklineQuery>>,
+    State(state): State<AppState>,
+    access: AccountAccess,
+    timezone: UserTimezone,
+) -> Result<Json<ApiResponse<AccountSparkline>>, (StatusCode, Json<ErrorResponse>)> {
+    let days = query.days.unwrap_or(90);
+    debug!("Fetching {}-day sparkline for account ID: {}", days, account_id);
+
+    let internal_error = |message: String| {
+        error!("Failed to build sparkline for account ID {}: {}", account_id, message);
+        sparkline_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "INTERNAL_ERROR",
+            "Failed to compute account sparkline".to_string(),
+        )
+    };
+
+    let account_model = account::Entity::find_by_id(account_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| internal_error(e.to_string()))?
+        .filter(|account| access.can_access(account.id))
+        .ok_or_else(|| {
+            warn!("Account with ID {} not found", account_id);
+            sparkline_error(
+                StatusCode::NOT_FOUND,
+                "ACCOUNT_NOT_FOUND",
+                format!("Account with id {} not found", account_id),
+            )
+        })?;
+
+    let scenario_id = ScenarioPreferences::load(&state.db)
+        .await
+        .map_err(|e| internal_error(e.to_string()))?
+        .resolve(None, std::slice::from_ref(&account_model));
+
+    let end_date = timezone.today();
+    let start_date = end_date - chrono::Duration::days(days);
+
+    let cache_key = format!("sparkline_{}_{}_{}_scenario_{:?}", account_id, days, end_date, scenario_id);
+    let timeseries = if let Some(CachedData::Timeseries(timeseries)) = state.cache.get(&cache_key).await {
+        debug!("Sparkline for account ID {} retrieved from cache", account_id);
+        timeseries
+    } else {
+        let compute = default_compute_with_scenario(Some(end_date), scenario_id);
+        let df = compute
+            .compute_account_state(&state.db, std::slice::from_ref(&account_model), start_date, end_date)
+            .await
+            .map_err(|e| internal_error(e.to_string()))?;
+        let mut points: Vec<AccountStatePoint> = convert_dataframe_to_timeseries(df)
+            .map_err(|e| internal_error(e.to_string()))?
+            .data_points
+            .into_iter()
+            .filter(|p| p.account_id == account_id)
+            .collect();
+        points.sort_by_key(|p| p.date);
+
+        let timeseries = AccountStateTimeseries::new_with_range(
+            downsample(points, SPARKLINE_POINTS),
+            DateRange { start: start_date, end: end_date },
+        );
+        state
+            .cache
+            .insert(cache_key, CachedData::Timeseries(timeseries.clone()))
+            .await;
+        timeseries
+    };
+
+    let points = timeseries.data_points;
+    let min_balance = points.iter().map(|p| p.balance).min();
+    let max_balance = points.iter().map(|p| p.balance).max();
+    let change = match (points.first(), points.last()) {
+        (Some(first), Some(last)) => Some(last.balance - first.balance),
+        _ => None,
+    };
+
+    info!("Sparkline for account ID {} built with {} points", account_id, points.len());
+
+    Ok(Json(ApiResponse {
+        data: AccountSparkline {
+            account_id,
+            start_date,
+            end_date,
+            points,
+            min_balance,
+            max_balance,
+            change,
+        },
+        message: "Account sparkline retrieved successfully".to_string(),
+        success: true,
+    }))
+}
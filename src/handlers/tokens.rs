@@ -0,0 +1,221 @@
+use crate::helpers::auth::{
+    generate_api_token, hash_bearer_token, AuthenticatedUser, Credential, API_TOKEN_DISPLAY_LENGTH,
+};
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use axum_valid::Valid;
+use chrono::{Duration, NaiveDateTime};
+use model::entities::api_token;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, Set};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, instrument, trace, warn};
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// Request body for creating an API token
+#[derive(Debug, Deserialize, Serialize, ToSchema, Validate)]
+pub struct CreateApiTokenRequest {
+    /// What the token is used for, e.g. "import cron job"
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    /// Days until the token expires; never when omitted
+    #[validate(range(min = 1, max = 3650))]
+    pub expires_in_days: Option<i64>,
+}
+
+/// An API token, without its secret
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ApiTokenResponse {
+    pub id: i32,
+    pub name: String,
+    /// First characters of the token
+    pub token_prefix: String,
+    pub created_at: NaiveDateTime,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+impl From<api_token::Model> for ApiTokenResponse {
+    fn from(model: api_token::Model) -> Self {
+        Self {
+            id: model.id,
+            name: model.name,
+            token_prefix: model.token_prefix,
+            created_at: model.created_at,
+            last_used_at: model.last_used_at,
+            expires_at: model.expires_at,
+        }
+    }
+}
+
+/// A newly created API token including its secret
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreatedApiTokenResponse {
+    /// Bearer token to send as `Authorization: Bearer <token>`; it is not shown again
+    pub token: String,
+    #[serde(flatten)]
+    pub api_token: ApiTokenResponse,
+}
+
+fn token_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+fn database_error(e: DbErr) -> (StatusCode, Json<ErrorResponse>) {
+    error!("Database error in API tokens: {}", e);
+    token_error(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", e.to_string())
+}
+
+/// List the current user's API tokens
+#[utoipa::path(
+    get,
+    path = "/api/v1/tokens",
+    responses(
+        (status = 200, description = "API tokens of the current user", body = ApiResponse<Vec<ApiTokenResponse>>),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "tokens"
+)]
+#[instrument(skip(state))]
+pub async fn get_api_tokens(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+) -> Result<Json<ApiResponse<Vec<ApiTokenResponse>>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_api_tokens function");
+
+    let tokens = api_token::Entity::find()
+        .filter(api_token::Column::UserId.eq(auth.user_id))
+        .order_by_asc(api_token::Column::Id)
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+
+    debug!("Found {} API tokens of user {}", tokens.len(), auth.user_id);
+    Ok(Json(ApiResponse {
+        data: tokens.into_iter().map(ApiTokenResponse::from).collect(),
+        message: "API tokens retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Create an API token
+///
+/// Tokens authenticate scripts without an interactive login. The secret is only
+/// returned in this response. Creating tokens requires a login session, so a leaked
+/// token can't be used to mint new ones.
+#[utoipa::path(
+    post,
+    path = "/api/v1/tokens",
+    request_body = CreateApiTokenRequest,
+    responses(
+        (status = 201, description = "API token created", body = ApiResponse<CreatedApiTokenResponse>),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 403, description = "Request was authenticated with an API token", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "tokens"
+)]
+#[instrument(skip(state))]
+pub async fn create_api_token(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Valid(Json(request)): Valid<Json<CreateApiTokenRequest>>,
+) -> Result<(StatusCode, Json<ApiResponse<CreatedApiTokenResponse>>), (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering create_api_token function");
+
+    if let Credential::ApiToken(token_id) = auth.credential {
+        warn!("User {} tried to create an API token with API token {}", auth.user_id, token_id);
+        return Err(token_error(
+            StatusCode::FORBIDDEN,
+            "SESSION_REQUIRED",
+            "API tokens can only be created from a login session".to_string(),
+        ));
+    }
+
+    let token = generate_api_token();
+    let now = chrono::Utc::now().naive_utc();
+    let created = api_token::ActiveModel {
+        user_id: Set(auth.user_id),
+        name: Set(request.name),
+        token_hash: Set(hash_bearer_token(&token)),
+        token_prefix: Set(token.chars().take(API_TOKEN_DISPLAY_LENGTH).collect()),
+        created_at: Set(now),
+        last_used_at: Set(None),
+        expires_at: Set(request.expires_in_days.map(|days| now + Duration::days(days))),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await
+    .map_err(database_error)?;
+
+    info!("User {} created API token {} '{}'", auth.user_id, created.id, created.name);
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse {
+            data: CreatedApiTokenResponse {
+                token,
+                api_token: created.into(),
+            },
+            message: "API token created successfully".to_string(),
+            success: true,
+        }),
+    ))
+}
+
+/// Revoke an API token
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tokens/{token_id}",
+    params(
+        ("token_id" = i32, Path, description = "API token ID"),
+    ),
+    responses(
+        (status = 204, description = "API token revoked"),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 404, description = "API token not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "tokens"
+)]
+#[instrument(skip(state))]
+pub async fn delete_api_token(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(token_id): Path<i32>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering delete_api_token function");
+
+    let removed = api_token::Entity::delete_many()
+        .filter(api_token::Column::Id.eq(token_id))
+        .filter(api_token::Column::UserId.eq(auth.user_id))
+        .exec(&state.db)
+        .await
+        .map_err(database_error)?;
+    if removed.rows_affected == 0 {
+        warn!("API token {} of user {} not found", token_id, auth.user_id);
+        return Err(token_error(
+            StatusCode::NOT_FOUND,
+            "TOKEN_NOT_FOUND",
+            format!("API token with id {} not found", token_id),
+        ));
+    }
+
+    info!("User {} revoked API token {}", auth.user_id, token_id);
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,52 +1,168 @@
+pub mod bulk;
+pub mod category_suggestions;
 pub mod one_offs;
 pub mod recurring;
+pub mod recurring_approval;
+pub mod recurring_bulk;
 pub mod recurring_instances;
+pub mod recurring_suggestions;
 pub mod imported;
+pub mod legs;
+pub mod planned;
+pub mod purchases;
+pub mod quick_entry;
+pub mod search;
+pub mod similar;
 
 // Re-export all the types and functions from one_offs for backward compatibility
 pub use one_offs::{
-    CreateTransactionRequest, UpdateTransactionRequest, TransactionResponse,
+    CreateTransactionRequest, UpdateTransactionRequest, TransactionResponse, TransactionStatus,
+    UncategorizedTransactionsResponse, CategoryAssignment, BulkCategorizeRequest, BulkCategorizeResponse,
     create_transaction, get_transactions, get_account_transactions, 
     get_transaction, update_transaction, delete_transaction,
+    get_uncategorized_transactions, bulk_categorize_transactions, get_transactions_geojson,
     // Re-export OpenAPI path structs generated by utoipa macro
     __path_create_transaction, __path_get_transactions, __path_get_account_transactions,
     __path_get_transaction, __path_update_transaction, __path_delete_transaction,
+    __path_get_uncategorized_transactions, __path_bulk_categorize_transactions,
+    __path_get_transactions_geojson,
+};
+
+// Re-export purchase reminder types and functions
+pub use purchases::{
+    PurchaseDeadline, PurchaseRemindersQuery, PurchaseReminderResponse,
+    get_purchase_reminders,
+    // Re-export OpenAPI path structs generated by utoipa macro
+    __path_get_purchase_reminders,
+};
+
+// Re-export bulk transaction types and functions
+pub use bulk::{
+    BulkTransactionPatch, BulkUpdateTransactionsRequest, BulkDeleteTransactionsRequest, BulkTransactionsResponse,
+    bulk_update_transactions, bulk_delete_transactions,
+    // Re-export OpenAPI path structs generated by utoipa macro
+    __path_bulk_update_transactions, __path_bulk_delete_transactions,
+};
+
+// Re-export transaction leg types and functions
+pub use legs::{
+    CreateTransactionLegRequest, TransactionLegResponse,
+    create_transaction_leg,
+    // Re-export OpenAPI path structs generated by utoipa macro
+    __path_create_transaction_leg,
+};
+
+// Re-export planned transaction types and functions
+pub use planned::{
+    ConfirmTransactionRequest,
+    confirm_transaction,
+    // Re-export OpenAPI path structs generated by utoipa macro
+    __path_confirm_transaction,
+};
+
+// Re-export quick entry types and functions
+pub use quick_entry::{
+    QuickEntryRequest,
+    create_quick_transaction,
+    // Re-export OpenAPI path structs generated by utoipa macro
+    __path_create_quick_transaction,
+};
+
+// Re-export transaction search types and functions
+pub use search::{
+    TransactionSearchQuery, TransactionSortField, SortDirection,
+    search_transactions,
+    // Re-export OpenAPI path structs generated by utoipa macro
+    __path_search_transactions,
+};
+
+// Re-export similar transaction types and functions
+pub use similar::{
+    SimilarTransactionsQuery, SimilarTransaction, SimilarTransactionsResponse,
+    get_similar_transactions,
+    // Re-export OpenAPI path structs generated by utoipa macro
+    __path_get_similar_transactions,
+};
+
+// Re-export category suggestion types and functions
+pub use category_suggestions::{
+    CategorySuggestionsQuery, SuggestedCategory, CategorySuggestionsResponse,
+    get_category_suggestions,
+    // Re-export OpenAPI path structs generated by utoipa macro
+    __path_get_category_suggestions,
 };
 
 // Re-export recurring transaction types and functions
 pub use recurring::{
-    CreateRecurringTransactionRequest, UpdateRecurringTransactionRequest, RecurringTransactionResponse,
+    CreateRecurringTransactionRequest, UpdateRecurringTransactionRequest, RecurringTransactionResponse, FxMode,
+    AmountStrategy, DateAdjustment,
     CreateRecurringInstanceRequest, RecurringInstanceResponse, RecurringTransactionQuery,
     MissingInstanceInfo, MissingInstancesQuery,
+    DueProfileQuery, DuePaymentInfo, DueDayEntry, RecurringDueProfileResponse,
+    ContractRemindersQuery, ContractReminderResponse,
     BulkCreateInstancesRequest, BulkInstanceItem, BulkCreateInstancesResponse,
     create_recurring_transaction, get_recurring_transactions, get_recurring_transaction,
     update_recurring_transaction, delete_recurring_transaction, create_recurring_instance,
-    get_missing_instances, bulk_create_instances,
+    get_missing_instances, bulk_create_instances, get_recurring_due_profile, get_contract_reminders,
     // Re-export OpenAPI path structs generated by utoipa macro
     __path_create_recurring_transaction, __path_get_recurring_transactions, __path_get_recurring_transaction,
     __path_update_recurring_transaction, __path_delete_recurring_transaction, __path_create_recurring_instance,
-    __path_get_missing_instances, __path_bulk_create_instances,
+    __path_get_missing_instances, __path_bulk_create_instances, __path_get_recurring_due_profile,
+    __path_get_contract_reminders,
+};
+
+// Re-export recurring approval types and functions
+pub use recurring_approval::{
+    RecurringApprovalPolicyRequest, RecurringApprovalPolicyResponse,
+    set_recurring_approval_policy, approve_recurring_transaction, reject_recurring_transaction,
+    // Re-export OpenAPI path structs generated by utoipa macro
+    __path_set_recurring_approval_policy, __path_approve_recurring_transaction,
+    __path_reject_recurring_transaction,
+};
+
+// Re-export recurring bulk edit types and functions
+pub use recurring_bulk::{
+    BulkRecurringTransactionPatch, BulkUpdateRecurringTransactionsRequest, BulkRecurringItemResult,
+    BulkUpdateRecurringTransactionsResponse, bulk_update_recurring_transactions,
+    // Re-export OpenAPI path structs generated by utoipa macro
+    __path_bulk_update_recurring_transactions,
 };
 
 // Re-export recurring instance types and functions
 pub use recurring_instances::{
-    UpdateRecurringInstanceRequest, RecurringInstanceQuery,
+    UpdateRecurringInstanceRequest, RecurringInstanceQuery, InstanceGrouping, RecurringInstanceMonthGroup,
+    GenerateInstancesQuery,
     get_recurring_instances, get_recurring_instance,
-    update_recurring_instance, delete_recurring_instance,
+    update_recurring_instance, delete_recurring_instance, generate_instances,
+    skip_recurring_instance, unskip_recurring_instance,
     // Re-export OpenAPI path structs generated by utoipa macro
     __path_get_recurring_instances, __path_get_recurring_instance,
     __path_update_recurring_instance, __path_delete_recurring_instance,
+    __path_generate_instances, __path_skip_recurring_instance, __path_unskip_recurring_instance,
+};
+
+// Re-export recurring suggestion types and functions
+pub use recurring_suggestions::{
+    RecurringSuggestionQuery, RecurringSuggestionResponse,
+    ConvertRecurringSuggestionRequest, ConvertRecurringSuggestionResponse,
+    get_recurring_suggestions, convert_recurring_suggestion,
+    // Re-export OpenAPI path structs generated by utoipa macro
+    __path_get_recurring_suggestions, __path_convert_recurring_suggestion,
 };
 
 // Re-export imported transaction types and functions
 pub use imported::{
     CreateImportedTransactionRequest, UpdateImportedTransactionRequest, ImportedTransactionResponse,
     ReconcileImportedTransactionRequest, ReconciledTransactionInfo, ImportedTransactionQuery,
+    BankChargeClassificationResponse, BulkReconcileRequest, BulkReconcileItem, BulkReconcileItemResult,
+    BulkReconcileResponse,
     create_imported_transaction, get_imported_transactions, get_account_imported_transactions,
     get_imported_transaction, update_imported_transaction, delete_imported_transaction,
-    reconcile_imported_transaction, clear_imported_transaction_reconciliation,
+    reconcile_imported_transaction, clear_imported_transaction_reconciliation, classify_bank_charges,
+    bulk_reconcile_imported_transactions,
     // Re-export OpenAPI path structs generated by utoipa macro
     __path_create_imported_transaction, __path_get_imported_transactions, __path_get_account_imported_transactions,
     __path_get_imported_transaction, __path_update_imported_transaction, __path_delete_imported_transaction,
     __path_reconcile_imported_transaction, __path_clear_imported_transaction_reconciliation,
+    __path_classify_bank_charges, __path_bulk_reconcile_imported_transactions,
 };
@@ -0,0 +1,446 @@
+use crate::handlers::double_entry::ensure_double_entry_in;
+use crate::handlers::periods::PeriodLockOverride;
+use crate::helpers::auth::{ensure_admin, AuthenticatedUser};
+use crate::handlers::tags::TransactionTagFilter;
+use crate::helpers::access::AccountAccess;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use model::entities::{category, locked_period, one_off_transaction, one_off_transaction_tag, tag};
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, DatabaseTransaction, DbErr, EntityTrait, PaginatorTrait, QueryFilter,
+    Set, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tracing::{debug, error, info, instrument, warn};
+use utoipa::ToSchema;
+
+/// Changes applied to every selected transaction. Fields left out are kept.
+#[derive(Debug, Default, Deserialize, Serialize, ToSchema)]
+pub struct BulkTransactionPatch {
+    pub category_id: Option<i32>,
+    pub include_in_statistics: Option<bool>,
+    /// Tags attached to every transaction that doesn't carry them yet
+    pub add_tag_ids: Option<Vec<i32>>,
+    /// Tags detached from every transaction
+    pub remove_tag_ids: Option<Vec<i32>>,
+}
+
+impl BulkTransactionPatch {
+    fn is_empty(&self) -> bool {
+        self.category_id.is_none()
+            && self.include_in_statistics.is_none()
+            && self.add_tag_ids.as_ref().is_none_or(|ids| ids.is_empty())
+            && self.remove_tag_ids.as_ref().is_none_or(|ids| ids.is_empty())
+    }
+}
+
+/// Request body for changing many one-off transactions at once.
+/// Either `transaction_ids` or `filter` must be provided; when both are given
+/// only transactions matching both are changed.
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct BulkUpdateTransactionsRequest {
+    /// Explicit list of one-off transaction IDs
+    pub transaction_ids: Option<Vec<i32>>,
+    /// Filter selecting one-off transactions
+    pub filter: Option<TransactionTagFilter>,
+    pub patch: BulkTransactionPatch,
+    /// When true, nothing is written and only the counts are returned
+    pub dry_run: Option<bool>,
+}
+
+/// Request body for deleting many one-off transactions at once.
+/// Either `transaction_ids` or `filter` must be provided; when both are given
+/// only transactions matching both are deleted.
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct BulkDeleteTransactionsRequest {
+    /// Explicit list of one-off transaction IDs
+    pub transaction_ids: Option<Vec<i32>>,
+    /// Filter selecting one-off transactions
+    pub filter: Option<TransactionTagFilter>,
+    /// When true, nothing is deleted and only the counts are returned
+    pub dry_run: Option<bool>,
+}
+
+/// Result of a bulk update or delete
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BulkTransactionsResponse {
+    /// Number of transactions selected by the request
+    pub matched: usize,
+    /// Transactions changed or deleted (or that would be, on a dry run)
+    pub affected: usize,
+    /// Requested transaction IDs that do not exist
+    pub not_found: Vec<i32>,
+    /// Transaction IDs skipped because they are reconciled and locked
+    pub locked: Vec<i32>,
+    /// Transaction IDs skipped because they are dated inside a locked period
+    pub period_locked: Vec<i32>,
+    /// Transaction IDs skipped because their accounts are shared read-only
+    pub read_only: Vec<i32>,
+    pub dry_run: bool,
+}
+
+fn bulk_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+fn database_error(e: DbErr) -> (StatusCode, Json<ErrorResponse>) {
+    error!("Database error in bulk transaction change: {}", e);
+    bulk_error(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", e.to_string())
+}
+
+/// Loads the transactions selected by IDs and filter and sorts out those that may not change.
+///
+/// Returns the IDs of the transactions to change next to the response describing the
+/// selection. Transactions of other users are reported like missing ones.
+async fn select_transactions(
+    db: &DatabaseConnection,
+    access: &AccountAccess,
+    auth: Option<&AuthenticatedUser>,
+    transaction_ids: Option<&Vec<i32>>,
+    filter: Option<&TransactionTagFilter>,
+    lock_override: &PeriodLockOverride,
+    dry_run: bool,
+) -> Result<(Vec<i32>, BulkTransactionsResponse), (StatusCode, Json<ErrorResponse>)> {
+    let transaction_ids = transaction_ids.filter(|ids| !ids.is_empty());
+    let filter = filter.filter(|filter| !filter.is_empty());
+    if transaction_ids.is_none() && filter.is_none() {
+        warn!("Bulk transaction change without transaction IDs or filter");
+        return Err(bulk_error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_SELECTION",
+            "Provide transaction_ids or a non-empty filter".to_string(),
+        ));
+    }
+
+    let mut condition = Condition::all();
+    if let Some(ids) = transaction_ids {
+        condition = condition.add(one_off_transaction::Column::Id.is_in(ids.clone()));
+    }
+    if let Some(filter) = filter {
+        condition = condition.add(filter.condition());
+    }
+    let transactions = access
+        .filter(
+            one_off_transaction::Entity::find(),
+            [one_off_transaction::Column::TargetAccountId, one_off_transaction::Column::SourceAccountId],
+        )
+        .filter(condition)
+        .all(db)
+        .await
+        .map_err(database_error)?;
+    let locked_periods = if lock_override.is_set() {
+        ensure_admin(db, auth, "modify transactions in locked periods").await?;
+        Vec::new()
+    } else {
+        locked_period::Entity::find().all(db).await.map_err(database_error)?
+    };
+
+    let found: HashSet<i32> = transactions.iter().map(|transaction| transaction.id).collect();
+    let mut response = BulkTransactionsResponse {
+        matched: transactions.len(),
+        affected: 0,
+        not_found: transaction_ids
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|id| !found.contains(id))
+            .collect(),
+        locked: Vec::new(),
+        period_locked: Vec::new(),
+        read_only: Vec::new(),
+        dry_run,
+    };
+    let mut selected = Vec::new();
+    for transaction in transactions {
+        if !access.can_write_any(transaction.target_account_id, transaction.source_account_id) {
+            response.read_only.push(transaction.id);
+        } else if transaction.is_locked {
+            response.locked.push(transaction.id);
+        } else if locked_periods.iter().any(|period| period.contains(transaction.date)) {
+            response.period_locked.push(transaction.id);
+        } else {
+            selected.push(transaction.id);
+        }
+    }
+    response.affected = selected.len();
+    Ok((selected, response))
+}
+
+/// Change many transactions at once
+///
+/// Sets the category or statistics flag and attaches or detaches tags on every selected
+/// transaction. Transactions that can't be changed one by one (reconciled, inside a locked
+/// period or shared read-only) are reported and skipped; an unknown category or tag rejects
+/// the whole request, and so does, in strict double-entry mode, a category that leaves one
+/// of the transactions unbalanced.
+#[utoipa::path(
+    post,
+    path = "/api/v1/transactions/bulk-update",
+    tag = "transactions",
+    params(PeriodLockOverride),
+    request_body = BulkUpdateTransactionsRequest,
+    responses(
+        (status = 200, description = "Transactions changed (or counted on dry run)", body = ApiResponse<BulkTransactionsResponse>),
+        (status = 400, description = "No selection, empty patch, unknown category or tag, or a double-entry violation", body = ErrorResponse),
+        (status = 403, description = "admin_override was passed by a non-administrator", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn bulk_update_transactions(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    auth: Option<AuthenticatedUser>,
+    Query(lock_override): Query<PeriodLockOverride>,
+    Json(request): Json<BulkUpdateTransactionsRequest>,
+) -> Result<Json<ApiResponse<BulkTransactionsResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("Bulk updating transactions: {:?}", request);
+
+    let patch = &request.patch;
+    if patch.is_empty() {
+        warn!("Bulk transaction update without changes");
+        return Err(bulk_error(
+            StatusCode::BAD_REQUEST,
+            "EMPTY_PATCH",
+            "patch must change at least one field".to_string(),
+        ));
+    }
+    if let Some(category_id) = patch.category_id {
+        let known = category::Entity::find_by_id(category_id)
+            .count(&state.db)
+            .await
+            .map_err(database_error)?;
+        if known == 0 {
+            warn!("Bulk update referenced unknown category {}", category_id);
+            return Err(bulk_error(
+                StatusCode::BAD_REQUEST,
+                "INVALID_CATEGORY_ID",
+                format!("Category with id {} does not exist", category_id),
+            ));
+        }
+    }
+    let add_tag_ids = patch.add_tag_ids.clone().unwrap_or_default();
+    let remove_tag_ids = patch.remove_tag_ids.clone().unwrap_or_default();
+    let tag_ids: HashSet<i32> = add_tag_ids.iter().chain(&remove_tag_ids).copied().collect();
+    if !tag_ids.is_empty() {
+        let known: HashSet<i32> = tag::Entity::find()
+            .filter(tag::Column::Id.is_in(tag_ids.clone()))
+            .all(&state.db)
+            .await
+            .map_err(database_error)?
+            .into_iter()
+            .map(|tag| tag.id)
+            .collect();
+        if let Some(missing) = tag_ids.iter().find(|id| !known.contains(id)) {
+            warn!("Bulk update referenced unknown tag {}", missing);
+            return Err(bulk_error(
+                StatusCode::BAD_REQUEST,
+                "INVALID_TAG_ID",
+                format!("Tag with id {} does not exist", missing),
+            ));
+        }
+    }
+
+    let dry_run = request.dry_run.unwrap_or(false);
+    let (selected, response) = select_transactions(
+        &state.db,
+        &access,
+        auth.as_ref(),
+        request.transaction_ids.as_ref(),
+        request.filter.as_ref(),
+        &lock_override,
+        dry_run,
+    )
+    .await?;
+
+    // In strict mode every re-categorized transaction must still balance, otherwise nothing is changed
+    let check_double_entry = state.double_entry_strict && patch.category_id.is_some();
+    if (!dry_run || check_double_entry) && !selected.is_empty() {
+        let txn = state.db.begin().await.map_err(database_error)?;
+        if !dry_run {
+            apply_patch(&txn, patch, &selected, &add_tag_ids, &remove_tag_ids).await?;
+        }
+        if check_double_entry {
+            let transactions = one_off_transaction::Entity::find()
+                .filter(one_off_transaction::Column::Id.is_in(selected.clone()))
+                .all(&txn)
+                .await
+                .map_err(database_error)?;
+            for transaction in transactions {
+                ensure_double_entry_in(
+                    &state,
+                    &txn,
+                    transaction.target_account_id,
+                    transaction.source_account_id,
+                    patch.category_id.or(transaction.category_id),
+                    false,
+                )
+                .await
+                .map_err(|(status, Json(mut e))| {
+                    warn!("Bulk update would leave transaction {} violating double-entry rules", transaction.id);
+                    e.error = format!("Transaction {}: {}", transaction.id, e.error);
+                    (status, Json(e))
+                })?;
+            }
+        }
+        if dry_run {
+            txn.rollback().await.map_err(database_error)?;
+        } else {
+            txn.commit().await.map_err(database_error)?;
+        }
+    }
+
+    info!(
+        "Bulk transaction update: matched={}, affected={}, skipped={}, dry_run={}",
+        response.matched,
+        response.affected,
+        response.locked.len() + response.period_locked.len() + response.read_only.len(),
+        dry_run
+    );
+    Ok(Json(ApiResponse {
+        data: response,
+        message: if dry_run { "Dry run".to_string() } else { "Transactions updated successfully".to_string() },
+        success: true,
+    }))
+}
+
+/// Writes the patch to the selected transactions within `txn`.
+async fn apply_patch(
+    txn: &DatabaseTransaction,
+    patch: &BulkTransactionPatch,
+    selected: &[i32],
+    add_tag_ids: &[i32],
+    remove_tag_ids: &[i32],
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if patch.category_id.is_some() || patch.include_in_statistics.is_some() {
+        let mut changes = one_off_transaction::ActiveModel::default();
+        if let Some(category_id) = patch.category_id {
+            changes.category_id = Set(Some(category_id));
+        }
+        if let Some(include_in_statistics) = patch.include_in_statistics {
+            changes.include_in_statistics = Set(include_in_statistics);
+        }
+        one_off_transaction::Entity::update_many()
+            .set(changes)
+            .filter(one_off_transaction::Column::Id.is_in(selected.to_vec()))
+            .exec(txn)
+            .await
+            .map_err(database_error)?;
+    }
+    if !remove_tag_ids.is_empty() {
+        one_off_transaction_tag::Entity::delete_many()
+            .filter(one_off_transaction_tag::Column::TransactionId.is_in(selected.to_vec()))
+            .filter(one_off_transaction_tag::Column::TagId.is_in(remove_tag_ids.to_vec()))
+            .exec(txn)
+            .await
+            .map_err(database_error)?;
+    }
+    if !add_tag_ids.is_empty() {
+        let existing: HashSet<(i32, i32)> = one_off_transaction_tag::Entity::find()
+            .filter(one_off_transaction_tag::Column::TransactionId.is_in(selected.to_vec()))
+            .filter(one_off_transaction_tag::Column::TagId.is_in(add_tag_ids.to_vec()))
+            .all(txn)
+            .await
+            .map_err(database_error)?
+            .into_iter()
+            .map(|link| (link.transaction_id, link.tag_id))
+            .collect();
+        let links: Vec<one_off_transaction_tag::ActiveModel> = selected
+            .iter()
+            .flat_map(|transaction_id| add_tag_ids.iter().map(move |tag_id| (*transaction_id, *tag_id)))
+            .filter(|link| !existing.contains(link))
+            .map(|(transaction_id, tag_id)| one_off_transaction_tag::ActiveModel {
+                transaction_id: Set(transaction_id),
+                tag_id: Set(tag_id),
+            })
+            .collect();
+        if !links.is_empty() {
+            one_off_transaction_tag::Entity::insert_many(links)
+                .exec(txn)
+                .await
+                .map_err(database_error)?;
+        }
+    }
+    Ok(())
+}
+
+/// Delete many transactions at once
+///
+/// Deletes every selected transaction together with its legs. Transactions that can't be
+/// deleted one by one (reconciled, inside a locked period or shared read-only) are
+/// reported and kept.
+#[utoipa::path(
+    post,
+    path = "/api/v1/transactions/bulk-delete",
+    tag = "transactions",
+    params(PeriodLockOverride),
+    request_body = BulkDeleteTransactionsRequest,
+    responses(
+        (status = 200, description = "Transactions deleted (or counted on dry run)", body = ApiResponse<BulkTransactionsResponse>),
+        (status = 400, description = "Neither transaction IDs nor a filter were provided", body = ErrorResponse),
+        (status = 403, description = "admin_override was passed by a non-administrator", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn bulk_delete_transactions(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    auth: Option<AuthenticatedUser>,
+    Query(lock_override): Query<PeriodLockOverride>,
+    Json(request): Json<BulkDeleteTransactionsRequest>,
+) -> Result<Json<ApiResponse<BulkTransactionsResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("Bulk deleting transactions: {:?}", request);
+
+    let dry_run = request.dry_run.unwrap_or(false);
+    let (selected, response) = select_transactions(
+        &state.db,
+        &access,
+        auth.as_ref(),
+        request.transaction_ids.as_ref(),
+        request.filter.as_ref(),
+        &lock_override,
+        dry_run,
+    )
+    .await?;
+
+    if !dry_run && !selected.is_empty() {
+        let txn = state.db.begin().await.map_err(database_error)?;
+        one_off_transaction::Entity::delete_many()
+            .filter(one_off_transaction::Column::ParentTransactionId.is_in(selected.clone()))
+            .exec(&txn)
+            .await
+            .map_err(database_error)?;
+        one_off_transaction::Entity::delete_many()
+            .filter(one_off_transaction::Column::Id.is_in(selected.clone()))
+            .exec(&txn)
+            .await
+            .map_err(database_error)?;
+        txn.commit().await.map_err(database_error)?;
+    }
+
+    info!(
+        "Bulk transaction delete: matched={}, affected={}, skipped={}, dry_run={}",
+        response.matched,
+        response.affected,
+        response.locked.len() + response.period_locked.len() + response.read_only.len(),
+        dry_run
+    );
+    Ok(Json(ApiResponse {
+        data: response,
+        message: if dry_run { "Dry run".to_string() } else { "Transactions deleted successfully".to_string() },
+        success: true,
+    }))
+}
@@ -0,0 +1,196 @@
+use crate::helpers::access::AccountAccess;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use axum_valid::Valid;
+use compute::category_suggestions::{keywords, suggest_categories};
+use model::entities::{category, imported_transaction, one_off_transaction};
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tracing::{debug, error, info, instrument, warn};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Query parameters for category suggestions
+#[derive(Debug, Deserialize, ToSchema, IntoParams, Validate)]
+pub struct CategorySuggestionsQuery {
+    /// Maximum number of categories to suggest (default: 3)
+    #[validate(range(min = 1, max = 20))]
+    pub limit: Option<usize>,
+}
+
+/// A category the imported transaction likely belongs to
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SuggestedCategory {
+    pub category_id: i32,
+    pub category_name: Option<String>,
+    /// Between 0 and 1; higher when more of the similar transactions agree and the
+    /// closest one shares more of the description's keywords
+    pub confidence: f64,
+    /// Number of categorized transactions sharing a keyword with the description
+    pub matches: usize,
+}
+
+/// Category suggestions for an imported transaction, most confident first
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CategorySuggestionsResponse {
+    pub transaction_id: i32,
+    /// Keywords taken from the description, sorted alphabetically
+    pub keywords: Vec<String>,
+    pub suggestions: Vec<SuggestedCategory>,
+}
+
+fn suggestion_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// Suggest categories for an imported transaction
+///
+/// Compares the words of the description with the categorized imported and one-off
+/// transactions of accessible accounts. One-off transactions reconciled from an import
+/// are represented by the import's bank description and not counted twice.
+#[utoipa::path(
+    get,
+    path = "/api/v1/imported-transactions/{transaction_id}/category-suggestions",
+    tag = "imported-transactions",
+    params(
+        ("transaction_id" = i32, Path, description = "Imported transaction ID"),
+        CategorySuggestionsQuery,
+    ),
+    responses(
+        (status = 200, description = "Category suggestions retrieved successfully", body = ApiResponse<CategorySuggestionsResponse>),
+        (status = 404, description = "Imported transaction not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_category_suggestions(
+    Path(transaction_id): Path<i32>,
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Valid(Query(query)): Valid<Query<CategorySuggestionsQuery>>,
+) -> Result<Json<ApiResponse<CategorySuggestionsResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("Suggesting categories for imported transaction {}", transaction_id);
+
+    let database_error = |e: DbErr| {
+        error!("Database error while suggesting categories for imported transaction {}: {}", transaction_id, e);
+        suggestion_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "DATABASE_ERROR",
+            "Failed to suggest categories".to_string(),
+        )
+    };
+
+    let transaction = imported_transaction::Entity::find_by_id(transaction_id)
+        .one(&state.db)
+        .await
+        .map_err(database_error)?
+        .filter(|transaction| access.can_access(transaction.account_id))
+        .ok_or_else(|| {
+            warn!("Imported transaction {} not found", transaction_id);
+            suggestion_error(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                format!("Imported transaction with id {} not found", transaction_id),
+            )
+        })?;
+
+    let imported = access
+        .filter(imported_transaction::Entity::find(), [imported_transaction::Column::AccountId])
+        .filter(imported_transaction::Column::Id.ne(transaction_id))
+        .filter(imported_transaction::Column::CategoryId.is_not_null())
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+    let reconciled_one_offs: HashSet<i32> = imported_transaction::Entity::find()
+        .filter(
+            imported_transaction::Column::ReconciledTransactionType
+                .eq(imported_transaction::ReconciledTransactionEntityType::OneOff),
+        )
+        .all(&state.db)
+        .await
+        .map_err(database_error)?
+        .into_iter()
+        .filter_map(|row| row.reconciled_transaction_id)
+        .collect();
+    let one_offs = access
+        .filter(
+            one_off_transaction::Entity::find(),
+            [one_off_transaction::Column::TargetAccountId, one_off_transaction::Column::SourceAccountId],
+        )
+        .filter(one_off_transaction::Column::CategoryId.is_not_null())
+        .filter(one_off_transaction::Column::IsSimulated.eq(false))
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+
+    let one_off_texts: Vec<(String, i32)> = one_offs
+        .into_iter()
+        .filter(|one_off| !reconciled_one_offs.contains(&one_off.id))
+        .filter_map(|one_off| {
+            let text = match &one_off.description {
+                Some(description) => format!("{} {}", one_off.name, description),
+                None => one_off.name.clone(),
+            };
+            one_off.category_id.map(|category_id| (text, category_id))
+        })
+        .collect();
+    let history = imported
+        .iter()
+        .filter_map(|row| row.category_id.map(|category_id| (row.description.as_str(), category_id)))
+        .chain(one_off_texts.iter().map(|(text, category_id)| (text.as_str(), *category_id)));
+
+    let limit = query.limit.unwrap_or(3);
+    let suggestions = suggest_categories(&transaction.description, history, limit);
+
+    let category_names: HashMap<i32, String> = if suggestions.is_empty() {
+        HashMap::new()
+    } else {
+        category::Entity::find()
+            .filter(category::Column::Id.is_in(suggestions.iter().map(|s| s.category_id)))
+            .all(&state.db)
+            .await
+            .map_err(database_error)?
+            .into_iter()
+            .map(|cat| (cat.id, cat.name))
+            .collect()
+    };
+
+    let mut description_keywords: Vec<String> = keywords(&transaction.description).into_iter().collect();
+    description_keywords.sort();
+
+    info!(
+        "Suggested {} categories for imported transaction {}",
+        suggestions.len(),
+        transaction_id
+    );
+
+    Ok(Json(ApiResponse {
+        data: CategorySuggestionsResponse {
+            transaction_id,
+            keywords: description_keywords,
+            suggestions: suggestions
+                .into_iter()
+                .map(|suggestion| SuggestedCategory {
+                    category_id: suggestion.category_id,
+                    category_name: category_names.get(&suggestion.category_id).cloned(),
+                    confidence: suggestion.confidence,
+                    matches: suggestion.matches,
+                })
+                .collect(),
+        },
+        message: "Category suggestions retrieved successfully".to_string(),
+        success: true,
+    }))
+}
@@ -1,17 +1,31 @@
+use crate::helpers::access::{account_forbidden, account_read_only, AccountAccess};
+use crate::helpers::bank_charges::{classify_bank_charge, system_category_id, BankChargeKind};
+use crate::helpers::fx::foreign_currency_from_raw_data;
+use crate::helpers::merchants::{MerchantEnricher, MerchantInfo};
 use crate::schemas::{ApiResponse, AppState, ErrorResponse};
 use axum::{
     extract::{Path, State, Query},
     http::StatusCode,
     response::Json,
 };
+use axum_valid::Valid;
 use chrono::NaiveDate;
-use model::entities::{imported_transaction, account};
+use common::{AmountLocale, PaginatedResponse, Pagination};
+use model::entities::{
+    account, imported_transaction, one_off_transaction, recurring_income, recurring_transaction,
+    recurring_transaction_instance,
+};
 use model::transaction::{Tag, TransactionGenerator};
 use rust_decimal::Decimal;
-use sea_orm::{ActiveModelTrait, EntityTrait, Set, ColumnTrait, QueryFilter, DbErr};
+use sea_orm::{
+    ActiveModelTrait, EntityTrait, Set, ColumnTrait, ConnectionTrait, PaginatorTrait, QueryFilter, QueryOrder, DbErr,
+    TransactionTrait,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use tracing::{instrument, error, warn, info, debug, trace};
 use utoipa::{ToSchema, IntoParams};
+use validator::Validate;
 
 /// Request body for creating a new imported transaction
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
@@ -30,6 +44,9 @@ pub struct CreateImportedTransactionRequest {
     pub raw_data: Option<serde_json::Value>,
     /// Optional category ID
     pub category_id: Option<i32>,
+    /// Decimal separator of the amounts written as text in `raw_data`, from the import
+    /// profile of the bank (guessed by default)
+    pub amount_locale: Option<AmountLocale>,
 }
 
 /// Request body for updating an imported transaction
@@ -45,6 +62,8 @@ pub struct UpdateImportedTransactionRequest {
     pub raw_data: Option<serde_json::Value>,
     /// Optional category ID
     pub category_id: Option<i32>,
+    /// Decimal separator of the amounts written as text in `raw_data` (guessed by default)
+    pub amount_locale: Option<AmountLocale>,
 }
 
 /// Request body for reconciling an imported transaction
@@ -88,7 +107,14 @@ pub struct ImportedTransactionResponse {
     pub reconciled_transaction_id: Option<i32>,
     pub reconciled_transaction_info: Option<ReconciledTransactionInfo>,
     pub category_id: Option<i32>,
+    /// Amount in the original payment currency, for foreign currency payments
+    pub original_amount: Option<Decimal>,
+    pub original_currency: Option<String>,
+    /// Currency conversion fee in the account currency (positive)
+    pub fx_fee: Option<Decimal>,
     pub tags: Vec<TagInfo>,
+    /// Cleaned merchant name and logo derived from the description
+    pub merchant: Option<MerchantInfo>,
 }
 
 /// Information about the reconciled transaction
@@ -99,8 +125,14 @@ pub struct ReconciledTransactionInfo {
 }
 
 /// Query parameters for filtering imported transactions
-#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+#[derive(Debug, Deserialize, ToSchema, IntoParams, Validate)]
 pub struct ImportedTransactionQuery {
+    /// Page number (default: 1)
+    #[validate(range(min = 1, max = 10000))]
+    pub page: Option<u64>,
+    /// Page size (default: 50)
+    #[validate(range(min = 1, max = 1000))]
+    pub limit: Option<u64>,
     /// Filter by account ID
     pub account_id: Option<i32>,
     /// Filter by reconciliation status
@@ -151,7 +183,11 @@ impl From<imported_transaction::Model> for ImportedTransactionResponse {
             reconciled_transaction_id: model.reconciled_transaction_id,
             reconciled_transaction_info,
             category_id: model.category_id,
+            original_amount: model.original_amount,
+            original_currency: model.original_currency,
+            fx_fee: model.fx_fee,
             tags: Vec::new(), // Will be populated by with_tags method
+            merchant: None, // Will be populated by with_merchant method
         }
     }
 }
@@ -170,6 +206,23 @@ impl ImportedTransactionResponse {
         response.tags = tag_infos;
         Ok(response)
     }
+
+    /// Attach merchant details resolved from the description
+    pub fn with_merchant(mut self, enricher: &MerchantEnricher) -> Self {
+        self.merchant = enricher.enrich(&self.description);
+        self
+    }
+}
+
+/// Load merchant mappings, falling back to heuristic cleaning only if the table can't be read
+async fn load_merchant_enricher(db: &sea_orm::DatabaseConnection) -> MerchantEnricher {
+    match MerchantEnricher::load(db).await {
+        Ok(enricher) => enricher,
+        Err(e) => {
+            warn!("Failed to load merchant mappings: {}", e);
+            MerchantEnricher::default()
+        }
+    }
 }
 
 /// Create a new imported transaction
@@ -180,7 +233,8 @@ impl ImportedTransactionResponse {
     request_body = CreateImportedTransactionRequest,
     responses(
         (status = 201, description = "Imported transaction created successfully", body = ApiResponse<ImportedTransactionResponse>),
-        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 400, description = "Invalid request or the account is virtual", body = ErrorResponse),
+        (status = 403, description = "No access to the account, or it is shared read-only", body = ErrorResponse),
         (status = 409, description = "Duplicate import hash", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
@@ -188,17 +242,40 @@ impl ImportedTransactionResponse {
 #[instrument]
 pub async fn create_imported_transaction(
     State(state): State<AppState>,
+    access: AccountAccess,
     Json(request): Json<CreateImportedTransactionRequest>,
 ) -> Result<(StatusCode, Json<ApiResponse<ImportedTransactionResponse>>), (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering create_imported_transaction function");
     debug!("Creating imported transaction for account_id: {}, amount: {}, import_hash: {}", 
            request.account_id, request.amount, request.import_hash);
 
+    if !access.can_access(request.account_id) {
+        warn!("Account {} is not accessible to user {:?}", request.account_id, access.user_id());
+        return Err(account_forbidden(request.account_id));
+    }
+    if !access.can_write(request.account_id) {
+        warn!("Account {} is read-only for user {:?}", request.account_id, access.user_id());
+        return Err(account_read_only(request.account_id));
+    }
+
     // Validate that the account exists
     trace!("Validating account_id: {}", request.account_id);
-    match account::Entity::find_by_id(request.account_id).one(&state.db).await {
-        Ok(Some(_account)) => {
+    let account_currency = match account::Entity::find_by_id(request.account_id).one(&state.db).await {
+        Ok(Some(account)) if account.account_kind.is_virtual() => {
+            warn!("Attempted to import a transaction into virtual account {}", request.account_id);
+            let error_response = ErrorResponse {
+                error: format!(
+                    "Account {} is a {:?} account and cannot receive imported transactions",
+                    request.account_id, account.account_kind
+                ),
+                code: "VIRTUAL_ACCOUNT_NOT_IMPORTABLE".to_string(),
+                success: false,
+            };
+            return Err((StatusCode::BAD_REQUEST, Json(error_response)));
+        }
+        Ok(Some(account)) => {
             debug!("Account with ID {} found", request.account_id);
+            account.currency_code
         }
         Ok(None) => {
             warn!("Attempted to create imported transaction with non-existent account_id: {}", request.account_id);
@@ -218,7 +295,7 @@ pub async fn create_imported_transaction(
             };
             return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)));
         }
-    }
+    };
 
     // Check for duplicate import hash
     trace!("Checking for duplicate import_hash: {}", request.import_hash);
@@ -250,6 +327,35 @@ pub async fn create_imported_transaction(
         }
     }
 
+    // Book bank fees and interest into their system categories unless a category was given
+    let category_id = match (request.category_id, classify_bank_charge(&request.description, request.amount)) {
+        (Some(category_id), _) => Some(category_id),
+        (None, Some(kind)) => match system_category_id(&state.db, kind).await {
+            Ok(category_id) => {
+                debug!("Classified imported transaction '{}' as {:?}", request.description, kind);
+                Some(category_id)
+            }
+            Err(e) => {
+                warn!("Failed to resolve system category for {:?}: {}", kind, e);
+                None
+            }
+        },
+        (None, None) => None,
+    };
+
+    // Split out the original currency amount and conversion fee of foreign payments
+    let foreign_currency = request
+        .raw_data
+        .as_ref()
+        .and_then(|raw_data| {
+            foreign_currency_from_raw_data(
+                raw_data,
+                request.amount,
+                &account_currency,
+                request.amount_locale.unwrap_or_default(),
+            )
+        });
+
     // Create the imported transaction
     let new_imported_transaction = imported_transaction::ActiveModel {
         account_id: Set(request.account_id),
@@ -260,7 +366,10 @@ pub async fn create_imported_transaction(
         raw_data: Set(request.raw_data.map(sea_orm::JsonValue::from)),
         reconciled_transaction_type: Set(None),
         reconciled_transaction_id: Set(None),
-        category_id: Set(request.category_id),
+        category_id: Set(category_id),
+        original_amount: Set(foreign_currency.as_ref().map(|fx| fx.original_amount)),
+        original_currency: Set(foreign_currency.as_ref().map(|fx| fx.original_currency.clone())),
+        fx_fee: Set(foreign_currency.and_then(|fx| fx.fx_fee)),
         ..Default::default()
     };
 
@@ -313,19 +422,23 @@ pub async fn create_imported_transaction(
     tag = "imported-transactions",
     params(ImportedTransactionQuery),
     responses(
-        (status = 200, description = "List of imported transactions", body = ApiResponse<Vec<ImportedTransactionResponse>>),
+        (status = 200, description = "List of imported transactions", body = PaginatedResponse<Vec<ImportedTransactionResponse>>),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
 #[instrument]
 pub async fn get_imported_transactions(
     State(state): State<AppState>,
-    Query(query): Query<ImportedTransactionQuery>,
-) -> Result<Json<ApiResponse<Vec<ImportedTransactionResponse>>>, StatusCode> {
+    access: AccountAccess,
+    Valid(Query(query)): Valid<Query<ImportedTransactionQuery>>,
+) -> Result<Json<PaginatedResponse<Vec<ImportedTransactionResponse>>>, StatusCode> {
     trace!("Entering get_imported_transactions function");
     debug!("Getting imported transactions with query: {:?}", query);
 
-    let mut query_builder = imported_transaction::Entity::find();
+    let page = query.page.unwrap_or(1);
+    let limit = query.limit.unwrap_or(50);
+
+    let mut query_builder = access.filter(imported_transaction::Entity::find(), [imported_transaction::Column::AccountId]);
 
     // Apply filters
     if let Some(account_id) = query.account_id {
@@ -348,33 +461,43 @@ pub async fn get_imported_transactions(
         query_builder = query_builder.filter(imported_transaction::Column::Date.lte(date_to));
     }
 
+    let database_error = |e: DbErr| {
+        error!("Failed to retrieve imported transactions: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    };
+
     trace!("Executing database query for imported transactions");
-    match query_builder.all(&state.db).await {
-        Ok(imported_transactions) => {
-            info!("Successfully retrieved {} imported transactions", imported_transactions.len());
-            
-            let mut responses = Vec::new();
-            for transaction in imported_transactions {
-                match ImportedTransactionResponse::with_tags(transaction.clone(), &state.db).await {
-                    Ok(response) => responses.push(response),
-                    Err(tag_error) => {
-                        warn!("Failed to fetch tags for imported transaction {}: {}", transaction.id, tag_error);
-                        responses.push(ImportedTransactionResponse::from(transaction));
-                    }
-                }
+    let paginator = query_builder
+        .order_by_desc(imported_transaction::Column::Date)
+        .order_by_desc(imported_transaction::Column::Id)
+        .paginate(&state.db, limit);
+    let total_count = paginator.num_items().await.map_err(database_error)?;
+    let imported_transactions = paginator.fetch_page(page - 1).await.map_err(database_error)?;
+    info!("Successfully retrieved {} of {} imported transactions", imported_transactions.len(), total_count);
+
+    let mut responses = Vec::new();
+    for transaction in imported_transactions {
+        match ImportedTransactionResponse::with_tags(transaction.clone(), &state.db).await {
+            Ok(response) => responses.push(response),
+            Err(tag_error) => {
+                warn!("Failed to fetch tags for imported transaction {}: {}", transaction.id, tag_error);
+                responses.push(ImportedTransactionResponse::from(transaction));
             }
-            
-            Ok(Json(ApiResponse {
-                data: responses,
-                message: "Imported transactions retrieved successfully".to_string(),
-                success: true,
-            }))
-        }
-        Err(e) => {
-            error!("Failed to retrieve imported transactions: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
+
+    let enricher = load_merchant_enricher(&state.db).await;
+    let responses: Vec<ImportedTransactionResponse> = responses
+        .into_iter()
+        .map(|response| response.with_merchant(&enricher))
+        .collect();
+
+    Ok(Json(PaginatedResponse {
+        data: responses,
+        pagination: Pagination::new(total_count, page, limit),
+        message: "Imported transactions retrieved successfully".to_string(),
+        success: true,
+    }))
 }
 
 /// Get imported transactions for a specific account
@@ -387,6 +510,7 @@ pub async fn get_imported_transactions(
     ),
     responses(
         (status = 200, description = "List of imported transactions for the account", body = ApiResponse<Vec<ImportedTransactionResponse>>),
+        (status = 404, description = "Account not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
@@ -394,10 +518,16 @@ pub async fn get_imported_transactions(
 pub async fn get_account_imported_transactions(
     Path(account_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
 ) -> Result<Json<ApiResponse<Vec<ImportedTransactionResponse>>>, StatusCode> {
     trace!("Entering get_account_imported_transactions function");
     debug!("Getting imported transactions for account_id: {}", account_id);
 
+    if !access.can_access(account_id) {
+        warn!("Account {} is not accessible to user {:?}", account_id, access.user_id());
+        return Err(StatusCode::NOT_FOUND);
+    }
+
     match imported_transaction::Entity::find()
         .filter(imported_transaction::Column::AccountId.eq(account_id))
         .all(&state.db)
@@ -417,6 +547,12 @@ pub async fn get_account_imported_transactions(
                     }
                 }
             }
+
+            let enricher = load_merchant_enricher(&state.db).await;
+            let responses: Vec<ImportedTransactionResponse> = responses
+                .into_iter()
+                .map(|response| response.with_merchant(&enricher))
+                .collect();
             
             Ok(Json(ApiResponse {
                 data: responses,
@@ -449,18 +585,24 @@ pub async fn get_account_imported_transactions(
 pub async fn get_imported_transaction(
     Path(transaction_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
 ) -> Result<Json<ApiResponse<ImportedTransactionResponse>>, StatusCode> {
     trace!("Entering get_imported_transaction function");
     debug!("Getting imported transaction with id: {}", transaction_id);
 
     match imported_transaction::Entity::find_by_id(transaction_id).one(&state.db).await {
+        Ok(Some(imported_transaction)) if !access.can_access(imported_transaction.account_id) => {
+            warn!("Imported transaction {} is not accessible to user {:?}", transaction_id, access.user_id());
+            Err(StatusCode::NOT_FOUND)
+        }
         Ok(Some(imported_transaction)) => {
             info!("Successfully retrieved imported transaction with id: {}", transaction_id);
+            let enricher = load_merchant_enricher(&state.db).await;
             
             match ImportedTransactionResponse::with_tags(imported_transaction.clone(), &state.db).await {
                 Ok(response) => {
                     Ok(Json(ApiResponse {
-                        data: response,
+                        data: response.with_merchant(&enricher),
                         message: "Imported transaction retrieved successfully".to_string(),
                         success: true,
                     }))
@@ -469,7 +611,7 @@ pub async fn get_imported_transaction(
                     warn!("Failed to fetch tags for imported transaction {}: {}", imported_transaction.id, tag_error);
                     let response = ImportedTransactionResponse::from(imported_transaction);
                     Ok(Json(ApiResponse {
-                        data: response,
+                        data: response.with_merchant(&enricher),
                         message: "Imported transaction retrieved successfully".to_string(),
                         success: true,
                     }))
@@ -498,6 +640,7 @@ pub async fn get_imported_transaction(
     request_body = UpdateImportedTransactionRequest,
     responses(
         (status = 200, description = "Imported transaction updated successfully", body = ApiResponse<ImportedTransactionResponse>),
+        (status = 403, description = "The transaction's account is shared read-only", body = ErrorResponse),
         (status = 404, description = "Imported transaction not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
@@ -506,6 +649,7 @@ pub async fn get_imported_transaction(
 pub async fn update_imported_transaction(
     Path(transaction_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
     Json(request): Json<UpdateImportedTransactionRequest>,
 ) -> Result<Json<ApiResponse<ImportedTransactionResponse>>, StatusCode> {
     trace!("Entering update_imported_transaction function");
@@ -523,6 +667,20 @@ pub async fn update_imported_transaction(
             return Err(StatusCode::INTERNAL_SERVER_ERROR);
         }
     };
+    ensure_imported_writable(&access, &existing_imported_transaction)?;
+
+    // Inputs of the foreign currency split, if either of them changes
+    let account_id = existing_imported_transaction.account_id;
+    let amount_locale = request.amount_locale.unwrap_or_default();
+    let fx_inputs = (request.amount.is_some() || request.raw_data.is_some()).then(|| {
+        (
+            request.amount.unwrap_or(existing_imported_transaction.amount),
+            request
+                .raw_data
+                .clone()
+                .or_else(|| existing_imported_transaction.raw_data.clone()),
+        )
+    });
 
     // Create an active model for updating
     let mut imported_transaction_update: imported_transaction::ActiveModel = existing_imported_transaction.into();
@@ -544,6 +702,30 @@ pub async fn update_imported_transaction(
         imported_transaction_update.category_id = Set(Some(category_id));
     }
 
+    // Re-derive the foreign currency split when the data it depends on changed
+    if let Some((amount, raw_data)) = fx_inputs {
+        match account::Entity::find_by_id(account_id).one(&state.db).await {
+            Ok(Some(account)) => {
+                let foreign_currency = raw_data
+                    .as_ref()
+                    .and_then(|raw_data| {
+                        foreign_currency_from_raw_data(raw_data, amount, &account.currency_code, amount_locale)
+                    });
+                imported_transaction_update.original_amount = Set(foreign_currency.as_ref().map(|fx| fx.original_amount));
+                imported_transaction_update.original_currency =
+                    Set(foreign_currency.as_ref().map(|fx| fx.original_currency.clone()));
+                imported_transaction_update.fx_fee = Set(foreign_currency.and_then(|fx| fx.fx_fee));
+            }
+            Ok(None) => {
+                warn!("Account {} of imported transaction {} not found", account_id, transaction_id);
+            }
+            Err(e) => {
+                error!("Failed to load account {} for imported transaction {}: {}", account_id, transaction_id, e);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        }
+    }
+
     trace!("Attempting to update imported transaction in database");
     match imported_transaction_update.update(&state.db).await {
         Ok(updated_imported_transaction) => {
@@ -585,6 +767,7 @@ pub async fn update_imported_transaction(
     ),
     responses(
         (status = 200, description = "Imported transaction deleted successfully", body = ApiResponse<String>),
+        (status = 403, description = "The transaction's account is shared read-only", body = ErrorResponse),
         (status = 404, description = "Imported transaction not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
@@ -593,12 +776,13 @@ pub async fn update_imported_transaction(
 pub async fn delete_imported_transaction(
     Path(transaction_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
 ) -> Result<Json<ApiResponse<String>>, StatusCode> {
     trace!("Entering delete_imported_transaction function");
     debug!("Deleting imported transaction with id: {}", transaction_id);
 
     // First, check if the imported transaction exists
-    let _existing_imported_transaction = match imported_transaction::Entity::find_by_id(transaction_id).one(&state.db).await {
+    let existing_imported_transaction = match imported_transaction::Entity::find_by_id(transaction_id).one(&state.db).await {
         Ok(Some(imported_transaction)) => imported_transaction,
         Ok(None) => {
             warn!("Imported transaction with id {} not found for deletion", transaction_id);
@@ -609,6 +793,7 @@ pub async fn delete_imported_transaction(
             return Err(StatusCode::INTERNAL_SERVER_ERROR);
         }
     };
+    ensure_imported_writable(&access, &existing_imported_transaction)?;
 
     // Delete the imported transaction
     trace!("Attempting to delete imported transaction from database");
@@ -628,6 +813,33 @@ pub async fn delete_imported_transaction(
     }
 }
 
+/// Rejects with 404 imported transactions of accounts the caller can't see and with 403
+/// those of accounts only shared read-only with them.
+fn ensure_imported_writable(
+    access: &AccountAccess,
+    transaction: &imported_transaction::Model,
+) -> Result<(), StatusCode> {
+    if !access.can_access(transaction.account_id) {
+        warn!("Imported transaction {} is not accessible to user {:?}", transaction.id, access.user_id());
+        return Err(StatusCode::NOT_FOUND);
+    }
+    if !access.can_write(transaction.account_id) {
+        warn!("Imported transaction {} is read-only for user {:?}", transaction.id, access.user_id());
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(())
+}
+
+fn parse_reconciled_transaction_type(value: &str) -> Option<imported_transaction::ReconciledTransactionEntityType> {
+    match value {
+        "OneOff" => Some(imported_transaction::ReconciledTransactionEntityType::OneOff),
+        "Recurring" => Some(imported_transaction::ReconciledTransactionEntityType::Recurring),
+        "RecurringIncome" => Some(imported_transaction::ReconciledTransactionEntityType::RecurringIncome),
+        "RecurringInstance" => Some(imported_transaction::ReconciledTransactionEntityType::RecurringInstance),
+        _ => None,
+    }
+}
+
 /// Reconcile an imported transaction with a real transaction
 #[utoipa::path(
     post,
@@ -639,6 +851,7 @@ pub async fn delete_imported_transaction(
     request_body = ReconcileImportedTransactionRequest,
     responses(
         (status = 200, description = "Imported transaction reconciled successfully", body = ApiResponse<ImportedTransactionResponse>),
+        (status = 403, description = "The transaction's account is shared read-only", body = ErrorResponse),
         (status = 404, description = "Imported transaction not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
@@ -647,6 +860,7 @@ pub async fn delete_imported_transaction(
 pub async fn reconcile_imported_transaction(
     Path(transaction_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
     Json(request): Json<ReconcileImportedTransactionRequest>,
 ) -> Result<Json<ApiResponse<ImportedTransactionResponse>>, StatusCode> {
     trace!("Entering reconcile_imported_transaction function");
@@ -665,17 +879,12 @@ pub async fn reconcile_imported_transaction(
             return Err(StatusCode::INTERNAL_SERVER_ERROR);
         }
     };
+    ensure_imported_writable(&access, &existing_imported_transaction)?;
 
     // Convert string to enum
-    let transaction_type = match request.transaction_type.as_str() {
-        "OneOff" => imported_transaction::ReconciledTransactionEntityType::OneOff,
-        "Recurring" => imported_transaction::ReconciledTransactionEntityType::Recurring,
-        "RecurringIncome" => imported_transaction::ReconciledTransactionEntityType::RecurringIncome,
-        "RecurringInstance" => imported_transaction::ReconciledTransactionEntityType::RecurringInstance,
-        _ => {
-            warn!("Invalid transaction type: {}", request.transaction_type);
-            return Err(StatusCode::BAD_REQUEST);
-        }
+    let Some(transaction_type) = parse_reconciled_transaction_type(&request.transaction_type) else {
+        warn!("Invalid transaction type: {}", request.transaction_type);
+        return Err(StatusCode::BAD_REQUEST);
     };
 
     // Update the reconciliation fields
@@ -714,6 +923,167 @@ pub async fn reconcile_imported_transaction(
     }
 }
 
+/// Request body for reconciling several imported transactions at once
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct BulkReconcileRequest {
+    pub items: Vec<BulkReconcileItem>,
+}
+
+/// One imported transaction and the transaction to reconcile it with
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct BulkReconcileItem {
+    pub imported_id: i32,
+    /// Type of transaction to reconcile with ("OneOff", "Recurring", "RecurringIncome", "RecurringInstance")
+    pub transaction_type: String,
+    pub transaction_id: i32,
+}
+
+/// Outcome of one item of a bulk reconciliation
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BulkReconcileItemResult {
+    pub imported_id: i32,
+    pub success: bool,
+    /// Why the item was not reconciled
+    pub error: Option<String>,
+}
+
+/// Outcome of a bulk reconciliation, with one result per item in request order
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BulkReconcileResponse {
+    pub reconciled_count: usize,
+    pub failed_count: usize,
+    pub results: Vec<BulkReconcileItemResult>,
+}
+
+/// Reconciles one item, returning why it was rejected if it could not be reconciled.
+///
+/// Imported transactions and targets the caller can't see count as missing.
+async fn reconcile_item<C: ConnectionTrait>(
+    db: &C,
+    access: &AccountAccess,
+    item: &BulkReconcileItem,
+) -> Result<Option<String>, DbErr> {
+    use imported_transaction::ReconciledTransactionEntityType as EntityType;
+
+    let Some(transaction_type) = parse_reconciled_transaction_type(&item.transaction_type) else {
+        return Ok(Some(format!("Invalid transaction type: {}", item.transaction_type)));
+    };
+    let existing = imported_transaction::Entity::find_by_id(item.imported_id).one(db).await?;
+    let Some(existing) = existing.filter(|existing| access.can_access(existing.account_id)) else {
+        return Ok(Some(format!("Imported transaction {} not found", item.imported_id)));
+    };
+    if !access.can_write(existing.account_id) {
+        return Ok(Some(format!("Account {} is shared with you read-only", existing.account_id)));
+    }
+
+    let target_visible = match transaction_type {
+        EntityType::OneOff => one_off_transaction::Entity::find_by_id(item.transaction_id)
+            .one(db)
+            .await?
+            .is_some_and(|target| access.can_access_any(target.target_account_id, target.source_account_id)),
+        EntityType::Recurring => recurring_transaction::Entity::find_by_id(item.transaction_id)
+            .one(db)
+            .await?
+            .is_some_and(|target| access.can_access_any(target.target_account_id, target.source_account_id)),
+        EntityType::RecurringIncome => recurring_income::Entity::find_by_id(item.transaction_id)
+            .one(db)
+            .await?
+            .is_some_and(|target| access.can_access(target.target_account_id)),
+        EntityType::RecurringInstance => {
+            match recurring_transaction_instance::Entity::find_by_id(item.transaction_id).one(db).await? {
+                Some(instance) => recurring_transaction::Entity::find_by_id(instance.recurring_transaction_id)
+                    .one(db)
+                    .await?
+                    .is_some_and(|rule| access.can_access_any(rule.target_account_id, rule.source_account_id)),
+                None => false,
+            }
+        }
+    };
+    if !target_visible {
+        return Ok(Some(format!("{} transaction {} not found", item.transaction_type, item.transaction_id)));
+    }
+
+    let mut update: imported_transaction::ActiveModel = existing.into();
+    update.reconciled_transaction_type = Set(Some(transaction_type));
+    update.reconciled_transaction_id = Set(Some(item.transaction_id));
+    update.update(db).await?;
+    Ok(None)
+}
+
+/// Reconcile several imported transactions in one request
+///
+/// All items are processed in a single database transaction. Items that can't be
+/// reconciled (unknown or inaccessible imported transaction, transaction type or target,
+/// an account shared read-only, or an imported transaction listed twice) are reported in
+/// their result and don't affect the others.
+#[utoipa::path(
+    post,
+    path = "/api/v1/imported-transactions/bulk-reconcile",
+    tag = "imported-transactions",
+    request_body = BulkReconcileRequest,
+    responses(
+        (status = 200, description = "Items processed", body = ApiResponse<BulkReconcileResponse>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn bulk_reconcile_imported_transactions(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Json(request): Json<BulkReconcileRequest>,
+) -> Result<Json<ApiResponse<BulkReconcileResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Bulk reconciling {} imported transactions", request.items.len());
+
+    let database_error = |e: DbErr| {
+        error!("Database error during bulk reconciliation: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Failed to reconcile imported transactions: {}", e),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    };
+
+    let txn = state.db.begin().await.map_err(database_error)?;
+    let mut seen = HashSet::new();
+    let mut results = Vec::with_capacity(request.items.len());
+
+    for item in &request.items {
+        let failure = if seen.insert(item.imported_id) {
+            reconcile_item(&txn, &access, item).await.map_err(database_error)?
+        } else {
+            Some(format!("Imported transaction {} is listed more than once", item.imported_id))
+        };
+
+        if let Some(error) = &failure {
+            warn!("Not reconciling imported transaction {}: {}", item.imported_id, error);
+        }
+        results.push(BulkReconcileItemResult {
+            imported_id: item.imported_id,
+            success: failure.is_none(),
+            error: failure,
+        });
+    }
+
+    txn.commit().await.map_err(database_error)?;
+
+    let reconciled_count = results.iter().filter(|result| result.success).count();
+    let failed_count = results.len() - reconciled_count;
+    info!("Bulk reconciliation completed: {} reconciled, {} failed", reconciled_count, failed_count);
+
+    Ok(Json(ApiResponse {
+        data: BulkReconcileResponse {
+            reconciled_count,
+            failed_count,
+            results,
+        },
+        message: format!("Reconciled {} of {} imported transactions", reconciled_count, request.items.len()),
+        success: true,
+    }))
+}
+
 /// Clear reconciliation for an imported transaction
 #[utoipa::path(
     delete,
@@ -724,6 +1094,7 @@ pub async fn reconcile_imported_transaction(
     ),
     responses(
         (status = 200, description = "Reconciliation cleared successfully", body = ApiResponse<ImportedTransactionResponse>),
+        (status = 403, description = "The transaction's account is shared read-only", body = ErrorResponse),
         (status = 404, description = "Imported transaction not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
@@ -732,6 +1103,7 @@ pub async fn reconcile_imported_transaction(
 pub async fn clear_imported_transaction_reconciliation(
     Path(transaction_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
 ) -> Result<Json<ApiResponse<ImportedTransactionResponse>>, StatusCode> {
     trace!("Entering clear_imported_transaction_reconciliation function");
     debug!("Clearing reconciliation for imported transaction {}", transaction_id);
@@ -748,6 +1120,7 @@ pub async fn clear_imported_transaction_reconciliation(
             return Err(StatusCode::INTERNAL_SERVER_ERROR);
         }
     };
+    ensure_imported_writable(&access, &existing_imported_transaction)?;
 
     // Clear the reconciliation fields
     let mut imported_transaction_update: imported_transaction::ActiveModel = existing_imported_transaction.into();
@@ -784,3 +1157,87 @@ pub async fn clear_imported_transaction_reconciliation(
         }
     }
 }
+
+/// Result of classifying bank fees and interest among imported transactions
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BankChargeClassificationResponse {
+    /// Number of rows that were assigned a system category
+    pub classified: usize,
+    pub fees: usize,
+    pub interest_charged: usize,
+    pub interest_earned: usize,
+}
+
+/// Classify bank fees and interest among uncategorized imported transactions
+///
+/// New imports are classified automatically; this endpoint applies the same rules
+/// to rows imported before, booking them into the system categories
+/// "Bank Fees" and "Interest Charges" (both under "Bank Costs") and "Interest Income".
+/// Only rows of accounts the caller may change are classified.
+#[utoipa::path(
+    post,
+    path = "/api/v1/imported-transactions/classify-bank-charges",
+    tag = "imported-transactions",
+    responses(
+        (status = 200, description = "Imported transactions classified successfully", body = ApiResponse<BankChargeClassificationResponse>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn classify_bank_charges(
+    State(state): State<AppState>,
+    access: AccountAccess,
+) -> Result<Json<ApiResponse<BankChargeClassificationResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    let database_error = |e: DbErr| {
+        error!("Database error while classifying bank charges: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to classify imported transactions".to_string(),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    };
+
+    let uncategorized = access
+        .filter(imported_transaction::Entity::find(), [imported_transaction::Column::AccountId])
+        .filter(imported_transaction::Column::CategoryId.is_null())
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+    debug!("Classifying {} uncategorized imported transactions", uncategorized.len());
+
+    let mut result = BankChargeClassificationResponse {
+        classified: 0,
+        fees: 0,
+        interest_charged: 0,
+        interest_earned: 0,
+    };
+    for row in uncategorized {
+        if !access.can_write(row.account_id) {
+            continue;
+        }
+        let Some(kind) = classify_bank_charge(&row.description, row.amount) else {
+            continue;
+        };
+        let category_id = system_category_id(&state.db, kind).await.map_err(database_error)?;
+        let mut update: imported_transaction::ActiveModel = row.into();
+        update.category_id = Set(Some(category_id));
+        update.update(&state.db).await.map_err(database_error)?;
+
+        result.classified += 1;
+        match kind {
+            BankChargeKind::Fee => result.fees += 1,
+            BankChargeKind::InterestCharged => result.interest_charged += 1,
+            BankChargeKind::InterestEarned => result.interest_earned += 1,
+        }
+    }
+
+    info!("Classified {} imported transactions as bank charges", result.classified);
+    Ok(Json(ApiResponse {
+        data: result,
+        message: "Imported transactions classified successfully".to_string(),
+        success: true,
+    }))
+}
@@ -0,0 +1,196 @@
+use super::one_offs::TransactionResponse;
+use crate::handlers::double_entry::ensure_double_entry;
+use crate::handlers::periods::{ensure_dates_unlocked, PeriodLockOverride};
+use crate::helpers::auth::AuthenticatedUser;
+use crate::helpers::access::{account_read_only, AccountAccess};
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use model::entities::one_off_transaction;
+use rust_decimal::Decimal;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, Set};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{debug, error, info, instrument, warn};
+use utoipa::ToSchema;
+
+/// Request body for attaching a leg (fee, delivery charge, cashback) to a transaction
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateTransactionLegRequest {
+    pub name: String,
+    pub description: Option<String>,
+    /// Amount of the leg (negative for fees, positive for cashback)
+    pub amount: Decimal,
+    /// Category the leg is counted under, independent of the parent's category
+    pub category_id: Option<i32>,
+}
+
+/// A leg attached to a transaction
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TransactionLegResponse {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub amount: Decimal,
+    pub category_id: Option<i32>,
+}
+
+impl From<one_off_transaction::Model> for TransactionLegResponse {
+    fn from(model: one_off_transaction::Model) -> Self {
+        Self {
+            id: model.id,
+            name: model.name,
+            description: model.description,
+            amount: model.amount,
+            category_id: model.category_id,
+        }
+    }
+}
+
+/// Load the legs of the given transactions, grouped by parent ID.
+pub async fn load_legs(
+    db: &DatabaseConnection,
+    parent_ids: &[i32],
+) -> Result<HashMap<i32, Vec<TransactionLegResponse>>, DbErr> {
+    if parent_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let legs = one_off_transaction::Entity::find()
+        .filter(one_off_transaction::Column::ParentTransactionId.is_in(parent_ids.iter().copied()))
+        .order_by_asc(one_off_transaction::Column::Id)
+        .all(db)
+        .await?;
+
+    let mut grouped: HashMap<i32, Vec<TransactionLegResponse>> = HashMap::new();
+    for leg in legs {
+        if let Some(parent_id) = leg.parent_transaction_id {
+            grouped.entry(parent_id).or_default().push(TransactionLegResponse::from(leg));
+        }
+    }
+    Ok(grouped)
+}
+
+fn leg_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// Attach a leg to a transaction
+///
+/// Legs are stored as one-off transactions on the parent's account and date, so they
+/// count towards balances and category statistics under their own category, while
+/// transaction listings show them nested under the parent with a combined `total_amount`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/transactions/{transaction_id}/legs",
+    tag = "transactions",
+    params(
+        ("transaction_id" = i32, Path, description = "Parent transaction ID"),
+        PeriodLockOverride,
+    ),
+    request_body = CreateTransactionLegRequest,
+    responses(
+        (status = 201, description = "Leg attached; returns the parent with all legs", body = ApiResponse<TransactionResponse>),
+        (status = 400, description = "Parent is itself a leg", body = ErrorResponse),
+        (status = 403, description = "The parent's account is shared read-only, or admin_override was passed by a non-administrator", body = ErrorResponse),
+        (status = 404, description = "Parent transaction not found", body = ErrorResponse),
+        (status = 409, description = "Parent is locked or dated inside a locked period", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn create_transaction_leg(
+    Path(transaction_id): Path<i32>,
+    State(state): State<AppState>,
+    access: AccountAccess,
+    auth: Option<AuthenticatedUser>,
+    Query(lock_override): Query<PeriodLockOverride>,
+    Json(request): Json<CreateTransactionLegRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<TransactionResponse>>), (StatusCode, Json<ErrorResponse>)> {
+    debug!("Attaching leg '{}' to transaction {}", request.name, transaction_id);
+
+    let database_error = |e: DbErr| {
+        error!("Database error while attaching leg to transaction {}: {}", transaction_id, e);
+        leg_error(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", "Failed to attach leg".to_string())
+    };
+
+    let parent = one_off_transaction::Entity::find_by_id(transaction_id)
+        .one(&state.db)
+        .await
+        .map_err(database_error)?
+        .filter(|parent| access.can_access_any(parent.target_account_id, parent.source_account_id))
+        .ok_or_else(|| {
+            warn!("Transaction {} not found for leg", transaction_id);
+            leg_error(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                format!("Transaction with id {} not found", transaction_id),
+            )
+        })?;
+
+    // The leg is booked on the parent's account, so that one has to be writable
+    if !access.can_write(parent.target_account_id) {
+        return Err(account_read_only(parent.target_account_id));
+    }
+    if parent.parent_transaction_id.is_some() {
+        warn!("Transaction {} is a leg itself, refusing nested leg", transaction_id);
+        return Err(leg_error(
+            StatusCode::BAD_REQUEST,
+            "NESTED_LEG",
+            "Legs can only be attached to top-level transactions".to_string(),
+        ));
+    }
+    if parent.is_locked {
+        warn!("Transaction {} is reconciled and locked, refusing leg", transaction_id);
+        return Err(leg_error(
+            StatusCode::CONFLICT,
+            "TRANSACTION_LOCKED",
+            "Transaction is reconciled and locked".to_string(),
+        ));
+    }
+    ensure_dates_unlocked(&state.db, auth.as_ref(), &[parent.date], &lock_override).await?;
+    ensure_double_entry(&state, parent.target_account_id, None, request.category_id, false).await?;
+
+    let leg = one_off_transaction::ActiveModel {
+        name: Set(request.name),
+        description: Set(request.description),
+        amount: Set(request.amount),
+        date: Set(parent.date),
+        include_in_statistics: Set(parent.include_in_statistics),
+        target_account_id: Set(parent.target_account_id),
+        source_account_id: Set(None),
+        category_id: Set(request.category_id),
+        ledger_name: Set(None),
+        linked_import_id: Set(None),
+        scenario_id: Set(parent.scenario_id),
+        is_simulated: Set(parent.is_simulated),
+        parent_transaction_id: Set(Some(parent.id)),
+        status: Set(parent.status),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await
+    .map_err(database_error)?;
+    info!("Attached leg {} to transaction {}", leg.id, transaction_id);
+
+    let response = TransactionResponse::with_tags(parent, &state.db)
+        .await
+        .map_err(database_error)?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse {
+            data: response,
+            message: "Transaction leg created successfully".to_string(),
+            success: true,
+        }),
+    ))
+}
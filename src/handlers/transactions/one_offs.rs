@@ -1,18 +1,58 @@
+use super::legs::{load_legs, TransactionLegResponse};
+use super::purchases::validate_purchase_details;
+use crate::helpers::access::{account_forbidden, account_read_only, AccountAccess};
+use crate::helpers::fx::currency_conversion;
+use crate::helpers::geo::{coordinates_from_raw_data, valid_coordinates};
+use crate::handlers::double_entry::{ensure_double_entry, ensure_double_entry_in};
+use crate::handlers::periods::{ensure_dates_unlocked, find_locked_period, PeriodLockOverride};
+use crate::helpers::auth::{ensure_admin, AuthenticatedUser};
+use crate::helpers::sparse_fields::{list_response, paginated_response, FieldSelection};
 use crate::schemas::{ApiResponse, AppState, ErrorResponse};
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    response::Json,
+    response::{Json, Response},
 };
 use chrono::NaiveDate;
-use model::entities::{account, one_off_transaction};
+use common::{
+    BoundingBox, Pagination, TransactionFeature, TransactionFeatureCollection,
+    TransactionFeatureProperties,
+};
+use model::entities::{account, category, imported_transaction, locked_period, one_off_transaction};
 use model::transaction::{Tag, Transaction, TransactionGenerator};
 use rust_decimal::Decimal;
-use sea_orm::{ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Set};
+use sea_orm::{ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, instrument, trace, warn};
 use utoipa::{IntoParams, ToSchema};
 
+/// Whether the money of a transaction has actually moved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+pub enum TransactionStatus {
+    /// The money moved; counted in actual balances
+    Confirmed,
+    /// Expected on its date; counted in forecasts until confirmed
+    Planned,
+}
+
+impl From<one_off_transaction::OneOffStatus> for TransactionStatus {
+    fn from(status: one_off_transaction::OneOffStatus) -> Self {
+        match status {
+            one_off_transaction::OneOffStatus::Confirmed => TransactionStatus::Confirmed,
+            one_off_transaction::OneOffStatus::Planned => TransactionStatus::Planned,
+        }
+    }
+}
+
+impl From<TransactionStatus> for one_off_transaction::OneOffStatus {
+    fn from(status: TransactionStatus) -> Self {
+        match status {
+            TransactionStatus::Confirmed => one_off_transaction::OneOffStatus::Confirmed,
+            TransactionStatus::Planned => one_off_transaction::OneOffStatus::Planned,
+        }
+    }
+}
+
 /// Request body for creating a new one-off transaction
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct CreateTransactionRequest {
@@ -40,6 +80,27 @@ pub struct CreateTransactionRequest {
     pub scenario_id: Option<i32>,
     /// Whether this is a simulated transaction (default: false)
     pub is_simulated: Option<bool>,
+    /// Latitude where the transaction happened (filled from the linked import's raw data if omitted)
+    pub latitude: Option<f64>,
+    /// Longitude where the transaction happened (filled from the linked import's raw data if omitted)
+    pub longitude: Option<f64>,
+    /// `Planned` for money expected to move on `date` (default: `Confirmed`)
+    pub status: Option<TransactionStatus>,
+    /// Amount in the currency the payment was made in, when it differs from the account
+    /// currency; `amount` is then the settled amount in the account currency
+    pub original_amount: Option<Decimal>,
+    /// ISO 4217 code of the currency the payment was made in
+    pub original_currency: Option<String>,
+    /// Account currency units per unit of `original_currency` (derived from both amounts if omitted)
+    pub conversion_rate: Option<Decimal>,
+    /// Whether this is a purchase of goods (default: false)
+    pub is_purchase: Option<bool>,
+    /// Last day the warranty covers the purchased item; purchases only
+    pub warranty_end_date: Option<NaiveDate>,
+    /// Last day the purchased item can be returned; purchases only
+    pub return_deadline: Option<NaiveDate>,
+    /// Document of the target account holding the receipt; purchases only
+    pub receipt_document_id: Option<i32>,
 }
 
 /// Request body for updating a transaction
@@ -65,6 +126,27 @@ pub struct UpdateTransactionRequest {
     pub linked_import_id: Option<String>,
     /// Category ID
     pub category_id: Option<i32>,
+    /// Latitude where the transaction happened
+    pub latitude: Option<f64>,
+    /// Longitude where the transaction happened
+    pub longitude: Option<f64>,
+    /// Amount in the currency the payment was made in
+    pub original_amount: Option<Decimal>,
+    /// ISO 4217 code of the currency the payment was made in
+    pub original_currency: Option<String>,
+    /// Account currency units per unit of `original_currency` (re-derived if omitted while an amount changes)
+    pub conversion_rate: Option<Decimal>,
+    /// Remove the original amount, currency and rate
+    pub clear_original_currency: Option<bool>,
+    /// Whether this is a purchase of goods; `false` also removes the warranty, return
+    /// deadline and receipt
+    pub is_purchase: Option<bool>,
+    /// Last day the warranty covers the purchased item
+    pub warranty_end_date: Option<NaiveDate>,
+    /// Last day the purchased item can be returned
+    pub return_deadline: Option<NaiveDate>,
+    /// Document of the target account holding the receipt
+    pub receipt_document_id: Option<i32>,
 }
 
 /// Tag information for API responses
@@ -102,6 +184,31 @@ pub struct TransactionResponse {
     pub tags: Vec<TagInfo>,
     pub scenario_id: Option<i32>,
     pub is_simulated: bool,
+    /// Reconciliation session the transaction was ticked off in
+    pub reconciliation_session_id: Option<i32>,
+    /// Reconciled transactions are locked against updates and deletes
+    pub is_locked: bool,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// Set when this transaction is a leg of another transaction
+    pub parent_transaction_id: Option<i32>,
+    /// Fee and cashback legs attached to this transaction
+    pub legs: Vec<TransactionLegResponse>,
+    /// Amount including all legs
+    pub total_amount: Decimal,
+    pub status: TransactionStatus,
+    /// Amount in the currency the payment was made in; `amount` is the settled amount
+    pub original_amount: Option<Decimal>,
+    pub original_currency: Option<String>,
+    /// Account currency units per unit of `original_currency`
+    pub conversion_rate: Option<Decimal>,
+    pub is_purchase: bool,
+    /// Last day the warranty covers the purchased item
+    pub warranty_end_date: Option<NaiveDate>,
+    /// Last day the purchased item can be returned
+    pub return_deadline: Option<NaiveDate>,
+    /// Account document holding the receipt
+    pub receipt_document_id: Option<i32>,
 }
 
 impl From<one_off_transaction::Model> for TransactionResponse {
@@ -121,12 +228,27 @@ impl From<one_off_transaction::Model> for TransactionResponse {
             tags: Vec::new(), // Will be populated by with_tags method
             scenario_id: model.scenario_id,
             is_simulated: model.is_simulated,
+            reconciliation_session_id: model.reconciliation_session_id,
+            is_locked: model.is_locked,
+            latitude: model.latitude,
+            longitude: model.longitude,
+            parent_transaction_id: model.parent_transaction_id,
+            legs: Vec::new(), // Will be populated by with_tags method
+            total_amount: model.amount,
+            status: model.status.into(),
+            original_amount: model.original_amount,
+            original_currency: model.original_currency,
+            conversion_rate: model.conversion_rate,
+            is_purchase: model.is_purchase,
+            warranty_end_date: model.warranty_end_date,
+            return_deadline: model.return_deadline,
+            receipt_document_id: model.receipt_document_id,
         }
     }
 }
 
 impl TransactionResponse {
-    /// Create a TransactionResponse with tags fetched from the database
+    /// Create a TransactionResponse with tags and attached legs fetched from the database
     pub async fn with_tags(
         model: one_off_transaction::Model,
         db: &sea_orm::DatabaseConnection,
@@ -134,28 +256,66 @@ impl TransactionResponse {
         // Use the get_tag_for_transaction method from the Transaction trait
         let tags = model.get_tag_for_transaction(db, true).await;
         let tag_infos: Vec<TagInfo> = tags.into_iter().map(TagInfo::from).collect();
+        let legs = load_legs(db, &[model.id]).await?.remove(&model.id).unwrap_or_default();
 
         let mut response = Self::from(model);
         response.tags = tag_infos;
+        response.total_amount += legs.iter().map(|leg| leg.amount).sum::<Decimal>();
+        response.legs = legs;
         Ok(response)
     }
 }
 
+/// Fields loaded with extra queries per transaction.
+const RELATED_FIELDS: &[&str] = &["tags", "legs", "total_amount"];
+
+/// Build responses for a transaction listing.
+///
+/// Tags and legs are only loaded when `fields` asks for them; if they can't be loaded
+/// the transaction is listed without them.
+pub(super) async fn transaction_responses(
+    db: &sea_orm::DatabaseConnection,
+    transactions: Vec<one_off_transaction::Model>,
+    fields: Option<&FieldSelection>,
+) -> Vec<TransactionResponse> {
+    if fields.is_some_and(|fields| !fields.contains_any(RELATED_FIELDS)) {
+        return transactions.into_iter().map(TransactionResponse::from).collect();
+    }
+
+    let mut responses = Vec::with_capacity(transactions.len());
+    for transaction in transactions {
+        match TransactionResponse::with_tags(transaction.clone(), db).await {
+            Ok(response) => responses.push(response),
+            Err(tag_error) => {
+                warn!("Failed to fetch tags for transaction {}: {}", transaction.id, tag_error);
+                responses.push(TransactionResponse::from(transaction));
+            }
+        }
+    }
+    responses
+}
+
 /// Create a new transaction
 #[utoipa::path(
     post,
     path = "/api/v1/transactions",
     tag = "transactions",
+    params(PeriodLockOverride),
     request_body = CreateTransactionRequest,
     responses(
         (status = 201, description = "Transaction created successfully", body = ApiResponse<TransactionResponse>),
         (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "No access to the target or source account, or it is shared read-only, or admin_override was passed by a non-administrator", body = ErrorResponse),
+        (status = 409, description = "Transaction date falls inside a locked period", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
 #[instrument]
 pub async fn create_transaction(
     State(state): State<AppState>,
+    access: AccountAccess,
+    auth: Option<AuthenticatedUser>,
+    Query(lock_override): Query<PeriodLockOverride>,
     Json(request): Json<CreateTransactionRequest>,
 ) -> Result<(StatusCode, Json<ApiResponse<TransactionResponse>>), (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering create_transaction function");
@@ -164,9 +324,10 @@ pub async fn create_transaction(
 
     // Validate that the target account exists
     trace!("Validating target_account_id: {}", request.target_account_id);
-    match account::Entity::find_by_id(request.target_account_id).one(&state.db).await {
-        Ok(Some(_account)) => {
+    let target_account = match account::Entity::find_by_id(request.target_account_id).one(&state.db).await {
+        Ok(Some(account)) => {
             debug!("Target account with ID {} found", request.target_account_id);
+            account
         }
         Ok(None) => {
             warn!("Attempted to create transaction with non-existent target_account_id: {}", request.target_account_id);
@@ -186,7 +347,7 @@ pub async fn create_transaction(
             };
             return Err((StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)));
         }
-    }
+    };
 
     // Validate source account if provided
     if let Some(source_account_id) = request.source_account_id {
@@ -216,6 +377,72 @@ pub async fn create_transaction(
         }
     }
 
+    for account_id in std::iter::once(request.target_account_id).chain(request.source_account_id) {
+        if !access.can_access(account_id) {
+            warn!("User {:?} has no access to account {}", access.user_id(), account_id);
+            return Err(account_forbidden(account_id));
+        }
+        if !access.can_write(account_id) {
+            warn!("Account {} is read-only for user {:?}", account_id, access.user_id());
+            return Err(account_read_only(account_id));
+        }
+    }
+
+    ensure_dates_unlocked(&state.db, auth.as_ref(), &[request.date], &lock_override).await?;
+    ensure_double_entry(
+        &state,
+        request.target_account_id,
+        request.source_account_id,
+        request.category_id,
+        false,
+    )
+    .await?;
+    validate_purchase_details(
+        &state.db,
+        request.target_account_id,
+        request.is_purchase.unwrap_or(false),
+        request.warranty_end_date,
+        request.return_deadline,
+        request.receipt_document_id,
+    )
+    .await?;
+
+    let conversion = currency_conversion(
+        request.amount,
+        request.original_amount,
+        request.original_currency.as_deref(),
+        request.conversion_rate,
+        &target_account.currency_code,
+    )
+    .map_err(|problem| {
+        warn!("Rejected transaction with invalid currency conversion: {}", problem);
+        let error_response = ErrorResponse {
+            error: problem.to_string(),
+            code: "INVALID_CURRENCY_CONVERSION".to_string(),
+            success: false,
+        };
+        (StatusCode::BAD_REQUEST, Json(error_response))
+    })?;
+
+    let coordinates = match (request.latitude, request.longitude) {
+        (Some(latitude), Some(longitude)) if valid_coordinates(latitude, longitude) => {
+            Some((latitude, longitude))
+        }
+        (None, None) => match &request.linked_import_id {
+            Some(import_hash) => linked_import_coordinates(&state.db, import_hash).await,
+            None => None,
+        },
+        _ => {
+            warn!("Rejected transaction with invalid coordinates: {:?}, {:?}", request.latitude, request.longitude);
+            let error_response = ErrorResponse {
+                error: "latitude and longitude must both be given and within WGS84 range".to_string(),
+                code: "INVALID_COORDINATES".to_string(),
+                success: false,
+            };
+            return Err((StatusCode::BAD_REQUEST, Json(error_response)));
+        }
+    };
+
     let new_transaction = one_off_transaction::ActiveModel {
         name: Set(request.name.clone()),
         description: Set(request.description.clone()),
@@ -229,6 +456,16 @@ pub async fn create_transaction(
         category_id: Set(request.category_id),
         scenario_id: Set(request.scenario_id),
         is_simulated: Set(request.is_simulated.unwrap_or(false)),
+        latitude: Set(coordinates.map(|(latitude, _)| latitude)),
+        longitude: Set(coordinates.map(|(_, longitude)| longitude)),
+        status: Set(request.status.unwrap_or(TransactionStatus::Confirmed).into()),
+        original_amount: Set(conversion.as_ref().map(|conversion| conversion.original_amount)),
+        original_currency: Set(conversion.as_ref().map(|conversion| conversion.original_currency.clone())),
+        conversion_rate: Set(conversion.as_ref().map(|conversion| conversion.conversion_rate)),
+        is_purchase: Set(request.is_purchase.unwrap_or(false)),
+        warranty_end_date: Set(request.warranty_end_date),
+        return_deadline: Set(request.return_deadline),
+        receipt_document_id: Set(request.receipt_document_id),
         ..Default::default()
     };
 
@@ -312,6 +549,14 @@ pub struct TransactionQuery {
     pub year: Option<i32>,
     /// Filter by month (1-12)
     pub month: Option<u32>,
+    /// Only return transactions belonging to this scenario
+    pub scenario_id: Option<i32>,
+    /// Filter by simulated flag (`false` returns baseline data only)
+    pub is_simulated: Option<bool>,
+    /// Filter by status, e.g. `Planned` for transactions still waiting for confirmation
+    pub status: Option<TransactionStatus>,
+    /// Comma-separated fields to return per transaction, e.g. `id,date,amount,name` (default: all)
+    pub fields: Option<String>,
 }
 
 /// Get all transactions
@@ -321,7 +566,7 @@ pub struct TransactionQuery {
     tag = "transactions",
     params(TransactionQuery),
     responses(
-        (status = 200, description = "Transactions retrieved successfully", body = ApiResponse<Vec<TransactionResponse>>),
+        (status = 200, description = "Transactions retrieved successfully", body = PaginatedResponse<Vec<TransactionResponse>>),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
@@ -329,18 +574,26 @@ pub struct TransactionQuery {
 pub async fn get_transactions(
     Query(query): Query<TransactionQuery>,
     State(state): State<AppState>,
-) -> Result<Json<ApiResponse<Vec<TransactionResponse>>>, StatusCode> {
+    access: AccountAccess,
+) -> Result<Response, StatusCode> {
     trace!("Entering get_transactions function");
 
     let page = query.page.unwrap_or(1);
     let limit = query.limit.unwrap_or(50);
+    let fields = FieldSelection::parse(query.fields.as_deref());
 
     debug!("Fetching transactions - page: {}, limit: {}", page, limit);
 
-    let mut query_builder = one_off_transaction::Entity::find();
+    let mut query_builder = access.filter(
+        one_off_transaction::Entity::find(),
+        [one_off_transaction::Column::TargetAccountId, one_off_transaction::Column::SourceAccountId],
+    );
 
     if let Some(category_id) = query.category_id {
         query_builder = query_builder.filter(one_off_transaction::Column::CategoryId.eq(category_id));
+    } else {
+        // Legs are nested under their parent unless filtering by the leg's own category
+        query_builder = query_builder.filter(one_off_transaction::Column::ParentTransactionId.is_null());
     }
     if let Some(target_account_id) = query.target_account_id {
         query_builder = query_builder.filter(one_off_transaction::Column::TargetAccountId.eq(target_account_id));
@@ -348,6 +601,16 @@ pub async fn get_transactions(
     if let Some(source_account_id) = query.source_account_id {
         query_builder = query_builder.filter(one_off_transaction::Column::SourceAccountId.eq(source_account_id));
     }
+    if let Some(scenario_id) = query.scenario_id {
+        query_builder = query_builder.filter(one_off_transaction::Column::ScenarioId.eq(scenario_id));
+    }
+    if let Some(is_simulated) = query.is_simulated {
+        query_builder = query_builder.filter(one_off_transaction::Column::IsSimulated.eq(is_simulated));
+    }
+    if let Some(status) = query.status {
+        let status: one_off_transaction::OneOffStatus = status.into();
+        query_builder = query_builder.filter(one_off_transaction::Column::Status.eq(status));
+    }
     if let (Some(year), Some(month)) = (query.year, query.month) {
         let start = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
         let end = if month == 12 {
@@ -360,34 +623,31 @@ pub async fn get_transactions(
             .filter(one_off_transaction::Column::Date.lt(end));
     }
 
-    match query_builder
+    let paginator = query_builder
         .order_by_desc(one_off_transaction::Column::Date)
-        .paginate(&state.db, limit)
-        .fetch_page(page - 1)
-        .await
-    {
+        .paginate(&state.db, limit);
+    let total_count = match paginator.num_items().await {
+        Ok(total_count) => total_count,
+        Err(db_error) => {
+            error!("Failed to count transactions: {}", db_error);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    match paginator.fetch_page(page - 1).await {
         Ok(transactions) => {
             let transaction_count = transactions.len();
-            debug!("Retrieved {} transactions from database", transaction_count);
+            debug!("Retrieved {} of {} transactions from database", transaction_count, total_count);
 
-            let mut transaction_responses = Vec::new();
-            for transaction in transactions {
-                match TransactionResponse::with_tags(transaction.clone(), &state.db).await {
-                    Ok(response) => transaction_responses.push(response),
-                    Err(tag_error) => {
-                        warn!("Failed to fetch tags for transaction {}: {}", transaction.id, tag_error);
-                        transaction_responses.push(TransactionResponse::from(transaction));
-                    }
-                }
-            }
+            let responses = transaction_responses(&state.db, transactions, fields.as_ref()).await;
 
             info!("Successfully retrieved {} transactions", transaction_count);
-            let response = ApiResponse {
-                data: transaction_responses,
-                message: "Transactions retrieved successfully".to_string(),
-                success: true,
-            };
-            Ok(Json(response))
+            Ok(paginated_response(
+                responses,
+                fields.as_ref(),
+                Pagination::new(total_count, page, limit),
+                "Transactions retrieved successfully".to_string(),
+            ))
         }
         Err(db_error) => {
             error!("Failed to retrieve transactions from database: {}", db_error);
@@ -396,6 +656,13 @@ pub async fn get_transactions(
     }
 }
 
+/// Query parameters for listing the transactions of an account
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AccountTransactionsQuery {
+    /// Comma-separated fields to return per transaction, e.g. `id,date,amount,name` (default: all)
+    pub fields: Option<String>,
+}
+
 /// Get transactions for a specific account
 #[utoipa::path(
     get,
@@ -403,20 +670,29 @@ pub async fn get_transactions(
     tag = "transactions",
     params(
         ("account_id" = i32, Path, description = "Account ID"),
+        AccountTransactionsQuery,
     ),
     responses(
         (status = 200, description = "Account transactions retrieved successfully", body = ApiResponse<Vec<TransactionResponse>>),
+        (status = 404, description = "Account not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
 #[instrument]
 pub async fn get_account_transactions(
     Path(account_id): Path<i32>,
+    Query(query): Query<AccountTransactionsQuery>,
     State(state): State<AppState>,
-) -> Result<Json<ApiResponse<Vec<TransactionResponse>>>, StatusCode> {
+    access: AccountAccess,
+) -> Result<Response, StatusCode> {
     trace!("Entering get_account_transactions function for account_id: {}", account_id);
     debug!("Fetching transactions for account ID: {}", account_id);
 
+    if !access.can_access(account_id) {
+        warn!("Account {} is not accessible to user {:?}", account_id, access.user_id());
+        return Err(StatusCode::NOT_FOUND);
+    }
+
     use sea_orm::{ColumnTrait, Condition, QueryFilter};
 
     // Find transactions where the account is either target or source
@@ -427,6 +703,7 @@ pub async fn get_account_transactions(
     trace!("Executing query to find transactions for account {}", account_id);
     match one_off_transaction::Entity::find()
         .filter(condition)
+        .filter(one_off_transaction::Column::ParentTransactionId.is_null())
         .all(&state.db)
         .await
     {
@@ -434,6 +711,120 @@ pub async fn get_account_transactions(
             let transaction_count = transactions.len();
             debug!("Retrieved {} transactions for account ID: {}", transaction_count, account_id);
 
+            let fields = FieldSelection::parse(query.fields.as_deref());
+            let responses = transaction_responses(&state.db, transactions, fields.as_ref()).await;
+
+            info!("Successfully retrieved {} transactions for account ID: {}", transaction_count, account_id);
+            Ok(list_response(
+                responses,
+                fields.as_ref(),
+                "Account transactions retrieved successfully".to_string(),
+            ))
+        }
+        Err(db_error) => {
+            error!("Failed to retrieve transactions for account ID {}: {}", account_id, db_error);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Query parameters for listing uncategorized transactions
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct UncategorizedQuery {
+    /// Only consider transactions on or after this date
+    pub since: Option<NaiveDate>,
+    /// Maximum number of transactions returned in the list (default: 100)
+    pub limit: Option<u64>,
+}
+
+/// Uncategorized transactions with their total count
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UncategorizedTransactionsResponse {
+    /// Total number of uncategorized transactions (not limited)
+    pub count: u64,
+    /// Most recent uncategorized transactions, newest first
+    pub transactions: Vec<TransactionResponse>,
+}
+
+/// A single transaction to category assignment
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CategoryAssignment {
+    pub transaction_id: i32,
+    pub category_id: i32,
+}
+
+/// Request body for categorizing many transactions at once
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct BulkCategorizeRequest {
+    pub assignments: Vec<CategoryAssignment>,
+}
+
+/// Result of a bulk categorization
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkCategorizeResponse {
+    /// Number of transactions updated
+    pub updated: usize,
+    /// Transaction IDs that do not exist
+    pub not_found: Vec<i32>,
+    /// Transaction IDs skipped because they are reconciled and locked
+    pub locked: Vec<i32>,
+    /// Transaction IDs skipped because they are dated inside a locked period
+    pub period_locked: Vec<i32>,
+    /// Transaction IDs skipped because their accounts are shared read-only
+    pub read_only: Vec<i32>,
+    /// Transaction IDs skipped because, in strict double-entry mode, the category would
+    /// leave them unbalanced
+    pub double_entry_violations: Vec<i32>,
+}
+
+/// Get transactions that have no category yet
+///
+/// Simulated (scenario) transactions are excluded.
+#[utoipa::path(
+    get,
+    path = "/api/v1/transactions/uncategorized",
+    tag = "transactions",
+    params(UncategorizedQuery),
+    responses(
+        (status = 200, description = "Uncategorized transactions retrieved successfully", body = ApiResponse<UncategorizedTransactionsResponse>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument]
+pub async fn get_uncategorized_transactions(
+    Query(query): Query<UncategorizedQuery>,
+    State(state): State<AppState>,
+    access: AccountAccess,
+) -> Result<Json<ApiResponse<UncategorizedTransactionsResponse>>, StatusCode> {
+    trace!("Entering get_uncategorized_transactions function");
+
+    let limit = query.limit.unwrap_or(100);
+    let mut query_builder = access
+        .filter(
+            one_off_transaction::Entity::find(),
+            [one_off_transaction::Column::TargetAccountId, one_off_transaction::Column::SourceAccountId],
+        )
+        .filter(one_off_transaction::Column::CategoryId.is_null())
+        .filter(one_off_transaction::Column::IsSimulated.eq(false));
+    if let Some(since) = query.since {
+        query_builder = query_builder.filter(one_off_transaction::Column::Date.gte(since));
+    }
+
+    let count = match query_builder.clone().count(&state.db).await {
+        Ok(count) => count,
+        Err(db_error) => {
+            error!("Failed to count uncategorized transactions: {}", db_error);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    match query_builder
+        .order_by_desc(one_off_transaction::Column::Date)
+        .limit(limit)
+        .all(&state.db)
+        .await
+    {
+        Ok(transactions) => {
             let mut transaction_responses = Vec::new();
             for transaction in transactions {
                 match TransactionResponse::with_tags(transaction.clone(), &state.db).await {
@@ -445,21 +836,319 @@ pub async fn get_account_transactions(
                 }
             }
 
-            info!("Successfully retrieved {} transactions for account ID: {}", transaction_count, account_id);
-            let response = ApiResponse {
-                data: transaction_responses,
-                message: "Account transactions retrieved successfully".to_string(),
+            info!("Found {} uncategorized transactions", count);
+            Ok(Json(ApiResponse {
+                data: UncategorizedTransactionsResponse {
+                    count,
+                    transactions: transaction_responses,
+                },
+                message: "Uncategorized transactions retrieved successfully".to_string(),
                 success: true,
-            };
-            Ok(Json(response))
+            }))
         }
         Err(db_error) => {
-            error!("Failed to retrieve transactions for account ID {}: {}", account_id, db_error);
+            error!("Failed to retrieve uncategorized transactions: {}", db_error);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
 }
 
+/// Assign categories to many transactions at once
+///
+/// Unknown transactions, reconciled (locked) transactions, transactions inside a locked
+/// period or on accounts shared read-only and, in strict double-entry mode, transactions
+/// the category would leave unbalanced are reported and skipped; an unknown category
+/// rejects the whole request.
+#[utoipa::path(
+    post,
+    path = "/api/v1/transactions/categorize",
+    tag = "transactions",
+    params(PeriodLockOverride),
+    request_body = BulkCategorizeRequest,
+    responses(
+        (status = 200, description = "Transactions categorized", body = ApiResponse<BulkCategorizeResponse>),
+        (status = 400, description = "Unknown category", body = ErrorResponse),
+        (status = 403, description = "admin_override was passed by a non-administrator", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument]
+pub async fn bulk_categorize_transactions(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    auth: Option<AuthenticatedUser>,
+    Query(lock_override): Query<PeriodLockOverride>,
+    Json(request): Json<BulkCategorizeRequest>,
+) -> Result<Json<ApiResponse<BulkCategorizeResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering bulk_categorize_transactions function");
+    debug!("Categorizing {} transactions", request.assignments.len());
+
+    let database_error = |message: &str| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: message.to_string(),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    };
+
+    let mut category_ids: Vec<i32> = request.assignments.iter().map(|a| a.category_id).collect();
+    category_ids.sort_unstable();
+    category_ids.dedup();
+
+    let known_categories: Vec<i32> = category::Entity::find()
+        .select_only()
+        .column(category::Column::Id)
+        .filter(category::Column::Id.is_in(category_ids.clone()))
+        .into_tuple()
+        .all(&state.db)
+        .await
+        .map_err(|e| {
+            error!("Failed to validate categories: {}", e);
+            database_error("Internal server error while validating categories")
+        })?;
+
+    if let Some(missing) = category_ids.iter().find(|id| !known_categories.contains(id)) {
+        warn!("Bulk categorize referenced unknown category {}", missing);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: format!("Category with id {} does not exist", missing),
+                code: "INVALID_CATEGORY_ID".to_string(),
+                success: false,
+            }),
+        ));
+    }
+
+    let locked_periods = if lock_override.is_set() {
+        ensure_admin(&state.db, auth.as_ref(), "modify transactions in locked periods").await?;
+        Vec::new()
+    } else {
+        locked_period::Entity::find().all(&state.db).await.map_err(|e| {
+            error!("Failed to load locked periods: {}", e);
+            database_error("Internal server error while checking locked periods")
+        })?
+    };
+
+    let txn = state.db.begin().await.map_err(|e| {
+        error!("Failed to start database transaction: {}", e);
+        database_error("Failed to categorize transactions")
+    })?;
+
+    let mut response = BulkCategorizeResponse {
+        updated: 0,
+        not_found: Vec::new(),
+        locked: Vec::new(),
+        period_locked: Vec::new(),
+        read_only: Vec::new(),
+        double_entry_violations: Vec::new(),
+    };
+
+    for assignment in &request.assignments {
+        let existing = one_off_transaction::Entity::find_by_id(assignment.transaction_id)
+            .one(&txn)
+            .await
+            .map_err(|e| {
+                error!("Failed to fetch transaction {}: {}", assignment.transaction_id, e);
+                database_error("Failed to categorize transactions")
+            })?;
+
+        match existing {
+            None => response.not_found.push(assignment.transaction_id),
+            // Transactions of other users are reported like missing ones
+            Some(transaction) if !access.can_access_any(transaction.target_account_id, transaction.source_account_id) => {
+                response.not_found.push(transaction.id)
+            }
+            Some(transaction) if !access.can_write_any(transaction.target_account_id, transaction.source_account_id) => {
+                response.read_only.push(transaction.id)
+            }
+            Some(transaction) if transaction.is_locked => response.locked.push(transaction.id),
+            Some(transaction) if locked_periods.iter().any(|period| period.contains(transaction.date)) => {
+                response.period_locked.push(transaction.id)
+            }
+            Some(transaction) => {
+                match ensure_double_entry_in(
+                    &state,
+                    &txn,
+                    transaction.target_account_id,
+                    transaction.source_account_id,
+                    Some(assignment.category_id),
+                    false,
+                )
+                .await
+                {
+                    Ok(()) => {}
+                    Err((StatusCode::BAD_REQUEST, _)) => {
+                        warn!("Category {} would unbalance transaction {}", assignment.category_id, transaction.id);
+                        response.double_entry_violations.push(transaction.id);
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                }
+                let mut active: one_off_transaction::ActiveModel = transaction.into();
+                active.category_id = Set(Some(assignment.category_id));
+                active.update(&txn).await.map_err(|e| {
+                    error!("Failed to categorize transaction {}: {}", assignment.transaction_id, e);
+                    database_error("Failed to categorize transactions")
+                })?;
+                response.updated += 1;
+            }
+        }
+    }
+
+    txn.commit().await.map_err(|e| {
+        error!("Failed to commit bulk categorization: {}", e);
+        database_error("Failed to categorize transactions")
+    })?;
+
+    info!(
+        "Bulk categorized {} transactions ({} not found, {} locked, {} in locked periods, {} read-only, {} unbalanced)",
+        response.updated,
+        response.not_found.len(),
+        response.locked.len(),
+        response.period_locked.len(),
+        response.read_only.len(),
+        response.double_entry_violations.len()
+    );
+    Ok(Json(ApiResponse {
+        data: response,
+        message: "Transactions categorized successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Look up coordinates in the raw data of the imported transaction a new transaction is linked to
+async fn linked_import_coordinates(db: &sea_orm::DatabaseConnection, import_hash: &str) -> Option<(f64, f64)> {
+    match imported_transaction::Entity::find()
+        .filter(imported_transaction::Column::ImportHash.eq(import_hash))
+        .one(db)
+        .await
+    {
+        Ok(Some(imported)) => imported.raw_data.as_ref().and_then(coordinates_from_raw_data),
+        Ok(None) => None,
+        Err(db_error) => {
+            warn!("Failed to look up linked import {} for coordinates: {}", import_hash, db_error);
+            None
+        }
+    }
+}
+
+/// Query parameters for the transactions map
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct GeoJsonQuery {
+    /// Bounding box as `min_lon,min_lat,max_lon,max_lat`
+    pub bbox: Option<String>,
+    /// Only transactions on or after this date
+    pub start_date: Option<NaiveDate>,
+    /// Only transactions on or before this date
+    pub end_date: Option<NaiveDate>,
+    /// Only transactions touching this account
+    pub account_id: Option<i32>,
+}
+
+/// Get geolocated transactions as GeoJSON
+///
+/// Returns a `FeatureCollection` of points; transactions without coordinates are omitted.
+#[utoipa::path(
+    get,
+    path = "/api/v1/transactions/geojson",
+    tag = "transactions",
+    params(GeoJsonQuery),
+    responses(
+        (status = 200, description = "GeoJSON feature collection", body = TransactionFeatureCollection),
+        (status = 400, description = "Invalid bounding box", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_transactions_geojson(
+    Query(query): Query<GeoJsonQuery>,
+    State(state): State<AppState>,
+    access: AccountAccess,
+) -> Result<Json<TransactionFeatureCollection>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_transactions_geojson function");
+
+    let bbox = match query.bbox.as_deref().map(BoundingBox::parse).transpose() {
+        Ok(bbox) => bbox,
+        Err(message) => {
+            warn!("Invalid bbox: {}", message);
+            let error_response = ErrorResponse {
+                error: message,
+                code: "INVALID_BBOX".to_string(),
+                success: false,
+            };
+            return Err((StatusCode::BAD_REQUEST, Json(error_response)));
+        }
+    };
+
+    let mut query_builder = access
+        .filter(
+            one_off_transaction::Entity::find(),
+            [one_off_transaction::Column::TargetAccountId, one_off_transaction::Column::SourceAccountId],
+        )
+        .filter(one_off_transaction::Column::Latitude.is_not_null())
+        .filter(one_off_transaction::Column::Longitude.is_not_null());
+    if let Some(bbox) = bbox {
+        query_builder = query_builder
+            .filter(one_off_transaction::Column::Latitude.between(bbox.min_lat, bbox.max_lat));
+        // Longitude is checked in memory so boxes crossing the antimeridian work
+    }
+    if let Some(start_date) = query.start_date {
+        query_builder = query_builder.filter(one_off_transaction::Column::Date.gte(start_date));
+    }
+    if let Some(end_date) = query.end_date {
+        query_builder = query_builder.filter(one_off_transaction::Column::Date.lte(end_date));
+    }
+    if let Some(account_id) = query.account_id {
+        query_builder = query_builder.filter(
+            sea_orm::Condition::any()
+                .add(one_off_transaction::Column::TargetAccountId.eq(account_id))
+                .add(one_off_transaction::Column::SourceAccountId.eq(account_id)),
+        );
+    }
+
+    let transactions = query_builder
+        .order_by_desc(one_off_transaction::Column::Date)
+        .all(&state.db)
+        .await
+        .map_err(|db_error| {
+            error!("Failed to retrieve geolocated transactions: {}", db_error);
+            let error_response = ErrorResponse {
+                error: "Internal server error while retrieving transactions".to_string(),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            };
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(error_response))
+        })?;
+
+    let features: Vec<TransactionFeature> = transactions
+        .into_iter()
+        .filter_map(|transaction| {
+            let (latitude, longitude) = (transaction.latitude?, transaction.longitude?);
+            if bbox.is_some_and(|bbox| !bbox.contains(longitude, latitude)) {
+                return None;
+            }
+            Some(TransactionFeature::new(
+                longitude,
+                latitude,
+                TransactionFeatureProperties {
+                    transaction_id: transaction.id,
+                    name: transaction.name,
+                    amount: transaction.amount,
+                    date: transaction.date,
+                    target_account_id: transaction.target_account_id,
+                    category_id: transaction.category_id,
+                },
+            ))
+        })
+        .collect();
+
+    info!("Returning {} geolocated transactions", features.len());
+    Ok(Json(TransactionFeatureCollection::new(features)))
+}
+
 /// Get a specific transaction by ID
 #[utoipa::path(
     get,
@@ -478,6 +1167,7 @@ pub async fn get_account_transactions(
 pub async fn get_transaction(
     Path(transaction_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
 ) -> Result<Json<ApiResponse<TransactionResponse>>, StatusCode> {
     trace!("Entering get_transaction function for transaction_id: {}", transaction_id);
     debug!("Fetching transaction with ID: {}", transaction_id);
@@ -486,7 +1176,9 @@ pub async fn get_transaction(
         .one(&state.db)
         .await
     {
-        Ok(Some(transaction_model)) => {
+        Ok(Some(transaction_model))
+            if access.can_access_any(transaction_model.target_account_id, transaction_model.source_account_id) =>
+        {
             info!("Successfully retrieved transaction with ID: {}, name: {}", 
                   transaction_model.id, transaction_model.name);
 
@@ -510,7 +1202,7 @@ pub async fn get_transaction(
                 }
             }
         }
-        Ok(None) => {
+        Ok(_) => {
             warn!("Transaction with ID {} not found", transaction_id);
             Err(StatusCode::NOT_FOUND)
         }
@@ -528,12 +1220,15 @@ pub async fn get_transaction(
     tag = "transactions",
     params(
         ("transaction_id" = i32, Path, description = "Transaction ID"),
+        PeriodLockOverride,
     ),
     request_body = UpdateTransactionRequest,
     responses(
         (status = 200, description = "Transaction updated successfully", body = ApiResponse<TransactionResponse>),
+        (status = 403, description = "Transaction or the new target or source account is shared read-only or not accessible, or admin_override was passed by a non-administrator", body = ErrorResponse),
         (status = 404, description = "Transaction not found", body = ErrorResponse),
         (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 409, description = "Transaction is reconciled and locked, or dated inside a locked period", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
@@ -541,6 +1236,9 @@ pub async fn get_transaction(
 pub async fn update_transaction(
     Path(transaction_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
+    auth: Option<AuthenticatedUser>,
+    Query(lock_override): Query<PeriodLockOverride>,
     Json(request): Json<UpdateTransactionRequest>,
 ) -> Result<Json<ApiResponse<TransactionResponse>>, StatusCode> {
     trace!("Entering update_transaction function for transaction_id: {}", transaction_id);
@@ -552,11 +1250,11 @@ pub async fn update_transaction(
         .one(&state.db)
         .await
     {
-        Ok(Some(transaction)) => {
+        Ok(Some(transaction)) if access.can_access_any(transaction.target_account_id, transaction.source_account_id) => {
             debug!("Found existing transaction: {}", transaction.name);
             transaction
         }
-        Ok(None) => {
+        Ok(_) => {
             warn!("Transaction with ID {} not found for update", transaction_id);
             return Err(StatusCode::NOT_FOUND);
         }
@@ -566,6 +1264,121 @@ pub async fn update_transaction(
         }
     };
 
+    if !access.can_write_any(existing_transaction.target_account_id, existing_transaction.source_account_id) {
+        warn!("Transaction with ID {} is read-only for user {:?}, refusing update", transaction_id, access.user_id());
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    if existing_transaction.is_locked {
+        warn!("Transaction with ID {} is reconciled and locked, refusing update", transaction_id);
+        return Err(StatusCode::CONFLICT);
+    }
+
+    if let Some(account_id) = request
+        .target_account_id
+        .into_iter()
+        .chain(request.source_account_id)
+        .find(|account_id| !access.can_write(*account_id))
+    {
+        warn!("User {:?} has no access to account {}, refusing update", access.user_id(), account_id);
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let mut affected_dates = vec![existing_transaction.date];
+    affected_dates.extend(request.date);
+    match find_locked_period(&state.db, auth.as_ref(), &affected_dates, &lock_override).await {
+        Ok(None) => {}
+        Ok(Some(period)) => {
+            warn!("Transaction with ID {} touches locked period {}, refusing update", transaction_id, period.id);
+            return Err(StatusCode::CONFLICT);
+        }
+        Err((status, _)) => return Err(status),
+    }
+
+    if let Err((status, _)) = ensure_double_entry(
+        &state,
+        request.target_account_id.unwrap_or(existing_transaction.target_account_id),
+        request.source_account_id.or(existing_transaction.source_account_id),
+        request.category_id.or(existing_transaction.category_id),
+        false,
+    )
+    .await
+    {
+        warn!("Transaction with ID {} would violate double-entry rules, refusing update", transaction_id);
+        return Err(status);
+    }
+
+    // Details that are not re-sent are kept, unless the transaction stops being a purchase
+    let is_purchase = request.is_purchase.unwrap_or(existing_transaction.is_purchase);
+    let keep_details = request.is_purchase != Some(false);
+    let warranty_end_date = request
+        .warranty_end_date
+        .or(existing_transaction.warranty_end_date.filter(|_| keep_details));
+    let return_deadline = request
+        .return_deadline
+        .or(existing_transaction.return_deadline.filter(|_| keep_details));
+    let receipt_document_id = request
+        .receipt_document_id
+        .or(existing_transaction.receipt_document_id.filter(|_| keep_details));
+    if let Err((status, _)) = validate_purchase_details(
+        &state.db,
+        request.target_account_id.unwrap_or(existing_transaction.target_account_id),
+        is_purchase,
+        warranty_end_date,
+        return_deadline,
+        receipt_document_id,
+    )
+    .await
+    {
+        warn!("Transaction with ID {} has invalid purchase details, refusing update", transaction_id);
+        return Err(status);
+    }
+
+    // The conversion is re-validated whenever the settled amount or its account changes
+    let conversion = if request.clear_original_currency == Some(true) {
+        Some(None)
+    } else if request.original_amount.is_some()
+        || request.original_currency.is_some()
+        || request.conversion_rate.is_some()
+        || (existing_transaction.original_currency.is_some()
+            && (request.amount.is_some() || request.target_account_id.is_some()))
+    {
+        let target_account_id = request.target_account_id.unwrap_or(existing_transaction.target_account_id);
+        let account_currency = match account::Entity::find_by_id(target_account_id).one(&state.db).await {
+            Ok(Some(account)) => account.currency_code,
+            Ok(None) => {
+                warn!("Target account {} of transaction {} not found", target_account_id, transaction_id);
+                return Err(StatusCode::BAD_REQUEST);
+            }
+            Err(db_error) => {
+                error!("Failed to lookup account {} for transaction {}: {}", target_account_id, transaction_id, db_error);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        };
+        // A stored rate no longer matches once either amount changes
+        let keep_rate = request.amount.is_none() && request.original_amount.is_none();
+        match currency_conversion(
+            request.amount.unwrap_or(existing_transaction.amount),
+            request.original_amount.or(existing_transaction.original_amount),
+            request
+                .original_currency
+                .as_deref()
+                .or(existing_transaction.original_currency.as_deref()),
+            request
+                .conversion_rate
+                .or(existing_transaction.conversion_rate.filter(|_| keep_rate)),
+            &account_currency,
+        ) {
+            Ok(conversion) => Some(conversion),
+            Err(problem) => {
+                warn!("Rejected update of transaction {}: {}", transaction_id, problem);
+                return Err(StatusCode::BAD_REQUEST);
+            }
+        }
+    } else {
+        None
+    };
+
     // Create active model for update
     let mut transaction_active: one_off_transaction::ActiveModel = existing_transaction.into();
     let mut updated_fields = Vec::new();
@@ -621,6 +1434,42 @@ pub async fn update_transaction(
         transaction_active.category_id = Set(Some(category_id));
         updated_fields.push(format!("category_id: {:?}", category_id));
     }
+    if let Some(conversion) = conversion {
+        debug!("Updating transaction currency conversion to: {:?}", conversion);
+        transaction_active.original_amount = Set(conversion.as_ref().map(|conversion| conversion.original_amount));
+        transaction_active.original_currency =
+            Set(conversion.as_ref().map(|conversion| conversion.original_currency.clone()));
+        transaction_active.conversion_rate = Set(conversion.as_ref().map(|conversion| conversion.conversion_rate));
+        updated_fields.push(format!("currency conversion: {:?}", conversion));
+    }
+    if request.is_purchase.is_some()
+        || request.warranty_end_date.is_some()
+        || request.return_deadline.is_some()
+        || request.receipt_document_id.is_some()
+    {
+        debug!(
+            "Updating transaction purchase details to: {}, warranty {:?}, return {:?}, receipt {:?}",
+            is_purchase, warranty_end_date, return_deadline, receipt_document_id
+        );
+        transaction_active.is_purchase = Set(is_purchase);
+        transaction_active.warranty_end_date = Set(warranty_end_date);
+        transaction_active.return_deadline = Set(return_deadline);
+        transaction_active.receipt_document_id = Set(receipt_document_id);
+        updated_fields.push(format!("purchase: {}", is_purchase));
+    }
+    match (request.latitude, request.longitude) {
+        (Some(latitude), Some(longitude)) if valid_coordinates(latitude, longitude) => {
+            debug!("Updating transaction coordinates to: {}, {}", latitude, longitude);
+            transaction_active.latitude = Set(Some(latitude));
+            transaction_active.longitude = Set(Some(longitude));
+            updated_fields.push(format!("coordinates: {}, {}", latitude, longitude));
+        }
+        (None, None) => {}
+        _ => {
+            warn!("Rejected update of transaction {} with invalid coordinates", transaction_id);
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
 
     if updated_fields.is_empty() {
         debug!("No fields to update for transaction ID: {}", transaction_id);
@@ -634,6 +1483,24 @@ pub async fn update_transaction(
             info!("Transaction with ID {} updated successfully. Updated fields: {}", 
                   transaction_id, if updated_fields.is_empty() { "none".to_string() } else { updated_fields.join(", ") });
 
+            // Legs always share the parent's date and account
+            if let Err(db_error) = one_off_transaction::Entity::update_many()
+                .col_expr(
+                    one_off_transaction::Column::Date,
+                    sea_orm::sea_query::Expr::value(updated_transaction.date),
+                )
+                .col_expr(
+                    one_off_transaction::Column::TargetAccountId,
+                    sea_orm::sea_query::Expr::value(updated_transaction.target_account_id),
+                )
+                .filter(one_off_transaction::Column::ParentTransactionId.eq(transaction_id))
+                .exec(&state.db)
+                .await
+            {
+                error!("Failed to update legs of transaction {}: {}", transaction_id, db_error);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+
             match TransactionResponse::with_tags(updated_transaction.clone(), &state.db).await {
                 Ok(transaction_response) => {
                     let response = ApiResponse {
@@ -668,10 +1535,13 @@ pub async fn update_transaction(
     tag = "transactions",
     params(
         ("transaction_id" = i32, Path, description = "Transaction ID"),
+        PeriodLockOverride,
     ),
     responses(
         (status = 200, description = "Transaction deleted successfully", body = ApiResponse<String>),
+        (status = 403, description = "Transaction is shared read-only, or admin_override was passed by a non-administrator", body = ErrorResponse),
         (status = 404, description = "Transaction not found", body = ErrorResponse),
+        (status = 409, description = "Transaction is reconciled and locked, or dated inside a locked period", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
@@ -679,10 +1549,55 @@ pub async fn update_transaction(
 pub async fn delete_transaction(
     Path(transaction_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
+    auth: Option<AuthenticatedUser>,
+    Query(lock_override): Query<PeriodLockOverride>,
 ) -> Result<Json<ApiResponse<String>>, StatusCode> {
     trace!("Entering delete_transaction function for transaction_id: {}", transaction_id);
     debug!("Attempting to delete transaction with ID: {}", transaction_id);
 
+    match one_off_transaction::Entity::find_by_id(transaction_id)
+        .one(&state.db)
+        .await
+    {
+        Ok(Some(transaction)) if !access.can_access_any(transaction.target_account_id, transaction.source_account_id) => {
+            warn!("Transaction with ID {} is not accessible to user {:?}", transaction_id, access.user_id());
+            return Err(StatusCode::NOT_FOUND);
+        }
+        Ok(Some(transaction)) if !access.can_write_any(transaction.target_account_id, transaction.source_account_id) => {
+            warn!("Transaction with ID {} is read-only for user {:?}, refusing delete", transaction_id, access.user_id());
+            return Err(StatusCode::FORBIDDEN);
+        }
+        Ok(Some(transaction)) if transaction.is_locked => {
+            warn!("Transaction with ID {} is reconciled and locked, refusing delete", transaction_id);
+            return Err(StatusCode::CONFLICT);
+        }
+        Ok(Some(transaction)) => {
+            match find_locked_period(&state.db, auth.as_ref(), &[transaction.date], &lock_override).await {
+                Ok(None) => {}
+                Ok(Some(period)) => {
+                    warn!("Transaction with ID {} is inside locked period {}, refusing delete", transaction_id, period.id);
+                    return Err(StatusCode::CONFLICT);
+                }
+                Err((status, _)) => return Err(status),
+            }
+        }
+        Ok(None) => {}
+        Err(db_error) => {
+            error!("Failed to lookup transaction with ID {} for deletion: {}", transaction_id, db_error);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    }
+
+    if let Err(db_error) = one_off_transaction::Entity::delete_many()
+        .filter(one_off_transaction::Column::ParentTransactionId.eq(transaction_id))
+        .exec(&state.db)
+        .await
+    {
+        error!("Failed to delete legs of transaction {}: {}", transaction_id, db_error);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
     match one_off_transaction::Entity::delete_by_id(transaction_id)
         .exec(&state.db)
         .await
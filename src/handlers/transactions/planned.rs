@@ -0,0 +1,151 @@
+use super::one_offs::TransactionResponse;
+use crate::handlers::periods::{ensure_dates_unlocked, PeriodLockOverride};
+use crate::helpers::auth::AuthenticatedUser;
+use crate::helpers::access::{account_read_only, AccountAccess};
+use crate::helpers::timezone::UserTimezone;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::NaiveDate;
+use model::entities::one_off_transaction;
+use rust_decimal::Decimal;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, Set, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, instrument, warn};
+use utoipa::ToSchema;
+
+/// Request body for confirming a planned transaction
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ConfirmTransactionRequest {
+    /// Date the money actually moved (default: today)
+    pub date: Option<NaiveDate>,
+    /// Amount that actually moved (default: the planned amount)
+    pub amount: Option<Decimal>,
+}
+
+fn confirm_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// Confirm a planned transaction
+///
+/// Marks the money of a planned transaction as moved, so it counts towards actual
+/// balances from then on. The date and amount can be corrected to what really happened;
+/// attached legs follow the transaction.
+#[utoipa::path(
+    post,
+    path = "/api/v1/transactions/{transaction_id}/confirm",
+    tag = "transactions",
+    params(
+        ("transaction_id" = i32, Path, description = "Planned transaction ID"),
+        PeriodLockOverride,
+    ),
+    request_body = ConfirmTransactionRequest,
+    responses(
+        (status = 200, description = "Transaction confirmed", body = ApiResponse<TransactionResponse>),
+        (status = 400, description = "Transaction is a leg of another transaction", body = ErrorResponse),
+        (status = 403, description = "Transaction is only booked on read-only accounts, or admin_override was passed by a non-administrator", body = ErrorResponse),
+        (status = 404, description = "Transaction not found", body = ErrorResponse),
+        (status = 409, description = "Transaction is not planned, or the date falls inside a locked period", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn confirm_transaction(
+    Path(transaction_id): Path<i32>,
+    State(state): State<AppState>,
+    access: AccountAccess,
+    timezone: UserTimezone,
+    auth: Option<AuthenticatedUser>,
+    Query(lock_override): Query<PeriodLockOverride>,
+    Json(request): Json<ConfirmTransactionRequest>,
+) -> Result<Json<ApiResponse<TransactionResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("Confirming planned transaction {}", transaction_id);
+
+    let database_error = |e: DbErr| {
+        error!("Database error while confirming transaction {}: {}", transaction_id, e);
+        confirm_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "DATABASE_ERROR",
+            "Failed to confirm transaction".to_string(),
+        )
+    };
+
+    let transaction = one_off_transaction::Entity::find_by_id(transaction_id)
+        .one(&state.db)
+        .await
+        .map_err(database_error)?
+        .filter(|transaction| access.can_access_any(transaction.target_account_id, transaction.source_account_id))
+        .ok_or_else(|| {
+            warn!("Transaction {} not found for confirmation", transaction_id);
+            confirm_error(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                format!("Transaction with id {} not found", transaction_id),
+            )
+        })?;
+
+    if !access.can_write_any(transaction.target_account_id, transaction.source_account_id) {
+        warn!("Transaction {} is read-only for user {:?}", transaction_id, access.user_id());
+        return Err(account_read_only(transaction.target_account_id));
+    }
+    if transaction.parent_transaction_id.is_some() {
+        warn!("Transaction {} is a leg, refusing to confirm it on its own", transaction_id);
+        return Err(confirm_error(
+            StatusCode::BAD_REQUEST,
+            "TRANSACTION_IS_LEG",
+            "Legs are confirmed together with their parent transaction".to_string(),
+        ));
+    }
+    if !transaction.is_planned() {
+        warn!("Transaction {} is not planned", transaction_id);
+        return Err(confirm_error(
+            StatusCode::CONFLICT,
+            "NOT_PLANNED",
+            format!("Transaction with id {} is already confirmed", transaction_id),
+        ));
+    }
+
+    let date = request.date.unwrap_or_else(|| timezone.today());
+    ensure_dates_unlocked(&state.db, auth.as_ref(), &[transaction.date, date], &lock_override).await?;
+
+    let db_transaction = state.db.begin().await.map_err(database_error)?;
+    let mut active: one_off_transaction::ActiveModel = transaction.into();
+    active.status = Set(one_off_transaction::OneOffStatus::Confirmed);
+    active.date = Set(date);
+    if let Some(amount) = request.amount {
+        active.amount = Set(amount);
+    }
+    let confirmed = active.update(&db_transaction).await.map_err(database_error)?;
+    one_off_transaction::Entity::update_many()
+        .set(one_off_transaction::ActiveModel {
+            status: Set(one_off_transaction::OneOffStatus::Confirmed),
+            date: Set(date),
+            ..Default::default()
+        })
+        .filter(one_off_transaction::Column::ParentTransactionId.eq(transaction_id))
+        .exec(&db_transaction)
+        .await
+        .map_err(database_error)?;
+    db_transaction.commit().await.map_err(database_error)?;
+
+    info!("Confirmed planned transaction {} on {} with amount {}", transaction_id, date, confirmed.amount);
+    let response = TransactionResponse::with_tags(confirmed, &state.db)
+        .await
+        .map_err(database_error)?;
+    Ok(Json(ApiResponse {
+        data: response,
+        message: "Transaction confirmed successfully".to_string(),
+        success: true,
+    }))
+}
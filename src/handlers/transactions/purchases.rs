@@ -0,0 +1,205 @@
+use crate::helpers::access::AccountAccess;
+use crate::helpers::timezone::UserTimezone;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use axum_valid::Valid;
+use chrono::NaiveDate;
+use compute::warranties::{upcoming_purchase_deadlines, PurchaseDeadlineKind};
+use model::entities::{account_document, one_off_transaction};
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, instrument, trace, warn};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Which right of a purchase runs out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+pub enum PurchaseDeadline {
+    /// The return window closes
+    Return,
+    /// The warranty ends
+    Warranty,
+}
+
+impl From<PurchaseDeadlineKind> for PurchaseDeadline {
+    fn from(kind: PurchaseDeadlineKind) -> Self {
+        match kind {
+            PurchaseDeadlineKind::Return => PurchaseDeadline::Return,
+            PurchaseDeadlineKind::Warranty => PurchaseDeadline::Warranty,
+        }
+    }
+}
+
+/// Query parameters for purchase reminders
+#[derive(Debug, Deserialize, IntoParams, Validate)]
+pub struct PurchaseRemindersQuery {
+    /// Remind about deadlines up to this many days ahead (defaults to 30)
+    #[validate(range(min = 0, max = 366))]
+    pub within_days: Option<i64>,
+    /// Only include purchases paid from this account
+    pub account_id: Option<i32>,
+}
+
+/// A purchase whose return window or warranty ends soon
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PurchaseReminderResponse {
+    pub transaction_id: i32,
+    pub name: String,
+    pub amount: Decimal,
+    /// Day of the purchase
+    pub date: NaiveDate,
+    pub target_account_id: i32,
+    pub kind: PurchaseDeadline,
+    /// Last day the item can be returned or the warranty claimed
+    pub deadline: NaiveDate,
+    /// Days left until the deadline; zero on the deadline itself
+    pub days_until_deadline: i64,
+    /// Account document holding the receipt
+    pub receipt_document_id: Option<i32>,
+}
+
+fn purchase_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// Check the purchase details of a transaction on `account_id`.
+///
+/// Warranty, return deadline and receipt are only kept for purchases, and the receipt
+/// has to be a document of the account the purchase was paid from.
+pub(super) async fn validate_purchase_details(
+    db: &DatabaseConnection,
+    account_id: i32,
+    is_purchase: bool,
+    warranty_end_date: Option<NaiveDate>,
+    return_deadline: Option<NaiveDate>,
+    receipt_document_id: Option<i32>,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if !is_purchase && (warranty_end_date.is_some() || return_deadline.is_some() || receipt_document_id.is_some()) {
+        warn!("Rejected purchase details on a transaction that is not a purchase");
+        return Err(purchase_error(
+            StatusCode::BAD_REQUEST,
+            "NOT_A_PURCHASE",
+            "warranty_end_date, return_deadline and receipt_document_id need is_purchase".to_string(),
+        ));
+    }
+
+    let Some(document_id) = receipt_document_id else {
+        return Ok(());
+    };
+    let receipt = account_document::Entity::find_by_id(document_id)
+        .one(db)
+        .await
+        .map_err(|e| {
+            error!("Database error while looking up receipt document {}: {}", document_id, e);
+            purchase_error(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", e.to_string())
+        })?;
+    if receipt.is_none_or(|document| document.account_id != account_id) {
+        warn!("Receipt document {} is not a document of account {}", document_id, account_id);
+        return Err(purchase_error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_RECEIPT_DOCUMENT",
+            format!("Document {} is not a document of account {}", document_id, account_id),
+        ));
+    }
+    Ok(())
+}
+
+/// Get purchase return and warranty reminders
+///
+/// Lists purchases whose return deadline or warranty end falls within the next
+/// `within_days` days, most urgent first. A purchase with both coming up is listed
+/// twice. Meant to be polled by notification tooling.
+#[utoipa::path(
+    get,
+    path = "/api/v1/transactions/purchase-reminders",
+    tag = "transactions",
+    params(PurchaseRemindersQuery),
+    responses(
+        (status = 200, description = "Purchase reminders retrieved successfully", body = ApiResponse<Vec<PurchaseReminderResponse>>),
+        (status = 400, description = "Invalid query parameters", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_purchase_reminders(
+    Valid(Query(query)): Valid<Query<PurchaseRemindersQuery>>,
+    State(state): State<AppState>,
+    access: AccountAccess,
+    timezone: UserTimezone,
+) -> Result<Json<ApiResponse<Vec<PurchaseReminderResponse>>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Fetching purchase reminders with query: {:?}", query);
+
+    let today = timezone.today();
+    let within_days = query.within_days.unwrap_or(30);
+
+    let mut select = access
+        .filter(
+            one_off_transaction::Entity::find(),
+            [one_off_transaction::Column::TargetAccountId, one_off_transaction::Column::SourceAccountId],
+        )
+        .filter(one_off_transaction::Column::IsPurchase.eq(true))
+        .filter(one_off_transaction::Column::IsSimulated.eq(false))
+        .filter(
+            Condition::any()
+                .add(one_off_transaction::Column::ReturnDeadline.is_not_null())
+                .add(one_off_transaction::Column::WarrantyEndDate.is_not_null()),
+        );
+    if let Some(account_id) = query.account_id {
+        select = select.filter(one_off_transaction::Column::TargetAccountId.eq(account_id));
+    }
+    let purchases = select.all(&state.db).await.map_err(|e| {
+        error!("Database error while fetching purchases: {}", e);
+        purchase_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "DATABASE_ERROR",
+            "Failed to fetch purchases".to_string(),
+        )
+    })?;
+    debug!("Checking return and warranty deadlines of {} purchases", purchases.len());
+
+    let by_id: std::collections::HashMap<i32, &one_off_transaction::Model> =
+        purchases.iter().map(|purchase| (purchase.id, purchase)).collect();
+    let reminders: Vec<PurchaseReminderResponse> = upcoming_purchase_deadlines(
+        purchases
+            .iter()
+            .map(|purchase| (purchase.id, purchase.return_deadline, purchase.warranty_end_date)),
+        today,
+        within_days,
+    )
+    .into_iter()
+    .filter_map(|reminder| {
+        let purchase = by_id.get(&reminder.source_id)?;
+        Some(PurchaseReminderResponse {
+            transaction_id: purchase.id,
+            name: purchase.name.clone(),
+            amount: purchase.amount,
+            date: purchase.date,
+            target_account_id: purchase.target_account_id,
+            kind: reminder.kind.into(),
+            deadline: reminder.deadline,
+            days_until_deadline: reminder.days_until_deadline,
+            receipt_document_id: purchase.receipt_document_id,
+        })
+    })
+    .collect();
+
+    info!("Found {} purchase deadlines within {} days", reminders.len(), within_days);
+
+    Ok(Json(ApiResponse {
+        data: reminders,
+        message: "Purchase reminders retrieved successfully".to_string(),
+        success: true,
+    }))
+}
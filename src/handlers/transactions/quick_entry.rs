@@ -0,0 +1,101 @@
+use super::one_offs::{create_transaction, CreateTransactionRequest, TransactionResponse};
+use crate::handlers::periods::PeriodLockOverride;
+use crate::helpers::access::AccountAccess;
+use crate::helpers::auth::AuthenticatedUser;
+use crate::helpers::timezone::UserTimezone;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::NaiveDate;
+use common::{parse_amount, AmountLocale};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument, warn};
+use utoipa::ToSchema;
+
+/// Request body for quickly entering a transaction as typed
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct QuickEntryRequest {
+    /// Account the transaction is booked on
+    pub target_account_id: i32,
+    /// Transaction name
+    pub name: String,
+    /// Amount as typed, e.g. "-1 234,50 Kč" or "(12.00)"; negative for expenses
+    pub amount: String,
+    /// Decimal separator used in `amount` (guessed by default)
+    pub amount_locale: Option<AmountLocale>,
+    /// Transaction date (default: today)
+    pub date: Option<NaiveDate>,
+    /// Category ID
+    pub category_id: Option<i32>,
+}
+
+/// Quickly enter a transaction
+///
+/// Creates a confirmed one-off transaction from an amount written the way people
+/// type it, with thousand separators, a decimal comma and currency symbols allowed.
+#[utoipa::path(
+    post,
+    path = "/api/v1/transactions/quick",
+    tag = "transactions",
+    params(PeriodLockOverride),
+    request_body = QuickEntryRequest,
+    responses(
+        (status = 201, description = "Transaction created successfully", body = ApiResponse<TransactionResponse>),
+        (status = 400, description = "The amount can't be parsed, or the request is invalid", body = ErrorResponse),
+        (status = 403, description = "No access to the account, or it is shared read-only, or admin_override was passed by a non-administrator", body = ErrorResponse),
+        (status = 409, description = "Transaction date falls inside a locked period", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn create_quick_transaction(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    timezone: UserTimezone,
+    auth: Option<AuthenticatedUser>,
+    lock_override: Query<PeriodLockOverride>,
+    Json(request): Json<QuickEntryRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<TransactionResponse>>), (StatusCode, Json<ErrorResponse>)> {
+    debug!("Quick entry of '{}' on account {}", request.amount, request.target_account_id);
+
+    let amount = parse_amount(&request.amount, request.amount_locale.unwrap_or_default()).map_err(|e| {
+        warn!("Rejected quick entry amount '{}': {}", request.amount, e);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: e.to_string(),
+                code: "INVALID_AMOUNT".to_string(),
+                success: false,
+            }),
+        )
+    })?;
+
+    let transaction = CreateTransactionRequest {
+        name: request.name,
+        description: None,
+        amount,
+        date: request.date.unwrap_or_else(|| timezone.today()),
+        include_in_statistics: None,
+        target_account_id: request.target_account_id,
+        source_account_id: None,
+        ledger_name: None,
+        linked_import_id: None,
+        category_id: request.category_id,
+        scenario_id: None,
+        is_simulated: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
+    };
+    create_transaction(State(state), access, auth, lock_override, Json(transaction)).await
+}
@@ -1,3 +1,7 @@
+use crate::handlers::double_entry::ensure_double_entry;
+use crate::handlers::transactions::recurring_approval::initial_approval_status;
+use crate::helpers::access::{account_forbidden, account_read_only, AccountAccess};
+use crate::helpers::timezone::UserTimezone;
 use crate::schemas::{ApiResponse, AppState, ErrorResponse};
 use axum::{
     extract::{Path, Query, State},
@@ -6,10 +10,18 @@ use axum::{
 };
 use axum_valid::Valid;
 use chrono::NaiveDate;
+use common::{PaginatedResponse, Pagination};
+use compute::account::utils::{load_holiday_calendar, recurring_transaction_occurrences};
+use compute::contracts::upcoming_cancellation_deadlines;
+use compute::due_profile::{due_day_profile, DueOccurrence};
+use compute::overdue::{overdue_days, OverdueThresholds};
+use compute::recurrence_rule::RecurrenceRule;
+use compute::recurring_amounts::estimated_amount;
 use model::entities::{recurring_transaction, recurring_transaction_instance};
 use model::transaction::{Tag, TransactionGenerator};
 use rust_decimal::Decimal;
-use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Set};
+use rust_decimal::prelude::ToPrimitive;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Set};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, instrument, trace, warn};
 use utoipa::{IntoParams, ToSchema};
@@ -33,6 +45,103 @@ impl From<Tag> for TagInfo {
     }
 }
 
+/// How the source leg of a transfer between accounts of different currencies is valued
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+pub enum FxMode {
+    /// Convert the amount with the exchange rate of the execution date
+    ExecutionRate,
+    /// Convert the amount with the agreed `fx_rate`
+    FixedRate,
+    /// Take the manually entered `source_amount`
+    ManualAmount,
+}
+
+impl From<recurring_transaction::FxMode> for FxMode {
+    fn from(mode: recurring_transaction::FxMode) -> Self {
+        match mode {
+            recurring_transaction::FxMode::ExecutionRate => FxMode::ExecutionRate,
+            recurring_transaction::FxMode::FixedRate => FxMode::FixedRate,
+            recurring_transaction::FxMode::ManualAmount => FxMode::ManualAmount,
+        }
+    }
+}
+
+impl From<FxMode> for recurring_transaction::FxMode {
+    fn from(mode: FxMode) -> Self {
+        match mode {
+            FxMode::ExecutionRate => recurring_transaction::FxMode::ExecutionRate,
+            FxMode::FixedRate => recurring_transaction::FxMode::FixedRate,
+            FxMode::ManualAmount => recurring_transaction::FxMode::ManualAmount,
+        }
+    }
+}
+
+/// How upcoming occurrences of a recurring transaction are valued
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+pub enum AmountStrategy {
+    /// Always the fixed `amount`
+    Fixed,
+    /// The amount of the latest paid instance
+    LastPaid,
+    /// The average of the last `amount_history_count` paid instances
+    AverageOfLastN,
+}
+
+impl From<recurring_transaction::AmountStrategy> for AmountStrategy {
+    fn from(strategy: recurring_transaction::AmountStrategy) -> Self {
+        match strategy {
+            recurring_transaction::AmountStrategy::Fixed => AmountStrategy::Fixed,
+            recurring_transaction::AmountStrategy::LastPaid => AmountStrategy::LastPaid,
+            recurring_transaction::AmountStrategy::AverageOfLastN => AmountStrategy::AverageOfLastN,
+        }
+    }
+}
+
+impl From<AmountStrategy> for recurring_transaction::AmountStrategy {
+    fn from(strategy: AmountStrategy) -> Self {
+        match strategy {
+            AmountStrategy::Fixed => recurring_transaction::AmountStrategy::Fixed,
+            AmountStrategy::LastPaid => recurring_transaction::AmountStrategy::LastPaid,
+            AmountStrategy::AverageOfLastN => recurring_transaction::AmountStrategy::AverageOfLastN,
+        }
+    }
+}
+
+/// How occurrences of month-based periods are moved off missing days and weekends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+pub enum DateAdjustment {
+    /// Keep the day of `start_date`, skipping months without it
+    None,
+    /// Use the last day of months without the day of `start_date`
+    ClampToMonthEnd,
+    /// Clamp to the month end and move weekend and holiday dates to the business day before
+    PreviousBusinessDay,
+    /// Clamp to the month end and move weekend and holiday dates to the business day after
+    NextBusinessDay,
+}
+
+impl From<recurring_transaction::DateAdjustment> for DateAdjustment {
+    fn from(adjustment: recurring_transaction::DateAdjustment) -> Self {
+        match adjustment {
+            recurring_transaction::DateAdjustment::None => DateAdjustment::None,
+            recurring_transaction::DateAdjustment::ClampToMonthEnd => DateAdjustment::ClampToMonthEnd,
+            recurring_transaction::DateAdjustment::PreviousBusinessDay => DateAdjustment::PreviousBusinessDay,
+            recurring_transaction::DateAdjustment::NextBusinessDay => DateAdjustment::NextBusinessDay,
+        }
+    }
+}
+
+impl From<DateAdjustment> for recurring_transaction::DateAdjustment {
+    fn from(adjustment: DateAdjustment) -> Self {
+        match adjustment {
+            DateAdjustment::None => recurring_transaction::DateAdjustment::None,
+            DateAdjustment::ClampToMonthEnd => recurring_transaction::DateAdjustment::ClampToMonthEnd,
+            DateAdjustment::PreviousBusinessDay => recurring_transaction::DateAdjustment::PreviousBusinessDay,
+            DateAdjustment::NextBusinessDay => recurring_transaction::DateAdjustment::NextBusinessDay,
+        }
+    }
+}
+
 /// Request body for creating a recurring transaction
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct CreateRecurringTransactionRequest {
@@ -62,6 +171,36 @@ pub struct CreateRecurringTransactionRequest {
     pub scenario_id: Option<i32>,
     /// Whether this is a simulated transaction (default: false)
     pub is_simulated: Option<bool>,
+    /// Member creating the transaction; on accounts with an approval threshold
+    /// another member has to approve it. Ignored for authenticated requests, which
+    /// are attributed to the logged-in user.
+    pub created_by_user_id: Option<i32>,
+    /// Days before the renewal date the contract behind the payment has to be cancelled
+    pub notice_period_days: Option<i32>,
+    /// Date the contract behind the payment renews unless cancelled
+    pub renewal_date: Option<NaiveDate>,
+    /// How the source leg is valued when the accounts use different currencies;
+    /// without it `amount` leaves the source account unchanged
+    pub fx_mode: Option<FxMode>,
+    /// Units of the source account currency per unit of the target account currency,
+    /// required by `FixedRate`
+    pub fx_rate: Option<Decimal>,
+    /// Amount in the source account currency with the same sign as `amount`,
+    /// required by `ManualAmount`
+    pub source_amount: Option<Decimal>,
+    /// How upcoming occurrences are valued (default: `Fixed`); `amount` is used until
+    /// instances are paid
+    pub amount_strategy: Option<AmountStrategy>,
+    /// Paid instances averaged by `AverageOfLastN` (default: 3)
+    pub amount_history_count: Option<i32>,
+    /// RFC 5545 RRULE for schedules `period` can't express, e.g.
+    /// `FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1` for the last business day of the
+    /// month. Occurrences then follow the rule, starting at `start_date`
+    pub rrule: Option<String>,
+    /// How monthly, quarterly, half-yearly and yearly occurrences are moved off days the
+    /// month doesn't have or weekends (default: `None`), e.g. `ClampToMonthEnd` for rent
+    /// due on the 31st
+    pub date_adjustment: Option<DateAdjustment>,
 }
 
 /// Request body for updating a recurring transaction
@@ -93,6 +232,27 @@ pub struct UpdateRecurringTransactionRequest {
     pub scenario_id: Option<i32>,
     /// Whether this is a simulated transaction (default: false)
     pub is_simulated: Option<bool>,
+    /// Days before the renewal date the contract behind the payment has to be cancelled
+    pub notice_period_days: Option<i32>,
+    /// Date the contract behind the payment renews unless cancelled
+    pub renewal_date: Option<NaiveDate>,
+    /// How the source leg is valued when the accounts use different currencies;
+    /// without it `amount` leaves the source account unchanged
+    pub fx_mode: Option<FxMode>,
+    /// Units of the source account currency per unit of the target account currency,
+    /// required by `FixedRate`
+    pub fx_rate: Option<Decimal>,
+    /// Amount in the source account currency with the same sign as `amount`,
+    /// required by `ManualAmount`
+    pub source_amount: Option<Decimal>,
+    /// How upcoming occurrences are valued
+    pub amount_strategy: Option<AmountStrategy>,
+    /// Paid instances averaged by `AverageOfLastN`
+    pub amount_history_count: Option<i32>,
+    /// RFC 5545 RRULE replacing the period; an empty string goes back to `period`
+    pub rrule: Option<String>,
+    /// How month-based occurrences are moved off missing days and weekends
+    pub date_adjustment: Option<DateAdjustment>,
 }
 
 /// Recurring transaction response model
@@ -113,6 +273,22 @@ pub struct RecurringTransactionResponse {
     pub tags: Vec<TagInfo>,
     pub scenario_id: Option<i32>,
     pub is_simulated: bool,
+    /// "Approved", "PendingApproval" or "Rejected"; only approved rules count in forecasts
+    pub approval_status: String,
+    pub created_by_user_id: Option<i32>,
+    pub reviewed_by_user_id: Option<i32>,
+    pub notice_period_days: Option<i32>,
+    pub renewal_date: Option<NaiveDate>,
+    pub fx_mode: Option<FxMode>,
+    pub fx_rate: Option<Decimal>,
+    pub source_amount: Option<Decimal>,
+    pub amount_strategy: AmountStrategy,
+    pub amount_history_count: Option<i32>,
+    /// Amount upcoming occurrences are forecast with, following `amount_strategy`
+    pub estimated_amount: Decimal,
+    /// RFC 5545 RRULE the occurrences follow instead of `period`
+    pub rrule: Option<String>,
+    pub date_adjustment: DateAdjustment,
 }
 
 impl From<recurring_transaction::Model> for RecurringTransactionResponse {
@@ -133,12 +309,27 @@ impl From<recurring_transaction::Model> for RecurringTransactionResponse {
             tags: Vec::new(), // Will be populated by with_tags method
             scenario_id: model.scenario_id,
             is_simulated: model.is_simulated,
+            approval_status: format!("{:?}", model.approval_status),
+            created_by_user_id: model.created_by_user_id,
+            reviewed_by_user_id: model.reviewed_by_user_id,
+            notice_period_days: model.notice_period_days,
+            renewal_date: model.renewal_date,
+            fx_mode: model.fx_mode.map(FxMode::from),
+            fx_rate: model.fx_rate,
+            source_amount: model.source_amount,
+            amount_strategy: model.amount_strategy.into(),
+            amount_history_count: model.amount_history_count,
+            estimated_amount: model.amount,
+            rrule: model.rrule,
+            date_adjustment: model.date_adjustment.into(),
         }
     }
 }
 
 impl RecurringTransactionResponse {
     /// Create a RecurringTransactionResponse with tags fetched from the database
+    ///
+    /// Transactions with a variable amount also get their estimate from the paid instances.
     pub async fn with_tags(
         model: recurring_transaction::Model,
         db: &sea_orm::DatabaseConnection,
@@ -146,9 +337,20 @@ impl RecurringTransactionResponse {
         // Use the get_tag_for_transaction method from the TransactionGenerator trait
         let tags = model.get_tag_for_transaction(db, true).await;
         let tag_infos: Vec<TagInfo> = tags.into_iter().map(TagInfo::from).collect();
+        let estimated = if model.amount_strategy == recurring_transaction::AmountStrategy::Fixed {
+            model.amount
+        } else {
+            let paid = recurring_transaction_instance::Entity::find()
+                .filter(recurring_transaction_instance::Column::RecurringTransactionId.eq(model.id))
+                .filter(recurring_transaction_instance::Column::Status.eq(recurring_transaction_instance::InstanceStatus::Paid))
+                .all(db)
+                .await?;
+            estimated_amount(&model, &paid)
+        };
 
         let mut response = Self::from(model);
         response.tags = tag_infos;
+        response.estimated_amount = estimated;
         Ok(response)
     }
 }
@@ -168,6 +370,10 @@ pub struct RecurringTransactionQuery {
     pub source_account_id: Option<i32>,
     /// Filter by category ID
     pub category_id: Option<i32>,
+    /// Only return recurring transactions belonging to this scenario
+    pub scenario_id: Option<i32>,
+    /// Filter by simulated flag (`false` returns baseline data only)
+    pub is_simulated: Option<bool>,
 }
 
 /// Request body for creating a recurring transaction instance
@@ -195,7 +401,20 @@ pub struct RecurringInstanceResponse {
     pub paid_date: Option<NaiveDate>,
     pub paid_amount: Option<Decimal>,
     pub reconciled_imported_transaction_id: Option<i32>,
+    /// Category of the instance, or of its recurring transaction when the instance has none
+    pub category_id: Option<i32>,
+    pub category_name: Option<String>,
+    /// Scenario the recurring transaction belongs to
+    pub scenario_id: Option<i32>,
+    pub scenario_name: Option<String>,
+    /// Whether the recurring transaction is only simulated in its scenario
+    pub is_simulated: bool,
     pub tags: Vec<TagInfo>,
+    pub notes: Option<String>,
+    /// The charge is contested and does not count towards paid totals until settled
+    pub disputed: bool,
+    /// Still pending after its due date
+    pub overdue: bool,
 }
 
 impl From<recurring_transaction_instance::Model> for RecurringInstanceResponse {
@@ -214,7 +433,15 @@ impl From<recurring_transaction_instance::Model> for RecurringInstanceResponse {
             paid_date: model.paid_date,
             paid_amount: model.paid_amount,
             reconciled_imported_transaction_id: model.reconciled_imported_transaction_id,
+            category_id: model.category_id,
+            category_name: None,
+            scenario_id: None,
+            scenario_name: None,
+            is_simulated: false,
             tags: Vec::new(), // Will be populated by with_tags method
+            notes: model.notes,
+            disputed: model.disputed,
+            overdue: model.overdue,
         }
     }
 }
@@ -225,43 +452,142 @@ impl RecurringInstanceResponse {
         model: recurring_transaction_instance::Model,
         db: &sea_orm::DatabaseConnection,
     ) -> Result<Self, sea_orm::DbErr> {
-        use sea_orm::EntityTrait;
-        use model::entities::account;
-
-        // Fetch the parent recurring transaction to get its tags
-        let parent_transaction = recurring_transaction::Entity::find_by_id(model.recurring_transaction_id)
-            .one(db)
-            .await?;
+        let mut responses = Self::with_parents(vec![model], db).await?;
+        Ok(responses.remove(0))
+    }
 
-        let mut response = Self::from(model);
+    /// Create responses for many instances, filled in from their parent recurring transactions
+    ///
+    /// Parents, accounts, categories and scenarios are loaded with one query each and tags
+    /// once per parent, however many instances there are.
+    pub async fn with_parents(
+        models: Vec<recurring_transaction_instance::Model>,
+        db: &sea_orm::DatabaseConnection,
+    ) -> Result<Vec<Self>, sea_orm::DbErr> {
+        use model::entities::{account, category, scenario};
+        use std::collections::{HashMap, HashSet};
+
+        let parent_ids: HashSet<i32> = models.iter().map(|model| model.recurring_transaction_id).collect();
+        let parents: HashMap<i32, recurring_transaction::Model> = recurring_transaction::Entity::find()
+            .filter(recurring_transaction::Column::Id.is_in(parent_ids))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|parent| (parent.id, parent))
+            .collect();
 
-        if let Some(parent) = parent_transaction {
-            // Get transaction name
-            response.recurring_transaction_name = Some(parent.name.clone());
-            response.target_account_id = Some(parent.target_account_id);
-            response.source_account_id = parent.source_account_id;
+        let account_ids: HashSet<i32> = parents
+            .values()
+            .flat_map(|parent| std::iter::once(parent.target_account_id).chain(parent.source_account_id))
+            .collect();
+        let account_names: HashMap<i32, String> = account::Entity::find()
+            .filter(account::Column::Id.is_in(account_ids))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|account| (account.id, account.name))
+            .collect();
 
-            // Fetch target account name
-            if let Ok(Some(target_account)) = account::Entity::find_by_id(parent.target_account_id).one(db).await {
-                response.target_account_name = Some(target_account.name);
-            }
+        let category_ids: HashSet<i32> = models
+            .iter()
+            .filter_map(|model| {
+                model
+                    .category_id
+                    .or_else(|| parents.get(&model.recurring_transaction_id)?.category_id)
+            })
+            .collect();
+        let category_names: HashMap<i32, String> = category::Entity::find()
+            .filter(category::Column::Id.is_in(category_ids))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|category| (category.id, category.name))
+            .collect();
 
-            // Fetch source account name if present
-            if let Some(source_id) = parent.source_account_id {
-                if let Ok(Some(source_account)) = account::Entity::find_by_id(source_id).one(db).await {
-                    response.source_account_name = Some(source_account.name);
-                }
-            }
+        let scenario_ids: HashSet<i32> = parents.values().filter_map(|parent| parent.scenario_id).collect();
+        let scenario_names: HashMap<i32, String> = scenario::Entity::find()
+            .filter(scenario::Column::Id.is_in(scenario_ids))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|scenario| (scenario.id, scenario.name))
+            .collect();
 
+        let mut tags: HashMap<i32, Vec<Tag>> = HashMap::new();
+        for parent in parents.values() {
             // Use the get_tag_for_transaction method from the TransactionGenerator trait
-            let tags = parent.get_tag_for_transaction(db, true).await;
-            response.tags = tags.into_iter().map(TagInfo::from).collect();
+            tags.insert(parent.id, parent.get_tag_for_transaction(db, true).await);
         }
 
-        Ok(response)
+        Ok(models
+            .into_iter()
+            .map(|model| {
+                let parent = parents.get(&model.recurring_transaction_id);
+                let mut response = Self::from(model);
+                if let Some(parent) = parent {
+                    response.recurring_transaction_name = Some(parent.name.clone());
+                    response.target_account_id = Some(parent.target_account_id);
+                    response.target_account_name = account_names.get(&parent.target_account_id).cloned();
+                    response.source_account_id = parent.source_account_id;
+                    response.source_account_name = parent
+                        .source_account_id
+                        .and_then(|source_id| account_names.get(&source_id).cloned());
+                    response.category_id = response.category_id.or(parent.category_id);
+                    response.scenario_id = parent.scenario_id;
+                    response.scenario_name = parent
+                        .scenario_id
+                        .and_then(|scenario_id| scenario_names.get(&scenario_id).cloned());
+                    response.is_simulated = parent.is_simulated;
+                    response.tags = tags
+                        .get(&parent.id)
+                        .into_iter()
+                        .flatten()
+                        .cloned()
+                        .map(TagInfo::from)
+                        .collect();
+                }
+                response.category_name = response
+                    .category_id
+                    .and_then(|category_id| category_names.get(&category_id).cloned());
+                response
+            })
+            .collect())
     }
 }
 
+/// Whether the recurring transaction exists and is booked on an account the caller can access.
+pub(super) async fn recurring_transaction_accessible(
+    db: &sea_orm::DatabaseConnection,
+    access: &AccountAccess,
+    recurring_transaction_id: i32,
+) -> Result<bool, sea_orm::DbErr> {
+    recurring_transaction_allows(db, access, recurring_transaction_id, AccountAccess::can_access_any).await
+}
+
+/// Whether the recurring transaction exists and is booked on an account the caller can change.
+pub(super) async fn recurring_transaction_writable(
+    db: &sea_orm::DatabaseConnection,
+    access: &AccountAccess,
+    recurring_transaction_id: i32,
+) -> Result<bool, sea_orm::DbErr> {
+    recurring_transaction_allows(db, access, recurring_transaction_id, AccountAccess::can_write_any).await
+}
+
+async fn recurring_transaction_allows(
+    db: &sea_orm::DatabaseConnection,
+    access: &AccountAccess,
+    recurring_transaction_id: i32,
+    allows: fn(&AccountAccess, i32, Option<i32>) -> bool,
+) -> Result<bool, sea_orm::DbErr> {
+    if *access == AccountAccess::Unrestricted {
+        return Ok(true);
+    }
+    Ok(recurring_transaction::Entity::find_by_id(recurring_transaction_id)
+        .one(db)
+        .await?
+        .is_some_and(|transaction| allows(access, transaction.target_account_id, transaction.source_account_id)))
+}
+
 /// Create a new recurring transaction instance
 #[utoipa::path(
     post,
@@ -271,6 +597,7 @@ impl RecurringInstanceResponse {
     responses(
         (status = 201, description = "Recurring transaction instance created successfully", body = ApiResponse<RecurringInstanceResponse>),
         (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "Recurring transaction is shared read-only", body = ErrorResponse),
         (status = 404, description = "Recurring transaction not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
@@ -279,6 +606,7 @@ impl RecurringInstanceResponse {
 pub async fn create_recurring_instance(
     Path(recurring_transaction_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
     Json(request): Json<CreateRecurringInstanceRequest>,
 ) -> Result<(StatusCode, Json<ApiResponse<RecurringInstanceResponse>>), StatusCode> {
     trace!("Entering create_recurring_instance function");
@@ -290,11 +618,15 @@ pub async fn create_recurring_instance(
         .one(&state.db)
         .await
     {
-        Ok(Some(transaction)) => {
+        Ok(Some(transaction)) if access.can_write_any(transaction.target_account_id, transaction.source_account_id) => {
             debug!("Found recurring transaction: {}", transaction.name);
             transaction
         }
-        Ok(None) => {
+        Ok(Some(transaction)) if access.can_access_any(transaction.target_account_id, transaction.source_account_id) => {
+            warn!("Recurring transaction with ID {} is read-only for user {:?}", recurring_transaction_id, access.user_id());
+            return Err(StatusCode::FORBIDDEN);
+        }
+        Ok(_) => {
             warn!("Recurring transaction with ID {} not found", recurring_transaction_id);
             return Err(StatusCode::NOT_FOUND);
         }
@@ -365,6 +697,124 @@ fn parse_recurrence_period(period_str: &str) -> Result<recurring_transaction::Re
     }
 }
 
+/// Checks that the FX handling of a transfer has what it needs to value the source leg.
+fn validate_fx(
+    fx_mode: Option<FxMode>,
+    fx_rate: Option<Decimal>,
+    source_amount: Option<Decimal>,
+    amount: Decimal,
+    source_account_id: Option<i32>,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    let problem = match fx_mode {
+        None => None,
+        Some(_) if source_account_id.is_none() => Some("FX handling only applies to transfers with a source account"),
+        Some(FxMode::ExecutionRate) => None,
+        Some(FxMode::FixedRate) if !fx_rate.is_some_and(|rate| rate > Decimal::ZERO) => {
+            Some("FixedRate needs a positive fx_rate")
+        }
+        Some(FxMode::FixedRate) => None,
+        Some(FxMode::ManualAmount)
+            if !source_amount
+                .is_some_and(|source| !source.is_zero() && source.is_sign_negative() == amount.is_sign_negative()) =>
+        {
+            Some("ManualAmount needs a non-zero source_amount with the same sign as amount")
+        }
+        Some(FxMode::ManualAmount) => None,
+    };
+    match problem {
+        None => Ok(()),
+        Some(problem) => {
+            warn!("Rejected invalid FX handling: {}", problem);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: problem.to_string(),
+                    code: "INVALID_FX_HANDLING".to_string(),
+                    success: false,
+                }),
+            ))
+        }
+    }
+}
+
+/// Most paid instances `AverageOfLastN` can average
+const MAX_AMOUNT_HISTORY_COUNT: i32 = 36;
+
+/// Length of the `rrule` column
+const MAX_RRULE_LENGTH: usize = 255;
+
+/// Checks an RFC 5545 RRULE, returning it trimmed, or `None` for an empty rule.
+fn validate_rrule(rrule: &str) -> Result<Option<String>, (StatusCode, Json<ErrorResponse>)> {
+    let rrule = rrule.trim();
+    if rrule.is_empty() {
+        return Ok(None);
+    }
+    let problem = if rrule.len() > MAX_RRULE_LENGTH {
+        format!("rrule must be at most {} characters", MAX_RRULE_LENGTH)
+    } else {
+        match rrule.parse::<RecurrenceRule>() {
+            Ok(_) => return Ok(Some(rrule.to_string())),
+            Err(e) => e,
+        }
+    };
+    warn!("Rejected invalid rrule {:?}: {}", rrule, problem);
+    Err((
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse {
+            error: format!("Invalid rrule: {}", problem),
+            code: "INVALID_RRULE".to_string(),
+            success: false,
+        }),
+    ))
+}
+
+/// Checks that a history count is only given to, and sensible for, `AverageOfLastN`.
+fn validate_amount_strategy(
+    amount_strategy: AmountStrategy,
+    amount_history_count: Option<i32>,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    let problem = match amount_history_count {
+        None => None,
+        Some(_) if amount_strategy != AmountStrategy::AverageOfLastN => {
+            Some("amount_history_count only applies to the AverageOfLastN strategy".to_string())
+        }
+        Some(count) if !(1..=MAX_AMOUNT_HISTORY_COUNT).contains(&count) => Some(format!(
+            "amount_history_count must be between 1 and {}",
+            MAX_AMOUNT_HISTORY_COUNT
+        )),
+        Some(_) => None,
+    };
+    match problem {
+        None => Ok(()),
+        Some(problem) => {
+            warn!("Rejected invalid amount strategy: {}", problem);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: problem,
+                    code: "INVALID_AMOUNT_STRATEGY".to_string(),
+                    success: false,
+                }),
+            ))
+        }
+    }
+}
+
+fn validate_notice_period(notice_period_days: Option<i32>) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if notice_period_days.is_some_and(|days| days < 0) {
+        warn!("Rejected negative notice period: {:?}", notice_period_days);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "notice_period_days must not be negative".to_string(),
+                code: "VALIDATION_ERROR".to_string(),
+                success: false,
+            }),
+        ));
+    }
+    Ok(())
+}
+
 /// Create a new recurring transaction
 #[utoipa::path(
     post,
@@ -374,12 +824,14 @@ fn parse_recurrence_period(period_str: &str) -> Result<recurring_transaction::Re
     responses(
         (status = 201, description = "Recurring transaction created successfully", body = ApiResponse<RecurringTransactionResponse>),
         (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "No access to the target or source account, or it is shared read-only", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
 #[instrument]
 pub async fn create_recurring_transaction(
     State(state): State<AppState>,
+    access: AccountAccess,
     Json(request): Json<CreateRecurringTransactionRequest>,
 ) -> Result<(StatusCode, Json<ApiResponse<RecurringTransactionResponse>>), (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering create_recurring_transaction function");
@@ -401,6 +853,60 @@ pub async fn create_recurring_transaction(
         }
     };
 
+    validate_notice_period(request.notice_period_days)?;
+    validate_fx(
+        request.fx_mode,
+        request.fx_rate,
+        request.source_amount,
+        request.amount,
+        request.source_account_id,
+    )?;
+    validate_amount_strategy(
+        request.amount_strategy.unwrap_or(AmountStrategy::Fixed),
+        request.amount_history_count,
+    )?;
+    let rrule = request.rrule.as_deref().map(validate_rrule).transpose()?.flatten();
+    for account_id in std::iter::once(request.target_account_id).chain(request.source_account_id) {
+        if !access.can_access(account_id) {
+            warn!("User {:?} has no access to account {}", access.user_id(), account_id);
+            return Err(account_forbidden(account_id));
+        }
+        if !access.can_write(account_id) {
+            warn!("Account {} is read-only for user {:?}", account_id, access.user_id());
+            return Err(account_read_only(account_id));
+        }
+    }
+    ensure_double_entry(
+        &state,
+        request.target_account_id,
+        request.source_account_id,
+        request.category_id,
+        request.fx_mode.is_some(),
+    )
+    .await?;
+
+    let approval_status = initial_approval_status(
+        &state.db,
+        request.target_account_id,
+        request.source_account_id,
+        request.amount,
+    )
+    .await
+    .map_err(|e| {
+        error!("Failed to check approval policy: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to create recurring transaction".to_string(),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    })?;
+    if approval_status == recurring_transaction::ApprovalStatus::PendingApproval {
+        info!("Recurring transaction '{}' exceeds the approval threshold, awaiting approval", request.name);
+    }
+
     // Create the new recurring transaction
     let new_transaction = recurring_transaction::ActiveModel {
         name: Set(request.name),
@@ -416,6 +922,17 @@ pub async fn create_recurring_transaction(
         category_id: Set(request.category_id),
         scenario_id: Set(request.scenario_id),
         is_simulated: Set(request.is_simulated.unwrap_or(false)),
+        approval_status: Set(approval_status),
+        created_by_user_id: Set(access.user_id().or(request.created_by_user_id)),
+        notice_period_days: Set(request.notice_period_days),
+        renewal_date: Set(request.renewal_date),
+        fx_mode: Set(request.fx_mode.map(Into::into)),
+        fx_rate: Set(request.fx_rate),
+        source_amount: Set(request.source_amount),
+        amount_strategy: Set(request.amount_strategy.unwrap_or(AmountStrategy::Fixed).into()),
+        amount_history_count: Set(request.amount_history_count),
+        rrule: Set(rrule),
+        date_adjustment: Set(request.date_adjustment.unwrap_or(DateAdjustment::None).into()),
         ..Default::default()
     };
 
@@ -465,7 +982,7 @@ pub async fn create_recurring_transaction(
     tag = "recurring-transactions",
     params(RecurringTransactionQuery),
     responses(
-        (status = 200, description = "Recurring transactions retrieved successfully", body = ApiResponse<Vec<RecurringTransactionResponse>>),
+        (status = 200, description = "Recurring transactions retrieved successfully", body = PaginatedResponse<Vec<RecurringTransactionResponse>>),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
@@ -473,7 +990,8 @@ pub async fn create_recurring_transaction(
 pub async fn get_recurring_transactions(
     Valid(Query(query)): Valid<Query<RecurringTransactionQuery>>,
     State(state): State<AppState>,
-) -> Result<(StatusCode, Json<ApiResponse<Vec<RecurringTransactionResponse>>>), (StatusCode, Json<ErrorResponse>)> {
+    access: AccountAccess,
+) -> Result<(StatusCode, Json<PaginatedResponse<Vec<RecurringTransactionResponse>>>), (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering get_recurring_transactions function");
 
     let page = query.page.unwrap_or(1);
@@ -481,7 +999,10 @@ pub async fn get_recurring_transactions(
 
     debug!("Fetching recurring transactions - page: {}, limit: {}", page, limit);
 
-    let mut query_builder = recurring_transaction::Entity::find();
+    let mut query_builder = access.filter(
+        recurring_transaction::Entity::find(),
+        [recurring_transaction::Column::TargetAccountId, recurring_transaction::Column::SourceAccountId],
+    );
 
     // Apply filters
     if let Some(target_account_id) = query.target_account_id {
@@ -496,45 +1017,51 @@ pub async fn get_recurring_transactions(
         query_builder = query_builder.filter(recurring_transaction::Column::CategoryId.eq(category_id));
     }
 
-    match query_builder
+    if let Some(scenario_id) = query.scenario_id {
+        query_builder = query_builder.filter(recurring_transaction::Column::ScenarioId.eq(scenario_id));
+    }
+
+    if let Some(is_simulated) = query.is_simulated {
+        query_builder = query_builder.filter(recurring_transaction::Column::IsSimulated.eq(is_simulated));
+    }
+
+    let database_error = |e: DbErr| {
+        error!("Failed to retrieve recurring transactions: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to retrieve recurring transactions".to_string(),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    };
+
+    let paginator = query_builder
         .order_by_asc(recurring_transaction::Column::Id)
-        .paginate(&state.db, limit)
-        .fetch_page(page - 1)
-        .await
-    {
-        Ok(transactions) => {
-            info!("Successfully retrieved {} recurring transactions", transactions.len());
-
-            let mut response_data = Vec::new();
-            for transaction in transactions {
-                match RecurringTransactionResponse::with_tags(transaction.clone(), &state.db).await {
-                    Ok(response) => response_data.push(response),
-                    Err(tag_error) => {
-                        warn!("Failed to fetch tags for recurring transaction {}: {}", transaction.id, tag_error);
-                        response_data.push(RecurringTransactionResponse::from(transaction));
-                    }
-                }
+        .paginate(&state.db, limit);
+    let total_count = paginator.num_items().await.map_err(database_error)?;
+    let transactions = paginator.fetch_page(page - 1).await.map_err(database_error)?;
+    info!("Successfully retrieved {} of {} recurring transactions", transactions.len(), total_count);
+
+    let mut response_data = Vec::new();
+    for transaction in transactions {
+        match RecurringTransactionResponse::with_tags(transaction.clone(), &state.db).await {
+            Ok(response) => response_data.push(response),
+            Err(tag_error) => {
+                warn!("Failed to fetch tags for recurring transaction {}: {}", transaction.id, tag_error);
+                response_data.push(RecurringTransactionResponse::from(transaction));
             }
-
-            let response = ApiResponse {
-                data: response_data,
-                message: "Recurring transactions retrieved successfully".to_string(),
-                success: true,
-            };
-            Ok((StatusCode::OK, Json(response)))
-        }
-        Err(e) => {
-            error!("Failed to retrieve recurring transactions: {}", e);
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: "Failed to retrieve recurring transactions".to_string(),
-                    code: "DATABASE_ERROR".to_string(),
-                    success: false,
-                }),
-            ))
         }
     }
+
+    let response = PaginatedResponse {
+        data: response_data,
+        pagination: Pagination::new(total_count, page, limit),
+        message: "Recurring transactions retrieved successfully".to_string(),
+        success: true,
+    };
+    Ok((StatusCode::OK, Json(response)))
 }
 
 /// Get a specific recurring transaction by ID
@@ -552,6 +1079,7 @@ pub async fn get_recurring_transactions(
 pub async fn get_recurring_transaction(
     Path(recurring_transaction_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
 ) -> Result<(StatusCode, Json<ApiResponse<RecurringTransactionResponse>>), (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering get_recurring_transaction function");
     debug!("Fetching recurring transaction with ID: {}", recurring_transaction_id);
@@ -560,7 +1088,7 @@ pub async fn get_recurring_transaction(
         .one(&state.db)
         .await
     {
-        Ok(Some(transaction)) => {
+        Ok(Some(transaction)) if access.can_access_any(transaction.target_account_id, transaction.source_account_id) => {
             info!("Successfully retrieved recurring transaction: {}", transaction.name);
 
             match RecurringTransactionResponse::with_tags(transaction.clone(), &state.db).await {
@@ -583,7 +1111,7 @@ pub async fn get_recurring_transaction(
                 }
             }
         }
-        Ok(None) => {
+        Ok(_) => {
             warn!("Recurring transaction with ID {} not found", recurring_transaction_id);
             Err((
                 StatusCode::NOT_FOUND,
@@ -617,6 +1145,7 @@ pub async fn get_recurring_transaction(
     responses(
         (status = 200, description = "Recurring transaction updated successfully", body = ApiResponse<RecurringTransactionResponse>),
         (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "Recurring transaction or the new target or source account is shared read-only or not accessible", body = ErrorResponse),
         (status = 404, description = "Recurring transaction not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
@@ -625,6 +1154,7 @@ pub async fn get_recurring_transaction(
 pub async fn update_recurring_transaction(
     Path(recurring_transaction_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
     Json(request): Json<UpdateRecurringTransactionRequest>,
 ) -> Result<(StatusCode, Json<ApiResponse<RecurringTransactionResponse>>), (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering update_recurring_transaction function");
@@ -635,8 +1165,10 @@ pub async fn update_recurring_transaction(
         .one(&state.db)
         .await
     {
-        Ok(Some(transaction)) => transaction,
-        Ok(None) => {
+        Ok(Some(transaction)) if access.can_access_any(transaction.target_account_id, transaction.source_account_id) => {
+            transaction
+        }
+        Ok(_) => {
             warn!("Recurring transaction with ID {} not found", recurring_transaction_id);
             return Err((
                 StatusCode::NOT_FOUND,
@@ -680,6 +1212,46 @@ pub async fn update_recurring_transaction(
         None
     };
 
+    validate_notice_period(request.notice_period_days)?;
+    validate_fx(
+        request.fx_mode.or(existing_transaction.fx_mode.map(FxMode::from)),
+        request.fx_rate.or(existing_transaction.fx_rate),
+        request.source_amount.or(existing_transaction.source_amount),
+        request.amount.unwrap_or(existing_transaction.amount),
+        request.source_account_id.or(existing_transaction.source_account_id),
+    )?;
+    // Switching to another strategy drops the history count of AverageOfLastN
+    let amount_strategy = request
+        .amount_strategy
+        .unwrap_or(existing_transaction.amount_strategy.into());
+    let amount_history_count = request.amount_history_count.or(existing_transaction
+        .amount_history_count
+        .filter(|_| amount_strategy == AmountStrategy::AverageOfLastN));
+    validate_amount_strategy(amount_strategy, amount_history_count)?;
+    let rrule = request.rrule.as_deref().map(validate_rrule).transpose()?;
+    access.ensure_can_write_any(existing_transaction.target_account_id, existing_transaction.source_account_id)?;
+    if let Some(account_id) = request
+        .target_account_id
+        .into_iter()
+        .chain(request.source_account_id)
+        .find(|account_id| !access.can_write(*account_id))
+    {
+        warn!("User {:?} can't book on account {}", access.user_id(), account_id);
+        return Err(if access.can_access(account_id) {
+            account_read_only(account_id)
+        } else {
+            account_forbidden(account_id)
+        });
+    }
+    ensure_double_entry(
+        &state,
+        request.target_account_id.unwrap_or(existing_transaction.target_account_id),
+        request.source_account_id.or(existing_transaction.source_account_id),
+        request.category_id.or(existing_transaction.category_id),
+        request.fx_mode.is_some() || existing_transaction.fx_mode.is_some(),
+    )
+    .await?;
+
     // Create the update model
     let mut update_model: recurring_transaction::ActiveModel = existing_transaction.into();
 
@@ -722,6 +1294,31 @@ pub async fn update_recurring_transaction(
     if let Some(is_simulated) = request.is_simulated {
         update_model.is_simulated = Set(is_simulated);
     }
+    if let Some(notice_period_days) = request.notice_period_days {
+        update_model.notice_period_days = Set(Some(notice_period_days));
+    }
+    if let Some(renewal_date) = request.renewal_date {
+        update_model.renewal_date = Set(Some(renewal_date));
+    }
+    if let Some(fx_mode) = request.fx_mode {
+        update_model.fx_mode = Set(Some(fx_mode.into()));
+    }
+    if let Some(fx_rate) = request.fx_rate {
+        update_model.fx_rate = Set(Some(fx_rate));
+    }
+    if let Some(source_amount) = request.source_amount {
+        update_model.source_amount = Set(Some(source_amount));
+    }
+    if request.amount_strategy.is_some() || request.amount_history_count.is_some() {
+        update_model.amount_strategy = Set(amount_strategy.into());
+        update_model.amount_history_count = Set(amount_history_count);
+    }
+    if let Some(rrule) = rrule {
+        update_model.rrule = Set(rrule);
+    }
+    if let Some(date_adjustment) = request.date_adjustment {
+        update_model.date_adjustment = Set(date_adjustment.into());
+    }
 
     match update_model.update(&state.db).await {
         Ok(updated_transaction) => {
@@ -768,6 +1365,7 @@ pub async fn update_recurring_transaction(
     tag = "recurring-transactions",
     responses(
         (status = 200, description = "Recurring transaction deleted successfully", body = ApiResponse<String>),
+        (status = 403, description = "Recurring transaction is shared read-only", body = ErrorResponse),
         (status = 404, description = "Recurring transaction not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
@@ -776,6 +1374,7 @@ pub async fn update_recurring_transaction(
 pub async fn delete_recurring_transaction(
     Path(recurring_transaction_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
 ) -> Result<(StatusCode, Json<ApiResponse<String>>), (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering delete_recurring_transaction function");
     debug!("Deleting recurring transaction with ID: {}", recurring_transaction_id);
@@ -785,7 +1384,8 @@ pub async fn delete_recurring_transaction(
         .one(&state.db)
         .await
     {
-        Ok(Some(_)) => {
+        Ok(Some(transaction)) if access.can_access_any(transaction.target_account_id, transaction.source_account_id) => {
+            access.ensure_can_write_any(transaction.target_account_id, transaction.source_account_id)?;
             // Transaction exists, proceed with deletion
             match recurring_transaction::Entity::delete_by_id(recurring_transaction_id)
                 .exec(&state.db)
@@ -813,7 +1413,7 @@ pub async fn delete_recurring_transaction(
                 }
             }
         }
-        Ok(None) => {
+        Ok(_) => {
             warn!("Recurring transaction with ID {} not found", recurring_transaction_id);
             Err((
                 StatusCode::NOT_FOUND,
@@ -849,6 +1449,10 @@ pub struct MissingInstanceInfo {
     pub is_pending: bool,
     /// If the instance exists (pending), this is its ID
     pub instance_id: Option<i32>,
+    /// Days since the due date
+    pub overdue_days: i64,
+    /// "Due", "Notice", "Warning" or "Critical", escalating as `overdue_days` crosses the thresholds
+    pub severity: String,
 }
 
 /// Query parameters for getting missing instances
@@ -860,6 +1464,8 @@ pub struct MissingInstancesQuery {
     pub end_date: Option<NaiveDate>,
     /// Optional recurring transaction ID to filter by
     pub recurring_transaction_id: Option<i32>,
+    /// Comma separated days overdue at which severity escalates (defaults to "3,7,30")
+    pub overdue_thresholds: Option<String>,
 }
 
 /// Get missing instances for recurring transactions
@@ -870,6 +1476,7 @@ pub struct MissingInstancesQuery {
     params(MissingInstancesQuery),
     responses(
         (status = 200, description = "Missing instances retrieved successfully", body = ApiResponse<Vec<MissingInstanceInfo>>),
+        (status = 400, description = "Invalid overdue thresholds", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
@@ -877,19 +1484,40 @@ pub struct MissingInstancesQuery {
 pub async fn get_missing_instances(
     Valid(Query(query)): Valid<Query<MissingInstancesQuery>>,
     State(state): State<AppState>,
+    access: AccountAccess,
+    timezone: UserTimezone,
 ) -> Result<(StatusCode, Json<ApiResponse<Vec<MissingInstanceInfo>>>), (StatusCode, Json<ErrorResponse>)> {
     trace!("Fetching missing instances with query: {:?}", query);
 
-    let today = chrono::Local::now().date_naive();
+    let today = timezone.today();
     let start_date = query.start_date.unwrap_or_else(|| today - chrono::Duration::days(31 * 16));
     let end_date = query.end_date.unwrap_or(today + chrono::Duration::days(5));
+    let thresholds = match query.overdue_thresholds.as_deref().map(OverdueThresholds::parse) {
+        None => OverdueThresholds::default(),
+        Some(Ok(thresholds)) => thresholds,
+        Some(Err(e)) => {
+            warn!("Invalid overdue thresholds: {}", e);
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: e,
+                    code: "INVALID_THRESHOLDS".to_string(),
+                    success: false,
+                }),
+            ));
+        }
+    };
+    let overdue = |due_date: NaiveDate| {
+        let days = overdue_days(due_date, today);
+        (days, format!("{:?}", thresholds.severity(days)))
+    };
 
     // Fetch recurring transactions
     let recurring_transactions = if let Some(rt_id) = query.recurring_transaction_id {
         // Fetch specific recurring transaction
         match recurring_transaction::Entity::find_by_id(rt_id).one(&state.db).await {
-            Ok(Some(rt)) => vec![rt],
-            Ok(None) => {
+            Ok(Some(rt)) if access.can_access_any(rt.target_account_id, rt.source_account_id) => vec![rt],
+            Ok(_) => {
                 return Err((
                     StatusCode::NOT_FOUND,
                     Json(ErrorResponse {
@@ -913,7 +1541,14 @@ pub async fn get_missing_instances(
         }
     } else {
         // Fetch all recurring transactions
-        match recurring_transaction::Entity::find().all(&state.db).await {
+        match access
+            .filter(
+                recurring_transaction::Entity::find(),
+                [recurring_transaction::Column::TargetAccountId, recurring_transaction::Column::SourceAccountId],
+            )
+            .all(&state.db)
+            .await
+        {
             Ok(rts) => rts,
             Err(e) => {
                 error!("Database error while fetching recurring transactions: {}", e);
@@ -929,20 +1564,28 @@ pub async fn get_missing_instances(
         }
     };
 
+    let holidays = match load_holiday_calendar(&state.db).await {
+        Ok(holidays) => holidays,
+        Err(e) => {
+            error!("Database error while loading holidays: {}", e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Failed to load holidays".to_string(),
+                    code: "DATABASE_ERROR".to_string(),
+                    success: false,
+                }),
+            ));
+        }
+    };
+
     let mut missing_instances = Vec::new();
 
     // For each recurring transaction, generate expected dates and check for missing instances
     for rt in recurring_transactions {
-        // Use the transaction generator to get all expected transaction dates
-        let transactions = rt.generate_transactions(start_date, end_date, today, &state.db).await;
-
-        // Get the unique dates from the transactions (for the target account)
-        let mut expected_dates: std::collections::HashSet<NaiveDate> = std::collections::HashSet::new();
-        for transaction in transactions {
-            if transaction.account() == rt.target_account_id {
-                expected_dates.insert(transaction.date());
-            }
-        }
+        // All expected transaction dates, following the rrule when the transaction has one
+        let expected_dates: std::collections::HashSet<NaiveDate> =
+            recurring_transaction_occurrences(&rt, &holidays, start_date, end_date).into_iter().collect();
 
         // Fetch existing instances for this recurring transaction in the date range
         let existing_instances = match recurring_transaction_instance::Entity::find()
@@ -968,6 +1611,7 @@ pub async fn get_missing_instances(
         // Find missing dates or pending instances (expected but not paid/skipped)
         for expected_date in expected_dates {
             if expected_date <= today {
+                let (overdue_days, severity) = overdue(expected_date);
                 match existing_map.get(&expected_date) {
                     None => {
                         // Truly missing - no instance exists
@@ -978,6 +1622,8 @@ pub async fn get_missing_instances(
                             expected_amount: rt.amount,
                             is_pending: false,
                             instance_id: None,
+                            overdue_days,
+                            severity,
                         });
                     }
                     Some((recurring_transaction_instance::InstanceStatus::Pending, id)) => {
@@ -989,6 +1635,8 @@ pub async fn get_missing_instances(
                             expected_amount: rt.amount,
                             is_pending: true,
                             instance_id: Some(*id),
+                            overdue_days,
+                            severity,
                         });
                     }
                     Some((recurring_transaction_instance::InstanceStatus::Paid, _)) => {
@@ -1049,6 +1697,7 @@ pub struct BulkInstanceItem {
 #[instrument(skip(state))]
 pub async fn bulk_create_instances(
     State(state): State<AppState>,
+    access: AccountAccess,
     Valid(Json(request)): Valid<Json<BulkCreateInstancesRequest>>,
 ) -> Result<(StatusCode, Json<ApiResponse<BulkCreateInstancesResponse>>), (StatusCode, Json<ErrorResponse>)> {
     trace!("Bulk creating/updating {} instances", request.instances.len());
@@ -1064,6 +1713,17 @@ pub async fn bulk_create_instances(
             if request.mark_as_paid {
                 match recurring_transaction_instance::Entity::find_by_id(instance_id).one(&state.db).await {
                     Ok(Some(instance)) => {
+                        match recurring_transaction_writable(&state.db, &access, instance.recurring_transaction_id).await {
+                            Ok(true) => {}
+                            Ok(false) => {
+                                warn!("Instance {} not found for update", instance_id);
+                                continue;
+                            }
+                            Err(e) => {
+                                error!("Database error while checking access to instance {}: {}", instance_id, e);
+                                continue;
+                            }
+                        }
                         let mut active_model: recurring_transaction_instance::ActiveModel = instance.into();
                         active_model.status = Set(recurring_transaction_instance::InstanceStatus::Paid);
                         active_model.paid_date = Set(Some(item.due_date));
@@ -1093,8 +1753,8 @@ pub async fn bulk_create_instances(
             // Create new instance
             // First, fetch the recurring transaction to get the amount
             let recurring_transaction = match recurring_transaction::Entity::find_by_id(item.recurring_transaction_id).one(&state.db).await {
-                Ok(Some(rt)) => rt,
-                Ok(None) => {
+                Ok(Some(rt)) if access.can_write_any(rt.target_account_id, rt.source_account_id) => rt,
+                Ok(_) => {
                     warn!("Recurring transaction {} not found", item.recurring_transaction_id);
                     continue;
                 }
@@ -1153,3 +1813,298 @@ pub struct BulkCreateInstancesResponse {
     pub updated_count: usize,
     pub skipped_count: usize,
 }
+
+/// Query parameters for the due-day profile
+#[derive(Debug, Deserialize, IntoParams, Validate)]
+pub struct DueProfileQuery {
+    /// First day of the profiled window (defaults to today)
+    pub start_date: Option<NaiveDate>,
+    /// Number of months to profile (defaults to 12)
+    #[validate(range(min = 1, max = 60))]
+    pub months: Option<u32>,
+    /// Only include recurring transactions of this account
+    pub account_id: Option<i32>,
+}
+
+/// Average monthly contribution of a recurring transaction to a due day
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DuePaymentInfo {
+    pub recurring_transaction_id: i32,
+    pub name: String,
+    pub monthly_average: Decimal,
+}
+
+/// Expected outflow on one day of the month
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DueDayEntry {
+    /// Day of the month (1-31)
+    pub day: u32,
+    /// Average expected outflow per month on this day (positive)
+    pub monthly_average: Decimal,
+    /// Share of the total monthly outflow due on this day (0.0 - 1.0)
+    pub share: f64,
+    /// Number of occurrences on this day within the profiled window
+    pub occurrences: usize,
+    /// Recurring transactions due on this day, largest first
+    pub payments: Vec<DuePaymentInfo>,
+}
+
+/// Expected recurring outflow aggregated by day of month
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RecurringDueProfileResponse {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub months: u32,
+    /// Average expected recurring outflow per month
+    pub total_monthly_outflow: Decimal,
+    /// Day with the highest expected outflow, if there is any outflow at all
+    pub peak_day: Option<u32>,
+    /// One entry per day of the month (1-31)
+    pub days: Vec<DueDayEntry>,
+}
+
+/// Get the due-day profile of recurring expenses
+///
+/// Aggregates the expected outflow of all non-simulated recurring expenses per day of
+/// the month over the profiled window, showing how payments cluster within a month.
+#[utoipa::path(
+    get,
+    path = "/api/v1/recurring-transactions/due-profile",
+    tag = "recurring-transactions",
+    params(DueProfileQuery),
+    responses(
+        (status = 200, description = "Due-day profile retrieved successfully", body = ApiResponse<RecurringDueProfileResponse>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_recurring_due_profile(
+    Valid(Query(query)): Valid<Query<DueProfileQuery>>,
+    State(state): State<AppState>,
+    access: AccountAccess,
+    timezone: UserTimezone,
+) -> Result<Json<ApiResponse<RecurringDueProfileResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Computing recurring due profile with query: {:?}", query);
+
+    let start_date = query.start_date.unwrap_or_else(|| timezone.today());
+    let months = query.months.unwrap_or(12);
+    let end_date = start_date
+        .checked_add_months(chrono::Months::new(months))
+        .and_then(|date| date.pred_opt())
+        .unwrap_or(start_date);
+
+    let mut select = access
+        .filter(
+            recurring_transaction::Entity::find(),
+            [recurring_transaction::Column::TargetAccountId, recurring_transaction::Column::SourceAccountId],
+        )
+        .filter(recurring_transaction::Column::IsSimulated.eq(false))
+        .filter(recurring_transaction::Column::Amount.lt(Decimal::ZERO));
+    if let Some(account_id) = query.account_id {
+        select = select.filter(recurring_transaction::Column::TargetAccountId.eq(account_id));
+    }
+    let recurring_transactions = select.all(&state.db).await.map_err(|e| {
+        error!("Database error while fetching recurring transactions: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to fetch recurring transactions".to_string(),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    })?;
+    let holidays = load_holiday_calendar(&state.db).await.map_err(|e| {
+        error!("Database error while loading holidays: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to load holidays".to_string(),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    })?;
+
+    let occurrences: Vec<DueOccurrence> = recurring_transactions
+        .iter()
+        .flat_map(|rt| {
+            recurring_transaction_occurrences(rt, &holidays, start_date, end_date)
+                .into_iter()
+                .map(move |date| DueOccurrence {
+                    source_id: rt.id,
+                    name: rt.name.clone(),
+                    date,
+                    amount: -rt.amount,
+                })
+        })
+        .collect();
+    debug!(
+        "Profiling {} occurrences of {} recurring transactions",
+        occurrences.len(),
+        recurring_transactions.len()
+    );
+
+    let profile = due_day_profile(&occurrences, months);
+    let total_monthly_outflow: Decimal = profile.iter().map(|day| day.monthly_average).sum();
+    let peak_day = profile
+        .iter()
+        .filter(|day| day.monthly_average > Decimal::ZERO)
+        .max_by(|a, b| a.monthly_average.cmp(&b.monthly_average).then(b.day.cmp(&a.day)))
+        .map(|day| day.day);
+
+    let days = profile
+        .into_iter()
+        .map(|day| DueDayEntry {
+            day: day.day,
+            monthly_average: day.monthly_average,
+            share: if total_monthly_outflow > Decimal::ZERO {
+                (day.monthly_average / total_monthly_outflow).to_f64().unwrap_or(0.0)
+            } else {
+                0.0
+            },
+            occurrences: day.occurrences,
+            payments: day
+                .payments
+                .into_iter()
+                .map(|payment| DuePaymentInfo {
+                    recurring_transaction_id: payment.source_id,
+                    name: payment.name,
+                    monthly_average: payment.monthly_average,
+                })
+                .collect(),
+        })
+        .collect();
+
+    info!(
+        "Computed recurring due profile: {} per month, peak day {:?}",
+        total_monthly_outflow, peak_day
+    );
+
+    Ok(Json(ApiResponse {
+        data: RecurringDueProfileResponse {
+            start_date,
+            end_date,
+            months,
+            total_monthly_outflow,
+            peak_day,
+            days,
+        },
+        message: "Recurring due profile retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Query parameters for contract reminders
+#[derive(Debug, Deserialize, IntoParams, Validate)]
+pub struct ContractRemindersQuery {
+    /// Remind about deadlines up to this many days ahead (defaults to 30)
+    #[validate(range(min = 0, max = 366))]
+    pub within_days: Option<i64>,
+    /// Only include recurring transactions of this account
+    pub account_id: Option<i32>,
+}
+
+/// A contract that has to be cancelled soon to avoid its renewal
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ContractReminderResponse {
+    pub recurring_transaction_id: i32,
+    pub name: String,
+    pub amount: Decimal,
+    pub period: String,
+    pub target_account_id: i32,
+    pub renewal_date: NaiveDate,
+    pub notice_period_days: i32,
+    /// Last day the contract can be cancelled
+    pub cancellation_deadline: NaiveDate,
+    /// Days left until the cancellation deadline; zero on the deadline itself
+    pub days_until_deadline: i64,
+}
+
+/// Get contract cancellation reminders
+///
+/// Lists recurring transactions with a renewal date and notice period whose
+/// cancellation deadline falls within the next `within_days` days, most urgent first.
+/// Meant to be polled by notification tooling.
+#[utoipa::path(
+    get,
+    path = "/api/v1/recurring-transactions/contract-reminders",
+    tag = "recurring-transactions",
+    params(ContractRemindersQuery),
+    responses(
+        (status = 200, description = "Contract reminders retrieved successfully", body = ApiResponse<Vec<ContractReminderResponse>>),
+        (status = 400, description = "Invalid query parameters", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_contract_reminders(
+    Valid(Query(query)): Valid<Query<ContractRemindersQuery>>,
+    State(state): State<AppState>,
+    access: AccountAccess,
+    timezone: UserTimezone,
+) -> Result<Json<ApiResponse<Vec<ContractReminderResponse>>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Fetching contract reminders with query: {:?}", query);
+
+    let today = timezone.today();
+    let within_days = query.within_days.unwrap_or(30);
+
+    let mut select = access
+        .filter(
+            recurring_transaction::Entity::find(),
+            [recurring_transaction::Column::TargetAccountId, recurring_transaction::Column::SourceAccountId],
+        )
+        .filter(recurring_transaction::Column::IsSimulated.eq(false))
+        .filter(recurring_transaction::Column::ApprovalStatus.ne(recurring_transaction::ApprovalStatus::Rejected))
+        .filter(recurring_transaction::Column::RenewalDate.is_not_null())
+        .filter(recurring_transaction::Column::NoticePeriodDays.is_not_null());
+    if let Some(account_id) = query.account_id {
+        select = select.filter(recurring_transaction::Column::TargetAccountId.eq(account_id));
+    }
+    let contracts = select.all(&state.db).await.map_err(|e| {
+        error!("Database error while fetching contracts: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to fetch recurring transactions".to_string(),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    })?;
+    debug!("Checking cancellation deadlines of {} contracts", contracts.len());
+
+    let by_id: std::collections::HashMap<i32, &recurring_transaction::Model> =
+        contracts.iter().map(|contract| (contract.id, contract)).collect();
+    let reminders: Vec<ContractReminderResponse> = upcoming_cancellation_deadlines(
+        contracts.iter().filter_map(|contract| {
+            Some((contract.id, contract.renewal_date?, contract.notice_period_days?))
+        }),
+        today,
+        within_days,
+    )
+    .into_iter()
+    .filter_map(|reminder| {
+        let contract = by_id.get(&reminder.source_id)?;
+        Some(ContractReminderResponse {
+            recurring_transaction_id: contract.id,
+            name: contract.name.clone(),
+            amount: contract.amount,
+            period: format!("{:?}", contract.period),
+            target_account_id: contract.target_account_id,
+            renewal_date: reminder.renewal_date,
+            notice_period_days: contract.notice_period_days?,
+            cancellation_deadline: reminder.cancellation_deadline,
+            days_until_deadline: reminder.days_until_deadline,
+        })
+    })
+    .collect();
+
+    info!("Found {} contracts to cancel within {} days", reminders.len(), within_days);
+
+    Ok(Json(ApiResponse {
+        data: reminders,
+        message: "Contract reminders retrieved successfully".to_string(),
+        success: true,
+    }))
+}
@@ -0,0 +1,285 @@
+use crate::handlers::transactions::recurring::RecurringTransactionResponse;
+use crate::helpers::access::AccountAccess;
+use crate::helpers::auth::AuthenticatedUser;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use model::entities::{account, account_allowed_user, recurring_transaction};
+use rust_decimal::Decimal;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter, Set};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, instrument, warn};
+use utoipa::ToSchema;
+
+use recurring_transaction::ApprovalStatus;
+
+/// Request body for configuring the approval policy of a shared account
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct RecurringApprovalPolicyRequest {
+    /// New recurring transactions whose absolute amount reaches this value need
+    /// approval by another member; `null` turns approvals off
+    pub recurring_approval_threshold: Option<Decimal>,
+}
+
+/// Approval policy of an account
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RecurringApprovalPolicyResponse {
+    pub account_id: i32,
+    pub recurring_approval_threshold: Option<Decimal>,
+}
+
+fn approval_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// Approval status a new recurring transaction starts in.
+///
+/// The rule waits for approval when any account it posts to has a threshold
+/// and the absolute amount reaches it.
+pub async fn initial_approval_status(
+    db: &DatabaseConnection,
+    target_account_id: i32,
+    source_account_id: Option<i32>,
+    amount: Decimal,
+) -> Result<ApprovalStatus, DbErr> {
+    let account_ids: Vec<i32> = std::iter::once(target_account_id).chain(source_account_id).collect();
+    let accounts = account::Entity::find()
+        .filter(account::Column::Id.is_in(account_ids))
+        .all(db)
+        .await?;
+
+    let needs_approval = accounts.iter().any(|account| {
+        account
+            .recurring_approval_threshold
+            .is_some_and(|threshold| amount.abs() >= threshold)
+    });
+    Ok(if needs_approval {
+        ApprovalStatus::PendingApproval
+    } else {
+        ApprovalStatus::Approved
+    })
+}
+
+/// Set the recurring transaction approval policy of an account
+///
+/// Only shared accounts (with at least one allowed user besides the owner) can
+/// require approvals, since someone other than the creator has to review the rule.
+/// Existing recurring transactions keep their status. Only the account's owner may
+/// change the policy.
+#[utoipa::path(
+    put,
+    path = "/api/v1/accounts/{account_id}/recurring-approval-policy",
+    tag = "recurring-transactions",
+    params(
+        ("account_id" = i32, Path, description = "Account ID")
+    ),
+    request_body = RecurringApprovalPolicyRequest,
+    responses(
+        (status = 200, description = "Approval policy updated", body = ApiResponse<RecurringApprovalPolicyResponse>),
+        (status = 400, description = "Negative threshold or account not shared", body = ErrorResponse),
+        (status = 403, description = "Account is only shared with the caller", body = ErrorResponse),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn set_recurring_approval_policy(
+    Path(account_id): Path<i32>,
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Json(request): Json<RecurringApprovalPolicyRequest>,
+) -> Result<Json<ApiResponse<RecurringApprovalPolicyResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("Setting recurring approval threshold of account {} to {:?}", account_id, request.recurring_approval_threshold);
+    access.ensure_can_manage(account_id)?;
+
+    let database_error = |e: DbErr| {
+        error!("Database error while updating approval policy of account {}: {}", account_id, e);
+        approval_error(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", "Failed to update approval policy".to_string())
+    };
+
+    let account = account::Entity::find_by_id(account_id)
+        .one(&state.db)
+        .await
+        .map_err(database_error)?
+        .ok_or_else(|| {
+            warn!("Account {} not found", account_id);
+            approval_error(StatusCode::NOT_FOUND, "NOT_FOUND", format!("Account with id {} not found", account_id))
+        })?;
+
+    if let Some(threshold) = request.recurring_approval_threshold {
+        if threshold.is_sign_negative() {
+            return Err(approval_error(
+                StatusCode::BAD_REQUEST,
+                "INVALID_THRESHOLD",
+                "Approval threshold must not be negative".to_string(),
+            ));
+        }
+        let members = account_allowed_user::Entity::find()
+            .filter(account_allowed_user::Column::AccountId.eq(account_id))
+            .count(&state.db)
+            .await
+            .map_err(database_error)?;
+        if members == 0 {
+            warn!("Account {} has no other members, refusing approval policy", account_id);
+            return Err(approval_error(
+                StatusCode::BAD_REQUEST,
+                "ACCOUNT_NOT_SHARED",
+                "Approvals need an account shared with at least one other user".to_string(),
+            ));
+        }
+    }
+
+    let mut active: account::ActiveModel = account.into();
+    active.recurring_approval_threshold = Set(request.recurring_approval_threshold);
+    let updated = active.update(&state.db).await.map_err(database_error)?;
+    info!("Updated recurring approval threshold of account {}", account_id);
+
+    Ok(Json(ApiResponse {
+        data: RecurringApprovalPolicyResponse {
+            account_id: updated.id,
+            recurring_approval_threshold: updated.recurring_approval_threshold,
+        },
+        message: "Approval policy updated successfully".to_string(),
+        success: true,
+    }))
+}
+
+async fn review_recurring_transaction(
+    state: &AppState,
+    access: &AccountAccess,
+    id: i32,
+    reviewer_id: i32,
+    decision: ApprovalStatus,
+) -> Result<RecurringTransactionResponse, (StatusCode, Json<ErrorResponse>)> {
+    let database_error = |e: DbErr| {
+        error!("Database error while reviewing recurring transaction {}: {}", id, e);
+        approval_error(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", "Failed to review recurring transaction".to_string())
+    };
+
+    let transaction = recurring_transaction::Entity::find_by_id(id)
+        .one(&state.db)
+        .await
+        .map_err(database_error)?
+        .filter(|transaction| access.can_access_any(transaction.target_account_id, transaction.source_account_id))
+        .ok_or_else(|| {
+            warn!("Recurring transaction {} not found", id);
+            approval_error(StatusCode::NOT_FOUND, "NOT_FOUND", format!("Recurring transaction with id {} not found", id))
+        })?;
+
+    if transaction.approval_status != ApprovalStatus::PendingApproval {
+        warn!("Recurring transaction {} is not pending approval ({:?})", id, transaction.approval_status);
+        return Err(approval_error(
+            StatusCode::CONFLICT,
+            "NOT_PENDING",
+            format!("Recurring transaction is already {:?}", transaction.approval_status),
+        ));
+    }
+    if transaction.created_by_user_id == Some(reviewer_id) {
+        warn!("User {} tried to review their own recurring transaction {}", reviewer_id, id);
+        return Err(approval_error(
+            StatusCode::FORBIDDEN,
+            "SELF_APPROVAL",
+            "Recurring transactions must be reviewed by another member".to_string(),
+        ));
+    }
+
+    if !access.can_write(transaction.target_account_id) {
+        warn!("User {} is not a member of account {}", reviewer_id, transaction.target_account_id);
+        return Err(approval_error(
+            StatusCode::FORBIDDEN,
+            "NOT_A_MEMBER",
+            "Only members of the account can review its recurring transactions".to_string(),
+        ));
+    }
+
+    let mut active: recurring_transaction::ActiveModel = transaction.into();
+    active.approval_status = Set(decision);
+    active.reviewed_by_user_id = Set(Some(reviewer_id));
+    let updated = active.update(&state.db).await.map_err(database_error)?;
+    info!("Recurring transaction {} marked {:?} by user {}", id, decision, reviewer_id);
+
+    RecurringTransactionResponse::with_tags(updated, &state.db)
+        .await
+        .map_err(database_error)
+}
+
+/// Approve a recurring transaction awaiting approval
+///
+/// The authenticated caller reviews the rule. They must be able to book on the target
+/// account and must not be the member who created the rule. Approved rules start counting in forecasts.
+#[utoipa::path(
+    post,
+    path = "/api/v1/recurring-transactions/{recurring_transaction_id}/approve",
+    tag = "recurring-transactions",
+    params(
+        ("recurring_transaction_id" = i32, Path, description = "Recurring transaction ID")
+    ),
+    responses(
+        (status = 200, description = "Recurring transaction approved", body = ApiResponse<RecurringTransactionResponse>),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 403, description = "Reviewer is the creator or not a member of the account", body = ErrorResponse),
+        (status = 404, description = "Recurring transaction not found", body = ErrorResponse),
+        (status = 409, description = "Recurring transaction is not pending approval", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn approve_recurring_transaction(
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    access: AccountAccess,
+) -> Result<Json<ApiResponse<RecurringTransactionResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("User {} approving recurring transaction {}", auth.user_id, id);
+    let transaction = review_recurring_transaction(&state, &access, id, auth.user_id, ApprovalStatus::Approved).await?;
+    Ok(Json(ApiResponse {
+        data: transaction,
+        message: "Recurring transaction approved".to_string(),
+        success: true,
+    }))
+}
+
+/// Reject a recurring transaction awaiting approval
+///
+/// Rejected rules stay visible but never count in balances or forecasts.
+#[utoipa::path(
+    post,
+    path = "/api/v1/recurring-transactions/{recurring_transaction_id}/reject",
+    tag = "recurring-transactions",
+    params(
+        ("recurring_transaction_id" = i32, Path, description = "Recurring transaction ID")
+    ),
+    responses(
+        (status = 200, description = "Recurring transaction rejected", body = ApiResponse<RecurringTransactionResponse>),
+        (status = 401, description = "Not authenticated", body = ErrorResponse),
+        (status = 403, description = "Reviewer is the creator or not a member of the account", body = ErrorResponse),
+        (status = 404, description = "Recurring transaction not found", body = ErrorResponse),
+        (status = 409, description = "Recurring transaction is not pending approval", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn reject_recurring_transaction(
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    access: AccountAccess,
+) -> Result<Json<ApiResponse<RecurringTransactionResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("User {} rejecting recurring transaction {}", auth.user_id, id);
+    let transaction = review_recurring_transaction(&state, &access, id, auth.user_id, ApprovalStatus::Rejected).await?;
+    Ok(Json(ApiResponse {
+        data: transaction,
+        message: "Recurring transaction rejected".to_string(),
+        success: true,
+    }))
+}
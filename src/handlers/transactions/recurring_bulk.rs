@@ -0,0 +1,303 @@
+use crate::handlers::double_entry::ensure_double_entry;
+use crate::helpers::access::{account_forbidden, account_read_only, AccountAccess};
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{extract::State, http::StatusCode, response::Json};
+use chrono::NaiveDate;
+use model::entities::{account, category, recurring_transaction};
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, Set, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tracing::{debug, error, info, instrument, warn};
+use utoipa::ToSchema;
+
+/// Changes applied to every listed recurring transaction. Fields left out are kept.
+#[derive(Debug, Default, Deserialize, Serialize, ToSchema)]
+pub struct BulkRecurringTransactionPatch {
+    /// Book the transactions on this account, e.g. after switching banks
+    pub target_account_id: Option<i32>,
+    /// Transfer the money from this account
+    pub source_account_id: Option<i32>,
+    pub category_id: Option<i32>,
+    pub include_in_statistics: Option<bool>,
+    /// Let the transactions end on this date
+    pub end_date: Option<NaiveDate>,
+}
+
+impl BulkRecurringTransactionPatch {
+    fn is_empty(&self) -> bool {
+        self.target_account_id.is_none()
+            && self.source_account_id.is_none()
+            && self.category_id.is_none()
+            && self.include_in_statistics.is_none()
+            && self.end_date.is_none()
+    }
+}
+
+/// Request body for changing many recurring transactions at once
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct BulkUpdateRecurringTransactionsRequest {
+    pub recurring_transaction_ids: Vec<i32>,
+    pub patch: BulkRecurringTransactionPatch,
+    /// When true, every item is validated but nothing is written
+    pub dry_run: Option<bool>,
+}
+
+/// Outcome of one recurring transaction of a bulk update
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BulkRecurringItemResult {
+    pub recurring_transaction_id: i32,
+    pub success: bool,
+    /// Why the transaction can't be changed
+    pub error: Option<String>,
+}
+
+/// Outcome of a bulk update, with one result per listed transaction in request order
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BulkUpdateRecurringTransactionsResponse {
+    /// Whether the changes were written; never on a dry run or when any item failed
+    pub applied: bool,
+    pub valid_count: usize,
+    pub failed_count: usize,
+    pub results: Vec<BulkRecurringItemResult>,
+    pub dry_run: bool,
+}
+
+fn bulk_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+fn database_error(e: DbErr) -> (StatusCode, Json<ErrorResponse>) {
+    error!("Database error in bulk recurring transaction update: {}", e);
+    bulk_error(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", e.to_string())
+}
+
+/// Checks the accounts and category the patch moves transactions to.
+async fn validate_patch(
+    state: &AppState,
+    access: &AccountAccess,
+    patch: &BulkRecurringTransactionPatch,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if patch.is_empty() {
+        warn!("Bulk recurring transaction update without changes");
+        return Err(bulk_error(
+            StatusCode::BAD_REQUEST,
+            "EMPTY_PATCH",
+            "patch must change at least one field".to_string(),
+        ));
+    }
+    for account_id in patch.target_account_id.into_iter().chain(patch.source_account_id) {
+        if !access.can_access(account_id) {
+            warn!("User {:?} has no access to account {}", access.user_id(), account_id);
+            return Err(account_forbidden(account_id));
+        }
+        if !access.can_write(account_id) {
+            warn!("Account {} is read-only for user {:?}", account_id, access.user_id());
+            return Err(account_read_only(account_id));
+        }
+        let known = account::Entity::find_by_id(account_id)
+            .count(&state.db)
+            .await
+            .map_err(database_error)?;
+        if known == 0 {
+            warn!("Bulk update referenced unknown account {}", account_id);
+            return Err(bulk_error(
+                StatusCode::BAD_REQUEST,
+                "INVALID_ACCOUNT_ID",
+                format!("Account with id {} does not exist", account_id),
+            ));
+        }
+    }
+    if let Some(category_id) = patch.category_id {
+        let known = category::Entity::find_by_id(category_id)
+            .count(&state.db)
+            .await
+            .map_err(database_error)?;
+        if known == 0 {
+            warn!("Bulk update referenced unknown category {}", category_id);
+            return Err(bulk_error(
+                StatusCode::BAD_REQUEST,
+                "INVALID_CATEGORY_ID",
+                format!("Category with id {} does not exist", category_id),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Checks one transaction as it would be after the patch, returning why it can't be
+/// changed.
+async fn validate_item(
+    state: &AppState,
+    access: &AccountAccess,
+    transaction: &recurring_transaction::Model,
+    patch: &BulkRecurringTransactionPatch,
+) -> Result<Option<String>, (StatusCode, Json<ErrorResponse>)> {
+    if !access.can_write_any(transaction.target_account_id, transaction.source_account_id) {
+        return Ok(Some(format!(
+            "Recurring transaction {} is booked on accounts shared with you read-only",
+            transaction.id
+        )));
+    }
+    if let Some(end_date) = patch.end_date.filter(|end_date| *end_date < transaction.start_date) {
+        return Ok(Some(format!(
+            "end_date {} is before the start date {}",
+            end_date, transaction.start_date
+        )));
+    }
+    let target_account_id = patch.target_account_id.unwrap_or(transaction.target_account_id);
+    let source_account_id = patch.source_account_id.or(transaction.source_account_id);
+    if source_account_id == Some(target_account_id) {
+        return Ok(Some(format!("Recurring transaction {} would transfer to its own source account", transaction.id)));
+    }
+    match ensure_double_entry(
+        state,
+        target_account_id,
+        source_account_id,
+        patch.category_id.or(transaction.category_id),
+        transaction.fx_mode.is_some(),
+    )
+    .await
+    {
+        Ok(()) => Ok(None),
+        Err((StatusCode::BAD_REQUEST, Json(violation))) => Ok(Some(violation.error)),
+        Err(e) => Err(e),
+    }
+}
+
+/// Change many recurring transactions at once
+///
+/// Applies the same patch to every listed recurring transaction, e.g. to move all rules
+/// to a new account after switching banks or to re-categorize a set of them. Every item
+/// is validated first and reported in its result: unknown transactions, transactions on
+/// read-only accounts, end dates before the start and double-entry violations fail the
+/// item. The changes are only written, in a single database transaction, when every
+/// item is valid.
+#[utoipa::path(
+    patch,
+    path = "/api/v1/recurring-transactions/bulk",
+    tag = "recurring-transactions",
+    request_body = BulkUpdateRecurringTransactionsRequest,
+    responses(
+        (status = 200, description = "Items validated and, if all are valid, changed", body = ApiResponse<BulkUpdateRecurringTransactionsResponse>),
+        (status = 400, description = "No transactions listed, empty patch, or unknown account or category", body = ErrorResponse),
+        (status = 403, description = "No access to the new target or source account, or it is shared read-only", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn bulk_update_recurring_transactions(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Json(request): Json<BulkUpdateRecurringTransactionsRequest>,
+) -> Result<Json<ApiResponse<BulkUpdateRecurringTransactionsResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("Bulk updating recurring transactions: {:?}", request);
+
+    if request.recurring_transaction_ids.is_empty() {
+        warn!("Bulk recurring transaction update without transaction IDs");
+        return Err(bulk_error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_SELECTION",
+            "Provide recurring_transaction_ids".to_string(),
+        ));
+    }
+    let patch = &request.patch;
+    validate_patch(&state, &access, patch).await?;
+
+    // Transactions of other users are reported like missing ones
+    let transactions: HashMap<i32, recurring_transaction::Model> = access
+        .filter(
+            recurring_transaction::Entity::find(),
+            [recurring_transaction::Column::TargetAccountId, recurring_transaction::Column::SourceAccountId],
+        )
+        .filter(recurring_transaction::Column::Id.is_in(request.recurring_transaction_ids.clone()))
+        .all(&state.db)
+        .await
+        .map_err(database_error)?
+        .into_iter()
+        .map(|transaction| (transaction.id, transaction))
+        .collect();
+
+    let mut seen = HashSet::new();
+    let mut results = Vec::with_capacity(request.recurring_transaction_ids.len());
+    for &id in &request.recurring_transaction_ids {
+        let failure = if !seen.insert(id) {
+            Some(format!("Recurring transaction {} is listed more than once", id))
+        } else if let Some(transaction) = transactions.get(&id) {
+            validate_item(&state, &access, transaction, patch).await?
+        } else {
+            Some(format!("Recurring transaction {} not found", id))
+        };
+
+        if let Some(error) = &failure {
+            warn!("Not updating recurring transaction {}: {}", id, error);
+        }
+        results.push(BulkRecurringItemResult {
+            recurring_transaction_id: id,
+            success: failure.is_none(),
+            error: failure,
+        });
+    }
+
+    let valid_count = results.iter().filter(|result| result.success).count();
+    let failed_count = results.len() - valid_count;
+    let dry_run = request.dry_run.unwrap_or(false);
+    let applied = !dry_run && failed_count == 0;
+
+    if applied {
+        let mut changes = recurring_transaction::ActiveModel::default();
+        if let Some(target_account_id) = patch.target_account_id {
+            changes.target_account_id = Set(target_account_id);
+        }
+        if let Some(source_account_id) = patch.source_account_id {
+            changes.source_account_id = Set(Some(source_account_id));
+        }
+        if let Some(category_id) = patch.category_id {
+            changes.category_id = Set(Some(category_id));
+        }
+        if let Some(include_in_statistics) = patch.include_in_statistics {
+            changes.include_in_statistics = Set(include_in_statistics);
+        }
+        if let Some(end_date) = patch.end_date {
+            changes.end_date = Set(Some(end_date));
+        }
+
+        let txn = state.db.begin().await.map_err(database_error)?;
+        recurring_transaction::Entity::update_many()
+            .set(changes)
+            .filter(recurring_transaction::Column::Id.is_in(seen))
+            .exec(&txn)
+            .await
+            .map_err(database_error)?;
+        txn.commit().await.map_err(database_error)?;
+    }
+
+    info!(
+        "Bulk recurring transaction update: valid={}, failed={}, applied={}, dry_run={}",
+        valid_count, failed_count, applied, dry_run
+    );
+    let message = if applied {
+        format!("Updated {} recurring transactions", valid_count)
+    } else if dry_run {
+        "Dry run".to_string()
+    } else {
+        format!("Nothing updated, {} of {} recurring transactions failed validation", failed_count, results.len())
+    };
+    Ok(Json(ApiResponse {
+        data: BulkUpdateRecurringTransactionsResponse {
+            applied,
+            valid_count,
+            failed_count,
+            results,
+            dry_run,
+        },
+        message,
+        success: true,
+    }))
+}
@@ -1,21 +1,30 @@
+use crate::handlers::periods::{ensure_dates_unlocked, PeriodLockOverride};
+use crate::helpers::auth::AuthenticatedUser;
+use crate::helpers::access::AccountAccess;
+use crate::helpers::sparse_fields::{paginated_response, FieldSelection};
 use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use crate::tasks::recurring_instances::{generate_recurring_instances, InstanceGenerationSummary, DEFAULT_DAYS_AHEAD};
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    response::Json,
+    response::{IntoResponse, Json, Response},
 };
 use axum_valid::Valid;
-use chrono::NaiveDate;
+use chrono::{Datelike, NaiveDate};
+use common::Pagination;
 use model::entities::{recurring_transaction, recurring_transaction_instance};
 use model::transaction::TransactionGenerator;
 use rust_decimal::Decimal;
-use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Set};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
+    QueryTrait, Set,
+};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, instrument, trace, warn};
 use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
-use super::recurring::{RecurringInstanceResponse, TagInfo};
+use super::recurring::{recurring_transaction_accessible, recurring_transaction_writable, RecurringInstanceResponse, TagInfo};
 
 /// Query parameters for listing recurring transaction instances
 #[derive(Debug, Deserialize, ToSchema, IntoParams, Validate)]
@@ -30,6 +39,212 @@ pub struct RecurringInstanceQuery {
     pub recurring_transaction_id: Option<i32>,
     /// Filter by status
     pub status: Option<String>,
+    /// Only disputed (`true`) or undisputed (`false`) instances
+    pub disputed: Option<bool>,
+    /// Only instances marked overdue (`true`) or not (`false`)
+    pub overdue: Option<bool>,
+    /// Group instances by calendar month of their due date (pagination is ignored when grouping)
+    pub group_by: Option<InstanceGrouping>,
+    /// Comma-separated fields to return per instance, e.g. `id,due_date,expected_amount` (default: all).
+    /// When grouping, applies to the instances within each month.
+    pub fields: Option<String>,
+}
+
+/// How recurring instances are grouped in the listing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum InstanceGrouping {
+    /// Group by calendar month of the due date
+    Month,
+}
+
+/// Recurring instances due within one calendar month, with subtotals
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RecurringInstanceMonthGroup {
+    /// Month in `YYYY-MM` format
+    pub month: String,
+    /// Paid instances that are not disputed
+    pub paid_count: u32,
+    pub pending_count: u32,
+    pub skipped_count: u32,
+    /// Instances under dispute, whatever their status
+    pub disputed_count: u32,
+    /// Expected amount of all instances that were not skipped
+    pub total_expected: Decimal,
+    /// Amount actually paid (falls back to the expected amount when no paid amount was recorded),
+    /// leaving out disputed instances
+    pub total_paid: Decimal,
+    /// Expected amount of instances still pending
+    pub total_pending: Decimal,
+    /// Amount of paid instances under dispute
+    pub total_disputed: Decimal,
+    pub instances: Vec<RecurringInstanceResponse>,
+}
+
+impl RecurringInstanceMonthGroup {
+    fn new(month: String) -> Self {
+        Self {
+            month,
+            paid_count: 0,
+            pending_count: 0,
+            skipped_count: 0,
+            disputed_count: 0,
+            total_expected: Decimal::ZERO,
+            total_paid: Decimal::ZERO,
+            total_pending: Decimal::ZERO,
+            total_disputed: Decimal::ZERO,
+            instances: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, instance: &recurring_transaction_instance::Model, response: RecurringInstanceResponse) {
+        use recurring_transaction_instance::InstanceStatus;
+
+        if instance.disputed {
+            self.disputed_count += 1;
+        }
+        match instance.status {
+            InstanceStatus::Paid if instance.disputed => {
+                self.total_expected += instance.expected_amount;
+                self.total_disputed += instance.paid_amount.unwrap_or(instance.expected_amount);
+            }
+            InstanceStatus::Paid => {
+                self.paid_count += 1;
+                self.total_expected += instance.expected_amount;
+                self.total_paid += instance.paid_amount.unwrap_or(instance.expected_amount);
+            }
+            InstanceStatus::Pending => {
+                self.pending_count += 1;
+                self.total_expected += instance.expected_amount;
+                self.total_pending += instance.expected_amount;
+            }
+            InstanceStatus::Skipped => self.skipped_count += 1,
+        }
+        self.instances.push(response);
+    }
+}
+
+/// Group instances (already ordered by due date) into calendar months, keeping that order.
+fn group_instances_by_month(
+    instances: Vec<(recurring_transaction_instance::Model, RecurringInstanceResponse)>,
+) -> Vec<RecurringInstanceMonthGroup> {
+    let mut groups: Vec<RecurringInstanceMonthGroup> = Vec::new();
+    for (instance, response) in instances {
+        let month = format!("{:04}-{:02}", instance.due_date.year(), instance.due_date.month());
+        if groups.last().is_none_or(|group| group.month != month) {
+            groups.push(RecurringInstanceMonthGroup::new(month));
+        }
+        if let Some(group) = groups.last_mut() {
+            group.push(&instance, response);
+        }
+    }
+    groups
+}
+
+/// Fields filled in from the parent recurring transaction, which takes extra queries per instance.
+const PARENT_FIELDS: &[&str] = &[
+    "recurring_transaction_name",
+    "target_account_id",
+    "target_account_name",
+    "source_account_id",
+    "source_account_name",
+    "category_id",
+    "category_name",
+    "scenario_id",
+    "scenario_name",
+    "is_simulated",
+    "tags",
+];
+
+/// Build responses for the given instances, falling back to bare responses if the parents can't be loaded.
+///
+/// Details of the parent recurring transactions are only loaded when `fields` asks for them.
+async fn instance_responses(
+    db: &DatabaseConnection,
+    instances: Vec<recurring_transaction_instance::Model>,
+    fields: Option<&FieldSelection>,
+) -> Vec<(recurring_transaction_instance::Model, RecurringInstanceResponse)> {
+    let load_parent = fields.is_none_or(|fields| fields.contains_any(PARENT_FIELDS));
+    let responses = if load_parent {
+        match RecurringInstanceResponse::with_parents(instances.clone(), db).await {
+            Ok(responses) => responses,
+            Err(parent_error) => {
+                warn!("Failed to fetch recurring transactions of {} instances: {}", instances.len(), parent_error);
+                instances.iter().cloned().map(RecurringInstanceResponse::from).collect()
+            }
+        }
+    } else {
+        instances.iter().cloned().map(RecurringInstanceResponse::from).collect()
+    };
+    instances.into_iter().zip(responses).collect()
+}
+
+/// Rejects with 404 instances whose recurring transaction the caller can't access.
+async fn ensure_instance_accessible(
+    db: &DatabaseConnection,
+    access: &AccountAccess,
+    instance: &recurring_transaction_instance::Model,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    let accessible = recurring_transaction_accessible(db, access, instance.recurring_transaction_id)
+        .await
+        .map_err(|e| {
+            error!("Database error while checking access to recurring instance {}: {}", instance.id, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Failed to retrieve recurring instance".to_string(),
+                    code: "DATABASE_ERROR".to_string(),
+                    success: false,
+                }),
+            )
+        })?;
+    if accessible {
+        return Ok(());
+    }
+    warn!("Recurring instance {} is not accessible to user {:?}", instance.id, access.user_id());
+    Err((
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse {
+            error: format!("Recurring instance with id {} does not exist", instance.id),
+            code: "INSTANCE_NOT_FOUND".to_string(),
+            success: false,
+        }),
+    ))
+}
+
+/// Like [`ensure_instance_accessible`], but also rejects with 403 instances whose
+/// recurring transaction is only shared read-only with the caller.
+async fn ensure_instance_writable(
+    db: &DatabaseConnection,
+    access: &AccountAccess,
+    instance: &recurring_transaction_instance::Model,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    ensure_instance_accessible(db, access, instance).await?;
+    let writable = recurring_transaction_writable(db, access, instance.recurring_transaction_id)
+        .await
+        .map_err(|e| {
+            error!("Database error while checking access to recurring instance {}: {}", instance.id, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Failed to retrieve recurring instance".to_string(),
+                    code: "DATABASE_ERROR".to_string(),
+                    success: false,
+                }),
+            )
+        })?;
+    if writable {
+        return Ok(());
+    }
+    warn!("Recurring instance {} is read-only for user {:?}", instance.id, access.user_id());
+    Err((
+        StatusCode::FORBIDDEN,
+        Json(ErrorResponse {
+            error: format!("Recurring instance {} is shared with you read-only", instance.id),
+            code: "ACCOUNT_READ_ONLY".to_string(),
+            success: false,
+        }),
+    ))
 }
 
 /// Request body for updating a recurring transaction instance
@@ -45,16 +260,24 @@ pub struct UpdateRecurringInstanceRequest {
     pub paid_date: Option<NaiveDate>,
     /// Set the paid amount
     pub paid_amount: Option<Decimal>,
+    /// Replace the notes; an empty string clears them
+    pub notes: Option<String>,
+    /// Open (`true`) or settle (`false`) a dispute of the charge
+    pub disputed: Option<bool>,
 }
 
 /// Get all recurring transaction instances
+///
+/// With `group_by=month` the instances are returned grouped by the calendar month of
+/// their due date, each group carrying paid/pending/skipped counts and subtotals.
 #[utoipa::path(
     get,
     path = "/api/v1/recurring-instances",
     tag = "recurring-transactions",
     params(RecurringInstanceQuery),
     responses(
-        (status = 200, description = "Recurring transaction instances retrieved successfully", body = ApiResponse<Vec<RecurringInstanceResponse>>),
+        (status = 200, description = "Recurring transaction instances retrieved successfully", body = PaginatedResponse<Vec<RecurringInstanceResponse>>),
+        (status = 200, description = "Recurring transaction instances grouped by month (group_by=month)", body = ApiResponse<Vec<RecurringInstanceMonthGroup>>),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
@@ -62,15 +285,32 @@ pub struct UpdateRecurringInstanceRequest {
 pub async fn get_recurring_instances(
     Valid(Query(query)): Valid<Query<RecurringInstanceQuery>>,
     State(state): State<AppState>,
-) -> Result<(StatusCode, Json<ApiResponse<Vec<RecurringInstanceResponse>>>), (StatusCode, Json<ErrorResponse>)> {
+    access: AccountAccess,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering get_recurring_instances function");
 
     let page = query.page.unwrap_or(1);
     let limit = query.limit.unwrap_or(100);
+    let fields = FieldSelection::parse(query.fields.as_deref());
 
-    debug!("Fetching recurring instances - page: {}, limit: {}", page, limit);
+    debug!("Fetching recurring instances - page: {}, limit: {}, group_by: {:?}", page, limit, query.group_by);
 
     let mut query_builder = recurring_transaction_instance::Entity::find();
+    if access.user_id().is_some() {
+        let accessible_recurring = access
+            .filter(
+                recurring_transaction::Entity::find(),
+                [
+                    recurring_transaction::Column::TargetAccountId,
+                    recurring_transaction::Column::SourceAccountId,
+                ],
+            )
+            .select_only()
+            .column(recurring_transaction::Column::Id)
+            .into_query();
+        query_builder = query_builder
+            .filter(recurring_transaction_instance::Column::RecurringTransactionId.in_subquery(accessible_recurring));
+    }
 
     // Apply filters
     if let Some(recurring_id) = query.recurring_transaction_id {
@@ -83,32 +323,74 @@ pub async fn get_recurring_instances(
         }
     }
 
-    match query_builder
-        .order_by_desc(recurring_transaction_instance::Column::DueDate)
-        .paginate(&state.db, limit)
-        .fetch_page(page - 1)
-        .await
-    {
-        Ok(instances) => {
+    if let Some(disputed) = query.disputed {
+        query_builder = query_builder.filter(recurring_transaction_instance::Column::Disputed.eq(disputed));
+    }
+
+    if let Some(overdue) = query.overdue {
+        query_builder = query_builder.filter(recurring_transaction_instance::Column::Overdue.eq(overdue));
+    }
+
+    let query_builder = query_builder.order_by_desc(recurring_transaction_instance::Column::DueDate);
+    let result = match query.group_by {
+        // Month subtotals must cover every matching instance, so grouping skips pagination
+        Some(InstanceGrouping::Month) => query_builder.all(&state.db).await.map(|instances| (instances, None)),
+        None => {
+            async {
+                let paginator = query_builder.paginate(&state.db, limit);
+                let total_count = paginator.num_items().await?;
+                let instances = paginator.fetch_page(page - 1).await?;
+                Ok::<_, DbErr>((instances, Some(Pagination::new(total_count, page, limit))))
+            }
+            .await
+        }
+    };
+
+    match result {
+        Ok((instances, pagination)) => {
             info!("Successfully retrieved {} recurring instances", instances.len());
 
-            let mut response_data = Vec::new();
-            for instance in instances {
-                match RecurringInstanceResponse::with_tags(instance.clone(), &state.db).await {
-                    Ok(response) => response_data.push(response),
-                    Err(tag_error) => {
-                        warn!("Failed to fetch tags for recurring instance {}: {}", instance.id, tag_error);
-                        response_data.push(RecurringInstanceResponse::from(instance));
+            let responses = instance_responses(&state.db, instances, fields.as_ref()).await;
+            let message = "Recurring instances retrieved successfully".to_string();
+
+            let response = match (pagination, fields) {
+                (Some(pagination), fields) => paginated_response(
+                    responses.into_iter().map(|(_, response)| response).collect::<Vec<_>>(),
+                    fields.as_ref(),
+                    pagination,
+                    message,
+                ),
+                (None, None) => Json(ApiResponse {
+                    data: group_instances_by_month(responses),
+                    message,
+                    success: true,
+                })
+                .into_response(),
+                (None, Some(fields)) => {
+                    let mut groups = serde_json::to_value(group_instances_by_month(responses)).map_err(|e| {
+                        error!("Failed to serialize recurring instance groups: {}", e);
+                        (
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            Json(ErrorResponse {
+                                error: "Failed to retrieve recurring instances".to_string(),
+                                code: "SERIALIZATION_ERROR".to_string(),
+                                success: false,
+                            }),
+                        )
+                    })?;
+                    let instances = groups
+                        .as_array_mut()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|group| group.get_mut("instances").and_then(|value| value.as_array_mut()))
+                        .flatten();
+                    for instance in instances {
+                        fields.retain(instance);
                     }
+                    Json(ApiResponse { data: groups, message, success: true }).into_response()
                 }
-            }
-
-            let response = ApiResponse {
-                data: response_data,
-                message: "Recurring instances retrieved successfully".to_string(),
-                success: true,
             };
-            Ok((StatusCode::OK, Json(response)))
+            Ok(response)
         }
         Err(e) => {
             error!("Failed to retrieve recurring instances: {}", e);
@@ -139,6 +421,7 @@ pub async fn get_recurring_instances(
 pub async fn get_recurring_instance(
     Path(instance_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
 ) -> Result<(StatusCode, Json<ApiResponse<RecurringInstanceResponse>>), (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering get_recurring_instance function");
     debug!("Fetching recurring instance with ID: {}", instance_id);
@@ -148,6 +431,7 @@ pub async fn get_recurring_instance(
         .await
     {
         Ok(Some(instance)) => {
+            ensure_instance_accessible(&state.db, &access, &instance).await?;
             info!("Successfully retrieved recurring instance ID: {}", instance.id);
 
             match RecurringInstanceResponse::with_tags(instance.clone(), &state.db).await {
@@ -200,11 +484,14 @@ pub async fn get_recurring_instance(
     put,
     path = "/api/v1/recurring-instances/{instance_id}",
     tag = "recurring-transactions",
+    params(PeriodLockOverride),
     request_body = UpdateRecurringInstanceRequest,
     responses(
         (status = 200, description = "Recurring instance updated successfully", body = ApiResponse<RecurringInstanceResponse>),
         (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "Recurring instance is shared read-only, or admin_override was passed by a non-administrator", body = ErrorResponse),
         (status = 404, description = "Recurring instance not found", body = ErrorResponse),
+        (status = 409, description = "Instance is dated inside a locked period", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
@@ -212,6 +499,9 @@ pub async fn get_recurring_instance(
 pub async fn update_recurring_instance(
     Path(instance_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
+    auth: Option<AuthenticatedUser>,
+    Query(lock_override): Query<PeriodLockOverride>,
     Json(request): Json<UpdateRecurringInstanceRequest>,
 ) -> Result<(StatusCode, Json<ApiResponse<RecurringInstanceResponse>>), (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering update_recurring_instance function");
@@ -247,6 +537,14 @@ pub async fn update_recurring_instance(
         }
     };
 
+    ensure_instance_writable(&state.db, &access, &existing_instance).await?;
+
+    let mut affected_dates = vec![existing_instance.due_date];
+    affected_dates.extend(existing_instance.paid_date);
+    affected_dates.extend(request.due_date);
+    affected_dates.extend(request.paid_date);
+    ensure_dates_unlocked(&state.db, auth.as_ref(), &affected_dates, &lock_override).await?;
+
     // Parse status if provided
     let status = if let Some(status_str) = &request.status {
         match parse_instance_status(status_str) {
@@ -270,6 +568,13 @@ pub async fn update_recurring_instance(
     // Create the update model
     let mut update_model: recurring_transaction_instance::ActiveModel = existing_instance.into();
 
+    // Settled or rescheduled instances are no longer overdue; the generation job marks
+    // them again if they are still pending past the new due date
+    if status.as_ref().is_some_and(|s| *s != recurring_transaction_instance::InstanceStatus::Pending)
+        || request.due_date.is_some()
+    {
+        update_model.overdue = Set(false);
+    }
     if let Some(s) = status {
         update_model.status = Set(s);
     }
@@ -285,6 +590,12 @@ pub async fn update_recurring_instance(
     if let Some(paid_amount) = request.paid_amount {
         update_model.paid_amount = Set(Some(paid_amount));
     }
+    if let Some(notes) = request.notes {
+        update_model.notes = Set(Some(notes).filter(|notes| !notes.trim().is_empty()));
+    }
+    if let Some(disputed) = request.disputed {
+        update_model.disputed = Set(disputed);
+    }
 
     match update_model.update(&state.db).await {
         Ok(updated_instance) => {
@@ -329,9 +640,12 @@ pub async fn update_recurring_instance(
     delete,
     path = "/api/v1/recurring-instances/{instance_id}",
     tag = "recurring-transactions",
+    params(PeriodLockOverride),
     responses(
         (status = 200, description = "Recurring instance deleted successfully", body = ApiResponse<String>),
+        (status = 403, description = "Recurring instance is shared read-only, or admin_override was passed by a non-administrator", body = ErrorResponse),
         (status = 404, description = "Recurring instance not found", body = ErrorResponse),
+        (status = 409, description = "Instance is dated inside a locked period", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
@@ -339,6 +653,9 @@ pub async fn update_recurring_instance(
 pub async fn delete_recurring_instance(
     Path(instance_id): Path<i32>,
     State(state): State<AppState>,
+    access: AccountAccess,
+    auth: Option<AuthenticatedUser>,
+    Query(lock_override): Query<PeriodLockOverride>,
 ) -> Result<(StatusCode, Json<ApiResponse<String>>), (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering delete_recurring_instance function");
     debug!("Deleting recurring instance with ID: {}", instance_id);
@@ -348,8 +665,13 @@ pub async fn delete_recurring_instance(
         .one(&state.db)
         .await
     {
-        Ok(Some(_)) => {
-            // Instance exists, proceed with deletion
+        Ok(Some(instance)) => {
+            ensure_instance_writable(&state.db, &access, &instance).await?;
+            let mut affected_dates = vec![instance.due_date];
+            affected_dates.extend(instance.paid_date);
+            ensure_dates_unlocked(&state.db, auth.as_ref(), &affected_dates, &lock_override).await?;
+
+            // Instance exists and is not locked, proceed with deletion
             match recurring_transaction_instance::Entity::delete_by_id(instance_id)
                 .exec(&state.db)
                 .await
@@ -401,6 +723,225 @@ pub async fn delete_recurring_instance(
     }
 }
 
+/// Move a pending instance to `Skipped` (`skip`) or a skipped one back to `Pending`.
+///
+/// Due date and expected amount are kept as they are. Instances already in the
+/// requested status are returned unchanged; paid instances can't be skipped or unskipped.
+async fn set_instance_skipped(
+    state: &AppState,
+    access: &AccountAccess,
+    auth: Option<&AuthenticatedUser>,
+    instance_id: i32,
+    lock_override: &PeriodLockOverride,
+    skip: bool,
+) -> Result<recurring_transaction_instance::Model, (StatusCode, Json<ErrorResponse>)> {
+    let database_error = |e: DbErr| {
+        error!("Database error while skipping recurring instance {}: {}", instance_id, e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to update recurring instance".to_string(),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    };
+
+    let Some(instance) = recurring_transaction_instance::Entity::find_by_id(instance_id)
+        .one(&state.db)
+        .await
+        .map_err(database_error)?
+    else {
+        warn!("Recurring instance with ID {} not found", instance_id);
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("Recurring instance with id {} does not exist", instance_id),
+                code: "INSTANCE_NOT_FOUND".to_string(),
+                success: false,
+            }),
+        ));
+    };
+    ensure_instance_writable(&state.db, access, &instance).await?;
+
+    let (from, to) = if skip {
+        (recurring_transaction_instance::InstanceStatus::Pending, recurring_transaction_instance::InstanceStatus::Skipped)
+    } else {
+        (recurring_transaction_instance::InstanceStatus::Skipped, recurring_transaction_instance::InstanceStatus::Pending)
+    };
+    if instance.status == to {
+        debug!("Recurring instance {} is already {:?}", instance_id, to);
+        return Ok(instance);
+    }
+    if instance.status != from {
+        warn!("Recurring instance {} is {:?}, refusing to set it {:?}", instance_id, instance.status, to);
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ErrorResponse {
+                error: format!("Recurring instance {} is {:?}, only {:?} instances can be set {:?}", instance_id, instance.status, from, to),
+                code: "INVALID_INSTANCE_STATUS".to_string(),
+                success: false,
+            }),
+        ));
+    }
+    ensure_dates_unlocked(&state.db, auth, &[instance.due_date], lock_override).await?;
+
+    let mut update_model: recurring_transaction_instance::ActiveModel = instance.into();
+    update_model.status = Set(to);
+    // The generation job marks an unskipped instance again if it is past due
+    update_model.overdue = Set(false);
+    update_model.update(&state.db).await.map_err(database_error)
+}
+
+async fn skip_response(
+    state: &AppState,
+    instance: recurring_transaction_instance::Model,
+    message: &str,
+) -> Json<ApiResponse<RecurringInstanceResponse>> {
+    let data = match RecurringInstanceResponse::with_tags(instance.clone(), &state.db).await {
+        Ok(instance_response) => instance_response,
+        Err(tag_error) => {
+            warn!("Failed to fetch tags for recurring instance {}: {}", instance.id, tag_error);
+            RecurringInstanceResponse::from(instance)
+        }
+    };
+    Json(ApiResponse {
+        data,
+        message: message.to_string(),
+        success: true,
+    })
+}
+
+/// Skip a recurring transaction instance
+///
+/// Marks a pending instance as skipped for its period, e.g. a subscription paused over a
+/// vacation, keeping its due date and expected amount.
+#[utoipa::path(
+    post,
+    path = "/api/v1/recurring-instances/{instance_id}/skip",
+    tag = "recurring-transactions",
+    params(
+        ("instance_id" = i32, Path, description = "Recurring instance ID"),
+        PeriodLockOverride,
+    ),
+    responses(
+        (status = 200, description = "Recurring instance skipped", body = ApiResponse<RecurringInstanceResponse>),
+        (status = 403, description = "Recurring instance is shared read-only, or admin_override was passed by a non-administrator", body = ErrorResponse),
+        (status = 404, description = "Recurring instance not found", body = ErrorResponse),
+        (status = 409, description = "Instance is already paid, or dated inside a locked period", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn skip_recurring_instance(
+    Path(instance_id): Path<i32>,
+    State(state): State<AppState>,
+    access: AccountAccess,
+    auth: Option<AuthenticatedUser>,
+    Query(lock_override): Query<PeriodLockOverride>,
+) -> Result<Json<ApiResponse<RecurringInstanceResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering skip_recurring_instance function for instance_id: {}", instance_id);
+    let instance = set_instance_skipped(&state, &access, auth.as_ref(), instance_id, &lock_override, true).await?;
+    info!("Skipped recurring instance {} due {}", instance.id, instance.due_date);
+    Ok(skip_response(&state, instance, "Recurring instance skipped successfully").await)
+}
+
+/// Unskip a recurring transaction instance
+///
+/// Moves a skipped instance back to pending on its original due date.
+#[utoipa::path(
+    post,
+    path = "/api/v1/recurring-instances/{instance_id}/unskip",
+    tag = "recurring-transactions",
+    params(
+        ("instance_id" = i32, Path, description = "Recurring instance ID"),
+        PeriodLockOverride,
+    ),
+    responses(
+        (status = 200, description = "Recurring instance pending again", body = ApiResponse<RecurringInstanceResponse>),
+        (status = 403, description = "Recurring instance is shared read-only, or admin_override was passed by a non-administrator", body = ErrorResponse),
+        (status = 404, description = "Recurring instance not found", body = ErrorResponse),
+        (status = 409, description = "Instance is already paid, or dated inside a locked period", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn unskip_recurring_instance(
+    Path(instance_id): Path<i32>,
+    State(state): State<AppState>,
+    access: AccountAccess,
+    auth: Option<AuthenticatedUser>,
+    Query(lock_override): Query<PeriodLockOverride>,
+) -> Result<Json<ApiResponse<RecurringInstanceResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering unskip_recurring_instance function for instance_id: {}", instance_id);
+    let instance = set_instance_skipped(&state, &access, auth.as_ref(), instance_id, &lock_override, false).await?;
+    info!("Unskipped recurring instance {} due {}", instance.id, instance.due_date);
+    Ok(skip_response(&state, instance, "Recurring instance unskipped successfully").await)
+}
+
+/// Query parameters for generating recurring instances
+#[derive(Debug, Deserialize, ToSchema, IntoParams, Validate)]
+pub struct GenerateInstancesQuery {
+    /// Days ahead to create pending instances for (default: 30)
+    #[validate(range(min = 1, max = 366))]
+    pub days_ahead: Option<u32>,
+}
+
+/// Generate upcoming recurring instances
+///
+/// Creates the pending instances of approved recurring transactions due today or within
+/// the next `days_ahead` days and marks pending instances past their due date overdue.
+/// The server also does this in the background. Instances are shared by all users,
+/// so "today" is the date in the server's timezone.
+#[utoipa::path(
+    post,
+    path = "/api/v1/recurring-instances/generate",
+    tag = "recurring-transactions",
+    params(GenerateInstancesQuery),
+    responses(
+        (status = 200, description = "Recurring instances generated", body = ApiResponse<InstanceGenerationSummary>),
+        (status = 400, description = "Invalid days_ahead", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn generate_instances(
+    Valid(Query(query)): Valid<Query<GenerateInstancesQuery>>,
+    State(state): State<AppState>,
+) -> Result<(StatusCode, Json<ApiResponse<InstanceGenerationSummary>>), (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering generate_instances function");
+    let today = compute::today_in(&state.timezone);
+    let days_ahead = query.days_ahead.unwrap_or(DEFAULT_DAYS_AHEAD);
+
+    match generate_recurring_instances(&state.db, today, days_ahead).await {
+        Ok(summary) => {
+            info!(
+                "Generated {} recurring instances, marked {} overdue",
+                summary.created_count, summary.overdue_count
+            );
+            Ok((
+                StatusCode::OK,
+                Json(ApiResponse {
+                    data: summary,
+                    message: "Recurring instances generated successfully".to_string(),
+                    success: true,
+                }),
+            ))
+        }
+        Err(e) => {
+            error!("Failed to generate recurring instances: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Failed to generate recurring instances".to_string(),
+                    code: "DATABASE_ERROR".to_string(),
+                    success: false,
+                }),
+            ))
+        }
+    }
+}
+
 // Helper function to parse instance status string to InstanceStatus enum
 fn parse_instance_status(status_str: &str) -> Result<recurring_transaction_instance::InstanceStatus, String> {
     match status_str {
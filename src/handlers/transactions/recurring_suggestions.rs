@@ -0,0 +1,370 @@
+use crate::handlers::periods::{ensure_dates_unlocked, PeriodLockOverride};
+use crate::helpers::auth::AuthenticatedUser;
+use crate::handlers::transactions::recurring::RecurringTransactionResponse;
+use crate::helpers::access::AccountAccess;
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::{Duration, NaiveDate};
+use compute::account::utils::{generate_occurrences, load_holiday_calendar};
+use compute::recurring_detection::{HistoryEntry, RecurringCandidate, RecurringDetector};
+use model::entities::{one_off_transaction, recurring_transaction, recurring_transaction_instance};
+use rust_decimal::Decimal;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{debug, error, info, instrument, trace, warn};
+use utoipa::{IntoParams, ToSchema};
+
+/// Query parameters for recurring suggestions
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct RecurringSuggestionQuery {
+    /// Only look at transactions of this account
+    pub account_id: Option<i32>,
+    /// Minimum number of matching transactions (default: 3)
+    pub min_occurrences: Option<usize>,
+}
+
+/// A group of one-off transactions that looks like a recurring payment
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RecurringSuggestionResponse {
+    pub name: String,
+    pub target_account_id: i32,
+    /// Detected recurrence period
+    pub period: String,
+    /// Median amount of the matching transactions
+    pub amount: Decimal,
+    /// Matching one-off transactions, oldest first
+    pub transaction_ids: Vec<i32>,
+    pub first_date: NaiveDate,
+    pub last_date: NaiveDate,
+    /// When the next occurrence is expected
+    pub next_date: NaiveDate,
+}
+
+impl From<RecurringCandidate> for RecurringSuggestionResponse {
+    fn from(candidate: RecurringCandidate) -> Self {
+        Self {
+            name: candidate.name,
+            target_account_id: candidate.account_id,
+            period: format!("{:?}", candidate.period),
+            amount: candidate.amount,
+            transaction_ids: candidate.transaction_ids,
+            first_date: candidate.first_date,
+            last_date: candidate.last_date,
+            next_date: candidate.next_date,
+        }
+    }
+}
+
+/// Request body for converting a suggestion into a recurring transaction
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ConvertRecurringSuggestionRequest {
+    /// The one-off transactions to convert (usually a suggestion's `transaction_ids`)
+    pub transaction_ids: Vec<i32>,
+    /// Name of the new recurring transaction (defaults to the latest transaction's name)
+    pub name: Option<String>,
+    /// Category of the new recurring transaction (defaults to the most common category of the rows)
+    pub category_id: Option<i32>,
+}
+
+/// Result of converting one-off history into a recurring transaction
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ConvertRecurringSuggestionResponse {
+    pub recurring_transaction: RecurringTransactionResponse,
+    /// Number of paid instances created from the historical rows
+    pub instances_created: usize,
+    /// One-off transactions replaced by the paid instances
+    pub removed_transaction_ids: Vec<i32>,
+}
+
+fn history_entry(transaction: &one_off_transaction::Model) -> HistoryEntry {
+    HistoryEntry {
+        id: transaction.id,
+        name: transaction.name.clone(),
+        amount: transaction.amount,
+        date: transaction.date,
+        account_id: transaction.target_account_id,
+    }
+}
+
+fn suggestion_error(status: StatusCode, code: &str, message: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error: message,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// Suggest recurring transactions hidden in one-off history
+///
+/// Scans real (non-simulated) one-off transactions for groups with the same payee and a
+/// similar amount that repeat on a regular schedule. Only accessible accounts are scanned.
+#[utoipa::path(
+    get,
+    path = "/api/v1/transactions/recurring-suggestions",
+    tag = "transactions",
+    params(RecurringSuggestionQuery),
+    responses(
+        (status = 200, description = "Recurring suggestions", body = ApiResponse<Vec<RecurringSuggestionResponse>>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_recurring_suggestions(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Query(query): Query<RecurringSuggestionQuery>,
+) -> Result<Json<ApiResponse<Vec<RecurringSuggestionResponse>>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_recurring_suggestions function");
+
+    let mut query_builder = access
+        .filter(one_off_transaction::Entity::find(), [one_off_transaction::Column::TargetAccountId])
+        .filter(one_off_transaction::Column::IsSimulated.eq(false));
+    if let Some(account_id) = query.account_id {
+        query_builder = query_builder.filter(one_off_transaction::Column::TargetAccountId.eq(account_id));
+    }
+
+    let transactions = query_builder.all(&state.db).await.map_err(|e| {
+        error!("Failed to fetch transactions for recurring detection: {}", e);
+        suggestion_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "DATABASE_ERROR",
+            "Failed to fetch transactions".to_string(),
+        )
+    })?;
+
+    let mut detector = RecurringDetector::default();
+    if let Some(min_occurrences) = query.min_occurrences {
+        detector.min_occurrences = min_occurrences;
+    }
+    let suggestions: Vec<RecurringSuggestionResponse> = detector
+        .detect(transactions.iter().map(history_entry).collect())
+        .into_iter()
+        .map(RecurringSuggestionResponse::from)
+        .collect();
+
+    info!(
+        "Found {} recurring suggestions in {} transactions",
+        suggestions.len(),
+        transactions.len()
+    );
+    Ok(Json(ApiResponse {
+        data: suggestions,
+        message: "Recurring suggestions retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Convert one-off history into a recurring transaction
+///
+/// Creates a recurring transaction starting at the oldest row and turns every row into a
+/// paid instance of it (matched to the nearest scheduled occurrence). The original one-off
+/// rows are removed so they aren't counted twice in balances.
+#[utoipa::path(
+    post,
+    path = "/api/v1/transactions/recurring-suggestions/convert",
+    tag = "transactions",
+    params(PeriodLockOverride),
+    request_body = ConvertRecurringSuggestionRequest,
+    responses(
+        (status = 201, description = "Recurring transaction created", body = ApiResponse<ConvertRecurringSuggestionResponse>),
+        (status = 400, description = "Transactions do not form a recurring pattern", body = ErrorResponse),
+        (status = 403, description = "A transaction's account is shared read-only, or admin_override was passed by a non-administrator", body = ErrorResponse),
+        (status = 409, description = "A transaction is locked", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn convert_recurring_suggestion(
+    State(state): State<AppState>,
+    access: AccountAccess,
+    auth: Option<AuthenticatedUser>,
+    Query(lock_override): Query<PeriodLockOverride>,
+    Json(request): Json<ConvertRecurringSuggestionRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<ConvertRecurringSuggestionResponse>>), (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering convert_recurring_suggestion function");
+    debug!("Converting {} transactions into a recurring transaction", request.transaction_ids.len());
+
+    let transactions = one_off_transaction::Entity::find()
+        .filter(one_off_transaction::Column::Id.is_in(request.transaction_ids.clone()))
+        .all(&state.db)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch transactions to convert: {}", e);
+            suggestion_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DATABASE_ERROR",
+                "Failed to fetch transactions".to_string(),
+            )
+        })?
+        .into_iter()
+        .filter(|t| access.can_access_any(t.target_account_id, t.source_account_id))
+        .collect::<Vec<_>>();
+
+    if let Some(missing) = request
+        .transaction_ids
+        .iter()
+        .find(|id| !transactions.iter().any(|t| t.id == **id))
+    {
+        warn!("Transaction {} to convert does not exist", missing);
+        return Err(suggestion_error(
+            StatusCode::BAD_REQUEST,
+            "TRANSACTION_NOT_FOUND",
+            format!("Transaction with id {} does not exist", missing),
+        ));
+    }
+    for transaction in &transactions {
+        access.ensure_can_write_any(transaction.target_account_id, transaction.source_account_id)?;
+    }
+    if let Some(locked) = transactions.iter().find(|t| t.is_locked) {
+        warn!("Transaction {} to convert is locked", locked.id);
+        return Err(suggestion_error(
+            StatusCode::CONFLICT,
+            "TRANSACTION_LOCKED",
+            format!("Transaction {} is reconciled and locked", locked.id),
+        ));
+    }
+    if transactions.iter().any(|t| t.is_simulated) {
+        return Err(suggestion_error(
+            StatusCode::BAD_REQUEST,
+            "NOT_RECURRING",
+            "Simulated transactions cannot be converted".to_string(),
+        ));
+    }
+    let dates: Vec<NaiveDate> = transactions.iter().map(|t| t.date).collect();
+    ensure_dates_unlocked(&state.db, auth.as_ref(), &dates, &lock_override).await?;
+
+    // Every row has to be part of the pattern, on a single account
+    let account_id = transactions.first().map(|t| t.target_account_id);
+    let candidate = RecurringDetector::default()
+        .evaluate(transactions.iter().map(history_entry).collect())
+        .filter(|candidate| {
+            candidate.transaction_ids.len() == transactions.len()
+                && transactions.iter().all(|t| Some(t.target_account_id) == account_id)
+        })
+        .ok_or_else(|| {
+            warn!("Transactions {:?} do not form a recurring pattern", request.transaction_ids);
+            suggestion_error(
+                StatusCode::BAD_REQUEST,
+                "NOT_RECURRING",
+                "The transactions do not form a regular pattern with similar amounts on one account".to_string(),
+            )
+        })?;
+
+    let latest = transactions
+        .iter()
+        .max_by_key(|t| (t.date, t.id))
+        .expect("pattern has at least one transaction");
+    let category_id = request.category_id.or_else(|| {
+        let mut counts: HashMap<i32, usize> = HashMap::new();
+        for category_id in transactions.iter().filter_map(|t| t.category_id) {
+            *counts.entry(category_id).or_default() += 1;
+        }
+        counts.into_iter().max_by_key(|(id, count)| (*count, -id)).map(|(id, _)| id)
+    });
+
+    let db_error = |e: sea_orm::DbErr| {
+        error!("Failed to convert transactions into recurring transaction: {}", e);
+        suggestion_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "DATABASE_ERROR",
+            "Failed to create recurring transaction".to_string(),
+        )
+    };
+
+    let txn = state.db.begin().await.map_err(db_error)?;
+
+    let recurring = recurring_transaction::ActiveModel {
+        name: Set(request.name.clone().unwrap_or_else(|| candidate.name.clone())),
+        description: Set(latest.description.clone()),
+        amount: Set(candidate.amount),
+        start_date: Set(candidate.first_date),
+        end_date: Set(None),
+        period: Set(candidate.period.clone()),
+        include_in_statistics: Set(latest.include_in_statistics),
+        target_account_id: Set(latest.target_account_id),
+        source_account_id: Set(latest.source_account_id),
+        ledger_name: Set(latest.ledger_name.clone()),
+        category_id: Set(category_id),
+        scenario_id: Set(None),
+        is_simulated: Set(false),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(db_error)?;
+
+    // Match each historical row to the closest scheduled occurrence
+    let holidays = load_holiday_calendar(&txn).await.map_err(db_error)?;
+    let mut occurrences = generate_occurrences(
+        candidate.first_date,
+        None,
+        &candidate.period,
+        &holidays,
+        candidate.first_date,
+        candidate.next_date + Duration::days(1),
+    );
+    let mut ordered: Vec<&one_off_transaction::Model> = transactions.iter().collect();
+    ordered.sort_by_key(|t| (t.date, t.id));
+
+    let mut instances_created = 0;
+    for transaction in &ordered {
+        let Some(position) = occurrences
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, due)| (**due - transaction.date).num_days().abs())
+            .map(|(position, _)| position)
+        else {
+            break;
+        };
+        let due_date = occurrences.remove(position);
+
+        recurring_transaction_instance::ActiveModel {
+            recurring_transaction_id: Set(recurring.id),
+            status: Set(recurring_transaction_instance::InstanceStatus::Paid),
+            due_date: Set(due_date),
+            expected_amount: Set(recurring.amount),
+            paid_date: Set(Some(transaction.date)),
+            paid_amount: Set(Some(transaction.amount)),
+            reconciled_imported_transaction_id: Set(None),
+            category_id: Set(transaction.category_id),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(db_error)?;
+        instances_created += 1;
+    }
+
+    one_off_transaction::Entity::delete_many()
+        .filter(one_off_transaction::Column::Id.is_in(candidate.transaction_ids.clone()))
+        .exec(&txn)
+        .await
+        .map_err(db_error)?;
+
+    txn.commit().await.map_err(db_error)?;
+
+    info!(
+        "Converted {} one-off transactions into recurring transaction {}",
+        instances_created, recurring.id
+    );
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse {
+            data: ConvertRecurringSuggestionResponse {
+                recurring_transaction: RecurringTransactionResponse::from(recurring),
+                instances_created,
+                removed_transaction_ids: candidate.transaction_ids,
+            },
+            message: "Recurring transaction created from history".to_string(),
+            success: true,
+        }),
+    ))
+}
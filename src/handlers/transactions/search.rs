@@ -0,0 +1,231 @@
+use super::one_offs::{transaction_responses, TransactionStatus};
+use crate::helpers::access::AccountAccess;
+use crate::helpers::sparse_fields::{paginated_response, FieldSelection};
+use crate::schemas::{AppState, ErrorResponse};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{Json, Response},
+};
+use chrono::NaiveDate;
+use common::Pagination;
+use model::entities::{one_off_transaction, one_off_transaction_tag};
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, Condition, DbErr, EntityTrait, Order, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, instrument, warn};
+use utoipa::{IntoParams, ToSchema};
+
+/// Largest page the search returns
+const MAX_PAGE_SIZE: u64 = 500;
+
+/// Column search results are ordered by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TransactionSortField {
+    #[default]
+    Date,
+    Amount,
+    Name,
+    Id,
+}
+
+impl TransactionSortField {
+    fn column(self) -> one_off_transaction::Column {
+        match self {
+            TransactionSortField::Date => one_off_transaction::Column::Date,
+            TransactionSortField::Amount => one_off_transaction::Column::Amount,
+            TransactionSortField::Name => one_off_transaction::Column::Name,
+            TransactionSortField::Id => one_off_transaction::Column::Id,
+        }
+    }
+}
+
+/// Direction search results are ordered in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// Query parameters for searching transactions. All given filters must match.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct TransactionSearchQuery {
+    /// Text contained in the name or description
+    pub q: Option<String>,
+    /// Minimum amount (inclusive)
+    pub min_amount: Option<Decimal>,
+    /// Maximum amount (inclusive)
+    pub max_amount: Option<Decimal>,
+    /// Transactions on or after this date
+    pub start_date: Option<NaiveDate>,
+    /// Transactions on or before this date
+    pub end_date: Option<NaiveDate>,
+    /// Transactions in this category; also matches legs booked under it
+    pub category_id: Option<i32>,
+    /// Transactions carrying this tag
+    pub tag_id: Option<i32>,
+    /// Transactions where this account is the target or the source
+    pub account_id: Option<i32>,
+    /// Transactions belonging to this scenario
+    pub scenario_id: Option<i32>,
+    /// Filter by status
+    pub status: Option<TransactionStatus>,
+    /// Page number (default: 1)
+    pub page: Option<u64>,
+    /// Items per page (default: 50, at most 500)
+    pub limit: Option<u64>,
+    /// Sort column (default: date)
+    pub sort: Option<TransactionSortField>,
+    /// Sort direction (default: desc)
+    pub order: Option<SortDirection>,
+    /// Comma-separated fields to return per transaction, e.g. `id,date,amount,name` (default: all)
+    pub fields: Option<String>,
+}
+
+impl TransactionSearchQuery {
+    /// Describes the first inconsistent parameter, if any.
+    fn problem(&self) -> Option<&'static str> {
+        if self.page == Some(0) {
+            Some("page starts at 1")
+        } else if self.limit.is_some_and(|limit| limit == 0 || limit > MAX_PAGE_SIZE) {
+            Some("limit must be between 1 and 500")
+        } else if matches!((self.min_amount, self.max_amount), (Some(min), Some(max)) if min > max) {
+            Some("min_amount must not exceed max_amount")
+        } else if matches!((self.start_date, self.end_date), (Some(start), Some(end)) if start > end) {
+            Some("start_date must not be after end_date")
+        } else {
+            None
+        }
+    }
+
+    fn condition(&self) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(text) = self.q.as_deref().map(str::trim).filter(|text| !text.is_empty()) {
+            condition = condition.add(
+                Condition::any()
+                    .add(one_off_transaction::Column::Name.contains(text))
+                    .add(one_off_transaction::Column::Description.contains(text)),
+            );
+        }
+        if let Some(min_amount) = self.min_amount {
+            condition = condition.add(one_off_transaction::Column::Amount.gte(min_amount));
+        }
+        if let Some(max_amount) = self.max_amount {
+            condition = condition.add(one_off_transaction::Column::Amount.lte(max_amount));
+        }
+        if let Some(start_date) = self.start_date {
+            condition = condition.add(one_off_transaction::Column::Date.gte(start_date));
+        }
+        if let Some(end_date) = self.end_date {
+            condition = condition.add(one_off_transaction::Column::Date.lte(end_date));
+        }
+        if let Some(category_id) = self.category_id {
+            condition = condition.add(one_off_transaction::Column::CategoryId.eq(category_id));
+        } else {
+            // Legs are nested under their parent unless searching by the leg's own category
+            condition = condition.add(one_off_transaction::Column::ParentTransactionId.is_null());
+        }
+        if let Some(tag_id) = self.tag_id {
+            let tagged = one_off_transaction_tag::Entity::find()
+                .select_only()
+                .column(one_off_transaction_tag::Column::TransactionId)
+                .filter(one_off_transaction_tag::Column::TagId.eq(tag_id))
+                .into_query();
+            condition = condition.add(one_off_transaction::Column::Id.in_subquery(tagged));
+        }
+        if let Some(account_id) = self.account_id {
+            condition = condition.add(
+                Condition::any()
+                    .add(one_off_transaction::Column::TargetAccountId.eq(account_id))
+                    .add(one_off_transaction::Column::SourceAccountId.eq(account_id)),
+            );
+        }
+        if let Some(scenario_id) = self.scenario_id {
+            condition = condition.add(one_off_transaction::Column::ScenarioId.eq(scenario_id));
+        }
+        if let Some(status) = self.status {
+            let status: one_off_transaction::OneOffStatus = status.into();
+            condition = condition.add(one_off_transaction::Column::Status.eq(status));
+        }
+        condition
+    }
+}
+
+/// Search transactions
+///
+/// Filters one-off transactions in the database by text, amount and date ranges,
+/// category, tag, account and scenario, and returns one sorted page of them.
+#[utoipa::path(
+    get,
+    path = "/api/v1/transactions/search",
+    tag = "transactions",
+    params(TransactionSearchQuery),
+    responses(
+        (status = 200, description = "Matching transactions", body = PaginatedResponse<Vec<TransactionResponse>>),
+        (status = 400, description = "Invalid search parameters", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn search_transactions(
+    Query(query): Query<TransactionSearchQuery>,
+    State(state): State<AppState>,
+    access: AccountAccess,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    if let Some(problem) = query.problem() {
+        warn!("Rejected transaction search: {}", problem);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: problem.to_string(),
+                code: "INVALID_SEARCH".to_string(),
+                success: false,
+            }),
+        ));
+    }
+
+    let page = query.page.unwrap_or(1);
+    let limit = query.limit.unwrap_or(50);
+    let fields = FieldSelection::parse(query.fields.as_deref());
+    let order = match query.order.unwrap_or_default() {
+        SortDirection::Asc => Order::Asc,
+        SortDirection::Desc => Order::Desc,
+    };
+    debug!("Searching transactions - page: {}, limit: {}, sort: {:?} {:?}", page, limit, query.sort, order);
+
+    let database_error = |e: DbErr| {
+        error!("Failed to search transactions: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to search transactions".to_string(),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    };
+
+    let paginator = access
+        .filter(
+            one_off_transaction::Entity::find(),
+            [one_off_transaction::Column::TargetAccountId, one_off_transaction::Column::SourceAccountId],
+        )
+        .filter(query.condition())
+        .order_by(query.sort.unwrap_or_default().column(), order.clone())
+        .order_by(one_off_transaction::Column::Id, order)
+        .paginate(&state.db, limit);
+    let total_count = paginator.num_items().await.map_err(database_error)?;
+    let transactions = paginator.fetch_page(page - 1).await.map_err(database_error)?;
+
+    info!("Transaction search matched {} transactions, {} on page {}", total_count, transactions.len(), page);
+    let responses = transaction_responses(&state.db, transactions, fields.as_ref()).await;
+    Ok(paginated_response(
+        responses,
+        fields.as_ref(),
+        Pagination::new(total_count, page, limit),
+        "Transactions retrieved successfully".to_string(),
+    ))
+}
@@ -0,0 +1,245 @@
+use crate::helpers::access::AccountAccess;
+use crate::helpers::merchants::{MerchantEnricher, MerchantInfo};
+use crate::schemas::{ApiResponse, AppState, ErrorResponse};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use axum_valid::Valid;
+use chrono::NaiveDate;
+use model::entities::{category, one_off_transaction};
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{debug, error, info, instrument, warn};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Query parameters for similar transactions
+#[derive(Debug, Deserialize, ToSchema, IntoParams, Validate)]
+pub struct SimilarTransactionsQuery {
+    /// Maximum number of similar transactions to return (default: 20)
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<usize>,
+}
+
+/// A past transaction resembling the requested one
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SimilarTransaction {
+    pub id: i32,
+    pub name: String,
+    pub date: NaiveDate,
+    pub amount: Decimal,
+    pub target_account_id: i32,
+    pub category_id: Option<i32>,
+    pub category_name: Option<String>,
+    /// Whether the amount is within 10% of the requested transaction's amount
+    pub amount_matches: bool,
+}
+
+/// Similar transactions and the category they are usually booked under
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SimilarTransactionsResponse {
+    pub transaction_id: i32,
+    /// Normalized payee the match was made on
+    pub merchant: Option<MerchantInfo>,
+    /// Most common category among the similar transactions
+    pub suggested_category_id: Option<i32>,
+    pub suggested_category_name: Option<String>,
+    /// How many of the similar transactions use the suggested category
+    pub suggested_category_count: usize,
+    /// How many of the similar transactions have any category
+    pub categorized_count: usize,
+    pub transactions: Vec<SimilarTransaction>,
+}
+
+/// Key identifying a payee: a merchant mapping when one matched, otherwise the cleaned name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum PayeeKey {
+    Merchant(i32),
+    Name(String),
+}
+
+fn payee_key(merchant: &MerchantInfo) -> PayeeKey {
+    match merchant.merchant_id {
+        Some(id) => PayeeKey::Merchant(id),
+        None => PayeeKey::Name(merchant.name.to_lowercase()),
+    }
+}
+
+/// Amounts of the same sign within 10% of each other count as the same amount pattern.
+fn amounts_match(a: Decimal, b: Decimal) -> bool {
+    if a.is_sign_negative() != b.is_sign_negative() {
+        return false;
+    }
+    let reference = a.abs().max(b.abs());
+    reference.is_zero() || (a - b).abs() <= reference * Decimal::new(10, 2)
+}
+
+fn similar_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// Get past transactions similar to a transaction
+///
+/// Transactions match when their descriptions resolve to the same payee through the
+/// merchant mappings (or the cleaned name when no mapping applies). When the payee
+/// can't be determined, transactions with a matching amount on the same account are
+/// used instead. The most common category among the matches is returned as a suggestion.
+#[utoipa::path(
+    get,
+    path = "/api/v1/transactions/{transaction_id}/similar",
+    tag = "transactions",
+    params(
+        ("transaction_id" = i32, Path, description = "Transaction ID"),
+        SimilarTransactionsQuery,
+    ),
+    responses(
+        (status = 200, description = "Similar transactions retrieved successfully", body = ApiResponse<SimilarTransactionsResponse>),
+        (status = 404, description = "Transaction not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(state))]
+pub async fn get_similar_transactions(
+    Path(transaction_id): Path<i32>,
+    State(state): State<AppState>,
+    access: AccountAccess,
+    Valid(Query(query)): Valid<Query<SimilarTransactionsQuery>>,
+) -> Result<Json<ApiResponse<SimilarTransactionsResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    debug!("Looking up transactions similar to {}", transaction_id);
+
+    let database_error = |e: DbErr| {
+        error!("Database error while finding transactions similar to {}: {}", transaction_id, e);
+        similar_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "DATABASE_ERROR",
+            "Failed to find similar transactions".to_string(),
+        )
+    };
+
+    let transaction = one_off_transaction::Entity::find_by_id(transaction_id)
+        .one(&state.db)
+        .await
+        .map_err(database_error)?
+        .filter(|transaction| access.can_access_any(transaction.target_account_id, transaction.source_account_id))
+        .ok_or_else(|| {
+            warn!("Transaction {} not found", transaction_id);
+            similar_error(
+                StatusCode::NOT_FOUND,
+                "NOT_FOUND",
+                format!("Transaction with id {} not found", transaction_id),
+            )
+        })?;
+
+    let enricher = MerchantEnricher::load(&state.db).await.map_err(database_error)?;
+    let merchant = enricher.enrich(&transaction.name);
+    let key = merchant.as_ref().map(payee_key);
+
+    let candidates = access
+        .filter(
+            one_off_transaction::Entity::find(),
+            [one_off_transaction::Column::TargetAccountId, one_off_transaction::Column::SourceAccountId],
+        )
+        .filter(one_off_transaction::Column::Id.ne(transaction_id))
+        .filter(one_off_transaction::Column::IsSimulated.eq(false))
+        .all(&state.db)
+        .await
+        .map_err(database_error)?;
+
+    let mut matches: Vec<(one_off_transaction::Model, bool)> = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let amount_matches = amounts_match(candidate.amount, transaction.amount);
+            let similar = match &key {
+                Some(key) => enricher
+                    .enrich(&candidate.name)
+                    .is_some_and(|candidate_merchant| payee_key(&candidate_merchant) == *key),
+                None => amount_matches && candidate.target_account_id == transaction.target_account_id,
+            };
+            similar.then_some((candidate, amount_matches))
+        })
+        .collect();
+
+    // Closest amounts first, then the most recent
+    matches.sort_by(|(a, a_matches), (b, b_matches)| {
+        b_matches.cmp(a_matches).then(b.date.cmp(&a.date)).then(b.id.cmp(&a.id))
+    });
+
+    // Most common category; ties go to the category used most recently
+    let mut category_counts: HashMap<i32, (usize, NaiveDate)> = HashMap::new();
+    for (candidate, _) in &matches {
+        if let Some(category_id) = candidate.category_id {
+            let entry = category_counts.entry(category_id).or_insert((0, candidate.date));
+            entry.0 += 1;
+            entry.1 = entry.1.max(candidate.date);
+        }
+    }
+    let categorized_count = category_counts.values().map(|(count, _)| count).sum();
+    let suggestion = category_counts
+        .iter()
+        .max_by_key(|(_, (count, last_used))| (*count, *last_used))
+        .map(|(category_id, (count, _))| (*category_id, *count));
+
+    let limit = query.limit.unwrap_or(20);
+    matches.truncate(limit);
+
+    let mut category_ids: Vec<i32> = matches.iter().filter_map(|(candidate, _)| candidate.category_id).collect();
+    category_ids.extend(suggestion.map(|(category_id, _)| category_id));
+    let category_names: HashMap<i32, String> = if category_ids.is_empty() {
+        HashMap::new()
+    } else {
+        category::Entity::find()
+            .filter(category::Column::Id.is_in(category_ids))
+            .all(&state.db)
+            .await
+            .map_err(database_error)?
+            .into_iter()
+            .map(|cat| (cat.id, cat.name))
+            .collect()
+    };
+
+    let transactions: Vec<SimilarTransaction> = matches
+        .into_iter()
+        .map(|(candidate, amount_matches)| SimilarTransaction {
+            id: candidate.id,
+            name: candidate.name,
+            date: candidate.date,
+            amount: candidate.amount,
+            target_account_id: candidate.target_account_id,
+            category_name: candidate.category_id.and_then(|id| category_names.get(&id).cloned()),
+            category_id: candidate.category_id,
+            amount_matches,
+        })
+        .collect();
+
+    info!(
+        "Found {} transactions similar to {} (suggested category: {:?})",
+        transactions.len(),
+        transaction_id,
+        suggestion
+    );
+
+    Ok(Json(ApiResponse {
+        data: SimilarTransactionsResponse {
+            transaction_id,
+            merchant,
+            suggested_category_id: suggestion.map(|(category_id, _)| category_id),
+            suggested_category_name: suggestion.and_then(|(category_id, _)| category_names.get(&category_id).cloned()),
+            suggested_category_count: suggestion.map(|(_, count)| count).unwrap_or(0),
+            categorized_count,
+            transactions,
+        },
+        message: "Similar transactions retrieved successfully".to_string(),
+        success: true,
+    }))
+}
@@ -1,10 +1,12 @@
+use crate::helpers::auth::{ensure_admin, AuthenticatedUser};
+use crate::helpers::timezone::parse_timezone;
 use crate::schemas::{ApiResponse, AppState, ErrorResponse};
 use axum::{
     extract::{Path, State},
     http::StatusCode,
     response::Json,
 };
-use model::entities::user;
+use model::entities::{scenario, user};
 use sea_orm::{ActiveModelTrait, EntityTrait, Set, DbErr};
 use serde::{Deserialize, Serialize};
 use tracing::{instrument, error, warn, info, debug, trace};
@@ -40,6 +42,19 @@ impl From<user::Model> for UserResponse {
     }
 }
 
+/// Rejects the request with `403 Forbidden` unless the caller is the user or an administrator.
+async fn ensure_self_or_admin(
+    db: &sea_orm::DatabaseConnection,
+    auth: Option<&AuthenticatedUser>,
+    user_id: i32,
+    action: &str,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if auth.is_some_and(|auth| auth.user_id == user_id) {
+        return Ok(());
+    }
+    ensure_admin(db, auth, action).await
+}
+
 /// Create a new user
 #[utoipa::path(
     post,
@@ -49,16 +64,19 @@ impl From<user::Model> for UserResponse {
     responses(
         (status = 201, description = "User created successfully", body = ApiResponse<UserResponse>),
         (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 403, description = "The caller is not an administrator", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
 #[instrument]
 pub async fn create_user(
     State(state): State<AppState>,
+    auth: Option<AuthenticatedUser>,
     Json(request): Json<CreateUserRequest>,
 ) -> Result<(StatusCode, Json<ApiResponse<UserResponse>>), (StatusCode, Json<ErrorResponse>)> {
     trace!("Entering create_user function");
     debug!("Creating user with username: {}", request.username);
+    ensure_admin(&state.db, auth.as_ref(), "create users").await?;
 
     let new_user = user::ActiveModel {
         username: Set(request.username.clone()),
@@ -208,6 +226,7 @@ pub async fn get_user(
     request_body = UpdateUserRequest,
     responses(
         (status = 200, description = "User updated successfully", body = ApiResponse<UserResponse>),
+        (status = 403, description = "The caller is neither the user nor an administrator", body = ErrorResponse),
         (status = 404, description = "User not found", body = ErrorResponse),
         (status = 400, description = "Invalid request", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
@@ -217,10 +236,14 @@ pub async fn get_user(
 pub async fn update_user(
     Path(user_id): Path<i32>,
     State(state): State<AppState>,
+    auth: Option<AuthenticatedUser>,
     Json(request): Json<UpdateUserRequest>,
 ) -> Result<Json<ApiResponse<UserResponse>>, StatusCode> {
     trace!("Entering update_user function for user_id: {}", user_id);
     debug!("Updating user with ID: {}", user_id);
+    ensure_self_or_admin(&state.db, auth.as_ref(), user_id, "update other users")
+        .await
+        .map_err(|(status, _)| status)?;
 
     // First, find the existing user
     trace!("Looking up existing user with ID: {}", user_id);
@@ -285,6 +308,7 @@ pub async fn update_user(
     ),
     responses(
         (status = 200, description = "User deleted successfully", body = ApiResponse<String>),
+        (status = 403, description = "The caller is neither the user nor an administrator", body = ErrorResponse),
         (status = 404, description = "User not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
@@ -293,9 +317,13 @@ pub async fn update_user(
 pub async fn delete_user(
     Path(user_id): Path<i32>,
     State(state): State<AppState>,
+    auth: Option<AuthenticatedUser>,
 ) -> Result<Json<ApiResponse<String>>, StatusCode> {
     trace!("Entering delete_user function for user_id: {}", user_id);
     debug!("Attempting to delete user with ID: {}", user_id);
+    ensure_self_or_admin(&state.db, auth.as_ref(), user_id, "delete other users")
+        .await
+        .map_err(|(status, _)| status)?;
 
     match user::Entity::delete_by_id(user_id).exec(&state.db).await {
         Ok(delete_result) => {
@@ -318,4 +346,198 @@ pub async fn delete_user(
             Err(StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
-}
\ No newline at end of file
+}
+/// User preferences
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UserSettings {
+    /// Scenario that statistics and timeseries endpoints include when no `scenario_id` is passed;
+    /// `null` means the baseline without simulated transactions
+    pub active_scenario_id: Option<i32>,
+    /// UI theme: "light", "dark" or "system"; `null` follows the system preference
+    pub theme: Option<String>,
+    /// IANA timezone such as "Europe/Prague" deciding what "today" is for due dates,
+    /// balances and forecasts; `null` uses the server's timezone
+    pub timezone: Option<String>,
+}
+
+impl From<user::Model> for UserSettings {
+    fn from(model: user::Model) -> Self {
+        Self {
+            active_scenario_id: model.active_scenario_id,
+            theme: model.theme,
+            timezone: model.timezone,
+        }
+    }
+}
+
+/// Themes the frontend knows how to apply.
+const THEMES: &[&str] = &["light", "dark", "system"];
+
+fn settings_error(status: StatusCode, code: &str, error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+}
+
+async fn find_user_for_settings(
+    db: &sea_orm::DatabaseConnection,
+    user_id: i32,
+) -> Result<user::Model, (StatusCode, Json<ErrorResponse>)> {
+    match user::Entity::find_by_id(user_id).one(db).await {
+        Ok(Some(user_model)) => Ok(user_model),
+        Ok(None) => {
+            warn!("User with ID {} not found", user_id);
+            Err(settings_error(
+                StatusCode::NOT_FOUND,
+                "USER_NOT_FOUND",
+                format!("User with id {} not found", user_id),
+            ))
+        }
+        Err(db_error) => {
+            error!("Failed to retrieve user with ID {}: {}", user_id, db_error);
+            Err(settings_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DATABASE_ERROR",
+                "Failed to retrieve user".to_string(),
+            ))
+        }
+    }
+}
+
+/// Get a user's settings
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{user_id}/settings",
+    tag = "users",
+    params(
+        ("user_id" = i32, Path, description = "User ID"),
+    ),
+    responses(
+        (status = 200, description = "User settings retrieved successfully", body = ApiResponse<UserSettings>),
+        (status = 403, description = "The caller is neither the user nor an administrator", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument]
+pub async fn get_user_settings(
+    Path(user_id): Path<i32>,
+    State(state): State<AppState>,
+    auth: Option<AuthenticatedUser>,
+) -> Result<Json<ApiResponse<UserSettings>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering get_user_settings function for user_id: {}", user_id);
+    ensure_self_or_admin(&state.db, auth.as_ref(), user_id, "read other users' settings").await?;
+
+    let user_model = find_user_for_settings(&state.db, user_id).await?;
+    Ok(Json(ApiResponse {
+        data: UserSettings::from(user_model),
+        message: "User settings retrieved successfully".to_string(),
+        success: true,
+    }))
+}
+
+/// Replace a user's settings
+#[utoipa::path(
+    put,
+    path = "/api/v1/users/{user_id}/settings",
+    tag = "users",
+    params(
+        ("user_id" = i32, Path, description = "User ID"),
+    ),
+    request_body = UserSettings,
+    responses(
+        (status = 200, description = "User settings updated successfully", body = ApiResponse<UserSettings>),
+        (status = 400, description = "Scenario does not exist, unknown theme or unknown timezone", body = ErrorResponse),
+        (status = 403, description = "The caller is neither the user nor an administrator", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument]
+pub async fn update_user_settings(
+    Path(user_id): Path<i32>,
+    State(state): State<AppState>,
+    auth: Option<AuthenticatedUser>,
+    Json(request): Json<UserSettings>,
+) -> Result<Json<ApiResponse<UserSettings>>, (StatusCode, Json<ErrorResponse>)> {
+    trace!("Entering update_user_settings function for user_id: {}", user_id);
+    debug!("Updating settings of user {}: {:?}", user_id, request);
+    ensure_self_or_admin(&state.db, auth.as_ref(), user_id, "change other users' settings").await?;
+
+    let user_model = find_user_for_settings(&state.db, user_id).await?;
+
+    if let Some(theme) = request.theme.as_deref() {
+        if !THEMES.contains(&theme) {
+            warn!("Rejected unknown theme '{}' for user {}", theme, user_id);
+            return Err(settings_error(
+                StatusCode::BAD_REQUEST,
+                "INVALID_THEME",
+                format!("Unknown theme '{}', expected one of: {}", theme, THEMES.join(", ")),
+            ));
+        }
+    }
+
+    if let Some(timezone) = request.timezone.as_deref() {
+        if parse_timezone(timezone).is_none() {
+            warn!("Rejected unknown timezone '{}' for user {}", timezone, user_id);
+            return Err(settings_error(
+                StatusCode::BAD_REQUEST,
+                "INVALID_TIMEZONE",
+                format!("Unknown timezone '{}', expected an IANA name such as Europe/Prague", timezone),
+            ));
+        }
+    }
+
+    if let Some(scenario_id) = request.active_scenario_id {
+        match scenario::Entity::find_by_id(scenario_id).one(&state.db).await {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                warn!("Attempted to activate non-existent scenario {} for user {}", scenario_id, user_id);
+                return Err(settings_error(
+                    StatusCode::BAD_REQUEST,
+                    "INVALID_SCENARIO_ID",
+                    format!("Scenario with id {} does not exist", scenario_id),
+                ));
+            }
+            Err(db_error) => {
+                error!("Failed to validate scenario {}: {}", scenario_id, db_error);
+                return Err(settings_error(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "DATABASE_ERROR",
+                    "Failed to validate scenario".to_string(),
+                ));
+            }
+        }
+    }
+
+    let mut user_active: user::ActiveModel = user_model.into();
+    user_active.active_scenario_id = Set(request.active_scenario_id);
+    user_active.theme = Set(request.theme);
+    user_active.timezone = Set(request.timezone);
+    match user_active.update(&state.db).await {
+        Ok(updated_user) => {
+            info!(
+                "User {} settings updated (active scenario {:?}, theme {:?}, timezone {:?})",
+                user_id, updated_user.active_scenario_id, updated_user.theme, updated_user.timezone
+            );
+            Ok(Json(ApiResponse {
+                data: UserSettings::from(updated_user),
+                message: "User settings updated successfully".to_string(),
+                success: true,
+            }))
+        }
+        Err(db_error) => {
+            error!("Failed to update settings of user {}: {}", user_id, db_error);
+            Err(settings_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DATABASE_ERROR",
+                "Failed to update user settings".to_string(),
+            ))
+        }
+    }
+}
@@ -1,3 +1,21 @@
+pub mod access;
+pub mod audit_chain;
+pub mod auth;
+pub mod bank_charges;
+pub mod category_taxonomy;
 pub mod colors;
 pub mod converters;
+pub mod double_entry;
+pub mod field_encryption;
+pub mod forecast_snapshots;
+pub mod fx;
+pub mod geo;
+pub mod merchants;
+pub mod notifications;
+pub mod pdf;
+pub mod scenarios;
+pub mod schema_drift;
+pub mod sparse_fields;
 pub mod stats;
+pub mod time_travel;
+pub mod timezone;
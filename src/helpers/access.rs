@@ -0,0 +1,220 @@
+use std::collections::HashSet;
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{request::Parts, StatusCode},
+    response::Json,
+};
+use model::entities::{account, account_allowed_user, account_share};
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QuerySelect, Select};
+use tracing::error;
+
+use crate::helpers::auth::AuthenticatedUser;
+use crate::schemas::{AppState, ErrorResponse};
+
+/// The accounts a request may work with.
+///
+/// Authenticated users see the accounts they own plus the ones shared with them
+/// through `accounts_allowed_users` or `account_shares`. Allowed users and write shares
+/// may book on the account, read shares only see it; only the owner may change or
+/// delete an account itself. Anonymous requests, which only get through when
+/// authentication isn't required, are unrestricted so single-user installations keep
+/// working.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountAccess {
+    Unrestricted,
+    User {
+        user_id: i32,
+        owned: HashSet<i32>,
+        /// Shared with write access
+        shared: HashSet<i32>,
+        /// Shared with read access only
+        read_only: HashSet<i32>,
+    },
+}
+
+impl AccountAccess {
+    /// Loads the accounts visible to `auth`.
+    pub async fn load(db: &DatabaseConnection, auth: Option<&AuthenticatedUser>) -> Result<Self, DbErr> {
+        let Some(auth) = auth else {
+            return Ok(Self::Unrestricted);
+        };
+
+        let owned = account::Entity::find()
+            .select_only()
+            .column(account::Column::Id)
+            .filter(account::Column::OwnerId.eq(auth.user_id))
+            .into_tuple::<i32>()
+            .all(db)
+            .await?;
+        let shared = account_allowed_user::Entity::find()
+            .select_only()
+            .column(account_allowed_user::Column::AccountId)
+            .filter(account_allowed_user::Column::UserId.eq(auth.user_id))
+            .into_tuple::<i32>()
+            .all(db)
+            .await?;
+        let shares = account_share::Entity::find()
+            .select_only()
+            .column(account_share::Column::AccountId)
+            .column(account_share::Column::Permission)
+            .filter(account_share::Column::UserId.eq(auth.user_id))
+            .into_tuple::<(i32, account_share::SharePermission)>()
+            .all(db)
+            .await?;
+
+        let mut shared: HashSet<i32> = shared.into_iter().collect();
+        let mut read_only = HashSet::new();
+        for (account_id, permission) in shares {
+            match permission {
+                account_share::SharePermission::Write => shared.insert(account_id),
+                account_share::SharePermission::Read => read_only.insert(account_id),
+            };
+        }
+        read_only.retain(|account_id| !shared.contains(account_id));
+
+        Ok(Self::User {
+            user_id: auth.user_id,
+            owned: owned.into_iter().collect(),
+            shared,
+            read_only,
+        })
+    }
+
+    /// The authenticated user, if any.
+    pub fn user_id(&self) -> Option<i32> {
+        match self {
+            Self::Unrestricted => None,
+            Self::User { user_id, .. } => Some(*user_id),
+        }
+    }
+
+    /// Whether the account and everything booked on it may be read.
+    pub fn can_access(&self, account_id: i32) -> bool {
+        match self {
+            Self::Unrestricted => true,
+            Self::User { owned, shared, read_only, .. } => {
+                owned.contains(&account_id) || shared.contains(&account_id) || read_only.contains(&account_id)
+            }
+        }
+    }
+
+    /// Whether records booked on the account may be created, changed and deleted.
+    pub fn can_write(&self, account_id: i32) -> bool {
+        match self {
+            Self::Unrestricted => true,
+            Self::User { owned, shared, .. } => owned.contains(&account_id) || shared.contains(&account_id),
+        }
+    }
+
+    /// Whether the account itself may be changed, deleted or shared.
+    pub fn can_manage(&self, account_id: i32) -> bool {
+        match self {
+            Self::Unrestricted => true,
+            Self::User { owned, .. } => owned.contains(&account_id),
+        }
+    }
+
+    /// Whether a record booked on `target_account_id`, and optionally transferring from
+    /// `source_account_id`, is visible. Either side of a transfer is enough.
+    pub fn can_access_any(&self, target_account_id: i32, source_account_id: Option<i32>) -> bool {
+        self.can_access(target_account_id) || source_account_id.is_some_and(|id| self.can_access(id))
+    }
+
+    /// Whether a record booked on `target_account_id`, and optionally transferring from
+    /// `source_account_id`, may be changed. Either side of a transfer is enough.
+    pub fn can_write_any(&self, target_account_id: i32, source_account_id: Option<i32>) -> bool {
+        self.can_write(target_account_id) || source_account_id.is_some_and(|id| self.can_write(id))
+    }
+
+    /// Rejects changes to a visible record that is only booked on read-only accounts.
+    pub fn ensure_can_write_any(
+        &self,
+        target_account_id: i32,
+        source_account_id: Option<i32>,
+    ) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+        if self.can_write_any(target_account_id, source_account_id) {
+            Ok(())
+        } else {
+            Err(account_read_only(target_account_id))
+        }
+    }
+
+    /// Rejects changes to an account that isn't the caller's: 404 when they can't see it
+    /// at all, 403 when it is only shared with them.
+    pub fn ensure_can_manage(&self, account_id: i32) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+        if self.can_manage(account_id) {
+            Ok(())
+        } else if self.can_access(account_id) {
+            Err(account_forbidden(account_id))
+        } else {
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: format!("Account with id {} not found", account_id),
+                    code: "ACCOUNT_NOT_FOUND".to_string(),
+                    success: false,
+                }),
+            ))
+        }
+    }
+
+    /// Restricts `select` to rows where any of `columns` references an accessible account.
+    pub fn filter<E: EntityTrait>(&self, select: Select<E>, columns: impl IntoIterator<Item = E::Column>) -> Select<E> {
+        match self {
+            Self::Unrestricted => select,
+            Self::User { owned, shared, read_only, .. } => {
+                let ids: Vec<i32> = owned.iter().chain(shared).chain(read_only).copied().collect();
+                let condition = columns
+                    .into_iter()
+                    .fold(Condition::any(), |condition, column| condition.add(column.is_in(ids.clone())));
+                select.filter(condition)
+            }
+        }
+    }
+}
+
+/// 403 answered when a request references an account the caller can't use.
+pub fn account_forbidden(account_id: i32) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::FORBIDDEN,
+        Json(ErrorResponse {
+            error: format!("No access to account {}", account_id),
+            code: "ACCOUNT_FORBIDDEN".to_string(),
+            success: false,
+        }),
+    )
+}
+
+/// 403 answered when changing records of an account shared read-only with the caller.
+pub fn account_read_only(account_id: i32) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        StatusCode::FORBIDDEN,
+        Json(ErrorResponse {
+            error: format!("Account {} is shared with you read-only", account_id),
+            code: "ACCOUNT_READ_ONLY".to_string(),
+            success: false,
+        }),
+    )
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for AccountAccess {
+    type Rejection = (StatusCode, Json<ErrorResponse>);
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let auth = parts.extensions.get::<AuthenticatedUser>();
+        Self::load(&state.db, auth).await.map_err(|e| {
+            error!("Failed to load accessible accounts: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Failed to load accessible accounts".to_string(),
+                    code: "DATABASE_ERROR".to_string(),
+                    success: false,
+                }),
+            )
+        })
+    }
+}
@@ -0,0 +1,175 @@
+use chrono::{NaiveDateTime, Timelike, Utc};
+use model::entities::audit_entry;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, DbErr, EntityTrait, QueryOrder, Set};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::Mutex;
+use utoipa::ToSchema;
+
+/// `previous_hash` of the first entry in the chain.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Recorded instead of the value of a sensitive request body field.
+pub const REDACTED: &str = "[redacted]";
+
+/// Request body fields whose values never enter the chain: credentials, note secrets and
+/// uploaded file contents.
+const SENSITIVE_FIELDS: [&str; 6] = [
+    "password",
+    "current_password",
+    "new_password",
+    "secret",
+    "token",
+    "content_base64",
+];
+
+/// Serializes appends so concurrent requests can't both link to the same predecessor.
+static APPEND_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// Hex SHA-256 over an entry's fields and the hash of the entry before it.
+///
+/// Every field is length-prefixed so values can't be shifted between fields
+/// without changing the hash.
+pub fn entry_hash(
+    previous_hash: &str,
+    recorded_at: NaiveDateTime,
+    method: &str,
+    path: &str,
+    status: i32,
+    payload: &str,
+) -> String {
+    let recorded_at = recorded_at.format("%Y-%m-%dT%H:%M:%S%.6f").to_string();
+    let status = status.to_string();
+
+    let mut hasher = Sha256::new();
+    for field in [previous_hash, &recorded_at, method, path, &status, payload] {
+        hasher.update((field.len() as u64).to_be_bytes());
+        hasher.update(field.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+fn redact_value(value: &mut serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Object(fields) => {
+            let mut redacted = false;
+            for (name, field) in fields.iter_mut() {
+                if SENSITIVE_FIELDS.contains(&name.as_str()) && !field.is_null() {
+                    *field = serde_json::Value::String(REDACTED.to_string());
+                    redacted = true;
+                } else {
+                    redacted |= redact_value(field);
+                }
+            }
+            redacted
+        }
+        serde_json::Value::Array(items) => items.iter_mut().fold(false, |redacted, item| redact_value(item) | redacted),
+        _ => false,
+    }
+}
+
+/// The payload to record for a request body.
+///
+/// Sensitive fields of JSON bodies, at any depth, are replaced by [`REDACTED`]; other
+/// bodies, and JSON bodies without such fields, are recorded as they are.
+pub fn redact_payload(payload: &str) -> String {
+    let Ok(mut json) = serde_json::from_str::<serde_json::Value>(payload) else {
+        return payload.to_string();
+    };
+    if redact_value(&mut json) {
+        json.to_string()
+    } else {
+        payload.to_string()
+    }
+}
+
+/// Appends an entry to the end of the chain.
+pub async fn append_entry(
+    db: &DatabaseConnection,
+    method: &str,
+    path: &str,
+    status: i32,
+    payload: &str,
+) -> Result<audit_entry::Model, DbErr> {
+    let _guard = APPEND_LOCK.lock().await;
+
+    let previous_hash = audit_entry::Entity::find()
+        .order_by_desc(audit_entry::Column::Id)
+        .one(db)
+        .await?
+        .map(|entry| entry.hash)
+        .unwrap_or_else(|| GENESIS_HASH.to_string());
+
+    // Stored timestamps keep microseconds at most, so hash exactly what is stored
+    let now = Utc::now().naive_utc();
+    let recorded_at = now.with_nanosecond(now.nanosecond() / 1_000 * 1_000).unwrap_or(now);
+    let hash = entry_hash(&previous_hash, recorded_at, method, path, status, payload);
+
+    audit_entry::ActiveModel {
+        recorded_at: Set(recorded_at),
+        method: Set(method.to_string()),
+        path: Set(path.to_string()),
+        status: Set(status),
+        payload: Set(payload.to_string()),
+        previous_hash: Set(previous_hash),
+        hash: Set(hash),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+}
+
+/// Outcome of walking the whole audit chain.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditChainVerification {
+    /// True when every entry links to its predecessor and matches its own hash
+    pub valid: bool,
+    /// Number of entries checked
+    pub entries_checked: usize,
+    /// Hash of the last entry, to compare with a copy kept elsewhere
+    pub head_hash: Option<String>,
+    /// First entry that doesn't verify
+    pub first_invalid_id: Option<i32>,
+    /// Why `first_invalid_id` failed
+    pub reason: Option<String>,
+}
+
+/// Walks the audit chain from the first entry and stops at the first broken link.
+pub async fn verify_chain(db: &DatabaseConnection) -> Result<AuditChainVerification, DbErr> {
+    let entries = audit_entry::Entity::find()
+        .order_by_asc(audit_entry::Column::Id)
+        .all(db)
+        .await?;
+
+    let mut previous_hash = GENESIS_HASH.to_string();
+    for (checked, entry) in entries.iter().enumerate() {
+        let reason = if entry.previous_hash != previous_hash {
+            Some("Entry does not link to the previous entry; an entry was removed or reordered")
+        } else if entry.hash
+            != entry_hash(&entry.previous_hash, entry.recorded_at, &entry.method, &entry.path, entry.status, &entry.payload)
+        {
+            Some("Entry content does not match its hash; the entry was modified")
+        } else {
+            None
+        };
+
+        if let Some(reason) = reason {
+            return Ok(AuditChainVerification {
+                valid: false,
+                entries_checked: checked + 1,
+                head_hash: entries.last().map(|entry| entry.hash.clone()),
+                first_invalid_id: Some(entry.id),
+                reason: Some(reason.to_string()),
+            });
+        }
+        previous_hash = entry.hash.clone();
+    }
+
+    Ok(AuditChainVerification {
+        valid: true,
+        entries_checked: entries.len(),
+        head_hash: entries.last().map(|entry| entry.hash.clone()),
+        first_invalid_id: None,
+        reason: None,
+    })
+}
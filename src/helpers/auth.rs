@@ -0,0 +1,213 @@
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderMap, StatusCode},
+    response::Json,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use model::entities::{api_token, user, user_session};
+use sea_orm::{ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait, QueryFilter, Set};
+use sha2::{Digest, Sha256};
+
+use crate::schemas::ErrorResponse;
+
+/// Days a session stays valid after login.
+pub const SESSION_TTL_DAYS: i64 = 30;
+
+/// Number of random bytes in a session token.
+const SESSION_TOKEN_BYTES: usize = 32;
+
+/// Prefix telling API tokens apart from session tokens.
+pub const API_TOKEN_PREFIX: &str = "frt_";
+
+/// Characters of an API token kept in clear text to recognize it.
+pub const API_TOKEN_DISPLAY_LENGTH: usize = 12;
+
+/// Hashes a password with Argon2id and a random salt, returning the PHC string to store.
+pub fn hash_password(password: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    Ok(Argon2::default().hash_password(password.as_bytes(), &salt)?.to_string())
+}
+
+/// Whether `password` matches a hash produced by [`hash_password`].
+///
+/// A malformed stored hash never matches.
+pub fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash)
+        .map(|parsed| Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+        .unwrap_or(false)
+}
+
+/// A new random bearer token, URL-safe base64 without padding.
+pub fn generate_session_token() -> String {
+    let mut bytes = [0u8; SESSION_TOKEN_BYTES];
+    argon2::password_hash::rand_core::RngCore::fill_bytes(&mut OsRng, &mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// A new random API token: [`API_TOKEN_PREFIX`] followed by a session-style token.
+pub fn generate_api_token() -> String {
+    format!("{}{}", API_TOKEN_PREFIX, generate_session_token())
+}
+
+/// Hex-encoded SHA-256 of a bearer token, as stored in `user_sessions` and `api_tokens`.
+pub fn hash_bearer_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+/// The token of an `Authorization: Bearer <token>` header, if present.
+pub fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+/// What a request was authenticated with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Credential {
+    /// Id of the login session.
+    Session(i32),
+    /// Id of the API token.
+    ApiToken(i32),
+}
+
+/// The user a request was authenticated as.
+///
+/// Put into the request extensions by the `authenticate` middleware. Handlers that
+/// need the caller take it as an extractor, which rejects unauthenticated requests
+/// with 401; handlers that only use it when available take `Option<AuthenticatedUser>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticatedUser {
+    pub user_id: i32,
+    pub username: String,
+    pub credential: Credential,
+}
+
+/// Looks up the user of a bearer token, which is either an API token or a session token.
+pub async fn find_bearer_user(db: &DatabaseConnection, token: &str) -> Result<Option<AuthenticatedUser>, DbErr> {
+    if token.starts_with(API_TOKEN_PREFIX) {
+        find_api_token_user(db, token).await
+    } else {
+        find_session_user(db, token).await
+    }
+}
+
+/// Looks up the user of an unexpired session by its bearer token.
+pub async fn find_session_user(
+    db: &DatabaseConnection,
+    token: &str,
+) -> Result<Option<AuthenticatedUser>, DbErr> {
+    let now = chrono::Utc::now().naive_utc();
+    let session = user_session::Entity::find()
+        .filter(user_session::Column::TokenHash.eq(hash_bearer_token(token)))
+        .filter(user_session::Column::ExpiresAt.gt(now))
+        .find_also_related(user::Entity)
+        .one(db)
+        .await?;
+
+    Ok(session.and_then(|(session, user)| {
+        user.map(|user| AuthenticatedUser {
+            user_id: user.id,
+            username: user.username,
+            credential: Credential::Session(session.id),
+        })
+    }))
+}
+
+/// Looks up the user of an unexpired API token and records that the token was used.
+pub async fn find_api_token_user(
+    db: &DatabaseConnection,
+    token: &str,
+) -> Result<Option<AuthenticatedUser>, DbErr> {
+    let now = chrono::Utc::now().naive_utc();
+    let found = api_token::Entity::find()
+        .filter(api_token::Column::TokenHash.eq(hash_bearer_token(token)))
+        .filter(
+            Condition::any()
+                .add(api_token::Column::ExpiresAt.is_null())
+                .add(api_token::Column::ExpiresAt.gt(now)),
+        )
+        .find_also_related(user::Entity)
+        .one(db)
+        .await?;
+    let Some((api_token, Some(user))) = found else {
+        return Ok(None);
+    };
+
+    let token_id = api_token.id;
+    let mut active: api_token::ActiveModel = api_token.into();
+    active.last_used_at = Set(Some(now));
+    active.update(db).await?;
+
+    Ok(Some(AuthenticatedUser {
+        user_id: user.id,
+        username: user.username,
+        credential: Credential::ApiToken(token_id),
+    }))
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AuthenticatedUser
+where
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, Json<ErrorResponse>);
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts.extensions.get::<AuthenticatedUser>().cloned().ok_or_else(|| {
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(ErrorResponse {
+                    error: "Authentication required".to_string(),
+                    code: "UNAUTHORIZED".to_string(),
+                    success: false,
+                }),
+            )
+        })
+    }
+}
+
+/// Rejects the request with `403 Forbidden` unless the caller is an administrator.
+///
+/// Requests without an authenticated user are let through, as the server then runs
+/// without authentication. `action` completes "Only administrators may ...".
+pub async fn ensure_admin(
+    db: &DatabaseConnection,
+    auth: Option<&AuthenticatedUser>,
+    action: &str,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    let Some(auth) = auth else {
+        return Ok(());
+    };
+    let caller = user::Entity::find_by_id(auth.user_id).one(db).await.map_err(|e| {
+        tracing::error!("Database error while checking administrator rights: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to check administrator rights".to_string(),
+                code: "DATABASE_ERROR".to_string(),
+                success: false,
+            }),
+        )
+    })?;
+    if caller.is_some_and(|caller| caller.is_admin) {
+        return Ok(());
+    }
+    tracing::warn!("User {} is not allowed to {}", auth.user_id, action);
+    Err((
+        StatusCode::FORBIDDEN,
+        Json(ErrorResponse {
+            error: format!("Only administrators may {}", action),
+            code: "ADMIN_REQUIRED".to_string(),
+            success: false,
+        }),
+    ))
+}
@@ -0,0 +1,123 @@
+use model::entities::category;
+use rust_decimal::Decimal;
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, Set};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Parent system category grouping fees and interest paid to the bank.
+pub const BANK_COSTS_CATEGORY: &str = "Bank Costs";
+
+/// Words that mark a row as a bank fee.
+const FEE_WORDS: &[&str] = &["FEE", "FEES", "POPLATEK", "POPLATKY", "POPLATKU", "COMMISSION"];
+/// Phrases that mark a row as a bank fee where single words would be too broad.
+const FEE_PHRASES: &[&str] = &["SERVICE CHARGE", "BANK CHARGE", "ACCOUNT MAINTENANCE", "OVERDRAFT CHARGE", "VEDENI UCTU"];
+/// Words that mark a row as interest.
+const INTEREST_WORDS: &[&str] = &["INTEREST", "UROK", "UROKY", "UROKU", "ZINSEN"];
+
+/// Kind of bank charge recognised in an imported row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum BankChargeKind {
+    /// Account, card or transaction fee paid to the bank.
+    Fee,
+    /// Interest paid to the bank (overdraft, loan).
+    InterestCharged,
+    /// Interest credited by the bank.
+    InterestEarned,
+}
+
+impl BankChargeKind {
+    /// Name of the system category rows of this kind are booked into.
+    pub fn category_name(&self) -> &'static str {
+        match self {
+            BankChargeKind::Fee => "Bank Fees",
+            BankChargeKind::InterestCharged => "Interest Charges",
+            BankChargeKind::InterestEarned => "Interest Income",
+        }
+    }
+
+    /// Parent of the system category, so fees and charged interest roll up into bank costs.
+    fn parent_category_name(&self) -> Option<&'static str> {
+        match self {
+            BankChargeKind::Fee | BankChargeKind::InterestCharged => Some(BANK_COSTS_CATEGORY),
+            BankChargeKind::InterestEarned => None,
+        }
+    }
+}
+
+/// Recognise bank fees and interest from an imported row's description.
+///
+/// Matching is done on whole words of the upper-cased description with Czech
+/// diacritics folded, so "Poplatek za vedení účtu" and "Monthly account fee" are
+/// fees while "Coffee" is not. Interest is classified by the sign of the amount;
+/// positive "fee" rows are refunds and are left alone.
+pub fn classify_bank_charge(description: &str, amount: Decimal) -> Option<BankChargeKind> {
+    let normalized = fold_diacritics(&description.to_uppercase());
+    let words: Vec<&str> = normalized
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    let phrase = words.join(" ");
+
+    if words.iter().any(|word| INTEREST_WORDS.contains(word)) {
+        return match amount.cmp(&Decimal::ZERO) {
+            std::cmp::Ordering::Greater => Some(BankChargeKind::InterestEarned),
+            std::cmp::Ordering::Less => Some(BankChargeKind::InterestCharged),
+            std::cmp::Ordering::Equal => None,
+        };
+    }
+
+    let is_fee = words.iter().any(|word| FEE_WORDS.contains(word))
+        || FEE_PHRASES.iter().any(|fee_phrase| phrase.contains(fee_phrase));
+    (is_fee && amount < Decimal::ZERO).then_some(BankChargeKind::Fee)
+}
+
+fn fold_diacritics(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            'Á' => 'A',
+            'Č' => 'C',
+            'Ď' => 'D',
+            'É' | 'Ě' => 'E',
+            'Í' => 'I',
+            'Ň' => 'N',
+            'Ó' => 'O',
+            'Ř' => 'R',
+            'Š' => 'S',
+            'Ť' => 'T',
+            'Ú' | 'Ů' => 'U',
+            'Ý' => 'Y',
+            'Ž' => 'Z',
+            'Ü' => 'U',
+            c => c,
+        })
+        .collect()
+}
+
+/// Return the ID of the system category for `kind`, creating it (and its parent) if missing.
+pub async fn system_category_id<C: ConnectionTrait>(db: &C, kind: BankChargeKind) -> Result<i32, DbErr> {
+    let parent_id = match kind.parent_category_name() {
+        Some(parent) => Some(find_or_create_category(db, parent, None).await?),
+        None => None,
+    };
+    find_or_create_category(db, kind.category_name(), parent_id).await
+}
+
+async fn find_or_create_category<C: ConnectionTrait>(db: &C, name: &str, parent_id: Option<i32>) -> Result<i32, DbErr> {
+    if let Some(existing) = category::Entity::find()
+        .filter(category::Column::Name.eq(name))
+        .one(db)
+        .await?
+    {
+        return Ok(existing.id);
+    }
+
+    let created = category::ActiveModel {
+        name: Set(name.to_string()),
+        description: Set(Some("Created automatically for bank fees and interest".to_string())),
+        parent_id: Set(parent_id),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    Ok(created.id)
+}
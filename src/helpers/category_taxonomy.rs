@@ -0,0 +1,144 @@
+use model::entities::category;
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, Set};
+
+/// Locales the starter taxonomy is translated into.
+pub const SUPPORTED_LOCALES: &[&str] = &["en", "cs"];
+
+/// A starter category with its English and Czech names.
+struct StarterCategory {
+    en: &'static str,
+    cs: &'static str,
+    children: &'static [(&'static str, &'static str)],
+}
+
+impl StarterCategory {
+    fn name(&self, locale: &str) -> &'static str {
+        localized(locale, (self.en, self.cs))
+    }
+}
+
+fn localized(locale: &str, (en, cs): (&'static str, &'static str)) -> &'static str {
+    match locale {
+        "cs" => cs,
+        _ => en,
+    }
+}
+
+const STARTER_TAXONOMY: &[StarterCategory] = &[
+    StarterCategory {
+        en: "Housing",
+        cs: "Bydlení",
+        children: &[
+            ("Rent", "Nájem"),
+            ("Mortgage", "Hypotéka"),
+            ("Utilities", "Energie"),
+            ("Home Maintenance", "Údržba domácnosti"),
+        ],
+    },
+    StarterCategory {
+        en: "Food",
+        cs: "Jídlo",
+        children: &[("Groceries", "Potraviny"), ("Restaurants", "Restaurace"), ("Coffee", "Káva")],
+    },
+    StarterCategory {
+        en: "Transport",
+        cs: "Doprava",
+        children: &[
+            ("Fuel", "Pohonné hmoty"),
+            ("Public Transport", "Veřejná doprava"),
+            ("Car Maintenance", "Údržba auta"),
+            ("Parking", "Parkování"),
+        ],
+    },
+    StarterCategory {
+        en: "Health",
+        cs: "Zdraví",
+        children: &[("Pharmacy", "Lékárna"), ("Doctor", "Lékař"), ("Sport", "Sport")],
+    },
+    StarterCategory {
+        en: "Shopping",
+        cs: "Nákupy",
+        children: &[("Clothing", "Oblečení"), ("Electronics", "Elektronika"), ("Household", "Domácnost")],
+    },
+    StarterCategory {
+        en: "Leisure",
+        cs: "Volný čas",
+        children: &[("Entertainment", "Zábava"), ("Travel", "Cestování"), ("Subscriptions", "Předplatné")],
+    },
+    StarterCategory {
+        en: "Bills",
+        cs: "Poplatky",
+        children: &[("Phone & Internet", "Telefon a internet"), ("Insurance", "Pojištění"), ("Taxes", "Daně")],
+    },
+    StarterCategory {
+        en: "Income",
+        cs: "Příjmy",
+        children: &[("Salary", "Mzda"), ("Bonus", "Odměny"), ("Refunds", "Vratky")],
+    },
+    StarterCategory {
+        en: "Savings & Investments",
+        cs: "Spoření a investice",
+        children: &[],
+    },
+    StarterCategory {
+        en: "Gifts & Donations",
+        cs: "Dary",
+        children: &[],
+    },
+];
+
+/// Outcome of seeding the starter taxonomy.
+pub struct SeedOutcome {
+    /// Categories created by this run.
+    pub created: Vec<category::Model>,
+    /// Number of starter categories that already existed and were left untouched.
+    pub existing: usize,
+}
+
+/// Install the starter category taxonomy in `locale`.
+///
+/// Categories are matched by name, so running the seed again (or after the user
+/// created some of the categories by hand) only adds what is missing.
+pub async fn seed_starter_categories<C: ConnectionTrait>(db: &C, locale: &str) -> Result<SeedOutcome, DbErr> {
+    let mut outcome = SeedOutcome {
+        created: Vec::new(),
+        existing: 0,
+    };
+
+    for starter in STARTER_TAXONOMY {
+        let parent_id = ensure_category(db, starter.name(locale), None, &mut outcome).await?;
+        for child in starter.children {
+            ensure_category(db, localized(locale, *child), Some(parent_id), &mut outcome).await?;
+        }
+    }
+
+    Ok(outcome)
+}
+
+async fn ensure_category<C: ConnectionTrait>(
+    db: &C,
+    name: &str,
+    parent_id: Option<i32>,
+    outcome: &mut SeedOutcome,
+) -> Result<i32, DbErr> {
+    if let Some(existing) = category::Entity::find()
+        .filter(category::Column::Name.eq(name))
+        .one(db)
+        .await?
+    {
+        outcome.existing += 1;
+        return Ok(existing.id);
+    }
+
+    let created = category::ActiveModel {
+        name: Set(name.to_string()),
+        description: Set(None),
+        parent_id: Set(parent_id),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    let id = created.id;
+    outcome.created.push(created);
+    Ok(id)
+}
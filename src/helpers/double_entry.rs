@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::ToSchema;
+
+/// A way in which a transaction fails to balance as a double-entry posting.
+///
+/// Categories play the role of income/expense accounts: a transaction either moves
+/// money between two asset accounts (a transfer) or between one account and a category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DoubleEntryViolation {
+    /// Neither a source account nor a category, so money appears or disappears.
+    MissingCounterEntry,
+    /// Source and target are the same account.
+    SelfTransfer,
+    /// A transfer that also posts to a category (three legs).
+    TransferWithCategory,
+    /// A transfer between accounts in different currencies can't balance with one amount.
+    CurrencyMismatch,
+}
+
+impl DoubleEntryViolation {
+    pub fn description(&self) -> &'static str {
+        match self {
+            DoubleEntryViolation::MissingCounterEntry => {
+                "Income and expenses must post to a category; transfers need a source account"
+            }
+            DoubleEntryViolation::SelfTransfer => "Source and target account are the same",
+            DoubleEntryViolation::TransferWithCategory => {
+                "Transfers between accounts must not also post to a category"
+            }
+            DoubleEntryViolation::CurrencyMismatch => {
+                "Transfer legs are in different currencies"
+            }
+        }
+    }
+}
+
+/// Checks the legs of a transaction against the double-entry rules.
+///
+/// `currencies` maps account IDs to their currency codes; accounts missing from the
+/// map are not checked for currency mismatches. Transfers that value their source leg
+/// with an exchange rate or a second amount (`converts_currency`) may span currencies.
+pub fn check_double_entry(
+    target_account_id: i32,
+    source_account_id: Option<i32>,
+    category_id: Option<i32>,
+    currencies: &HashMap<i32, String>,
+    converts_currency: bool,
+) -> Vec<DoubleEntryViolation> {
+    let mut violations = Vec::new();
+    match source_account_id {
+        None if category_id.is_none() => violations.push(DoubleEntryViolation::MissingCounterEntry),
+        None => {}
+        Some(source_account_id) => {
+            if source_account_id == target_account_id {
+                violations.push(DoubleEntryViolation::SelfTransfer);
+            }
+            if category_id.is_some() {
+                violations.push(DoubleEntryViolation::TransferWithCategory);
+            }
+            if let (Some(source), Some(target)) = (
+                currencies.get(&source_account_id),
+                currencies.get(&target_account_id),
+            ) {
+                if !converts_currency && !source.eq_ignore_ascii_case(target) {
+                    violations.push(DoubleEntryViolation::CurrencyMismatch);
+                }
+            }
+        }
+    }
+    violations
+}
@@ -0,0 +1,114 @@
+use aes_gcm::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Key, Nonce,
+};
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::Utc;
+use model::entities::field_encryption_salt;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, DbErr, EntityTrait, QueryOrder, Set};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use thiserror::Error;
+
+/// Length of the random nonce stored in front of every ciphertext.
+const NONCE_LEN: usize = 12;
+
+/// Length of the salt generated for key derivation.
+const SALT_LEN: usize = 16;
+
+#[derive(Debug, Error)]
+pub enum FieldEncryptionError {
+    #[error("Failed to encrypt field")]
+    Encrypt,
+
+    #[error("Stored value is not a valid encrypted field")]
+    Malformed,
+
+    #[error("Failed to decrypt field; it was encrypted with a different key")]
+    Decrypt,
+
+    #[error("Failed to derive the field encryption key: {0}")]
+    KeyDerivation(String),
+}
+
+/// Encrypts sensitive text fields, like account numbers, before they are stored.
+///
+/// Values are sealed with AES-256-GCM and stored as base64 of the nonce followed by the
+/// ciphertext. The key is derived from the configured passphrase with Argon2id and the
+/// salt stored in the database, see [`field_encryption_salt`].
+///
+/// Values sealed before the key was derived with Argon2id used the SHA-256 of the
+/// passphrase as key; they are still decrypted, and sealed with the new key once saved again.
+#[derive(Clone)]
+pub struct FieldCipher {
+    cipher: Aes256Gcm,
+    legacy_cipher: Aes256Gcm,
+}
+
+impl fmt::Debug for FieldCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("FieldCipher { .. }")
+    }
+}
+
+/// Returns the salt the field encryption key is derived with, creating it on first use.
+pub async fn field_encryption_salt(db: &DatabaseConnection) -> Result<Vec<u8>, DbErr> {
+    let stored = field_encryption_salt::Entity::find()
+        .order_by_asc(field_encryption_salt::Column::Id)
+        .one(db)
+        .await?;
+    if let Some(stored) = stored {
+        return Ok(stored.salt);
+    }
+
+    let mut salt = vec![0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let created = field_encryption_salt::ActiveModel {
+        salt: Set(salt),
+        created_at: Set(Utc::now().naive_utc()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    Ok(created.salt)
+}
+
+impl FieldCipher {
+    /// Derives the key from `passphrase` and `salt` with Argon2id in its default parameters.
+    pub fn from_passphrase(passphrase: &str, salt: &[u8]) -> Result<Self, FieldEncryptionError> {
+        let mut key = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+            .map_err(|e| FieldEncryptionError::KeyDerivation(e.to_string()))?;
+        let legacy_key = Sha256::digest(passphrase.as_bytes());
+        Ok(Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)),
+            legacy_cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&legacy_key)),
+        })
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> Result<String, FieldEncryptionError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| FieldEncryptionError::Encrypt)?;
+        Ok(STANDARD.encode([nonce.as_slice(), &ciphertext].concat()))
+    }
+
+    pub fn decrypt(&self, stored: &str) -> Result<String, FieldEncryptionError> {
+        let sealed = STANDARD.decode(stored).map_err(|_| FieldEncryptionError::Malformed)?;
+        if sealed.len() < NONCE_LEN {
+            return Err(FieldEncryptionError::Malformed);
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let nonce = Nonce::from_slice(nonce);
+        let plaintext = self
+            .cipher
+            .decrypt(nonce, ciphertext)
+            .or_else(|_| self.legacy_cipher.decrypt(nonce, ciphertext))
+            .map_err(|_| FieldEncryptionError::Decrypt)?;
+        String::from_utf8(plaintext).map_err(|_| FieldEncryptionError::Malformed)
+    }
+}
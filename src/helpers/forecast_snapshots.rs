@@ -0,0 +1,115 @@
+use crate::helpers::converters::convert_dataframe_to_timeseries;
+use chrono::{Duration, NaiveDate};
+use compute::{
+    account::{utils::history_start, AccountStateCalculator},
+    default_compute,
+    error::ComputeError,
+};
+use model::entities::{account, forecast_snapshot};
+use rust_decimal::Decimal;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, Set};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Days ahead a forecast snapshot projects balances for.
+pub const FORECAST_HORIZONS: [i32; 2] = [30, 90];
+
+#[derive(Debug, Error)]
+pub enum ForecastSnapshotError {
+    #[error("Database error: {0}")]
+    Database(#[from] DbErr),
+
+    #[error("Compute error: {0}")]
+    Compute(#[from] ComputeError),
+
+    #[error("Failed to read computed balances: {0}")]
+    Conversion(String),
+}
+
+/// Computes daily balances of `accounts` between `start` and `end` as seen on `today`.
+///
+/// Days after `today` are projections, earlier days are what actually happened. Everything
+/// booked before `start` counts towards the balances.
+pub async fn balances_between(
+    db: &DatabaseConnection,
+    accounts: &[account::Model],
+    today: NaiveDate,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<HashMap<(i32, NaiveDate), Decimal>, ForecastSnapshotError> {
+    if accounts.is_empty() || start > end {
+        return Ok(HashMap::new());
+    }
+
+    let from = history_start(db, accounts, start).await?;
+    let df = default_compute(Some(today))
+        .compute_account_state(db, accounts, from, end)
+        .await?;
+    let timeseries = convert_dataframe_to_timeseries(df).map_err(ForecastSnapshotError::Conversion)?;
+    Ok(timeseries
+        .data_points
+        .into_iter()
+        .filter(|point| point.date >= start)
+        .map(|point| ((point.account_id, point.date), point.balance))
+        .collect())
+}
+
+/// Projects every account's balance `FORECAST_HORIZONS` days ahead of `made_on` and
+/// stores the projections, replacing those already stored for the same day.
+pub async fn record_forecast_snapshots(
+    db: &DatabaseConnection,
+    made_on: NaiveDate,
+) -> Result<Vec<forecast_snapshot::Model>, ForecastSnapshotError> {
+    let accounts = account::Entity::find().all(db).await?;
+    record_account_forecast_snapshots(db, &accounts, made_on).await
+}
+
+/// Records forecast snapshots as [`record_forecast_snapshots`] does, for `accounts` only.
+pub async fn record_account_forecast_snapshots(
+    db: &DatabaseConnection,
+    accounts: &[account::Model],
+    made_on: NaiveDate,
+) -> Result<Vec<forecast_snapshot::Model>, ForecastSnapshotError> {
+    let furthest = made_on + Duration::days(FORECAST_HORIZONS.into_iter().max().unwrap_or_default().into());
+    let balances = balances_between(db, accounts, made_on, made_on, furthest).await?;
+
+    let mut snapshots = Vec::new();
+    for account in accounts {
+        for horizon_days in FORECAST_HORIZONS {
+            let target_date = made_on + Duration::days(horizon_days.into());
+            let Some(projected_balance) = balances.get(&(account.id, target_date)).copied() else {
+                continue;
+            };
+
+            let existing = forecast_snapshot::Entity::find()
+                .filter(forecast_snapshot::Column::AccountId.eq(account.id))
+                .filter(forecast_snapshot::Column::MadeOn.eq(made_on))
+                .filter(forecast_snapshot::Column::HorizonDays.eq(horizon_days))
+                .one(db)
+                .await?;
+            let snapshot = match existing {
+                Some(existing) => {
+                    let mut update: forecast_snapshot::ActiveModel = existing.into();
+                    update.target_date = Set(target_date);
+                    update.projected_balance = Set(projected_balance);
+                    update.update(db).await?
+                }
+                None => {
+                    forecast_snapshot::ActiveModel {
+                        account_id: Set(account.id),
+                        made_on: Set(made_on),
+                        horizon_days: Set(horizon_days),
+                        target_date: Set(target_date),
+                        projected_balance: Set(projected_balance),
+                        ..Default::default()
+                    }
+                    .insert(db)
+                    .await?
+                }
+            };
+            snapshots.push(snapshot);
+        }
+    }
+
+    Ok(snapshots)
+}
@@ -0,0 +1,195 @@
+use chrono::NaiveDate;
+use common::{AmountLocale, parse_amount};
+use model::entities::exchange_rate;
+use rust_decimal::Decimal;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, Set};
+use serde_json::Value;
+
+const ORIGINAL_AMOUNT_KEYS: &[&str] = &["original_amount", "originalAmount", "foreign_amount", "instructed_amount"];
+const ORIGINAL_CURRENCY_KEYS: &[&str] = &["original_currency", "originalCurrency", "foreign_currency", "instructed_currency"];
+/// Nested objects holding `amount` and `currency` of the original payment.
+const ORIGINAL_OBJECT_KEYS: &[&str] = &["original", "instructedAmount", "foreign"];
+const FEE_KEYS: &[&str] = &["fx_fee", "fxFee", "conversion_fee", "currency_conversion_fee"];
+/// Rates without the bank's markup; the applied rate would imply a zero fee.
+const REFERENCE_RATE_KEYS: &[&str] = &["reference_rate", "referenceRate", "market_rate", "mid_rate", "ecb_rate"];
+
+/// Foreign currency details of an imported card payment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForeignCurrencyDetails {
+    /// Amount in the payment currency, with the same sign as the settled amount.
+    pub original_amount: Decimal,
+    pub original_currency: String,
+    /// Conversion fee in the account currency (positive), if it can be determined.
+    pub fx_fee: Option<Decimal>,
+}
+
+/// Extract the original amount and currency of a foreign payment from raw import data.
+///
+/// Accepts flat keys (`original_amount`/`original_currency` and common variants) or
+/// a nested `original`/`instructedAmount` object with `amount` and `currency`.
+/// Returns `None` when the payment was made in the account currency. The fee is
+/// taken from an explicit `fx_fee`-like field, or implied from a reference rate as
+/// the part of the settled amount exceeding `original_amount * reference_rate`.
+/// Amounts given as text are parsed with the import's `locale`.
+pub fn foreign_currency_from_raw_data(
+    raw_data: &Value,
+    settled_amount: Decimal,
+    account_currency: &str,
+    locale: AmountLocale,
+) -> Option<ForeignCurrencyDetails> {
+    let nested = ORIGINAL_OBJECT_KEYS.iter().find_map(|key| raw_data.get(*key).filter(|v| v.is_object()));
+    let (original_amount, original_currency) = match nested {
+        Some(object) => (decimal_field(object, &["amount"], locale)?, string_field(object, &["currency"])?),
+        None => (
+            decimal_field(raw_data, ORIGINAL_AMOUNT_KEYS, locale)?,
+            string_field(raw_data, ORIGINAL_CURRENCY_KEYS)?,
+        ),
+    };
+
+    let original_currency = original_currency.trim().to_uppercase();
+    if original_currency.is_empty() || original_currency.eq_ignore_ascii_case(account_currency) {
+        return None;
+    }
+
+    let original_amount = if settled_amount.is_sign_negative() {
+        -original_amount.abs()
+    } else {
+        original_amount.abs()
+    };
+
+    let fx_fee = decimal_field(raw_data, FEE_KEYS, locale).map(|fee| fee.abs()).or_else(|| {
+        let rate = decimal_field(raw_data, REFERENCE_RATE_KEYS, locale)?;
+        let converted = (original_amount.abs() * rate).round_dp(2);
+        Some((settled_amount.abs() - converted).max(Decimal::ZERO))
+    });
+
+    Some(ForeignCurrencyDetails {
+        original_amount,
+        original_currency,
+        fx_fee,
+    })
+}
+
+/// A payment made in another currency than the account it was booked to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurrencyConversion {
+    /// Amount in the payment currency, with the same sign as the settled amount.
+    pub original_amount: Decimal,
+    pub original_currency: String,
+    /// Units of the account currency paid per unit of `original_currency`.
+    pub conversion_rate: Decimal,
+}
+
+/// Normalizes a currency code to upper case, or `None` if it isn't three letters.
+pub fn normalize_currency(code: &str) -> Option<String> {
+    let code = code.trim();
+    (code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic())).then(|| code.to_uppercase())
+}
+
+/// Validate the foreign currency details entered for a transaction settled as
+/// `settled_amount` in `account_currency`.
+///
+/// Returns `Ok(None)` when no details were given. The original amount and currency
+/// must be given together; the original amount takes the sign of the settled amount
+/// and a missing rate is derived from both amounts. Errors describe the first problem.
+pub fn currency_conversion(
+    settled_amount: Decimal,
+    original_amount: Option<Decimal>,
+    original_currency: Option<&str>,
+    conversion_rate: Option<Decimal>,
+    account_currency: &str,
+) -> Result<Option<CurrencyConversion>, &'static str> {
+    let (original_amount, original_currency) = match (original_amount, original_currency) {
+        (None, None) if conversion_rate.is_none() => return Ok(None),
+        (Some(original_amount), Some(original_currency)) => (original_amount, original_currency),
+        _ => return Err("original_amount and original_currency must be given together"),
+    };
+    let original_currency =
+        normalize_currency(original_currency).ok_or("original_currency must be a three-letter ISO 4217 code")?;
+    if original_currency.eq_ignore_ascii_case(account_currency) {
+        return Err("original_currency must differ from the account currency");
+    }
+    if original_amount.is_zero() {
+        return Err("original_amount must not be zero");
+    }
+
+    let conversion_rate = match conversion_rate {
+        Some(rate) if rate <= Decimal::ZERO => return Err("conversion_rate must be positive"),
+        Some(rate) => rate,
+        None => (settled_amount.abs() / original_amount.abs()).round_dp(10),
+    };
+    let original_amount = if settled_amount.is_sign_negative() {
+        -original_amount.abs()
+    } else {
+        original_amount.abs()
+    };
+
+    Ok(Some(CurrencyConversion {
+        original_amount,
+        original_currency,
+        conversion_rate,
+    }))
+}
+
+fn decimal_field(object: &Value, keys: &[&str], locale: AmountLocale) -> Option<Decimal> {
+    keys.iter().find_map(|key| match object.get(*key)? {
+        Value::Number(n) => n.to_string().parse().ok(),
+        Value::String(s) => parse_amount(s, locale).ok(),
+        _ => None,
+    })
+}
+
+fn string_field(object: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|key| object.get(*key)?.as_str().map(str::to_string))
+}
+
+/// Stores the rate of a currency pair for a day, replacing the one already stored.
+///
+/// Currency codes are expected upper case. Returns the stored row and whether it
+/// was newly created.
+pub async fn store_rate(
+    db: &DatabaseConnection,
+    base: &str,
+    quote: &str,
+    date: NaiveDate,
+    rate: Decimal,
+) -> Result<(exchange_rate::Model, bool), DbErr> {
+    let existing = exchange_rate::Entity::find()
+        .filter(exchange_rate::Column::BaseCurrency.eq(base))
+        .filter(exchange_rate::Column::QuoteCurrency.eq(quote))
+        .filter(exchange_rate::Column::Date.eq(date))
+        .one(db)
+        .await?;
+
+    match existing {
+        Some(existing) => {
+            let mut active: exchange_rate::ActiveModel = existing.into();
+            active.rate = Set(rate);
+            Ok((active.update(db).await?, false))
+        }
+        None => {
+            let created = exchange_rate::ActiveModel {
+                base_currency: Set(base.to_string()),
+                quote_currency: Set(quote.to_string()),
+                date: Set(date),
+                rate: Set(rate),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+            Ok((created, true))
+        }
+    }
+}
+
+/// Rate converting one unit of `base` into `quote` on `date`.
+///
+/// See [`exchange_rate::rate_on`].
+pub async fn rate_on(
+    db: &DatabaseConnection,
+    base: &str,
+    quote: &str,
+    date: NaiveDate,
+) -> Result<Option<Decimal>, DbErr> {
+    exchange_rate::rate_on(db, base, quote, date).await
+}
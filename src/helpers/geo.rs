@@ -0,0 +1,33 @@
+use serde_json::Value;
+
+/// Returns true if the pair is a valid WGS84 coordinate.
+pub fn valid_coordinates(latitude: f64, longitude: f64) -> bool {
+    (-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude)
+}
+
+/// Extract `(latitude, longitude)` from an imported transaction's raw data.
+///
+/// Bank exports disagree on naming, so `latitude`/`lat` and `longitude`/`lon`/`lng`
+/// are accepted either at the top level or nested under `location` or `geo`.
+/// Values may be numbers or numeric strings.
+pub fn coordinates_from_raw_data(raw_data: &Value) -> Option<(f64, f64)> {
+    let candidates = [
+        Some(raw_data),
+        raw_data.get("location"),
+        raw_data.get("geo"),
+    ];
+
+    candidates.into_iter().flatten().find_map(|object| {
+        let latitude = number_field(object, &["latitude", "lat"])?;
+        let longitude = number_field(object, &["longitude", "lon", "lng"])?;
+        valid_coordinates(latitude, longitude).then_some((latitude, longitude))
+    })
+}
+
+fn number_field(object: &Value, keys: &[&str]) -> Option<f64> {
+    keys.iter().find_map(|key| match object.get(*key)? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    })
+}
@@ -0,0 +1,91 @@
+use model::entities::merchant;
+use sea_orm::{DatabaseConnection, DbErr, EntityTrait};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Prefixes banks put in front of card payment descriptions.
+const NOISE_PREFIXES: &[&str] = &["POS ", "CARD PAYMENT ", "PURCHASE ", "PAYPAL *", "SQ *", "SUMUP *"];
+
+/// Merchant details attached to transaction DTOs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MerchantInfo {
+    /// Cleaned merchant name
+    pub name: String,
+    /// Logo URL, if a mapping provides one
+    pub logo_url: Option<String>,
+    /// ID of the merchant mapping that matched, if any
+    pub merchant_id: Option<i32>,
+}
+
+/// Enriches raw bank descriptions using the local merchant mapping table.
+///
+/// Load it once per request and reuse it for every row.
+#[derive(Debug, Default)]
+pub struct MerchantEnricher {
+    merchants: Vec<merchant::Model>,
+}
+
+impl MerchantEnricher {
+    /// Load all merchant mappings from the database.
+    pub async fn load(db: &DatabaseConnection) -> Result<Self, DbErr> {
+        let merchants = merchant::Entity::find().all(db).await?;
+        Ok(Self { merchants })
+    }
+
+    /// Resolve merchant details for a description.
+    ///
+    /// Uses the longest matching mapping; falls back to a heuristically cleaned
+    /// name without a logo. Returns `None` if nothing usable is left.
+    pub fn enrich(&self, description: &str) -> Option<MerchantInfo> {
+        let mapping = self
+            .merchants
+            .iter()
+            .filter(|m| m.matches(description))
+            .max_by_key(|m| m.pattern.len());
+
+        match mapping {
+            Some(m) => Some(MerchantInfo {
+                name: m.name.clone(),
+                logo_url: m.logo_url.clone(),
+                merchant_id: Some(m.id),
+            }),
+            None => clean_merchant_name(description).map(|name| MerchantInfo {
+                name,
+                logo_url: None,
+                merchant_id: None,
+            }),
+        }
+    }
+}
+
+/// Strip card-terminal noise from a raw description.
+///
+/// Drops known payment processor prefixes and any token containing digits
+/// (terminal IDs, card numbers, dates), then title-cases the rest.
+pub fn clean_merchant_name(description: &str) -> Option<String> {
+    let upper = description.trim().to_uppercase();
+    let mut rest = upper.as_str();
+    for prefix in NOISE_PREFIXES {
+        if let Some(stripped) = rest.strip_prefix(prefix) {
+            rest = stripped;
+        }
+    }
+
+    let words: Vec<String> = rest
+        .split(|c: char| c.is_whitespace() || c == '*')
+        .filter(|word| !word.is_empty() && !word.chars().any(|c| c.is_ascii_digit()))
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_string() + &chars.as_str().to_lowercase(),
+                None => String::new(),
+            }
+        })
+        .collect();
+
+    if words.is_empty() {
+        None
+    } else {
+        Some(words.join(" "))
+    }
+}
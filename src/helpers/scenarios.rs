@@ -0,0 +1,39 @@
+use model::entities::{account, user};
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter};
+use std::collections::HashMap;
+
+/// Users' active-scenario preferences, used when a request doesn't name a scenario.
+///
+/// Load it once per request and reuse it for every account.
+#[derive(Debug, Default)]
+pub struct ScenarioPreferences {
+    by_user: HashMap<i32, i32>,
+}
+
+impl ScenarioPreferences {
+    /// Load the active scenario of every user that has one.
+    pub async fn load(db: &DatabaseConnection) -> Result<Self, DbErr> {
+        let by_user = user::Entity::find()
+            .filter(user::Column::ActiveScenarioId.is_not_null())
+            .all(db)
+            .await?
+            .into_iter()
+            .filter_map(|u| u.active_scenario_id.map(|scenario_id| (u.id, scenario_id)))
+            .collect();
+        Ok(Self { by_user })
+    }
+
+    /// Scenario to compute with: the requested one, otherwise the owner's preference.
+    ///
+    /// Accounts of several owners have no single preference, so they fall back to the baseline.
+    pub fn resolve(&self, requested: Option<i32>, accounts: &[account::Model]) -> Option<i32> {
+        if requested.is_some() {
+            return requested;
+        }
+        let owner_id = accounts.first()?.owner_id;
+        if accounts.iter().any(|a| a.owner_id != owner_id) {
+            return None;
+        }
+        self.by_user.get(&owner_id).copied()
+    }
+}
@@ -0,0 +1,268 @@
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{ConnectionTrait, Database, DatabaseConnection, DbBackend, DbErr, Statement, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// Tables that are not part of the entity schema.
+const IGNORED_TABLES: [&str; 1] = ["seaql_migrations"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ColumnSchema {
+    data_type: String,
+    not_null: bool,
+}
+
+/// Indexes are compared by what they cover rather than by name, since SQLite
+/// names the ones backing PRIMARY KEY/UNIQUE constraints itself.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct IndexSchema {
+    columns: Vec<String>,
+    unique: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct ForeignKeySchema {
+    columns: Vec<String>,
+    table: String,
+    references: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct TableSchema {
+    columns: BTreeMap<String, ColumnSchema>,
+    indexes: BTreeSet<IndexSchema>,
+    foreign_keys: BTreeSet<ForeignKeySchema>,
+}
+
+/// One difference between the live schema and the expected one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaDrift {
+    pub table: String,
+    pub problem: String,
+}
+
+impl fmt::Display for SchemaDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.table, self.problem)
+    }
+}
+
+/// Result of comparing a database with the schema the migrations produce.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaReport {
+    /// Migrations known to this build but not recorded as applied
+    pub pending_migrations: Vec<String>,
+    /// Migrations recorded as applied but unknown to this build
+    pub unknown_migrations: Vec<String>,
+    pub drift: Vec<SchemaDrift>,
+}
+
+impl SchemaReport {
+    pub fn is_clean(&self) -> bool {
+        self.pending_migrations.is_empty() && self.unknown_migrations.is_empty() && self.drift.is_empty()
+    }
+}
+
+fn sqlite_query(sql: &str, table: &str) -> Statement {
+    Statement::from_sql_and_values(DbBackend::Sqlite, sql, [Value::from(table)])
+}
+
+async fn read_table(db: &DatabaseConnection, table: &str) -> Result<TableSchema, DbErr> {
+    let mut schema = TableSchema::default();
+
+    for row in db
+        .query_all(sqlite_query(r#"SELECT name, type, "notnull" FROM pragma_table_info(?)"#, table))
+        .await?
+    {
+        schema.columns.insert(
+            row.try_get("", "name")?,
+            ColumnSchema {
+                data_type: row.try_get::<String>("", "type")?.to_lowercase(),
+                not_null: row.try_get::<i32>("", "notnull")? != 0,
+            },
+        );
+    }
+
+    for row in db
+        .query_all(sqlite_query(r#"SELECT name, "unique" FROM pragma_index_list(?)"#, table))
+        .await?
+    {
+        let index: String = row.try_get("", "name")?;
+        let columns = db
+            .query_all(sqlite_query("SELECT name FROM pragma_index_info(?) ORDER BY seqno", &index))
+            .await?
+            .iter()
+            .map(|row| row.try_get("", "name"))
+            .collect::<Result<Vec<String>, DbErr>>()?;
+        schema.indexes.insert(IndexSchema {
+            columns,
+            unique: row.try_get::<i32>("", "unique")? != 0,
+        });
+    }
+
+    let mut foreign_keys: BTreeMap<i32, ForeignKeySchema> = BTreeMap::new();
+    for row in db
+        .query_all(sqlite_query(
+            r#"SELECT id, "table", "from", "to" FROM pragma_foreign_key_list(?) ORDER BY id, seq"#,
+            table,
+        ))
+        .await?
+    {
+        let foreign_key = foreign_keys.entry(row.try_get("", "id")?).or_insert_with(|| ForeignKeySchema {
+            columns: Vec::new(),
+            table: String::new(),
+            references: Vec::new(),
+        });
+        foreign_key.table = row.try_get("", "table")?;
+        foreign_key.columns.push(row.try_get("", "from")?);
+        // NULL when the key references the parent's primary key implicitly
+        foreign_key
+            .references
+            .push(row.try_get::<Option<String>>("", "to")?.unwrap_or_default());
+    }
+    schema.foreign_keys = foreign_keys.into_values().collect();
+
+    Ok(schema)
+}
+
+async fn read_schema(db: &DatabaseConnection) -> Result<BTreeMap<String, TableSchema>, DbErr> {
+    let tables = db
+        .query_all(Statement::from_string(
+            DbBackend::Sqlite,
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+        ))
+        .await?
+        .iter()
+        .map(|row| row.try_get("", "name"))
+        .collect::<Result<Vec<String>, DbErr>>()?;
+
+    let mut schema = BTreeMap::new();
+    for table in tables {
+        if IGNORED_TABLES.contains(&table.as_str()) {
+            continue;
+        }
+        let table_schema = read_table(db, &table).await?;
+        schema.insert(table, table_schema);
+    }
+    Ok(schema)
+}
+
+/// Reads which migrations the database has recorded as applied, without
+/// creating the bookkeeping table when it is missing.
+async fn applied_migrations(db: &DatabaseConnection) -> Result<BTreeSet<String>, DbErr> {
+    let has_table = db
+        .query_one(Statement::from_string(
+            DbBackend::Sqlite,
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name = 'seaql_migrations'",
+        ))
+        .await?
+        .is_some();
+    if !has_table {
+        return Ok(BTreeSet::new());
+    }
+
+    db.query_all(Statement::from_string(DbBackend::Sqlite, "SELECT version FROM seaql_migrations"))
+        .await?
+        .iter()
+        .map(|row| row.try_get("", "version"))
+        .collect()
+}
+
+fn describe_index(index: &IndexSchema) -> String {
+    let kind = if index.unique { "unique index" } else { "index" };
+    format!("{} on ({})", kind, index.columns.join(", "))
+}
+
+fn describe_foreign_key(foreign_key: &ForeignKeySchema) -> String {
+    format!(
+        "foreign key ({}) -> {}({})",
+        foreign_key.columns.join(", "),
+        foreign_key.table,
+        foreign_key.references.join(", ")
+    )
+}
+
+fn compare_table(table: &str, expected: &TableSchema, actual: &TableSchema, drift: &mut Vec<SchemaDrift>) {
+    let mut report = |problem: String| {
+        drift.push(SchemaDrift {
+            table: table.to_string(),
+            problem,
+        })
+    };
+
+    for (name, column) in &expected.columns {
+        match actual.columns.get(name) {
+            None => report(format!("missing column `{}`", name)),
+            Some(live) if live.data_type != column.data_type => report(format!(
+                "column `{}` has type `{}`, expected `{}`",
+                name, live.data_type, column.data_type
+            )),
+            Some(live) if live.not_null != column.not_null => report(format!(
+                "column `{}` is {}, expected {}",
+                name,
+                if live.not_null { "NOT NULL" } else { "nullable" },
+                if column.not_null { "NOT NULL" } else { "nullable" }
+            )),
+            Some(_) => {}
+        }
+    }
+    for name in actual.columns.keys().filter(|name| !expected.columns.contains_key(*name)) {
+        report(format!("unexpected column `{}`", name));
+    }
+
+    for index in expected.indexes.difference(&actual.indexes) {
+        report(format!("missing {}", describe_index(index)));
+    }
+    for index in actual.indexes.difference(&expected.indexes) {
+        report(format!("unexpected {}", describe_index(index)));
+    }
+
+    for foreign_key in expected.foreign_keys.difference(&actual.foreign_keys) {
+        report(format!("missing {}", describe_foreign_key(foreign_key)));
+    }
+    for foreign_key in actual.foreign_keys.difference(&expected.foreign_keys) {
+        report(format!("unexpected {}", describe_foreign_key(foreign_key)));
+    }
+}
+
+/// Compares a SQLite database with the schema the entity definitions expect.
+///
+/// The expected schema is built by running all migrations on an empty in-memory
+/// database, so it covers columns (type and nullability), indexes and foreign keys.
+/// The database is only read, never modified.
+pub async fn detect_schema_drift(db: &DatabaseConnection) -> Result<SchemaReport, DbErr> {
+    if db.get_database_backend() != DbBackend::Sqlite {
+        return Err(DbErr::Custom("Schema drift detection only supports SQLite databases".to_string()));
+    }
+
+    let reference = Database::connect("sqlite::memory:").await?;
+    Migrator::up(&reference, None).await?;
+    let expected = read_schema(&reference).await?;
+    let actual = read_schema(db).await?;
+
+    let known: Vec<String> = Migrator::migrations().iter().map(|migration| migration.name().to_string()).collect();
+    let applied = applied_migrations(db).await?;
+
+    let mut drift = Vec::new();
+    for (table, expected_table) in &expected {
+        match actual.get(table) {
+            Some(actual_table) => compare_table(table, expected_table, actual_table, &mut drift),
+            None => drift.push(SchemaDrift {
+                table: table.clone(),
+                problem: "missing table".to_string(),
+            }),
+        }
+    }
+    for table in actual.keys().filter(|table| !expected.contains_key(*table)) {
+        drift.push(SchemaDrift {
+            table: table.clone(),
+            problem: "unexpected table".to_string(),
+        });
+    }
+
+    Ok(SchemaReport {
+        pending_migrations: known.iter().filter(|name| !applied.contains(*name)).cloned().collect(),
+        unknown_migrations: applied.iter().filter(|name| !known.contains(name)).cloned().collect(),
+        drift,
+    })
+}
@@ -0,0 +1,87 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use common::{PaginatedResponse, Pagination};
+use serde::Serialize;
+use serde_json::Value;
+use tracing::error;
+
+use crate::schemas::ApiResponse;
+
+/// Fields requested with a `fields=id,date,amount` query parameter.
+///
+/// List endpoints serialize only these top-level fields of every item, so clients that
+/// need a few columns don't download whole records. Unknown names are ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSelection(Vec<String>);
+
+impl FieldSelection {
+    /// Parses the comma-separated `fields` parameter; `None` when every field is wanted.
+    pub fn parse(fields: Option<&str>) -> Option<Self> {
+        let names: Vec<String> = fields?
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect();
+        (!names.is_empty()).then_some(Self(names))
+    }
+
+    /// Whether any of `fields` was requested, e.g. to skip loading unused relations.
+    pub fn contains_any(&self, fields: &[&str]) -> bool {
+        self.0.iter().any(|name| fields.contains(&name.as_str()))
+    }
+
+    /// Drops the fields of a JSON object that weren't requested.
+    pub fn retain(&self, value: &mut Value) {
+        if let Value::Object(object) = value {
+            object.retain(|key, _| self.0.iter().any(|name| name == key));
+        }
+    }
+
+    /// Serializes `items`, keeping only the requested fields of each.
+    pub fn select<T: Serialize>(&self, items: &[T]) -> Result<Vec<Value>, serde_json::Error> {
+        items
+            .iter()
+            .map(|item| {
+                let mut value = serde_json::to_value(item)?;
+                self.retain(&mut value);
+                Ok(value)
+            })
+            .collect()
+    }
+}
+
+/// `ApiResponse` listing `items`, trimmed to `fields` when a selection was requested.
+pub fn list_response<T: Serialize>(items: Vec<T>, fields: Option<&FieldSelection>, message: String) -> Response {
+    let Some(fields) = fields else {
+        return Json(ApiResponse { data: items, message, success: true }).into_response();
+    };
+    match fields.select(&items) {
+        Ok(data) => Json(ApiResponse { data, message, success: true }).into_response(),
+        Err(e) => {
+            error!("Failed to serialize selected fields: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// `PaginatedResponse` with one page of `items`, trimmed to `fields` when a selection was requested.
+pub fn paginated_response<T: Serialize>(
+    items: Vec<T>,
+    fields: Option<&FieldSelection>,
+    pagination: Pagination,
+    message: String,
+) -> Response {
+    let Some(fields) = fields else {
+        return Json(PaginatedResponse { data: items, pagination, message, success: true }).into_response();
+    };
+    match fields.select(&items) {
+        Ok(data) => Json(PaginatedResponse { data, pagination, message, success: true }).into_response(),
+        Err(e) => {
+            error!("Failed to serialize selected fields: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
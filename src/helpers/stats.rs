@@ -1,12 +1,15 @@
+use crate::helpers::fx::rate_on;
 use crate::schemas::StatisticsQuery;
 use chrono::{Datelike, NaiveDate};
 use common::{AccountStatistics, TimePeriod};
-use compute::{account::AccountStateCalculator, account_stats, default_compute};
-use model::entities::account;
-use sea_orm::DatabaseConnection;
+use compute::{account::AccountStateCalculator, account_stats, default_compute_with_scenario};
+use model::entities::{account, account_statistics_exclusion, imported_transaction, one_off_transaction};
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter};
+use std::collections::HashSet;
 
 /// Helper function to determine time period from query parameters
-pub fn determine_time_period(query: &StatisticsQuery) -> TimePeriod {
+pub fn determine_time_period(query: &StatisticsQuery, today: NaiveDate) -> TimePeriod {
     if let (Some(start), Some(end)) = (query.start_date, query.end_date) {
         TimePeriod::date_range(start, end)
     } else if let (Some(year), Some(month)) = (query.year, query.month) {
@@ -15,20 +18,35 @@ pub fn determine_time_period(query: &StatisticsQuery) -> TimePeriod {
         TimePeriod::year(year)
     } else {
         // Default to current year
-        TimePeriod::year(chrono::Utc::now().year())
+        TimePeriod::year(today.year())
     }
 }
 
-/// Compute statistics for a single account for a given time period
+/// Statistics exclusion windows of the given accounts
+pub async fn load_statistics_exclusions(
+    db: &DatabaseConnection,
+    account_ids: &[i32],
+) -> Result<account_stats::StatisticsExclusions, DbErr> {
+    let exclusions = account_statistics_exclusion::Entity::find()
+        .filter(account_statistics_exclusion::Column::AccountId.is_in(account_ids.iter().copied()))
+        .all(db)
+        .await?;
+    Ok(account_stats::StatisticsExclusions::new(&exclusions))
+}
+
+/// Compute statistics for a single account for a given time period as seen on `today`,
+/// including the simulated transactions of `scenario_id` if given
 pub async fn compute_account_statistics(
     db: &DatabaseConnection,
     account: &account::Model,
     period: &TimePeriod,
+    scenario_id: Option<i32>,
+    today: NaiveDate,
 ) -> Result<AccountStatistics, Box<dyn std::error::Error + Send + Sync>> {
     let accounts = vec![account.clone()];
-    let compute = default_compute(None);
+    let compute = default_compute_with_scenario(Some(today), scenario_id);
     let account_id = account.id;
-    let today = chrono::Utc::now().date_naive();
+    let exclusions = load_statistics_exclusions(db, &[account_id]).await?;
 
     let current_state_stats = account_stats::state_at_date(
         &compute as &dyn AccountStateCalculator,
@@ -62,6 +80,7 @@ pub async fn compute_account_statistics(
                 db,
                 &accounts,
                 *year,
+                &exclusions,
             )
                 .await
                 .unwrap_or_else(|_| vec![]);
@@ -70,6 +89,7 @@ pub async fn compute_account_statistics(
                 db,
                 &accounts,
                 *year,
+                &exclusions,
             )
                 .await
                 .unwrap_or_else(|_| vec![]);
@@ -78,6 +98,7 @@ pub async fn compute_account_statistics(
                 db,
                 &accounts,
                 *year,
+                &exclusions,
             )
                 .await
                 .unwrap_or_else(|_| vec![]);
@@ -86,6 +107,7 @@ pub async fn compute_account_statistics(
                 db,
                 &accounts,
                 *year,
+                &exclusions,
             )
                 .await
                 .unwrap_or_else(|_| vec![]);
@@ -94,7 +116,8 @@ pub async fn compute_account_statistics(
                 db,
                 &accounts,
                 *year,
-                chrono::Utc::now().date_naive(),
+                today,
+                &exclusions,
             )
                 .await
                 .unwrap_or_else(|_| vec![]);
@@ -137,6 +160,7 @@ pub async fn compute_account_statistics(
                     .first()
                     .and_then(|s| s.end_of_period_state),
                 goal_reached_date: goal_reached_date,
+                fx_fees: None,
             }
         }
         TimePeriod::Month { year, month } => {
@@ -146,6 +170,7 @@ pub async fn compute_account_statistics(
                 &accounts,
                 *year,
                 *month,
+                &exclusions,
             )
                 .await
                 .unwrap_or_else(|_| vec![]);
@@ -155,6 +180,7 @@ pub async fn compute_account_statistics(
                 &accounts,
                 *year,
                 *month,
+                &exclusions,
             )
                 .await
                 .unwrap_or_else(|_| vec![]);
@@ -164,6 +190,7 @@ pub async fn compute_account_statistics(
                 &accounts,
                 *year,
                 *month,
+                &exclusions,
             )
                 .await
                 .unwrap_or_else(|_| vec![]);
@@ -173,6 +200,7 @@ pub async fn compute_account_statistics(
                 &accounts,
                 *year,
                 *month,
+                &exclusions,
             )
                 .await
                 .unwrap_or_else(|_| vec![]);
@@ -182,7 +210,8 @@ pub async fn compute_account_statistics(
                 &accounts,
                 *year,
                 *month,
-                chrono::Utc::now().date_naive(),
+                today,
+                &exclusions,
             )
                 .await
                 .unwrap_or_else(|_| vec![]);
@@ -226,6 +255,7 @@ pub async fn compute_account_statistics(
                     .first()
                     .and_then(|s| s.end_of_period_state),
                 goal_reached_date: goal_reached_date,
+                fx_fees: None,
             }
         }
         TimePeriod::DateRange { start, end: _ } => {
@@ -235,6 +265,7 @@ pub async fn compute_account_statistics(
                 db,
                 &accounts,
                 year,
+                &exclusions,
             )
                 .await
                 .unwrap_or_else(|_| vec![]);
@@ -243,6 +274,7 @@ pub async fn compute_account_statistics(
                 db,
                 &accounts,
                 year,
+                &exclusions,
             )
                 .await
                 .unwrap_or_else(|_| vec![]);
@@ -251,6 +283,7 @@ pub async fn compute_account_statistics(
                 db,
                 &accounts,
                 year,
+                &exclusions,
             )
                 .await
                 .unwrap_or_else(|_| vec![]);
@@ -259,6 +292,7 @@ pub async fn compute_account_statistics(
                 db,
                 &accounts,
                 year,
+                &exclusions,
             )
                 .await
                 .unwrap_or_else(|_| vec![]);
@@ -267,7 +301,8 @@ pub async fn compute_account_statistics(
                 db,
                 &accounts,
                 year,
-                chrono::Utc::now().date_naive(),
+                today,
+                &exclusions,
             )
                 .await
                 .unwrap_or_else(|_| vec![]);
@@ -310,9 +345,68 @@ pub async fn compute_account_statistics(
                     .first()
                     .and_then(|s| s.end_of_period_state),
                 goal_reached_date: goal_reached_date,
+                fx_fees: None,
             }
         }
     };
 
-    Ok(statistics)
+    let (period_start, period_end) = period.bounds();
+    let fx_fees = total_fx_fees(db, account, period_start, period_end).await?;
+
+    Ok(AccountStatistics { fx_fees, ..statistics })
+}
+
+/// Sum of currency conversion fees of an account within a date range
+///
+/// Counts the fees recorded on imported rows, plus the cost of one-off transactions
+/// paid in another currency: how much worse the settled amount is than the original
+/// amount converted at the stored market rate of that day. One-offs without a known
+/// market rate, or linked to an import that already carries a fee, are skipped.
+pub async fn total_fx_fees(
+    db: &DatabaseConnection,
+    account: &account::Model,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<Option<Decimal>, DbErr> {
+    let imported = imported_transaction::Entity::find()
+        .filter(imported_transaction::Column::AccountId.eq(account.id))
+        .filter(imported_transaction::Column::Date.between(start_date, end_date))
+        .filter(imported_transaction::Column::FxFee.is_not_null())
+        .all(db)
+        .await?;
+    let imported_hashes: HashSet<&str> = imported.iter().map(|row| row.import_hash.as_str()).collect();
+    let mut fees: Vec<Decimal> = imported.iter().filter_map(|row| row.fx_fee).collect();
+
+    let converted = one_off_transaction::Entity::find()
+        .filter(one_off_transaction::Column::TargetAccountId.eq(account.id))
+        .filter(one_off_transaction::Column::Date.between(start_date, end_date))
+        .filter(one_off_transaction::Column::OriginalCurrency.is_not_null())
+        .all(db)
+        .await?;
+    for transaction in converted {
+        if transaction
+            .linked_import_id
+            .as_deref()
+            .is_some_and(|hash| imported_hashes.contains(hash))
+        {
+            continue;
+        }
+        let (Some(original_amount), Some(original_currency)) =
+            (transaction.original_amount, transaction.original_currency.as_deref())
+        else {
+            continue;
+        };
+        if let Some(rate) = rate_on(db, original_currency, &account.currency_code, transaction.date).await? {
+            let market_amount = (original_amount.abs() * rate).round_dp(2);
+            // Payments cost more than the market amount, refunds bring in less
+            let fee = if transaction.amount.is_sign_negative() {
+                transaction.amount.abs() - market_amount
+            } else {
+                market_amount - transaction.amount
+            };
+            fees.push(fee.max(Decimal::ZERO));
+        }
+    }
+
+    Ok((!fees.is_empty()).then(|| fees.into_iter().sum()))
 }
@@ -0,0 +1,228 @@
+use chrono::{DateTime, NaiveDateTime, TimeDelta, Utc};
+use migration::{Migrator, MigratorTrait};
+use model::entities::{account_document, book_snapshot, user};
+use moka::future::Cache;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectOptions, Database, DatabaseConnection, DbErr, EntityName, EntityTrait,
+    IdenStatic, IntoActiveModel, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::handlers::export::{build_archive, export_tables, ExportFormat};
+use crate::handlers::restore::restore_archive;
+use crate::helpers::access::AccountAccess;
+
+#[derive(Debug, Error)]
+pub enum TimeTravelError {
+    #[error("No snapshot of the books was taken by {0}; snapshots are recorded while --audit-chain is enabled")]
+    NoSnapshot(NaiveDateTime),
+
+    #[error("Snapshot {id} can't be restored: {reason}")]
+    SnapshotUnreadable { id: i32, reason: String },
+
+    #[error("Database error: {0}")]
+    Database(#[from] DbErr),
+}
+
+/// Number of restored snapshots kept in memory.
+const RESTORED_BOOKS_CAPACITY: u64 = 4;
+
+/// How long a restored snapshot is kept in memory.
+const RESTORED_BOOKS_TTL: Duration = Duration::from_secs(3600);
+
+/// Days every snapshot is kept for; older ones are thinned to the last one of each day.
+pub const BOOK_SNAPSHOT_FULL_HISTORY_DAYS: i64 = 7;
+
+/// Cache of the in-memory databases restored from book snapshots, by snapshot id.
+pub type RestoredBooksCache = Cache<i32, DatabaseConnection>;
+
+/// Creates the cache [`restore_book_snapshot`] keeps restored snapshots in.
+pub fn restored_books_cache() -> RestoredBooksCache {
+    Cache::builder()
+        .max_capacity(RESTORED_BOOKS_CAPACITY)
+        .time_to_live(RESTORED_BOOKS_TTL)
+        .build()
+}
+
+/// Books restored from a snapshot to answer a read as of some time.
+#[derive(Debug, Clone)]
+pub struct RestoredBooks {
+    /// In-memory database holding the snapshot
+    pub db: DatabaseConnection,
+    pub snapshot_id: i32,
+    pub taken_at: NaiveDateTime,
+    /// Set to `taken_at` when this is the newest snapshot and the read is for a later time,
+    /// so changes made since the snapshot are missing.
+    pub stale_since: Option<NaiveDateTime>,
+}
+
+/// Request extension with the time, in UTC, a read is answered as of.
+///
+/// Put there by the `as_of` middleware, so that [`UserTimezone`](crate::helpers::timezone::UserTimezone)
+/// takes "today" at that time too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AsOf(pub NaiveDateTime);
+
+/// Parses an `as_of` timestamp: RFC 3339, or a date and time without offset taken as UTC.
+pub fn parse_as_of(value: &str) -> Option<NaiveDateTime> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|timestamp| timestamp.naive_utc())
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f"))
+        .ok()
+}
+
+/// Stores a snapshot of the books, unless nothing changed since the last one.
+///
+/// The snapshot is a full export of every account, so it covers changes made by
+/// background tasks and CLI commands as well as through the API. Account documents are
+/// recorded without their content, which would otherwise be copied into every snapshot.
+pub async fn record_book_snapshot(db: &DatabaseConnection) -> Result<Option<book_snapshot::Model>, DbErr> {
+    let mut tables = export_tables(db, &AccountAccess::Unrestricted).await?;
+    for table in tables
+        .iter_mut()
+        .filter(|table| table.name == account_document::Entity.table_name())
+    {
+        for row in &mut table.rows {
+            row[account_document::Column::Content.as_str()] = serde_json::json!([]);
+        }
+    }
+    let rows: Vec<(&str, &Vec<serde_json::Value>)> =
+        tables.iter().map(|table| (table.name.as_str(), &table.rows)).collect();
+    let content = serde_json::to_vec(&rows).map_err(|e| DbErr::Custom(format!("Failed to serialize books: {}", e)))?;
+    let sha256 = format!("{:x}", Sha256::digest(&content));
+
+    let latest: Option<String> = book_snapshot::Entity::find()
+        .select_only()
+        .column(book_snapshot::Column::Sha256)
+        .order_by_desc(book_snapshot::Column::Id)
+        .into_tuple()
+        .one(db)
+        .await?;
+    if latest.as_deref() == Some(sha256.as_str()) {
+        return Ok(None);
+    }
+
+    let archive = build_archive(&tables, ExportFormat::Json)
+        .map_err(|e| DbErr::Custom(format!("Failed to build snapshot archive: {}", e)))?;
+    let snapshot = book_snapshot::ActiveModel {
+        taken_at: Set(Utc::now().naive_utc()),
+        sha256: Set(sha256),
+        archive: Set(archive),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    Ok(Some(snapshot))
+}
+
+/// Deletes the snapshots superseded by a later one taken the same day, once they are
+/// more than [`BOOK_SNAPSHOT_FULL_HISTORY_DAYS`] days older than `now`.
+///
+/// Reads as of such a day are then answered from the books as they were at its end.
+/// Returns the number of snapshots deleted.
+pub async fn prune_book_snapshots(db: &DatabaseConnection, now: NaiveDateTime) -> Result<u64, DbErr> {
+    let cutoff = now - TimeDelta::days(BOOK_SNAPSHOT_FULL_HISTORY_DAYS);
+    let snapshots: Vec<(i32, NaiveDateTime)> = book_snapshot::Entity::find()
+        .select_only()
+        .column(book_snapshot::Column::Id)
+        .column(book_snapshot::Column::TakenAt)
+        .filter(book_snapshot::Column::TakenAt.lt(cutoff))
+        .order_by_asc(book_snapshot::Column::TakenAt)
+        .order_by_asc(book_snapshot::Column::Id)
+        .into_tuple()
+        .all(db)
+        .await?;
+    let superseded: Vec<i32> = snapshots
+        .windows(2)
+        .filter(|pair| pair[0].1.date() == pair[1].1.date())
+        .map(|pair| pair[0].0)
+        .collect();
+    if superseded.is_empty() {
+        return Ok(0);
+    }
+
+    let result = book_snapshot::Entity::delete_many()
+        .filter(book_snapshot::Column::Id.is_in(superseded))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+/// Restores the latest snapshot taken at or before `as_of` into an in-memory database.
+///
+/// Restored snapshots are kept in `cache`, so repeated reads from the same snapshot don't
+/// restore it again. Snapshots don't contain users, so the current ones are copied over
+/// first.
+pub async fn restore_book_snapshot(
+    db: &DatabaseConnection,
+    cache: &RestoredBooksCache,
+    as_of: NaiveDateTime,
+) -> Result<RestoredBooks, TimeTravelError> {
+    let (snapshot_id, taken_at): (i32, NaiveDateTime) = book_snapshot::Entity::find()
+        .select_only()
+        .column(book_snapshot::Column::Id)
+        .column(book_snapshot::Column::TakenAt)
+        .filter(book_snapshot::Column::TakenAt.lte(as_of))
+        .order_by_desc(book_snapshot::Column::TakenAt)
+        .order_by_desc(book_snapshot::Column::Id)
+        .into_tuple()
+        .one(db)
+        .await?
+        .ok_or(TimeTravelError::NoSnapshot(as_of))?;
+    let later_snapshots = book_snapshot::Entity::find()
+        .filter(book_snapshot::Column::TakenAt.gt(as_of))
+        .count(db)
+        .await?;
+    let stale_since = (later_snapshots == 0 && as_of > taken_at).then_some(taken_at);
+
+    let restored = match cache.get(&snapshot_id).await {
+        Some(restored) => restored,
+        None => {
+            let restored = restore_snapshot(db, snapshot_id).await?;
+            cache.insert(snapshot_id, restored.clone()).await;
+            restored
+        }
+    };
+    Ok(RestoredBooks {
+        db: restored,
+        snapshot_id,
+        taken_at,
+        stale_since,
+    })
+}
+
+async fn restore_snapshot(db: &DatabaseConnection, snapshot_id: i32) -> Result<DatabaseConnection, TimeTravelError> {
+    let snapshot = book_snapshot::Entity::find_by_id(snapshot_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| TimeTravelError::SnapshotUnreadable {
+            id: snapshot_id,
+            reason: "it was deleted".to_string(),
+        })?;
+
+    // The database lives as long as its only connection, which outlives the cache entry
+    let mut options = ConnectOptions::new("sqlite::memory:");
+    options
+        .max_connections(1)
+        .min_connections(1)
+        .idle_timeout(RESTORED_BOOKS_TTL * 2)
+        .max_lifetime(RESTORED_BOOKS_TTL * 2);
+    let restored = Database::connect(options).await?;
+    Migrator::up(&restored, None).await?;
+    let users = user::Entity::find().all(db).await?;
+    if !users.is_empty() {
+        user::Entity::insert_many(users.into_iter().map(|user| user.into_active_model().reset_all()))
+            .exec(&restored)
+            .await?;
+    }
+
+    restore_archive(&restored, &snapshot.archive)
+        .await
+        .map_err(|e| TimeTravelError::SnapshotUnreadable {
+            id: snapshot.id,
+            reason: e.to_string(),
+        })?;
+    Ok(restored)
+}
@@ -0,0 +1,91 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{request::Parts, StatusCode},
+    response::Json,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+use model::entities::user;
+use sea_orm::{DatabaseConnection, DbErr, EntityTrait};
+use tracing::{error, warn};
+
+use crate::helpers::auth::AuthenticatedUser;
+use crate::helpers::time_travel::AsOf;
+use crate::schemas::{AppState, ErrorResponse};
+
+/// Parses an IANA timezone name such as "Europe/Prague".
+pub fn parse_timezone(name: &str) -> Option<Tz> {
+    name.parse().ok()
+}
+
+/// The timezone "today" is resolved in for a request.
+///
+/// Authenticated users get their own `timezone` setting; anonymous requests and users
+/// without one get the server's `--timezone`. Handlers take it as an extractor and pass
+/// [`UserTimezone::today`] to the compute entry points instead of reading the clock.
+/// Reads answered as of a past time get that time's date as "today".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UserTimezone {
+    pub timezone: Tz,
+    /// Instant "today" is taken at instead of now
+    pub as_of: Option<DateTime<Utc>>,
+}
+
+impl UserTimezone {
+    /// Loads the timezone of `auth`, falling back to `default`.
+    pub async fn load(db: &DatabaseConnection, default: Tz, auth: Option<&AuthenticatedUser>) -> Result<Self, DbErr> {
+        let Some(auth) = auth else {
+            return Ok(Self {
+                timezone: default,
+                as_of: None,
+            });
+        };
+
+        let timezone = user::Entity::find_by_id(auth.user_id)
+            .one(db)
+            .await?
+            .and_then(|user| user.timezone)
+            .and_then(|name| {
+                let timezone = parse_timezone(&name);
+                if timezone.is_none() {
+                    warn!("Ignoring unknown timezone '{}' of user {}", name, auth.user_id);
+                }
+                timezone
+            });
+        Ok(Self {
+            timezone: timezone.unwrap_or(default),
+            as_of: None,
+        })
+    }
+
+    /// The current date in this timezone.
+    pub fn today(&self) -> NaiveDate {
+        match self.as_of {
+            Some(as_of) => compute::date_in(as_of, &self.timezone),
+            None => compute::today_in(&self.timezone),
+        }
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for UserTimezone {
+    type Rejection = (StatusCode, Json<ErrorResponse>);
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let auth = parts.extensions.get::<AuthenticatedUser>();
+        let as_of = parts.extensions.get::<AsOf>().map(|as_of| as_of.0.and_utc());
+        let timezone = Self::load(&state.db, state.timezone, auth).await.map_err(|e| {
+            error!("Failed to load user timezone: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Failed to load user timezone".to_string(),
+                    code: "DATABASE_ERROR".to_string(),
+                    success: false,
+                }),
+            )
+        })?;
+        Ok(Self { as_of, ..timezone })
+    }
+}
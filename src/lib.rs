@@ -5,3 +5,4 @@ pub mod helpers;
 pub mod middleware;
 pub mod router;
 pub mod schemas;
+pub mod tasks;
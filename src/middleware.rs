@@ -1,7 +1,31 @@
-use axum::{body::Body, extract::State, http::Request, middleware::Next, response::Response};
-use tracing::debug;
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Query, State},
+    http::{header, HeaderValue, Method, Request, StatusCode, Uri},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use moka::future::Cache;
+use std::collections::HashMap;
+use tower::ServiceExt;
+use tracing::{debug, error, info, warn};
 
-use crate::schemas::AppState;
+use crate::handlers::batch::BATCH_PATH;
+use crate::helpers::audit_chain::{append_entry, redact_payload};
+use crate::helpers::auth::{bearer_token, find_bearer_user, AuthenticatedUser};
+use crate::helpers::time_travel::{parse_as_of, restore_book_snapshot, AsOf, TimeTravelError};
+use crate::router::create_test_router;
+use crate::schemas::{AppState, ErrorResponse};
+
+/// Largest request body recorded in the audit chain.
+const MAX_AUDITED_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+/// Login, logout and password changes, which the audit chain doesn't record.
+const AUTH_PATH_PREFIX: &str = "/api/v1/auth/";
+
+fn is_mutation(method: &Method) -> bool {
+    method == Method::POST || method == Method::PUT || method == Method::DELETE || method == Method::PATCH
+}
 
 /// Middleware that invalidates all cached data when a mutating request
 /// (POST, PUT, DELETE, PATCH) is received.
@@ -13,15 +37,253 @@ pub async fn invalidate_cache_on_mutation(
     let method = request.method().clone();
     let response = next.run(request).await;
 
-    if response.status().is_success()
-        && (method == axum::http::Method::POST
-            || method == axum::http::Method::PUT
-            || method == axum::http::Method::DELETE
-            || method == axum::http::Method::PATCH)
-    {
+    if response.status().is_success() && is_mutation(&method) {
         debug!("Invalidating cache after successful {} request", method);
         state.cache.invalidate_all();
     }
 
     response
 }
+
+/// Middleware that appends every successful mutating request to the audit hash chain.
+///
+/// Does nothing unless the audit chain is enabled. The request body is buffered so it
+/// can be recorded as the entry's payload, with passwords, secrets and file contents
+/// redacted; a failure to record is logged but does not fail the already completed
+/// request. Batches aren't recorded themselves, as each of their requests passes through
+/// this middleware on its own, and neither are the authentication routes.
+pub async fn record_audit_entry(
+    State(state): State<AppState>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    if !state.audit_chain
+        || !is_mutation(request.method())
+        || path == BATCH_PATH
+        || path.starts_with(AUTH_PATH_PREFIX)
+    {
+        return next.run(request).await;
+    }
+
+    let method = request.method().to_string();
+    let path = request
+        .uri()
+        .path_and_query()
+        .map(|path| path.to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+
+    let (parts, body) = request.into_parts();
+    let bytes = match to_bytes(body, MAX_AUDITED_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("Rejected {} {} with an unreadable or oversized body: {}", method, path, e);
+            return StatusCode::PAYLOAD_TOO_LARGE.into_response();
+        }
+    };
+    let payload = redact_payload(&String::from_utf8_lossy(&bytes));
+
+    let response = next.run(Request::from_parts(parts, Body::from(bytes))).await;
+
+    if response.status().is_success() {
+        let status = i32::from(response.status().as_u16());
+        match append_entry(&state.db, &method, &path, status, &payload).await {
+            Ok(entry) => debug!("Recorded audit entry {} for {} {}", entry.id, method, path),
+            Err(e) => error!("Failed to record audit entry for {} {}: {}", method, path, e),
+        }
+    }
+
+    response
+}
+
+/// Routes behind authentication when it is required.
+const AUTHENTICATED_PATH_PREFIX: &str = "/api/v1/";
+/// API routes reachable without a session: logging in and fetching the specification.
+const PUBLIC_PATHS: [&str; 2] = ["/api/v1/auth/login", crate::router::OPENAPI_SPEC_PATH];
+
+fn auth_error(status: StatusCode, code: &str, error: &str) -> Response {
+    (
+        status,
+        Json(ErrorResponse {
+            error: error.to_string(),
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+        .into_response()
+}
+
+/// Middleware resolving the `Authorization: Bearer <token>` header to the user of the
+/// session or API token.
+///
+/// The user is put into the request extensions, where handlers extract it as
+/// [`AuthenticatedUser`](crate::helpers::auth::AuthenticatedUser). When authentication
+/// is required, API requests without a valid token are rejected with 401; login, the
+/// health check, metrics and the API documentation stay reachable. A user already in
+/// the extensions, as for reads served from a snapshot of the books, is kept.
+pub async fn authenticate(
+    State(state): State<AppState>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Response {
+    if request.extensions().get::<AuthenticatedUser>().is_some() {
+        return next.run(request).await;
+    }
+    let user = match bearer_token(request.headers()) {
+        Some(token) => match find_bearer_user(&state.db, token).await {
+            Ok(user) => user,
+            Err(e) => {
+                error!("Database error while looking up bearer token: {}", e);
+                return auth_error(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "DATABASE_ERROR",
+                    "Failed to verify token",
+                );
+            }
+        },
+        None => None,
+    };
+
+    match user {
+        Some(user) => {
+            debug!("Request authenticated as user {}", user.user_id);
+            request.extensions_mut().insert(user);
+        }
+        None if state.auth_required
+            && request.uri().path().starts_with(AUTHENTICATED_PATH_PREFIX)
+            && !PUBLIC_PATHS.contains(&request.uri().path()) =>
+        {
+            warn!("Rejected unauthenticated {} {}", request.method(), request.uri().path());
+            return auth_error(StatusCode::UNAUTHORIZED, "UNAUTHORIZED", "Authentication required");
+        }
+        None => {}
+    }
+
+    next.run(request).await
+}
+
+/// Routes that accept the `as_of` query parameter.
+const AS_OF_PATH_PREFIXES: [&str; 2] = ["/api/v1/accounts", "/api/v1/statistics"];
+
+/// Response header naming when the snapshot an `as_of` read was answered from was taken.
+pub const AS_OF_SNAPSHOT_HEADER: &str = "x-as-of-snapshot";
+
+/// Response header set when an `as_of` read is for a time after the newest snapshot, naming
+/// when that snapshot was taken; changes made since then are missing from the response.
+pub const AS_OF_STALE_SINCE_HEADER: &str = "x-as-of-stale-since";
+
+fn as_of_error(status: StatusCode, code: &str, error: String) -> Response {
+    (
+        status,
+        Json(ErrorResponse {
+            error,
+            code: code.to_string(),
+            success: false,
+        }),
+    )
+        .into_response()
+}
+
+/// Middleware answering account and statistics reads with `?as_of=<timestamp>` from
+/// the books as they were at that time.
+///
+/// The latest book snapshot taken by the timestamp is restored into an in-memory
+/// database, or taken from the ones restored before, and the request, without `as_of`, is served from it with the caller's
+/// session and "today" taken at the timestamp. Other requests pass through.
+pub async fn serve_as_of(
+    State(state): State<AppState>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    if request.method() != Method::GET
+        || !AS_OF_PATH_PREFIXES
+            .iter()
+            .any(|prefix| request.uri().path().starts_with(prefix))
+    {
+        return next.run(request).await;
+    }
+    let as_of = Query::<HashMap<String, String>>::try_from_uri(request.uri())
+        .ok()
+        .and_then(|Query(params)| params.get("as_of").cloned());
+    let Some(as_of) = as_of else {
+        return next.run(request).await;
+    };
+
+    let Some(timestamp) = parse_as_of(&as_of) else {
+        return as_of_error(
+            StatusCode::BAD_REQUEST,
+            "INVALID_AS_OF",
+            format!("Invalid as_of '{}', expected an RFC 3339 timestamp", as_of),
+        );
+    };
+
+    info!("Serving {} as of {}", request.uri().path(), timestamp);
+    let restored = match restore_book_snapshot(&state.db, &state.restored_books, timestamp).await {
+        Ok(restored) => restored,
+        Err(TimeTravelError::Database(e)) => {
+            error!("Database error while restoring a book snapshot: {}", e);
+            return as_of_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DATABASE_ERROR",
+                "Failed to reconstruct historical state".to_string(),
+            );
+        }
+        Err(e) => {
+            warn!("Cannot reconstruct state as of {}: {}", timestamp, e);
+            return as_of_error(StatusCode::CONFLICT, "HISTORY_UNAVAILABLE", e.to_string());
+        }
+    };
+    let snapshot_state = AppState {
+        db: restored.db.clone(),
+        cache: Cache::new(100),
+        audit_chain: false,
+        fx_provider: None,
+        smtp: None,
+        ..state.clone()
+    };
+
+    // Forward the request without `as_of`, leaving compression to the outer layers
+    let (mut parts, body) = request.into_parts();
+    let query: Vec<&str> = parts
+        .uri
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .filter(|pair| !pair.is_empty() && !pair.starts_with("as_of="))
+        .collect();
+    let path_and_query = if query.is_empty() {
+        parts.uri.path().to_string()
+    } else {
+        format!("{}?{}", parts.uri.path(), query.join("&"))
+    };
+    parts.uri = match path_and_query.parse::<Uri>() {
+        Ok(uri) => uri,
+        Err(e) => return as_of_error(StatusCode::BAD_REQUEST, "INVALID_URI", e.to_string()),
+    };
+    parts.headers.remove(header::ACCEPT_ENCODING);
+    // Only the session carries over, the path parameters are matched again by the inner router
+    let user = parts.extensions.remove::<AuthenticatedUser>();
+    parts.extensions.clear();
+    if let Some(user) = user {
+        parts.extensions.insert(user);
+    }
+    parts.extensions.insert(AsOf(timestamp));
+
+    let mut response = create_test_router(snapshot_state)
+        .oneshot(Request::from_parts(parts, body))
+        .await
+        .unwrap_or_else(|never| match never {});
+    if let Ok(taken_at) = HeaderValue::from_str(&restored.taken_at.and_utc().to_rfc3339()) {
+        response.headers_mut().insert(AS_OF_SNAPSHOT_HEADER, taken_at);
+    }
+    if let Some(stale_since) = restored.stale_since {
+        debug!(
+            "Answering as of {} from snapshot {} taken at {}, the newest one",
+            timestamp, restored.snapshot_id, stale_since
+        );
+        if let Ok(stale_since) = HeaderValue::from_str(&stale_since.and_utc().to_rfc3339()) {
+            response.headers_mut().insert(AS_OF_STALE_SINCE_HEADER, stale_since);
+        }
+    }
+    response
+}
@@ -1,50 +1,101 @@
 use crate::handlers::{
-    accounts::{create_account, delete_account, get_account, get_accounts, update_account},
+    account_comparison::compare_accounts,
+    account_freshness::get_account_freshness,
+    balance_preview::preview_account_balance,
+    account_notes::{
+        create_account_note, delete_account_document, delete_account_note, download_account_document,
+        get_account_documents, get_account_notes, update_account_note, upload_account_document,
+    },
+    account_shares::{get_account_shares, share_account, unshare_account},
+    accounts::{create_account, delete_account, get_account, get_accounts, update_account, validate_account},
+    assets::{create_asset, delete_asset, dispose_asset, get_asset, get_assets, update_asset},
+    audit::verify_audit_chain,
+    auth::{change_password, get_current_user, login, logout},
+    batch::execute_batch,
+    budgets::{
+        create_budget, delete_budget, get_budget, get_budget_progress, get_budget_status, get_budgets,
+        suggest_budgets, update_budget,
+    },
     cache::flush_cache,
     categories::{
         create_category, delete_category, get_categories, get_category, get_category_children,
-        get_category_stats, update_category,
+        get_category_forecast, get_category_stats, seed_categories, update_category,
     },
+    diagnostics::submit_diagnostic_report,
+    double_entry::get_double_entry_report,
+    exchange_rates::{create_exchange_rate, get_exchange_rates, refresh_exchange_rates},
+    export::{export_chart_of_accounts, export_full},
+    forecast::{create_forecast_snapshots, get_account_forecast, get_forecast_accuracy},
+    goals::get_goal_progress,
     health::health_check,
+    holidays::{
+        create_holiday, delete_holiday, get_holiday_calendars, get_holidays, update_holiday_calendars,
+    },
+    insights::get_cashflow_smoothing_advice,
     manual_account_states::{
         create_manual_account_state, delete_manual_account_state, get_all_manual_account_states,
         get_manual_account_state, get_manual_account_states, update_manual_account_state,
     },
+    merchants::{create_merchant, delete_merchant, get_merchants},
     metrics::{get_account_metrics, get_dashboard_metrics},
+    networth::get_networth_timeseries,
+    notifications::{
+        create_notification_channel, delete_notification_channel, get_notification_channels,
+        get_recurring_alerts, send_due_payment_alerts, test_notification_channel,
+        update_notification_channel, update_recurring_alerts,
+    },
+    openapi::get_openapi_coverage,
+    periods::{get_locked_periods, lock_period, unlock_period},
     prompt::get_financial_prompt,
+    reconciliation::{
+        add_reconciliation_transaction, complete_reconciliation_session,
+        create_reconciliation_session, delete_reconciliation_session, get_reconciliation_session,
+        get_reconciliation_sessions, remove_reconciliation_transaction,
+        update_reconciliation_session,
+    },
     recurring_income::{
         create_recurring_income, delete_recurring_income, get_recurring_income,
         get_recurring_incomes, update_recurring_income,
     },
+    reports::{get_cashflow_report, get_flow_report},
+    restore::{import_full, MAX_ARCHIVE_BYTES},
+    safe_to_spend::get_safe_to_spend,
     scenarios::{
-        apply_scenario, create_scenario, delete_scenario, get_scenario, get_scenarios,
+        apply_scenario, clone_scenario, create_scenario, delete_scenario, get_scenario, get_scenarios,
         update_scenario,
     },
     statistics::{get_account_statistics, get_all_accounts_statistics, get_monthly_min_balance},
     tags::{
-        create_tag, delete_tag, get_tag, get_tag_children, get_tags,
+        assign_tag_to_transactions, create_tag, delete_tag, get_tag, get_tag_children, get_tag_limits, get_tags,
         link_tag_to_parent, unlink_tag_from_parent, update_tag,
     },
-    timeseries::{get_account_timeseries, get_all_accounts_timeseries},
+    timeseries::{get_account_sparkline, get_account_timeseries, get_all_accounts_timeseries},
+    tokens::{create_api_token, delete_api_token, get_api_tokens},
     transactions::{
-        bulk_create_instances, clear_imported_transaction_reconciliation, create_imported_transaction, create_recurring_instance,
-        create_recurring_transaction, create_transaction, delete_imported_transaction,
+        approve_recurring_transaction, bulk_categorize_transactions, bulk_create_instances, bulk_delete_transactions, bulk_reconcile_imported_transactions, bulk_update_recurring_transactions, bulk_update_transactions, classify_bank_charges, clear_imported_transaction_reconciliation, create_imported_transaction, create_recurring_instance,
+        confirm_transaction, convert_recurring_suggestion, create_quick_transaction, create_recurring_transaction, create_transaction, create_transaction_leg, delete_imported_transaction,
         delete_recurring_instance, delete_recurring_transaction, delete_transaction,
-        get_account_imported_transactions, get_account_transactions, get_imported_transaction,
-        get_imported_transactions,
-        get_missing_instances, get_recurring_instance,
-        get_recurring_instances, get_recurring_transaction,
-        get_recurring_transactions, get_transaction, get_transactions,
-        reconcile_imported_transaction, update_imported_transaction, update_recurring_instance,
+        get_account_imported_transactions, get_account_transactions, get_category_suggestions, get_imported_transaction,
+        generate_instances, get_imported_transactions,
+        get_contract_reminders, get_missing_instances, get_recurring_due_profile, get_recurring_instance,
+        get_recurring_instances, get_recurring_suggestions, get_recurring_transaction,
+        get_recurring_transactions, get_similar_transactions, get_transaction, get_transactions,
+        get_purchase_reminders, get_transactions_geojson, get_uncategorized_transactions,
+        reconcile_imported_transaction, reject_recurring_transaction, search_transactions, set_recurring_approval_policy,
+        skip_recurring_instance, unskip_recurring_instance,
+        update_imported_transaction, update_recurring_instance,
         update_recurring_transaction, update_transaction,
     },
-    users::{create_user, delete_user, get_user, get_users, update_user},
+    users::{create_user, delete_user, get_user, get_user_settings, get_users, update_user, update_user_settings},
 };
-use crate::middleware::invalidate_cache_on_mutation;
+use crate::middleware::{authenticate, invalidate_cache_on_mutation, record_audit_entry, serve_as_of};
 use crate::schemas::{ApiDoc, AppState};
 use axum::{
+    extract::DefaultBodyLimit,
+    handler::Handler,
+    http::Method,
     middleware as axum_middleware,
-    routing::{delete, get, post, put},
+    routing::{self, MethodRouter},
     Router,
 };
 use axum_prometheus::PrometheusMetricLayer;
@@ -56,6 +107,82 @@ use tower_http::{
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
+/// Where the assembled OpenAPI specification is served, reachable without a session.
+pub const OPENAPI_SPEC_PATH: &str = "/api/v1/openapi.json";
+
+/// One method and path served by the API router, in axum `:param` syntax.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiRoute {
+    pub method: Method,
+    pub path: &'static str,
+}
+
+/// Handlers for one path together with the methods they serve.
+///
+/// Stands in for axum's `MethodRouter` in `build_routes` so every route is
+/// recorded for the OpenAPI coverage check.
+struct Endpoint {
+    methods: Vec<Method>,
+    inner: MethodRouter<AppState>,
+}
+
+impl Endpoint {
+    fn post<H, T>(mut self, handler: H) -> Self
+    where
+        H: Handler<T, AppState>,
+        T: 'static,
+    {
+        self.methods.push(Method::POST);
+        self.inner = self.inner.post(handler);
+        self
+    }
+
+    fn layer(mut self, limit: DefaultBodyLimit) -> Self {
+        self.inner = self.inner.layer(limit);
+        self
+    }
+}
+
+fn get<H: Handler<T, AppState>, T: 'static>(handler: H) -> Endpoint {
+    Endpoint { methods: vec![Method::GET], inner: routing::get(handler) }
+}
+
+fn post<H: Handler<T, AppState>, T: 'static>(handler: H) -> Endpoint {
+    Endpoint { methods: vec![Method::POST], inner: routing::post(handler) }
+}
+
+fn put<H: Handler<T, AppState>, T: 'static>(handler: H) -> Endpoint {
+    Endpoint { methods: vec![Method::PUT], inner: routing::put(handler) }
+}
+
+fn patch<H: Handler<T, AppState>, T: 'static>(handler: H) -> Endpoint {
+    Endpoint { methods: vec![Method::PATCH], inner: routing::patch(handler) }
+}
+
+fn delete<H: Handler<T, AppState>, T: 'static>(handler: H) -> Endpoint {
+    Endpoint { methods: vec![Method::DELETE], inner: routing::delete(handler) }
+}
+
+/// The API router together with every route added to it.
+struct ApiRoutes {
+    router: Router<AppState>,
+    routes: Vec<ApiRoute>,
+}
+
+impl ApiRoutes {
+    fn route(mut self, path: &'static str, endpoint: Endpoint) -> Self {
+        self.routes
+            .extend(endpoint.methods.into_iter().map(|method| ApiRoute { method, path }));
+        self.router = self.router.route(path, endpoint.inner);
+        self
+    }
+}
+
+/// Every route of the API, for comparing against the OpenAPI specification.
+pub fn api_routes() -> Vec<ApiRoute> {
+    register_routes(Router::new()).routes
+}
+
 /// Create application router with all routes and middleware.
 ///
 /// When `enable_metrics` is true, a Prometheus metrics layer and `/metrics` endpoint
@@ -71,19 +198,30 @@ pub fn create_test_router(state: AppState) -> Router {
 }
 
 fn create_router_inner(state: AppState, enable_metrics: bool) -> Router {
-    let mut router = Router::new()
-        .route("/health", get(health_check));
+    let mut router = Router::new();
 
     if enable_metrics {
         let (prometheus_layer, metric_handle) = PrometheusMetricLayer::pair();
         router = router
-            .route("/metrics", get(move || async move { metric_handle.render() }));
+            .route("/metrics", routing::get(move || async move { metric_handle.render() }));
 
         return build_routes(router)
             .layer(axum_middleware::from_fn_with_state(
                 state.clone(),
                 invalidate_cache_on_mutation,
             ))
+            .layer(axum_middleware::from_fn_with_state(
+                state.clone(),
+                record_audit_entry,
+            ))
+            .layer(axum_middleware::from_fn_with_state(
+                state.clone(),
+                serve_as_of,
+            ))
+            .layer(axum_middleware::from_fn_with_state(
+                state.clone(),
+                authenticate,
+            ))
             .layer(
                 ServiceBuilder::new()
                     .layer(TraceLayer::new_for_http())
@@ -100,6 +238,18 @@ fn create_router_inner(state: AppState, enable_metrics: bool) -> Router {
             state.clone(),
             invalidate_cache_on_mutation,
         ))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            record_audit_entry,
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            serve_as_of,
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            authenticate,
+        ))
         .layer(
             ServiceBuilder::new()
                 .layer(TraceLayer::new_for_http())
@@ -111,15 +261,45 @@ fn create_router_inner(state: AppState, enable_metrics: bool) -> Router {
 }
 
 fn build_routes(router: Router<AppState>) -> Router<AppState> {
-    router
+    register_routes(router)
+        .router
+        // Swagger UI and the specification it renders
+        .merge(SwaggerUi::new("/swagger-ui").url(OPENAPI_SPEC_PATH, ApiDoc::openapi()))
+}
+
+fn register_routes(router: Router<AppState>) -> ApiRoutes {
+    ApiRoutes { router, routes: Vec::new() }
+        .route("/health", get(health_check))
+        // OpenAPI coverage of this router
+        .route("/api/v1/openapi/coverage", get(get_openapi_coverage))
+        // Authentication
+        .route("/api/v1/auth/login", post(login))
+        .route("/api/v1/auth/logout", post(logout))
+        .route("/api/v1/auth/me", get(get_current_user))
+        .route("/api/v1/auth/password", put(change_password))
+        // API tokens for scripts
+        .route("/api/v1/tokens", get(get_api_tokens).post(create_api_token))
+        .route("/api/v1/tokens/:token_id", delete(delete_api_token))
+        // Several requests in one round trip
+        .route("/api/v1/batch", post(execute_batch))
         // Cache management
         .route("/api/v1/cache/flush", post(flush_cache))
+        // Frontend crash reports
+        .route("/api/v1/diagnostics", post(submit_diagnostic_report))
+        // Tamper evidence
+        .route("/api/v1/audit/verify", get(verify_audit_chain))
         // Account CRUD routes
         .route("/api/v1/accounts", post(create_account))
         .route("/api/v1/accounts", get(get_accounts))
         .route("/api/v1/accounts/:account_id", get(get_account))
         .route("/api/v1/accounts/:account_id", put(update_account))
         .route("/api/v1/accounts/:account_id", delete(delete_account))
+        .route("/api/v1/accounts/:account_id/validation", get(validate_account))
+        .route("/api/v1/accounts/compare", get(compare_accounts))
+        .route("/api/v1/accounts/freshness", get(get_account_freshness))
+        .route("/api/v1/accounts/:account_id/shares", get(get_account_shares))
+        .route("/api/v1/accounts/:account_id/shares/:user_id", put(share_account))
+        .route("/api/v1/accounts/:account_id/shares/:user_id", delete(unshare_account))
         // Manual account states routes
         .route("/api/v1/manual-account-states", get(get_all_manual_account_states))
         .route("/api/v1/accounts/:account_id/manual-states", post(create_manual_account_state))
@@ -127,15 +307,30 @@ fn build_routes(router: Router<AppState>) -> Router<AppState> {
         .route("/api/v1/accounts/:account_id/manual-states/:state_id", get(get_manual_account_state))
         .route("/api/v1/accounts/:account_id/manual-states/:state_id", put(update_manual_account_state))
         .route("/api/v1/accounts/:account_id/manual-states/:state_id", delete(delete_manual_account_state))
+        // Account notes and documents routes
+        .route("/api/v1/accounts/:account_id/notes", post(create_account_note))
+        .route("/api/v1/accounts/:account_id/notes", get(get_account_notes))
+        .route("/api/v1/accounts/:account_id/notes/:note_id", put(update_account_note))
+        .route("/api/v1/accounts/:account_id/notes/:note_id", delete(delete_account_note))
+        .route("/api/v1/accounts/:account_id/documents", post(upload_account_document))
+        .route("/api/v1/accounts/:account_id/documents", get(get_account_documents))
+        .route("/api/v1/accounts/:account_id/documents/:document_id", delete(delete_account_document))
+        .route(
+            "/api/v1/accounts/:account_id/documents/:document_id/download",
+            get(download_account_document),
+        )
         // User CRUD routes
         .route("/api/v1/users", post(create_user))
         .route("/api/v1/users", get(get_users))
         .route("/api/v1/users/:user_id", get(get_user))
         .route("/api/v1/users/:user_id", put(update_user))
         .route("/api/v1/users/:user_id", delete(delete_user))
+        .route("/api/v1/users/:user_id/settings", get(get_user_settings))
+        .route("/api/v1/users/:user_id/settings", put(update_user_settings))
         // Tag CRUD routes
         .route("/api/v1/tags", post(create_tag))
         .route("/api/v1/tags", get(get_tags))
+        .route("/api/v1/tags/limits", get(get_tag_limits))
         .route("/api/v1/tags/:tag_id", get(get_tag))
         .route("/api/v1/tags/:tag_id", put(update_tag))
         .route("/api/v1/tags/:tag_id", delete(delete_tag))
@@ -143,6 +338,7 @@ fn build_routes(router: Router<AppState>) -> Router<AppState> {
         .route("/api/v1/tags/:tag_id/children", get(get_tag_children))
         .route("/api/v1/tags/:tag_id/parent/:parent_id", put(link_tag_to_parent))
         .route("/api/v1/tags/:tag_id/parent", delete(unlink_tag_from_parent))
+        .route("/api/v1/tags/:tag_id/assign", post(assign_tag_to_transactions))
         // Category CRUD routes
         .route("/api/v1/categories", post(create_category))
         .route("/api/v1/categories", get(get_categories))
@@ -151,37 +347,94 @@ fn build_routes(router: Router<AppState>) -> Router<AppState> {
         .route("/api/v1/categories/:id", delete(delete_category))
         // Category tree structure and stats routes
         .route("/api/v1/categories/:id/children", get(get_category_children))
+        .route("/api/v1/categories/:id/forecast", get(get_category_forecast))
         .route("/api/v1/categories/stats", get(get_category_stats))
+        .route("/api/v1/categories/seed", post(seed_categories))
+        .route("/api/v1/double-entry/report", get(get_double_entry_report))
+        .route("/api/v1/export/chart-of-accounts", get(export_chart_of_accounts))
+        .route("/api/v1/export/full", get(export_full))
+        .route(
+            "/api/v1/import/full",
+            post(import_full).layer(DefaultBodyLimit::max(MAX_ARCHIVE_BYTES)),
+        )
+        .route("/api/v1/insights/cashflow-smoothing", get(get_cashflow_smoothing_advice))
         // Transaction CRUD routes
         .route("/api/v1/transactions", post(create_transaction))
         .route("/api/v1/transactions", get(get_transactions))
+        .route("/api/v1/transactions/quick", post(create_quick_transaction))
+        .route("/api/v1/transactions/search", get(search_transactions))
+        .route("/api/v1/transactions/uncategorized", get(get_uncategorized_transactions))
+        .route("/api/v1/transactions/categorize", post(bulk_categorize_transactions))
+        .route("/api/v1/transactions/bulk-update", post(bulk_update_transactions))
+        .route("/api/v1/transactions/bulk-delete", post(bulk_delete_transactions))
+        .route("/api/v1/transactions/geojson", get(get_transactions_geojson))
+        .route("/api/v1/transactions/purchase-reminders", get(get_purchase_reminders))
+        .route("/api/v1/transactions/recurring-suggestions", get(get_recurring_suggestions))
+        .route("/api/v1/transactions/recurring-suggestions/convert", post(convert_recurring_suggestion))
         .route("/api/v1/transactions/:transaction_id", get(get_transaction))
         .route("/api/v1/transactions/:transaction_id", put(update_transaction))
         .route("/api/v1/transactions/:transaction_id", delete(delete_transaction))
+        .route("/api/v1/transactions/:transaction_id/confirm", post(confirm_transaction))
+        .route("/api/v1/transactions/:transaction_id/legs", post(create_transaction_leg))
+        .route("/api/v1/transactions/:transaction_id/similar", get(get_similar_transactions))
         .route("/api/v1/accounts/:account_id/transactions", get(get_account_transactions))
         // Recurring transaction routes
         .route("/api/v1/recurring-transactions", post(create_recurring_transaction))
         .route("/api/v1/recurring-transactions", get(get_recurring_transactions))
         .route("/api/v1/recurring-transactions/missing-instances", get(get_missing_instances))
+        .route("/api/v1/recurring-transactions/due-profile", get(get_recurring_due_profile))
+        .route("/api/v1/recurring-transactions/contract-reminders", get(get_contract_reminders))
         .route("/api/v1/recurring-transactions/bulk-create-instances", post(bulk_create_instances))
+        .route("/api/v1/recurring-transactions/bulk", patch(bulk_update_recurring_transactions))
         .route("/api/v1/recurring-transactions/:recurring_transaction_id", get(get_recurring_transaction))
         .route("/api/v1/recurring-transactions/:recurring_transaction_id", put(update_recurring_transaction))
         .route("/api/v1/recurring-transactions/:recurring_transaction_id", delete(delete_recurring_transaction))
         .route("/api/v1/recurring-transactions/:recurring_transaction_id/instances", post(create_recurring_instance))
+        .route("/api/v1/recurring-transactions/:recurring_transaction_id/approve", post(approve_recurring_transaction))
+        .route("/api/v1/recurring-transactions/:recurring_transaction_id/reject", post(reject_recurring_transaction))
+        .route("/api/v1/recurring-transactions/:recurring_transaction_id/alerts", get(get_recurring_alerts))
+        .route("/api/v1/recurring-transactions/:recurring_transaction_id/alerts", put(update_recurring_alerts))
+        .route("/api/v1/accounts/:account_id/recurring-approval-policy", put(set_recurring_approval_policy))
         // Recurring instance routes
         .route("/api/v1/recurring-instances", get(get_recurring_instances))
+        .route("/api/v1/recurring-instances/generate", post(generate_instances))
         .route("/api/v1/recurring-instances/:instance_id", get(get_recurring_instance))
         .route("/api/v1/recurring-instances/:instance_id", put(update_recurring_instance))
         .route("/api/v1/recurring-instances/:instance_id", delete(delete_recurring_instance))
+        .route("/api/v1/recurring-instances/:instance_id/skip", post(skip_recurring_instance))
+        .route("/api/v1/recurring-instances/:instance_id/unskip", post(unskip_recurring_instance))
         // Imported transaction routes
         .route("/api/v1/imported-transactions", post(create_imported_transaction))
         .route("/api/v1/imported-transactions", get(get_imported_transactions))
+        .route("/api/v1/imported-transactions/classify-bank-charges", post(classify_bank_charges))
+        .route("/api/v1/imported-transactions/bulk-reconcile", post(bulk_reconcile_imported_transactions))
         .route("/api/v1/imported-transactions/:transaction_id", get(get_imported_transaction))
         .route("/api/v1/imported-transactions/:transaction_id", put(update_imported_transaction))
         .route("/api/v1/imported-transactions/:transaction_id", delete(delete_imported_transaction))
         .route("/api/v1/accounts/:account_id/imported-transactions", get(get_account_imported_transactions))
         .route("/api/v1/imported-transactions/:transaction_id/reconcile", post(reconcile_imported_transaction))
         .route("/api/v1/imported-transactions/:transaction_id/reconcile", delete(clear_imported_transaction_reconciliation))
+        .route("/api/v1/imported-transactions/:transaction_id/category-suggestions", get(get_category_suggestions))
+        // Merchant enrichment mappings
+        .route("/api/v1/merchants", post(create_merchant))
+        .route("/api/v1/merchants", get(get_merchants))
+        .route("/api/v1/merchants/:merchant_id", delete(delete_merchant))
+        // Budget routes
+        .route("/api/v1/budgets", post(create_budget))
+        .route("/api/v1/budgets", get(get_budgets))
+        .route("/api/v1/budgets/status", get(get_budget_status))
+        .route("/api/v1/budgets/suggest", post(suggest_budgets))
+        .route("/api/v1/budgets/:budget_id", get(get_budget))
+        .route("/api/v1/budgets/:budget_id", put(update_budget))
+        .route("/api/v1/budgets/:budget_id", delete(delete_budget))
+        .route("/api/v1/budgets/:budget_id/progress", get(get_budget_progress))
+        // Forecast accuracy tracking
+        .route("/api/v1/forecast/snapshots", post(create_forecast_snapshots))
+        .route("/api/v1/forecast/accuracy", get(get_forecast_accuracy))
+        // Historical exchange rates
+        .route("/api/v1/exchange-rates", post(create_exchange_rate))
+        .route("/api/v1/exchange-rates", get(get_exchange_rates))
+        .route("/api/v1/exchange-rates/refresh", post(refresh_exchange_rates))
         // Recurring income routes
         .route("/api/v1/recurring-incomes", post(create_recurring_income))
         .route("/api/v1/recurring-incomes", get(get_recurring_incomes))
@@ -195,9 +448,48 @@ fn build_routes(router: Router<AppState>) -> Router<AppState> {
         .route("/api/v1/scenarios/:scenario_id", put(update_scenario))
         .route("/api/v1/scenarios/:scenario_id", delete(delete_scenario))
         .route("/api/v1/scenarios/:scenario_id/apply", post(apply_scenario))
+        .route("/api/v1/scenarios/:scenario_id/clone", post(clone_scenario))
+        // Locked accounting period routes
+        .route("/api/v1/periods", get(get_locked_periods))
+        .route("/api/v1/periods/lock", post(lock_period))
+        .route("/api/v1/periods/:period_id", delete(unlock_period))
+        // Holiday calendar routes
+        .route("/api/v1/holidays", get(get_holidays))
+        .route("/api/v1/holidays", post(create_holiday))
+        .route("/api/v1/holidays/calendars", get(get_holiday_calendars))
+        .route("/api/v1/holidays/calendars", put(update_holiday_calendars))
+        .route("/api/v1/holidays/:holiday_id", delete(delete_holiday))
+        // Notification channel and payment alert routes
+        .route("/api/v1/notification-channels", get(get_notification_channels))
+        .route("/api/v1/notification-channels", post(create_notification_channel))
+        .route("/api/v1/notification-channels/:channel_id", put(update_notification_channel))
+        .route("/api/v1/notification-channels/:channel_id", delete(delete_notification_channel))
+        .route("/api/v1/notification-channels/:channel_id/test", post(test_notification_channel))
+        .route("/api/v1/payment-alerts/send", post(send_due_payment_alerts))
+        // Reconciliation session routes
+        .route("/api/v1/reconciliation-sessions", post(create_reconciliation_session))
+        .route("/api/v1/reconciliation-sessions", get(get_reconciliation_sessions))
+        .route("/api/v1/reconciliation-sessions/:session_id", get(get_reconciliation_session))
+        .route("/api/v1/reconciliation-sessions/:session_id", put(update_reconciliation_session))
+        .route("/api/v1/reconciliation-sessions/:session_id", delete(delete_reconciliation_session))
+        .route("/api/v1/reconciliation-sessions/:session_id/transactions/:transaction_id", post(add_reconciliation_transaction))
+        .route("/api/v1/reconciliation-sessions/:session_id/transactions/:transaction_id", delete(remove_reconciliation_transaction))
+        .route("/api/v1/reconciliation-sessions/:session_id/complete", post(complete_reconciliation_session))
         // Metrics routes
         .route("/api/v1/metrics/dashboard", get(get_dashboard_metrics))
         .route("/api/v1/accounts/:account_id/metrics", get(get_account_metrics))
+        // Household net worth
+        .route("/api/v1/networth/timeseries", get(get_networth_timeseries))
+        // Household asset inventory
+        .route("/api/v1/assets", post(create_asset))
+        .route("/api/v1/assets", get(get_assets))
+        .route("/api/v1/assets/:asset_id", get(get_asset))
+        .route("/api/v1/assets/:asset_id", put(update_asset))
+        .route("/api/v1/assets/:asset_id", delete(delete_asset))
+        .route("/api/v1/assets/:asset_id/dispose", post(dispose_asset))
+        // Reports
+        .route("/api/v1/reports/cashflow", get(get_cashflow_report))
+        .route("/api/v1/reports/flows", get(get_flow_report))
         // Prompt generation
         .route("/api/v1/prompt", get(get_financial_prompt))
         // API v1 routes (existing statistics and timeseries)
@@ -213,14 +505,23 @@ fn build_routes(router: Router<AppState>) -> Router<AppState> {
             "/api/v1/accounts/:account_id/timeseries",
             get(get_account_timeseries),
         )
+        .route(
+            "/api/v1/accounts/:account_id/sparkline",
+            get(get_account_sparkline),
+        )
+        .route(
+            "/api/v1/accounts/:account_id/balance-preview",
+            post(preview_account_balance),
+        )
+        .route("/api/v1/accounts/:account_id/forecast", get(get_account_forecast))
+        .route("/api/v1/accounts/:account_id/goal-progress", get(get_goal_progress))
         .route(
             "/api/v1/accounts/statistics",
             get(get_all_accounts_statistics),
         )
+        .route("/api/v1/statistics/safe-to-spend", get(get_safe_to_spend))
         .route(
             "/api/v1/accounts/timeseries",
             get(get_all_accounts_timeseries),
         )
-        // Swagger UI
-        .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
 }
@@ -1,14 +1,18 @@
 use chrono::NaiveDate;
 use common::{
-    AccountKindMetricsDto, AccountMetricsDto, AccountStateTimeseries, AccountStatistics,
-    AccountStatisticsCollection, DashboardMetricsDto, DateRange, DebtMetricsDto,
-    InvestmentMetricsDto, MonthlyMinBalance, MonthlyMinBalanceSeries, OperatingMetricsDto,
-    ReserveMetricsDto, TimePeriod,
+    AccountKindMetricsDto, AccountMetricsDto, AccountStateTimeseries, AccountStatistics, AmountLocale,
+    BalancePreviewPoint, BalancePreviewRequest, BalancePreviewResponse, PreviewTransaction,
+    AccountStatisticsCollection, CashflowPeriodDto, CashflowReportDto, DashboardMetricsDto, DateRange,
+    DebtMetricsDto, FlowEdgeDto, FlowNodeDto, FlowNodeKind, FlowReportDto, InvestmentMetricsDto,
+    MonthlyMinBalance, MonthlyMinBalanceSeries, OperatingMetricsDto, PaginatedResponse, Pagination, PointGeometry,
+    ReportGranularity, ReserveMetricsDto, TimePeriod, TransactionFeature, TransactionFeatureCollection,
+    TransactionFeatureProperties,
 };
 use moka::future::Cache;
 use sea_orm::DatabaseConnection;
 use serde::{Deserialize, Serialize};
-use utoipa::{OpenApi, ToSchema};
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi, ToSchema};
 use validator::Validate;
 
 /// Application state shared across handlers
@@ -18,6 +22,22 @@ pub struct AppState {
     pub db: DatabaseConnection,
     /// Cache for expensive operations
     pub cache: Cache<String, CachedData>,
+    /// Reject transactions that don't balance as double-entry postings
+    pub double_entry_strict: bool,
+    /// Record successful mutations in the tamper-evident audit chain
+    pub audit_chain: bool,
+    /// Provider daily exchange rates are fetched from, if any
+    pub fx_provider: Option<crate::tasks::exchange_rates::FxProvider>,
+    /// Cipher for sensitive fields such as account numbers; storing them is refused without it
+    pub field_cipher: Option<crate::helpers::field_encryption::FieldCipher>,
+    /// Reject API requests that don't carry a valid session token
+    pub auth_required: bool,
+    /// Timezone deciding what "today" is for requests without a user timezone
+    pub timezone: chrono_tz::Tz,
+    /// SMTP server e-mail notification channels send through, if any
+    pub smtp: Option<crate::helpers::notifications::SmtpSettings>,
+    /// Book snapshots restored for `as_of` reads
+    pub restored_books: crate::helpers::time_travel::RestoredBooksCache,
 }
 
 /// Cached data types
@@ -43,6 +63,8 @@ pub struct StatisticsQuery {
     /// Include accounts excluded from statistics
     #[serde(default)]
     pub include_ignored: bool,
+    /// Scenario ID to include simulated transactions (default: the owner's active scenario)
+    pub scenario_id: Option<i32>,
 }
 
 /// Query parameters for monthly minimum balance endpoint
@@ -51,6 +73,8 @@ pub struct MonthlyMinBalanceQuery {
     /// Number of past months to include (default 12, max 120)
     #[validate(range(min = 1, max = 120))]
     pub months: Option<u32>,
+    /// Scenario ID to include simulated transactions (default: the owner's active scenario)
+    pub scenario_id: Option<i32>,
 }
 
 /// Query parameters for timeseries endpoints
@@ -64,7 +88,7 @@ pub struct TimeseriesQuery {
     /// Include accounts excluded from statistics
     #[serde(default)]
     pub include_ignored: bool,
-    /// Scenario ID to include simulated transactions
+    /// Scenario ID to include simulated transactions (default: the owner's active scenario)
     pub scenario_id: Option<i32>,
 }
 
@@ -113,33 +137,114 @@ pub struct HealthResponse {
 #[openapi(
     paths(
         crate::handlers::health::health_check,
+        crate::handlers::diagnostics::submit_diagnostic_report,
+        crate::handlers::audit::verify_audit_chain,
+        crate::handlers::auth::login,
+        crate::handlers::auth::logout,
+        crate::handlers::auth::get_current_user,
+        crate::handlers::auth::change_password,
+        crate::handlers::tokens::get_api_tokens,
+        crate::handlers::tokens::create_api_token,
+        crate::handlers::tokens::delete_api_token,
+        crate::handlers::batch::execute_batch,
+        crate::handlers::cache::flush_cache,
+        crate::handlers::openapi::get_openapi_coverage,
         crate::handlers::accounts::create_account,
         crate::handlers::accounts::get_accounts,
         crate::handlers::accounts::get_account,
         crate::handlers::accounts::update_account,
+        crate::handlers::accounts::validate_account,
         crate::handlers::accounts::delete_account,
+        crate::handlers::account_comparison::compare_accounts,
+        crate::handlers::account_freshness::get_account_freshness,
+        crate::handlers::account_shares::get_account_shares,
+        crate::handlers::account_shares::share_account,
+        crate::handlers::account_shares::unshare_account,
         crate::handlers::manual_account_states::create_manual_account_state,
         crate::handlers::manual_account_states::get_manual_account_states,
         crate::handlers::manual_account_states::get_manual_account_state,
         crate::handlers::manual_account_states::update_manual_account_state,
         crate::handlers::manual_account_states::delete_manual_account_state,
+        crate::handlers::manual_account_states::get_all_manual_account_states,
+        crate::handlers::account_notes::create_account_note,
+        crate::handlers::account_notes::get_account_notes,
+        crate::handlers::account_notes::update_account_note,
+        crate::handlers::account_notes::delete_account_note,
+        crate::handlers::account_notes::upload_account_document,
+        crate::handlers::account_notes::get_account_documents,
+        crate::handlers::account_notes::download_account_document,
+        crate::handlers::account_notes::delete_account_document,
         crate::handlers::users::create_user,
         crate::handlers::users::get_users,
         crate::handlers::users::get_user,
         crate::handlers::users::update_user,
         crate::handlers::users::delete_user,
+        crate::handlers::users::get_user_settings,
+        crate::handlers::users::update_user_settings,
+        crate::handlers::tags::create_tag,
+        crate::handlers::tags::get_tags,
+        crate::handlers::tags::get_tag,
+        crate::handlers::tags::update_tag,
+        crate::handlers::tags::delete_tag,
+        crate::handlers::tags::get_tag_children,
+        crate::handlers::tags::get_tag_limits,
+        crate::handlers::tags::link_tag_to_parent,
+        crate::handlers::tags::unlink_tag_from_parent,
+        crate::handlers::tags::assign_tag_to_transactions,
+        crate::handlers::categories::create_category,
+        crate::handlers::categories::get_categories,
+        crate::handlers::categories::get_category,
+        crate::handlers::categories::update_category,
+        crate::handlers::categories::delete_category,
+        crate::handlers::categories::get_category_children,
+        crate::handlers::categories::get_category_forecast,
+        crate::handlers::categories::get_category_stats,
+        crate::handlers::categories::seed_categories,
         crate::handlers::transactions::create_transaction,
         crate::handlers::transactions::get_transactions,
+        crate::handlers::transactions::search_transactions,
         crate::handlers::transactions::get_account_transactions,
         crate::handlers::transactions::get_transaction,
         crate::handlers::transactions::update_transaction,
         crate::handlers::transactions::delete_transaction,
+        crate::handlers::transactions::get_uncategorized_transactions,
+        crate::handlers::transactions::bulk_categorize_transactions,
+        crate::handlers::transactions::bulk_update_transactions,
+        crate::handlers::transactions::bulk_delete_transactions,
+        crate::handlers::transactions::get_transactions_geojson,
+        crate::handlers::transactions::get_purchase_reminders,
+        crate::handlers::transactions::create_transaction_leg,
+        crate::handlers::transactions::confirm_transaction,
+        crate::handlers::transactions::create_quick_transaction,
+        crate::handlers::transactions::get_similar_transactions,
+        crate::handlers::transactions::get_recurring_suggestions,
+        crate::handlers::transactions::convert_recurring_suggestion,
+        crate::handlers::double_entry::get_double_entry_report,
+        crate::handlers::export::export_chart_of_accounts,
+        crate::handlers::export::export_full,
+        crate::handlers::restore::import_full,
+        crate::handlers::insights::get_cashflow_smoothing_advice,
         crate::handlers::transactions::create_recurring_transaction,
         crate::handlers::transactions::get_recurring_transactions,
         crate::handlers::transactions::get_recurring_transaction,
         crate::handlers::transactions::update_recurring_transaction,
         crate::handlers::transactions::delete_recurring_transaction,
         crate::handlers::transactions::create_recurring_instance,
+        crate::handlers::transactions::get_missing_instances,
+        crate::handlers::transactions::bulk_create_instances,
+        crate::handlers::transactions::get_recurring_instances,
+        crate::handlers::transactions::get_recurring_instance,
+        crate::handlers::transactions::update_recurring_instance,
+        crate::handlers::transactions::delete_recurring_instance,
+        crate::handlers::transactions::generate_instances,
+        crate::handlers::transactions::skip_recurring_instance,
+        crate::handlers::transactions::unskip_recurring_instance,
+        crate::handlers::transactions::get_recurring_due_profile,
+        crate::handlers::transactions::get_contract_reminders,
+        crate::handlers::transactions::set_recurring_approval_policy,
+        crate::handlers::transactions::approve_recurring_transaction,
+        crate::handlers::transactions::reject_recurring_transaction,
+        crate::handlers::transactions::bulk_update_recurring_transactions,
         crate::handlers::transactions::create_imported_transaction,
         crate::handlers::transactions::get_imported_transactions,
         crate::handlers::transactions::get_account_imported_transactions,
@@ -147,7 +252,10 @@ pub struct HealthResponse {
         crate::handlers::transactions::update_imported_transaction,
         crate::handlers::transactions::delete_imported_transaction,
         crate::handlers::transactions::reconcile_imported_transaction,
+        crate::handlers::transactions::bulk_reconcile_imported_transactions,
         crate::handlers::transactions::clear_imported_transaction_reconciliation,
+        crate::handlers::transactions::get_category_suggestions,
+        crate::handlers::transactions::classify_bank_charges,
         crate::handlers::recurring_income::create_recurring_income,
         crate::handlers::recurring_income::get_recurring_incomes,
         crate::handlers::recurring_income::get_recurring_income,
@@ -159,18 +267,89 @@ pub struct HealthResponse {
         crate::handlers::scenarios::update_scenario,
         crate::handlers::scenarios::delete_scenario,
         crate::handlers::scenarios::apply_scenario,
+        crate::handlers::scenarios::clone_scenario,
+        crate::handlers::merchants::create_merchant,
+        crate::handlers::merchants::get_merchants,
+        crate::handlers::merchants::delete_merchant,
+        crate::handlers::budgets::create_budget,
+        crate::handlers::budgets::get_budgets,
+        crate::handlers::budgets::get_budget,
+        crate::handlers::budgets::update_budget,
+        crate::handlers::budgets::delete_budget,
+        crate::handlers::budgets::get_budget_status,
+        crate::handlers::budgets::get_budget_progress,
+        crate::handlers::budgets::suggest_budgets,
+        crate::handlers::forecast::create_forecast_snapshots,
+        crate::handlers::forecast::get_forecast_accuracy,
+        crate::handlers::forecast::get_account_forecast,
+        crate::handlers::goals::get_goal_progress,
+        crate::handlers::exchange_rates::create_exchange_rate,
+        crate::handlers::exchange_rates::get_exchange_rates,
+        crate::handlers::exchange_rates::refresh_exchange_rates,
+        crate::handlers::periods::lock_period,
+        crate::handlers::periods::get_locked_periods,
+        crate::handlers::periods::unlock_period,
+        crate::handlers::holidays::get_holidays,
+        crate::handlers::holidays::create_holiday,
+        crate::handlers::holidays::delete_holiday,
+        crate::handlers::holidays::get_holiday_calendars,
+        crate::handlers::holidays::update_holiday_calendars,
+        crate::handlers::notifications::get_notification_channels,
+        crate::handlers::notifications::create_notification_channel,
+        crate::handlers::notifications::update_notification_channel,
+        crate::handlers::notifications::delete_notification_channel,
+        crate::handlers::notifications::test_notification_channel,
+        crate::handlers::notifications::get_recurring_alerts,
+        crate::handlers::notifications::update_recurring_alerts,
+        crate::handlers::notifications::send_due_payment_alerts,
+        crate::handlers::reconciliation::create_reconciliation_session,
+        crate::handlers::reconciliation::get_reconciliation_sessions,
+        crate::handlers::reconciliation::get_reconciliation_session,
+        crate::handlers::reconciliation::update_reconciliation_session,
+        crate::handlers::reconciliation::delete_reconciliation_session,
+        crate::handlers::reconciliation::add_reconciliation_transaction,
+        crate::handlers::reconciliation::remove_reconciliation_transaction,
+        crate::handlers::reconciliation::complete_reconciliation_session,
         crate::handlers::statistics::get_account_statistics,
         crate::handlers::statistics::get_monthly_min_balance,
         crate::handlers::timeseries::get_account_timeseries,
         crate::handlers::statistics::get_all_accounts_statistics,
+        crate::handlers::safe_to_spend::get_safe_to_spend,
         crate::handlers::timeseries::get_all_accounts_timeseries,
+        crate::handlers::timeseries::get_account_sparkline,
+        crate::handlers::balance_preview::preview_account_balance,
         crate::handlers::metrics::get_dashboard_metrics,
         crate::handlers::metrics::get_account_metrics,
+        crate::handlers::networth::get_networth_timeseries,
+        crate::handlers::assets::create_asset,
+        crate::handlers::assets::get_assets,
+        crate::handlers::assets::get_asset,
+        crate::handlers::assets::update_asset,
+        crate::handlers::assets::delete_asset,
+        crate::handlers::assets::dispose_asset,
+        crate::handlers::reports::get_cashflow_report,
+        crate::handlers::reports::get_flow_report,
+        crate::handlers::prompt::get_financial_prompt,
     ),
     components(
         schemas(
+            crate::handlers::auth::LoginRequest,
+            crate::handlers::auth::LoginResponse,
+            crate::handlers::auth::ChangePasswordRequest,
+            ApiResponse<crate::handlers::auth::LoginResponse>,
+            crate::handlers::tokens::CreateApiTokenRequest,
+            crate::handlers::tokens::ApiTokenResponse,
+            crate::handlers::tokens::CreatedApiTokenResponse,
+            ApiResponse<Vec<crate::handlers::tokens::ApiTokenResponse>>,
+            ApiResponse<crate::handlers::tokens::CreatedApiTokenResponse>,
+            crate::handlers::batch::BatchRequestItem,
+            crate::handlers::batch::BatchRequest,
+            crate::handlers::batch::BatchResponseItem,
+            ApiResponse<Vec<crate::handlers::batch::BatchResponseItem>>,
             ApiResponse<AccountStatisticsCollection>,
             ApiResponse<AccountStateTimeseries>,
+            ApiResponse<crate::handlers::timeseries::AccountSparkline>,
+            ApiResponse<BalancePreviewResponse>,
             ApiResponse<Vec<AccountStatisticsCollection>>,
             ApiResponse<crate::handlers::accounts::AccountResponse>,
             ApiResponse<Vec<crate::handlers::accounts::AccountResponse>>,
@@ -179,54 +358,305 @@ pub struct HealthResponse {
             ApiResponse<crate::handlers::transactions::TransactionResponse>,
             ApiResponse<Vec<crate::handlers::transactions::TransactionResponse>>,
             ApiResponse<String>,
+            Pagination,
+            PaginatedResponse<Vec<crate::handlers::transactions::TransactionResponse>>,
+            PaginatedResponse<Vec<crate::handlers::transactions::ImportedTransactionResponse>>,
+            PaginatedResponse<Vec<crate::handlers::transactions::RecurringTransactionResponse>>,
+            PaginatedResponse<Vec<crate::handlers::transactions::RecurringInstanceResponse>>,
+            PaginatedResponse<Vec<crate::handlers::recurring_income::RecurringIncomeResponse>>,
             crate::handlers::accounts::CreateAccountRequest,
             crate::handlers::accounts::UpdateAccountRequest,
+            crate::handlers::accounts::BalanceInterpolation,
+            crate::handlers::accounts::AccountStatisticsExclusion,
+            crate::handlers::account_comparison::AccountComparisonQuery,
+            crate::handlers::account_comparison::AccountBalanceChange,
+            crate::handlers::account_comparison::TransactionContribution,
+            crate::handlers::account_comparison::CategoryContribution,
+            crate::handlers::account_comparison::AccountComparisonResponse,
+            ApiResponse<crate::handlers::account_comparison::AccountComparisonResponse>,
+            crate::handlers::account_freshness::AccountFreshness,
+            ApiResponse<Vec<crate::handlers::account_freshness::AccountFreshness>>,
+            crate::handlers::account_shares::SharePermission,
+            crate::handlers::account_shares::ShareAccountRequest,
+            crate::handlers::account_shares::AccountShareResponse,
+            ApiResponse<crate::handlers::account_shares::AccountShareResponse>,
+            ApiResponse<Vec<crate::handlers::account_shares::AccountShareResponse>>,
             crate::handlers::accounts::AccountResponse,
+            crate::handlers::accounts::AccountValidationIssue,
+            crate::handlers::accounts::AccountValidationResponse,
+            ApiResponse<crate::handlers::accounts::AccountValidationResponse>,
             crate::handlers::manual_account_states::CreateManualAccountStateRequest,
             crate::handlers::manual_account_states::UpdateManualAccountStateRequest,
             crate::handlers::manual_account_states::ManualAccountStateResponse,
             ApiResponse<crate::handlers::manual_account_states::ManualAccountStateResponse>,
             ApiResponse<Vec<crate::handlers::manual_account_states::ManualAccountStateResponse>>,
+            crate::handlers::account_notes::CreateAccountNoteRequest,
+            crate::handlers::account_notes::UpdateAccountNoteRequest,
+            crate::handlers::account_notes::AccountNoteResponse,
+            crate::handlers::account_notes::UploadAccountDocumentRequest,
+            crate::handlers::account_notes::AccountDocumentResponse,
+            ApiResponse<crate::handlers::account_notes::AccountNoteResponse>,
+            ApiResponse<Vec<crate::handlers::account_notes::AccountNoteResponse>>,
+            ApiResponse<crate::handlers::account_notes::AccountDocumentResponse>,
+            ApiResponse<Vec<crate::handlers::account_notes::AccountDocumentResponse>>,
             crate::handlers::users::CreateUserRequest,
             crate::handlers::users::UpdateUserRequest,
             crate::handlers::users::UserResponse,
+            crate::handlers::users::UserSettings,
+            ApiResponse<crate::handlers::users::UserSettings>,
+            crate::handlers::tags::CreateTagRequest,
+            crate::handlers::tags::UpdateTagRequest,
+            crate::handlers::tags::TagResponse,
+            crate::handlers::tags::TagChildrenQuery,
+            crate::handlers::tags::TransactionTagFilter,
+            crate::handlers::tags::AssignTagRequest,
+            crate::handlers::tags::AssignTagResponse,
+            crate::handlers::tags::TagLimitsQuery,
+            crate::handlers::tags::TagLimitResponse,
+            crate::handlers::tags::TagLimitsResponse,
+            ApiResponse<crate::handlers::tags::TagResponse>,
+            ApiResponse<Vec<crate::handlers::tags::TagResponse>>,
+            ApiResponse<crate::handlers::tags::AssignTagResponse>,
+            ApiResponse<crate::handlers::tags::TagLimitsResponse>,
+            crate::handlers::categories::CreateCategoryRequest,
+            crate::handlers::categories::UpdateCategoryRequest,
+            crate::handlers::categories::CategoryResponse,
+            crate::handlers::categories::SeedCategoriesQuery,
+            crate::handlers::categories::SeedCategoriesResponse,
+            crate::handlers::categories::YearlyTotal,
+            crate::handlers::categories::CategoryStatsResponse,
+            crate::handlers::categories::MonthlyForecastEntry,
+            crate::handlers::categories::CategoryForecastResponse,
+            ApiResponse<crate::handlers::categories::CategoryResponse>,
+            ApiResponse<Vec<crate::handlers::categories::CategoryResponse>>,
+            ApiResponse<crate::handlers::categories::SeedCategoriesResponse>,
+            ApiResponse<Vec<crate::handlers::categories::CategoryStatsResponse>>,
+            ApiResponse<crate::handlers::categories::CategoryForecastResponse>,
             crate::handlers::transactions::CreateTransactionRequest,
             crate::handlers::transactions::UpdateTransactionRequest,
             crate::handlers::transactions::TransactionResponse,
+            crate::handlers::transactions::CreateTransactionLegRequest,
+            crate::handlers::transactions::ConfirmTransactionRequest,
+            crate::handlers::transactions::TransactionStatus,
+            crate::handlers::transactions::PurchaseDeadline,
+            crate::handlers::transactions::PurchaseReminderResponse,
+            ApiResponse<Vec<crate::handlers::transactions::PurchaseReminderResponse>>,
+            crate::handlers::transactions::TransactionSortField,
+            crate::handlers::transactions::SortDirection,
+            crate::handlers::transactions::TransactionLegResponse,
+            crate::handlers::transactions::SimilarTransactionsQuery,
+            crate::handlers::transactions::SimilarTransaction,
+            crate::handlers::transactions::SimilarTransactionsResponse,
+            ApiResponse<crate::handlers::transactions::SimilarTransactionsResponse>,
+            crate::handlers::transactions::UncategorizedTransactionsResponse,
+            crate::handlers::transactions::CategoryAssignment,
+            crate::handlers::transactions::BulkCategorizeRequest,
+            crate::handlers::transactions::BulkCategorizeResponse,
+            ApiResponse<crate::handlers::transactions::UncategorizedTransactionsResponse>,
+            ApiResponse<crate::handlers::transactions::BulkCategorizeResponse>,
+            crate::handlers::transactions::BulkTransactionPatch,
+            crate::handlers::transactions::BulkUpdateTransactionsRequest,
+            crate::handlers::transactions::BulkDeleteTransactionsRequest,
+            crate::handlers::transactions::BulkTransactionsResponse,
+            ApiResponse<crate::handlers::transactions::BulkTransactionsResponse>,
+            TransactionFeatureCollection,
+            TransactionFeature,
+            TransactionFeatureProperties,
+            PointGeometry,
+            crate::handlers::transactions::RecurringSuggestionResponse,
+            crate::handlers::transactions::ConvertRecurringSuggestionRequest,
+            crate::handlers::transactions::ConvertRecurringSuggestionResponse,
+            ApiResponse<Vec<crate::handlers::transactions::RecurringSuggestionResponse>>,
+            ApiResponse<crate::handlers::transactions::ConvertRecurringSuggestionResponse>,
+            crate::handlers::double_entry::DoubleEntryReport,
+            crate::handlers::double_entry::DoubleEntryReportEntry,
+            crate::helpers::double_entry::DoubleEntryViolation,
+            ApiResponse<crate::handlers::double_entry::DoubleEntryReport>,
+            crate::handlers::export::ExportFormat,
+            crate::handlers::export::AccountType,
+            crate::handlers::export::ChartOfAccountsEntry,
+            crate::handlers::export::ChartOfAccountsExport,
+            ApiResponse<crate::handlers::export::ChartOfAccountsExport>,
+            crate::handlers::restore::RestoredTable,
+            crate::handlers::restore::RestoreConflict,
+            crate::handlers::restore::RestoreResponse,
+            ApiResponse<crate::handlers::restore::RestoreResponse>,
+            crate::handlers::insights::PaymentShiftSuggestion,
+            crate::handlers::insights::CashflowSmoothingResponse,
+            ApiResponse<crate::handlers::insights::CashflowSmoothingResponse>,
             crate::handlers::transactions::CreateRecurringTransactionRequest,
             crate::handlers::transactions::UpdateRecurringTransactionRequest,
             crate::handlers::transactions::RecurringTransactionResponse,
+            crate::handlers::transactions::FxMode,
+            crate::handlers::transactions::AmountStrategy,
+            crate::handlers::transactions::DateAdjustment,
+            crate::handlers::transactions::RecurringApprovalPolicyRequest,
+            crate::handlers::transactions::BulkRecurringTransactionPatch,
+            crate::handlers::transactions::BulkUpdateRecurringTransactionsRequest,
+            crate::handlers::transactions::BulkRecurringItemResult,
+            crate::handlers::transactions::BulkUpdateRecurringTransactionsResponse,
+            ApiResponse<crate::handlers::transactions::BulkUpdateRecurringTransactionsResponse>,
+            crate::handlers::transactions::RecurringApprovalPolicyResponse,
+            ApiResponse<crate::handlers::transactions::RecurringApprovalPolicyResponse>,
+            crate::handlers::transactions::DuePaymentInfo,
+            crate::handlers::transactions::DueDayEntry,
+            crate::handlers::transactions::RecurringDueProfileResponse,
+            ApiResponse<crate::handlers::transactions::RecurringDueProfileResponse>,
+            crate::handlers::transactions::ContractReminderResponse,
+            ApiResponse<Vec<crate::handlers::transactions::ContractReminderResponse>>,
             crate::handlers::transactions::RecurringTransactionQuery,
             crate::handlers::transactions::CreateRecurringInstanceRequest,
             crate::handlers::transactions::RecurringInstanceResponse,
+            crate::handlers::transactions::UpdateRecurringInstanceRequest,
+            crate::handlers::transactions::RecurringInstanceQuery,
+            ApiResponse<crate::handlers::transactions::RecurringInstanceResponse>,
+            crate::handlers::transactions::MissingInstanceInfo,
+            ApiResponse<Vec<crate::handlers::transactions::MissingInstanceInfo>>,
+            crate::handlers::transactions::BulkCreateInstancesRequest,
+            crate::handlers::transactions::BulkInstanceItem,
+            crate::handlers::transactions::BulkCreateInstancesResponse,
+            ApiResponse<crate::handlers::transactions::BulkCreateInstancesResponse>,
+            crate::handlers::transactions::InstanceGrouping,
+            crate::handlers::transactions::RecurringInstanceMonthGroup,
+            ApiResponse<Vec<crate::handlers::transactions::RecurringInstanceMonthGroup>>,
+            crate::tasks::recurring_instances::InstanceGenerationSummary,
+            ApiResponse<crate::tasks::recurring_instances::InstanceGenerationSummary>,
+            AmountLocale,
+            crate::handlers::transactions::QuickEntryRequest,
             crate::handlers::transactions::CreateImportedTransactionRequest,
             crate::handlers::transactions::UpdateImportedTransactionRequest,
             crate::handlers::transactions::ImportedTransactionResponse,
             crate::handlers::transactions::ReconcileImportedTransactionRequest,
+            crate::handlers::transactions::BulkReconcileRequest,
+            crate::handlers::transactions::BulkReconcileItem,
+            crate::handlers::transactions::BulkReconcileItemResult,
+            crate::handlers::transactions::BulkReconcileResponse,
+            ApiResponse<crate::handlers::transactions::BulkReconcileResponse>,
             crate::handlers::transactions::ReconciledTransactionInfo,
             crate::handlers::transactions::ImportedTransactionQuery,
             ApiResponse<crate::handlers::transactions::ImportedTransactionResponse>,
             ApiResponse<Vec<crate::handlers::transactions::ImportedTransactionResponse>>,
+            crate::handlers::transactions::BankChargeClassificationResponse,
+            crate::handlers::transactions::CategorySuggestionsQuery,
+            crate::handlers::transactions::SuggestedCategory,
+            crate::handlers::transactions::CategorySuggestionsResponse,
+            ApiResponse<crate::handlers::transactions::CategorySuggestionsResponse>,
+            ApiResponse<crate::handlers::transactions::BankChargeClassificationResponse>,
             crate::handlers::recurring_income::CreateRecurringIncomeRequest,
             crate::handlers::recurring_income::UpdateRecurringIncomeRequest,
             crate::handlers::recurring_income::RecurringIncomeResponse,
             crate::handlers::recurring_income::RecurringIncomeQuery,
             crate::handlers::scenarios::CreateScenarioRequest,
             crate::handlers::scenarios::UpdateScenarioRequest,
+            crate::handlers::scenarios::CloneScenarioRequest,
             crate::handlers::scenarios::ScenarioResponse,
             crate::handlers::scenarios::ListScenariosQuery,
             ApiResponse<crate::handlers::scenarios::ScenarioResponse>,
             ApiResponse<Vec<crate::handlers::scenarios::ScenarioResponse>>,
+            crate::helpers::merchants::MerchantInfo,
+            crate::handlers::merchants::CreateMerchantRequest,
+            crate::handlers::merchants::MerchantResponse,
+            ApiResponse<crate::handlers::merchants::MerchantResponse>,
+            ApiResponse<Vec<crate::handlers::merchants::MerchantResponse>>,
+            crate::handlers::budgets::CreateBudgetRequest,
+            crate::handlers::budgets::UpdateBudgetRequest,
+            crate::handlers::budgets::BudgetResponse,
+            crate::handlers::budgets::BudgetStatusQuery,
+            crate::handlers::budgets::BudgetStatusResponse,
+            ApiResponse<crate::handlers::budgets::BudgetResponse>,
+            ApiResponse<Vec<crate::handlers::budgets::BudgetResponse>>,
+            ApiResponse<Vec<crate::handlers::budgets::BudgetStatusResponse>>,
+            crate::handlers::budgets::BudgetProgressQuery,
+            crate::handlers::budgets::BudgetProgressPointResponse,
+            crate::handlers::budgets::BudgetProgressResponse,
+            ApiResponse<crate::handlers::budgets::BudgetProgressResponse>,
+            crate::handlers::budgets::SuggestBudgetsRequest,
+            crate::handlers::budgets::BudgetSuggestionResponse,
+            crate::handlers::budgets::BudgetSuggestionsResponse,
+            ApiResponse<crate::handlers::budgets::BudgetSuggestionsResponse>,
+            crate::handlers::forecast::ForecastSnapshotResponse,
+            crate::handlers::forecast::ForecastAccuracyQuery,
+            crate::handlers::forecast::EvaluatedForecastResponse,
+            crate::handlers::forecast::HorizonAccuracyResponse,
+            crate::handlers::forecast::ForecastAccuracyResponse,
+            ApiResponse<Vec<crate::handlers::forecast::ForecastSnapshotResponse>>,
+            ApiResponse<crate::handlers::forecast::ForecastAccuracyResponse>,
+            crate::handlers::forecast::AccountForecastQuery,
+            crate::handlers::forecast::AccountForecastPoint,
+            crate::handlers::forecast::CategorySpendingForecast,
+            crate::handlers::forecast::AccountForecastResponse,
+            ApiResponse<crate::handlers::forecast::AccountForecastResponse>,
+            crate::handlers::goals::GoalProgressQuery,
+            crate::handlers::goals::GoalProgressResponse,
+            ApiResponse<crate::handlers::goals::GoalProgressResponse>,
+            crate::handlers::exchange_rates::CreateExchangeRateRequest,
+            crate::handlers::exchange_rates::ExchangeRateResponse,
+            crate::handlers::exchange_rates::ExchangeRateQuery,
+            ApiResponse<crate::handlers::exchange_rates::ExchangeRateResponse>,
+            ApiResponse<Vec<crate::handlers::exchange_rates::ExchangeRateResponse>>,
+            crate::tasks::exchange_rates::ExchangeRateRefreshSummary,
+            ApiResponse<crate::tasks::exchange_rates::ExchangeRateRefreshSummary>,
+            crate::handlers::periods::LockPeriodRequest,
+            crate::handlers::periods::LockedPeriodResponse,
+            crate::handlers::periods::PeriodLockOverride,
+            ApiResponse<crate::handlers::periods::LockedPeriodResponse>,
+            ApiResponse<Vec<crate::handlers::periods::LockedPeriodResponse>>,
+            crate::handlers::holidays::HolidayQuery,
+            crate::handlers::holidays::CreateHolidayRequest,
+            crate::handlers::holidays::HolidayResponse,
+            crate::handlers::holidays::HolidayCalendarInfo,
+            crate::handlers::holidays::HolidayCalendarsResponse,
+            crate::handlers::holidays::UpdateHolidayCalendarsRequest,
+            ApiResponse<crate::handlers::holidays::HolidayResponse>,
+            ApiResponse<Vec<crate::handlers::holidays::HolidayResponse>>,
+            ApiResponse<crate::handlers::holidays::HolidayCalendarsResponse>,
+            crate::handlers::notifications::NotificationChannelKind,
+            crate::handlers::notifications::CreateNotificationChannelRequest,
+            crate::handlers::notifications::UpdateNotificationChannelRequest,
+            crate::handlers::notifications::NotificationChannelResponse,
+            crate::handlers::notifications::RecurringAlertSettings,
+            crate::handlers::notifications::UpdateRecurringAlertsRequest,
+            crate::tasks::payment_alerts::PaymentAlertSummary,
+            ApiResponse<crate::handlers::notifications::NotificationChannelResponse>,
+            ApiResponse<Vec<crate::handlers::notifications::NotificationChannelResponse>>,
+            ApiResponse<crate::handlers::notifications::RecurringAlertSettings>,
+            ApiResponse<crate::tasks::payment_alerts::PaymentAlertSummary>,
+            crate::handlers::reconciliation::CreateReconciliationSessionRequest,
+            crate::handlers::reconciliation::UpdateReconciliationSessionRequest,
+            crate::handlers::reconciliation::ReconciliationSessionResponse,
+            crate::handlers::reconciliation::ReconciliationSessionQuery,
+            ApiResponse<crate::handlers::reconciliation::ReconciliationSessionResponse>,
+            ApiResponse<Vec<crate::handlers::reconciliation::ReconciliationSessionResponse>>,
             ErrorResponse,
             HealthResponse,
+            crate::handlers::openapi::DocumentedRoute,
+            crate::handlers::openapi::OpenApiCoverageResponse,
+            ApiResponse<crate::handlers::openapi::OpenApiCoverageResponse>,
+            crate::handlers::diagnostics::ApiCallRecord,
+            crate::handlers::diagnostics::DiagnosticReportRequest,
+            crate::handlers::diagnostics::DiagnosticReportResponse,
+            ApiResponse<crate::handlers::diagnostics::DiagnosticReportResponse>,
+            crate::helpers::audit_chain::AuditChainVerification,
+            ApiResponse<crate::helpers::audit_chain::AuditChainVerification>,
             MonthlyMinBalanceQuery,
             StatisticsQuery,
             TimeseriesQuery,
             AccountStatisticsCollection,
             AccountStatistics,
+            crate::handlers::safe_to_spend::SafeToSpendQuery,
+            crate::handlers::safe_to_spend::CommitmentType,
+            crate::handlers::safe_to_spend::CommittedOutflow,
+            crate::handlers::safe_to_spend::SafeToSpendResponse,
+            ApiResponse<crate::handlers::safe_to_spend::SafeToSpendResponse>,
             TimePeriod,
             AccountStateTimeseries,
             DateRange,
+            crate::handlers::timeseries::AccountSparkline,
+            crate::handlers::timeseries::SparklineQuery,
+            PreviewTransaction,
+            BalancePreviewRequest,
+            BalancePreviewPoint,
+            BalancePreviewResponse,
             DashboardMetricsDto,
             AccountMetricsDto,
             AccountKindMetricsDto,
@@ -239,22 +669,72 @@ pub struct HealthResponse {
             ApiResponse<MonthlyMinBalanceSeries>,
             ApiResponse<DashboardMetricsDto>,
             ApiResponse<AccountMetricsDto>,
+            crate::handlers::networth::NetWorthGrouping,
+            crate::handlers::networth::NetWorthQuery,
+            crate::handlers::networth::NetWorthPointResponse,
+            crate::handlers::networth::NetWorthGroupResponse,
+            crate::handlers::networth::NetWorthTimeseriesResponse,
+            ApiResponse<crate::handlers::networth::NetWorthTimeseriesResponse>,
+            crate::handlers::assets::DepreciationMethod,
+            crate::handlers::assets::CreateAssetRequest,
+            crate::handlers::assets::UpdateAssetRequest,
+            crate::handlers::assets::DisposeAssetRequest,
+            crate::handlers::assets::AssetResponse,
+            ApiResponse<crate::handlers::assets::AssetResponse>,
+            ApiResponse<Vec<crate::handlers::assets::AssetResponse>>,
+            crate::handlers::reports::CashflowReportQuery,
+            ReportGranularity,
+            CashflowPeriodDto,
+            CashflowReportDto,
+            ApiResponse<CashflowReportDto>,
+            crate::handlers::reports::FlowReportQuery,
+            FlowNodeKind,
+            FlowNodeDto,
+            FlowEdgeDto,
+            FlowReportDto,
+            ApiResponse<FlowReportDto>,
         )
     ),
     tags(
         (name = "health", description = "Health check endpoints"),
+        (name = "openapi", description = "Coverage of the router by this specification"),
+        (name = "cache", description = "Cached computation management"),
+        (name = "diagnostics", description = "Frontend crash reports"),
+        (name = "audit", description = "Tamper-evident audit hash chain"),
+        (name = "auth", description = "Login sessions"),
+        (name = "tokens", description = "API tokens for programmatic access"),
+        (name = "batch", description = "Several API requests in one round trip"),
         (name = "accounts", description = "Account CRUD operations"),
         (name = "manual-account-states", description = "Manual account state CRUD operations"),
+        (name = "account-notes", description = "Account notes and document attachments"),
         (name = "users", description = "User CRUD operations"),
+        (name = "tags", description = "Transaction tags, tag trees and monthly tag limits"),
+        (name = "categories", description = "Category CRUD, tree structure, statistics and forecasts"),
         (name = "transactions", description = "Transaction CRUD operations"),
         (name = "recurring-transactions", description = "Recurring transaction operations"),
         (name = "imported-transactions", description = "Imported transaction CRUD operations and reconciliation"),
         (name = "recurring-incomes", description = "Recurring income operations"),
         (name = "scenarios", description = "What-if scenario operations for hypothetical financial analysis"),
+        (name = "merchants", description = "Merchant name and logo enrichment mappings"),
+        (name = "budgets", description = "Spending limits per category and period"),
+        (name = "forecast", description = "Accuracy of past balance projections"),
+        (name = "exchange-rates", description = "Historical currency exchange rates"),
+        (name = "periods", description = "Locked accounting periods"),
+        (name = "holidays", description = "Holiday calendars skipped by work-day recurrences"),
+        (name = "notifications", description = "Notification channels and alerts for upcoming and overdue recurring payments"),
+        (name = "reconciliation", description = "Statement-style account reconciliation sessions"),
+        (name = "double-entry", description = "Double-entry integrity validation"),
+        (name = "export", description = "Exports for accountants and external tools"),
+        (name = "insights", description = "Advice derived from forecasts"),
         (name = "metrics", description = "Financial metrics and dashboard endpoints"),
+        (name = "networth", description = "Household net worth across accounts"),
+        (name = "assets", description = "Household assets with depreciation and disposal"),
+        (name = "reports", description = "Period summaries across all accounts"),
         (name = "statistics", description = "Account statistics endpoints"),
         (name = "timeseries", description = "Account timeseries endpoints"),
+        (name = "prompt", description = "Financial assessment prompt generation"),
     ),
+    modifiers(&SecurityAddon),
     info(
         title = "FinRust API",
         description = "Home Finance Tracker API - A comprehensive financial tracking and analysis system",
@@ -270,3 +750,17 @@ pub struct HealthResponse {
     )
 )]
 pub struct ApiDoc;
+
+/// Registers the bearer token scheme used by login sessions and API tokens.
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "bearer_auth",
+                SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+            );
+        }
+    }
+}
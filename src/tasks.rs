@@ -0,0 +1,77 @@
+//! Background tasks spawned by the web server.
+
+pub mod book_snapshots;
+pub mod exchange_rates;
+pub mod forecast_snapshots;
+pub mod payment_alerts;
+pub mod recurring_instances;
+
+use crate::helpers::notifications::Notifier;
+use crate::schemas::AppState;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+const FORECAST_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(24 * 3600);
+const BOOK_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Spawns the periodic tasks enabled in `state`.
+///
+/// Forecast snapshots are recorded daily, and with the audit chain enabled the books are
+/// snapshotted hourly for `as_of` reads. Exchange rates are refreshed every
+/// `fx_refresh_interval` when an FX provider is configured. Recurring instances are
+/// generated `instance_days_ahead` days ahead every `instance_generation_interval`,
+/// unless `instance_days_ahead` is zero. Payment alerts are sent every
+/// `payment_alert_interval`.
+pub fn spawn_background_tasks(
+    state: &AppState,
+    fx_refresh_interval: Duration,
+    instance_days_ahead: u32,
+    instance_generation_interval: Duration,
+    payment_alert_interval: Duration,
+) -> Vec<JoinHandle<()>> {
+    let mut tasks = vec![forecast_snapshots::spawn_forecast_snapshots(
+        state.db.clone(),
+        state.timezone,
+        FORECAST_SNAPSHOT_INTERVAL,
+    )];
+
+    if state.audit_chain {
+        info!("Recording book snapshots every {:?}", BOOK_SNAPSHOT_INTERVAL);
+        tasks.push(book_snapshots::spawn_book_snapshots(state.db.clone(), BOOK_SNAPSHOT_INTERVAL));
+    }
+
+    if let Some(provider) = state.fx_provider {
+        info!("Refreshing exchange rates from {:?} every {:?}", provider, fx_refresh_interval);
+        tasks.push(exchange_rates::spawn_exchange_rate_refresh(
+            state.db.clone(),
+            provider,
+            fx_refresh_interval,
+        ));
+    }
+
+    if instance_days_ahead > 0 {
+        info!(
+            "Generating recurring instances {} days ahead every {:?}",
+            instance_days_ahead, instance_generation_interval
+        );
+        tasks.push(recurring_instances::spawn_instance_generation(
+            state.db.clone(),
+            state.timezone,
+            instance_days_ahead,
+            instance_generation_interval,
+        ));
+    }
+
+    match Notifier::new(state.smtp.clone()) {
+        Ok(notifier) => tasks.push(payment_alerts::spawn_payment_alerts(
+            state.db.clone(),
+            notifier,
+            state.timezone,
+            payment_alert_interval,
+        )),
+        Err(e) => warn!("Payment alerts disabled, failed to set up notifications: {}", e),
+    }
+
+    tasks
+}
@@ -0,0 +1,32 @@
+use crate::helpers::time_travel::{prune_book_snapshots, record_book_snapshot};
+use chrono::Utc;
+use sea_orm::DatabaseConnection;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, info, warn};
+
+/// Records a snapshot of the books now and then every `every`, until the runtime shuts down.
+///
+/// A snapshot is only stored when the books changed since the previous one. `as_of`
+/// reads are answered from these, so history is as fine-grained as `every`. Older
+/// snapshots are then pruned to one a day, see [`prune_book_snapshots`].
+pub fn spawn_book_snapshots(db: DatabaseConnection, every: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            match record_book_snapshot(&db).await {
+                Ok(Some(snapshot)) => info!("Recorded book snapshot {} at {}", snapshot.id, snapshot.taken_at),
+                Ok(None) => debug!("Books unchanged since the last snapshot"),
+                Err(e) => warn!("Failed to record a book snapshot: {}", e),
+            }
+            match prune_book_snapshots(&db, Utc::now().naive_utc()).await {
+                Ok(0) => {}
+                Ok(pruned) => info!("Pruned {} superseded book snapshots", pruned),
+                Err(e) => warn!("Failed to prune book snapshots: {}", e),
+            }
+        }
+    })
+}
@@ -0,0 +1,202 @@
+use crate::helpers::fx::store_rate;
+use chrono::NaiveDate;
+use model::entities::account;
+use rust_decimal::Decimal;
+use sea_orm::{DatabaseConnection, DbErr, EntityTrait, QuerySelect};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, info, warn};
+use utoipa::ToSchema;
+
+const ECB_DAILY_URL: &str = "https://www.ecb.europa.eu/stats/eurofxref/eurofxref-daily.xml";
+const FRANKFURTER_LATEST_URL: &str = "https://api.frankfurter.app/latest";
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Source of daily exchange rates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum FxProvider {
+    /// European Central Bank reference rates, quoted against EUR only
+    Ecb,
+    /// Frankfurter API, quoted against any of the account currencies
+    Frankfurter,
+}
+
+#[derive(Debug, Error)]
+pub enum FxFetchError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Unexpected provider response: {0}")]
+    Parse(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] DbErr),
+}
+
+/// Rates of one base currency published for a single day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublishedRates {
+    pub base_currency: String,
+    pub date: NaiveDate,
+    /// Quote currency and units of it per unit of the base currency
+    pub rates: Vec<(String, Decimal)>,
+}
+
+/// Outcome of one refresh run.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExchangeRateRefreshSummary {
+    pub provider: String,
+    /// Number of rates created or replaced
+    pub rates_stored: usize,
+    /// Publication day of the fetched rates
+    pub date: Option<NaiveDate>,
+}
+
+fn xml_attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    ['\'', '"'].into_iter().find_map(|quote| {
+        let start = tag.find(&format!("{}={}", name, quote))? + name.len() + 2;
+        let end = tag[start..].find(quote)?;
+        Some(&tag[start..start + end])
+    })
+}
+
+/// Parses the ECB daily reference rates document (`eurofxref-daily.xml`).
+pub fn parse_ecb_daily(xml: &str) -> Result<PublishedRates, FxFetchError> {
+    let mut date = None;
+    let mut rates = Vec::new();
+
+    for element in xml.split("<Cube").skip(1) {
+        let tag = &element[..element.find('>').unwrap_or(element.len())];
+        if let Some(time) = xml_attribute(tag, "time") {
+            date = Some(
+                NaiveDate::parse_from_str(time, "%Y-%m-%d")
+                    .map_err(|e| FxFetchError::Parse(format!("invalid date '{}': {}", time, e)))?,
+            );
+        }
+        if let (Some(currency), Some(rate)) = (xml_attribute(tag, "currency"), xml_attribute(tag, "rate")) {
+            let rate = rate
+                .parse::<Decimal>()
+                .map_err(|e| FxFetchError::Parse(format!("invalid rate '{}' for {}: {}", rate, currency, e)))?;
+            rates.push((currency.to_uppercase(), rate));
+        }
+    }
+
+    let date = date.ok_or_else(|| FxFetchError::Parse("no publication date in ECB response".to_string()))?;
+    if rates.is_empty() {
+        return Err(FxFetchError::Parse("no rates in ECB response".to_string()));
+    }
+    Ok(PublishedRates {
+        base_currency: "EUR".to_string(),
+        date,
+        rates,
+    })
+}
+
+#[derive(Deserialize)]
+struct FrankfurterResponse {
+    base: String,
+    date: NaiveDate,
+    rates: HashMap<String, Decimal>,
+}
+
+/// Parses a Frankfurter `/latest` response.
+pub fn parse_frankfurter(json: &str) -> Result<PublishedRates, FxFetchError> {
+    let response: FrankfurterResponse =
+        serde_json::from_str(json).map_err(|e| FxFetchError::Parse(e.to_string()))?;
+    let mut rates: Vec<(String, Decimal)> = response
+        .rates
+        .into_iter()
+        .map(|(currency, rate)| (currency.to_uppercase(), rate))
+        .collect();
+    rates.sort();
+    Ok(PublishedRates {
+        base_currency: response.base.to_uppercase(),
+        date: response.date,
+        rates,
+    })
+}
+
+async fn fetch_text(request: reqwest::RequestBuilder) -> Result<String, FxFetchError> {
+    Ok(request.send().await?.error_for_status()?.text().await?)
+}
+
+/// Currencies used by accounts, which the Frankfurter provider fetches rates for.
+async fn account_currencies(db: &DatabaseConnection) -> Result<BTreeSet<String>, DbErr> {
+    let currencies: Vec<String> = account::Entity::find()
+        .select_only()
+        .column(account::Column::CurrencyCode)
+        .distinct()
+        .into_tuple()
+        .all(db)
+        .await?;
+    Ok(currencies.into_iter().map(|code| code.trim().to_uppercase()).collect())
+}
+
+/// Fetches the latest rates from `provider` and stores them, replacing rates
+/// already stored for the same day.
+pub async fn refresh_exchange_rates(
+    db: &DatabaseConnection,
+    provider: FxProvider,
+) -> Result<ExchangeRateRefreshSummary, FxFetchError> {
+    let client = reqwest::Client::builder()
+        .timeout(HTTP_TIMEOUT)
+        .user_agent(concat!("finrust/", env!("CARGO_PKG_VERSION")))
+        .build()?;
+
+    let published = match provider {
+        FxProvider::Ecb => {
+            let body = fetch_text(client.get(ECB_DAILY_URL)).await?;
+            vec![parse_ecb_daily(&body)?]
+        }
+        FxProvider::Frankfurter => {
+            let mut published = Vec::new();
+            for base in account_currencies(db).await? {
+                debug!("Fetching Frankfurter rates for {}", base);
+                let body = fetch_text(client.get(FRANKFURTER_LATEST_URL).query(&[("from", &base)])).await?;
+                published.push(parse_frankfurter(&body)?);
+            }
+            published
+        }
+    };
+
+    let mut rates_stored = 0;
+    for day in &published {
+        for (quote, rate) in &day.rates {
+            if *quote == day.base_currency || *rate <= Decimal::ZERO {
+                continue;
+            }
+            store_rate(db, &day.base_currency, quote, day.date, *rate).await?;
+            rates_stored += 1;
+        }
+    }
+
+    Ok(ExchangeRateRefreshSummary {
+        provider: format!("{:?}", provider),
+        rates_stored,
+        date: published.iter().map(|day| day.date).max(),
+    })
+}
+
+/// Refreshes exchange rates now and then every `every`, until the runtime shuts down.
+///
+/// Failures are logged and retried on the next tick.
+pub fn spawn_exchange_rate_refresh(db: DatabaseConnection, provider: FxProvider, every: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            match refresh_exchange_rates(&db, provider).await {
+                Ok(summary) => info!(
+                    "Refreshed {} exchange rates from {:?} for {:?}",
+                    summary.rates_stored, provider, summary.date
+                ),
+                Err(e) => warn!("Failed to refresh exchange rates from {:?}: {}", provider, e),
+            }
+        }
+    })
+}
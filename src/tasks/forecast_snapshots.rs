@@ -0,0 +1,26 @@
+use crate::helpers::forecast_snapshots::record_forecast_snapshots;
+use chrono_tz::Tz;
+use sea_orm::DatabaseConnection;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{info, warn};
+
+/// Records forecast snapshots now and then every `every`, until the runtime shuts down.
+///
+/// Snapshots are per day, so running more often only refreshes today's projections.
+/// "Today" is the date in the server's `timezone`.
+pub fn spawn_forecast_snapshots(db: DatabaseConnection, timezone: Tz, every: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let today = compute::today_in(&timezone);
+            match record_forecast_snapshots(&db, today).await {
+                Ok(snapshots) => info!("Recorded {} forecast snapshots for {}", snapshots.len(), today),
+                Err(e) => warn!("Failed to record forecast snapshots for {}: {}", today, e),
+            }
+        }
+    })
+}
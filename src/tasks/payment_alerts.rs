@@ -0,0 +1,269 @@
+use crate::helpers::notifications::{Notification, Notifier};
+use chrono::{Duration as Days, NaiveDate, Utc};
+use chrono_tz::Tz;
+use model::entities::payment_alert::{self, PaymentAlertKind};
+use model::entities::recurring_transaction_instance::{self, InstanceStatus};
+use model::entities::{account, account_share, notification_channel, recurring_alert, recurring_transaction};
+use rust_decimal::Decimal;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, Set};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, info, warn};
+use utoipa::ToSchema;
+
+/// Instances overdue for longer are not alerted about, so enabling overdue alerts on a
+/// recurring transaction with a long unpaid history doesn't flood the channels.
+const OVERDUE_ALERT_WINDOW_DAYS: i64 = 30;
+
+/// Outcome of one payment alert run.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PaymentAlertSummary {
+    pub date: NaiveDate,
+    /// Instances alerted about as due soon
+    pub upcoming_sent: usize,
+    /// Instances alerted about as overdue
+    pub overdue_sent: usize,
+    /// Alerts no channel accepted; they are retried on the next run
+    pub failed: usize,
+}
+
+/// Payload of the `data` field of payment alerts posted to webhooks.
+#[derive(Debug, Serialize)]
+struct PaymentAlertData<'a> {
+    kind: &'a str,
+    recurring_transaction_id: i32,
+    recurring_instance_id: i32,
+    name: &'a str,
+    due_date: NaiveDate,
+    expected_amount: Decimal,
+    currency_code: &'a str,
+}
+
+fn alert_notification(
+    kind: PaymentAlertKind,
+    rule: &recurring_transaction::Model,
+    instance: &recurring_transaction_instance::Model,
+    currency_code: &str,
+    today: NaiveDate,
+) -> Notification {
+    let amount = format!("{} {}", instance.expected_amount.abs().round_dp(2), currency_code);
+    let (title, message) = match kind {
+        PaymentAlertKind::Upcoming => {
+            let when = match (instance.due_date - today).num_days() {
+                0 => "today".to_string(),
+                1 => "tomorrow".to_string(),
+                days => format!("in {} days", days),
+            };
+            (
+                format!("{} is due {}", rule.name, when),
+                format!("{} of {} is due on {}.", rule.name, amount, instance.due_date),
+            )
+        }
+        PaymentAlertKind::Overdue => (
+            format!("{} is overdue", rule.name),
+            format!(
+                "{} of {} was due on {} and is still unpaid.",
+                rule.name, amount, instance.due_date
+            ),
+        ),
+    };
+    let data = PaymentAlertData {
+        kind: match kind {
+            PaymentAlertKind::Upcoming => "Upcoming",
+            PaymentAlertKind::Overdue => "Overdue",
+        },
+        recurring_transaction_id: rule.id,
+        recurring_instance_id: instance.id,
+        name: &rule.name,
+        due_date: instance.due_date,
+        expected_amount: instance.expected_amount,
+        currency_code,
+    };
+    Notification {
+        title,
+        message,
+        data: serde_json::to_value(data).unwrap_or_default(),
+    }
+}
+
+/// Sends the alerts due as of `today` for recurring transactions with alert settings.
+///
+/// A pending instance gets an upcoming alert once it is due within `days_before` days
+/// and an overdue alert once `today` is past its due date, unless it is overdue by more
+/// than 30 days already. Each instance is alerted about at most once per kind; alerts
+/// none of the channels accepted are retried on the next run. Simulated and unapproved
+/// recurring transactions get none.
+///
+/// Alerts only go to household channels and to channels of the users who own the
+/// recurring transaction's accounts or have them shared.
+pub async fn send_payment_alerts(
+    db: &DatabaseConnection,
+    notifier: &Notifier,
+    today: NaiveDate,
+) -> Result<PaymentAlertSummary, DbErr> {
+    let mut summary = PaymentAlertSummary {
+        date: today,
+        upcoming_sent: 0,
+        overdue_sent: 0,
+        failed: 0,
+    };
+
+    let channels = notification_channel::Entity::find()
+        .filter(notification_channel::Column::Enabled.eq(true))
+        .all(db)
+        .await?;
+    if channels.is_empty() {
+        debug!("No enabled notification channels, skipping payment alerts");
+        return Ok(summary);
+    }
+
+    let settings = recurring_alert::Entity::find()
+        .find_also_related(recurring_transaction::Entity)
+        .all(db)
+        .await?;
+    let accounts: HashMap<i32, account::Model> = account::Entity::find()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|account| (account.id, account))
+        .collect();
+    let mut grantees: HashMap<i32, Vec<i32>> = HashMap::new();
+    for share in account_share::Entity::find().all(db).await? {
+        grantees.entry(share.account_id).or_default().push(share.user_id);
+    }
+    // Users who may see an account: its owner and everyone it is shared with
+    let audience = |account_id: i32| {
+        accounts
+            .get(&account_id)
+            .map(|account| account.owner_id)
+            .into_iter()
+            .chain(grantees.get(&account_id).into_iter().flatten().copied())
+    };
+
+    for (setting, rule) in settings {
+        let Some(rule) = rule else { continue };
+        if rule.is_simulated || rule.approval_status != recurring_transaction::ApprovalStatus::Approved {
+            continue;
+        }
+        if setting.days_before.is_none() && !setting.notify_overdue {
+            continue;
+        }
+        let recipients: HashSet<i32> = audience(rule.target_account_id)
+            .chain(rule.source_account_id.into_iter().flat_map(&audience))
+            .collect();
+        let targets: Vec<&notification_channel::Model> = channels
+            .iter()
+            .filter(|channel| setting.channel_id.is_none_or(|id| id == channel.id))
+            .filter(|channel| channel.user_id.is_none_or(|user_id| recipients.contains(&user_id)))
+            .collect();
+        if targets.is_empty() {
+            debug!("No enabled channel may receive alerts of recurring transaction {}", rule.id);
+            continue;
+        }
+
+        let instances = recurring_transaction_instance::Entity::find()
+            .filter(recurring_transaction_instance::Column::RecurringTransactionId.eq(rule.id))
+            .filter(recurring_transaction_instance::Column::Status.eq(InstanceStatus::Pending))
+            .filter(recurring_transaction_instance::Column::DueDate.between(
+                today - Days::days(OVERDUE_ALERT_WINDOW_DAYS),
+                today + Days::days(setting.days_before.unwrap_or(0).max(0).into()),
+            ))
+            .all(db)
+            .await?;
+        if instances.is_empty() {
+            continue;
+        }
+        let sent: HashSet<(i32, PaymentAlertKind)> = payment_alert::Entity::find()
+            .filter(
+                payment_alert::Column::RecurringTransactionInstanceId
+                    .is_in(instances.iter().map(|instance| instance.id)),
+            )
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|alert| (alert.recurring_transaction_instance_id, alert.kind))
+            .collect();
+
+        for instance in &instances {
+            let kind = if instance.due_date < today {
+                if !setting.notify_overdue {
+                    continue;
+                }
+                PaymentAlertKind::Overdue
+            } else if setting.days_before.is_some() {
+                PaymentAlertKind::Upcoming
+            } else {
+                continue;
+            };
+            if sent.contains(&(instance.id, kind)) {
+                continue;
+            }
+
+            let currency_code = accounts
+                .get(&rule.target_account_id)
+                .map(|account| account.currency_code.as_str())
+                .unwrap_or("");
+            let notification = alert_notification(kind, &rule, instance, currency_code, today);
+            let mut delivered = false;
+            for channel in &targets {
+                match notifier.send(channel, &notification).await {
+                    Ok(()) => delivered = true,
+                    Err(e) => warn!(
+                        "Failed to alert about recurring instance {} through channel {}: {}",
+                        instance.id, channel.id, e
+                    ),
+                }
+            }
+            if !delivered {
+                summary.failed += 1;
+                continue;
+            }
+
+            payment_alert::ActiveModel {
+                recurring_transaction_instance_id: Set(instance.id),
+                kind: Set(kind),
+                sent_at: Set(Utc::now().naive_utc()),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+            match kind {
+                PaymentAlertKind::Upcoming => summary.upcoming_sent += 1,
+                PaymentAlertKind::Overdue => summary.overdue_sent += 1,
+            }
+            debug!("Sent {:?} alert for recurring instance {}", kind, instance.id);
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Sends payment alerts now and then every `every`, until the runtime shuts down.
+///
+/// "Today" is the date in the server's `timezone`. Failures are logged and retried on
+/// the next tick.
+pub fn spawn_payment_alerts(
+    db: DatabaseConnection,
+    notifier: Notifier,
+    timezone: Tz,
+    every: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let today = compute::today_in(&timezone);
+            match send_payment_alerts(&db, &notifier, today).await {
+                Ok(summary) => info!(
+                    "Sent {} upcoming and {} overdue payment alerts, {} failed",
+                    summary.upcoming_sent, summary.overdue_sent, summary.failed
+                ),
+                Err(e) => warn!("Failed to send payment alerts for {}: {}", today, e),
+            }
+        }
+    })
+}
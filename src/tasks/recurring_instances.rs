@@ -0,0 +1,138 @@
+use chrono::{Duration as Days, NaiveDate};
+use chrono_tz::Tz;
+use compute::account::utils::{load_holiday_calendar, recurring_transaction_occurrences};
+use compute::recurring_amounts::estimated_amount;
+use model::entities::{recurring_transaction, recurring_transaction_instance};
+use sea_orm::sea_query::Expr;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, Set};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, info, warn};
+use utoipa::ToSchema;
+
+/// Days ahead instances are generated for when not configured.
+pub const DEFAULT_DAYS_AHEAD: u32 = 30;
+
+/// Outcome of one instance generation run.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct InstanceGenerationSummary {
+    pub date: NaiveDate,
+    pub days_ahead: u32,
+    /// Pending instances created for upcoming due dates
+    pub created_count: usize,
+    /// Pending instances newly marked overdue
+    pub overdue_count: u64,
+}
+
+/// Creates the pending instances of approved recurring transactions due from `today`
+/// through `days_ahead` days later, and marks pending instances due before `today` overdue.
+///
+/// Due dates that already have an instance, in any status, are left alone, so running
+/// this repeatedly only fills the gaps. Simulated recurring transactions get none.
+pub async fn generate_recurring_instances(
+    db: &DatabaseConnection,
+    today: NaiveDate,
+    days_ahead: u32,
+) -> Result<InstanceGenerationSummary, DbErr> {
+    let first_day = today;
+    let last_day = today + Days::days(days_ahead.into());
+
+    let rules = recurring_transaction::Entity::find()
+        .filter(recurring_transaction::Column::IsSimulated.eq(false))
+        .filter(recurring_transaction::Column::ApprovalStatus.eq(recurring_transaction::ApprovalStatus::Approved))
+        .filter(recurring_transaction::Column::StartDate.lte(last_day))
+        .all(db)
+        .await?;
+    let holidays = load_holiday_calendar(db).await?;
+
+    let mut created_count = 0;
+    for rule in rules {
+        let due_dates = recurring_transaction_occurrences(&rule, &holidays, first_day, last_day);
+        if due_dates.is_empty() {
+            continue;
+        }
+
+        let existing: HashSet<NaiveDate> = recurring_transaction_instance::Entity::find()
+            .filter(recurring_transaction_instance::Column::RecurringTransactionId.eq(rule.id))
+            .filter(recurring_transaction_instance::Column::DueDate.between(first_day, last_day))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|instance| instance.due_date)
+            .collect();
+
+        let expected_amount = if rule.amount_strategy == recurring_transaction::AmountStrategy::Fixed {
+            rule.amount
+        } else {
+            let paid = recurring_transaction_instance::Entity::find()
+                .filter(recurring_transaction_instance::Column::RecurringTransactionId.eq(rule.id))
+                .filter(recurring_transaction_instance::Column::Status.eq(recurring_transaction_instance::InstanceStatus::Paid))
+                .all(db)
+                .await?;
+            estimated_amount(&rule, &paid)
+        };
+
+        for due_date in due_dates.into_iter().filter(|date| !existing.contains(date)) {
+            recurring_transaction_instance::ActiveModel {
+                recurring_transaction_id: Set(rule.id),
+                status: Set(recurring_transaction_instance::InstanceStatus::Pending),
+                due_date: Set(due_date),
+                expected_amount: Set(expected_amount),
+                paid_date: Set(None),
+                paid_amount: Set(None),
+                reconciled_imported_transaction_id: Set(None),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+            created_count += 1;
+        }
+        debug!("Generated instances of recurring transaction {} up to {}", rule.id, last_day);
+    }
+
+    let overdue_count = recurring_transaction_instance::Entity::update_many()
+        .col_expr(recurring_transaction_instance::Column::Overdue, Expr::value(true))
+        .filter(recurring_transaction_instance::Column::Status.eq(recurring_transaction_instance::InstanceStatus::Pending))
+        .filter(recurring_transaction_instance::Column::DueDate.lt(today))
+        .filter(recurring_transaction_instance::Column::Overdue.eq(false))
+        .exec(db)
+        .await?
+        .rows_affected;
+
+    Ok(InstanceGenerationSummary {
+        date: today,
+        days_ahead,
+        created_count,
+        overdue_count,
+    })
+}
+
+/// Generates recurring instances now and then every `every`, until the runtime shuts down.
+///
+/// "Today" is the date in the server's `timezone`. Failures are logged and retried on
+/// the next tick.
+pub fn spawn_instance_generation(
+    db: DatabaseConnection,
+    timezone: Tz,
+    days_ahead: u32,
+    every: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let today = compute::today_in(&timezone);
+            match generate_recurring_instances(&db, today, days_ahead).await {
+                Ok(summary) => info!(
+                    "Generated {} recurring instances up to {} days ahead, marked {} overdue",
+                    summary.created_count, days_ahead, summary.overdue_count
+                ),
+                Err(e) => warn!("Failed to generate recurring instances for {}: {}", today, e),
+            }
+        }
+    })
+}
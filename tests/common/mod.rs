@@ -3,12 +3,44 @@ use finrust::schemas::AppState;
 use axum::Router;
 use migration::{Migrator, MigratorTrait};
 use moka::future::Cache;
-use sea_orm::{ActiveModelTrait, Database, DatabaseConnection, Set};
+use sea_orm::{ActiveModelTrait, ConnectionTrait, Database, DatabaseConnection, Set};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use testcontainers_modules::postgres::Postgres;
+use testcontainers_modules::testcontainers::{runners::SyncRunner, Container};
 use tracing::Level;
 use tracing_subscriber::FmtSubscriber;
 
-/// Create an in-memory SQLite database for testing
+/// Database backend the integration suite runs against.
+///
+/// Selected with the `TEST_DATABASE` environment variable (`sqlite`, the default,
+/// or `postgres`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestBackend {
+    Sqlite,
+    Postgres,
+}
+
+impl TestBackend {
+    pub fn from_env() -> Self {
+        match std::env::var("TEST_DATABASE").map(|value| value.to_lowercase()).as_deref() {
+            Ok("postgres") | Ok("postgresql") => TestBackend::Postgres,
+            Ok("sqlite") | Err(_) => TestBackend::Sqlite,
+            Ok(other) => panic!("Unsupported TEST_DATABASE '{}', expected 'sqlite' or 'postgres'", other),
+        }
+    }
+}
+
+/// Create a migrated database for testing on the backend selected by `TEST_DATABASE`
 pub async fn setup_test_db() -> DatabaseConnection {
+    match TestBackend::from_env() {
+        TestBackend::Sqlite => setup_sqlite_test_db().await,
+        TestBackend::Postgres => setup_postgres_test_db().await,
+    }
+}
+
+/// Create an in-memory SQLite database for testing
+pub async fn setup_sqlite_test_db() -> DatabaseConnection {
     let db = Database::connect("sqlite::memory:")
         .await
         .expect("Failed to connect to in-memory database");
@@ -21,6 +53,59 @@ pub async fn setup_test_db() -> DatabaseConnection {
     db
 }
 
+static POSTGRES_CONTAINER: OnceLock<Container<Postgres>> = OnceLock::new();
+static POSTGRES_DATABASES: AtomicUsize = AtomicUsize::new(0);
+
+/// URL of the Postgres server used for tests, without a database name.
+///
+/// Uses `TEST_POSTGRES_URL` when set (e.g. a CI service), otherwise starts one
+/// container shared by all tests of the binary.
+fn postgres_server_url() -> String {
+    if let Ok(url) = std::env::var("TEST_POSTGRES_URL") {
+        return url.trim_end_matches('/').to_string();
+    }
+
+    let container = POSTGRES_CONTAINER.get_or_init(|| {
+        // The blocking runner can't be used from inside the test's async runtime
+        std::thread::spawn(|| Postgres::default().start().expect("Failed to start Postgres container"))
+            .join()
+            .expect("Postgres container startup panicked")
+    });
+    let host = container.get_host().expect("Failed to get Postgres container host");
+    let port = container
+        .get_host_port_ipv4(5432)
+        .expect("Failed to get Postgres container port");
+    format!("postgres://postgres:postgres@{}:{}", host, port)
+}
+
+/// Create a fresh, migrated Postgres database for a single test
+pub async fn setup_postgres_test_db() -> DatabaseConnection {
+    let server_url = postgres_server_url();
+    let database = format!(
+        "finrust_test_{}_{}",
+        std::process::id(),
+        POSTGRES_DATABASES.fetch_add(1, Ordering::SeqCst)
+    );
+
+    let admin = Database::connect(format!("{}/postgres", server_url))
+        .await
+        .expect("Failed to connect to Postgres");
+    admin
+        .execute_unprepared(&format!("CREATE DATABASE \"{}\"", database))
+        .await
+        .expect("Failed to create test database");
+    admin.close().await.expect("Failed to close Postgres admin connection");
+
+    let db = Database::connect(format!("{}/{}", server_url, database))
+        .await
+        .expect("Failed to connect to test database");
+    Migrator::up(&db, None)
+        .await
+        .expect("Failed to run migrations");
+
+    db
+}
+
 /// Create AppState for testing
 pub async fn setup_test_app_state() -> AppState {
     let db = setup_test_db().await;
@@ -41,7 +126,7 @@ pub async fn setup_test_app_state() -> AppState {
 
     let cache = Cache::new(100);
 
-    AppState { db, cache }
+    AppState { db, cache, double_entry_strict: false, audit_chain: false, fx_provider: None, field_cipher: None, auth_required: false, timezone: chrono_tz::Tz::UTC, smtp: None, restored_books: finrust::helpers::time_travel::restored_books_cache() }
 }
 
 /// Initialize tracing for tests with output to STDERR.
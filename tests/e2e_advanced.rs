@@ -36,6 +36,7 @@ async fn test_e2e_advanced_scenario() {
             target_amount: None,
             color: None,
             is_liquid: None,
+            balance_interpolation: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -56,6 +57,7 @@ async fn test_e2e_advanced_scenario() {
             target_amount: None,
             color: None,
             is_liquid: None,
+            balance_interpolation: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -76,6 +78,7 @@ async fn test_e2e_advanced_scenario() {
             target_amount: None,
             color: None,
             is_liquid: None,
+            balance_interpolation: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -96,6 +99,7 @@ async fn test_e2e_advanced_scenario() {
             target_amount: None,
             color: None,
             is_liquid: None,
+            balance_interpolation: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -116,6 +120,7 @@ async fn test_e2e_advanced_scenario() {
             target_amount: Some(Decimal::new(50000, 0)),
             color: None,
             is_liquid: None,
+            balance_interpolation: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -173,6 +178,16 @@ async fn test_e2e_advanced_scenario() {
             category_id: None,
             scenario_id: None,
             is_simulated: None,
+            created_by_user_id: None,
+            notice_period_days: None,
+            renewal_date: None,
+            fx_mode: None,
+            fx_rate: None,
+            source_amount: None,
+            amount_strategy: None,
+            amount_history_count: None,
+            rrule: None,
+            date_adjustment: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -196,6 +211,16 @@ async fn test_e2e_advanced_scenario() {
             category_id: None,
             scenario_id: None,
             is_simulated: None,
+            created_by_user_id: None,
+            notice_period_days: None,
+            renewal_date: None,
+            fx_mode: None,
+            fx_rate: None,
+            source_amount: None,
+            amount_strategy: None,
+            amount_history_count: None,
+            rrule: None,
+            date_adjustment: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -219,6 +244,16 @@ async fn test_e2e_advanced_scenario() {
             category_id: None,
             scenario_id: None,
             is_simulated: None,
+            created_by_user_id: None,
+            notice_period_days: None,
+            renewal_date: None,
+            fx_mode: None,
+            fx_rate: None,
+            source_amount: None,
+            amount_strategy: None,
+            amount_history_count: None,
+            rrule: None,
+            date_adjustment: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -241,6 +276,16 @@ async fn test_e2e_advanced_scenario() {
             category_id: None,
             scenario_id: None,
             is_simulated: None,
+            created_by_user_id: None,
+            notice_period_days: None,
+            renewal_date: None,
+            fx_mode: None,
+            fx_rate: None,
+            source_amount: None,
+            amount_strategy: None,
+            amount_history_count: None,
+            rrule: None,
+            date_adjustment: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -343,6 +388,7 @@ async fn test_e2e_advanced_scenario() {
             import_hash: "hash_001".to_string(),
             raw_data: None,
             category_id: None,
+            amount_locale: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -360,6 +406,7 @@ async fn test_e2e_advanced_scenario() {
             import_hash: "hash_002".to_string(),
             raw_data: None,
             category_id: None,
+            amount_locale: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -374,6 +421,7 @@ async fn test_e2e_advanced_scenario() {
             import_hash: "hash_003".to_string(),
             raw_data: None,
             category_id: None,
+            amount_locale: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -417,6 +465,16 @@ async fn test_e2e_advanced_scenario() {
             category_id: None,
             scenario_id: None,
             is_simulated: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -459,6 +517,7 @@ async fn test_e2e_advanced_scenario() {
         .json(&CreateScenarioRequest {
             name: "Buy new car".to_string(),
             description: None,
+            activation_date: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -484,6 +543,16 @@ async fn test_e2e_advanced_scenario() {
             category_id: None,
             scenario_id: Some(scenario_id),
             is_simulated: Some(true),
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -528,6 +597,16 @@ async fn test_e2e_advanced_scenario() {
             category_id: None,
             scenario_id: None,
             is_simulated: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -548,6 +627,16 @@ async fn test_e2e_advanced_scenario() {
             category_id: None,
             scenario_id: None,
             is_simulated: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -568,6 +657,16 @@ async fn test_e2e_advanced_scenario() {
             category_id: None,
             scenario_id: None,
             is_simulated: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -743,6 +842,7 @@ async fn test_include_ignored_accounts_in_dashboard() {
             target_amount: None,
             color: None,
             is_liquid: Some(true),
+            balance_interpolation: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -763,6 +863,7 @@ async fn test_include_ignored_accounts_in_dashboard() {
             target_amount: None,
             color: None,
             is_liquid: Some(false),
+            balance_interpolation: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -783,6 +884,7 @@ async fn test_include_ignored_accounts_in_dashboard() {
             target_amount: None,
             color: None,
             is_liquid: Some(false),
+            balance_interpolation: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -809,6 +911,16 @@ async fn test_include_ignored_accounts_in_dashboard() {
             category_id: None,
             scenario_id: None,
             is_simulated: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -829,6 +941,16 @@ async fn test_include_ignored_accounts_in_dashboard() {
             category_id: None,
             scenario_id: None,
             is_simulated: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -849,6 +971,16 @@ async fn test_include_ignored_accounts_in_dashboard() {
             category_id: None,
             scenario_id: None,
             is_simulated: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
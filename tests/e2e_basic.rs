@@ -47,6 +47,7 @@ async fn test_e2e_basic_scenario() {
             target_amount: None,
             color: None,
             is_liquid: None,
+            balance_interpolation: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -67,6 +68,7 @@ async fn test_e2e_basic_scenario() {
             target_amount: None,
             color: None,
             is_liquid: None,
+            balance_interpolation: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -114,6 +116,16 @@ async fn test_e2e_basic_scenario() {
             category_id: None,
             scenario_id: None,
             is_simulated: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -134,6 +146,16 @@ async fn test_e2e_basic_scenario() {
             category_id: Some(groceries_id),
             scenario_id: None,
             is_simulated: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -154,6 +176,16 @@ async fn test_e2e_basic_scenario() {
             category_id: None,
             scenario_id: None,
             is_simulated: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -174,6 +206,16 @@ async fn test_e2e_basic_scenario() {
             category_id: None,
             scenario_id: None,
             is_simulated: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -44,6 +44,7 @@ async fn test_e2e_intermediate_scenario() {
             target_amount: None,
             color: None,
             is_liquid: None,
+            balance_interpolation: None,
         };
         let resp = server.post("/api/v1/accounts").json(&req).await;
         resp.assert_status(StatusCode::CREATED);
@@ -153,6 +154,16 @@ async fn test_e2e_intermediate_scenario() {
         category_id: Some(rent_id),
         scenario_id: None,
         is_simulated: Some(false),
+        created_by_user_id: None,
+        notice_period_days: None,
+        renewal_date: None,
+        fx_mode: None,
+        fx_rate: None,
+        source_amount: None,
+        amount_strategy: None,
+        amount_history_count: None,
+        rrule: None,
+        date_adjustment: None,
     };
     let resp = server
         .post("/api/v1/recurring-transactions")
@@ -177,6 +188,16 @@ async fn test_e2e_intermediate_scenario() {
         category_id: Some(utilities_id),
         scenario_id: None,
         is_simulated: Some(false),
+        created_by_user_id: None,
+        notice_period_days: None,
+        renewal_date: None,
+        fx_mode: None,
+        fx_rate: None,
+        source_amount: None,
+        amount_strategy: None,
+        amount_history_count: None,
+        rrule: None,
+        date_adjustment: None,
     };
     let resp = server
         .post("/api/v1/recurring-transactions")
@@ -201,6 +222,16 @@ async fn test_e2e_intermediate_scenario() {
         category_id: None,
         scenario_id: None,
         is_simulated: Some(false),
+        created_by_user_id: None,
+        notice_period_days: None,
+        renewal_date: None,
+        fx_mode: None,
+        fx_rate: None,
+        source_amount: None,
+        amount_strategy: None,
+        amount_history_count: None,
+        rrule: None,
+        date_adjustment: None,
     };
     let resp = server
         .post("/api/v1/recurring-transactions")
@@ -243,6 +274,8 @@ async fn test_e2e_intermediate_scenario() {
                 expected_amount: None,
                 paid_date: Some(date),
                 paid_amount: Some(expected_amount),
+                notes: None,
+                disputed: None,
             };
             let resp = server
                 .put(&format!("/api/v1/recurring-instances/{}", instance_id))
@@ -292,6 +325,16 @@ async fn test_e2e_intermediate_scenario() {
             category_id: *cat,
             scenario_id: None,
             is_simulated: Some(false),
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
         };
         let resp = server.post("/api/v1/transactions").json(&req).await;
         resp.assert_status(StatusCode::CREATED);
@@ -45,6 +45,7 @@ async fn create_account(
             target_amount: target,
             color: None,
             is_liquid: Some(liquid),
+            balance_interpolation: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -76,6 +77,16 @@ async fn create_txn(
             category_id: None,
             scenario_id: None,
             is_simulated: Some(false),
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -534,6 +545,16 @@ async fn test_snapshot_dashboard_burn_rates() {
             category_id: None,
             scenario_id: None,
             is_simulated: Some(false),
+            created_by_user_id: None,
+            notice_period_days: None,
+            renewal_date: None,
+            fx_mode: None,
+            fx_rate: None,
+            source_amount: None,
+            amount_strategy: None,
+            amount_history_count: None,
+            rrule: None,
+            date_adjustment: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -555,6 +576,16 @@ async fn test_snapshot_dashboard_burn_rates() {
             category_id: None,
             scenario_id: None,
             is_simulated: Some(false),
+            created_by_user_id: None,
+            notice_period_days: None,
+            renewal_date: None,
+            fx_mode: None,
+            fx_rate: None,
+            source_amount: None,
+            amount_strategy: None,
+            amount_history_count: None,
+            rrule: None,
+            date_adjustment: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
@@ -576,6 +607,16 @@ async fn test_snapshot_dashboard_burn_rates() {
             category_id: None,
             scenario_id: None,
             is_simulated: Some(false),
+            created_by_user_id: None,
+            notice_period_days: None,
+            renewal_date: None,
+            fx_mode: None,
+            fx_rate: None,
+            source_amount: None,
+            amount_strategy: None,
+            amount_history_count: None,
+            rrule: None,
+            date_adjustment: None,
         })
         .await;
     resp.assert_status(StatusCode::CREATED);
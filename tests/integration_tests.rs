@@ -1,6 +1,6 @@
 mod common;
 
-use finrust::handlers::accounts::{CreateAccountRequest, UpdateAccountRequest};
+use finrust::handlers::accounts::{AccountStatisticsExclusion, CreateAccountRequest, UpdateAccountRequest};
 use finrust::handlers::transactions::CreateTransactionRequest;
 use finrust::handlers::users::{CreateUserRequest, UpdateUserRequest};
 use finrust::schemas::{ApiResponse, TimeseriesQuery};
@@ -290,6 +290,7 @@ async fn test_create_account() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     // Send POST request to create account
@@ -337,6 +338,7 @@ async fn test_get_accounts() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let create_response = server
@@ -380,6 +382,7 @@ async fn test_create_account_with_invalid_owner_id() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     // Send POST request to create account
@@ -421,6 +424,16 @@ async fn test_create_transaction_with_invalid_target_account_id() {
         category_id: None,
         is_simulated: Some(false),
         scenario_id: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
     };
 
     // Send POST request to create transaction
@@ -460,6 +473,7 @@ async fn test_create_transaction_with_invalid_source_account_id() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -484,6 +498,16 @@ async fn test_create_transaction_with_invalid_source_account_id() {
         category_id: None,
         is_simulated: Some(false),
         scenario_id: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
     };
 
     // Send POST request to create transaction
@@ -566,6 +590,7 @@ async fn test_get_account_by_id() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let create_response = server
@@ -615,6 +640,7 @@ async fn test_update_account() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let create_response = server
@@ -637,6 +663,8 @@ async fn test_update_account() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
+        statistics_exclusions: None,
     };
 
     let response = server
@@ -691,6 +719,7 @@ async fn test_delete_account() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let create_response = server
@@ -751,6 +780,8 @@ async fn test_update_nonexistent_account() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
+        statistics_exclusions: None,
     };
 
     let response = server
@@ -797,6 +828,7 @@ async fn test_complex_timeseries_api_scenario() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account2_request = CreateAccountRequest {
@@ -810,6 +842,7 @@ async fn test_complex_timeseries_api_scenario() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     // Create accounts
@@ -844,6 +877,16 @@ async fn test_complex_timeseries_api_scenario() {
         category_id: None,
         is_simulated: Some(false),
         scenario_id: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
     };
 
     // Account 2: 100,000 on 2025-01-01
@@ -860,6 +903,16 @@ async fn test_complex_timeseries_api_scenario() {
         category_id: None,
         is_simulated: Some(false),
         scenario_id: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
     };
 
     // Create initial balance transactions
@@ -889,6 +942,16 @@ async fn test_complex_timeseries_api_scenario() {
         category_id: None,
         is_simulated: Some(false),
         scenario_id: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
     };
 
     let update1_response = server
@@ -919,6 +982,16 @@ async fn test_complex_timeseries_api_scenario() {
             category_id: None,
             is_simulated: Some(false),
             scenario_id: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
         };
 
         let tx_response = server
@@ -942,6 +1015,16 @@ async fn test_complex_timeseries_api_scenario() {
         category_id: None,
         is_simulated: Some(false),
         scenario_id: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
     };
 
     let jan_response = server
@@ -964,6 +1047,16 @@ async fn test_complex_timeseries_api_scenario() {
         category_id: None,
         is_simulated: Some(false),
         scenario_id: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
     };
 
     let acc2_response = server
@@ -986,6 +1079,16 @@ async fn test_complex_timeseries_api_scenario() {
         category_id: None,
         is_simulated: Some(false),
         scenario_id: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
     };
 
     let transfer_response = server
@@ -1274,8 +1377,7 @@ async fn test_get_missing_instances() {
     use finrust::handlers::transactions::MissingInstanceInfo;
     use chrono::Datelike;
     use finrust::schemas::ApiResponse;
-    use model::entities::{account, recurring_transaction, recurring_transaction_instance};
-    use sea_orm::{ActiveModelTrait, Set};
+    use test_factories::{AccountFactory, RecurringInstanceFactory, RecurringTransactionFactory};
 
     // Setup test server and state
     let app_state = setup_test_app_state().await;
@@ -1283,65 +1385,40 @@ async fn test_get_missing_instances() {
     let server = TestServer::new(app).unwrap();
 
     // Create test account
-    let test_account = account::ActiveModel {
-        name: Set("Test Account for Missing Instances".to_string()),
-        description: Set(Some("Account for testing missing instances".to_string())),
-        currency_code: Set("USD".to_string()),
-        owner_id: Set(1),
-        include_in_statistics: Set(true),
-        ledger_name: Set(Some("test_missing_ledger".to_string())),
-        account_kind: Set(model::entities::account::AccountKind::RealAccount),
-        ..Default::default()
-    };
-    let account = test_account.insert(&app_state.db).await.expect("Failed to create account");
+    let account = AccountFactory::new()
+        .name("Test Account for Missing Instances")
+        .owner(1)
+        .ledger_name("test_missing_ledger")
+        .create(&app_state.db)
+        .await
+        .expect("Failed to create account");
 
     // Create a recurring transaction that started 3 months ago (monthly)
     let today = chrono::Local::now().date_naive();
     let three_months_ago = (today - chrono::Duration::days(90)).with_day(1).unwrap();
-    let recurring_tx = recurring_transaction::ActiveModel {
-        name: Set("Monthly Rent".to_string()),
-        description: Set(Some("Monthly rent payment".to_string())),
-        amount: Set(rust_decimal::Decimal::new(-1500, 0)),
-        start_date: Set(three_months_ago),
-        end_date: Set(None),
-        period: Set(recurring_transaction::RecurrencePeriod::Monthly),
-        include_in_statistics: Set(true),
-        target_account_id: Set(account.id),
-        source_account_id: Set(None),
-        ledger_name: Set(Some("test_missing_ledger".to_string())),
-        ..Default::default()
-    };
-    let recurring_transaction = recurring_tx.insert(&app_state.db).await.expect("Failed to create recurring transaction");
+    let recurring_transaction = RecurringTransactionFactory::new(&account)
+        .name("Monthly Rent")
+        .amount(-1500)
+        .start_date(three_months_ago)
+        .ledger_name("test_missing_ledger")
+        .create(&app_state.db)
+        .await
+        .expect("Failed to create recurring transaction");
 
     // Create one paid instance (2 months ago)
-    let (y, m) = (three_months_ago.year(), three_months_ago.month());
-    let (next_y, next_m) = if m == 12 { (y + 1, 1) } else { (y, m + 1) };
-    let two_months_ago = chrono::NaiveDate::from_ymd_opt(next_y, next_m, 1).unwrap();
-    let paid_instance = recurring_transaction_instance::ActiveModel {
-        recurring_transaction_id: Set(recurring_transaction.id),
-        status: Set(recurring_transaction_instance::InstanceStatus::Paid),
-        due_date: Set(two_months_ago),
-        expected_amount: Set(rust_decimal::Decimal::new(-1500, 0)),
-        paid_date: Set(Some(two_months_ago)),
-        paid_amount: Set(Some(rust_decimal::Decimal::new(-1500, 0))),
-        ..Default::default()
-    };
-    paid_instance.insert(&app_state.db).await.expect("Failed to create paid instance");
+    let two_months_ago = three_months_ago.checked_add_months(chrono::Months::new(1)).unwrap();
+    RecurringInstanceFactory::new(&recurring_transaction, two_months_ago)
+        .paid()
+        .create(&app_state.db)
+        .await
+        .expect("Failed to create paid instance");
 
     // Create one pending instance (1 month ago)
-    let (y, m) = (two_months_ago.year(), two_months_ago.month());
-    let (next_y, next_m) = if m == 12 { (y + 1, 1) } else { (y, m + 1) };
-    let one_month_ago = chrono::NaiveDate::from_ymd_opt(next_y, next_m, 1).unwrap();
-    let pending_instance = recurring_transaction_instance::ActiveModel {
-        recurring_transaction_id: Set(recurring_transaction.id),
-        status: Set(recurring_transaction_instance::InstanceStatus::Pending),
-        due_date: Set(one_month_ago),
-        expected_amount: Set(rust_decimal::Decimal::new(-1500, 0)),
-        paid_date: Set(None),
-        paid_amount: Set(None),
-        ..Default::default()
-    };
-    let pending = pending_instance.insert(&app_state.db).await.expect("Failed to create pending instance");
+    let one_month_ago = two_months_ago.checked_add_months(chrono::Months::new(1)).unwrap();
+    let pending = RecurringInstanceFactory::new(&recurring_transaction, one_month_ago)
+        .create(&app_state.db)
+        .await
+        .expect("Failed to create pending instance");
 
     // No instance for current month (today) - this should be missing
 
@@ -1372,6 +1449,30 @@ async fn test_get_missing_instances() {
     assert!(!missing_info.is_pending, "Should be marked as not pending");
     assert_eq!(missing_info.instance_id, None, "Should not have instance ID");
 
+    // Severity escalates with the days overdue
+    assert_eq!(pending_info.overdue_days, (today - one_month_ago).num_days());
+    let expected_severity = match pending_info.overdue_days {
+        0..=2 => "Due",
+        3..=6 => "Notice",
+        7..=29 => "Warning",
+        _ => "Critical",
+    };
+    assert_eq!(pending_info.severity, expected_severity);
+
+    let response = server
+        .get("/api/v1/recurring-transactions/missing-instances")
+        .add_query_param("overdue_thresholds", "1,2,3")
+        .await;
+    let response_body: ApiResponse<Vec<MissingInstanceInfo>> = response.json();
+    let pending_info = response_body.data.iter().find(|i| i.due_date == one_month_ago).unwrap();
+    assert_eq!(pending_info.severity, "Critical");
+
+    server
+        .get("/api/v1/recurring-transactions/missing-instances")
+        .add_query_param("overdue_thresholds", "30,7")
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+
     println!("✓ Test passed: get_missing_instances correctly identifies pending and missing instances");
 }
 
@@ -1790,6 +1891,7 @@ async fn test_create_manual_account_state() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -1877,6 +1979,7 @@ async fn test_get_manual_account_states() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -1950,6 +2053,7 @@ async fn test_get_manual_account_state_by_id() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -2011,6 +2115,7 @@ async fn test_get_manual_account_state_not_found() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -2055,6 +2160,7 @@ async fn test_update_manual_account_state() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -2126,6 +2232,7 @@ async fn test_update_manual_account_state_not_found() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -2176,6 +2283,7 @@ async fn test_delete_manual_account_state() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -2237,6 +2345,7 @@ async fn test_delete_manual_account_state_not_found() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -2283,6 +2392,7 @@ async fn test_create_imported_transaction() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -2306,6 +2416,7 @@ async fn test_create_imported_transaction() {
             "merchant_id": "12345"
         })),
         category_id: None,
+        amount_locale: None,
     };
 
     let response = server
@@ -2359,6 +2470,7 @@ async fn test_create_imported_transaction_duplicate_hash() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -2378,6 +2490,7 @@ async fn test_create_imported_transaction_duplicate_hash() {
         import_hash: "duplicate_hash_123".to_string(),
         raw_data: None,
         category_id: None,
+        amount_locale: None,
     };
 
     let response1 = server
@@ -2422,6 +2535,7 @@ async fn test_get_imported_transactions() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -2442,6 +2556,7 @@ async fn test_get_imported_transactions() {
             import_hash: format!("hash_{}", i),
             raw_data: None,
             category_id: None,
+            amount_locale: None,
         };
 
         let response = server
@@ -2483,6 +2598,7 @@ async fn test_get_imported_transactions_with_filters() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -2502,6 +2618,7 @@ async fn test_get_imported_transactions_with_filters() {
         import_hash: "filter_test_hash".to_string(),
         raw_data: None,
         category_id: None,
+        amount_locale: None,
     };
 
     let response = server
@@ -2554,6 +2671,7 @@ async fn test_get_account_imported_transactions() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -2574,6 +2692,7 @@ async fn test_get_account_imported_transactions() {
             import_hash: format!("account_hash_{}", i),
             raw_data: None,
             category_id: None,
+            amount_locale: None,
         };
 
         let response = server
@@ -2618,6 +2737,7 @@ async fn test_get_imported_transaction() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -2637,6 +2757,7 @@ async fn test_get_imported_transaction() {
         import_hash: "get_test_hash".to_string(),
         raw_data: Some(serde_json::json!({"test": "data"})),
         category_id: None,
+        amount_locale: None,
     };
 
     let create_response = server
@@ -2710,6 +2831,7 @@ async fn test_update_imported_transaction() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -2729,6 +2851,7 @@ async fn test_update_imported_transaction() {
         import_hash: "update_test_hash".to_string(),
         raw_data: None,
         category_id: None,
+        amount_locale: None,
     };
 
     let create_response = server
@@ -2747,6 +2870,7 @@ async fn test_update_imported_transaction() {
         amount: Some(Decimal::new(-3000, 2)), // -$30.00
         raw_data: Some(serde_json::json!({"updated": "data"})),
         category_id: None,
+        amount_locale: None,
     };
 
     let response = server
@@ -2794,6 +2918,7 @@ async fn test_update_nonexistent_imported_transaction() {
         amount: Some(Decimal::new(-3000, 2)),
         raw_data: None,
         category_id: None,
+        amount_locale: None,
     };
 
     let response = server
@@ -2827,6 +2952,7 @@ async fn test_delete_imported_transaction() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -2846,6 +2972,7 @@ async fn test_delete_imported_transaction() {
         import_hash: "delete_test_hash".to_string(),
         raw_data: None,
         category_id: None,
+        amount_locale: None,
     };
 
     let create_response = server
@@ -2911,6 +3038,7 @@ async fn test_reconcile_imported_transaction() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -2935,6 +3063,16 @@ async fn test_reconcile_imported_transaction() {
         category_id: None,
         is_simulated: Some(false),
         scenario_id: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
     };
 
     let transaction_response = server
@@ -2957,6 +3095,7 @@ async fn test_reconcile_imported_transaction() {
             "category": "Food"
         })),
         category_id: None,
+        amount_locale: None,
     };
 
     let create_response = server
@@ -3027,6 +3166,7 @@ async fn test_reconcile_imported_transaction_invalid_type() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -3046,6 +3186,7 @@ async fn test_reconcile_imported_transaction_invalid_type() {
         import_hash: "invalid_type_test_hash".to_string(),
         raw_data: None,
         category_id: None,
+        amount_locale: None,
     };
 
     let create_response = server
@@ -3094,6 +3235,7 @@ async fn test_clear_imported_transaction_reconciliation() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -3118,6 +3260,16 @@ async fn test_clear_imported_transaction_reconciliation() {
         category_id: None,
         is_simulated: Some(false),
         scenario_id: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
     };
 
     let transaction_response = server
@@ -3137,6 +3289,7 @@ async fn test_clear_imported_transaction_reconciliation() {
         import_hash: "clear_reconcile_test_hash".to_string(),
         raw_data: None,
         category_id: None,
+        amount_locale: None,
     };
 
     let create_response = server
@@ -3235,6 +3388,7 @@ async fn test_imported_transaction_filtering_by_reconciliation_status() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
 
     let account_response = server
@@ -3259,6 +3413,16 @@ async fn test_imported_transaction_filtering_by_reconciliation_status() {
         category_id: None,
         is_simulated: Some(false),
         scenario_id: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
     };
 
     let transaction_response = server
@@ -3278,6 +3442,7 @@ async fn test_imported_transaction_filtering_by_reconciliation_status() {
         import_hash: "filter_reconciled_hash".to_string(),
         raw_data: None,
         category_id: None,
+        amount_locale: None,
     };
 
     let create_response1 = server
@@ -3296,6 +3461,7 @@ async fn test_imported_transaction_filtering_by_reconciliation_status() {
         import_hash: "filter_unreconciled_hash".to_string(),
         raw_data: None,
         category_id: None,
+        amount_locale: None,
     };
 
     let create_response2 = server
@@ -3867,7 +4033,7 @@ async fn test_category_stats_endpoint_exists() {
     let app = setup_test_app().await;
     let server = TestServer::new(app).unwrap();
 
-    // Test that the endpoint exists and returns OK (even if stubbed)
+    // Test that the endpoint exists and returns OK without any transactions
     let response = server
         .get("/api/v1/categories/stats?start_date=2024-01-01&end_date=2024-12-31")
         .await;
@@ -3901,6 +4067,7 @@ async fn test_monthly_min_balance_returns_ok_for_existing_account() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
     let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
     account_resp.assert_status(StatusCode::CREATED);
@@ -3957,6 +4124,7 @@ async fn test_monthly_min_balance_default_months() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
     let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
     account_resp.assert_status(StatusCode::CREATED);
@@ -4003,6 +4171,7 @@ async fn test_monthly_min_balance_with_transactions() {
         target_amount: None,
         color: None,
         is_liquid: None,
+        balance_interpolation: None,
     };
     let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
     account_resp.assert_status(StatusCode::CREATED);
@@ -4022,6 +4191,16 @@ async fn test_monthly_min_balance_with_transactions() {
         category_id: None,
         is_simulated: None,
         scenario_id: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
     };
     let txn_resp = server.post("/api/v1/transactions").json(&txn).await;
     txn_resp.assert_status(StatusCode::CREATED);
@@ -4049,3 +4228,9529 @@ async fn test_monthly_min_balance_with_transactions() {
         assert!(point["min_balance"].is_string() || point["min_balance"].is_number());
     }
 }
+
+#[tokio::test]
+async fn test_reconciliation_session_locks_transactions() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Reconciled Account".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let txn = CreateTransactionRequest {
+        name: "Salary".to_string(),
+        description: None,
+        amount: Decimal::new(150000, 2),
+        date: NaiveDate::from_ymd_opt(2025, 3, 10).unwrap(),
+        include_in_statistics: Some(true),
+        target_account_id: account_id,
+        source_account_id: None,
+        ledger_name: None,
+        linked_import_id: None,
+        category_id: None,
+        is_simulated: None,
+        scenario_id: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
+    };
+    let txn_resp = server.post("/api/v1/transactions").json(&txn).await;
+    txn_resp.assert_status(StatusCode::CREATED);
+    let txn_body: ApiResponse<serde_json::Value> = txn_resp.json();
+    let txn_id = txn_body.data["id"].as_i64().unwrap();
+
+    let session_resp = server
+        .post("/api/v1/reconciliation-sessions")
+        .json(&serde_json::json!({
+            "account_id": account_id,
+            "statement_date": "2025-03-31",
+            "statement_balance": "1500.00"
+        }))
+        .await;
+    session_resp.assert_status(StatusCode::CREATED);
+    let session_body: ApiResponse<serde_json::Value> = session_resp.json();
+    let session_id = session_body.data["id"].as_i64().unwrap();
+
+    // Completing before the statement balances is rejected
+    let early = server
+        .post(&format!("/api/v1/reconciliation-sessions/{}/complete", session_id))
+        .await;
+    early.assert_status(StatusCode::BAD_REQUEST);
+
+    let tick = server
+        .post(&format!(
+            "/api/v1/reconciliation-sessions/{}/transactions/{}",
+            session_id, txn_id
+        ))
+        .await;
+    tick.assert_status(StatusCode::OK);
+    let tick_body: ApiResponse<serde_json::Value> = tick.json();
+    assert_eq!(tick_body.data["difference"].as_str().unwrap().parse::<Decimal>().unwrap(), Decimal::ZERO);
+
+    let complete = server
+        .post(&format!("/api/v1/reconciliation-sessions/{}/complete", session_id))
+        .await;
+    complete.assert_status(StatusCode::OK);
+    let complete_body: ApiResponse<serde_json::Value> = complete.json();
+    assert_eq!(complete_body.data["status"], "Completed");
+
+    // Locked transactions can no longer be edited or deleted
+    let update = server
+        .put(&format!("/api/v1/transactions/{}", txn_id))
+        .json(&serde_json::json!({ "name": "Changed" }))
+        .await;
+    update.assert_status(StatusCode::CONFLICT);
+
+    let delete = server.delete(&format!("/api/v1/transactions/{}", txn_id)).await;
+    delete.assert_status(StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_locked_period_rejects_transaction_mutations() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Period Lock Account".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let txn = CreateTransactionRequest {
+        name: "January groceries".to_string(),
+        description: None,
+        amount: Decimal::new(-4200, 2),
+        date: NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+        include_in_statistics: Some(true),
+        target_account_id: account_id,
+        source_account_id: None,
+        ledger_name: None,
+        linked_import_id: None,
+        category_id: None,
+        is_simulated: None,
+        scenario_id: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
+    };
+    let txn_resp = server.post("/api/v1/transactions").json(&txn).await;
+    txn_resp.assert_status(StatusCode::CREATED);
+    let txn_body: ApiResponse<serde_json::Value> = txn_resp.json();
+    let txn_id = txn_body.data["id"].as_i64().unwrap();
+
+    let lock_resp = server
+        .post("/api/v1/periods/lock")
+        .json(&serde_json::json!({
+            "start_date": "2025-01-01",
+            "end_date": "2025-01-31",
+            "note": "January reported"
+        }))
+        .await;
+    lock_resp.assert_status(StatusCode::CREATED);
+
+    // New transactions and edits inside the period are rejected
+    let create_locked = server.post("/api/v1/transactions").json(&txn).await;
+    create_locked.assert_status(StatusCode::CONFLICT);
+
+    let update_locked = server
+        .put(&format!("/api/v1/transactions/{}", txn_id))
+        .json(&serde_json::json!({ "name": "Changed" }))
+        .await;
+    update_locked.assert_status(StatusCode::CONFLICT);
+
+    let delete_locked = server.delete(&format!("/api/v1/transactions/{}", txn_id)).await;
+    delete_locked.assert_status(StatusCode::CONFLICT);
+
+    // The admin override bypasses the lock
+    let update_override = server
+        .put(&format!("/api/v1/transactions/{}?admin_override=true", txn_id))
+        .json(&serde_json::json!({ "name": "Changed" }))
+        .await;
+    update_override.assert_status(StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_period_lock_override_requires_admin() {
+    use axum::http::{header, HeaderValue};
+    use finrust::helpers::auth::hash_password;
+    use model::entities::user;
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+    let state = setup_test_app_state().await;
+    let existing = user::Entity::find_by_id(1).one(&state.db).await.unwrap().unwrap();
+    let mut active: user::ActiveModel = existing.into();
+    active.password_hash = Set(Some(hash_password("correct horse").unwrap()));
+    let test_user = active.update(&state.db).await.unwrap();
+    let db = state.db.clone();
+    let server = TestServer::new(finrust::router::create_test_router(state)).unwrap();
+
+    let login: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/auth/login")
+        .json(&serde_json::json!({ "username": "test_user1", "password": "correct horse" }))
+        .await
+        .json();
+    let bearer = HeaderValue::from_str(&format!("Bearer {}", login.data["token"].as_str().unwrap())).unwrap();
+
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/accounts")
+        .add_header(header::AUTHORIZATION, bearer.clone())
+        .json(&serde_json::json!({ "name": "Checking", "currency_code": "USD", "owner_id": 1 }))
+        .await
+        .json();
+    let account_id = body.data["id"].as_i64().unwrap();
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/transactions")
+        .add_header(header::AUTHORIZATION, bearer.clone())
+        .json(&serde_json::json!({
+            "name": "January groceries",
+            "amount": "-42",
+            "date": "2025-01-15",
+            "target_account_id": account_id,
+        }))
+        .await
+        .json();
+    let transaction_id = body.data["id"].as_i64().unwrap();
+    let transaction_url = format!("/api/v1/transactions/{}?admin_override=true", transaction_id);
+    server
+        .post("/api/v1/periods/lock")
+        .json(&serde_json::json!({ "start_date": "2025-01-01", "end_date": "2025-01-31" }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    // The owner of the account may not bypass the lock without being an administrator
+    server
+        .put(&transaction_url)
+        .add_header(header::AUTHORIZATION, bearer.clone())
+        .json(&serde_json::json!({ "name": "Changed" }))
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+    let response = server
+        .post("/api/v1/transactions/bulk-delete?admin_override=true")
+        .add_header(header::AUTHORIZATION, bearer.clone())
+        .json(&serde_json::json!({ "transaction_ids": [transaction_id] }))
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "ADMIN_REQUIRED");
+    server
+        .delete(&transaction_url)
+        .add_header(header::AUTHORIZATION, bearer.clone())
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+
+    let mut active: user::ActiveModel = test_user.into();
+    active.is_admin = Set(true);
+    active.update(&db).await.unwrap();
+    server
+        .put(&transaction_url)
+        .add_header(header::AUTHORIZATION, bearer)
+        .json(&serde_json::json!({ "name": "Changed" }))
+        .await
+        .assert_status_ok();
+}
+
+#[tokio::test]
+async fn test_transactions_filter_by_simulated_flag() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Scenario Filter Account".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    for (name, is_simulated) in [("Baseline", false), ("What-if", true)] {
+        let txn = CreateTransactionRequest {
+            name: name.to_string(),
+            description: None,
+            amount: Decimal::new(10000, 2),
+            date: NaiveDate::from_ymd_opt(2025, 5, 1).unwrap(),
+            include_in_statistics: Some(true),
+            target_account_id: account_id,
+            source_account_id: None,
+            ledger_name: None,
+            linked_import_id: None,
+            category_id: None,
+            is_simulated: Some(is_simulated),
+            scenario_id: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
+        };
+        server
+            .post("/api/v1/transactions")
+            .json(&txn)
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+
+    let baseline = server
+        .get(&format!(
+            "/api/v1/transactions?targetAccountId={}&isSimulated=false",
+            account_id
+        ))
+        .await;
+    baseline.assert_status(StatusCode::OK);
+    let body: ApiResponse<Vec<serde_json::Value>> = baseline.json();
+    assert_eq!(body.data.len(), 1);
+    assert_eq!(body.data[0]["name"], "Baseline");
+}
+
+#[tokio::test]
+async fn test_bulk_assign_tag_with_dry_run() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Vacation Account".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    for (name, day) in [("Hotel Rome", 3), ("Hotel Rome", 4), ("Groceries", 20)] {
+        let txn = CreateTransactionRequest {
+            name: name.to_string(),
+            description: None,
+            amount: Decimal::new(-9000, 2),
+            date: NaiveDate::from_ymd_opt(2025, 7, day).unwrap(),
+            include_in_statistics: Some(true),
+            target_account_id: account_id,
+            source_account_id: None,
+            ledger_name: None,
+            linked_import_id: None,
+            category_id: None,
+            is_simulated: None,
+            scenario_id: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
+        };
+        server
+            .post("/api/v1/transactions")
+            .json(&txn)
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+
+    let tag_resp = server
+        .post("/api/v1/tags")
+        .json(&serde_json::json!({ "name": "Vacation 2025" }))
+        .await;
+    tag_resp.assert_status(StatusCode::CREATED);
+    let tag_body: ApiResponse<serde_json::Value> = tag_resp.json();
+    let tag_id = tag_body.data["id"].as_i64().unwrap();
+
+    let filter = serde_json::json!({
+        "account_id": account_id,
+        "name_contains": "Hotel"
+    });
+
+    let dry_run = server
+        .post(&format!("/api/v1/tags/{}/assign", tag_id))
+        .json(&serde_json::json!({ "filter": filter, "dry_run": true }))
+        .await;
+    dry_run.assert_status(StatusCode::OK);
+    let dry_body: ApiResponse<serde_json::Value> = dry_run.json();
+    assert_eq!(dry_body.data["affected"], 2);
+
+    let assign = server
+        .post(&format!("/api/v1/tags/{}/assign", tag_id))
+        .json(&serde_json::json!({ "filter": filter }))
+        .await;
+    assign.assert_status(StatusCode::OK);
+    let assign_body: ApiResponse<serde_json::Value> = assign.json();
+    assert_eq!(assign_body.data["affected"], 2);
+
+    // Re-running is idempotent
+    let again = server
+        .post(&format!("/api/v1/tags/{}/assign", tag_id))
+        .json(&serde_json::json!({ "filter": filter }))
+        .await;
+    let again_body: ApiResponse<serde_json::Value> = again.json();
+    assert_eq!(again_body.data["affected"], 0);
+    assert_eq!(again_body.data["already_tagged"], 2);
+
+    let empty = server
+        .post(&format!("/api/v1/tags/{}/assign", tag_id))
+        .json(&serde_json::json!({}))
+        .await;
+    empty.assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_uncategorized_inbox_and_bulk_categorize() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Inbox Account".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let mut transaction_ids = Vec::new();
+    for day in [1, 2] {
+        let txn = CreateTransactionRequest {
+            name: format!("Coffee {}", day),
+            description: None,
+            amount: Decimal::new(-450, 2),
+            date: NaiveDate::from_ymd_opt(2025, 9, day).unwrap(),
+            include_in_statistics: Some(true),
+            target_account_id: account_id,
+            source_account_id: None,
+            ledger_name: None,
+            linked_import_id: None,
+            category_id: None,
+            is_simulated: None,
+            scenario_id: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
+        };
+        let resp = server.post("/api/v1/transactions").json(&txn).await;
+        resp.assert_status(StatusCode::CREATED);
+        let body: ApiResponse<serde_json::Value> = resp.json();
+        transaction_ids.push(body.data["id"].as_i64().unwrap());
+    }
+
+    let inbox = server.get("/api/v1/transactions/uncategorized?since=2025-09-01").await;
+    inbox.assert_status(StatusCode::OK);
+    let inbox_body: ApiResponse<serde_json::Value> = inbox.json();
+    assert_eq!(inbox_body.data["count"], 2);
+
+    let category_resp = server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Coffee" }))
+        .await;
+    category_resp.assert_status(StatusCode::CREATED);
+    let category_body: ApiResponse<serde_json::Value> = category_resp.json();
+    let category_id = category_body.data["id"].as_i64().unwrap();
+
+    let categorize = server
+        .post("/api/v1/transactions/categorize")
+        .json(&serde_json::json!({
+            "assignments": transaction_ids
+                .iter()
+                .map(|id| serde_json::json!({ "transaction_id": id, "category_id": category_id }))
+                .collect::<Vec<_>>()
+        }))
+        .await;
+    categorize.assert_status(StatusCode::OK);
+    let categorize_body: ApiResponse<serde_json::Value> = categorize.json();
+    assert_eq!(categorize_body.data["updated"], 2);
+
+    let inbox_after = server.get("/api/v1/transactions/uncategorized?since=2025-09-01").await;
+    let inbox_after_body: ApiResponse<serde_json::Value> = inbox_after.json();
+    assert_eq!(inbox_after_body.data["count"], 0);
+}
+
+#[tokio::test]
+async fn test_bulk_categorize_skips_locked_periods_and_double_entry_violations() {
+    let state = setup_test_app_state().await;
+    let server = TestServer::new(finrust::router::create_test_router(state.clone())).unwrap();
+
+    let mut account_ids = Vec::new();
+    for name in ["Checking", "Savings"] {
+        let body: ApiResponse<serde_json::Value> = server
+            .post("/api/v1/accounts")
+            .json(&serde_json::json!({ "name": name, "currency_code": "USD", "owner_id": 1 }))
+            .await
+            .json();
+        account_ids.push(body.data["id"].as_i64().unwrap());
+    }
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Groceries" }))
+        .await
+        .json();
+    let category_id = body.data["id"].as_i64().unwrap();
+    // Uncategorized expenses are only accepted outside strict mode
+    let mut transaction_ids = Vec::new();
+    for (date, source_account_id) in [("2025-01-15", None), ("2025-02-15", None), ("2025-02-16", Some(account_ids[1]))] {
+        let response = server
+            .post("/api/v1/transactions")
+            .json(&serde_json::json!({
+                "name": "Entry",
+                "amount": "-20.00",
+                "date": date,
+                "target_account_id": account_ids[0],
+                "source_account_id": source_account_id,
+            }))
+            .await;
+        response.assert_status(StatusCode::CREATED);
+        transaction_ids.push(response.json::<ApiResponse<serde_json::Value>>().data["id"].as_i64().unwrap());
+    }
+    server
+        .post("/api/v1/periods/lock")
+        .json(&serde_json::json!({ "start_date": "2025-01-01", "end_date": "2025-01-31" }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let mut strict_state = state;
+    strict_state.double_entry_strict = true;
+    let server = TestServer::new(finrust::router::create_test_router(strict_state)).unwrap();
+    let assignments: Vec<serde_json::Value> = transaction_ids
+        .iter()
+        .map(|id| serde_json::json!({ "transaction_id": id, "category_id": category_id }))
+        .collect();
+    let response = server
+        .post("/api/v1/transactions/categorize")
+        .json(&serde_json::json!({ "assignments": assignments }))
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["updated"], 1);
+    assert_eq!(body.data["period_locked"], serde_json::json!([transaction_ids[0]]));
+    assert_eq!(body.data["double_entry_violations"], serde_json::json!([transaction_ids[2]]));
+    for (transaction_id, categorized) in transaction_ids.iter().zip([false, true, false]) {
+        let body: ApiResponse<serde_json::Value> =
+            server.get(&format!("/api/v1/transactions/{}", transaction_id)).await.json();
+        assert_eq!(body.data["category_id"].is_null(), !categorized);
+    }
+
+    let response = server
+        .post("/api/v1/transactions/categorize")
+        .add_query_param("admin_override", true)
+        .json(&serde_json::json!({ "assignments": [assignments[0]] }))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<ApiResponse<serde_json::Value>>().data["updated"], 1);
+}
+
+#[tokio::test]
+async fn test_imported_transaction_merchant_enrichment() {
+    use finrust::handlers::transactions::CreateImportedTransactionRequest;
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Card Account".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    server
+        .post("/api/v1/merchants")
+        .json(&serde_json::json!({
+            "pattern": "spotify",
+            "name": "Spotify",
+            "logo_url": "https://example.com/spotify.png"
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    for (description, hash) in [("PAYPAL *SPOTIFY 4029357733", "m1"), ("POS 1234 CORNER BAKERY", "m2")] {
+        let request = CreateImportedTransactionRequest {
+            account_id,
+            date: NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(),
+            description: description.to_string(),
+            amount: Decimal::new(-999, 2),
+            import_hash: hash.to_string(),
+            raw_data: None,
+            category_id: None,
+            amount_locale: None,
+        };
+        server
+            .post("/api/v1/imported-transactions")
+            .json(&request)
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+
+    let response = server
+        .get(&format!("/api/v1/accounts/{}/imported-transactions", account_id))
+        .await;
+    response.assert_status(StatusCode::OK);
+    let body: ApiResponse<Vec<serde_json::Value>> = response.json();
+
+    let spotify = body.data.iter().find(|t| t["import_hash"] == "m1").unwrap();
+    assert_eq!(spotify["merchant"]["name"], "Spotify");
+    assert_eq!(spotify["merchant"]["logo_url"], "https://example.com/spotify.png");
+
+    let bakery = body.data.iter().find(|t| t["import_hash"] == "m2").unwrap();
+    assert_eq!(bakery["merchant"]["name"], "Corner Bakery");
+    assert!(bakery["merchant"]["logo_url"].is_null());
+}
+
+#[tokio::test]
+async fn test_transactions_geojson_bbox() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Travel Account".to_string(),
+        description: None,
+        currency_code: "EUR".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    // Prague, Lisbon and one without coordinates
+    for (name, coordinates) in [
+        ("Prague lunch", Some((50.087, 14.421))),
+        ("Lisbon dinner", Some((38.722, -9.139))),
+        ("Online order", None),
+    ] {
+        let txn = CreateTransactionRequest {
+            name: name.to_string(),
+            description: None,
+            amount: Decimal::new(-2000, 2),
+            date: NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+            include_in_statistics: Some(true),
+            target_account_id: account_id,
+            source_account_id: None,
+            ledger_name: None,
+            linked_import_id: None,
+            category_id: None,
+            scenario_id: None,
+            is_simulated: None,
+            latitude: coordinates.map(|(lat, _)| lat),
+            longitude: coordinates.map(|(_, lon)| lon),
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
+        };
+        let resp = server.post("/api/v1/transactions").json(&txn).await;
+        resp.assert_status(StatusCode::CREATED);
+    }
+
+    let all = server.get("/api/v1/transactions/geojson").await;
+    all.assert_status(StatusCode::OK);
+    let all_body: serde_json::Value = all.json();
+    assert_eq!(all_body["type"], "FeatureCollection");
+    assert_eq!(all_body["features"].as_array().unwrap().len(), 2);
+
+    let central_europe = server.get("/api/v1/transactions/geojson?bbox=12.0,48.5,19.0,51.1").await;
+    central_europe.assert_status(StatusCode::OK);
+    let europe_body: serde_json::Value = central_europe.json();
+    let features = europe_body["features"].as_array().unwrap();
+    assert_eq!(features.len(), 1);
+    assert_eq!(features[0]["properties"]["name"], "Prague lunch");
+    assert_eq!(features[0]["geometry"]["coordinates"][0], 14.421);
+
+    let invalid = server.get("/api/v1/transactions/geojson?bbox=1,2,3").await;
+    invalid.assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_category_spending_forecast() {
+    use chrono::Datelike;
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Grocery Account".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let parent_resp = server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Food" }))
+        .await;
+    parent_resp.assert_status(StatusCode::CREATED);
+    let parent_body: ApiResponse<serde_json::Value> = parent_resp.json();
+    let parent_id = parent_body.data["id"].as_i64().unwrap() as i32;
+
+    let child_resp = server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Groceries", "parent_id": parent_id }))
+        .await;
+    child_resp.assert_status(StatusCode::CREATED);
+    let child_body: ApiResponse<serde_json::Value> = child_resp.json();
+    let child_id = child_body.data["id"].as_i64().unwrap() as i32;
+
+    // 90 spent in each of the last three months, split between parent and child
+    let this_month = chrono::Utc::now().date_naive().with_day(1).unwrap();
+    for back in 1..=3 {
+        let date = this_month.checked_sub_months(chrono::Months::new(back)).unwrap();
+        for (category_id, cents) in [(parent_id, -3000), (child_id, -6000)] {
+            let txn = CreateTransactionRequest {
+                name: "Shopping".to_string(),
+                description: None,
+                amount: Decimal::new(cents, 2),
+                date,
+                include_in_statistics: Some(true),
+                target_account_id: account_id,
+                source_account_id: None,
+                ledger_name: None,
+                linked_import_id: None,
+                category_id: Some(category_id),
+                scenario_id: None,
+                is_simulated: None,
+                latitude: None,
+                longitude: None,
+                status: None,
+                original_amount: None,
+                original_currency: None,
+                conversion_rate: None,
+                is_purchase: None,
+                warranty_end_date: None,
+                return_deadline: None,
+                receipt_document_id: None,
+            };
+            let resp = server.post("/api/v1/transactions").json(&txn).await;
+            resp.assert_status(StatusCode::CREATED);
+        }
+    }
+
+    let resp = server
+        .get(&format!("/api/v1/categories/{}/forecast?lookback_months=3&horizon=2", parent_id))
+        .await;
+    resp.assert_status(StatusCode::OK);
+    let body: ApiResponse<serde_json::Value> = resp.json();
+    let amount = |value: &serde_json::Value| value.as_str().unwrap().parse::<Decimal>().unwrap();
+    assert_eq!(amount(&body.data["average_monthly"]), Decimal::new(90, 0));
+    assert_eq!(amount(&body.data["suggested_budget"]), Decimal::new(90, 0));
+    assert_eq!(body.data["forecast"].as_array().unwrap().len(), 2);
+
+    let missing = server.get("/api/v1/categories/999999/forecast").await;
+    missing.assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_recurring_suggestions_from_one_off_history() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Subscriptions Account".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    for (index, (month, day)) in [(1, 5), (2, 4), (3, 6), (4, 5)].into_iter().enumerate() {
+        let txn = CreateTransactionRequest {
+            name: format!("Spotify P{}", 1000 + index),
+            description: None,
+            amount: Decimal::new(-999, 2),
+            date: NaiveDate::from_ymd_opt(2025, month, day).unwrap(),
+            include_in_statistics: Some(true),
+            target_account_id: account_id,
+            source_account_id: None,
+            ledger_name: None,
+            linked_import_id: None,
+            category_id: None,
+            scenario_id: None,
+            is_simulated: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
+        };
+        let resp = server.post("/api/v1/transactions").json(&txn).await;
+        resp.assert_status(StatusCode::CREATED);
+    }
+
+    let suggestions = server
+        .get(&format!("/api/v1/transactions/recurring-suggestions?account_id={}", account_id))
+        .await;
+    suggestions.assert_status(StatusCode::OK);
+    let suggestions_body: ApiResponse<Vec<serde_json::Value>> = suggestions.json();
+    assert_eq!(suggestions_body.data.len(), 1);
+    assert_eq!(suggestions_body.data[0]["period"], "Monthly");
+    let transaction_ids = suggestions_body.data[0]["transaction_ids"].clone();
+    assert_eq!(transaction_ids.as_array().unwrap().len(), 4);
+
+    let convert = server
+        .post("/api/v1/transactions/recurring-suggestions/convert")
+        .json(&serde_json::json!({ "transaction_ids": transaction_ids, "name": "Spotify" }))
+        .await;
+    convert.assert_status(StatusCode::CREATED);
+    let convert_body: ApiResponse<serde_json::Value> = convert.json();
+    assert_eq!(convert_body.data["instances_created"], 4);
+    assert_eq!(convert_body.data["recurring_transaction"]["name"], "Spotify");
+    assert_eq!(convert_body.data["recurring_transaction"]["start_date"], "2025-01-05");
+
+    let remaining = server
+        .get(&format!("/api/v1/accounts/{}/transactions", account_id))
+        .await;
+    remaining.assert_status(StatusCode::OK);
+    let remaining_body: ApiResponse<Vec<serde_json::Value>> = remaining.json();
+    assert!(remaining_body.data.is_empty());
+
+    let after = server
+        .get(&format!("/api/v1/transactions/recurring-suggestions?account_id={}", account_id))
+        .await;
+    let after_body: ApiResponse<Vec<serde_json::Value>> = after.json();
+    assert!(after_body.data.is_empty());
+}
+
+#[tokio::test]
+async fn test_double_entry_strict_mode_and_report() {
+    let mut state = setup_test_app_state().await;
+    state.double_entry_strict = true;
+    let server = TestServer::new(finrust::router::create_test_router(state)).unwrap();
+
+    let mut account_ids = Vec::new();
+    for (name, currency) in [("Checking", "USD"), ("Savings", "USD"), ("Euro Wallet", "EUR")] {
+        let account_request = CreateAccountRequest {
+            name: name.to_string(),
+            description: None,
+            currency_code: currency.to_string(),
+            owner_id: 1,
+            include_in_statistics: Some(true),
+            ledger_name: None,
+            account_kind: None,
+            target_amount: None,
+            color: None,
+            is_liquid: None,
+            balance_interpolation: None,
+        };
+        let resp = server.post("/api/v1/accounts").json(&account_request).await;
+        resp.assert_status(StatusCode::CREATED);
+        let body: ApiResponse<serde_json::Value> = resp.json();
+        account_ids.push(body.data["id"].as_i64().unwrap() as i32);
+    }
+
+    let category_resp = server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Groceries" }))
+        .await;
+    category_resp.assert_status(StatusCode::CREATED);
+    let category_body: ApiResponse<serde_json::Value> = category_resp.json();
+    let category_id = category_body.data["id"].as_i64().unwrap() as i32;
+
+    let transaction = |source_account_id: Option<i32>, category_id: Option<i32>| CreateTransactionRequest {
+        name: "Entry".to_string(),
+        description: None,
+        amount: Decimal::new(-2500, 2),
+        date: NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+        include_in_statistics: Some(true),
+        target_account_id: account_ids[0],
+        source_account_id,
+        ledger_name: None,
+        linked_import_id: None,
+        category_id,
+        scenario_id: None,
+        is_simulated: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
+    };
+
+    // Expense without a category has no counter entry
+    let rejected = server.post("/api/v1/transactions").json(&transaction(None, None)).await;
+    rejected.assert_status(StatusCode::BAD_REQUEST);
+    let rejected_body: serde_json::Value = rejected.json();
+    assert_eq!(rejected_body["code"], "DOUBLE_ENTRY_VIOLATION");
+
+    // Transfers between accounts with different currencies can't balance
+    let mismatched = server
+        .post("/api/v1/transactions")
+        .json(&transaction(Some(account_ids[2]), None))
+        .await;
+    mismatched.assert_status(StatusCode::BAD_REQUEST);
+
+    server
+        .post("/api/v1/transactions")
+        .json(&transaction(None, Some(category_id)))
+        .await
+        .assert_status(StatusCode::CREATED);
+    server
+        .post("/api/v1/transactions")
+        .json(&transaction(Some(account_ids[1]), None))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let report = server.get("/api/v1/double-entry/report").await;
+    report.assert_status(StatusCode::OK);
+    let report_body: ApiResponse<serde_json::Value> = report.json();
+    assert_eq!(report_body.data["strict_mode"], true);
+    assert_eq!(report_body.data["checked"], 2);
+    assert!(report_body.data["entries"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_double_entry_report_lists_violations() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Migrated Account".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let txn = CreateTransactionRequest {
+        name: "Unbalanced".to_string(),
+        description: None,
+        amount: Decimal::new(-1000, 2),
+        date: NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+        include_in_statistics: Some(true),
+        target_account_id: account_id,
+        source_account_id: None,
+        ledger_name: None,
+        linked_import_id: None,
+        category_id: None,
+        scenario_id: None,
+        is_simulated: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
+    };
+    server
+        .post("/api/v1/transactions")
+        .json(&txn)
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let report = server.get("/api/v1/double-entry/report").await;
+    report.assert_status(StatusCode::OK);
+    let report_body: ApiResponse<serde_json::Value> = report.json();
+    assert_eq!(report_body.data["strict_mode"], false);
+    let entries = report_body.data["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["transaction_kind"], "one_off");
+    assert_eq!(entries[0]["violations"][0], "MISSING_COUNTER_ENTRY");
+}
+
+#[tokio::test]
+async fn test_chart_of_accounts_export() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Checking".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let parent_resp = server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Food" }))
+        .await;
+    parent_resp.assert_status(StatusCode::CREATED);
+    let parent_body: ApiResponse<serde_json::Value> = parent_resp.json();
+    let parent_id = parent_body.data["id"].as_i64().unwrap();
+    let child_resp = server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Groceries", "parent_id": parent_id }))
+        .await;
+    child_resp.assert_status(StatusCode::CREATED);
+    let child_body: ApiResponse<serde_json::Value> = child_resp.json();
+    let groceries_id = child_body.data["id"].as_i64().unwrap() as i32;
+
+    for (name, amount, category_id) in [
+        ("Salary", Decimal::new(300000, 2), None),
+        ("Supermarket", Decimal::new(-4550, 2), Some(groceries_id)),
+    ] {
+        let txn = CreateTransactionRequest {
+            name: name.to_string(),
+            description: None,
+            amount,
+            date: NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            include_in_statistics: Some(true),
+            target_account_id: account_id,
+            source_account_id: None,
+            ledger_name: None,
+            linked_import_id: None,
+            category_id,
+            scenario_id: None,
+            is_simulated: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
+        };
+        server.post("/api/v1/transactions").json(&txn).await.assert_status(StatusCode::CREATED);
+    }
+
+    let response = server
+        .get("/api/v1/export/chart-of-accounts?cutoff_date=2025-03-31")
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["period_start"], "2025-01-01");
+    let entries = body.data["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 3);
+
+    let asset = &entries[0];
+    assert_eq!(asset["code"], "1001");
+    assert_eq!(asset["full_name"], "Assets:Checking");
+    assert_eq!(asset["account_type"], "Asset");
+    assert_eq!(asset["source_id"], account_id);
+
+    let income = entries.iter().find(|e| e["account_type"] == "Income").unwrap();
+    assert_eq!(income["full_name"], "Income:Uncategorized");
+    assert_eq!(income["balance"].as_str().unwrap().parse::<Decimal>().unwrap(), Decimal::new(300000, 2));
+
+    let expense = entries.iter().find(|e| e["account_type"] == "Expense").unwrap();
+    assert_eq!(expense["code"], "5001");
+    assert_eq!(expense["full_name"], "Expenses:Food:Groceries");
+    assert_eq!(expense["balance"].as_str().unwrap().parse::<Decimal>().unwrap(), Decimal::new(4550, 2));
+    assert_eq!(expense["source_id"], groceries_id);
+
+    let csv_response = server
+        .get("/api/v1/export/chart-of-accounts?cutoff_date=2025-03-31&format=csv")
+        .await;
+    csv_response.assert_status_ok();
+    assert!(
+        csv_response
+            .header("content-type")
+            .to_str()
+            .unwrap()
+            .starts_with("text/csv")
+    );
+    let csv = csv_response.text();
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("code,full_name,account_type,currency,balance,source,source_id")
+    );
+    let expense_line = csv.lines().find(|line| line.starts_with("5001,")).unwrap();
+    assert!(expense_line.starts_with("5001,Expenses:Food:Groceries,Expense,USD,45.5"));
+    assert!(expense_line.ends_with(&format!(",category,{}", groceries_id)));
+
+    let invalid = server
+        .get("/api/v1/export/chart-of-accounts?cutoff_date=2025-01-01&period_start=2025-02-01")
+        .await;
+    invalid.assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_recurring_due_profile() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Checking".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap();
+
+    for (name, amount, start_date) in [
+        ("Rent", "-1000", "2025-01-01"),
+        ("Internet", "-50", "2025-01-01"),
+        ("Gym", "-30", "2025-01-15"),
+        ("Salary", "3000", "2025-01-10"),
+    ] {
+        server
+            .post("/api/v1/recurring-transactions")
+            .json(&serde_json::json!({
+                "name": name,
+                "amount": amount,
+                "start_date": start_date,
+                "period": "Monthly",
+                "target_account_id": account_id,
+            }))
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+
+    let response = server
+        .get("/api/v1/recurring-transactions/due-profile?start_date=2025-01-01&months=6")
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    let profile = &body.data;
+    assert_eq!(profile["end_date"], "2025-06-30");
+    let dec = |value: &serde_json::Value| value.as_str().unwrap().parse::<Decimal>().unwrap();
+    assert_eq!(dec(&profile["total_monthly_outflow"]), Decimal::from(1080));
+    assert_eq!(profile["peak_day"], 1);
+
+    let days = profile["days"].as_array().unwrap();
+    assert_eq!(days.len(), 31);
+    assert_eq!(dec(&days[0]["monthly_average"]), Decimal::from(1050));
+    assert_eq!(days[0]["occurrences"], 12);
+    assert_eq!(days[0]["payments"][0]["name"], "Rent");
+    assert_eq!(dec(&days[14]["monthly_average"]), Decimal::from(30));
+    // Income is not an outflow
+    assert_eq!(days[9]["occurrences"], 0);
+}
+
+#[tokio::test]
+async fn test_cashflow_smoothing_advice() {
+    use chrono::{Datelike, Months};
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Checking".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap();
+
+    let today = chrono::Utc::now().date_naive();
+    server
+        .post(&format!("/api/v1/accounts/{}/manual-states", account_id))
+        .json(&serde_json::json!({ "date": today, "amount": "500" }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    // Rent is due two weeks before the salary arrives
+    let next_month = today.with_day(1).unwrap() + Months::new(1);
+    for (name, amount, day) in [("Rent", "-1500", 1), ("Salary", "2000", 15)] {
+        server
+            .post("/api/v1/recurring-transactions")
+            .json(&serde_json::json!({
+                "name": name,
+                "amount": amount,
+                "start_date": next_month.with_day(day).unwrap(),
+                "period": "Monthly",
+                "target_account_id": account_id,
+            }))
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+
+    let response = server
+        .get(&format!("/api/v1/insights/cashflow-smoothing?account_id={}&months=3", account_id))
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    let advice = &body.data;
+    assert_eq!(advice["income_days"], serde_json::json!([15]));
+    assert_eq!(advice["peak_outflow_day"], 1);
+    assert!(advice["days_below_buffer"].as_u64().unwrap() > 0);
+    assert_eq!(advice["projected_days_below_buffer"], 0);
+
+    let suggestions = advice["suggestions"].as_array().unwrap();
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0]["name"], "Rent");
+    assert_eq!(suggestions[0]["from_day"], 1);
+    assert_eq!(suggestions[0]["to_day"], 15);
+
+    let missing = server
+        .get("/api/v1/insights/cashflow-smoothing?account_id=9999")
+        .await;
+    missing.assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_imported_bank_charges_are_classified() {
+    use finrust::handlers::transactions::CreateImportedTransactionRequest;
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Checking".to_string(),
+        description: None,
+        currency_code: "CZK".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let mut category_ids = Vec::new();
+    for (index, (description, amount)) in [
+        ("Poplatek za vedení účtu", Decimal::new(-4900, 2)),
+        ("Monthly card fee", Decimal::new(-2500, 2)),
+        ("Úrok připsaný", Decimal::new(123, 2)),
+        ("COFFEE SHOP", Decimal::new(-400, 2)),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let request = CreateImportedTransactionRequest {
+            account_id,
+            date: NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            description: description.to_string(),
+            amount,
+            import_hash: format!("bank_charge_{}", index),
+            raw_data: None,
+            category_id: None,
+            amount_locale: None,
+        };
+        let response = server.post("/api/v1/imported-transactions").json(&request).await;
+        response.assert_status(StatusCode::CREATED);
+        let body: ApiResponse<serde_json::Value> = response.json();
+        category_ids.push((body.data["id"].as_i64().unwrap(), body.data["category_id"].as_i64()));
+    }
+
+    let categories_resp = server.get("/api/v1/categories").await;
+    categories_resp.assert_status_ok();
+    let categories_body: ApiResponse<Vec<serde_json::Value>> = categories_resp.json();
+    let category_by_name = |name: &str| {
+        categories_body
+            .data
+            .iter()
+            .find(|c| c["name"] == name)
+            .cloned()
+            .unwrap_or_else(|| panic!("missing category {}", name))
+    };
+    let bank_costs = category_by_name("Bank Costs");
+    let fees = category_by_name("Bank Fees");
+    let interest_income = category_by_name("Interest Income");
+    assert_eq!(fees["parent_id"], bank_costs["id"]);
+    assert!(interest_income["parent_id"].is_null());
+
+    assert_eq!(category_ids[0].1, fees["id"].as_i64());
+    assert_eq!(category_ids[1].1, fees["id"].as_i64());
+    assert_eq!(category_ids[2].1, interest_income["id"].as_i64());
+    assert_eq!(category_ids[3].1, None);
+
+    // Rows that became bank charges after import are picked up by the backfill
+    let coffee_id = category_ids[3].0;
+    server
+        .put(&format!("/api/v1/imported-transactions/{}", coffee_id))
+        .json(&serde_json::json!({ "description": "Overdraft interest", "amount": "-5.00" }))
+        .await
+        .assert_status_ok();
+    let classify = server.post("/api/v1/imported-transactions/classify-bank-charges").await;
+    classify.assert_status_ok();
+    let classify_body: ApiResponse<serde_json::Value> = classify.json();
+    assert_eq!(classify_body.data["classified"], 1);
+    assert_eq!(classify_body.data["interest_charged"], 1);
+
+    let updated: ApiResponse<serde_json::Value> = server
+        .get(&format!("/api/v1/imported-transactions/{}", coffee_id))
+        .await
+        .json();
+    let interest_charges = server.get("/api/v1/categories").await.json::<ApiResponse<Vec<serde_json::Value>>>();
+    let interest_charges = interest_charges
+        .data
+        .iter()
+        .find(|c| c["name"] == "Interest Charges")
+        .unwrap()
+        .clone();
+    assert_eq!(updated.data["category_id"], interest_charges["id"]);
+    assert_eq!(interest_charges["parent_id"], bank_costs["id"]);
+}
+
+#[tokio::test]
+async fn test_imported_foreign_currency_fees() {
+    use ::common::AmountLocale;
+    use finrust::handlers::transactions::CreateImportedTransactionRequest;
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Card".to_string(),
+        description: None,
+        currency_code: "CZK".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let rows = [
+        // Fee implied by the reference rate: 255.00 - 10.00 * 25.00
+        (
+            Decimal::new(-25500, 2),
+            serde_json::json!({ "original_amount": "10.00", "original_currency": "EUR", "reference_rate": "25.00" }),
+            None,
+        ),
+        // Fee stated explicitly, nested original amount
+        (
+            Decimal::new(-47300, 2),
+            serde_json::json!({ "original": { "amount": -20, "currency": "usd" }, "fx_fee": "3.00" }),
+            None,
+        ),
+        // Paid in the account currency
+        (
+            Decimal::new(-10000, 2),
+            serde_json::json!({ "original_amount": "100.00", "original_currency": "CZK" }),
+            None,
+        ),
+        // Decimal comma declared by the import profile, which would otherwise read as 1500
+        (
+            Decimal::new(-3750, 2),
+            serde_json::json!({ "original_amount": "1,500", "original_currency": "EUR" }),
+            Some(AmountLocale::CommaDecimal),
+        ),
+    ];
+
+    let mut created = Vec::new();
+    for (index, (amount, raw_data, amount_locale)) in rows.into_iter().enumerate() {
+        let request = CreateImportedTransactionRequest {
+            account_id,
+            date: NaiveDate::from_ymd_opt(2025, 5, 10).unwrap(),
+            description: format!("Card payment {}", index),
+            amount,
+            import_hash: format!("fx_{}", index),
+            raw_data: Some(raw_data),
+            category_id: None,
+            amount_locale,
+        };
+        let response = server.post("/api/v1/imported-transactions").json(&request).await;
+        response.assert_status(StatusCode::CREATED);
+        let body: ApiResponse<serde_json::Value> = response.json();
+        created.push(body.data);
+    }
+
+    let dec = |value: &serde_json::Value| value.as_str().unwrap().parse::<Decimal>().unwrap();
+    assert_eq!(dec(&created[0]["original_amount"]), Decimal::new(-1000, 2));
+    assert_eq!(created[0]["original_currency"], "EUR");
+    assert_eq!(dec(&created[0]["fx_fee"]), Decimal::new(500, 2));
+    assert_eq!(dec(&created[1]["original_amount"]), Decimal::from(-20));
+    assert_eq!(created[1]["original_currency"], "USD");
+    assert_eq!(dec(&created[1]["fx_fee"]), Decimal::new(300, 2));
+    assert!(created[2]["original_amount"].is_null());
+    assert!(created[2]["fx_fee"].is_null());
+    assert_eq!(dec(&created[3]["original_amount"]), Decimal::new(-15, 1));
+    assert_eq!(created[3]["original_currency"], "EUR");
+
+    let stats_resp = server
+        .get(&format!("/api/v1/accounts/{}/statistics?year=2025", account_id))
+        .await;
+    stats_resp.assert_status_ok();
+    let stats_body: ApiResponse<serde_json::Value> = stats_resp.json();
+    assert_eq!(dec(&stats_body.data["statistics"][0]["fx_fees"]), Decimal::new(800, 2));
+
+    let other_year: ApiResponse<serde_json::Value> = server
+        .get(&format!("/api/v1/accounts/{}/statistics?year=2024", account_id))
+        .await
+        .json();
+    assert!(other_year.data["statistics"][0]["fx_fees"].is_null());
+}
+
+#[tokio::test]
+async fn test_quick_entry_parses_typed_amounts() {
+    let server = TestServer::new(setup_test_app().await).unwrap();
+
+    let response = server
+        .post("/api/v1/accounts")
+        .json(&serde_json::json!({ "name": "Wallet", "currency_code": "CZK", "owner_id": 1 }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let account_id = response.json::<serde_json::Value>()["data"]["id"].as_i64().unwrap();
+
+    let dec = |value: &serde_json::Value| value.as_str().unwrap().parse::<Decimal>().unwrap();
+    let response = server
+        .post("/api/v1/transactions/quick")
+        .json(&serde_json::json!({
+            "target_account_id": account_id,
+            "name": "Groceries",
+            "amount": "-1 234,50 Kč",
+            "date": "2025-05-10",
+        }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let body: serde_json::Value = response.json();
+    assert_eq!(dec(&body["data"]["amount"]), Decimal::new(-123450, 2));
+    assert_eq!(body["data"]["date"], "2025-05-10");
+    assert_eq!(body["data"]["target_account_id"], account_id);
+
+    // Explicit locale, and today by default
+    let response = server
+        .post("/api/v1/transactions/quick")
+        .json(&serde_json::json!({
+            "target_account_id": account_id,
+            "name": "Refund",
+            "amount": "1,500",
+            "amount_locale": "CommaDecimal",
+        }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let body: serde_json::Value = response.json();
+    assert_eq!(dec(&body["data"]["amount"]), Decimal::new(15, 1));
+    assert_eq!(body["data"]["date"], chrono::Utc::now().date_naive().to_string());
+
+    for amount in ["1e5", "12.00-", "12-34", "coffee"] {
+        let response = server
+            .post("/api/v1/transactions/quick")
+            .json(&serde_json::json!({ "target_account_id": account_id, "name": "Typo", "amount": amount }))
+            .await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+        assert_eq!(response.json::<serde_json::Value>()["code"], "INVALID_AMOUNT", "amount {:?}", amount);
+    }
+
+    let transactions: serde_json::Value = server
+        .get(&format!("/api/v1/accounts/{}/transactions", account_id))
+        .await
+        .json();
+    assert_eq!(transactions["data"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_transaction_legs() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Card".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let mut category_ids = Vec::new();
+    for name in ["Shopping", "Fees", "Cashback"] {
+        let response = server
+            .post("/api/v1/categories")
+            .json(&serde_json::json!({ "name": name }))
+            .await;
+        response.assert_status(StatusCode::CREATED);
+        let body: ApiResponse<serde_json::Value> = response.json();
+        category_ids.push(body.data["id"].as_i64().unwrap());
+    }
+    let (shopping_id, fees_id, cashback_id) = (category_ids[0], category_ids[1], category_ids[2]);
+
+    let purchase = CreateTransactionRequest {
+        name: "Online order".to_string(),
+        description: None,
+        amount: Decimal::new(-10000, 2),
+        date: NaiveDate::from_ymd_opt(2025, 3, 10).unwrap(),
+        include_in_statistics: Some(true),
+        target_account_id: account_id,
+        source_account_id: None,
+        ledger_name: None,
+        linked_import_id: None,
+        category_id: Some(shopping_id as i32),
+        is_simulated: Some(false),
+        scenario_id: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
+    };
+    let purchase_resp = server.post("/api/v1/transactions").json(&purchase).await;
+    purchase_resp.assert_status(StatusCode::CREATED);
+    let purchase_body: ApiResponse<serde_json::Value> = purchase_resp.json();
+    let parent_id = purchase_body.data["id"].as_i64().unwrap();
+
+    let fee_resp = server
+        .post(&format!("/api/v1/transactions/{}/legs", parent_id))
+        .json(&serde_json::json!({ "name": "Delivery", "amount": "-5", "category_id": fees_id }))
+        .await;
+    fee_resp.assert_status(StatusCode::CREATED);
+
+    let cashback_resp = server
+        .post(&format!("/api/v1/transactions/{}/legs", parent_id))
+        .json(&serde_json::json!({ "name": "Cashback", "amount": "2", "category_id": cashback_id }))
+        .await;
+    cashback_resp.assert_status(StatusCode::CREATED);
+    let parent: ApiResponse<serde_json::Value> = cashback_resp.json();
+
+    let dec = |value: &serde_json::Value| value.as_str().unwrap().parse::<Decimal>().unwrap();
+    let legs = parent.data["legs"].as_array().unwrap();
+    assert_eq!(legs.len(), 2);
+    assert_eq!(legs[0]["category_id"], fees_id);
+    assert_eq!(legs[1]["category_id"], cashback_id);
+    assert_eq!(dec(&parent.data["amount"]), Decimal::from(-100));
+    assert_eq!(dec(&parent.data["total_amount"]), Decimal::from(-103));
+    let fee_leg_id = legs[0]["id"].as_i64().unwrap();
+
+    // Legs are nested under the parent rather than listed on their own
+    let listing: ApiResponse<Vec<serde_json::Value>> = server.get("/api/v1/transactions").await.json();
+    assert_eq!(listing.data.len(), 1);
+    assert_eq!(listing.data[0]["id"], parent_id);
+    assert_eq!(dec(&listing.data[0]["total_amount"]), Decimal::from(-103));
+
+    // ...but filtering by the leg's category finds the leg itself
+    let by_category: ApiResponse<Vec<serde_json::Value>> = server
+        .get(&format!("/api/v1/transactions?categoryId={}", fees_id))
+        .await
+        .json();
+    assert_eq!(by_category.data.len(), 1);
+    assert_eq!(by_category.data[0]["id"], fee_leg_id);
+    assert_eq!(by_category.data[0]["parent_transaction_id"], parent_id);
+
+    let nested_resp = server
+        .post(&format!("/api/v1/transactions/{}/legs", fee_leg_id))
+        .json(&serde_json::json!({ "name": "Nested", "amount": "-1" }))
+        .await;
+    nested_resp.assert_status(StatusCode::BAD_REQUEST);
+    let nested_body: serde_json::Value = nested_resp.json();
+    assert_eq!(nested_body["code"], "NESTED_LEG");
+
+    let delete_resp = server.delete(&format!("/api/v1/transactions/{}", parent_id)).await;
+    delete_resp.assert_status_ok();
+    server
+        .get(&format!("/api/v1/transactions/{}", fee_leg_id))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_recurring_instances_grouped_by_month() {
+    use crate::common::setup_test_app_state;
+    use finrust::router::create_test_router;
+    use model::entities::recurring_transaction_instance::{self, InstanceStatus};
+    use model::entities::{account, recurring_transaction};
+    use sea_orm::{ActiveModelTrait, Set};
+
+    let app_state = setup_test_app_state().await;
+    let app = create_test_router(app_state.clone());
+    let server = TestServer::new(app).unwrap();
+
+    let account = account::ActiveModel {
+        name: Set("Bills".to_string()),
+        currency_code: Set("USD".to_string()),
+        owner_id: Set(1),
+        include_in_statistics: Set(true),
+        ..Default::default()
+    }
+    .insert(&app_state.db)
+    .await
+    .unwrap();
+
+    let rule = recurring_transaction::ActiveModel {
+        name: Set("Utilities".to_string()),
+        amount: Set(Decimal::from(-100)),
+        start_date: Set(NaiveDate::from_ymd_opt(2025, 1, 5).unwrap()),
+        period: Set(recurring_transaction::RecurrencePeriod::Monthly),
+        include_in_statistics: Set(true),
+        target_account_id: Set(account.id),
+        ..Default::default()
+    }
+    .insert(&app_state.db)
+    .await
+    .unwrap();
+
+    let instances = [
+        ((2025, 1, 5), InstanceStatus::Paid, Some(Decimal::from(-110))),
+        ((2025, 1, 20), InstanceStatus::Skipped, None),
+        ((2025, 2, 5), InstanceStatus::Paid, None),
+        ((2025, 2, 20), InstanceStatus::Pending, None),
+    ];
+    for ((year, month, day), status, paid_amount) in instances {
+        recurring_transaction_instance::ActiveModel {
+            recurring_transaction_id: Set(rule.id),
+            status: Set(status),
+            due_date: Set(NaiveDate::from_ymd_opt(year, month, day).unwrap()),
+            expected_amount: Set(Decimal::from(-100)),
+            paid_date: Set(None),
+            paid_amount: Set(paid_amount),
+            reconciled_imported_transaction_id: Set(None),
+            category_id: Set(None),
+            ..Default::default()
+        }
+        .insert(&app_state.db)
+        .await
+        .unwrap();
+    }
+
+    let response = server.get("/api/v1/recurring-instances?group_by=month").await;
+    response.assert_status_ok();
+    let body: ApiResponse<Vec<serde_json::Value>> = response.json();
+    assert_eq!(body.data.len(), 2);
+
+    let dec = |value: &serde_json::Value| value.as_str().unwrap().parse::<Decimal>().unwrap();
+
+    let february = &body.data[0];
+    assert_eq!(february["month"], "2025-02");
+    assert_eq!(february["paid_count"], 1);
+    assert_eq!(february["pending_count"], 1);
+    assert_eq!(february["skipped_count"], 0);
+    assert_eq!(dec(&february["total_expected"]), Decimal::from(-200));
+    assert_eq!(dec(&february["total_paid"]), Decimal::from(-100));
+    assert_eq!(dec(&february["total_pending"]), Decimal::from(-100));
+    assert_eq!(february["instances"].as_array().unwrap().len(), 2);
+
+    let january = &body.data[1];
+    assert_eq!(january["month"], "2025-01");
+    assert_eq!(january["paid_count"], 1);
+    assert_eq!(january["skipped_count"], 1);
+    assert_eq!(dec(&january["total_expected"]), Decimal::from(-100));
+    assert_eq!(dec(&january["total_paid"]), Decimal::from(-110));
+    assert_eq!(dec(&january["total_pending"]), Decimal::ZERO);
+
+    // Without grouping the flat, paginated list is unchanged
+    let flat: ApiResponse<Vec<serde_json::Value>> = server.get("/api/v1/recurring-instances?limit=3").await.json();
+    assert_eq!(flat.data.len(), 3);
+}
+
+#[tokio::test]
+async fn test_account_kind_rules() {
+    use finrust::handlers::accounts::AccountKind;
+    use finrust::handlers::transactions::CreateImportedTransactionRequest;
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = |name: &str, account_kind: AccountKind, target_amount: Option<Decimal>| CreateAccountRequest {
+        name: name.to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: Some(account_kind),
+        target_amount,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+
+    // Savings goals need a target
+    let missing_target = server
+        .post("/api/v1/accounts")
+        .json(&account_request("Holiday", AccountKind::Goal, None))
+        .await;
+    missing_target.assert_status(StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = missing_target.json();
+    assert_eq!(body["code"], "TARGET_AMOUNT_REQUIRED");
+
+    let goal_resp = server
+        .post("/api/v1/accounts")
+        .json(&account_request("Holiday", AccountKind::Goal, Some(Decimal::from(2000))))
+        .await;
+    goal_resp.assert_status(StatusCode::CREATED);
+    let goal_body: ApiResponse<serde_json::Value> = goal_resp.json();
+    let goal_id = goal_body.data["id"].as_i64().unwrap();
+
+    // Switching an account to Goal without a target is rejected as well
+    let checking_resp = server
+        .post("/api/v1/accounts")
+        .json(&account_request("Checking", AccountKind::RealAccount, None))
+        .await;
+    checking_resp.assert_status(StatusCode::CREATED);
+    let checking_body: ApiResponse<serde_json::Value> = checking_resp.json();
+    let checking_id = checking_body.data["id"].as_i64().unwrap();
+    let to_goal = server
+        .put(&format!("/api/v1/accounts/{}", checking_id))
+        .json(&serde_json::json!({ "account_kind": "Goal" }))
+        .await;
+    to_goal.assert_status(StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = to_goal.json();
+    assert_eq!(body["code"], "TARGET_AMOUNT_REQUIRED");
+
+    // Renaming an existing goal keeps its target and passes
+    server
+        .put(&format!("/api/v1/accounts/{}", goal_id))
+        .json(&serde_json::json!({ "name": "Summer holiday" }))
+        .await
+        .assert_status_ok();
+
+    // Envelope accounts can't be import targets
+    let envelope_resp = server
+        .post("/api/v1/accounts")
+        .json(&account_request("Groceries envelope", AccountKind::Allowance, None))
+        .await;
+    envelope_resp.assert_status(StatusCode::CREATED);
+    let envelope_body: ApiResponse<serde_json::Value> = envelope_resp.json();
+    let envelope_id = envelope_body.data["id"].as_i64().unwrap() as i32;
+    let import = server
+        .post("/api/v1/imported-transactions")
+        .json(&CreateImportedTransactionRequest {
+            account_id: envelope_id,
+            date: NaiveDate::from_ymd_opt(2025, 1, 10).unwrap(),
+            description: "Supermarket".to_string(),
+            amount: Decimal::from(-40),
+            import_hash: "envelope_import".to_string(),
+            raw_data: None,
+            category_id: None,
+            amount_locale: None,
+        })
+        .await;
+    import.assert_status(StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = import.json();
+    assert_eq!(body["code"], "VIRTUAL_ACCOUNT_NOT_IMPORTABLE");
+
+    let envelope_validation: ApiResponse<serde_json::Value> = server
+        .get(&format!("/api/v1/accounts/{}/validation", envelope_id))
+        .await
+        .json();
+    assert_eq!(envelope_validation.data["accepts_imports"], false);
+
+    // A loan that has been overpaid is reported
+    let loan_resp = server
+        .post("/api/v1/accounts")
+        .json(&account_request("Car loan", AccountKind::Debt, None))
+        .await;
+    loan_resp.assert_status(StatusCode::CREATED);
+    let loan_body: ApiResponse<serde_json::Value> = loan_resp.json();
+    let loan_id = loan_body.data["id"].as_i64().unwrap() as i32;
+
+    let validation: ApiResponse<serde_json::Value> = server
+        .get(&format!("/api/v1/accounts/{}/validation", loan_id))
+        .await
+        .json();
+    assert!(validation.data["issues"].as_array().unwrap().is_empty());
+
+    let overpayment = CreateTransactionRequest {
+        name: "Overpayment".to_string(),
+        description: None,
+        amount: Decimal::from(500),
+        date: chrono::Utc::now().date_naive() - chrono::Duration::days(1),
+        include_in_statistics: Some(true),
+        target_account_id: loan_id,
+        source_account_id: None,
+        ledger_name: None,
+        linked_import_id: None,
+        category_id: None,
+        is_simulated: Some(false),
+        scenario_id: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
+    };
+    server
+        .post("/api/v1/transactions")
+        .json(&overpayment)
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let validation_resp = server.get(&format!("/api/v1/accounts/{}/validation", loan_id)).await;
+    validation_resp.assert_status_ok();
+    let validation: ApiResponse<serde_json::Value> = validation_resp.json();
+    let issues = validation.data["issues"].as_array().unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["code"], "LIABILITY_POSITIVE_BALANCE");
+    assert_eq!(validation.data["accepts_imports"], true);
+
+    server
+        .get("/api/v1/accounts/99999/validation")
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_similar_transactions() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Checking".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let mut category_ids = Vec::new();
+    for name in ["Groceries", "Household"] {
+        let response = server
+            .post("/api/v1/categories")
+            .json(&serde_json::json!({ "name": name }))
+            .await;
+        response.assert_status(StatusCode::CREATED);
+        let body: ApiResponse<serde_json::Value> = response.json();
+        category_ids.push(body.data["id"].as_i64().unwrap() as i32);
+    }
+    let (groceries_id, household_id) = (category_ids[0], category_ids[1]);
+
+    server
+        .post("/api/v1/merchants")
+        .json(&serde_json::json!({ "pattern": "tesco", "name": "Tesco" }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let rows = [
+        ("POS TESCO STORES 4411", -5200, 3, Some(groceries_id)),
+        ("Tesco Express 0192", -4800, 10, Some(groceries_id)),
+        ("TESCO EXTRA 77", -25000, 17, Some(household_id)),
+        ("Shell 2231", -5000, 20, Some(household_id)),
+        ("CARD PAYMENT TESCO 1234", -5000, 24, None),
+    ];
+    let mut ids = Vec::new();
+    for (name, cents, day, category_id) in rows {
+        let request = CreateTransactionRequest {
+            name: name.to_string(),
+            description: None,
+            amount: Decimal::new(cents, 2),
+            date: NaiveDate::from_ymd_opt(2025, 4, day).unwrap(),
+            include_in_statistics: Some(true),
+            target_account_id: account_id,
+            source_account_id: None,
+            ledger_name: None,
+            linked_import_id: None,
+            category_id,
+            is_simulated: Some(false),
+            scenario_id: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
+        };
+        let response = server.post("/api/v1/transactions").json(&request).await;
+        response.assert_status(StatusCode::CREATED);
+        let body: ApiResponse<serde_json::Value> = response.json();
+        ids.push(body.data["id"].as_i64().unwrap());
+    }
+
+    let response = server.get(&format!("/api/v1/transactions/{}/similar", ids[4])).await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["merchant"]["name"], "Tesco");
+    assert_eq!(body.data["suggested_category_id"], groceries_id);
+    assert_eq!(body.data["suggested_category_name"], "Groceries");
+    assert_eq!(body.data["suggested_category_count"], 2);
+    assert_eq!(body.data["categorized_count"], 3);
+
+    // Only Tesco payments, matching amounts first and then newest first
+    let similar: Vec<i64> = body.data["transactions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|txn| txn["id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(similar, vec![ids[1], ids[0], ids[2]]);
+    assert_eq!(body.data["transactions"][2]["amount_matches"], false);
+
+    let limited: ApiResponse<serde_json::Value> = server
+        .get(&format!("/api/v1/transactions/{}/similar?limit=1", ids[4]))
+        .await
+        .json();
+    assert_eq!(limited.data["transactions"].as_array().unwrap().len(), 1);
+    assert_eq!(limited.data["suggested_category_id"], groceries_id);
+
+    server
+        .get("/api/v1/transactions/99999/similar")
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_active_scenario_preference() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Checking".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let scenario_resp = server
+        .post("/api/v1/scenarios")
+        .json(&serde_json::json!({ "name": "New car" }))
+        .await;
+    scenario_resp.assert_status(StatusCode::CREATED);
+    let scenario_body: ApiResponse<serde_json::Value> = scenario_resp.json();
+    let scenario_id = scenario_body.data["id"].as_i64().unwrap() as i32;
+
+    let transactions = [
+        (Decimal::from(1000), NaiveDate::from_ymd_opt(2025, 1, 5).unwrap(), None),
+        (Decimal::from(-300), NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(), Some(scenario_id)),
+    ];
+    for (amount, date, scenario) in transactions {
+        let request = CreateTransactionRequest {
+            name: "Transaction".to_string(),
+            description: None,
+            amount,
+            date,
+            include_in_statistics: Some(true),
+            target_account_id: account_id,
+            source_account_id: None,
+            ledger_name: None,
+            linked_import_id: None,
+            category_id: None,
+            is_simulated: Some(scenario.is_some()),
+            scenario_id: scenario,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
+        };
+        server
+            .post("/api/v1/transactions")
+            .json(&request)
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+
+    let timeseries_url = format!(
+        "/api/v1/accounts/{}/timeseries?start_date=2025-01-01&end_date=2025-03-01",
+        account_id
+    );
+    let last_balance = |body: ApiResponse<serde_json::Value>| {
+        let points = body.data["data_points"].as_array().unwrap().clone();
+        points.last().unwrap()["balance"].as_str().unwrap().parse::<Decimal>().unwrap()
+    };
+
+    let baseline: ApiResponse<serde_json::Value> = server.get(&timeseries_url).await.json();
+    assert_eq!(last_balance(baseline), Decimal::from(1000));
+
+    let settings: ApiResponse<serde_json::Value> = server.get("/api/v1/users/1/settings").await.json();
+    assert!(settings.data["active_scenario_id"].is_null());
+
+    let update = server
+        .put("/api/v1/users/1/settings")
+        .json(&serde_json::json!({ "active_scenario_id": scenario_id }))
+        .await;
+    update.assert_status_ok();
+
+    // Same request, no scenario_id: the owner's active scenario applies and the cached baseline isn't reused
+    let with_scenario: ApiResponse<serde_json::Value> = server.get(&timeseries_url).await.json();
+    assert_eq!(last_balance(with_scenario), Decimal::from(700));
+
+    let all_accounts: ApiResponse<serde_json::Value> = server
+        .get("/api/v1/accounts/timeseries?start_date=2025-01-01&end_date=2025-03-01")
+        .await
+        .json();
+    assert_eq!(last_balance(all_accounts), Decimal::from(700));
+
+    server
+        .put("/api/v1/users/1/settings")
+        .json(&serde_json::json!({ "active_scenario_id": 99999 }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+
+    // Deleting the scenario clears the preference
+    server
+        .delete(&format!("/api/v1/scenarios/{}", scenario_id))
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+    let settings: ApiResponse<serde_json::Value> = server.get("/api/v1/users/1/settings").await.json();
+    assert!(settings.data["active_scenario_id"].is_null());
+
+    let back_to_baseline: ApiResponse<serde_json::Value> = server.get(&timeseries_url).await.json();
+    assert_eq!(last_balance(back_to_baseline), Decimal::from(1000));
+
+    server
+        .get("/api/v1/users/99999/settings")
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_scenario_activation_date() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Checking".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let scenario_resp = server
+        .post("/api/v1/scenarios")
+        .json(&serde_json::json!({ "name": "New job", "activation_date": "2025-03-01" }))
+        .await;
+    scenario_resp.assert_status(StatusCode::CREATED);
+    let scenario_body: ApiResponse<serde_json::Value> = scenario_resp.json();
+    let scenario_id = scenario_body.data["id"].as_i64().unwrap() as i32;
+    assert_eq!(scenario_body.data["activation_date"], "2025-03-01");
+
+    let transactions = [
+        (Decimal::from(1000), NaiveDate::from_ymd_opt(2025, 1, 5).unwrap(), None),
+        (Decimal::from(500), NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(), Some(scenario_id)),
+        (Decimal::from(700), NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(), Some(scenario_id)),
+    ];
+    for (amount, date, scenario) in transactions {
+        let request = CreateTransactionRequest {
+            name: "Salary".to_string(),
+            description: None,
+            amount,
+            date,
+            include_in_statistics: Some(true),
+            target_account_id: account_id,
+            source_account_id: None,
+            ledger_name: None,
+            linked_import_id: None,
+            category_id: None,
+            is_simulated: Some(scenario.is_some()),
+            scenario_id: scenario,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
+        };
+        server
+            .post("/api/v1/transactions")
+            .json(&request)
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+
+    let timeseries_url = format!(
+        "/api/v1/accounts/{}/timeseries?start_date=2025-01-01&end_date=2025-04-01&scenario_id={}",
+        account_id, scenario_id
+    );
+    let last_balance = |body: ApiResponse<serde_json::Value>| {
+        let points = body.data["data_points"].as_array().unwrap().clone();
+        points.last().unwrap()["balance"].as_str().unwrap().parse::<Decimal>().unwrap()
+    };
+
+    // The February simulated salary predates the activation date and is ignored
+    let scheduled: ApiResponse<serde_json::Value> = server.get(&timeseries_url).await.json();
+    assert_eq!(last_balance(scheduled), Decimal::from(1700));
+
+    server
+        .put(&format!("/api/v1/scenarios/{}", scenario_id))
+        .json(&serde_json::json!({ "clear_activation_date": true }))
+        .await
+        .assert_status_ok();
+
+    let unscheduled: ApiResponse<serde_json::Value> = server.get(&timeseries_url).await.json();
+    assert_eq!(last_balance(unscheduled), Decimal::from(2200));
+}
+
+#[tokio::test]
+async fn test_seed_starter_categories() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    // A category the user already created by hand is kept and reused as a parent
+    server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Food", "description": "Mine" }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let first = server.post("/api/v1/categories/seed").await;
+    first.assert_status_ok();
+    let first_body: ApiResponse<serde_json::Value> = first.json();
+    assert_eq!(first_body.data["locale"], "en");
+    assert_eq!(first_body.data["existing_count"], 1);
+    let created = first_body.data["created"].as_array().unwrap();
+    assert!(created.iter().any(|c| c["name"] == "Groceries"));
+    assert!(!created.iter().any(|c| c["name"] == "Food"));
+
+    let categories: ApiResponse<serde_json::Value> = server.get("/api/v1/categories").await.json();
+    let categories = categories.data.as_array().unwrap().clone();
+    let food = categories.iter().find(|c| c["name"] == "Food").unwrap();
+    assert_eq!(food["description"], "Mine");
+    let groceries = categories.iter().find(|c| c["name"] == "Groceries").unwrap();
+    assert_eq!(groceries["parent_id"], food["id"]);
+
+    // Seeding again adds nothing
+    let second: ApiResponse<serde_json::Value> = server.post("/api/v1/categories/seed").await.json();
+    assert!(second.data["created"].as_array().unwrap().is_empty());
+    assert_eq!(second.data["existing_count"].as_u64().unwrap() as usize, categories.len());
+
+    let czech: ApiResponse<serde_json::Value> = server.post("/api/v1/categories/seed?locale=cs").await.json();
+    assert!(czech.data["created"].as_array().unwrap().iter().any(|c| c["name"] == "Potraviny"));
+
+    server
+        .post("/api/v1/categories/seed?locale=xx")
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_account_sparkline() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Checking".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let today = chrono::Local::now().date_naive();
+    for (amount, days_ago) in [(1000, 80), (-250, 10)] {
+        let request = CreateTransactionRequest {
+            name: "Transaction".to_string(),
+            description: None,
+            amount: Decimal::from(amount),
+            date: today - chrono::Duration::days(days_ago),
+            include_in_statistics: Some(true),
+            target_account_id: account_id,
+            source_account_id: None,
+            ledger_name: None,
+            linked_import_id: None,
+            category_id: None,
+            is_simulated: None,
+            scenario_id: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
+        };
+        server
+            .post("/api/v1/transactions")
+            .json(&request)
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+
+    let response = server.get(&format!("/api/v1/accounts/{}/sparkline", account_id)).await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    let points = body.data["points"].as_array().unwrap();
+    assert!(!points.is_empty() && points.len() <= 30);
+    assert_eq!(body.data["end_date"], today.format("%Y-%m-%d").to_string());
+    let last = points.last().unwrap()["balance"].as_str().unwrap().parse::<Decimal>().unwrap();
+    assert_eq!(last, Decimal::from(750));
+    let max = body.data["max_balance"].as_str().unwrap().parse::<Decimal>().unwrap();
+    assert_eq!(max, Decimal::from(1000));
+
+    server
+        .get(&format!("/api/v1/accounts/{}/sparkline?days=3", account_id))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    server
+        .get("/api/v1/accounts/99999/sparkline")
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_user_theme_setting() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let settings: ApiResponse<serde_json::Value> = server.get("/api/v1/users/1/settings").await.json();
+    assert!(settings.data["theme"].is_null());
+
+    let update = server
+        .put("/api/v1/users/1/settings")
+        .json(&serde_json::json!({ "active_scenario_id": null, "theme": "dark" }))
+        .await;
+    update.assert_status_ok();
+
+    let settings: ApiResponse<serde_json::Value> = server.get("/api/v1/users/1/settings").await.json();
+    assert_eq!(settings.data["theme"], "dark");
+
+    server
+        .put("/api/v1/users/1/settings")
+        .json(&serde_json::json!({ "active_scenario_id": null, "theme": "neon" }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+
+    let settings: ApiResponse<serde_json::Value> = server.get("/api/v1/users/1/settings").await.json();
+    assert_eq!(settings.data["theme"], "dark");
+}
+
+#[tokio::test]
+async fn test_submit_diagnostic_report() {
+    use model::entities::diagnostic_report;
+    use sea_orm::EntityTrait;
+
+    let app_state = setup_test_app_state().await;
+    let app = finrust::router::create_test_router(app_state.clone());
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .post("/api/v1/diagnostics")
+        .json(&serde_json::json!({
+            "message": "panicked at src/components/transactions/view.rs:42:10: index out of bounds",
+            "route": "/transactions",
+            "app_version": "0.1.0",
+            "user_agent": "Mozilla/5.0",
+            "api_calls": [
+                { "method": "GET", "endpoint": "/accounts", "status": 200, "duration_ms": 12.5, "error": null },
+                { "method": "GET", "endpoint": "/transactions", "status": null, "duration_ms": null, "error": "Request failed" }
+            ]
+        }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let body: ApiResponse<serde_json::Value> = response.json();
+    let report_id = body.data["id"].as_i64().unwrap() as i32;
+
+    let stored = diagnostic_report::Entity::find_by_id(report_id)
+        .one(&app_state.db)
+        .await
+        .expect("Failed to fetch diagnostic report")
+        .expect("Diagnostic report was not stored");
+    assert_eq!(stored.route.as_deref(), Some("/transactions"));
+    let api_calls: Vec<serde_json::Value> = serde_json::from_str(&stored.api_calls).unwrap();
+    assert_eq!(api_calls.len(), 2);
+    assert_eq!(api_calls[1]["error"], "Request failed");
+
+    // The API call log is optional
+    server
+        .post("/api/v1/diagnostics")
+        .json(&serde_json::json!({ "message": "render error" }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    server
+        .post("/api/v1/diagnostics")
+        .json(&serde_json::json!({ "message": "" }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_recurring_transaction_approval_workflow() {
+    use axum::http::{header, HeaderValue};
+    use finrust::helpers::auth::hash_password;
+    use model::entities::{account_allowed_user, account_share, user};
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+    let app_state = setup_test_app_state().await;
+    user::ActiveModel {
+        username: Set("test_user3".to_string()),
+        ..Default::default()
+    }
+    .insert(&app_state.db)
+    .await
+    .expect("Failed to create test user 3");
+    for user_id in [1, 2, 3] {
+        let existing = user::Entity::find_by_id(user_id).one(&app_state.db).await.unwrap().unwrap();
+        let mut active: user::ActiveModel = existing.into();
+        active.password_hash = Set(Some(hash_password("correct horse").unwrap()));
+        active.update(&app_state.db).await.unwrap();
+    }
+    let app = finrust::router::create_test_router(app_state.clone());
+    let server = TestServer::new(app).unwrap();
+
+    let mut tokens = Vec::new();
+    for username in ["test_user1", "test_user2", "test_user3"] {
+        let body: ApiResponse<serde_json::Value> = server
+            .post("/api/v1/auth/login")
+            .json(&serde_json::json!({ "username": username, "password": "correct horse" }))
+            .await
+            .json();
+        tokens.push(HeaderValue::from_str(&format!("Bearer {}", body.data["token"].as_str().unwrap())).unwrap());
+    }
+    let (owner, member, reader) = (tokens[0].clone(), tokens[1].clone(), tokens[2].clone());
+
+    let account_request = CreateAccountRequest {
+        name: "Household".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server
+        .post("/api/v1/accounts")
+        .add_header(header::AUTHORIZATION, owner.clone())
+        .json(&account_request)
+        .await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+    let policy_url = format!("/api/v1/accounts/{}/recurring-approval-policy", account_id);
+
+    // Approvals need a second member
+    let response = server
+        .put(&policy_url)
+        .add_header(header::AUTHORIZATION, owner.clone())
+        .json(&serde_json::json!({ "recurring_approval_threshold": "500" }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "ACCOUNT_NOT_SHARED");
+
+    account_allowed_user::ActiveModel {
+        account_id: Set(account_id),
+        user_id: Set(2),
+    }
+    .insert(&app_state.db)
+    .await
+    .expect("Failed to share account");
+    account_share::ActiveModel {
+        account_id: Set(account_id),
+        user_id: Set(3),
+        permission: Set(account_share::SharePermission::Read),
+        created_at: Set(chrono::Utc::now().naive_utc()),
+    }
+    .insert(&app_state.db)
+    .await
+    .expect("Failed to share account read-only");
+
+    // Only the owner sets the policy
+    let response = server
+        .put(&policy_url)
+        .add_header(header::AUTHORIZATION, member.clone())
+        .json(&serde_json::json!({ "recurring_approval_threshold": "1" }))
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "ACCOUNT_FORBIDDEN");
+    server
+        .put(&policy_url)
+        .add_header(header::AUTHORIZATION, owner.clone())
+        .json(&serde_json::json!({ "recurring_approval_threshold": "500" }))
+        .await
+        .assert_status_ok();
+
+    let create = |name: &str, amount: &str| {
+        serde_json::json!({
+            "name": name,
+            "amount": amount,
+            "start_date": "2024-01-01",
+            "period": "Monthly",
+            "target_account_id": account_id,
+        })
+    };
+
+    let response = server
+        .post("/api/v1/recurring-transactions")
+        .add_header(header::AUTHORIZATION, owner.clone())
+        .json(&create("Streaming", "-15"))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["approval_status"], "Approved");
+
+    let response = server
+        .post("/api/v1/recurring-transactions")
+        .add_header(header::AUTHORIZATION, owner.clone())
+        .json(&create("Rent", "-1200"))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["approval_status"], "PendingApproval");
+    let rent_id = body.data["id"].as_i64().unwrap();
+
+    // The reviewer is the caller: anonymous requests, the creator and read-only members can't approve
+    server
+        .post(&format!("/api/v1/recurring-transactions/{}/approve", rent_id))
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    let response = server
+        .post(&format!("/api/v1/recurring-transactions/{}/approve", rent_id))
+        .add_header(header::AUTHORIZATION, owner.clone())
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "SELF_APPROVAL");
+
+    let response = server
+        .post(&format!("/api/v1/recurring-transactions/{}/approve", rent_id))
+        .add_header(header::AUTHORIZATION, reader.clone())
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "NOT_A_MEMBER");
+
+    let response = server
+        .post(&format!("/api/v1/recurring-transactions/{}/approve", rent_id))
+        .add_header(header::AUTHORIZATION, member.clone())
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["approval_status"], "Approved");
+    assert_eq!(body.data["reviewed_by_user_id"], 2);
+
+    server
+        .post(&format!("/api/v1/recurring-transactions/{}/reject", rent_id))
+        .add_header(header::AUTHORIZATION, member.clone())
+        .await
+        .assert_status(StatusCode::CONFLICT);
+
+    let response = server
+        .post("/api/v1/recurring-transactions")
+        .add_header(header::AUTHORIZATION, owner.clone())
+        .json(&create("New car", "-800"))
+        .await;
+    let body: ApiResponse<serde_json::Value> = response.json();
+    let car_id = body.data["id"].as_i64().unwrap();
+    let response = server
+        .post(&format!("/api/v1/recurring-transactions/{}/reject", car_id))
+        .add_header(header::AUTHORIZATION, member.clone())
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["approval_status"], "Rejected");
+
+    // Turning the policy off leaves existing rules as they are
+    server
+        .put(&policy_url)
+        .add_header(header::AUTHORIZATION, owner.clone())
+        .json(&serde_json::json!({ "recurring_approval_threshold": null }))
+        .await
+        .assert_status_ok();
+    let response = server
+        .post("/api/v1/recurring-transactions")
+        .add_header(header::AUTHORIZATION, owner.clone())
+        .json(&create("Holiday", "-3000"))
+        .await;
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["approval_status"], "Approved");
+    let body: ApiResponse<serde_json::Value> = server
+        .get(&format!("/api/v1/recurring-transactions/{}", car_id))
+        .add_header(header::AUTHORIZATION, owner.clone())
+        .await
+        .json();
+    assert_eq!(body.data["approval_status"], "Rejected");
+}
+
+#[tokio::test]
+async fn test_tag_spending_limits() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Everyday".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_resp = server.post("/api/v1/accounts").json(&account_request).await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let tag_resp = server
+        .post("/api/v1/tags")
+        .json(&serde_json::json!({ "name": "Eating out", "monthly_limit": "200" }))
+        .await;
+    tag_resp.assert_status(StatusCode::CREATED);
+    let tag_body: ApiResponse<serde_json::Value> = tag_resp.json();
+    let eating_out_id = tag_body.data["id"].as_i64().unwrap();
+    assert_eq!(tag_body.data["monthly_limit"].as_str().unwrap().parse::<Decimal>().unwrap(), Decimal::from(200));
+
+    let tag_resp = server
+        .post("/api/v1/tags")
+        .json(&serde_json::json!({ "name": "Restaurants", "parent_id": eating_out_id }))
+        .await;
+    let tag_body: ApiResponse<serde_json::Value> = tag_resp.json();
+    let restaurants_id = tag_body.data["id"].as_i64().unwrap();
+
+    server
+        .post("/api/v1/tags")
+        .json(&serde_json::json!({ "name": "Broken", "monthly_limit": "-5" }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+
+    let body: ApiResponse<serde_json::Value> = server.get("/api/v1/tags/limits?year=2025&month=3").await.json();
+    assert!(body.data["limits"][0]["spent"].as_str().unwrap().parse::<Decimal>().unwrap().is_zero());
+    assert_eq!(body.data["limits"][0]["level"], "WithinLimit");
+
+    let mut transaction_ids = Vec::new();
+    for (name, amount, day) in [("Pizza", -9000, 3), ("Sushi", -8000, 14), ("Refund", 2000, 15)] {
+        let txn = CreateTransactionRequest {
+            name: name.to_string(),
+            description: None,
+            amount: Decimal::new(amount, 2),
+            date: NaiveDate::from_ymd_opt(2025, 3, day).unwrap(),
+            include_in_statistics: Some(true),
+            target_account_id: account_id,
+            source_account_id: None,
+            ledger_name: None,
+            linked_import_id: None,
+            category_id: None,
+            is_simulated: None,
+            scenario_id: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
+        };
+        let resp = server.post("/api/v1/transactions").json(&txn).await;
+        resp.assert_status(StatusCode::CREATED);
+        let body: ApiResponse<serde_json::Value> = resp.json();
+        transaction_ids.push(body.data["id"].as_i64().unwrap());
+    }
+    server
+        .post(&format!("/api/v1/tags/{}/assign", restaurants_id))
+        .json(&serde_json::json!({ "transaction_ids": transaction_ids }))
+        .await
+        .assert_status_ok();
+
+    // Expenses tagged with the child tag count towards the parent's limit; income does not
+    let response = server.get("/api/v1/tags/limits?year=2025&month=3").await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    let limits = body.data["limits"].as_array().unwrap();
+    assert_eq!(limits.len(), 1);
+    assert_eq!(limits[0]["tag_name"], "Eating out");
+    assert_eq!(limits[0]["spent"].as_str().unwrap().parse::<Decimal>().unwrap(), Decimal::from(170));
+    assert_eq!(limits[0]["level"], "Warning");
+
+    let body: ApiResponse<serde_json::Value> = server.get("/api/v1/tags/limits?year=2025&month=4").await.json();
+    assert_eq!(body.data["limits"][0]["level"], "WithinLimit");
+
+    // Lowering the limit below the spending flags it as exceeded; zero removes it
+    server
+        .put(&format!("/api/v1/tags/{}", eating_out_id))
+        .json(&serde_json::json!({ "monthly_limit": "150" }))
+        .await
+        .assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = server.get("/api/v1/tags/limits?year=2025&month=3").await.json();
+    assert_eq!(body.data["limits"][0]["level"], "Exceeded");
+    assert_eq!(body.data["limits"][0]["remaining"].as_str().unwrap().parse::<Decimal>().unwrap(), Decimal::from(-20));
+
+    server
+        .put(&format!("/api/v1/tags/{}", eating_out_id))
+        .json(&serde_json::json!({ "monthly_limit": "0" }))
+        .await
+        .assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = server.get("/api/v1/tags/limits?year=2025&month=3").await.json();
+    assert!(body.data["limits"].as_array().unwrap().is_empty());
+
+    server
+        .get("/api/v1/tags/limits?year=2025&month=13")
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_audit_chain_detects_tampering() {
+    use model::entities::audit_entry;
+    use sea_orm::{ActiveModelTrait, EntityTrait, QueryOrder, Set};
+
+    let mut app_state = setup_test_app_state().await;
+    app_state.audit_chain = true;
+    let app = finrust::router::create_test_router(app_state.clone());
+    let server = TestServer::new(app).unwrap();
+
+    let body: ApiResponse<serde_json::Value> = server.get("/api/v1/audit/verify").await.json();
+    assert_eq!(body.data["valid"], true);
+    assert_eq!(body.data["entries_checked"], 0);
+
+    for name in ["Audited A", "Audited B", "Audited C"] {
+        server
+            .post("/api/v1/tags")
+            .json(&serde_json::json!({ "name": name }))
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+    // Failed requests change nothing and are not recorded
+    server
+        .post("/api/v1/tags")
+        .json(&serde_json::json!({ "name": "Audited A" }))
+        .await
+        .assert_status(StatusCode::CONFLICT);
+
+    let response = server.get("/api/v1/audit/verify").await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["valid"], true);
+    assert_eq!(body.data["entries_checked"], 3);
+
+    let entries = audit_entry::Entity::find()
+        .order_by_asc(audit_entry::Column::Id)
+        .all(&app_state.db)
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].method, "POST");
+    assert_eq!(entries[0].path, "/api/v1/tags");
+    assert!(entries[0].payload.contains("Audited A"));
+    assert_eq!(entries[1].previous_hash, entries[0].hash);
+    assert_eq!(body.data["head_hash"], entries[2].hash.as_str());
+
+    // Rewriting the payload of the second entry is detected there
+    let mut tampered: audit_entry::ActiveModel = entries[1].clone().into();
+    tampered.payload = Set(r#"{"name":"Something else"}"#.to_string());
+    tampered.update(&app_state.db).await.unwrap();
+
+    let body: ApiResponse<serde_json::Value> = server.get("/api/v1/audit/verify").await.json();
+    assert_eq!(body.data["valid"], false);
+    assert_eq!(body.data["first_invalid_id"], entries[1].id);
+
+    // Deleting it instead breaks the link of the entry after it
+    audit_entry::Entity::delete_by_id(entries[1].id)
+        .exec(&app_state.db)
+        .await
+        .unwrap();
+    let body: ApiResponse<serde_json::Value> = server.get("/api/v1/audit/verify").await.json();
+    assert_eq!(body.data["valid"], false);
+    assert_eq!(body.data["first_invalid_id"], entries[2].id);
+}
+
+#[tokio::test]
+async fn test_audit_chain_redacts_credentials() {
+    use finrust::helpers::auth::hash_password;
+    use model::entities::{audit_entry, user};
+    use sea_orm::{ActiveModelTrait, EntityTrait, QueryOrder, Set};
+
+    let mut app_state = setup_test_app_state().await;
+    app_state.audit_chain = true;
+    let test_user = user::Entity::find_by_id(1).one(&app_state.db).await.unwrap().unwrap();
+    let mut active: user::ActiveModel = test_user.into();
+    active.password_hash = Set(Some(hash_password("correct horse").unwrap()));
+    active.update(&app_state.db).await.unwrap();
+    let server = TestServer::new(finrust::router::create_test_router(app_state.clone())).unwrap();
+
+    server
+        .post("/api/v1/auth/login")
+        .json(&serde_json::json!({ "username": "test_user1", "password": "correct horse" }))
+        .await
+        .assert_status_ok();
+    let account: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/accounts")
+        .json(&serde_json::json!({ "name": "Audited", "currency_code": "EUR", "owner_id": 1 }))
+        .await
+        .json();
+    let account_id = account.data["id"].as_i64().unwrap();
+    server
+        .post(&format!("/api/v1/accounts/{}/documents", account_id))
+        .json(&serde_json::json!({ "file_name": "contract.pdf", "content_base64": "c2VjcmV0IGNvbnRyYWN0" }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let entries = audit_entry::Entity::find()
+        .order_by_asc(audit_entry::Column::Id)
+        .all(&app_state.db)
+        .await
+        .unwrap();
+    let paths: Vec<&str> = entries.iter().map(|entry| entry.path.as_str()).collect();
+    assert_eq!(paths, ["/api/v1/accounts".to_string(), format!("/api/v1/accounts/{}/documents", account_id)]);
+    assert!(entries[0].payload.contains("Audited"));
+    let document: serde_json::Value = serde_json::from_str(&entries[1].payload).unwrap();
+    assert_eq!(document["file_name"], "contract.pdf");
+    assert_eq!(document["content_base64"], "[redacted]");
+
+    let body: ApiResponse<serde_json::Value> = server.get("/api/v1/audit/verify").await.json();
+    assert_eq!(body.data["valid"], true);
+}
+
+#[tokio::test]
+async fn test_schema_drift_detection() {
+    use finrust::helpers::schema_drift::detect_schema_drift;
+    use sea_orm::ConnectionTrait;
+
+    // Drift detection reads SQLite's catalog, whatever backend the suite runs on
+    let db = common::setup_sqlite_test_db().await;
+
+    let report = detect_schema_drift(&db).await.unwrap();
+    assert!(report.is_clean(), "fresh database reported drift: {:?}", report);
+
+    // Simulate a half-applied migration and a manual index
+    db.execute_unprepared("ALTER TABLE tags DROP COLUMN monthly_limit").await.unwrap();
+    db.execute_unprepared("CREATE INDEX idx_manual ON accounts (name)").await.unwrap();
+    db.execute_unprepared("DELETE FROM seaql_migrations WHERE version = 'm20261015_000013_add_audit_entries'")
+        .await
+        .unwrap();
+    db.execute_unprepared("DROP TABLE audit_entries").await.unwrap();
+
+    let report = detect_schema_drift(&db).await.unwrap();
+    assert!(!report.is_clean());
+    assert_eq!(report.pending_migrations, vec!["m20261015_000013_add_audit_entries".to_string()]);
+    assert!(report.unknown_migrations.is_empty());
+
+    let problems: Vec<String> = report.drift.iter().map(|drift| drift.to_string()).collect();
+    assert!(problems.contains(&"tags: missing column `monthly_limit`".to_string()), "{:?}", problems);
+    assert!(problems.contains(&"accounts: unexpected index on (name)".to_string()), "{:?}", problems);
+    assert!(problems.contains(&"audit_entries: missing table".to_string()), "{:?}", problems);
+    assert_eq!(problems.len(), 3, "{:?}", problems);
+}
+
+#[tokio::test]
+async fn test_exchange_rates() {
+    use finrust::handlers::exchange_rates::ExchangeRateResponse;
+    use finrust::helpers::fx::rate_on;
+
+    let app_state = setup_test_app_state().await;
+    let app = finrust::router::create_test_router(app_state.clone());
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .post("/api/v1/exchange-rates")
+        .json(&serde_json::json!({
+            "base_currency": "eur",
+            "quote_currency": "CZK",
+            "date": "2026-10-01",
+            "rate": "25.1"
+        }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let body: ApiResponse<ExchangeRateResponse> = response.json();
+    assert_eq!(body.data.base_currency, "EUR");
+    let first_id = body.data.id;
+
+    // Same pair and day replaces the stored rate
+    let response = server
+        .post("/api/v1/exchange-rates")
+        .json(&serde_json::json!({
+            "base_currency": "EUR",
+            "quote_currency": "CZK",
+            "date": "2026-10-01",
+            "rate": "25"
+        }))
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<ExchangeRateResponse> = response.json();
+    assert_eq!(body.data.id, first_id);
+    assert_eq!(body.data.rate, Decimal::new(25, 0));
+
+    server
+        .post("/api/v1/exchange-rates")
+        .json(&serde_json::json!({
+            "base_currency": "EUR",
+            "quote_currency": "CZK",
+            "date": "2026-10-05",
+            "rate": "24.5"
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    server
+        .post("/api/v1/exchange-rates")
+        .json(&serde_json::json!({
+            "base_currency": "USD",
+            "quote_currency": "CZK",
+            "date": "2026-10-05",
+            "rate": "21"
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    for invalid in [
+        serde_json::json!({ "base_currency": "EURO", "quote_currency": "CZK", "date": "2026-10-01", "rate": "1" }),
+        serde_json::json!({ "base_currency": "EUR", "quote_currency": "EUR", "date": "2026-10-01", "rate": "1" }),
+        serde_json::json!({ "base_currency": "EUR", "quote_currency": "USD", "date": "2026-10-01", "rate": "0" }),
+    ] {
+        server
+            .post("/api/v1/exchange-rates")
+            .json(&invalid)
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    let body: ApiResponse<Vec<ExchangeRateResponse>> = server
+        .get("/api/v1/exchange-rates")
+        .add_query_param("base_currency", "eur")
+        .await
+        .json();
+    let dates: Vec<String> = body.data.iter().map(|rate| rate.date.to_string()).collect();
+    assert_eq!(dates, vec!["2026-10-05", "2026-10-01"]);
+
+    let body: ApiResponse<Vec<ExchangeRateResponse>> = server
+        .get("/api/v1/exchange-rates")
+        .add_query_param("date_to", "2026-10-04")
+        .await
+        .json();
+    assert_eq!(body.data.len(), 1);
+
+    // Lookups use the latest rate on or before the day, or the inverse pair
+    let date = |day: u32| NaiveDate::from_ymd_opt(2026, 10, day).unwrap();
+    assert_eq!(rate_on(&app_state.db, "EUR", "CZK", date(3)).await.unwrap(), Some(Decimal::new(25, 0)));
+    assert_eq!(rate_on(&app_state.db, "EUR", "CZK", date(9)).await.unwrap(), Some(Decimal::new(245, 1)));
+    assert_eq!(rate_on(&app_state.db, "CZK", "EUR", date(3)).await.unwrap(), Some(Decimal::new(4, 2)));
+    assert_eq!(rate_on(&app_state.db, "EUR", "CZK", NaiveDate::from_ymd_opt(2026, 9, 30).unwrap()).await.unwrap(), None);
+    assert_eq!(rate_on(&app_state.db, "czk", "CZK", date(1)).await.unwrap(), Some(Decimal::ONE));
+}
+
+#[tokio::test]
+async fn test_exchange_rate_provider_parsing() {
+    use finrust::tasks::exchange_rates::{parse_ecb_daily, parse_frankfurter};
+
+    let ecb = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gesmes:Envelope xmlns:gesmes="http://www.gesmes.org/xml/2002-08-01" xmlns="http://www.ecb.int/vocabulary/2002-08-01/eurofxref">
+    <gesmes:subject>Reference rates</gesmes:subject>
+    <Cube>
+        <Cube time='2026-10-14'>
+            <Cube currency='USD' rate='1.0856'/>
+            <Cube currency='CZK' rate='24.310'/>
+        </Cube>
+    </Cube>
+</gesmes:Envelope>"#;
+    let published = parse_ecb_daily(ecb).unwrap();
+    assert_eq!(published.base_currency, "EUR");
+    assert_eq!(published.date, NaiveDate::from_ymd_opt(2026, 10, 14).unwrap());
+    assert_eq!(
+        published.rates,
+        vec![("USD".to_string(), Decimal::new(10856, 4)), ("CZK".to_string(), Decimal::new(24310, 3))]
+    );
+    assert!(parse_ecb_daily("<html>maintenance</html>").is_err());
+
+    let frankfurter = r#"{"amount":1.0,"base":"CZK","date":"2026-10-14","rates":{"USD":0.04466,"EUR":0.04113}}"#;
+    let published = parse_frankfurter(frankfurter).unwrap();
+    assert_eq!(published.base_currency, "CZK");
+    assert_eq!(
+        published.rates,
+        vec![("EUR".to_string(), Decimal::new(4113, 5)), ("USD".to_string(), Decimal::new(4466, 5))]
+    );
+    assert!(parse_frankfurter(r#"{"message":"not found"}"#).is_err());
+
+    // Manual refresh needs a configured provider
+    let server = TestServer::new(setup_test_app().await).unwrap();
+    let response = server.post("/api/v1/exchange-rates/refresh").await;
+    response.assert_status(StatusCode::CONFLICT);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["code"], "NOT_CONFIGURED");
+}
+
+#[tokio::test]
+async fn test_as_of_reads_book_snapshots() {
+    use chrono::{SecondsFormat, Utc};
+    use finrust::helpers::time_travel::record_book_snapshot;
+    use model::entities::book_snapshot;
+    use sea_orm::{ActiveModelTrait, EntityTrait, IntoActiveModel, Set};
+
+    let app_state = setup_test_app_state().await;
+    let server = TestServer::new(finrust::router::create_test_router(app_state.clone())).unwrap();
+
+    let response = server
+        .post("/api/v1/accounts")
+        .json(&serde_json::json!({ "name": "Old name", "currency_code": "CZK", "owner_id": 1 }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let account_id = response.json::<serde_json::Value>()["data"]["id"].as_i64().unwrap();
+
+    // No snapshot yet
+    let response = server
+        .get("/api/v1/accounts")
+        .add_query_param("as_of", Utc::now().to_rfc3339())
+        .await;
+    response.assert_status(StatusCode::CONFLICT);
+    assert_eq!(response.json::<serde_json::Value>()["code"], "HISTORY_UNAVAILABLE");
+
+    let snapshot = record_book_snapshot(&app_state.db).await.unwrap().expect("first snapshot");
+    assert!(record_book_snapshot(&app_state.db).await.unwrap().is_none(), "unchanged books are not snapshotted again");
+
+    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    let before_rename = Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true);
+    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+    server
+        .put(&format!("/api/v1/accounts/{}", account_id))
+        .json(&serde_json::json!({ "name": "New name" }))
+        .await
+        .assert_status_ok();
+    record_book_snapshot(&app_state.db).await.unwrap().expect("snapshot after the rename");
+
+    let current: serde_json::Value = server.get(&format!("/api/v1/accounts/{}", account_id)).await.json();
+    assert_eq!(current["data"]["name"], "New name");
+
+    let response = server
+        .get(&format!("/api/v1/accounts/{}", account_id))
+        .add_query_param("as_of", &before_rename)
+        .await;
+    response.assert_status_ok();
+    assert!(response.headers().contains_key("x-as-of-snapshot"));
+    assert!(!response.headers().contains_key("x-as-of-stale-since"), "a later snapshot exists");
+    let historical: serde_json::Value = response.json();
+    assert_eq!(historical["data"]["name"], "Old name");
+
+    // Past the newest snapshot the response is marked as possibly stale
+    let response = server
+        .get(&format!("/api/v1/accounts/{}", account_id))
+        .add_query_param("as_of", Utc::now().to_rfc3339())
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.header("x-as-of-stale-since"), response.header("x-as-of-snapshot"));
+    let latest: serde_json::Value = response.json();
+    assert_eq!(latest["data"]["name"], "New name");
+
+    // Statistics are served too, with "today" taken at the timestamp
+    let mut first = snapshot.into_active_model();
+    first.taken_at = Set(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap().and_hms_opt(12, 0, 0).unwrap());
+    first.update(&app_state.db).await.unwrap();
+    let response = server
+        .get("/api/v1/statistics/safe-to-spend")
+        .add_query_param("account_ids", account_id)
+        .add_query_param("as_of", "2026-01-02T08:00:00Z")
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["today"], "2026-01-02");
+    assert_eq!(body["data"]["account_ids"], serde_json::json!([account_id]));
+    assert_eq!(book_snapshot::Entity::find().all(&app_state.db).await.unwrap().len(), 2);
+
+    server
+        .get("/api/v1/accounts")
+        .add_query_param("as_of", "2000-01-01T00:00:00Z")
+        .await
+        .assert_status(StatusCode::CONFLICT);
+
+    server
+        .get("/api/v1/accounts")
+        .add_query_param("as_of", "yesterday")
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_book_snapshots_leave_out_documents_and_are_pruned() {
+    use chrono::{NaiveDateTime, Utc};
+    use finrust::helpers::time_travel::{prune_book_snapshots, record_book_snapshot};
+    use model::entities::{account_document, book_snapshot};
+    use sea_orm::{ActiveModelTrait, EntityTrait, QueryOrder, Set};
+
+    let app_state = setup_test_app_state().await;
+    let server = TestServer::new(finrust::router::create_test_router(app_state.clone())).unwrap();
+    let response = server
+        .post("/api/v1/accounts")
+        .json(&serde_json::json!({ "name": "Documents", "currency_code": "CZK", "owner_id": 1 }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let account_id = response.json::<serde_json::Value>()["data"]["id"].as_i64().unwrap() as i32;
+
+    let content = vec![7u8; 64 * 1024];
+    account_document::ActiveModel {
+        account_id: Set(account_id),
+        file_name: Set("contract.pdf".to_string()),
+        content_type: Set("application/pdf".to_string()),
+        size_bytes: Set(content.len() as i64),
+        sha256: Set("0".repeat(64)),
+        content: Set(content),
+        uploaded_at: Set(Utc::now().naive_utc()),
+        ..Default::default()
+    }
+    .insert(&app_state.db)
+    .await
+    .unwrap();
+    let snapshot = record_book_snapshot(&app_state.db).await.unwrap().expect("snapshot");
+    assert!(snapshot.archive.len() < 16 * 1024, "archive of {} bytes holds the document", snapshot.archive.len());
+
+    let response = server
+        .get(&format!("/api/v1/accounts/{}/documents", account_id))
+        .add_query_param("as_of", Utc::now().to_rfc3339())
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<serde_json::Value>()["data"][0]["file_name"], "contract.pdf");
+
+    // Only the last snapshot of each day older than a week is kept
+    let at = |value: &str| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap();
+    for taken_at in ["2026-01-01 08:00", "2026-01-01 20:00", "2026-01-02 08:00", "2026-01-09 08:00", "2026-01-09 09:00"] {
+        book_snapshot::ActiveModel {
+            taken_at: Set(at(taken_at)),
+            sha256: Set(taken_at.to_string()),
+            archive: Set(snapshot.archive.clone()),
+            ..Default::default()
+        }
+        .insert(&app_state.db)
+        .await
+        .unwrap();
+    }
+    assert_eq!(prune_book_snapshots(&app_state.db, at("2026-01-10 12:00")).await.unwrap(), 1);
+    let kept: Vec<NaiveDateTime> = book_snapshot::Entity::find()
+        .order_by_asc(book_snapshot::Column::TakenAt)
+        .all(&app_state.db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|snapshot| snapshot.taken_at)
+        .filter(|taken_at| taken_at.date() < at("2026-02-01 00:00").date())
+        .collect();
+    assert_eq!(
+        kept,
+        vec![at("2026-01-01 20:00"), at("2026-01-02 08:00"), at("2026-01-09 08:00"), at("2026-01-09 09:00")]
+    );
+}
+
+#[tokio::test]
+async fn test_budgets() {
+    use finrust::handlers::budgets::{BudgetResponse, BudgetStatusResponse};
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Everyday".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/accounts").json(&account_request).await.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let food: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Food" }))
+        .await
+        .json();
+    let food_id = food.data["id"].as_i64().unwrap() as i32;
+    let groceries: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Groceries", "parent_id": food_id }))
+        .await
+        .json();
+    let groceries_id = groceries.data["id"].as_i64().unwrap() as i32;
+
+    let response = server
+        .post("/api/v1/budgets")
+        .json(&serde_json::json!({
+            "category_id": food_id,
+            "period": "Monthly",
+            "amount": "300",
+            "rollover": true,
+            "start_date": "2026-01-01"
+        }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let body: ApiResponse<BudgetResponse> = response.json();
+    let budget_id = body.data.id;
+    assert_eq!(body.data.period, "Monthly");
+
+    for invalid in [
+        serde_json::json!({ "category_id": food_id, "period": "Weekly", "amount": "10", "start_date": "2026-01-01" }),
+        serde_json::json!({ "category_id": food_id, "period": "Monthly", "amount": "0", "start_date": "2026-01-01" }),
+        serde_json::json!({ "category_id": 9999, "period": "Monthly", "amount": "10", "start_date": "2026-01-01" }),
+    ] {
+        server.post("/api/v1/budgets").json(&invalid).await.assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    for (name, amount, date, category_id, is_simulated) in [
+        ("Market", -10000, (2026, 1, 10), groceries_id, false),
+        ("Restaurant", -5000, (2026, 2, 3), food_id, false),
+        ("Market", -12000, (2026, 2, 20), groceries_id, false),
+        ("Planned feast", -90000, (2026, 2, 21), food_id, true),
+        ("Refund", 2000, (2026, 2, 22), food_id, false),
+    ] {
+        let txn = CreateTransactionRequest {
+            name: name.to_string(),
+            description: None,
+            amount: Decimal::new(amount, 2),
+            date: NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap(),
+            include_in_statistics: Some(true),
+            target_account_id: account_id,
+            source_account_id: None,
+            ledger_name: None,
+            linked_import_id: None,
+            category_id: Some(category_id),
+            is_simulated: Some(is_simulated),
+            scenario_id: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
+        };
+        server.post("/api/v1/transactions").json(&txn).await.assert_status(StatusCode::CREATED);
+    }
+
+    // January left 200 unspent, which rolls over into February
+    let response = server.get("/api/v1/budgets/status").add_query_param("date", "2026-02-28").await;
+    response.assert_status_ok();
+    let body: ApiResponse<Vec<BudgetStatusResponse>> = response.json();
+    assert_eq!(body.data.len(), 1);
+    let status = &body.data[0];
+    assert_eq!(status.budget_id, budget_id);
+    assert_eq!(status.period_start, NaiveDate::from_ymd_opt(2026, 2, 1).unwrap());
+    assert_eq!(status.carried_over, Decimal::from(200));
+    assert_eq!(status.spent, Decimal::from(170));
+    assert_eq!(status.remaining, Decimal::from(330));
+
+    let body: ApiResponse<BudgetResponse> = server
+        .put(&format!("/api/v1/budgets/{}", budget_id))
+        .json(&serde_json::json!({ "rollover": false, "amount": "150" }))
+        .await
+        .json();
+    assert!(!body.data.rollover);
+
+    let body: ApiResponse<Vec<BudgetStatusResponse>> =
+        server.get("/api/v1/budgets/status").add_query_param("date", "2026-02-10").await.json();
+    assert_eq!(body.data[0].carried_over, Decimal::ZERO);
+    assert_eq!(body.data[0].spent, Decimal::from(50));
+    assert_eq!(body.data[0].remaining, Decimal::from(100));
+
+    // Nothing is reported before the budget starts
+    let body: ApiResponse<Vec<BudgetStatusResponse>> =
+        server.get("/api/v1/budgets/status").add_query_param("date", "2025-12-31").await.json();
+    assert!(body.data.is_empty());
+
+    server
+        .delete(&format!("/api/v1/budgets/{}", budget_id))
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+    server
+        .get(&format!("/api/v1/budgets/{}", budget_id))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_forecast_accuracy() {
+    use finrust::handlers::forecast::{ForecastAccuracyResponse, ForecastSnapshotResponse};
+    use model::entities::forecast_snapshot;
+    use sea_orm::{ActiveModelTrait, Set};
+
+    let app_state = setup_test_app_state().await;
+    let app = finrust::router::create_test_router(app_state.clone());
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Checking".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/accounts").json(&account_request).await.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    server
+        .post(&format!("/api/v1/accounts/{}/manual-states", account_id))
+        .json(&serde_json::json!({ "date": "2026-01-01", "amount": "1000" }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    let txn = CreateTransactionRequest {
+        name: "Rent".to_string(),
+        description: None,
+        amount: Decimal::from(-200),
+        date: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+        include_in_statistics: Some(true),
+        target_account_id: account_id,
+        source_account_id: None,
+        ledger_name: None,
+        linked_import_id: None,
+        category_id: None,
+        is_simulated: None,
+        scenario_id: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
+    };
+    server.post("/api/v1/transactions").json(&txn).await.assert_status(StatusCode::CREATED);
+
+    let body: ApiResponse<ForecastAccuracyResponse> = server.get("/api/v1/forecast/accuracy").await.json();
+    assert!(body.data.horizons.is_empty());
+
+    // Today's projections target future days and are not evaluated yet
+    let response = server.post("/api/v1/forecast/snapshots").await;
+    response.assert_status_ok();
+    let body: ApiResponse<Vec<ForecastSnapshotResponse>> = response.json();
+    let horizons: Vec<i32> = body.data.iter().map(|snapshot| snapshot.horizon_days).collect();
+    assert_eq!(horizons, vec![30, 90]);
+
+    // Projections made in the past, as the daily task would have stored them
+    for (horizon_days, target_date, projected) in [(30, (2026, 1, 31), 1100), (90, (2026, 4, 1), 700)] {
+        forecast_snapshot::ActiveModel {
+            account_id: Set(account_id),
+            made_on: Set(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()),
+            horizon_days: Set(horizon_days),
+            target_date: Set(NaiveDate::from_ymd_opt(target_date.0, target_date.1, target_date.2).unwrap()),
+            projected_balance: Set(Decimal::from(projected)),
+            ..Default::default()
+        }
+        .insert(&app_state.db)
+        .await
+        .unwrap();
+    }
+
+    let response = server
+        .get("/api/v1/forecast/accuracy")
+        .add_query_param("account_id", account_id)
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<ForecastAccuracyResponse> = response.json();
+    assert_eq!(body.data.forecasts.len(), 2);
+    assert_eq!(body.data.forecasts[0].horizon_days, 90);
+    assert_eq!(body.data.forecasts[0].actual_balance, Decimal::from(800));
+    assert_eq!(body.data.forecasts[0].error, Decimal::from(-100));
+
+    let horizons = &body.data.horizons;
+    assert_eq!(horizons.len(), 2);
+    assert_eq!(horizons[0].horizon_days, 30);
+    assert_eq!(horizons[0].samples, 1);
+    assert_eq!(horizons[0].mean_error, Decimal::from(300));
+    assert_eq!(horizons[0].mean_absolute_percentage_error, Some(Decimal::new(375, 1)));
+    assert_eq!(horizons[1].mean_absolute_error, Decimal::from(100));
+}
+
+#[tokio::test]
+async fn test_budget_progress() {
+    use finrust::handlers::budgets::{BudgetProgressResponse, BudgetResponse};
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Everyday".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/accounts").json(&account_request).await.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+    let category: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Fuel" }))
+        .await
+        .json();
+    let category_id = category.data["id"].as_i64().unwrap() as i32;
+
+    let budget: ApiResponse<BudgetResponse> = server
+        .post("/api/v1/budgets")
+        .json(&serde_json::json!({
+            "category_id": category_id,
+            "period": "Monthly",
+            "amount": "100",
+            "rollover": true,
+            "start_date": "2026-03-01"
+        }))
+        .await
+        .json();
+    let budget_id = budget.data.id;
+
+    for (amount, date) in [(-4000, (2026, 3, 10)), (-3000, (2026, 3, 30)), (-5000, (2026, 4, 1))] {
+        let txn = CreateTransactionRequest {
+            name: "Gas station".to_string(),
+            description: None,
+            amount: Decimal::new(amount, 2),
+            date: NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap(),
+            include_in_statistics: Some(true),
+            target_account_id: account_id,
+            source_account_id: None,
+            ledger_name: None,
+            linked_import_id: None,
+            category_id: Some(category_id),
+            is_simulated: None,
+            scenario_id: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
+        };
+        server.post("/api/v1/transactions").json(&txn).await.assert_status(StatusCode::CREATED);
+    }
+
+    let response = server
+        .get(&format!("/api/v1/budgets/{}/progress", budget_id))
+        .add_query_param("start_date", "2026-03-29")
+        .add_query_param("end_date", "2026-04-02")
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<BudgetProgressResponse> = response.json();
+    let spent: Vec<Decimal> = body.data.points.iter().map(|point| point.spent).collect();
+    assert_eq!(spent, [40, 70, 70, 50, 50].map(Decimal::from));
+    assert_eq!(body.data.points[2].remaining, Decimal::from(30));
+    // April starts with March's 30 left over
+    assert_eq!(body.data.points[3].period_start, NaiveDate::from_ymd_opt(2026, 4, 1).unwrap());
+    assert_eq!(body.data.points[3].available, Decimal::from(130));
+    assert_eq!(body.data.points[4].remaining, Decimal::from(80));
+
+    server
+        .get(&format!("/api/v1/budgets/{}/progress", budget_id))
+        .add_query_param("start_date", "2026-04-02")
+        .add_query_param("end_date", "2026-04-01")
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    server
+        .get("/api/v1/budgets/9999/progress")
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_bulk_reconcile_imported_transactions() {
+    use finrust::handlers::transactions::{BulkReconcileResponse, CreateImportedTransactionRequest};
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Checking".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/accounts").json(&account_request).await.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let mut imported_ids = Vec::new();
+    for (description, hash) in [("COFFEE", "bulk_hash_1"), ("BOOKS", "bulk_hash_2")] {
+        let request = CreateImportedTransactionRequest {
+            account_id,
+            date: NaiveDate::from_ymd_opt(2026, 5, 4).unwrap(),
+            description: description.to_string(),
+            amount: Decimal::new(-450, 2),
+            import_hash: hash.to_string(),
+            raw_data: None,
+            category_id: None,
+            amount_locale: None,
+        };
+        let body: ApiResponse<serde_json::Value> =
+            server.post("/api/v1/imported-transactions").json(&request).await.json();
+        imported_ids.push(body.data["id"].as_i64().unwrap() as i32);
+    }
+
+    let txn = CreateTransactionRequest {
+        name: "Coffee".to_string(),
+        description: None,
+        amount: Decimal::new(-450, 2),
+        date: NaiveDate::from_ymd_opt(2026, 5, 4).unwrap(),
+        include_in_statistics: Some(true),
+        target_account_id: account_id,
+        source_account_id: None,
+        ledger_name: None,
+        linked_import_id: None,
+        category_id: None,
+        is_simulated: None,
+        scenario_id: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
+    };
+    let body: ApiResponse<serde_json::Value> = server.post("/api/v1/transactions").json(&txn).await.json();
+    let transaction_id = body.data["id"].as_i64().unwrap() as i32;
+
+    let response = server
+        .post("/api/v1/imported-transactions/bulk-reconcile")
+        .json(&serde_json::json!({
+            "items": [
+                { "imported_id": imported_ids[0], "transaction_type": "OneOff", "transaction_id": transaction_id },
+                { "imported_id": imported_ids[1], "transaction_type": "Bogus", "transaction_id": transaction_id },
+                { "imported_id": imported_ids[1], "transaction_type": "OneOff", "transaction_id": 9999 },
+                { "imported_id": imported_ids[0], "transaction_type": "OneOff", "transaction_id": transaction_id },
+                { "imported_id": 9999, "transaction_type": "OneOff", "transaction_id": transaction_id }
+            ]
+        }))
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<BulkReconcileResponse> = response.json();
+    assert_eq!(body.data.reconciled_count, 1);
+    assert_eq!(body.data.failed_count, 4);
+    let outcomes: Vec<bool> = body.data.results.iter().map(|result| result.success).collect();
+    assert_eq!(outcomes, vec![true, false, false, false, false]);
+    assert!(body.data.results[3].error.as_deref().unwrap().contains("more than once"));
+
+    let body: ApiResponse<serde_json::Value> = server
+        .get(&format!("/api/v1/imported-transactions/{}", imported_ids[0]))
+        .await
+        .json();
+    assert_eq!(body.data["reconciled_transaction_id"], transaction_id);
+    let body: ApiResponse<serde_json::Value> = server
+        .get(&format!("/api/v1/imported-transactions/{}", imported_ids[1]))
+        .await
+        .json();
+    assert!(body.data["reconciled_transaction_id"].is_null());
+}
+
+#[tokio::test]
+async fn test_networth_timeseries() {
+    use finrust::handlers::accounts::AccountKind;
+    use finrust::handlers::networth::NetWorthTimeseriesResponse;
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let mut account_ids = Vec::new();
+    for (name, owner_id, account_kind, include_in_statistics, balance) in [
+        ("Checking", 1, AccountKind::RealAccount, true, "1000"),
+        ("Car loan", 1, AccountKind::Debt, true, "-300"),
+        ("Savings", 2, AccountKind::Savings, true, "500"),
+        ("Pocket money", 2, AccountKind::Allowance, true, "50"),
+        ("Ignored savings", 2, AccountKind::Savings, false, "2000"),
+    ] {
+        let account_request = CreateAccountRequest {
+            name: name.to_string(),
+            description: None,
+            currency_code: "USD".to_string(),
+            owner_id,
+            include_in_statistics: Some(include_in_statistics),
+            ledger_name: None,
+            account_kind: Some(account_kind),
+            target_amount: None,
+            color: None,
+            is_liquid: None,
+            balance_interpolation: None,
+        };
+        let account_body: ApiResponse<serde_json::Value> =
+            server.post("/api/v1/accounts").json(&account_request).await.json();
+        let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+        server
+            .post(&format!("/api/v1/accounts/{}/manual-states", account_id))
+            .json(&serde_json::json!({ "date": "2026-01-01", "amount": balance }))
+            .await
+            .assert_status(StatusCode::CREATED);
+        account_ids.push(account_id);
+    }
+
+    let response = server
+        .get("/api/v1/networth/timeseries")
+        .add_query_param("start_date", "2026-02-01")
+        .add_query_param("end_date", "2026-02-03")
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<NetWorthTimeseriesResponse> = response.json();
+    // The allowance and the account excluded from statistics are left out
+    assert_eq!(body.data.account_ids, account_ids[..3].to_vec());
+    assert_eq!(body.data.points.len(), 3);
+    assert!(body.data.points.iter().all(|point| point.net_worth == Decimal::from(1200)));
+    assert!(body.data.groups.is_empty());
+
+    let body: ApiResponse<NetWorthTimeseriesResponse> = server
+        .get("/api/v1/networth/timeseries")
+        .add_query_param("start_date", "2026-02-01")
+        .add_query_param("end_date", "2026-02-03")
+        .add_query_param("group_by", "owner")
+        .await
+        .json();
+    let owners: Vec<(&str, Decimal)> = body
+        .data
+        .groups
+        .iter()
+        .map(|group| (group.key.as_str(), group.points[0].net_worth))
+        .collect();
+    assert_eq!(owners, vec![("1", Decimal::from(700)), ("2", Decimal::from(500))]);
+
+    let body: ApiResponse<NetWorthTimeseriesResponse> = server
+        .get("/api/v1/networth/timeseries")
+        .add_query_param("start_date", "2026-02-01")
+        .add_query_param("end_date", "2026-02-03")
+        .add_query_param("group_by", "account_kind")
+        .await
+        .json();
+    let kinds: Vec<(&str, Decimal)> = body
+        .data
+        .groups
+        .iter()
+        .map(|group| (group.key.as_str(), group.points[2].net_worth))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ("Debt", Decimal::from(-300)),
+            ("RealAccount", Decimal::from(1000)),
+            ("Savings", Decimal::from(500)),
+        ]
+    );
+
+    server
+        .get("/api/v1/networth/timeseries")
+        .add_query_param("start_date", "2026-02-03")
+        .add_query_param("end_date", "2026-02-01")
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_suggest_budgets() {
+    use chrono::{Datelike, Months};
+    use finrust::handlers::budgets::{BudgetResponse, BudgetSuggestionsResponse};
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Everyday".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/accounts").json(&account_request).await.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let mut category_ids = Vec::new();
+    for (name, parent_id) in [("Food", None), ("Groceries", Some(0)), ("Rent", None)] {
+        let body: ApiResponse<serde_json::Value> = server
+            .post("/api/v1/categories")
+            .json(&serde_json::json!({ "name": name, "parent_id": parent_id.map(|index: usize| category_ids[index]) }))
+            .await
+            .json();
+        category_ids.push(body.data["id"].as_i64().unwrap() as i32);
+    }
+    let (food_id, groceries_id, rent_id) = (category_ids[0], category_ids[1], category_ids[2]);
+
+    let today = chrono::Local::now().date_naive();
+    let this_month = NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap();
+    let in_month_back = |back: u32| this_month - Months::new(back) + chrono::Duration::days(4);
+    for (amount, back, category_id) in [
+        (-12300, 1, groceries_id),
+        (-14000, 2, food_id),
+        (-15000, 3, food_id),
+        (-80000, 1, rent_id),
+        (-80000, 2, rent_id),
+        // Older than the requested history
+        (-500000, 4, food_id),
+    ] {
+        let txn = CreateTransactionRequest {
+            name: "Expense".to_string(),
+            description: None,
+            amount: Decimal::new(amount, 2),
+            date: in_month_back(back),
+            include_in_statistics: Some(true),
+            target_account_id: account_id,
+            source_account_id: None,
+            ledger_name: None,
+            linked_import_id: None,
+            category_id: Some(category_id),
+            is_simulated: None,
+            scenario_id: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
+        };
+        server.post("/api/v1/transactions").json(&txn).await.assert_status(StatusCode::CREATED);
+    }
+
+    let rent_budget: ApiResponse<BudgetResponse> = server
+        .post("/api/v1/budgets")
+        .json(&serde_json::json!({
+            "category_id": rent_id,
+            "period": "Monthly",
+            "amount": "750",
+            "start_date": this_month
+        }))
+        .await
+        .json();
+
+    let response = server
+        .post("/api/v1/budgets/suggest")
+        .json(&serde_json::json!({ "months": 3 }))
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<BudgetSuggestionsResponse> = response.json();
+    assert_eq!(body.data.history_start, this_month - Months::new(3));
+    assert_eq!(body.data.history_end, this_month.pred_opt().unwrap());
+    assert!(body.data.created.is_empty());
+
+    let suggestions: Vec<(i32, Decimal, Option<i32>)> = body
+        .data
+        .suggestions
+        .iter()
+        .map(|suggestion| (suggestion.category_id, suggestion.amount, suggestion.existing_budget_id))
+        .collect();
+    // Groceries count towards food; rent has no spending in one of the three months
+    assert_eq!(
+        suggestions,
+        vec![
+            (food_id, Decimal::from(140), None),
+            (rent_id, Decimal::from(800), Some(rent_budget.data.id)),
+        ]
+    );
+    assert_eq!(body.data.suggestions[0].category_name, "Food");
+    assert_eq!(body.data.suggestions[0].start_date, this_month);
+
+    let body: ApiResponse<BudgetSuggestionsResponse> = server
+        .post("/api/v1/budgets/suggest")
+        .json(&serde_json::json!({ "months": 3, "apply": true }))
+        .await
+        .json();
+    assert_eq!(body.data.created.len(), 1);
+    assert_eq!(body.data.created[0].category_id, food_id);
+    assert_eq!(body.data.created[0].amount, Decimal::from(140));
+
+    let budgets: ApiResponse<Vec<BudgetResponse>> = server.get("/api/v1/budgets").await.json();
+    assert_eq!(budgets.data.len(), 2);
+
+    server
+        .post("/api/v1/budgets/suggest")
+        .json(&serde_json::json!({ "months": 0 }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_category_stats_include_imported_transactions() {
+    use finrust::handlers::transactions::{CreateImportedTransactionRequest, ReconcileImportedTransactionRequest};
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Everyday".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/accounts").json(&account_request).await.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let food: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Food" }))
+        .await
+        .json();
+    let food_id = food.data["id"].as_i64().unwrap() as i32;
+    let groceries: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Groceries", "parent_id": food_id }))
+        .await
+        .json();
+    let groceries_id = groceries.data["id"].as_i64().unwrap() as i32;
+
+    let mut transaction_ids = Vec::new();
+    for (amount, category_id) in [(-100, food_id), (-40, groceries_id)] {
+        let txn = CreateTransactionRequest {
+            name: "Expense".to_string(),
+            description: None,
+            amount: Decimal::from(amount),
+            date: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            include_in_statistics: Some(true),
+            target_account_id: account_id,
+            source_account_id: None,
+            ledger_name: None,
+            linked_import_id: None,
+            category_id: Some(category_id),
+            is_simulated: None,
+            scenario_id: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
+        };
+        let body: ApiResponse<serde_json::Value> = server.post("/api/v1/transactions").json(&txn).await.json();
+        transaction_ids.push(body.data["id"].as_i64().unwrap() as i32);
+    }
+
+    let mut imported_ids = Vec::new();
+    for (amount, hash) in [(-25, "stats_hash_1"), (-40, "stats_hash_2")] {
+        let request = CreateImportedTransactionRequest {
+            account_id,
+            date: NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+            description: "MARKET".to_string(),
+            amount: Decimal::from(amount),
+            import_hash: hash.to_string(),
+            raw_data: None,
+            category_id: Some(groceries_id),
+            amount_locale: None,
+        };
+        let body: ApiResponse<serde_json::Value> =
+            server.post("/api/v1/imported-transactions").json(&request).await.json();
+        imported_ids.push(body.data["id"].as_i64().unwrap() as i32);
+    }
+    // The reconciled import is the same purchase as the one-off groceries expense
+    server
+        .post(&format!("/api/v1/imported-transactions/{}/reconcile", imported_ids[1]))
+        .json(&ReconcileImportedTransactionRequest {
+            transaction_type: "OneOff".to_string(),
+            transaction_id: transaction_ids[1],
+        })
+        .await
+        .assert_status_ok();
+
+    let response = server
+        .get("/api/v1/categories/stats?start_date=2026-01-01&end_date=2026-12-31")
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<Vec<serde_json::Value>> = response.json();
+    assert_eq!(body.data.len(), 2);
+
+    let food_stats = body.data.iter().find(|stats| stats["category_id"] == food_id).unwrap();
+    assert_eq!(food_stats["own_total"], "-100");
+    assert_eq!(food_stats["total_amount"], "-165");
+    assert_eq!(food_stats["transaction_count"], 3);
+    assert_eq!(food_stats["percentage"], 100.0);
+
+    let groceries_stats = body.data.iter().find(|stats| stats["category_id"] == groceries_id).unwrap();
+    assert_eq!(groceries_stats["own_total"], "-65");
+    assert_eq!(groceries_stats["total_amount"], "-65");
+    assert_eq!(groceries_stats["transaction_count"], 2);
+}
+
+#[tokio::test]
+async fn test_account_notes_and_documents() {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use finrust::helpers::field_encryption::{field_encryption_salt, FieldCipher};
+    use finrust::router::create_test_router;
+    use model::entities::account_note;
+    use sea_orm::EntityTrait;
+
+    let mut app_state = setup_test_app_state().await;
+    let salt = field_encryption_salt(&app_state.db).await.unwrap();
+    assert_eq!(field_encryption_salt(&app_state.db).await.unwrap(), salt, "the salt is stored");
+    app_state.field_cipher = Some(FieldCipher::from_passphrase("test passphrase", &salt).unwrap());
+    let server = TestServer::new(create_test_router(app_state.clone())).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Mortgage".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/accounts").json(&account_request).await.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    // Notes: the secret is returned decrypted but stored encrypted
+    let response = server
+        .post(&format!("/api/v1/accounts/{}/notes", account_id))
+        .json(&serde_json::json!({
+            "title": "Contract",
+            "body": "Fixed rate until 2030",
+            "secret": "CZ65 0800 0000 1920 0014 5399"
+        }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let note: ApiResponse<serde_json::Value> = response.json();
+    let note_id = note.data["id"].as_i64().unwrap() as i32;
+    assert_eq!(note.data["has_secret"], true);
+    assert_eq!(note.data["secret"], "CZ65 0800 0000 1920 0014 5399");
+
+    let stored = account_note::Entity::find_by_id(note_id)
+        .one(&app_state.db)
+        .await
+        .unwrap()
+        .unwrap();
+    let ciphertext = stored.secret_ciphertext.unwrap();
+    assert!(!ciphertext.contains("1920"));
+    let other_salt = FieldCipher::from_passphrase("test passphrase", b"another stored salt").unwrap();
+    assert!(other_salt.decrypt(&ciphertext).is_err(), "the key depends on the salt");
+
+    let notes: ApiResponse<Vec<serde_json::Value>> =
+        server.get(&format!("/api/v1/accounts/{}/notes", account_id)).await.json();
+    assert_eq!(notes.data.len(), 1);
+    assert_eq!(notes.data[0]["secret"], "CZ65 0800 0000 1920 0014 5399");
+
+    let response = server
+        .put(&format!("/api/v1/accounts/{}/notes/{}", account_id, note_id))
+        .json(&serde_json::json!({ "body": "Refinanced", "secret": "" }))
+        .await;
+    response.assert_status_ok();
+    let updated: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(updated.data["title"], "Contract");
+    assert_eq!(updated.data["body"], "Refinanced");
+    assert_eq!(updated.data["has_secret"], false);
+
+    server
+        .post("/api/v1/accounts/9999/notes")
+        .json(&serde_json::json!({ "title": "Orphan" }))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+
+    // Documents: listed without content, downloaded as-is
+    let content = b"%PDF-1.4 statement".to_vec();
+    let response = server
+        .post(&format!("/api/v1/accounts/{}/documents", account_id))
+        .json(&serde_json::json!({
+            "file_name": "statement.pdf",
+            "content_type": "application/pdf",
+            "content_base64": STANDARD.encode(&content)
+        }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let document: ApiResponse<serde_json::Value> = response.json();
+    let document_id = document.data["id"].as_i64().unwrap() as i32;
+    assert_eq!(document.data["size_bytes"], content.len());
+    assert_eq!(document.data["sha256"].as_str().unwrap().len(), 64);
+
+    server
+        .post(&format!("/api/v1/accounts/{}/documents", account_id))
+        .json(&serde_json::json!({ "file_name": "broken.pdf", "content_base64": "not base64!" }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+
+    let documents: ApiResponse<Vec<serde_json::Value>> =
+        server.get(&format!("/api/v1/accounts/{}/documents", account_id)).await.json();
+    assert_eq!(documents.data.len(), 1);
+    assert_eq!(documents.data[0]["file_name"], "statement.pdf");
+    assert!(documents.data[0].get("content").is_none());
+
+    let download = server
+        .get(&format!("/api/v1/accounts/{}/documents/{}/download", account_id, document_id))
+        .await;
+    download.assert_status_ok();
+    assert_eq!(download.header("content-type").to_str().unwrap(), "application/pdf");
+    assert!(
+        download
+            .header("content-disposition")
+            .to_str()
+            .unwrap()
+            .contains("statement.pdf")
+    );
+    assert_eq!(download.as_bytes().to_vec(), content);
+
+    // Deleting
+    server
+        .delete(&format!("/api/v1/accounts/{}/documents/{}", account_id, document_id))
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+    server
+        .get(&format!("/api/v1/accounts/{}/documents/{}/download", account_id, document_id))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    server
+        .delete(&format!("/api/v1/accounts/{}/notes/{}", account_id, note_id))
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+    let notes: ApiResponse<Vec<serde_json::Value>> =
+        server.get(&format!("/api/v1/accounts/{}/notes", account_id)).await.json();
+    assert!(notes.data.is_empty());
+}
+
+#[tokio::test]
+async fn test_account_note_secret_requires_encryption_key() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Savings".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/accounts").json(&account_request).await.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let response = server
+        .post(&format!("/api/v1/accounts/{}/notes", account_id))
+        .json(&serde_json::json!({ "title": "PIN", "secret": "1234" }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "ENCRYPTION_NOT_CONFIGURED");
+
+    server
+        .post(&format!("/api/v1/accounts/{}/notes", account_id))
+        .json(&serde_json::json!({ "title": "Branch", "body": "Main street" }))
+        .await
+        .assert_status(StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn test_cashflow_report() {
+    use finrust::handlers::transactions::CreateImportedTransactionRequest;
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let mut account_ids = Vec::new();
+    for name in ["Checking", "Savings"] {
+        let account_request = CreateAccountRequest {
+            name: name.to_string(),
+            description: None,
+            currency_code: "USD".to_string(),
+            owner_id: 1,
+            include_in_statistics: Some(true),
+            ledger_name: None,
+            account_kind: None,
+            target_amount: None,
+            color: None,
+            is_liquid: None,
+            balance_interpolation: None,
+        };
+        let account_body: ApiResponse<serde_json::Value> =
+            server.post("/api/v1/accounts").json(&account_request).await.json();
+        account_ids.push(account_body.data["id"].as_i64().unwrap() as i32);
+    }
+    let (checking_id, savings_id) = (account_ids[0], account_ids[1]);
+
+    let transactions = [
+        ("Salary", 3000, NaiveDate::from_ymd_opt(2026, 1, 5).unwrap(), checking_id, None),
+        ("Rent", -1200, NaiveDate::from_ymd_opt(2026, 1, 20).unwrap(), checking_id, None),
+        // Moving money to savings is neither income nor expense
+        ("To savings", 500, NaiveDate::from_ymd_opt(2026, 2, 3).unwrap(), savings_id, Some(checking_id)),
+    ];
+    for (name, amount, date, target_account_id, source_account_id) in transactions {
+        let txn = CreateTransactionRequest {
+            name: name.to_string(),
+            description: None,
+            amount: Decimal::from(amount),
+            date,
+            include_in_statistics: Some(true),
+            target_account_id,
+            source_account_id,
+            ledger_name: None,
+            linked_import_id: None,
+            category_id: None,
+            is_simulated: None,
+            scenario_id: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
+        };
+        server.post("/api/v1/transactions").json(&txn).await.assert_status(StatusCode::CREATED);
+    }
+
+    let imported = CreateImportedTransactionRequest {
+        account_id: checking_id,
+        date: NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+        description: "MARKET".to_string(),
+        amount: Decimal::from(-40),
+        import_hash: "cashflow_hash_1".to_string(),
+        raw_data: None,
+        category_id: None,
+        amount_locale: None,
+    };
+    server
+        .post("/api/v1/imported-transactions")
+        .json(&imported)
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let response = server
+        .get("/api/v1/reports/cashflow?granularity=month&start_date=2026-01-01&end_date=2026-03-31")
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    let amount = |value: &serde_json::Value| value.as_str().unwrap().parse::<Decimal>().unwrap();
+    let periods = body.data["periods"].as_array().unwrap();
+    assert_eq!(periods.len(), 3);
+    assert_eq!(periods[0]["start_date"], "2026-01-01");
+    assert_eq!(amount(&periods[0]["income"]), Decimal::from(3000));
+    assert_eq!(amount(&periods[0]["expense"]), Decimal::from(1200));
+    assert_eq!(amount(&periods[0]["net"]), Decimal::from(1800));
+    assert_eq!(amount(&periods[1]["net"]), Decimal::from(0));
+    assert_eq!(amount(&periods[2]["expense"]), Decimal::from(40));
+    assert_eq!(amount(&body.data["total_net"]), Decimal::from(1760));
+
+    let quarterly: ApiResponse<serde_json::Value> = server
+        .get("/api/v1/reports/cashflow?granularity=quarter&start_date=2026-01-01&end_date=2026-03-31")
+        .await
+        .json();
+    assert_eq!(quarterly.data["granularity"], "quarter");
+    assert_eq!(quarterly.data["periods"].as_array().unwrap().len(), 1);
+    assert_eq!(amount(&quarterly.data["periods"][0]["income"]), Decimal::from(3000));
+    assert_eq!(amount(&quarterly.data["periods"][0]["expense"]), Decimal::from(1240));
+
+    server
+        .get("/api/v1/reports/cashflow?start_date=2026-03-31&end_date=2026-01-01")
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_contract_reminders() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Household".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/accounts").json(&account_request).await.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let today = chrono::Local::now().date_naive();
+    let create = |name: &str, renewal_in_days: i64, notice_period_days: i32| {
+        serde_json::json!({
+            "name": name,
+            "amount": "-30",
+            "start_date": "2024-01-01",
+            "period": "Monthly",
+            "target_account_id": account_id,
+            "renewal_date": today + chrono::Duration::days(renewal_in_days),
+            "notice_period_days": notice_period_days,
+        })
+    };
+
+    // Deadline in 10 days
+    let response = server
+        .post("/api/v1/recurring-transactions")
+        .json(&create("Phone plan", 40, 30))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["notice_period_days"], 30);
+    let phone_id = body.data["id"].as_i64().unwrap();
+
+    // Deadline in 100 days, and one that already passed
+    for (name, renewal_in_days, notice_period_days) in [("Insurance", 190, 90), ("Gym", 5, 30)] {
+        server
+            .post("/api/v1/recurring-transactions")
+            .json(&create(name, renewal_in_days, notice_period_days))
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+    // A plain recurring payment without contract terms
+    server
+        .post("/api/v1/recurring-transactions")
+        .json(&serde_json::json!({
+            "name": "Groceries",
+            "amount": "-200",
+            "start_date": "2024-01-01",
+            "period": "Weekly",
+            "target_account_id": account_id,
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let response = server.get("/api/v1/recurring-transactions/contract-reminders").await;
+    response.assert_status_ok();
+    let body: ApiResponse<Vec<serde_json::Value>> = response.json();
+    assert_eq!(body.data.len(), 1);
+    assert_eq!(body.data[0]["recurring_transaction_id"], phone_id);
+    assert_eq!(body.data[0]["days_until_deadline"], 10);
+    assert_eq!(
+        body.data[0]["cancellation_deadline"],
+        (today + chrono::Duration::days(10)).to_string()
+    );
+
+    let body: ApiResponse<Vec<serde_json::Value>> = server
+        .get("/api/v1/recurring-transactions/contract-reminders?within_days=120")
+        .await
+        .json();
+    let names: Vec<&str> = body.data.iter().map(|reminder| reminder["name"].as_str().unwrap()).collect();
+    assert_eq!(names, vec!["Phone plan", "Insurance"]);
+
+    server
+        .put(&format!("/api/v1/recurring-transactions/{}", phone_id))
+        .json(&serde_json::json!({ "notice_period_days": -1 }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_flow_report() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let mut account_ids = Vec::new();
+    for name in ["Checking", "Savings"] {
+        let account_request = CreateAccountRequest {
+            name: name.to_string(),
+            description: None,
+            currency_code: "USD".to_string(),
+            owner_id: 1,
+            include_in_statistics: Some(true),
+            ledger_name: None,
+            account_kind: None,
+            target_amount: None,
+            color: None,
+            is_liquid: None,
+            balance_interpolation: None,
+        };
+        let account_body: ApiResponse<serde_json::Value> =
+            server.post("/api/v1/accounts").json(&account_request).await.json();
+        account_ids.push(account_body.data["id"].as_i64().unwrap() as i32);
+    }
+    let (checking_id, savings_id) = (account_ids[0], account_ids[1]);
+
+    let housing: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Housing" }))
+        .await
+        .json();
+    let housing_id = housing.data["id"].as_i64().unwrap() as i32;
+    let rent: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Rent", "parent_id": housing_id }))
+        .await
+        .json();
+    let rent_id = rent.data["id"].as_i64().unwrap() as i32;
+
+    server
+        .post("/api/v1/recurring-transactions")
+        .json(&serde_json::json!({
+            "name": "Salary",
+            "amount": "3000",
+            "start_date": "2026-01-05",
+            "end_date": "2026-01-05",
+            "period": "Monthly",
+            "target_account_id": checking_id,
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let transactions = [
+        ("Rent", -1200, checking_id, None, Some(rent_id)),
+        ("Coffee", -30, checking_id, None, None),
+        ("To savings", 500, savings_id, Some(checking_id), None),
+    ];
+    for (name, amount, target_account_id, source_account_id, category_id) in transactions {
+        let txn = CreateTransactionRequest {
+            name: name.to_string(),
+            description: None,
+            amount: Decimal::from(amount),
+            date: NaiveDate::from_ymd_opt(2026, 1, 20).unwrap(),
+            include_in_statistics: Some(true),
+            target_account_id,
+            source_account_id,
+            ledger_name: None,
+            linked_import_id: None,
+            category_id,
+            is_simulated: None,
+            scenario_id: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
+        };
+        server.post("/api/v1/transactions").json(&txn).await.assert_status(StatusCode::CREATED);
+    }
+
+    let response = server
+        .get("/api/v1/reports/flows?start_date=2026-01-01&end_date=2026-01-31")
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+
+    let nodes = body.data["nodes"].as_array().unwrap();
+    let node = |id: &str| nodes.iter().find(|node| node["id"] == id).cloned();
+    let checking = format!("account:{}", checking_id);
+    let savings = format!("account:{}", savings_id);
+    let housing_node = format!("category:{}", housing_id);
+    assert_eq!(nodes.len(), 5);
+    assert_eq!(node("income:Salary").unwrap()["kind"], "income_source");
+    assert_eq!(node(&checking).unwrap()["label"], "Checking");
+    assert_eq!(node(&housing_node).unwrap()["label"], "Housing");
+    assert_eq!(node("category:uncategorized").unwrap()["kind"], "category");
+
+    let edges = body.data["edges"].as_array().unwrap();
+    let amount = |source: &str, target: &str| {
+        edges
+            .iter()
+            .find(|edge| edge["source"] == source && edge["target"] == target)
+            .map(|edge| edge["amount"].as_str().unwrap().parse::<Decimal>().unwrap())
+    };
+    assert_eq!(edges.len(), 4);
+    assert_eq!(amount("income:Salary", &checking), Some(Decimal::from(3000)));
+    assert_eq!(amount(&checking, &housing_node), Some(Decimal::from(1200)));
+    assert_eq!(amount(&checking, "category:uncategorized"), Some(Decimal::from(30)));
+    assert_eq!(amount(&checking, &savings), Some(Decimal::from(500)));
+}
+
+#[tokio::test]
+async fn test_login_sessions() {
+    use axum::http::{header, HeaderValue};
+    use finrust::helpers::auth::hash_password;
+    use model::entities::user;
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+    let mut state = setup_test_app_state().await;
+    let test_user = user::Entity::find_by_id(1).one(&state.db).await.unwrap().unwrap();
+    let mut active: user::ActiveModel = test_user.into();
+    active.password_hash = Set(Some(hash_password("correct horse").unwrap()));
+    active.update(&state.db).await.unwrap();
+    state.auth_required = true;
+    let server = TestServer::new(finrust::router::create_test_router(state)).unwrap();
+    let bearer = |token: &str| HeaderValue::from_str(&format!("Bearer {}", token)).unwrap();
+
+    // The API is closed without a session, the health check is not
+    server.get("/health").await.assert_status(StatusCode::OK);
+    let response = server.get("/api/v1/accounts").await;
+    response.assert_status(StatusCode::UNAUTHORIZED);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "UNAUTHORIZED");
+
+    let response = server
+        .post("/api/v1/auth/login")
+        .json(&serde_json::json!({ "username": "test_user1", "password": "wrong password" }))
+        .await;
+    response.assert_status(StatusCode::UNAUTHORIZED);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "INVALID_CREDENTIALS");
+
+    // Users without a password cannot log in
+    server
+        .post("/api/v1/auth/login")
+        .json(&serde_json::json!({ "username": "test_user2", "password": "" }))
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    let response = server
+        .post("/api/v1/auth/login")
+        .json(&serde_json::json!({ "username": "test_user1", "password": "correct horse" }))
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["user_id"], 1);
+    let token = body.data["token"].as_str().unwrap().to_string();
+
+    server
+        .get("/api/v1/accounts")
+        .add_header(header::AUTHORIZATION, bearer(&token))
+        .await
+        .assert_status_ok();
+    let me: ApiResponse<serde_json::Value> = server
+        .get("/api/v1/auth/me")
+        .add_header(header::AUTHORIZATION, bearer(&token))
+        .await
+        .json();
+    assert_eq!(me.data["username"], "test_user1");
+
+    // Recurring transactions are attributed to the logged-in user
+    let account_request = CreateAccountRequest {
+        name: "Checking".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/accounts")
+        .add_header(header::AUTHORIZATION, bearer(&token))
+        .json(&account_request)
+        .await
+        .json();
+    let account_id = account_body.data["id"].as_i64().unwrap();
+    let recurring: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/recurring-transactions")
+        .add_header(header::AUTHORIZATION, bearer(&token))
+        .json(&serde_json::json!({
+            "name": "Rent",
+            "amount": "-800",
+            "start_date": "2026-01-01",
+            "period": "Monthly",
+            "target_account_id": account_id,
+        }))
+        .await
+        .json();
+    assert_eq!(recurring.data["created_by_user_id"], 1);
+
+    server
+        .put("/api/v1/auth/password")
+        .add_header(header::AUTHORIZATION, bearer(&token))
+        .json(&serde_json::json!({ "current_password": "correct horse", "new_password": "short" }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+
+    server
+        .post("/api/v1/auth/logout")
+        .add_header(header::AUTHORIZATION, bearer(&token))
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+    server
+        .get("/api/v1/accounts")
+        .add_header(header::AUTHORIZATION, bearer(&token))
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_sparse_fieldsets() {
+    use model::entities::recurring_transaction_instance::{self, InstanceStatus};
+    use model::entities::recurring_transaction;
+    use sea_orm::{ActiveModelTrait, Set};
+
+    let state = setup_test_app_state().await;
+    let server = TestServer::new(finrust::router::create_test_router(state.clone())).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Checking".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/accounts").json(&account_request).await.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    for (name, amount) in [("Groceries", -45), ("Salary", 3000)] {
+        server
+            .post("/api/v1/transactions")
+            .json(&serde_json::json!({
+                "name": name,
+                "description": "Full record",
+                "amount": amount.to_string(),
+                "date": "2026-10-01",
+                "target_account_id": account_id,
+            }))
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+
+    let keys = |item: &serde_json::Value| {
+        let mut keys: Vec<String> = item.as_object().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    };
+
+    let response = server.get("/api/v1/transactions?fields=id,date,amount,name").await;
+    response.assert_status_ok();
+    let body: ApiResponse<Vec<serde_json::Value>> = response.json();
+    assert_eq!(body.data.len(), 2);
+    for item in &body.data {
+        assert_eq!(keys(item), vec!["amount", "date", "id", "name"]);
+    }
+
+    // Unknown fields are ignored and related fields are still loaded on request
+    let body: ApiResponse<Vec<serde_json::Value>> = server
+        .get(&format!("/api/v1/accounts/{}/transactions?fields=id,tags,bogus", account_id))
+        .await
+        .json();
+    assert_eq!(keys(&body.data[0]), vec!["id", "tags"]);
+
+    // Without a selection every field is returned
+    let body: ApiResponse<Vec<serde_json::Value>> = server.get("/api/v1/transactions").await.json();
+    assert_eq!(body.data[0]["description"], "Full record");
+
+    let rule = recurring_transaction::ActiveModel {
+        name: Set("Rent".to_string()),
+        amount: Set(Decimal::from(-800)),
+        start_date: Set(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()),
+        period: Set(recurring_transaction::RecurrencePeriod::Monthly),
+        include_in_statistics: Set(true),
+        target_account_id: Set(account_id),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await
+    .unwrap();
+    recurring_transaction_instance::ActiveModel {
+        recurring_transaction_id: Set(rule.id),
+        status: Set(InstanceStatus::Pending),
+        due_date: Set(NaiveDate::from_ymd_opt(2026, 10, 1).unwrap()),
+        expected_amount: Set(Decimal::from(-800)),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await
+    .unwrap();
+
+    let body: ApiResponse<Vec<serde_json::Value>> = server
+        .get("/api/v1/recurring-instances?fields=id,due_date,recurring_transaction_name")
+        .await
+        .json();
+    assert_eq!(keys(&body.data[0]), vec!["due_date", "id", "recurring_transaction_name"]);
+    assert_eq!(body.data[0]["recurring_transaction_name"], "Rent");
+
+    let body: ApiResponse<Vec<serde_json::Value>> = server
+        .get("/api/v1/recurring-instances?group_by=month&fields=id")
+        .await
+        .json();
+    assert_eq!(body.data[0]["month"], "2026-10");
+    assert_eq!(keys(&body.data[0]["instances"][0]), vec!["id"]);
+}
+
+#[tokio::test]
+async fn test_batch_requests() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let response = server
+        .post("/api/v1/batch")
+        .json(&serde_json::json!({
+            "requests": [
+                {
+                    "method": "POST",
+                    "path": "/api/v1/accounts",
+                    "body": {
+                        "name": "Checking",
+                        "currency_code": "USD",
+                        "owner_id": 1,
+                    },
+                },
+                { "method": "get", "path": "/api/v1/accounts" },
+                { "method": "GET", "path": "/api/v1/accounts/9999" },
+                { "method": "GET", "path": "/health" },
+                { "method": "POST", "path": "/api/v1/batch", "body": { "requests": [] } },
+            ]
+        }))
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<Vec<serde_json::Value>> = response.json();
+    assert_eq!(body.data.len(), 5);
+
+    assert_eq!(body.data[0]["status"], 201);
+    assert_eq!(body.data[0]["body"]["data"]["name"], "Checking");
+    // Requests run in order, so the account created first is listed
+    assert_eq!(body.data[1]["status"], 200);
+    assert_eq!(body.data[1]["body"]["data"].as_array().unwrap().len(), 1);
+    assert_eq!(body.data[2]["status"], 404);
+    // Only API endpoints can be batched, and batches don't nest
+    assert_eq!(body.data[3]["status"], 400);
+    assert_eq!(body.data[3]["body"]["code"], "INVALID_PATH");
+    assert_eq!(body.data[4]["status"], 400);
+
+    server
+        .post("/api/v1/batch")
+        .json(&serde_json::json!({ "requests": [] }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_per_user_authorization() {
+    use axum::http::{header, HeaderValue};
+    use finrust::helpers::auth::hash_password;
+    use model::entities::user;
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+    let state = setup_test_app_state().await;
+    for user_id in [1, 2] {
+        let existing = user::Entity::find_by_id(user_id).one(&state.db).await.unwrap().unwrap();
+        let mut active: user::ActiveModel = existing.into();
+        active.password_hash = Set(Some(hash_password("correct horse").unwrap()));
+        active.update(&state.db).await.unwrap();
+    }
+    let server = TestServer::new(finrust::router::create_test_router(state)).unwrap();
+
+    let mut tokens = Vec::new();
+    for username in ["test_user1", "test_user2"] {
+        let body: ApiResponse<serde_json::Value> = server
+            .post("/api/v1/auth/login")
+            .json(&serde_json::json!({ "username": username, "password": "correct horse" }))
+            .await
+            .json();
+        tokens.push(HeaderValue::from_str(&format!("Bearer {}", body.data["token"].as_str().unwrap())).unwrap());
+    }
+    let (alice, bob) = (tokens[0].clone(), tokens[1].clone());
+
+    let account_request = |name: &str, owner_id: i32| CreateAccountRequest {
+        name: name.to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let response = server
+        .post("/api/v1/accounts")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .json(&account_request("Not mine", 1))
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "OWNER_FORBIDDEN");
+
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/accounts")
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .json(&account_request("Alice checking", 1))
+        .await
+        .json();
+    let alice_account = body.data["id"].as_i64().unwrap();
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/accounts")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .json(&account_request("Bob checking", 2))
+        .await
+        .json();
+    let bob_account = body.data["id"].as_i64().unwrap();
+
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/transactions")
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .json(&serde_json::json!({
+            "name": "Groceries",
+            "amount": "-45",
+            "date": "2026-01-10",
+            "target_account_id": alice_account,
+        }))
+        .await
+        .json();
+    let alice_transaction = body.data["id"].as_i64().unwrap();
+
+    // Bob sees only his own account and none of Alice's data
+    let accounts: ApiResponse<Vec<serde_json::Value>> = server
+        .get("/api/v1/accounts")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .json();
+    assert_eq!(accounts.data.len(), 1);
+    assert_eq!(accounts.data[0]["id"], bob_account);
+    server
+        .get(&format!("/api/v1/accounts/{}", alice_account))
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    let transactions: ApiResponse<Vec<serde_json::Value>> = server
+        .get("/api/v1/transactions")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .json();
+    assert!(transactions.data.is_empty());
+    server
+        .get(&format!("/api/v1/transactions/{}", alice_transaction))
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    server
+        .delete(&format!("/api/v1/transactions/{}", alice_transaction))
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    server
+        .get(&format!("/api/v1/accounts/{}/manual-states", alice_account))
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    server
+        .get(&format!("/api/v1/accounts/{}/notes", alice_account))
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    server
+        .get(&format!("/api/v1/transactions/{}/similar", alice_transaction))
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    server
+        .post(&format!("/api/v1/transactions/{}/legs", alice_transaction))
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .json(&serde_json::json!({ "name": "Fee", "amount": "-1" }))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    let report: ApiResponse<serde_json::Value> = server
+        .get("/api/v1/reports/cashflow?start_date=2026-01-01&end_date=2026-01-31")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .json();
+    assert_eq!(report.data["total_expense"].as_str().unwrap().parse::<Decimal>().unwrap(), Decimal::ZERO);
+    let report: ApiResponse<serde_json::Value> = server
+        .get("/api/v1/double-entry/report")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .json();
+    assert_eq!(report.data["checked"], 0);
+    let response = server
+        .post("/api/v1/reconciliation-sessions")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .json(&serde_json::json!({
+            "account_id": alice_account,
+            "statement_date": "2026-01-31",
+            "statement_balance": "0",
+        }))
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "ACCOUNT_FORBIDDEN");
+    let response = server
+        .post("/api/v1/recurring-transactions")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .json(&serde_json::json!({
+            "name": "Rent",
+            "amount": "-800",
+            "start_date": "2026-01-01",
+            "period": "Monthly",
+            "target_account_id": alice_account,
+        }))
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "ACCOUNT_FORBIDDEN");
+
+    // Only the owner can share an account
+    server
+        .put(&format!("/api/v1/accounts/{}/shares/2", alice_account))
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .json(&serde_json::json!({ "permission": "Write" }))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    server
+        .put(&format!("/api/v1/accounts/{}/shares/2", alice_account))
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .json(&serde_json::json!({ "permission": "Write" }))
+        .await
+        .assert_status_ok();
+
+    // Once shared, Bob works with the account's data but can't change the account itself
+    let accounts: ApiResponse<Vec<serde_json::Value>> = server
+        .get("/api/v1/accounts")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .json();
+    assert_eq!(accounts.data.len(), 2);
+    server
+        .get(&format!("/api/v1/transactions/{}", alice_transaction))
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .assert_status_ok();
+    server
+        .post(&format!("/api/v1/accounts/{}/manual-states", alice_account))
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .json(&serde_json::json!({ "date": "2026-01-31", "amount": "955" }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    server
+        .delete(&format!("/api/v1/accounts/{}", alice_account))
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+
+    // Alice still doesn't see Bob's own account
+    let accounts: ApiResponse<Vec<serde_json::Value>> = server
+        .get("/api/v1/accounts")
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .await
+        .json();
+    assert_eq!(accounts.data.len(), 1);
+    assert_eq!(accounts.data[0]["id"], alice_account);
+
+    // Without authentication required, anonymous requests see everything
+    let accounts: ApiResponse<Vec<serde_json::Value>> = server.get("/api/v1/accounts").await.json();
+    assert_eq!(accounts.data.len(), 2);
+}
+
+#[tokio::test]
+async fn test_user_endpoints_limited_to_self_or_admin() {
+    use axum::http::{header, HeaderValue};
+    use finrust::helpers::auth::hash_password;
+    use model::entities::user;
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+    let state = setup_test_app_state().await;
+    let mut users = Vec::new();
+    for user_id in [1, 2] {
+        let existing = user::Entity::find_by_id(user_id).one(&state.db).await.unwrap().unwrap();
+        let mut active: user::ActiveModel = existing.into();
+        active.password_hash = Set(Some(hash_password("correct horse").unwrap()));
+        users.push(active.update(&state.db).await.unwrap());
+    }
+    let db = state.db.clone();
+    let server = TestServer::new(finrust::router::create_test_router(state)).unwrap();
+
+    let mut tokens = Vec::new();
+    for username in ["test_user1", "test_user2"] {
+        let body: ApiResponse<serde_json::Value> = server
+            .post("/api/v1/auth/login")
+            .json(&serde_json::json!({ "username": username, "password": "correct horse" }))
+            .await
+            .json();
+        tokens.push(HeaderValue::from_str(&format!("Bearer {}", body.data["token"].as_str().unwrap())).unwrap());
+    }
+    let (alice, bob) = (tokens[0].clone(), tokens[1].clone());
+    let settings = serde_json::json!({ "active_scenario_id": null, "theme": "dark", "timezone": null });
+
+    // Bob manages only himself
+    let response = server
+        .get("/api/v1/users/1/settings")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "ADMIN_REQUIRED");
+    server
+        .put("/api/v1/users/1/settings")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .json(&settings)
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+    server
+        .put("/api/v1/users/1")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .json(&serde_json::json!({ "username": "mallory" }))
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+    server
+        .delete("/api/v1/users/1")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+    server
+        .post("/api/v1/users")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .json(&serde_json::json!({ "username": "eve" }))
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+    server
+        .put("/api/v1/users/2/settings")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .json(&settings)
+        .await
+        .assert_status_ok();
+
+    // An administrator manages everyone
+    let mut active: user::ActiveModel = users.remove(0).into();
+    active.is_admin = Set(true);
+    active.update(&db).await.unwrap();
+    let body: ApiResponse<serde_json::Value> = server
+        .get("/api/v1/users/2/settings")
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .await
+        .json();
+    assert_eq!(body.data["theme"], "dark");
+    server
+        .post("/api/v1/users")
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .json(&serde_json::json!({ "username": "eve" }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    server
+        .delete("/api/v1/users/2")
+        .add_header(header::AUTHORIZATION, alice)
+        .await
+        .assert_status_ok();
+}
+
+/// Logs in test users 1 (Alice) and 2 (Bob) and gives Alice an account with an expense.
+///
+/// Returns the server, Alice's and Bob's authorization headers and Alice's account ID.
+async fn alice_and_bob() -> (TestServer, axum::http::HeaderValue, axum::http::HeaderValue, i64) {
+    use axum::http::{header, HeaderValue};
+    use finrust::helpers::auth::hash_password;
+    use model::entities::user;
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+    let state = setup_test_app_state().await;
+    for user_id in [1, 2] {
+        let existing = user::Entity::find_by_id(user_id).one(&state.db).await.unwrap().unwrap();
+        let mut active: user::ActiveModel = existing.into();
+        active.password_hash = Set(Some(hash_password("correct horse").unwrap()));
+        active.update(&state.db).await.unwrap();
+    }
+    let server = TestServer::new(finrust::router::create_test_router(state)).unwrap();
+
+    let mut tokens = Vec::new();
+    for username in ["test_user1", "test_user2"] {
+        let body: ApiResponse<serde_json::Value> = server
+            .post("/api/v1/auth/login")
+            .json(&serde_json::json!({ "username": username, "password": "correct horse" }))
+            .await
+            .json();
+        tokens.push(HeaderValue::from_str(&format!("Bearer {}", body.data["token"].as_str().unwrap())).unwrap());
+    }
+    let (alice, bob) = (tokens[0].clone(), tokens[1].clone());
+
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/accounts")
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .json(&serde_json::json!({ "name": "Alice checking", "currency_code": "USD", "owner_id": 1 }))
+        .await
+        .json();
+    let alice_account = body.data["id"].as_i64().unwrap();
+    server
+        .post("/api/v1/transactions")
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .json(&serde_json::json!({
+            "name": "Groceries",
+            "amount": "-45",
+            "date": "2026-01-10",
+            "target_account_id": alice_account,
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    (server, alice, bob, alice_account)
+}
+
+#[tokio::test]
+async fn test_category_stats_hide_other_users_accounts() {
+    use axum::http::header;
+
+    let (server, alice, bob, alice_account) = alice_and_bob().await;
+    let stats_url = format!(
+        "/api/v1/categories/stats?start_date=2026-01-01&end_date=2026-01-31&account_id={}",
+        alice_account
+    );
+    server
+        .get(&stats_url)
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .await
+        .assert_status_ok();
+    server
+        .get(&stats_url)
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_metrics_hide_other_users_accounts() {
+    use axum::http::header;
+
+    let (server, alice, bob, alice_account) = alice_and_bob().await;
+    let metrics_url = format!("/api/v1/accounts/{}/metrics", alice_account);
+    server
+        .get(&metrics_url)
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .await
+        .assert_status_ok();
+    server
+        .get(&metrics_url)
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_forecast_accuracy_hides_other_users_accounts() {
+    use axum::http::header;
+
+    let (server, alice, bob, alice_account) = alice_and_bob().await;
+    let accuracy_url = format!("/api/v1/forecast/accuracy?account_id={}", alice_account);
+    server
+        .get(&accuracy_url)
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .await
+        .assert_status_ok();
+    server
+        .get(&accuracy_url)
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+
+    // Snapshots requested by Bob cover only his accounts
+    let body: ApiResponse<Vec<serde_json::Value>> = server
+        .post("/api/v1/forecast/snapshots")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .json();
+    assert!(body.data.iter().all(|snapshot| snapshot["account_id"] != alice_account));
+}
+
+#[tokio::test]
+async fn test_chart_of_accounts_hides_other_users_accounts() {
+    use axum::http::header;
+
+    let (server, alice, bob, alice_account) = alice_and_bob().await;
+    let is_alice_account =
+        |entry: &serde_json::Value| entry["source"] == "account" && entry["source_id"] == alice_account;
+    let chart_url = "/api/v1/export/chart-of-accounts?cutoff_date=2026-01-31&period_start=2026-01-01";
+    let body: ApiResponse<serde_json::Value> =
+        server.get(chart_url).add_header(header::AUTHORIZATION, alice.clone()).await.json();
+    assert!(body.data["entries"].as_array().unwrap().iter().any(is_alice_account));
+    let body: ApiResponse<serde_json::Value> =
+        server.get(chart_url).add_header(header::AUTHORIZATION, bob.clone()).await.json();
+    assert!(body.data["entries"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_recurring_income_hides_other_users_accounts() {
+    use axum::http::header;
+
+    let (server, alice, bob, alice_account) = alice_and_bob().await;
+    let income = serde_json::json!({
+        "name": "Salary",
+        "amount": "3000",
+        "start_date": "2026-01-01",
+        "period": "Monthly",
+        "target_account_id": alice_account,
+    });
+    let response = server
+        .post("/api/v1/recurring-incomes")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .json(&income)
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "ACCOUNT_FORBIDDEN");
+
+    let response = server
+        .post("/api/v1/recurring-incomes")
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .json(&income)
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let body: ApiResponse<serde_json::Value> = response.json();
+    let income_url = format!("/api/v1/recurring-incomes/{}", body.data["id"]);
+
+    let incomes: ApiResponse<Vec<serde_json::Value>> = server
+        .get("/api/v1/recurring-incomes")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .json();
+    assert!(incomes.data.is_empty());
+    server
+        .get(&income_url)
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    server
+        .put(&income_url)
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .json(&serde_json::json!({ "amount": "1" }))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    server
+        .delete(&income_url)
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    server
+        .get(&income_url)
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .await
+        .assert_status_ok();
+}
+
+#[tokio::test]
+async fn test_tag_limits_count_only_visible_accounts() {
+    use axum::http::header;
+
+    let (server, alice, bob, _) = alice_and_bob().await;
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/tags")
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .json(&serde_json::json!({ "name": "Food", "monthly_limit": "100" }))
+        .await
+        .json();
+    let tag_id = body.data["id"].as_i64().unwrap();
+    let transactions: ApiResponse<Vec<serde_json::Value>> = server
+        .get("/api/v1/transactions")
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .await
+        .json();
+    server
+        .post(&format!("/api/v1/tags/{}/assign", tag_id))
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .json(&serde_json::json!({ "transaction_ids": [transactions.data[0]["id"]] }))
+        .await
+        .assert_status_ok();
+
+    let spent = |body: ApiResponse<serde_json::Value>| {
+        body.data["limits"][0]["spent"].as_str().unwrap().parse::<Decimal>().unwrap()
+    };
+    let limits_url = "/api/v1/tags/limits?year=2026&month=1";
+    let body = server.get(limits_url).add_header(header::AUTHORIZATION, alice.clone()).await.json();
+    assert_eq!(spent(body), Decimal::from(45));
+    let body = server.get(limits_url).add_header(header::AUTHORIZATION, bob.clone()).await.json();
+    assert_eq!(spent(body), Decimal::ZERO);
+}
+
+#[tokio::test]
+async fn test_scenarios_on_other_users_accounts_are_hidden() {
+    use axum::http::header;
+
+    let (server, alice, bob, alice_account) = alice_and_bob().await;
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/scenarios")
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .json(&serde_json::json!({ "name": "New car" }))
+        .await
+        .json();
+    let scenario_id = body.data["id"].as_i64().unwrap();
+    server
+        .post("/api/v1/transactions")
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .json(&serde_json::json!({
+            "name": "Car",
+            "amount": "-20000",
+            "date": "2026-06-01",
+            "target_account_id": alice_account,
+            "scenario_id": scenario_id,
+            "is_simulated": true,
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    server
+        .post(&format!("/api/v1/scenarios/{}/clone", scenario_id))
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .json(&serde_json::json!({}))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    server
+        .post(&format!("/api/v1/scenarios/{}/apply", scenario_id))
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    server
+        .post(&format!("/api/v1/scenarios/{}/apply", scenario_id))
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .await
+        .assert_status_ok();
+}
+
+#[tokio::test]
+async fn test_api_tokens() {
+    use axum::http::{header, HeaderValue};
+    use finrust::helpers::auth::hash_password;
+    use model::entities::user;
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+    let mut state = setup_test_app_state().await;
+    let test_user = user::Entity::find_by_id(1).one(&state.db).await.unwrap().unwrap();
+    let mut active: user::ActiveModel = test_user.into();
+    active.password_hash = Set(Some(hash_password("correct horse").unwrap()));
+    active.update(&state.db).await.unwrap();
+    state.auth_required = true;
+    let server = TestServer::new(finrust::router::create_test_router(state)).unwrap();
+    let bearer = |token: &str| HeaderValue::from_str(&format!("Bearer {}", token)).unwrap();
+
+    let login: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/auth/login")
+        .json(&serde_json::json!({ "username": "test_user1", "password": "correct horse" }))
+        .await
+        .json();
+    let session = login.data["token"].as_str().unwrap().to_string();
+
+    server
+        .post("/api/v1/tokens")
+        .add_header(header::AUTHORIZATION, bearer(&session))
+        .json(&serde_json::json!({ "name": "" }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+
+    let response = server
+        .post("/api/v1/tokens")
+        .add_header(header::AUTHORIZATION, bearer(&session))
+        .json(&serde_json::json!({ "name": "import cron job", "expires_in_days": 90 }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let created: ApiResponse<serde_json::Value> = response.json();
+    let token = created.data["token"].as_str().unwrap().to_string();
+    let token_id = created.data["id"].as_i64().unwrap();
+    assert!(token.starts_with("frt_"));
+    assert_eq!(created.data["token_prefix"], &token[..12]);
+    assert_eq!(created.data["name"], "import cron job");
+    assert!(created.data["expires_at"].is_string());
+    assert!(created.data["last_used_at"].is_null());
+
+    // Scripts authenticate with the token instead of logging in
+    server
+        .get("/api/v1/accounts")
+        .add_header(header::AUTHORIZATION, bearer(&token))
+        .await
+        .assert_status_ok();
+    let me: ApiResponse<serde_json::Value> = server
+        .get("/api/v1/auth/me")
+        .add_header(header::AUTHORIZATION, bearer(&token))
+        .await
+        .json();
+    assert_eq!(me.data["username"], "test_user1");
+
+    // The listing never exposes the secret and records when the token was used
+    let tokens: ApiResponse<Vec<serde_json::Value>> = server
+        .get("/api/v1/tokens")
+        .add_header(header::AUTHORIZATION, bearer(&session))
+        .await
+        .json();
+    assert_eq!(tokens.data.len(), 1);
+    assert!(tokens.data[0].get("token").is_none());
+    assert!(tokens.data[0]["last_used_at"].is_string());
+
+    // A token can't mint more tokens or be logged out like a session
+    let response = server
+        .post("/api/v1/tokens")
+        .add_header(header::AUTHORIZATION, bearer(&token))
+        .json(&serde_json::json!({ "name": "another" }))
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "SESSION_REQUIRED");
+    server
+        .post("/api/v1/auth/logout")
+        .add_header(header::AUTHORIZATION, bearer(&token))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+
+    // Unknown or made-up tokens are rejected
+    server
+        .get("/api/v1/accounts")
+        .add_header(header::AUTHORIZATION, bearer("frt_not-a-real-token"))
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+    server
+        .delete("/api/v1/tokens/9999")
+        .add_header(header::AUTHORIZATION, bearer(&session))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+
+    server
+        .delete(&format!("/api/v1/tokens/{}", token_id))
+        .add_header(header::AUTHORIZATION, bearer(&session))
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+    server
+        .get("/api/v1/accounts")
+        .add_header(header::AUTHORIZATION, bearer(&token))
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_user_timezone_decides_today() {
+    use axum::http::{header, HeaderValue};
+    use chrono_tz::Tz;
+    use finrust::helpers::auth::hash_password;
+    use model::entities::user;
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+    // UTC+14 and UTC-12 are always on different dates, one of them a day off from UTC
+    let ahead = Tz::Pacific__Kiritimati;
+    let behind = Tz::Etc__GMTPlus12;
+    let today_in = |timezone: Tz| chrono::Utc::now().with_timezone(&timezone).date_naive();
+
+    let mut state = setup_test_app_state().await;
+    let test_user = user::Entity::find_by_id(1).one(&state.db).await.unwrap().unwrap();
+    let mut active: user::ActiveModel = test_user.into();
+    active.password_hash = Set(Some(hash_password("correct horse").unwrap()));
+    active.update(&state.db).await.unwrap();
+    state.timezone = ahead;
+    let server = TestServer::new(finrust::router::create_test_router(state)).unwrap();
+    let bearer = |token: &str| HeaderValue::from_str(&format!("Bearer {}", token)).unwrap();
+
+    let account: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/accounts")
+        .json(&serde_json::json!({
+            "name": "Checking",
+            "currency_code": "USD",
+            "owner_id": 1,
+        }))
+        .await
+        .json();
+    let start_date = today_in(behind) - chrono::Duration::days(3);
+    server
+        .post("/api/v1/recurring-transactions")
+        .json(&serde_json::json!({
+            "name": "Coffee",
+            "amount": "-3",
+            "start_date": start_date,
+            "period": "Daily",
+            "target_account_id": account.data["id"],
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    // The first instance that isn't overdue yet is the one due today
+    let due_today = |instances: &[serde_json::Value]| {
+        instances
+            .iter()
+            .filter(|instance| instance["overdue_days"] == 0)
+            .filter_map(|instance| instance["due_date"].as_str())
+            .min()
+            .map(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap())
+    };
+
+    // Anonymous requests use the server's timezone
+    let missing: ApiResponse<Vec<serde_json::Value>> = server
+        .get("/api/v1/recurring-transactions/missing-instances")
+        .add_query_param("start_date", start_date)
+        .await
+        .json();
+    assert_eq!(due_today(&missing.data), Some(today_in(ahead)));
+
+    // Logged-in users get their own timezone once they set one
+    let login: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/auth/login")
+        .json(&serde_json::json!({ "username": "test_user1", "password": "correct horse" }))
+        .await
+        .json();
+    let token = login.data["token"].as_str().unwrap().to_string();
+
+    let response = server
+        .put("/api/v1/users/1/settings")
+        .json(&serde_json::json!({ "active_scenario_id": null, "theme": null, "timezone": "Mars/Olympus_Mons" }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "INVALID_TIMEZONE");
+
+    server
+        .put("/api/v1/users/1/settings")
+        .json(&serde_json::json!({ "active_scenario_id": null, "theme": null, "timezone": "Etc/GMT+12" }))
+        .await
+        .assert_status_ok();
+    let settings: ApiResponse<serde_json::Value> = server.get("/api/v1/users/1/settings").await.json();
+    assert_eq!(settings.data["timezone"], "Etc/GMT+12");
+
+    let missing: ApiResponse<Vec<serde_json::Value>> = server
+        .get("/api/v1/recurring-transactions/missing-instances")
+        .add_query_param("start_date", start_date)
+        .add_header(header::AUTHORIZATION, bearer(&token))
+        .await
+        .json();
+    assert_eq!(due_today(&missing.data), Some(today_in(behind)));
+    assert_ne!(today_in(ahead), today_in(behind));
+}
+
+#[tokio::test]
+async fn test_account_shares() {
+    use axum::http::{header, HeaderValue};
+    use finrust::helpers::auth::hash_password;
+    use model::entities::user;
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+    let state = setup_test_app_state().await;
+    for user_id in [1, 2] {
+        let existing = user::Entity::find_by_id(user_id).one(&state.db).await.unwrap().unwrap();
+        let mut active: user::ActiveModel = existing.into();
+        active.password_hash = Set(Some(hash_password("correct horse").unwrap()));
+        active.update(&state.db).await.unwrap();
+    }
+    let server = TestServer::new(finrust::router::create_test_router(state)).unwrap();
+
+    let mut tokens = Vec::new();
+    for username in ["test_user1", "test_user2"] {
+        let body: ApiResponse<serde_json::Value> = server
+            .post("/api/v1/auth/login")
+            .json(&serde_json::json!({ "username": username, "password": "correct horse" }))
+            .await
+            .json();
+        tokens.push(HeaderValue::from_str(&format!("Bearer {}", body.data["token"].as_str().unwrap())).unwrap());
+    }
+    let (alice, bob) = (tokens[0].clone(), tokens[1].clone());
+
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/accounts")
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .json(&serde_json::json!({
+            "name": "Household",
+            "currency_code": "USD",
+            "owner_id": 1,
+            "include_in_statistics": true,
+        }))
+        .await
+        .json();
+    let account_id = body.data["id"].as_i64().unwrap();
+    server
+        .post(&format!("/api/v1/accounts/{}/manual-states", account_id))
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .json(&serde_json::json!({ "date": "2026-01-01", "amount": "1000" }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    let transaction = serde_json::json!({
+        "name": "Groceries",
+        "amount": "-45",
+        "date": "2026-01-10",
+        "target_account_id": account_id,
+    });
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/transactions")
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .json(&transaction)
+        .await
+        .json();
+    let transaction_id = body.data["id"].as_i64().unwrap();
+
+    // Sharing with the owner or a missing user is refused
+    let response = server
+        .put(&format!("/api/v1/accounts/{}/shares/1", account_id))
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .json(&serde_json::json!({ "permission": "Read" }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "SHARE_WITH_OWNER");
+    server
+        .put(&format!("/api/v1/accounts/{}/shares/999", account_id))
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .json(&serde_json::json!({ "permission": "Read" }))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+
+    let body: ApiResponse<serde_json::Value> = server
+        .put(&format!("/api/v1/accounts/{}/shares/2", account_id))
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .json(&serde_json::json!({ "permission": "Read" }))
+        .await
+        .json();
+    assert_eq!(body.data["username"], "test_user2");
+    assert_eq!(body.data["permission"], "Read");
+
+    // A read share shows the account, its balances and statistics
+    let shares: ApiResponse<Vec<serde_json::Value>> = server
+        .get(&format!("/api/v1/accounts/{}/shares", account_id))
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .json();
+    assert_eq!(shares.data.len(), 1);
+    let accounts: ApiResponse<Vec<serde_json::Value>> = server
+        .get("/api/v1/accounts")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .json();
+    assert_eq!(accounts.data.len(), 1);
+    let timeseries: ApiResponse<serde_json::Value> = server
+        .get(&format!("/api/v1/accounts/{}/timeseries", account_id))
+        .add_query_param("start_date", "2026-01-01")
+        .add_query_param("end_date", "2026-01-31")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .json();
+    assert!(!timeseries.data["data_points"].as_array().unwrap().is_empty());
+    let timeseries: ApiResponse<serde_json::Value> = server
+        .get("/api/v1/accounts/timeseries")
+        .add_query_param("start_date", "2026-01-01")
+        .add_query_param("end_date", "2026-01-31")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .json();
+    assert!(timeseries.data["data_points"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|point| point["account_id"] == account_id));
+    let statistics: ApiResponse<Vec<serde_json::Value>> = server
+        .get("/api/v1/accounts/statistics")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .json();
+    assert_eq!(statistics.data.len(), 1);
+    server
+        .get(&format!("/api/v1/accounts/{}/statistics", account_id))
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .assert_status_ok();
+
+    // ...but nothing can be booked or changed on it
+    let response = server
+        .post("/api/v1/transactions")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .json(&transaction)
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "ACCOUNT_READ_ONLY");
+    let response = server
+        .post(&format!("/api/v1/accounts/{}/manual-states", account_id))
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .json(&serde_json::json!({ "date": "2026-01-31", "amount": "955" }))
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "ACCOUNT_READ_ONLY");
+    server
+        .delete(&format!("/api/v1/transactions/{}", transaction_id))
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+
+    // Only the owner manages shares
+    server
+        .put(&format!("/api/v1/accounts/{}/shares/2", account_id))
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .json(&serde_json::json!({ "permission": "Write" }))
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+
+    // Upgrading to a write share allows booking
+    server
+        .put(&format!("/api/v1/accounts/{}/shares/2", account_id))
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .json(&serde_json::json!({ "permission": "Write" }))
+        .await
+        .assert_status_ok();
+    server
+        .post("/api/v1/transactions")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .json(&transaction)
+        .await
+        .assert_status(StatusCode::CREATED);
+    server
+        .post(&format!("/api/v1/accounts/{}/manual-states", account_id))
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .json(&serde_json::json!({ "date": "2026-01-31", "amount": "955" }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    server
+        .delete(&format!("/api/v1/accounts/{}", account_id))
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+
+    // Removing the share hides the account again
+    server
+        .delete(&format!("/api/v1/accounts/{}/shares/2", account_id))
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+    server
+        .delete(&format!("/api/v1/accounts/{}/shares/2", account_id))
+        .add_header(header::AUTHORIZATION, alice.clone())
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    server
+        .get(&format!("/api/v1/accounts/{}/timeseries", account_id))
+        .add_query_param("start_date", "2026-01-01")
+        .add_query_param("end_date", "2026-01-31")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    let statistics: ApiResponse<Vec<serde_json::Value>> = server
+        .get("/api/v1/accounts/statistics")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await
+        .json();
+    assert!(statistics.data.is_empty());
+}
+
+#[tokio::test]
+async fn test_recurring_transfer_across_currencies() {
+    use compute::account::forecast::recurring::recurring_amount_for_account;
+    use model::entities::recurring_transaction;
+    use sea_orm::EntityTrait;
+
+    let mut state = setup_test_app_state().await;
+    state.double_entry_strict = true;
+    let server = TestServer::new(finrust::router::create_test_router(state.clone())).unwrap();
+
+    let mut account_ids = Vec::new();
+    for (name, currency) in [("Checking", "CZK"), ("Euro Savings", "EUR")] {
+        let body: ApiResponse<serde_json::Value> = server
+            .post("/api/v1/accounts")
+            .json(&serde_json::json!({
+                "name": name,
+                "currency_code": currency,
+                "owner_id": 1,
+                "include_in_statistics": true,
+            }))
+            .await
+            .json();
+        account_ids.push(body.data["id"].as_i64().unwrap() as i32);
+    }
+    let (checking_id, savings_id) = (account_ids[0], account_ids[1]);
+    server
+        .post("/api/v1/exchange-rates")
+        .json(&serde_json::json!({
+            "base_currency": "EUR",
+            "quote_currency": "CZK",
+            "date": "2026-10-01",
+            "rate": "25"
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let transfer = |fx: serde_json::Value| {
+        let mut request = serde_json::json!({
+            "name": "Savings",
+            "amount": "100",
+            "start_date": "2026-10-01",
+            "period": "Monthly",
+            "target_account_id": savings_id,
+            "source_account_id": checking_id,
+        });
+        request.as_object_mut().unwrap().extend(fx.as_object().unwrap().clone());
+        request
+    };
+
+    // Without FX handling the legs can't balance across currencies
+    let response = server.post("/api/v1/recurring-transactions").json(&transfer(serde_json::json!({}))).await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "DOUBLE_ENTRY_VIOLATION");
+
+    for invalid in [
+        serde_json::json!({ "fx_mode": "FixedRate" }),
+        serde_json::json!({ "fx_mode": "FixedRate", "fx_rate": "0" }),
+        serde_json::json!({ "fx_mode": "ManualAmount", "source_amount": "-2400" }),
+    ] {
+        let response = server.post("/api/v1/recurring-transactions").json(&transfer(invalid)).await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+        let error: serde_json::Value = response.json();
+        assert_eq!(error["code"], "INVALID_FX_HANDLING");
+    }
+
+    let mut source_amounts = Vec::new();
+    for fx in [
+        serde_json::json!({ "fx_mode": "ExecutionRate" }),
+        serde_json::json!({ "fx_mode": "FixedRate", "fx_rate": "24.5" }),
+        serde_json::json!({ "fx_mode": "ManualAmount", "source_amount": "2400" }),
+    ] {
+        let response = server.post("/api/v1/recurring-transactions").json(&transfer(fx.clone())).await;
+        response.assert_status(StatusCode::CREATED);
+        let body: ApiResponse<serde_json::Value> = response.json();
+        assert_eq!(body.data["fx_mode"], fx["fx_mode"]);
+
+        let rule = recurring_transaction::Entity::find_by_id(body.data["id"].as_i64().unwrap() as i32)
+            .one(&state.db)
+            .await
+            .unwrap()
+            .unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 11, 1).unwrap();
+        assert_eq!(
+            recurring_amount_for_account(&state.db, &rule, savings_id, date).await.unwrap(),
+            Decimal::new(100, 0)
+        );
+        source_amounts.push(recurring_amount_for_account(&state.db, &rule, checking_id, date).await.unwrap());
+
+        if fx["fx_mode"] == "ExecutionRate" {
+            // No rate is known before the first stored one
+            let before = NaiveDate::from_ymd_opt(2026, 9, 1).unwrap();
+            assert_eq!(
+                recurring_amount_for_account(&state.db, &rule, checking_id, before).await.unwrap(),
+                Decimal::ZERO
+            );
+        }
+    }
+    assert_eq!(source_amounts, vec![Decimal::new(-2500, 0), Decimal::new(-2450, 0), Decimal::new(-2400, 0)]);
+}
+
+#[tokio::test]
+async fn test_planned_transactions_and_confirmation() {
+    use chrono::Duration;
+
+    let server = TestServer::new(finrust::router::create_test_router(setup_test_app_state().await)).unwrap();
+    let today = chrono::Utc::now().date_naive();
+
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/accounts")
+        .json(&serde_json::json!({
+            "name": "Checking",
+            "currency_code": "USD",
+            "owner_id": 1,
+            "include_in_statistics": true,
+        }))
+        .await
+        .json();
+    let account_id = body.data["id"].as_i64().unwrap() as i32;
+    server
+        .post(&format!("/api/v1/accounts/{}/manual-states", account_id))
+        .json(&serde_json::json!({ "date": today - Duration::days(10), "amount": "1000" }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let planned = |name: &str, amount: &str, date: NaiveDate| {
+        serde_json::json!({
+            "name": name,
+            "amount": amount,
+            "date": date,
+            "target_account_id": account_id,
+            "status": "Planned",
+        })
+    };
+    let response = server
+        .post("/api/v1/transactions")
+        .json(&planned("Car repair", "-200", today + Duration::days(5)))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["status"], "Planned");
+    let response = server
+        .post("/api/v1/transactions")
+        .json(&planned("Refund", "50", today - Duration::days(3)))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let body: ApiResponse<serde_json::Value> = response.json();
+    let refund_id = body.data["id"].as_i64().unwrap();
+
+    let server = &server;
+    let balances = || async move {
+        let body: ApiResponse<AccountStateTimeseries> = server
+            .get(&format!("/api/v1/accounts/{}/timeseries", account_id))
+            .add_query_param("start_date", today - Duration::days(5))
+            .add_query_param("end_date", today + Duration::days(10))
+            .await
+            .json();
+        let balance_on = |date: NaiveDate| {
+            body.data.data_points.iter().find(|point| point.date == date).map(|point| point.balance).unwrap()
+        };
+        (
+            balance_on(today - Duration::days(1)),
+            balance_on(today),
+            balance_on(today + Duration::days(10)),
+        )
+    };
+
+    // The overdue planned refund isn't part of the past balance but still expected from today on
+    assert_eq!(balances().await, (Decimal::new(1000, 0), Decimal::new(1050, 0), Decimal::new(850, 0)));
+
+    let body: ApiResponse<Vec<serde_json::Value>> =
+        server.get("/api/v1/transactions").add_query_param("status", "Planned").await.json();
+    assert_eq!(body.data.len(), 2);
+
+    // Confirming books the refund on the day the money arrived
+    let response = server
+        .post(&format!("/api/v1/transactions/{}/confirm", refund_id))
+        .json(&serde_json::json!({ "date": today - Duration::days(2), "amount": "45" }))
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["status"], "Confirmed");
+    assert_eq!(body.data["amount"].as_str().unwrap().parse::<Decimal>().unwrap(), Decimal::new(45, 0));
+    assert_eq!(balances().await, (Decimal::new(1045, 0), Decimal::new(1045, 0), Decimal::new(845, 0)));
+
+    let response = server
+        .post(&format!("/api/v1/transactions/{}/confirm", refund_id))
+        .json(&serde_json::json!({}))
+        .await;
+    response.assert_status(StatusCode::CONFLICT);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "NOT_PLANNED");
+
+    let body: ApiResponse<Vec<serde_json::Value>> =
+        server.get("/api/v1/transactions").add_query_param("status", "Planned").await.json();
+    assert_eq!(body.data.len(), 1);
+    assert_eq!(body.data[0]["name"], "Car repair");
+}
+
+#[tokio::test]
+async fn test_planned_transactions_are_not_spending() {
+    use finrust::handlers::budgets::{BudgetProgressResponse, BudgetResponse};
+
+    let server = TestServer::new(finrust::router::create_test_router(setup_test_app_state().await)).unwrap();
+
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/accounts")
+        .json(&serde_json::json!({
+            "name": "Checking",
+            "currency_code": "USD",
+            "owner_id": 1,
+            "include_in_statistics": true,
+        }))
+        .await
+        .json();
+    let account_id = body.data["id"].as_i64().unwrap();
+    let body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/categories").json(&serde_json::json!({ "name": "Car" })).await.json();
+    let category_id = body.data["id"].as_i64().unwrap();
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/tags")
+        .json(&serde_json::json!({ "name": "Car costs", "monthly_limit": "500" }))
+        .await
+        .json();
+    let tag_id = body.data["id"].as_i64().unwrap();
+    let budget: ApiResponse<BudgetResponse> = server
+        .post("/api/v1/budgets")
+        .json(&serde_json::json!({
+            "category_id": category_id,
+            "period": "Monthly",
+            "amount": "500",
+            "start_date": "2026-03-01"
+        }))
+        .await
+        .json();
+
+    let mut transaction_ids = Vec::new();
+    for (name, amount, status) in [("Fuel", "-60", "Confirmed"), ("Car repair", "-300", "Planned")] {
+        let body: ApiResponse<serde_json::Value> = server
+            .post("/api/v1/transactions")
+            .json(&serde_json::json!({
+                "name": name,
+                "amount": amount,
+                "date": "2026-03-10",
+                "target_account_id": account_id,
+                "category_id": category_id,
+                "status": status,
+            }))
+            .await
+            .json();
+        transaction_ids.push(body.data["id"].as_i64().unwrap());
+    }
+    server
+        .post(&format!("/api/v1/tags/{}/assign", tag_id))
+        .json(&serde_json::json!({ "transaction_ids": transaction_ids }))
+        .await
+        .assert_status_ok();
+
+    // Only the confirmed fuel purchase is spent; the planned repair may still not happen
+    let body: ApiResponse<BudgetProgressResponse> = server
+        .get(&format!("/api/v1/budgets/{}/progress", budget.data.id))
+        .add_query_param("start_date", "2026-03-31")
+        .add_query_param("end_date", "2026-03-31")
+        .await
+        .json();
+    assert_eq!(body.data.points[0].spent, Decimal::from(60));
+
+    let body: ApiResponse<Vec<serde_json::Value>> = server
+        .get("/api/v1/categories/stats?start_date=2026-03-01&end_date=2026-03-31")
+        .await
+        .json();
+    let car = body.data.iter().find(|stats| stats["category_id"] == category_id).unwrap();
+    assert_eq!(car["total_amount"].as_str().unwrap().parse::<Decimal>().unwrap(), Decimal::from(-60));
+
+    let body: ApiResponse<serde_json::Value> = server.get("/api/v1/tags/limits?year=2026&month=3").await.json();
+    assert_eq!(body.data["limits"][0]["spent"].as_str().unwrap().parse::<Decimal>().unwrap(), Decimal::from(60));
+
+    let body: ApiResponse<serde_json::Value> = server
+        .get("/api/v1/reports/cashflow?start_date=2026-03-01&end_date=2026-03-31")
+        .await
+        .json();
+    assert_eq!(body.data["total_expense"].as_str().unwrap().parse::<Decimal>().unwrap(), Decimal::from(60));
+}
+
+#[tokio::test]
+async fn test_transaction_search() {
+    let server = TestServer::new(finrust::router::create_test_router(setup_test_app_state().await)).unwrap();
+
+    let mut account_ids = Vec::new();
+    for name in ["Checking", "Savings"] {
+        let body: ApiResponse<serde_json::Value> = server
+            .post("/api/v1/accounts")
+            .json(&serde_json::json!({
+                "name": name,
+                "currency_code": "USD",
+                "owner_id": 1,
+                "include_in_statistics": true,
+            }))
+            .await
+            .json();
+        account_ids.push(body.data["id"].as_i64().unwrap());
+    }
+    let (checking_id, savings_id) = (account_ids[0], account_ids[1]);
+
+    for (name, description, amount, date, account_id) in [
+        ("Grocery store", None, "-45.20", "2026-03-02", checking_id),
+        ("Rent", Some("March rent"), "-900", "2026-03-01", checking_id),
+        ("Farmers market", Some("Weekly groceries"), "-30", "2026-03-09", checking_id),
+        ("Interest", None, "12.50", "2026-03-31", savings_id),
+        ("Grocery store", None, "-51.10", "2026-04-02", checking_id),
+    ] {
+        server
+            .post("/api/v1/transactions")
+            .json(&serde_json::json!({
+                "name": name,
+                "description": description,
+                "amount": amount,
+                "date": date,
+                "target_account_id": account_id,
+            }))
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+
+    let search = |params: &[(&str, String)]| {
+        let mut request = server.get("/api/v1/transactions/search");
+        for (key, value) in params {
+            request = request.add_query_param(key, value);
+        }
+        async move {
+            let body: ApiResponse<Vec<serde_json::Value>> = request.await.json();
+            body.data.iter().map(|t| t["name"].as_str().unwrap().to_string()).collect::<Vec<_>>()
+        }
+    };
+
+    // Free text matches the name or the description, newest first by default
+    assert_eq!(
+        search(&[("q", "grocer".to_string())]).await,
+        vec!["Grocery store", "Farmers market", "Grocery store"]
+    );
+
+    // Amount and date ranges combine with the account filter
+    assert_eq!(
+        search(&[
+            ("account_id", checking_id.to_string()),
+            ("min_amount", "-100".to_string()),
+            ("max_amount", "0".to_string()),
+            ("start_date", "2026-03-01".to_string()),
+            ("end_date", "2026-03-31".to_string()),
+            ("sort", "amount".to_string()),
+            ("order", "asc".to_string()),
+        ])
+        .await,
+        vec!["Grocery store", "Farmers market"]
+    );
+    assert_eq!(search(&[("account_id", savings_id.to_string())]).await, vec!["Interest"]);
+
+    // Pagination follows the requested order
+    assert_eq!(
+        search(&[
+            ("sort", "date".to_string()),
+            ("order", "asc".to_string()),
+            ("limit", "2".to_string()),
+            ("page", "2".to_string()),
+        ])
+        .await,
+        vec!["Farmers market", "Interest"]
+    );
+
+    // Tag filter
+    let body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/tags").json(&serde_json::json!({ "name": "Housing" })).await.json();
+    let tag_id = body.data["id"].as_i64().unwrap();
+    server
+        .post(&format!("/api/v1/tags/{}/assign", tag_id))
+        .json(&serde_json::json!({ "filter": { "name_contains": "Rent" } }))
+        .await
+        .assert_status_ok();
+    assert_eq!(search(&[("tag_id", tag_id.to_string())]).await, vec!["Rent"]);
+
+    // Inconsistent ranges are rejected
+    for (key, value, other_key, other_value) in [
+        ("min_amount", "10", "max_amount", "5"),
+        ("start_date", "2026-04-01", "end_date", "2026-03-01"),
+    ] {
+        let response = server
+            .get("/api/v1/transactions/search")
+            .add_query_param(key, value)
+            .add_query_param(other_key, other_value)
+            .await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = response.json();
+        assert_eq!(body["code"], "INVALID_SEARCH");
+    }
+    server
+        .get("/api/v1/transactions/search")
+        .add_query_param("page", 0)
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_list_pagination_metadata() {
+    let server = TestServer::new(finrust::router::create_test_router(setup_test_app_state().await)).unwrap();
+
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/accounts")
+        .json(&serde_json::json!({
+            "name": "Checking",
+            "currency_code": "USD",
+            "owner_id": 1,
+            "include_in_statistics": true,
+        }))
+        .await
+        .json();
+    let account_id = body.data["id"].as_i64().unwrap();
+
+    for day in 1..=5 {
+        server
+            .post("/api/v1/transactions")
+            .json(&serde_json::json!({
+                "name": format!("Coffee {}", day),
+                "amount": "-3.50",
+                "date": format!("2026-05-0{}", day),
+                "target_account_id": account_id,
+            }))
+            .await
+            .assert_status(StatusCode::CREATED);
+        server
+            .post("/api/v1/imported-transactions")
+            .json(&serde_json::json!({
+                "account_id": account_id,
+                "date": format!("2026-05-0{}", day),
+                "description": format!("COFFEE {}", day),
+                "amount": "-3.50",
+                "import_hash": format!("pagination_{}", day),
+            }))
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+
+    let page = |path: &'static str| {
+        let request = server.get(path).add_query_param("page", 3).add_query_param("limit", 2);
+        async move {
+            let response = request.await;
+            response.assert_status_ok();
+            let body: serde_json::Value = response.json();
+            assert_eq!(body["data"].as_array().unwrap().len(), 1, "{}", path);
+            body["pagination"].clone()
+        }
+    };
+
+    for path in ["/api/v1/transactions", "/api/v1/transactions/search", "/api/v1/imported-transactions"] {
+        assert_eq!(
+            page(path).await,
+            serde_json::json!({ "total_count": 5, "page": 3, "page_size": 2, "total_pages": 3 }),
+            "{}",
+            path
+        );
+    }
+
+    // Past the last page the metadata still describes the whole result set
+    let body: serde_json::Value = server
+        .get("/api/v1/transactions")
+        .add_query_param("page", 9)
+        .add_query_param("limit", 2)
+        .await
+        .json();
+    assert!(body["data"].as_array().unwrap().is_empty());
+    assert_eq!(body["pagination"]["total_count"], 5);
+
+    let body: serde_json::Value = server.get("/api/v1/recurring-transactions").await.json();
+    assert_eq!(
+        body["pagination"],
+        serde_json::json!({ "total_count": 0, "page": 1, "page_size": 50, "total_pages": 0 })
+    );
+}
+
+#[tokio::test]
+async fn test_linear_balance_interpolation() {
+    let server = TestServer::new(finrust::router::create_test_router(setup_test_app_state().await)).unwrap();
+
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/accounts")
+        .json(&serde_json::json!({
+            "name": "Pension Fund",
+            "currency_code": "USD",
+            "owner_id": 1,
+            "include_in_statistics": true,
+            "account_kind": "Investment",
+            "balance_interpolation": "Linear",
+        }))
+        .await
+        .json();
+    assert_eq!(body.data["balance_interpolation"], "Linear");
+    let account_id = body.data["id"].as_i64().unwrap();
+
+    for (date, amount) in [("2025-01-01", "1000"), ("2025-01-11", "2000")] {
+        server
+            .post(&format!("/api/v1/accounts/{}/manual-states", account_id))
+            .json(&serde_json::json!({ "date": date, "amount": amount }))
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+
+    let query = TimeseriesQuery {
+        start_date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+        end_date: NaiveDate::from_ymd_opt(2025, 1, 20).unwrap(),
+        include_ignored: true,
+        scenario_id: None,
+    };
+    let balance_on = |timeseries: &AccountStateTimeseries, day: u32| -> Decimal {
+        let date = NaiveDate::from_ymd_opt(2025, 1, day).unwrap();
+        timeseries
+            .data_points
+            .iter()
+            .find(|point| point.account_id == account_id as i32 && point.date == date)
+            .map(|point| point.balance)
+            .unwrap_or_else(|| panic!("No data point on {date}"))
+    };
+
+    let timeseries: ApiResponse<AccountStateTimeseries> = server
+        .get(&format!("/api/v1/accounts/{}/timeseries", account_id))
+        .add_query_params(&query)
+        .await
+        .json();
+    assert_eq!(balance_on(&timeseries.data, 1), Decimal::new(1000, 0));
+    assert_eq!(balance_on(&timeseries.data, 6), Decimal::new(1500, 0));
+    assert_eq!(balance_on(&timeseries.data, 11), Decimal::new(2000, 0));
+    assert_eq!(balance_on(&timeseries.data, 20), Decimal::new(2000, 0));
+
+    // Switching back to steps holds the first state until the second one
+    let body: ApiResponse<serde_json::Value> = server
+        .put(&format!("/api/v1/accounts/{}", account_id))
+        .json(&serde_json::json!({ "balance_interpolation": "Step" }))
+        .await
+        .json();
+    assert_eq!(body.data["balance_interpolation"], "Step");
+
+    let timeseries: ApiResponse<AccountStateTimeseries> = server
+        .get(&format!("/api/v1/accounts/{}/timeseries", account_id))
+        .add_query_params(&query)
+        .await
+        .json();
+    assert_eq!(balance_on(&timeseries.data, 6), Decimal::new(1000, 0));
+}
+
+#[tokio::test]
+async fn test_account_comparison() {
+    let server = TestServer::new(finrust::router::create_test_router(setup_test_app_state().await)).unwrap();
+
+    let mut account_ids = Vec::new();
+    for name in ["Checking", "Savings"] {
+        let body: ApiResponse<serde_json::Value> = server
+            .post("/api/v1/accounts")
+            .json(&serde_json::json!({
+                "name": name,
+                "currency_code": "USD",
+                "owner_id": 1,
+                "include_in_statistics": true,
+            }))
+            .await
+            .json();
+        account_ids.push(body.data["id"].as_i64().unwrap());
+    }
+    let (checking_id, savings_id) = (account_ids[0], account_ids[1]);
+
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Rent" }))
+        .await
+        .json();
+    let rent_id = body.data["id"].as_i64().unwrap();
+
+    for transaction in [
+        serde_json::json!({ "name": "Salary", "amount": "1000", "date": "2026-01-05", "target_account_id": checking_id }),
+        serde_json::json!({ "name": "Rent", "amount": "-300", "date": "2026-02-10", "target_account_id": checking_id, "category_id": rent_id }),
+        serde_json::json!({ "name": "Refund", "amount": "50", "date": "2026-02-15", "target_account_id": checking_id }),
+        serde_json::json!({ "name": "Saving", "amount": "200", "date": "2026-02-20", "target_account_id": savings_id, "source_account_id": checking_id }),
+    ] {
+        server.post("/api/v1/transactions").json(&transaction).await.assert_status(StatusCode::CREATED);
+    }
+
+    let response = server
+        .get("/api/v1/accounts/compare")
+        .add_query_param("date_a", "2026-01-31")
+        .add_query_param("date_b", "2026-02-28")
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    let data = &body.data;
+    let decimal = |value: &serde_json::Value| value.as_str().unwrap().parse::<Decimal>().unwrap();
+
+    let change = |account_id: i64| {
+        let account = data["accounts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|account| account["account_id"] == account_id)
+            .unwrap();
+        (decimal(&account["balance_a"]), decimal(&account["balance_b"]), decimal(&account["change"]))
+    };
+    assert_eq!(change(checking_id), (Decimal::from(1000), Decimal::from(550), Decimal::from(-450)));
+    assert_eq!(change(savings_id), (Decimal::ZERO, Decimal::from(200), Decimal::from(200)));
+    assert_eq!(decimal(&data["total_a"]), Decimal::from(1000));
+    assert_eq!(decimal(&data["total_b"]), Decimal::from(750));
+    assert_eq!(decimal(&data["total_change"]), Decimal::from(-250));
+
+    // The transfer between the two compared accounts doesn't change their total
+    let transactions = data["top_transactions"].as_array().unwrap();
+    assert_eq!(transactions.len(), 2);
+    assert_eq!(transactions[0]["name"], "Rent");
+    assert_eq!(decimal(&transactions[0]["amount"]), Decimal::from(-300));
+    assert_eq!(transactions[1]["name"], "Refund");
+
+    let categories = data["top_categories"].as_array().unwrap();
+    assert_eq!(categories.len(), 2);
+    assert_eq!(categories[0]["category_id"], rent_id);
+    assert_eq!(categories[0]["name"], "Rent");
+    assert_eq!(decimal(&categories[0]["amount"]), Decimal::from(-300));
+    assert_eq!(categories[1]["name"], "Uncategorized");
+    assert_eq!(decimal(&categories[1]["amount"]), Decimal::from(50));
+
+    server
+        .get("/api/v1/accounts/compare")
+        .add_query_param("date_a", "2026-03-01")
+        .add_query_param("date_b", "2026-02-01")
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_bulk_update_and_delete_transactions() {
+    let server = TestServer::new(finrust::router::create_test_router(setup_test_app_state().await)).unwrap();
+
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/accounts")
+        .json(&serde_json::json!({
+            "name": "Checking",
+            "currency_code": "USD",
+            "owner_id": 1,
+            "include_in_statistics": true,
+        }))
+        .await
+        .json();
+    let account_id = body.data["id"].as_i64().unwrap();
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Coffee" }))
+        .await
+        .json();
+    let category_id = body.data["id"].as_i64().unwrap();
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/tags")
+        .json(&serde_json::json!({ "name": "Habits" }))
+        .await
+        .json();
+    let tag_id = body.data["id"].as_i64().unwrap();
+
+    let mut transaction_ids = Vec::new();
+    for (name, date) in [("Coffee shop", "2026-03-01"), ("Coffee beans", "2026-03-02"), ("Groceries", "2026-03-03")] {
+        let body: ApiResponse<serde_json::Value> = server
+            .post("/api/v1/transactions")
+            .json(&serde_json::json!({
+                "name": name,
+                "amount": "-4.20",
+                "date": date,
+                "target_account_id": account_id,
+            }))
+            .await
+            .json();
+        transaction_ids.push(body.data["id"].as_i64().unwrap());
+    }
+
+    // A dry run only counts
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/transactions/bulk-update")
+        .json(&serde_json::json!({
+            "filter": { "name_contains": "Coffee" },
+            "patch": { "category_id": category_id, "add_tag_ids": [tag_id] },
+            "dry_run": true,
+        }))
+        .await
+        .json();
+    assert_eq!(body.data["matched"], 2);
+    assert_eq!(body.data["affected"], 2);
+    let body: ApiResponse<serde_json::Value> =
+        server.get(&format!("/api/v1/transactions/{}", transaction_ids[0])).await.json();
+    assert!(body.data["category_id"].is_null());
+
+    let response = server
+        .post("/api/v1/transactions/bulk-update")
+        .json(&serde_json::json!({
+            "filter": { "name_contains": "Coffee" },
+            "patch": { "category_id": category_id, "add_tag_ids": [tag_id] },
+        }))
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["affected"], 2);
+    for transaction_id in &transaction_ids[..2] {
+        let body: ApiResponse<serde_json::Value> =
+            server.get(&format!("/api/v1/transactions/{}", transaction_id)).await.json();
+        assert_eq!(body.data["category_id"], category_id);
+        assert_eq!(body.data["tags"][0]["id"], tag_id);
+    }
+    let body: ApiResponse<serde_json::Value> =
+        server.get(&format!("/api/v1/transactions/{}", transaction_ids[2])).await.json();
+    assert!(body.data["category_id"].is_null());
+
+    // Tagging again doesn't duplicate links, removing detaches them
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/transactions/bulk-update")
+        .json(&serde_json::json!({
+            "transaction_ids": [transaction_ids[0]],
+            "patch": { "add_tag_ids": [tag_id], "remove_tag_ids": [] },
+        }))
+        .await
+        .json();
+    assert_eq!(body.data["affected"], 1);
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/transactions/bulk-update")
+        .json(&serde_json::json!({
+            "transaction_ids": transaction_ids,
+            "patch": { "remove_tag_ids": [tag_id] },
+        }))
+        .await
+        .json();
+    assert_eq!(body.data["affected"], 3);
+    let body: ApiResponse<serde_json::Value> =
+        server.get(&format!("/api/v1/transactions/{}", transaction_ids[1])).await.json();
+    assert!(body.data["tags"].as_array().unwrap().is_empty());
+
+    for request in [
+        serde_json::json!({ "patch": { "category_id": category_id } }),
+        serde_json::json!({ "filter": {}, "patch": { "category_id": category_id } }),
+        serde_json::json!({ "transaction_ids": transaction_ids, "patch": {} }),
+        serde_json::json!({ "transaction_ids": transaction_ids, "patch": { "category_id": 99999 } }),
+        serde_json::json!({ "transaction_ids": transaction_ids, "patch": { "add_tag_ids": [99999] } }),
+    ] {
+        server
+            .post("/api/v1/transactions/bulk-update")
+            .json(&request)
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    let response = server
+        .post("/api/v1/transactions/bulk-delete")
+        .json(&serde_json::json!({ "transaction_ids": [transaction_ids[0], transaction_ids[2], 99999] }))
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["matched"], 2);
+    assert_eq!(body.data["affected"], 2);
+    assert_eq!(body.data["not_found"], serde_json::json!([99999]));
+    server
+        .get(&format!("/api/v1/transactions/{}", transaction_ids[0]))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    server
+        .get(&format!("/api/v1/transactions/{}", transaction_ids[1]))
+        .await
+        .assert_status_ok();
+}
+
+#[tokio::test]
+async fn test_bulk_update_category_keeps_double_entry() {
+    let mut state = setup_test_app_state().await;
+    state.double_entry_strict = true;
+    let server = TestServer::new(finrust::router::create_test_router(state)).unwrap();
+
+    let mut account_ids = Vec::new();
+    for name in ["Checking", "Savings"] {
+        let body: ApiResponse<serde_json::Value> = server
+            .post("/api/v1/accounts")
+            .json(&serde_json::json!({ "name": name, "currency_code": "USD", "owner_id": 1 }))
+            .await
+            .json();
+        account_ids.push(body.data["id"].as_i64().unwrap());
+    }
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Groceries" }))
+        .await
+        .json();
+    let category_id = body.data["id"].as_i64().unwrap();
+    let mut transaction_ids = Vec::new();
+    for (name, source_account_id, category_id) in [
+        ("Shopping", None, Some(category_id)),
+        ("Transfer", Some(account_ids[1]), None),
+    ] {
+        let response = server
+            .post("/api/v1/transactions")
+            .json(&serde_json::json!({
+                "name": name,
+                "amount": "-20.00",
+                "date": "2026-03-01",
+                "target_account_id": account_ids[0],
+                "source_account_id": source_account_id,
+                "category_id": category_id,
+            }))
+            .await;
+        response.assert_status(StatusCode::CREATED);
+        transaction_ids.push(response.json::<ApiResponse<serde_json::Value>>().data["id"].as_i64().unwrap());
+    }
+
+    // A transfer can't post to a category, so nothing is changed, on a dry run neither
+    for dry_run in [true, false] {
+        let response = server
+            .post("/api/v1/transactions/bulk-update")
+            .json(&serde_json::json!({
+                "transaction_ids": transaction_ids,
+                "patch": { "category_id": category_id, "include_in_statistics": false },
+                "dry_run": dry_run,
+            }))
+            .await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = response.json();
+        assert_eq!(body["code"], "DOUBLE_ENTRY_VIOLATION");
+        assert!(body["error"].as_str().unwrap().contains(&transaction_ids[1].to_string()));
+    }
+    for transaction_id in &transaction_ids {
+        let body: ApiResponse<serde_json::Value> =
+            server.get(&format!("/api/v1/transactions/{}", transaction_id)).await.json();
+        assert_eq!(body.data["include_in_statistics"], true);
+    }
+
+    let response = server
+        .post("/api/v1/transactions/bulk-update")
+        .json(&serde_json::json!({
+            "transaction_ids": [transaction_ids[0]],
+            "patch": { "category_id": category_id, "include_in_statistics": false },
+        }))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<ApiResponse<serde_json::Value>>().data["affected"], 1);
+}
+
+#[tokio::test]
+async fn test_recurring_instances_carry_category_and_scenario() {
+    let server = TestServer::new(finrust::router::create_test_router(setup_test_app_state().await)).unwrap();
+
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/accounts")
+        .json(&serde_json::json!({
+            "name": "Checking",
+            "currency_code": "USD",
+            "owner_id": 1,
+            "include_in_statistics": true,
+        }))
+        .await
+        .json();
+    let account_id = body.data["id"].as_i64().unwrap();
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Utilities" }))
+        .await
+        .json();
+    let category_id = body.data["id"].as_i64().unwrap();
+    let body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/scenarios")
+        .json(&serde_json::json!({ "name": "New flat" }))
+        .await
+        .json();
+    let scenario_id = body.data["id"].as_i64().unwrap();
+
+    let mut recurring_ids = Vec::new();
+    for request in [
+        serde_json::json!({
+            "name": "Electricity",
+            "amount": "-80",
+            "start_date": "2026-01-10",
+            "period": "Monthly",
+            "target_account_id": account_id,
+            "category_id": category_id,
+        }),
+        serde_json::json!({
+            "name": "Higher rent",
+            "amount": "-1200",
+            "start_date": "2026-01-01",
+            "period": "Monthly",
+            "target_account_id": account_id,
+            "scenario_id": scenario_id,
+            "is_simulated": true,
+        }),
+    ] {
+        let body: ApiResponse<serde_json::Value> =
+            server.post("/api/v1/recurring-transactions").json(&request).await.json();
+        recurring_ids.push(body.data["id"].as_i64().unwrap());
+    }
+    for recurring_id in &recurring_ids {
+        server
+            .post(&format!("/api/v1/recurring-transactions/{}/instances", recurring_id))
+            .json(&serde_json::json!({ "date": "2026-02-10" }))
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+
+    let response = server.get("/api/v1/recurring-instances").await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    let instance = |recurring_id: i64| {
+        body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|instance| instance["recurring_transaction_id"] == recurring_id)
+            .unwrap()
+            .clone()
+    };
+
+    let electricity = instance(recurring_ids[0]);
+    assert_eq!(electricity["category_id"], category_id);
+    assert_eq!(electricity["category_name"], "Utilities");
+    assert!(electricity["scenario_id"].is_null());
+    assert_eq!(electricity["is_simulated"], false);
+    assert_eq!(electricity["target_account_name"], "Checking");
+
+    let rent = instance(recurring_ids[1]);
+    assert!(rent["category_id"].is_null());
+    assert_eq!(rent["scenario_id"], scenario_id);
+    assert_eq!(rent["scenario_name"], "New flat");
+    assert_eq!(rent["is_simulated"], true);
+
+    let body: ApiResponse<serde_json::Value> = server
+        .get(&format!("/api/v1/recurring-instances/{}", electricity["id"]))
+        .await
+        .json();
+    assert_eq!(body.data["category_name"], "Utilities");
+}
+
+#[tokio::test]
+async fn test_transaction_currency_override() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/accounts")
+        .json(&serde_json::json!({ "name": "Card", "currency_code": "CZK", "owner_id": 1 }))
+        .await
+        .json();
+    let account_id = account_body.data["id"].as_i64().unwrap();
+    server
+        .post("/api/v1/exchange-rates")
+        .json(&serde_json::json!({
+            "base_currency": "EUR",
+            "quote_currency": "CZK",
+            "date": "2025-06-01",
+            "rate": "25.00"
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    // Paid 10 EUR abroad, 257.50 CZK settled; the rate is derived from both amounts
+    let response = server
+        .post("/api/v1/transactions")
+        .json(&serde_json::json!({
+            "name": "Museum tickets",
+            "amount": "-257.50",
+            "date": "2025-06-03",
+            "target_account_id": account_id,
+            "original_amount": "10",
+            "original_currency": "eur",
+        }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let body: ApiResponse<serde_json::Value> = response.json();
+    let transaction_id = body.data["id"].as_i64().unwrap();
+    let dec = |value: &serde_json::Value| value.as_str().unwrap().parse::<Decimal>().unwrap();
+    assert_eq!(dec(&body.data["amount"]), Decimal::new(-25750, 2));
+    assert_eq!(dec(&body.data["original_amount"]), Decimal::from(-10));
+    assert_eq!(body.data["original_currency"], "EUR");
+    assert_eq!(dec(&body.data["conversion_rate"]), Decimal::new(2575, 2));
+
+    // 7.50 CZK more than 10 EUR at the market rate of 25.00
+    let stats: ApiResponse<serde_json::Value> = server
+        .get(&format!("/api/v1/accounts/{}/statistics?year=2025", account_id))
+        .await
+        .json();
+    assert_eq!(dec(&stats.data["statistics"][0]["fx_fees"]), Decimal::new(750, 2));
+
+    for invalid in [
+        serde_json::json!({ "original_amount": "100", "original_currency": "CZK" }),
+        serde_json::json!({ "original_currency": "EUR" }),
+        serde_json::json!({ "original_amount": "10", "original_currency": "EUR", "conversion_rate": "0" }),
+    ] {
+        let mut request = serde_json::json!({
+            "name": "Invalid",
+            "amount": "-100",
+            "date": "2025-06-03",
+            "target_account_id": account_id,
+        });
+        request.as_object_mut().unwrap().extend(invalid.as_object().unwrap().clone());
+        let response = server.post("/api/v1/transactions").json(&request).await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = response.json();
+        assert_eq!(body["code"], "INVALID_CURRENCY_CONVERSION");
+    }
+
+    // Changing the settled amount re-derives the rate
+    let body: ApiResponse<serde_json::Value> = server
+        .put(&format!("/api/v1/transactions/{}", transaction_id))
+        .json(&serde_json::json!({ "amount": "-260" }))
+        .await
+        .json();
+    assert_eq!(dec(&body.data["original_amount"]), Decimal::from(-10));
+    assert_eq!(dec(&body.data["conversion_rate"]), Decimal::from(26));
+
+    let response = server
+        .get(&format!("/api/v1/transactions/{}", transaction_id))
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["original_currency"], "EUR");
+
+    let body: ApiResponse<serde_json::Value> = server
+        .put(&format!("/api/v1/transactions/{}", transaction_id))
+        .json(&serde_json::json!({ "clear_original_currency": true }))
+        .await
+        .json();
+    assert!(body.data["original_amount"].is_null());
+    assert!(body.data["original_currency"].is_null());
+    assert!(body.data["conversion_rate"].is_null());
+
+    let stats: ApiResponse<serde_json::Value> = server
+        .get(&format!("/api/v1/accounts/{}/statistics?year=2025", account_id))
+        .await
+        .json();
+    assert!(stats.data["statistics"][0]["fx_fees"].is_null());
+}
+
+#[tokio::test]
+async fn test_safe_to_spend() {
+    use chrono::Datelike;
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+    let today = chrono::Utc::now().date_naive();
+    let day = |offset: i64| today + chrono::Duration::days(offset);
+
+    let account_body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/accounts")
+        .json(&serde_json::json!({ "name": "Checking", "currency_code": "CZK", "owner_id": 1 }))
+        .await
+        .json();
+    let account_id = account_body.data["id"].as_i64().unwrap();
+    let mut category_ids = Vec::new();
+    for name in ["Utilities", "Groceries"] {
+        let body: ApiResponse<serde_json::Value> =
+            server.post("/api/v1/categories").json(&serde_json::json!({ "name": name })).await.json();
+        category_ids.push(body.data["id"].as_i64().unwrap());
+    }
+
+    for request in [
+        serde_json::json!({ "name": "Opening", "amount": "10000", "date": today, "target_account_id": account_id }),
+        serde_json::json!({
+            "name": "Shopping",
+            "amount": "-100",
+            "date": today,
+            "target_account_id": account_id,
+            "category_id": category_ids[1],
+        }),
+        serde_json::json!({
+            "name": "Concert tickets",
+            "amount": "-300",
+            "date": day(5),
+            "target_account_id": account_id,
+            "status": "Planned",
+        }),
+    ] {
+        server.post("/api/v1/transactions").json(&request).await.assert_status(StatusCode::CREATED);
+    }
+    server
+        .post("/api/v1/recurring-transactions")
+        .json(&serde_json::json!({
+            "name": "Electricity",
+            "amount": "-1200",
+            "start_date": day(3),
+            "period": "Monthly",
+            "target_account_id": account_id,
+            "category_id": category_ids[0],
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    server
+        .post("/api/v1/recurring-incomes")
+        .json(&serde_json::json!({
+            "name": "Salary",
+            "amount": "30000",
+            "start_date": day(10),
+            "period": "Monthly",
+            "target_account_id": account_id,
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    server
+        .post("/api/v1/budgets")
+        .json(&serde_json::json!({
+            "category_id": category_ids[1],
+            "period": "Yearly",
+            "amount": "500",
+            "start_date": today,
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let response = server.get("/api/v1/statistics/safe-to-spend").await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    let dec = |value: &serde_json::Value| value.as_str().unwrap().parse::<Decimal>().unwrap();
+    // The yearly budget's period ends today on the last day of the year
+    let budget_left = if today.month() == 12 && today.day() == 31 { 0 } else { 400 };
+
+    assert_eq!(body.data["account_ids"], serde_json::json!([account_id]));
+    assert_eq!(dec(&body.data["balance"]), Decimal::from(9900));
+    assert_eq!(body.data["next_income_date"], serde_json::json!(day(10)));
+    assert_eq!(body.data["until"], serde_json::json!(day(9)));
+    let commitments = body.data["commitments"].as_array().unwrap();
+    assert_eq!(commitments[0]["kind"], "RecurringPayment");
+    assert_eq!(commitments[0]["name"], "Electricity");
+    assert_eq!(commitments[0]["date"], serde_json::json!(day(3)));
+    assert_eq!(dec(&commitments[0]["amount"]), Decimal::from(1200));
+    assert_eq!(commitments[1]["kind"], "PlannedTransaction");
+    assert_eq!(dec(&commitments[1]["amount"]), Decimal::from(300));
+    if budget_left > 0 {
+        assert_eq!(commitments[2]["kind"], "BudgetRemainder");
+        assert_eq!(commitments[2]["name"], "Groceries");
+        assert_eq!(dec(&commitments[2]["amount"]), Decimal::from(budget_left));
+    }
+    assert_eq!(dec(&body.data["committed"]), Decimal::from(1500 + budget_left));
+    assert_eq!(dec(&body.data["safe_to_spend"]), Decimal::from(8400 - budget_left));
+
+    let selected: ApiResponse<serde_json::Value> = server
+        .get(&format!("/api/v1/statistics/safe-to-spend?account_ids={}", account_id))
+        .await
+        .json();
+    assert_eq!(selected.data["safe_to_spend"], body.data["safe_to_spend"]);
+
+    server
+        .get("/api/v1/statistics/safe-to-spend?account_ids=abc")
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    server
+        .get("/api/v1/statistics/safe-to-spend?account_ids=9999")
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_imported_transaction_category_suggestions() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_resp = server
+        .post("/api/v1/accounts")
+        .json(&serde_json::json!({ "name": "Checking", "currency_code": "CZK", "owner_id": 1 }))
+        .await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap();
+
+    let mut category_ids = Vec::new();
+    for name in ["Groceries", "Transport"] {
+        let response = server
+            .post("/api/v1/categories")
+            .json(&serde_json::json!({ "name": name }))
+            .await;
+        response.assert_status(StatusCode::CREATED);
+        let body: ApiResponse<serde_json::Value> = response.json();
+        category_ids.push(body.data["id"].as_i64().unwrap());
+    }
+    let (groceries_id, transport_id) = (category_ids[0], category_ids[1]);
+
+    let rows = [
+        ("CARD PAYMENT TESCO PRAHA 4411", Some(groceries_id)),
+        ("CARD PAYMENT TESCO BRNO 0192", Some(groceries_id)),
+        ("CARD PAYMENT PRAGUE TRANSIT 77", Some(transport_id)),
+        ("CARD PAYMENT TESCO 1234", None),
+    ];
+    let mut ids = Vec::new();
+    for (index, (description, category_id)) in rows.into_iter().enumerate() {
+        let response = server
+            .post("/api/v1/imported-transactions")
+            .json(&serde_json::json!({
+                "account_id": account_id,
+                "date": "2026-09-01",
+                "description": description,
+                "amount": "-250.00",
+                "import_hash": format!("suggestion-{}", index),
+                "category_id": category_id,
+            }))
+            .await;
+        response.assert_status(StatusCode::CREATED);
+        let body: ApiResponse<serde_json::Value> = response.json();
+        ids.push(body.data["id"].as_i64().unwrap());
+    }
+    // One-off history counts as well
+    server
+        .post("/api/v1/transactions")
+        .json(&serde_json::json!({
+            "name": "Prague transit monthly pass",
+            "amount": "-550.00",
+            "date": "2026-09-02",
+            "target_account_id": account_id,
+            "category_id": transport_id,
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let response = server
+        .get(&format!("/api/v1/imported-transactions/{}/category-suggestions", ids[3]))
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    let data = &body.data;
+    assert_eq!(data["keywords"], serde_json::json!(["card", "payment", "tesco"]));
+    let suggestions = data["suggestions"].as_array().unwrap();
+    assert_eq!(suggestions.len(), 2);
+    assert_eq!(suggestions[0]["category_id"], groceries_id);
+    assert_eq!(suggestions[0]["category_name"], "Groceries");
+    assert_eq!(suggestions[0]["matches"], 2);
+    assert_eq!(suggestions[1]["category_id"], transport_id);
+    assert_eq!(suggestions[1]["matches"], 1);
+    let confidence = |s: &serde_json::Value| s["confidence"].as_f64().unwrap();
+    assert!(confidence(&suggestions[0]) > confidence(&suggestions[1]));
+    assert!(confidence(&suggestions[0]) <= 1.0);
+
+    // The limit keeps the most confident suggestions
+    let response = server
+        .get(&format!("/api/v1/imported-transactions/{}/category-suggestions?limit=1", ids[3]))
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    let suggestions = body.data["suggestions"].as_array().unwrap();
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0]["category_id"], groceries_id);
+
+    server
+        .get("/api/v1/imported-transactions/9999/category-suggestions")
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_full_export_archive() {
+    use std::io::Read;
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_resp = server
+        .post("/api/v1/accounts")
+        .json(&serde_json::json!({ "name": "Checking, main", "currency_code": "EUR", "owner_id": 1 }))
+        .await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_body: ApiResponse<serde_json::Value> = account_resp.json();
+    let account_id = account_body.data["id"].as_i64().unwrap();
+
+    server
+        .post(&format!("/api/v1/accounts/{}/manual-states", account_id))
+        .json(&serde_json::json!({ "date": "2026-01-01", "amount": "1000.00" }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    for name in ["Rent", "Groceries"] {
+        server
+            .post("/api/v1/transactions")
+            .json(&serde_json::json!({
+                "name": name,
+                "amount": "-50.00",
+                "date": "2026-01-05",
+                "target_account_id": account_id,
+            }))
+            .await
+            .assert_status(StatusCode::CREATED);
+    }
+
+    let read_archive = |bytes: Vec<u8>| {
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+        let mut files = std::collections::HashMap::new();
+        for index in 0..archive.len() {
+            let mut file = archive.by_index(index).unwrap();
+            let mut content = String::new();
+            file.read_to_string(&mut content).unwrap();
+            files.insert(file.name().to_string(), content);
+        }
+        files
+    };
+
+    let response = server.get("/api/v1/export/full").await;
+    response.assert_status_ok();
+    assert_eq!(response.header("content-type").to_str().unwrap(), "application/zip");
+    assert!(response
+        .header("content-disposition")
+        .to_str()
+        .unwrap()
+        .starts_with("attachment; filename=\"finrust-export-"));
+    let files = read_archive(response.as_bytes().to_vec());
+
+    let manifest: serde_json::Value = serde_json::from_str(&files["manifest.json"]).unwrap();
+    assert_eq!(manifest["format"], "json");
+    let tables = manifest["tables"].as_array().unwrap();
+    for table in [
+        "accounts",
+        "categories",
+        "tags",
+        "manual_account_states",
+        "one_off_transactions",
+        "recurring_transactions",
+        "recurring_transaction_instances",
+        "recurring_incomes",
+        "account_notes",
+        "account_documents",
+        "account_shares",
+        "accounts_allowed_users",
+        "budgets",
+        "exchange_rates",
+        "locked_periods",
+        "holidays",
+    ] {
+        assert!(tables.iter().any(|entry| entry["table"] == table), "missing table {}", table);
+    }
+    let rows_of = |table: &str| {
+        tables.iter().find(|entry| entry["table"] == table).unwrap()["rows"].as_u64().unwrap()
+    };
+    assert_eq!(rows_of("accounts"), 1);
+    assert_eq!(rows_of("manual_account_states"), 1);
+    assert_eq!(rows_of("one_off_transactions"), 2);
+
+    let accounts: Vec<serde_json::Value> = serde_json::from_str(&files["accounts.json"]).unwrap();
+    assert_eq!(accounts[0]["id"], account_id);
+    assert_eq!(accounts[0]["name"], "Checking, main");
+    let transactions: Vec<serde_json::Value> = serde_json::from_str(&files["one_off_transactions.json"]).unwrap();
+    assert_eq!(transactions[0]["name"], "Rent");
+    assert_eq!(transactions[1]["name"], "Groceries");
+
+    let response = server.get("/api/v1/export/full?format=csv").await;
+    response.assert_status_ok();
+    let files = read_archive(response.as_bytes().to_vec());
+    let manifest: serde_json::Value = serde_json::from_str(&files["manifest.json"]).unwrap();
+    assert_eq!(manifest["format"], "csv");
+    let accounts_csv = &files["accounts.csv"];
+    let mut lines = accounts_csv.lines();
+    assert!(lines.next().unwrap().starts_with("id,name,"));
+    assert!(lines.next().unwrap().starts_with(&format!("{},\"Checking, main\",", account_id)));
+    assert!(files.contains_key("one_off_transactions.csv"));
+    assert!(!files.contains_key("accounts.json"));
+}
+
+#[tokio::test]
+async fn test_full_export_only_contains_accessible_accounts() {
+    use axum::http::{header, HeaderValue};
+    use finrust::helpers::auth::hash_password;
+    use model::entities::user;
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+    use std::io::Read;
+
+    let state = setup_test_app_state().await;
+    for user_id in [1, 2] {
+        let existing = user::Entity::find_by_id(user_id).one(&state.db).await.unwrap().unwrap();
+        let mut active: user::ActiveModel = existing.into();
+        active.password_hash = Set(Some(hash_password("correct horse").unwrap()));
+        active.update(&state.db).await.unwrap();
+    }
+    let server = TestServer::new(finrust::router::create_test_router(state)).unwrap();
+
+    let mut tokens = Vec::new();
+    for username in ["test_user1", "test_user2"] {
+        let body: ApiResponse<serde_json::Value> = server
+            .post("/api/v1/auth/login")
+            .json(&serde_json::json!({ "username": username, "password": "correct horse" }))
+            .await
+            .json();
+        tokens.push(HeaderValue::from_str(&format!("Bearer {}", body.data["token"].as_str().unwrap())).unwrap());
+    }
+    let (alice, bob) = (tokens[0].clone(), tokens[1].clone());
+
+    let mut accounts = Vec::new();
+    for (token, name, owner_id) in [(&alice, "Alice checking", 1), (&bob, "Bob checking", 2)] {
+        let body: ApiResponse<serde_json::Value> = server
+            .post("/api/v1/accounts")
+            .add_header(header::AUTHORIZATION, token.clone())
+            .json(&serde_json::json!({ "name": name, "currency_code": "EUR", "owner_id": owner_id }))
+            .await
+            .json();
+        let account_id = body.data["id"].as_i64().unwrap();
+        server
+            .post("/api/v1/transactions")
+            .add_header(header::AUTHORIZATION, token.clone())
+            .json(&serde_json::json!({
+                "name": format!("{} groceries", name),
+                "amount": "-20.00",
+                "date": "2026-01-05",
+                "target_account_id": account_id,
+            }))
+            .await
+            .assert_status(StatusCode::CREATED);
+        server
+            .post(&format!("/api/v1/accounts/{}/notes", account_id))
+            .add_header(header::AUTHORIZATION, token.clone())
+            .json(&serde_json::json!({ "title": format!("{} contract", name) }))
+            .await
+            .assert_status(StatusCode::CREATED);
+        accounts.push(account_id);
+    }
+
+    let response = server
+        .get("/api/v1/export/full")
+        .add_header(header::AUTHORIZATION, bob.clone())
+        .await;
+    response.assert_status_ok();
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(response.as_bytes().to_vec())).unwrap();
+    let mut read = |name: &str| {
+        let mut content = String::new();
+        archive.by_name(name).unwrap().read_to_string(&mut content).unwrap();
+        serde_json::from_str::<Vec<serde_json::Value>>(&content).unwrap()
+    };
+
+    let exported = read("accounts.json");
+    assert_eq!(exported.len(), 1);
+    assert_eq!(exported[0]["id"], accounts[1]);
+    let transactions = read("one_off_transactions.json");
+    assert_eq!(transactions.len(), 1);
+    assert_eq!(transactions[0]["name"], "Bob checking groceries");
+    let notes = read("account_notes.json");
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0]["title"], "Bob checking contract");
+}
+
+#[tokio::test]
+async fn test_full_import_restores_export() {
+    use std::io::Write;
+
+    let source = TestServer::new(setup_test_app().await).unwrap();
+    let account_resp = source
+        .post("/api/v1/accounts")
+        .json(&serde_json::json!({ "name": "Checking", "currency_code": "EUR", "owner_id": 1 }))
+        .await;
+    account_resp.assert_status(StatusCode::CREATED);
+    let account_id = account_resp.json::<ApiResponse<serde_json::Value>>().data["id"].as_i64().unwrap();
+
+    // The child is created first and moved under a later parent, so restoring by id order would break
+    let child_resp = source
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Groceries" }))
+        .await;
+    child_resp.assert_status(StatusCode::CREATED);
+    let child_id = child_resp.json::<ApiResponse<serde_json::Value>>().data["id"].as_i64().unwrap();
+    let parent_resp = source
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Food" }))
+        .await;
+    parent_resp.assert_status(StatusCode::CREATED);
+    let parent_id = parent_resp.json::<ApiResponse<serde_json::Value>>().data["id"].as_i64().unwrap();
+    source
+        .put(&format!("/api/v1/categories/{}", child_id))
+        .json(&serde_json::json!({ "parent_id": parent_id }))
+        .await
+        .assert_status_ok();
+
+    source
+        .post("/api/v1/transactions")
+        .json(&serde_json::json!({
+            "name": "Market, \"weekly\"",
+            "amount": "-42.50",
+            "date": "2026-01-05",
+            "target_account_id": account_id,
+            "category_id": child_id,
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    let document: ApiResponse<serde_json::Value> = source
+        .post(&format!("/api/v1/accounts/{}/documents", account_id))
+        .json(&serde_json::json!({ "file_name": "contract.txt", "content_base64": "c2lnbmVkLCAibm8gcmVmdW5kcyI=" }))
+        .await
+        .json();
+    let document_id = document.data["id"].as_i64().unwrap();
+
+    for format in ["json", "csv"] {
+        let export = source.get(&format!("/api/v1/export/full?format={}", format)).await;
+        export.assert_status_ok();
+        let archive = export.as_bytes().clone();
+
+        let target = TestServer::new(setup_test_app().await).unwrap();
+        let response = target
+            .post("/api/v1/import/full")
+            .content_type("application/zip")
+            .bytes(archive.clone())
+            .await;
+        response.assert_status(StatusCode::CREATED);
+        let body: ApiResponse<serde_json::Value> = response.json();
+        assert!(body.success);
+        let restored = body.data["tables"].as_array().unwrap();
+        let rows_of = |table: &str| restored.iter().find(|entry| entry["table"] == table).unwrap()["rows"].clone();
+        assert_eq!(rows_of("accounts"), 1);
+        assert_eq!(rows_of("categories"), 2);
+        assert_eq!(rows_of("one_off_transactions"), 1);
+
+        let accounts: ApiResponse<Vec<serde_json::Value>> = target.get("/api/v1/accounts").await.json();
+        assert_eq!(accounts.data.len(), 1);
+        assert_eq!(accounts.data[0]["id"], account_id);
+        let child: ApiResponse<serde_json::Value> =
+            target.get(&format!("/api/v1/categories/{}", child_id)).await.json();
+        assert_eq!(child.data["parent_id"], parent_id);
+        let transactions: ApiResponse<Vec<serde_json::Value>> = target.get("/api/v1/transactions").await.json();
+        assert_eq!(transactions.data[0]["name"], "Market, \"weekly\"");
+        let amount: Decimal = transactions.data[0]["amount"].as_str().unwrap().parse().unwrap();
+        assert_eq!(amount, Decimal::new(-4250, 2));
+        assert_eq!(transactions.data[0]["category_id"], child_id);
+        let download = target
+            .get(&format!("/api/v1/accounts/{}/documents/{}/download", account_id, document_id))
+            .await;
+        download.assert_status_ok();
+        assert_eq!(download.as_bytes().as_ref(), b"signed, \"no refunds\"");
+
+        // A second restore would duplicate everything
+        let response = target
+            .post("/api/v1/import/full")
+            .content_type("application/zip")
+            .bytes(archive)
+            .await;
+        response.assert_status(StatusCode::CONFLICT);
+        let body: ApiResponse<serde_json::Value> = response.json();
+        assert!(!body.success);
+        let conflicts = body.data["conflicts"].as_array().unwrap();
+        assert!(conflicts.iter().any(|conflict| conflict["table"] == "accounts" && conflict["row"].is_null()));
+    }
+
+    // A transaction pointing at an account missing from the archive
+    let manifest = serde_json::json!({
+        "exported_at": "2026-01-01T00:00:00Z",
+        "format": "json",
+        "tables": [
+            { "table": "accounts", "file": "accounts.json", "rows": 0 },
+            { "table": "one_off_transactions", "file": "one_off_transactions.json", "rows": 1 },
+        ],
+    });
+    let transactions = serde_json::json!([{
+        "id": 1,
+        "name": "Orphan",
+        "amount": "-1.00",
+        "date": "2026-01-05",
+        "target_account_id": 7,
+        "include_in_statistics": true,
+        "is_simulated": false,
+        "is_locked": false,
+        "status": "Confirmed",
+        "is_purchase": false,
+    }]);
+    let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    for (name, content) in [
+        ("manifest.json", manifest),
+        ("accounts.json", serde_json::json!([])),
+        ("one_off_transactions.json", transactions),
+    ] {
+        writer.start_file(name, zip::write::SimpleFileOptions::default()).unwrap();
+        writer.write_all(content.to_string().as_bytes()).unwrap();
+    }
+    let archive = writer.finish().unwrap().into_inner();
+
+    let target = TestServer::new(setup_test_app().await).unwrap();
+    let response = target
+        .post("/api/v1/import/full")
+        .content_type("application/zip")
+        .bytes(archive.into())
+        .await;
+    response.assert_status(StatusCode::CONFLICT);
+    let body: ApiResponse<serde_json::Value> = response.json();
+    let conflicts = body.data["conflicts"].as_array().unwrap();
+    assert!(conflicts.iter().any(|conflict| conflict["table"] == "one_off_transactions"
+        && conflict["row"] == 1
+        && conflict["column"] == "target_account_id"));
+    let accounts: ApiResponse<Vec<serde_json::Value>> = target.get("/api/v1/accounts").await.json();
+    assert!(accounts.data.is_empty());
+
+    target
+        .post("/api/v1/import/full")
+        .content_type("application/zip")
+        .bytes(b"not a zip".to_vec().into())
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_full_import_rejects_archives_unpacking_too_large() {
+    use finrust::handlers::restore::MAX_RESTORE_BYTES;
+    use std::io::Write;
+
+    // Highly compressible, so the archive itself stays small
+    let chunk = vec![b' '; 1024 * 1024];
+    let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .compression_level(Some(1));
+    writer.start_file("manifest.json", options).unwrap();
+    for _ in 0..=MAX_RESTORE_BYTES / chunk.len() as u64 {
+        writer.write_all(&chunk).unwrap();
+    }
+    let archive = writer.finish().unwrap().into_inner();
+    assert!(archive.len() < finrust::handlers::restore::MAX_ARCHIVE_BYTES);
+
+    let server = TestServer::new(setup_test_app().await).unwrap();
+    let response = server
+        .post("/api/v1/import/full")
+        .content_type("application/zip")
+        .bytes(archive.into())
+        .await;
+    response.assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "ARCHIVE_TOO_LARGE");
+}
+
+#[tokio::test]
+async fn test_full_import_requires_admin() {
+    use axum::http::{header, HeaderValue};
+    use finrust::helpers::auth::hash_password;
+    use model::entities::user;
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+    let mut state = setup_test_app_state().await;
+    let existing = user::Entity::find_by_id(1).one(&state.db).await.unwrap().unwrap();
+    let mut active: user::ActiveModel = existing.into();
+    active.password_hash = Set(Some(hash_password("correct horse").unwrap()));
+    let test_user = active.update(&state.db).await.unwrap();
+    state.auth_required = true;
+    let db = state.db.clone();
+    let server = TestServer::new(finrust::router::create_test_router(state)).unwrap();
+
+    let login: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/auth/login")
+        .json(&serde_json::json!({ "username": "test_user1", "password": "correct horse" }))
+        .await
+        .json();
+    let bearer = HeaderValue::from_str(&format!("Bearer {}", login.data["token"].as_str().unwrap())).unwrap();
+
+    let response = server
+        .post("/api/v1/import/full")
+        .add_header(header::AUTHORIZATION, bearer.clone())
+        .content_type("application/zip")
+        .bytes(b"not a zip".to_vec().into())
+        .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    let error: serde_json::Value = response.json();
+    assert_eq!(error["code"], "ADMIN_REQUIRED");
+
+    let mut active: user::ActiveModel = test_user.into();
+    active.is_admin = Set(true);
+    active.update(&db).await.unwrap();
+    server
+        .post("/api/v1/import/full")
+        .add_header(header::AUTHORIZATION, bearer)
+        .content_type("application/zip")
+        .bytes(b"not a zip".to_vec().into())
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_balance_preview() {
+    use ::common::BalancePreviewResponse;
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Checking".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/accounts").json(&account_request).await.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+    server
+        .post(&format!("/api/v1/accounts/{}/manual-states", account_id))
+        .json(&serde_json::json!({ "date": "2026-01-01", "amount": "1000" }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    let transaction_response = server
+        .post("/api/v1/transactions")
+        .json(&serde_json::json!({
+            "name": "Rent",
+            "amount": "-200",
+            "date": "2026-01-10",
+            "target_account_id": account_id,
+        }))
+        .await;
+    transaction_response.assert_status(StatusCode::CREATED);
+    let transaction_body: ApiResponse<serde_json::Value> = transaction_response.json();
+    let transaction_id = transaction_body.data["id"].as_i64().unwrap();
+
+    let preview_url = format!("/api/v1/accounts/{}/balance-preview", account_id);
+    let preview = |body: serde_json::Value| {
+        let server = &server;
+        let preview_url = &preview_url;
+        async move {
+            let response = server.post(preview_url).json(&body).await;
+            response.assert_status_ok();
+            let body: ApiResponse<BalancePreviewResponse> = response.json();
+            body.data
+        }
+    };
+
+    // A new expense
+    let data = preview(serde_json::json!({
+        "start_date": "2026-01-08",
+        "end_date": "2026-01-12",
+        "transaction": { "amount": "-50", "date": "2026-01-11" },
+    }))
+    .await;
+    assert_eq!(data.points.len(), 5);
+    assert_eq!(data.end_before, Decimal::from(800));
+    assert_eq!(data.end_after, Decimal::from(750));
+    assert_eq!(data.lowest_balance, Some(Decimal::from(750)));
+    assert_eq!(data.lowest_date, NaiveDate::from_ymd_opt(2026, 1, 11));
+
+    // Editing the saved transaction replaces it
+    let data = preview(serde_json::json!({
+        "start_date": "2026-01-08",
+        "end_date": "2026-01-12",
+        "transaction": { "amount": "-300", "date": "2026-01-09" },
+        "transaction_id": transaction_id,
+    }))
+    .await;
+    let after: Vec<Decimal> = data.points.iter().map(|point| point.after).collect();
+    assert_eq!(after, vec![Decimal::from(1000), Decimal::from(700), Decimal::from(700), Decimal::from(700), Decimal::from(700)]);
+    assert_eq!(data.points[2].before, Decimal::from(800));
+
+    // Deleting it
+    let data = preview(serde_json::json!({
+        "start_date": "2026-01-08",
+        "end_date": "2026-01-12",
+        "transaction_id": transaction_id,
+    }))
+    .await;
+    assert_eq!(data.end_after, Decimal::from(1000));
+
+    // A weekly expense that started before the previewed days
+    let data = preview(serde_json::json!({
+        "start_date": "2026-01-08",
+        "end_date": "2026-01-12",
+        "transaction": { "amount": "-10", "date": "2026-01-01", "period": "Weekly" },
+    }))
+    .await;
+    assert_eq!(data.points[0].after, Decimal::from(980));
+    assert_eq!(data.end_after, Decimal::from(780));
+
+    server
+        .post(&preview_url)
+        .json(&serde_json::json!({
+            "start_date": "2026-01-08",
+            "end_date": "2026-01-12",
+            "transaction": { "amount": "-10", "date": "2026-01-01", "period": "Fortnightly" },
+        }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    server
+        .post(&preview_url)
+        .json(&serde_json::json!({ "start_date": "2026-01-12", "end_date": "2026-01-08" }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    server
+        .post(&preview_url)
+        .json(&serde_json::json!({ "start_date": "2026-01-08", "end_date": "2026-01-12", "transaction_id": 9999 }))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    server
+        .post("/api/v1/accounts/9999/balance-preview")
+        .json(&serde_json::json!({ "start_date": "2026-01-08", "end_date": "2026-01-12" }))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_account_statistics_exclusions() {
+    use finrust::handlers::budgets::BudgetStatusResponse;
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "House fund".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/accounts").json(&account_request).await.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let housing: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Housing" }))
+        .await
+        .json();
+    let housing_id = housing.data["id"].as_i64().unwrap() as i32;
+    server
+        .post("/api/v1/budgets")
+        .json(&serde_json::json!({
+            "category_id": housing_id,
+            "period": "Monthly",
+            "amount": "100",
+            "start_date": "2025-01-01"
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    // The house is paid in March and the mortgage arrives in April
+    for (name, amount, date, category_id) in [
+        ("Salary", 1000, (2025, 1, 1), None),
+        ("House", -5000, (2025, 3, 15), Some(housing_id)),
+        ("Mortgage", 5000, (2025, 4, 10), None),
+    ] {
+        let txn = CreateTransactionRequest {
+            name: name.to_string(),
+            description: None,
+            amount: Decimal::from(amount),
+            date: NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap(),
+            include_in_statistics: Some(true),
+            target_account_id: account_id,
+            source_account_id: None,
+            ledger_name: None,
+            linked_import_id: None,
+            category_id,
+            is_simulated: Some(false),
+            scenario_id: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
+        };
+        server.post("/api/v1/transactions").json(&txn).await.assert_status(StatusCode::CREATED);
+    }
+
+    async fn min_state(server: &TestServer, account_id: i32) -> Decimal {
+        let body: ApiResponse<serde_json::Value> = server
+            .get(&format!("/api/v1/accounts/{}/statistics?year=2025", account_id))
+            .await
+            .json();
+        body.data["statistics"][0]["min_state"].as_str().unwrap().parse().unwrap()
+    }
+    async fn march_spent(server: &TestServer) -> Decimal {
+        let body: ApiResponse<Vec<BudgetStatusResponse>> =
+            server.get("/api/v1/budgets/status").add_query_param("date", "2025-03-31").await.json();
+        body.data[0].spent
+    }
+    assert_eq!(min_state(&server, account_id).await, Decimal::from(-4000));
+    assert_eq!(march_spent(&server).await, Decimal::from(5000));
+
+    let mut update_request = UpdateAccountRequest {
+        name: None,
+        description: None,
+        currency_code: None,
+        include_in_statistics: None,
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+        statistics_exclusions: Some(vec![AccountStatisticsExclusion {
+            start_date: NaiveDate::from_ymd_opt(2025, 4, 30).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            reason: None,
+        }]),
+    };
+    server
+        .put(&format!("/api/v1/accounts/{}", account_id))
+        .json(&update_request)
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+
+    let exclusion = AccountStatisticsExclusion {
+        start_date: NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+        end_date: NaiveDate::from_ymd_opt(2025, 4, 30).unwrap(),
+        reason: Some("House purchase".to_string()),
+    };
+    update_request.statistics_exclusions = Some(vec![exclusion.clone()]);
+    let response = server
+        .put(&format!("/api/v1/accounts/{}", account_id))
+        .json(&update_request)
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["statistics_exclusions"][0]["reason"], "House purchase");
+
+    let body: ApiResponse<serde_json::Value> =
+        server.get(&format!("/api/v1/accounts/{}", account_id)).await.json();
+    assert_eq!(body.data["statistics_exclusions"][0]["start_date"], "2025-03-01");
+    assert_eq!(body.data["statistics_exclusions"][0]["end_date"], "2025-04-30");
+
+    assert_eq!(min_state(&server, account_id).await, Decimal::from(1000));
+    assert_eq!(march_spent(&server).await, Decimal::ZERO);
+
+    // Leaving the exclusions out keeps them, an empty list removes them
+    update_request.statistics_exclusions = None;
+    update_request.name = Some("House savings".to_string());
+    let body: ApiResponse<serde_json::Value> = server
+        .put(&format!("/api/v1/accounts/{}", account_id))
+        .json(&update_request)
+        .await
+        .json();
+    assert_eq!(body.data["statistics_exclusions"].as_array().unwrap().len(), 1);
+
+    update_request.statistics_exclusions = Some(Vec::new());
+    server
+        .put(&format!("/api/v1/accounts/{}", account_id))
+        .json(&update_request)
+        .await
+        .assert_status_ok();
+    assert_eq!(min_state(&server, account_id).await, Decimal::from(-4000));
+}
+
+#[tokio::test]
+async fn test_clone_and_apply_scenario() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Checking".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/accounts").json(&account_request).await.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let scenario_body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/scenarios")
+        .json(&serde_json::json!({ "name": "New car", "description": "Financed", "activation_date": "2025-01-01" }))
+        .await
+        .json();
+    let scenario_id = scenario_body.data["id"].as_i64().unwrap() as i32;
+
+    for (amount, date, scenario) in [
+        (Decimal::from(1000), NaiveDate::from_ymd_opt(2025, 1, 5).unwrap(), None),
+        (Decimal::from(-300), NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(), Some(scenario_id)),
+    ] {
+        let request = CreateTransactionRequest {
+            name: "Transaction".to_string(),
+            description: None,
+            amount,
+            date,
+            include_in_statistics: Some(true),
+            target_account_id: account_id,
+            source_account_id: None,
+            ledger_name: None,
+            linked_import_id: None,
+            category_id: None,
+            is_simulated: Some(scenario.is_some()),
+            scenario_id: scenario,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
+        };
+        server.post("/api/v1/transactions").json(&request).await.assert_status(StatusCode::CREATED);
+    }
+
+    let response = server
+        .post(&format!("/api/v1/scenarios/{}/clone", scenario_id))
+        .json(&serde_json::json!({}))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let clone_body: ApiResponse<serde_json::Value> = response.json();
+    let clone_id = clone_body.data["id"].as_i64().unwrap() as i32;
+    assert_ne!(clone_id, scenario_id);
+    assert_eq!(clone_body.data["name"], "New car (copy)");
+    assert_eq!(clone_body.data["description"], "Financed");
+    assert_eq!(clone_body.data["activation_date"], "2025-01-01");
+    assert_eq!(clone_body.data["is_active"], false);
+
+    let named: ApiResponse<serde_json::Value> = server
+        .post(&format!("/api/v1/scenarios/{}/clone", scenario_id))
+        .json(&serde_json::json!({ "name": "Cheaper car" }))
+        .await
+        .json();
+    assert_eq!(named.data["name"], "Cheaper car");
+
+    server
+        .post("/api/v1/scenarios/99999/clone")
+        .json(&serde_json::json!({}))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+
+    let timeseries_url = |scenario: Option<i32>| {
+        let mut url = format!(
+            "/api/v1/accounts/{}/timeseries?start_date=2025-01-01&end_date=2025-03-01",
+            account_id
+        );
+        if let Some(scenario) = scenario {
+            url.push_str(&format!("&scenario_id={}", scenario));
+        }
+        url
+    };
+    let balance = |body: ApiResponse<serde_json::Value>| {
+        let points = body.data["data_points"].as_array().unwrap().clone();
+        points.last().unwrap()["balance"].as_str().unwrap().parse::<Decimal>().unwrap()
+    };
+
+    // The copy carries the simulated expense
+    let cloned: ApiResponse<serde_json::Value> = server.get(&timeseries_url(Some(clone_id))).await.json();
+    assert_eq!(balance(cloned), Decimal::from(700));
+
+    // Applying the copy makes its expense real, the original keeps its own simulated one
+    server
+        .post(&format!("/api/v1/scenarios/{}/apply", clone_id))
+        .await
+        .assert_status_ok();
+    let baseline: ApiResponse<serde_json::Value> = server.get(&timeseries_url(None)).await.json();
+    assert_eq!(balance(baseline), Decimal::from(700));
+    let original: ApiResponse<serde_json::Value> = server.get(&timeseries_url(Some(scenario_id))).await.json();
+    assert_eq!(balance(original), Decimal::from(400));
+}
+
+#[tokio::test]
+async fn test_account_forecast() {
+    use chrono::{Datelike, Months};
+    use finrust::handlers::forecast::AccountForecastResponse;
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Everyday".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/accounts").json(&account_request).await.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+
+    let groceries: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/categories")
+        .json(&serde_json::json!({ "name": "Groceries" }))
+        .await
+        .json();
+    let groceries_id = groceries.data["id"].as_i64().unwrap() as i32;
+
+    // Groceries cost 300 two months ago and 100 last month
+    let today = chrono::Local::now().date_naive();
+    let this_month = today.with_day(1).unwrap();
+    let month_ago = |months: u32| this_month.checked_sub_months(Months::new(months)).unwrap() + chrono::Duration::days(14);
+    for (name, amount, date, category_id) in [
+        ("Salary", 1000, month_ago(3), None),
+        ("Groceries", -300, month_ago(2), Some(groceries_id)),
+        ("Groceries", -100, month_ago(1), Some(groceries_id)),
+    ] {
+        let txn = CreateTransactionRequest {
+            name: name.to_string(),
+            description: None,
+            amount: Decimal::from(amount),
+            date,
+            include_in_statistics: Some(true),
+            target_account_id: account_id,
+            source_account_id: None,
+            ledger_name: None,
+            linked_import_id: None,
+            category_id,
+            is_simulated: Some(false),
+            scenario_id: None,
+            latitude: None,
+            longitude: None,
+            status: None,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: None,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
+        };
+        server.post("/api/v1/transactions").json(&txn).await.assert_status(StatusCode::CREATED);
+    }
+
+    let response = server
+        .get(&format!("/api/v1/accounts/{}/forecast", account_id))
+        .add_query_param("months", 3)
+        .add_query_param("lookback_months", 2)
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<AccountForecastResponse> = response.json();
+    let forecast = body.data;
+
+    assert_eq!(forecast.months, 3);
+    assert_eq!(forecast.monthly_spending, Decimal::from(200));
+    assert_eq!(forecast.categories.len(), 1);
+    assert_eq!(forecast.categories[0].category_name.as_deref(), Some("Groceries"));
+    assert!(forecast.monthly_deviation > Decimal::from(141) && forecast.monthly_deviation < Decimal::from(142));
+
+    let end_date = today.checked_add_months(Months::new(3)).unwrap();
+    assert_eq!(forecast.points.len() as i64, (end_date - today).num_days() + 1);
+    let first = &forecast.points[0];
+    assert_eq!(first.date, today);
+    assert_eq!(first.balance, Decimal::from(600));
+    assert_eq!(first.lower, first.upper);
+
+    // Three months of groceries are spent by the end, give or take the partial months
+    let last = forecast.points.last().unwrap();
+    assert_eq!(last.date, end_date);
+    assert!(last.balance > Decimal::from(-30) && last.balance < Decimal::from(30));
+    let width = |point: &finrust::handlers::forecast::AccountForecastPoint| point.upper - point.lower;
+    assert!(width(last) > width(&forecast.points[1]));
+    assert!(last.lower < last.balance && last.balance < last.upper);
+
+    for months in ["0", "61"] {
+        server
+            .get(&format!("/api/v1/accounts/{}/forecast", account_id))
+            .add_query_param("months", months)
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+    server
+        .get("/api/v1/accounts/99999/forecast")
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_recurring_instance_disputes() {
+    use crate::common::setup_test_app_state;
+    use finrust::router::create_test_router;
+    use model::entities::recurring_transaction_instance::{self, InstanceStatus};
+    use model::entities::{account, recurring_transaction};
+    use sea_orm::{ActiveModelTrait, Set};
+
+    let app_state = setup_test_app_state().await;
+    let app = create_test_router(app_state.clone());
+    let server = TestServer::new(app).unwrap();
+
+    let account = account::ActiveModel {
+        name: Set("Bills".to_string()),
+        currency_code: Set("USD".to_string()),
+        owner_id: Set(1),
+        include_in_statistics: Set(true),
+        ..Default::default()
+    }
+    .insert(&app_state.db)
+    .await
+    .unwrap();
+
+    let rule = recurring_transaction::ActiveModel {
+        name: Set("Gym".to_string()),
+        amount: Set(Decimal::from(-50)),
+        start_date: Set(NaiveDate::from_ymd_opt(2025, 3, 1).unwrap()),
+        period: Set(recurring_transaction::RecurrencePeriod::Weekly),
+        include_in_statistics: Set(true),
+        target_account_id: Set(account.id),
+        ..Default::default()
+    }
+    .insert(&app_state.db)
+    .await
+    .unwrap();
+
+    let mut instance_ids = Vec::new();
+    for day in [1, 8] {
+        let instance = recurring_transaction_instance::ActiveModel {
+            recurring_transaction_id: Set(rule.id),
+            status: Set(InstanceStatus::Paid),
+            due_date: Set(NaiveDate::from_ymd_opt(2025, 3, day).unwrap()),
+            expected_amount: Set(Decimal::from(-50)),
+            paid_date: Set(Some(NaiveDate::from_ymd_opt(2025, 3, day).unwrap())),
+            paid_amount: Set(Some(Decimal::from(-50))),
+            ..Default::default()
+        }
+        .insert(&app_state.db)
+        .await
+        .unwrap();
+        assert!(!instance.disputed);
+        instance_ids.push(instance.id);
+    }
+
+    // The second charge came after the membership was cancelled
+    let response = server
+        .put(&format!("/api/v1/recurring-instances/{}", instance_ids[1]))
+        .json(&serde_json::json!({
+            "disputed": true,
+            "notes": "Charged after cancellation, chargeback requested"
+        }))
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["disputed"], true);
+    assert_eq!(body.data["notes"], "Charged after cancellation, chargeback requested");
+    assert_eq!(body.data["status"], "Paid");
+
+    let disputed: ApiResponse<Vec<serde_json::Value>> =
+        server.get("/api/v1/recurring-instances?disputed=true").await.json();
+    assert_eq!(disputed.data.len(), 1);
+    assert_eq!(disputed.data[0]["id"], instance_ids[1]);
+    let undisputed: ApiResponse<Vec<serde_json::Value>> =
+        server.get("/api/v1/recurring-instances?disputed=false").await.json();
+    assert_eq!(undisputed.data.len(), 1);
+    assert_eq!(undisputed.data[0]["id"], instance_ids[0]);
+
+    let dec = |value: &serde_json::Value| value.as_str().unwrap().parse::<Decimal>().unwrap();
+    let groups: ApiResponse<Vec<serde_json::Value>> =
+        server.get("/api/v1/recurring-instances?group_by=month").await.json();
+    let march = &groups.data[0];
+    assert_eq!(march["paid_count"], 1);
+    assert_eq!(march["disputed_count"], 1);
+    assert_eq!(dec(&march["total_expected"]), Decimal::from(-100));
+    assert_eq!(dec(&march["total_paid"]), Decimal::from(-50));
+    assert_eq!(dec(&march["total_disputed"]), Decimal::from(-50));
+
+    // Settling the dispute counts the charge as paid again; an empty note clears it
+    let body: ApiResponse<serde_json::Value> = server
+        .put(&format!("/api/v1/recurring-instances/{}", instance_ids[1]))
+        .json(&serde_json::json!({ "disputed": false, "notes": "" }))
+        .await
+        .json();
+    assert_eq!(body.data["disputed"], false);
+    assert!(body.data["notes"].is_null());
+
+    let groups: ApiResponse<Vec<serde_json::Value>> =
+        server.get("/api/v1/recurring-instances?group_by=month").await.json();
+    assert_eq!(groups.data[0]["paid_count"], 2);
+    assert_eq!(groups.data[0]["disputed_count"], 0);
+    assert_eq!(dec(&groups.data[0]["total_paid"]), Decimal::from(-100));
+    assert_eq!(dec(&groups.data[0]["total_disputed"]), Decimal::ZERO);
+}
+
+#[tokio::test]
+async fn test_household_assets() {
+    use finrust::handlers::assets::AssetResponse;
+    use finrust::handlers::networth::NetWorthTimeseriesResponse;
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Checking".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/accounts").json(&account_request).await.json();
+    let account_id = account_body.data["id"].as_i64().unwrap() as i32;
+    server
+        .post(&format!("/api/v1/accounts/{}/manual-states", account_id))
+        .json(&serde_json::json!({ "date": "2025-01-01", "amount": "3000" }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let purchase = CreateTransactionRequest {
+        name: "Laptop".to_string(),
+        description: None,
+        amount: Decimal::from(-2400),
+        date: NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+        include_in_statistics: Some(true),
+        target_account_id: account_id,
+        source_account_id: None,
+        ledger_name: None,
+        linked_import_id: None,
+        category_id: None,
+        is_simulated: Some(false),
+        scenario_id: None,
+        latitude: None,
+        longitude: None,
+        status: None,
+        original_amount: None,
+        original_currency: None,
+        conversion_rate: None,
+        is_purchase: None,
+        warranty_end_date: None,
+        return_deadline: None,
+        receipt_document_id: None,
+    };
+    let purchase_body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/transactions").json(&purchase).await.json();
+    let purchase_id = purchase_body.data["id"].as_i64().unwrap() as i32;
+
+    server
+        .post("/api/v1/assets")
+        .json(&serde_json::json!({
+            "name": "Laptop",
+            "account_id": account_id,
+            "purchase_transaction_id": purchase_id,
+            "salvage_value": "3000",
+            "useful_life_months": 24
+        }))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+
+    // Date and price come from the purchase transaction
+    let response = server
+        .post("/api/v1/assets")
+        .json(&serde_json::json!({
+            "name": "Laptop",
+            "account_id": account_id,
+            "purchase_transaction_id": purchase_id,
+            "salvage_value": "240",
+            "useful_life_months": 24
+        }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let body: ApiResponse<AssetResponse> = response.json();
+    let asset_id = body.data.id;
+    assert_eq!(body.data.purchase_date, NaiveDate::from_ymd_opt(2025, 1, 15).unwrap());
+    assert_eq!(body.data.purchase_price, Decimal::from(2400));
+
+    // A month in, the laptop has lost 90 of its value
+    let body: ApiResponse<NetWorthTimeseriesResponse> = server
+        .get("/api/v1/networth/timeseries")
+        .add_query_param("start_date", "2025-02-15")
+        .add_query_param("end_date", "2025-02-15")
+        .add_query_param("group_by", "account_kind")
+        .await
+        .json();
+    assert_eq!(body.data.asset_ids, vec![asset_id]);
+    assert_eq!(body.data.points[0].net_worth, Decimal::from(2910));
+    assert_eq!(body.data.assets[0].net_worth, Decimal::from(2310));
+    let asset_group = body.data.groups.iter().find(|group| group.key == "Asset").unwrap();
+    assert_eq!(asset_group.points[0].net_worth, Decimal::from(2310));
+
+    // Sold a year in, 180 above its depreciated value
+    let response = server
+        .post(&format!("/api/v1/assets/{}/dispose", asset_id))
+        .json(&serde_json::json!({ "date": "2026-01-15", "sale_price": "1500" }))
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<AssetResponse> = response.json();
+    assert_eq!(body.data.disposal_date, Some(NaiveDate::from_ymd_opt(2026, 1, 15).unwrap()));
+    assert_eq!(body.data.gain_on_disposal, Some(Decimal::from(180)));
+    assert_eq!(body.data.current_value, Decimal::ZERO);
+    let sale_id = body.data.disposal_transaction_id.unwrap();
+
+    let sale: ApiResponse<serde_json::Value> = server.get(&format!("/api/v1/transactions/{}", sale_id)).await.json();
+    assert_eq!(sale.data["name"], "Sale of Laptop");
+    assert_eq!(sale.data["target_account_id"], account_id);
+
+    let body: ApiResponse<NetWorthTimeseriesResponse> = server
+        .get("/api/v1/networth/timeseries")
+        .add_query_param("start_date", "2026-01-15")
+        .add_query_param("end_date", "2026-01-15")
+        .await
+        .json();
+    assert_eq!(body.data.points[0].net_worth, Decimal::from(2100));
+    assert!(body.data.assets.is_empty());
+
+    server
+        .post(&format!("/api/v1/assets/{}/dispose", asset_id))
+        .json(&serde_json::json!({ "date": "2026-02-01", "sale_price": "100" }))
+        .await
+        .assert_status(StatusCode::CONFLICT);
+
+    let listed: ApiResponse<Vec<AssetResponse>> = server.get("/api/v1/assets").await.json();
+    assert_eq!(listed.data.len(), 1);
+    server
+        .delete(&format!("/api/v1/assets/{}", asset_id))
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+    server
+        .get(&format!("/api/v1/assets/{}", asset_id))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_goal_progress() {
+    use chrono::{Duration, Months};
+    use finrust::handlers::accounts::AccountKind;
+    use finrust::handlers::goals::GoalProgressResponse;
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = |name: &str, account_kind: AccountKind, target_amount: Option<Decimal>| CreateAccountRequest {
+        name: name.to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: Some(account_kind),
+        target_amount,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let goal: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/accounts")
+        .json(&account_request("New car", AccountKind::Goal, Some(Decimal::from(10000))))
+        .await
+        .json();
+    let goal_id = goal.data["id"].as_i64().unwrap() as i32;
+    let checking: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/accounts")
+        .json(&account_request("Checking", AccountKind::RealAccount, None))
+        .await
+        .json();
+    let checking_id = checking.data["id"].as_i64().unwrap() as i32;
+
+    let today = chrono::Local::now().date_naive();
+    server
+        .post(&format!("/api/v1/accounts/{}/manual-states", goal_id))
+        .json(&serde_json::json!({ "date": today - Duration::days(2), "amount": "3500" }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    // Without recurring inflows the goal is never projected to complete
+    let body: ApiResponse<GoalProgressResponse> =
+        server.get(&format!("/api/v1/accounts/{}/goal-progress", goal_id)).await.json();
+    assert_eq!(body.data.current_balance, Decimal::from(3500));
+    assert_eq!(body.data.percent_complete, Decimal::from(35));
+    assert_eq!(body.data.monthly_inflow, Decimal::ZERO);
+    assert_eq!(body.data.required_monthly_contribution, Decimal::new(54167, 2));
+    assert!(body.data.projected_completion_date.is_none());
+    assert!(!body.data.on_track);
+
+    server
+        .post("/api/v1/transactions")
+        .json(&serde_json::json!({
+            "name": "First bonus",
+            "amount": "3000",
+            "date": today - Duration::days(1),
+            "target_account_id": goal_id,
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    server
+        .post("/api/v1/recurring-incomes")
+        .json(&serde_json::json!({
+            "name": "Bonus",
+            "amount": "3000",
+            "start_date": today - Duration::days(1),
+            "period": "Quarterly",
+            "target_account_id": goal_id,
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    let response = server
+        .get(&format!("/api/v1/accounts/{}/goal-progress", goal_id))
+        .add_query_param("target_date", today.checked_add_months(Months::new(7)).unwrap())
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<GoalProgressResponse> = response.json();
+    // The first bonus is already in, the rest arrive at 1000 a month
+    assert_eq!(body.data.current_balance, Decimal::from(6500));
+    assert_eq!(body.data.monthly_inflow, Decimal::from(1000));
+    assert_eq!(body.data.remaining, Decimal::from(3500));
+    assert_eq!(body.data.required_monthly_contribution, Decimal::from(500));
+    assert_eq!(body.data.projected_completion_date, today.checked_add_months(Months::new(4)));
+    assert!(body.data.on_track);
+
+    server
+        .get(&format!("/api/v1/accounts/{}/goal-progress", checking_id))
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    server
+        .get("/api/v1/accounts/99999/goal-progress")
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_recurring_instance_generation() {
+    use crate::common::setup_test_app_state;
+    use finrust::router::create_test_router;
+    use finrust::tasks::recurring_instances::generate_recurring_instances;
+    use model::entities::recurring_transaction_instance::{self, InstanceStatus};
+    use model::entities::{account, recurring_transaction};
+    use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+
+    let app_state = setup_test_app_state().await;
+    let app = create_test_router(app_state.clone());
+    let server = TestServer::new(app).unwrap();
+
+    let account = account::ActiveModel {
+        name: Set("Bills".to_string()),
+        currency_code: Set("USD".to_string()),
+        owner_id: Set(1),
+        include_in_statistics: Set(true),
+        ..Default::default()
+    }
+    .insert(&app_state.db)
+    .await
+    .unwrap();
+
+    let mut rule_ids = Vec::new();
+    for (name, is_simulated) in [("Gym", false), ("Maybe a second gym", true)] {
+        let rule = recurring_transaction::ActiveModel {
+            name: Set(name.to_string()),
+            amount: Set(Decimal::from(-50)),
+            start_date: Set(NaiveDate::from_ymd_opt(2025, 3, 3).unwrap()),
+            period: Set(recurring_transaction::RecurrencePeriod::Weekly),
+            include_in_statistics: Set(true),
+            target_account_id: Set(account.id),
+            is_simulated: Set(is_simulated),
+            approval_status: Set(recurring_transaction::ApprovalStatus::Approved),
+            ..Default::default()
+        }
+        .insert(&app_state.db)
+        .await
+        .unwrap();
+        rule_ids.push(rule.id);
+    }
+
+    let first = recurring_transaction_instance::ActiveModel {
+        recurring_transaction_id: Set(rule_ids[0]),
+        status: Set(InstanceStatus::Pending),
+        due_date: Set(NaiveDate::from_ymd_opt(2025, 3, 3).unwrap()),
+        expected_amount: Set(Decimal::from(-50)),
+        ..Default::default()
+    }
+    .insert(&app_state.db)
+    .await
+    .unwrap();
+
+    // Two weeks from the 10th are the 10th itself, the 17th and the 24th; the 3rd is left unpaid
+    let today = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+    let summary = generate_recurring_instances(&app_state.db, today, 14).await.unwrap();
+    assert_eq!(summary.created_count, 3);
+    assert_eq!(summary.overdue_count, 1);
+
+    let instances = recurring_transaction_instance::Entity::find()
+        .filter(recurring_transaction_instance::Column::RecurringTransactionId.is_in(rule_ids.clone()))
+        .order_by_asc(recurring_transaction_instance::Column::DueDate)
+        .all(&app_state.db)
+        .await
+        .unwrap();
+    let generated: Vec<(i32, NaiveDate, bool)> = instances
+        .iter()
+        .map(|instance| (instance.recurring_transaction_id, instance.due_date, instance.overdue))
+        .collect();
+    assert_eq!(
+        generated,
+        vec![
+            (rule_ids[0], NaiveDate::from_ymd_opt(2025, 3, 3).unwrap(), true),
+            (rule_ids[0], NaiveDate::from_ymd_opt(2025, 3, 10).unwrap(), false),
+            (rule_ids[0], NaiveDate::from_ymd_opt(2025, 3, 17).unwrap(), false),
+            (rule_ids[0], NaiveDate::from_ymd_opt(2025, 3, 24).unwrap(), false),
+        ]
+    );
+    assert!(instances[1..].iter().all(|instance| instance.status == InstanceStatus::Pending
+        && instance.expected_amount == Decimal::from(-50)));
+
+    // Running again only fills gaps
+    let summary = generate_recurring_instances(&app_state.db, today, 14).await.unwrap();
+    assert_eq!(summary.created_count, 0);
+    assert_eq!(summary.overdue_count, 0);
+
+    let overdue: ApiResponse<Vec<serde_json::Value>> =
+        server.get("/api/v1/recurring-instances?overdue=true").await.json();
+    assert_eq!(overdue.data.len(), 1);
+    assert_eq!(overdue.data[0]["id"], first.id);
+
+    // Paying the late instance clears the mark
+    let body: ApiResponse<serde_json::Value> = server
+        .put(&format!("/api/v1/recurring-instances/{}", first.id))
+        .json(&serde_json::json!({ "status": "Paid", "paid_date": "2025-03-11", "paid_amount": "-50" }))
+        .await
+        .json();
+    assert_eq!(body.data["overdue"], false);
+
+    server
+        .post("/api/v1/recurring-instances/generate")
+        .add_query_param("days_ahead", 0)
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    let response = server
+        .post("/api/v1/recurring-instances/generate")
+        .add_query_param("days_ahead", 6)
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    // A week counting today holds exactly one Monday
+    assert_eq!(body.data["days_ahead"], 6);
+    assert_eq!(body.data["created_count"], 1);
+}
+
+#[tokio::test]
+async fn test_purchase_warranty_reminders() {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let mut account_ids = Vec::new();
+    for name in ["Card", "Other"] {
+        let account_request = CreateAccountRequest {
+            name: name.to_string(),
+            description: None,
+            currency_code: "USD".to_string(),
+            owner_id: 1,
+            include_in_statistics: Some(true),
+            ledger_name: None,
+            account_kind: None,
+            target_amount: None,
+            color: None,
+            is_liquid: None,
+            balance_interpolation: None,
+        };
+        let account_body: ApiResponse<serde_json::Value> =
+            server.post("/api/v1/accounts").json(&account_request).await.json();
+        account_ids.push(account_body.data["id"].as_i64().unwrap() as i32);
+    }
+    let (account_id, other_account_id) = (account_ids[0], account_ids[1]);
+
+    let receipt_body: ApiResponse<serde_json::Value> = server
+        .post(&format!("/api/v1/accounts/{}/documents", account_id))
+        .json(&serde_json::json!({
+            "file_name": "receipt.pdf",
+            "content_type": "application/pdf",
+            "content_base64": STANDARD.encode(b"%PDF-1.4 receipt")
+        }))
+        .await
+        .json();
+    let receipt_id = receipt_body.data["id"].as_i64().unwrap();
+
+    let today = chrono::Local::now().date_naive();
+    let response = server
+        .post("/api/v1/transactions")
+        .json(&serde_json::json!({
+            "name": "Headphones",
+            "amount": "-199",
+            "date": today,
+            "target_account_id": account_id,
+            "is_purchase": true,
+            "return_deadline": today + chrono::Duration::days(14),
+            "warranty_end_date": today + chrono::Duration::days(730),
+            "receipt_document_id": receipt_id,
+        }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["is_purchase"], true);
+    assert_eq!(body.data["receipt_document_id"], receipt_id);
+    let headphones_id = body.data["id"].as_i64().unwrap();
+
+    let response = server
+        .post("/api/v1/transactions")
+        .json(&serde_json::json!({
+            "name": "Kettle",
+            "amount": "-40",
+            "date": today - chrono::Duration::days(700),
+            "target_account_id": account_id,
+            "is_purchase": true,
+            "warranty_end_date": today + chrono::Duration::days(30),
+        }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let kettle_id = response.json::<ApiResponse<serde_json::Value>>().data["id"].as_i64().unwrap();
+
+    // Purchase details need a purchase, and the receipt has to belong to its account
+    let response = server
+        .post("/api/v1/transactions")
+        .json(&serde_json::json!({
+            "name": "Groceries",
+            "amount": "-60",
+            "date": today,
+            "target_account_id": account_id,
+            "warranty_end_date": today,
+        }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    assert_eq!(response.json::<serde_json::Value>()["code"], "NOT_A_PURCHASE");
+    let response = server
+        .post("/api/v1/transactions")
+        .json(&serde_json::json!({
+            "name": "Blender",
+            "amount": "-80",
+            "date": today,
+            "target_account_id": other_account_id,
+            "is_purchase": true,
+            "receipt_document_id": receipt_id,
+        }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    assert_eq!(response.json::<serde_json::Value>()["code"], "INVALID_RECEIPT_DOCUMENT");
+
+    let response = server.get("/api/v1/transactions/purchase-reminders").await;
+    response.assert_status_ok();
+    let body: ApiResponse<Vec<serde_json::Value>> = response.json();
+    assert_eq!(body.data.len(), 2);
+    assert_eq!(body.data[0]["transaction_id"], headphones_id);
+    assert_eq!(body.data[0]["kind"], "Return");
+    assert_eq!(body.data[0]["days_until_deadline"], 14);
+    assert_eq!(body.data[0]["receipt_document_id"], receipt_id);
+    assert_eq!(body.data[1]["transaction_id"], kettle_id);
+    assert_eq!(body.data[1]["kind"], "Warranty");
+
+    let body: ApiResponse<Vec<serde_json::Value>> =
+        server.get("/api/v1/transactions/purchase-reminders?within_days=7").await.json();
+    assert!(body.data.is_empty());
+    server
+        .get("/api/v1/transactions/purchase-reminders?within_days=1000")
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+
+    // No longer a purchase: the dates go and it drops out of the reminders
+    let response = server
+        .put(&format!("/api/v1/transactions/{}", kettle_id))
+        .json(&serde_json::json!({ "is_purchase": false }))
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["warranty_end_date"], serde_json::Value::Null);
+
+    // Deleting the receipt detaches it from the purchase
+    server
+        .delete(&format!("/api/v1/accounts/{}/documents/{}", account_id, receipt_id))
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+    let body: ApiResponse<Vec<serde_json::Value>> = server.get("/api/v1/transactions/purchase-reminders").await.json();
+    assert_eq!(body.data.len(), 1);
+    assert_eq!(body.data[0]["receipt_document_id"], serde_json::Value::Null);
+}
+
+#[tokio::test]
+async fn test_skip_and_unskip_recurring_instance() {
+    use crate::common::setup_test_app_state;
+    use finrust::router::create_test_router;
+    use model::entities::recurring_transaction_instance::{self, InstanceStatus};
+    use model::entities::{account, recurring_transaction};
+    use sea_orm::{ActiveModelTrait, Set};
+
+    let app_state = setup_test_app_state().await;
+    let app = create_test_router(app_state.clone());
+    let server = TestServer::new(app).unwrap();
+
+    let account = account::ActiveModel {
+        name: Set("Subscriptions".to_string()),
+        currency_code: Set("USD".to_string()),
+        owner_id: Set(1),
+        include_in_statistics: Set(true),
+        ..Default::default()
+    }
+    .insert(&app_state.db)
+    .await
+    .unwrap();
+    let rule = recurring_transaction::ActiveModel {
+        name: Set("Meal kit".to_string()),
+        amount: Set(Decimal::from(-60)),
+        start_date: Set(NaiveDate::from_ymd_opt(2025, 6, 2).unwrap()),
+        period: Set(recurring_transaction::RecurrencePeriod::Weekly),
+        include_in_statistics: Set(true),
+        target_account_id: Set(account.id),
+        approval_status: Set(recurring_transaction::ApprovalStatus::Approved),
+        ..Default::default()
+    }
+    .insert(&app_state.db)
+    .await
+    .unwrap();
+
+    let mut instance_ids = Vec::new();
+    for (day, status) in [(2, InstanceStatus::Paid), (9, InstanceStatus::Pending)] {
+        let instance = recurring_transaction_instance::ActiveModel {
+            recurring_transaction_id: Set(rule.id),
+            status: Set(status),
+            due_date: Set(NaiveDate::from_ymd_opt(2025, 6, day).unwrap()),
+            expected_amount: Set(Decimal::from(-60)),
+            ..Default::default()
+        }
+        .insert(&app_state.db)
+        .await
+        .unwrap();
+        instance_ids.push(instance.id);
+    }
+    let (paid_id, vacation_id) = (instance_ids[0], instance_ids[1]);
+
+    let response = server.post(&format!("/api/v1/recurring-instances/{}/skip", vacation_id)).await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["status"], "Skipped");
+    assert_eq!(body.data["due_date"], "2025-06-09");
+    assert_eq!(body.data["expected_amount"], "-60");
+
+    // Skipping twice is harmless
+    server
+        .post(&format!("/api/v1/recurring-instances/{}/skip", vacation_id))
+        .await
+        .assert_status_ok();
+
+    let response = server.post(&format!("/api/v1/recurring-instances/{}/unskip", vacation_id)).await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["status"], "Pending");
+    assert_eq!(body.data["due_date"], "2025-06-09");
+
+    // Paid instances stay paid
+    let response = server.post(&format!("/api/v1/recurring-instances/{}/skip", paid_id)).await;
+    response.assert_status(StatusCode::CONFLICT);
+    assert_eq!(response.json::<serde_json::Value>()["code"], "INVALID_INSTANCE_STATUS");
+    server
+        .post(&format!("/api/v1/recurring-instances/{}/unskip", paid_id))
+        .await
+        .assert_status(StatusCode::CONFLICT);
+
+    server
+        .post("/api/v1/recurring-instances/99999/skip")
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_account_freshness() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let mut account_ids = Vec::new();
+    for name in ["Checking", "Savings", "Brokerage", "Unused"] {
+        let account_request = CreateAccountRequest {
+            name: name.to_string(),
+            description: None,
+            currency_code: "USD".to_string(),
+            owner_id: 1,
+            include_in_statistics: Some(true),
+            ledger_name: None,
+            account_kind: None,
+            target_amount: None,
+            color: None,
+            is_liquid: None,
+            balance_interpolation: None,
+        };
+        let account_body: ApiResponse<serde_json::Value> =
+            server.post("/api/v1/accounts").json(&account_request).await.json();
+        account_ids.push(account_body.data["id"].as_i64().unwrap());
+    }
+    let (checking_id, savings_id, brokerage_id, unused_id) =
+        (account_ids[0], account_ids[1], account_ids[2], account_ids[3]);
+
+    let today = chrono::Local::now().date_naive();
+    let days_ago = |days: i64| today - chrono::Duration::days(days);
+
+    // A transfer keeps both of its accounts fresh
+    server
+        .post("/api/v1/transactions")
+        .json(&serde_json::json!({
+            "name": "Monthly saving",
+            "amount": "100",
+            "date": days_ago(3),
+            "target_account_id": savings_id,
+            "source_account_id": checking_id,
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    server
+        .post("/api/v1/imported-transactions")
+        .json(&serde_json::json!({
+            "account_id": checking_id,
+            "date": days_ago(1),
+            "description": "COFFEE",
+            "amount": "-4",
+            "import_hash": "freshness-coffee",
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    server
+        .post(&format!("/api/v1/accounts/{}/manual-states", brokerage_id))
+        .json(&serde_json::json!({ "date": days_ago(60), "amount": "5000" }))
+        .await
+        .assert_status(StatusCode::CREATED);
+    // Planned money hasn't arrived yet
+    server
+        .post("/api/v1/transactions")
+        .json(&serde_json::json!({
+            "name": "Dividend",
+            "amount": "20",
+            "date": days_ago(2),
+            "target_account_id": brokerage_id,
+            "status": "Planned",
+        }))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let response = server.get("/api/v1/accounts/freshness").await;
+    response.assert_status_ok();
+    let body: ApiResponse<Vec<serde_json::Value>> = response.json();
+    let ids: Vec<i64> = body.data.iter().map(|account| account["account_id"].as_i64().unwrap()).collect();
+    assert_eq!(ids, vec![unused_id, brokerage_id, savings_id, checking_id]);
+
+    let unused = &body.data[0];
+    assert_eq!(unused["is_stale"], true);
+    assert_eq!(unused["days_since_last_activity"], serde_json::Value::Null);
+    let brokerage = &body.data[1];
+    assert_eq!(brokerage["is_stale"], true);
+    assert_eq!(brokerage["last_transaction_date"], serde_json::Value::Null);
+    assert_eq!(brokerage["days_since_last_activity"], 60);
+    let checking = &body.data[3];
+    assert_eq!(checking["is_stale"], false);
+    assert_eq!(checking["last_transaction_date"], days_ago(3).to_string());
+    assert_eq!(checking["last_import_date"], days_ago(1).to_string());
+    assert_eq!(checking["days_since_last_activity"], 1);
+
+    let body: ApiResponse<Vec<serde_json::Value>> =
+        server.get("/api/v1/accounts/freshness?stale_after_days=90").await.json();
+    assert_eq!(body.data[1]["is_stale"], false);
+
+    server
+        .get("/api/v1/accounts/freshness?stale_after_days=0")
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_variable_amount_recurring_transaction() {
+    use crate::common::setup_test_app_state;
+    use finrust::router::create_test_router;
+    use model::entities::{recurring_transaction, recurring_transaction_instance};
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+    use test_factories::RecurringInstanceFactory;
+
+    let app_state = setup_test_app_state().await;
+    let app = create_test_router(app_state.clone());
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Utilities".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/accounts").json(&account_request).await.json();
+    let account_id = account_body.data["id"].as_i64().unwrap();
+
+    let electricity = serde_json::json!({
+        "name": "Electricity",
+        "amount": "-80",
+        "start_date": "2025-01-10",
+        "period": "Monthly",
+        "target_account_id": account_id,
+        "amount_strategy": "AverageOfLastN",
+        "amount_history_count": 2,
+    });
+    let response = server.post("/api/v1/recurring-transactions").json(&electricity).await;
+    response.assert_status(StatusCode::CREATED);
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["amount_strategy"], "AverageOfLastN");
+    assert_eq!(body.data["amount_history_count"], 2);
+    // Nothing paid yet, so the fixed amount is the best guess
+    let estimated = |data: &serde_json::Value| data["estimated_amount"].as_str().unwrap().parse::<Decimal>().unwrap();
+    assert_eq!(estimated(&body.data), Decimal::from(-80));
+    let rule_id = body.data["id"].as_i64().unwrap() as i32;
+
+    let rule = recurring_transaction::Entity::find_by_id(rule_id)
+        .one(&app_state.db)
+        .await
+        .unwrap()
+        .unwrap();
+    for (month, paid_amount) in [(1, -120), (2, -60), (3, -90)] {
+        let due_date = NaiveDate::from_ymd_opt(2025, month, 10).unwrap();
+        RecurringInstanceFactory::new(&rule, due_date)
+            .paid_on(due_date, paid_amount)
+            .create(&app_state.db)
+            .await
+            .unwrap();
+    }
+
+    let body: ApiResponse<serde_json::Value> =
+        server.get(&format!("/api/v1/recurring-transactions/{}", rule_id)).await.json();
+    // Average of the two latest payments; the rule amount itself stays untouched
+    assert_eq!(body.data["amount"].as_str().unwrap().parse::<Decimal>().unwrap(), Decimal::from(-80));
+    assert_eq!(estimated(&body.data), Decimal::from(-75));
+
+    // Another strategy drops the history count
+    let response = server
+        .put(&format!("/api/v1/recurring-transactions/{}", rule_id))
+        .json(&serde_json::json!({ "amount_strategy": "LastPaid" }))
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["amount_history_count"], serde_json::Value::Null);
+    assert_eq!(estimated(&body.data), Decimal::from(-90));
+
+    // Generated instances expect the estimate as well
+    let summary = finrust::tasks::recurring_instances::generate_recurring_instances(
+        &app_state.db,
+        NaiveDate::from_ymd_opt(2025, 4, 1).unwrap(),
+        14,
+    )
+    .await
+    .unwrap();
+    assert_eq!(summary.created_count, 1);
+    let generated = recurring_transaction_instance::Entity::find()
+        .filter(recurring_transaction_instance::Column::RecurringTransactionId.eq(rule_id))
+        .filter(recurring_transaction_instance::Column::DueDate.eq(NaiveDate::from_ymd_opt(2025, 4, 10).unwrap()))
+        .one(&app_state.db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(generated.expected_amount, Decimal::from(-90));
+
+    let response = server
+        .put(&format!("/api/v1/recurring-transactions/{}", rule_id))
+        .json(&serde_json::json!({ "amount_history_count": 4 }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    assert_eq!(response.json::<serde_json::Value>()["code"], "INVALID_AMOUNT_STRATEGY");
+
+    let mut no_history = electricity.clone();
+    no_history["amount_history_count"] = serde_json::json!(0);
+    server
+        .post("/api/v1/recurring-transactions")
+        .json(&no_history)
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_recurring_transaction_rrule() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Payroll".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/accounts").json(&account_request).await.json();
+    let account_id = account_body.data["id"].as_i64().unwrap();
+
+    let salary = |rrule: &str| {
+        serde_json::json!({
+            "name": "Salary",
+            "amount": "3000",
+            "start_date": "2025-01-01",
+            "period": "Monthly",
+            "target_account_id": account_id,
+            "rrule": rrule,
+        })
+    };
+
+    for invalid in ["FREQ=HOURLY", "FREQ=MONTHLY;BYDAY=FUNDAY", "BYDAY=MO"] {
+        let response = server.post("/api/v1/recurring-transactions").json(&salary(invalid)).await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+        assert_eq!(response.json::<serde_json::Value>()["code"], "INVALID_RRULE");
+    }
+
+    // Paid on the last business day of every month
+    let response = server
+        .post("/api/v1/recurring-transactions")
+        .json(&salary("FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1"))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["rrule"], "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1");
+    let salary_id = body.data["id"].as_i64().unwrap();
+
+    let server = &server;
+    let due_dates = move || async move {
+        let missing: ApiResponse<Vec<serde_json::Value>> = server
+            .get("/api/v1/recurring-transactions/missing-instances")
+            .add_query_param("recurring_transaction_id", salary_id)
+            .add_query_param("start_date", "2025-01-01")
+            .add_query_param("end_date", "2025-06-30")
+            .await
+            .json();
+        let mut dates: Vec<String> = missing
+            .data
+            .iter()
+            .map(|instance| instance["due_date"].as_str().unwrap().to_string())
+            .collect();
+        dates.sort();
+        dates
+    };
+    // May 31st 2025 is a Saturday
+    assert_eq!(
+        due_dates().await,
+        vec!["2025-01-31", "2025-02-28", "2025-03-31", "2025-04-30", "2025-05-30", "2025-06-30"]
+    );
+
+    let response = server
+        .put(&format!("/api/v1/recurring-transactions/{}", salary_id))
+        .json(&serde_json::json!({ "rrule": "FREQ=WEEKLY;INTERVAL=" }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    assert_eq!(response.json::<serde_json::Value>()["code"], "INVALID_RRULE");
+
+    // An empty rule goes back to the monthly period
+    let response = server
+        .put(&format!("/api/v1/recurring-transactions/{}", salary_id))
+        .json(&serde_json::json!({ "rrule": "" }))
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["rrule"], serde_json::Value::Null);
+    assert_eq!(
+        due_dates().await,
+        vec!["2025-01-01", "2025-02-01", "2025-03-01", "2025-04-01", "2025-05-01", "2025-06-01"]
+    );
+}
+
+#[tokio::test]
+async fn test_bulk_update_recurring_transactions() {
+    use finrust::handlers::categories::CreateCategoryRequest;
+
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let mut account_ids = Vec::new();
+    for name in ["Old Bank", "New Bank"] {
+        let account_request = CreateAccountRequest {
+            name: name.to_string(),
+            description: None,
+            currency_code: "USD".to_string(),
+            owner_id: 1,
+            include_in_statistics: Some(true),
+            ledger_name: None,
+            account_kind: None,
+            target_amount: None,
+            color: None,
+            is_liquid: None,
+            balance_interpolation: None,
+        };
+        let account_body: ApiResponse<serde_json::Value> =
+            server.post("/api/v1/accounts").json(&account_request).await.json();
+        account_ids.push(account_body.data["id"].as_i64().unwrap());
+    }
+    let (old_bank, new_bank) = (account_ids[0], account_ids[1]);
+
+    let category_body: ApiResponse<serde_json::Value> = server
+        .post("/api/v1/categories")
+        .json(&CreateCategoryRequest {
+            name: "Utilities".to_string(),
+            description: None,
+            parent_id: None,
+        })
+        .await
+        .json();
+    let category_id = category_body.data["id"].as_i64().unwrap();
+
+    let mut rule_ids = Vec::new();
+    for (name, amount) in [("Electricity", "-80"), ("Water", "-30")] {
+        let body: ApiResponse<serde_json::Value> = server
+            .post("/api/v1/recurring-transactions")
+            .json(&serde_json::json!({
+                "name": name,
+                "amount": amount,
+                "start_date": "2025-01-01",
+                "period": "Monthly",
+                "target_account_id": old_bank,
+            }))
+            .await
+            .json();
+        rule_ids.push(body.data["id"].as_i64().unwrap());
+    }
+
+    // An empty selection or patch is rejected
+    let response = server
+        .patch("/api/v1/recurring-transactions/bulk")
+        .json(&serde_json::json!({ "recurring_transaction_ids": [], "patch": { "category_id": category_id } }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    assert_eq!(response.json::<serde_json::Value>()["code"], "INVALID_SELECTION");
+    let response = server
+        .patch("/api/v1/recurring-transactions/bulk")
+        .json(&serde_json::json!({ "recurring_transaction_ids": rule_ids, "patch": {} }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    assert_eq!(response.json::<serde_json::Value>()["code"], "EMPTY_PATCH");
+    let response = server
+        .patch("/api/v1/recurring-transactions/bulk")
+        .json(&serde_json::json!({ "recurring_transaction_ids": rule_ids, "patch": { "category_id": 99999 } }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    assert_eq!(response.json::<serde_json::Value>()["code"], "INVALID_CATEGORY_ID");
+
+    let server = &server;
+    let rule = move |id: i64| async move {
+        let body: ApiResponse<serde_json::Value> =
+            server.get(&format!("/api/v1/recurring-transactions/{}", id)).await.json();
+        body.data
+    };
+    let move_to_new_bank = serde_json::json!({ "target_account_id": new_bank, "category_id": category_id });
+
+    // One unknown rule blocks the whole update
+    let response = server
+        .patch("/api/v1/recurring-transactions/bulk")
+        .json(&serde_json::json!({
+            "recurring_transaction_ids": [rule_ids[0], 99999, rule_ids[1]],
+            "patch": move_to_new_bank,
+        }))
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["applied"], false);
+    assert_eq!(body.data["valid_count"], 2);
+    assert_eq!(body.data["failed_count"], 1);
+    let results = body.data["results"].as_array().unwrap();
+    assert_eq!(results[0]["success"], true);
+    assert_eq!(results[1]["recurring_transaction_id"], 99999);
+    assert_eq!(results[1]["success"], false);
+    assert!(results[1]["error"].as_str().unwrap().contains("not found"));
+    assert_eq!(rule(rule_ids[0]).await["target_account_id"].as_i64(), Some(old_bank));
+
+    // An end date before the start fails the item
+    let body: ApiResponse<serde_json::Value> = server
+        .patch("/api/v1/recurring-transactions/bulk")
+        .json(&serde_json::json!({ "recurring_transaction_ids": rule_ids, "patch": { "end_date": "2024-12-31" } }))
+        .await
+        .json();
+    assert_eq!(body.data["applied"], false);
+    assert_eq!(body.data["failed_count"], 2);
+
+    // A dry run validates without writing
+    let body: ApiResponse<serde_json::Value> = server
+        .patch("/api/v1/recurring-transactions/bulk")
+        .json(&serde_json::json!({ "recurring_transaction_ids": rule_ids, "patch": move_to_new_bank, "dry_run": true }))
+        .await
+        .json();
+    assert_eq!(body.data["dry_run"], true);
+    assert_eq!(body.data["applied"], false);
+    assert_eq!(body.data["valid_count"], 2);
+    assert_eq!(rule(rule_ids[1]).await["target_account_id"].as_i64(), Some(old_bank));
+
+    let response = server
+        .patch("/api/v1/recurring-transactions/bulk")
+        .json(&serde_json::json!({ "recurring_transaction_ids": rule_ids, "patch": move_to_new_bank }))
+        .await;
+    response.assert_status_ok();
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["applied"], true);
+    assert_eq!(body.data["failed_count"], 0);
+    for &id in &rule_ids {
+        let updated = rule(id).await;
+        assert_eq!(updated["target_account_id"].as_i64(), Some(new_bank));
+        assert_eq!(updated["category_id"].as_i64(), Some(category_id));
+        assert_eq!(updated["start_date"], "2025-01-01");
+    }
+}
+
+#[tokio::test]
+async fn test_recurring_transaction_date_adjustment() {
+    let app = setup_test_app().await;
+    let server = TestServer::new(app).unwrap();
+
+    let account_request = CreateAccountRequest {
+        name: "Checking".to_string(),
+        description: None,
+        currency_code: "USD".to_string(),
+        owner_id: 1,
+        include_in_statistics: Some(true),
+        ledger_name: None,
+        account_kind: None,
+        target_amount: None,
+        color: None,
+        is_liquid: None,
+        balance_interpolation: None,
+    };
+    let account_body: ApiResponse<serde_json::Value> =
+        server.post("/api/v1/accounts").json(&account_request).await.json();
+    let account_id = account_body.data["id"].as_i64().unwrap();
+
+    // Rent due on the 31st
+    let response = server
+        .post("/api/v1/recurring-transactions")
+        .json(&serde_json::json!({
+            "name": "Rent",
+            "amount": "-1200",
+            "start_date": "2025-01-31",
+            "period": "Monthly",
+            "target_account_id": account_id,
+            "date_adjustment": "ClampToMonthEnd",
+        }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let body: ApiResponse<serde_json::Value> = response.json();
+    assert_eq!(body.data["date_adjustment"], "ClampToMonthEnd");
+    let rent_id = body.data["id"].as_i64().unwrap();
+
+    let server = &server;
+    let due_dates = move || async move {
+        let missing: ApiResponse<Vec<serde_json::Value>> = server
+            .get("/api/v1/recurring-transactions/missing-instances")
+            .add_query_param("recurring_transaction_id", rent_id)
+            .add_query_param("start_date", "2025-01-01")
+            .add_query_param("end_date", "2025-06-30")
+            .await
+            .json();
+        let mut dates: Vec<String> = missing
+            .data
+            .iter()
+            .map(|instance| instance["due_date"].as_str().unwrap().to_string())
+            .collect();
+        dates.sort();
+        dates
+    };
+    // Back on the 31st after February
+    assert_eq!(
+        due_dates().await,
+        vec!["2025-01-31", "2025-02-28", "2025-03-31", "2025-04-30", "2025-05-31", "2025-06-30"]
+    );
+
+    // May 31st 2025 is a Saturday
+    let response = server
+        .put(&format!("/api/v1/recurring-transactions/{}", rent_id))
+        .json(&serde_json::json!({ "date_adjustment": "NextBusinessDay" }))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(
+        due_dates().await,
+        vec!["2025-01-31", "2025-02-28", "2025-03-31", "2025-04-30", "2025-06-02", "2025-06-30"]
+    );
+
+    let response = server
+        .put(&format!("/api/v1/recurring-transactions/{}", rent_id))
+        .json(&serde_json::json!({ "date_adjustment": "PreviousBusinessDay" }))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(
+        due_dates().await,
+        vec!["2025-01-31", "2025-02-28", "2025-03-31", "2025-04-30", "2025-05-30", "2025-06-30"]
+    );
+
+    let response = server
+        .put(&format!("/api/v1/recurring-transactions/{}", rent_id))
+        .json(&serde_json::json!({ "date_adjustment": "SometimesMaybe" }))
+        .await;
+    assert!(response.status_code().is_client_error());
+}
+
+#[tokio::test]
+async fn test_holiday_calendars_skip_work_days() {
+    use finrust::handlers::holidays::{HolidayCalendarsResponse, HolidayResponse};
+    use finrust::schemas::ApiResponse;
+    use model::entities::recurring_transaction::{DateAdjustment, RecurrencePeriod};
+    use test_factories::{AccountFactory, RecurringTransactionFactory};
+
+    let app_state = setup_test_app_state().await;
+    let app = finrust::router::create_test_router(app_state.clone());
+    let server = TestServer::new(app).unwrap();
+
+    let account = AccountFactory::new().owner(1).create(&app_state.db).await.unwrap();
+    let lunch = RecurringTransactionFactory::new(&account)
+        .name("Lunch")
+        .amount(-10)
+        .start_date(NaiveDate::from_ymd_opt(2025, 12, 22).unwrap())
+        .period(RecurrencePeriod::WorkDay)
+        .create(&app_state.db)
+        .await
+        .unwrap();
+    // December 24th 2025 is a Wednesday
+    let invoice = RecurringTransactionFactory::new(&account)
+        .name("Invoice")
+        .start_date(NaiveDate::from_ymd_opt(2025, 12, 24).unwrap())
+        .date_adjustment(DateAdjustment::NextBusinessDay)
+        .create(&app_state.db)
+        .await
+        .unwrap();
+
+    let server = &server;
+    let due_dates = move |recurring_transaction_id: i32| async move {
+        let missing: ApiResponse<Vec<serde_json::Value>> = server
+            .get("/api/v1/recurring-transactions/missing-instances")
+            .add_query_param("recurring_transaction_id", recurring_transaction_id)
+            .add_query_param("start_date", "2025-12-22")
+            .add_query_param("end_date", "2025-12-31")
+            .await
+            .json();
+        let mut dates: Vec<String> = missing
+            .data
+            .iter()
+            .map(|instance| instance["due_date"].as_str().unwrap()[8..].to_string())
+            .collect();
+        dates.sort();
+        dates
+    };
+    assert_eq!(due_dates(lunch.id).await, vec!["22", "23", "24", "25", "26", "29", "30", "31"]);
+    assert_eq!(due_dates(invoice.id).await, vec!["24"]);
+
+    let response = server
+        .put("/api/v1/holidays/calendars")
+        .json(&serde_json::json!({ "country_codes": ["cz"] }))
+        .await;
+    response.assert_status_ok();
+    let calendars: ApiResponse<HolidayCalendarsResponse> = response.json();
+    assert_eq!(calendars.data.enabled.len(), 1);
+    assert_eq!(calendars.data.enabled[0].code, "CZ");
+    assert!(calendars.data.available.iter().any(|calendar| calendar.code == "DE"));
+
+    // Christmas Eve and both Christmas holidays are off in Czechia
+    assert_eq!(due_dates(lunch.id).await, vec!["22", "23", "29", "30", "31"]);
+    assert_eq!(due_dates(invoice.id).await, vec!["29"]);
+
+    let response = server
+        .post("/api/v1/holidays")
+        .json(&serde_json::json!({ "date": "2025-12-30", "name": "Office closed" }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let office_closed: ApiResponse<HolidayResponse> = response.json();
+    let office_closed_id = office_closed.data.id.unwrap();
+    assert_eq!(due_dates(lunch.id).await, vec!["22", "23", "29", "31"]);
+
+    let response = server
+        .post("/api/v1/holidays")
+        .json(&serde_json::json!({ "date": "2025-12-30", "name": "Inventory" }))
+        .await;
+    response.assert_status(StatusCode::CONFLICT);
+
+    let holidays: ApiResponse<Vec<HolidayResponse>> = server
+        .get("/api/v1/holidays")
+        .add_query_param("start_date", "2025-12-01")
+        .add_query_param("end_date", "2025-12-31")
+        .await
+        .json();
+    let listed: Vec<(String, Option<String>)> = holidays
+        .data
+        .iter()
+        .map(|holiday| (holiday.date.to_string(), holiday.country_code.clone()))
+        .collect();
+    assert_eq!(
+        listed,
+        vec![
+            ("2025-12-24".to_string(), Some("CZ".to_string())),
+            ("2025-12-25".to_string(), Some("CZ".to_string())),
+            ("2025-12-26".to_string(), Some("CZ".to_string())),
+            ("2025-12-30".to_string(), None),
+        ]
+    );
+
+    let response = server.delete(&format!("/api/v1/holidays/{}", office_closed_id)).await;
+    response.assert_status(StatusCode::NO_CONTENT);
+    assert_eq!(due_dates(lunch.id).await, vec!["22", "23", "29", "30", "31"]);
+    let response = server.delete(&format!("/api/v1/holidays/{}", office_closed_id)).await;
+    response.assert_status(StatusCode::NOT_FOUND);
+
+    let response = server
+        .put("/api/v1/holidays/calendars")
+        .json(&serde_json::json!({ "country_codes": ["XX"] }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_openapi_spec_and_coverage() {
+    use finrust::handlers::openapi::OpenApiCoverageResponse;
+
+    let mut state = setup_test_app_state().await;
+    state.auth_required = true;
+    let server = TestServer::new(finrust::router::create_test_router(state)).unwrap();
+
+    // Client generators fetch the specification without a session
+    let response = server.get("/api/v1/openapi.json").await;
+    response.assert_status_ok();
+    let spec: serde_json::Value = response.json();
+    let paths = spec["paths"].as_object().unwrap();
+    for path in [
+        "/api/v1/categories",
+        "/api/v1/tags/{tag_id}",
+        "/api/v1/recurring-instances/{instance_id}",
+        "/api/v1/recurring-transactions/{recurring_transaction_id}/approve",
+        "/api/v1/cache/flush",
+        "/api/v1/prompt",
+    ] {
+        assert!(paths.contains_key(path), "{} missing from the spec", path);
+    }
+    assert!(spec["components"]["schemas"].get("CategoryResponse").is_some());
+    assert!(spec["components"]["schemas"].get("TagLimitsResponse").is_some());
+
+    // The coverage check itself is an ordinary authenticated route
+    server
+        .get("/api/v1/openapi/coverage")
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    let server = TestServer::new(finrust::router::create_test_router(setup_test_app_state().await)).unwrap();
+    let response = server.get("/api/v1/openapi/coverage").await;
+    response.assert_status_ok();
+    let coverage: ApiResponse<OpenApiCoverageResponse> = response.json();
+    assert!(coverage.data.complete, "undocumented: {:?}, unrouted: {:?}", coverage.data.undocumented, coverage.data.unrouted);
+    assert!(coverage.data.route_count > 100);
+    assert_eq!(coverage.data.documented_count, coverage.data.route_count);
+}
+
+#[tokio::test]
+async fn test_payment_alerts_through_webhook() {
+    use axum::{routing::post, Router};
+    use finrust::handlers::notifications::{NotificationChannelResponse, RecurringAlertSettings};
+    use finrust::schemas::ApiResponse;
+    use finrust::tasks::payment_alerts::PaymentAlertSummary;
+    use std::sync::{Arc, Mutex};
+    use test_factories::{AccountFactory, RecurringInstanceFactory, RecurringTransactionFactory};
+
+    // Webhook receiver recording every posted notification
+    let received: Arc<Mutex<Vec<serde_json::Value>>> = Arc::default();
+    let webhook = Router::new().route(
+        "/hook",
+        post({
+            let received = received.clone();
+            move |axum::Json(body): axum::Json<serde_json::Value>| async move {
+                received.lock().unwrap().push(body);
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let webhook_url = format!("http://{}/hook", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, webhook).await.unwrap() });
+
+    let app_state = setup_test_app_state().await;
+    let server = TestServer::new(finrust::router::create_test_router(app_state.clone())).unwrap();
+    let today = compute::today_in(&app_state.timezone);
+
+    let account = AccountFactory::new().owner(1).currency("EUR").create(&app_state.db).await.unwrap();
+    let rent = RecurringTransactionFactory::new(&account)
+        .name("Rent")
+        .amount(-900)
+        .create(&app_state.db)
+        .await
+        .unwrap();
+    let overdue = RecurringInstanceFactory::new(&rent, today - chrono::Duration::days(3))
+        .create(&app_state.db)
+        .await
+        .unwrap();
+    let upcoming = RecurringInstanceFactory::new(&rent, today + chrono::Duration::days(2))
+        .create(&app_state.db)
+        .await
+        .unwrap();
+    // Too far ahead, already paid and overdue for too long, respectively
+    RecurringInstanceFactory::new(&rent, today + chrono::Duration::days(20))
+        .create(&app_state.db)
+        .await
+        .unwrap();
+    RecurringInstanceFactory::new(&rent, today - chrono::Duration::days(1))
+        .paid()
+        .create(&app_state.db)
+        .await
+        .unwrap();
+    RecurringInstanceFactory::new(&rent, today - chrono::Duration::days(60))
+        .create(&app_state.db)
+        .await
+        .unwrap();
+
+    let response = server
+        .post("/api/v1/notification-channels")
+        .json(&serde_json::json!({ "name": "Family hook", "kind": "Webhook", "target": "ftp://example.com" }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let response = server
+        .post("/api/v1/notification-channels")
+        .json(&serde_json::json!({ "name": "Family inbox", "kind": "Email", "target": "not an address" }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    let response = server
+        .post("/api/v1/notification-channels")
+        .json(&serde_json::json!({ "name": "Family hook", "kind": "Webhook", "target": webhook_url }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let channel: ApiResponse<NotificationChannelResponse> = response.json();
+    assert!(channel.data.enabled);
+
+    // Without alert settings nothing is sent
+    let summary: ApiResponse<PaymentAlertSummary> = server.post("/api/v1/payment-alerts/send").await.json();
+    assert_eq!((summary.data.upcoming_sent, summary.data.overdue_sent), (0, 0));
+    let settings: ApiResponse<RecurringAlertSettings> = server
+        .get(&format!("/api/v1/recurring-transactions/{}/alerts", rent.id))
+        .await
+        .json();
+    assert_eq!(settings.data.days_before, None);
+    assert!(!settings.data.notify_overdue);
+
+    let response = server
+        .put(&format!("/api/v1/recurring-transactions/{}/alerts", rent.id))
+        .json(&serde_json::json!({ "days_before": 400, "notify_overdue": true }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let response = server
+        .put(&format!("/api/v1/recurring-transactions/{}/alerts", rent.id))
+        .json(&serde_json::json!({ "days_before": 5, "notify_overdue": true, "channel_id": 999999 }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let response = server
+        .put("/api/v1/recurring-transactions/999999/alerts")
+        .json(&serde_json::json!({ "days_before": 5 }))
+        .await;
+    response.assert_status(StatusCode::NOT_FOUND);
+
+    let response = server
+        .put(&format!("/api/v1/recurring-transactions/{}/alerts", rent.id))
+        .json(&serde_json::json!({ "days_before": 5, "notify_overdue": true, "channel_id": channel.data.id }))
+        .await;
+    response.assert_status_ok();
+    let settings: ApiResponse<RecurringAlertSettings> = response.json();
+    assert_eq!(settings.data.days_before, Some(5));
+    assert_eq!(settings.data.channel_id, Some(channel.data.id));
+
+    let summary: ApiResponse<PaymentAlertSummary> = server.post("/api/v1/payment-alerts/send").await.json();
+    assert_eq!(summary.data.date, today);
+    assert_eq!((summary.data.upcoming_sent, summary.data.overdue_sent, summary.data.failed), (1, 1, 0));
+    {
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 2);
+        let alert = |kind: &str| received.iter().find(|body| body["data"]["kind"] == kind).unwrap().clone();
+        let overdue_alert = alert("Overdue");
+        assert_eq!(overdue_alert["title"], "Rent is overdue");
+        assert_eq!(overdue_alert["data"]["recurring_instance_id"], overdue.id);
+        assert_eq!(overdue_alert["data"]["currency_code"], "EUR");
+        let upcoming_alert = alert("Upcoming");
+        assert_eq!(upcoming_alert["title"], "Rent is due in 2 days");
+        assert_eq!(upcoming_alert["data"]["recurring_instance_id"], upcoming.id);
+    }
+
+    // Each instance is alerted about once per kind
+    let summary: ApiResponse<PaymentAlertSummary> = server.post("/api/v1/payment-alerts/send").await.json();
+    assert_eq!((summary.data.upcoming_sent, summary.data.overdue_sent), (0, 0));
+    assert_eq!(received.lock().unwrap().len(), 2);
+
+    let response = server.post(&format!("/api/v1/notification-channels/{}/test", channel.data.id)).await;
+    response.assert_status_ok();
+    assert_eq!(received.lock().unwrap().len(), 3);
+    assert_eq!(received.lock().unwrap()[2]["data"]["kind"], "Test");
+
+    // E-mail channels need an SMTP server
+    let email: ApiResponse<NotificationChannelResponse> = server
+        .post("/api/v1/notification-channels")
+        .json(&serde_json::json!({ "name": "Inbox", "kind": "Email", "target": "family@example.com", "enabled": false }))
+        .await
+        .json();
+    let response = server.post(&format!("/api/v1/notification-channels/{}/test", email.data.id)).await;
+    response.assert_status(StatusCode::CONFLICT);
+
+    let response = server
+        .put(&format!("/api/v1/notification-channels/{}", channel.data.id))
+        .json(&serde_json::json!({ "enabled": false }))
+        .await;
+    response.assert_status_ok();
+    let channels: ApiResponse<Vec<NotificationChannelResponse>> = server.get("/api/v1/notification-channels").await.json();
+    assert_eq!(channels.data.len(), 2);
+    assert!(channels.data.iter().all(|channel| !channel.enabled));
+
+    let response = server.delete(&format!("/api/v1/notification-channels/{}", channel.data.id)).await;
+    response.assert_status(StatusCode::NO_CONTENT);
+    let response = server.delete(&format!("/api/v1/notification-channels/{}", channel.data.id)).await;
+    response.assert_status(StatusCode::NOT_FOUND);
+    let settings: ApiResponse<RecurringAlertSettings> = server
+        .get(&format!("/api/v1/recurring-transactions/{}/alerts", rent.id))
+        .await
+        .json();
+    assert_eq!(settings.data.channel_id, None);
+}
+
+#[tokio::test]
+async fn test_payment_alerts_reach_only_account_users() {
+    use axum::{extract::Path, routing::post, Router};
+    use finrust::tasks::payment_alerts::PaymentAlertSummary;
+    use model::entities::{account_share, notification_channel, recurring_alert};
+    use sea_orm::{ActiveModelTrait, Set};
+    use std::sync::{Arc, Mutex};
+    use test_factories::{AccountFactory, RecurringInstanceFactory, RecurringTransactionFactory};
+
+    // Webhook receiver recording which user's hook got which alert
+    let received: Arc<Mutex<Vec<(String, serde_json::Value)>>> = Arc::default();
+    let webhook = Router::new().route(
+        "/hook/:user",
+        post({
+            let received = received.clone();
+            move |Path(user): Path<String>, axum::Json(body): axum::Json<serde_json::Value>| async move {
+                received.lock().unwrap().push((user, body));
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let webhook_url = format!("http://{}/hook", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, webhook).await.unwrap() });
+
+    let app_state = setup_test_app_state().await;
+    let db = &app_state.db;
+    let server = TestServer::new(finrust::router::create_test_router(app_state.clone())).unwrap();
+    let today = compute::today_in(&app_state.timezone);
+
+    for (user, user_id) in [("alice", 1), ("bob", 2)] {
+        notification_channel::ActiveModel {
+            name: Set(format!("{}'s hook", user)),
+            kind: Set(notification_channel::NotificationChannelKind::Webhook),
+            target: Set(format!("{}/{}", webhook_url, user)),
+            enabled: Set(true),
+            created_at: Set(chrono::Utc::now().naive_utc()),
+            user_id: Set(Some(user_id)),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap();
+    }
+
+    // Alice keeps one account to herself and shares the other with Bob
+    let private = AccountFactory::new().owner(1).create(db).await.unwrap();
+    let shared = AccountFactory::new().owner(1).create(db).await.unwrap();
+    account_share::ActiveModel {
+        account_id: Set(shared.id),
+        user_id: Set(2),
+        permission: Set(account_share::SharePermission::Read),
+        created_at: Set(chrono::Utc::now().naive_utc()),
+    }
+    .insert(db)
+    .await
+    .unwrap();
+    for (account, name) in [(&private, "Therapy"), (&shared, "Rent")] {
+        let rule = RecurringTransactionFactory::new(account).name(name).amount(-100).create(db).await.unwrap();
+        RecurringInstanceFactory::new(&rule, today + chrono::Duration::days(2))
+            .create(db)
+            .await
+            .unwrap();
+        recurring_alert::ActiveModel {
+            recurring_transaction_id: Set(rule.id),
+            days_before: Set(Some(5)),
+            notify_overdue: Set(false),
+            channel_id: Set(None),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap();
+    }
+
+    let summary: ApiResponse<PaymentAlertSummary> = server.post("/api/v1/payment-alerts/send").await.json();
+    assert_eq!(summary.data.upcoming_sent, 2);
+    let mut delivered: Vec<(String, String)> = received
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(user, body)| (user.clone(), body["data"]["name"].as_str().unwrap().to_string()))
+        .collect();
+    delivered.sort();
+    assert_eq!(
+        delivered,
+        vec![
+            ("alice".to_string(), "Rent".to_string()),
+            ("alice".to_string(), "Therapy".to_string()),
+            ("bob".to_string(), "Rent".to_string()),
+        ]
+    );
+}
@@ -74,3 +74,40 @@ fn test_all_error_responses_reference_correct_schema() {
 
     assert!(openapi_json.contains("ErrorResponse"));
 }
+
+#[test]
+fn test_openapi_covers_every_route() {
+    use finrust::handlers::openapi::openapi_coverage;
+    use finrust::router::api_routes;
+
+    let coverage = openapi_coverage(&api_routes(), &ApiDoc::openapi());
+
+    assert!(coverage.undocumented.is_empty(), "Routes missing from the spec: {:?}", coverage.undocumented);
+    assert!(coverage.unrouted.is_empty(), "Documented operations without a route: {:?}", coverage.unrouted);
+    assert!(coverage.complete);
+    assert_eq!(coverage.documented_count, coverage.route_count);
+}
+
+#[test]
+fn test_openapi_coverage_reports_mismatches() {
+    use axum::http::Method;
+    use finrust::handlers::openapi::openapi_coverage;
+    use finrust::router::ApiRoute;
+
+    let routes = vec![
+        ApiRoute { method: Method::GET, path: "/health" },
+        ApiRoute { method: Method::GET, path: "/api/v1/widgets/:widget_id" },
+    ];
+    let coverage = openapi_coverage(&routes, &ApiDoc::openapi());
+
+    assert!(!coverage.complete);
+    assert_eq!(coverage.route_count, 2);
+    assert_eq!(coverage.documented_count, 1);
+    assert_eq!(coverage.undocumented.len(), 1);
+    assert_eq!(coverage.undocumented[0].method, "GET");
+    assert_eq!(coverage.undocumented[0].path, "/api/v1/widgets/{widget_id}");
+    assert!(coverage
+        .unrouted
+        .iter()
+        .any(|route| route.method == "POST" && route.path == "/api/v1/accounts"));
+}
@@ -0,0 +1,209 @@
+//! Locale tolerant parsing of monetary amounts.
+//!
+//! Bank exports and hand-typed input disagree on how numbers are written
+//! (`1.234,56` vs `1,234.56`, `1 234,56 Kč`, `(12.00)`). Every place
+//! that turns user or file provided text into a [`Decimal`] should go through
+//! [`parse_amount`] so the rules stay in one spot.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// Hint describing which character is used as the decimal separator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+pub enum AmountLocale {
+    /// Guess the separators from the input itself.
+    #[default]
+    Auto,
+    /// `.` is the decimal separator, `,` groups thousands (e.g. `1,234.56`).
+    DotDecimal,
+    /// `,` is the decimal separator, `.` groups thousands (e.g. `1.234,56`).
+    CommaDecimal,
+}
+
+/// Error returned when an amount cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AmountParseError {
+    /// The input contained no digits at all.
+    Empty,
+    /// The input could not be interpreted as a number.
+    Invalid(String),
+}
+
+impl fmt::Display for AmountParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AmountParseError::Empty => write!(f, "amount is empty"),
+            AmountParseError::Invalid(input) => write!(f, "invalid amount '{}'", input),
+        }
+    }
+}
+
+impl std::error::Error for AmountParseError {}
+
+/// Parses a monetary amount written in any of the common locale formats.
+///
+/// Whitespace (including non-breaking spaces) and apostrophes used as thousand
+/// separators are ignored, as are currency symbols or codes before or after the
+/// number. Negative values are written with a leading `-` or wrapped in
+/// parentheses. Anything else, such as letters between digits (`1e5`) or a sign
+/// in the middle of the number, is rejected.
+///
+/// With [`AmountLocale::Auto`] the last separator is treated as the decimal
+/// separator when both `.` and `,` are present. A single `,` followed by exactly
+/// three digits is treated as a thousands separator; a single `.` is always a
+/// decimal separator.
+pub fn parse_amount(input: &str, locale: AmountLocale) -> Result<Decimal, AmountParseError> {
+    let invalid = || AmountParseError::Invalid(input.to_string());
+
+    let mut rest = strip_currency(input);
+    let mut negative = false;
+    if let Some(enclosed) = rest.strip_prefix('(') {
+        rest = strip_currency(enclosed.strip_suffix(')').ok_or_else(invalid)?);
+        negative = true;
+    }
+    if let Some(unsigned) = rest.strip_prefix(['-', '\u{2212}']) {
+        if negative {
+            return Err(invalid());
+        }
+        rest = strip_currency(unsigned);
+        negative = true;
+    } else if let Some(unsigned) = rest.strip_prefix('+') {
+        rest = strip_currency(unsigned);
+    }
+
+    let mut body = String::with_capacity(rest.len());
+    for c in rest.chars() {
+        match c {
+            '0'..='9' | '.' | ',' => body.push(c),
+            c if c.is_whitespace() || c == '\'' || c == '\u{2019}' => {}
+            _ => return Err(invalid()),
+        }
+    }
+
+    if !body.chars().any(|c| c.is_ascii_digit()) {
+        return Err(AmountParseError::Empty);
+    }
+
+    let decimal_separator = match locale {
+        AmountLocale::DotDecimal => Some('.'),
+        AmountLocale::CommaDecimal => Some(','),
+        AmountLocale::Auto => guess_decimal_separator(&body),
+    };
+
+    let normalized: String = body
+        .chars()
+        .filter_map(|c| match c {
+            '.' | ',' if Some(c) == decimal_separator => Some('.'),
+            '.' | ',' => None,
+            c => Some(c),
+        })
+        .collect();
+
+    if normalized.matches('.').count() > 1 {
+        return Err(invalid());
+    }
+
+    let value = Decimal::from_str(&normalized).map_err(|_| invalid())?;
+    Ok(if negative { -value } else { value })
+}
+
+/// Decides which separator (if any) is the decimal one for [`AmountLocale::Auto`].
+fn guess_decimal_separator(body: &str) -> Option<char> {
+    let last_dot = body.rfind('.');
+    let last_comma = body.rfind(',');
+
+    match (last_dot, last_comma) {
+        (Some(dot), Some(comma)) => Some(if dot > comma { '.' } else { ',' }),
+        (Some(_), None) if body.matches('.').count() == 1 => Some('.'),
+        (None, Some(comma)) if body.matches(',').count() == 1 => {
+            let digits_after = body.len() - comma - 1;
+            if digits_after == 3 { None } else { Some(',') }
+        }
+        _ => None,
+    }
+}
+
+/// Trims whitespace and a currency symbol or code (e.g. "$", "€", "CZK", "Kč")
+/// from both ends of `input`.
+fn strip_currency(input: &str) -> &str {
+    let is_affix = |c: char| c.is_whitespace() || c.is_alphabetic() || is_currency_symbol(c);
+    input.trim_matches(is_affix)
+}
+
+/// Returns true for characters from the Unicode currency symbol block and the
+/// handful of symbols commonly found in bank exports.
+fn is_currency_symbol(c: char) -> bool {
+    matches!(c, '$' | '€' | '£' | '¥' | '₩' | '₹' | '₽' | '¢') || ('\u{20A0}'..='\u{20CF}').contains(&c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_parse_plain_amounts() {
+        assert_eq!(parse_amount("1234.56", AmountLocale::Auto), Ok(dec("1234.56")));
+        assert_eq!(parse_amount("-42", AmountLocale::Auto), Ok(dec("-42")));
+        assert_eq!(parse_amount("+7.5", AmountLocale::Auto), Ok(dec("7.5")));
+    }
+
+    #[test]
+    fn test_parse_auto_detects_separators() {
+        assert_eq!(parse_amount("1.234,56", AmountLocale::Auto), Ok(dec("1234.56")));
+        assert_eq!(parse_amount("1,234.56", AmountLocale::Auto), Ok(dec("1234.56")));
+        assert_eq!(parse_amount("1 234,56", AmountLocale::Auto), Ok(dec("1234.56")));
+        assert_eq!(parse_amount("1'234.56", AmountLocale::Auto), Ok(dec("1234.56")));
+        assert_eq!(parse_amount("12,5", AmountLocale::Auto), Ok(dec("12.5")));
+        assert_eq!(parse_amount("1,234", AmountLocale::Auto), Ok(dec("1234")));
+        assert_eq!(parse_amount("1.234.567", AmountLocale::Auto), Ok(dec("1234567")));
+    }
+
+    #[test]
+    fn test_parse_with_explicit_locale() {
+        assert_eq!(parse_amount("1,234", AmountLocale::CommaDecimal), Ok(dec("1.234")));
+        assert_eq!(parse_amount("1.234", AmountLocale::CommaDecimal), Ok(dec("1234")));
+        assert_eq!(parse_amount("1,234.5", AmountLocale::DotDecimal), Ok(dec("1234.5")));
+    }
+
+    #[test]
+    fn test_parse_currency_and_negative_forms() {
+        assert_eq!(parse_amount("$1,234.56", AmountLocale::Auto), Ok(dec("1234.56")));
+        assert_eq!(parse_amount("1 234,56 Kč", AmountLocale::Auto), Ok(dec("1234.56")));
+        assert_eq!(parse_amount("EUR -12,00", AmountLocale::Auto), Ok(dec("-12.00")));
+        assert_eq!(parse_amount("(12.00)", AmountLocale::Auto), Ok(dec("-12.00")));
+        assert_eq!(parse_amount("1\u{a0}000 €", AmountLocale::Auto), Ok(dec("1000")));
+        assert_eq!(parse_amount("-$12.50", AmountLocale::Auto), Ok(dec("-12.50")));
+        assert_eq!(parse_amount("CZK (1 200,00)", AmountLocale::Auto), Ok(dec("-1200.00")));
+        assert_eq!(parse_amount("(€12)", AmountLocale::Auto), Ok(dec("-12")));
+        assert_eq!(parse_amount("\u{2212}3", AmountLocale::Auto), Ok(dec("-3")));
+    }
+
+    #[test]
+    fn test_parse_rejects_misplaced_signs() {
+        assert!(parse_amount("12.00-", AmountLocale::Auto).is_err());
+        assert!(parse_amount("12-34", AmountLocale::Auto).is_err());
+        assert!(parse_amount("--12", AmountLocale::Auto).is_err());
+        assert!(parse_amount("(-12)", AmountLocale::Auto).is_err());
+        assert!(parse_amount("(12", AmountLocale::Auto).is_err());
+        assert!(parse_amount("12)", AmountLocale::Auto).is_err());
+        assert!(parse_amount("1(2)", AmountLocale::Auto).is_err());
+        assert!(parse_amount("+-12", AmountLocale::Auto).is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_garbage() {
+        assert_eq!(parse_amount("", AmountLocale::Auto), Err(AmountParseError::Empty));
+        assert_eq!(parse_amount("USD", AmountLocale::Auto), Err(AmountParseError::Empty));
+        assert!(parse_amount("12#3", AmountLocale::Auto).is_err());
+        assert!(parse_amount("1e5", AmountLocale::Auto).is_err());
+        assert!(parse_amount("12 USD 34", AmountLocale::Auto).is_err());
+        assert!(parse_amount("1.2.3", AmountLocale::DotDecimal).is_err());
+    }
+}
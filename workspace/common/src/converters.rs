@@ -78,6 +78,7 @@ pub fn compute_stats_to_common_stats(
         end_of_current_month_state,
         end_of_period_state,
         goal_reached_date: None,
+        fx_fees: None,
     }
 }
 
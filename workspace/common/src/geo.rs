@@ -0,0 +1,171 @@
+//! GeoJSON payloads for plotting transactions on a map.
+//!
+//! Only the small subset of GeoJSON needed by the spending map is modelled:
+//! a `FeatureCollection` of `Point` features with transaction properties.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Geographic bounding box in WGS84 degrees.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BoundingBox {
+    pub min_lon: f64,
+    pub min_lat: f64,
+    pub max_lon: f64,
+    pub max_lat: f64,
+}
+
+impl BoundingBox {
+    /// Parses the GeoJSON `bbox` order: `min_lon,min_lat,max_lon,max_lat`.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let parts: Vec<f64> = input
+            .split(',')
+            .map(|part| part.trim().parse::<f64>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| format!("invalid bbox '{}'", input))?;
+
+        let [min_lon, min_lat, max_lon, max_lat] = parts[..] else {
+            return Err(format!("bbox must have 4 values, got {}", parts.len()));
+        };
+
+        if !(-180.0..=180.0).contains(&min_lon)
+            || !(-180.0..=180.0).contains(&max_lon)
+            || !(-90.0..=90.0).contains(&min_lat)
+            || !(-90.0..=90.0).contains(&max_lat)
+        {
+            return Err(format!("bbox '{}' is out of range", input));
+        }
+        if min_lat > max_lat {
+            return Err(format!("bbox '{}' has min_lat above max_lat", input));
+        }
+
+        Ok(Self { min_lon, min_lat, max_lon, max_lat })
+    }
+
+    /// Returns true if the point lies inside the box. Boxes crossing the
+    /// antimeridian (`min_lon > max_lon`) are supported.
+    pub fn contains(&self, lon: f64, lat: f64) -> bool {
+        let lon_inside = if self.min_lon <= self.max_lon {
+            self.min_lon <= lon && lon <= self.max_lon
+        } else {
+            lon >= self.min_lon || lon <= self.max_lon
+        };
+        lon_inside && self.min_lat <= lat && lat <= self.max_lat
+    }
+}
+
+/// GeoJSON `Point` geometry. Coordinates are `[longitude, latitude]`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PointGeometry {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub coordinates: [f64; 2],
+}
+
+impl PointGeometry {
+    pub fn new(lon: f64, lat: f64) -> Self {
+        Self {
+            kind: "Point".to_string(),
+            coordinates: [lon, lat],
+        }
+    }
+}
+
+/// Transaction details carried by each map feature.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TransactionFeatureProperties {
+    pub transaction_id: i32,
+    pub name: String,
+    pub amount: Decimal,
+    pub date: NaiveDate,
+    pub target_account_id: i32,
+    pub category_id: Option<i32>,
+}
+
+/// GeoJSON `Feature` for a single transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TransactionFeature {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub geometry: PointGeometry,
+    pub properties: TransactionFeatureProperties,
+}
+
+impl TransactionFeature {
+    pub fn new(lon: f64, lat: f64, properties: TransactionFeatureProperties) -> Self {
+        Self {
+            kind: "Feature".to_string(),
+            geometry: PointGeometry::new(lon, lat),
+            properties,
+        }
+    }
+}
+
+/// GeoJSON `FeatureCollection` of geolocated transactions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TransactionFeatureCollection {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub features: Vec<TransactionFeature>,
+}
+
+impl TransactionFeatureCollection {
+    pub fn new(features: Vec<TransactionFeature>) -> Self {
+        Self {
+            kind: "FeatureCollection".to_string(),
+            features,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bbox() {
+        let bbox = BoundingBox::parse("14.2, 49.9, 14.7, 50.2").unwrap();
+        assert_eq!(bbox.min_lon, 14.2);
+        assert_eq!(bbox.max_lat, 50.2);
+        assert!(bbox.contains(14.42, 50.08));
+        assert!(!bbox.contains(16.6, 49.2));
+    }
+
+    #[test]
+    fn test_parse_bbox_rejects_invalid_input() {
+        assert!(BoundingBox::parse("1,2,3").is_err());
+        assert!(BoundingBox::parse("a,b,c,d").is_err());
+        assert!(BoundingBox::parse("0,95,1,96").is_err());
+        assert!(BoundingBox::parse("0,10,1,5").is_err());
+    }
+
+    #[test]
+    fn test_bbox_across_antimeridian() {
+        let bbox = BoundingBox::parse("170,-20,-170,0").unwrap();
+        assert!(bbox.contains(179.0, -10.0));
+        assert!(bbox.contains(-175.0, -10.0));
+        assert!(!bbox.contains(0.0, -10.0));
+    }
+
+    #[test]
+    fn test_feature_serializes_as_geojson() {
+        let feature = TransactionFeature::new(
+            14.42,
+            50.08,
+            TransactionFeatureProperties {
+                transaction_id: 1,
+                name: "Coffee".to_string(),
+                amount: Decimal::new(-450, 2),
+                date: NaiveDate::from_ymd_opt(2025, 5, 1).unwrap(),
+                target_account_id: 2,
+                category_id: None,
+            },
+        );
+        let json = serde_json::to_value(&feature).unwrap();
+        assert_eq!(json["type"], "Feature");
+        assert_eq!(json["geometry"]["type"], "Point");
+        assert_eq!(json["geometry"]["coordinates"][0], 14.42);
+    }
+}
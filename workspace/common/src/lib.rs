@@ -2,15 +2,34 @@
 //! These structs mirror the backend handlers' request/response payloads
 //! so the frontend can deserialize API responses without duplicating shapes.
 
+pub mod amount;
+mod geo;
 mod statistics;
 mod timeseries;
 pub mod metrics;
+mod pagination;
+mod preview;
+pub mod reports;
+
+pub use amount::{AmountLocale, AmountParseError, parse_amount};
+pub use geo::{
+    BoundingBox, PointGeometry, TransactionFeature, TransactionFeatureCollection,
+    TransactionFeatureProperties,
+};
 
 pub use metrics::{
     AccountKindMetricsDto, AccountMetricsDto, CashflowBreakdownDto, CashflowContributionDto,
     DashboardMetricsDto, DebtMetricsDto, InvestmentMetricsDto, OperatingMetricsDto,
     ReserveMetricsDto,
 };
+pub use pagination::{Pagination, PaginatedResponse};
+pub use preview::{
+    BalancePreviewPoint, BalancePreviewRequest, BalancePreviewResponse, PreviewTransaction,
+};
+pub use reports::{
+    CashflowPeriodDto, CashflowReportDto, FlowEdgeDto, FlowNodeDto, FlowNodeKind, FlowReportDto,
+    ReportGranularity,
+};
 pub use statistics::{
     AccountStatistics, AccountStatisticsCollection, MonthlyMinBalance, MonthlyMinBalanceSeries,
     TimePeriod,
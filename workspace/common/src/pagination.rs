@@ -0,0 +1,73 @@
+//! Pagination metadata for list endpoints
+//!
+//! Paginated lists answer with the usual `data`, `message` and `success` fields plus a
+//! `pagination` object, so clients can render pagers without fetching every record.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Position of a page within the whole result set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Pagination {
+    /// Number of records matching the request across all pages
+    pub total_count: u64,
+    /// Current page, starting at 1
+    pub page: u64,
+    /// Maximum number of records per page
+    pub page_size: u64,
+    /// Number of pages needed for all records
+    pub total_pages: u64,
+}
+
+impl Pagination {
+    /// Pagination of page `page` holding at most `page_size` of `total_count` records
+    pub fn new(total_count: u64, page: u64, page_size: u64) -> Self {
+        let total_pages = if page_size == 0 { 0 } else { total_count.div_ceil(page_size) };
+        Self {
+            total_count,
+            page,
+            page_size,
+            total_pages,
+        }
+    }
+
+    /// Whether pages follow the current one
+    pub fn has_next(&self) -> bool {
+        self.page < self.total_pages
+    }
+}
+
+/// One page of a list response
+///
+/// Same shape as `ApiResponse`, with the position of the page in `pagination`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PaginatedResponse<T> {
+    /// Records of the current page
+    pub data: T,
+    /// Where the page lies within all matching records
+    pub pagination: Pagination,
+    /// Response message
+    pub message: String,
+    /// Success flag
+    pub success: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_total_pages_rounds_up() {
+        assert_eq!(Pagination::new(0, 1, 50).total_pages, 0);
+        assert_eq!(Pagination::new(50, 1, 50).total_pages, 1);
+        assert_eq!(Pagination::new(51, 1, 50).total_pages, 2);
+        assert_eq!(Pagination::new(10, 1, 0).total_pages, 0);
+    }
+
+    #[test]
+    fn test_has_next() {
+        assert!(Pagination::new(51, 1, 50).has_next());
+        assert!(!Pagination::new(51, 2, 50).has_next());
+        assert!(!Pagination::new(0, 1, 50).has_next());
+    }
+}
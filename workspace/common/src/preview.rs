@@ -0,0 +1,62 @@
+//! Balance preview structures for transport layer
+//!
+//! Request and response of `POST /api/v1/accounts/{account_id}/balance-preview`, which
+//! shows how an unsaved transaction would move an account's balance.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A transaction as entered in a form, not saved yet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PreviewTransaction {
+    /// Effect on the previewed account: the amount when it is the target account,
+    /// the negated amount when it is the source account
+    pub amount: Decimal,
+    /// Date of the transaction, or start date when recurring
+    pub date: NaiveDate,
+    /// Recurrence period (Daily, Weekly, WorkDay, Monthly, Quarterly, HalfYearly, Yearly),
+    /// empty for a one-off transaction
+    pub period: Option<String>,
+    /// Last date a recurring transaction may occur on
+    pub end_date: Option<NaiveDate>,
+}
+
+/// Request body for previewing the balance impact of a transaction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BalancePreviewRequest {
+    /// First day of the preview (inclusive)
+    pub start_date: NaiveDate,
+    /// Last day of the preview (inclusive)
+    pub end_date: NaiveDate,
+    /// The transaction from the form, empty to preview deleting the replaced one
+    pub transaction: Option<PreviewTransaction>,
+    /// One-off transaction being edited, its saved version is taken out of the balances
+    pub transaction_id: Option<i32>,
+    /// Recurring transaction being edited, its saved version is taken out of the balances
+    pub recurring_transaction_id: Option<i32>,
+}
+
+/// Balance on one day without and with the previewed change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BalancePreviewPoint {
+    pub date: NaiveDate,
+    pub before: Decimal,
+    pub after: Decimal,
+}
+
+/// How a transaction would move the balance of an account
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BalancePreviewResponse {
+    pub account_id: i32,
+    pub points: Vec<BalancePreviewPoint>,
+    /// Balance on `end_date` without the change
+    pub end_before: Decimal,
+    /// Balance on `end_date` with the change
+    pub end_after: Decimal,
+    /// Lowest balance with the change
+    pub lowest_balance: Option<Decimal>,
+    /// First day the lowest balance is reached
+    pub lowest_date: Option<NaiveDate>,
+}
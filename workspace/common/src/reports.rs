@@ -0,0 +1,150 @@
+//! Report transport DTOs.
+//!
+//! Summaries over a date range computed by the `compute` crate and served by the
+//! `/api/v1/reports` endpoints.
+
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Length of the periods a report is broken into.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportGranularity {
+    /// Monday to Sunday
+    Week,
+    /// Calendar month
+    #[default]
+    Month,
+    /// Calendar quarter
+    Quarter,
+}
+
+impl ReportGranularity {
+    /// First and last day of the period containing `date`.
+    pub fn bounds(&self, date: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match self {
+            ReportGranularity::Week => {
+                let start = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+                (start, start + Duration::days(6))
+            }
+            ReportGranularity::Month | ReportGranularity::Quarter => {
+                let (first_month, months) = match self {
+                    ReportGranularity::Quarter => ((date.month() - 1) / 3 * 3 + 1, 3),
+                    _ => (date.month(), 1),
+                };
+                let start =
+                    NaiveDate::from_ymd_opt(date.year(), first_month, 1).expect("valid first day of period");
+                let end = start
+                    .checked_add_months(Months::new(months))
+                    .and_then(|next| next.pred_opt())
+                    .expect("valid last day of period");
+                (start, end)
+            }
+        }
+    }
+}
+
+/// Income and expenses of one period.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CashflowPeriodDto {
+    /// First day of the period, clipped to the report range
+    pub start_date: NaiveDate,
+    /// Last day of the period, clipped to the report range
+    pub end_date: NaiveDate,
+    /// Sum of incoming amounts
+    pub income: Decimal,
+    /// Sum of outgoing amounts, as a positive number
+    pub expense: Decimal,
+    /// `income - expense`
+    pub net: Decimal,
+}
+
+/// Income versus expenses across all accounts, per period.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CashflowReportDto {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub granularity: ReportGranularity,
+    /// Every period of the range in order, including ones without transactions
+    pub periods: Vec<CashflowPeriodDto>,
+    pub total_income: Decimal,
+    pub total_expense: Decimal,
+    pub total_net: Decimal,
+}
+
+/// What a node of the flow report stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FlowNodeKind {
+    /// Where income comes from, e.g. an employer
+    IncomeSource,
+    Account,
+    /// A top-level spending category
+    Category,
+}
+
+/// A node of the flow report.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FlowNodeDto {
+    /// Stable identifier edges refer to, e.g. "account:3"
+    pub id: String,
+    pub label: String,
+    pub kind: FlowNodeKind,
+}
+
+/// Money moved between two nodes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FlowEdgeDto {
+    /// Id of the node the money comes from
+    pub source: String,
+    /// Id of the node the money goes to
+    pub target: String,
+    /// Always positive
+    pub amount: Decimal,
+}
+
+/// Money flowing from income sources to accounts and on to spending categories,
+/// shaped for a Sankey diagram.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FlowReportDto {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// Income sources first, then accounts, then categories
+    pub nodes: Vec<FlowNodeDto>,
+    pub edges: Vec<FlowEdgeDto>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_report_granularity_bounds() {
+        // 2026-10-15 is a Thursday
+        assert_eq!(
+            ReportGranularity::Week.bounds(date(2026, 10, 15)),
+            (date(2026, 10, 12), date(2026, 10, 18))
+        );
+        assert_eq!(
+            ReportGranularity::Month.bounds(date(2026, 2, 15)),
+            (date(2026, 2, 1), date(2026, 2, 28))
+        );
+        assert_eq!(
+            ReportGranularity::Quarter.bounds(date(2026, 12, 31)),
+            (date(2026, 10, 1), date(2026, 12, 31))
+        );
+    }
+
+    #[test]
+    fn test_report_granularity_deserialization() {
+        let granularity: ReportGranularity = serde_json::from_str("\"quarter\"").unwrap();
+        assert_eq!(granularity, ReportGranularity::Quarter);
+        assert_eq!(ReportGranularity::default(), ReportGranularity::Month);
+    }
+}
@@ -34,6 +34,9 @@ pub struct AccountStatistics {
     pub end_of_period_state: Option<Decimal>,
     /// Date when goal target is reached (for Goal accounts only)
     pub goal_reached_date: Option<NaiveDate>,
+    /// Total currency conversion fees paid in the period (positive)
+    #[serde(default)]
+    pub fx_fees: Option<Decimal>,
 }
 
 /// A single data point representing the minimum balance for a given month.
@@ -125,6 +128,25 @@ impl TimePeriod {
         Self::DateRange { start, end }
     }
 
+    /// First and last day (inclusive) covered by the period
+    pub fn bounds(&self) -> (NaiveDate, NaiveDate) {
+        match self {
+            Self::Year(year) => (
+                NaiveDate::from_ymd_opt(*year, 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(*year, 12, 31).unwrap(),
+            ),
+            Self::Month { year, month } => {
+                let start = NaiveDate::from_ymd_opt(*year, *month, 1).unwrap();
+                let end = start
+                    .checked_add_months(chrono::Months::new(1))
+                    .and_then(|next| next.pred_opt())
+                    .unwrap();
+                (start, end)
+            }
+            Self::DateRange { start, end } => (*start, *end),
+        }
+    }
+
     /// Get a human-readable description of the period
     pub fn description(&self) -> String {
         match self {
@@ -152,6 +174,7 @@ mod tests {
             end_of_current_month_state: Some(Decimal::new(375, 2)),
             end_of_period_state: Some(Decimal::new(400, 2)),
             goal_reached_date: None,
+            fx_fees: None,
         };
 
         assert_eq!(stats.account_id, 1);
@@ -171,6 +194,7 @@ mod tests {
             end_of_current_month_state: None,
             end_of_period_state: None,
             goal_reached_date: None,
+            fx_fees: None,
         };
 
         let stats2 = AccountStatistics {
@@ -184,6 +208,7 @@ mod tests {
             end_of_current_month_state: None,
             end_of_period_state: None,
             goal_reached_date: None,
+            fx_fees: None,
         };
 
         let collection =
@@ -209,6 +234,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_time_period_bounds() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(TimePeriod::year(2024).bounds(), (date(2024, 1, 1), date(2024, 12, 31)));
+        assert_eq!(TimePeriod::month(2024, 2).bounds(), (date(2024, 2, 1), date(2024, 2, 29)));
+        assert_eq!(TimePeriod::month(2024, 12).bounds(), (date(2024, 12, 1), date(2024, 12, 31)));
+        assert_eq!(
+            TimePeriod::date_range(date(2024, 3, 5), date(2024, 4, 1)).bounds(),
+            (date(2024, 3, 5), date(2024, 4, 1))
+        );
+    }
+
     #[test]
     fn test_monthly_min_balance_series_serialization_roundtrip() {
         let series = MonthlyMinBalanceSeries {
@@ -1,5 +1,7 @@
 pub mod account_state;
+pub mod interpolation;
 pub mod recurring;
+pub mod scenario;
 pub mod transactions;
 
 use async_trait::async_trait;
@@ -96,10 +98,10 @@ impl AccountStateCalculator for BalanceCalculator {
         start_date: NaiveDate,
         end_date: NaiveDate,
     ) -> Result<DataFrame> {
-        // Use the provided today date or default to the current date
+        // Use the provided today date or default to the current UTC date
         let today = self
             .today
-            .unwrap_or_else(|| chrono::Local::now().date_naive());
+            .unwrap_or_else(|| crate::today_in(&chrono::Utc));
         compute_balance(db, accounts, start_date, end_date, today, self.scenario_context).await
     }
 
@@ -110,9 +112,11 @@ impl AccountStateCalculator for BalanceCalculator {
 
 use self::{
     account_state::{get_all_manual_states, get_manual_states_in_range},
+    interpolation::interpolate_between_states,
     recurring::{get_recurring_income, get_recurring_transactions},
     transactions::{get_imported_transactions, get_transactions_for_account},
 };
+use super::forecast::recurring::recurring_amount_for_account;
 
 /// Computes the balance for accounts within a specified date range.
 ///
@@ -130,6 +134,9 @@ use self::{
 /// (which may be outside the requested range—"last balance in bigger history").
 /// Transactions before that state are ignored.
 ///
+/// Accounts with linear balance interpolation move in a straight line between two manual
+/// states that have no transactions in between, instead of jumping on the later state.
+///
 /// # Scenario Context
 /// - `None`: Fetch only real transactions (is_simulated = false)
 /// - `Some(id)`: Fetch real transactions OR simulated transactions belonging to the scenario
@@ -266,6 +273,15 @@ async fn compute_balance(
         // Add one-off transactions
         trace!("Processing one-off transactions for account {}", account.id);
         for tx in transactions {
+            // Planned transactions count on their date; past ones that were never confirmed
+            // are left to the unpaid calculator
+            if tx.is_planned() && tx.date < today {
+                trace!(
+                    "Skipping unconfirmed planned transaction: date={}, amount={}",
+                    tx.date, tx.amount
+                );
+                continue;
+            }
             let amount = if tx.target_account_id == account.id {
                 tx.amount
             } else if Some(account.id) == tx.source_account_id {
@@ -307,13 +323,7 @@ async fn compute_balance(
             account.id
         );
         for (date, tx) in recurring_transactions {
-            let amount = if tx.target_account_id == account.id {
-                tx.amount
-            } else if Some(account.id) == tx.source_account_id {
-                -tx.amount
-            } else {
-                Decimal::ZERO
-            };
+            let amount = recurring_amount_for_account(db, &tx, account.id, date).await?;
 
             trace!(
                 "Adding recurring transaction: date={}, amount={}",
@@ -473,6 +483,21 @@ async fn compute_balance(
                 date = date.succ_opt().unwrap();
             }
         }
+
+        if account.balance_interpolation == account::BalanceInterpolation::Linear {
+            debug!("Interpolating balances between manual states for account {}", account.id);
+            let mut states: Vec<_> = all_manual_states.iter().map(|state| (state.date, state.amount)).collect();
+            states.sort_by_key(|(date, _)| *date);
+            let transaction_dates: Vec<_> = all_transactions.iter().map(|(date, _)| *date).collect();
+            interpolate_between_states(
+                &mut balance_data,
+                account.id,
+                &states,
+                &transaction_dates,
+                start_date,
+                end_date,
+            );
+        }
     }
 
     // Convert the HashMap to a DataFrame
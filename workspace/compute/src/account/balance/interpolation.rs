@@ -0,0 +1,104 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use tracing::trace;
+
+/// Replaces the flat steps between consecutive manual states with straight lines.
+///
+/// `states` are the `(date, amount)` manual states of the account ordered by date.
+/// Only gaps without any of `transaction_dates` strictly between two states are
+/// interpolated; where money was booked the computed balance already shows how it
+/// moved. Balances outside `start_date..=end_date` are left untouched.
+pub fn interpolate_between_states(
+    balances: &mut HashMap<(i32, NaiveDate), Decimal>,
+    account_id: i32,
+    states: &[(NaiveDate, Decimal)],
+    transaction_dates: &[NaiveDate],
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) {
+    for pair in states.windows(2) {
+        let (from, from_amount) = pair[0];
+        let (to, to_amount) = pair[1];
+        let span = (to - from).num_days();
+        if span < 2 || to <= start_date || from >= end_date {
+            continue;
+        }
+        if transaction_dates.iter().any(|date| *date > from && *date < to) {
+            trace!(
+                "Keeping steps for account {} between {} and {}: transactions were booked",
+                account_id, from, to
+            );
+            continue;
+        }
+
+        let mut date = std::cmp::max(from.succ_opt().unwrap(), start_date);
+        while date < to && date <= end_date {
+            let elapsed = Decimal::from((date - from).num_days());
+            let balance = from_amount + (to_amount - from_amount) * elapsed / Decimal::from(span);
+            balances.insert((account_id, date), balance.round_dp(2));
+            date = date.succ_opt().unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, day).unwrap()
+    }
+
+    fn flat(start: u32, end: u32, amount: i64) -> HashMap<(i32, NaiveDate), Decimal> {
+        (start..=end).map(|day| ((1, date(day)), Decimal::new(amount, 0))).collect()
+    }
+
+    #[test]
+    fn test_interpolates_between_states() {
+        let mut balances = flat(1, 11, 100);
+        let states = [(date(1), Decimal::new(100, 0)), (date(11), Decimal::new(200, 0))];
+        balances.insert((1, date(11)), Decimal::new(200, 0));
+
+        interpolate_between_states(&mut balances, 1, &states, &[], date(1), date(11));
+
+        assert_eq!(balances[&(1, date(1))], Decimal::new(100, 0));
+        assert_eq!(balances[&(1, date(2))], Decimal::new(110, 0));
+        assert_eq!(balances[&(1, date(6))], Decimal::new(150, 0));
+        assert_eq!(balances[&(1, date(10))], Decimal::new(190, 0));
+        assert_eq!(balances[&(1, date(11))], Decimal::new(200, 0));
+    }
+
+    #[test]
+    fn test_keeps_steps_where_transactions_were_booked() {
+        let mut balances = flat(1, 11, 100);
+        let states = [(date(1), Decimal::new(100, 0)), (date(11), Decimal::new(200, 0))];
+
+        interpolate_between_states(&mut balances, 1, &states, &[date(5)], date(1), date(11));
+
+        assert_eq!(balances[&(1, date(6))], Decimal::new(100, 0));
+    }
+
+    #[test]
+    fn test_only_touches_requested_range() {
+        let mut balances = flat(4, 6, 100);
+        let states = [(date(1), Decimal::new(0, 0)), (date(11), Decimal::new(100, 0))];
+
+        interpolate_between_states(&mut balances, 1, &states, &[], date(4), date(6));
+
+        assert_eq!(balances.len(), 3);
+        assert_eq!(balances[&(1, date(4))], Decimal::new(30, 0));
+        assert_eq!(balances[&(1, date(6))], Decimal::new(50, 0));
+    }
+
+    #[test]
+    fn test_rounds_to_cents() {
+        let mut balances = HashMap::new();
+        let states = [(date(1), Decimal::new(0, 0)), (date(4), Decimal::new(100, 0))];
+
+        interpolate_between_states(&mut balances, 1, &states, &[], date(1), date(4));
+
+        assert_eq!(balances[&(1, date(2))], Decimal::new(3333, 2));
+        assert_eq!(balances[&(1, date(3))], Decimal::new(6667, 2));
+    }
+}
@@ -5,8 +5,10 @@ use model::entities::{recurring_income, recurring_transaction_instance};
 use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter};
 use tracing::{debug, instrument, trace};
 
-use crate::account::utils::generate_occurrences;
+use crate::account::balance::scenario::{is_active_on, scenario_activation_date};
+use crate::account::utils::{generate_occurrences, load_holiday_calendar};
 use crate::error::Result;
+use crate::holidays::HolidayCalendar;
 
 use super::common::process_occurrences;
 
@@ -21,6 +23,7 @@ use super::common::process_occurrences;
 /// # Scenario Context
 /// - `None`: Fetch only real income (is_simulated = false)
 /// - `Some(id)`: Fetch real income OR simulated income belonging to the scenario
+///   (simulated occurrences before the scenario's activation date are dropped)
 #[instrument(skip(db), fields(account_id = account_id, start_date = %start_date, end_date = %end_date, today = %today, scenario_context = ?scenario_context
 ))]
 pub async fn get_recurring_income(
@@ -52,6 +55,8 @@ pub async fn get_recurring_income(
     let income_ids: Vec<i32> = incomes.iter().map(|i| i.id).collect();
     let instances_map = fetch_income_instances_batch(db, &income_ids).await?;
 
+    let activation_date = scenario_activation_date(db, scenario_context).await?;
+    let holidays = load_holiday_calendar(db).await?;
     let mut result = Vec::new();
 
     for income in &incomes {
@@ -62,9 +67,13 @@ pub async fn get_recurring_income(
 
         let instances = instances_map.get(&income.id).map(|v| v.as_slice()).unwrap_or(&[]);
         let valid_dates =
-            process_income_occurrences(income, instances, start_date, end_date, today);
+            process_income_occurrences(income, instances, &holidays, start_date, end_date, today);
 
         for date in valid_dates {
+            if income.is_simulated && !is_active_on(activation_date, date) {
+                trace!("Skipping simulated occurrence on {} before scenario activation", date);
+                continue;
+            }
             result.push((date, income.clone()));
         }
     }
@@ -151,6 +160,7 @@ async fn fetch_income_instances_batch(
 fn process_income_occurrences(
     income: &recurring_income::Model,
     instances: &[recurring_transaction_instance::Model],
+    holidays: &HolidayCalendar,
     start_date: NaiveDate,
     end_date: NaiveDate,
     today: NaiveDate,
@@ -159,6 +169,7 @@ fn process_income_occurrences(
         income.start_date,
         income.end_date,
         &income.period,
+        holidays,
         start_date,
         end_date,
     );
@@ -5,8 +5,11 @@ use model::entities::{recurring_transaction, recurring_transaction_instance};
 use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter};
 use tracing::{debug, instrument, trace};
 
-use crate::account::utils::generate_occurrences;
+use crate::account::balance::scenario::{is_active_on, scenario_activation_date};
+use crate::account::utils::{load_holiday_calendar, recurring_transaction_occurrences};
 use crate::error::Result;
+use crate::holidays::HolidayCalendar;
+use crate::recurring_amounts::with_estimated_amount;
 
 use super::common::process_occurrences;
 
@@ -21,6 +24,7 @@ use super::common::process_occurrences;
 /// # Scenario Context
 /// - `None`: Fetch only real transactions (is_simulated = false)
 /// - `Some(id)`: Fetch real transactions OR simulated transactions belonging to the scenario
+///   (simulated occurrences before the scenario's activation date are dropped)
 #[instrument(skip(db), fields(account_id = account_id, start_date = %start_date, end_date = %end_date, today = %today, scenario_context = ?scenario_context
 ))]
 pub async fn get_recurring_transactions(
@@ -52,6 +56,8 @@ pub async fn get_recurring_transactions(
     let tx_ids: Vec<i32> = transactions.iter().map(|tx| tx.id).collect();
     let instances_map = fetch_transaction_instances_batch(db, &tx_ids).await?;
 
+    let activation_date = scenario_activation_date(db, scenario_context).await?;
+    let holidays = load_holiday_calendar(db).await?;
     let mut result = Vec::new();
 
     for tx in &transactions {
@@ -62,10 +68,20 @@ pub async fn get_recurring_transactions(
 
         let instances = instances_map.get(&tx.id).map(|v| v.as_slice()).unwrap_or(&[]);
         let valid_dates =
-            process_transaction_occurrences(tx, instances, start_date, end_date, today);
+            process_transaction_occurrences(tx, instances, &holidays, start_date, end_date, today);
+        // Occurrences still to come are valued from the payment history
+        let upcoming = with_estimated_amount(tx, instances);
 
         for date in valid_dates {
-            result.push((date, tx.clone()));
+            if tx.is_simulated && !is_active_on(activation_date, date) {
+                trace!("Skipping simulated occurrence on {} before scenario activation", date);
+                continue;
+            }
+            if date >= today {
+                result.push((date, upcoming.clone()));
+            } else {
+                result.push((date, tx.clone()));
+            }
         }
     }
 
@@ -96,7 +112,9 @@ async fn fetch_recurring_transactions(
                 .add(recurring_transaction::Column::EndDate.is_null())
                 .add(recurring_transaction::Column::EndDate.gte(start_date)),
         )
-        .filter(recurring_transaction::Column::StartDate.lte(end_date));
+        .filter(recurring_transaction::Column::StartDate.lte(end_date))
+        // Rules still awaiting approval (or rejected) must not move the balance
+        .filter(recurring_transaction::Column::ApprovalStatus.eq(recurring_transaction::ApprovalStatus::Approved));
 
     // Apply scenario filtering
     query = match scenario_context {
@@ -155,12 +173,12 @@ async fn fetch_transaction_instances_batch(
 fn process_transaction_occurrences(
     tx: &recurring_transaction::Model,
     instances: &[recurring_transaction_instance::Model],
+    holidays: &HolidayCalendar,
     start_date: NaiveDate,
     end_date: NaiveDate,
     today: NaiveDate,
 ) -> Vec<NaiveDate> {
-    let occurrences =
-        generate_occurrences(tx.start_date, tx.end_date, &tx.period, start_date, end_date);
+    let occurrences = recurring_transaction_occurrences(tx, holidays, start_date, end_date);
 
     debug!(
         "Generated {} occurrences for recurring transaction id={}",
@@ -0,0 +1,22 @@
+use chrono::NaiveDate;
+use model::entities::scenario;
+use sea_orm::{DatabaseConnection, EntityTrait};
+
+use crate::error::Result;
+
+/// Returns the date from which the scenario's simulated transactions apply, if it is scheduled.
+pub async fn scenario_activation_date(
+    db: &DatabaseConnection,
+    scenario_context: Option<i32>,
+) -> Result<Option<NaiveDate>> {
+    let Some(scenario_id) = scenario_context else {
+        return Ok(None);
+    };
+    let scenario = scenario::Entity::find_by_id(scenario_id).one(db).await?;
+    Ok(scenario.and_then(|s| s.activation_date))
+}
+
+/// Whether a simulated item dated `date` is in effect given the scenario's activation date.
+pub fn is_active_on(activation_date: Option<NaiveDate>, date: NaiveDate) -> bool {
+    activation_date.is_none_or(|activation| date >= activation)
+}
@@ -1,8 +1,9 @@
-use chrono::NaiveDate;
+use chrono::{Duration, NaiveDate};
 use model::entities::{imported_transaction, one_off_transaction};
 use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter};
 use tracing::{debug, instrument, trace};
 
+use crate::account::balance::scenario::scenario_activation_date;
 use crate::error::Result;
 
 /// Gets all one-off transactions for the account within the given date range.
@@ -10,6 +11,7 @@ use crate::error::Result;
 /// # Scenario Context
 /// - `None`: Fetch only real transactions (is_simulated = false)
 /// - `Some(id)`: Fetch real transactions OR simulated transactions belonging to the scenario
+///   dated on or after the scenario's activation date
 #[instrument(skip(db), fields(account_id = account_id, start_date = %start_date, end_date = %end_date, scenario_context = ?scenario_context
 ))]
 pub async fn get_transactions_for_account(
@@ -43,15 +45,17 @@ pub async fn get_transactions_for_account(
             query.filter(one_off_transaction::Column::IsSimulated.eq(false))
         }
         Some(scenario_id) => {
-            // Scenario mode: real OR (simulated AND belongs to this scenario)
+            // Scenario mode: real OR (simulated AND belongs to this scenario AND on/after its activation)
+            let mut simulated = Condition::all()
+                .add(one_off_transaction::Column::IsSimulated.eq(true))
+                .add(one_off_transaction::Column::ScenarioId.eq(scenario_id));
+            if let Some(activation_date) = scenario_activation_date(db, scenario_context).await? {
+                simulated = simulated.add(one_off_transaction::Column::Date.gte(activation_date));
+            }
             query.filter(
                 Condition::any()
                     .add(one_off_transaction::Column::IsSimulated.eq(false))
-                    .add(
-                        Condition::all()
-                            .add(one_off_transaction::Column::IsSimulated.eq(true))
-                            .add(one_off_transaction::Column::ScenarioId.eq(scenario_id)),
-                    ),
+                    .add(simulated),
             )
         }
     };
@@ -76,6 +80,44 @@ pub async fn get_transactions_for_account(
     Ok(transactions)
 }
 
+/// Fetches planned one-off transactions of the account dated between `start_date` and
+/// `today` that were never confirmed.
+///
+/// Like past-due recurring transactions they are still expected, so their date is moved
+/// to `today + future_offset`.
+#[instrument(skip(db), fields(account_id = account_id, start_date = %start_date, today = %today, future_offset = %future_offset.num_days()
+))]
+pub async fn get_past_due_planned_transactions(
+    db: &DatabaseConnection,
+    account_id: i32,
+    start_date: NaiveDate,
+    today: NaiveDate,
+    future_offset: Duration,
+) -> Result<Vec<(NaiveDate, one_off_transaction::Model)>> {
+    let transactions = one_off_transaction::Entity::find()
+        .filter(
+            Condition::any()
+                .add(one_off_transaction::Column::TargetAccountId.eq(account_id))
+                .add(one_off_transaction::Column::SourceAccountId.eq(account_id)),
+        )
+        .filter(one_off_transaction::Column::Status.eq(one_off_transaction::OneOffStatus::Planned))
+        .filter(one_off_transaction::Column::IsSimulated.eq(false))
+        .filter(one_off_transaction::Column::Date.gte(start_date))
+        .filter(one_off_transaction::Column::Date.lt(today))
+        .all(db)
+        .await?;
+
+    debug!(
+        "Found {} past-due planned transactions for account_id={} before {}",
+        transactions.len(),
+        account_id,
+        today
+    );
+
+    let new_date = today + future_offset;
+    Ok(transactions.into_iter().map(|tx| (new_date, tx)).collect())
+}
+
 /// Gets all imported transactions for the account within the given date range.
 #[instrument(skip(db), fields(account_id = account_id, start_date = %start_date, end_date = %end_date
 ))]
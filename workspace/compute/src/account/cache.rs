@@ -28,6 +28,7 @@ impl ComputeStateKey {
             account.name.hash(&mut hasher);
             account.currency_code.hash(&mut hasher);
             account.owner_id.hash(&mut hasher);
+            account.balance_interpolation.hash(&mut hasher);
         }
         let accounts_hash = hasher.finish();
 
@@ -253,6 +254,8 @@ mod tests {
             target_amount: None,
             color: None,
             is_liquid: true,
+            recurring_approval_threshold: None,
+            balance_interpolation: account::BalanceInterpolation::Step,
         };
         let account2 = account::Model {
             id: 2,
@@ -266,6 +269,8 @@ mod tests {
             target_amount: None,
             color: None,
             is_liquid: true,
+            recurring_approval_threshold: None,
+            balance_interpolation: account::BalanceInterpolation::Step,
         };
 
         let date1 = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
@@ -12,7 +12,7 @@ use tracing::{debug, info, instrument, trace};
 use super::{AccountStateCalculator, MergeMethod};
 use crate::error::Result;
 
-use self::recurring::{get_balance_sheet_transactions, get_recurring_income};
+use self::recurring::{get_balance_sheet_transactions, get_recurring_income, recurring_amount_for_account};
 
 /// A calculator that computes account forecasts based on recurring transactions and income.
 pub struct ForecastCalculator {
@@ -109,10 +109,10 @@ impl AccountStateCalculator for ForecastCalculator {
         start_date: NaiveDate,
         end_date: NaiveDate,
     ) -> Result<DataFrame> {
-        // Use the provided today date or default to the current date
+        // Use the provided today date or default to the current UTC date
         let today = self
             .today
-            .unwrap_or_else(|| chrono::Local::now().date_naive());
+            .unwrap_or_else(|| crate::today_in(&chrono::Utc));
 
         compute_forecast(
             db,
@@ -226,13 +226,7 @@ async fn compute_forecast(
             account.id
         );
         for (date, tx) in recurring_transactions {
-            let amount = if tx.target_account_id == account.id {
-                tx.amount
-            } else if Some(account.id) == tx.source_account_id {
-                -tx.amount
-            } else {
-                Decimal::ZERO
-            };
+            let amount = recurring_amount_for_account(db, &tx, account.id, date).await?;
 
             trace!(
                 "Adding recurring transaction: date={}, amount={}",
@@ -1,11 +1,13 @@
 use chrono::{Duration, NaiveDate};
 use model::entities::{recurring_income, recurring_transaction, recurring_transaction_instance};
+use rust_decimal::Decimal;
 use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter};
-use std::collections::HashSet;
-use tracing::{debug, instrument, trace};
+use std::collections::{HashMap, HashSet};
+use tracing::{debug, instrument, trace, warn};
 
-use crate::account::utils::generate_occurrences;
+use crate::account::utils::{generate_occurrences, load_holiday_calendar, recurring_transaction_occurrences};
 use crate::error::Result;
+use crate::recurring_amounts::with_estimated_amount;
 
 // ---------------------------------------------------------------------------------
 // FUNCTION FOR `compute_balance` (Calculator 0)
@@ -30,24 +32,23 @@ pub async fn get_balance_sheet_transactions(
                 .add(recurring_transaction::Column::TargetAccountId.eq(account_id))
                 .add(recurring_transaction::Column::SourceAccountId.eq(account_id)),
         )
+        .filter(recurring_transaction::Column::ApprovalStatus.eq(recurring_transaction::ApprovalStatus::Approved))
         .all(db)
         .await?;
 
     let mut result = Vec::new();
-    let instance_map: HashSet<(i32, NaiveDate)> = recurring_transaction_instance::Entity::find()
-        .filter(
-            recurring_transaction_instance::Column::Status
-                .eq(recurring_transaction_instance::InstanceStatus::Paid),
-        )
-        .all(db)
-        .await?
-        .into_iter()
+    let holidays = load_holiday_calendar(db).await?;
+    let paid_instances = paid_instances_by_transaction(db).await?;
+    let instance_map: HashSet<(i32, NaiveDate)> = paid_instances
+        .values()
+        .flatten()
         .map(|i| (i.recurring_transaction_id, i.due_date))
         .collect();
 
     for tx in &transactions {
-        let occurrences =
-            generate_occurrences(tx.start_date, tx.end_date, &tx.period, start_date, end_date);
+        let occurrences = recurring_transaction_occurrences(tx, &holidays, start_date, end_date);
+        let paid = paid_instances.get(&tx.id).map(|v| v.as_slice()).unwrap_or(&[]);
+        let upcoming = with_estimated_amount(tx, paid);
 
         for date in occurrences {
             if date < today {
@@ -55,7 +56,7 @@ pub async fn get_balance_sheet_transactions(
                     result.push((date, tx.clone()));
                 }
             } else {
-                result.push((date, tx.clone()));
+                result.push((date, upcoming.clone()));
             }
         }
     }
@@ -89,21 +90,21 @@ pub async fn get_past_due_transactions(
                 .add(recurring_transaction::Column::SourceAccountId.eq(account_id)),
         )
         .filter(recurring_transaction::Column::StartDate.lt(today))
+        .filter(recurring_transaction::Column::ApprovalStatus.eq(recurring_transaction::ApprovalStatus::Approved))
         .all(db)
         .await?;
 
     let mut result = Vec::new();
+    let holidays = load_holiday_calendar(db).await?;
     // Only load Paid instances
     // Pending and Skipped instances are NOT considered paid, so they should show up as unpaid
-    let instances = recurring_transaction_instance::Entity::find()
-        .filter(recurring_transaction_instance::Column::Status.eq(recurring_transaction_instance::InstanceStatus::Paid))
-        .all(db)
-        .await?;
+    let paid_instances = paid_instances_by_transaction(db).await?;
 
-    debug!("Loaded {} paid instances for past-due check", instances.len());
+    debug!("Loaded paid instances of {} recurring transactions for past-due check", paid_instances.len());
 
-    let instance_map: HashSet<(i32, NaiveDate)> = instances
-        .into_iter()
+    let instance_map: HashSet<(i32, NaiveDate)> = paid_instances
+        .values()
+        .flatten()
         .map(|i| (i.recurring_transaction_id, i.due_date))
         .collect();
 
@@ -114,8 +115,7 @@ pub async fn get_past_due_transactions(
         // Only look at past occurrences (before today)
         let occurrence_end = today.pred_opt().unwrap_or(today);
 
-        let occurrences =
-            generate_occurrences(tx.start_date, tx.end_date, &tx.period, occurrence_start, occurrence_end);
+        let occurrences = recurring_transaction_occurrences(tx, &holidays, occurrence_start, occurrence_end);
 
         // Collect all unpaid occurrences
         let unpaid_dates: Vec<NaiveDate> = occurrences
@@ -130,13 +130,29 @@ pub async fn get_past_due_transactions(
                 "Found {} unpaid occurrences for tx id={}, using most recent: {} -> {}",
                 unpaid_dates.len(), tx.id, most_recent, new_date
             );
-            result.push((new_date, tx.clone()));
+            let paid = paid_instances.get(&tx.id).map(|v| v.as_slice()).unwrap_or(&[]);
+            result.push((new_date, with_estimated_amount(tx, paid)));
         }
     }
 
     Ok(result)
 }
 
+/// All paid recurring transaction instances, grouped by their recurring transaction.
+async fn paid_instances_by_transaction(
+    db: &DatabaseConnection,
+) -> Result<HashMap<i32, Vec<recurring_transaction_instance::Model>>> {
+    let mut map: HashMap<i32, Vec<recurring_transaction_instance::Model>> = HashMap::new();
+    for instance in recurring_transaction_instance::Entity::find()
+        .filter(recurring_transaction_instance::Column::Status.eq(recurring_transaction_instance::InstanceStatus::Paid))
+        .all(db)
+        .await?
+    {
+        map.entry(instance.recurring_transaction_id).or_default().push(instance);
+    }
+    Ok(map)
+}
+
 /// Gets all future recurring income for the account within the given date range.
 /// As per your requirement, income cannot be past-due, so this function only
 /// finds occurrences on or after `today`.
@@ -172,12 +188,14 @@ pub async fn get_recurring_income(
     );
 
     let mut result = Vec::new();
+    let holidays = load_holiday_calendar(db).await?;
 
     for income in &incomes {
         let occurrences = generate_occurrences(
             income.start_date,
             income.end_date,
             &income.period,
+            &holidays,
             start_date,
             end_date,
         );
@@ -201,3 +219,33 @@ pub async fn get_recurring_income(
     );
     Ok(result)
 }
+
+/// Change a recurring transaction occurring on `date` makes to the balance of `account_id`.
+///
+/// The source account of a transfer between currencies is debited the amount converted
+/// according to the transfer's FX handling. When the rate of the execution date isn't
+/// known yet the source leg is left out.
+pub async fn recurring_amount_for_account(
+    db: &DatabaseConnection,
+    tx: &recurring_transaction::Model,
+    account_id: i32,
+    date: NaiveDate,
+) -> Result<Decimal> {
+    if tx.target_account_id == account_id {
+        return Ok(tx.amount);
+    }
+    if tx.source_account_id != Some(account_id) {
+        return Ok(Decimal::ZERO);
+    }
+
+    match tx.source_leg_amount_on(db, tx.amount, date).await? {
+        Some(source_amount) => Ok(-source_amount),
+        None => {
+            warn!(
+                "No exchange rate for recurring transaction {} on {}, leaving out its source leg",
+                tx.id, date
+            );
+            Ok(Decimal::ZERO)
+        }
+    }
+}
@@ -1,8 +1,10 @@
-use std::sync::atomic::AtomicU64;
-
 use chrono::{Datelike, NaiveDate};
 use rust_decimal::Decimal;
-use sea_orm::{ActiveModelTrait, DatabaseConnection, DbErr, Set};
+use sea_orm::{DatabaseConnection, DbErr};
+use test_factories::{
+    AccountFactory, ManualAccountStateFactory, OneOffTransactionFactory, RecurringInstanceFactory,
+    RecurringTransactionFactory,
+};
 
 use model::entities::{
     account, manual_account_state, one_off_transaction, recurring_transaction,
@@ -12,31 +14,11 @@ use model::entities::{
 pub type Result<T> = std::result::Result<T, DbErr>;
 
 pub async fn new_account(db: &DatabaseConnection) -> Result<account::Model> {
-    static ACCOUNT_ID: AtomicU64 = AtomicU64::new(0);
-
-    let current_id = ACCOUNT_ID.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-    // Create a test user first
-    let user = model::entities::user::ActiveModel {
-        username: Set(format!("user_{}", current_id)),
-        ..Default::default()
-    }
-    .insert(db)
-    .await?;
-
-    // Create a test account
-    let account = account::ActiveModel {
-        name: Set(format!("Test account {}", current_id)),
-        description: Set(Some("Account for balance testing".to_string())),
-        currency_code: Set("USD".to_string()),
-        owner_id: Set(user.id),
-        include_in_statistics: Set(true),
-        ledger_name: Set(None),
-        ..Default::default()
-    }
-    .insert(db)
-    .await?;
-
-    Ok(account)
+    // Every account gets its own owner
+    AccountFactory::new()
+        .description("Account for balance testing")
+        .create(db)
+        .await
 }
 
 pub async fn new_manual_account_state(
@@ -45,14 +27,9 @@ pub async fn new_manual_account_state(
     date: NaiveDate,
     amount: i64,
 ) -> Result<manual_account_state::Model> {
-    manual_account_state::ActiveModel {
-        account_id: Set(account.id),
-        date: Set(date),
-        amount: Set(Decimal::new(amount * 100, 2)),
-        ..Default::default()
-    }
-    .insert(db)
-    .await
+    ManualAccountStateFactory::new(account, date, Decimal::new(amount * 100, 2))
+        .create(db)
+        .await
 }
 
 pub async fn new_recurring_transaction(
@@ -61,21 +38,13 @@ pub async fn new_recurring_transaction(
     date: NaiveDate,
     amount: i64,
 ) -> Result<recurring_transaction::Model> {
-    recurring_transaction::ActiveModel {
-        name: Set("Monthly".to_string()),
-        description: Set(Some("Monthly payment".to_string())),
-        amount: Set(Decimal::new(amount * 100, 2)), // -$500.00
-        start_date: Set(date),
-        end_date: Set(None), // Indefinite
-        period: Set(recurring_transaction::RecurrencePeriod::Monthly),
-        include_in_statistics: Set(true),
-        target_account_id: Set(account.id),
-        source_account_id: Set(None),
-        ledger_name: Set(None),
-        ..Default::default()
-    }
-    .insert(db)
-    .await
+    RecurringTransactionFactory::new(account)
+        .name("Monthly")
+        .description("Monthly payment")
+        .amount(Decimal::new(amount * 100, 2))
+        .start_date(date)
+        .create(db)
+        .await
 }
 
 pub async fn new_recurring_instance(
@@ -83,23 +52,16 @@ pub async fn new_recurring_instance(
     transaction: &recurring_transaction::Model,
     date: NaiveDate,
 ) -> Result<recurring_transaction_instance::Model> {
-    recurring_transaction_instance::ActiveModel {
-        recurring_transaction_id: Set(transaction.id),
-        status: Set(recurring_transaction_instance::InstanceStatus::Paid),
-        due_date: Set(transaction
-            .start_date
-            .with_month(date.month())
-            .unwrap()
-            .with_year(date.year())
-            .unwrap()),
-        expected_amount: Set(transaction.amount),
-        paid_date: Set(Some(date)),
-        paid_amount: Set(Some(transaction.amount)),
-        reconciled_imported_transaction_id: Set(None),
-        ..Default::default()
-    }
-    .insert(db)
-    .await
+    let due_date = transaction
+        .start_date
+        .with_month(date.month())
+        .unwrap()
+        .with_year(date.year())
+        .unwrap();
+    RecurringInstanceFactory::new(transaction, due_date)
+        .paid_on(date, transaction.amount)
+        .create(db)
+        .await
 }
 
 pub async fn new_one_off_trsansaction(
@@ -108,20 +70,13 @@ pub async fn new_one_off_trsansaction(
     date: NaiveDate,
     amount: i64,
 ) -> Result<one_off_transaction::Model> {
-    one_off_transaction::ActiveModel {
-        name: Set("One-off".to_string()),
-        description: Set(Some("One-off".to_string())),
-        amount: Set(Decimal::new(amount * 100, 2)),
-        date: Set(date),
-        include_in_statistics: Set(true),
-        target_account_id: Set(account.id),
-        source_account_id: Set(None),
-        ledger_name: Set(None),
-        linked_import_id: Set(None),
-        ..Default::default()
-    }
-    .insert(db)
-    .await
+    OneOffTransactionFactory::new(account)
+        .name("One-off")
+        .description("One-off")
+        .amount(Decimal::new(amount * 100, 2))
+        .date(date)
+        .create(db)
+        .await
 }
 
 pub async fn new_one_off_account_transfer(
@@ -131,18 +86,12 @@ pub async fn new_one_off_account_transfer(
     date: NaiveDate,
     amount: i64,
 ) -> Result<one_off_transaction::Model> {
-    one_off_transaction::ActiveModel {
-        name: Set("One-off transfer".to_string()),
-        description: Set(Some("One-off transfer".to_string())),
-        amount: Set(Decimal::new(amount * 100, 2)),
-        date: Set(date),
-        include_in_statistics: Set(true),
-        target_account_id: Set(target_account.id),
-        source_account_id: Set(Some(source_account.id)),
-        ledger_name: Set(None),
-        linked_import_id: Set(None),
-        ..Default::default()
-    }
-    .insert(db)
-    .await
+    OneOffTransactionFactory::new(target_account)
+        .name("One-off transfer")
+        .description("One-off transfer")
+        .amount(Decimal::new(amount * 100, 2))
+        .date(date)
+        .source_account(source_account)
+        .create(db)
+        .await
 }
@@ -359,3 +359,69 @@ async fn test_scenario_application() -> Result<()> {
 
     Ok(())
 }
+
+/// Test Case 3: "Scheduled Scenario"
+///
+/// Setup: Scenario activates on Jan 10 and has ghost transactions on Jan 5 (-$300) and Jan 15 (-$200).
+/// Assert: ScenarioCalculator sees only the Jan 15 ghost: $1000 - $200 = $800.
+#[tokio::test]
+async fn test_scenario_activation_date() -> Result<()> {
+    let db = setup_db().await?;
+
+    let user = create_test_user(&db, "test_user").await?;
+    let account = create_test_account(&db, "Checking", user.id).await?;
+
+    let start_date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    create_manual_state(&db, account.id, start_date, Decimal::new(100000, 2)).await?;
+
+    let scenario = create_scenario(&db, "New Job", Some("Starts on Jan 10")).await?;
+    let mut active_scenario: scenario::ActiveModel = scenario.clone().into();
+    active_scenario.activation_date = Set(Some(NaiveDate::from_ymd_opt(2025, 1, 10).unwrap()));
+    active_scenario.update(&db).await?;
+
+    create_transaction(
+        &db,
+        "Before Activation",
+        account.id,
+        Decimal::new(-30000, 2),
+        NaiveDate::from_ymd_opt(2025, 1, 5).unwrap(),
+        Some(scenario.id),
+        true,
+    )
+    .await?;
+    create_transaction(
+        &db,
+        "After Activation",
+        account.id,
+        Decimal::new(-20000, 2),
+        NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+        Some(scenario.id),
+        true,
+    )
+    .await?;
+
+    let end_date = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+    let today = NaiveDate::from_ymd_opt(2025, 1, 20).unwrap();
+
+    let scenario_calc =
+        BalanceCalculator::new_with_today_and_scenario(MergeMethod::FirstWins, today, scenario.id);
+    let mut scenario_result = scenario_calc
+        .compute_account_state(&db, &[account.clone()], start_date, end_date)
+        .await?;
+    scenario_result
+        .sort_in_place(vec!["date"], polars::prelude::SortMultipleOptions::new())
+        .unwrap();
+    let scenario_balance = scenario_result
+        .column("balance")?
+        .str()?
+        .get(scenario_result.height() - 1)
+        .unwrap()
+        .parse::<f64>()
+        .unwrap();
+    assert_eq!(
+        scenario_balance, 800.0,
+        "Only ghost transactions on or after the activation date should apply"
+    );
+
+    Ok(())
+}
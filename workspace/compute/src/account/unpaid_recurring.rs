@@ -10,12 +10,14 @@ use super::{AccountStateCalculator, MergeMethod};
 use crate::error::Result;
 
 // Correctly import the renamed functions
-use super::forecast::recurring::{get_past_due_transactions, get_recurring_income};
+use super::forecast::recurring::{get_past_due_transactions, get_recurring_income, recurring_amount_for_account};
+use super::balance::transactions::get_past_due_planned_transactions;
 
 /// A calculator that computes future non-paid recurring transactions and income.
 ///
 /// This calculator is responsible for projecting recurring transactions and income
-/// that have not yet been paid (no instance created) into the future.
+/// that have not yet been paid (no instance created), and planned one-off transactions
+/// that were not confirmed, into the future.
 pub struct UnpaidRecurringCalculator {
     /// The merge method to use when combining results from multiple calculators.
     merge_method: MergeMethod,
@@ -48,7 +50,7 @@ impl UnpaidRecurringCalculator {
     pub fn default() -> Self {
         Self {
             merge_method: MergeMethod::Sum,
-            today: chrono::Local::now().date_naive(),
+            today: crate::today_in(&chrono::Utc),
             future_offset: Duration::days(7),
         }
     }
@@ -116,6 +118,9 @@ async fn compute_unpaid_recurring(
         let recurring_income =
             get_recurring_income(db, account.id, start_date, today, today, future_offset).await?;
 
+        let planned_transactions =
+            get_past_due_planned_transactions(db, account.id, start_date, today, future_offset).await?;
+
         debug!(
             "Found {} past-due transactions and {} recurring income for account {}",
             recurring_transactions.len(),
@@ -124,13 +129,14 @@ async fn compute_unpaid_recurring(
         );
 
         for (date, tx) in recurring_transactions {
-            let amount = if tx.target_account_id == account.id {
-                tx.amount
-            } else if Some(account.id) == tx.source_account_id {
-                -tx.amount
-            } else {
-                Decimal::ZERO
-            };
+            let amount = recurring_amount_for_account(db, &tx, account.id, date).await?;
+            if !amount.is_zero() {
+                all_deltas.push((account.id, date, amount));
+            }
+        }
+
+        for (date, tx) in planned_transactions {
+            let amount = if tx.target_account_id == account.id { tx.amount } else { -tx.amount };
             if !amount.is_zero() {
                 all_deltas.push((account.id, date, amount));
             }
@@ -1,16 +1,98 @@
-use chrono::{Datelike, Duration, NaiveDate};
-use model::entities::recurring_transaction;
-use tracing::{debug, instrument, trace};
+use chrono::{Duration, NaiveDate};
+use model::entities::recurring_transaction::{self, DateAdjustment, RecurrencePeriod};
+use model::entities::{account, holiday, holiday_calendar, imported_transaction, one_off_transaction, recurring_income};
+use sea_orm::{ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use tracing::{debug, instrument, warn};
 
-use super::days_in_month;
+use crate::holidays::{HolidayCalendar, HolidayCountry};
+use crate::recurrence;
+use crate::recurrence_rule::RecurrenceRule;
+
+/// Loads the enabled country calendars and the custom holidays.
+///
+/// Calendars of countries that are no longer built in are ignored.
+pub async fn load_holiday_calendar(db: &impl ConnectionTrait) -> Result<HolidayCalendar, DbErr> {
+    let countries = holiday_calendar::Entity::find()
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|calendar| match calendar.country_code.parse::<HolidayCountry>() {
+            Ok(country) => Some(country),
+            Err(e) => {
+                warn!("Ignoring holiday calendar {}: {}", calendar.id, e);
+                None
+            }
+        });
+    let custom = holiday::Entity::find()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|holiday| (holiday.date, holiday.name));
+    Ok(HolidayCalendar::new(countries, custom))
+}
+
+/// Date to compute balances from so that they include everything booked on `accounts`
+/// before `start`.
+///
+/// Accounts without a manual state start at zero on the first day computed, so balances
+/// at `start` need the computation to begin at their earliest transaction or recurrence.
+pub async fn history_start(
+    db: &impl ConnectionTrait,
+    accounts: &[account::Model],
+    start: NaiveDate,
+) -> Result<NaiveDate, DbErr> {
+    let ids: Vec<i32> = accounts.iter().map(|account| account.id).collect();
+    if ids.is_empty() {
+        return Ok(start);
+    }
+
+    let one_off = one_off_transaction::Entity::find()
+        .filter(
+            Condition::any()
+                .add(one_off_transaction::Column::TargetAccountId.is_in(ids.clone()))
+                .add(one_off_transaction::Column::SourceAccountId.is_in(ids.clone())),
+        )
+        .order_by_asc(one_off_transaction::Column::Date)
+        .one(db)
+        .await?
+        .map(|transaction| transaction.date);
+    let imported = imported_transaction::Entity::find()
+        .filter(imported_transaction::Column::AccountId.is_in(ids.clone()))
+        .order_by_asc(imported_transaction::Column::Date)
+        .one(db)
+        .await?
+        .map(|transaction| transaction.date);
+    let recurring = recurring_transaction::Entity::find()
+        .filter(
+            Condition::any()
+                .add(recurring_transaction::Column::TargetAccountId.is_in(ids.clone()))
+                .add(recurring_transaction::Column::SourceAccountId.is_in(ids.clone())),
+        )
+        .order_by_asc(recurring_transaction::Column::StartDate)
+        .one(db)
+        .await?
+        .map(|transaction| transaction.start_date);
+    let income = recurring_income::Entity::find()
+        .filter(recurring_income::Column::TargetAccountId.is_in(ids))
+        .order_by_asc(recurring_income::Column::StartDate)
+        .one(db)
+        .await?
+        .map(|income| income.start_date);
+
+    Ok([one_off, imported, recurring, income].into_iter().flatten().fold(start, std::cmp::min))
+}
 
 /// Generates occurrence dates for a recurring event within the given date range.
-#[instrument(fields(start_date = %start_date, end_date = ?end_date, period = ?period, range_start = %range_start, range_end = %range_end
+///
+/// See [`recurrence::occurrences`], which the frontend uses for its previews. Work-day
+/// recurrences additionally skip the days in `holidays`.
+#[instrument(skip(holidays), fields(start_date = %start_date, end_date = ?end_date, period = ?period, range_start = %range_start, range_end = %range_end
 ))]
 pub fn generate_occurrences(
     start_date: NaiveDate,
     end_date: Option<NaiveDate>,
     period: &recurring_transaction::RecurrencePeriod,
+    holidays: &HolidayCalendar,
     range_start: NaiveDate,
     range_end: NaiveDate,
 ) -> Vec<NaiveDate> {
@@ -18,114 +100,81 @@ pub fn generate_occurrences(
         "Generating occurrences for period {:?} from {} to {}",
         period, range_start, range_end
     );
-    let mut occurrences = Vec::new();
-    let mut current_date = start_date;
-    trace!("Initial date: {}", current_date);
-
-    // Check if the event ends before the range starts
-    if let Some(end) = end_date {
-        if end < range_start {
-            debug!(
-                "Event ends at {} which is before range start {}, returning empty list",
-                end, range_start
-            );
-            return occurrences;
-        }
+    let mut occurrences = recurrence::occurrences(start_date, end_date, period.into(), range_start, range_end);
+    if *period == RecurrencePeriod::WorkDay {
+        occurrences.retain(|date| !holidays.is_holiday(*date));
     }
+    debug!("Generated {} occurrences", occurrences.len());
+    occurrences
+}
 
-    // Generate occurrences until we reach the end of the range or the end of the event
-    while current_date <= range_end {
-        if current_date >= range_start {
-            trace!("Adding occurrence: {}", current_date);
-            occurrences.push(current_date);
-        } else {
-            trace!(
-                "Skipping date {} as it's before range start {}",
-                current_date, range_start
-            );
-        }
-
-        // Calculate the next occurrence based on the period
-        trace!("Calculating next occurrence based on period: {:?}", period);
-        match period {
-            recurring_transaction::RecurrencePeriod::Daily => {
-                current_date = current_date.succ_opt().unwrap();
-                trace!("Daily: next date is {}", current_date);
-            }
-            recurring_transaction::RecurrencePeriod::Weekly => {
-                current_date += Duration::days(7);
-                trace!("Weekly: next date is {}", current_date);
-            }
-            recurring_transaction::RecurrencePeriod::WorkDay => {
-                // Skip to the next work day (Monday-Friday)
-                current_date = current_date.succ_opt().unwrap();
-                while current_date.weekday().num_days_from_monday() >= 5 {
-                    trace!("WorkDay: skipping weekend day {}", current_date);
-                    current_date = current_date.succ_opt().unwrap();
-                }
-                trace!("WorkDay: next date is {}", current_date);
-            }
-            recurring_transaction::RecurrencePeriod::Monthly => {
-                // Add one month
-                let year = current_date.year() + (current_date.month() / 12) as i32;
-                let month = (current_date.month() % 12) + 1;
-                let day = std::cmp::min(current_date.day(), days_in_month(year, month));
-                trace!(
-                    "Monthly: calculating next date with year={}, month={}, day={}",
-                    year, month, day
-                );
-                current_date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
-                trace!("Monthly: next date is {}", current_date);
-            }
-            recurring_transaction::RecurrencePeriod::Quarterly => {
-                // Add three months
-                let total_months = current_date.month() - 1 + 3;
-                let year = current_date.year() + (total_months / 12) as i32;
-                let month = (total_months % 12) + 1;
-                let day = std::cmp::min(current_date.day(), days_in_month(year, month));
-                trace!(
-                    "Quarterly: calculating next date with year={}, month={}, day={}",
-                    year, month, day
-                );
-                current_date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
-                trace!("Quarterly: next date is {}", current_date);
-            }
-            recurring_transaction::RecurrencePeriod::HalfYearly => {
-                // Add six months
-                let total_months = current_date.month() - 1 + 6;
-                let year = current_date.year() + (total_months / 12) as i32;
-                let month = (total_months % 12) + 1;
-                let day = std::cmp::min(current_date.day(), days_in_month(year, month));
-                trace!(
-                    "HalfYearly: calculating next date with year={}, month={}, day={}",
-                    year, month, day
-                );
-                current_date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
-                trace!("HalfYearly: next date is {}", current_date);
-            }
-            recurring_transaction::RecurrencePeriod::Yearly => {
-                // Add one year
-                let year = current_date.year() + 1;
-                let month = current_date.month();
-                let day = std::cmp::min(current_date.day(), days_in_month(year, month));
-                trace!(
-                    "Yearly: calculating next date with year={}, month={}, day={}",
-                    year, month, day
-                );
-                current_date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
-                trace!("Yearly: next date is {}", current_date);
-            }
+/// Occurrence dates of a recurring transaction within the given date range.
+///
+/// Follows the transaction's `rrule` when it has one and its `period`, moved by its
+/// `date_adjustment`, otherwise. Business days respect `holidays`. Rules are validated
+/// when saved; one that no longer parses falls back to the period.
+pub fn recurring_transaction_occurrences(
+    transaction: &recurring_transaction::Model,
+    holidays: &HolidayCalendar,
+    range_start: NaiveDate,
+    range_end: NaiveDate,
+) -> Vec<NaiveDate> {
+    let rule = transaction.rrule.as_deref().and_then(|rrule| match rrule.parse::<RecurrenceRule>() {
+        Ok(rule) => Some(rule),
+        Err(e) => {
+            warn!("Ignoring invalid rrule of recurring transaction {}: {}", transaction.id, e);
+            None
         }
-
-        // Check if we've reached the end of the event
-        if let Some(end) = end_date {
-            if current_date > end {
-                debug!("Reached event end date {}, stopping", end);
-                break;
-            }
+    });
+    match rule {
+        Some(rule) => rule.occurrences(transaction.start_date, transaction.end_date, range_start, range_end),
+        None if transaction.date_adjustment != DateAdjustment::None
+            && matches!(
+                transaction.period,
+                RecurrencePeriod::Monthly
+                    | RecurrencePeriod::Quarterly
+                    | RecurrencePeriod::HalfYearly
+                    | RecurrencePeriod::Yearly
+            ) =>
+        {
+            adjusted_occurrences(transaction, holidays, range_start, range_end)
         }
+        None => generate_occurrences(
+            transaction.start_date,
+            transaction.end_date,
+            &transaction.period,
+            holidays,
+            range_start,
+            range_end,
+        ),
     }
+}
 
-    debug!("Generated {} occurrences", occurrences.len());
-    occurrences
+/// Month-based occurrences kept on the day of `start_date`, clamped to the month end and
+/// moved off weekends and holidays as the transaction's `date_adjustment` says.
+fn adjusted_occurrences(
+    transaction: &recurring_transaction::Model,
+    holidays: &HolidayCalendar,
+    range_start: NaiveDate,
+    range_end: NaiveDate,
+) -> Vec<NaiveDate> {
+    // Weekends next to holidays move occurrences by several days, possibly into the range
+    let margin = Duration::days(7);
+    let mut dates: Vec<NaiveDate> = recurrence::month_end_clamped_occurrences(
+        transaction.start_date,
+        transaction.end_date,
+        (&transaction.period).into(),
+        range_start - margin,
+        range_end + margin,
+    )
+    .into_iter()
+    .map(|date| match transaction.date_adjustment {
+        DateAdjustment::PreviousBusinessDay => holidays.business_day(date, false),
+        DateAdjustment::NextBusinessDay => holidays.business_day(date, true),
+        DateAdjustment::None | DateAdjustment::ClampToMonthEnd => date,
+    })
+    .filter(|date| (range_start..=range_end).contains(date))
+    .collect();
+    dates.dedup();
+    dates
 }
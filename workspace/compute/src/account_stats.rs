@@ -3,6 +3,10 @@
 //! This module provides functions to calculate various statistics about accounts
 //! such as minimum and maximum states, average expenses and income, upcoming expenses,
 //! and end-of-period states.
+//!
+//! Days covered by an account's [`StatisticsExclusions`] are left out of the minimum,
+//! maximum, averages and upcoming expenses. End-of-period states are actual balances
+//! and always include them.
 
 use chrono::{Datelike, NaiveDate};
 use polars::prelude::*;
@@ -12,7 +16,7 @@ use std::collections::HashMap;
 use std::str::FromStr;
 use tracing::instrument;
 
-use model::entities::account;
+use model::entities::{account, account_statistics_exclusion};
 
 use crate::account::AccountStateCalculator;
 use crate::error::Result;
@@ -29,13 +33,61 @@ pub struct AccountStats {
     pub end_of_period_state: Option<Decimal>,
 }
 
+/// Date ranges per account that statistics leave out.
+#[derive(Debug, Clone, Default)]
+pub struct StatisticsExclusions {
+    windows: HashMap<i32, Vec<(NaiveDate, NaiveDate)>>,
+}
+
+impl StatisticsExclusions {
+    pub fn new(exclusions: &[account_statistics_exclusion::Model]) -> Self {
+        let mut result = Self::default();
+        for exclusion in exclusions {
+            result.add(exclusion.account_id, exclusion.start_date, exclusion.end_date);
+        }
+        result
+    }
+
+    /// Leaves `start..=end` of the account out of statistics.
+    pub fn add(&mut self, account_id: i32, start: NaiveDate, end: NaiveDate) {
+        self.windows.entry(account_id).or_default().push((start, end));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// Whether `date` of the account is left out of statistics.
+    pub fn excludes(&self, account_id: i32, date: NaiveDate) -> bool {
+        self.windows
+            .get(&account_id)
+            .is_some_and(|windows| windows.iter().any(|(start, end)| (*start..=*end).contains(&date)))
+    }
+
+    /// Whether the row `i` of a frame with `date` stored as days since the Unix epoch is
+    /// left out.
+    fn excludes_row(&self, account_id: i32, date_col: &Column, i: usize) -> Result<bool> {
+        if !self.windows.contains_key(&account_id) {
+            return Ok(false);
+        }
+        let days = date_col
+            .get(i)
+            .map_err(|e| crate::error::ComputeError::Series(format!("Error getting date at row {i}: {e}")))?
+            .try_extract::<i64>()
+            .map_err(|e| crate::error::ComputeError::Series(format!("Error extracting date as i64 at row {i}: {e}")))?;
+        let date = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap() + chrono::Duration::days(days);
+        Ok(self.excludes(account_id, date))
+    }
+}
+
 /// Computes minimum account state for the specified year
-#[instrument(skip(calculator, db, accounts))]
+#[instrument(skip(calculator, db, accounts, exclusions))]
 pub async fn min_state_in_year(
     calculator: &dyn AccountStateCalculator,
     db: &DatabaseConnection,
     accounts: &[account::Model],
     year: i32,
+    exclusions: &StatisticsExclusions,
 ) -> Result<Vec<AccountStats>> {
     let start_date = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
     let end_date = NaiveDate::from_ymd_opt(year, 12, 31).unwrap();
@@ -43,17 +95,18 @@ pub async fn min_state_in_year(
     let df = calculator
         .compute_account_state(db, accounts, start_date, end_date)
         .await?;
-    compute_min_state_from_dataframe(df)
+    compute_min_state_from_dataframe(df, exclusions)
 }
 
 /// Computes minimum account state for the specified month
-#[instrument(skip(calculator, db, accounts))]
+#[instrument(skip(calculator, db, accounts, exclusions))]
 pub async fn min_state_in_month(
     calculator: &dyn AccountStateCalculator,
     db: &DatabaseConnection,
     accounts: &[account::Model],
     year: i32,
     month: u32,
+    exclusions: &StatisticsExclusions,
 ) -> Result<Vec<AccountStats>> {
     let start_date = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
     let end_date = get_last_day_of_month(year, month);
@@ -61,16 +114,17 @@ pub async fn min_state_in_month(
     let df = calculator
         .compute_account_state(db, accounts, start_date, end_date)
         .await?;
-    compute_min_state_from_dataframe(df)
+    compute_min_state_from_dataframe(df, exclusions)
 }
 
 /// Computes maximum account state for the specified year
-#[instrument(skip(calculator, db, accounts))]
+#[instrument(skip(calculator, db, accounts, exclusions))]
 pub async fn max_state_in_year(
     calculator: &dyn AccountStateCalculator,
     db: &DatabaseConnection,
     accounts: &[account::Model],
     year: i32,
+    exclusions: &StatisticsExclusions,
 ) -> Result<Vec<AccountStats>> {
     let start_date = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
     let end_date = NaiveDate::from_ymd_opt(year, 12, 31).unwrap();
@@ -78,17 +132,18 @@ pub async fn max_state_in_year(
     let df = calculator
         .compute_account_state(db, accounts, start_date, end_date)
         .await?;
-    compute_max_state_from_dataframe(df)
+    compute_max_state_from_dataframe(df, exclusions)
 }
 
 /// Computes maximum account state for the specified month
-#[instrument(skip(calculator, db, accounts))]
+#[instrument(skip(calculator, db, accounts, exclusions))]
 pub async fn max_state_in_month(
     calculator: &dyn AccountStateCalculator,
     db: &DatabaseConnection,
     accounts: &[account::Model],
     year: i32,
     month: u32,
+    exclusions: &StatisticsExclusions,
 ) -> Result<Vec<AccountStats>> {
     let start_date = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
     let end_date = get_last_day_of_month(year, month);
@@ -96,16 +151,17 @@ pub async fn max_state_in_month(
     let df = calculator
         .compute_account_state(db, accounts, start_date, end_date)
         .await?;
-    compute_max_state_from_dataframe(df)
+    compute_max_state_from_dataframe(df, exclusions)
 }
 
 /// Computes average expense for the specified year
-#[instrument(skip(calculator, db, accounts))]
+#[instrument(skip(calculator, db, accounts, exclusions))]
 pub async fn average_expense_in_year(
     calculator: &dyn AccountStateCalculator,
     db: &DatabaseConnection,
     accounts: &[account::Model],
     year: i32,
+    exclusions: &StatisticsExclusions,
 ) -> Result<Vec<AccountStats>> {
     let start_date = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
     let end_date = NaiveDate::from_ymd_opt(year, 12, 31).unwrap();
@@ -113,17 +169,18 @@ pub async fn average_expense_in_year(
     let df = calculator
         .compute_account_state(db, accounts, start_date, end_date)
         .await?;
-    compute_basic_stats_from_dataframe(df, StatType::AverageExpense)
+    compute_basic_stats_from_dataframe(df, StatType::AverageExpense, exclusions)
 }
 
 /// Computes average expense for the specified month
-#[instrument(skip(calculator, db, accounts))]
+#[instrument(skip(calculator, db, accounts, exclusions))]
 pub async fn average_expense_in_month(
     calculator: &dyn AccountStateCalculator,
     db: &DatabaseConnection,
     accounts: &[account::Model],
     year: i32,
     month: u32,
+    exclusions: &StatisticsExclusions,
 ) -> Result<Vec<AccountStats>> {
     let start_date = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
     let end_date = get_last_day_of_month(year, month);
@@ -131,16 +188,17 @@ pub async fn average_expense_in_month(
     let df = calculator
         .compute_account_state(db, accounts, start_date, end_date)
         .await?;
-    compute_basic_stats_from_dataframe(df, StatType::AverageExpense)
+    compute_basic_stats_from_dataframe(df, StatType::AverageExpense, exclusions)
 }
 
 /// Computes average income for the specified year
-#[instrument(skip(calculator, db, accounts))]
+#[instrument(skip(calculator, db, accounts, exclusions))]
 pub async fn average_income_in_year(
     calculator: &dyn AccountStateCalculator,
     db: &DatabaseConnection,
     accounts: &[account::Model],
     year: i32,
+    exclusions: &StatisticsExclusions,
 ) -> Result<Vec<AccountStats>> {
     let start_date = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
     let end_date = NaiveDate::from_ymd_opt(year, 12, 31).unwrap();
@@ -148,17 +206,18 @@ pub async fn average_income_in_year(
     let df = calculator
         .compute_account_state(db, accounts, start_date, end_date)
         .await?;
-    compute_basic_stats_from_dataframe(df, StatType::AverageIncome)
+    compute_basic_stats_from_dataframe(df, StatType::AverageIncome, exclusions)
 }
 
 /// Computes average income for the specified month
-#[instrument(skip(calculator, db, accounts))]
+#[instrument(skip(calculator, db, accounts, exclusions))]
 pub async fn average_income_in_month(
     calculator: &dyn AccountStateCalculator,
     db: &DatabaseConnection,
     accounts: &[account::Model],
     year: i32,
     month: u32,
+    exclusions: &StatisticsExclusions,
 ) -> Result<Vec<AccountStats>> {
     let start_date = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
     let end_date = get_last_day_of_month(year, month);
@@ -166,28 +225,29 @@ pub async fn average_income_in_month(
     let df = calculator
         .compute_account_state(db, accounts, start_date, end_date)
         .await?;
-    compute_basic_stats_from_dataframe(df, StatType::AverageIncome)
+    compute_basic_stats_from_dataframe(df, StatType::AverageIncome, exclusions)
 }
 
 /// Computes upcoming expenses until the end of the specified year
-#[instrument(skip(calculator, db, accounts))]
+#[instrument(skip(calculator, db, accounts, exclusions))]
 pub async fn upcoming_expenses_until_year_end(
     calculator: &dyn AccountStateCalculator,
     db: &DatabaseConnection,
     accounts: &[account::Model],
     year: i32,
     from_date: NaiveDate,
+    exclusions: &StatisticsExclusions,
 ) -> Result<Vec<AccountStats>> {
     let end_date = NaiveDate::from_ymd_opt(year, 12, 31).unwrap();
 
     let df = calculator
         .compute_account_state(db, accounts, from_date, end_date)
         .await?;
-    compute_basic_stats_from_dataframe(df, StatType::UpcomingExpenses)
+    compute_basic_stats_from_dataframe(df, StatType::UpcomingExpenses, exclusions)
 }
 
 /// Computes upcoming expenses until the end of the specified month
-#[instrument(skip(calculator, db, accounts))]
+#[instrument(skip(calculator, db, accounts, exclusions))]
 pub async fn upcoming_expenses_until_month_end(
     calculator: &dyn AccountStateCalculator,
     db: &DatabaseConnection,
@@ -195,13 +255,14 @@ pub async fn upcoming_expenses_until_month_end(
     year: i32,
     month: u32,
     from_date: NaiveDate,
+    exclusions: &StatisticsExclusions,
 ) -> Result<Vec<AccountStats>> {
     let end_date = get_last_day_of_month(year, month);
 
     let df = calculator
         .compute_account_state(db, accounts, from_date, end_date)
         .await?;
-    compute_basic_stats_from_dataframe(df, StatType::UpcomingExpenses)
+    compute_basic_stats_from_dataframe(df, StatType::UpcomingExpenses, exclusions)
 }
 
 /// Computes end of year state for the specified year
@@ -244,13 +305,14 @@ pub async fn end_of_month_state(
 /// For each calendar month between `start_date` and `end_date` (inclusive),
 /// the function calculates the account state over that month and extracts
 /// the minimum balance.  The result is returned chronologically (oldest first).
-#[instrument(skip(calculator, db, account))]
+#[instrument(skip(calculator, db, account, exclusions))]
 pub async fn min_balance_per_month(
     calculator: &dyn AccountStateCalculator,
     db: &DatabaseConnection,
     account: &account::Model,
     start_date: NaiveDate,
     end_date: NaiveDate,
+    exclusions: &StatisticsExclusions,
 ) -> Result<Vec<(i32, NaiveDate, rust_decimal::Decimal)>> {
     let accounts = vec![account.clone()];
     let mut results: Vec<(i32, NaiveDate, rust_decimal::Decimal)> = Vec::new();
@@ -270,7 +332,7 @@ pub async fn min_balance_per_month(
             .compute_account_state(db, &accounts, month_start, month_end)
             .await?;
 
-        let stats = compute_min_state_from_dataframe(df)?;
+        let stats = compute_min_state_from_dataframe(df, exclusions)?;
         if let Some(stat) = stats.into_iter().find(|s| s.account_id == account.id) {
             if let Some(min_val) = stat.min_state {
                 results.push((cur_year, month_start, min_val));
@@ -312,7 +374,7 @@ enum StatType {
     UpcomingExpenses,
 }
 
-fn compute_min_state_from_dataframe(df: DataFrame) -> Result<Vec<AccountStats>> {
+fn compute_min_state_from_dataframe(df: DataFrame, exclusions: &StatisticsExclusions) -> Result<Vec<AccountStats>> {
     // Map each account_id to its minimum Decimal balance found in the frame
     let account_col = df
         .column("account_id")
@@ -321,6 +383,9 @@ fn compute_min_state_from_dataframe(df: DataFrame) -> Result<Vec<AccountStats>>
     let balance_col = df
         .column("balance")
         .map_err(|e| crate::error::ComputeError::DataFrame(format!("Missing balance column: {e}")))?;
+    let date_col = df
+        .column("date")
+        .map_err(|e| crate::error::ComputeError::DataFrame(format!("Missing date column: {e}")))?;
 
     let mut mins: HashMap<i32, Decimal> = HashMap::new();
 
@@ -330,6 +395,9 @@ fn compute_min_state_from_dataframe(df: DataFrame) -> Result<Vec<AccountStats>>
             .map_err(|e| crate::error::ComputeError::Series(format!("Error getting account at row {i}: {e}")))?
             .try_extract::<i32>()
             .map_err(|e| crate::error::ComputeError::Series(format!("Error extracting account as i32 at row {i}: {e}")))?;
+        if exclusions.excludes_row(account_id, date_col, i)? {
+            continue;
+        }
 
         let bal_any = balance_col
             .get(i)
@@ -367,7 +435,7 @@ fn compute_min_state_from_dataframe(df: DataFrame) -> Result<Vec<AccountStats>>
     Ok(stats)
 }
 
-fn compute_max_state_from_dataframe(df: DataFrame) -> Result<Vec<AccountStats>> {
+fn compute_max_state_from_dataframe(df: DataFrame, exclusions: &StatisticsExclusions) -> Result<Vec<AccountStats>> {
     let account_col = df
         .column("account_id")
         .or_else(|_| df.column("account"))
@@ -375,6 +443,9 @@ fn compute_max_state_from_dataframe(df: DataFrame) -> Result<Vec<AccountStats>>
     let balance_col = df
         .column("balance")
         .map_err(|e| crate::error::ComputeError::DataFrame(format!("Missing balance column: {e}")))?;
+    let date_col = df
+        .column("date")
+        .map_err(|e| crate::error::ComputeError::DataFrame(format!("Missing date column: {e}")))?;
 
     let mut maxs: HashMap<i32, Decimal> = HashMap::new();
 
@@ -384,6 +455,9 @@ fn compute_max_state_from_dataframe(df: DataFrame) -> Result<Vec<AccountStats>>
             .map_err(|e| crate::error::ComputeError::Series(format!("Error getting account at row {i}: {e}")))?
             .try_extract::<i32>()
             .map_err(|e| crate::error::ComputeError::Series(format!("Error extracting account as i32 at row {i}: {e}")))?;
+        if exclusions.excludes_row(account_id, date_col, i)? {
+            continue;
+        }
 
         let bal_any = balance_col
             .get(i)
@@ -424,6 +498,7 @@ fn compute_max_state_from_dataframe(df: DataFrame) -> Result<Vec<AccountStats>>
 fn compute_basic_stats_from_dataframe(
     df: DataFrame,
     stat_type: StatType,
+    exclusions: &StatisticsExclusions,
 ) -> Result<Vec<AccountStats>> {
     // Extract required columns
     let account_col = df
@@ -437,8 +512,8 @@ fn compute_basic_stats_from_dataframe(
         .column("balance")
         .map_err(|e| crate::error::ComputeError::DataFrame(format!("Missing balance column: {e}")))?;
 
-    // Build per-account time series of (date, balance)
-    let mut series_map: HashMap<i32, Vec<(i64, Decimal)>> = HashMap::new();
+    // Build per-account time series of (date, balance, excluded)
+    let mut series_map: HashMap<i32, Vec<(i64, Decimal, bool)>> = HashMap::new();
 
     for i in 0..df.height() {
         let account_id = account_col
@@ -464,13 +539,14 @@ fn compute_basic_stats_from_dataframe(
         let bal = Decimal::from_str(&bal_str)
             .map_err(|e| crate::error::ComputeError::Decimal(format!("Invalid balance '{bal_str}' at row {i}: {e}")))?;
 
-        series_map.entry(account_id).or_default().push((date, bal));
+        let excluded = exclusions.excludes_row(account_id, date_col, i)?;
+        series_map.entry(account_id).or_default().push((date, bal, excluded));
     }
 
     // Compute stats per account
     let mut out: Vec<AccountStats> = Vec::with_capacity(series_map.len());
     for (account_id, mut points) in series_map {
-        points.sort_by_key(|(d, _)| *d);
+        points.sort_by_key(|(d, _, _)| *d);
 
         let mut sum_pos = Decimal::ZERO;
         let mut sum_neg_abs = Decimal::ZERO;
@@ -479,8 +555,12 @@ fn compute_basic_stats_from_dataframe(
         let mut monthly_income: HashMap<(i32, u32), Decimal> = HashMap::new();
 
         for w in points.windows(2) {
-            let (_, prev) = w[0];
-            let (date_num, curr) = w[1];
+            let (_, prev, _) = w[0];
+            let (date_num, curr, excluded) = w[1];
+            // A change landing on an excluded day belongs to the excluded window
+            if excluded {
+                continue;
+            }
             let delta = curr - prev;
 
             if delta > Decimal::ZERO {
@@ -806,6 +886,55 @@ mod tests {
         assert_eq!(stats[1].end_of_period_state, Some(Decimal::new(18000, 2)));
     }
 
+    fn epoch_days(date: NaiveDate) -> i64 {
+        (date - NaiveDate::from_ymd_opt(1970, 1, 1).unwrap()).num_days()
+    }
+
+    #[test]
+    fn test_min_and_max_state_skip_excluded_days() {
+        let d1 = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let d2 = NaiveDate::from_ymd_opt(2026, 3, 20).unwrap();
+        let d3 = NaiveDate::from_ymd_opt(2026, 3, 31).unwrap();
+        let frame = || {
+            df! {
+                "account_id" => &[1i32, 1, 1, 2],
+                "date" => &[epoch_days(d1), epoch_days(d2), epoch_days(d3), epoch_days(d2)],
+                "balance" => &["100.00", "-5000.00", "150.00", "-20.00"],
+            }
+            .unwrap()
+        };
+        let mut exclusions = StatisticsExclusions::default();
+        exclusions.add(1, d2, d2);
+
+        let mut mins = compute_min_state_from_dataframe(frame(), &exclusions).unwrap();
+        mins.sort_by_key(|s| s.account_id);
+        assert_eq!(mins[0].min_state, Some(Decimal::new(10000, 2)));
+        // Other accounts keep the day
+        assert_eq!(mins[1].min_state, Some(Decimal::new(-2000, 2)));
+
+        let maxs = compute_max_state_from_dataframe(frame(), &StatisticsExclusions::default()).unwrap();
+        let max = maxs.iter().find(|s| s.account_id == 1).unwrap();
+        assert_eq!(max.max_state, Some(Decimal::new(15000, 2)));
+    }
+
+    #[test]
+    fn test_upcoming_expenses_skip_changes_in_excluded_window() {
+        let d1 = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let d2 = NaiveDate::from_ymd_opt(2026, 3, 20).unwrap();
+        let d3 = NaiveDate::from_ymd_opt(2026, 3, 31).unwrap();
+        let df = df! {
+            "account_id" => &[1i32, 1, 1],
+            "date" => &[epoch_days(d1), epoch_days(d2), epoch_days(d3)],
+            "balance" => &["1000.00", "-9000.00", "-9100.00"],
+        }
+        .unwrap();
+        let mut exclusions = StatisticsExclusions::default();
+        exclusions.add(1, d2, d2);
+
+        let stats = compute_basic_stats_from_dataframe(df, StatType::UpcomingExpenses, &exclusions).unwrap();
+        assert_eq!(stats[0].upcoming_expenses, Some(Decimal::new(10000, 2)));
+    }
+
     #[test]
     fn test_calculate_goal_reached_date_future_date() {
         // Create a DataFrame with dates in the future
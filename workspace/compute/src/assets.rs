@@ -0,0 +1,125 @@
+use chrono::{Datelike, NaiveDate};
+use model::entities::asset::{self, DepreciationMethod};
+use rust_decimal::Decimal;
+
+/// Whole months from `from` to `to`, counting a month once its day of month is reached.
+pub fn months_elapsed(from: NaiveDate, to: NaiveDate) -> u32 {
+    if to <= from {
+        return 0;
+    }
+    let months = (to.year() - from.year()) * 12 + to.month() as i32 - from.month() as i32;
+    let months = if to.day() < from.day() { months - 1 } else { months };
+    months.max(0) as u32
+}
+
+/// Depreciated value of the asset on `date`, as if it was still held.
+///
+/// Nothing before the purchase date. The value drops once a month on the day of the
+/// purchase and stays at the salvage value after the useful life.
+pub fn book_value(asset: &asset::Model, date: NaiveDate) -> Decimal {
+    if date < asset.purchase_date {
+        return Decimal::ZERO;
+    }
+    let life = asset.useful_life_months.max(1) as u32;
+    let months = months_elapsed(asset.purchase_date, date).min(life);
+    let price = asset.purchase_price;
+    let salvage = asset.salvage_value.min(price);
+
+    let value = match asset.depreciation_method {
+        DepreciationMethod::None => price,
+        DepreciationMethod::StraightLine => price - (price - salvage) * Decimal::from(months) / Decimal::from(life),
+        DepreciationMethod::DoubleDeclining if months == life => salvage,
+        DepreciationMethod::DoubleDeclining => {
+            let keep = (Decimal::ONE - Decimal::TWO / Decimal::from(life)).max(Decimal::ZERO);
+            (0..months).fold(price, |value, _| value * keep).max(salvage)
+        }
+    };
+    value.round_dp(2)
+}
+
+/// Value the asset adds to the net worth on `date`: its book value while held, nothing
+/// from the disposal date on.
+pub fn value_on(asset: &asset::Model, date: NaiveDate) -> Decimal {
+    if asset.disposal_date.is_some_and(|disposed| date >= disposed) {
+        return Decimal::ZERO;
+    }
+    book_value(asset, date)
+}
+
+/// Profit of selling the asset for `sale_price` on `date`; negative for a loss.
+pub fn gain_on_disposal(asset: &asset::Model, date: NaiveDate, sale_price: Decimal) -> Decimal {
+    sale_price - book_value(asset, date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn laptop(method: DepreciationMethod) -> asset::Model {
+        asset::Model {
+            id: 1,
+            name: "Laptop".to_string(),
+            description: None,
+            account_id: 1,
+            purchase_date: date(2025, 1, 15),
+            purchase_price: Decimal::from(2400),
+            salvage_value: Decimal::from(240),
+            depreciation_method: method,
+            useful_life_months: 24,
+            purchase_transaction_id: None,
+            disposal_date: None,
+            sale_price: None,
+            disposal_transaction_id: None,
+        }
+    }
+
+    #[test]
+    fn test_months_elapsed() {
+        assert_eq!(months_elapsed(date(2025, 1, 15), date(2025, 1, 31)), 0);
+        assert_eq!(months_elapsed(date(2025, 1, 15), date(2025, 2, 14)), 0);
+        assert_eq!(months_elapsed(date(2025, 1, 15), date(2025, 2, 15)), 1);
+        assert_eq!(months_elapsed(date(2025, 1, 15), date(2026, 1, 15)), 12);
+        assert_eq!(months_elapsed(date(2025, 1, 15), date(2024, 12, 1)), 0);
+    }
+
+    #[test]
+    fn test_straight_line() {
+        let asset = laptop(DepreciationMethod::StraightLine);
+
+        assert_eq!(book_value(&asset, date(2025, 1, 14)), Decimal::ZERO);
+        assert_eq!(book_value(&asset, date(2025, 1, 15)), Decimal::from(2400));
+        // 2160 over 24 months is 90 a month
+        assert_eq!(book_value(&asset, date(2025, 2, 15)), Decimal::from(2310));
+        assert_eq!(book_value(&asset, date(2026, 1, 15)), Decimal::from(1320));
+        assert_eq!(book_value(&asset, date(2027, 1, 15)), Decimal::from(240));
+        assert_eq!(book_value(&asset, date(2030, 1, 1)), Decimal::from(240));
+    }
+
+    #[test]
+    fn test_double_declining_and_no_depreciation() {
+        let asset = laptop(DepreciationMethod::DoubleDeclining);
+
+        // Two 24ths of the remaining value go every month
+        assert_eq!(book_value(&asset, date(2025, 2, 15)), Decimal::from(2200));
+        assert!(book_value(&asset, date(2026, 1, 15)) < Decimal::from(1200));
+        assert_eq!(book_value(&asset, date(2027, 1, 15)), Decimal::from(240));
+
+        let art = laptop(DepreciationMethod::None);
+        assert_eq!(book_value(&art, date(2040, 1, 1)), Decimal::from(2400));
+    }
+
+    #[test]
+    fn test_disposal() {
+        let mut asset = laptop(DepreciationMethod::StraightLine);
+        asset.disposal_date = Some(date(2026, 1, 15));
+
+        assert_eq!(value_on(&asset, date(2026, 1, 14)), Decimal::from(1410));
+        assert_eq!(value_on(&asset, date(2026, 1, 15)), Decimal::ZERO);
+        assert_eq!(gain_on_disposal(&asset, date(2026, 1, 15), Decimal::from(1500)), Decimal::from(180));
+        assert_eq!(gain_on_disposal(&asset, date(2026, 1, 15), Decimal::from(1000)), Decimal::from(-320));
+    }
+}
@@ -0,0 +1,380 @@
+//! Forward projection of an account balance with a confidence band.
+//!
+//! The regular compute already carries recurring transactions and incomes into the
+//! future. What it cannot know is the everyday spending that is only entered once it
+//! happens, so this adds the average monthly one-off spending per category on top of it
+//! and widens a band around the result by how much that spending varied in the past.
+
+use chrono::{Months, NaiveDate};
+use model::entities::{account, one_off_transaction};
+use polars::prelude::*;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use tracing::{debug, instrument};
+
+use crate::account::utils::history_start;
+use crate::account::AccountStateCalculator;
+use crate::account_stats::StatisticsExclusions;
+use crate::category_forecast::{month_start, CategoryForecaster};
+use crate::default_compute_with_scenario;
+use crate::error::{ComputeError, Result};
+
+/// Default number of past months the spending averages look at
+pub const DEFAULT_LOOKBACK_MONTHS: u32 = 6;
+
+/// Two-sided 80% quantile of the normal distribution, the width of the band in deviations
+const BAND_Z: f64 = 1.2816;
+
+/// Average number of days in a month, to scale the monthly deviation to a day count
+const DAYS_PER_MONTH: f64 = 30.436875;
+
+/// Projected balance on one day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceForecastPoint {
+    pub date: NaiveDate,
+    /// Expected balance
+    pub balance: Decimal,
+    /// Lower edge of the 80% confidence band
+    pub lower: Decimal,
+    /// Upper edge of the 80% confidence band
+    pub upper: Decimal,
+}
+
+/// Expected one-off spending of one category per month.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategorySpending {
+    /// `None` for uncategorized transactions
+    pub category_id: Option<i32>,
+    /// Average monthly spend, as a positive amount
+    pub monthly_average: Decimal,
+}
+
+/// Result of [`BalanceForecastCalculator::forecast`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BalanceForecast {
+    /// Expected one-off spending per month over all categories, as a positive amount
+    pub monthly_spending: Decimal,
+    /// Sample standard deviation of the monthly spending within the lookback
+    pub monthly_deviation: Decimal,
+    /// Largest spenders first
+    pub categories: Vec<CategorySpending>,
+    /// One point per day from today to the end of the horizon
+    pub points: Vec<BalanceForecastPoint>,
+}
+
+/// Projects the balance of an account a number of months ahead.
+///
+/// Recurring transactions, recurring incomes and already planned one-off transactions
+/// come from the regular compute (with the given scenario, if any). Everyday spending is
+/// the average of the account's confirmed one-off expenses per category over the last
+/// `lookback_months` full months, spread evenly over the days of each future month.
+/// Days left out of statistics by `exclusions` do not count towards the averages.
+pub struct BalanceForecastCalculator {
+    today: NaiveDate,
+    lookback_months: u32,
+    scenario_id: Option<i32>,
+    exclusions: StatisticsExclusions,
+}
+
+impl BalanceForecastCalculator {
+    pub fn new(today: NaiveDate) -> Self {
+        Self {
+            today,
+            lookback_months: DEFAULT_LOOKBACK_MONTHS,
+            scenario_id: None,
+            exclusions: StatisticsExclusions::default(),
+        }
+    }
+
+    /// Number of past months the spending averages look at (at least one).
+    pub fn with_lookback_months(mut self, lookback_months: u32) -> Self {
+        self.lookback_months = lookback_months.max(1);
+        self
+    }
+
+    /// Includes the recurring items of a what-if scenario in the projection.
+    pub fn with_scenario(mut self, scenario_id: Option<i32>) -> Self {
+        self.scenario_id = scenario_id;
+        self
+    }
+
+    pub fn with_exclusions(mut self, exclusions: StatisticsExclusions) -> Self {
+        self.exclusions = exclusions;
+        self
+    }
+
+    pub fn lookback_months(&self) -> u32 {
+        self.lookback_months
+    }
+
+    /// Forecasts the balance of `account` for `months` months after today.
+    #[instrument(skip(self, db, account), fields(account_id = account.id))]
+    pub async fn forecast(&self, db: &DatabaseConnection, account: &account::Model, months: u32) -> Result<BalanceForecast> {
+        let end_date = self
+            .today
+            .checked_add_months(Months::new(months))
+            .ok_or_else(|| ComputeError::Date(format!("{months} months after {} is out of range", self.today)))?;
+
+        let accounts = std::slice::from_ref(account);
+        let from = history_start(db, accounts, self.today).await?;
+        let df = default_compute_with_scenario(Some(self.today), self.scenario_id)
+            .compute_account_state(db, accounts, from, end_date)
+            .await?;
+        let mut balances = balances_of(&df, account.id)?;
+        balances.retain(|(date, _)| *date >= self.today);
+
+        let history = self.spending_history(db, account.id).await?;
+        let first_month = month_start(self.today);
+        let forecaster = CategoryForecaster::new(self.lookback_months);
+        let mut categories: Vec<CategorySpending> = history
+            .iter()
+            .map(|(category_id, monthly)| CategorySpending {
+                category_id: *category_id,
+                monthly_average: forecaster.average(monthly, first_month).round_dp(2),
+            })
+            .filter(|spending| spending.monthly_average > Decimal::ZERO)
+            .collect();
+        categories.sort_by(|a, b| b.monthly_average.cmp(&a.monthly_average));
+        let monthly_spending: Decimal = categories.iter().map(|spending| spending.monthly_average).sum();
+
+        let mut totals: BTreeMap<NaiveDate, Decimal> = BTreeMap::new();
+        for (month, amount) in history.values().flatten() {
+            *totals.entry(*month).or_default() += *amount;
+        }
+        let monthly_deviation = deviation(&lookback_window(&totals, first_month, self.lookback_months));
+        debug!(
+            "Forecasting {} days with monthly spending {} (deviation {}) over {} categories",
+            balances.len(),
+            monthly_spending,
+            monthly_deviation,
+            categories.len()
+        );
+
+        Ok(BalanceForecast {
+            monthly_spending,
+            monthly_deviation,
+            categories,
+            points: project(&balances, self.today, monthly_spending, monthly_deviation),
+        })
+    }
+
+    /// Confirmed one-off expenses of the account within the lookback, per category and month.
+    async fn spending_history(
+        &self,
+        db: &DatabaseConnection,
+        account_id: i32,
+    ) -> Result<BTreeMap<Option<i32>, BTreeMap<NaiveDate, Decimal>>> {
+        let first_month = month_start(self.today);
+        let history_start = first_month
+            .checked_sub_months(Months::new(self.lookback_months))
+            .unwrap_or(first_month);
+
+        // Transfers only move money between own accounts and are left out
+        let transactions = one_off_transaction::Entity::find()
+            .filter(one_off_transaction::Column::TargetAccountId.eq(account_id))
+            .filter(one_off_transaction::Column::SourceAccountId.is_null())
+            .filter(one_off_transaction::Column::Date.gte(history_start))
+            .filter(one_off_transaction::Column::Date.lt(first_month))
+            .filter(one_off_transaction::Column::Amount.lt(Decimal::ZERO))
+            .filter(one_off_transaction::Column::IsSimulated.eq(false))
+            .filter(one_off_transaction::Column::IncludeInStatistics.eq(true))
+            .filter(one_off_transaction::Column::Status.eq(one_off_transaction::OneOffStatus::Confirmed))
+            .all(db)
+            .await?;
+
+        let mut history: BTreeMap<Option<i32>, BTreeMap<NaiveDate, Decimal>> = BTreeMap::new();
+        for transaction in transactions {
+            if self.exclusions.excludes(account_id, transaction.date) {
+                continue;
+            }
+            *history
+                .entry(transaction.category_id)
+                .or_default()
+                .entry(month_start(transaction.date))
+                .or_default() += -transaction.amount;
+        }
+        Ok(history)
+    }
+}
+
+/// Spreads the expected monthly spending over the days after `today` and puts a band
+/// around the result.
+///
+/// `balances` are the balances without everyday spending, sorted by date. Each day after
+/// `today` takes its share of `monthly_spending` by the length of its month. The band is
+/// `monthly_deviation` scaled by the square root of the months ahead, as if each month's
+/// spending varied independently. Days up to `today` are taken as they are.
+pub fn project(
+    balances: &[(NaiveDate, Decimal)],
+    today: NaiveDate,
+    monthly_spending: Decimal,
+    monthly_deviation: Decimal,
+) -> Vec<BalanceForecastPoint> {
+    let deviation = monthly_deviation.to_f64().unwrap_or(0.0);
+    let mut spent = Decimal::ZERO;
+    balances
+        .iter()
+        .map(|&(date, balance)| {
+            if date <= today {
+                return BalanceForecastPoint {
+                    date,
+                    balance,
+                    lower: balance,
+                    upper: balance,
+                };
+            }
+            spent += monthly_spending / Decimal::from(days_in_month(date));
+            let balance = (balance - spent).round_dp(2);
+            let days_ahead = (date - today).num_days() as f64;
+            let band = Decimal::from_f64(BAND_Z * deviation * (days_ahead / DAYS_PER_MONTH).sqrt())
+                .unwrap_or_default()
+                .round_dp(2);
+            BalanceForecastPoint {
+                date,
+                balance,
+                lower: balance - band,
+                upper: balance + band,
+            }
+        })
+        .collect()
+}
+
+/// The `lookback_months` months preceding `first_month`, with zero for months without spend.
+fn lookback_window(totals: &BTreeMap<NaiveDate, Decimal>, first_month: NaiveDate, lookback_months: u32) -> Vec<Decimal> {
+    (1..=lookback_months)
+        .filter_map(|back| first_month.checked_sub_months(Months::new(back)))
+        .map(|month| totals.get(&month).copied().unwrap_or(Decimal::ZERO))
+        .collect()
+}
+
+/// Sample standard deviation, zero for fewer than two values.
+fn deviation(values: &[Decimal]) -> Decimal {
+    if values.len() < 2 {
+        return Decimal::ZERO;
+    }
+    let values: Vec<f64> = values.iter().filter_map(|value| value.to_f64()).collect();
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let variance = values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+    Decimal::from_f64(variance.sqrt()).unwrap_or_default().round_dp(2)
+}
+
+fn days_in_month(date: NaiveDate) -> u32 {
+    let first = month_start(date);
+    first
+        .checked_add_months(Months::new(1))
+        .map(|next| (next - first).num_days() as u32)
+        .unwrap_or(31)
+}
+
+/// Balances of one account from a compute frame, sorted by date.
+fn balances_of(df: &DataFrame, account_id: i32) -> Result<Vec<(NaiveDate, Decimal)>> {
+    let account_col = df
+        .column("account_id")
+        .map_err(|e| ComputeError::DataFrame(format!("Missing account_id column: {e}")))?;
+    let date_col = df
+        .column("date")
+        .map_err(|e| ComputeError::DataFrame(format!("Missing date column: {e}")))?;
+    let balance_col = df
+        .column("balance")
+        .map_err(|e| ComputeError::DataFrame(format!("Missing balance column: {e}")))?;
+    let unix_epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+
+    let mut balances = Vec::with_capacity(df.height());
+    for i in 0..df.height() {
+        let row_account = account_col
+            .get(i)
+            .map_err(|e| ComputeError::Series(format!("Error getting account at row {i}: {e}")))?
+            .try_extract::<i32>()
+            .map_err(|e| ComputeError::Series(format!("Error extracting account as i32 at row {i}: {e}")))?;
+        if row_account != account_id {
+            continue;
+        }
+
+        let date = match date_col
+            .get(i)
+            .map_err(|e| ComputeError::Series(format!("Error getting date at row {i}: {e}")))?
+        {
+            AnyValue::Date(days) => unix_epoch + chrono::Duration::days(days as i64),
+            AnyValue::Int64(days) => unix_epoch + chrono::Duration::days(days),
+            AnyValue::String(s) => NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .map_err(|e| ComputeError::Date(format!("Invalid date string '{s}' at row {i}: {e}")))?,
+            AnyValue::StringOwned(s) => NaiveDate::parse_from_str(s.as_str(), "%Y-%m-%d")
+                .map_err(|e| ComputeError::Date(format!("Invalid date string '{s}' at row {i}: {e}")))?,
+            other => return Err(ComputeError::Series(format!("Unexpected date type at row {i}: {other:?}"))),
+        };
+
+        let balance = match balance_col
+            .get(i)
+            .map_err(|e| ComputeError::Series(format!("Error getting balance at row {i}: {e}")))?
+        {
+            AnyValue::String(s) => s.to_string(),
+            AnyValue::StringOwned(s) => s.to_string(),
+            other => other.to_string(),
+        };
+        let balance = Decimal::from_str(&balance)
+            .map_err(|e| ComputeError::Decimal(format!("Invalid balance '{balance}' at row {i}: {e}")))?;
+        balances.push((date, balance));
+    }
+    balances.sort_by_key(|(date, _)| *date);
+    Ok(balances)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    /// A flat balance of 1000 from 2025-01-31 to 2025-03-31.
+    fn flat() -> Vec<(NaiveDate, Decimal)> {
+        let start = date(2025, 1, 31);
+        (0..=59).map(|day| (start + chrono::Duration::days(day), Decimal::from(1000))).collect()
+    }
+
+    #[test]
+    fn test_spending_is_spread_over_each_month() {
+        let points = project(&flat(), date(2025, 1, 31), Decimal::from(280), Decimal::ZERO);
+
+        assert_eq!(points[0].date, date(2025, 1, 31));
+        assert_eq!(points[0].balance, Decimal::from(1000));
+        // February has 28 days, so 10 per day
+        assert_eq!(points[1].balance, Decimal::from(990));
+        assert_eq!(points[28].date, date(2025, 2, 28));
+        assert_eq!(points[28].balance, Decimal::from(720));
+        // March has 31 days, the full month is spent by its last day
+        assert_eq!(points[59].date, date(2025, 3, 31));
+        assert_eq!(points[59].balance, Decimal::from(440));
+        assert!(points.iter().all(|point| point.lower == point.balance && point.upper == point.balance));
+    }
+
+    #[test]
+    fn test_band_widens_with_distance() {
+        let points = project(&flat(), date(2025, 1, 31), Decimal::ZERO, Decimal::from(100));
+
+        assert_eq!(points[0].lower, points[0].upper);
+        let widths: Vec<Decimal> = points.iter().map(|point| point.upper - point.lower).collect();
+        assert!(widths.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(points.iter().all(|point| point.lower <= point.balance && point.balance <= point.upper));
+        // About one month ahead the band is the 80% quantile of one monthly deviation
+        let month_ahead = points[30].upper - points[30].balance;
+        assert!((Decimal::from(127)..=Decimal::from(130)).contains(&month_ahead));
+    }
+
+    #[test]
+    fn test_deviation_of_monthly_totals() {
+        let mut totals = BTreeMap::new();
+        totals.insert(date(2025, 1, 1), Decimal::from(100));
+        totals.insert(date(2025, 2, 1), Decimal::from(300));
+        // March is missing and counts as zero: values 100, 300, 0 have deviation 152.75
+        let window = lookback_window(&totals, date(2025, 4, 1), 3);
+        assert_eq!(window.len(), 3);
+        assert_eq!(deviation(&window), Decimal::new(15275, 2));
+        assert_eq!(deviation(&[Decimal::from(5)]), Decimal::ZERO);
+        assert_eq!(days_in_month(date(2024, 2, 10)), 29);
+    }
+}
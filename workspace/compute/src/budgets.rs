@@ -0,0 +1,410 @@
+use chrono::NaiveDate;
+use model::entities::{budget, category};
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tracing::debug;
+
+/// A single expense with a category.
+#[derive(Debug, Clone)]
+pub struct CategorizedExpense {
+    pub category_id: i32,
+    pub date: NaiveDate,
+    /// Spent amount as a positive number.
+    pub amount: Decimal,
+}
+
+/// Spending against a budget in the period containing the evaluation date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetStatus {
+    pub budget_id: i32,
+    pub category_id: i32,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    /// Limit of a single period.
+    pub limit: Decimal,
+    /// Left over from earlier periods (negative when they were overspent); zero without rollover.
+    pub carried_over: Decimal,
+    /// Spent in the current period up to the evaluation date.
+    pub spent: Decimal,
+}
+
+impl BudgetStatus {
+    /// Amount that may be spent in the current period.
+    pub fn available(&self) -> Decimal {
+        self.limit + self.carried_over
+    }
+
+    /// Amount left in the current period; negative once the budget is exceeded.
+    pub fn remaining(&self) -> Decimal {
+        self.available() - self.spent
+    }
+}
+
+/// Cumulative spending against a budget at the end of one day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetProgressPoint {
+    pub date: NaiveDate,
+    /// Start of the period the day belongs to; the cumulative spending restarts with it.
+    pub period_start: NaiveDate,
+    /// Limit plus the amount carried over into the period.
+    pub available: Decimal,
+    /// Spent since the start of the period up to and including the day.
+    pub spent: Decimal,
+}
+
+impl BudgetProgressPoint {
+    /// Negative once the budget is exceeded.
+    pub fn remaining(&self) -> Decimal {
+        self.available - self.spent
+    }
+}
+
+/// Suggested limits are multiples of this amount.
+pub const SUGGESTION_ROUNDING: i64 = 10;
+
+/// Monthly limit proposed for a top-level category from past spending.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetSuggestion {
+    pub category_id: i32,
+    /// Median of the monthly totals, months without spending counting as zero.
+    pub median: Decimal,
+    /// The median rounded to a multiple of `SUGGESTION_ROUNDING`.
+    pub amount: Decimal,
+}
+
+/// Returns `root` and the ids of all its subcategories.
+pub fn category_with_descendants(categories: &[category::Model], root: i32) -> HashSet<i32> {
+    let mut children: HashMap<i32, Vec<i32>> = HashMap::new();
+    for category in categories {
+        if let Some(parent_id) = category.parent_id {
+            children.entry(parent_id).or_default().push(category.id);
+        }
+    }
+
+    let mut ids = HashSet::new();
+    let mut stack = vec![root];
+    while let Some(id) = stack.pop() {
+        // The visited check also guards against a malformed hierarchy with a cycle
+        if ids.insert(id) {
+            stack.extend(children.get(&id).into_iter().flatten().copied());
+        }
+    }
+    ids
+}
+
+/// Returns the top-level ancestor of `category_id`, or the category itself without a parent.
+pub fn top_level_category(categories: &[category::Model], category_id: i32) -> i32 {
+    let parents: HashMap<i32, i32> = categories
+        .iter()
+        .filter_map(|category| Some((category.id, category.parent_id?)))
+        .collect();
+
+    let mut visited = HashSet::new();
+    let mut id = category_id;
+    // The visited check also guards against a malformed hierarchy with a cycle
+    while let Some(parent_id) = parents.get(&id).copied().filter(|_| visited.insert(id)) {
+        id = parent_id;
+    }
+    id
+}
+
+/// Evaluates one budget for the period containing `date`.
+///
+/// Expenses of the budget's category and its subcategories dated up to `date` count.
+/// With rollover, every period since the one containing `start_date` adds its unspent
+/// amount (or subtracts its overspending) to the next one. Returns `None` when the
+/// budget only starts in a later period.
+pub fn evaluate_budget(
+    budget: &budget::Model,
+    categories: &[category::Model],
+    expenses: &[CategorizedExpense],
+    date: NaiveDate,
+) -> Option<BudgetStatus> {
+    let (current_start, current_end) = budget.period.bounds(date);
+    let (mut period_start, mut period_end) = budget.period.bounds(budget.start_date);
+    if period_start > current_start {
+        return None;
+    }
+
+    let covered = category_with_descendants(categories, budget.category_id);
+    let spent_between = |start: NaiveDate, end: NaiveDate| -> Decimal {
+        expenses
+            .iter()
+            .filter(|expense| covered.contains(&expense.category_id))
+            .filter(|expense| start <= expense.date && expense.date <= end && expense.date <= date)
+            .map(|expense| expense.amount)
+            .sum()
+    };
+
+    let mut carried_over = Decimal::ZERO;
+    while budget.rollover && period_start < current_start {
+        carried_over += budget.amount - spent_between(period_start, period_end);
+        (period_start, period_end) = budget.period.bounds(period_end.succ_opt()?);
+    }
+
+    Some(BudgetStatus {
+        budget_id: budget.id,
+        category_id: budget.category_id,
+        period_start: current_start,
+        period_end: current_end,
+        limit: budget.amount,
+        carried_over,
+        spent: spent_between(current_start, current_end),
+    })
+}
+
+/// Evaluates all budgets active on `date`, ordered by budget id.
+pub fn evaluate_budgets(
+    budgets: &[budget::Model],
+    categories: &[category::Model],
+    expenses: &[CategorizedExpense],
+    date: NaiveDate,
+) -> Vec<BudgetStatus> {
+    let mut statuses: Vec<BudgetStatus> = budgets
+        .iter()
+        .filter_map(|budget| evaluate_budget(budget, categories, expenses, date))
+        .collect();
+    statuses.sort_by_key(|status| status.budget_id);
+    debug!("Evaluated {} budgets against {} expenses on {}", statuses.len(), expenses.len(), date);
+    statuses
+}
+
+/// Day-by-day cumulative spending against a budget from `start` to `end`.
+///
+/// Spending accumulates from the start of each period, so a range starting mid-period
+/// begins with what was already spent in it. Days before the budget starts are left out.
+pub fn budget_progress(
+    budget: &budget::Model,
+    categories: &[category::Model],
+    expenses: &[CategorizedExpense],
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Vec<BudgetProgressPoint> {
+    let covered = category_with_descendants(categories, budget.category_id);
+    let mut daily: BTreeMap<NaiveDate, Decimal> = BTreeMap::new();
+    for expense in expenses.iter().filter(|expense| covered.contains(&expense.category_id)) {
+        *daily.entry(expense.date).or_insert(Decimal::ZERO) += expense.amount;
+    }
+
+    let mut points = Vec::new();
+    let mut current: Option<BudgetStatus> = None;
+    let mut spent = Decimal::ZERO;
+    for date in start.iter_days().take_while(|date| *date <= end) {
+        match &current {
+            Some(status) if date <= status.period_end => spent += daily.get(&date).copied().unwrap_or_default(),
+            _ => {
+                // First day of the range or of a new period: start from what the period has spent so far
+                current = evaluate_budget(budget, categories, expenses, date);
+                spent = current.as_ref().map_or(Decimal::ZERO, |status| status.spent);
+            }
+        }
+        if let Some(status) = &current {
+            points.push(BudgetProgressPoint {
+                date,
+                period_start: status.period_start,
+                available: status.available(),
+                spent,
+            });
+        }
+    }
+    points
+}
+
+/// Suggests a monthly limit for every top-level category spent on in `months`.
+///
+/// Expenses of subcategories count towards their top-level category, so the suggested
+/// budgets never cover an expense twice. Categories whose suggestion rounds to zero are
+/// left out; the rest are ordered by category id.
+pub fn suggest_monthly_budgets(
+    categories: &[category::Model],
+    expenses: &[CategorizedExpense],
+    months: &[(NaiveDate, NaiveDate)],
+) -> Vec<BudgetSuggestion> {
+    let mut totals: BTreeMap<i32, Vec<Decimal>> = BTreeMap::new();
+    for expense in expenses {
+        let Some(month) = months
+            .iter()
+            .position(|(start, end)| *start <= expense.date && expense.date <= *end)
+        else {
+            continue;
+        };
+        let monthly = totals
+            .entry(top_level_category(categories, expense.category_id))
+            .or_insert_with(|| vec![Decimal::ZERO; months.len()]);
+        monthly[month] += expense.amount;
+    }
+
+    let step = Decimal::from(SUGGESTION_ROUNDING);
+    totals
+        .into_iter()
+        .filter_map(|(category_id, mut monthly)| {
+            monthly.sort();
+            let middle = monthly.len() / 2;
+            let median = if monthly.len() % 2 == 0 {
+                (monthly[middle - 1] + monthly[middle]) / Decimal::TWO
+            } else {
+                monthly[middle]
+            };
+            let amount = (median / step).round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero) * step;
+            (!amount.is_zero()).then_some(BudgetSuggestion {
+                category_id,
+                median,
+                amount,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use model::entities::budget::BudgetPeriod;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn make_category(id: i32, parent_id: Option<i32>) -> category::Model {
+        category::Model {
+            id,
+            name: format!("Category {}", id),
+            description: None,
+            parent_id,
+        }
+    }
+
+    fn make_budget(category_id: i32, amount: i64, rollover: bool) -> budget::Model {
+        budget::Model {
+            id: 1,
+            category_id,
+            period: BudgetPeriod::Monthly,
+            amount: Decimal::from(amount),
+            rollover,
+            start_date: date(2026, 1, 15),
+        }
+    }
+
+    fn expense(category_id: i32, date: NaiveDate, amount: i64) -> CategorizedExpense {
+        CategorizedExpense {
+            category_id,
+            date,
+            amount: Decimal::from(amount),
+        }
+    }
+
+    #[test]
+    fn test_budget_period_bounds() {
+        assert_eq!(BudgetPeriod::Monthly.bounds(date(2024, 2, 10)), (date(2024, 2, 1), date(2024, 2, 29)));
+        assert_eq!(BudgetPeriod::Quarterly.bounds(date(2026, 8, 31)), (date(2026, 7, 1), date(2026, 9, 30)));
+        assert_eq!(BudgetPeriod::Yearly.bounds(date(2026, 12, 31)), (date(2026, 1, 1), date(2026, 12, 31)));
+    }
+
+    #[test]
+    fn test_evaluate_budget_counts_subcategories_in_current_period() {
+        let categories = vec![make_category(1, None), make_category(2, Some(1)), make_category(3, None)];
+        let expenses = vec![
+            expense(1, date(2026, 3, 2), 100),
+            expense(2, date(2026, 3, 10), 50),
+            expense(3, date(2026, 3, 10), 1000),
+            expense(1, date(2026, 2, 27), 70),
+            expense(1, date(2026, 3, 25), 30),
+        ];
+
+        let status = evaluate_budget(&make_budget(1, 400, false), &categories, &expenses, date(2026, 3, 20)).unwrap();
+        assert_eq!((status.period_start, status.period_end), (date(2026, 3, 1), date(2026, 3, 31)));
+        // The expense on the 25th is after the evaluation date
+        assert_eq!(status.spent, Decimal::from(150));
+        assert_eq!(status.carried_over, Decimal::ZERO);
+        assert_eq!(status.remaining(), Decimal::from(250));
+
+        assert!(evaluate_budget(&make_budget(1, 400, false), &categories, &expenses, date(2025, 12, 31)).is_none());
+    }
+
+    #[test]
+    fn test_evaluate_budget_rolls_over_leftovers_and_overspending() {
+        let categories = vec![make_category(1, None)];
+        let expenses = vec![
+            expense(1, date(2026, 1, 20), 60),
+            expense(1, date(2026, 2, 5), 180),
+            expense(1, date(2026, 3, 5), 10),
+        ];
+        let budget = make_budget(1, 100, true);
+
+        let january = evaluate_budget(&budget, &categories, &expenses, date(2026, 1, 31)).unwrap();
+        assert_eq!(january.carried_over, Decimal::ZERO);
+        assert_eq!(january.remaining(), Decimal::from(40));
+
+        // January leaves 40, February overspends by 80
+        let march = evaluate_budget(&budget, &categories, &expenses, date(2026, 3, 31)).unwrap();
+        assert_eq!(march.carried_over, Decimal::from(-40));
+        assert_eq!(march.available(), Decimal::from(60));
+        assert_eq!(march.remaining(), Decimal::from(50));
+    }
+
+    #[test]
+    fn test_budget_progress_accumulates_within_periods() {
+        let categories = vec![make_category(1, None), make_category(2, Some(1))];
+        let expenses = vec![
+            expense(1, date(2026, 1, 20), 60),
+            expense(2, date(2026, 1, 30), 20),
+            expense(1, date(2026, 1, 30), 5),
+            expense(1, date(2026, 2, 2), 30),
+        ];
+        let budget = make_budget(1, 100, true);
+
+        let points = budget_progress(&budget, &categories, &expenses, date(2026, 1, 29), date(2026, 2, 3));
+        let spent: Vec<Decimal> = points.iter().map(|point| point.spent).collect();
+        assert_eq!(spent, [60, 85, 85, 0, 30, 30].map(Decimal::from));
+        assert_eq!(points[2].remaining(), Decimal::from(15));
+        // February starts with January's leftover on top of the limit
+        assert_eq!(points[3].period_start, date(2026, 2, 1));
+        assert_eq!(points[3].available, Decimal::from(115));
+
+        // Days before the budget starts have no progress
+        let points = budget_progress(&budget, &categories, &expenses, date(2025, 12, 30), date(2026, 1, 2));
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].date, date(2026, 1, 1));
+    }
+
+    #[test]
+    fn test_suggest_monthly_budgets_uses_rounded_median_per_top_level_category() {
+        let categories = vec![make_category(1, None), make_category(2, Some(1)), make_category(3, None)];
+        let months: Vec<(NaiveDate, NaiveDate)> = (1..=4)
+            .map(|month| BudgetPeriod::Monthly.bounds(date(2026, month, 1)))
+            .collect();
+        let expenses = vec![
+            expense(1, date(2026, 1, 5), 100),
+            expense(2, date(2026, 1, 20), 23),
+            expense(1, date(2026, 2, 5), 140),
+            expense(2, date(2026, 3, 3), 150),
+            expense(1, date(2026, 4, 9), 400),
+            // Only one month with spending, the median is zero
+            expense(3, date(2026, 2, 14), 80),
+            // Outside the months
+            expense(1, date(2026, 5, 1), 1000),
+        ];
+
+        let suggestions = suggest_monthly_budgets(&categories, &expenses, &months);
+        // Monthly totals 123, 140, 150, 400
+        assert_eq!(
+            suggestions,
+            vec![BudgetSuggestion {
+                category_id: 1,
+                median: Decimal::new(145, 0),
+                amount: Decimal::from(150),
+            }]
+        );
+        assert!(suggest_monthly_budgets(&categories, &[], &months).is_empty());
+    }
+
+    #[test]
+    fn test_top_level_category() {
+        let categories = vec![make_category(1, None), make_category(2, Some(1)), make_category(3, Some(2))];
+        assert_eq!(top_level_category(&categories, 3), 1);
+        assert_eq!(top_level_category(&categories, 1), 1);
+        assert_eq!(top_level_category(&categories, 9), 9);
+
+        let cycle = vec![make_category(1, Some(2)), make_category(2, Some(1))];
+        assert!([1, 2].contains(&top_level_category(&cycle, 1)));
+    }
+}
@@ -0,0 +1,143 @@
+use chrono::NaiveDate;
+use common::reports::{CashflowPeriodDto, CashflowReportDto, ReportGranularity};
+use rust_decimal::Decimal;
+
+/// Splits `start..=end` into consecutive periods of `granularity`.
+///
+/// The first and last period are clipped to the range.
+pub fn report_periods(
+    start: NaiveDate,
+    end: NaiveDate,
+    granularity: ReportGranularity,
+) -> Vec<(NaiveDate, NaiveDate)> {
+    let mut periods = Vec::new();
+    let mut current = start;
+    while current <= end {
+        let (_, period_end) = granularity.bounds(current);
+        let period_end = period_end.min(end);
+        periods.push((current, period_end));
+        match period_end.succ_opt() {
+            Some(next) => current = next,
+            None => break,
+        }
+    }
+    periods
+}
+
+/// Sums signed amounts into income and expense per period between `start` and `end`.
+///
+/// Positive amounts are income, negative ones expenses. Every period of the range is
+/// returned, so gaps show up as zeros. Amounts outside the range are ignored.
+pub fn compute_cashflow_report(
+    amounts: impl IntoIterator<Item = (NaiveDate, Decimal)>,
+    start: NaiveDate,
+    end: NaiveDate,
+    granularity: ReportGranularity,
+) -> CashflowReportDto {
+    let mut periods: Vec<CashflowPeriodDto> = report_periods(start, end, granularity)
+        .into_iter()
+        .map(|(start_date, end_date)| CashflowPeriodDto {
+            start_date,
+            end_date,
+            income: Decimal::ZERO,
+            expense: Decimal::ZERO,
+            net: Decimal::ZERO,
+        })
+        .collect();
+
+    for (date, amount) in amounts {
+        if date < start || date > end {
+            continue;
+        }
+        let index = periods.partition_point(|period| period.end_date < date);
+        let period = &mut periods[index];
+        if amount >= Decimal::ZERO {
+            period.income += amount;
+        } else {
+            period.expense += amount.abs();
+        }
+    }
+    for period in &mut periods {
+        period.net = period.income - period.expense;
+    }
+
+    let total_income: Decimal = periods.iter().map(|period| period.income).sum();
+    let total_expense: Decimal = periods.iter().map(|period| period.expense).sum();
+    CashflowReportDto {
+        start_date: start,
+        end_date: end,
+        granularity,
+        periods,
+        total_income,
+        total_expense,
+        total_net: total_income - total_expense,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_report_periods_are_clipped_to_range() {
+        let periods = report_periods(date(2026, 1, 15), date(2026, 3, 10), ReportGranularity::Month);
+        assert_eq!(
+            periods,
+            vec![
+                (date(2026, 1, 15), date(2026, 1, 31)),
+                (date(2026, 2, 1), date(2026, 2, 28)),
+                (date(2026, 3, 1), date(2026, 3, 10)),
+            ]
+        );
+
+        let quarters = report_periods(date(2026, 1, 1), date(2026, 12, 31), ReportGranularity::Quarter);
+        assert_eq!(quarters.len(), 4);
+        assert_eq!(quarters[3], (date(2026, 10, 1), date(2026, 12, 31)));
+    }
+
+    #[test]
+    fn test_cashflow_report_splits_income_and_expense() {
+        let amounts = vec![
+            (date(2026, 1, 5), Decimal::from(3000)),
+            (date(2026, 1, 20), Decimal::from(-1200)),
+            (date(2026, 1, 31), Decimal::from(-300)),
+            (date(2026, 3, 1), Decimal::from(-500)),
+            // Outside the range
+            (date(2026, 4, 1), Decimal::from(10_000)),
+        ];
+
+        let report = compute_cashflow_report(amounts, date(2026, 1, 1), date(2026, 3, 31), ReportGranularity::Month);
+        assert_eq!(report.periods.len(), 3);
+
+        let january = &report.periods[0];
+        assert_eq!(january.income, Decimal::from(3000));
+        assert_eq!(january.expense, Decimal::from(1500));
+        assert_eq!(january.net, Decimal::from(1500));
+
+        // February has no transactions but is still reported
+        assert_eq!(report.periods[1].start_date, date(2026, 2, 1));
+        assert_eq!(report.periods[1].net, Decimal::ZERO);
+
+        assert_eq!(report.periods[2].net, Decimal::from(-500));
+        assert_eq!(report.total_income, Decimal::from(3000));
+        assert_eq!(report.total_expense, Decimal::from(2000));
+        assert_eq!(report.total_net, Decimal::from(1000));
+    }
+
+    #[test]
+    fn test_cashflow_report_weekly() {
+        // 2026-10-12 is a Monday
+        let amounts = vec![
+            (date(2026, 10, 11), Decimal::from(-10)),
+            (date(2026, 10, 12), Decimal::from(-20)),
+        ];
+        let report = compute_cashflow_report(amounts, date(2026, 10, 5), date(2026, 10, 18), ReportGranularity::Week);
+        assert_eq!(report.periods.len(), 2);
+        assert_eq!(report.periods[0].expense, Decimal::from(10));
+        assert_eq!(report.periods[1].expense, Decimal::from(20));
+    }
+}
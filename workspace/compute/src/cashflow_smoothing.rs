@@ -0,0 +1,230 @@
+use chrono::{Datelike, NaiveDate};
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+
+use crate::account::days_in_month;
+
+/// A recurring payment whose due day could be moved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShiftCandidate {
+    /// ID of the recurring rule.
+    pub source_id: i32,
+    pub name: String,
+    /// Current day of month the payment is due on.
+    pub day: u32,
+    /// Outflow per occurrence, as a positive amount.
+    pub amount: Decimal,
+    /// Occurrences within the forecasted window.
+    pub occurrences: Vec<NaiveDate>,
+}
+
+/// A recommended due-day change and its effect on the forecast.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShiftSuggestion {
+    pub source_id: i32,
+    pub name: String,
+    pub from_day: u32,
+    pub to_day: u32,
+    pub amount: Decimal,
+    /// Lowest forecasted balance before and after applying this (and all earlier) suggestions.
+    pub min_balance_before: Decimal,
+    pub min_balance_after: Decimal,
+    /// Number of forecasted days below the buffer before and after.
+    pub days_below_buffer_before: usize,
+    pub days_below_buffer_after: usize,
+}
+
+/// Summary of a daily balance series relative to the buffer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeriesScore {
+    pub min_balance: Decimal,
+    pub days_below_buffer: usize,
+}
+
+impl SeriesScore {
+    fn is_better_than(&self, other: &SeriesScore) -> bool {
+        (self.days_below_buffer, -self.min_balance) < (other.days_below_buffer, -other.min_balance)
+    }
+}
+
+/// Recommends moving recurring payments to other days of the month so the
+/// forecasted balance stays above a buffer.
+///
+/// The advisor starts from a daily balance forecast and repeatedly tries to move
+/// one payment to one of the target days (usually the days income arrives). Each
+/// move is validated by replaying it against the forecast: every occurrence is moved
+/// within its own month, which raises the balance between the old and new due date
+/// when paying later (or lowers it when paying earlier). The move that removes the
+/// most days below the buffer wins; ties go to the highest minimum balance. Moves
+/// are applied cumulatively until the forecast no longer dips or nothing improves it.
+#[derive(Debug, Clone)]
+pub struct SmoothingAdvisor {
+    buffer: Decimal,
+    target_days: Vec<u32>,
+}
+
+impl SmoothingAdvisor {
+    /// Creates an advisor moving payments to `target_days`.
+    ///
+    /// When no target days are given every day from 1 to 28 is considered.
+    pub fn new(buffer: Decimal, target_days: Vec<u32>) -> Self {
+        let mut target_days: Vec<u32> = target_days.into_iter().filter(|day| (1..=31).contains(day)).collect();
+        if target_days.is_empty() {
+            target_days = (1..=28).collect();
+        }
+        target_days.sort_unstable();
+        target_days.dedup();
+        Self { buffer, target_days }
+    }
+
+    /// Scores a daily balance series against the buffer.
+    pub fn score(&self, balances: &BTreeMap<NaiveDate, Decimal>) -> SeriesScore {
+        SeriesScore {
+            min_balance: balances.values().copied().min().unwrap_or(Decimal::ZERO),
+            days_below_buffer: balances.values().filter(|balance| **balance < self.buffer).count(),
+        }
+    }
+
+    /// Suggests due-day changes for `candidates` given the `baseline` daily balance forecast.
+    pub fn advise(&self, baseline: &BTreeMap<NaiveDate, Decimal>, candidates: &[ShiftCandidate]) -> Vec<ShiftSuggestion> {
+        let mut balances = baseline.clone();
+        let mut score = self.score(&balances);
+        let mut remaining: Vec<&ShiftCandidate> = candidates.iter().filter(|c| !c.occurrences.is_empty()).collect();
+        let mut suggestions = Vec::new();
+
+        while score.days_below_buffer > 0 && !remaining.is_empty() {
+            let mut best: Option<(usize, u32, BTreeMap<NaiveDate, Decimal>, SeriesScore)> = None;
+
+            for (index, candidate) in remaining.iter().enumerate() {
+                for &to_day in &self.target_days {
+                    if to_day == candidate.day {
+                        continue;
+                    }
+                    let shifted = shift_payment(&balances, candidate, to_day);
+                    let shifted_score = self.score(&shifted);
+                    let current_best = best.as_ref().map(|(_, _, _, s)| *s).unwrap_or(score);
+                    if shifted_score.is_better_than(&current_best) {
+                        best = Some((index, to_day, shifted, shifted_score));
+                    }
+                }
+            }
+
+            let Some((index, to_day, shifted, shifted_score)) = best else {
+                break;
+            };
+            let candidate = remaining.remove(index);
+            suggestions.push(ShiftSuggestion {
+                source_id: candidate.source_id,
+                name: candidate.name.clone(),
+                from_day: candidate.day,
+                to_day,
+                amount: candidate.amount,
+                min_balance_before: score.min_balance,
+                min_balance_after: shifted_score.min_balance,
+                days_below_buffer_before: score.days_below_buffer,
+                days_below_buffer_after: shifted_score.days_below_buffer,
+            });
+            balances = shifted;
+            score = shifted_score;
+        }
+
+        suggestions
+    }
+}
+
+/// Replays moving every occurrence of `candidate` to `to_day` of the same month.
+fn shift_payment(
+    balances: &BTreeMap<NaiveDate, Decimal>,
+    candidate: &ShiftCandidate,
+    to_day: u32,
+) -> BTreeMap<NaiveDate, Decimal> {
+    let mut shifted = balances.clone();
+    for &date in &candidate.occurrences {
+        let day = to_day.min(days_in_month(date.year(), date.month()));
+        let Some(new_date) = date.with_day(day) else {
+            continue;
+        };
+        let (from, to, delta) = if new_date > date {
+            (date, new_date, candidate.amount)
+        } else {
+            (new_date, date, -candidate.amount)
+        };
+        for (_, balance) in shifted.range_mut(from..to) {
+            *balance += delta;
+        }
+    }
+    shifted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    /// Daily balances for Jan-Feb 2025 with income on the 15th and the given payments.
+    fn forecast(start: i64, income: i64, payments: &[(u32, i64)]) -> BTreeMap<NaiveDate, Decimal> {
+        let mut balances = BTreeMap::new();
+        let mut balance = Decimal::from(start);
+        let mut current = date(1, 1);
+        while current <= date(2, 28) {
+            if current.day() == 15 {
+                balance += Decimal::from(income);
+            }
+            for (day, amount) in payments {
+                if current.day() == *day {
+                    balance -= Decimal::from(*amount);
+                }
+            }
+            balances.insert(current, balance);
+            current = current.succ_opt().unwrap();
+        }
+        balances
+    }
+
+    fn candidate(source_id: i32, day: u32, amount: i64) -> ShiftCandidate {
+        ShiftCandidate {
+            source_id,
+            name: format!("Payment {}", source_id),
+            day,
+            amount: Decimal::from(amount),
+            occurrences: vec![date(1, day), date(2, day)],
+        }
+    }
+
+    #[test]
+    fn test_no_suggestions_without_dips() {
+        let balances = forecast(5000, 2000, &[(1, 1000)]);
+        let advisor = SmoothingAdvisor::new(Decimal::ZERO, vec![15]);
+        assert!(advisor.advise(&balances, &[candidate(1, 1, 1000)]).is_empty());
+    }
+
+    #[test]
+    fn test_moves_payment_after_income() {
+        // Rent on the 1st drains the account until salary arrives on the 15th
+        let balances = forecast(500, 2000, &[(1, 1500), (20, 100)]);
+        let advisor = SmoothingAdvisor::new(Decimal::ZERO, vec![15]);
+        let before = advisor.score(&balances);
+        assert!(before.days_below_buffer > 0);
+
+        let suggestions = advisor.advise(&balances, &[candidate(1, 1, 1500), candidate(2, 20, 100)]);
+        assert_eq!(suggestions.len(), 1);
+        let suggestion = &suggestions[0];
+        assert_eq!(suggestion.source_id, 1);
+        assert_eq!((suggestion.from_day, suggestion.to_day), (1, 15));
+        assert_eq!(suggestion.days_below_buffer_after, 0);
+        assert!(suggestion.min_balance_after >= Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_shift_clamps_to_month_end() {
+        let balances = forecast(0, 0, &[]);
+        let shifted = shift_payment(&balances, &candidate(1, 1, 100), 31);
+        assert_eq!(shifted[&date(1, 30)], Decimal::from(100));
+        assert_eq!(shifted[&date(1, 31)], Decimal::ZERO);
+        assert_eq!(shifted[&date(2, 27)], Decimal::from(100));
+        assert_eq!(shifted[&date(2, 28)], Decimal::ZERO);
+    }
+}
@@ -0,0 +1,149 @@
+use chrono::{Datelike, Months, NaiveDate};
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+
+/// Forecast of the expected spend in one calendar month.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonthlyExpenseForecast {
+    /// First day of the forecasted month.
+    pub month: NaiveDate,
+    /// Expected spend, as a positive amount.
+    pub amount: Decimal,
+}
+
+/// Forecasts per-category expenses from the category's monthly spend history.
+///
+/// The forecast is a seasonal average over the last `lookback_months` months before
+/// the first forecasted month. When the lookback covers at least a full year, each
+/// forecasted month is the average of the same calendar month within the lookback
+/// (so December forecasts look at previous Decembers); otherwise every month gets
+/// the flat average of the lookback. Months without any spend count as zero.
+#[derive(Debug, Clone)]
+pub struct CategoryForecaster {
+    lookback_months: u32,
+}
+
+impl CategoryForecaster {
+    /// Creates a forecaster looking at the given number of past months (at least one).
+    pub fn new(lookback_months: u32) -> Self {
+        Self {
+            lookback_months: lookback_months.max(1),
+        }
+    }
+
+    pub fn lookback_months(&self) -> u32 {
+        self.lookback_months
+    }
+
+    /// Average monthly spend over the lookback window preceding `first_month`.
+    pub fn average(&self, monthly_expenses: &BTreeMap<NaiveDate, Decimal>, first_month: NaiveDate) -> Decimal {
+        let window = self.window(monthly_expenses, month_start(first_month));
+        let total: Decimal = window.iter().map(|(_, amount)| *amount).sum();
+        total / Decimal::from(window.len().max(1) as u32)
+    }
+
+    /// Forecasts `horizon` months starting at the month containing `first_month`.
+    ///
+    /// `monthly_expenses` maps the first day of a month to the spend in that month.
+    pub fn forecast(
+        &self,
+        monthly_expenses: &BTreeMap<NaiveDate, Decimal>,
+        first_month: NaiveDate,
+        horizon: u32,
+    ) -> Vec<MonthlyExpenseForecast> {
+        let first_month = month_start(first_month);
+        let window = self.window(monthly_expenses, first_month);
+        let flat_average = self.average(monthly_expenses, first_month);
+        let seasonal = self.lookback_months >= 12;
+
+        (0..horizon)
+            .filter_map(|offset| first_month.checked_add_months(Months::new(offset)))
+            .map(|month| {
+                let amount = if seasonal {
+                    let same_month: Vec<Decimal> = window
+                        .iter()
+                        .filter(|(past, _)| past.month() == month.month())
+                        .map(|(_, amount)| *amount)
+                        .collect();
+                    if same_month.is_empty() {
+                        flat_average
+                    } else {
+                        same_month.iter().sum::<Decimal>() / Decimal::from(same_month.len() as u32)
+                    }
+                } else {
+                    flat_average
+                };
+                MonthlyExpenseForecast {
+                    month,
+                    amount: amount.round_dp(2),
+                }
+            })
+            .collect()
+    }
+
+    /// The lookback months preceding `first_month`, oldest first, with zero for months without spend.
+    fn window(&self, monthly_expenses: &BTreeMap<NaiveDate, Decimal>, first_month: NaiveDate) -> Vec<(NaiveDate, Decimal)> {
+        (1..=self.lookback_months)
+            .rev()
+            .filter_map(|back| first_month.checked_sub_months(Months::new(back)))
+            .map(|month| (month, monthly_expenses.get(&month).copied().unwrap_or(Decimal::ZERO)))
+            .collect()
+    }
+}
+
+/// First day of the month containing `date`.
+pub fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn month(year: i32, month: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, 1).unwrap()
+    }
+
+    #[test]
+    fn test_flat_average_for_short_lookback() {
+        let mut history = BTreeMap::new();
+        history.insert(month(2025, 1), Decimal::new(100, 0));
+        history.insert(month(2025, 2), Decimal::new(200, 0));
+        // March has no spend and counts as zero
+
+        let forecaster = CategoryForecaster::new(3);
+        let forecast = forecaster.forecast(&history, NaiveDate::from_ymd_opt(2025, 4, 17).unwrap(), 2);
+
+        assert_eq!(forecast.len(), 2);
+        assert_eq!(forecast[0].month, month(2025, 4));
+        assert_eq!(forecast[0].amount, Decimal::new(100, 0));
+        assert_eq!(forecast[1].month, month(2025, 5));
+        assert_eq!(forecast[1].amount, Decimal::new(100, 0));
+    }
+
+    #[test]
+    fn test_seasonal_average_uses_same_calendar_month() {
+        let mut history = BTreeMap::new();
+        for m in 1..=12 {
+            history.insert(month(2024, m), Decimal::new(100, 0));
+        }
+        history.insert(month(2024, 12), Decimal::new(700, 0));
+
+        let forecaster = CategoryForecaster::new(12);
+        let forecast = forecaster.forecast(&history, month(2024, 12).checked_add_months(Months::new(1)).unwrap(), 12);
+
+        assert_eq!(forecast[0].month, month(2025, 1));
+        assert_eq!(forecast[0].amount, Decimal::new(100, 0));
+        assert_eq!(forecast[11].month, month(2025, 12));
+        assert_eq!(forecast[11].amount, Decimal::new(700, 0));
+        assert_eq!(forecaster.average(&history, month(2025, 1)), Decimal::new(150, 0));
+    }
+
+    #[test]
+    fn test_empty_history_forecasts_zero() {
+        let forecaster = CategoryForecaster::new(0);
+        assert_eq!(forecaster.lookback_months(), 1);
+        let forecast = forecaster.forecast(&BTreeMap::new(), month(2025, 1), 1);
+        assert_eq!(forecast[0].amount, Decimal::ZERO);
+    }
+}
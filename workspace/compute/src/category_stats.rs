@@ -0,0 +1,172 @@
+use crate::budgets::category_with_descendants;
+use chrono::{Datelike, NaiveDate};
+use model::entities::category;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use std::collections::{BTreeMap, HashMap};
+
+/// A signed transaction amount booked to a category.
+#[derive(Debug, Clone)]
+pub struct CategoryAmount {
+    pub category_id: i32,
+    pub date: NaiveDate,
+    /// Negative for expenses, positive for income.
+    pub amount: Decimal,
+}
+
+/// Totals of one category over a date range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryStats {
+    pub category_id: i32,
+    /// Sum of the category's own transactions, excluding subcategories.
+    pub own_total: Decimal,
+    /// Sum including all subcategories.
+    pub total: Decimal,
+    /// Totals per calendar year, including subcategories.
+    pub yearly_totals: BTreeMap<i32, Decimal>,
+    /// `total` spread over the calendar years the range touches.
+    pub average_per_year: Decimal,
+    /// Share of the absolute totals of all top-level categories, in percent.
+    pub percentage: f64,
+    /// Number of transactions, including subcategories.
+    pub transaction_count: i64,
+}
+
+/// Sums `amounts` per category between `start` and `end`, rolling subcategories up
+/// into their parents.
+///
+/// Categories without any transaction in their subtree are left out, the others keep
+/// the order of `categories`. Amounts of unknown categories are ignored.
+pub fn compute_category_stats(
+    categories: &[category::Model],
+    amounts: &[CategoryAmount],
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Vec<CategoryStats> {
+    let mut own: HashMap<i32, (BTreeMap<i32, Decimal>, i64)> = HashMap::new();
+    for amount in amounts.iter().filter(|amount| start <= amount.date && amount.date <= end) {
+        let (yearly, count) = own.entry(amount.category_id).or_default();
+        *yearly.entry(amount.date.year()).or_default() += amount.amount;
+        *count += 1;
+    }
+
+    let rolled_up: Vec<(&category::Model, BTreeMap<i32, Decimal>, i64)> = categories
+        .iter()
+        .map(|category| {
+            let mut yearly: BTreeMap<i32, Decimal> = BTreeMap::new();
+            let mut count = 0;
+            for id in category_with_descendants(categories, category.id) {
+                if let Some((own_yearly, own_count)) = own.get(&id) {
+                    for (year, amount) in own_yearly {
+                        *yearly.entry(*year).or_default() += amount;
+                    }
+                    count += own_count;
+                }
+            }
+            (category, yearly, count)
+        })
+        .collect();
+
+    let grand_total: Decimal = rolled_up
+        .iter()
+        .filter(|(category, _, _)| category.parent_id.is_none())
+        .map(|(_, yearly, _)| yearly.values().copied().sum::<Decimal>().abs())
+        .sum();
+    let years = Decimal::from((start.year()..=end.year()).count().max(1));
+
+    rolled_up
+        .into_iter()
+        .filter_map(|(category, yearly_totals, transaction_count)| {
+            let total: Decimal = yearly_totals.values().copied().sum();
+            if transaction_count == 0 && total.is_zero() {
+                return None;
+            }
+            let percentage = if grand_total > Decimal::ZERO {
+                (total.abs() / grand_total * Decimal::ONE_HUNDRED).to_f64().unwrap_or(0.0)
+            } else {
+                0.0
+            };
+
+            Some(CategoryStats {
+                category_id: category.id,
+                own_total: own
+                    .get(&category.id)
+                    .map(|(yearly, _)| yearly.values().copied().sum())
+                    .unwrap_or_default(),
+                total,
+                yearly_totals,
+                average_per_year: total / years,
+                percentage,
+                transaction_count,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn make_category(id: i32, parent_id: Option<i32>) -> category::Model {
+        category::Model {
+            id,
+            name: format!("Category {}", id),
+            description: None,
+            parent_id,
+        }
+    }
+
+    fn amount(category_id: i32, date: NaiveDate, amount: i64) -> CategoryAmount {
+        CategoryAmount {
+            category_id,
+            date,
+            amount: Decimal::from(amount),
+        }
+    }
+
+    #[test]
+    fn test_category_stats_roll_up_subcategories() {
+        let categories = vec![
+            make_category(1, None),
+            make_category(2, Some(1)),
+            make_category(3, Some(2)),
+            make_category(4, None),
+            make_category(5, None),
+        ];
+        let amounts = vec![
+            amount(1, date(2025, 12, 30), -100),
+            amount(2, date(2026, 1, 10), -50),
+            amount(3, date(2026, 2, 1), -50),
+            amount(4, date(2026, 2, 1), 200),
+            // Outside the range
+            amount(5, date(2027, 1, 1), -999),
+        ];
+
+        let stats = compute_category_stats(&categories, &amounts, date(2025, 1, 1), date(2026, 12, 31));
+        let ids: Vec<i32> = stats.iter().map(|stats| stats.category_id).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4]);
+
+        let root = &stats[0];
+        assert_eq!(root.own_total, Decimal::from(-100));
+        assert_eq!(root.total, Decimal::from(-200));
+        assert_eq!(root.transaction_count, 3);
+        assert_eq!(root.yearly_totals[&2025], Decimal::from(-100));
+        assert_eq!(root.yearly_totals[&2026], Decimal::from(-100));
+        assert_eq!(root.average_per_year, Decimal::from(-100));
+        assert_eq!(root.percentage, 50.0);
+
+        assert_eq!(stats[1].own_total, Decimal::from(-50));
+        assert_eq!(stats[1].total, Decimal::from(-100));
+        assert_eq!(stats[1].percentage, 25.0);
+        assert_eq!(stats[3].total, Decimal::from(200));
+    }
+
+    #[test]
+    fn test_category_stats_without_amounts() {
+        let categories = vec![make_category(1, None)];
+        assert!(compute_category_stats(&categories, &[], date(2026, 1, 1), date(2026, 1, 31)).is_empty());
+    }
+}
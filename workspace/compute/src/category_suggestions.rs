@@ -0,0 +1,145 @@
+use crate::recurring_detection::normalize_name;
+use std::collections::{HashMap, HashSet};
+
+/// Shorter words are mostly abbreviations or leftovers of reference numbers.
+const MIN_KEYWORD_LEN: usize = 3;
+
+/// A category proposed for a transaction description.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategorySuggestion {
+    pub category_id: i32,
+    /// Between 0 and 1; the category's share of the keyword matches, scaled by how
+    /// closely its best match resembles the description
+    pub confidence: f64,
+    /// How many categorized descriptions share a keyword with the description
+    pub matches: usize,
+}
+
+/// Distinct words of a description that are long enough to identify a payee.
+pub fn keywords(description: &str) -> HashSet<String> {
+    normalize_name(description)
+        .split(' ')
+        .filter(|word| word.chars().count() >= MIN_KEYWORD_LEN)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Suggests up to `limit` categories for `description` from already categorized
+/// `(description, category_id)` pairs.
+///
+/// Each past description sharing a keyword votes for its category with the weighted
+/// share of the description's keywords it contains. Keywords are weighted by how rare
+/// they are in the history, so a payee name outweighs words like "payment" or "card".
+pub fn suggest_categories<'a>(
+    description: &str,
+    history: impl IntoIterator<Item = (&'a str, i32)>,
+    limit: usize,
+) -> Vec<CategorySuggestion> {
+    let query = keywords(description);
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let history: Vec<(HashSet<String>, i32)> = history
+        .into_iter()
+        .map(|(text, category_id)| (keywords(text), category_id))
+        .filter(|(words, _)| !words.is_empty())
+        .collect();
+
+    let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+    for (words, _) in &history {
+        for word in words.iter().filter(|word| query.contains(*word)) {
+            *document_frequency.entry(word.as_str()).or_insert(0) += 1;
+        }
+    }
+    let documents = history.len().max(1) as f64;
+    let weight = |word: &str| {
+        let frequency = document_frequency.get(word).copied().unwrap_or(0).max(1) as f64;
+        (1.0 + documents / frequency).ln()
+    };
+    let query_weight: f64 = query.iter().map(|word| weight(word.as_str())).sum();
+
+    // Per category: summed similarity, best similarity and number of matches
+    let mut votes: HashMap<i32, (f64, f64, usize)> = HashMap::new();
+    for (words, category_id) in &history {
+        let shared: f64 = words.intersection(&query).map(|word| weight(word.as_str())).sum();
+        if shared == 0.0 {
+            continue;
+        }
+        let similarity = shared / query_weight;
+        let entry = votes.entry(*category_id).or_insert((0.0, 0.0, 0));
+        entry.0 += similarity;
+        entry.1 = entry.1.max(similarity);
+        entry.2 += 1;
+    }
+
+    let total: f64 = votes.values().map(|(score, _, _)| score).sum();
+    let mut suggestions: Vec<CategorySuggestion> = votes
+        .into_iter()
+        .map(|(category_id, (score, best, matches))| CategorySuggestion {
+            category_id,
+            confidence: score / total * best,
+            matches,
+        })
+        .collect();
+    suggestions.sort_by(|a, b| {
+        b.confidence
+            .total_cmp(&a.confidence)
+            .then(b.matches.cmp(&a.matches))
+            .then(a.category_id.cmp(&b.category_id))
+    });
+    suggestions.truncate(limit);
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GROCERIES: i32 = 1;
+    const TRANSPORT: i32 = 2;
+    const DINING: i32 = 3;
+
+    #[test]
+    fn test_keywords_drop_numbers_and_short_words() {
+        let words = keywords("CARD PAYMENT 4821 TESCO PRAHA 7, CZ");
+
+        assert_eq!(
+            words,
+            ["card", "payment", "tesco", "praha"].into_iter().map(String::from).collect::<HashSet<_>>()
+        );
+    }
+
+    #[test]
+    fn test_rare_payee_name_outweighs_common_words() {
+        let mut history = vec![("CARD PAYMENT TESCO PRAHA", GROCERIES), ("CARD PAYMENT TESCO BRNO", GROCERIES)];
+        history.extend((0..6).map(|_| ("CARD PAYMENT PRAGUE TRANSIT", TRANSPORT)));
+
+        let suggestions = suggest_categories("Card payment 0912 TESCO", history, 3);
+
+        assert_eq!(suggestions[0].category_id, GROCERIES);
+        assert_eq!(suggestions[0].matches, 2);
+        assert_eq!(suggestions[1].category_id, TRANSPORT);
+        assert!(suggestions[0].confidence > suggestions[1].confidence);
+        assert!(suggestions.iter().all(|s| s.confidence > 0.0 && s.confidence <= 1.0));
+    }
+
+    #[test]
+    fn test_exact_unanimous_match_is_fully_confident() {
+        let history = [("Netflix.com", DINING), ("NETFLIX.COM 12/09", DINING), ("Shell station", TRANSPORT)];
+
+        let suggestions = suggest_categories("NETFLIX.COM", history, 3);
+
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].category_id, DINING);
+        assert!((suggestions[0].confidence - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_limit_and_unrelated_descriptions() {
+        let history = [("Pizza place", DINING), ("Pizza store", GROCERIES), ("Pizza bus", TRANSPORT)];
+
+        assert_eq!(suggest_categories("pizza", history, 2).len(), 2);
+        assert!(suggest_categories("Dentist", history, 3).is_empty());
+        assert!(suggest_categories("12 / 34", history, 3).is_empty());
+    }
+}
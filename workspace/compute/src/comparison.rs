@@ -0,0 +1,103 @@
+use crate::flows::FlowEntry;
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashSet};
+
+/// How much an amount booked to `account_id` changes the combined balance of `account_ids`.
+///
+/// Transfers add to their target and take from their source, so a transfer between two
+/// of the accounts nets out to zero.
+pub fn effect_on_accounts(
+    account_id: i32,
+    source_account_id: Option<i32>,
+    amount: Decimal,
+    account_ids: &HashSet<i32>,
+) -> Decimal {
+    let incoming = if account_ids.contains(&account_id) { amount } else { Decimal::ZERO };
+    let outgoing = match source_account_id {
+        Some(source_account_id) if account_ids.contains(&source_account_id) => amount,
+        _ => Decimal::ZERO,
+    };
+    incoming - outgoing
+}
+
+/// Keeps the `limit` items with the largest absolute amount, largest first.
+///
+/// Items that don't change anything are dropped; ties keep their original order.
+pub fn largest_changes<T>(items: impl IntoIterator<Item = (T, Decimal)>, limit: usize) -> Vec<(T, Decimal)> {
+    let mut items: Vec<(T, Decimal)> = items.into_iter().filter(|(_, amount)| !amount.is_zero()).collect();
+    items.sort_by(|(_, a), (_, b)| b.abs().cmp(&a.abs()));
+    items.truncate(limit);
+    items
+}
+
+/// Sums how much each category changed the combined balance of `account_ids`.
+///
+/// Entries without a category are summed under `None`. Only the `limit` categories with
+/// the largest absolute change are returned, largest first.
+pub fn category_changes(
+    entries: &[FlowEntry],
+    account_ids: &HashSet<i32>,
+    limit: usize,
+) -> Vec<(Option<i32>, Decimal)> {
+    let mut by_category: BTreeMap<Option<i32>, Decimal> = BTreeMap::new();
+    for entry in entries {
+        *by_category.entry(entry.category_id).or_default() +=
+            effect_on_accounts(entry.account_id, entry.source_account_id, entry.amount, account_ids);
+    }
+    largest_changes(by_category, limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn entry(account_id: i32, source_account_id: Option<i32>, amount: i64, category_id: Option<i32>) -> FlowEntry {
+        FlowEntry {
+            date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            account_id,
+            source_account_id,
+            amount: Decimal::from(amount),
+            category_id,
+            source_name: None,
+        }
+    }
+
+    #[test]
+    fn test_transfers_between_compared_accounts_net_out() {
+        let accounts = HashSet::from([1, 2]);
+
+        assert_eq!(effect_on_accounts(1, Some(2), Decimal::from(100), &accounts), Decimal::ZERO);
+        assert_eq!(effect_on_accounts(1, Some(3), Decimal::from(100), &accounts), Decimal::from(100));
+        assert_eq!(effect_on_accounts(3, Some(2), Decimal::from(100), &accounts), Decimal::from(-100));
+        assert_eq!(effect_on_accounts(1, None, Decimal::from(-40), &accounts), Decimal::from(-40));
+    }
+
+    #[test]
+    fn test_largest_changes_ranks_by_magnitude() {
+        let ranked = largest_changes(
+            [("rent", Decimal::from(-900)), ("salary", Decimal::from(2000)), ("none", Decimal::ZERO), ("fee", Decimal::from(-5))],
+            2,
+        );
+
+        assert_eq!(ranked, vec![("salary", Decimal::from(2000)), ("rent", Decimal::from(-900))]);
+    }
+
+    #[test]
+    fn test_category_changes_sums_per_category() {
+        let accounts = HashSet::from([1, 2]);
+        let entries = [
+            entry(1, None, -300, Some(10)),
+            entry(2, None, -200, Some(10)),
+            entry(1, None, 50, None),
+            entry(1, Some(2), 500, Some(20)),
+            entry(1, None, 120, Some(30)),
+        ];
+
+        assert_eq!(
+            category_changes(&entries, &accounts, 5),
+            vec![(Some(10), Decimal::from(-500)), (Some(30), Decimal::from(120)), (None, Decimal::from(50))]
+        );
+        assert_eq!(category_changes(&entries, &accounts, 1).len(), 1);
+    }
+}
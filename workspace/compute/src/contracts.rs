@@ -0,0 +1,79 @@
+use chrono::{Duration, NaiveDate};
+
+/// Last day a contract renewing on `renewal_date` can be cancelled.
+pub fn cancellation_deadline(renewal_date: NaiveDate, notice_period_days: i32) -> NaiveDate {
+    renewal_date - Duration::days(notice_period_days.max(0) as i64)
+}
+
+/// A contract whose cancellation deadline is coming up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractReminder {
+    pub source_id: i32,
+    pub renewal_date: NaiveDate,
+    pub cancellation_deadline: NaiveDate,
+    /// Days left to cancel; zero on the deadline itself.
+    pub days_until_deadline: i64,
+}
+
+/// Contracts, given as `(id, renewal_date, notice_period_days)`, that can still be
+/// cancelled and whose deadline is at most `within_days` after `today`.
+///
+/// Reminders are ordered by deadline, most urgent first.
+pub fn upcoming_cancellation_deadlines(
+    contracts: impl IntoIterator<Item = (i32, NaiveDate, i32)>,
+    today: NaiveDate,
+    within_days: i64,
+) -> Vec<ContractReminder> {
+    let mut reminders: Vec<ContractReminder> = contracts
+        .into_iter()
+        .filter_map(|(source_id, renewal_date, notice_period_days)| {
+            let deadline = cancellation_deadline(renewal_date, notice_period_days);
+            let days_until_deadline = (deadline - today).num_days();
+            (0..=within_days).contains(&days_until_deadline).then_some(ContractReminder {
+                source_id,
+                renewal_date,
+                cancellation_deadline: deadline,
+                days_until_deadline,
+            })
+        })
+        .collect();
+    reminders.sort_by_key(|reminder| (reminder.cancellation_deadline, reminder.source_id));
+    reminders
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_cancellation_deadline() {
+        assert_eq!(cancellation_deadline(date(2027, 1, 1), 30), date(2026, 12, 2));
+        assert_eq!(cancellation_deadline(date(2027, 1, 1), 0), date(2027, 1, 1));
+    }
+
+    #[test]
+    fn test_upcoming_cancellation_deadlines() {
+        let today = date(2026, 10, 15);
+        let contracts = vec![
+            // Deadline 2026-11-01, in 17 days
+            (1, date(2026, 12, 1), 30),
+            // Deadline today
+            (2, date(2026, 10, 29), 14),
+            // Deadline already passed
+            (3, date(2026, 10, 20), 30),
+            // Deadline too far away
+            (4, date(2027, 6, 1), 30),
+        ];
+
+        let reminders = upcoming_cancellation_deadlines(contracts, today, 30);
+        let ids: Vec<i32> = reminders.iter().map(|reminder| reminder.source_id).collect();
+        assert_eq!(ids, vec![2, 1]);
+        assert_eq!(reminders[0].days_until_deadline, 0);
+        assert_eq!(reminders[1].cancellation_deadline, date(2026, 11, 1));
+        assert_eq!(reminders[1].days_until_deadline, 17);
+    }
+}
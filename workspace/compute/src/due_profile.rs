@@ -0,0 +1,127 @@
+use chrono::{Datelike, NaiveDate};
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+
+/// A single expected payment of a recurring rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DueOccurrence {
+    /// ID of the recurring rule the payment belongs to.
+    pub source_id: i32,
+    pub name: String,
+    pub date: NaiveDate,
+    /// Expected outflow, as a positive amount.
+    pub amount: Decimal,
+}
+
+/// Average monthly contribution of one recurring rule to a due day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuePayment {
+    pub source_id: i32,
+    pub name: String,
+    pub monthly_average: Decimal,
+}
+
+/// Aggregated expected outflow for one day of the month.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DueDay {
+    /// Day of the month (1-31).
+    pub day: u32,
+    /// Sum of all occurrences falling on this day within the profiled window.
+    pub total_outflow: Decimal,
+    /// `total_outflow` spread over the number of profiled months.
+    pub monthly_average: Decimal,
+    pub occurrences: usize,
+    /// Rules due on this day, largest first.
+    pub payments: Vec<DuePayment>,
+}
+
+/// Aggregates expected outflows by day of month.
+///
+/// Returns one entry for every day from 1 to 31 (days without payments have zero
+/// totals) so the result can be rendered directly as a heat strip. Averages are
+/// divided by `months`, the length of the window the occurrences were generated for.
+pub fn due_day_profile(occurrences: &[DueOccurrence], months: u32) -> Vec<DueDay> {
+    let months = Decimal::from(months.max(1));
+    let mut days: Vec<DueDay> = (1..=31)
+        .map(|day| DueDay {
+            day,
+            total_outflow: Decimal::ZERO,
+            monthly_average: Decimal::ZERO,
+            occurrences: 0,
+            payments: Vec::new(),
+        })
+        .collect();
+    let mut per_rule: Vec<BTreeMap<i32, (String, Decimal)>> = vec![BTreeMap::new(); 31];
+
+    for occurrence in occurrences {
+        let index = (occurrence.date.day() - 1) as usize;
+        days[index].total_outflow += occurrence.amount;
+        days[index].occurrences += 1;
+        per_rule[index]
+            .entry(occurrence.source_id)
+            .or_insert_with(|| (occurrence.name.clone(), Decimal::ZERO))
+            .1 += occurrence.amount;
+    }
+
+    for (day, rules) in days.iter_mut().zip(per_rule) {
+        day.monthly_average = (day.total_outflow / months).round_dp(2);
+        day.payments = rules
+            .into_iter()
+            .map(|(source_id, (name, total))| DuePayment {
+                source_id,
+                name,
+                monthly_average: (total / months).round_dp(2),
+            })
+            .collect();
+        day.payments
+            .sort_by(|a, b| b.monthly_average.cmp(&a.monthly_average).then(a.source_id.cmp(&b.source_id)));
+    }
+
+    days
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn occurrence(source_id: i32, date: (i32, u32, u32), amount: i64) -> DueOccurrence {
+        DueOccurrence {
+            source_id,
+            name: format!("Rule {}", source_id),
+            date: NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap(),
+            amount: Decimal::from(amount),
+        }
+    }
+
+    #[test]
+    fn test_profile_covers_every_day() {
+        let profile = due_day_profile(&[], 12);
+        assert_eq!(profile.len(), 31);
+        assert!(profile.iter().all(|d| d.total_outflow == Decimal::ZERO && d.payments.is_empty()));
+    }
+
+    #[test]
+    fn test_profile_aggregates_by_day_of_month() {
+        let occurrences = vec![
+            occurrence(1, (2025, 1, 1), 1000),
+            occurrence(1, (2025, 2, 1), 1000),
+            occurrence(2, (2025, 1, 1), 50),
+            occurrence(2, (2025, 2, 1), 50),
+            occurrence(3, (2025, 1, 15), 30),
+        ];
+        let profile = due_day_profile(&occurrences, 2);
+
+        let first = &profile[0];
+        assert_eq!(first.day, 1);
+        assert_eq!(first.total_outflow, Decimal::from(2100));
+        assert_eq!(first.monthly_average, Decimal::from(1050));
+        assert_eq!(first.occurrences, 4);
+        assert_eq!(first.payments.len(), 2);
+        assert_eq!(first.payments[0].source_id, 1);
+        assert_eq!(first.payments[0].monthly_average, Decimal::from(1000));
+
+        let fifteenth = &profile[14];
+        assert_eq!(fifteenth.monthly_average, Decimal::from(15));
+        assert_eq!(fifteenth.occurrences, 1);
+    }
+}
@@ -0,0 +1,266 @@
+use crate::budgets::top_level_category;
+use chrono::NaiveDate;
+use common::reports::{FlowEdgeDto, FlowNodeDto, FlowNodeKind, FlowReportDto};
+use model::entities::{account, category};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+
+/// Label of income that has neither a named source nor a category.
+pub const OTHER_INCOME_LABEL: &str = "Other income";
+/// Label of expenses without a category.
+pub const UNCATEGORIZED_LABEL: &str = "Uncategorized";
+
+/// A signed amount booked to an account.
+#[derive(Debug, Clone)]
+pub struct FlowEntry {
+    pub date: NaiveDate,
+    /// The account the amount is booked to.
+    pub account_id: i32,
+    /// Set for transfers; the amount then leaves this account.
+    pub source_account_id: Option<i32>,
+    /// Positive for income, negative for expenses.
+    pub amount: Decimal,
+    pub category_id: Option<i32>,
+    /// Who pays the income, e.g. the employer of a salary.
+    pub source_name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum NodeKey {
+    IncomeSource(String),
+    IncomeCategory(i32),
+    OtherIncome,
+    Account(i32),
+    Category(i32),
+    Uncategorized,
+}
+
+impl NodeKey {
+    fn id(&self) -> String {
+        match self {
+            NodeKey::IncomeSource(name) => format!("income:{}", name),
+            NodeKey::IncomeCategory(id) => format!("income:category:{}", id),
+            NodeKey::OtherIncome => "income:other".to_string(),
+            NodeKey::Account(id) => format!("account:{}", id),
+            NodeKey::Category(id) => format!("category:{}", id),
+            NodeKey::Uncategorized => "category:uncategorized".to_string(),
+        }
+    }
+
+    fn kind(&self) -> FlowNodeKind {
+        match self {
+            NodeKey::IncomeSource(_) | NodeKey::IncomeCategory(_) | NodeKey::OtherIncome => {
+                FlowNodeKind::IncomeSource
+            }
+            NodeKey::Account(_) => FlowNodeKind::Account,
+            NodeKey::Category(_) | NodeKey::Uncategorized => FlowNodeKind::Category,
+        }
+    }
+}
+
+/// Builds the money flow between `start` and `end` as nodes and edges of a Sankey diagram.
+///
+/// Income flows from its source into the account: the named source if there is one,
+/// otherwise the income's top-level category or "Other income". Expenses flow from the
+/// account into their top-level category or "Uncategorized". Transfers flow between the
+/// two accounts. Amounts between the same two nodes are summed, and flows that net out
+/// to zero are left out.
+pub fn build_flow_report(
+    entries: &[FlowEntry],
+    accounts: &[account::Model],
+    categories: &[category::Model],
+    start: NaiveDate,
+    end: NaiveDate,
+) -> FlowReportDto {
+    let mut edges: BTreeMap<(NodeKey, NodeKey), Decimal> = BTreeMap::new();
+    for entry in entries.iter().filter(|entry| start <= entry.date && entry.date <= end) {
+        let account = NodeKey::Account(entry.account_id);
+        let (from, to, amount) = match entry.source_account_id {
+            Some(source_account_id) if entry.amount >= Decimal::ZERO => {
+                (NodeKey::Account(source_account_id), account, entry.amount)
+            }
+            Some(source_account_id) => (account, NodeKey::Account(source_account_id), -entry.amount),
+            None if entry.amount >= Decimal::ZERO => {
+                let source = match (&entry.source_name, entry.category_id) {
+                    (Some(name), _) => NodeKey::IncomeSource(name.clone()),
+                    (None, Some(category_id)) => {
+                        NodeKey::IncomeCategory(top_level_category(categories, category_id))
+                    }
+                    (None, None) => NodeKey::OtherIncome,
+                };
+                (source, account, entry.amount)
+            }
+            None => {
+                let target = match entry.category_id {
+                    Some(category_id) => NodeKey::Category(top_level_category(categories, category_id)),
+                    None => NodeKey::Uncategorized,
+                };
+                (account, target, -entry.amount)
+            }
+        };
+        *edges.entry((from, to)).or_default() += amount;
+    }
+    edges.retain(|_, amount| !amount.is_zero());
+
+    let account_names: HashMap<i32, &str> =
+        accounts.iter().map(|account| (account.id, account.name.as_str())).collect();
+    let category_names: HashMap<i32, &str> =
+        categories.iter().map(|category| (category.id, category.name.as_str())).collect();
+    let label = |key: &NodeKey| match key {
+        NodeKey::IncomeSource(name) => name.clone(),
+        NodeKey::IncomeCategory(id) | NodeKey::Category(id) => category_names
+            .get(id)
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| format!("Category {}", id)),
+        NodeKey::OtherIncome => OTHER_INCOME_LABEL.to_string(),
+        NodeKey::Account(id) => account_names
+            .get(id)
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| format!("Account {}", id)),
+        NodeKey::Uncategorized => UNCATEGORIZED_LABEL.to_string(),
+    };
+
+    let mut keys: Vec<&NodeKey> = edges.keys().flat_map(|(from, to)| [from, to]).collect();
+    keys.sort_by_key(|key| (key.kind(), *key));
+    keys.dedup();
+
+    FlowReportDto {
+        start_date: start,
+        end_date: end,
+        nodes: keys
+            .into_iter()
+            .map(|key| FlowNodeDto {
+                id: key.id(),
+                label: label(key),
+                kind: key.kind(),
+            })
+            .collect(),
+        edges: edges
+            .into_iter()
+            .map(|((from, to), amount)| FlowEdgeDto {
+                source: from.id(),
+                target: to.id(),
+                amount,
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use model::entities::account::AccountKind;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn make_category(id: i32, name: &str, parent_id: Option<i32>) -> category::Model {
+        category::Model {
+            id,
+            name: name.to_string(),
+            description: None,
+            parent_id,
+        }
+    }
+
+    fn entry(account_id: i32, amount: i64) -> FlowEntry {
+        FlowEntry {
+            date: date(2026, 3, 1),
+            account_id,
+            source_account_id: None,
+            amount: Decimal::from(amount),
+            category_id: None,
+            source_name: None,
+        }
+    }
+
+    #[test]
+    fn test_flow_report_from_income_to_categories() {
+        let categories = vec![
+            make_category(1, "Housing", None),
+            make_category(2, "Rent", Some(1)),
+            make_category(3, "Gifts", None),
+        ];
+        let entries = vec![
+            FlowEntry {
+                source_name: Some("ACME".to_string()),
+                ..entry(10, 3000)
+            },
+            FlowEntry {
+                category_id: Some(3),
+                ..entry(10, 100)
+            },
+            FlowEntry {
+                category_id: Some(2),
+                ..entry(10, -1200)
+            },
+            FlowEntry {
+                category_id: Some(1),
+                ..entry(10, -300)
+            },
+            entry(10, -50),
+            // Transfer of 500 from account 10 to account 11
+            FlowEntry {
+                source_account_id: Some(10),
+                ..entry(11, 500)
+            },
+            // Outside the range
+            FlowEntry {
+                date: date(2026, 5, 1),
+                ..entry(10, -999)
+            },
+        ];
+
+        let report = build_flow_report(&entries, &[], &categories, date(2026, 3, 1), date(2026, 3, 31));
+        let ids: Vec<&str> = report.nodes.iter().map(|node| node.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "income:ACME",
+                "income:category:3",
+                "account:10",
+                "account:11",
+                "category:1",
+                "category:uncategorized",
+            ]
+        );
+        assert_eq!(report.nodes[1].label, "Gifts");
+        assert_eq!(report.nodes[2].label, "Account 10");
+
+        let amount = |source: &str, target: &str| {
+            report
+                .edges
+                .iter()
+                .find(|edge| edge.source == source && edge.target == target)
+                .map(|edge| edge.amount)
+        };
+        assert_eq!(amount("income:ACME", "account:10"), Some(Decimal::from(3000)));
+        assert_eq!(amount("account:10", "category:1"), Some(Decimal::from(1500)));
+        assert_eq!(amount("account:10", "category:uncategorized"), Some(Decimal::from(50)));
+        assert_eq!(amount("account:10", "account:11"), Some(Decimal::from(500)));
+        assert_eq!(report.edges.len(), 5);
+    }
+
+    #[test]
+    fn test_flow_report_uses_account_names() {
+        let accounts = vec![account::Model {
+            id: 10,
+            name: "Checking".to_string(),
+            description: None,
+            currency_code: "CZK".to_string(),
+            owner_id: 1,
+            include_in_statistics: true,
+            ledger_name: None,
+            account_kind: AccountKind::RealAccount,
+            target_amount: None,
+            color: None,
+            is_liquid: true,
+            recurring_approval_threshold: None,
+            balance_interpolation: account::BalanceInterpolation::Step,
+        }];
+        let report = build_flow_report(&[entry(10, -20)], &accounts, &[], date(2026, 3, 1), date(2026, 3, 31));
+        assert_eq!(report.nodes[0].label, "Checking");
+        assert_eq!(report.nodes[1].label, UNCATEGORIZED_LABEL);
+    }
+}
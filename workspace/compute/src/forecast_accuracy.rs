@@ -0,0 +1,106 @@
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+
+/// A past projection paired with the balance the account actually reached.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForecastOutcome {
+    pub horizon_days: i32,
+    pub projected: Decimal,
+    pub actual: Decimal,
+}
+
+impl ForecastOutcome {
+    /// Positive when the projection was too optimistic.
+    pub fn error(&self) -> Decimal {
+        self.projected - self.actual
+    }
+}
+
+/// How far projections of one horizon were off.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HorizonAccuracy {
+    pub horizon_days: i32,
+    pub samples: usize,
+    /// Average signed error; positive means projections tend to be too optimistic.
+    pub mean_error: Decimal,
+    pub mean_absolute_error: Decimal,
+    /// Average absolute error relative to the actual balance, in percent.
+    /// Outcomes with a zero actual balance are left out; `None` when none remain.
+    pub mean_absolute_percentage_error: Option<Decimal>,
+}
+
+/// Summarizes outcomes per horizon, shortest horizon first.
+pub fn summarize_accuracy(outcomes: &[ForecastOutcome]) -> Vec<HorizonAccuracy> {
+    let mut by_horizon: BTreeMap<i32, Vec<&ForecastOutcome>> = BTreeMap::new();
+    for outcome in outcomes {
+        by_horizon.entry(outcome.horizon_days).or_default().push(outcome);
+    }
+
+    by_horizon
+        .into_iter()
+        .map(|(horizon_days, outcomes)| {
+            let samples = Decimal::from(outcomes.len());
+            let percentages: Vec<Decimal> = outcomes
+                .iter()
+                .filter(|outcome| !outcome.actual.is_zero())
+                .map(|outcome| (outcome.error() / outcome.actual).abs() * Decimal::ONE_HUNDRED)
+                .collect();
+
+            HorizonAccuracy {
+                horizon_days,
+                samples: outcomes.len(),
+                mean_error: (outcomes.iter().map(|outcome| outcome.error()).sum::<Decimal>() / samples).round_dp(2),
+                mean_absolute_error: (outcomes.iter().map(|outcome| outcome.error().abs()).sum::<Decimal>() / samples)
+                    .round_dp(2),
+                mean_absolute_percentage_error: (!percentages.is_empty()).then(|| {
+                    (percentages.iter().sum::<Decimal>() / Decimal::from(percentages.len())).round_dp(2)
+                }),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(horizon_days: i32, projected: i64, actual: i64) -> ForecastOutcome {
+        ForecastOutcome {
+            horizon_days,
+            projected: Decimal::from(projected),
+            actual: Decimal::from(actual),
+        }
+    }
+
+    #[test]
+    fn test_summarize_accuracy_per_horizon() {
+        let summary = summarize_accuracy(&[
+            outcome(90, 1000, 800),
+            outcome(30, 1100, 1000),
+            outcome(30, 900, 1000),
+            outcome(30, 500, 0),
+        ]);
+
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0].horizon_days, 30);
+        assert_eq!(summary[0].samples, 3);
+        assert_eq!(summary[0].mean_error, Decimal::new(16667, 2));
+        assert_eq!(summary[0].mean_absolute_error, Decimal::new(23333, 2));
+        // The outcome with a zero actual balance has no relative error
+        assert_eq!(summary[0].mean_absolute_percentage_error, Some(Decimal::from(10)));
+
+        assert_eq!(summary[1].horizon_days, 90);
+        assert_eq!(summary[1].mean_error, Decimal::from(200));
+        assert_eq!(summary[1].mean_absolute_percentage_error, Some(Decimal::from(25)));
+    }
+
+    #[test]
+    fn test_summarize_accuracy_without_relative_errors() {
+        let summary = summarize_accuracy(&[outcome(30, -50, 0)]);
+        assert_eq!(summary[0].mean_error, Decimal::from(-50));
+        assert_eq!(summary[0].mean_absolute_error, Decimal::from(50));
+        assert_eq!(summary[0].mean_absolute_percentage_error, None);
+
+        assert!(summarize_accuracy(&[]).is_empty());
+    }
+}
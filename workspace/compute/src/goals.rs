@@ -0,0 +1,184 @@
+//! Progress of savings-goal accounts towards their target amount.
+//!
+//! The projection only counts money that reliably arrives: recurring transactions and
+//! incomes paid into the account. One-off deposits speed a goal up but are not promised.
+
+use chrono::{Months, NaiveDate};
+use model::entities::{recurring_income, recurring_transaction};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter};
+
+use crate::error::Result;
+use crate::metrics::account_metrics::monthly_equivalent;
+use crate::metrics::filter_active_recurring;
+
+/// Where a goal stands on one day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoalProgress {
+    pub target_amount: Decimal,
+    pub current_balance: Decimal,
+    /// What is still missing; zero once the target is reached
+    pub remaining: Decimal,
+    /// Share of the target saved, 0-100
+    pub percent_complete: Decimal,
+    /// Recurring money paid into the account per month
+    pub monthly_inflow: Decimal,
+    /// Monthly saving needed to reach the target by the target date
+    pub required_monthly_contribution: Decimal,
+    /// When the recurring inflows reach the target; `None` if they never do
+    pub projected_completion_date: Option<NaiveDate>,
+}
+
+/// Whole months from `today` to `target_date`, counting a started month as a full one
+/// and at least one.
+fn months_until(today: NaiveDate, target_date: NaiveDate) -> u32 {
+    let mut months = 1;
+    while today
+        .checked_add_months(Months::new(months))
+        .is_some_and(|date| date < target_date)
+    {
+        months += 1;
+    }
+    months
+}
+
+/// Progress towards `target_amount` with `current_balance` saved on `today`.
+///
+/// The completion date advances by whole months of `monthly_inflow`, since recurring
+/// inflows arrive in steps rather than continuously.
+pub fn goal_progress(
+    target_amount: Decimal,
+    current_balance: Decimal,
+    monthly_inflow: Decimal,
+    today: NaiveDate,
+    target_date: NaiveDate,
+) -> GoalProgress {
+    let remaining = (target_amount - current_balance).max(Decimal::ZERO);
+    let percent_complete = if target_amount > Decimal::ZERO {
+        (current_balance / target_amount * Decimal::ONE_HUNDRED)
+            .clamp(Decimal::ZERO, Decimal::ONE_HUNDRED)
+            .round_dp(2)
+    } else {
+        Decimal::ONE_HUNDRED
+    };
+    let required_monthly_contribution = (remaining / Decimal::from(months_until(today, target_date))).round_dp(2);
+
+    let projected_completion_date = if remaining.is_zero() {
+        Some(today)
+    } else if monthly_inflow > Decimal::ZERO {
+        (remaining / monthly_inflow)
+            .ceil()
+            .to_u32()
+            .and_then(|months| today.checked_add_months(Months::new(months)))
+    } else {
+        None
+    };
+
+    GoalProgress {
+        target_amount,
+        current_balance,
+        remaining,
+        percent_complete,
+        monthly_inflow: monthly_inflow.round_dp(2),
+        required_monthly_contribution,
+        projected_completion_date,
+    }
+}
+
+/// Monthly equivalent of the recurring transactions and incomes active on `today` that
+/// pay into the account, including transfers from other accounts.
+pub async fn monthly_recurring_inflow(db: &DatabaseConnection, account_id: i32, today: NaiveDate) -> Result<Decimal> {
+    let transactions = recurring_transaction::Entity::find()
+        .filter(
+            Condition::any()
+                .add(recurring_transaction::Column::TargetAccountId.eq(account_id))
+                .add(recurring_transaction::Column::SourceAccountId.eq(account_id)),
+        )
+        .all(db)
+        .await?;
+    let from_transactions: Decimal = filter_active_recurring(&transactions, today)
+        .into_iter()
+        .filter_map(|transaction| {
+            // A transfer moves money out of its source account
+            let amount = if transaction.target_account_id == account_id {
+                transaction.amount
+            } else {
+                -transaction.amount
+            };
+            (amount > Decimal::ZERO).then(|| monthly_equivalent(amount, &transaction.period))
+        })
+        .sum();
+
+    let from_incomes: Decimal = recurring_income::Entity::find()
+        .filter(recurring_income::Column::TargetAccountId.eq(account_id))
+        .filter(recurring_income::Column::IsSimulated.eq(false))
+        .filter(recurring_income::Column::StartDate.lte(today))
+        .all(db)
+        .await?
+        .into_iter()
+        .filter(|income| income.end_date.is_none_or(|end| end >= today) && income.amount > Decimal::ZERO)
+        .map(|income| monthly_equivalent(income.amount, &income.period))
+        .sum();
+
+    Ok(from_transactions + from_incomes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_months_until() {
+        assert_eq!(months_until(date(2026, 1, 15), date(2026, 1, 20)), 1);
+        assert_eq!(months_until(date(2026, 1, 15), date(2026, 2, 15)), 1);
+        assert_eq!(months_until(date(2026, 1, 15), date(2026, 2, 16)), 2);
+        assert_eq!(months_until(date(2026, 1, 15), date(2025, 1, 1)), 1);
+    }
+
+    #[test]
+    fn test_goal_progress() {
+        let progress = goal_progress(
+            Decimal::from(10000),
+            Decimal::from(2500),
+            Decimal::from(1000),
+            date(2026, 1, 15),
+            date(2027, 1, 15),
+        );
+
+        assert_eq!(progress.remaining, Decimal::from(7500));
+        assert_eq!(progress.percent_complete, Decimal::from(25));
+        assert_eq!(progress.required_monthly_contribution, Decimal::from(625));
+        // 7.5 months of inflow need 8 deposits
+        assert_eq!(progress.projected_completion_date, Some(date(2026, 9, 15)));
+    }
+
+    #[test]
+    fn test_goal_progress_reached_or_stalled() {
+        let reached = goal_progress(
+            Decimal::from(1000),
+            Decimal::from(1200),
+            Decimal::ZERO,
+            date(2026, 1, 15),
+            date(2026, 6, 1),
+        );
+        assert_eq!(reached.remaining, Decimal::ZERO);
+        assert_eq!(reached.percent_complete, Decimal::ONE_HUNDRED);
+        assert_eq!(reached.required_monthly_contribution, Decimal::ZERO);
+        assert_eq!(reached.projected_completion_date, Some(date(2026, 1, 15)));
+
+        let stalled = goal_progress(
+            Decimal::from(1000),
+            Decimal::from(-100),
+            Decimal::ZERO,
+            date(2026, 1, 15),
+            date(2026, 6, 1),
+        );
+        assert_eq!(stalled.percent_complete, Decimal::ZERO);
+        assert_eq!(stalled.projected_completion_date, None);
+    }
+}
@@ -0,0 +1,270 @@
+//! Public holidays of country calendars and custom dates.
+//!
+//! Like [`crate::recurrence`], this module only depends on `chrono`, so it is available
+//! without the `database` feature.
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+
+/// Country whose public holidays are built in.
+///
+/// Only nationwide holidays are included, on their actual dates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HolidayCountry {
+    /// Austria
+    AT,
+    /// Czech Republic
+    CZ,
+    /// Germany
+    DE,
+    /// United States, federal holidays
+    US,
+}
+
+impl HolidayCountry {
+    pub const ALL: [HolidayCountry; 4] = [HolidayCountry::AT, HolidayCountry::CZ, HolidayCountry::DE, HolidayCountry::US];
+
+    /// ISO 3166-1 alpha-2 code of the country.
+    pub fn code(&self) -> &'static str {
+        match self {
+            HolidayCountry::AT => "AT",
+            HolidayCountry::CZ => "CZ",
+            HolidayCountry::DE => "DE",
+            HolidayCountry::US => "US",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HolidayCountry::AT => "Austria",
+            HolidayCountry::CZ => "Czech Republic",
+            HolidayCountry::DE => "Germany",
+            HolidayCountry::US => "United States",
+        }
+    }
+
+    /// Public holidays of the country in `year`, ordered by date.
+    pub fn holidays(&self, year: i32) -> Vec<(NaiveDate, &'static str)> {
+        let fixed = |month: u32, day: u32, name: &'static str| NaiveDate::from_ymd_opt(year, month, day).map(|date| (date, name));
+        let easter = easter_sunday(year);
+        let from_easter = |days: i64, name: &'static str| Some((easter + Duration::days(days), name));
+        let nth_weekday = |month: u32, weekday: Weekday, n: u8, name: &'static str| {
+            NaiveDate::from_weekday_of_month_opt(year, month, weekday, n).map(|date| (date, name))
+        };
+
+        let mut holidays: Vec<(NaiveDate, &'static str)> = match self {
+            HolidayCountry::AT => vec![
+                fixed(1, 1, "New Year's Day"),
+                fixed(1, 6, "Epiphany"),
+                from_easter(1, "Easter Monday"),
+                fixed(5, 1, "Labour Day"),
+                from_easter(39, "Ascension Day"),
+                from_easter(50, "Whit Monday"),
+                from_easter(60, "Corpus Christi"),
+                fixed(8, 15, "Assumption Day"),
+                fixed(10, 26, "National Day"),
+                fixed(11, 1, "All Saints' Day"),
+                fixed(12, 8, "Immaculate Conception"),
+                fixed(12, 25, "Christmas Day"),
+                fixed(12, 26, "St. Stephen's Day"),
+            ],
+            HolidayCountry::CZ => vec![
+                fixed(1, 1, "Restoration Day of the Independent Czech State"),
+                from_easter(-2, "Good Friday"),
+                from_easter(1, "Easter Monday"),
+                fixed(5, 1, "Labour Day"),
+                fixed(5, 8, "Liberation Day"),
+                fixed(7, 5, "Saints Cyril and Methodius Day"),
+                fixed(7, 6, "Jan Hus Day"),
+                fixed(9, 28, "St. Wenceslas Day"),
+                fixed(10, 28, "Independent Czechoslovak State Day"),
+                fixed(11, 17, "Struggle for Freedom and Democracy Day"),
+                fixed(12, 24, "Christmas Eve"),
+                fixed(12, 25, "Christmas Day"),
+                fixed(12, 26, "St. Stephen's Day"),
+            ],
+            HolidayCountry::DE => vec![
+                fixed(1, 1, "New Year's Day"),
+                from_easter(-2, "Good Friday"),
+                from_easter(1, "Easter Monday"),
+                fixed(5, 1, "Labour Day"),
+                from_easter(39, "Ascension Day"),
+                from_easter(50, "Whit Monday"),
+                fixed(10, 3, "German Unity Day"),
+                fixed(12, 25, "Christmas Day"),
+                fixed(12, 26, "Boxing Day"),
+            ],
+            HolidayCountry::US => vec![
+                fixed(1, 1, "New Year's Day"),
+                nth_weekday(1, Weekday::Mon, 3, "Martin Luther King Jr. Day"),
+                nth_weekday(2, Weekday::Mon, 3, "Washington's Birthday"),
+                last_weekday_of_month(year, 5, Weekday::Mon).map(|date| (date, "Memorial Day")),
+                fixed(6, 19, "Juneteenth").filter(|_| year >= 2021),
+                fixed(7, 4, "Independence Day"),
+                nth_weekday(9, Weekday::Mon, 1, "Labor Day"),
+                nth_weekday(10, Weekday::Mon, 2, "Columbus Day"),
+                fixed(11, 11, "Veterans Day"),
+                nth_weekday(11, Weekday::Thu, 4, "Thanksgiving Day"),
+                fixed(12, 25, "Christmas Day"),
+            ],
+        }
+        .into_iter()
+        .flatten()
+        .collect();
+        holidays.sort();
+        holidays
+    }
+}
+
+impl fmt::Display for HolidayCountry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for HolidayCountry {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        HolidayCountry::ALL
+            .into_iter()
+            .find(|country| country.code().eq_ignore_ascii_case(value.trim()))
+            .ok_or_else(|| format!("No holiday calendar for country: {}", value))
+    }
+}
+
+/// Easter Sunday of `year` in the Gregorian calendar.
+pub fn easter_sunday(year: i32) -> NaiveDate {
+    // Anonymous Gregorian algorithm
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32).unwrap()
+}
+
+fn last_weekday_of_month(year: i32, month: u32, weekday: Weekday) -> Option<NaiveDate> {
+    let mut date = NaiveDate::from_ymd_opt(year, month, 1)?
+        .checked_add_months(chrono::Months::new(1))?
+        .pred_opt()?;
+    while date.weekday() != weekday {
+        date = date.pred_opt()?;
+    }
+    Some(date)
+}
+
+/// Days without business: the holidays of the enabled countries plus custom dates.
+///
+/// The default calendar has no holidays, leaving only weekends as non-business days.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HolidayCalendar {
+    countries: BTreeSet<HolidayCountry>,
+    custom: BTreeMap<NaiveDate, String>,
+}
+
+impl HolidayCalendar {
+    pub fn new(
+        countries: impl IntoIterator<Item = HolidayCountry>,
+        custom: impl IntoIterator<Item = (NaiveDate, String)>,
+    ) -> Self {
+        Self {
+            countries: countries.into_iter().collect(),
+            custom: custom.into_iter().collect(),
+        }
+    }
+
+    pub fn countries(&self) -> impl Iterator<Item = HolidayCountry> + '_ {
+        self.countries.iter().copied()
+    }
+
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.custom.contains_key(&date)
+            || self
+                .countries
+                .iter()
+                .any(|country| country.holidays(date.year()).iter().any(|(holiday, _)| *holiday == date))
+    }
+
+    /// Monday to Friday, except holidays.
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !self.is_holiday(date)
+    }
+
+    /// The closest business day on or after `date`, or on or before it when not `forward`.
+    pub fn business_day(&self, date: NaiveDate, forward: bool) -> NaiveDate {
+        let step = Duration::days(if forward { 1 } else { -1 });
+        let mut date = date;
+        while !self.is_business_day(date) {
+            date += step;
+        }
+        date
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_easter_sunday() {
+        assert_eq!(easter_sunday(2024), date(2024, 3, 31));
+        assert_eq!(easter_sunday(2025), date(2025, 4, 20));
+        assert_eq!(easter_sunday(2026), date(2026, 4, 5));
+        assert_eq!(easter_sunday(2038), date(2038, 4, 25));
+    }
+
+    #[test]
+    fn test_country_holidays() {
+        let cz = HolidayCountry::CZ.holidays(2025);
+        assert_eq!(cz.len(), 13);
+        assert!(cz.contains(&(date(2025, 4, 18), "Good Friday")));
+        assert!(cz.contains(&(date(2025, 12, 24), "Christmas Eve")));
+
+        let us: Vec<NaiveDate> = HolidayCountry::US.holidays(2025).into_iter().map(|(date, _)| date).collect();
+        assert!(us.contains(&date(2025, 1, 20)));
+        assert!(us.contains(&date(2025, 5, 26)));
+        assert!(us.contains(&date(2025, 11, 27)));
+        assert!(!HolidayCountry::US.holidays(2020).iter().any(|(_, name)| *name == "Juneteenth"));
+
+        let de: Vec<NaiveDate> = HolidayCountry::DE.holidays(2025).into_iter().map(|(date, _)| date).collect();
+        assert!(de.contains(&date(2025, 5, 29)));
+        assert!(de.contains(&date(2025, 6, 9)));
+    }
+
+    #[test]
+    fn test_parse_country() {
+        assert_eq!("cz".parse::<HolidayCountry>(), Ok(HolidayCountry::CZ));
+        assert_eq!(HolidayCountry::AT.code().parse::<HolidayCountry>(), Ok(HolidayCountry::AT));
+        assert!("XX".parse::<HolidayCountry>().is_err());
+    }
+
+    #[test]
+    fn test_business_days() {
+        let calendar = HolidayCalendar::new([HolidayCountry::CZ], [(date(2025, 12, 29), "Office closed".to_string())]);
+        assert!(calendar.is_holiday(date(2025, 12, 24)));
+        assert!(calendar.is_holiday(date(2025, 12, 29)));
+        assert!(!calendar.is_business_day(date(2025, 12, 27)));
+        assert!(calendar.is_business_day(date(2025, 12, 23)));
+
+        // Christmas holidays, a weekend and the custom day follow December 23rd
+        assert_eq!(calendar.business_day(date(2025, 12, 24), true), date(2025, 12, 30));
+        assert_eq!(calendar.business_day(date(2025, 12, 28), false), date(2025, 12, 23));
+        assert!(HolidayCalendar::default().is_business_day(date(2025, 12, 24)));
+    }
+}
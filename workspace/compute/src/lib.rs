@@ -1,38 +1,111 @@
+#[cfg(feature = "database")]
 pub mod account;
+#[cfg(feature = "database")]
 pub mod account_stats;
+#[cfg(feature = "database")]
+pub mod assets;
+#[cfg(feature = "database")]
+pub mod balance_forecast;
+#[cfg(feature = "database")]
+pub mod budgets;
+#[cfg(feature = "database")]
+pub mod cashflow_report;
+#[cfg(feature = "database")]
+pub mod cashflow_smoothing;
+#[cfg(feature = "database")]
 pub mod categories;
+#[cfg(feature = "database")]
+pub mod category_forecast;
+#[cfg(feature = "database")]
+pub mod category_suggestions;
+#[cfg(feature = "database")]
+pub mod category_stats;
+#[cfg(feature = "database")]
+pub mod comparison;
+#[cfg(feature = "database")]
+pub mod contracts;
+#[cfg(feature = "database")]
+pub mod due_profile;
+#[cfg(feature = "database")]
 pub mod error;
+#[cfg(feature = "database")]
+pub mod flows;
+#[cfg(feature = "database")]
+pub mod forecast_accuracy;
+#[cfg(feature = "database")]
+pub mod goals;
+pub mod holidays;
+#[cfg(feature = "database")]
 pub mod metrics;
+#[cfg(feature = "database")]
+pub mod networth;
+#[cfg(feature = "database")]
+pub mod overdue;
+pub mod preview;
+pub mod recurrence;
+pub mod recurrence_rule;
+#[cfg(feature = "database")]
+pub mod recurring_amounts;
+#[cfg(feature = "database")]
+pub mod recurring_detection;
+#[cfg(feature = "database")]
+pub mod safe_to_spend;
+#[cfg(feature = "database")]
 pub mod tags;
+#[cfg(feature = "database")]
 pub mod transaction;
+#[cfg(feature = "database")]
+pub mod warranties;
 
+#[cfg(feature = "database")]
 use crate::account::cache::AccountStateCacheCalculator;
+#[cfg(feature = "database")]
 use crate::account::AccountStateCalculator;
+#[cfg(feature = "database")]
 use account::{
     balance::BalanceCalculator, merge::MergeCalculator, unpaid_recurring::UnpaidRecurringCalculator,
     MergeMethod,
 };
-use chrono::{NaiveDate, Utc};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+#[cfg(feature = "database")]
 use std::time::Duration;
 
+/// The calendar date in `timezone` at the instant `now`.
+///
+/// "Today" depends on where the user is: shortly after midnight in Prague it is still the
+/// previous day in UTC, so anything due today would show up a day late if the date was
+/// taken from UTC.
+pub fn date_in<Tz: TimeZone>(now: DateTime<Utc>, timezone: &Tz) -> NaiveDate {
+    now.with_timezone(timezone).date_naive()
+}
+
+/// The current date in `timezone`, to pass as "today" to the compute entry points.
+pub fn today_in<Tz: TimeZone>(timezone: &Tz) -> NaiveDate {
+    date_in(Utc::now(), timezone)
+}
+
+#[cfg(feature = "database")]
+#[cfg(feature = "database")]
 /// Returns a default pre-configured compute instance that will be used most of the time.
 ///
-/// This function uses the provided date as "today" or the current date if none is provided.
+/// This function uses the provided date as "today" or the current UTC date if none is provided.
+/// Callers serving a user should pass the user's date, see [`today_in`].
 /// It has the same configuration as the one used in the `test_scenario_merge_real` test.
 pub fn default_compute(today: Option<NaiveDate>) -> impl AccountStateCalculator {
     default_compute_with_scenario(today, None)
 }
 
+#[cfg(feature = "database")]
 /// Returns a default pre-configured compute instance with scenario support.
 ///
-/// This function uses the provided date as "today" or the current date if none is provided.
+/// This function uses the provided date as "today" or the current UTC date if none is provided.
 /// If a scenario_id is provided, it will include simulated transactions belonging to that scenario.
 pub fn default_compute_with_scenario(
     today: Option<NaiveDate>,
     scenario_id: Option<i32>,
 ) -> impl AccountStateCalculator {
     // Create the today date
-    let today = today.unwrap_or_else(|| Utc::now().date_naive());
+    let today = today.unwrap_or_else(|| today_in(&Utc));
 
     // Create the balance calculator with optional scenario context
     let balance_calculator = match scenario_id {
@@ -63,12 +136,47 @@ pub fn default_compute_with_scenario(
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "database")]
     use account::testing::{run_and_assert_scenario, ScenarioMergeReal};
-    use tokio;
+    use chrono::FixedOffset;
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0).unwrap()
+    }
+
+    /// Just after midnight east of UTC it is already the next day locally.
+    #[test]
+    fn test_date_in_east_of_utc_after_midnight() {
+        let prague_summer = FixedOffset::east_opt(2 * 3600).unwrap();
+        let now = utc(2026, 6, 21, 22, 30);
+
+        assert_eq!(date_in(now, &Utc), NaiveDate::from_ymd_opt(2026, 6, 21).unwrap());
+        assert_eq!(date_in(now, &prague_summer), NaiveDate::from_ymd_opt(2026, 6, 22).unwrap());
+    }
+
+    /// Just after midnight UTC it is still the previous day west of UTC.
+    #[test]
+    fn test_date_in_west_of_utc_before_midnight() {
+        let new_york_winter = FixedOffset::west_opt(5 * 3600).unwrap();
+        let now = utc(2026, 1, 1, 3, 0);
+
+        assert_eq!(date_in(now, &Utc), NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+        assert_eq!(date_in(now, &new_york_winter), NaiveDate::from_ymd_opt(2025, 12, 31).unwrap());
+    }
+
+    /// Exactly at local midnight the new day has started.
+    #[test]
+    fn test_date_in_at_local_midnight() {
+        let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+
+        assert_eq!(date_in(utc(2026, 3, 31, 14, 59), &tokyo), NaiveDate::from_ymd_opt(2026, 3, 31).unwrap());
+        assert_eq!(date_in(utc(2026, 3, 31, 15, 0), &tokyo), NaiveDate::from_ymd_opt(2026, 4, 1).unwrap());
+    }
 
     /// Test using the default compute with the real_merge scenario within range.
     /// This test verifies that the default compute works correctly with a scenario
     /// that is within the expected date range.
+    #[cfg(feature = "database")]
     #[tokio::test]
     async fn test_default_compute_within_range() {
         let scenario = ScenarioMergeReal::new();
@@ -84,6 +192,7 @@ mod tests {
     /// Test using the default compute with the real_merge scenario outside range.
     /// This test verifies that the default compute works correctly with a scenario
     /// that is outside the expected date range.
+    #[cfg(feature = "database")]
     #[tokio::test]
     async fn test_default_compute_outside_range() {
         let scenario = ScenarioMergeReal::new();
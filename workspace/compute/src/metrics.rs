@@ -14,7 +14,7 @@ pub mod cross_account_metrics;
 ///
 /// A transaction is active when `start_date <= date` and either `end_date`
 /// is `None` (open-ended) or `end_date >= date`. Simulated transactions
-/// (scenario what-if entries) are always excluded.
+/// (scenario what-if entries) and rules not yet approved are always excluded.
 pub fn filter_active_recurring(
     transactions: &[recurring_transaction::Model],
     date: NaiveDate,
@@ -23,6 +23,7 @@ pub fn filter_active_recurring(
         .iter()
         .filter(|r| {
             !r.is_simulated
+                && r.approval_status == recurring_transaction::ApprovalStatus::Approved
                 && r.start_date <= date
                 && r.end_date.map_or(true, |end| end >= date)
         })
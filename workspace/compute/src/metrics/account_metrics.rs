@@ -697,6 +697,18 @@ mod tests {
             ledger_name: None,
             scenario_id: None,
             is_simulated: false,
+            approval_status: recurring_transaction::ApprovalStatus::Approved,
+            created_by_user_id: None,
+            reviewed_by_user_id: None,
+            notice_period_days: None,
+            renewal_date: None,
+            fx_mode: None,
+            fx_rate: None,
+            source_amount: None,
+            amount_strategy: recurring_transaction::AmountStrategy::Fixed,
+            amount_history_count: None,
+            rrule: None,
+            date_adjustment: recurring_transaction::DateAdjustment::None,
         }
     }
 
@@ -745,4 +757,23 @@ mod tests {
             || (tx.source_account_id == Some(debt_account_id) && tx.amount.is_sign_negative());
         assert!(!is_payment);
     }
+
+    #[test]
+    fn test_filter_active_recurring_skips_unapproved() {
+        let today = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+        let approved = make_recurring(1, Decimal::from(-100), 1, None);
+        let pending = recurring_transaction::Model {
+            approval_status: recurring_transaction::ApprovalStatus::PendingApproval,
+            ..make_recurring(2, Decimal::from(-5000), 1, None)
+        };
+        let rejected = recurring_transaction::Model {
+            approval_status: recurring_transaction::ApprovalStatus::Rejected,
+            ..make_recurring(3, Decimal::from(-5000), 1, None)
+        };
+
+        let all = vec![approved, pending, rejected];
+        let active = filter_active_recurring(&all, today);
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].id, 1);
+    }
 }
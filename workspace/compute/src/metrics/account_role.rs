@@ -532,6 +532,8 @@ mod tests {
             target_amount: target,
             color: None,
             is_liquid: true,
+            recurring_approval_threshold: None,
+            balance_interpolation: account::BalanceInterpolation::Step,
         }
     }
 
@@ -400,10 +400,22 @@ pub async fn compute_dashboard_metrics(
     calculator: &dyn AccountStateCalculator,
     db: &DatabaseConnection,
     today: NaiveDate,
+) -> Result<DashboardMetricsDto> {
+    let all_accounts: Vec<account::Model> = account::Entity::find().all(db).await?;
+    compute_dashboard_metrics_for_accounts(calculator, db, all_accounts, today).await
+}
+
+/// Computes the dashboard over `all_accounts` only, as [`compute_dashboard_metrics`]
+/// does over every account. Recurring transactions not touching any of them are ignored.
+#[instrument(skip(calculator, db, all_accounts))]
+pub async fn compute_dashboard_metrics_for_accounts(
+    calculator: &dyn AccountStateCalculator,
+    db: &DatabaseConnection,
+    all_accounts: Vec<account::Model>,
+    today: NaiveDate,
 ) -> Result<DashboardMetricsDto> {
     debug!("Computing dashboard metrics (batch strategy)");
 
-    let all_accounts: Vec<account::Model> = account::Entity::find().all(db).await?;
     trace!(account_count = all_accounts.len(), "Fetched accounts for dashboard");
 
     if all_accounts.is_empty() {
@@ -588,8 +600,15 @@ pub async fn compute_dashboard_metrics(
         .sum();
 
     // Recurring transactions for burn-rate calculations
-    let all_recurring_raw: Vec<recurring_transaction::Model> =
-        recurring_transaction::Entity::find().all(db).await?;
+    let account_ids: Vec<i32> = all_accounts.iter().map(|a| a.id).collect();
+    let all_recurring_raw: Vec<recurring_transaction::Model> = recurring_transaction::Entity::find()
+        .filter(
+            Condition::any()
+                .add(recurring_transaction::Column::TargetAccountId.is_in(account_ids.clone()))
+                .add(recurring_transaction::Column::SourceAccountId.is_in(account_ids)),
+        )
+        .all(db)
+        .await?;
     let all_recurring = filter_active_recurring(&all_recurring_raw, today);
 
     trace!(
@@ -0,0 +1,84 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+
+/// Combined balance of a set of accounts on one day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetWorthPoint {
+    pub date: NaiveDate,
+    pub net_worth: Decimal,
+}
+
+/// Sums daily account balances into one series, earliest day first.
+///
+/// Liabilities are expected to carry negative balances, so they reduce the sum.
+pub fn sum_by_date(balances: impl IntoIterator<Item = (NaiveDate, Decimal)>) -> Vec<NetWorthPoint> {
+    let mut by_date: BTreeMap<NaiveDate, Decimal> = BTreeMap::new();
+    for (date, balance) in balances {
+        *by_date.entry(date).or_default() += balance;
+    }
+
+    by_date
+        .into_iter()
+        .map(|(date, net_worth)| NetWorthPoint { date, net_worth })
+        .collect()
+}
+
+/// Sums daily account balances into one series per group, ordered by group.
+pub fn sum_by_group<K: Ord>(
+    balances: impl IntoIterator<Item = (K, NaiveDate, Decimal)>,
+) -> BTreeMap<K, Vec<NetWorthPoint>> {
+    let mut by_group: BTreeMap<K, Vec<(NaiveDate, Decimal)>> = BTreeMap::new();
+    for (group, date, balance) in balances {
+        by_group.entry(group).or_default().push((date, balance));
+    }
+
+    by_group
+        .into_iter()
+        .map(|(group, balances)| (group, sum_by_date(balances)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+    }
+
+    #[test]
+    fn test_sum_by_date_nets_liabilities() {
+        let series = sum_by_date([
+            (day(2), Decimal::from(1000)),
+            (day(1), Decimal::from(900)),
+            (day(2), Decimal::from(-250)),
+            (day(1), Decimal::from(-300)),
+        ]);
+
+        assert_eq!(
+            series,
+            vec![
+                NetWorthPoint { date: day(1), net_worth: Decimal::from(600) },
+                NetWorthPoint { date: day(2), net_worth: Decimal::from(750) },
+            ]
+        );
+        assert!(sum_by_date([]).is_empty());
+    }
+
+    #[test]
+    fn test_sum_by_group() {
+        let groups = sum_by_group([
+            ("Savings", day(1), Decimal::from(100)),
+            ("Debt", day(1), Decimal::from(-40)),
+            ("Savings", day(1), Decimal::from(50)),
+            ("Savings", day(2), Decimal::from(160)),
+        ]);
+
+        assert_eq!(groups.keys().copied().collect::<Vec<_>>(), vec!["Debt", "Savings"]);
+        assert_eq!(groups["Debt"], vec![NetWorthPoint { date: day(1), net_worth: Decimal::from(-40) }]);
+        assert_eq!(groups["Savings"].len(), 2);
+        assert_eq!(groups["Savings"][0].net_worth, Decimal::from(150));
+        assert_eq!(groups["Savings"][1].net_worth, Decimal::from(160));
+    }
+}
@@ -0,0 +1,109 @@
+use chrono::NaiveDate;
+
+/// Default days overdue at which a missing payment escalates to the next severity.
+pub const DEFAULT_OVERDUE_THRESHOLDS: [i64; 3] = [3, 7, 30];
+
+/// How urgent a missing recurring payment is, escalating with the days overdue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OverdueSeverity {
+    /// Below the first threshold
+    Due,
+    Notice,
+    Warning,
+    Critical,
+}
+
+/// Ascending days overdue at which the severity escalates: crossing the first
+/// threshold is a `Notice`, the second a `Warning` and the third `Critical`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverdueThresholds(Vec<i64>);
+
+impl Default for OverdueThresholds {
+    fn default() -> Self {
+        Self(DEFAULT_OVERDUE_THRESHOLDS.to_vec())
+    }
+}
+
+impl OverdueThresholds {
+    /// Accepts one to three strictly ascending, positive thresholds.
+    pub fn new(days: Vec<i64>) -> Result<Self, String> {
+        if days.is_empty() || days.len() > DEFAULT_OVERDUE_THRESHOLDS.len() {
+            return Err(format!(
+                "Expected between 1 and {} overdue thresholds, got {}",
+                DEFAULT_OVERDUE_THRESHOLDS.len(),
+                days.len()
+            ));
+        }
+        if days[0] <= 0 || days.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err("Overdue thresholds must be positive and strictly ascending".to_string());
+        }
+        Ok(Self(days))
+    }
+
+    /// Parses a comma separated list of days, e.g. "3,7,30".
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let days = value
+            .split(',')
+            .map(|part| {
+                part.trim()
+                    .parse::<i64>()
+                    .map_err(|_| format!("Invalid overdue threshold '{}'", part.trim()))
+            })
+            .collect::<Result<Vec<i64>, String>>()?;
+        Self::new(days)
+    }
+
+    pub fn days(&self) -> &[i64] {
+        &self.0
+    }
+
+    pub fn severity(&self, overdue_days: i64) -> OverdueSeverity {
+        match self.0.iter().filter(|threshold| overdue_days >= **threshold).count() {
+            0 => OverdueSeverity::Due,
+            1 => OverdueSeverity::Notice,
+            2 => OverdueSeverity::Warning,
+            _ => OverdueSeverity::Critical,
+        }
+    }
+}
+
+/// Days between `due_date` and `today`; zero for payments not yet due.
+pub fn overdue_days(due_date: NaiveDate, today: NaiveDate) -> i64 {
+    (today - due_date).num_days().max(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_severity_escalates_with_default_thresholds() {
+        let thresholds = OverdueThresholds::default();
+        assert_eq!(thresholds.severity(0), OverdueSeverity::Due);
+        assert_eq!(thresholds.severity(2), OverdueSeverity::Due);
+        assert_eq!(thresholds.severity(3), OverdueSeverity::Notice);
+        assert_eq!(thresholds.severity(7), OverdueSeverity::Warning);
+        assert_eq!(thresholds.severity(29), OverdueSeverity::Warning);
+        assert_eq!(thresholds.severity(400), OverdueSeverity::Critical);
+    }
+
+    #[test]
+    fn test_parse_thresholds() {
+        let thresholds = OverdueThresholds::parse("1, 14").unwrap();
+        assert_eq!(thresholds.days(), &[1, 14]);
+        assert_eq!(thresholds.severity(20), OverdueSeverity::Warning);
+
+        assert!(OverdueThresholds::parse("").is_err());
+        assert!(OverdueThresholds::parse("7,3").is_err());
+        assert!(OverdueThresholds::parse("0,3").is_err());
+        assert!(OverdueThresholds::parse("1,2,3,4").is_err());
+        assert!(OverdueThresholds::parse("three").is_err());
+    }
+
+    #[test]
+    fn test_overdue_days() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+        assert_eq!(overdue_days(NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(), today), 14);
+        assert_eq!(overdue_days(NaiveDate::from_ymd_opt(2026, 10, 20).unwrap(), today), 0);
+    }
+}
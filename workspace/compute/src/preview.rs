@@ -0,0 +1,203 @@
+//! Balance impact of a transaction that is being edited, before it is saved.
+//!
+//! The frontend runs this on every keystroke against the balances it already has, the
+//! server runs the same code against freshly computed balances for the authoritative
+//! numbers. Neither side touches the database here.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use crate::recurrence::{self, Recurrence};
+
+/// A one-off or recurring transaction as entered in a form.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedTransaction {
+    /// Signed amount as stored, negative for expenses
+    pub amount: Decimal,
+    /// Date of the transaction, or of the first occurrence when recurring
+    pub date: NaiveDate,
+    /// `None` for a one-off transaction
+    pub recurrence: Option<Recurrence>,
+    /// Last date a recurring transaction may occur on
+    pub end_date: Option<NaiveDate>,
+}
+
+impl PlannedTransaction {
+    pub fn one_off(amount: Decimal, date: NaiveDate) -> Self {
+        Self {
+            amount,
+            date,
+            recurrence: None,
+            end_date: None,
+        }
+    }
+
+    pub fn recurring(amount: Decimal, date: NaiveDate, recurrence: Recurrence, end_date: Option<NaiveDate>) -> Self {
+        Self {
+            amount,
+            date,
+            recurrence: Some(recurrence),
+            end_date,
+        }
+    }
+
+    /// Dates the transaction occurs on, up to and including `until`.
+    pub fn dates_until(&self, until: NaiveDate) -> Vec<NaiveDate> {
+        match self.recurrence {
+            None if self.date <= until => vec![self.date],
+            None => Vec::new(),
+            Some(recurrence) => recurrence::occurrences(self.date, self.end_date, recurrence, self.date, until),
+        }
+    }
+}
+
+/// Balance on one day without and with the change.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreviewPoint {
+    pub date: NaiveDate,
+    pub before: Decimal,
+    pub after: Decimal,
+}
+
+/// Result of [`preview_balance`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BalancePreview {
+    pub points: Vec<PreviewPoint>,
+    /// Balance on the last day, without the change
+    pub end_before: Decimal,
+    /// Balance on the last day, with the change
+    pub end_after: Decimal,
+    /// Lowest balance with the change and the first day it is reached
+    pub lowest_after: Option<(NaiveDate, Decimal)>,
+}
+
+impl BalancePreview {
+    /// How much the change moves the balance on the last day.
+    pub fn end_difference(&self) -> Decimal {
+        self.end_after - self.end_before
+    }
+}
+
+/// Signed amount per date, sorted by date.
+fn deltas(transaction: Option<&PlannedTransaction>, sign: Decimal, until: NaiveDate) -> Vec<(NaiveDate, Decimal)> {
+    transaction
+        .map(|transaction| {
+            transaction
+                .dates_until(until)
+                .into_iter()
+                .map(|date| (date, transaction.amount * sign))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Applies an edit to a daily balance series.
+///
+/// `balances` are the current balances of the account sorted by date, `original` is the
+/// saved version of the transaction being edited (which the balances already include)
+/// and `edited` is the version from the form. Pass `None` as `original` for a new
+/// transaction, or as `edited` to preview a deletion. Occurrences before the first day
+/// move every point, as the balance carries them forward.
+pub fn preview_balance(
+    balances: &[(NaiveDate, Decimal)],
+    original: Option<&PlannedTransaction>,
+    edited: Option<&PlannedTransaction>,
+) -> BalancePreview {
+    let Some(&(last_date, _)) = balances.last() else {
+        return BalancePreview::default();
+    };
+
+    let mut changes = deltas(original, Decimal::NEGATIVE_ONE, last_date);
+    changes.extend(deltas(edited, Decimal::ONE, last_date));
+    changes.sort_by_key(|(date, _)| *date);
+
+    let mut preview = BalancePreview::default();
+    let mut pending = changes.into_iter().peekable();
+    let mut difference = Decimal::ZERO;
+    for &(date, before) in balances {
+        while let Some((_, amount)) = pending.next_if(|(change_date, _)| *change_date <= date) {
+            difference += amount;
+        }
+        let after = before + difference;
+        if preview.lowest_after.is_none_or(|(_, lowest)| after < lowest) {
+            preview.lowest_after = Some((date, after));
+        }
+        preview.points.push(PreviewPoint { date, before, after });
+    }
+
+    if let Some(last) = preview.points.last() {
+        preview.end_before = last.before;
+        preview.end_after = last.after;
+    }
+    preview
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    /// A flat balance of 1000 for every day of January 2025.
+    fn january() -> Vec<(NaiveDate, Decimal)> {
+        (1..=31).map(|day| (date(2025, 1, day), Decimal::from(1000))).collect()
+    }
+
+    #[test]
+    fn test_new_one_off_transaction() {
+        let expense = PlannedTransaction::one_off(Decimal::from(-300), date(2025, 1, 10));
+        let preview = preview_balance(&january(), None, Some(&expense));
+
+        assert_eq!(preview.points[8].after, Decimal::from(1000));
+        assert_eq!(preview.points[9].after, Decimal::from(700));
+        assert_eq!(preview.end_before, Decimal::from(1000));
+        assert_eq!(preview.end_after, Decimal::from(700));
+        assert_eq!(preview.end_difference(), Decimal::from(-300));
+        assert_eq!(preview.lowest_after, Some((date(2025, 1, 10), Decimal::from(700))));
+    }
+
+    #[test]
+    fn test_edit_moves_and_changes_amount() {
+        let original = PlannedTransaction::one_off(Decimal::from(-100), date(2025, 1, 5));
+        let edited = PlannedTransaction::one_off(Decimal::from(-250), date(2025, 1, 20));
+        let preview = preview_balance(&january(), Some(&original), Some(&edited));
+
+        // The original is taken out from its date, the edited version added from its own
+        assert_eq!(preview.points[3].after, Decimal::from(1000));
+        assert_eq!(preview.points[4].after, Decimal::from(1100));
+        assert_eq!(preview.points[19].after, Decimal::from(850));
+        assert_eq!(preview.end_difference(), Decimal::from(-150));
+    }
+
+    #[test]
+    fn test_deletion_and_past_occurrences() {
+        let original = PlannedTransaction::one_off(Decimal::from(-40), date(2024, 12, 15));
+        let preview = preview_balance(&january(), Some(&original), None);
+
+        assert!(preview.points.iter().all(|point| point.after == Decimal::from(1040)));
+    }
+
+    #[test]
+    fn test_recurring_weekly_with_end_date() {
+        let weekly = PlannedTransaction::recurring(
+            Decimal::from(-50),
+            date(2025, 1, 1),
+            Recurrence::Weekly,
+            Some(date(2025, 1, 15)),
+        );
+        let preview = preview_balance(&january(), None, Some(&weekly));
+
+        assert_eq!(preview.points[0].after, Decimal::from(950));
+        assert_eq!(preview.points[7].after, Decimal::from(900));
+        assert_eq!(preview.points[14].after, Decimal::from(850));
+        assert_eq!(preview.end_after, Decimal::from(850));
+    }
+
+    #[test]
+    fn test_empty_balances() {
+        let expense = PlannedTransaction::one_off(Decimal::from(-300), date(2025, 1, 10));
+        assert_eq!(preview_balance(&[], None, Some(&expense)), BalancePreview::default());
+    }
+}
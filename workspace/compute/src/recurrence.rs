@@ -0,0 +1,278 @@
+//! Recurrence date arithmetic without database types.
+//!
+//! This module only depends on `chrono`, so it is available without the `database`
+//! feature and is shared with the WASM frontend.
+
+use chrono::{Datelike, Duration, NaiveDate};
+use std::fmt;
+use std::str::FromStr;
+
+/// How often a recurring transaction or income repeats.
+///
+/// Mirrors `RecurrencePeriod` of the model, using the same string values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recurrence {
+    Daily,
+    Weekly,
+    /// Monday-Friday
+    WorkDay,
+    Monthly,
+    Quarterly,
+    HalfYearly,
+    Yearly,
+}
+
+impl Recurrence {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Recurrence::Daily => "Daily",
+            Recurrence::Weekly => "Weekly",
+            Recurrence::WorkDay => "WorkDay",
+            Recurrence::Monthly => "Monthly",
+            Recurrence::Quarterly => "Quarterly",
+            Recurrence::HalfYearly => "HalfYearly",
+            Recurrence::Yearly => "Yearly",
+        }
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Recurrence {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "Daily" => Ok(Recurrence::Daily),
+            "Weekly" => Ok(Recurrence::Weekly),
+            "WorkDay" => Ok(Recurrence::WorkDay),
+            "Monthly" => Ok(Recurrence::Monthly),
+            "Quarterly" => Ok(Recurrence::Quarterly),
+            "HalfYearly" => Ok(Recurrence::HalfYearly),
+            "Yearly" => Ok(Recurrence::Yearly),
+            _ => Err(format!("Invalid recurrence period: {}", value)),
+        }
+    }
+}
+
+#[cfg(feature = "database")]
+impl From<&model::entities::recurring_transaction::RecurrencePeriod> for Recurrence {
+    fn from(period: &model::entities::recurring_transaction::RecurrencePeriod) -> Self {
+        use model::entities::recurring_transaction::RecurrencePeriod;
+        match period {
+            RecurrencePeriod::Daily => Recurrence::Daily,
+            RecurrencePeriod::Weekly => Recurrence::Weekly,
+            RecurrencePeriod::WorkDay => Recurrence::WorkDay,
+            RecurrencePeriod::Monthly => Recurrence::Monthly,
+            RecurrencePeriod::Quarterly => Recurrence::Quarterly,
+            RecurrencePeriod::HalfYearly => Recurrence::HalfYearly,
+            RecurrencePeriod::Yearly => Recurrence::Yearly,
+        }
+    }
+}
+
+/// Returns the number of days in the given month.
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    let next_month_year = year + (month / 12) as i32;
+    let next_month = (month % 12) + 1;
+    NaiveDate::from_ymd_opt(next_month_year, next_month, 1)
+        .and_then(|first_day| first_day.pred_opt())
+        .map(|last_day| last_day.day())
+        .unwrap_or(28)
+}
+
+/// Moves `date` forward by `months`, keeping the day where the target month allows it.
+fn add_months(date: NaiveDate, months: u32) -> NaiveDate {
+    let total_months = date.month() - 1 + months;
+    let year = date.year() + (total_months / 12) as i32;
+    let month = (total_months % 12) + 1;
+    let day = std::cmp::min(date.day(), days_in_month(year, month));
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+/// The occurrence following `date`.
+///
+/// Months step from the previous occurrence, so a series started on the 31st continues
+/// on the 28th after February, the same as the server computes it.
+pub fn next_occurrence(date: NaiveDate, recurrence: Recurrence) -> NaiveDate {
+    match recurrence {
+        Recurrence::Daily => date.succ_opt().unwrap(),
+        Recurrence::Weekly => date + Duration::days(7),
+        Recurrence::WorkDay => {
+            let mut next = date.succ_opt().unwrap();
+            while next.weekday().num_days_from_monday() >= 5 {
+                next = next.succ_opt().unwrap();
+            }
+            next
+        }
+        Recurrence::Monthly => add_months(date, 1),
+        Recurrence::Quarterly => add_months(date, 3),
+        Recurrence::HalfYearly => add_months(date, 6),
+        Recurrence::Yearly => add_months(date, 12),
+    }
+}
+
+/// Occurrence dates of a series starting at `start_date` that fall within
+/// `range_start..=range_end`, stopping after `end_date` if the series ends.
+pub fn occurrences(
+    start_date: NaiveDate,
+    end_date: Option<NaiveDate>,
+    recurrence: Recurrence,
+    range_start: NaiveDate,
+    range_end: NaiveDate,
+) -> Vec<NaiveDate> {
+    let mut dates = Vec::new();
+    if end_date.is_some_and(|end| end < range_start) {
+        return dates;
+    }
+
+    let mut current = start_date;
+    while current <= range_end {
+        if current >= range_start {
+            dates.push(current);
+        }
+        current = next_occurrence(current, recurrence);
+        if end_date.is_some_and(|end| current > end) {
+            break;
+        }
+    }
+    dates
+}
+
+/// Months between two occurrences of a month-based recurrence.
+fn months_per_step(recurrence: Recurrence) -> Option<u32> {
+    match recurrence {
+        Recurrence::Monthly => Some(1),
+        Recurrence::Quarterly => Some(3),
+        Recurrence::HalfYearly => Some(6),
+        Recurrence::Yearly => Some(12),
+        Recurrence::Daily | Recurrence::Weekly | Recurrence::WorkDay => None,
+    }
+}
+
+/// Like [`occurrences`], except that month-based series keep the day of `start_date`
+/// instead of stepping from the previous occurrence: a series started on the 31st falls
+/// on the 28th in February and on the 31st again in March.
+pub fn month_end_clamped_occurrences(
+    start_date: NaiveDate,
+    end_date: Option<NaiveDate>,
+    recurrence: Recurrence,
+    range_start: NaiveDate,
+    range_end: NaiveDate,
+) -> Vec<NaiveDate> {
+    let Some(step) = months_per_step(recurrence) else {
+        return occurrences(start_date, end_date, recurrence, range_start, range_end);
+    };
+
+    let mut dates = Vec::new();
+    let mut months = 0;
+    loop {
+        let current = add_months(start_date, months);
+        if current > range_end || end_date.is_some_and(|end| current > end) {
+            break;
+        }
+        if current >= range_start {
+            dates.push(current);
+        }
+        months += step;
+    }
+    dates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_days_in_month() {
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(2025, 2), 28);
+        assert_eq!(days_in_month(2025, 4), 30);
+        assert_eq!(days_in_month(2025, 12), 31);
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        for recurrence in [
+            Recurrence::Daily,
+            Recurrence::Weekly,
+            Recurrence::WorkDay,
+            Recurrence::Monthly,
+            Recurrence::Quarterly,
+            Recurrence::HalfYearly,
+            Recurrence::Yearly,
+        ] {
+            assert_eq!(recurrence.as_str().parse::<Recurrence>(), Ok(recurrence));
+        }
+        assert!("Fortnightly".parse::<Recurrence>().is_err());
+    }
+
+    #[test]
+    fn test_work_day_skips_weekend() {
+        // 2025-01-03 is a Friday
+        assert_eq!(next_occurrence(date(2025, 1, 3), Recurrence::WorkDay), date(2025, 1, 6));
+        assert_eq!(next_occurrence(date(2025, 1, 6), Recurrence::WorkDay), date(2025, 1, 7));
+    }
+
+    #[test]
+    fn test_month_steps_clamp_day() {
+        assert_eq!(next_occurrence(date(2025, 1, 31), Recurrence::Monthly), date(2025, 2, 28));
+        assert_eq!(next_occurrence(date(2025, 11, 30), Recurrence::Quarterly), date(2026, 2, 28));
+        assert_eq!(next_occurrence(date(2025, 8, 31), Recurrence::HalfYearly), date(2026, 2, 28));
+        assert_eq!(next_occurrence(date(2024, 2, 29), Recurrence::Yearly), date(2025, 2, 28));
+        assert_eq!(next_occurrence(date(2025, 12, 15), Recurrence::Monthly), date(2026, 1, 15));
+    }
+
+    #[test]
+    fn test_occurrences_within_range_and_end() {
+        let dates = occurrences(
+            date(2025, 1, 10),
+            Some(date(2025, 4, 10)),
+            Recurrence::Monthly,
+            date(2025, 2, 1),
+            date(2025, 12, 31),
+        );
+        assert_eq!(dates, vec![date(2025, 2, 10), date(2025, 3, 10), date(2025, 4, 10)]);
+    }
+
+    #[test]
+    fn test_occurrences_ended_before_range() {
+        let dates = occurrences(
+            date(2025, 1, 1),
+            Some(date(2025, 1, 31)),
+            Recurrence::Weekly,
+            date(2025, 2, 1),
+            date(2025, 3, 1),
+        );
+        assert!(dates.is_empty());
+    }
+
+    #[test]
+    fn test_month_end_clamped_occurrences_keep_start_day() {
+        let dates = month_end_clamped_occurrences(
+            date(2025, 1, 31),
+            None,
+            Recurrence::Monthly,
+            date(2025, 1, 1),
+            date(2025, 4, 30),
+        );
+        assert_eq!(dates, vec![date(2025, 1, 31), date(2025, 2, 28), date(2025, 3, 31), date(2025, 4, 30)]);
+
+        let dates = month_end_clamped_occurrences(
+            date(2024, 2, 29),
+            Some(date(2028, 3, 1)),
+            Recurrence::Yearly,
+            date(2025, 1, 1),
+            date(2030, 12, 31),
+        );
+        assert_eq!(dates, vec![date(2025, 2, 28), date(2026, 2, 28), date(2027, 2, 28), date(2028, 2, 29)]);
+    }
+}
@@ -0,0 +1,457 @@
+//! RFC 5545 `RRULE` recurrence rules for schedules a [`Recurrence`] can't express,
+//! like "the last business day of the month", "the 2nd Tuesday" or "every 3 weeks".
+//!
+//! Like [`crate::recurrence`] this module only depends on `chrono`. Only whole days are
+//! supported: `FREQ` is one of `DAILY`, `WEEKLY`, `MONTHLY` or `YEARLY`, and the rule may
+//! use `INTERVAL`, `COUNT`, `UNTIL`, `BYDAY`, `BYMONTHDAY`, `BYMONTH` and `BYSETPOS`.
+//! Weeks start on Monday.
+//!
+//! [`Recurrence`]: crate::recurrence::Recurrence
+
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+use std::str::FromStr;
+
+use crate::recurrence::days_in_month;
+
+/// How often the periods of a rule repeat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A parsed `RRULE`, e.g. `FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    /// Every how many periods the rule repeats.
+    pub interval: u32,
+    /// Number of occurrences after which the series ends.
+    pub count: Option<u32>,
+    /// Last day an occurrence may fall on.
+    pub until: Option<NaiveDate>,
+    /// Weekdays, optionally numbered within the month or year: `2TU` is the second
+    /// Tuesday, `-1FR` the last Friday.
+    pub by_day: Vec<(Option<i32>, Weekday)>,
+    /// Days of the month; negative values count from the end of the month.
+    pub by_month_day: Vec<i32>,
+    pub by_month: Vec<u32>,
+    /// Picks occurrences by their position within each period; negative values count
+    /// from the end.
+    pub by_set_pos: Vec<i32>,
+}
+
+fn parse_number<T: FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .parse::<T>()
+        .map_err(|_| format!("Invalid {} value: {}", key, value))
+}
+
+fn parse_list<T>(
+    key: &str,
+    value: &str,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Result<Vec<T>, String> {
+    value
+        .split(',')
+        .map(|item| parse(item).ok_or_else(|| format!("Invalid {} value: {}", key, item)))
+        .collect()
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    match value {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// `2TU`, `-1FR` or a plain `MO`.
+fn parse_by_day(value: &str) -> Option<(Option<i32>, Weekday)> {
+    let split = value.len().checked_sub(2)?;
+    let weekday = parse_weekday(value.get(split..)?)?;
+    let ordinal = match value.get(..split)? {
+        "" => None,
+        number => {
+            let ordinal = number.parse::<i32>().ok()?;
+            if ordinal == 0 || ordinal.abs() > 53 {
+                return None;
+            }
+            Some(ordinal)
+        }
+    };
+    Some((ordinal, weekday))
+}
+
+/// `UNTIL` as a date or a date-time; only the day is kept.
+fn parse_until(value: &str) -> Option<NaiveDate> {
+    let (date, time) = value.split_at_checked(8)?;
+    if !time.is_empty() && !time.starts_with('T') {
+        return None;
+    }
+    NaiveDate::parse_from_str(date, "%Y%m%d").ok()
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let value = value
+            .get(..6)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("RRULE:"))
+            .map_or(value, |_| &value[6..]);
+
+        let mut frequency = None;
+        let mut rule = RecurrenceRule {
+            frequency: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_month: Vec::new(),
+            by_set_pos: Vec::new(),
+        };
+        for part in value.split(';').filter(|part| !part.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid rule part: {}", part))?;
+            let key = key.to_ascii_uppercase();
+            let value = value.to_ascii_uppercase();
+            match key.as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(format!("Unsupported FREQ: {}", value)),
+                    })
+                }
+                "INTERVAL" => rule.interval = parse_number(&key, &value)?,
+                "COUNT" => rule.count = Some(parse_number(&key, &value)?),
+                "UNTIL" => {
+                    rule.until = Some(parse_until(&value).ok_or_else(|| format!("Invalid UNTIL value: {}", value))?)
+                }
+                "BYDAY" => rule.by_day = parse_list(&key, &value, parse_by_day)?,
+                "BYMONTHDAY" => {
+                    rule.by_month_day = parse_list(&key, &value, |day| {
+                        day.parse::<i32>().ok().filter(|day| (1..=31).contains(&day.abs()))
+                    })?
+                }
+                "BYMONTH" => {
+                    rule.by_month = parse_list(&key, &value, |month| {
+                        month.parse::<u32>().ok().filter(|month| (1..=12).contains(month))
+                    })?
+                }
+                "BYSETPOS" => {
+                    rule.by_set_pos = parse_list(&key, &value, |position| {
+                        position.parse::<i32>().ok().filter(|position| (1..=366).contains(&position.abs()))
+                    })?
+                }
+                // Weeks always start on Monday
+                "WKST" if value == "MO" => {}
+                _ => return Err(format!("Unsupported rule part: {}", part)),
+            }
+        }
+
+        rule.frequency = frequency.ok_or_else(|| "FREQ is required".to_string())?;
+        if rule.interval == 0 {
+            return Err("INTERVAL must be at least 1".to_string());
+        }
+        if rule.count == Some(0) {
+            return Err("COUNT must be at least 1".to_string());
+        }
+        if rule.count.is_some() && rule.until.is_some() {
+            return Err("COUNT and UNTIL can't be combined".to_string());
+        }
+        if rule.by_day.iter().any(|(ordinal, _)| ordinal.is_some())
+            && matches!(rule.frequency, Frequency::Daily | Frequency::Weekly)
+        {
+            return Err("Numbered BYDAY values need FREQ=MONTHLY or FREQ=YEARLY".to_string());
+        }
+        if rule.frequency == Frequency::Weekly && !rule.by_month_day.is_empty() {
+            return Err("BYMONTHDAY can't be used with FREQ=WEEKLY".to_string());
+        }
+        if !rule.by_set_pos.is_empty()
+            && rule.by_day.is_empty()
+            && rule.by_month_day.is_empty()
+            && rule.by_month.is_empty()
+        {
+            return Err("BYSETPOS needs BYDAY, BYMONTHDAY or BYMONTH".to_string());
+        }
+        Ok(rule)
+    }
+}
+
+/// Whether `date` is the `ordinal`-th of its weekday within the month, or within the
+/// year if `in_year`.
+fn is_nth_weekday(date: NaiveDate, ordinal: i32, in_year: bool) -> bool {
+    let (day, days) = if in_year {
+        let days = if date.leap_year() { 366 } else { 365 };
+        (date.ordinal() as i32, days)
+    } else {
+        (date.day() as i32, days_in_month(date.year(), date.month()) as i32)
+    };
+    if ordinal > 0 {
+        (day - 1) / 7 + 1 == ordinal
+    } else {
+        (days - day) / 7 + 1 == -ordinal
+    }
+}
+
+impl RecurrenceRule {
+    /// First day of the period containing `date`.
+    fn period_start(&self, date: NaiveDate) -> NaiveDate {
+        match self.frequency {
+            Frequency::Daily => date,
+            Frequency::Weekly => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+            Frequency::Monthly => date.with_day(1).unwrap(),
+            Frequency::Yearly => NaiveDate::from_ymd_opt(date.year(), 1, 1).unwrap(),
+        }
+    }
+
+    fn next_period(&self, period_start: NaiveDate) -> Option<NaiveDate> {
+        match self.frequency {
+            Frequency::Daily => period_start.checked_add_signed(Duration::days(self.interval as i64)),
+            Frequency::Weekly => period_start.checked_add_signed(Duration::weeks(self.interval as i64)),
+            Frequency::Monthly => period_start.checked_add_months(Months::new(self.interval)),
+            Frequency::Yearly => period_start.checked_add_months(Months::new(self.interval.checked_mul(12)?)),
+        }
+    }
+
+    fn period_end(&self, period_start: NaiveDate) -> NaiveDate {
+        let end = match self.frequency {
+            Frequency::Daily => Some(period_start),
+            Frequency::Weekly => period_start.checked_add_signed(Duration::days(6)),
+            Frequency::Monthly => period_start.checked_add_months(Months::new(1)).and_then(|next| next.pred_opt()),
+            Frequency::Yearly => NaiveDate::from_ymd_opt(period_start.year(), 12, 31),
+        };
+        end.unwrap_or(NaiveDate::MAX)
+    }
+
+    /// Whether `date` matches the `BY*` parts; parts the rule leaves out are taken from
+    /// `start_date`, e.g. a plain monthly rule repeats on the day of the month it starts.
+    fn matches(&self, date: NaiveDate, start_date: NaiveDate) -> bool {
+        if !self.by_month.is_empty() && !self.by_month.contains(&date.month()) {
+            return false;
+        }
+        if !self.by_month_day.is_empty() {
+            let days = days_in_month(date.year(), date.month()) as i32;
+            let day = date.day() as i32;
+            if !self
+                .by_month_day
+                .iter()
+                .any(|&by_day| by_day == day || days + 1 + by_day == day)
+            {
+                return false;
+            }
+        }
+        if !self.by_day.is_empty() {
+            let in_year = self.frequency == Frequency::Yearly && self.by_month.is_empty();
+            return self.by_day.iter().any(|&(ordinal, weekday)| {
+                date.weekday() == weekday && ordinal.is_none_or(|ordinal| is_nth_weekday(date, ordinal, in_year))
+            });
+        }
+
+        match self.frequency {
+            Frequency::Daily => true,
+            Frequency::Weekly => date.weekday() == start_date.weekday(),
+            Frequency::Monthly => !self.by_month_day.is_empty() || date.day() == start_date.day(),
+            Frequency::Yearly => {
+                (!self.by_month.is_empty() || !self.by_month_day.is_empty() || date.month() == start_date.month())
+                    && (!self.by_month_day.is_empty() || date.day() == start_date.day())
+            }
+        }
+    }
+
+    /// Occurrences within the period starting at `period_start`, in order.
+    fn period_dates(&self, period_start: NaiveDate, start_date: NaiveDate) -> Vec<NaiveDate> {
+        let period_end = self.period_end(period_start);
+        let dates: Vec<NaiveDate> = period_start
+            .iter_days()
+            .take_while(|date| *date <= period_end)
+            .filter(|date| self.matches(*date, start_date))
+            .collect();
+        if self.by_set_pos.is_empty() {
+            return dates;
+        }
+
+        let mut picked: Vec<NaiveDate> = self
+            .by_set_pos
+            .iter()
+            .filter_map(|&position| {
+                let index = if position > 0 {
+                    position as usize - 1
+                } else {
+                    dates.len().checked_sub(position.unsigned_abs() as usize)?
+                };
+                dates.get(index).copied()
+            })
+            .collect();
+        picked.sort();
+        picked.dedup();
+        picked
+    }
+
+    /// Occurrence dates of a series starting at `start_date` that fall within
+    /// `range_start..=range_end`, stopping after `end_date` if the series ends.
+    ///
+    /// Unlike RFC 5545 `DTSTART`, `start_date` is only an occurrence if it matches the
+    /// rule. `COUNT` counts occurrences from `start_date`, including the ones before
+    /// `range_start`.
+    pub fn occurrences(
+        &self,
+        start_date: NaiveDate,
+        end_date: Option<NaiveDate>,
+        range_start: NaiveDate,
+        range_end: NaiveDate,
+    ) -> Vec<NaiveDate> {
+        let last = [Some(range_end), end_date, self.until].into_iter().flatten().min().unwrap_or(range_end);
+        let mut dates = Vec::new();
+        let mut seen = 0;
+        let mut period_start = self.period_start(start_date);
+        while period_start <= last {
+            for date in self.period_dates(period_start, start_date) {
+                if date < start_date {
+                    continue;
+                }
+                if date > last {
+                    return dates;
+                }
+                if date >= range_start {
+                    dates.push(date);
+                }
+                seen += 1;
+                if self.count.is_some_and(|count| seen >= count) {
+                    return dates;
+                }
+            }
+            match self.next_period(period_start) {
+                Some(next) => period_start = next,
+                None => break,
+            }
+        }
+        dates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn rule(value: &str) -> RecurrenceRule {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let parsed = rule("RRULE:FREQ=MONTHLY;INTERVAL=2;BYDAY=2TU,-1FR;UNTIL=20261231T000000Z");
+        assert_eq!(parsed.frequency, Frequency::Monthly);
+        assert_eq!(parsed.interval, 2);
+        assert_eq!(parsed.by_day, vec![(Some(2), Weekday::Tue), (Some(-1), Weekday::Fri)]);
+        assert_eq!(parsed.until, Some(date(2026, 12, 31)));
+        assert_eq!(rule("freq=weekly;wkst=mo").frequency, Frequency::Weekly);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for invalid in [
+            "",
+            "INTERVAL=2",
+            "FREQ=HOURLY",
+            "FREQ=MONTHLY;INTERVAL=0",
+            "FREQ=MONTHLY;COUNT=3;UNTIL=20261231",
+            "FREQ=MONTHLY;BYDAY=XX",
+            "FREQ=MONTHLY;BYDAY=0MO",
+            "FREQ=WEEKLY;BYDAY=2TU",
+            "FREQ=WEEKLY;BYMONTHDAY=1",
+            "FREQ=MONTHLY;BYMONTHDAY=32",
+            "FREQ=YEARLY;BYMONTH=13",
+            "FREQ=MONTHLY;BYSETPOS=1",
+            "FREQ=MONTHLY;BYHOUR=9",
+            "FREQ=WEEKLY;WKST=SU",
+            "FREQ=MONTHLY;UNTIL=2026",
+        ] {
+            assert!(invalid.parse::<RecurrenceRule>().is_err(), "{} should be rejected", invalid);
+        }
+    }
+
+    #[test]
+    fn test_last_business_day_of_month() {
+        let dates = rule("FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1").occurrences(
+            date(2026, 1, 1),
+            None,
+            date(2026, 1, 1),
+            date(2026, 5, 31),
+        );
+        // May 2026 ends on a Sunday
+        assert_eq!(
+            dates,
+            vec![date(2026, 1, 30), date(2026, 2, 27), date(2026, 3, 31), date(2026, 4, 30), date(2026, 5, 29)]
+        );
+    }
+
+    #[test]
+    fn test_second_tuesday() {
+        let dates = rule("FREQ=MONTHLY;BYDAY=2TU").occurrences(date(2026, 1, 1), None, date(2026, 1, 1), date(2026, 3, 31));
+        assert_eq!(dates, vec![date(2026, 1, 13), date(2026, 2, 10), date(2026, 3, 10)]);
+    }
+
+    #[test]
+    fn test_every_three_weeks() {
+        // 2026-01-05 is a Monday
+        let dates = rule("FREQ=WEEKLY;INTERVAL=3").occurrences(date(2026, 1, 5), None, date(2026, 1, 20), date(2026, 3, 1));
+        assert_eq!(dates, vec![date(2026, 1, 26), date(2026, 2, 16)]);
+    }
+
+    #[test]
+    fn test_plain_rules_follow_start_date() {
+        let monthly = rule("FREQ=MONTHLY").occurrences(date(2026, 1, 31), None, date(2026, 1, 1), date(2026, 5, 31));
+        // Months without a 31st are skipped
+        assert_eq!(monthly, vec![date(2026, 1, 31), date(2026, 3, 31), date(2026, 5, 31)]);
+
+        let yearly = rule("FREQ=YEARLY").occurrences(date(2024, 3, 15), None, date(2024, 1, 1), date(2026, 12, 31));
+        assert_eq!(yearly, vec![date(2024, 3, 15), date(2025, 3, 15), date(2026, 3, 15)]);
+    }
+
+    #[test]
+    fn test_last_day_of_month_and_yearly_by_month() {
+        let dates = rule("FREQ=MONTHLY;BYMONTHDAY=-1").occurrences(date(2024, 1, 15), None, date(2024, 1, 1), date(2024, 3, 31));
+        assert_eq!(dates, vec![date(2024, 1, 31), date(2024, 2, 29), date(2024, 3, 31)]);
+
+        let dates = rule("FREQ=YEARLY;BYMONTH=6,12;BYMONTHDAY=15").occurrences(
+            date(2025, 7, 1),
+            None,
+            date(2025, 1, 1),
+            date(2026, 12, 31),
+        );
+        assert_eq!(dates, vec![date(2025, 12, 15), date(2026, 6, 15), date(2026, 12, 15)]);
+    }
+
+    #[test]
+    fn test_count_until_and_end_date() {
+        let monthly = rule("FREQ=MONTHLY;COUNT=3");
+        // The first occurrence is before the range but still counts
+        let dates = monthly.occurrences(date(2026, 1, 10), None, date(2026, 2, 1), date(2026, 12, 31));
+        assert_eq!(dates, vec![date(2026, 2, 10), date(2026, 3, 10)]);
+
+        let dates = rule("FREQ=WEEKLY;UNTIL=20260120").occurrences(date(2026, 1, 6), None, date(2026, 1, 1), date(2026, 12, 31));
+        assert_eq!(dates, vec![date(2026, 1, 6), date(2026, 1, 13), date(2026, 1, 20)]);
+
+        let dates = monthly.occurrences(date(2026, 1, 10), Some(date(2026, 2, 28)), date(2026, 1, 1), date(2026, 12, 31));
+        assert_eq!(dates, vec![date(2026, 1, 10), date(2026, 2, 10)]);
+    }
+}
@@ -0,0 +1,161 @@
+//! Amounts of upcoming occurrences of recurring transactions whose amount varies, such
+//! as utility bills, estimated from what was actually paid.
+
+use model::entities::recurring_transaction::{self, AmountStrategy};
+use model::entities::recurring_transaction_instance;
+use rust_decimal::Decimal;
+
+/// Paid instances averaged by [`AmountStrategy::AverageOfLastN`] when no count is set.
+pub const DEFAULT_HISTORY_COUNT: usize = 3;
+
+/// Amount an upcoming occurrence of `tx` is expected to have, given its paid instances
+/// in any order.
+///
+/// Paid instances count with their paid amount, or the expected one if none was
+/// recorded. Without paid instances, and for [`AmountStrategy::Fixed`], this is `amount`.
+pub fn estimated_amount(tx: &recurring_transaction::Model, paid: &[recurring_transaction_instance::Model]) -> Decimal {
+    if tx.amount_strategy == AmountStrategy::Fixed {
+        return tx.amount;
+    }
+
+    let mut history: Vec<&recurring_transaction_instance::Model> = paid
+        .iter()
+        .filter(|instance| {
+            instance.recurring_transaction_id == tx.id
+                && instance.status == recurring_transaction_instance::InstanceStatus::Paid
+        })
+        .collect();
+    history.sort_by_key(|instance| std::cmp::Reverse(instance.due_date));
+    let count = match tx.amount_strategy {
+        AmountStrategy::LastPaid => 1,
+        _ => tx
+            .amount_history_count
+            .and_then(|count| usize::try_from(count).ok())
+            .filter(|count| *count > 0)
+            .unwrap_or(DEFAULT_HISTORY_COUNT),
+    };
+    let amounts: Vec<Decimal> = history
+        .into_iter()
+        .take(count)
+        .map(|instance| instance.paid_amount.unwrap_or(instance.expected_amount))
+        .collect();
+    if amounts.is_empty() {
+        return tx.amount;
+    }
+    (amounts.iter().sum::<Decimal>() / Decimal::from(amounts.len())).round_dp(2)
+}
+
+/// `tx` valued with [`estimated_amount`], for the occurrences still to come.
+pub fn with_estimated_amount(
+    tx: &recurring_transaction::Model,
+    paid: &[recurring_transaction_instance::Model],
+) -> recurring_transaction::Model {
+    recurring_transaction::Model {
+        amount: estimated_amount(tx, paid),
+        ..tx.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use model::entities::recurring_transaction::{ApprovalStatus, RecurrencePeriod};
+    use model::entities::recurring_transaction_instance::InstanceStatus;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn electricity(amount_strategy: AmountStrategy, amount_history_count: Option<i32>) -> recurring_transaction::Model {
+        recurring_transaction::Model {
+            id: 1,
+            name: "Electricity".to_string(),
+            description: None,
+            amount: Decimal::from(-80),
+            start_date: date(2026, 1, 10),
+            end_date: None,
+            period: RecurrencePeriod::Monthly,
+            include_in_statistics: true,
+            target_account_id: 1,
+            source_account_id: None,
+            category_id: None,
+            ledger_name: None,
+            scenario_id: None,
+            is_simulated: false,
+            approval_status: ApprovalStatus::Approved,
+            created_by_user_id: None,
+            reviewed_by_user_id: None,
+            notice_period_days: None,
+            renewal_date: None,
+            fx_mode: None,
+            fx_rate: None,
+            source_amount: None,
+            amount_strategy,
+            amount_history_count,
+            rrule: None,
+            date_adjustment: recurring_transaction::DateAdjustment::None,
+        }
+    }
+
+    fn paid(id: i32, due_date: NaiveDate, paid_amount: Option<i64>) -> recurring_transaction_instance::Model {
+        recurring_transaction_instance::Model {
+            id,
+            recurring_transaction_id: 1,
+            status: InstanceStatus::Paid,
+            due_date,
+            expected_amount: Decimal::from(-80),
+            paid_date: Some(due_date),
+            paid_amount: paid_amount.map(Decimal::from),
+            reconciled_imported_transaction_id: None,
+            category_id: None,
+            notes: None,
+            disputed: false,
+            overdue: false,
+        }
+    }
+
+    fn history() -> Vec<recurring_transaction_instance::Model> {
+        vec![
+            paid(1, date(2026, 1, 10), Some(-120)),
+            paid(3, date(2026, 3, 10), Some(-60)),
+            paid(2, date(2026, 2, 10), Some(-90)),
+            paid(4, date(2026, 4, 10), None),
+        ]
+    }
+
+    #[test]
+    fn test_fixed_ignores_history() {
+        let tx = electricity(AmountStrategy::Fixed, None);
+        assert_eq!(estimated_amount(&tx, &history()), Decimal::from(-80));
+    }
+
+    #[test]
+    fn test_last_paid() {
+        let tx = electricity(AmountStrategy::LastPaid, None);
+        let mut instances = history();
+        instances.pop();
+        assert_eq!(estimated_amount(&tx, &instances), Decimal::from(-60));
+        // Without a recorded paid amount the expected one counts
+        assert_eq!(estimated_amount(&tx, &history()), Decimal::from(-80));
+    }
+
+    #[test]
+    fn test_average_of_last_n() {
+        let tx = electricity(AmountStrategy::AverageOfLastN, Some(2));
+        let mut instances = history();
+        instances.pop();
+        assert_eq!(estimated_amount(&tx, &instances), Decimal::from(-75));
+
+        // Three by default
+        let tx = electricity(AmountStrategy::AverageOfLastN, None);
+        assert_eq!(estimated_amount(&tx, &instances), Decimal::from(-90));
+    }
+
+    #[test]
+    fn test_no_history_keeps_amount() {
+        let tx = electricity(AmountStrategy::AverageOfLastN, Some(6));
+        assert_eq!(estimated_amount(&tx, &[]), Decimal::from(-80));
+        assert_eq!(with_estimated_amount(&tx, &[]), tx);
+    }
+}
@@ -0,0 +1,252 @@
+use chrono::{Duration, Months, NaiveDate};
+use model::entities::recurring_transaction::RecurrencePeriod;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+/// A historical one-off transaction considered by the detector.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    pub id: i32,
+    pub name: String,
+    pub amount: Decimal,
+    pub date: NaiveDate,
+    pub account_id: i32,
+}
+
+/// A group of historical transactions that look like one recurring payment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecurringCandidate {
+    /// Name of the most recent transaction in the group.
+    pub name: String,
+    pub account_id: i32,
+    pub period: RecurrencePeriod,
+    /// Median amount of the group.
+    pub amount: Decimal,
+    /// Transactions in the group, oldest first.
+    pub transaction_ids: Vec<i32>,
+    pub first_date: NaiveDate,
+    pub last_date: NaiveDate,
+    /// When the next occurrence is expected if the pattern continues.
+    pub next_date: NaiveDate,
+}
+
+/// Finds periodic patterns in one-off transaction history.
+///
+/// Transactions are grouped by account and normalized name (case, digits and
+/// punctuation are ignored so "Netflix #1234" and "NETFLIX 5678" match). Within a
+/// group, amounts further than `amount_tolerance` (relative) from the median are
+/// dropped, and the remaining dates must be spaced consistently enough to map to
+/// a weekly, monthly, quarterly, half-yearly or yearly period.
+#[derive(Debug, Clone)]
+pub struct RecurringDetector {
+    /// Minimum number of matching transactions for a pattern.
+    pub min_occurrences: usize,
+    /// Maximum relative deviation of an amount from the group median.
+    pub amount_tolerance: Decimal,
+}
+
+impl Default for RecurringDetector {
+    fn default() -> Self {
+        Self {
+            min_occurrences: 3,
+            amount_tolerance: Decimal::new(10, 2),
+        }
+    }
+}
+
+impl RecurringDetector {
+    /// Detects recurring candidates in the given history, largest groups first.
+    pub fn detect(&self, entries: Vec<HistoryEntry>) -> Vec<RecurringCandidate> {
+        let mut groups: HashMap<(i32, String), Vec<HistoryEntry>> = HashMap::new();
+        for entry in entries {
+            let key = (entry.account_id, normalize_name(&entry.name));
+            if key.1.is_empty() {
+                continue;
+            }
+            groups.entry(key).or_default().push(entry);
+        }
+
+        let mut candidates: Vec<RecurringCandidate> = groups
+            .into_values()
+            .filter_map(|group| self.evaluate(group))
+            .collect();
+        candidates.sort_by(|a, b| {
+            b.transaction_ids
+                .len()
+                .cmp(&a.transaction_ids.len())
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        candidates
+    }
+
+    /// Checks whether an already grouped set of transactions forms a recurring pattern.
+    ///
+    /// Outlier amounts are dropped from the result, so callers that need every entry
+    /// to be part of the pattern should compare the returned transaction IDs.
+    pub fn evaluate(&self, mut entries: Vec<HistoryEntry>) -> Option<RecurringCandidate> {
+        if entries.len() < self.min_occurrences.max(2) {
+            return None;
+        }
+
+        let median_amount = median(entries.iter().map(|entry| entry.amount).collect());
+        let tolerance = median_amount.abs() * self.amount_tolerance;
+        entries.retain(|entry| (entry.amount - median_amount).abs() <= tolerance);
+        if entries.len() < self.min_occurrences.max(2) {
+            return None;
+        }
+
+        entries.sort_by_key(|entry| (entry.date, entry.id));
+        let intervals: Vec<i64> = entries
+            .windows(2)
+            .map(|pair| (pair[1].date - pair[0].date).num_days())
+            .collect();
+        let median_interval = median(intervals.iter().map(|days| Decimal::from(*days)).collect());
+        let period = period_for_interval(median_interval)?;
+        let (min_days, max_days) = interval_range(&period);
+        if intervals.iter().any(|days| *days < min_days || *days > max_days) {
+            return None;
+        }
+
+        let first = entries.first()?;
+        let last = entries.last()?;
+        Some(RecurringCandidate {
+            name: last.name.clone(),
+            account_id: last.account_id,
+            next_date: next_occurrence(last.date, &period),
+            period,
+            amount: median(entries.iter().map(|entry| entry.amount).collect()),
+            transaction_ids: entries.iter().map(|entry| entry.id).collect(),
+            first_date: first.date,
+            last_date: last.date,
+        })
+    }
+}
+
+/// Lowercases a transaction name and strips digits and punctuation so reference numbers don't split groups.
+pub fn normalize_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_alphabetic() { c.to_lowercase().next().unwrap_or(c) } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn median(mut values: Vec<Decimal>) -> Decimal {
+    if values.is_empty() {
+        return Decimal::ZERO;
+    }
+    values.sort();
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / Decimal::from(2)
+    } else {
+        values[mid]
+    }
+}
+
+/// Accepted spacing in days between two occurrences of each supported period.
+fn interval_range(period: &RecurrencePeriod) -> (i64, i64) {
+    match period {
+        RecurrencePeriod::Weekly => (5, 9),
+        RecurrencePeriod::Monthly => (25, 36),
+        RecurrencePeriod::Quarterly => (83, 98),
+        RecurrencePeriod::HalfYearly => (172, 193),
+        RecurrencePeriod::Yearly => (350, 380),
+        // Daily and workday patterns are too noisy to infer from one-off history
+        RecurrencePeriod::Daily | RecurrencePeriod::WorkDay => (1, 1),
+    }
+}
+
+fn period_for_interval(days: Decimal) -> Option<RecurrencePeriod> {
+    [
+        RecurrencePeriod::Weekly,
+        RecurrencePeriod::Monthly,
+        RecurrencePeriod::Quarterly,
+        RecurrencePeriod::HalfYearly,
+        RecurrencePeriod::Yearly,
+    ]
+    .into_iter()
+    .find(|period| {
+        let (min_days, max_days) = interval_range(period);
+        days >= Decimal::from(min_days) && days <= Decimal::from(max_days)
+    })
+}
+
+fn next_occurrence(date: NaiveDate, period: &RecurrencePeriod) -> NaiveDate {
+    let next = match period {
+        RecurrencePeriod::Daily => date.checked_add_signed(Duration::days(1)),
+        RecurrencePeriod::WorkDay => date.checked_add_signed(Duration::days(1)),
+        RecurrencePeriod::Weekly => date.checked_add_signed(Duration::days(7)),
+        RecurrencePeriod::Monthly => date.checked_add_months(Months::new(1)),
+        RecurrencePeriod::Quarterly => date.checked_add_months(Months::new(3)),
+        RecurrencePeriod::HalfYearly => date.checked_add_months(Months::new(6)),
+        RecurrencePeriod::Yearly => date.checked_add_months(Months::new(12)),
+    };
+    next.unwrap_or(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: i32, name: &str, cents: i64, date: (i32, u32, u32)) -> HistoryEntry {
+        HistoryEntry {
+            id,
+            name: name.to_string(),
+            amount: Decimal::new(cents, 2),
+            date: NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap(),
+            account_id: 1,
+        }
+    }
+
+    #[test]
+    fn test_normalize_name_ignores_reference_numbers() {
+        assert_eq!(normalize_name("NETFLIX.COM #4821"), "netflix com");
+        assert_eq!(normalize_name("Netflix.com 1193"), "netflix com");
+        assert_eq!(normalize_name("1234"), "");
+    }
+
+    #[test]
+    fn test_detects_monthly_subscription_and_drops_outliers() {
+        let detector = RecurringDetector::default();
+        let candidates = detector.detect(vec![
+            entry(1, "Netflix #1", -1599, (2025, 1, 3)),
+            entry(2, "NETFLIX #2", -1599, (2025, 2, 4)),
+            entry(3, "Netflix #3", -1649, (2025, 3, 3)),
+            entry(4, "Netflix #4", -1599, (2025, 4, 2)),
+            // A one-time purchase from the same merchant
+            entry(5, "Netflix gift card", -5000, (2025, 3, 20)),
+            entry(6, "Coffee", -450, (2025, 1, 3)),
+        ]);
+
+        assert_eq!(candidates.len(), 1);
+        let candidate = &candidates[0];
+        assert_eq!(candidate.period, RecurrencePeriod::Monthly);
+        assert_eq!(candidate.transaction_ids, vec![1, 2, 3, 4]);
+        assert_eq!(candidate.amount, Decimal::new(-1599, 2));
+        assert_eq!(candidate.next_date, NaiveDate::from_ymd_opt(2025, 5, 2).unwrap());
+    }
+
+    #[test]
+    fn test_irregular_spacing_is_not_recurring() {
+        let detector = RecurringDetector::default();
+        let candidate = detector.evaluate(vec![
+            entry(1, "Gym", -3000, (2025, 1, 1)),
+            entry(2, "Gym", -3000, (2025, 1, 29)),
+            entry(3, "Gym", -3000, (2025, 4, 15)),
+        ]);
+        assert!(candidate.is_none());
+    }
+
+    #[test]
+    fn test_too_few_occurrences() {
+        let detector = RecurringDetector::default();
+        assert!(detector
+            .evaluate(vec![
+                entry(1, "Rent", -100000, (2025, 1, 1)),
+                entry(2, "Rent", -100000, (2025, 2, 1)),
+            ])
+            .is_none());
+    }
+}
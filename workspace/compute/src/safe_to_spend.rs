@@ -0,0 +1,155 @@
+use crate::budgets::{category_with_descendants, BudgetStatus};
+use chrono::NaiveDate;
+use model::entities::category;
+use rust_decimal::Decimal;
+
+/// What a committed outflow comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitmentKind {
+    /// An occurrence of a recurring expense that has not been paid yet
+    RecurringPayment,
+    /// A one-off transaction dated in the future
+    PlannedTransaction,
+    /// The part of a budget that is still expected to be spent in its current period
+    BudgetRemainder,
+}
+
+/// Money already promised before the next income arrives.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Commitment {
+    pub kind: CommitmentKind,
+    /// Recurring transaction, one-off transaction or budget id, depending on `kind`
+    pub source_id: i32,
+    pub name: String,
+    /// Due date; the end of the budget period for budget remainders
+    pub date: NaiveDate,
+    pub category_id: Option<i32>,
+    /// Outflow as a positive number
+    pub amount: Decimal,
+}
+
+/// The first of `income_dates` after `today`.
+pub fn next_income_date(income_dates: impl IntoIterator<Item = NaiveDate>, today: NaiveDate) -> Option<NaiveDate> {
+    income_dates.into_iter().filter(|date| *date > today).min()
+}
+
+/// Reserves what is left of each budget in its current period.
+///
+/// Payments already listed in `commitments` within the budget's categories and period
+/// are part of its remainder, so only the rest is reserved. Budgets that are used up
+/// or whose period ends by `today` reserve nothing.
+pub fn budget_reservations(
+    statuses: &[BudgetStatus],
+    categories: &[category::Model],
+    commitments: &[Commitment],
+    today: NaiveDate,
+) -> Vec<Commitment> {
+    statuses
+        .iter()
+        .filter(|status| status.period_end > today)
+        .filter_map(|status| {
+            let covered = category_with_descendants(categories, status.category_id);
+            let listed: Decimal = commitments
+                .iter()
+                .filter(|commitment| commitment.category_id.is_some_and(|id| covered.contains(&id)))
+                .filter(|commitment| status.period_start <= commitment.date && commitment.date <= status.period_end)
+                .map(|commitment| commitment.amount)
+                .sum();
+            let amount = status.remaining() - listed;
+            (amount > Decimal::ZERO).then(|| Commitment {
+                kind: CommitmentKind::BudgetRemainder,
+                source_id: status.budget_id,
+                name: categories
+                    .iter()
+                    .find(|category| category.id == status.category_id)
+                    .map(|category| category.name.clone())
+                    .unwrap_or_default(),
+                date: status.period_end,
+                category_id: Some(status.category_id),
+                amount,
+            })
+        })
+        .collect()
+}
+
+/// Balance left after setting aside every commitment.
+pub fn safe_to_spend(balance: Decimal, commitments: &[Commitment]) -> Decimal {
+    balance - commitments.iter().map(|commitment| commitment.amount).sum::<Decimal>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    fn category(id: i32, parent_id: Option<i32>) -> category::Model {
+        category::Model {
+            id,
+            name: format!("Category {}", id),
+            description: None,
+            parent_id,
+        }
+    }
+
+    fn payment(category_id: Option<i32>, day: u32, amount: i64) -> Commitment {
+        Commitment {
+            kind: CommitmentKind::RecurringPayment,
+            source_id: 1,
+            name: "Payment".to_string(),
+            date: date(day),
+            category_id,
+            amount: Decimal::from(amount),
+        }
+    }
+
+    fn status(category_id: i32, spent: i64) -> BudgetStatus {
+        BudgetStatus {
+            budget_id: 7,
+            category_id,
+            period_start: date(1),
+            period_end: date(31),
+            limit: Decimal::from(500),
+            carried_over: Decimal::ZERO,
+            spent: Decimal::from(spent),
+        }
+    }
+
+    #[test]
+    fn test_next_income_date_skips_today_and_earlier() {
+        assert_eq!(next_income_date([date(15), date(25), date(5)], date(15)), Some(date(25)));
+        assert_eq!(next_income_date([date(1)], date(15)), None);
+    }
+
+    #[test]
+    fn test_budget_reservation_excludes_listed_payments() {
+        let categories = [category(1, None), category(2, Some(1)), category(3, None)];
+        let commitments = [payment(Some(2), 20, 120), payment(Some(3), 20, 50), payment(Some(2), 31, 30)];
+
+        let reservations = budget_reservations(&[status(1, 200)], &categories, &commitments, date(15));
+
+        assert_eq!(reservations.len(), 1);
+        assert_eq!(reservations[0].kind, CommitmentKind::BudgetRemainder);
+        assert_eq!(reservations[0].amount, Decimal::from(150));
+        assert_eq!(reservations[0].name, "Category 1");
+        assert_eq!(reservations[0].date, date(31));
+    }
+
+    #[test]
+    fn test_used_up_or_ending_budgets_reserve_nothing() {
+        let categories = [category(1, None)];
+
+        assert!(budget_reservations(&[status(1, 600)], &categories, &[], date(15)).is_empty());
+        assert!(budget_reservations(&[status(1, 100)], &categories, &[], date(31)).is_empty());
+    }
+
+    #[test]
+    fn test_safe_to_spend_subtracts_commitments() {
+        let commitments = [payment(None, 20, 120), payment(Some(3), 21, 80)];
+
+        assert_eq!(safe_to_spend(Decimal::from(1000), &commitments), Decimal::from(800));
+        assert_eq!(safe_to_spend(Decimal::from(150), &commitments), Decimal::from(-50));
+    }
+}
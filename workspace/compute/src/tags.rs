@@ -1,9 +1,11 @@
 use chrono::NaiveDate;
+use model::entities::tag;
 use model::transaction::{Transaction, Tag};
 use polars::prelude::*;
 use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
 use sea_orm::DatabaseConnection;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tracing::{debug, info, instrument};
 
 use crate::error::Result;
@@ -134,6 +136,97 @@ impl Default for TagsComputer {
     }
 }
 
+/// Share of a tag's monthly limit (in percent) from which spending is reported as a warning.
+pub const TAG_LIMIT_WARNING_PERCENT: u32 = 80;
+
+/// How close the spending of a tag is to its monthly limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagLimitLevel {
+    WithinLimit,
+    /// At least [`TAG_LIMIT_WARNING_PERCENT`] of the limit is spent.
+    Warning,
+    /// More than the limit is spent.
+    Exceeded,
+}
+
+/// A single expense carrying a tag.
+#[derive(Debug, Clone)]
+pub struct TaggedExpense {
+    pub transaction_id: i32,
+    pub tag_id: i32,
+    /// Spent amount as a positive number.
+    pub amount: Decimal,
+}
+
+/// Spending of one tag with a monthly limit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagLimitStatus {
+    pub tag_id: i32,
+    pub tag_name: String,
+    pub limit: Decimal,
+    pub spent: Decimal,
+    pub level: TagLimitLevel,
+}
+
+impl TagLimitStatus {
+    /// Amount left before the limit is reached; negative once it is exceeded.
+    pub fn remaining(&self) -> Decimal {
+        self.limit - self.spent
+    }
+}
+
+/// Evaluates the monthly limits of `tags` against the expenses of one month.
+///
+/// An expense counts towards the limit of its tag and of every ancestor tag, so a
+/// limit on "Eating out" also covers expenses tagged with its child "Restaurants".
+/// A transaction tagged with both a tag and its ancestor is counted only once.
+/// The result contains one entry per tag with a positive limit, the most used first.
+pub fn evaluate_tag_limits(tags: &[tag::Model], expenses: &[TaggedExpense]) -> Vec<TagLimitStatus> {
+    let by_id: HashMap<i32, &tag::Model> = tags.iter().map(|tag| (tag.id, tag)).collect();
+
+    let mut counted: HashSet<(i32, i32)> = HashSet::new();
+    let mut spent: HashMap<i32, Decimal> = HashMap::new();
+    for expense in expenses {
+        let mut current = by_id.get(&expense.tag_id).copied();
+        let mut visited = HashSet::new();
+        while let Some(tag) = current {
+            if !visited.insert(tag.id) {
+                break; // Malformed hierarchy with a cycle
+            }
+            if tag.monthly_limit.is_some() && counted.insert((tag.id, expense.transaction_id)) {
+                *spent.entry(tag.id).or_insert(Decimal::ZERO) += expense.amount;
+            }
+            current = tag.parent_id.and_then(|parent_id| by_id.get(&parent_id).copied());
+        }
+    }
+
+    let mut statuses: Vec<TagLimitStatus> = tags
+        .iter()
+        .filter_map(|tag| {
+            let limit = tag.monthly_limit.filter(|limit| limit.is_sign_positive() && !limit.is_zero())?;
+            let spent = spent.get(&tag.id).copied().unwrap_or(Decimal::ZERO);
+            let level = if spent > limit {
+                TagLimitLevel::Exceeded
+            } else if spent * Decimal::from(100) >= limit * Decimal::from(TAG_LIMIT_WARNING_PERCENT) {
+                TagLimitLevel::Warning
+            } else {
+                TagLimitLevel::WithinLimit
+            };
+            Some(TagLimitStatus {
+                tag_id: tag.id,
+                tag_name: tag.name.clone(),
+                limit,
+                spent,
+                level,
+            })
+        })
+        .collect();
+
+    statuses.sort_by(|a, b| (b.spent / b.limit).cmp(&(a.spent / a.limit)).then(a.tag_name.cmp(&b.tag_name)));
+    debug!("Evaluated {} tag limits against {} tagged expenses", statuses.len(), expenses.len());
+    statuses
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -213,4 +306,63 @@ mod tests {
         assert!(column_names.contains(&"tag_name".to_string()));
         assert!(column_names.contains(&"amount".to_string()));
     }
+
+    fn make_tag(id: i32, name: &str, parent_id: Option<i32>, monthly_limit: Option<i64>) -> tag::Model {
+        tag::Model {
+            id,
+            name: name.to_string(),
+            description: None,
+            parent_id,
+            ledger_name: None,
+            monthly_limit: monthly_limit.map(Decimal::from),
+        }
+    }
+
+    fn expense(transaction_id: i32, tag_id: i32, amount: i64) -> TaggedExpense {
+        TaggedExpense {
+            transaction_id,
+            tag_id,
+            amount: Decimal::from(amount),
+        }
+    }
+
+    #[test]
+    fn test_evaluate_tag_limits_rolls_up_to_parent() {
+        let tags = vec![
+            make_tag(1, "Eating out", None, Some(2000)),
+            make_tag(2, "Restaurants", Some(1), None),
+            make_tag(3, "Coffee", Some(1), Some(100)),
+            make_tag(4, "Groceries", None, None),
+        ];
+        let expenses = vec![
+            expense(10, 2, 900),
+            expense(11, 3, 120),
+            // Tagged with both the child and the parent: counted once
+            expense(12, 2, 700),
+            expense(12, 1, 700),
+            expense(13, 4, 5000),
+        ];
+
+        let statuses = evaluate_tag_limits(&tags, &expenses);
+        assert_eq!(statuses.len(), 2);
+
+        assert_eq!(statuses[0].tag_name, "Coffee");
+        assert_eq!(statuses[0].spent, Decimal::from(120));
+        assert_eq!(statuses[0].level, TagLimitLevel::Exceeded);
+        assert_eq!(statuses[0].remaining(), Decimal::from(-20));
+
+        assert_eq!(statuses[1].tag_name, "Eating out");
+        assert_eq!(statuses[1].spent, Decimal::from(1720));
+        assert_eq!(statuses[1].level, TagLimitLevel::Warning);
+    }
+
+    #[test]
+    fn test_evaluate_tag_limits_without_spending() {
+        let tags = vec![make_tag(1, "Hobbies", None, Some(300)), make_tag(2, "Old", None, Some(0))];
+
+        let statuses = evaluate_tag_limits(&tags, &[]);
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].spent, Decimal::ZERO);
+        assert_eq!(statuses[0].level, TagLimitLevel::WithinLimit);
+    }
 }
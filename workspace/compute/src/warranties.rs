@@ -0,0 +1,101 @@
+use chrono::NaiveDate;
+
+/// Which right of a purchase runs out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PurchaseDeadlineKind {
+    Return,
+    Warranty,
+}
+
+/// A purchase whose return window or warranty is about to end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PurchaseReminder {
+    pub source_id: i32,
+    pub kind: PurchaseDeadlineKind,
+    /// Last day the return or warranty claim is possible.
+    pub deadline: NaiveDate,
+    /// Days left; zero on the deadline itself.
+    pub days_until_deadline: i64,
+}
+
+/// Return deadlines and warranty ends of purchases, given as
+/// `(id, return_deadline, warranty_end_date)`, at most `within_days` after `today`.
+///
+/// A purchase with both dates coming up gets a reminder for each. Reminders are ordered
+/// by deadline, most urgent first.
+pub fn upcoming_purchase_deadlines(
+    purchases: impl IntoIterator<Item = (i32, Option<NaiveDate>, Option<NaiveDate>)>,
+    today: NaiveDate,
+    within_days: i64,
+) -> Vec<PurchaseReminder> {
+    let mut reminders: Vec<PurchaseReminder> = purchases
+        .into_iter()
+        .flat_map(|(source_id, return_deadline, warranty_end_date)| {
+            [
+                return_deadline.map(|deadline| (source_id, PurchaseDeadlineKind::Return, deadline)),
+                warranty_end_date.map(|deadline| (source_id, PurchaseDeadlineKind::Warranty, deadline)),
+            ]
+        })
+        .flatten()
+        .filter_map(|(source_id, kind, deadline)| {
+            let days_until_deadline = (deadline - today).num_days();
+            (0..=within_days).contains(&days_until_deadline).then_some(PurchaseReminder {
+                source_id,
+                kind,
+                deadline,
+                days_until_deadline,
+            })
+        })
+        .collect();
+    reminders.sort_by_key(|reminder| (reminder.deadline, reminder.source_id, reminder.kind));
+    reminders
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_upcoming_purchase_deadlines() {
+        let today = date(2026, 10, 15);
+        let purchases = vec![
+            // Return window closes in 14 days, warranty far away
+            (1, Some(date(2026, 10, 29)), Some(date(2028, 10, 1))),
+            // Warranty ends today
+            (2, None, Some(date(2026, 10, 15))),
+            // Return window already closed, warranty ends in 5 days
+            (3, Some(date(2026, 10, 1)), Some(date(2026, 10, 20))),
+            // Nothing tracked
+            (4, None, None),
+        ];
+
+        let reminders = upcoming_purchase_deadlines(purchases, today, 30);
+        let found: Vec<(i32, PurchaseDeadlineKind)> =
+            reminders.iter().map(|reminder| (reminder.source_id, reminder.kind)).collect();
+        assert_eq!(
+            found,
+            vec![
+                (2, PurchaseDeadlineKind::Warranty),
+                (3, PurchaseDeadlineKind::Warranty),
+                (1, PurchaseDeadlineKind::Return),
+            ]
+        );
+        assert_eq!(reminders[0].days_until_deadline, 0);
+        assert_eq!(reminders[2].deadline, date(2026, 10, 29));
+        assert_eq!(reminders[2].days_until_deadline, 14);
+    }
+
+    #[test]
+    fn test_both_deadlines_of_one_purchase() {
+        let today = date(2026, 10, 15);
+        let reminders = upcoming_purchase_deadlines(vec![(7, Some(date(2026, 10, 20)), Some(date(2026, 10, 20)))], today, 7);
+
+        assert_eq!(reminders.len(), 2);
+        assert_eq!(reminders[0].kind, PurchaseDeadlineKind::Return);
+        assert_eq!(reminders[1].kind, PurchaseDeadlineKind::Warranty);
+    }
+}
@@ -1,12 +1,18 @@
 pub mod account;
+pub mod batch;
+pub mod call_log;
 pub mod transaction;
 pub mod manual_account_state;
 pub mod statistics;
 pub mod timeseries;
 pub mod recurring_transaction;
 pub mod category;
+pub mod diagnostics;
 pub mod scenario;
+pub mod tag;
 pub mod metrics;
+pub mod report;
+pub mod user;
 
 use crate::settings;
 use gloo_net::http::Request;
@@ -40,6 +46,7 @@ where
 {
     let url = format!("{}{}", api_base(), endpoint);
     log::debug!("GET request to: {}", url);
+    let started_at = js_sys::Date::now();
 
     let response = Request::get(&url)
         .send()
@@ -47,12 +54,15 @@ where
         .map_err(|e| {
             let error_msg = format!("Request failed: {}", e);
             log::error!("GET {} - {}", endpoint, error_msg);
+            call_log::record("GET", endpoint, started_at, None, Some(&error_msg));
             error_msg
         })?;
+    let status = response.status();
 
     if !response.ok() {
-        let error_msg = format!("HTTP error: {}", response.status());
+        let error_msg = format!("HTTP error: {}", status);
         log::error!("GET {} - {}", endpoint, error_msg);
+        call_log::record("GET", endpoint, started_at, Some(status), Some(&error_msg));
         return Err(error_msg);
     }
 
@@ -63,13 +73,57 @@ where
         .map_err(|e| {
             let error_msg = format!("Failed to parse response: {}", e);
             log::error!("GET {} - {}", endpoint, error_msg);
+            call_log::record("GET", endpoint, started_at, Some(status), Some(&error_msg));
             error_msg
         })?;
 
+    call_log::record("GET", endpoint, started_at, Some(status), None);
     log::info!("GET {} - Success", endpoint);
     Ok(api_response.data)
 }
 
+/// GET request handler for endpoints that return a bare JSON document instead of an `ApiResponse`
+pub async fn get_raw<T>(endpoint: &str) -> Result<T, String>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let url = format!("{}{}", api_base(), endpoint);
+    log::debug!("GET (raw) request to: {}", url);
+    let started_at = js_sys::Date::now();
+
+    let response = Request::get(&url)
+        .send()
+        .await
+        .map_err(|e| {
+            let error_msg = format!("Request failed: {}", e);
+            log::error!("GET {} - {}", endpoint, error_msg);
+            call_log::record("GET", endpoint, started_at, None, Some(&error_msg));
+            error_msg
+        })?;
+    let status = response.status();
+
+    if !response.ok() {
+        let error_msg = format!("HTTP error: {}", status);
+        log::error!("GET {} - {}", endpoint, error_msg);
+        call_log::record("GET", endpoint, started_at, Some(status), Some(&error_msg));
+        return Err(error_msg);
+    }
+
+    let document: T = response
+        .json()
+        .await
+        .map_err(|e| {
+            let error_msg = format!("Failed to parse response: {}", e);
+            log::error!("GET {} - {}", endpoint, error_msg);
+            call_log::record("GET", endpoint, started_at, Some(status), Some(&error_msg));
+            error_msg
+        })?;
+
+    call_log::record("GET", endpoint, started_at, Some(status), None);
+    log::info!("GET {} - Success", endpoint);
+    Ok(document)
+}
+
 /// Common POST request handler
 pub async fn post<T, B>(endpoint: &str, body: &B) -> Result<T, String>
 where
@@ -78,12 +132,14 @@ where
 {
     let url = format!("{}{}", api_base(), endpoint);
     log::debug!("POST request to: {}", url);
+    let started_at = js_sys::Date::now();
 
     let response = Request::post(&url)
         .json(body)
         .map_err(|e| {
             let error_msg = format!("Failed to serialize request: {}", e);
             log::error!("POST {} - {}", endpoint, error_msg);
+            call_log::record("POST", endpoint, started_at, None, Some(&error_msg));
             error_msg
         })?
         .send()
@@ -91,23 +147,27 @@ where
         .map_err(|e| {
             let error_msg = format!("Request failed: {}", e);
             log::error!("POST {} - {}", endpoint, error_msg);
+            call_log::record("POST", endpoint, started_at, None, Some(&error_msg));
             error_msg
         })?;
+    let status = response.status();
 
     if !response.ok() {
         log::warn!("POST {} - Non-OK response: {}", endpoint, response.status());
         let error_response: Result<ErrorResponse, _> = response.json().await;
-        return Err(match error_response {
+        let error_msg = match error_response {
             Ok(err) => {
                 log::error!("POST {} - API error: {}", endpoint, err.error);
                 format!("Error: {}", err.error)
             }
             Err(_) => {
-                let error_msg = format!("HTTP error: {}", response.status());
+                let error_msg = format!("HTTP error: {}", status);
                 log::error!("POST {} - {}", endpoint, error_msg);
                 error_msg
             }
-        });
+        };
+        call_log::record("POST", endpoint, started_at, Some(status), Some(&error_msg));
+        return Err(error_msg);
     }
 
     log::trace!("POST {} - Response received, parsing JSON", endpoint);
@@ -117,9 +177,11 @@ where
         .map_err(|e| {
             let error_msg = format!("Failed to parse response: {}", e);
             log::error!("POST {} - {}", endpoint, error_msg);
+            call_log::record("POST", endpoint, started_at, Some(status), Some(&error_msg));
             error_msg
         })?;
 
+    call_log::record("POST", endpoint, started_at, Some(status), None);
     log::info!("POST {} - Success", endpoint);
     Ok(api_response.data)
 }
@@ -132,12 +194,14 @@ where
 {
     let url = format!("{}{}", api_base(), endpoint);
     log::debug!("PUT request to: {}", url);
+    let started_at = js_sys::Date::now();
 
     let response = Request::put(&url)
         .json(body)
         .map_err(|e| {
             let error_msg = format!("Failed to serialize request: {}", e);
             log::error!("PUT {} - {}", endpoint, error_msg);
+            call_log::record("PUT", endpoint, started_at, None, Some(&error_msg));
             error_msg
         })?
         .send()
@@ -145,23 +209,27 @@ where
         .map_err(|e| {
             let error_msg = format!("Request failed: {}", e);
             log::error!("PUT {} - {}", endpoint, error_msg);
+            call_log::record("PUT", endpoint, started_at, None, Some(&error_msg));
             error_msg
         })?;
+    let status = response.status();
 
     if !response.ok() {
         log::warn!("PUT {} - Non-OK response: {}", endpoint, response.status());
         let error_response: Result<ErrorResponse, _> = response.json().await;
-        return Err(match error_response {
+        let error_msg = match error_response {
             Ok(err) => {
                 log::error!("PUT {} - API error: {}", endpoint, err.error);
                 format!("Error: {}", err.error)
             }
             Err(_) => {
-                let error_msg = format!("HTTP error: {}", response.status());
+                let error_msg = format!("HTTP error: {}", status);
                 log::error!("PUT {} - {}", endpoint, error_msg);
                 error_msg
             }
-        });
+        };
+        call_log::record("PUT", endpoint, started_at, Some(status), Some(&error_msg));
+        return Err(error_msg);
     }
 
     log::trace!("PUT {} - Response received, parsing JSON", endpoint);
@@ -171,9 +239,11 @@ where
         .map_err(|e| {
             let error_msg = format!("Failed to parse response: {}", e);
             log::error!("PUT {} - {}", endpoint, error_msg);
+            call_log::record("PUT", endpoint, started_at, Some(status), Some(&error_msg));
             error_msg
         })?;
 
+    call_log::record("PUT", endpoint, started_at, Some(status), None);
     log::info!("PUT {} - Success", endpoint);
     Ok(api_response.data)
 }
@@ -185,6 +255,7 @@ where
 {
     let url = format!("{}{}", api_base(), endpoint);
     log::debug!("DELETE request to: {}", url);
+    let started_at = js_sys::Date::now();
 
     let response = Request::delete(&url)
         .send()
@@ -192,23 +263,27 @@ where
         .map_err(|e| {
             let error_msg = format!("Request failed: {}", e);
             log::error!("DELETE {} - {}", endpoint, error_msg);
+            call_log::record("DELETE", endpoint, started_at, None, Some(&error_msg));
             error_msg
         })?;
+    let status = response.status();
 
     if !response.ok() {
         log::warn!("DELETE {} - Non-OK response: {}", endpoint, response.status());
         let error_response: Result<ErrorResponse, _> = response.json().await;
-        return Err(match error_response {
+        let error_msg = match error_response {
             Ok(err) => {
                 log::error!("DELETE {} - API error: {}", endpoint, err.error);
                 format!("Error: {}", err.error)
             }
             Err(_) => {
-                let error_msg = format!("HTTP error: {}", response.status());
+                let error_msg = format!("HTTP error: {}", status);
                 log::error!("DELETE {} - {}", endpoint, error_msg);
                 error_msg
             }
-        });
+        };
+        call_log::record("DELETE", endpoint, started_at, Some(status), Some(&error_msg));
+        return Err(error_msg);
     }
 
     log::trace!("DELETE {} - Response received, parsing JSON", endpoint);
@@ -218,9 +293,11 @@ where
         .map_err(|e| {
             let error_msg = format!("Failed to parse response: {}", e);
             log::error!("DELETE {} - {}", endpoint, error_msg);
+            call_log::record("DELETE", endpoint, started_at, Some(status), Some(&error_msg));
             error_msg
         })?;
 
+    call_log::record("DELETE", endpoint, started_at, Some(status), None);
     log::info!("DELETE {} - Success", endpoint);
     Ok(api_response.data)
 }
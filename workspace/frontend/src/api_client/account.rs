@@ -80,6 +80,16 @@ impl AccountKind {
     }
 }
 
+/// How an account's balance moves between its manual states
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum BalanceInterpolation {
+    /// Balance holds until the next state
+    #[default]
+    Step,
+    /// Balance moves in a straight line towards the next state
+    Linear,
+}
+
 /// Account response model
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct AccountResponse {
@@ -94,6 +104,19 @@ pub struct AccountResponse {
     pub target_amount: Option<String>,
     pub color: Option<String>,
     pub is_liquid: bool,
+    #[serde(default)]
+    pub balance_interpolation: BalanceInterpolation,
+    /// Only returned when fetching or updating a single account
+    #[serde(default)]
+    pub statistics_exclusions: Option<Vec<StatisticsExclusion>>,
+}
+
+/// Date range of an account left out of statistics and budgets
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct StatisticsExclusion {
+    pub start_date: String,
+    pub end_date: String,
+    pub reason: Option<String>,
 }
 
 /// Account statistics response
@@ -139,6 +162,7 @@ pub struct CreateAccountRequest {
     pub target_amount: Option<String>,
     pub color: Option<String>,
     pub is_liquid: Option<bool>,
+    pub balance_interpolation: Option<BalanceInterpolation>,
 }
 
 /// Request body for updating an account
@@ -153,6 +177,10 @@ pub struct UpdateAccountRequest {
     pub target_amount: Option<String>,
     pub color: Option<String>,
     pub is_liquid: Option<bool>,
+    pub balance_interpolation: Option<BalanceInterpolation>,
+    /// Replaces the account's exclusions when set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statistics_exclusions: Option<Vec<StatisticsExclusion>>,
 }
 
 /// Get all accounts
@@ -0,0 +1,65 @@
+use crate::api_client::{self, ApiResponse};
+use serde::{Deserialize, Serialize};
+
+/// Prefix of the server routes batched endpoints are resolved against.
+const API_PREFIX: &str = "/api/v1";
+
+/// One request of a batch
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchRequestItem {
+    pub method: String,
+    pub path: String,
+    pub body: Option<serde_json::Value>,
+}
+
+impl BatchRequestItem {
+    /// A GET of `endpoint`, given relative to the API base like the other client calls.
+    pub fn get(endpoint: &str) -> Self {
+        Self {
+            method: "GET".to_string(),
+            path: format!("{}{}", API_PREFIX, endpoint),
+            body: None,
+        }
+    }
+}
+
+/// Outcome of one request of a batch
+#[derive(Debug, Clone, Deserialize)]
+pub struct BatchResponseItem {
+    pub status: u16,
+    pub body: serde_json::Value,
+}
+
+impl BatchResponseItem {
+    /// The `data` of a successful response.
+    pub fn data<T>(self) -> Result<T, String>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        if !(200..300).contains(&self.status) {
+            return Err(format!("HTTP error: {}", self.status));
+        }
+        serde_json::from_value::<ApiResponse<T>>(self.body)
+            .map(|response| response.data)
+            .map_err(|e| format!("Failed to parse response: {}", e))
+    }
+}
+
+#[derive(Serialize)]
+struct BatchRequest<'a> {
+    requests: &'a [BatchRequestItem],
+}
+
+/// Sends up to 20 requests in one round trip; responses come back in request order.
+pub async fn batch(requests: &[BatchRequestItem]) -> Result<Vec<BatchResponseItem>, String> {
+    log::trace!("Sending batch of {} requests", requests.len());
+    let result = api_client::post::<Vec<BatchResponseItem>, _>("/batch", &BatchRequest { requests }).await;
+
+    if let Err(ref e) = result {
+        log::error!("Failed to execute batch: {}", e);
+    } else {
+        log::info!("Successfully executed batch of {} requests", requests.len());
+    }
+
+    result
+}
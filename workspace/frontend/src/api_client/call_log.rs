@@ -0,0 +1,52 @@
+use serde::Serialize;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+/// How many of the most recent API calls are kept for diagnostic reports.
+const MAX_ENTRIES: usize = 50;
+
+/// One API call as recorded by the client, sent along with diagnostic reports.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ApiCallRecord {
+    pub method: String,
+    pub endpoint: String,
+    /// `None` when the request never got a response
+    pub status: Option<u16>,
+    pub duration_ms: Option<f64>,
+    pub error: Option<String>,
+}
+
+thread_local! {
+    static CALLS: RefCell<VecDeque<ApiCallRecord>> = RefCell::new(VecDeque::with_capacity(MAX_ENTRIES));
+}
+
+/// Append a finished call to the log, dropping the oldest entry when full.
+pub(crate) fn record(method: &str, endpoint: &str, started_at: f64, status: Option<u16>, error: Option<&str>) {
+    let entry = ApiCallRecord {
+        method: method.to_string(),
+        endpoint: endpoint.to_string(),
+        status,
+        duration_ms: Some(js_sys::Date::now() - started_at),
+        error: error.map(str::to_string),
+    };
+    CALLS.with(|calls| {
+        if let Ok(mut calls) = calls.try_borrow_mut() {
+            if calls.len() == MAX_ENTRIES {
+                calls.pop_front();
+            }
+            calls.push_back(entry);
+        }
+    });
+}
+
+/// The recorded calls, oldest first.
+///
+/// Never panics, so it is safe to call from the panic hook.
+pub fn recent_calls() -> Vec<ApiCallRecord> {
+    CALLS.with(|calls| {
+        calls
+            .try_borrow()
+            .map(|calls| calls.iter().cloned().collect())
+            .unwrap_or_default()
+    })
+}
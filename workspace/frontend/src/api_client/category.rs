@@ -124,3 +124,32 @@ pub async fn get_category_stats(start_date: &str, end_date: &str) -> Result<Vec<
     }
     result
 }
+
+/// Expected spend in one forecasted month
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct MonthlyForecastEntry {
+    pub month: String,
+    pub amount: String,
+}
+
+/// Per-category spending forecast, including child categories
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CategoryForecast {
+    pub category_id: i32,
+    pub category_name: String,
+    pub lookback_months: u32,
+    pub average_monthly: String,
+    pub suggested_budget: String,
+    pub forecast: Vec<MonthlyForecastEntry>,
+}
+
+/// Get the spending forecast and suggested budget for a category
+pub async fn get_category_forecast(category_id: i32) -> Result<CategoryForecast, String> {
+    log::trace!("Fetching forecast for category ID: {}", category_id);
+    let result = api_client::get::<CategoryForecast>(&format!("/categories/{}/forecast", category_id)).await;
+    match &result {
+        Ok(forecast) => log::info!("Fetched forecast for category: {} (suggested budget {})", forecast.category_name, forecast.suggested_budget),
+        Err(e) => log::error!("Failed to fetch forecast for category {}: {}", category_id, e),
+    }
+    result
+}
@@ -0,0 +1,23 @@
+use serde::Serialize;
+use crate::api_client::call_log::ApiCallRecord;
+use crate::settings;
+
+/// Payload of `POST /diagnostics`
+///
+/// `route` and `user_agent` are filled in by the crash screen script.
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticReport {
+    pub message: String,
+    pub route: Option<String>,
+    pub app_version: Option<String>,
+    pub user_agent: Option<String>,
+    pub api_calls: Vec<ApiCallRecord>,
+}
+
+/// Absolute URL of the diagnostics endpoint.
+///
+/// Reports are sent by plain JavaScript after a panic, when the Rust side can no
+/// longer run async code, so the crash screen needs the full URL up front.
+pub fn diagnostics_url() -> String {
+    settings::get_settings().api_url("/diagnostics")
+}
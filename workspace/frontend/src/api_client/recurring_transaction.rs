@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use crate::api_client;
+use common::PaginatedResponse;
 use rust_decimal::Decimal;
 
 /// Recurrence period enum (matching backend)
@@ -92,6 +93,12 @@ pub struct RecurringTransactionResponse {
     pub tags: Vec<TagInfo>,
     pub scenario_id: Option<i32>,
     pub is_simulated: bool,
+    /// "Approved", "PendingApproval" or "Rejected"
+    pub approval_status: String,
+    pub created_by_user_id: Option<i32>,
+    pub reviewed_by_user_id: Option<i32>,
+    pub notice_period_days: Option<i32>,
+    pub renewal_date: Option<String>,
 }
 
 /// Recurring transaction instance response model
@@ -110,7 +117,23 @@ pub struct RecurringInstanceResponse {
     pub paid_date: Option<String>,
     pub paid_amount: Option<String>,
     pub reconciled_imported_transaction_id: Option<i32>,
+    #[serde(default)]
+    pub category_id: Option<i32>,
+    #[serde(default)]
+    pub category_name: Option<String>,
+    #[serde(default)]
+    pub scenario_id: Option<i32>,
+    #[serde(default)]
+    pub scenario_name: Option<String>,
+    #[serde(default)]
+    pub is_simulated: bool,
     pub tags: Vec<TagInfo>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub disputed: bool,
+    #[serde(default)]
+    pub overdue: bool,
 }
 
 /// Request body for creating a recurring transaction
@@ -129,6 +152,7 @@ pub struct CreateRecurringTransactionRequest {
     pub category_id: Option<i32>,
     pub scenario_id: Option<i32>,
     pub is_simulated: Option<bool>,
+    pub created_by_user_id: Option<i32>,
 }
 
 /// Request body for updating a recurring transaction
@@ -176,15 +200,13 @@ pub async fn get_recurring_transaction(id: i32) -> Result<RecurringTransactionRe
     }
 }
 
-pub async fn get_recurring_transactions(
+fn recurring_transactions_url(
     page: Option<u64>,
     limit: Option<u64>,
     target_account_id: Option<i32>,
     source_account_id: Option<i32>,
     category_id: Option<i32>,
-) -> Result<Vec<RecurringTransactionResponse>, String> {
-    log::trace!("Fetching recurring transactions");
-
+) -> String {
     let mut query_params = Vec::new();
     if let Some(p) = page {
         query_params.push(format!("page={}", p));
@@ -207,9 +229,20 @@ pub async fn get_recurring_transactions(
     } else {
         format!("?{}", query_params.join("&"))
     };
+    format!("/recurring-transactions{}", query_string)
+}
+
+pub async fn get_recurring_transactions(
+    page: Option<u64>,
+    limit: Option<u64>,
+    target_account_id: Option<i32>,
+    source_account_id: Option<i32>,
+    category_id: Option<i32>,
+) -> Result<Vec<RecurringTransactionResponse>, String> {
+    log::trace!("Fetching recurring transactions");
 
     let result = api_client::get::<Vec<RecurringTransactionResponse>>(
-        &format!("/recurring-transactions{}", query_string)
+        &recurring_transactions_url(page, limit, target_account_id, source_account_id, category_id)
     ).await;
 
     match &result {
@@ -219,6 +252,30 @@ pub async fn get_recurring_transactions(
     result
 }
 
+/// Get one page of recurring transactions together with the total number of matching ones
+pub async fn get_recurring_transactions_page(
+    page: u64,
+    limit: u64,
+    target_account_id: Option<i32>,
+    category_id: Option<i32>,
+) -> Result<PaginatedResponse<Vec<RecurringTransactionResponse>>, String> {
+    log::trace!("Fetching recurring transaction page {}", page);
+
+    let result = api_client::get_raw::<PaginatedResponse<Vec<RecurringTransactionResponse>>>(
+        &recurring_transactions_url(Some(page), Some(limit), target_account_id, None, category_id)
+    ).await;
+
+    match &result {
+        Ok(response) => log::info!(
+            "Fetched {} of {} recurring transactions",
+            response.data.len(),
+            response.pagination.total_count
+        ),
+        Err(e) => log::error!("Failed to fetch recurring transaction page: {}", e),
+    }
+    result
+}
+
 /// Create a new recurring transaction
 pub async fn create_recurring_transaction(
     request: CreateRecurringTransactionRequest
@@ -268,6 +325,41 @@ pub async fn delete_recurring_transaction(id: i32) -> Result<String, String> {
     result
 }
 
+/// Empty request body for approving or rejecting a recurring transaction; the server
+/// takes the reviewer from the session
+#[derive(Debug, Serialize)]
+struct ReviewRecurringTransactionRequest {}
+
+/// Approve a recurring transaction awaiting approval
+pub async fn approve_recurring_transaction(id: i32) -> Result<RecurringTransactionResponse, String> {
+    log::debug!("Approving recurring transaction ID: {}", id);
+    let result = api_client::post::<RecurringTransactionResponse, _>(
+        &format!("/recurring-transactions/{}/approve", id),
+        &ReviewRecurringTransactionRequest {}
+    ).await;
+
+    match &result {
+        Ok(_) => log::info!("Successfully approved recurring transaction ID: {}", id),
+        Err(e) => log::error!("Failed to approve recurring transaction {}: {}", id, e),
+    }
+    result
+}
+
+/// Reject a recurring transaction awaiting approval
+pub async fn reject_recurring_transaction(id: i32) -> Result<RecurringTransactionResponse, String> {
+    log::debug!("Rejecting recurring transaction ID: {}", id);
+    let result = api_client::post::<RecurringTransactionResponse, _>(
+        &format!("/recurring-transactions/{}/reject", id),
+        &ReviewRecurringTransactionRequest {}
+    ).await;
+
+    match &result {
+        Ok(_) => log::info!("Successfully rejected recurring transaction ID: {}", id),
+        Err(e) => log::error!("Failed to reject recurring transaction {}: {}", id, e),
+    }
+    result
+}
+
 /// Create a new recurring transaction instance
 pub async fn create_recurring_instance(
     recurring_transaction_id: i32,
@@ -347,6 +439,9 @@ pub struct UpdateRecurringInstanceRequest {
     pub expected_amount: Option<String>,
     pub paid_date: Option<String>,
     pub paid_amount: Option<String>,
+    /// An empty string clears the notes
+    pub notes: Option<String>,
+    pub disputed: Option<bool>,
 }
 
 /// Update an existing recurring transaction instance
@@ -392,6 +487,12 @@ pub struct MissingInstanceInfo {
     pub is_pending: bool,
     /// If the instance exists (pending), this is its ID
     pub instance_id: Option<i32>,
+    /// Days since the due date
+    #[serde(default)]
+    pub overdue_days: i64,
+    /// "Due", "Notice", "Warning" or "Critical"
+    #[serde(default)]
+    pub severity: String,
 }
 
 /// Get missing instances for recurring transactions
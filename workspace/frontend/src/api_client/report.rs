@@ -0,0 +1,37 @@
+use crate::api_client;
+use common::reports::{CashflowReportDto, FlowReportDto, ReportGranularity};
+
+/// Fetches income, expense and net per period over the last year across all accounts.
+pub async fn get_cashflow_report(granularity: ReportGranularity) -> Result<CashflowReportDto, String> {
+    log::trace!("Fetching {:?} cashflow report", granularity);
+    let granularity = match granularity {
+        ReportGranularity::Week => "week",
+        ReportGranularity::Month => "month",
+        ReportGranularity::Quarter => "quarter",
+    };
+    let url = format!("/reports/cashflow?granularity={}", granularity);
+    let result = api_client::get::<CashflowReportDto>(&url).await;
+
+    if let Err(ref e) = result {
+        log::error!("Failed to fetch cashflow report: {}", e);
+    } else {
+        log::info!("Successfully fetched cashflow report");
+    }
+
+    result
+}
+
+/// Fetches the money flow from income sources through accounts to spending categories
+/// over the last year, for the Sankey diagram.
+pub async fn get_flow_report() -> Result<FlowReportDto, String> {
+    log::trace!("Fetching flow report");
+    let result = api_client::get::<FlowReportDto>("/reports/flows").await;
+
+    if let Err(ref e) = result {
+        log::error!("Failed to fetch flow report: {}", e);
+    } else {
+        log::info!("Successfully fetched flow report");
+    }
+
+    result
+}
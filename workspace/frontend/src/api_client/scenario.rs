@@ -117,3 +117,20 @@ pub async fn apply_scenario(scenario_id: i32) -> Result<String, String> {
     }
     result
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloneScenarioRequest {
+    pub name: Option<String>,
+}
+
+/// Clone a scenario together with its simulated transactions
+pub async fn clone_scenario(scenario_id: i32, request: CloneScenarioRequest) -> Result<Scenario, String> {
+    log::debug!("Cloning scenario {}", scenario_id);
+    let url = format!("/scenarios/{}/clone", scenario_id);
+    let result = api_client::post::<Scenario, _>(&url, &request).await;
+    match &result {
+        Ok(scenario) => log::info!("Cloned scenario {} into: {} (ID: {})", scenario_id, scenario.name, scenario.id),
+        Err(e) => log::error!("Failed to clone scenario {}: {}", scenario_id, e),
+    }
+    result
+}
@@ -96,3 +96,59 @@ pub async fn get_all_accounts_statistics() -> Result<Vec<AccountStatisticsCollec
 
     result
 }
+
+/// What a committed outflow comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommitmentType {
+    RecurringPayment,
+    PlannedTransaction,
+    BudgetRemainder,
+}
+
+impl CommitmentType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CommitmentType::RecurringPayment => "Recurring payment",
+            CommitmentType::PlannedTransaction => "Planned",
+            CommitmentType::BudgetRemainder => "Budget",
+        }
+    }
+}
+
+/// An outflow subtracted from the balance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommittedOutflow {
+    pub kind: CommitmentType,
+    pub source_id: i32,
+    pub name: String,
+    pub date: NaiveDate,
+    pub category_id: Option<i32>,
+    pub amount: Decimal,
+}
+
+/// How much can be spent today without touching money already committed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SafeToSpendResponse {
+    pub safe_to_spend: Decimal,
+    pub balance: Decimal,
+    pub committed: Decimal,
+    pub today: NaiveDate,
+    pub next_income_date: Option<NaiveDate>,
+    pub until: NaiveDate,
+    pub account_ids: Vec<i32>,
+    pub commitments: Vec<CommittedOutflow>,
+}
+
+/// Fetch the safe-to-spend figure of the default liquid accounts.
+pub async fn get_safe_to_spend() -> Result<SafeToSpendResponse, String> {
+    log::trace!("Fetching safe-to-spend");
+    let result = api_client::get::<SafeToSpendResponse>("/statistics/safe-to-spend").await;
+
+    if let Err(ref e) = result {
+        log::error!("Failed to fetch safe-to-spend: {}", e);
+    } else {
+        log::info!("Successfully fetched safe-to-spend");
+    }
+
+    result
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use crate::api_client;
+
+/// Spending of a tag against its monthly limit
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TagLimit {
+    pub tag_id: i32,
+    pub tag_name: String,
+    pub limit: String,
+    pub spent: String,
+    pub remaining: String,
+    /// "WithinLimit", "Warning" or "Exceeded"
+    pub level: String,
+}
+
+/// Tag spending limits for one month
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TagLimits {
+    pub year: i32,
+    pub month: u32,
+    pub limits: Vec<TagLimit>,
+}
+
+/// Get the spending of every tag with a monthly limit in the current month
+pub async fn get_tag_limits() -> Result<TagLimits, String> {
+    log::trace!("Fetching tag spending limits");
+    let result = api_client::get::<TagLimits>("/tags/limits").await;
+    match &result {
+        Ok(limits) => log::info!("Fetched {} tag limits for {}-{:02}", limits.limits.len(), limits.year, limits.month),
+        Err(e) => log::error!("Failed to fetch tag limits: {}", e),
+    }
+    result
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
+use common::{BalancePreviewRequest, BalancePreviewResponse};
 use crate::api_client;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -73,6 +74,51 @@ pub async fn get_account_timeseries_with_scenario(
     result
 }
 
+/// Downsampled balance history for drawing an inline sparkline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountSparkline {
+    pub account_id: i32,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub points: Vec<AccountStatePoint>,
+    pub min_balance: Option<Decimal>,
+    pub max_balance: Option<Decimal>,
+    pub change: Option<Decimal>,
+}
+
+pub async fn get_account_sparkline(account_id: i32, days: u32) -> Result<AccountSparkline, String> {
+    log::trace!("Fetching {}-day sparkline for account ID: {}", days, account_id);
+    let url = format!("/accounts/{}/sparkline?days={}", account_id, days);
+    let result = api_client::get::<AccountSparkline>(&url).await;
+
+    if let Err(ref e) = result {
+        log::error!("Failed to fetch account sparkline: {}", e);
+    } else {
+        log::info!("Successfully fetched sparkline for account ID: {}", account_id);
+    }
+
+    result
+}
+
+/// Authoritative balance impact of an unsaved transaction, computed by the server
+pub async fn preview_account_balance(
+    account_id: i32,
+    request: &BalancePreviewRequest,
+) -> Result<BalancePreviewResponse, String> {
+    log::trace!("Previewing balance of account ID: {} from {} to {}",
+        account_id, request.start_date, request.end_date);
+    let url = format!("/accounts/{}/balance-preview", account_id);
+    let result = api_client::post::<BalancePreviewResponse, _>(&url, request).await;
+
+    if let Err(ref e) = result {
+        log::error!("Failed to preview account balance: {}", e);
+    } else {
+        log::info!("Successfully previewed balance for account ID: {}", account_id);
+    }
+
+    result
+}
+
 pub async fn get_all_accounts_timeseries(
     start_date: NaiveDate,
     end_date: NaiveDate,
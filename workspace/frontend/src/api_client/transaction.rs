@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
 use crate::api_client;
+use common::{PaginatedResponse, TransactionFeatureCollection};
 
 /// Tag information for API responses
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
@@ -28,6 +29,23 @@ pub struct TransactionResponse {
     pub tags: Vec<TagInfo>,
     pub scenario_id: Option<i32>,
     pub is_simulated: bool,
+    /// Amount in the currency the payment was made in; `amount` is the settled amount
+    #[serde(default)]
+    pub original_amount: Option<Decimal>,
+    #[serde(default)]
+    pub original_currency: Option<String>,
+    #[serde(default)]
+    pub conversion_rate: Option<Decimal>,
+    #[serde(default)]
+    pub is_purchase: bool,
+    /// Last day the warranty covers the purchased item
+    #[serde(default)]
+    pub warranty_end_date: Option<NaiveDate>,
+    /// Last day the purchased item can be returned
+    #[serde(default)]
+    pub return_deadline: Option<NaiveDate>,
+    #[serde(default)]
+    pub receipt_document_id: Option<i32>,
 }
 
 /// Request body for creating a new transaction
@@ -74,12 +92,7 @@ pub struct TransactionFilters {
     pub month: Option<u32>,
 }
 
-/// Get all transactions with optional pagination and filters
-pub async fn get_transactions(
-    page: Option<u64>,
-    limit: Option<u64>,
-    filters: &TransactionFilters,
-) -> Result<Vec<TransactionResponse>, String> {
+fn transactions_url(page: Option<u64>, limit: Option<u64>, filters: &TransactionFilters) -> String {
     let mut url = "/transactions".to_string();
     let mut params = vec![];
 
@@ -109,6 +122,16 @@ pub async fn get_transactions(
         url.push('?');
         url.push_str(&params.join("&"));
     }
+    url
+}
+
+/// Get all transactions with optional pagination and filters
+pub async fn get_transactions(
+    page: Option<u64>,
+    limit: Option<u64>,
+    filters: &TransactionFilters,
+) -> Result<Vec<TransactionResponse>, String> {
+    let url = transactions_url(page, limit, filters);
 
     log::trace!("Fetching transactions: {}", url);
     let result = api_client::get::<Vec<TransactionResponse>>(&url).await;
@@ -119,6 +142,55 @@ pub async fn get_transactions(
     result
 }
 
+/// Get one page of transactions together with the total number of matching transactions
+pub async fn get_transactions_page(
+    page: u64,
+    limit: u64,
+    filters: &TransactionFilters,
+) -> Result<PaginatedResponse<Vec<TransactionResponse>>, String> {
+    let url = transactions_url(Some(page), Some(limit), filters);
+
+    log::trace!("Fetching transaction page: {}", url);
+    let result = api_client::get_raw::<PaginatedResponse<Vec<TransactionResponse>>>(&url).await;
+    match &result {
+        Ok(response) => log::info!(
+            "Fetched {} of {} transactions",
+            response.data.len(),
+            response.pagination.total_count
+        ),
+        Err(e) => log::error!("Failed to fetch transaction page: {}", e),
+    }
+    result
+}
+
+/// Get geolocated transactions as a GeoJSON feature collection
+pub async fn get_transactions_geojson(
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+) -> Result<TransactionFeatureCollection, String> {
+    let mut params = vec![];
+    if let Some(start) = start_date {
+        params.push(format!("start_date={}", start));
+    }
+    if let Some(end) = end_date {
+        params.push(format!("end_date={}", end));
+    }
+
+    let mut url = "/transactions/geojson".to_string();
+    if !params.is_empty() {
+        url.push('?');
+        url.push_str(&params.join("&"));
+    }
+
+    log::trace!("Fetching transactions GeoJSON: {}", url);
+    let result = api_client::get_raw::<TransactionFeatureCollection>(&url).await;
+    match &result {
+        Ok(collection) => log::info!("Fetched {} geolocated transactions", collection.features.len()),
+        Err(e) => log::error!("Failed to fetch transactions GeoJSON: {}", e),
+    }
+    result
+}
+
 /// Get a specific transaction by ID
 pub async fn get_transaction(transaction_id: i32) -> Result<TransactionResponse, String> {
     log::trace!("Fetching transaction with ID: {}", transaction_id);
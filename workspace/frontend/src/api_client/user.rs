@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use crate::api_client;
+
+/// Per-user preferences stored on the server
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct UserSettings {
+    pub active_scenario_id: Option<i32>,
+    /// "light", "dark" or "system"; `None` follows the system preference
+    pub theme: Option<String>,
+    /// IANA timezone deciding what "today" is; `None` uses the server's timezone
+    pub timezone: Option<String>,
+}
+
+/// Get the settings of a user
+pub async fn get_user_settings(user_id: i32) -> Result<UserSettings, String> {
+    log::trace!("Fetching settings for user {}", user_id);
+    let url = format!("/users/{}/settings", user_id);
+    let result = api_client::get::<UserSettings>(&url).await;
+    match &result {
+        Ok(settings) => log::info!("Fetched settings for user {}: {:?}", user_id, settings),
+        Err(e) => log::error!("Failed to fetch settings for user {}: {}", user_id, e),
+    }
+    result
+}
+
+/// Replace the settings of a user
+pub async fn update_user_settings(user_id: i32, settings: UserSettings) -> Result<UserSettings, String> {
+    log::debug!("Updating settings for user {}: {:?}", user_id, settings);
+    let url = format!("/users/{}/settings", user_id);
+    let result = api_client::put::<UserSettings, _>(&url, &settings).await;
+    match &result {
+        Ok(_) => log::info!("Updated settings for user {}", user_id),
+        Err(e) => log::error!("Failed to update settings for user {}: {}", user_id, e),
+    }
+    result
+}
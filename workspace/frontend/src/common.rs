@@ -3,3 +3,4 @@ pub mod toast;
 pub mod error;
 pub mod fetch_render;
 pub mod fetch_hook;
+pub mod shortcuts;
@@ -0,0 +1,134 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::KeyboardEvent;
+use yew::prelude::*;
+
+/// A key binding as listed in the shortcuts help modal.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Shortcut {
+    pub keys: &'static str,
+    pub description: &'static str,
+}
+
+/// Every shortcut the app understands, in the order shown in the help modal.
+pub const SHORTCUTS: &[Shortcut] = &[
+    Shortcut { keys: "j / k", description: "Move down / up in the table" },
+    Shortcut { keys: "Enter", description: "Open the selected row" },
+    Shortcut { keys: "n", description: "New transaction" },
+    Shortcut { keys: "/", description: "Focus the search box" },
+    Shortcut { keys: "?", description: "Show this help" },
+    Shortcut { keys: "Esc", description: "Close this help" },
+];
+
+type Bindings = Rc<RefCell<Vec<(&'static str, Callback<()>)>>>;
+
+/// Keys typed into form fields must not trigger shortcuts; Enter on a
+/// focused link or button keeps its native behavior too.
+fn should_ignore(event: &KeyboardEvent) -> bool {
+    if event.ctrl_key() || event.meta_key() || event.alt_key() {
+        return true;
+    }
+
+    let Some(element) = event.target().and_then(|t| t.dyn_into::<web_sys::Element>().ok()) else {
+        return false;
+    };
+    let tag = element.tag_name();
+    let is_editable = matches!(tag.as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+        || element.has_attribute("contenteditable");
+    let is_activatable = matches!(tag.as_str(), "A" | "BUTTON");
+
+    is_editable || (is_activatable && event.key() == "Enter")
+}
+
+/// Registers page-level keyboard shortcuts for as long as the component is mounted.
+///
+/// `bindings` maps a `KeyboardEvent.key` value (e.g. `"n"`, `"/"`, `"?"`) to the
+/// callback it triggers. The listener is attached to the window once; the
+/// bindings are refreshed on every render so callbacks always see current state.
+#[hook]
+pub fn use_keyboard_shortcuts(bindings: Vec<(&'static str, Callback<()>)>) {
+    let current: Bindings = use_mut_ref(Vec::new);
+    *current.borrow_mut() = bindings;
+
+    use_effect_with((), move |_| {
+        let listener = Closure::<dyn Fn(KeyboardEvent)>::new(move |event: KeyboardEvent| {
+            if should_ignore(&event) {
+                return;
+            }
+
+            let key = event.key();
+            let action = current
+                .borrow()
+                .iter()
+                .find(|(bound, _)| *bound == key)
+                .map(|(_, action)| action.clone());
+
+            if let Some(action) = action {
+                log::trace!("Keyboard shortcut '{}' triggered", key);
+                event.prevent_default();
+                action.emit(());
+            }
+        });
+
+        let window = web_sys::window();
+        if let Some(window) = &window {
+            let _ = window.add_event_listener_with_callback("keydown", listener.as_ref().unchecked_ref());
+        }
+
+        move || {
+            if let Some(window) = window {
+                let _ = window.remove_event_listener_with_callback("keydown", listener.as_ref().unchecked_ref());
+            }
+        }
+    });
+}
+
+/// j/k row navigation for a table of `len` rows.
+///
+/// Returns the index of the selected row (if any); Enter emits `on_open`
+/// with that index. The selection resets whenever the number of rows changes.
+#[hook]
+pub fn use_row_navigation(len: usize, on_open: Callback<usize>) -> Option<usize> {
+    let selected = use_state(|| None::<usize>);
+
+    {
+        let selected = selected.clone();
+        use_effect_with(len, move |_| {
+            selected.set(None);
+            || ()
+        });
+    }
+
+    let next = {
+        let selected = selected.clone();
+        Callback::from(move |_| {
+            if len > 0 {
+                selected.set(Some(selected.map_or(0, |index| (index + 1).min(len - 1))));
+            }
+        })
+    };
+
+    let previous = {
+        let selected = selected.clone();
+        Callback::from(move |_| {
+            if len > 0 {
+                selected.set(Some(selected.map_or(0, |index| index.saturating_sub(1))));
+            }
+        })
+    };
+
+    let open = {
+        let selected = selected.clone();
+        Callback::from(move |_| {
+            if let Some(index) = *selected {
+                on_open.emit(index);
+            }
+        })
+    };
+
+    use_keyboard_shortcuts(vec![("j", next), ("k", previous), ("Enter", open)]);
+
+    *selected
+}
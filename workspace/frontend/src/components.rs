@@ -1,5 +1,6 @@
 pub mod ai_prompt;
 pub mod common;
+pub mod error_boundary;
 pub mod layout;
 pub mod dashboard;
 pub mod accounts;
@@ -11,6 +12,8 @@ pub mod forecast;
 pub mod reports;
 pub mod settings;
 pub mod modals;
+pub mod print;
 pub mod manual_states;
 pub mod categories;
 pub mod scenarios;
+pub mod spending_map;
@@ -9,6 +9,7 @@ mod account_chart;
 mod account_forecast;
 mod account_metrics;
 mod goal_progress;
+mod sparkline;
 pub use account_cashflow_chart::AccountCashflowChart;
 pub use account_chart::AccountChart;
 pub use account_edit::AccountEdit;
@@ -4,6 +4,7 @@ use crate::common::fetch_hook::use_fetch_with_refetch;
 use crate::formatting::{fmt_amount, fmt_amount_f64};
 use crate::hooks::FetchState;
 use crate::Route;
+use super::sparkline::AccountSparkline;
 use yew::prelude::*;
 use yew_router::prelude::*;
 
@@ -90,9 +91,12 @@ pub fn account_card(props: &Props) -> Html {
                         }}
                     </div>
                 </div>
-                <div class="mt-4">
-                    <div class="text-xs text-gray-500 mb-2">{"Currency"}</div>
-                    <div class="badge badge-secondary badge-outline badge-sm">{&account.currency_code}</div>
+                <div class="mt-4 flex justify-between items-end">
+                    <div>
+                        <div class="text-xs text-gray-500 mb-2">{"Currency"}</div>
+                        <div class="badge badge-secondary badge-outline badge-sm">{&account.currency_code}</div>
+                    </div>
+                    <AccountSparkline account_id={account.id} />
                 </div>
 
                 // Display stats section - progress bar view for accounts with targets
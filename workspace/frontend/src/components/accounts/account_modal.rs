@@ -1,4 +1,4 @@
-use crate::api_client::account::{create_account, update_account, AccountKind, AccountResponse, CreateAccountRequest, UpdateAccountRequest};
+use crate::api_client::account::{create_account, update_account, AccountKind, AccountResponse, BalanceInterpolation, CreateAccountRequest, UpdateAccountRequest};
 use crate::colors;
 use yew::prelude::*;
 
@@ -72,6 +72,10 @@ pub fn account_modal(props: &AccountModalProps) -> Html {
                     _ => AccountKind::RealAccount,
                 };
                 let is_liquid = *is_liquid_state;
+                let balance_interpolation = match form_data.get("balance_interpolation").as_string().as_deref() {
+                    Some("Linear") => BalanceInterpolation::Linear,
+                    _ => BalanceInterpolation::Step,
+                };
 
                 let is_submitting = is_submitting.clone();
                 let error_message = error_message.clone();
@@ -95,6 +99,8 @@ pub fn account_modal(props: &AccountModalProps) -> Html {
                         target_amount: target_amount.clone(),
                         color: color.clone(),
                         is_liquid: Some(is_liquid),
+                        balance_interpolation: Some(balance_interpolation),
+                        statistics_exclusions: None,
                     };
 
                     wasm_bindgen_futures::spawn_local(async move {
@@ -126,6 +132,7 @@ pub fn account_modal(props: &AccountModalProps) -> Html {
                         target_amount,
                         color: color.clone(),
                         is_liquid: Some(is_liquid),
+                        balance_interpolation: Some(balance_interpolation),
                     };
 
                     wasm_bindgen_futures::spawn_local(async move {
@@ -166,6 +173,7 @@ pub fn account_modal(props: &AccountModalProps) -> Html {
     let default_ledger = props.account.as_ref().and_then(|a| a.ledger_name.clone()).unwrap_or_default();
     let default_include_stats = props.account.as_ref().map(|a| a.include_in_statistics).unwrap_or(true);
     let default_target_amount = props.account.as_ref().and_then(|a| a.target_amount.clone()).unwrap_or_default();
+    let default_interpolation = props.account.as_ref().map(|a| a.balance_interpolation).unwrap_or_default();
     let default_color = props.account.as_ref()
         .and_then(|a| a.color.clone())
         .unwrap_or_else(|| colors::ACCOUNT_COLORS[0].to_string());
@@ -352,6 +360,21 @@ pub fn account_modal(props: &AccountModalProps) -> Html {
                         </div>
                     </div>
 
+                    <div class="form-control">
+                        <label class="label"><span class="label-text">{"Between Manual Balances"}</span></label>
+                        <select
+                            name="balance_interpolation"
+                            class="select select-bordered w-full"
+                            disabled={*is_submitting}
+                        >
+                            <option value="Step" selected={default_interpolation == BalanceInterpolation::Step}>{"Hold until the next balance"}</option>
+                            <option value="Linear" selected={default_interpolation == BalanceInterpolation::Linear}>{"Change linearly"}</option>
+                        </select>
+                        <label class="label">
+                            <span class="label-text-alt">{"Linear suits accounts tracked only by periodic balances, such as investments or property"}</span>
+                        </label>
+                    </div>
+
                     <div class="modal-action">
                         <button
                             type="button"
@@ -0,0 +1,78 @@
+use crate::api_client::timeseries::get_account_sparkline;
+use crate::common::fetch_hook::use_fetch_with_refetch;
+use crate::formatting::fmt_amount;
+use crate::hooks::FetchState;
+use yew::prelude::*;
+
+const WIDTH: f64 = 120.0;
+const HEIGHT: f64 = 32.0;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub account_id: i32,
+    #[prop_or(90)]
+    pub days: u32,
+}
+
+/// Maps balances onto an SVG polyline spanning the sparkline box.
+fn polyline_points(balances: &[f64]) -> String {
+    let min = balances.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = balances.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let range = if max > min { max - min } else { 1.0 };
+    let step = if balances.len() > 1 { WIDTH / (balances.len() - 1) as f64 } else { 0.0 };
+
+    balances
+        .iter()
+        .enumerate()
+        .map(|(i, balance)| {
+            let x = i as f64 * step;
+            let y = HEIGHT - (balance - min) / range * HEIGHT;
+            format!("{:.1},{:.1}", x, y)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Small inline balance chart for the last `days` days, colored by the direction of change.
+#[function_component(AccountSparkline)]
+pub fn account_sparkline(props: &Props) -> Html {
+    let account_id = props.account_id;
+    let days = props.days;
+    let (sparkline_state, _) = use_fetch_with_refetch(move || get_account_sparkline(account_id, days));
+
+    match &*sparkline_state {
+        FetchState::Success(sparkline) if sparkline.points.len() > 1 => {
+            let balances: Vec<f64> = sparkline
+                .points
+                .iter()
+                .map(|p| p.balance.to_string().parse::<f64>().unwrap_or(0.0))
+                .collect();
+            let rising = sparkline.change.map(|c| !c.is_sign_negative()).unwrap_or(true);
+            let stroke = if rising { "stroke-success" } else { "stroke-error" };
+            let title = match sparkline.change {
+                Some(change) => format!("{} days: {}{}", days, if rising { "+" } else { "" }, fmt_amount(change)),
+                None => format!("{} days", days),
+            };
+
+            html! {
+                <svg
+                    class="inline-block"
+                    width={WIDTH.to_string()}
+                    height={HEIGHT.to_string()}
+                    viewBox={format!("0 0 {} {}", WIDTH, HEIGHT)}
+                    preserveAspectRatio="none"
+                >
+                    <title>{title}</title>
+                    <polyline
+                        class={classes!(stroke)}
+                        fill="none"
+                        stroke-width="1.5"
+                        points={polyline_points(&balances)}
+                    />
+                </svg>
+            }
+        }
+        FetchState::Loading => html! { <span class="loading loading-dots loading-xs"></span> },
+        _ => html! {},
+    }
+}
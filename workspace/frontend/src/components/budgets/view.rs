@@ -3,6 +3,8 @@ use crate::formatting::{fmt_amount_f64, use_currency};
 use crate::mock_data::{get_mock_budgets, get_mock_categories, get_mock_transactions};
 use std::collections::HashMap;
 use chrono::{Datelike, Local};
+use yew_router::prelude::*;
+use crate::router::Route;
 
 #[function_component(Budgets)]
 pub fn budgets() -> Html {
@@ -15,7 +17,8 @@ pub fn budgets() -> Html {
         .map(|c| (c.id.clone(), (c.name.clone(), c.color.clone())))
         .collect();
 
-    let current_month = Local::now().month();
+    let today = Local::now().date_naive();
+    let current_month = today.month();
 
     // Calculate actual spending per category for current month
     let mut spending: HashMap<String, f64> = HashMap::new();
@@ -33,7 +36,15 @@ pub fn budgets() -> Html {
         <>
             <div class="flex justify-between items-center mb-4">
                 <h2 class="text-2xl font-bold">{"Monthly Budget"}</h2>
-                <button class="btn btn-primary btn-sm"><i class="fas fa-plus"></i> {" Edit Allocations"}</button>
+                <div class="flex gap-2">
+                    <Link<Route>
+                        to={Route::PrintBudgetStatus { year: today.year(), month: current_month }}
+                        classes="btn btn-sm btn-outline"
+                    >
+                        <i class="fas fa-print"></i> {" Print"}
+                    </Link<Route>>
+                    <button class="btn btn-primary btn-sm"><i class="fas fa-plus"></i> {" Edit Allocations"}</button>
+                </div>
             </div>
             <div class="grid grid-cols-1 md:grid-cols-2 gap-6">
                 { for budgets.iter().map(|b| {
@@ -0,0 +1,206 @@
+use crate::api_client::timeseries::{get_account_timeseries, preview_account_balance};
+use crate::formatting::fmt_amount;
+use chrono::{Duration, Local, NaiveDate};
+use common::{BalancePreviewRequest, BalancePreviewResponse, PreviewTransaction};
+use compute::preview::{preview_balance, PlannedTransaction};
+use compute::recurrence::Recurrence;
+use gloo_timers::callback::Timeout;
+use rust_decimal::Decimal;
+use yew::prelude::*;
+
+/// Days after today covered by the preview
+const PREVIEW_DAYS: i64 = 90;
+/// Pause in typing before the server is asked for the authoritative numbers
+const SERVER_DEBOUNCE_MS: u32 = 600;
+
+/// Form contents to preview against the balances of one account
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreviewDraft {
+    pub account_id: i32,
+    /// The transaction as currently entered, its amount as it affects `account_id`
+    pub transaction: PreviewTransaction,
+    /// Saved version of the edited transaction, as it affects `account_id`
+    pub original: Option<PreviewTransaction>,
+    pub transaction_id: Option<i32>,
+    pub recurring_transaction_id: Option<i32>,
+}
+
+/// Effect of a transaction on `account_id`: its amount on the target account, the
+/// negated amount on the source account, nothing on any other account.
+pub fn effect_on(account_id: i32, amount: Decimal, target_account_id: i32, source_account_id: Option<i32>) -> Option<Decimal> {
+    if target_account_id == account_id {
+        Some(amount)
+    } else if source_account_id == Some(account_id) {
+        Some(-amount)
+    } else {
+        None
+    }
+}
+
+fn planned(transaction: &PreviewTransaction) -> Option<PlannedTransaction> {
+    match transaction.period.as_deref().filter(|period| !period.is_empty()) {
+        None => Some(PlannedTransaction::one_off(transaction.amount, transaction.date)),
+        Some(period) => {
+            let recurrence = period.parse::<Recurrence>().ok()?;
+            Some(PlannedTransaction::recurring(transaction.amount, transaction.date, recurrence, transaction.end_date))
+        }
+    }
+}
+
+/// Numbers shown by the panel, from either the local or the server preview
+struct Summary {
+    end_before: Decimal,
+    end_after: Decimal,
+    lowest: Option<(NaiveDate, Decimal)>,
+}
+
+impl From<&BalancePreviewResponse> for Summary {
+    fn from(response: &BalancePreviewResponse) -> Self {
+        Self {
+            end_before: response.end_before,
+            end_after: response.end_after,
+            lowest: response.lowest_date.zip(response.lowest_balance),
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct BalancePreviewProps {
+    /// `None` while the form is incomplete
+    pub draft: Option<PreviewDraft>,
+}
+
+/// Shows how the transaction being entered moves the account balance.
+///
+/// The preview is computed in the browser on every change from balances loaded once per
+/// account, using the same code as the server. Once typing pauses the server computes it
+/// from current data and its numbers replace the local estimate.
+#[function_component(BalancePreview)]
+pub fn balance_preview(props: &BalancePreviewProps) -> Html {
+    let today = Local::now().date_naive();
+    let end_date = today + Duration::days(PREVIEW_DAYS);
+    let balances = use_state(|| None::<(i32, Vec<(NaiveDate, Decimal)>)>);
+    let server_preview = use_state(|| None::<(PreviewDraft, BalancePreviewResponse)>);
+    let account_id = props.draft.as_ref().map(|draft| draft.account_id);
+
+    {
+        let balances = balances.clone();
+        use_effect_with(account_id, move |account_id| {
+            if let Some(account_id) = *account_id {
+                wasm_bindgen_futures::spawn_local(async move {
+                    match get_account_timeseries(account_id, today, end_date).await {
+                        Ok(timeseries) => {
+                            let mut points: Vec<(NaiveDate, Decimal)> = timeseries
+                                .data_points
+                                .into_iter()
+                                .filter(|point| point.account_id == account_id)
+                                .map(|point| (point.date, point.balance))
+                                .collect();
+                            points.sort_by_key(|(date, _)| *date);
+                            balances.set(Some((account_id, points)));
+                        }
+                        Err(e) => log::warn!("Balance preview has no balances for account {}: {}", account_id, e),
+                    }
+                });
+            }
+            || ()
+        });
+    }
+
+    {
+        let server_preview = server_preview.clone();
+        use_effect_with(props.draft.clone(), move |draft| {
+            let timeout = draft.clone().map(|draft| {
+                Timeout::new(SERVER_DEBOUNCE_MS, move || {
+                    wasm_bindgen_futures::spawn_local(async move {
+                        let request = BalancePreviewRequest {
+                            start_date: today,
+                            end_date,
+                            transaction: Some(draft.transaction.clone()),
+                            transaction_id: draft.transaction_id,
+                            recurring_transaction_id: draft.recurring_transaction_id,
+                        };
+                        match preview_account_balance(draft.account_id, &request).await {
+                            Ok(response) => server_preview.set(Some((draft, response))),
+                            Err(e) => log::warn!("Server balance preview failed: {}", e),
+                        }
+                    });
+                })
+            });
+            // Dropping the timeout cancels the request of a draft that was typed over
+            move || drop(timeout)
+        });
+    }
+
+    let Some(draft) = props.draft.as_ref() else {
+        return html! {};
+    };
+
+    let confirmed = server_preview
+        .as_ref()
+        .filter(|(previewed, _)| previewed == draft)
+        .map(|(_, response)| Summary::from(response));
+    let is_confirmed = confirmed.is_some();
+    let summary = confirmed.or_else(|| {
+        let (balances_account_id, points) = balances.as_ref()?;
+        if *balances_account_id != draft.account_id {
+            return None;
+        }
+        let original = draft.original.as_ref().and_then(planned);
+        let edited = planned(&draft.transaction)?;
+        let preview = preview_balance(points, original.as_ref(), Some(&edited));
+        preview.points.last()?;
+        Some(Summary {
+            end_before: preview.end_before,
+            end_after: preview.end_after,
+            lowest: preview.lowest_after,
+        })
+    });
+
+    let Some(summary) = summary else {
+        return html! {
+            <div class="text-sm text-gray-500 flex items-center gap-2">
+                <span class="loading loading-spinner loading-xs"></span>
+                {"Calculating balance impact..."}
+            </div>
+        };
+    };
+
+    let difference = summary.end_after - summary.end_before;
+    let goes_negative = summary.lowest.is_some_and(|(_, balance)| balance < Decimal::ZERO);
+
+    html! {
+        <div class={classes!("alert", if goes_negative { "alert-warning" } else { "alert-info" })}>
+            <div class="w-full text-sm space-y-1">
+                <div class="flex justify-between items-center">
+                    <span class="font-semibold">{format!("Balance on {}", end_date.format("%Y-%m-%d"))}</span>
+                    {if is_confirmed {
+                        html! { <span class="badge badge-success badge-sm">{"Confirmed"}</span> }
+                    } else {
+                        html! {
+                            <span class="badge badge-ghost badge-sm gap-1">
+                                <span class="loading loading-spinner loading-xs"></span>
+                                {"Estimate"}
+                            </span>
+                        }
+                    }}
+                </div>
+                <div>
+                    {format!("{} → {} ", fmt_amount(summary.end_before), fmt_amount(summary.end_after))}
+                    <span class={if difference < Decimal::ZERO { "text-error" } else { "text-success" }}>
+                        {format!("({}{})", if difference > Decimal::ZERO { "+" } else { "" }, fmt_amount(difference))}
+                    </span>
+                </div>
+                {if let Some((date, balance)) = summary.lowest {
+                    html! {
+                        <div class={classes!(goes_negative.then_some("font-semibold"))}>
+                            {format!("Lowest balance {} on {}", fmt_amount(balance), date.format("%Y-%m-%d"))}
+                        </div>
+                    }
+                } else {
+                    html! {}
+                }}
+            </div>
+        </div>
+    }
+}
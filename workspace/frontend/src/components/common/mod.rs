@@ -1 +1,2 @@
+pub mod balance_preview;
 pub mod pagination;
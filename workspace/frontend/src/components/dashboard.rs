@@ -4,5 +4,6 @@ mod chart;
 mod activity;
 mod category_stats;
 mod metrics;
+mod tag_limits;
 mod view;
 pub use view::Dashboard;
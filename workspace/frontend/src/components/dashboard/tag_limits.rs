@@ -0,0 +1,71 @@
+use yew::prelude::*;
+use crate::api_client::tag::{get_tag_limits, TagLimit};
+use crate::common::fetch_hook::use_fetch_with_refetch;
+use crate::formatting::{fmt_amount_f64, use_currency};
+use crate::hooks::FetchState;
+
+fn limit_row(limit: &TagLimit, currency: &str) -> Html {
+    let spent = limit.spent.parse::<f64>().unwrap_or(0.0);
+    let cap = limit.limit.parse::<f64>().unwrap_or(0.0);
+    let percentage = if cap > 0.0 { (spent / cap * 100.0).min(100.0) } else { 0.0 };
+    let (progress_class, badge) = match limit.level.as_str() {
+        "Exceeded" => ("progress-error", html! { <span class="badge badge-error badge-sm">{"over limit"}</span> }),
+        "Warning" => ("progress-warning", html! { <span class="badge badge-warning badge-sm">{"almost reached"}</span> }),
+        _ => ("progress-primary", html! {}),
+    };
+
+    html! {
+        <div key={limit.tag_id} class="mb-4">
+            <div class="flex justify-between items-center mb-1 gap-2">
+                <span class="text-sm font-medium flex items-center gap-2">
+                    <i class="fas fa-hashtag text-gray-400"></i>
+                    {&limit.tag_name}
+                    {badge}
+                </span>
+                <span class="text-sm text-gray-500">
+                    {format!("{} / {} {}", fmt_amount_f64(spent), fmt_amount_f64(cap), currency)}
+                </span>
+            </div>
+            <progress class={classes!("progress", "w-full", progress_class)} value={percentage.to_string()} max="100"></progress>
+        </div>
+    }
+}
+
+/// Dashboard card with the current month's spending of tags that have a monthly limit.
+///
+/// Hidden while no tag has a limit.
+#[function_component(TagLimits)]
+pub fn tag_limits() -> Html {
+    let currency = use_currency();
+    let (fetch_state, _refetch) = use_fetch_with_refetch(get_tag_limits);
+
+    let FetchState::Success(report) = &*fetch_state else {
+        if let FetchState::Error(error) = &*fetch_state {
+            log::warn!("Tag limits unavailable on the dashboard: {}", error);
+        }
+        return html! {};
+    };
+    if report.limits.is_empty() {
+        return html! {};
+    }
+
+    let over_limit = report.limits.iter().filter(|limit| limit.level != "WithinLimit").count();
+
+    html! {
+        <div class="card bg-base-100 shadow mt-6">
+            <div class="card-body">
+                <h2 class="card-title">
+                    {"Tag Spending Limits "}
+                    <span class="text-sm font-normal text-gray-500">{format!("({}-{:02})", report.year, report.month)}</span>
+                </h2>
+                if over_limit > 0 {
+                    <div class="alert alert-warning mb-2">
+                        <i class="fas fa-exclamation-triangle"></i>
+                        <span>{format!("{} tag limit(s) reached or close to the limit this month", over_limit)}</span>
+                    </div>
+                }
+                { for report.limits.iter().map(|limit| limit_row(limit, &currency)) }
+            </div>
+        </div>
+    }
+}
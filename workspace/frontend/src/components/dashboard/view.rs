@@ -4,6 +4,7 @@ use super::category_stats::CategoryStats;
 use super::chart::{BalanceBreakdownChart, LiquidBreakdownChart, NonLiquidBreakdownChart, AllAccountsBreakdownChart, DebtBreakdownChart, InvestmentEquityBreakdownChart, NetWorthChart};
 use super::metrics::DashboardMetrics;
 use super::stats::Stats;
+use super::tag_limits::TagLimits;
 use yew::prelude::*;
 
 #[function_component(Dashboard)]
@@ -14,6 +15,7 @@ pub fn dashboard() -> Html {
             <div class="mt-6">
                 <DashboardMetrics />
             </div>
+            <TagLimits />
             <div class="card bg-base-100 shadow mt-6">
                 <div class="card-body">
                     <h2 class="card-title">{"Account Bubbles"}</h2>
@@ -0,0 +1,118 @@
+use std::panic;
+use wasm_bindgen::prelude::*;
+use yew::prelude::*;
+use crate::api_client::call_log::recent_calls;
+use crate::api_client::diagnostics::{diagnostics_url, DiagnosticReport};
+
+// A panic aborts the wasm instance, so the recovery screen and the report
+// submission have to live entirely on the JavaScript side.
+#[wasm_bindgen(inline_js = "
+export function show_crash_screen(message, report, diagnostics_url) {
+    if (document.getElementById('crash-screen')) {
+        return;
+    }
+    report = report || { message: message, api_calls: [] };
+    report.route = window.location.pathname;
+    report.user_agent = navigator.userAgent;
+
+    const el = (tag, className, text) => {
+        const node = document.createElement(tag);
+        node.className = className;
+        if (text) {
+            node.textContent = text;
+        }
+        return node;
+    };
+
+    const body = el('div', 'card-body');
+    body.appendChild(el('h2', 'card-title text-error', 'Something went wrong'));
+    body.appendChild(el('p', '', 'FinRust hit an unexpected error and had to stop. Reloading usually helps; sending a report lets us find the cause.'));
+
+    const details = el('details', 'bg-base-200 rounded-box p-3');
+    details.appendChild(el('summary', 'cursor-pointer text-sm font-medium', 'Error details'));
+    details.appendChild(el('pre', 'mt-2 text-xs whitespace-pre-wrap break-all', message));
+    body.appendChild(details);
+
+    const status = el('p', 'text-sm opacity-70');
+    const send = el('button', 'btn btn-outline', 'Send report');
+    send.onclick = async () => {
+        send.disabled = true;
+        status.textContent = 'Sending report...';
+        try {
+            const response = await fetch(diagnostics_url, {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify(report),
+            });
+            if (!response.ok) {
+                throw new Error('HTTP error: ' + response.status);
+            }
+            const receipt = await response.json();
+            status.textContent = 'Report #' + receipt.data.id + ' sent, thank you.';
+        } catch (error) {
+            status.textContent = 'Could not send the report: ' + error.message;
+            send.disabled = false;
+        }
+    };
+    const home = el('button', 'btn btn-ghost', 'Go to dashboard');
+    home.onclick = () => window.location.assign('/');
+    const reload = el('button', 'btn btn-primary', 'Reload');
+    reload.onclick = () => window.location.reload();
+
+    const actions = el('div', 'card-actions justify-end');
+    actions.append(send, home, reload);
+    body.append(status, actions);
+
+    const card = el('div', 'card bg-base-100 shadow-xl w-full max-w-2xl');
+    card.appendChild(body);
+    const screen = el('div', 'min-h-screen flex items-center justify-center bg-base-200 p-4');
+    screen.id = 'crash-screen';
+    screen.appendChild(card);
+    document.body.replaceChildren(screen);
+}
+")]
+extern "C" {
+    fn show_crash_screen(message: &str, report: JsValue, diagnostics_url: &str);
+}
+
+fn install_panic_hook() {
+    log::debug!("Installing panic hook");
+    // Resolved now: settings may not be readable from inside the hook
+    let diagnostics_url = diagnostics_url();
+
+    panic::set_hook(Box::new(move |info| {
+        let message = info.to_string();
+        log::error!("Application panicked: {}", message);
+
+        let report = DiagnosticReport {
+            message: message.clone(),
+            route: None,
+            app_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            user_agent: None,
+            api_calls: recent_calls(),
+        };
+        let report = serde_wasm_bindgen::to_value(&report).unwrap_or(JsValue::NULL);
+        show_crash_screen(&message, report, &diagnostics_url);
+    }));
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ErrorBoundaryProps {
+    pub children: Children,
+}
+
+/// Top-level error boundary.
+///
+/// Any panic while rendering or handling events replaces the app with a recovery
+/// screen offering to reload, go back to the dashboard, or send a diagnostic report
+/// (error message, route, version and the recent API call log) to the server.
+#[function_component(ErrorBoundary)]
+pub fn error_boundary(props: &ErrorBoundaryProps) -> Html {
+    // Installed during the first render rather than in an effect, so panics in the
+    // children's first render are caught as well
+    use_memo((), |_| install_panic_hook());
+
+    html! {
+        <>{ for props.children.iter() }</>
+    }
+}
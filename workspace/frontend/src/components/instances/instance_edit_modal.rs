@@ -40,6 +40,8 @@ pub fn instance_edit_modal(props: &InstanceEditModalProps) -> Html {
                 let expected_amount = form_data.get("expected_amount").as_string();
                 let paid_date = form_data.get("paid_date").as_string();
                 let paid_amount = form_data.get("paid_amount").as_string();
+                let notes = form_data.get("notes").as_string();
+                let disputed = form_data.get("disputed").as_string().map(|v| v == "on").unwrap_or(false);
 
                 let is_submitting = is_submitting.clone();
                 let error_message = error_message.clone();
@@ -55,6 +57,8 @@ pub fn instance_edit_modal(props: &InstanceEditModalProps) -> Html {
                     expected_amount,
                     paid_date: if paid_date.as_ref().map(|d| d.is_empty()).unwrap_or(true) { None } else { paid_date },
                     paid_amount: if paid_amount.as_ref().map(|a| a.is_empty()).unwrap_or(true) { None } else { paid_amount },
+                    notes,
+                    disputed: Some(disputed),
                 };
 
                 wasm_bindgen_futures::spawn_local(async move {
@@ -158,6 +162,29 @@ pub fn instance_edit_modal(props: &InstanceEditModalProps) -> Html {
                         </div>
                     </div>
 
+                    <div class="form-control">
+                        <label class="label cursor-pointer justify-start gap-3">
+                            <input
+                                type="checkbox"
+                                name="disputed"
+                                class="checkbox checkbox-error"
+                                checked={props.instance.disputed}
+                                disabled={*is_submitting}
+                            />
+                            <span class="label-text">{"Disputed (left out of paid totals until settled)"}</span>
+                        </label>
+                    </div>
+
+                    <div class="form-control">
+                        <label class="label"><span class="label-text">{"Notes"}</span></label>
+                        <textarea
+                            name="notes"
+                            class="textarea textarea-bordered w-full"
+                            value={props.instance.notes.clone().unwrap_or_default()}
+                            disabled={*is_submitting}
+                        />
+                    </div>
+
                     <div class="modal-action">
                         <button
                             type="button"
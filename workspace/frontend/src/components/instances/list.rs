@@ -1,13 +1,14 @@
 use yew::prelude::*;
 use yew_router::prelude::*;
 use std::collections::HashMap;
-use crate::api_client::recurring_transaction::{RecurringInstanceResponse, get_recurring_instances, delete_recurring_instance, update_recurring_instance, UpdateRecurringInstanceRequest};
+use crate::api_client::recurring_transaction::{RecurringInstanceResponse, get_recurring_instances, delete_recurring_instance, update_recurring_instance};
 use crate::api_client::account::get_accounts;
 use crate::common::fetch_hook::use_fetch_with_refetch;
 use crate::common::toast::ToastContext;
 use crate::formatting::{fmt_amount_str, use_currency};
-use crate::hooks::FetchState;
+use crate::hooks::{FetchState, OptimisticUpdate, use_optimistic_update};
 use crate::router::Route;
+use super::status::{status_change, INSTANCE_STATUSES};
 
 #[derive(Clone, Copy, PartialEq)]
 enum SortColumn {
@@ -37,6 +38,11 @@ pub fn instances_list(props: &InstancesListProps) -> Html {
     let (fetch_state, refetch) = use_fetch_with_refetch(move || {
         get_recurring_instances(None, None, recurring_id, None)
     });
+    let update_status = use_optimistic_update(
+        fetch_state.clone(),
+        |instance: &RecurringInstanceResponse| instance.id,
+        update_recurring_instance,
+    );
     let (accounts_state, _) = use_fetch_with_refetch(get_accounts);
     let toast_ctx = use_context::<ToastContext>().expect("ToastContext not found");
     let currency = use_currency();
@@ -118,11 +124,11 @@ pub fn instances_list(props: &InstancesListProps) -> Html {
                                 return false;
                             }
                         }
-                        // Filter by status
-                        if let Some(status) = &*selected_status {
-                            if &i.status != status {
-                                return false;
-                            }
+                        // Filter by status; disputes are tracked apart from the status
+                        match selected_status.as_deref() {
+                            Some("Disputed") if !i.disputed => return false,
+                            Some(status) if status != "Disputed" && i.status != status => return false,
+                            _ => {}
                         }
                         true
                     })
@@ -232,13 +238,8 @@ pub fn instances_list(props: &InstancesListProps) -> Html {
                                         })
                                     };
 
-                                    let instance_id = instance.id;
                                     let current_status = instance.status.clone();
-                                    let status_refetch = refetch.clone();
-                                    let status_toast_ctx = toast_ctx.clone();
-                                    let instance_paid_date = instance.paid_date.clone();
-                                    let instance_paid_amount = instance.paid_amount.clone();
-                                    let instance_expected_amount = instance.expected_amount.clone();
+                                    let status_instance = instance.clone();
 
                                     html! {
                                         <tr>
@@ -257,6 +258,22 @@ pub fn instances_list(props: &InstancesListProps) -> Html {
                                                 <span class={classes!("badge", "badge-sm", status_badge)}>
                                                     {&instance.status}
                                                 </span>
+                                                {if instance.disputed {
+                                                    html! {
+                                                        <span class="badge badge-sm badge-error ml-1" title={instance.notes.clone().unwrap_or_default()}>
+                                                            {"Disputed"}
+                                                        </span>
+                                                    }
+                                                } else {
+                                                    html! {}
+                                                }}
+                                                {if instance.overdue {
+                                                    html! {
+                                                        <span class="badge badge-sm badge-warning ml-1">{"Overdue"}</span>
+                                                    }
+                                                } else {
+                                                    html! {}
+                                                }}
                                             </td>
                                             <td>{&instance.due_date}</td>
                                             <td class={classes!("font-mono", amount_class)}>
@@ -333,59 +350,17 @@ pub fn instances_list(props: &InstancesListProps) -> Html {
                                                         </button>
                                                         <ul tabindex="0" class="dropdown-content menu p-2 shadow bg-base-100 rounded-box w-40 z-50">
                                                             {
-                                                                vec!["Pending", "Paid", "Skipped"].into_iter().map(|status| {
-                                                                    let id = instance_id;
+                                                                INSTANCE_STATUSES.into_iter().map(|status| {
                                                                     let is_current = status == current_status.as_str();
                                                                     let on_status_click = {
-                                                                        let status = status.to_string();
-                                                                        let refetch = status_refetch.clone();
-                                                                        let toast_ctx = status_toast_ctx.clone();
-                                                                        let paid_date = instance_paid_date.clone();
-                                                                        let paid_amount = instance_paid_amount.clone();
-                                                                        let expected_amount = instance_expected_amount.clone();
+                                                                        let instance = status_instance.clone();
+                                                                        let update_status = update_status.clone();
                                                                         Callback::from(move |_: MouseEvent| {
-                                                                            let id = id;
-                                                                            let status = status.clone();
-                                                                            let refetch = refetch.clone();
-                                                                            let toast_ctx = toast_ctx.clone();
-                                                                            let paid_date = paid_date.clone();
-                                                                            let paid_amount = paid_amount.clone();
-                                                                            let expected_amount = expected_amount.clone();
-
-                                                                            wasm_bindgen_futures::spawn_local(async move {
-                                                                                // If changing to Paid status, ensure paid_date and paid_amount are set
-                                                                                let (final_paid_date, final_paid_amount) = if status == "Paid" {
-                                                                                    let pd = if paid_date.is_none() {
-                                                                                        Some(chrono::Local::now().format("%Y-%m-%d").to_string())
-                                                                                    } else {
-                                                                                        paid_date
-                                                                                    };
-                                                                                    let pa = if paid_amount.is_none() {
-                                                                                        Some(expected_amount)
-                                                                                    } else {
-                                                                                        paid_amount
-                                                                                    };
-                                                                                    (pd, pa)
-                                                                                } else {
-                                                                                    (None, None)
-                                                                                };
-
-                                                                                let request = UpdateRecurringInstanceRequest {
-                                                                                    status: Some(status.clone()),
-                                                                                    due_date: None,
-                                                                                    expected_amount: None,
-                                                                                    paid_date: final_paid_date,
-                                                                                    paid_amount: final_paid_amount,
-                                                                                };
-                                                                                match update_recurring_instance(id, request).await {
-                                                                                    Ok(_) => {
-                                                                                        toast_ctx.show_success(format!("Status changed to {}", status));
-                                                                                        refetch.emit(());
-                                                                                    }
-                                                                                    Err(e) => {
-                                                                                        toast_ctx.show_error(format!("Failed to update status: {}", e));
-                                                                                    }
-                                                                                }
+                                                                            let (optimistic, request) = status_change(&instance, status);
+                                                                            update_status.emit(OptimisticUpdate {
+                                                                                optimistic,
+                                                                                request,
+                                                                                success_message: format!("Status changed to {}", status),
                                                                             });
                                                                         })
                                                                     };
@@ -470,6 +445,7 @@ pub fn instances_list(props: &InstancesListProps) -> Html {
                         <option value="Pending">{"Pending"}</option>
                         <option value="Paid">{"Paid"}</option>
                         <option value="Skipped">{"Skipped"}</option>
+                        <option value="Disputed">{"Disputed"}</option>
                     </select>
                 </div>
 
@@ -1,5 +1,6 @@
 mod list;
 mod view;
+pub mod status;
 pub mod instance_edit_modal;
 
 pub use list::InstancesList;
@@ -0,0 +1,45 @@
+use crate::api_client::recurring_transaction::{RecurringInstanceResponse, UpdateRecurringInstanceRequest};
+
+/// Statuses offered in the instance status menus.
+pub const INSTANCE_STATUSES: [&str; 3] = ["Pending", "Paid", "Skipped"];
+
+/// Builds the update request for moving `instance` to `status`, together with the
+/// instance as it will look afterwards.
+///
+/// Marking an instance as paid fills in today's date and the expected amount when they
+/// aren't set yet; any other status clears both.
+pub fn status_change(
+    instance: &RecurringInstanceResponse,
+    status: &str,
+) -> (RecurringInstanceResponse, UpdateRecurringInstanceRequest) {
+    let (paid_date, paid_amount) = if status == "Paid" {
+        (
+            Some(
+                instance
+                    .paid_date
+                    .clone()
+                    .unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d").to_string()),
+            ),
+            Some(instance.paid_amount.clone().unwrap_or_else(|| instance.expected_amount.clone())),
+        )
+    } else {
+        (None, None)
+    };
+
+    let updated = RecurringInstanceResponse {
+        status: status.to_string(),
+        paid_date: paid_date.clone(),
+        paid_amount: paid_amount.clone(),
+        ..instance.clone()
+    };
+    let request = UpdateRecurringInstanceRequest {
+        status: Some(status.to_string()),
+        due_date: None,
+        expected_amount: None,
+        paid_date,
+        paid_amount,
+        notes: None,
+        disputed: None,
+    };
+    (updated, request)
+}
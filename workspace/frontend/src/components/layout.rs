@@ -2,3 +2,4 @@ pub mod navbar;
 pub mod sidebar;
 pub mod layout;
 pub mod breadcrumb;
+pub mod safe_to_spend;
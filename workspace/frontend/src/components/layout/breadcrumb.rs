@@ -80,10 +80,24 @@ pub fn breadcrumb() -> Html {
                 BreadcrumbItem { label: "Home".to_string(), route: Route::Dashboard },
                 BreadcrumbItem { label: "Reports".to_string(), route: Route::Reports }
             ],
+            Route::PrintMonthlyReport { year, month } => vec![
+                BreadcrumbItem { label: "Home".to_string(), route: Route::Dashboard },
+                BreadcrumbItem { label: "Reports".to_string(), route: Route::Reports },
+                BreadcrumbItem { label: format!("Print {}-{:02}", year, month), route: Route::PrintMonthlyReport { year, month } }
+            ],
+            Route::PrintBudgetStatus { year, month } => vec![
+                BreadcrumbItem { label: "Home".to_string(), route: Route::Dashboard },
+                BreadcrumbItem { label: "Budgets".to_string(), route: Route::Budgets },
+                BreadcrumbItem { label: format!("Print {}-{:02}", year, month), route: Route::PrintBudgetStatus { year, month } }
+            ],
             Route::AiPrompt => vec![
                 BreadcrumbItem { label: "Home".to_string(), route: Route::Dashboard },
                 BreadcrumbItem { label: "AI Assessment".to_string(), route: Route::AiPrompt }
             ],
+            Route::SpendingMap => vec![
+                BreadcrumbItem { label: "Home".to_string(), route: Route::Dashboard },
+                BreadcrumbItem { label: "Spending Map".to_string(), route: Route::SpendingMap }
+            ],
             Route::Settings => vec![
                 BreadcrumbItem { label: "Home".to_string(), route: Route::Dashboard },
                 BreadcrumbItem { label: "Settings".to_string(), route: Route::Settings }
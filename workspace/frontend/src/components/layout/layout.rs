@@ -2,6 +2,8 @@ use yew::prelude::*;
 use super::navbar::Navbar;
 use super::sidebar::Sidebar;
 use super::breadcrumb::Breadcrumb;
+use crate::common::shortcuts::use_keyboard_shortcuts;
+use crate::components::modals::ShortcutsModal;
 
 #[derive(Properties, PartialEq)]
 pub struct Props {
@@ -13,6 +15,20 @@ pub struct Props {
 
 #[function_component(Layout)]
 pub fn layout(props: &Props) -> Html {
+    let show_shortcuts = use_state(|| false);
+
+    let on_toggle_shortcuts = {
+        let show_shortcuts = show_shortcuts.clone();
+        Callback::from(move |_| show_shortcuts.set(!*show_shortcuts))
+    };
+
+    let on_close_shortcuts = {
+        let show_shortcuts = show_shortcuts.clone();
+        Callback::from(move |_| show_shortcuts.set(false))
+    };
+
+    use_keyboard_shortcuts(vec![("?", on_toggle_shortcuts), ("Escape", on_close_shortcuts.clone())]);
+
     html! {
         <div class="drawer lg:drawer-open">
             <input id="my-drawer" type="checkbox" class="drawer-toggle" />
@@ -24,6 +40,7 @@ pub fn layout(props: &Props) -> Html {
                 </main>
             </div>
             <Sidebar />
+            <ShortcutsModal show={*show_shortcuts} on_close={on_close_shortcuts} />
         </div>
     }
 }
@@ -1,19 +1,6 @@
 use yew::prelude::*;
-use web_sys::window;
-use wasm_bindgen::JsCast;
-use wasm_bindgen::prelude::*;
-
-#[wasm_bindgen(inline_js = "
-export function get_system_theme() {
-    if (window.matchMedia && window.matchMedia('(prefers-color-scheme: dark)').matches) {
-        return 'dark';
-    }
-    return 'light';
-}
-")]
-extern "C" {
-    fn get_system_theme() -> String;
-}
+use super::safe_to_spend::SafeToSpendWidget;
+use crate::theme::{ThemeContext, ThemePreference};
 
 #[derive(Properties, PartialEq)]
 pub struct Props {
@@ -24,56 +11,7 @@ pub struct Props {
 
 #[function_component(Navbar)]
 pub fn navbar(props: &Props) -> Html {
-    let theme = use_state(|| {
-        // Check localStorage for saved theme, otherwise check system preference
-        if let Some(window) = window() {
-            if let Ok(Some(storage)) = window.local_storage() {
-                if let Ok(Some(saved_theme)) = storage.get_item("theme") {
-                    return saved_theme;
-                }
-            }
-        }
-        // Use system preference as default
-        get_system_theme()
-    });
-
-    // Set initial theme on mount
-    use_effect_with((), {
-        let theme = theme.clone();
-        move |_| {
-            if let Some(window) = window() {
-                if let Some(document) = window.document() {
-                    if let Some(html) = document.document_element() {
-                        let _ = html.set_attribute("data-theme", &*theme);
-                    }
-                }
-            }
-            || ()
-        }
-    });
-
-    let on_theme_toggle = {
-        let theme = theme.clone();
-        Callback::from(move |e: Event| {
-            if let Some(input) = e.target().and_then(|t| t.dyn_into::<web_sys::HtmlInputElement>().ok()) {
-                let new_theme = if input.checked() { "dark" } else { "light" };
-                theme.set(new_theme.to_string());
-
-                // Update HTML attribute
-                if let Some(window) = window() {
-                    if let Some(document) = window.document() {
-                        if let Some(html) = document.document_element() {
-                            let _ = html.set_attribute("data-theme", new_theme);
-                        }
-                    }
-                    // Save to localStorage
-                    if let Ok(Some(storage)) = window.local_storage() {
-                        let _ = storage.set_item("theme", new_theme);
-                    }
-                }
-            }
-        })
-    };
+    let theme_ctx = use_context::<ThemeContext>().expect("ThemeContext not found");
 
     let on_refresh_click = {
         let on_refresh = props.on_refresh.clone();
@@ -84,7 +22,6 @@ pub fn navbar(props: &Props) -> Html {
         })
     };
 
-    let is_dark = *theme == "dark";
     let has_refresh = props.on_refresh.is_some();
 
     html! {
@@ -98,6 +35,7 @@ pub fn navbar(props: &Props) -> Html {
                 <h1 class="text-xl font-bold" id="page-title">{ &props.title }</h1>
             </div>
             <div class="flex-none gap-2">
+                <SafeToSpendWidget />
                 if has_refresh {
                     <button
                         class="btn btn-ghost btn-circle"
@@ -107,16 +45,32 @@ pub fn navbar(props: &Props) -> Html {
                         <i class="fas fa-sync-alt text-xl"></i>
                     </button>
                 }
-                <label class="swap swap-rotate btn btn-ghost btn-circle">
-                    <input
+                <div class="dropdown dropdown-end">
+                    <button
                         id="theme-toggle"
-                        type="checkbox"
-                        checked={is_dark}
-                        onchange={on_theme_toggle}
-                    />
-                    <i class="swap-on fill-current fas fa-sun text-xl"></i>
-                    <i class="swap-off fill-current fas fa-moon text-xl"></i>
-                </label>
+                        tabindex="0"
+                        class="btn btn-ghost btn-circle"
+                        title={format!("Theme: {}", theme_ctx.preference.label())}
+                    >
+                        <i class={classes!(theme_ctx.preference.icon(), "text-xl")}></i>
+                    </button>
+                    <ul tabindex="0" class="dropdown-content menu p-2 shadow bg-base-100 rounded-box w-36 z-50">
+                        { for ThemePreference::ALL.into_iter().map(|option| {
+                            let on_select = {
+                                let set_preference = theme_ctx.set_preference.clone();
+                                Callback::from(move |_: MouseEvent| set_preference.emit(option))
+                            };
+                            html! {
+                                <li>
+                                    <a class={classes!((option == theme_ctx.preference).then_some("active"))} onclick={on_select}>
+                                        <i class={option.icon()}></i>
+                                        { option.label() }
+                                    </a>
+                                </li>
+                            }
+                        })}
+                    </ul>
+                </div>
             </div>
         </div>
     }
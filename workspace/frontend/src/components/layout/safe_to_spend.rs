@@ -0,0 +1,94 @@
+use crate::api_client::statistics::{get_safe_to_spend, SafeToSpendResponse};
+use crate::formatting::{fmt_amount, use_currency};
+use gloo_timers::callback::Interval;
+use rust_decimal::Decimal;
+use yew::prelude::*;
+
+/// How often the figure is refetched while the page is open
+const REFRESH_INTERVAL_MS: u32 = 60_000;
+
+/// Header figure of how much can be spent today, with a drill-down of the committed
+/// outflows subtracted from the balance.
+#[function_component(SafeToSpendWidget)]
+pub fn safe_to_spend_widget() -> Html {
+    let currency = use_currency();
+    // The last figure stays visible while refreshing or after a failed refresh
+    let data = use_state(|| None::<SafeToSpendResponse>);
+
+    let refresh = {
+        let data = data.clone();
+        use_callback((), move |_: (), _| {
+            let data = data.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match get_safe_to_spend().await {
+                    Ok(response) => data.set(Some(response)),
+                    Err(err) => log::warn!("Keeping previous safe-to-spend figure: {}", err),
+                }
+            });
+        })
+    };
+
+    {
+        let refresh = refresh.clone();
+        use_effect_with((), move |_| {
+            refresh.emit(());
+            let interval = Interval::new(REFRESH_INTERVAL_MS, move || refresh.emit(()));
+            move || drop(interval)
+        });
+    }
+
+    let Some(response) = (*data).clone() else {
+        return html! {
+            <div class="flex items-center px-2" title="Safe to spend">
+                <span class="loading loading-dots loading-sm"></span>
+            </div>
+        };
+    };
+
+    let figure_class = if response.safe_to_spend < Decimal::ZERO { "text-error" } else { "text-success" };
+    let horizon = match response.next_income_date {
+        Some(date) => format!("until next income on {}", date.format("%d.%m.")),
+        None => format!("until {}", response.until.format("%d.%m.")),
+    };
+
+    html! {
+        <div class="dropdown dropdown-end">
+            <button tabindex="0" class="btn btn-ghost flex-col gap-0 h-auto py-1" title="Safe to spend today">
+                <span class="text-xs opacity-60">{ "Safe to spend" }</span>
+                <span class={classes!("font-bold", figure_class)}>
+                    { format!("{} {}", fmt_amount(response.safe_to_spend), currency) }
+                </span>
+            </button>
+            <div tabindex="0" class="dropdown-content card card-compact bg-base-100 shadow-lg w-80 z-50">
+                <div class="card-body">
+                    <h3 class="card-title text-base">{ "Safe to spend today" }</h3>
+                    <p class="text-xs opacity-60">{ horizon }</p>
+                    <div class="flex justify-between text-sm">
+                        <span>{ "Balance today" }</span>
+                        <span>{ fmt_amount(response.balance) }</span>
+                    </div>
+                    if response.commitments.is_empty() {
+                        <p class="text-sm opacity-60">{ "Nothing committed yet." }</p>
+                    } else {
+                        <ul class="text-sm max-h-64 overflow-y-auto">
+                            { for response.commitments.iter().map(|commitment| html! {
+                                <li class="flex justify-between gap-2 py-1 border-b border-base-200">
+                                    <span class="truncate">
+                                        <span class="badge badge-ghost badge-sm mr-1">{ commitment.kind.label() }</span>
+                                        { &commitment.name }
+                                        <span class="opacity-60">{ format!(" · {}", commitment.date.format("%d.%m.")) }</span>
+                                    </span>
+                                    <span class="text-error whitespace-nowrap">{ format!("-{}", fmt_amount(commitment.amount)) }</span>
+                                </li>
+                            })}
+                        </ul>
+                    }
+                    <div class="flex justify-between text-sm font-bold">
+                        <span>{ "Safe to spend" }</span>
+                        <span class={figure_class}>{ format!("{} {}", fmt_amount(response.safe_to_spend), currency) }</span>
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -1,5 +1,7 @@
 pub mod transaction_modal;
 pub mod recurring_modal;
+pub mod shortcuts_modal;
 
 pub use transaction_modal::{TransactionModal, TransactionFormData};
 pub use recurring_modal::{RecurringModal, RecurringFormData};
+pub use shortcuts_modal::ShortcutsModal;
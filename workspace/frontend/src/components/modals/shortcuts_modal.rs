@@ -0,0 +1,46 @@
+use yew::prelude::*;
+use crate::common::shortcuts::SHORTCUTS;
+
+#[derive(Properties, PartialEq)]
+pub struct ShortcutsModalProps {
+    pub show: bool,
+    pub on_close: Callback<()>,
+}
+
+#[function_component(ShortcutsModal)]
+pub fn shortcuts_modal(props: &ShortcutsModalProps) -> Html {
+    let on_close = {
+        let on_close = props.on_close.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            on_close.emit(());
+        })
+    };
+
+    html! {
+        <dialog class={classes!("modal", props.show.then_some("modal-open"))} id="shortcuts_modal">
+            <div class="modal-box">
+                <h3 class="font-bold text-lg">{"Keyboard Shortcuts"}</h3>
+                <table class="table table-sm mt-4">
+                    <tbody>
+                        { for SHORTCUTS.iter().map(|shortcut| html! {
+                            <tr>
+                                <td class="w-24"><kbd class="kbd kbd-sm">{shortcut.keys}</kbd></td>
+                                <td>{shortcut.description}</td>
+                            </tr>
+                        })}
+                    </tbody>
+                </table>
+                <p class="text-xs opacity-60 mt-2">
+                    {"Table navigation, new and search are available on the transactions and recurring pages."}
+                </p>
+                <div class="modal-action">
+                    <button class="btn" onclick={on_close.clone()}>{"Close"}</button>
+                </div>
+            </div>
+            <form class="modal-backdrop" method="dialog">
+                <button onclick={on_close}>{"close"}</button>
+            </form>
+        </dialog>
+    }
+}
@@ -0,0 +1,6 @@
+mod budget_status;
+mod frame;
+mod monthly_report;
+
+pub use budget_status::PrintBudgetStatus;
+pub use monthly_report::PrintMonthlyReport;
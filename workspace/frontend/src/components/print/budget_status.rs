@@ -0,0 +1,110 @@
+use yew::prelude::*;
+use crate::api_client::category::{get_category_forecast, get_category_stats};
+use crate::common::fetch_hook::use_fetch_with_refetch;
+use crate::formatting::{fmt_amount_f64, use_currency};
+use crate::hooks::FetchState;
+use super::frame::{month_bounds, PrintFrame};
+
+#[derive(Properties, PartialEq)]
+pub struct PrintBudgetStatusProps {
+    pub year: i32,
+    pub month: u32,
+}
+
+/// Spending of one top-level category against its suggested budget.
+#[derive(Clone, PartialEq)]
+struct BudgetLine {
+    category_name: String,
+    spent: f64,
+    budget: f64,
+}
+
+/// Loads the month's spending per top-level category and the suggested budget for each.
+async fn load_budget_status(year: i32, month: u32) -> Result<Vec<BudgetLine>, String> {
+    let (start, end) = month_bounds(year, month).ok_or_else(|| "Invalid report month".to_string())?;
+    let stats = get_category_stats(&start.format("%Y-%m-%d").to_string(), &end.format("%Y-%m-%d").to_string()).await?;
+
+    let mut lines = Vec::new();
+    for stat in stats.iter().filter(|s| s.parent_id.is_none()) {
+        let total = stat.total_amount.parse::<f64>().unwrap_or(0.0);
+        if total > 0.0 {
+            continue;
+        }
+        let forecast = get_category_forecast(stat.category_id).await?;
+        let budget = forecast.suggested_budget.parse::<f64>().unwrap_or(0.0);
+        if budget == 0.0 && total == 0.0 {
+            continue;
+        }
+        lines.push(BudgetLine {
+            category_name: stat.category_name.clone(),
+            spent: total.abs(),
+            budget,
+        });
+    }
+    lines.sort_by(|a, b| a.category_name.cmp(&b.category_name));
+    Ok(lines)
+}
+
+/// Print-optimized budget status: spending against the suggested budget of each category.
+#[function_component(PrintBudgetStatus)]
+pub fn print_budget_status(props: &PrintBudgetStatusProps) -> Html {
+    let currency = use_currency();
+    let (year, month) = (props.year, props.month);
+    let (budget_state, _) = use_fetch_with_refetch(move || load_budget_status(year, month));
+
+    let content = match &*budget_state {
+        FetchState::Success(lines) => {
+            let total_spent: f64 = lines.iter().map(|l| l.spent).sum();
+            let total_budget: f64 = lines.iter().map(|l| l.budget).sum();
+            html! {
+                <div class="print-section">
+                    <h2>{"Budget Status"}</h2>
+                    <table>
+                        <thead>
+                            <tr>
+                                <th>{"Category"}</th>
+                                <th class="amount">{format!("Spent ({})", currency)}</th>
+                                <th class="amount">{format!("Budget ({})", currency)}</th>
+                                <th class="amount">{format!("Remaining ({})", currency)}</th>
+                                <th class="amount">{"Used"}</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            { for lines.iter().map(|line| {
+                                let used = if line.budget > 0.0 { format!("{:.0}%", line.spent / line.budget * 100.0) } else { "—".to_string() };
+                                let over = line.spent > line.budget;
+                                html! {
+                                    <tr>
+                                        <td>{&line.category_name}</td>
+                                        <td class="amount">{fmt_amount_f64(line.spent)}</td>
+                                        <td class="amount">{fmt_amount_f64(line.budget)}</td>
+                                        <td class="amount" style={over.then_some("font-weight: 700")}>{fmt_amount_f64(line.budget - line.spent)}</td>
+                                        <td class="amount">{used}</td>
+                                    </tr>
+                                }
+                            })}
+                            <tr>
+                                <td><strong>{"Total"}</strong></td>
+                                <td class="amount"><strong>{fmt_amount_f64(total_spent)}</strong></td>
+                                <td class="amount"><strong>{fmt_amount_f64(total_budget)}</strong></td>
+                                <td class="amount"><strong>{fmt_amount_f64(total_budget - total_spent)}</strong></td>
+                                <td></td>
+                            </tr>
+                        </tbody>
+                    </table>
+                    <p class="text-xs" style="color: #666; margin-top: 0.5rem">
+                        {"Budgets are the suggested monthly budgets derived from each category's spending history."}
+                    </p>
+                </div>
+            }
+        }
+        FetchState::Error(err) => html! { <p>{format!("Failed to load budget status: {}", err)}</p> },
+        _ => html! { <p class="no-print">{"Loading..."}</p> },
+    };
+
+    html! {
+        <PrintFrame title="Budget Status" year={props.year} month={props.month}>
+            {content}
+        </PrintFrame>
+    }
+}
@@ -0,0 +1,84 @@
+use chrono::NaiveDate;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+/// Print stylesheet: plain black-on-white, no app chrome, tables kept together across pages.
+const PRINT_CSS: &str = r#"
+.print-page { max-width: 800px; margin: 0 auto; padding: 2rem; background: #fff; color: #111; font-size: 12pt; }
+.print-page h1 { font-size: 18pt; font-weight: 700; margin-bottom: 0.25rem; }
+.print-page h2 { font-size: 14pt; font-weight: 600; margin: 1.5rem 0 0.5rem; }
+.print-page table { width: 100%; border-collapse: collapse; }
+.print-page th, .print-page td { border-bottom: 1px solid #ccc; padding: 4px 6px; text-align: left; }
+.print-page td.amount, .print-page th.amount { text-align: right; font-variant-numeric: tabular-nums; }
+.print-page tr { page-break-inside: avoid; break-inside: avoid; }
+.print-page .print-section { page-break-inside: avoid; break-inside: avoid; }
+.print-page .page-break { page-break-before: always; break-before: page; }
+@media print {
+    @page { margin: 1.5cm; }
+    body { background: #fff !important; }
+    .no-print { display: none !important; }
+    .print-page { padding: 0; max-width: none; }
+}
+"#;
+
+#[derive(Properties, PartialEq)]
+pub struct PrintFrameProps {
+    pub title: String,
+    pub year: i32,
+    pub month: u32,
+    pub children: Children,
+}
+
+/// Returns the first and last day of the month, or `None` for an invalid month.
+pub fn month_bounds(year: i32, month: u32) -> Option<(NaiveDate, NaiveDate)> {
+    let start = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let next = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)?
+    };
+    Some((start, next.pred_opt()?))
+}
+
+/// Page wrapper shared by the print views: stylesheet, heading and a print/back toolbar
+/// that is hidden when printing.
+#[function_component(PrintFrame)]
+pub fn print_frame(props: &PrintFrameProps) -> Html {
+    let on_print = Callback::from(|_: MouseEvent| {
+        if let Some(window) = web_sys::window() {
+            let _ = window.print();
+        }
+    });
+
+    let on_back = {
+        let navigator = use_navigator();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(navigator) = &navigator {
+                navigator.back();
+            }
+        })
+    };
+
+    let period = NaiveDate::from_ymd_opt(props.year, props.month, 1)
+        .map(|d| d.format("%B %Y").to_string())
+        .unwrap_or_else(|| format!("{}-{:02}", props.year, props.month));
+    let generated = chrono::Local::now().format("%Y-%m-%d %H:%M").to_string();
+
+    html! {
+        <div class="print-page">
+            <style>{PRINT_CSS}</style>
+            <div class="no-print flex gap-2 justify-end mb-4">
+                <button class="btn btn-sm btn-ghost" onclick={on_back}>
+                    <i class="fas fa-arrow-left"></i> {" Back"}
+                </button>
+                <button class="btn btn-sm btn-primary" onclick={on_print}>
+                    <i class="fas fa-print"></i> {" Print"}
+                </button>
+            </div>
+            <h1>{&props.title}</h1>
+            <div class="text-sm">{period}</div>
+            <div class="text-xs" style="color: #666">{format!("Generated {}", generated)}</div>
+            { for props.children.iter() }
+        </div>
+    }
+}
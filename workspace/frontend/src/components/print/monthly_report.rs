@@ -0,0 +1,116 @@
+use yew::prelude::*;
+use crate::api_client::category::{get_category_stats, CategoryStatistics};
+use crate::common::fetch_hook::use_fetch_with_refetch;
+use crate::formatting::{fmt_amount_f64, use_currency};
+use crate::hooks::FetchState;
+use super::frame::{month_bounds, PrintFrame};
+
+#[derive(Properties, PartialEq)]
+pub struct PrintMonthlyReportProps {
+    pub year: i32,
+    pub month: u32,
+}
+
+fn amount(stat: &CategoryStatistics) -> f64 {
+    stat.total_amount.parse::<f64>().unwrap_or(0.0)
+}
+
+/// Print-optimized monthly report: income and spending per category with totals.
+#[function_component(PrintMonthlyReport)]
+pub fn print_monthly_report(props: &PrintMonthlyReportProps) -> Html {
+    let currency = use_currency();
+    let bounds = month_bounds(props.year, props.month);
+
+    let (stats_state, _) = use_fetch_with_refetch(move || async move {
+        match bounds {
+            Some((start, end)) => {
+                get_category_stats(&start.format("%Y-%m-%d").to_string(), &end.format("%Y-%m-%d").to_string()).await
+            }
+            None => Err("Invalid report month".to_string()),
+        }
+    });
+
+    let content = match &*stats_state {
+        FetchState::Success(stats) => {
+            // Top-level categories carry the tree totals; children are listed beneath them
+            let mut roots: Vec<&CategoryStatistics> = stats
+                .iter()
+                .filter(|s| s.parent_id.is_none() && s.transaction_count > 0)
+                .collect();
+            roots.sort_by(|a, b| amount(a).partial_cmp(&amount(b)).unwrap_or(std::cmp::Ordering::Equal));
+
+            let income: f64 = roots.iter().map(|s| amount(s)).filter(|a| *a > 0.0).sum();
+            let expenses: f64 = roots.iter().map(|s| amount(s)).filter(|a| *a < 0.0).sum();
+
+            let render_section = |title: &str, incoming: bool| {
+                let rows: Vec<&&CategoryStatistics> = roots
+                    .iter()
+                    .filter(|s| (amount(s) > 0.0) == incoming)
+                    .collect();
+                html! {
+                    <div class="print-section">
+                        <h2>{title}</h2>
+                        <table>
+                            <thead>
+                                <tr>
+                                    <th>{"Category"}</th>
+                                    <th class="amount">{"Transactions"}</th>
+                                    <th class="amount">{format!("Amount ({})", currency)}</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                { for rows.iter().map(|root| {
+                                    let children: Vec<&CategoryStatistics> = stats
+                                        .iter()
+                                        .filter(|s| s.parent_id == Some(root.category_id) && s.transaction_count > 0)
+                                        .collect();
+                                    html! {
+                                        <>
+                                            <tr>
+                                                <td><strong>{&root.category_name}</strong></td>
+                                                <td class="amount">{root.transaction_count}</td>
+                                                <td class="amount"><strong>{fmt_amount_f64(amount(root))}</strong></td>
+                                            </tr>
+                                            { for children.iter().map(|child| html! {
+                                                <tr>
+                                                    <td style="padding-left: 1.5rem">{&child.category_name}</td>
+                                                    <td class="amount">{child.transaction_count}</td>
+                                                    <td class="amount">{fmt_amount_f64(amount(child))}</td>
+                                                </tr>
+                                            })}
+                                        </>
+                                    }
+                                })}
+                            </tbody>
+                        </table>
+                    </div>
+                }
+            };
+
+            html! {
+                <>
+                    <div class="print-section">
+                        <h2>{"Summary"}</h2>
+                        <table>
+                            <tbody>
+                                <tr><td>{"Income"}</td><td class="amount">{format!("{} {}", fmt_amount_f64(income), currency)}</td></tr>
+                                <tr><td>{"Expenses"}</td><td class="amount">{format!("{} {}", fmt_amount_f64(expenses), currency)}</td></tr>
+                                <tr><td><strong>{"Net"}</strong></td><td class="amount"><strong>{format!("{} {}", fmt_amount_f64(income + expenses), currency)}</strong></td></tr>
+                            </tbody>
+                        </table>
+                    </div>
+                    {render_section("Income by Category", true)}
+                    {render_section("Spending by Category", false)}
+                </>
+            }
+        }
+        FetchState::Error(err) => html! { <p>{format!("Failed to load report: {}", err)}</p> },
+        _ => html! { <p class="no-print">{"Loading..."}</p> },
+    };
+
+    html! {
+        <PrintFrame title="Monthly Report" year={props.year} month={props.month}>
+            {content}
+        </PrintFrame>
+    }
+}
@@ -1,10 +1,11 @@
 use yew::prelude::*;
 use yew_router::prelude::*;
 use std::collections::HashMap;
-use crate::api_client::recurring_transaction::get_recurring_transactions;
+use crate::api_client::recurring_transaction::get_recurring_transactions_page;
 use crate::api_client::category::get_categories;
 use crate::api_client::account::get_accounts;
 use crate::common::fetch_hook::use_fetch_with_refetch;
+use crate::common::shortcuts::{use_keyboard_shortcuts, use_row_navigation};
 use crate::components::common::pagination::Pagination;
 use crate::formatting::{fmt_amount_str, use_currency};
 use crate::hooks::FetchState;
@@ -42,6 +43,7 @@ pub fn recurring_list(props: &RecurringListProps) -> Html {
     let current_page = use_state(|| 1u64);
     let items_per_page = 50u64;
     let fetch_state = use_state(|| FetchState::Loading);
+    let total_items = use_state(|| 0u64);
 
     let account_id = props.account_id;
 
@@ -52,10 +54,14 @@ pub fn recurring_list(props: &RecurringListProps) -> Html {
     let sort_direction = use_state(|| SortDirection::Descending);
     let selected_category = use_state(|| None::<i32>);
     let selected_target_account = use_state(|| None::<i32>);
+    let search = use_state(String::new);
+    let search_ref = use_node_ref();
+    let navigator = use_navigator();
 
     // Fetch data when page or filters change
     {
         let fetch_state = fetch_state.clone();
+        let total_items = total_items.clone();
         let page = *current_page;
         let category_id = *selected_category;
         let target_account_id = (*selected_target_account).or(account_id);
@@ -66,9 +72,10 @@ pub fn recurring_list(props: &RecurringListProps) -> Html {
                 fetch_state.set(FetchState::Loading);
 
                 wasm_bindgen_futures::spawn_local(async move {
-                    match get_recurring_transactions(Some(page), Some(items_per_page), target_account_id, None, category_id).await {
-                        Ok(data) => {
-                            fetch_state.set(FetchState::Success(data));
+                    match get_recurring_transactions_page(page, items_per_page, target_account_id, category_id).await {
+                        Ok(response) => {
+                            total_items.set(response.pagination.total_count);
+                            fetch_state.set(FetchState::Success(response.data));
                         }
                         Err(err) => {
                             fetch_state.set(FetchState::Error(err));
@@ -175,27 +182,77 @@ pub fn recurring_list(props: &RecurringListProps) -> Html {
         })
     };
 
+    // Rows as displayed: the loaded page narrowed by the search box, then sorted
+    let sorted_transactions = match &*fetch_state {
+        FetchState::Success(transactions) => {
+            let needle = search.trim().to_lowercase();
+            let mut sorted_transactions: Vec<_> = transactions
+                .iter()
+                .filter(|t| {
+                    needle.is_empty()
+                        || t.name.to_lowercase().contains(&needle)
+                        || t.description.as_ref().is_some_and(|d| d.to_lowercase().contains(&needle))
+                })
+                .cloned()
+                .collect();
+            sorted_transactions.sort_by(|a, b| {
+                let cmp = match *sort_column {
+                    SortColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                    SortColumn::Period => a.period.to_lowercase().cmp(&b.period.to_lowercase()),
+                    SortColumn::Amount => {
+                        let a_amt = a.amount.parse::<f64>().unwrap_or(0.0);
+                        let b_amt = b.amount.parse::<f64>().unwrap_or(0.0);
+                        a_amt.partial_cmp(&b_amt).unwrap_or(std::cmp::Ordering::Equal)
+                    },
+                    SortColumn::StartDate => a.start_date.cmp(&b.start_date),
+                };
+                match *sort_direction {
+                    SortDirection::Ascending => cmp,
+                    SortDirection::Descending => cmp.reverse(),
+                }
+            });
+            sorted_transactions
+        }
+        _ => vec![],
+    };
+
+    let selected_row = {
+        let ids: Vec<i32> = sorted_transactions.iter().map(|t| t.id).collect();
+        let navigator = navigator.clone();
+        use_row_navigation(
+            ids.len(),
+            Callback::from(move |index: usize| {
+                if let Some(navigator) = &navigator {
+                    navigator.push(&Route::RecurringDetail { id: ids[index] });
+                }
+            }),
+        )
+    };
+
+    {
+        let search_ref = search_ref.clone();
+        use_keyboard_shortcuts(vec![(
+            "/",
+            Callback::from(move |_| {
+                if let Some(input) = search_ref.cast::<web_sys::HtmlInputElement>() {
+                    let _ = input.focus();
+                }
+            }),
+        )]);
+    }
+
+    let on_search_input = {
+        let search = search.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(target) = e.target_dyn_into::<web_sys::HtmlInputElement>() {
+                search.set(target.value());
+            }
+        })
+    };
+
     let render_content = || -> Html {
         match &*fetch_state {
-            FetchState::Success(transactions) if !transactions.is_empty() => {
-                let mut sorted_transactions = transactions.clone();
-                sorted_transactions.sort_by(|a, b| {
-                    let cmp = match *sort_column {
-                        SortColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-                        SortColumn::Period => a.period.to_lowercase().cmp(&b.period.to_lowercase()),
-                        SortColumn::Amount => {
-                            let a_amt = a.amount.parse::<f64>().unwrap_or(0.0);
-                            let b_amt = b.amount.parse::<f64>().unwrap_or(0.0);
-                            a_amt.partial_cmp(&b_amt).unwrap_or(std::cmp::Ordering::Equal)
-                        },
-                        SortColumn::StartDate => a.start_date.cmp(&b.start_date),
-                    };
-                    match *sort_direction {
-                        SortDirection::Ascending => cmp,
-                        SortDirection::Descending => cmp.reverse(),
-                    }
-                });
-
+            FetchState::Success(_) if !sorted_transactions.is_empty() => {
                 let current_sort_column = *sort_column;
                 let current_sort_direction = *sort_direction;
                 html! {
@@ -214,7 +271,7 @@ pub fn recurring_list(props: &RecurringListProps) -> Html {
                                 </tr>
                             </thead>
                             <tbody>
-                                { for sorted_transactions.iter().map(|t| {
+                                { for sorted_transactions.iter().enumerate().map(|(index, t)| {
                                     let amount = match t.amount.parse::<f64>() {
                                         Ok(val) => val,
                                         Err(_) => 0.0,
@@ -255,7 +312,7 @@ pub fn recurring_list(props: &RecurringListProps) -> Html {
                                     };
 
                                     html! {
-                                        <tr>
+                                        <tr class={classes!((selected_row == Some(index)).then_some("bg-base-300"))}>
                                             <td class="font-bold">
                                                 <Link<Route>
                                                     to={Route::RecurringDetail { id: t.id }}
@@ -279,6 +336,11 @@ pub fn recurring_list(props: &RecurringListProps) -> Html {
                                                     } else {
                                                         html! {}
                                                     }}
+                                                    {match t.approval_status.as_str() {
+                                                        "PendingApproval" => html! { <span class="badge badge-xs badge-accent">{"awaiting approval"}</span> },
+                                                        "Rejected" => html! { <span class="badge badge-xs badge-error">{"rejected"}</span> },
+                                                        _ => html! {},
+                                                    }}
                                                 </div>
                                             </td>
                                             <td>{&t.period}</td>
@@ -374,6 +436,20 @@ pub fn recurring_list(props: &RecurringListProps) -> Html {
     html! {
         <div>
             <div class="flex gap-4 mb-4">
+                <div class="form-control">
+                    <label class="label">
+                        <span class="label-text">{"Search"}</span>
+                    </label>
+                    <input
+                        type="search"
+                        class="input input-bordered input-sm"
+                        placeholder="Name or description  ( / )"
+                        ref={search_ref}
+                        oninput={on_search_input}
+                        value={(*search).clone()}
+                    />
+                </div>
+
                 <div class="form-control">
                     <label class="label">
                         <span class="label-text">{"Filter by Category"}</span>
@@ -412,20 +488,10 @@ pub fn recurring_list(props: &RecurringListProps) -> Html {
             // Add pagination if we have data
             {match &*fetch_state {
                 FetchState::Success(transactions) if !transactions.is_empty() => {
-                    // Estimate total items based on page and items retrieved
-                    let items_on_page = transactions.len() as u64;
-                    let estimated_total = if items_on_page < items_per_page {
-                        // Last page
-                        (*current_page - 1) * items_per_page + items_on_page
-                    } else {
-                        // Assume there might be more pages
-                        (*current_page) * items_per_page + 1
-                    };
-
                     html! {
                         <Pagination
                             current_page={*current_page}
-                            total_items={estimated_total}
+                            total_items={*total_items}
                             items_per_page={items_per_page}
                             on_page_change={on_page_change.clone()}
                         />
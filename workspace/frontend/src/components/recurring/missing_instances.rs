@@ -222,7 +222,14 @@ pub fn missing_instances(props: &MissingInstancesProps) -> Html {
                                                                 {&instance.recurring_transaction_name}
                                                             </Link<Route>>
                                                         </td>
-                                                        <td>{&instance.due_date}</td>
+                                                        <td>
+                                                            {&instance.due_date}
+                                                            if instance.overdue_days > 0 {
+                                                                <span class={classes!("badge", "badge-sm", "ml-2", severity_badge_class(&instance.severity))}>
+                                                                    {format!("{} days overdue", instance.overdue_days)}
+                                                                </span>
+                                                            }
+                                                        </td>
                                                         <td class={classes!("font-mono", amount_class)}>
                                                             {format!("{}{}", amount_prefix, format_currency(&instance.expected_amount))}
                                                         </td>
@@ -296,3 +303,12 @@ pub fn missing_instances(props: &MissingInstancesProps) -> Html {
         </div>
     }
 }
+
+fn severity_badge_class(severity: &str) -> &'static str {
+    match severity {
+        "Critical" => "badge-error",
+        "Warning" => "badge-warning",
+        "Notice" => "badge-info",
+        _ => "badge-ghost",
+    }
+}
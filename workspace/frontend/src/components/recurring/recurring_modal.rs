@@ -7,7 +7,10 @@ use crate::api_client::account::AccountResponse;
 use crate::api_client::category::get_categories;
 use crate::api_client::scenario::get_scenarios;
 use crate::common::fetch_hook::use_fetch_with_refetch;
+use crate::components::common::balance_preview::{effect_on, BalancePreview, PreviewDraft};
 use crate::hooks::FetchState;
+use chrono::NaiveDate;
+use common::{parse_amount, AmountLocale, PreviewTransaction};
 
 #[derive(Properties, PartialEq)]
 pub struct RecurringModalProps {
@@ -24,6 +27,38 @@ pub struct RecurringModalProps {
     pub scenario_id: Option<i32>,
 }
 
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
+/// Reads the fields the balance preview needs, `None` while they are incomplete.
+fn read_preview_draft(form: &web_sys::HtmlFormElement, existing: Option<&RecurringTransactionResponse>) -> Option<PreviewDraft> {
+    let form_data = web_sys::FormData::new_with_form(form).ok()?;
+    let amount = parse_amount(&form_data.get("amount").as_string()?, AmountLocale::Auto).ok()?;
+    let date = parse_date(&form_data.get("start_date").as_string()?)?;
+    let end_date = form_data.get("end_date").as_string().and_then(|value| parse_date(&value));
+    let period = form_data.get("period").as_string()?;
+    let account_id = form_data.get("target_account_id").as_string()?.parse::<i32>().ok()?;
+
+    let original = existing.and_then(|t| {
+        let amount = parse_amount(&t.amount, AmountLocale::Auto).ok()?;
+        Some(PreviewTransaction {
+            amount: effect_on(account_id, amount, t.target_account_id, t.source_account_id)?,
+            date: parse_date(&t.start_date)?,
+            period: Some(t.period.clone()),
+            end_date: t.end_date.as_deref().and_then(parse_date),
+        })
+    });
+
+    Some(PreviewDraft {
+        account_id,
+        transaction: PreviewTransaction { amount, date, period: Some(period), end_date },
+        original,
+        transaction_id: None,
+        recurring_transaction_id: existing.map(|t| t.id),
+    })
+}
+
 #[function_component(RecurringModal)]
 pub fn recurring_modal(props: &RecurringModalProps) -> Html {
     let form_ref = use_node_ref();
@@ -33,6 +68,16 @@ pub fn recurring_modal(props: &RecurringModalProps) -> Html {
     // Fetch categories and scenarios for dropdowns
     let (categories_state, _) = use_fetch_with_refetch(get_categories);
     let (scenarios_state, _) = use_fetch_with_refetch(get_scenarios);
+    let preview_draft = use_state(|| None::<PreviewDraft>);
+
+    // A reopened modal starts without the preview of the previous form
+    {
+        let preview_draft = preview_draft.clone();
+        use_effect_with(props.show, move |_| {
+            preview_draft.set(None);
+            || ()
+        });
+    }
 
     let is_edit_mode = props.transaction.is_some();
     let title = if is_edit_mode { "Edit Recurring Transaction" } else { "Add Recurring Transaction" };
@@ -144,6 +189,8 @@ pub fn recurring_modal(props: &RecurringModalProps) -> Html {
                         category_id,
                         scenario_id,
                         is_simulated: Some(is_simulated),
+                        // TODO: Get from user context
+                        created_by_user_id: Some(1),
                     };
 
                     wasm_bindgen_futures::spawn_local(async move {
@@ -167,6 +214,17 @@ pub fn recurring_modal(props: &RecurringModalProps) -> Html {
         })
     };
 
+    let on_input = {
+        let form_ref = form_ref.clone();
+        let preview_draft = preview_draft.clone();
+        let transaction = props.transaction.clone();
+        Callback::from(move |_: InputEvent| {
+            if let Some(form) = form_ref.cast::<web_sys::HtmlFormElement>() {
+                preview_draft.set(read_preview_draft(&form, transaction.as_ref()));
+            }
+        })
+    };
+
     let on_close = {
         let on_close = props.on_close.clone();
         let is_submitting = *is_submitting;
@@ -218,7 +276,7 @@ pub fn recurring_modal(props: &RecurringModalProps) -> Html {
                     html! {}
                 }}
 
-                <form ref={form_ref} onsubmit={on_submit} class="py-4 space-y-4">
+                <form ref={form_ref} onsubmit={on_submit} oninput={on_input} class="py-4 space-y-4">
                     <div class="form-control">
                         <label class="label"><span class="label-text">{"Transaction Name"}</span></label>
                         <input
@@ -430,6 +488,8 @@ pub fn recurring_modal(props: &RecurringModalProps) -> Html {
                         </label>
                     </div>
 
+                    <BalancePreview draft={(*preview_draft).clone()} />
+
                     <div class="modal-action">
                         <button
                             type="button"
@@ -8,6 +8,7 @@ use crate::api_client::recurring_transaction::{
 };
 use crate::api_client::account::{get_accounts_with_ignored, AccountResponse};
 use crate::common::fetch_hook::use_fetch_with_refetch;
+use crate::common::shortcuts::use_keyboard_shortcuts;
 use crate::common::toast::ToastContext;
 use crate::hooks::FetchState;
 use yew::prelude::*;
@@ -91,6 +92,18 @@ pub fn recurring(props: &RecurringProps) -> Html {
         })
     };
 
+    {
+        let show_modal = show_modal.clone();
+        let edit_transaction = edit_transaction.clone();
+        use_keyboard_shortcuts(vec![(
+            "n",
+            Callback::from(move |_| {
+                edit_transaction.set(None);
+                show_modal.set(true);
+            }),
+        )]);
+    }
+
     let on_modal_close = {
         let show_modal = show_modal.clone();
         let edit_transaction = edit_transaction.clone();
@@ -1,10 +1,23 @@
+use chrono::Datelike;
 use yew::prelude::*;
+use yew_router::prelude::*;
+use crate::router::Route;
 use super::charts::{CategoryPieChart, CashFlowBarChart, SankeyChart, AccountBreakdownChart};
 
 #[function_component(Reports)]
 pub fn reports() -> Html {
+    let today = chrono::Local::now().date_naive();
+
     html! {
         <>
+            <div class="flex justify-end mb-4">
+                <Link<Route>
+                    to={Route::PrintMonthlyReport { year: today.year(), month: today.month() }}
+                    classes="btn btn-sm btn-outline"
+                >
+                    <i class="fas fa-print"></i> {" Printable Report"}
+                </Link<Route>>
+            </div>
             <div class="grid grid-cols-1 lg:grid-cols-2 gap-6 mb-6">
                 <div class="card bg-base-100 shadow">
                     <div class="card-body">
@@ -0,0 +1,228 @@
+use yew::prelude::*;
+use wasm_bindgen::prelude::*;
+use web_sys::{Element, HtmlInputElement};
+use chrono::{Duration, Local, NaiveDate};
+use serde::Serialize;
+use std::collections::HashMap;
+use crate::api_client::category::get_categories;
+use crate::api_client::transaction::get_transactions_geojson;
+use crate::colors::color_by_index;
+use crate::common::fetch_hook::use_fetch_with_refetch;
+use crate::formatting::fmt_amount;
+use crate::hooks::FetchState;
+
+/// Marker color for transactions without a category.
+const UNCATEGORIZED_COLOR: &str = "#9ca3af";
+
+#[wasm_bindgen(inline_js = "
+export function render_spending_map(element, markers_json) {
+    if (typeof L === 'undefined') {
+        console.error('Leaflet is not loaded, cannot render spending map');
+        return;
+    }
+    if (!element._finrustMap) {
+        const map = L.map(element);
+        L.tileLayer('https://{s}.tile.openstreetmap.org/{z}/{x}/{y}.png', {
+            maxZoom: 19,
+            attribution: '&copy; OpenStreetMap contributors',
+        }).addTo(map);
+        element._finrustMap = map;
+    }
+    const map = element._finrustMap;
+    if (element._finrustLayer) {
+        map.removeLayer(element._finrustLayer);
+    }
+
+    const markers = JSON.parse(markers_json);
+    // Cluster dense areas when the plugin is available, plain layer otherwise
+    const layer = L.markerClusterGroup ? L.markerClusterGroup({ maxClusterRadius: 40 }) : L.layerGroup();
+    for (const m of markers) {
+        const popup = document.createElement('div');
+        for (const line of [m.name, m.amount, m.date, m.category]) {
+            const row = document.createElement('div');
+            row.textContent = line;
+            popup.appendChild(row);
+        }
+        L.circleMarker([m.lat, m.lon], {
+            radius: 7,
+            weight: 1,
+            color: m.color,
+            fillColor: m.color,
+            fillOpacity: 0.8,
+        }).bindPopup(popup).addTo(layer);
+    }
+    layer.addTo(map);
+    element._finrustLayer = layer;
+
+    if (markers.length > 0) {
+        map.fitBounds(markers.map((m) => [m.lat, m.lon]), { padding: [24, 24], maxZoom: 15 });
+    } else {
+        map.setView([20, 0], 2);
+    }
+    setTimeout(() => map.invalidateSize(), 0);
+}
+
+export function destroy_spending_map(element) {
+    if (element._finrustMap) {
+        element._finrustMap.remove();
+        delete element._finrustMap;
+        delete element._finrustLayer;
+    }
+}
+")]
+extern "C" {
+    fn render_spending_map(element: &Element, markers_json: &str);
+    fn destroy_spending_map(element: &Element);
+}
+
+/// Marker data handed over to the Leaflet glue code.
+#[derive(Serialize)]
+struct MapMarker {
+    lat: f64,
+    lon: f64,
+    color: String,
+    name: String,
+    amount: String,
+    date: String,
+    category: String,
+}
+
+#[function_component(SpendingMap)]
+pub fn spending_map() -> Html {
+    let map_ref = use_node_ref();
+    let today = Local::now().date_naive();
+    let start_date = use_state(|| today - Duration::days(365));
+    let end_date = use_state(|| today);
+    let features_state = use_state(|| FetchState::Loading);
+    let (categories_state, _) = use_fetch_with_refetch(get_categories);
+
+    // Fetch features whenever the date range changes
+    {
+        let features_state = features_state.clone();
+        use_effect_with((*start_date, *end_date), move |(start, end)| {
+            let features_state = features_state.clone();
+            let (start, end) = (*start, *end);
+            features_state.set(FetchState::Loading);
+            wasm_bindgen_futures::spawn_local(async move {
+                match get_transactions_geojson(Some(start), Some(end)).await {
+                    Ok(collection) => features_state.set(FetchState::Success(collection)),
+                    Err(err) => features_state.set(FetchState::Error(err)),
+                }
+            });
+            || ()
+        });
+    }
+
+    // Category ID -> (name, color); colors follow the category list order so they stay stable
+    let category_styles: HashMap<i32, (String, &'static str)> = match &*categories_state {
+        FetchState::Success(categories) => categories
+            .iter()
+            .enumerate()
+            .map(|(index, category)| (category.id, (category.name.clone(), color_by_index(index))))
+            .collect(),
+        _ => HashMap::new(),
+    };
+
+    {
+        let category_styles = category_styles.clone();
+        use_effect_with((map_ref.clone(), features_state.clone(), categories_state.clone()),
+            move |(map_ref, features_state, _)| {
+            if let (Some(element), FetchState::Success(collection)) =
+                (map_ref.cast::<Element>(), &**features_state) {
+                let markers: Vec<MapMarker> = collection.features.iter().map(|feature| {
+                    let properties = &feature.properties;
+                    let (category, color) = properties.category_id
+                        .and_then(|id| category_styles.get(&id))
+                        .map(|(name, color)| (name.clone(), color.to_string()))
+                        .unwrap_or_else(|| ("Uncategorized".to_string(), UNCATEGORIZED_COLOR.to_string()));
+                    MapMarker {
+                        lon: feature.geometry.coordinates[0],
+                        lat: feature.geometry.coordinates[1],
+                        color,
+                        name: properties.name.clone(),
+                        amount: fmt_amount(properties.amount),
+                        date: properties.date.format("%Y-%m-%d").to_string(),
+                        category,
+                    }
+                }).collect();
+
+                match serde_json::to_string(&markers) {
+                    Ok(markers_json) => render_spending_map(&element, &markers_json),
+                    Err(e) => log::error!("Failed to serialize map markers: {}", e),
+                }
+            }
+            || ()
+        });
+    }
+
+    // Tear the Leaflet map down when the component unmounts
+    {
+        let map_ref = map_ref.clone();
+        use_effect_with((), move |_| {
+            move || {
+                if let Some(element) = map_ref.cast::<Element>() {
+                    destroy_spending_map(&element);
+                }
+            }
+        });
+    }
+
+    let on_date_change = |state: UseStateHandle<NaiveDate>| {
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Ok(date) = NaiveDate::parse_from_str(&input.value(), "%Y-%m-%d") {
+                state.set(date);
+            }
+        })
+    };
+
+    let status = match &*features_state {
+        FetchState::Loading | FetchState::NotStarted => html! {
+            <span class="loading loading-spinner loading-sm"></span>
+        },
+        FetchState::Success(collection) => html! {
+            <span class="text-sm opacity-70">{format!("{} transactions with a location", collection.features.len())}</span>
+        },
+        FetchState::Error(err) => html! {
+            <span class="text-sm text-error">{err.clone()}</span>
+        },
+    };
+
+    let mut legend: Vec<(String, &'static str)> = category_styles.values().cloned().collect();
+    legend.sort();
+
+    html! {
+        <div class="card bg-base-100 shadow">
+            <div class="card-body">
+                <div class="flex flex-wrap items-end gap-4 mb-4">
+                    <label class="form-control">
+                        <span class="label-text">{"From"}</span>
+                        <input type="date" class="input input-bordered input-sm"
+                            value={start_date.format("%Y-%m-%d").to_string()}
+                            onchange={on_date_change(start_date.clone())} />
+                    </label>
+                    <label class="form-control">
+                        <span class="label-text">{"To"}</span>
+                        <input type="date" class="input input-bordered input-sm"
+                            value={end_date.format("%Y-%m-%d").to_string()}
+                            onchange={on_date_change(end_date.clone())} />
+                    </label>
+                    {status}
+                </div>
+                <div ref={map_ref} class="w-full rounded-lg" style="height: 600px;"></div>
+                <div class="flex flex-wrap gap-3 mt-4 text-sm">
+                    { for legend.iter().map(|(name, color)| html! {
+                        <span class="flex items-center gap-1">
+                            <span class="inline-block w-3 h-3 rounded-full" style={format!("background-color: {}", color)}></span>
+                            {name.clone()}
+                        </span>
+                    }) }
+                    <span class="flex items-center gap-1">
+                        <span class="inline-block w-3 h-3 rounded-full" style={format!("background-color: {}", UNCATEGORIZED_COLOR)}></span>
+                        {"Uncategorized"}
+                    </span>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -3,10 +3,10 @@ use crate::api_client::category::{get_categories, CategoryResponse};
 use crate::api_client::scenario::Scenario;
 use crate::api_client::transaction::{create_transaction, update_transaction, CreateTransactionRequest, TransactionResponse, UpdateTransactionRequest};
 use crate::common::fetch_hook::use_fetch_with_refetch;
+use crate::components::common::balance_preview::{effect_on, BalancePreview, PreviewDraft};
 use crate::hooks::FetchState;
 use chrono::NaiveDate;
-use rust_decimal::Decimal;
-use std::str::FromStr;
+use common::{parse_amount, AmountLocale, PreviewTransaction};
 use yew::prelude::*;
 
 #[derive(Properties, PartialEq)]
@@ -26,12 +26,41 @@ pub struct TransactionModalProps {
     pub scenario_id: Option<i32>,
 }
 
+/// Reads the fields the balance preview needs, `None` while they are incomplete.
+fn read_preview_draft(form: &web_sys::HtmlFormElement, existing: Option<&TransactionResponse>) -> Option<PreviewDraft> {
+    let form_data = web_sys::FormData::new_with_form(form).ok()?;
+    let amount = parse_amount(&form_data.get("amount").as_string()?, AmountLocale::Auto).ok()?;
+    let date = NaiveDate::parse_from_str(&form_data.get("date").as_string()?, "%Y-%m-%d").ok()?;
+    let account_id = form_data.get("target_account_id").as_string()?.parse::<i32>().ok()?;
+
+    Some(PreviewDraft {
+        account_id,
+        transaction: PreviewTransaction { amount, date, period: None, end_date: None },
+        original: existing.and_then(|t| {
+            effect_on(account_id, t.amount, t.target_account_id, t.source_account_id)
+                .map(|amount| PreviewTransaction { amount, date: t.date, period: None, end_date: None })
+        }),
+        transaction_id: existing.map(|t| t.id),
+        recurring_transaction_id: None,
+    })
+}
+
 #[function_component(TransactionModal)]
 pub fn transaction_modal(props: &TransactionModalProps) -> Html {
     let form_ref = use_node_ref();
     let is_submitting = use_state(|| false);
     let error_message = use_state(|| None::<String>);
     let (categories_state, _) = use_fetch_with_refetch(get_categories);
+    let preview_draft = use_state(|| None::<PreviewDraft>);
+
+    // A reopened modal starts without the preview of the previous form
+    {
+        let preview_draft = preview_draft.clone();
+        use_effect_with(props.show, move |_| {
+            preview_draft.set(None);
+            || ()
+        });
+    }
 
     let is_edit_mode = props.transaction.is_some();
     let title = if is_edit_mode { "Edit Transaction" } else { "Add Transaction" };
@@ -75,7 +104,7 @@ pub fn transaction_modal(props: &TransactionModalProps) -> Html {
                 let is_simulated = form_data.get("is_simulated").as_string().map(|v| v == "on").unwrap_or(false);
 
                 // Parse amount
-                let amount = match Decimal::from_str(&amount_str) {
+                let amount = match parse_amount(&amount_str, AmountLocale::Auto) {
                     Ok(amt) => amt,
                     Err(_) => {
                         error_message.set(Some("Invalid amount format".to_string()));
@@ -209,6 +238,17 @@ pub fn transaction_modal(props: &TransactionModalProps) -> Html {
         })
     };
 
+    let on_input = {
+        let form_ref = form_ref.clone();
+        let preview_draft = preview_draft.clone();
+        let transaction = props.transaction.clone();
+        Callback::from(move |_: InputEvent| {
+            if let Some(form) = form_ref.cast::<web_sys::HtmlFormElement>() {
+                preview_draft.set(read_preview_draft(&form, transaction.as_ref()));
+            }
+        })
+    };
+
     let on_close = {
         let on_close = props.on_close.clone();
         let is_submitting = *is_submitting;
@@ -249,7 +289,7 @@ pub fn transaction_modal(props: &TransactionModalProps) -> Html {
                     html! {}
                 }}
 
-                <form ref={form_ref} onsubmit={on_submit} class="py-4 space-y-4">
+                <form ref={form_ref} onsubmit={on_submit} oninput={on_input} class="py-4 space-y-4">
                     <div class="form-control">
                         <label class="label"><span class="label-text">{"Transaction Name"}</span></label>
                         <input
@@ -417,6 +457,8 @@ pub fn transaction_modal(props: &TransactionModalProps) -> Html {
                         </label>
                     </div>
 
+                    <BalancePreview draft={(*preview_draft).clone()} />
+
                     <div class="modal-action">
                         <button
                             type="button"
@@ -1,11 +1,12 @@
 use yew::prelude::*;
 use yew_router::prelude::*;
 use std::collections::HashMap;
-use crate::api_client::transaction::{get_transactions, TransactionFilters, TransactionResponse};
+use crate::api_client::transaction::{get_transactions_page, TransactionFilters, TransactionResponse};
 use crate::api_client::account::get_accounts;
 use crate::api_client::category::get_categories;
 use crate::api_client::scenario::get_scenarios;
 use crate::common::fetch_hook::use_fetch_with_refetch;
+use crate::common::shortcuts::{use_keyboard_shortcuts, use_row_navigation};
 use crate::components::common::pagination::Pagination;
 use crate::formatting::fmt_amount;
 use crate::hooks::FetchState;
@@ -32,6 +33,7 @@ pub fn transactions() -> Html {
     let current_page = use_state(|| 1u64);
     let items_per_page = 50u64;
     let fetch_state = use_state(|| FetchState::Loading);
+    let total_items = use_state(|| 0u64);
 
     let (accounts_state, _) = use_fetch_with_refetch(get_accounts);
     let (categories_state, _) = use_fetch_with_refetch(get_categories);
@@ -43,6 +45,9 @@ pub fn transactions() -> Html {
     let selected_category = use_state(|| None::<i32>);
     let selected_source_account = use_state(|| None::<i32>);
     let selected_target_account = use_state(|| None::<i32>);
+    let search = use_state(String::new);
+    let search_ref = use_node_ref();
+    let navigator = use_navigator();
 
     let filters = {
         let month_val = *selected_month;
@@ -58,6 +63,7 @@ pub fn transactions() -> Html {
     // Fetch data when page or filters change
     {
         let fetch_state = fetch_state.clone();
+        let total_items = total_items.clone();
         let page = *current_page;
         let filters = filters.clone();
         use_effect_with(
@@ -67,9 +73,10 @@ pub fn transactions() -> Html {
                 fetch_state.set(FetchState::Loading);
 
                 wasm_bindgen_futures::spawn_local(async move {
-                    match get_transactions(Some(page), Some(items_per_page), &filters).await {
-                        Ok(data) => {
-                            fetch_state.set(FetchState::Success(data));
+                    match get_transactions_page(page, items_per_page, &filters).await {
+                        Ok(response) => {
+                            total_items.set(response.pagination.total_count);
+                            fetch_state.set(FetchState::Success(response.data));
                         }
                         Err(err) => {
                             fetch_state.set(FetchState::Error(err));
@@ -84,18 +91,21 @@ pub fn transactions() -> Html {
     // Create a refetch callback for manual refresh (e.g., after creating a transaction)
     let refetch = {
         let fetch_state = fetch_state.clone();
+        let total_items = total_items.clone();
         let current_page = current_page.clone();
         let filters = filters.clone();
         Callback::from(move |_| {
             let fetch_state = fetch_state.clone();
+            let total_items = total_items.clone();
             let page = *current_page;
             let filters = filters.clone();
             fetch_state.set(FetchState::Loading);
 
             wasm_bindgen_futures::spawn_local(async move {
-                match get_transactions(Some(page), Some(items_per_page), &filters).await {
-                    Ok(data) => {
-                        fetch_state.set(FetchState::Success(data));
+                match get_transactions_page(page, items_per_page, &filters).await {
+                    Ok(response) => {
+                        total_items.set(response.pagination.total_count);
+                        fetch_state.set(FetchState::Success(response.data));
                     }
                     Err(err) => {
                         fetch_state.set(FetchState::Error(err));
@@ -145,6 +155,75 @@ pub fn transactions() -> Html {
         _ => vec![],
     };
 
+    // Rows as displayed: the loaded page narrowed by the search box, then sorted
+    let visible_transactions: Vec<TransactionResponse> = match &*fetch_state {
+        FetchState::Success(transactions) => {
+            let needle = search.trim().to_lowercase();
+            let mut visible: Vec<TransactionResponse> = transactions
+                .iter()
+                .filter(|t| {
+                    needle.is_empty()
+                        || t.name.to_lowercase().contains(&needle)
+                        || t.description.as_ref().is_some_and(|d| d.to_lowercase().contains(&needle))
+                })
+                .cloned()
+                .collect();
+            visible.sort_by(|a, b| {
+                let cmp = match *sort_column {
+                    SortColumn::Date => a.date.cmp(&b.date),
+                    SortColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                    SortColumn::Account => {
+                        let a_name = account_map.get(&a.target_account_id).map(|s| s.as_str()).unwrap_or("");
+                        let b_name = account_map.get(&b.target_account_id).map(|s| s.as_str()).unwrap_or("");
+                        a_name.to_lowercase().cmp(&b_name.to_lowercase())
+                    },
+                    SortColumn::Amount => a.amount.cmp(&b.amount),
+                };
+                match *sort_direction {
+                    SortDirection::Ascending => cmp,
+                    SortDirection::Descending => cmp.reverse(),
+                }
+            });
+            visible
+        }
+        _ => vec![],
+    };
+
+    let selected_row = {
+        let ids: Vec<i32> = visible_transactions.iter().map(|t| t.id).collect();
+        let navigator = navigator.clone();
+        use_row_navigation(
+            ids.len(),
+            Callback::from(move |index: usize| {
+                if let Some(navigator) = &navigator {
+                    navigator.push(&Route::TransactionEdit { id: ids[index] });
+                }
+            }),
+        )
+    };
+
+    {
+        let show_modal = show_modal.clone();
+        let search_ref = search_ref.clone();
+        use_keyboard_shortcuts(vec![
+            ("n", Callback::from(move |_| show_modal.set(true))),
+            ("/", Callback::from(move |_| {
+                if let Some(input) = search_ref.cast::<web_sys::HtmlInputElement>() {
+                    let _ = input.focus();
+                }
+            })),
+        ]);
+    }
+
+    let on_search_input = {
+        let search = search.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(target) = e.target_dyn_into::<web_sys::HtmlInputElement>() {
+                search.set(target.value());
+            }
+        })
+    };
+
     let on_open_modal = {
         let show_modal = show_modal.clone();
         Callback::from(move |_| {
@@ -284,6 +363,20 @@ pub fn transactions() -> Html {
             </div>
 
             <div class="flex gap-4 mb-4">
+                <div class="form-control">
+                    <label class="label">
+                        <span class="label-text">{"Search"}</span>
+                    </label>
+                    <input
+                        type="search"
+                        class="input input-bordered input-sm"
+                        placeholder="Name or description  ( / )"
+                        ref={search_ref}
+                        oninput={on_search_input}
+                        value={(*search).clone()}
+                    />
+                </div>
+
                 <div class="form-control">
                     <label class="label">
                         <span class="label-text">{"Filter by Month"}</span>
@@ -360,32 +453,14 @@ pub fn transactions() -> Html {
                             </button>
                         </div>
                     },
-                    FetchState::Success(transactions) => {
-                        if transactions.is_empty() {
+                    FetchState::Success(_) => {
+                        if visible_transactions.is_empty() {
                             html! {
                                 <div class="text-center py-8">
                                     <p class="text-gray-500">{"No transactions found."}</p>
                                 </div>
                             }
                         } else {
-                            let mut sorted_transactions = transactions.clone();
-                            sorted_transactions.sort_by(|a, b| {
-                                let cmp = match *sort_column {
-                                    SortColumn::Date => a.date.cmp(&b.date),
-                                    SortColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-                                    SortColumn::Account => {
-                                        let a_name = account_map.get(&a.target_account_id).map(|s| s.as_str()).unwrap_or("");
-                                        let b_name = account_map.get(&b.target_account_id).map(|s| s.as_str()).unwrap_or("");
-                                        a_name.to_lowercase().cmp(&b_name.to_lowercase())
-                                    },
-                                    SortColumn::Amount => a.amount.cmp(&b.amount),
-                                };
-                                match *sort_direction {
-                                    SortDirection::Ascending => cmp,
-                                    SortDirection::Descending => cmp.reverse(),
-                                }
-                            });
-
                             let current_sort_column = *sort_column;
                             let current_sort_direction = *sort_direction;
 
@@ -404,8 +479,8 @@ pub fn transactions() -> Html {
                                             </tr>
                                         </thead>
                                         <tbody>
-                                            { for sorted_transactions.iter().map(|transaction| {
-                                                render_transaction_row(transaction, &account_map, &category_map)
+                                            { for visible_transactions.iter().enumerate().map(|(index, transaction)| {
+                                                render_transaction_row(transaction, &account_map, &category_map, selected_row == Some(index))
                                             })}
                                         </tbody>
                                     </table>
@@ -420,20 +495,10 @@ pub fn transactions() -> Html {
             // Add pagination
             {match &*fetch_state {
                 FetchState::Success(transactions) if !transactions.is_empty() => {
-                    // Estimate total items based on page and items retrieved
-                    let items_on_page = transactions.len() as u64;
-                    let estimated_total = if items_on_page < items_per_page {
-                        // Last page
-                        (*current_page - 1) * items_per_page + items_on_page
-                    } else {
-                        // Assume there might be more pages
-                        (*current_page) * items_per_page + 1
-                    };
-
                     html! {
                         <Pagination
                             current_page={*current_page}
-                            total_items={estimated_total}
+                            total_items={*total_items}
                             items_per_page={items_per_page}
                             on_page_change={on_page_change.clone()}
                         />
@@ -480,7 +545,7 @@ fn render_sortable_header(
     }
 }
 
-fn render_transaction_row(transaction: &TransactionResponse, account_map: &HashMap<i32, String>, category_map: &HashMap<i32, String>) -> Html {
+fn render_transaction_row(transaction: &TransactionResponse, account_map: &HashMap<i32, String>, category_map: &HashMap<i32, String>, selected: bool) -> Html {
     let amount_class = if transaction.amount >= rust_decimal::Decimal::ZERO {
         "text-success"
     } else {
@@ -543,7 +608,7 @@ fn render_transaction_row(transaction: &TransactionResponse, account_map: &HashM
     };
 
     html! {
-        <tr key={transaction.id} class="cursor-pointer">
+        <tr key={transaction.id} class={classes!("cursor-pointer", selected.then_some("bg-base-300"))}>
             <Link<Route> to={Route::TransactionEdit { id: transaction_id }} classes="contents">
                 <td>
                     <div class="font-semibold">{transaction.date.format("%Y-%m-%d").to_string()}</div>
@@ -1,3 +1,7 @@
+use std::future::Future;
+use yew::prelude::*;
+use crate::common::toast::ToastContext;
+
 /// API fetch state enum
 #[derive(Clone, PartialEq)]
 pub enum FetchState<T> {
@@ -53,3 +57,73 @@ impl<T> FetchState<T> {
 //
 // use_effect_with((), |_| { refetch.emit(()); || () });
 // ```
+
+/// A change to one item of a fetched list, shown before the server confirms it.
+pub struct OptimisticUpdate<T, R> {
+    /// The item as it will look once the mutation succeeds
+    pub optimistic: T,
+    /// Request sent to the server
+    pub request: R,
+    /// Toast shown once the server accepted the change
+    pub success_message: String,
+}
+
+/// Mutation hook with optimistic updates for a list loaded by `use_fetch_with_refetch`.
+///
+/// Emitting an [`OptimisticUpdate`] swaps the item (matched by `id_of`) into `state`
+/// immediately and runs `mutate(id, request)` in the background. On success the item is
+/// replaced by the server's version, without refetching the list; on error the list is
+/// rolled back to what it was before and an error toast is shown.
+#[hook]
+pub fn use_optimistic_update<T, R, F, Fut>(
+    state: UseStateHandle<FetchState<Vec<T>>>,
+    id_of: fn(&T) -> i32,
+    mutate: F,
+) -> Callback<OptimisticUpdate<T, R>>
+where
+    T: Clone + 'static,
+    R: 'static,
+    F: Fn(i32, R) -> Fut + 'static,
+    Fut: Future<Output = Result<T, String>> + 'static,
+{
+    let toast_ctx = use_context::<ToastContext>().expect("ToastContext not found");
+
+    // Rebuilt on every render so it always starts from the list currently shown
+    Callback::from(move |update: OptimisticUpdate<T, R>| {
+        let FetchState::Success(items) = &*state else {
+            log::warn!("Ignoring optimistic update while the list is not loaded");
+            return;
+        };
+
+        let id = id_of(&update.optimistic);
+        let previous = items.clone();
+        let with_item = move |item: T| -> Vec<T> {
+            previous
+                .iter()
+                .map(|existing| if id_of(existing) == id { item.clone() } else { existing.clone() })
+                .collect()
+        };
+
+        log::debug!("Applying optimistic update to item {}", id);
+        state.set(FetchState::Success(with_item(update.optimistic)));
+
+        let state = state.clone();
+        let toast_ctx = toast_ctx.clone();
+        let success_message = update.success_message;
+        let rollback = items.clone();
+        let mutation = mutate(id, update.request);
+        wasm_bindgen_futures::spawn_local(async move {
+            match mutation.await {
+                Ok(saved) => {
+                    state.set(FetchState::Success(with_item(saved)));
+                    toast_ctx.show_success(success_message);
+                }
+                Err(e) => {
+                    log::error!("Mutation of item {} failed, rolling back: {}", id, e);
+                    state.set(FetchState::Success(rollback));
+                    toast_ctx.show_error(format!("Change reverted: {}", e));
+                }
+            }
+        });
+    })
+}
@@ -11,21 +11,28 @@ pub mod formatting;
 pub mod hooks;
 pub mod common;
 pub mod settings;
+pub mod theme;
 
 use common::toast::ToastProvider;
+use components::error_boundary::ErrorBoundary;
 use formatting::CurrencyProvider;
 use router::{Route, switch};
+use theme::ThemeProvider;
 
 #[function_component(App)]
 pub fn app() -> Html {
     html! {
-        <ToastProvider>
-            <CurrencyProvider>
-                <BrowserRouter>
-                    <Switch<Route> render={switch} />
-                </BrowserRouter>
-            </CurrencyProvider>
-        </ToastProvider>
+        <ErrorBoundary>
+            <ToastProvider>
+                <ThemeProvider>
+                    <CurrencyProvider>
+                        <BrowserRouter>
+                            <Switch<Route> render={switch} />
+                        </BrowserRouter>
+                    </CurrencyProvider>
+                </ThemeProvider>
+            </ToastProvider>
+        </ErrorBoundary>
     }
 }
 
@@ -10,3 +10,4 @@ pub mod instances;
 pub mod categories;
 pub mod scenarios;
 pub mod scenario_detail;
+pub mod spending_map;
@@ -2,14 +2,15 @@ use yew::prelude::*;
 use yew_router::prelude::*;
 use crate::components::layout::layout::Layout;
 use crate::router::Route;
-use crate::api_client::recurring_transaction::{get_recurring_transaction, get_recurring_instances, delete_recurring_transaction, delete_recurring_instance, update_recurring_instance, UpdateRecurringInstanceRequest};
+use crate::api_client::recurring_transaction::{get_recurring_transaction, get_recurring_instances, delete_recurring_transaction, delete_recurring_instance, update_recurring_instance, approve_recurring_transaction, reject_recurring_transaction, RecurringInstanceResponse};
 use crate::api_client::category::get_categories;
 use crate::api_client::scenario::get_scenarios;
 use crate::common::fetch_hook::use_fetch_with_refetch;
 use crate::common::toast::ToastContext;
 use crate::formatting::{fmt_amount_str, use_currency};
-use crate::hooks::FetchState;
+use crate::hooks::{FetchState, OptimisticUpdate, use_optimistic_update};
 use crate::components::instances::instance_edit_modal::InstanceEditModal;
+use crate::components::instances::status::{status_change, INSTANCE_STATUSES};
 use std::collections::HashMap;
 
 #[derive(Properties, PartialEq)]
@@ -34,6 +35,12 @@ pub fn recurring_detail_page(props: &RecurringDetailPageProps) -> Html {
         get_recurring_instances(None, None, Some(id), None)
     });
 
+    let update_status = use_optimistic_update(
+        instances_state.clone(),
+        |instance: &RecurringInstanceResponse| instance.id,
+        update_recurring_instance,
+    );
+
     // Fetch categories
     let (categories_state, _) = use_fetch_with_refetch(get_categories);
     let (scenarios_state, _) = use_fetch_with_refetch(get_scenarios);
@@ -56,7 +63,7 @@ pub fn recurring_detail_page(props: &RecurringDetailPageProps) -> Html {
         _ => HashMap::new(),
     };
 
-    let edit_instance = use_state(|| None::<crate::api_client::recurring_transaction::RecurringInstanceResponse>);
+    let edit_instance = use_state(|| None::<RecurringInstanceResponse>);
     let show_edit_modal = use_state(|| false);
 
     let on_edit_recurring = {
@@ -91,6 +98,33 @@ pub fn recurring_detail_page(props: &RecurringDetailPageProps) -> Html {
         })
     };
 
+    let on_review = {
+        let toast_ctx = toast_ctx.clone();
+        let transaction_refetch = transaction_refetch.clone();
+        Callback::from(move |approve: bool| {
+            let toast_ctx = toast_ctx.clone();
+            let transaction_refetch = transaction_refetch.clone();
+
+            wasm_bindgen_futures::spawn_local(async move {
+                let result = if approve {
+                    approve_recurring_transaction(id).await
+                } else {
+                    reject_recurring_transaction(id).await
+                };
+                match result {
+                    Ok(_) => {
+                        let verb = if approve { "approved" } else { "rejected" };
+                        toast_ctx.show_success(format!("Recurring transaction {}", verb));
+                        transaction_refetch.emit(());
+                    }
+                    Err(e) => {
+                        toast_ctx.show_error(format!("Failed to review recurring transaction: {}", e));
+                    }
+                }
+            });
+        })
+    };
+
     let on_edit_instance = {
         let edit_instance = edit_instance.clone();
         let show_edit_modal = show_edit_modal.clone();
@@ -175,6 +209,30 @@ pub fn recurring_detail_page(props: &RecurringDetailPageProps) -> Html {
                                 html! { <></> }
                             }}
 
+                            {match transaction.approval_status.as_str() {
+                                "PendingApproval" => {
+                                    let on_approve = on_review.reform(|_: MouseEvent| true);
+                                    let on_reject = on_review.reform(|_: MouseEvent| false);
+                                    html! {
+                                        <div class="alert alert-warning mt-2">
+                                            <i class="fas fa-user-check"></i>
+                                            <span>{"Awaiting approval by another member of the account. It is left out of forecasts until approved."}</span>
+                                            <div class="flex gap-2">
+                                                <button class="btn btn-sm btn-success" onclick={on_approve}>{"Approve"}</button>
+                                                <button class="btn btn-sm btn-error btn-outline" onclick={on_reject}>{"Reject"}</button>
+                                            </div>
+                                        </div>
+                                    }
+                                }
+                                "Rejected" => html! {
+                                    <div class="alert alert-error mt-2">
+                                        <i class="fas fa-ban"></i>
+                                        <span>{"Rejected by another member of the account. It is left out of forecasts."}</span>
+                                    </div>
+                                },
+                                _ => html! { <></> },
+                            }}
+
                             <div class="divider"></div>
 
                             <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
@@ -429,13 +487,8 @@ pub fn recurring_detail_page(props: &RecurringDetailPageProps) -> Html {
                                                 })
                                             };
 
-                                            let instance_id = instance.id;
                                             let current_status = instance.status.clone();
-                                            let status_refetch = instances_refetch.clone();
-                                            let status_toast_ctx = toast_ctx.clone();
-                                            let instance_paid_date = instance.paid_date.clone();
-                                            let instance_paid_amount = instance.paid_amount.clone();
-                                            let instance_expected_amount = instance.expected_amount.clone();
+                                            let status_instance = instance.clone();
 
                                             html! {
                                                 <tr>
@@ -482,59 +535,17 @@ pub fn recurring_detail_page(props: &RecurringDetailPageProps) -> Html {
                                                                 </button>
                                                                 <ul tabindex="0" class="dropdown-content menu p-2 shadow bg-base-100 rounded-box w-40 z-50">
                                                                     {
-                                                                        vec!["Pending", "Paid", "Skipped"].into_iter().map(|status| {
-                                                                            let id = instance_id;
+                                                                        INSTANCE_STATUSES.into_iter().map(|status| {
                                                                             let is_current = status == current_status.as_str();
                                                                             let on_status_click = {
-                                                                                let status = status.to_string();
-                                                                                let instances_refetch = status_refetch.clone();
-                                                                                let toast_ctx = status_toast_ctx.clone();
-                                                                                let paid_date = instance_paid_date.clone();
-                                                                                let paid_amount = instance_paid_amount.clone();
-                                                                                let expected_amount = instance_expected_amount.clone();
+                                                                                let instance = status_instance.clone();
+                                                                                let update_status = update_status.clone();
                                                                                 Callback::from(move |_: MouseEvent| {
-                                                                                    let id = id;
-                                                                                    let status = status.clone();
-                                                                                    let instances_refetch = instances_refetch.clone();
-                                                                                    let toast_ctx = toast_ctx.clone();
-                                                                                    let paid_date = paid_date.clone();
-                                                                                    let paid_amount = paid_amount.clone();
-                                                                                    let expected_amount = expected_amount.clone();
-
-                                                                                    wasm_bindgen_futures::spawn_local(async move {
-                                                                                        // If changing to Paid status, ensure paid_date and paid_amount are set
-                                                                                        let (final_paid_date, final_paid_amount) = if status == "Paid" {
-                                                                                            let pd = if paid_date.is_none() {
-                                                                                                Some(chrono::Local::now().format("%Y-%m-%d").to_string())
-                                                                                            } else {
-                                                                                                paid_date
-                                                                                            };
-                                                                                            let pa = if paid_amount.is_none() {
-                                                                                                Some(expected_amount)
-                                                                                            } else {
-                                                                                                paid_amount
-                                                                                            };
-                                                                                            (pd, pa)
-                                                                                        } else {
-                                                                                            (None, None)
-                                                                                        };
-
-                                                                                        let request = UpdateRecurringInstanceRequest {
-                                                                                            status: Some(status.clone()),
-                                                                                            due_date: None,
-                                                                                            expected_amount: None,
-                                                                                            paid_date: final_paid_date,
-                                                                                            paid_amount: final_paid_amount,
-                                                                                        };
-                                                                                        match update_recurring_instance(id, request).await {
-                                                                                            Ok(_) => {
-                                                                                                toast_ctx.show_success(format!("Status changed to {}", status));
-                                                                                                instances_refetch.emit(());
-                                                                                            }
-                                                                                            Err(e) => {
-                                                                                                toast_ctx.show_error(format!("Failed to update status: {}", e));
-                                                                                            }
-                                                                                        }
+                                                                                    let (optimistic, request) = status_change(&instance, status);
+                                                                                    update_status.emit(OptimisticUpdate {
+                                                                                        optimistic,
+                                                                                        request,
+                                                                                        success_message: format!("Status changed to {}", status),
                                                                                     });
                                                                                 })
                                                                             };
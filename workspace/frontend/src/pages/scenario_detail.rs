@@ -5,7 +5,7 @@ use crate::components::transactions::TransactionModal;
 use crate::components::recurring::RecurringModal;
 use crate::components::scenarios::ScenarioModal;
 use crate::router::Route;
-use crate::api_client::scenario::{get_scenario, delete_scenario, apply_scenario};
+use crate::api_client::scenario::{get_scenario, delete_scenario, apply_scenario, clone_scenario, CloneScenarioRequest};
 use crate::api_client::account::{get_accounts_with_ignored, AccountResponse};
 use crate::api_client::transaction::{get_transactions, TransactionFilters, TransactionResponse};
 use crate::api_client::recurring_transaction::{get_recurring_transactions, RecurringTransactionResponse};
@@ -104,6 +104,24 @@ pub fn scenario_detail_page(props: &ScenarioDetailPageProps) -> Html {
         })
     };
 
+    let on_clone_click = {
+        let toast_ctx = toast_ctx.clone();
+        let navigator = navigator.clone();
+        Callback::from(move |_| {
+            let toast_ctx = toast_ctx.clone();
+            let navigator = navigator.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match clone_scenario(id, CloneScenarioRequest { name: None }).await {
+                    Ok(copy) => {
+                        toast_ctx.show_success(format!("Scenario cloned as {}", copy.name));
+                        navigator.push(&Route::ScenarioDetail { id: copy.id });
+                    }
+                    Err(e) => toast_ctx.show_error(format!("Failed to clone scenario: {}", e)),
+                }
+            });
+        })
+    };
+
     let on_apply_click = {
         let show_apply_modal = show_apply_modal.clone();
         Callback::from(move |_| show_apply_modal.set(true))
@@ -230,6 +248,9 @@ pub fn scenario_detail_page(props: &ScenarioDetailPageProps) -> Html {
                                         <button class="btn btn-sm btn-ghost" onclick={on_edit_click}>
                                             <i class="fas fa-edit"></i>{" Edit"}
                                         </button>
+                                        <button class="btn btn-sm btn-ghost" onclick={on_clone_click}>
+                                            <i class="fas fa-copy"></i>{" Clone"}
+                                        </button>
                                         <button class="btn btn-sm btn-error btn-outline" onclick={on_delete_click}>
                                             <i class="fas fa-trash"></i>{" Delete"}
                                         </button>
@@ -0,0 +1,23 @@
+use yew::prelude::*;
+
+use crate::components::layout::layout::Layout;
+use crate::components::spending_map::SpendingMap;
+
+#[function_component(SpendingMapPage)]
+pub fn spending_map_page() -> Html {
+    let refresh_trigger = use_state(|| 0);
+
+    let on_refresh = {
+        let refresh_trigger = refresh_trigger.clone();
+        Callback::from(move |_| {
+            log::debug!("Spending map page refresh triggered");
+            refresh_trigger.set(*refresh_trigger + 1);
+        })
+    };
+
+    html! {
+        <Layout title="Spending Map" on_refresh={Some(on_refresh)}>
+            <SpendingMap key={*refresh_trigger} />
+        </Layout>
+    }
+}
@@ -5,6 +5,7 @@ use crate::components::accounts::AccountEdit;
 use crate::components::budgets::Budgets;
 use crate::components::forecast::Forecast;
 use crate::components::layout::layout::Layout;
+use crate::components::print::{PrintBudgetStatus, PrintMonthlyReport};
 use crate::components::reports::Reports;
 use crate::components::settings::Settings;
 use crate::pages::accounts::AccountsPage;
@@ -19,6 +20,7 @@ use crate::pages::instances::InstancesPage;
 use crate::pages::categories::CategoriesPage;
 use crate::pages::scenarios::ScenariosPage;
 use crate::pages::scenario_detail::ScenarioDetailPage;
+use crate::pages::spending_map::SpendingMapPage;
 
 #[derive(Debug, Clone, Routable, PartialEq)]
 pub enum Route {
@@ -56,6 +58,12 @@ pub enum Route {
     Forecast,
     #[at("/reports")]
     Reports,
+    #[at("/print/reports/:year/:month")]
+    PrintMonthlyReport { year: i32, month: u32 },
+    #[at("/print/budgets/:year/:month")]
+    PrintBudgetStatus { year: i32, month: u32 },
+    #[at("/spending-map")]
+    SpendingMap,
     #[at("/settings")]
     Settings,
     #[at("/about")]
@@ -114,7 +122,7 @@ pub fn switch(routes: Route) -> Html {
         }
         Route::ScenarioDetail { id } => {
             log::trace!("Rendering Scenario Detail page for ID: {}", id);
-            html! { <ScenarioDetailPage id={id} /> }
+            html! { <ScenarioDetailPage key={id} id={id} /> }
         }
         Route::AiPrompt => {
             log::trace!("Rendering AI Prompt page");
@@ -132,6 +140,18 @@ pub fn switch(routes: Route) -> Html {
             log::trace!("Rendering Reports page");
             html! { <Layout title="Reports"><Reports /></Layout> }
         }
+        Route::PrintMonthlyReport { year, month } => {
+            log::trace!("Rendering printable monthly report for {}-{:02}", year, month);
+            html! { <PrintMonthlyReport key={format!("{}-{}", year, month)} year={year} month={month} /> }
+        }
+        Route::PrintBudgetStatus { year, month } => {
+            log::trace!("Rendering printable budget status for {}-{:02}", year, month);
+            html! { <PrintBudgetStatus key={format!("{}-{}", year, month)} year={year} month={month} /> }
+        }
+        Route::SpendingMap => {
+            log::trace!("Rendering Spending Map page");
+            html! { <SpendingMapPage /> }
+        }
         Route::Settings => {
             log::trace!("Rendering Settings page");
             html! { <Layout title="Settings"><Settings /></Layout> }
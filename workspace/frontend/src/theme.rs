@@ -0,0 +1,208 @@
+use crate::api_client::user::{get_user_settings, update_user_settings, UserSettings};
+use crate::common::toast::ToastContext;
+use wasm_bindgen::prelude::*;
+use web_sys::window;
+use yew::prelude::*;
+
+#[wasm_bindgen(inline_js = "
+function dark_query() {
+    return window.matchMedia ? window.matchMedia('(prefers-color-scheme: dark)') : null;
+}
+export function system_prefers_dark() {
+    const query = dark_query();
+    return query !== null && query.matches;
+}
+export function watch_system_theme(callback) {
+    const query = dark_query();
+    if (query !== null) {
+        query.addEventListener('change', callback);
+    }
+}
+export function unwatch_system_theme(callback) {
+    const query = dark_query();
+    if (query !== null) {
+        query.removeEventListener('change', callback);
+    }
+}
+")]
+extern "C" {
+    fn system_prefers_dark() -> bool;
+    fn watch_system_theme(callback: &Closure<dyn Fn()>);
+    fn unwatch_system_theme(callback: &Closure<dyn Fn()>);
+}
+
+/// localStorage key caching the preference, so the theme is right before the settings API answers.
+/// `index.html` reads the same key to avoid a flash of the wrong theme on load.
+const STORAGE_KEY: &str = "theme";
+
+// TODO: Get from user context
+const CURRENT_USER_ID: i32 = 1;
+
+/// The theme the user picked in the navbar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemePreference {
+    Light,
+    Dark,
+    /// Follow the operating system's light/dark setting, including live changes.
+    System,
+}
+
+impl ThemePreference {
+    pub const ALL: [ThemePreference; 3] = [ThemePreference::Light, ThemePreference::Dark, ThemePreference::System];
+
+    /// Value stored in the user settings and localStorage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThemePreference::Light => "light",
+            ThemePreference::Dark => "dark",
+            ThemePreference::System => "system",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|theme| theme.as_str() == value)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ThemePreference::Light => "Light",
+            ThemePreference::Dark => "Dark",
+            ThemePreference::System => "System",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            ThemePreference::Light => "fas fa-sun",
+            ThemePreference::Dark => "fas fa-moon",
+            ThemePreference::System => "fas fa-desktop",
+        }
+    }
+
+    /// DaisyUI theme to put on `<html data-theme>` right now.
+    pub fn resolve(&self) -> &'static str {
+        match self {
+            ThemePreference::Light => "light",
+            ThemePreference::Dark => "dark",
+            ThemePreference::System if system_prefers_dark() => "dark",
+            ThemePreference::System => "light",
+        }
+    }
+}
+
+fn apply(preference: ThemePreference) {
+    let theme = preference.resolve();
+    log::trace!("Applying theme '{}' for preference {:?}", theme, preference);
+    if let Some(html) = window().and_then(|w| w.document()).and_then(|d| d.document_element()) {
+        let _ = html.set_attribute("data-theme", theme);
+    }
+}
+
+fn load_cached() -> ThemePreference {
+    window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten())
+        .and_then(|saved| ThemePreference::parse(&saved))
+        .unwrap_or(ThemePreference::System)
+}
+
+fn cache(preference: ThemePreference) {
+    if let Some(storage) = window().and_then(|w| w.local_storage().ok().flatten()) {
+        let _ = storage.set_item(STORAGE_KEY, preference.as_str());
+    }
+}
+
+/// Yew context exposing the current theme preference and a way to change it.
+#[derive(Clone, PartialEq)]
+pub struct ThemeContext {
+    pub preference: ThemePreference,
+    pub set_preference: Callback<ThemePreference>,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ThemeProviderProps {
+    pub children: Children,
+}
+
+/// Applies the theme to the document and keeps it in sync with the user settings.
+///
+/// The cached preference is applied immediately; the account's stored theme replaces
+/// it once loaded, so the theme follows the user across devices. Changes are saved
+/// back through the settings API.
+#[function_component(ThemeProvider)]
+pub fn theme_provider(props: &ThemeProviderProps) -> Html {
+    let preference = use_state(load_cached);
+    let toast_ctx = use_context::<ToastContext>().expect("ToastContext not found");
+
+    use_effect_with(*preference, |preference| {
+        let preference = *preference;
+        apply(preference);
+        cache(preference);
+
+        let listener = (preference == ThemePreference::System).then(|| {
+            let listener = Closure::<dyn Fn()>::new(|| apply(ThemePreference::System));
+            watch_system_theme(&listener);
+            listener
+        });
+
+        move || {
+            if let Some(listener) = listener {
+                unwatch_system_theme(&listener);
+            }
+        }
+    });
+
+    {
+        let preference = preference.clone();
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                match get_user_settings(CURRENT_USER_ID).await {
+                    Ok(settings) => {
+                        if let Some(stored) = settings.theme.as_deref().and_then(ThemePreference::parse) {
+                            log::debug!("Using stored theme preference {:?}", stored);
+                            preference.set(stored);
+                        }
+                    }
+                    Err(e) => log::warn!("Could not load theme preference, keeping the cached one: {}", e),
+                }
+            });
+            || ()
+        });
+    }
+
+    let set_preference = {
+        let preference = preference.clone();
+        Callback::from(move |selected: ThemePreference| {
+            preference.set(selected);
+
+            let toast_ctx = toast_ctx.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                // The settings endpoint replaces every setting, so keep the others as stored
+                let saved = match get_user_settings(CURRENT_USER_ID).await {
+                    Ok(settings) => {
+                        let settings = UserSettings {
+                            theme: Some(selected.as_str().to_string()),
+                            ..settings
+                        };
+                        update_user_settings(CURRENT_USER_ID, settings).await
+                    }
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = saved {
+                    toast_ctx.show_warning(format!("Theme changed on this device only: {}", e));
+                }
+            });
+        })
+    };
+
+    let ctx = ThemeContext {
+        preference: *preference,
+        set_preference,
+    };
+
+    html! {
+        <ContextProvider<ThemeContext> context={ctx}>
+            { for props.children.iter() }
+        </ContextProvider<ThemeContext>>
+    }
+}
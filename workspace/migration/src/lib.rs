@@ -10,6 +10,46 @@ mod m20251215_000001_add_scenarios;
 mod m20251216_000001_add_goal_fields;
 mod m20260315_000001_add_account_color;
 mod m20260316_000001_add_account_types;
+mod m20261015_000001_add_reconciliation_sessions;
+mod m20261015_000002_add_locked_periods;
+mod m20261015_000003_add_merchants;
+mod m20261015_000004_add_transaction_coordinates;
+mod m20261015_000005_add_imported_fx_fields;
+mod m20261015_000006_add_transaction_legs;
+mod m20261015_000007_add_user_active_scenario;
+mod m20261015_000008_add_scenario_activation_date;
+mod m20261015_000009_add_user_theme;
+mod m20261015_000010_add_diagnostic_reports;
+mod m20261015_000011_add_recurring_approval;
+mod m20261015_000012_add_tag_monthly_limit;
+mod m20261015_000013_add_audit_entries;
+mod m20261015_000014_add_exchange_rates;
+mod m20261015_000015_add_budgets;
+mod m20261015_000016_add_forecast_snapshots;
+mod m20261015_000017_add_account_notes_and_documents;
+mod m20261015_000018_add_recurring_contract_terms;
+mod m20261015_000019_add_authentication;
+mod m20261015_000020_create_api_tokens;
+mod m20261015_000021_add_user_timezone;
+mod m20261015_000022_create_account_shares;
+mod m20261015_000023_add_recurring_fx;
+mod m20261015_000024_add_one_off_status;
+mod m20261015_000025_add_account_balance_interpolation;
+mod m20261015_000026_add_transaction_currency_override;
+mod m20261015_000027_add_account_statistics_exclusions;
+mod m20261015_000028_add_recurring_instance_disputes;
+mod m20261015_000029_add_assets;
+mod m20261015_000030_add_recurring_instance_overdue;
+mod m20261015_000031_add_purchase_warranties;
+mod m20261015_000032_add_recurring_amount_strategy;
+mod m20261015_000033_add_recurring_rrule;
+mod m20261015_000034_add_recurring_date_adjustment;
+mod m20261015_000035_add_holidays;
+mod m20261015_000036_add_payment_alerts;
+mod m20261015_000037_add_user_admin;
+mod m20261015_000038_add_book_snapshots;
+mod m20261015_000039_add_notification_channel_owner;
+mod m20261015_000040_add_field_encryption_salt;
 
 pub struct Migrator;
 
@@ -26,6 +66,46 @@ impl MigratorTrait for Migrator {
             Box::new(m20251216_000001_add_goal_fields::Migration),
             Box::new(m20260315_000001_add_account_color::Migration),
             Box::new(m20260316_000001_add_account_types::Migration),
+            Box::new(m20261015_000001_add_reconciliation_sessions::Migration),
+            Box::new(m20261015_000002_add_locked_periods::Migration),
+            Box::new(m20261015_000003_add_merchants::Migration),
+            Box::new(m20261015_000004_add_transaction_coordinates::Migration),
+            Box::new(m20261015_000005_add_imported_fx_fields::Migration),
+            Box::new(m20261015_000006_add_transaction_legs::Migration),
+            Box::new(m20261015_000007_add_user_active_scenario::Migration),
+            Box::new(m20261015_000008_add_scenario_activation_date::Migration),
+            Box::new(m20261015_000009_add_user_theme::Migration),
+            Box::new(m20261015_000010_add_diagnostic_reports::Migration),
+            Box::new(m20261015_000011_add_recurring_approval::Migration),
+            Box::new(m20261015_000012_add_tag_monthly_limit::Migration),
+            Box::new(m20261015_000013_add_audit_entries::Migration),
+            Box::new(m20261015_000014_add_exchange_rates::Migration),
+            Box::new(m20261015_000015_add_budgets::Migration),
+            Box::new(m20261015_000016_add_forecast_snapshots::Migration),
+            Box::new(m20261015_000017_add_account_notes_and_documents::Migration),
+            Box::new(m20261015_000018_add_recurring_contract_terms::Migration),
+            Box::new(m20261015_000019_add_authentication::Migration),
+            Box::new(m20261015_000020_create_api_tokens::Migration),
+            Box::new(m20261015_000021_add_user_timezone::Migration),
+            Box::new(m20261015_000022_create_account_shares::Migration),
+            Box::new(m20261015_000023_add_recurring_fx::Migration),
+            Box::new(m20261015_000024_add_one_off_status::Migration),
+            Box::new(m20261015_000025_add_account_balance_interpolation::Migration),
+            Box::new(m20261015_000026_add_transaction_currency_override::Migration),
+            Box::new(m20261015_000027_add_account_statistics_exclusions::Migration),
+            Box::new(m20261015_000028_add_recurring_instance_disputes::Migration),
+            Box::new(m20261015_000029_add_assets::Migration),
+            Box::new(m20261015_000030_add_recurring_instance_overdue::Migration),
+            Box::new(m20261015_000031_add_purchase_warranties::Migration),
+            Box::new(m20261015_000032_add_recurring_amount_strategy::Migration),
+            Box::new(m20261015_000033_add_recurring_rrule::Migration),
+            Box::new(m20261015_000034_add_recurring_date_adjustment::Migration),
+            Box::new(m20261015_000035_add_holidays::Migration),
+            Box::new(m20261015_000036_add_payment_alerts::Migration),
+            Box::new(m20261015_000037_add_user_admin::Migration),
+            Box::new(m20261015_000038_add_book_snapshots::Migration),
+            Box::new(m20261015_000039_add_notification_channel_owner::Migration),
+            Box::new(m20261015_000040_add_field_encryption_salt::Migration),
         ]
     }
 }
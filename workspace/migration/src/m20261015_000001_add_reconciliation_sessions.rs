@@ -0,0 +1,125 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 1. Create reconciliation_sessions table
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReconciliationSession::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ReconciliationSession::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ReconciliationSession::AccountId).integer().not_null())
+                    .col(ColumnDef::new(ReconciliationSession::StatementDate).date().not_null())
+                    .col(
+                        ColumnDef::new(ReconciliationSession::StatementBalance)
+                            .decimal_len(16, 4)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReconciliationSession::OpeningBalance)
+                            .decimal_len(16, 4)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ReconciliationSession::Status)
+                            .string_len(15)
+                            .not_null()
+                            .default("Open"),
+                    )
+                    .col(
+                        ColumnDef::new(ReconciliationSession::CreatedAt)
+                            .date_time()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(ReconciliationSession::CompletedAt).date_time())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-reconciliation-session-account")
+                            .from(ReconciliationSession::Table, ReconciliationSession::AccountId)
+                            .to(Alias::new("accounts"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 2. Add reconciliation tracking to one_off_transactions
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("one_off_transactions"))
+                    .add_column(ColumnDef::new(Alias::new("reconciliation_session_id")).integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("one_off_transactions"))
+                    .add_column(
+                        ColumnDef::new(Alias::new("is_locked"))
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("one_off_transactions"))
+                    .drop_column(Alias::new("is_locked"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("one_off_transactions"))
+                    .drop_column(Alias::new("reconciliation_session_id"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ReconciliationSession::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ReconciliationSession {
+    #[sea_orm(iden = "reconciliation_sessions")]
+    Table,
+    Id,
+    AccountId,
+    StatementDate,
+    StatementBalance,
+    OpeningBalance,
+    Status,
+    CreatedAt,
+    CompletedAt,
+}
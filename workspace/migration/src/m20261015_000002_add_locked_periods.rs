@@ -0,0 +1,51 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(LockedPeriod::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LockedPeriod::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(LockedPeriod::StartDate).date().not_null())
+                    .col(ColumnDef::new(LockedPeriod::EndDate).date().not_null())
+                    .col(ColumnDef::new(LockedPeriod::Note).text())
+                    .col(
+                        ColumnDef::new(LockedPeriod::LockedAt)
+                            .date_time()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LockedPeriod::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum LockedPeriod {
+    #[sea_orm(iden = "locked_periods")]
+    Table,
+    Id,
+    StartDate,
+    EndDate,
+    Note,
+    LockedAt,
+}
@@ -0,0 +1,46 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Merchant::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Merchant::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Merchant::Pattern).string().not_null().unique_key())
+                    .col(ColumnDef::new(Merchant::Name).string().not_null())
+                    .col(ColumnDef::new(Merchant::LogoUrl).string())
+                    .col(ColumnDef::new(Merchant::Website).string())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Merchant::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Merchant {
+    #[sea_orm(iden = "merchants")]
+    Table,
+    Id,
+    Pattern,
+    Name,
+    LogoUrl,
+    Website,
+}
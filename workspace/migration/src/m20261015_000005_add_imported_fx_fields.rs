@@ -0,0 +1,53 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("imported_transactions"))
+                    .add_column(ColumnDef::new(Alias::new("original_amount")).decimal_len(16, 4))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("imported_transactions"))
+                    .add_column(ColumnDef::new(Alias::new("original_currency")).string_len(3))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("imported_transactions"))
+                    .add_column(ColumnDef::new(Alias::new("fx_fee")).decimal_len(16, 4))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in ["fx_fee", "original_currency", "original_amount"] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("imported_transactions"))
+                        .drop_column(Alias::new(column))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
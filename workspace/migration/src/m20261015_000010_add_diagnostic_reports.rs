@@ -0,0 +1,55 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DiagnosticReport::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DiagnosticReport::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(DiagnosticReport::ReceivedAt)
+                            .date_time()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(DiagnosticReport::Message).text().not_null())
+                    .col(ColumnDef::new(DiagnosticReport::Route).string())
+                    .col(ColumnDef::new(DiagnosticReport::AppVersion).string())
+                    .col(ColumnDef::new(DiagnosticReport::UserAgent).string())
+                    .col(ColumnDef::new(DiagnosticReport::ApiCalls).text().not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DiagnosticReport::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum DiagnosticReport {
+    #[sea_orm(iden = "diagnostic_reports")]
+    Table,
+    Id,
+    ReceivedAt,
+    Message,
+    Route,
+    AppVersion,
+    UserAgent,
+    ApiCalls,
+}
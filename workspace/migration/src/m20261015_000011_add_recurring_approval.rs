@@ -0,0 +1,78 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Shared accounts can require approval for large recurring transactions
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("accounts"))
+                    .add_column(ColumnDef::new(Alias::new("recurring_approval_threshold")).decimal())
+                    .to_owned(),
+            )
+            .await?;
+
+        // SQLite only supports one column per ALTER TABLE statement
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("recurring_transactions"))
+                    .add_column(
+                        ColumnDef::new(Alias::new("approval_status"))
+                            .string_len(16)
+                            .not_null()
+                            .default("Approved"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("recurring_transactions"))
+                    .add_column(ColumnDef::new(Alias::new("created_by_user_id")).integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("recurring_transactions"))
+                    .add_column(ColumnDef::new(Alias::new("reviewed_by_user_id")).integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in ["reviewed_by_user_id", "created_by_user_id", "approval_status"] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("recurring_transactions"))
+                        .drop_column(Alias::new(column))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("accounts"))
+                    .drop_column(Alias::new("recurring_approval_threshold"))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuditEntry::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AuditEntry::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AuditEntry::RecordedAt).date_time().not_null())
+                    .col(ColumnDef::new(AuditEntry::Method).string_len(8).not_null())
+                    .col(ColumnDef::new(AuditEntry::Path).string().not_null())
+                    .col(ColumnDef::new(AuditEntry::Status).integer().not_null())
+                    .col(ColumnDef::new(AuditEntry::Payload).text().not_null())
+                    .col(ColumnDef::new(AuditEntry::PreviousHash).string_len(64).not_null())
+                    .col(ColumnDef::new(AuditEntry::Hash).string_len(64).not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AuditEntry::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AuditEntry {
+    #[sea_orm(iden = "audit_entries")]
+    Table,
+    Id,
+    RecordedAt,
+    Method,
+    Path,
+    Status,
+    Payload,
+    PreviousHash,
+    Hash,
+}
@@ -0,0 +1,54 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ExchangeRate::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ExchangeRate::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ExchangeRate::BaseCurrency).string_len(3).not_null())
+                    .col(ColumnDef::new(ExchangeRate::QuoteCurrency).string_len(3).not_null())
+                    .col(ColumnDef::new(ExchangeRate::Date).date().not_null())
+                    .col(ColumnDef::new(ExchangeRate::Rate).decimal_len(16, 8).not_null())
+                    .index(
+                        Index::create()
+                            .name("idx_exchange_rates_pair_date")
+                            .col(ExchangeRate::BaseCurrency)
+                            .col(ExchangeRate::QuoteCurrency)
+                            .col(ExchangeRate::Date)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ExchangeRate::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ExchangeRate {
+    #[sea_orm(iden = "exchange_rates")]
+    Table,
+    Id,
+    BaseCurrency,
+    QuoteCurrency,
+    Date,
+    Rate,
+}
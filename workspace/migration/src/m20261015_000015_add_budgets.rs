@@ -0,0 +1,56 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Budget::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Budget::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Budget::CategoryId).integer().not_null())
+                    .col(ColumnDef::new(Budget::Period).string_len(16).not_null())
+                    .col(ColumnDef::new(Budget::Amount).decimal_len(16, 4).not_null())
+                    .col(ColumnDef::new(Budget::Rollover).boolean().not_null().default(false))
+                    .col(ColumnDef::new(Budget::StartDate).date().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-budget-category")
+                            .from(Budget::Table, Budget::CategoryId)
+                            .to(Alias::new("categories"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Budget::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Budget {
+    #[sea_orm(iden = "budgets")]
+    Table,
+    Id,
+    CategoryId,
+    Period,
+    Amount,
+    Rollover,
+    StartDate,
+}
@@ -0,0 +1,64 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ForecastSnapshot::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ForecastSnapshot::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ForecastSnapshot::AccountId).integer().not_null())
+                    .col(ColumnDef::new(ForecastSnapshot::MadeOn).date().not_null())
+                    .col(ColumnDef::new(ForecastSnapshot::HorizonDays).integer().not_null())
+                    .col(ColumnDef::new(ForecastSnapshot::TargetDate).date().not_null())
+                    .col(ColumnDef::new(ForecastSnapshot::ProjectedBalance).decimal_len(16, 4).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-forecast-snapshot-account")
+                            .from(ForecastSnapshot::Table, ForecastSnapshot::AccountId)
+                            .to(Alias::new("accounts"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_forecast_snapshots_account_day_horizon")
+                            .col(ForecastSnapshot::AccountId)
+                            .col(ForecastSnapshot::MadeOn)
+                            .col(ForecastSnapshot::HorizonDays)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ForecastSnapshot::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ForecastSnapshot {
+    #[sea_orm(iden = "forecast_snapshots")]
+    Table,
+    Id,
+    AccountId,
+    MadeOn,
+    HorizonDays,
+    TargetDate,
+    ProjectedBalance,
+}
@@ -0,0 +1,121 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AccountNote::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AccountNote::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AccountNote::AccountId).integer().not_null())
+                    .col(ColumnDef::new(AccountNote::Title).string().not_null())
+                    .col(ColumnDef::new(AccountNote::Body).text().not_null())
+                    .col(ColumnDef::new(AccountNote::SecretCiphertext).text())
+                    .col(
+                        ColumnDef::new(AccountNote::CreatedAt)
+                            .date_time()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(AccountNote::UpdatedAt)
+                            .date_time()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-account-note-account")
+                            .from(AccountNote::Table, AccountNote::AccountId)
+                            .to(Alias::new("accounts"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(AccountDocument::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AccountDocument::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AccountDocument::AccountId).integer().not_null())
+                    .col(ColumnDef::new(AccountDocument::FileName).string().not_null())
+                    .col(ColumnDef::new(AccountDocument::ContentType).string().not_null())
+                    .col(ColumnDef::new(AccountDocument::SizeBytes).big_integer().not_null())
+                    .col(ColumnDef::new(AccountDocument::Sha256).string_len(64).not_null())
+                    .col(ColumnDef::new(AccountDocument::Content).blob().not_null())
+                    .col(
+                        ColumnDef::new(AccountDocument::UploadedAt)
+                            .date_time()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-account-document-account")
+                            .from(AccountDocument::Table, AccountDocument::AccountId)
+                            .to(Alias::new("accounts"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AccountDocument::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(AccountNote::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AccountNote {
+    #[sea_orm(iden = "account_notes")]
+    Table,
+    Id,
+    AccountId,
+    Title,
+    Body,
+    SecretCiphertext,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum AccountDocument {
+    #[sea_orm(iden = "account_documents")]
+    Table,
+    Id,
+    AccountId,
+    FileName,
+    ContentType,
+    SizeBytes,
+    Sha256,
+    Content,
+    UploadedAt,
+}
@@ -0,0 +1,65 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AccountShare::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(AccountShare::AccountId).integer().not_null())
+                    .col(ColumnDef::new(AccountShare::UserId).integer().not_null())
+                    .col(ColumnDef::new(AccountShare::Permission).string_len(16).not_null())
+                    .col(
+                        ColumnDef::new(AccountShare::CreatedAt)
+                            .date_time()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .name("pk-account-shares")
+                            .col(AccountShare::AccountId)
+                            .col(AccountShare::UserId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-account-share-account")
+                            .from(AccountShare::Table, AccountShare::AccountId)
+                            .to(Alias::new("accounts"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-account-share-user")
+                            .from(AccountShare::Table, AccountShare::UserId)
+                            .to(Alias::new("users"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AccountShare::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AccountShare {
+    #[sea_orm(iden = "account_shares")]
+    Table,
+    AccountId,
+    UserId,
+    Permission,
+    CreatedAt,
+}
@@ -0,0 +1,54 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only supports one column per ALTER TABLE statement
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("recurring_transactions"))
+                    .add_column(ColumnDef::new(Alias::new("fx_mode")).string_len(16))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("recurring_transactions"))
+                    .add_column(ColumnDef::new(Alias::new("fx_rate")).decimal_len(16, 8))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("recurring_transactions"))
+                    .add_column(ColumnDef::new(Alias::new("source_amount")).decimal_len(16, 4))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in ["source_amount", "fx_rate", "fx_mode"] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("recurring_transactions"))
+                        .drop_column(Alias::new(column))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Planned one-off transactions only count towards forecasts until confirmed
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("one_off_transactions"))
+                    .add_column(
+                        ColumnDef::new(Alias::new("status"))
+                            .string_len(16)
+                            .not_null()
+                            .default("Confirmed"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("one_off_transactions"))
+                    .drop_column(Alias::new("status"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
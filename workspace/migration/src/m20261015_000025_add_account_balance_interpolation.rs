@@ -0,0 +1,35 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // How balances between two manual account states are drawn
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("accounts"))
+                    .add_column(
+                        ColumnDef::new(Alias::new("balance_interpolation"))
+                            .string_len(16)
+                            .not_null()
+                            .default("Step"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("accounts"))
+                    .drop_column(Alias::new("balance_interpolation"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
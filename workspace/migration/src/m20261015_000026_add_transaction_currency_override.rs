@@ -0,0 +1,54 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only supports one column per ALTER TABLE statement
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("one_off_transactions"))
+                    .add_column(ColumnDef::new(Alias::new("original_amount")).decimal_len(16, 4))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("one_off_transactions"))
+                    .add_column(ColumnDef::new(Alias::new("original_currency")).string_len(3))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("one_off_transactions"))
+                    .add_column(ColumnDef::new(Alias::new("conversion_rate")).decimal_len(16, 8))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in ["conversion_rate", "original_currency", "original_amount"] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("one_off_transactions"))
+                        .drop_column(Alias::new(column))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
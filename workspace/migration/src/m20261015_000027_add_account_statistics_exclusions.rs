@@ -0,0 +1,54 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AccountStatisticsExclusion::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AccountStatisticsExclusion::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AccountStatisticsExclusion::AccountId).integer().not_null())
+                    .col(ColumnDef::new(AccountStatisticsExclusion::StartDate).date().not_null())
+                    .col(ColumnDef::new(AccountStatisticsExclusion::EndDate).date().not_null())
+                    .col(ColumnDef::new(AccountStatisticsExclusion::Reason).string())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-account-statistics-exclusion-account")
+                            .from(AccountStatisticsExclusion::Table, AccountStatisticsExclusion::AccountId)
+                            .to(Alias::new("accounts"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AccountStatisticsExclusion::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AccountStatisticsExclusion {
+    #[sea_orm(iden = "account_statistics_exclusions")]
+    Table,
+    Id,
+    AccountId,
+    StartDate,
+    EndDate,
+    Reason,
+}
@@ -0,0 +1,51 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only supports one column per ALTER TABLE statement
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("recurring_transaction_instances"))
+                    .add_column(ColumnDef::new(Alias::new("notes")).text())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Contested charges stay out of paid totals until the dispute is settled
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("recurring_transaction_instances"))
+                    .add_column(
+                        ColumnDef::new(Alias::new("disputed"))
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in ["disputed", "notes"] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("recurring_transaction_instances"))
+                        .drop_column(Alias::new(column))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
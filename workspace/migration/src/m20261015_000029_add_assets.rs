@@ -0,0 +1,88 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Asset::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Asset::Id).integer().not_null().auto_increment().primary_key())
+                    .col(ColumnDef::new(Asset::Name).string().not_null())
+                    .col(ColumnDef::new(Asset::Description).string())
+                    .col(ColumnDef::new(Asset::AccountId).integer().not_null())
+                    .col(ColumnDef::new(Asset::PurchaseDate).date().not_null())
+                    .col(ColumnDef::new(Asset::PurchasePrice).decimal_len(16, 4).not_null())
+                    .col(
+                        ColumnDef::new(Asset::SalvageValue)
+                            .decimal_len(16, 4)
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(Asset::DepreciationMethod)
+                            .string_len(16)
+                            .not_null()
+                            .default("StraightLine"),
+                    )
+                    .col(ColumnDef::new(Asset::UsefulLifeMonths).integer().not_null())
+                    .col(ColumnDef::new(Asset::PurchaseTransactionId).integer())
+                    .col(ColumnDef::new(Asset::DisposalDate).date())
+                    .col(ColumnDef::new(Asset::SalePrice).decimal_len(16, 4))
+                    .col(ColumnDef::new(Asset::DisposalTransactionId).integer())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-asset-account")
+                            .from(Asset::Table, Asset::AccountId)
+                            .to(Alias::new("accounts"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-asset-purchase-transaction")
+                            .from(Asset::Table, Asset::PurchaseTransactionId)
+                            .to(Alias::new("one_off_transactions"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-asset-disposal-transaction")
+                            .from(Asset::Table, Asset::DisposalTransactionId)
+                            .to(Alias::new("one_off_transactions"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(Asset::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Asset {
+    #[sea_orm(iden = "assets")]
+    Table,
+    Id,
+    Name,
+    Description,
+    AccountId,
+    PurchaseDate,
+    PurchasePrice,
+    SalvageValue,
+    DepreciationMethod,
+    UsefulLifeMonths,
+    PurchaseTransactionId,
+    DisposalDate,
+    SalePrice,
+    DisposalTransactionId,
+}
@@ -0,0 +1,35 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Set by the instance generation job on pending instances past their due date
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("recurring_transaction_instances"))
+                    .add_column(
+                        ColumnDef::new(Alias::new("overdue"))
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("recurring_transaction_instances"))
+                    .drop_column(Alias::new("overdue"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,50 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("recurring_transactions"))
+                    .add_column(
+                        ColumnDef::new(Alias::new("amount_strategy"))
+                            .string_len(16)
+                            .not_null()
+                            .default("Fixed"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Number of paid instances averaged by the AverageOfLastN strategy
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alias::new("recurring_transactions"))
+                    .add_column(ColumnDef::new(Alias::new("amount_history_count")).integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in ["amount_history_count", "amount_strategy"] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new("recurring_transactions"))
+                        .drop_column(Alias::new(column))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
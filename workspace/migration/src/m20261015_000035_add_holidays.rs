@@ -0,0 +1,75 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Holiday::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Holiday::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Holiday::Date).date().not_null().unique_key())
+                    .col(ColumnDef::new(Holiday::Name).string().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(HolidayCalendar::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(HolidayCalendar::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(HolidayCalendar::CountryCode)
+                            .string_len(2)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(HolidayCalendar::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Holiday::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Holiday {
+    #[sea_orm(iden = "holidays")]
+    Table,
+    Id,
+    Date,
+    Name,
+}
+
+#[derive(DeriveIden)]
+enum HolidayCalendar {
+    #[sea_orm(iden = "holiday_calendars")]
+    Table,
+    Id,
+    CountryCode,
+}
@@ -0,0 +1,174 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(NotificationChannel::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(NotificationChannel::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(NotificationChannel::Name).string().not_null())
+                    .col(ColumnDef::new(NotificationChannel::Kind).string_len(16).not_null())
+                    .col(ColumnDef::new(NotificationChannel::Target).string().not_null())
+                    .col(
+                        ColumnDef::new(NotificationChannel::Enabled)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationChannel::CreatedAt)
+                            .date_time()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(RecurringAlert::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RecurringAlert::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(RecurringAlert::RecurringTransactionId)
+                            .integer()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(RecurringAlert::DaysBefore).integer().null())
+                    .col(
+                        ColumnDef::new(RecurringAlert::NotifyOverdue)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(RecurringAlert::ChannelId).integer().null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-recurring-alert-recurring-transaction")
+                            .from(RecurringAlert::Table, RecurringAlert::RecurringTransactionId)
+                            .to(Alias::new("recurring_transactions"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-recurring-alert-channel")
+                            .from(RecurringAlert::Table, RecurringAlert::ChannelId)
+                            .to(NotificationChannel::Table, NotificationChannel::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PaymentAlert::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PaymentAlert::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(PaymentAlert::RecurringTransactionInstanceId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PaymentAlert::Kind).string_len(16).not_null())
+                    .col(
+                        ColumnDef::new(PaymentAlert::SentAt)
+                            .date_time()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-payment-alert-instance")
+                            .from(PaymentAlert::Table, PaymentAlert::RecurringTransactionInstanceId)
+                            .to(Alias::new("recurring_transaction_instances"), Alias::new("id"))
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx_payment_alerts_instance_kind")
+                            .col(PaymentAlert::RecurringTransactionInstanceId)
+                            .col(PaymentAlert::Kind)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PaymentAlert::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(RecurringAlert::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(NotificationChannel::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum NotificationChannel {
+    #[sea_orm(iden = "notification_channels")]
+    Table,
+    Id,
+    Name,
+    Kind,
+    Target,
+    Enabled,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum RecurringAlert {
+    #[sea_orm(iden = "recurring_alerts")]
+    Table,
+    Id,
+    RecurringTransactionId,
+    DaysBefore,
+    NotifyOverdue,
+    ChannelId,
+}
+
+#[derive(DeriveIden)]
+enum PaymentAlert {
+    #[sea_orm(iden = "payment_alerts")]
+    Table,
+    Id,
+    RecurringTransactionInstanceId,
+    Kind,
+    SentAt,
+}
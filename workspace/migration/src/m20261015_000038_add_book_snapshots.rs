@@ -0,0 +1,44 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(BookSnapshot::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(BookSnapshot::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(BookSnapshot::TakenAt).date_time().not_null())
+                    .col(ColumnDef::new(BookSnapshot::Sha256).string_len(64).not_null())
+                    .col(ColumnDef::new(BookSnapshot::Archive).blob().not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BookSnapshot::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum BookSnapshot {
+    #[sea_orm(iden = "book_snapshots")]
+    Table,
+    Id,
+    TakenAt,
+    Sha256,
+    Archive,
+}
@@ -0,0 +1,42 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(FieldEncryptionSalt::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(FieldEncryptionSalt::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(FieldEncryptionSalt::Salt).blob().not_null())
+                    .col(ColumnDef::new(FieldEncryptionSalt::CreatedAt).date_time().not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(FieldEncryptionSalt::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum FieldEncryptionSalt {
+    #[sea_orm(iden = "field_encryption_salts")]
+    Table,
+    Id,
+    Salt,
+    CreatedAt,
+}
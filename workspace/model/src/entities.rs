@@ -5,13 +5,34 @@
 
 pub mod account;
 pub mod account_allowed_user;
+pub mod account_document;
+pub mod account_note;
+pub mod account_share;
+pub mod account_statistics_exclusion;
 pub mod account_tag;
+pub mod api_token;
+pub mod asset;
+pub mod audit_entry;
+pub mod book_snapshot;
+pub mod budget;
 pub mod category;
+pub mod diagnostic_report;
+pub mod exchange_rate;
+pub mod field_encryption_salt;
+pub mod forecast_snapshot;
+pub mod holiday;
+pub mod holiday_calendar;
 pub mod imported_transaction;
 pub mod imported_transaction_tag;
+pub mod locked_period;
 pub mod manual_account_state;
+pub mod merchant;
+pub mod notification_channel;
 pub mod one_off_transaction;
 pub mod one_off_transaction_tag;
+pub mod payment_alert;
+pub mod reconciliation_session;
+pub mod recurring_alert;
 pub mod recurring_income;
 pub mod recurring_income_tag;
 pub mod recurring_transaction;
@@ -20,6 +41,7 @@ pub mod recurring_transaction_tag;
 pub mod scenario;
 pub mod tag;
 pub mod user;
+pub mod user_session;
 
 // Implementation modules for the Transaction trait are part of their respective entity modules
 
@@ -29,13 +51,34 @@ pub mod prelude {
     //! A prelude module for easy importing of all entities.
     pub use super::account::Entity as Account;
     pub use super::account_allowed_user::Entity as AccountAllowedUser;
+    pub use super::account_document::Entity as AccountDocument;
+    pub use super::account_note::Entity as AccountNote;
+    pub use super::account_share::Entity as AccountShare;
+    pub use super::account_statistics_exclusion::Entity as AccountStatisticsExclusion;
     pub use super::account_tag::Entity as AccountTag;
+    pub use super::api_token::Entity as ApiToken;
+    pub use super::asset::Entity as Asset;
+    pub use super::audit_entry::Entity as AuditEntry;
+    pub use super::book_snapshot::Entity as BookSnapshot;
+    pub use super::budget::Entity as Budget;
     pub use super::category::Entity as Category;
+    pub use super::diagnostic_report::Entity as DiagnosticReport;
+    pub use super::exchange_rate::Entity as ExchangeRate;
+    pub use super::field_encryption_salt::Entity as FieldEncryptionSalt;
+    pub use super::forecast_snapshot::Entity as ForecastSnapshot;
+    pub use super::holiday::Entity as Holiday;
+    pub use super::holiday_calendar::Entity as HolidayCalendar;
     pub use super::imported_transaction::Entity as ImportedTransaction;
     pub use super::imported_transaction_tag::Entity as ImportedTransactionTag;
+    pub use super::locked_period::Entity as LockedPeriod;
     pub use super::manual_account_state::Entity as ManualAccountState;
+    pub use super::merchant::Entity as Merchant;
+    pub use super::notification_channel::Entity as NotificationChannel;
     pub use super::one_off_transaction::Entity as OneOffTransaction;
     pub use super::one_off_transaction_tag::Entity as OneOffTransactionTag;
+    pub use super::payment_alert::Entity as PaymentAlert;
+    pub use super::reconciliation_session::Entity as ReconciliationSession;
+    pub use super::recurring_alert::Entity as RecurringAlert;
     pub use super::recurring_income::Entity as RecurringIncome;
     pub use super::recurring_income_tag::Entity as RecurringIncomeTag;
     pub use super::recurring_transaction::Entity as RecurringTransaction;
@@ -44,6 +87,7 @@ pub mod prelude {
     pub use super::scenario::Entity as Scenario;
     pub use super::tag::Entity as Tag;
     pub use super::user::Entity as User;
+    pub use super::user_session::Entity as UserSession;
 }
 
 #[cfg(test)]
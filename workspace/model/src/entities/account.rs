@@ -36,6 +36,41 @@ impl AccountKind {
     pub fn default_is_liquid(&self) -> bool {
         !matches!(self, AccountKind::Equity | AccountKind::House)
     }
+
+    /// Savings-goal accounts are meaningless without a target amount.
+    pub fn requires_target_amount(&self) -> bool {
+        matches!(self, AccountKind::Goal)
+    }
+
+    /// Envelope-style accounts that only earmark money held elsewhere and
+    /// therefore never appear on a bank statement.
+    pub fn is_virtual(&self) -> bool {
+        matches!(self, AccountKind::Allowance)
+    }
+
+    /// Accounts tracking money owed, whose balance should stay at or below zero.
+    pub fn is_liability(&self) -> bool {
+        matches!(self, AccountKind::Debt | AccountKind::Tax)
+    }
+
+    /// Whether balances of this kind add to the household net worth. Virtual accounts
+    /// are left out, their money is already counted in the account that holds it.
+    pub fn counts_towards_net_worth(&self) -> bool {
+        !self.is_virtual()
+    }
+}
+
+/// How the balance between two manual account states is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum BalanceInterpolation {
+    /// The balance stays at the last state until the next one
+    #[sea_orm(string_value = "Step")]
+    Step,
+    /// The balance moves in a straight line from one state to the next, for accounts
+    /// like investments that are only snapshotted now and then
+    #[sea_orm(string_value = "Linear")]
+    Linear,
 }
 
 /// Represents a financial account, like a bank account, credit card, or cash wallet.
@@ -67,6 +102,12 @@ pub struct Model {
     /// Whether this account is considered liquid (cash-like, quickly convertible)
     #[sea_orm(default_value = "true")]
     pub is_liquid: bool,
+    /// On shared accounts, new recurring transactions whose absolute amount reaches this
+    /// threshold need approval by another member before they count in forecasts.
+    pub recurring_approval_threshold: Option<Decimal>,
+    /// How balances between manual states are drawn where no transactions were booked
+    #[sea_orm(default_value = "Step")]
+    pub balance_interpolation: BalanceInterpolation,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -84,8 +125,17 @@ pub enum Relation {
     /// Relation for the many-to-many relationship for allowed users.
     #[sea_orm(has_many = "super::account_allowed_user::Entity")]
     AccountAllowedUser,
+    /// Users the account is shared with and what they may do.
+    #[sea_orm(has_many = "super::account_share::Entity")]
+    AccountShare,
     #[sea_orm(has_many = "super::manual_account_state::Entity")]
     ManualAccountState,
+    /// Date ranges left out of statistics and budgets.
+    #[sea_orm(has_many = "super::account_statistics_exclusion::Entity")]
+    AccountStatisticsExclusion,
+    /// Household items bought from the account.
+    #[sea_orm(has_many = "super::asset::Entity")]
+    Asset,
 }
 
 impl Related<tag::Entity> for Entity {
@@ -0,0 +1,40 @@
+use sea_orm::entity::prelude::*;
+
+use super::account;
+
+/// File attached to an account, such as a contract PDF or a statement.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "account_documents")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub account_id: i32,
+    pub file_name: String,
+    /// MIME type the file is served with on download.
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// Hex encoded SHA-256 of `content`.
+    pub sha256: String,
+    #[sea_orm(column_type = "Blob")]
+    pub content: Vec<u8>,
+    pub uploaded_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "account::Entity",
+        from = "Column::AccountId",
+        to = "account::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Account,
+}
+
+impl Related<account::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Account.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,42 @@
+use sea_orm::entity::prelude::*;
+
+use super::account;
+
+/// Long-form note kept with an account, e.g. contract terms or contact details.
+///
+/// Sensitive values such as account numbers go into `secret_ciphertext`, which is
+/// only ever stored encrypted.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "account_notes")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub account_id: i32,
+    pub title: String,
+    #[sea_orm(column_type = "Text")]
+    pub body: String,
+    /// Encrypted secret, base64 of the nonce followed by the ciphertext.
+    #[sea_orm(column_type = "Text", nullable)]
+    pub secret_ciphertext: Option<String>,
+    pub created_at: DateTime,
+    pub updated_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "account::Entity",
+        from = "Column::AccountId",
+        to = "account::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Account,
+}
+
+impl Related<account::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Account.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
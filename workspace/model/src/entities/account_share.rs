@@ -0,0 +1,62 @@
+use sea_orm::entity::prelude::*;
+
+use super::{account, user};
+
+/// What a user an account is shared with may do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum SharePermission {
+    /// See the account, its balances and everything booked on it.
+    #[sea_orm(string_value = "Read")]
+    Read,
+    /// Additionally book, change and delete transactions and balances on it.
+    #[sea_orm(string_value = "Write")]
+    Write,
+}
+
+/// Grants a user other than the owner access to an account, e.g. a partner
+/// maintaining the household accounts together.
+///
+/// Only the owner may change, delete or share the account itself.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "account_shares")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub account_id: i32,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_id: i32,
+    pub permission: SharePermission,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "account::Entity",
+        from = "Column::AccountId",
+        to = "account::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Account,
+    #[sea_orm(
+        belongs_to = "user::Entity",
+        from = "Column::UserId",
+        to = "user::Column::Id",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<account::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Account.def()
+    }
+}
+
+impl Related<user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
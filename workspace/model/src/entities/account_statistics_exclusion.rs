@@ -0,0 +1,38 @@
+use chrono::NaiveDate;
+use sea_orm::entity::prelude::*;
+
+use super::account;
+
+/// Days of an account left out of statistics and budgets, e.g. the months an account
+/// was used for a one-off house purchase. Balances themselves are not affected.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "account_statistics_exclusions")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub account_id: i32,
+    /// First excluded day (inclusive)
+    pub start_date: NaiveDate,
+    /// Last excluded day (inclusive)
+    pub end_date: NaiveDate,
+    pub reason: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "account::Entity",
+        from = "Column::AccountId",
+        to = "account::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Account,
+}
+
+impl Related<account::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Account.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
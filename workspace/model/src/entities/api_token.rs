@@ -0,0 +1,45 @@
+use sea_orm::entity::prelude::*;
+
+use super::user;
+
+/// Long-lived bearer token for scripts and other programmatic API access.
+///
+/// Like sessions, only the SHA-256 hash of the token is stored; the prefix is kept in
+/// clear text so users can tell their tokens apart.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "api_tokens")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub user_id: i32,
+    /// What the token is used for, e.g. "import cron job".
+    pub name: String,
+    /// Hex-encoded SHA-256 of the bearer token.
+    #[sea_orm(unique)]
+    pub token_hash: String,
+    /// First characters of the token, for recognizing it in listings.
+    pub token_prefix: String,
+    pub created_at: DateTime,
+    pub last_used_at: Option<DateTime>,
+    /// `None` for tokens that never expire.
+    pub expires_at: Option<DateTime>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "user::Entity",
+        from = "Column::UserId",
+        to = "user::Column::Id",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,85 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sea_orm::entity::prelude::*;
+
+use super::{account, one_off_transaction};
+
+/// How an asset loses value over its useful life.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum DepreciationMethod {
+    /// Keeps its purchase price, e.g. jewellery or art
+    #[sea_orm(string_value = "None")]
+    None,
+    /// Loses the same amount every month until the salvage value is reached
+    #[sea_orm(string_value = "StraightLine")]
+    StraightLine,
+    /// Loses twice the straight-line rate of its remaining value every month, so most of
+    /// the value goes early, as with cars and electronics
+    #[sea_orm(string_value = "DoubleDeclining")]
+    DoubleDeclining,
+}
+
+/// A household item of lasting value, e.g. a car, furniture or a laptop.
+///
+/// Assets add their depreciated value to the net worth from the purchase date until they
+/// are disposed of. Selling one books the proceeds as a one-off transaction on the account.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "assets")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    /// The account the asset was paid from; it decides who owns and may see the asset.
+    pub account_id: i32,
+    pub purchase_date: NaiveDate,
+    #[sea_orm(column_type = "Decimal(Some((16, 4)))")]
+    pub purchase_price: Decimal,
+    /// Value the asset keeps at the end of its useful life.
+    #[sea_orm(column_type = "Decimal(Some((16, 4)))")]
+    pub salvage_value: Decimal,
+    pub depreciation_method: DepreciationMethod,
+    pub useful_life_months: i32,
+    /// The transaction the asset was bought with.
+    pub purchase_transaction_id: Option<i32>,
+    /// Day the asset was sold or thrown away; it has no value from then on.
+    pub disposal_date: Option<NaiveDate>,
+    #[sea_orm(column_type = "Decimal(Some((16, 4)))", nullable)]
+    pub sale_price: Option<Decimal>,
+    /// The transaction the sale proceeds were booked with.
+    pub disposal_transaction_id: Option<i32>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "account::Entity",
+        from = "Column::AccountId",
+        to = "account::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Account,
+    #[sea_orm(
+        belongs_to = "one_off_transaction::Entity",
+        from = "Column::PurchaseTransactionId",
+        to = "one_off_transaction::Column::Id",
+        on_delete = "SetNull"
+    )]
+    PurchaseTransaction,
+    #[sea_orm(
+        belongs_to = "one_off_transaction::Entity",
+        from = "Column::DisposalTransactionId",
+        to = "one_off_transaction::Column::Id",
+        on_delete = "SetNull"
+    )]
+    DisposalTransaction,
+}
+
+impl Related<account::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Account.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
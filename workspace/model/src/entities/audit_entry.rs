@@ -0,0 +1,32 @@
+use sea_orm::entity::prelude::*;
+
+/// One successful mutating API request, recorded when the audit chain is enabled.
+///
+/// Entries form an append-only hash chain: `hash` covers the entry's fields and
+/// `previous_hash`, the hash of the entry before it. Editing or deleting any entry
+/// breaks every hash after it, which the verification endpoint detects.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "audit_entries")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// UTC time the request completed, truncated to microseconds.
+    pub recorded_at: DateTime,
+    pub method: String,
+    /// Request path including the query string.
+    pub path: String,
+    /// HTTP status code of the response.
+    pub status: i32,
+    /// Request body as received.
+    #[sea_orm(column_type = "Text")]
+    pub payload: String,
+    /// Hex SHA-256 of the previous entry; all zeros for the first one.
+    pub previous_hash: String,
+    /// Hex SHA-256 of this entry.
+    pub hash: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,22 @@
+use sea_orm::entity::prelude::*;
+
+/// Full copy of the books taken at `taken_at`, used to answer reads as of a past time.
+///
+/// `archive` is a full export in JSON format. A new snapshot is only stored when the
+/// data changed since the previous one.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "book_snapshots")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub taken_at: DateTime,
+    /// Hex encoded SHA-256 of the exported rows, to skip snapshots without changes.
+    pub sha256: String,
+    #[sea_orm(column_type = "Blob")]
+    pub archive: Vec<u8>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
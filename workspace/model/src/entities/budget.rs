@@ -0,0 +1,72 @@
+use chrono::{Datelike, NaiveDate};
+use sea_orm::entity::prelude::*;
+
+use super::category;
+
+/// Length of a budget period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum BudgetPeriod {
+    #[sea_orm(string_value = "Monthly")]
+    Monthly,
+    #[sea_orm(string_value = "Quarterly")]
+    Quarterly,
+    #[sea_orm(string_value = "Yearly")]
+    Yearly,
+}
+
+impl BudgetPeriod {
+    /// First and last day of the period containing `date`.
+    pub fn bounds(&self, date: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let (first_month, months) = match self {
+            BudgetPeriod::Monthly => (date.month(), 1),
+            BudgetPeriod::Quarterly => ((date.month() - 1) / 3 * 3 + 1, 3),
+            BudgetPeriod::Yearly => (1, 12),
+        };
+        let start = NaiveDate::from_ymd_opt(date.year(), first_month, 1).expect("valid first day of period");
+        let end = start
+            .checked_add_months(chrono::Months::new(months))
+            .and_then(|next| next.pred_opt())
+            .expect("valid last day of period");
+        (start, end)
+    }
+}
+
+/// Spending limit for a category over a repeating period.
+///
+/// Expenses of the category and all its subcategories count against the limit.
+/// With `rollover`, whatever was left unspent (or overspent) in earlier periods
+/// since `start_date` is added to (or taken from) the current period.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "budgets")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub category_id: i32,
+    pub period: BudgetPeriod,
+    /// Spending limit per period, as a positive amount.
+    #[sea_orm(column_type = "Decimal(Some((16, 4)))")]
+    pub amount: Decimal,
+    pub rollover: bool,
+    /// The budget applies from the period containing this date.
+    pub start_date: NaiveDate,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "category::Entity",
+        from = "Column::CategoryId",
+        to = "category::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Category,
+}
+
+impl Related<category::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Category.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,29 @@
+use sea_orm::entity::prelude::*;
+
+/// Diagnostic report submitted by the frontend after it crashed.
+///
+/// Kept so a failure the user ran into can be investigated later; reports are
+/// not linked to any other data.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "diagnostic_reports")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub received_at: DateTime,
+    /// Panic or error message as reported by the client.
+    #[sea_orm(column_type = "Text")]
+    pub message: String,
+    /// Frontend route the user was on, e.g. "/transactions".
+    pub route: Option<String>,
+    /// Version of the frontend build that crashed.
+    pub app_version: Option<String>,
+    pub user_agent: Option<String>,
+    /// JSON array of the most recent API calls made by the client.
+    #[sea_orm(column_type = "Text")]
+    pub api_calls: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,74 @@
+use chrono::NaiveDate;
+use sea_orm::entity::prelude::*;
+use sea_orm::QueryOrder;
+
+/// Historical exchange rate between two currencies.
+///
+/// One unit of `base_currency` was worth `rate` units of `quote_currency` on
+/// `date`. There is at most one rate per currency pair and day.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "exchange_rates")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// ISO 4217 code of the currency being priced, e.g. "EUR".
+    pub base_currency: String,
+    /// ISO 4217 code of the currency the price is expressed in, e.g. "CZK".
+    pub quote_currency: String,
+    pub date: NaiveDate,
+    #[sea_orm(column_type = "Decimal(Some((16, 8)))")]
+    pub rate: Decimal,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl Model {
+    /// Converts an amount of `base_currency` into `quote_currency`.
+    pub fn convert(&self, amount: Decimal) -> Decimal {
+        amount * self.rate
+    }
+}
+
+async fn latest_stored_rate(
+    db: &DatabaseConnection,
+    base: &str,
+    quote: &str,
+    date: NaiveDate,
+) -> Result<Option<Model>, DbErr> {
+    Entity::find()
+        .filter(Column::BaseCurrency.eq(base))
+        .filter(Column::QuoteCurrency.eq(quote))
+        .filter(Column::Date.lte(date))
+        .order_by_desc(Column::Date)
+        .one(db)
+        .await
+}
+
+/// Rate converting one unit of `base` into `quote` on `date`.
+///
+/// Uses the latest stored rate on or before `date` (rates aren't published on
+/// weekends and holidays), falling back to the inverse of the opposite pair when
+/// only that one is stored. Returns `None` when no rate is known.
+pub async fn rate_on(
+    db: &DatabaseConnection,
+    base: &str,
+    quote: &str,
+    date: NaiveDate,
+) -> Result<Option<Decimal>, DbErr> {
+    let base = base.trim().to_uppercase();
+    let quote = quote.trim().to_uppercase();
+    if base == quote {
+        return Ok(Some(Decimal::ONE));
+    }
+
+    if let Some(rate) = latest_stored_rate(db, &base, &quote, date).await? {
+        return Ok(Some(rate.rate));
+    }
+    Ok(latest_stored_rate(db, &quote, &base, date)
+        .await?
+        .filter(|inverse| !inverse.rate.is_zero())
+        .map(|inverse| Decimal::ONE / inverse.rate))
+}
+
+impl ActiveModelBehavior for ActiveModel {}
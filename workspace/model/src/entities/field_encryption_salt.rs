@@ -0,0 +1,19 @@
+use sea_orm::entity::prelude::*;
+
+/// Salt the field encryption key is derived from the passphrase with.
+///
+/// Created on the first start with field encryption enabled; only the first row is used.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "field_encryption_salts")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    #[sea_orm(column_type = "Blob")]
+    pub salt: Vec<u8>,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
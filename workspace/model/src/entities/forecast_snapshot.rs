@@ -0,0 +1,42 @@
+use chrono::NaiveDate;
+use sea_orm::entity::prelude::*;
+
+use super::account;
+
+/// Balance of an account projected on `made_on` for `target_date`.
+///
+/// Kept so that projections can later be compared with the balance the account
+/// actually reached. There is at most one snapshot per account, day and horizon.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "forecast_snapshots")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub account_id: i32,
+    /// Day the projection was made.
+    pub made_on: NaiveDate,
+    /// Days between `made_on` and `target_date`.
+    pub horizon_days: i32,
+    pub target_date: NaiveDate,
+    #[sea_orm(column_type = "Decimal(Some((16, 4)))")]
+    pub projected_balance: Decimal,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "account::Entity",
+        from = "Column::AccountId",
+        to = "account::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Account,
+}
+
+impl Related<account::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Account.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
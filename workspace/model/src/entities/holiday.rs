@@ -0,0 +1,21 @@
+use chrono::NaiveDate;
+use sea_orm::entity::prelude::*;
+
+/// A custom non-business day, such as a regional holiday or a bank closure.
+///
+/// Complements the built-in country calendars enabled in `holiday_calendars`. Work-day
+/// recurrences skip these dates.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "holidays")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    #[sea_orm(unique)]
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
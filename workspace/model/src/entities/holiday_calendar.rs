@@ -0,0 +1,19 @@
+use sea_orm::entity::prelude::*;
+
+/// A built-in country holiday calendar that is enabled.
+///
+/// Public holidays of every enabled country count as non-business days.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "holiday_calendars")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// ISO 3166-1 alpha-2 code of the country, e.g. `CZ`.
+    #[sea_orm(unique)]
+    pub country_code: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -77,6 +77,15 @@ pub struct Model {
 
     /// The category of the transaction.
     pub category_id: Option<i32>,
+
+    /// The amount in the currency the payment was made in, for foreign currency payments.
+    #[sea_orm(column_type = "Decimal(Some((16, 4)))", nullable)]
+    pub original_amount: Option<Decimal>,
+    /// ISO code of the currency the payment was made in.
+    pub original_currency: Option<String>,
+    /// Currency conversion fee implied by the import, in the account currency (positive).
+    #[sea_orm(column_type = "Decimal(Some((16, 4)))", nullable)]
+    pub fx_fee: Option<Decimal>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -137,6 +137,9 @@ mod tests {
             reconciled_transaction_type: None,
             reconciled_transaction_id: None,
             category_id: None,
+            original_amount: None,
+            original_currency: None,
+            fx_fee: None,
         };
 
         // Date range includes the transaction date
@@ -186,6 +189,9 @@ mod tests {
             reconciled_transaction_type: None,
             reconciled_transaction_id: None,
             category_id: None,
+            original_amount: None,
+            original_currency: None,
+            fx_fee: None,
         };
 
         let today = NaiveDate::from_ymd_opt(2023, 1, 20).unwrap(); // Set today to Jan 20, 2023
@@ -224,6 +230,9 @@ mod tests {
             reconciled_transaction_type: None,
             reconciled_transaction_id: None,
             category_id: None,
+            original_amount: None,
+            original_currency: None,
+            fx_fee: None,
         };
 
         // Test with expand=false (should return empty since no tags are set up in the mock DB)
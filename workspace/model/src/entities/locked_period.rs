@@ -0,0 +1,33 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use sea_orm::entity::prelude::*;
+
+/// A closed accounting period.
+///
+/// Transactions dated within `start_date..=end_date` cannot be created, edited or
+/// deleted unless the request explicitly asks for an admin override. Used to
+/// protect months that were already reconciled and reported.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "locked_periods")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// First day of the locked period (inclusive).
+    pub start_date: NaiveDate,
+    /// Last day of the locked period (inclusive).
+    pub end_date: NaiveDate,
+    /// Optional note explaining why the period was closed.
+    pub note: Option<String>,
+    pub locked_at: NaiveDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl Model {
+    /// Returns true if the given date falls inside this period.
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start_date <= date && date <= self.end_date
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,33 @@
+use sea_orm::entity::prelude::*;
+
+/// Local merchant mapping used to enrich raw bank descriptions.
+///
+/// A mapping matches when its `pattern` occurs (case-insensitively) in an
+/// imported transaction description; the longest matching pattern wins.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "merchants")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// Substring searched for in raw descriptions, e.g. "SPOTIFY".
+    #[sea_orm(unique)]
+    pub pattern: String,
+    /// Cleaned display name, e.g. "Spotify".
+    pub name: String,
+    /// Optional URL of the merchant logo.
+    pub logo_url: Option<String>,
+    /// Optional merchant website.
+    pub website: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl Model {
+    /// Returns true if this mapping applies to the given description.
+    pub fn matches(&self, description: &str) -> bool {
+        description.to_lowercase().contains(&self.pattern.to_lowercase())
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,44 @@
+use sea_orm::entity::prelude::*;
+
+/// How a notification channel delivers messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum NotificationChannelKind {
+    /// E-mail sent through the SMTP server the web server is configured with.
+    #[sea_orm(string_value = "Email")]
+    Email,
+    /// Plain-text message published to an ntfy topic.
+    #[sea_orm(string_value = "Ntfy")]
+    Ntfy,
+    /// JSON document posted to an arbitrary URL.
+    #[sea_orm(string_value = "Webhook")]
+    Webhook,
+}
+
+/// A destination payment alerts are sent to.
+///
+/// A channel belongs to the user who added it and receives alerts about the accounts
+/// they own or that are shared with them. Channels added without authentication belong
+/// to nobody and are shared by the whole household.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "notification_channels")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// Label shown in listings, e.g. "Family phone".
+    pub name: String,
+    pub kind: NotificationChannelKind,
+    /// Recipient address for e-mail, topic URL for ntfy, endpoint URL for webhooks.
+    pub target: String,
+    /// Disabled channels are kept but receive nothing.
+    #[sea_orm(default_value = "true")]
+    pub enabled: bool,
+    pub created_at: DateTime,
+    /// User the channel belongs to; `None` for household channels.
+    pub user_id: Option<i32>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -2,10 +2,22 @@ use chrono::NaiveDate;
 use rust_decimal::Decimal;
 use sea_orm::entity::prelude::*;
 
-use super::{account, category, scenario, tag};
+use super::{account, category, reconciliation_session, scenario, tag};
 
 pub mod transaction;
 
+/// Whether the money of a one-off transaction has actually moved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum OneOffStatus {
+    #[sea_orm(string_value = "Confirmed")]
+    Confirmed,
+    /// Expected to happen on `date`; counted in forecasts but not in actual balances
+    /// until confirmed.
+    #[sea_orm(string_value = "Planned")]
+    Planned,
+}
+
 /// A single, non-repeating transaction.
 /// Corresponds to `ExtraTransactionModel`.
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
@@ -38,6 +50,38 @@ pub struct Model {
     /// Whether this is a simulated transaction (for what-if scenarios).
     #[sea_orm(default_value = "false")]
     pub is_simulated: bool,
+    /// The reconciliation session this transaction was ticked off in.
+    pub reconciliation_session_id: Option<i32>,
+    /// Locked transactions were confirmed against a bank statement and must not be edited.
+    #[sea_orm(default_value = "false")]
+    pub is_locked: bool,
+    /// Latitude (WGS84) of where the transaction happened, if known.
+    pub latitude: Option<f64>,
+    /// Longitude (WGS84) of where the transaction happened, if known.
+    pub longitude: Option<f64>,
+    /// Set on fee or cashback legs attached to a purchase; the leg keeps its own
+    /// category but is shown as part of the parent's total.
+    pub parent_transaction_id: Option<i32>,
+    #[sea_orm(default_value = "Confirmed")]
+    pub status: OneOffStatus,
+    /// The amount in the currency the payment was made in, when it differs from the
+    /// account currency. `amount` then holds the settled amount in the account currency.
+    #[sea_orm(column_type = "Decimal(Some((16, 4)))", nullable)]
+    pub original_amount: Option<Decimal>,
+    /// ISO code of the currency the payment was made in.
+    pub original_currency: Option<String>,
+    /// Units of the account currency paid per unit of `original_currency`.
+    #[sea_orm(column_type = "Decimal(Some((16, 8)))", nullable)]
+    pub conversion_rate: Option<Decimal>,
+    /// Purchases of goods can carry a warranty, a return window and a receipt.
+    #[sea_orm(default_value = "false")]
+    pub is_purchase: bool,
+    /// Last day the warranty covers the purchased item.
+    pub warranty_end_date: Option<NaiveDate>,
+    /// Last day the purchased item can be returned.
+    pub return_deadline: Option<NaiveDate>,
+    /// The account document holding the receipt.
+    pub receipt_document_id: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -70,6 +114,13 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Scenario,
+    #[sea_orm(
+        belongs_to = "reconciliation_session::Entity",
+        from = "Column::ReconciliationSessionId",
+        to = "reconciliation_session::Column::Id",
+        on_delete = "SetNull"
+    )]
+    ReconciliationSession,
 }
 
 impl Related<tag::Entity> for Entity {
@@ -91,4 +142,17 @@ impl Related<scenario::Entity> for Entity {
     }
 }
 
+impl Related<reconciliation_session::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ReconciliationSession.def()
+    }
+}
+
+impl Model {
+    /// Whether the transaction is only planned and not confirmed yet.
+    pub fn is_planned(&self) -> bool {
+        self.status == OneOffStatus::Planned
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -43,7 +43,8 @@ impl TransactionGenerator for OneOffTransaction {
             target_transaction.set_category(category.clone());
 
             // For one-off transactions: if the transaction date is today or in the past, mark as paid
-            if self.date <= today {
+            // unless it is only planned
+            if self.date <= today && !self.is_planned() {
                 // Set paid_on to the transaction date at midnight (start of day)
                 target_transaction.set_paid_on(Some(self.date.and_hms_opt(0, 0, 0).unwrap()));
             }
@@ -61,7 +62,7 @@ impl TransactionGenerator for OneOffTransaction {
                 source_transaction.set_category(category);
 
                 // Apply the same payment logic to the source transaction
-                if self.date <= today {
+                if self.date <= today && !self.is_planned() {
                     // Set paid_on to the transaction date at midnight (start of day)
                     source_transaction.set_paid_on(Some(self.date.and_hms_opt(0, 0, 0).unwrap()));
                 }
@@ -144,6 +145,7 @@ impl TransactionGenerator for OneOffTransaction {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::entities::one_off_transaction::OneOffStatus;
     use chrono::NaiveDate;
     use rust_decimal::Decimal;
 
@@ -163,6 +165,19 @@ mod tests {
             category_id: None,
             is_simulated: false,
             scenario_id: None,
+            reconciliation_session_id: None,
+            is_locked: false,
+            latitude: None,
+            longitude: None,
+            parent_transaction_id: None,
+            status: OneOffStatus::Confirmed,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: false,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
         };
 
         // Date range includes the transaction date
@@ -216,6 +231,19 @@ mod tests {
             category_id: None,
             is_simulated: false,
             scenario_id: None,
+            reconciliation_session_id: None,
+            is_locked: false,
+            latitude: None,
+            longitude: None,
+            parent_transaction_id: None,
+            status: OneOffStatus::Confirmed,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: false,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
         };
 
         let today = NaiveDate::from_ymd_opt(2023, 1, 20).unwrap(); // Set today to Jan 20, 2023
@@ -252,6 +280,19 @@ mod tests {
             category_id: None,
             is_simulated: false,
             scenario_id: None,
+            reconciliation_session_id: None,
+            is_locked: false,
+            latitude: None,
+            longitude: None,
+            parent_transaction_id: None,
+            status: OneOffStatus::Confirmed,
+            original_amount: None,
+            original_currency: None,
+            conversion_rate: None,
+            is_purchase: false,
+            warranty_end_date: None,
+            return_deadline: None,
+            receipt_document_id: None,
         };
 
         let transactions = transfer
@@ -0,0 +1,46 @@
+use sea_orm::entity::prelude::*;
+
+use super::recurring_transaction_instance;
+
+/// Why a payment alert was sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum PaymentAlertKind {
+    /// The instance is due soon.
+    #[sea_orm(string_value = "Upcoming")]
+    Upcoming,
+    /// The instance is past its due date and still pending.
+    #[sea_orm(string_value = "Overdue")]
+    Overdue,
+}
+
+/// Record of an alert sent about a recurring instance, so each instance is alerted
+/// about at most once per kind.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "payment_alerts")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub recurring_transaction_instance_id: i32,
+    pub kind: PaymentAlertKind,
+    pub sent_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "recurring_transaction_instance::Entity",
+        from = "Column::RecurringTransactionInstanceId",
+        to = "recurring_transaction_instance::Column::Id",
+        on_delete = "Cascade"
+    )]
+    RecurringTransactionInstance,
+}
+
+impl Related<recurring_transaction_instance::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RecurringTransactionInstance.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
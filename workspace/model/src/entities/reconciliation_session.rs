@@ -0,0 +1,71 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+use sea_orm::entity::prelude::*;
+
+use super::{account, one_off_transaction};
+
+/// Lifecycle state of a reconciliation session.
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(15))")]
+pub enum ReconciliationStatus {
+    /// Transactions are still being ticked off against the statement.
+    #[sea_orm(string_value = "Open")]
+    Open,
+    /// The statement balance matched and included transactions were locked.
+    #[sea_orm(string_value = "Completed")]
+    Completed,
+}
+
+/// A statement-style reconciliation of one account.
+///
+/// The user enters the ending balance and date from a bank statement, then ticks
+/// off transactions until `opening_balance + sum(ticked) == statement_balance`.
+/// Completing the session locks every ticked transaction against further edits.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "reconciliation_sessions")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// The account being reconciled.
+    pub account_id: i32,
+    /// The closing date printed on the statement.
+    pub statement_date: NaiveDate,
+    /// The closing balance printed on the statement.
+    #[sea_orm(column_type = "Decimal(Some((16, 4)))")]
+    pub statement_balance: Decimal,
+    /// The balance the statement starts from (usually the previous statement's closing balance).
+    #[sea_orm(column_type = "Decimal(Some((16, 4)))")]
+    pub opening_balance: Decimal,
+    /// Current state of the session.
+    pub status: ReconciliationStatus,
+    pub created_at: NaiveDateTime,
+    /// When the session was completed and its transactions locked.
+    pub completed_at: Option<NaiveDateTime>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "account::Entity",
+        from = "Column::AccountId",
+        to = "account::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Account,
+    #[sea_orm(has_many = "one_off_transaction::Entity")]
+    OneOffTransaction,
+}
+
+impl Related<account::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Account.def()
+    }
+}
+
+impl Related<one_off_transaction::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::OneOffTransaction.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,55 @@
+use sea_orm::entity::prelude::*;
+
+use super::{notification_channel, recurring_transaction};
+
+/// When to alert about the instances of one recurring transaction.
+///
+/// Recurring transactions without settings are never alerted about.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "recurring_alerts")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    #[sea_orm(unique)]
+    pub recurring_transaction_id: i32,
+    /// Alert once a pending instance is due within this many days; `None` for no
+    /// upcoming alerts.
+    pub days_before: Option<i32>,
+    /// Alert once a pending instance is past its due date.
+    #[sea_orm(default_value = "false")]
+    pub notify_overdue: bool,
+    /// Channel to alert through; `None` for every enabled channel.
+    pub channel_id: Option<i32>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "recurring_transaction::Entity",
+        from = "Column::RecurringTransactionId",
+        to = "recurring_transaction::Column::Id",
+        on_delete = "Cascade"
+    )]
+    RecurringTransaction,
+    #[sea_orm(
+        belongs_to = "notification_channel::Entity",
+        from = "Column::ChannelId",
+        to = "notification_channel::Column::Id",
+        on_delete = "SetNull"
+    )]
+    NotificationChannel,
+}
+
+impl Related<recurring_transaction::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RecurringTransaction.def()
+    }
+}
+
+impl Related<notification_channel::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::NotificationChannel.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
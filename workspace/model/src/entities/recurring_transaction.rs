@@ -1,10 +1,10 @@
 pub mod transaction;
 
-use chrono::NaiveDate;
+use chrono::{Datelike, NaiveDate, Weekday};
 use rust_decimal::Decimal;
 use sea_orm::entity::prelude::*;
 
-use super::{account, category, scenario, tag};
+use super::{account, category, exchange_rate, scenario, tag};
 
 /// Enum for recurrence periods.
 #[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
@@ -26,6 +26,97 @@ pub enum RecurrencePeriod {
     Yearly,
 }
 
+/// Approval state of a recurring transaction on a shared account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum ApprovalStatus {
+    #[sea_orm(string_value = "Approved")]
+    Approved,
+    /// Waiting for another member of the account to approve it; ignored by forecasts.
+    #[sea_orm(string_value = "PendingApproval")]
+    PendingApproval,
+    #[sea_orm(string_value = "Rejected")]
+    Rejected,
+}
+
+/// How the source leg of a transfer between accounts of different currencies is valued.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum FxMode {
+    /// Convert `amount` with the exchange rate of the execution date.
+    #[sea_orm(string_value = "ExecutionRate")]
+    ExecutionRate,
+    /// Convert `amount` with the agreed `fx_rate`.
+    #[sea_orm(string_value = "FixedRate")]
+    FixedRate,
+    /// Take the manually entered `source_amount`.
+    #[sea_orm(string_value = "ManualAmount")]
+    ManualAmount,
+}
+
+/// How upcoming occurrences of a recurring transaction are valued.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum AmountStrategy {
+    /// Always `amount`.
+    #[sea_orm(string_value = "Fixed")]
+    Fixed,
+    /// The amount of the latest paid instance.
+    #[sea_orm(string_value = "LastPaid")]
+    LastPaid,
+    /// The average of the last `amount_history_count` paid instances.
+    #[sea_orm(string_value = "AverageOfLastN")]
+    AverageOfLastN,
+}
+
+/// How occurrences of a month-based recurring transaction are moved off days the month
+/// doesn't have or that aren't business days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(24))")]
+pub enum DateAdjustment {
+    /// Occurrences keep the day of `start_date`; months without that day are skipped.
+    #[sea_orm(string_value = "None")]
+    None,
+    /// Months without the day of `start_date` get the occurrence on their last day.
+    #[sea_orm(string_value = "ClampToMonthEnd")]
+    ClampToMonthEnd,
+    /// Clamped to the month end, then moved from a weekend to the Friday before.
+    #[sea_orm(string_value = "PreviousBusinessDay")]
+    PreviousBusinessDay,
+    /// Clamped to the month end, then moved from a weekend to the Monday after.
+    #[sea_orm(string_value = "NextBusinessDay")]
+    NextBusinessDay,
+}
+
+impl DateAdjustment {
+    /// Date of the occurrence scheduled on `day` of `month`, or `None` when the month
+    /// has no such day and nothing is adjusted.
+    pub fn occurrence_in_month(&self, year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+        if *self == DateAdjustment::None {
+            return NaiveDate::from_ymd_opt(year, month, day);
+        }
+        let first_of_month = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let last_day = first_of_month
+            .checked_add_months(chrono::Months::new(1))
+            .and_then(|next_month| next_month.pred_opt())?
+            .day();
+        NaiveDate::from_ymd_opt(year, month, day.min(last_day)).map(|date| self.to_business_day(date))
+    }
+
+    /// Moves a `date` on a weekend to the business day before or after it, depending on
+    /// the adjustment; other dates are returned unchanged.
+    pub fn to_business_day(&self, date: NaiveDate) -> NaiveDate {
+        let days = match (self, date.weekday()) {
+            (DateAdjustment::PreviousBusinessDay, Weekday::Sat) => -1,
+            (DateAdjustment::PreviousBusinessDay, Weekday::Sun) => -2,
+            (DateAdjustment::NextBusinessDay, Weekday::Sat) => 2,
+            (DateAdjustment::NextBusinessDay, Weekday::Sun) => 1,
+            _ => 0,
+        };
+        date + chrono::Duration::days(days)
+    }
+}
+
 /// A transaction that repeats on a regular schedule.
 /// Can be used for both income (salary) and expenses (rent, subscriptions).
 /// Corresponds to `RegularTransactionModel`.
@@ -60,6 +151,41 @@ pub struct Model {
     /// Whether this is a simulated transaction (for what-if scenarios).
     #[sea_orm(default_value = "false")]
     pub is_simulated: bool,
+    /// Only approved transactions are included in balances, forecasts and metrics.
+    #[sea_orm(default_value = "Approved")]
+    pub approval_status: ApprovalStatus,
+    /// Member who created the transaction, if known.
+    pub created_by_user_id: Option<i32>,
+    /// Member who approved or rejected the transaction.
+    pub reviewed_by_user_id: Option<i32>,
+    /// Days before `renewal_date` the underlying contract has to be cancelled.
+    pub notice_period_days: Option<i32>,
+    /// Date the underlying contract renews unless cancelled in time.
+    pub renewal_date: Option<NaiveDate>,
+    /// How the source leg is valued when the accounts use different currencies.
+    /// `None` moves `amount` out of the source account unchanged.
+    pub fx_mode: Option<FxMode>,
+    /// Units of the source account currency per unit of the target account currency,
+    /// used by [`FxMode::FixedRate`].
+    #[sea_orm(column_type = "Decimal(Some((16, 8)))", nullable)]
+    pub fx_rate: Option<Decimal>,
+    /// Value of each occurrence in the source account currency, with the same sign as
+    /// `amount`; used by [`FxMode::ManualAmount`].
+    #[sea_orm(column_type = "Decimal(Some((16, 4)))", nullable)]
+    pub source_amount: Option<Decimal>,
+    /// How upcoming occurrences are valued; without paid instances they use `amount`.
+    #[sea_orm(default_value = "Fixed")]
+    pub amount_strategy: AmountStrategy,
+    /// Paid instances averaged by [`AmountStrategy::AverageOfLastN`].
+    pub amount_history_count: Option<i32>,
+    /// RFC 5545 RRULE, e.g. `FREQ=MONTHLY;BYDAY=2TU`, for schedules `period` can't
+    /// express. When set, occurrences follow the rule and `period` only describes the
+    /// rough frequency, e.g. for monthly equivalents.
+    pub rrule: Option<String>,
+    /// How occurrences of month-based periods are moved, e.g. so rent due on the 31st
+    /// is paid on the last day of February. Not applied to `rrule` schedules.
+    #[sea_orm(default_value = "None")]
+    pub date_adjustment: DateAdjustment,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -113,4 +239,140 @@ impl Related<scenario::Entity> for Entity {
     }
 }
 
+impl Model {
+    /// Value of an occurrence moving `amount` into the target account, expressed in the
+    /// source account currency with the same sign as `amount`. The source account is
+    /// booked the negated value.
+    ///
+    /// `execution_rate` converts one unit of the target currency into the source
+    /// currency on the execution date and is only used by [`FxMode::ExecutionRate`].
+    /// A manual `source_amount` is scaled when an instance was paid with a different
+    /// amount. Returns `None` when the needed rate or amount is missing.
+    pub fn source_leg_amount(&self, amount: Decimal, execution_rate: Option<Decimal>) -> Option<Decimal> {
+        match self.fx_mode {
+            None => Some(amount),
+            Some(FxMode::ExecutionRate) => execution_rate.map(|rate| (amount * rate).round_dp(2)),
+            Some(FxMode::FixedRate) => self.fx_rate.map(|rate| (amount * rate).round_dp(2)),
+            Some(FxMode::ManualAmount) => self.source_amount.map(|source_amount| {
+                if amount == self.amount || self.amount.is_zero() {
+                    source_amount
+                } else {
+                    (source_amount * amount / self.amount).round_dp(2)
+                }
+            }),
+        }
+    }
+
+    /// Like [`Model::source_leg_amount`], looking the execution rate of `date` up in
+    /// the stored exchange rates.
+    pub async fn source_leg_amount_on(
+        &self,
+        db: &DatabaseConnection,
+        amount: Decimal,
+        date: NaiveDate,
+    ) -> Result<Option<Decimal>, DbErr> {
+        let Some(source_account_id) = self.source_account_id else {
+            return Ok(Some(amount));
+        };
+        if self.fx_mode != Some(FxMode::ExecutionRate) {
+            return Ok(self.source_leg_amount(amount, None));
+        }
+
+        let target = account::Entity::find_by_id(self.target_account_id).one(db).await?;
+        let source = account::Entity::find_by_id(source_account_id).one(db).await?;
+        let (Some(target), Some(source)) = (target, source) else {
+            return Ok(None);
+        };
+        let rate = exchange_rate::rate_on(db, &target.currency_code, &source.currency_code, date).await?;
+        Ok(self.source_leg_amount(amount, rate))
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transfer(fx_mode: Option<FxMode>) -> Model {
+        Model {
+            id: 1,
+            name: "Savings".to_string(),
+            description: None,
+            amount: Decimal::new(100, 0),
+            start_date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            end_date: None,
+            period: RecurrencePeriod::Monthly,
+            include_in_statistics: true,
+            target_account_id: 2,
+            source_account_id: Some(1),
+            category_id: None,
+            ledger_name: None,
+            scenario_id: None,
+            is_simulated: false,
+            approval_status: ApprovalStatus::Approved,
+            created_by_user_id: None,
+            reviewed_by_user_id: None,
+            notice_period_days: None,
+            renewal_date: None,
+            fx_mode,
+            fx_rate: Some(Decimal::new(25, 0)),
+            source_amount: Some(Decimal::new(2450, 0)),
+            amount_strategy: AmountStrategy::Fixed,
+            amount_history_count: None,
+            rrule: None,
+            date_adjustment: DateAdjustment::None,
+        }
+    }
+
+    #[test]
+    fn test_source_leg_amount_without_fx_mode() {
+        let tx = transfer(None);
+        assert_eq!(tx.source_leg_amount(Decimal::new(100, 0), Some(Decimal::new(30, 0))), Some(Decimal::new(100, 0)));
+    }
+
+    #[test]
+    fn test_source_leg_amount_execution_rate() {
+        let tx = transfer(Some(FxMode::ExecutionRate));
+        assert_eq!(
+            tx.source_leg_amount(Decimal::new(100, 0), Some(Decimal::new(24333, 3))),
+            Some(Decimal::new(243330, 2))
+        );
+        assert_eq!(tx.source_leg_amount(Decimal::new(100, 0), None), None);
+    }
+
+    #[test]
+    fn test_source_leg_amount_fixed_rate() {
+        let tx = transfer(Some(FxMode::FixedRate));
+        assert_eq!(tx.source_leg_amount(Decimal::new(100, 0), Some(Decimal::new(30, 0))), Some(Decimal::new(2500, 0)));
+    }
+
+    #[test]
+    fn test_source_leg_amount_manual_amount_scales_with_paid_amount() {
+        let tx = transfer(Some(FxMode::ManualAmount));
+        assert_eq!(tx.source_leg_amount(Decimal::new(100, 0), None), Some(Decimal::new(2450, 0)));
+        assert_eq!(tx.source_leg_amount(Decimal::new(50, 0), None), Some(Decimal::new(1225, 0)));
+    }
+
+    #[test]
+    fn test_date_adjustment_clamps_to_month_end() {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        assert_eq!(DateAdjustment::None.occurrence_in_month(2025, 2, 31), None);
+        assert_eq!(DateAdjustment::ClampToMonthEnd.occurrence_in_month(2025, 2, 31), Some(date(2025, 2, 28)));
+        assert_eq!(DateAdjustment::ClampToMonthEnd.occurrence_in_month(2024, 2, 31), Some(date(2024, 2, 29)));
+        assert_eq!(DateAdjustment::ClampToMonthEnd.occurrence_in_month(2025, 4, 15), Some(date(2025, 4, 15)));
+    }
+
+    #[test]
+    fn test_date_adjustment_moves_off_weekends() {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        // 2025-05-31 is a Saturday, 2025-08-31 a Sunday
+        assert_eq!(DateAdjustment::PreviousBusinessDay.occurrence_in_month(2025, 5, 31), Some(date(2025, 5, 30)));
+        assert_eq!(DateAdjustment::NextBusinessDay.occurrence_in_month(2025, 5, 31), Some(date(2025, 6, 2)));
+        assert_eq!(DateAdjustment::PreviousBusinessDay.occurrence_in_month(2025, 8, 31), Some(date(2025, 8, 29)));
+        assert_eq!(DateAdjustment::NextBusinessDay.occurrence_in_month(2025, 8, 31), Some(date(2025, 9, 1)));
+        assert_eq!(DateAdjustment::ClampToMonthEnd.to_business_day(date(2025, 5, 31)), date(2025, 5, 31));
+        // Wednesday
+        assert_eq!(DateAdjustment::NextBusinessDay.to_business_day(date(2025, 1, 1)), date(2025, 1, 1));
+    }
+}
@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use chrono::{Datelike, NaiveDate, Weekday};
+use rust_decimal::Decimal;
 use sea_orm::{
     ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, QueryFilter,
 };
@@ -7,6 +8,7 @@ use sea_orm::{
 use crate::entities::recurring_transaction::{Model as RecurringTransaction, RecurrencePeriod};
 use crate::entities::{category, recurring_transaction_instance, tag};
 use crate::transaction::{Category, Tag, Transaction, TransactionGenerator};
+use tracing::warn;
 
 #[async_trait]
 impl TransactionGenerator for RecurringTransaction {
@@ -132,7 +134,7 @@ impl TransactionGenerator for RecurringTransaction {
                 while NaiveDate::from_ymd_opt(current_year, current_month, 1).unwrap() <= end {
                     // Try to create a date with the same day in the current month
                     if let Some(date) =
-                        NaiveDate::from_ymd_opt(current_year, current_month, start_day)
+                        self.date_adjustment.occurrence_in_month(current_year, current_month, start_day)
                     {
                         if date >= effective_start && date <= end {
                             return true;
@@ -162,7 +164,7 @@ impl TransactionGenerator for RecurringTransaction {
                     if current_month % 3 == quarter_month % 3 {
                         // Try to create a date with the same day in the current month
                         if let Some(date) =
-                            NaiveDate::from_ymd_opt(current_year, current_month, start_day)
+                            self.date_adjustment.occurrence_in_month(current_year, current_month, start_day)
                         {
                             if date >= effective_start && date <= end {
                                 return true;
@@ -193,7 +195,7 @@ impl TransactionGenerator for RecurringTransaction {
                     if current_month % 6 == half_year_month % 6 {
                         // Try to create a date with the same day in the current month
                         if let Some(date) =
-                            NaiveDate::from_ymd_opt(current_year, current_month, start_day)
+                            self.date_adjustment.occurrence_in_month(current_year, current_month, start_day)
                         {
                             if date >= effective_start && date <= end {
                                 return true;
@@ -220,7 +222,7 @@ impl TransactionGenerator for RecurringTransaction {
                 while current_year <= end.year() {
                     // Try to create a date with the same day and month in the current year
                     if let Some(date) =
-                        NaiveDate::from_ymd_opt(current_year, start_month, start_day)
+                        self.date_adjustment.occurrence_in_month(current_year, start_month, start_day)
                     {
                         if date >= effective_start && date <= end {
                             return true;
@@ -331,7 +333,7 @@ impl TransactionGenerator for RecurringTransaction {
                 {
                     // Try to create a date with the same day in the current month
                     if let Some(date) =
-                        NaiveDate::from_ymd_opt(current_year, current_month, start_day)
+                        self.date_adjustment.occurrence_in_month(current_year, current_month, start_day)
                     {
                         if date >= effective_start && date <= effective_end {
                             add_transaction(&mut transactions, self, date, today, db).await;
@@ -361,7 +363,7 @@ impl TransactionGenerator for RecurringTransaction {
                     if current_month % 3 == quarter_month % 3 {
                         // Try to create a date with the same day in the current month
                         if let Some(date) =
-                            NaiveDate::from_ymd_opt(current_year, current_month, start_day)
+                            self.date_adjustment.occurrence_in_month(current_year, current_month, start_day)
                         {
                             if date >= effective_start && date <= effective_end {
                                 add_transaction(&mut transactions, self, date, today, db).await;
@@ -392,7 +394,7 @@ impl TransactionGenerator for RecurringTransaction {
                     if current_month % 6 == half_year_month % 6 {
                         // Try to create a date with the same day in the current month
                         if let Some(date) =
-                            NaiveDate::from_ymd_opt(current_year, current_month, start_day)
+                            self.date_adjustment.occurrence_in_month(current_year, current_month, start_day)
                         {
                             if date >= effective_start && date <= effective_end {
                                 add_transaction(&mut transactions, self, date, today, db).await;
@@ -417,7 +419,7 @@ impl TransactionGenerator for RecurringTransaction {
                 while current_year <= effective_end.year() {
                     // Try to create a date with the same day and month in the current year
                     if let Some(date) =
-                        NaiveDate::from_ymd_opt(current_year, start_month, start_day)
+                        self.date_adjustment.occurrence_in_month(current_year, start_month, start_day)
                     {
                         if date >= effective_start && date <= effective_end {
                             add_transaction(&mut transactions, self, date, today, db).await;
@@ -525,11 +527,14 @@ async fn add_transaction(
 
     // If there's a source account, add a transaction for it as well
     if let Some(source_account_id) = transaction.source_account_id {
-        // For the source account, the amount is negated
+        // For the source account, the amount is negated and converted into its currency
+        let Some(source_amount) = source_leg_amount(transaction, transaction.amount, date, db).await else {
+            return;
+        };
         let mut source_transaction = if tags.is_empty() {
-            Transaction::new(date, -transaction.amount, source_account_id)
+            Transaction::new(date, -source_amount, source_account_id)
         } else {
-            Transaction::new_with_tags(date, -transaction.amount, source_account_id, tags.clone())
+            Transaction::new_with_tags(date, -source_amount, source_account_id, tags.clone())
         };
         source_transaction.set_category(category);
 
@@ -547,17 +552,19 @@ async fn add_transaction(
                 recurring_transaction_instance::InstanceStatus::Paid => {
                     // Use the paid date if available, otherwise use due date
                     let paid_date = instance.paid_date.unwrap_or(instance.due_date);
+                    // Converted at the rate of the day it was actually paid
+                    let paid_amount = instance.paid_amount.unwrap_or(instance.expected_amount);
+                    let Some(paid_source_amount) = source_leg_amount(transaction, paid_amount, paid_date, db).await
+                    else {
+                        return;
+                    };
                     // Update transaction date to instance date and amount to actual paid amount if available (negated for source)
                     source_transaction = if tags.is_empty() {
-                        Transaction::new(
-                            instance.due_date,
-                            -(instance.paid_amount.unwrap_or(instance.expected_amount)),
-                            source_account_id,
-                        )
+                        Transaction::new(instance.due_date, -paid_source_amount, source_account_id)
                     } else {
                         Transaction::new_with_tags(
                             instance.due_date,
-                            -(instance.paid_amount.unwrap_or(instance.expected_amount)),
+                            -paid_source_amount,
                             source_account_id,
                             tags.clone(),
                         )
@@ -568,17 +575,18 @@ async fn add_transaction(
                     }
                 }
                 recurring_transaction_instance::InstanceStatus::Pending => {
+                    let Some(expected_source_amount) =
+                        source_leg_amount(transaction, instance.expected_amount, instance.due_date, db).await
+                    else {
+                        return;
+                    };
                     // Update transaction date to instance due date
                     source_transaction = if tags.is_empty() {
-                        Transaction::new(
-                            instance.due_date,
-                            -instance.expected_amount,
-                            source_account_id,
-                        )
+                        Transaction::new(instance.due_date, -expected_source_amount, source_account_id)
                     } else {
                         Transaction::new_with_tags(
                             instance.due_date,
-                            -instance.expected_amount,
+                            -expected_source_amount,
                             source_account_id,
                             tags.clone(),
                         )
@@ -600,9 +608,34 @@ async fn add_transaction(
     }
 }
 
+/// Amount of a transfer leaving its source account, in the source account's currency.
+/// `None`, leaving the source leg out, when the execution rate isn't known.
+async fn source_leg_amount(
+    transaction: &RecurringTransaction,
+    amount: Decimal,
+    date: NaiveDate,
+    db: &DatabaseConnection,
+) -> Option<Decimal> {
+    match transaction.source_leg_amount_on(db, amount, date).await {
+        Ok(Some(source_amount)) => Some(source_amount),
+        Ok(None) => {
+            warn!(
+                "No exchange rate for recurring transaction {} on {}, leaving out its source leg",
+                transaction.id, date
+            );
+            None
+        }
+        Err(e) => {
+            warn!("Failed to convert recurring transaction {} on {}: {}", transaction.id, date, e);
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::entities::recurring_transaction::{AmountStrategy, ApprovalStatus, DateAdjustment};
     use chrono::NaiveDate;
     use rust_decimal::Decimal;
 
@@ -623,6 +656,18 @@ mod tests {
             category_id: None,
             is_simulated: false,
             scenario_id: None,
+            approval_status: ApprovalStatus::Approved,
+            created_by_user_id: None,
+            reviewed_by_user_id: None,
+            notice_period_days: None,
+            renewal_date: None,
+            fx_mode: None,
+            fx_rate: None,
+            source_amount: None,
+            amount_strategy: AmountStrategy::Fixed,
+            amount_history_count: None,
+            rrule: None,
+            date_adjustment: DateAdjustment::None,
         };
 
         // Date range includes a monthly occurrence
@@ -670,6 +715,18 @@ mod tests {
             category_id: None,
             is_simulated: false,
             scenario_id: None,
+            approval_status: ApprovalStatus::Approved,
+            created_by_user_id: None,
+            reviewed_by_user_id: None,
+            notice_period_days: None,
+            renewal_date: None,
+            fx_mode: None,
+            fx_rate: None,
+            source_amount: None,
+            amount_strategy: AmountStrategy::Fixed,
+            amount_history_count: None,
+            rrule: None,
+            date_adjustment: DateAdjustment::None,
         };
 
         // Generate transactions for a 3-month period
@@ -733,6 +790,18 @@ mod tests {
             category_id: None,
             is_simulated: false,
             scenario_id: None,
+            approval_status: ApprovalStatus::Approved,
+            created_by_user_id: None,
+            reviewed_by_user_id: None,
+            notice_period_days: None,
+            renewal_date: None,
+            fx_mode: None,
+            fx_rate: None,
+            source_amount: None,
+            amount_strategy: AmountStrategy::Fixed,
+            amount_history_count: None,
+            rrule: None,
+            date_adjustment: DateAdjustment::None,
         };
 
         // Generate transactions for a 2-month period
@@ -807,6 +876,18 @@ mod tests {
             category_id: None,
             is_simulated: false,
             scenario_id: None,
+            approval_status: ApprovalStatus::Approved,
+            created_by_user_id: None,
+            reviewed_by_user_id: None,
+            notice_period_days: None,
+            renewal_date: None,
+            fx_mode: None,
+            fx_rate: None,
+            source_amount: None,
+            amount_strategy: AmountStrategy::Fixed,
+            amount_history_count: None,
+            rrule: None,
+            date_adjustment: DateAdjustment::None,
         };
 
         // Generate transactions for a 3-month period without any instances in the database
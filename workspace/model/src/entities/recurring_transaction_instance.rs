@@ -51,6 +51,20 @@ pub struct Model {
 
     /// The category of the instance.
     pub category_id: Option<i32>,
+
+    /// Free-form notes, e.g. how a dispute is progressing.
+    #[sea_orm(column_type = "Text", nullable)]
+    pub notes: Option<String>,
+
+    /// The charge is contested with the payee; a disputed instance does not count
+    /// towards paid totals until the dispute is settled.
+    #[sea_orm(default_value = "false")]
+    pub disputed: bool,
+
+    /// Still pending after its due date; set by the instance generation job and cleared
+    /// once the instance is paid or skipped.
+    #[sea_orm(default_value = "false")]
+    pub overdue: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
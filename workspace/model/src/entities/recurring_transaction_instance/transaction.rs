@@ -223,6 +223,9 @@ mod tests {
             paid_amount: Some(Decimal::new(-1000, 0)),
             reconciled_imported_transaction_id: None,
             category_id: None,
+            notes: None,
+            disputed: false,
+            overdue: false,
         };
 
         // Pending instance
@@ -236,6 +239,9 @@ mod tests {
             paid_amount: None,
             reconciled_imported_transaction_id: None,
             category_id: None,
+            notes: None,
+            disputed: false,
+            overdue: false,
         };
 
         // Skipped instance
@@ -249,6 +255,9 @@ mod tests {
             paid_amount: None,
             reconciled_imported_transaction_id: None,
             category_id: None,
+            notes: None,
+            disputed: false,
+            overdue: false,
         };
 
         // Test paid instance
@@ -294,6 +303,9 @@ mod tests {
             paid_amount: Some(Decimal::new(-950, 0)), // Slightly different from expected
             reconciled_imported_transaction_id: None,
             category_id: None,
+            notes: None,
+            disputed: false,
+            overdue: false,
         };
 
         // Generate transactions for the paid instance
@@ -327,6 +339,9 @@ mod tests {
             paid_amount: None,
             reconciled_imported_transaction_id: None,
             category_id: None,
+            notes: None,
+            disputed: false,
+            overdue: false,
         };
 
         // Generate transactions for the pending instance
@@ -360,6 +375,9 @@ mod tests {
             paid_amount: None,
             reconciled_imported_transaction_id: None,
             category_id: None,
+            notes: None,
+            disputed: false,
+            overdue: false,
         };
 
         // Generate transactions for the skipped instance
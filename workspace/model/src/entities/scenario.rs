@@ -1,4 +1,4 @@
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime};
 use sea_orm::entity::prelude::*;
 
 /// A what-if scenario containing hypothetical transactions.
@@ -13,6 +13,9 @@ pub struct Model {
     pub created_at: NaiveDateTime,
     #[sea_orm(default_value = "false")]
     pub is_active: bool,
+    /// Simulated transactions of this scenario only apply on or after this date.
+    /// `None` applies them over the whole timeline.
+    pub activation_date: Option<NaiveDate>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -17,6 +17,9 @@ pub struct Model {
     /// The name to use when exporting to Ledger CLI format.
     /// Can be a template string.
     pub ledger_name: Option<String>,
+    /// Maximum spending per calendar month for transactions carrying this tag
+    /// (or any of its child tags), independent of their category.
+    pub monthly_limit: Option<Decimal>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -93,6 +96,7 @@ mod tests {
             description: Set(description.map(|s| s.to_string())),
             parent_id: Set(parent_id),
             ledger_name: Set(None),
+            monthly_limit: Set(None),
         };
 
         tag.insert(db).await.unwrap()
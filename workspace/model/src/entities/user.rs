@@ -9,7 +9,18 @@ pub struct Model {
     pub id: i32,
     #[sea_orm(unique)]
     pub username: String,
-    // Other fields like password_hash, email, etc., would go here.
+    /// Scenario applied by statistics and timeseries endpoints when the client doesn't pass one.
+    pub active_scenario_id: Option<i32>,
+    /// UI theme preference ("light", "dark" or "system"); `None` follows the system preference.
+    pub theme: Option<String>,
+    /// Argon2 hash of the user's password in PHC format; users without one cannot log in.
+    pub password_hash: Option<String>,
+    /// IANA timezone, e.g. "Europe/Prague", deciding what "today" is for the user;
+    /// `None` uses the server's timezone.
+    pub timezone: Option<String>,
+    /// Administrators may restore full export archives over the API.
+    pub is_admin: bool,
+    // Other fields like email, etc., would go here.
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -17,6 +28,10 @@ pub enum Relation {
     // A user can own multiple accounts.
     #[sea_orm(has_many = "super::account::Entity")]
     Account,
+    #[sea_orm(has_many = "super::user_session::Entity")]
+    UserSession,
+    #[sea_orm(has_many = "super::api_token::Entity")]
+    ApiToken,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,199 @@
+use chrono::NaiveDate;
+use model::entities::{account, category, manual_account_state, user};
+use rust_decimal::Decimal;
+use sea_orm::{ActiveModelTrait, ConnectionTrait, DbErr, Set};
+
+use crate::sequence;
+
+/// Builds a user, named `user_<n>` unless a username is given.
+#[derive(Debug, Clone, Default)]
+pub struct UserFactory {
+    username: Option<String>,
+}
+
+impl UserFactory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    pub async fn create(self, db: &impl ConnectionTrait) -> Result<user::Model, DbErr> {
+        user::ActiveModel {
+            username: Set(self.username.unwrap_or_else(|| format!("user_{}", sequence()))),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+    }
+}
+
+/// Builds a USD account included in statistics. Without an owner, a new user is created
+/// for it.
+#[derive(Debug, Clone)]
+pub struct AccountFactory {
+    name: Option<String>,
+    description: Option<String>,
+    currency_code: String,
+    owner_id: Option<i32>,
+    include_in_statistics: bool,
+    ledger_name: Option<String>,
+    account_kind: Option<account::AccountKind>,
+    is_liquid: Option<bool>,
+}
+
+impl Default for AccountFactory {
+    fn default() -> Self {
+        Self {
+            name: None,
+            description: None,
+            currency_code: "USD".to_string(),
+            owner_id: None,
+            include_in_statistics: true,
+            ledger_name: None,
+            account_kind: None,
+            is_liquid: None,
+        }
+    }
+}
+
+impl AccountFactory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn currency(mut self, currency_code: impl Into<String>) -> Self {
+        self.currency_code = currency_code.into();
+        self
+    }
+
+    pub fn owner(mut self, owner_id: i32) -> Self {
+        self.owner_id = Some(owner_id);
+        self
+    }
+
+    pub fn include_in_statistics(mut self, include_in_statistics: bool) -> Self {
+        self.include_in_statistics = include_in_statistics;
+        self
+    }
+
+    pub fn ledger_name(mut self, ledger_name: impl Into<String>) -> Self {
+        self.ledger_name = Some(ledger_name.into());
+        self
+    }
+
+    pub fn kind(mut self, account_kind: account::AccountKind) -> Self {
+        self.account_kind = Some(account_kind);
+        self
+    }
+
+    pub fn liquid(mut self, is_liquid: bool) -> Self {
+        self.is_liquid = Some(is_liquid);
+        self
+    }
+
+    pub async fn create(self, db: &impl ConnectionTrait) -> Result<account::Model, DbErr> {
+        let owner_id = match self.owner_id {
+            Some(owner_id) => owner_id,
+            None => UserFactory::new().create(db).await?.id,
+        };
+        let mut account = account::ActiveModel {
+            name: Set(self.name.unwrap_or_else(|| format!("Test account {}", sequence()))),
+            description: Set(self.description),
+            currency_code: Set(self.currency_code),
+            owner_id: Set(owner_id),
+            include_in_statistics: Set(self.include_in_statistics),
+            ledger_name: Set(self.ledger_name),
+            ..Default::default()
+        };
+        if let Some(account_kind) = self.account_kind {
+            account.account_kind = Set(account_kind);
+        }
+        if let Some(is_liquid) = self.is_liquid {
+            account.is_liquid = Set(is_liquid);
+        }
+        account.insert(db).await
+    }
+}
+
+/// Builds a root category, named `Category <n>` unless a name is given.
+#[derive(Debug, Clone, Default)]
+pub struct CategoryFactory {
+    name: Option<String>,
+    description: Option<String>,
+    parent_id: Option<i32>,
+}
+
+impl CategoryFactory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn parent(mut self, parent: &category::Model) -> Self {
+        self.parent_id = Some(parent.id);
+        self
+    }
+
+    pub async fn create(self, db: &impl ConnectionTrait) -> Result<category::Model, DbErr> {
+        category::ActiveModel {
+            name: Set(self.name.unwrap_or_else(|| format!("Category {}", sequence()))),
+            description: Set(self.description),
+            parent_id: Set(self.parent_id),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+    }
+}
+
+/// Builds a manually entered balance of an account.
+#[derive(Debug, Clone)]
+pub struct ManualAccountStateFactory {
+    account_id: i32,
+    date: NaiveDate,
+    amount: Decimal,
+}
+
+impl ManualAccountStateFactory {
+    pub fn new(account: &account::Model, date: NaiveDate, amount: impl Into<Decimal>) -> Self {
+        Self {
+            account_id: account.id,
+            date,
+            amount: amount.into(),
+        }
+    }
+
+    pub async fn create(self, db: &impl ConnectionTrait) -> Result<manual_account_state::Model, DbErr> {
+        manual_account_state::ActiveModel {
+            account_id: Set(self.account_id),
+            date: Set(self.date),
+            amount: Set(self.amount),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+    }
+}
@@ -0,0 +1,102 @@
+//! Builders inserting test data, so scenario tests only spell out what matters to them.
+//!
+//! Every factory starts from defaults that satisfy the schema and is finished with
+//! `create`, which inserts the row and returns the model:
+//!
+//! ```ignore
+//! let account = AccountFactory::new().currency("CZK").create(&db).await?;
+//! let rent = RecurringTransactionFactory::new(&account).amount(-1200).create(&db).await?;
+//! RecurringInstanceFactory::new(&rent, rent.start_date).paid().create(&db).await?;
+//! ```
+//!
+//! Names and usernames get a sequence number, so factories can be called repeatedly
+//! against the same database.
+
+pub mod account;
+pub mod one_off;
+pub mod recurring;
+
+pub use account::{AccountFactory, CategoryFactory, ManualAccountStateFactory, UserFactory};
+pub use one_off::OneOffTransactionFactory;
+pub use recurring::{RecurringInstanceFactory, RecurringTransactionFactory};
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Next number of the sequence shared by all factories.
+fn sequence() -> u64 {
+    static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+    SEQUENCE.fetch_add(1, Ordering::SeqCst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use migration::{Migrator, MigratorTrait};
+    use model::entities::{account::AccountKind, recurring_transaction_instance::InstanceStatus};
+    use rust_decimal::Decimal;
+    use sea_orm::{Database, DatabaseConnection};
+
+    async fn setup_db() -> DatabaseConnection {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        Migrator::up(&db, None).await.unwrap();
+        db
+    }
+
+    #[tokio::test]
+    async fn test_account_factory_creates_owner() {
+        let db = setup_db().await;
+
+        let first = AccountFactory::new().create(&db).await.unwrap();
+        let second = AccountFactory::new()
+            .name("Savings")
+            .currency("CZK")
+            .kind(AccountKind::Savings)
+            .owner(first.owner_id)
+            .create(&db)
+            .await
+            .unwrap();
+
+        assert_eq!(first.currency_code, "USD");
+        assert_eq!(second.name, "Savings");
+        assert_eq!(second.currency_code, "CZK");
+        assert_eq!(second.account_kind, AccountKind::Savings);
+        assert_eq!(second.owner_id, first.owner_id);
+        assert_ne!(AccountFactory::new().create(&db).await.unwrap().owner_id, first.owner_id);
+    }
+
+    #[tokio::test]
+    async fn test_recurring_factories() {
+        let db = setup_db().await;
+        let checking = AccountFactory::new().create(&db).await.unwrap();
+        let savings = AccountFactory::new().owner(checking.owner_id).create(&db).await.unwrap();
+        let category = CategoryFactory::new().name("Savings").create(&db).await.unwrap();
+
+        let start = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let transfer = RecurringTransactionFactory::new(&savings)
+            .amount(250)
+            .start_date(start)
+            .source_account(&checking)
+            .category(&category)
+            .create(&db)
+            .await
+            .unwrap();
+        assert_eq!(transfer.amount, Decimal::from(250));
+        assert_eq!(transfer.source_account_id, Some(checking.id));
+        assert_eq!(transfer.category_id, Some(category.id));
+
+        let pending = RecurringInstanceFactory::new(&transfer, start).create(&db).await.unwrap();
+        assert_eq!(pending.status, InstanceStatus::Pending);
+        assert_eq!(pending.expected_amount, Decimal::from(250));
+
+        let paid_on = NaiveDate::from_ymd_opt(2025, 2, 17).unwrap();
+        let paid = RecurringInstanceFactory::new(&transfer, NaiveDate::from_ymd_opt(2025, 2, 15).unwrap())
+            .paid_on(paid_on, 240)
+            .create(&db)
+            .await
+            .unwrap();
+        assert_eq!(paid.status, InstanceStatus::Paid);
+        assert_eq!(paid.paid_date, Some(paid_on));
+        assert_eq!(paid.paid_amount, Some(Decimal::from(240)));
+    }
+}
@@ -0,0 +1,118 @@
+use chrono::NaiveDate;
+use model::entities::one_off_transaction::OneOffStatus;
+use model::entities::{account, category, one_off_transaction};
+use rust_decimal::Decimal;
+use sea_orm::{ActiveModelTrait, ConnectionTrait, DbErr, Set};
+
+use crate::sequence;
+
+/// Builds a confirmed expense of 100 on an account, dated 2024-01-01.
+#[derive(Debug, Clone)]
+pub struct OneOffTransactionFactory {
+    name: Option<String>,
+    description: Option<String>,
+    amount: Decimal,
+    date: NaiveDate,
+    include_in_statistics: bool,
+    target_account_id: i32,
+    source_account_id: Option<i32>,
+    category_id: Option<i32>,
+    ledger_name: Option<String>,
+    scenario_id: Option<i32>,
+    is_simulated: bool,
+    status: OneOffStatus,
+}
+
+impl OneOffTransactionFactory {
+    pub fn new(target_account: &account::Model) -> Self {
+        Self {
+            name: None,
+            description: None,
+            amount: Decimal::from(-100),
+            date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            include_in_statistics: true,
+            target_account_id: target_account.id,
+            source_account_id: None,
+            category_id: None,
+            ledger_name: None,
+            scenario_id: None,
+            is_simulated: false,
+            status: OneOffStatus::Confirmed,
+        }
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Positive for income, negative for expenses.
+    pub fn amount(mut self, amount: impl Into<Decimal>) -> Self {
+        self.amount = amount.into();
+        self
+    }
+
+    pub fn date(mut self, date: NaiveDate) -> Self {
+        self.date = date;
+        self
+    }
+
+    pub fn include_in_statistics(mut self, include_in_statistics: bool) -> Self {
+        self.include_in_statistics = include_in_statistics;
+        self
+    }
+
+    /// Turns the transaction into a transfer from `source_account`.
+    pub fn source_account(mut self, source_account: &account::Model) -> Self {
+        self.source_account_id = Some(source_account.id);
+        self
+    }
+
+    pub fn category(mut self, category: &category::Model) -> Self {
+        self.category_id = Some(category.id);
+        self
+    }
+
+    pub fn ledger_name(mut self, ledger_name: impl Into<String>) -> Self {
+        self.ledger_name = Some(ledger_name.into());
+        self
+    }
+
+    /// Makes the transaction a simulated part of a what-if scenario.
+    pub fn scenario(mut self, scenario_id: i32) -> Self {
+        self.scenario_id = Some(scenario_id);
+        self.is_simulated = true;
+        self
+    }
+
+    /// Expected on `date` but not happened yet.
+    pub fn planned(mut self) -> Self {
+        self.status = OneOffStatus::Planned;
+        self
+    }
+
+    pub async fn create(self, db: &impl ConnectionTrait) -> Result<one_off_transaction::Model, DbErr> {
+        one_off_transaction::ActiveModel {
+            name: Set(self.name.unwrap_or_else(|| format!("One-off {}", sequence()))),
+            description: Set(self.description),
+            amount: Set(self.amount),
+            date: Set(self.date),
+            include_in_statistics: Set(self.include_in_statistics),
+            target_account_id: Set(self.target_account_id),
+            source_account_id: Set(self.source_account_id),
+            category_id: Set(self.category_id),
+            ledger_name: Set(self.ledger_name),
+            scenario_id: Set(self.scenario_id),
+            is_simulated: Set(self.is_simulated),
+            status: Set(self.status),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+    }
+}
@@ -0,0 +1,231 @@
+use chrono::NaiveDate;
+use model::entities::recurring_transaction::{AmountStrategy, ApprovalStatus, DateAdjustment, RecurrencePeriod};
+use model::entities::recurring_transaction_instance::InstanceStatus;
+use model::entities::{account, category, recurring_transaction, recurring_transaction_instance};
+use rust_decimal::Decimal;
+use sea_orm::{ActiveModelTrait, ConnectionTrait, DbErr, Set};
+
+use crate::sequence;
+
+/// Builds an approved monthly expense of 100 on an account, starting 2024-01-01.
+#[derive(Debug, Clone)]
+pub struct RecurringTransactionFactory {
+    name: Option<String>,
+    description: Option<String>,
+    amount: Decimal,
+    start_date: NaiveDate,
+    end_date: Option<NaiveDate>,
+    period: RecurrencePeriod,
+    include_in_statistics: bool,
+    target_account_id: i32,
+    source_account_id: Option<i32>,
+    category_id: Option<i32>,
+    ledger_name: Option<String>,
+    scenario_id: Option<i32>,
+    is_simulated: bool,
+    approval_status: ApprovalStatus,
+    amount_strategy: AmountStrategy,
+    amount_history_count: Option<i32>,
+    rrule: Option<String>,
+    date_adjustment: DateAdjustment,
+}
+
+impl RecurringTransactionFactory {
+    pub fn new(target_account: &account::Model) -> Self {
+        Self {
+            name: None,
+            description: None,
+            amount: Decimal::from(-100),
+            start_date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            end_date: None,
+            period: RecurrencePeriod::Monthly,
+            include_in_statistics: true,
+            target_account_id: target_account.id,
+            source_account_id: None,
+            category_id: None,
+            ledger_name: None,
+            scenario_id: None,
+            is_simulated: false,
+            approval_status: ApprovalStatus::Approved,
+            amount_strategy: AmountStrategy::Fixed,
+            amount_history_count: None,
+            rrule: None,
+            date_adjustment: DateAdjustment::None,
+        }
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Positive for income, negative for expenses.
+    pub fn amount(mut self, amount: impl Into<Decimal>) -> Self {
+        self.amount = amount.into();
+        self
+    }
+
+    pub fn start_date(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = start_date;
+        self
+    }
+
+    pub fn end_date(mut self, end_date: NaiveDate) -> Self {
+        self.end_date = Some(end_date);
+        self
+    }
+
+    pub fn period(mut self, period: RecurrencePeriod) -> Self {
+        self.period = period;
+        self
+    }
+
+    pub fn include_in_statistics(mut self, include_in_statistics: bool) -> Self {
+        self.include_in_statistics = include_in_statistics;
+        self
+    }
+
+    /// Turns the transaction into a transfer from `source_account`.
+    pub fn source_account(mut self, source_account: &account::Model) -> Self {
+        self.source_account_id = Some(source_account.id);
+        self
+    }
+
+    pub fn category(mut self, category: &category::Model) -> Self {
+        self.category_id = Some(category.id);
+        self
+    }
+
+    pub fn ledger_name(mut self, ledger_name: impl Into<String>) -> Self {
+        self.ledger_name = Some(ledger_name.into());
+        self
+    }
+
+    /// Makes the transaction a simulated part of a what-if scenario.
+    pub fn scenario(mut self, scenario_id: i32) -> Self {
+        self.scenario_id = Some(scenario_id);
+        self.is_simulated = true;
+        self
+    }
+
+    pub fn approval_status(mut self, approval_status: ApprovalStatus) -> Self {
+        self.approval_status = approval_status;
+        self
+    }
+
+    pub fn amount_strategy(mut self, amount_strategy: AmountStrategy, amount_history_count: Option<i32>) -> Self {
+        self.amount_strategy = amount_strategy;
+        self.amount_history_count = amount_history_count;
+        self
+    }
+
+    pub fn rrule(mut self, rrule: impl Into<String>) -> Self {
+        self.rrule = Some(rrule.into());
+        self
+    }
+
+    pub fn date_adjustment(mut self, date_adjustment: DateAdjustment) -> Self {
+        self.date_adjustment = date_adjustment;
+        self
+    }
+
+    pub async fn create(self, db: &impl ConnectionTrait) -> Result<recurring_transaction::Model, DbErr> {
+        recurring_transaction::ActiveModel {
+            name: Set(self.name.unwrap_or_else(|| format!("Recurring {}", sequence()))),
+            description: Set(self.description),
+            amount: Set(self.amount),
+            start_date: Set(self.start_date),
+            end_date: Set(self.end_date),
+            period: Set(self.period),
+            include_in_statistics: Set(self.include_in_statistics),
+            target_account_id: Set(self.target_account_id),
+            source_account_id: Set(self.source_account_id),
+            category_id: Set(self.category_id),
+            ledger_name: Set(self.ledger_name),
+            scenario_id: Set(self.scenario_id),
+            is_simulated: Set(self.is_simulated),
+            approval_status: Set(self.approval_status),
+            amount_strategy: Set(self.amount_strategy),
+            amount_history_count: Set(self.amount_history_count),
+            rrule: Set(self.rrule),
+            date_adjustment: Set(self.date_adjustment),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+    }
+}
+
+/// Builds a pending instance of a recurring transaction, expecting its amount.
+#[derive(Debug, Clone)]
+pub struct RecurringInstanceFactory {
+    recurring_transaction_id: i32,
+    status: InstanceStatus,
+    due_date: NaiveDate,
+    expected_amount: Decimal,
+    paid_date: Option<NaiveDate>,
+    paid_amount: Option<Decimal>,
+    category_id: Option<i32>,
+}
+
+impl RecurringInstanceFactory {
+    pub fn new(recurring: &recurring_transaction::Model, due_date: NaiveDate) -> Self {
+        Self {
+            recurring_transaction_id: recurring.id,
+            status: InstanceStatus::Pending,
+            due_date,
+            expected_amount: recurring.amount,
+            paid_date: None,
+            paid_amount: None,
+            category_id: None,
+        }
+    }
+
+    pub fn expected_amount(mut self, expected_amount: impl Into<Decimal>) -> Self {
+        self.expected_amount = expected_amount.into();
+        self
+    }
+
+    /// Paid the expected amount on the due date.
+    pub fn paid(self) -> Self {
+        let (due_date, expected_amount) = (self.due_date, self.expected_amount);
+        self.paid_on(due_date, expected_amount)
+    }
+
+    pub fn paid_on(mut self, paid_date: NaiveDate, paid_amount: impl Into<Decimal>) -> Self {
+        self.status = InstanceStatus::Paid;
+        self.paid_date = Some(paid_date);
+        self.paid_amount = Some(paid_amount.into());
+        self
+    }
+
+    pub fn skipped(mut self) -> Self {
+        self.status = InstanceStatus::Skipped;
+        self
+    }
+
+    pub fn category(mut self, category: &category::Model) -> Self {
+        self.category_id = Some(category.id);
+        self
+    }
+
+    pub async fn create(self, db: &impl ConnectionTrait) -> Result<recurring_transaction_instance::Model, DbErr> {
+        recurring_transaction_instance::ActiveModel {
+            recurring_transaction_id: Set(self.recurring_transaction_id),
+            status: Set(self.status),
+            due_date: Set(self.due_date),
+            expected_amount: Set(self.expected_amount),
+            paid_date: Set(self.paid_date),
+            paid_amount: Set(self.paid_amount),
+            category_id: Set(self.category_id),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+    }
+}